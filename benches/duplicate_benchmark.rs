@@ -9,10 +9,8 @@
 use chrono::Local;
 use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
 use std::{hint::black_box, path::PathBuf, sync::Arc};
-use visualvault::{
-    core::DuplicateDetector,
-    models::{FileType, MediaFile},
-};
+use visualvault_core::DuplicateDetector;
+use visualvault_models::{FileType, MediaFile};
 
 fn create_test_files_with_duplicates(total: usize, duplicate_ratio: f32) -> Vec<Arc<MediaFile>> {
     let unique_count = ((total as f32) * (1.0 - duplicate_ratio)) as usize;
@@ -29,6 +27,9 @@ fn create_test_files_with_duplicates(total: usize, duplicate_ratio: f32) -> Vec<
             modified: Local::now(),
             created: Local::now(),
             metadata: None,
+            via_symlink: false,
+            live_photo_pair: None,
+            content_extension: None,
             hash: Some(Arc::from(format!("hash_{i:04}"))),
         }));
     }
@@ -47,6 +48,9 @@ fn create_test_files_with_duplicates(total: usize, duplicate_ratio: f32) -> Vec<
             modified: original.modified,
             created: original.created,
             metadata: original.metadata.clone(),
+            via_symlink: false,
+            live_photo_pair: None,
+            content_extension: None,
             hash: original.hash.clone(),
         });
         files.push(duplicate);
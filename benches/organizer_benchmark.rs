@@ -27,6 +27,9 @@ fn create_test_media_files(count: usize) -> Vec<Arc<MediaFile>> {
                 modified: Local::now(),
                 created: Local::now(),
                 metadata: None,
+                via_symlink: false,
+                live_photo_pair: None,
+                content_extension: None,
                 hash: None,
             })
         })
@@ -1,48 +1,68 @@
 use chrono::Local;
 use color_eyre::eyre::Result;
-use std::path::Path;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
 use std::sync::Arc;
-use tracing::{error, info};
-use visualvault_config::Settings;
-use visualvault_models::{DuplicateStats, ImageMetadata, MediaMetadata, ScanResult};
-use visualvault_utils::FolderStats;
-use walkdir::WalkDir;
-
-use super::{App, AppState};
+use tracing::{error, info, warn};
+use visualvault_lib::Engine;
+use visualvault_lib::internals::config::{Settings, StartupAction};
+use visualvault_lib::internals::core::{
+    FileList, FileQuery, FolderStatsUpdate, ImportRecord, OrganizedLedger, ScanCancelled, Scanner, project_space,
+};
+use visualvault_lib::internals::models::{
+    ActivityKind, ConflictStats, Dialog, DialogAction, DuplicateGroup, DuplicateStats, FileConflict, FilterSet,
+    MediaFile, NonMediaStats, NotifiedOperation, OperationReport, OrganizeResult, OrganizedFile, ScanResult,
+    SkipRuleStats, SpaceProjection,
+};
+use visualvault_lib::internals::utils::{Progress, STAGE_HASH, STAGE_ORGANIZE, create_cache_path, format_bytes};
+
+use super::{App, AppState, IMPORT_HISTORY_ROWS};
+use crate::state::ScanOutput;
 
 /// Parameters for executing a scan
 struct ScanParameters {
     source: std::path::PathBuf,
     recursive: bool,
-    scanner: Arc<visualvault_core::Scanner>,
-    progress: Arc<tokio::sync::RwLock<visualvault_utils::Progress>>,
-    filter_set: Option<visualvault_models::FilterSet>,
+    engine: Arc<Engine>,
+    progress: Arc<tokio::sync::RwLock<Progress>>,
+    filter_set: Option<FilterSet>,
+    /// Set when files organized by earlier runs are looked up after scanning
+    ledger: Option<Arc<tokio::sync::Mutex<OrganizedLedger>>>,
+    /// Bytes of scanned files kept in memory before the rest are paged from disk
+    memory_budget: Option<u64>,
 }
 
 struct OrganizeParameters {
-    files: Vec<Arc<visualvault_models::MediaFile>>,
+    files: Vec<Arc<MediaFile>>,
     destination: std::path::PathBuf,
     rename_duplicates: bool,
-    settings: visualvault_config::Settings,
-    organizer: Arc<visualvault_core::FileOrganizer>,
-    scanner: Arc<visualvault_core::Scanner>,
-    progress: Arc<tokio::sync::RwLock<visualvault_utils::Progress>>,
+    settings: Settings,
+    engine: Arc<Engine>,
+    scanner: Arc<Scanner>,
+    progress: Arc<tokio::sync::RwLock<Progress>>,
     start_time: chrono::DateTime<Local>,
 }
 
-struct OrganizeExecutionResult {
+pub(crate) struct OrganizeExecutionResult {
     files_organized: usize,
     files_total: usize,
     destination: std::path::PathBuf,
     success: bool,
+    rolled_back: bool,
+    cancelled: bool,
     skipped_duplicates: usize,
     errors: Vec<String>,
+    conflicts: ConflictStats,
+    pending_conflicts: Vec<FileConflict>,
+    non_media: NonMediaStats,
+    skipped_by_rules: SkipRuleStats,
+    files: Vec<OrganizedFile>,
     start_time: chrono::DateTime<Local>,
 }
 
 impl OrganizeExecutionResult {
     fn success(
-        result: visualvault_models::OrganizeResult,
+        result: OrganizeResult,
         files_total: usize,
         destination: std::path::PathBuf,
         start_time: chrono::DateTime<Local>,
@@ -52,8 +72,15 @@ impl OrganizeExecutionResult {
             files_total,
             destination,
             success: result.success,
+            rolled_back: result.rolled_back,
+            cancelled: result.cancelled,
             skipped_duplicates: result.skipped_duplicates,
             errors: result.errors,
+            conflicts: result.conflicts,
+            pending_conflicts: result.pending_conflicts,
+            non_media: result.non_media,
+            skipped_by_rules: result.skipped_by_rules,
+            files: result.files,
             start_time,
         }
     }
@@ -69,8 +96,15 @@ impl OrganizeExecutionResult {
             files_total,
             destination,
             success: false,
+            rolled_back: false,
+            cancelled: false,
             skipped_duplicates: 0,
             errors: vec![e.to_string()],
+            conflicts: ConflictStats::default(),
+            pending_conflicts: Vec::new(),
+            non_media: NonMediaStats::default(),
+            skipped_by_rules: SkipRuleStats::default(),
+            files: Vec::new(),
             start_time,
         }
     }
@@ -83,15 +117,35 @@ impl OrganizeExecutionResult {
         self.errors.len()
     }
 
-    fn convert_to_organize_result(self) -> visualvault_models::OrganizeResult {
-        visualvault_models::OrganizeResult {
+    /// Builds the report sent to the notification targets
+    fn to_report(&self) -> OperationReport {
+        let duration = (Local::now() - self.start_time).to_std().unwrap_or_default();
+        OperationReport::new(NotifiedOperation::Organize, duration)
+            .with_count("files_organized", self.files_organized as u64)
+            .with_count("files_total", self.files_total as u64)
+            .with_count("skipped_duplicates", self.skipped_duplicates as u64)
+            .with_count("skipped_by_rules", self.skipped_by_rules.total() as u64)
+            .with_count("conflicts_deferred", self.pending_conflicts.len() as u64)
+            .with_errors(self.errors.clone())
+    }
+
+    fn convert_to_organize_result(self) -> OrganizeResult {
+        OrganizeResult {
             files_organized: self.files_organized,
             files_total: self.files_total,
             destination: self.destination,
             success: self.success,
+            rolled_back: self.rolled_back,
+            cancelled: self.cancelled,
             timestamp: self.start_time,
             skipped_duplicates: self.skipped_duplicates,
             errors: self.errors,
+            conflicts: self.conflicts,
+            pending_conflicts: self.pending_conflicts,
+            non_media: self.non_media,
+            skipped_by_rules: self.skipped_by_rules,
+            files: self.files,
+            remote_transfer: None,
         }
     }
 }
@@ -105,6 +159,11 @@ impl App {
     /// - No source folder is configured
     /// - The scanner fails to scan the directory
     pub async fn start_scan(&mut self) -> Result<()> {
+        // Scanning replaces the files the running organize still refers to
+        if self.organize_task.is_some() {
+            self.error_message = Some("Wait for organizing to finish before scanning".to_string());
+            return Ok(());
+        }
         // Cancel any existing scan
         if let Some(task) = self.scan_task.take() {
             task.abort();
@@ -156,20 +215,44 @@ impl App {
                     let duration = self.scan_start_time.map(|start| start.elapsed()).unwrap_or_default();
 
                     match task.await {
-                        Ok(Ok((files, duplicates))) => {
-                            self.handle_successful_scan(&files, duplicates).await?;
+                        Ok(Ok((files, duplicates, organized_before, scope))) => {
+                            let report = OperationReport::new(NotifiedOperation::Scan, duration)
+                                .with_count("files_found", files.len() as u64)
+                                .with_count("duplicate_groups", duplicates.total_groups as u64)
+                                .with_count("duplicates", duplicates.total_duplicates as u64);
+                            self.handle_successful_scan(files, duplicates, organized_before, scope)
+                                .await?;
+                            self.record_performance(NotifiedOperation::Scan, duration).await;
+                            self.notify(report).await;
 
                             // Update last scan result with actual duration
                             if let Some(ref mut result) = self.last_scan_result {
                                 result.duration = duration;
                             }
                         }
+                        Ok(Err(e)) if e.is::<ScanCancelled>() => {
+                            self.error_message = Some("Scan cancelled".to_string());
+                            self.pending_startup_actions.clear();
+                            self.state = AppState::Dashboard;
+                        }
                         Ok(Err(e)) => {
                             self.handle_scan_error(&e);
+                            self.notify(OperationReport::failed(
+                                NotifiedOperation::Scan,
+                                duration,
+                                e.to_string(),
+                            ))
+                            .await;
                         }
                         Err(e) => {
                             let error = color_eyre::eyre::eyre!("Scan task failed: {}", e);
                             self.handle_scan_error(&error);
+                            self.notify(OperationReport::failed(
+                                NotifiedOperation::Scan,
+                                duration,
+                                error.to_string(),
+                            ))
+                            .await;
                         }
                     }
 
@@ -220,7 +303,11 @@ impl App {
     }
 
     /// Gets the configured source folder
-    async fn get_source_folder(&self) -> Result<std::path::PathBuf> {
+    async fn get_source_folder(&mut self) -> Result<std::path::PathBuf> {
+        if let Some(source) = self.source_override.take() {
+            info!("Scanner: Starting scan of temporary source {:?}", source);
+            return Ok(source);
+        }
         let settings = self.settings.read().await;
         let source = settings
             .source_folder
@@ -232,52 +319,141 @@ impl App {
     }
 
     /// Builds scan parameters from current state
-    fn build_scan_parameters(
-        &self,
-        source: &std::path::Path,
-        settings: &visualvault_config::Settings,
-    ) -> ScanParameters {
+    fn build_scan_parameters(&self, source: &std::path::Path, settings: &Settings) -> ScanParameters {
         ScanParameters {
             source: source.to_path_buf(),
             recursive: settings.recurse_subfolders,
-            scanner: Arc::clone(&self.scanner),
+            engine: Arc::clone(&self.engine),
             progress: Arc::clone(&self.progress),
             filter_set: if self.filter_set.is_active {
                 Some(self.filter_set.clone())
             } else {
                 None
             },
+            ledger: settings
+                .previously_organized
+                .is_enabled()
+                .then(|| Arc::clone(self.organizer.ledger())),
+            memory_budget: self.settings_cache.memory_budget_bytes(),
         }
     }
 
     /// Handles a successful scan result
     async fn handle_successful_scan(
         &mut self,
-        files: &[Arc<visualvault_models::MediaFile>],
+        files: FileList,
         duplicates: DuplicateStats,
+        organized_before: HashMap<PathBuf, PathBuf>,
+        scope: FileQuery,
     ) -> Result<()> {
-        Self::log_scan_results(files, &duplicates);
-        self.update_scan_data(files, duplicates).await;
-        self.create_scan_success_message(files.len());
+        let files_found = files.len();
+        Self::log_scan_results(files_found, &duplicates);
+        let hash_cache_summary = duplicates.hash_cache_summary();
+        self.update_scan_data(files, duplicates, scope).await;
+        self.record_scan_snapshot().await;
+        self.organized_before = organized_before.into_iter().collect();
+        let mismatched = self.cached_files.count(|file| file.content_extension.is_some()).await?;
+        self.create_scan_success_message(files_found, mismatched, hash_cache_summary.as_deref());
+        if let Some(message) = self.success_message.clone() {
+            self.log_activity(ActivityKind::Scan, message);
+        }
+        let failures = self.scanner.take_failures();
+        if !failures.is_empty() {
+            let count = failures.len();
+            self.error_center.record(failures);
+            self.success_message = self
+                .success_message
+                .take()
+                .map(|message| format!("{message}; {count} files could not be read (press ! to review)"));
+        }
+        self.suspect_files = self.scanner.take_suspects();
+        self.suspect_scroll = 0;
+        if !self.suspect_files.is_empty() {
+            let count = self.suspect_files.len();
+            self.success_message = self
+                .success_message
+                .take()
+                .map(|message| format!("{message}; {count} suspect files set aside (press Q to review)"));
+        }
+        self.archive_media = self.scanner.take_archive_media();
+        self.archive_scroll = 0;
+        self.selected_archive_media.clear();
+        if !self.archive_media.is_empty() {
+            let count = self.archive_media.len();
+            self.success_message = self
+                .success_message
+                .take()
+                .map(|message| format!("{message}; {count} media files found inside archives (press Z to review)"));
+        }
         self.state = AppState::Dashboard;
         Ok(())
     }
 
+    /// Lets the file cache answer searches, filter counts and duplicate lookups for the scanned
+    /// files, unless it does not hold exactly the files of the list, e.g. when some were set
+    /// aside as suspects
+    async fn set_cache_scope(&mut self, scope: FileQuery) {
+        match self.scanner.count_files(&scope).await {
+            Ok(count) if count == self.cached_files.len() => self.cached_files.set_cache_scope(scope),
+            Ok(count) => info!(
+                "File cache holds {} of the {} scanned files, reading the list instead",
+                count,
+                self.cached_files.len()
+            ),
+            Err(e) => warn!("Cannot count the scanned files in the file cache: {}", e),
+        }
+    }
+
+    /// Counts the scanned files per extension in the file cache
+    async fn update_extension_stats(&mut self) {
+        let Some(scope) = self.cached_files.cache_scope() else {
+            return;
+        };
+        match self.scanner.extension_stats(scope).await {
+            Ok(extensions) => {
+                self.statistics.files_by_extension = extensions
+                    .into_iter()
+                    .filter(|extension| !extension.extension.is_empty())
+                    .map(|extension| (extension.extension, extension.files))
+                    .collect();
+            }
+            Err(e) => warn!("Cannot count the scanned files per extension: {}", e),
+        }
+    }
+
     /// Logs scan results for debugging
-    fn log_scan_results(files: &[Arc<visualvault_models::MediaFile>], duplicates: &DuplicateStats) {
+    fn log_scan_results(files_found: usize, duplicates: &DuplicateStats) {
         info!("=== SCAN RESULTS ===");
-        info!("Scanner returned {} files", files.len());
+        info!("Scanner returned {} files", files_found);
         info!("Scanner returned {} duplicate groups", duplicates.len());
-        info!("App cached_files now has {} entries", files.len());
+        info!("App cached_files now has {} entries", files_found);
     }
 
     /// Updates internal data structures with scan results
-    async fn update_scan_data(&mut self, files: &[Arc<visualvault_models::MediaFile>], duplicates: DuplicateStats) {
+    async fn update_scan_data(&mut self, files: FileList, duplicates: DuplicateStats, scope: FileQuery) {
         let files_found = files.len();
 
-        self.statistics.update_from_scan_results(files, &duplicates);
-        self.file_manager.write().await.set_files(files.to_vec());
-        self.cached_files = files.to_vec();
+        self.statistics.start_scan_results(&duplicates);
+        let statistics = &mut self.statistics;
+        if let Err(e) = files.for_each(|_, file| statistics.add_scanned_file(file)).await {
+            warn!("Cannot count the scanned files: {}", e);
+        }
+        self.cached_files = files;
+        self.apply_file_sort_or_warn().await;
+        self.set_cache_scope(scope).await;
+        self.update_extension_stats().await;
+        // A paged list only works if no other copy of the files stays in memory
+        let kept = if self.cached_files.is_paged() {
+            Vec::new()
+        } else {
+            self.cached_files.to_vec().await.unwrap_or_default()
+        };
+        self.file_manager.write().await.set_files(kept);
+        self.marked_files.clear();
+        self.mark_anchor = None;
+        if let Err(e) = self.load_file_tags().await {
+            tracing::warn!("Failed to load file tags: {}", e);
+        }
 
         self.duplicate_groups = Self::convert_duplicate_groups(duplicates.groups);
 
@@ -289,9 +465,7 @@ impl App {
     }
 
     /// Converts duplicate groups to the internal format
-    fn convert_duplicate_groups(
-        groups: Vec<visualvault_models::DuplicateGroup>,
-    ) -> Option<Vec<Vec<visualvault_models::MediaFile>>> {
+    fn convert_duplicate_groups(groups: Vec<DuplicateGroup>) -> Option<Vec<Vec<MediaFile>>> {
         if groups.is_empty() {
             None
         } else {
@@ -305,7 +479,7 @@ impl App {
     }
 
     /// Creates the success message based on scan results
-    fn create_scan_success_message(&mut self, files_found: usize) {
+    fn create_scan_success_message(&mut self, files_found: usize, mismatched: usize, hash_cache_summary: Option<&str>) {
         if !self.scanner.is_complete() {
             self.success_message = Some("Scan in progress...".to_string());
             return;
@@ -314,24 +488,75 @@ impl App {
             groups.iter().map(|g| g.len().saturating_sub(1)).sum::<usize>()
         });
 
-        self.success_message = if duplicate_count > 0 {
+        let mut details = Vec::new();
+        if duplicate_count > 0 {
+            details.push(format!("{duplicate_count} duplicates"));
+        }
+        if !self.organized_before.is_empty() {
+            details.push(format!("{} organized before", self.organized_before.len()));
+        }
+        if mismatched > 0 {
+            details.push(format!("{mismatched} with mismatched extensions"));
+        }
+        details.extend(hash_cache_summary.map(str::to_string));
+
+        self.success_message = if details.is_empty() {
+            Some(format!("Scan complete: {files_found} files found"))
+        } else {
             Some(format!(
-                "Scan complete: {files_found} files found ({duplicate_count} duplicates)"
+                "Scan complete: {files_found} files found ({})",
+                details.join(", ")
             ))
-        } else {
-            Some(format!("Scan complete: {files_found} files found"))
         };
     }
 
     /// Handles scan errors
     fn handle_scan_error(&mut self, error: &color_eyre::eyre::Error) {
         error!("Scan failed: {}", error);
+        self.log_activity(ActivityKind::Error, format!("Scan failed: {error}"));
+        self.error_center.record(self.scanner.take_failures());
+        self.record_operation_error(format!("Scan failed: {error}"));
+        self.pending_startup_actions.clear();
         self.error_message = Some(format!("Scan failed: {error}"));
         self.state = AppState::Dashboard;
     }
 
+    /// Runs the next configured startup action once no other operation is in progress.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if starting the scan or the duplicate detection fails.
+    pub async fn run_next_startup_action(&mut self) -> Result<()> {
+        if self.pending_startup_actions.is_empty()
+            || self.scan_task.is_some()
+            || matches!(self.state, AppState::Scanning | AppState::Organizing)
+        {
+            return Ok(());
+        }
+
+        let Some(action) = self.pending_startup_actions.pop_front() else {
+            return Ok(());
+        };
+        info!("Running startup action: {}", action);
+
+        match action {
+            StartupAction::Scan => {
+                if self.settings.read().await.source_folder.is_none() {
+                    self.pending_startup_actions.clear();
+                    self.error_message = Some("Startup scan skipped: source folder not configured".to_string());
+                    return Ok(());
+                }
+                self.start_scan().await
+            }
+            StartupAction::Duplicates => self.start_duplicate_scan().await,
+        }
+    }
+
     /// Starts organizing files from the cached scan results.
     ///
+    /// When the active filter set is configured to restrict organization, only the
+    /// matching files are organized and the user is asked to confirm first.
+    ///
     /// # Errors
     ///
     /// Returns an error if:
@@ -339,19 +564,167 @@ impl App {
     /// - The organizer fails to organize the files
     /// - File operations fail during organization
     pub async fn start_organize(&mut self) -> Result<()> {
+        if !self.ensure_writable() {
+            return Ok(());
+        }
         if !self.validate_organize_preconditions() {
             return Ok(());
         }
 
+        if self.filter_set.restricts_organize() {
+            self.request_filtered_organize_confirmation().await?;
+            return Ok(());
+        }
+
+        let files = self.cached_files.to_vec().await?;
+        self.run_organize(files).await
+    }
+
+    /// Organizes the files that were waiting for the filtered organize confirmation.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - No destination folder is configured
+    /// - The organizer fails to organize the files
+    pub async fn confirm_filtered_organize(&mut self) -> Result<()> {
+        if let Some(files) = self.pending_filtered_organize.take() {
+            self.run_organize(files).await?;
+        }
+        Ok(())
+    }
+
+    /// Cancels a pending filtered organize
+    pub fn cancel_filtered_organize(&mut self) {
+        if self.pending_filtered_organize.take().is_some() {
+            self.error_message = Some("Organization cancelled".to_string());
+        }
+    }
+
+    /// Collects the files matching the active filters and asks whether to organize only them
+    /// or all scanned files
+    async fn request_filtered_organize_confirmation(&mut self) -> Result<()> {
+        let matching = self.cached_files.filter(|file| self.matches_filters(file)).await?;
+
+        if matching.is_empty() {
+            self.error_message = Some("No files match the active filters. Nothing to organize.".to_string());
+            return Ok(());
+        }
+
+        let space = self
+            .project_organize_space(&matching)
+            .await?
+            .map(|projection| format!(" {}.", describe_space(&projection)))
+            .unwrap_or_default();
+        let dialog = Dialog::choice(
+            DialogAction::Organize,
+            "Organize",
+            format!(
+                "{} of {} scanned files match the active filters.{space}",
+                matching.len(),
+                self.cached_files.len()
+            ),
+            vec![
+                format!("Organize the {} matching files", matching.len()),
+                format!("Organize all {} files", self.cached_files.len()),
+            ],
+        );
+        self.open_dialog(dialog);
+        self.pending_filtered_organize = Some(matching);
+        Ok(())
+    }
+
+    /// Organizes the given files and updates the application state with the result
+    pub(crate) async fn run_organize(&mut self, files: Vec<Arc<MediaFile>>) -> Result<()> {
+        if !self.ensure_writable() {
+            return Ok(());
+        }
+        if self.organize_task.is_some() {
+            self.error_message = Some("Files are already being organized".to_string());
+            return Ok(());
+        }
+        let projection = self.project_organize_space(&files).await?;
+        if let Some(projection) = projection.filter(|projection| !projection.fits()) {
+            let message = format!("Not enough space to organize: {}", describe_space(&projection));
+            self.log_activity(ActivityKind::Error, message.clone());
+            self.error_message = Some(message);
+            return Ok(());
+        }
+
         self.prepare_organize_state().await?;
+        if let Some(projection) = projection.filter(SpaceProjection::is_tight) {
+            self.warning_message = Some(format!(
+                "Starting to organize files; the destination drive will be nearly full: {}",
+                describe_space(&projection)
+            ));
+        }
 
-        let organize_params = self.build_organize_parameters().await?;
-        let organize_result = self.execute_organization(organize_params).await;
+        let organize_params = self.build_organize_parameters(files.clone()).await?;
+        self.organize_files = files;
+        self.organize_task = Some(tokio::spawn(Self::execute_organization(organize_params)));
+        Ok(())
+    }
 
-        self.process_organize_result(organize_result);
+    /// Finishes the organize run once its task is done: records the result, opens the report
+    /// and starts the transfers that follow a run.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if updating the cached files after the run fails.
+    pub async fn check_organize_completion(&mut self) -> Result<()> {
+        if !self
+            .organize_task
+            .as_ref()
+            .is_some_and(tokio::task::JoinHandle::is_finished)
+        {
+            return Ok(());
+        }
+        let Some(task) = self.organize_task.take() else {
+            return Ok(());
+        };
+        let files = std::mem::take(&mut self.organize_files);
+
+        let organize_result = match task.await {
+            Ok(result) => result,
+            Err(e) => {
+                let message = format!("Organize task failed: {e}");
+                self.record_operation_error(message.clone());
+                self.error_message = Some(message);
+                self.state = AppState::Dashboard;
+                return Ok(());
+            }
+        };
+        let report = organize_result.to_report();
+        let cancelled = organize_result.cancelled;
+        if !cancelled {
+            self.record_performance(
+                NotifiedOperation::Organize,
+                std::time::Duration::from_millis(report.duration_ms),
+            )
+            .await;
+        }
+        self.process_organize_result(organize_result, &files).await?;
+        // Files of a cancelled run wait in the destination until the next run sends them along
+        if !cancelled {
+            self.start_rclone_transfer().await;
+            self.start_cloud_upload().await;
+        }
+        self.notify(report).await;
         Ok(())
     }
 
+    /// Works out the space organizing `files` takes on the destination drive, or `None`
+    /// without a destination folder
+    async fn project_organize_space(&self, files: &[Arc<MediaFile>]) -> Result<Option<SpaceProjection>> {
+        let Some(destination) = self.settings.read().await.local_destination() else {
+            return Ok(None);
+        };
+        let files = files.to_vec();
+        let projection = tokio::task::spawn_blocking(move || project_space(&files, &destination)).await?;
+        info!("Organize space projection: {:?}", projection);
+        Ok(Some(projection))
+    }
+
     /// Validates that organization can proceed
     fn validate_organize_preconditions(&mut self) -> bool {
         if self.cached_files.is_empty() {
@@ -371,19 +744,18 @@ impl App {
     }
 
     /// Builds parameters needed for organization
-    async fn build_organize_parameters(&self) -> Result<OrganizeParameters> {
+    async fn build_organize_parameters(&self, files: Vec<Arc<MediaFile>>) -> Result<OrganizeParameters> {
         let settings = self.settings.read().await;
         let destination = settings
-            .destination_folder
-            .clone()
+            .local_destination()
             .ok_or_else(|| color_eyre::eyre::eyre!("No destination folder configured"))?;
 
         let params = OrganizeParameters {
-            files: self.cached_files.clone(),
+            files,
             destination,
             rename_duplicates: settings.rename_duplicates,
             settings: settings.clone(),
-            organizer: Arc::clone(&self.organizer),
+            engine: Arc::clone(&self.engine),
             scanner: Arc::clone(&self.scanner),
             progress: Arc::clone(&self.progress),
             start_time: Local::now(),
@@ -394,26 +766,31 @@ impl App {
     }
 
     /// Executes the organization process
-    async fn execute_organization(&self, params: OrganizeParameters) -> OrganizeExecutionResult {
+    async fn execute_organization(params: OrganizeParameters) -> OrganizeExecutionResult {
         let mut files = params.files;
         let files_total = files.len();
 
-        let progress_clone = Arc::clone(&self.progress);
-        let progress_callback = move |current: usize, message: Option<String>| {
-            if let Ok(mut prog) = progress_clone.try_write() {
-                prog.current = current;
-                if let Some(msg) = message {
-                    prog.message = msg;
-                }
-            }
-        };
-
         // Handle duplicates based on settings
         let duplicates = if params.rename_duplicates {
             DuplicateStats::new()
         } else {
-            match params.scanner.find_duplicates(&mut files, progress_callback).await {
-                Ok(stats) => stats,
+            {
+                let mut prog = params.progress.write().await;
+                prog.plan_stages(&[STAGE_HASH, STAGE_ORGANIZE]);
+                prog.start_stage(STAGE_HASH, files_total, 0, "Detecting duplicates...");
+                prog.parallelism = params.scanner.concurrency().map(|plan| plan.to_string());
+            }
+            match params
+                .scanner
+                .find_duplicates(&mut files, params.settings.hash_algorithm, Arc::clone(&params.progress))
+                .await
+            {
+                Ok(stats) => {
+                    params.progress.write().await.bytes_processed = stats.hashed_bytes;
+                    stats
+                }
+                // The organizer was cancelled as well, so the run below reports it as cancelled
+                Err(e) if e.is::<ScanCancelled>() => DuplicateStats::new(),
                 Err(e) => {
                     return OrganizeExecutionResult::error(&e, files_total, params.destination, params.start_time);
                 }
@@ -422,8 +799,8 @@ impl App {
 
         // Perform organization
         match params
-            .organizer
-            .organize_files_with_duplicates(files, duplicates, &params.settings, params.progress)
+            .engine
+            .organize(files, duplicates, &params.settings, params.progress)
             .await
         {
             Ok(result) => OrganizeExecutionResult::success(result, files_total, params.destination, params.start_time),
@@ -432,17 +809,58 @@ impl App {
     }
 
     /// Processes the organization result and updates application state
-    fn process_organize_result(&mut self, result: OrganizeExecutionResult) {
+    async fn process_organize_result(
+        &mut self,
+        result: OrganizeExecutionResult,
+        organized: &[Arc<MediaFile>],
+    ) -> Result<()> {
         info!("Organization complete: {} files organized", result.files_organized);
         self.update_organize_state(result);
-        self.clear_organize_data();
+        self.record_import().await;
+        self.record_organize_snapshot(organized).await;
+        self.index_organized_files(organized).await;
+        self.clear_organize_data(organized).await
+    }
+
+    /// Shows the finished run in the import history, which the engine kept in the cache database
+    async fn record_import(&mut self) {
+        let Some(result) = self.last_organize_result.as_ref().filter(|result| !result.rolled_back) else {
+            return;
+        };
+        let Some(source) = self.settings.read().await.source_folder.clone() else {
+            return;
+        };
+        let record = ImportRecord::new(&source, result);
+        self.import_history.insert(0, record);
+        self.import_history.truncate(IMPORT_HISTORY_ROWS);
     }
 
     /// Updates the application state based on organization result
-    fn update_organize_state(&mut self, result: OrganizeExecutionResult) {
+    fn update_organize_state(&mut self, mut result: OrganizeExecutionResult) {
         let message = Self::build_organize_message(&result);
         let has_errors = result.has_errors();
 
+        self.log_activity(ActivityKind::Organize, message.clone());
+        if let Some(first) = result.errors.first() {
+            self.log_activity(
+                ActivityKind::Error,
+                format!(
+                    "Organize failed for {} files, first error: {first}",
+                    result.error_count()
+                ),
+            );
+        }
+
+        self.record_organize_errors(&result.files, &result.errors);
+        if result.rolled_back {
+            self.record_operation_error(message.clone());
+        }
+
+        // Conflicts deferred by the "ask" policy are resolved through the conflict dialog
+        self.pending_conflicts = std::mem::take(&mut result.pending_conflicts).into();
+        self.conflict_resolutions.clear();
+
+        let has_files = !result.files.is_empty();
         self.last_organize_result = Some(result.convert_to_organize_result());
 
         if has_errors {
@@ -451,12 +869,30 @@ impl App {
             self.success_message = Some(message);
         }
 
-        self.state = AppState::Dashboard;
+        // The conflict dialog is answered from the dashboard before the report is looked at
+        if has_files && self.pending_conflicts.is_empty() {
+            self.open_organize_report();
+        } else {
+            self.state = AppState::Dashboard;
+        }
     }
 
     /// Builds the appropriate message based on organization result
     fn build_organize_message(result: &OrganizeExecutionResult) -> String {
-        let base_message = if result.skipped_duplicates > 0 {
+        if result.rolled_back {
+            // The first error explains why the run was rolled back
+            return result
+                .errors
+                .first()
+                .map_or_else(|| "Organization rolled back".to_string(), Clone::clone);
+        }
+
+        let mut base_message = if result.cancelled {
+            format!(
+                "Organization cancelled: {} of {} files organized",
+                result.files_organized, result.files_total
+            )
+        } else if result.skipped_duplicates > 0 {
             format!(
                 "Organization complete: {} files organized, {} duplicates skipped",
                 result.files_organized, result.skipped_duplicates
@@ -465,6 +901,27 @@ impl App {
             format!("Organization complete: {} files organized", result.files_organized)
         };
 
+        if result.conflicts.total() > 0 {
+            base_message = format!(
+                "{base_message}; conflicts: {}",
+                Self::describe_conflicts(&result.conflicts)
+            );
+        }
+
+        if result.non_media.total() > 0 {
+            base_message = format!(
+                "{base_message}; non-media: {}",
+                Self::describe_non_media(&result.non_media)
+            );
+        }
+
+        if result.skipped_by_rules.total() > 0 {
+            base_message = format!(
+                "{base_message}; skip rules: {}",
+                Self::describe_skip_rules(&result.skipped_by_rules)
+            );
+        }
+
         if result.has_errors() {
             format!("{} (with {} errors)", base_message, result.error_count())
         } else {
@@ -472,10 +929,48 @@ impl App {
         }
     }
 
-    /// Clears data used during organization
-    fn clear_organize_data(&mut self) {
-        self.cached_files.clear();
+    /// Summarizes how non-media files were handled, e.g. "3 ignored"
+    fn describe_non_media(non_media: &NonMediaStats) -> String {
+        [
+            (non_media.ignored, "ignored"),
+            (non_media.unsorted, "moved to Unsorted"),
+            (non_media.included, "organized"),
+        ]
+        .iter()
+        .filter(|(count, _)| *count > 0)
+        .map(|(count, label)| format!("{count} {label}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+    }
+
+    /// Summarizes the files each skip rule left in the source, e.g. "3 too small"
+    fn describe_skip_rules(skipped: &SkipRuleStats) -> String {
+        [
+            (skipped.too_small, "too small"),
+            (skipped.low_resolution, "low resolution"),
+            (skipped.too_new, "too new"),
+        ]
+        .iter()
+        .filter(|(count, _)| *count > 0)
+        .map(|(count, label)| format!("{count} {label}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+    }
+
+    /// Clears data used during organization, keeping files that were not part of the run
+    async fn clear_organize_data(&mut self, organized: &[Arc<MediaFile>]) -> Result<()> {
+        if organized.len() == self.cached_files.len() {
+            self.cached_files = FileList::default();
+            self.file_tags.clear();
+            self.organized_before.clear();
+        } else {
+            let organized: HashSet<PathBuf> = organized.iter().map(|file| file.path.clone()).collect();
+            self.cached_files.remove(&organized).await?;
+            self.file_tags.retain(|path, _| !organized.contains(path));
+            self.organized_before.retain(|path, _| !organized.contains(path));
+        }
         self.duplicate_groups = None;
+        Ok(())
     }
 
     /// Updates the application statistics based on the current file list.
@@ -485,178 +980,75 @@ impl App {
     /// This function currently does not return any errors, but the `Result` type
     /// is maintained for future compatibility with potential error conditions.
     pub async fn update_statistics(&mut self) -> Result<()> {
+        // A paged list is not mirrored in the file manager; its statistics came with the scan
+        if self.cached_files.is_paged() {
+            return Ok(());
+        }
         let files = self.file_manager.read().await.get_files();
         self.statistics.update_from_files(&files);
-        self.cached_files = files.to_vec();
-        Ok(())
-    }
-
-    /// Loads image metadata from the specified file path.
-    ///
-    /// # Errors
-    ///
-    /// Returns an error if:
-    /// - The file cannot be read from disk
-    /// - The file is not a valid image format
-    /// - The image data is corrupted or invalid
-    pub async fn load_image_metadata(&self, path: &Path) -> Result<MediaMetadata> {
-        use image::GenericImageView;
-
-        let file_data = tokio::fs::read(path).await?;
-        let path_owned = path.to_path_buf();
-
-        let metadata = tokio::task::spawn_blocking(move || -> Result<MediaMetadata> {
-            let img = image::load_from_memory(&file_data)?;
-            let (width, height) = img.dimensions();
-            let color_type = match img.color() {
-                image::ColorType::L8 => "Grayscale 8-bit",
-                image::ColorType::La8 => "Grayscale + Alpha 8-bit",
-                image::ColorType::Rgb8 => "RGB 8-bit",
-                image::ColorType::Rgba8 => "RGBA 8-bit",
-                image::ColorType::L16 => "Grayscale 16-bit",
-                image::ColorType::La16 => "Grayscale + Alpha 16-bit",
-                image::ColorType::Rgb16 => "RGB 16-bit",
-                image::ColorType::Rgba16 => "RGBA 16-bit",
-                _ => "Unknown",
-            };
-
-            let format = path_owned
-                .extension()
-                .and_then(|e| e.to_str())
-                .map_or_else(|| "Unknown".to_string(), str::to_uppercase);
-
-            Ok(MediaMetadata::Image(ImageMetadata {
-                width,
-                height,
-                format,
-                color_type: color_type.to_string(),
-            }))
-        })
-        .await??;
-
-        Ok(metadata)
+        self.cached_files = FileList::new(files.to_vec());
+        self.apply_file_sort().await
     }
 
     /// Updates folder statistics for the configured source and destination folders.
     ///
-    /// This function spawns background tasks to calculate statistics without blocking the UI.
+    /// The folders are added up in the background; the dashboard shows a spinner until the
+    /// new statistics are in.
     ///
     /// # Errors
     ///
-    /// Returns an error if:
-    /// - The tokio task spawning fails
-    /// - File system operations fail during statistics calculation
+    /// This function currently does not fail; it returns a `Result` for consistency with the
+    /// other actions.
     pub async fn update_folder_stats(&mut self) -> Result<()> {
         info!("Starting background folder statistics update...");
         self.success_message = Some("Calculating folder statistics...".to_string());
 
-        let settings = self.settings.read().await;
-        let mut paths_to_update = Vec::new();
-        if let Some(source) = &settings.source_folder {
-            paths_to_update.push(source.clone());
-        }
-        if let Some(dest) = &settings.destination_folder {
-            paths_to_update.push(dest.clone());
-        }
-        drop(settings);
-
-        // Cancel any existing tasks for these paths
-        for path in &paths_to_update {
-            if let Some(task) = self.folder_stats_tasks.remove(path) {
-                task.abort();
-            }
-        }
-
-        // Spawn new background tasks
-        for path in paths_to_update {
-            if !self.folder_stats_in_progress.contains(&path) {
-                self.folder_stats_in_progress.insert(path.clone());
-                let path_clone = path.clone();
-
-                // Spawn a background task
-                let task = tokio::spawn(async move { calculate_folder_stats_async(&path_clone).await });
-
-                self.folder_stats_tasks.insert(path, task);
-            }
+        let folders = stats_folders(&*self.settings.read().await);
+        for path in folders {
+            self.folder_stats.refresh(&path);
+            self.folder_stats_requested.insert(path);
         }
-
         Ok(())
     }
 
-    /// Checks for completed folder stats calculations and updates the cache
-    pub async fn check_folder_stats_completion(&mut self) {
-        let mut completed_paths = Vec::new();
-
-        // Check which tasks have completed
-        for (path, task) in &mut self.folder_stats_tasks {
-            if task.is_finished() {
-                completed_paths.push(path.clone());
+    /// Starts folders whose files settled after a change and collects finished statistics
+    pub fn check_folder_stats_completion(&mut self) {
+        for update in self.folder_stats.poll() {
+            let (FolderStatsUpdate::Updated(path) | FolderStatsUpdate::Failed(path)) = &update;
+            info!("Folder stats finished for {:?}", path);
+            if !self.folder_stats_requested.remove(path) {
+                continue;
             }
-        }
-
-        // Process completed tasks
-        for path in completed_paths {
-            if let Some(task) = self.folder_stats_tasks.remove(&path) {
-                self.folder_stats_in_progress.remove(&path);
-
-                match task.await {
-                    Ok(stats) => {
-                        info!("Folder stats updated for {:?}", path);
-                        self.folder_stats_cache.insert(path.clone(), stats);
-
-                        // Update success message if no other tasks are running
-                        if self.folder_stats_tasks.is_empty() {
-                            self.success_message = Some("Folder statistics updated".to_string());
-                        }
-                    }
-                    Err(e) => {
-                        error!("Failed to calculate stats for {:?}: {}", path, e);
-                        if self.folder_stats_tasks.is_empty() {
-                            self.error_message = Some("Failed to update some folder statistics".to_string());
-                        }
-                    }
-                }
+            if matches!(update, FolderStatsUpdate::Failed(_)) {
+                self.error_message = Some("Failed to update some folder statistics".to_string());
+            } else if self.folder_stats_requested.is_empty() {
+                self.success_message = Some("Folder statistics updated".to_string());
             }
         }
     }
 
-    /// Non-blocking version that starts background tasks if needed
+    /// Starts adding up the source and destination folders whose statistics are not known yet,
+    /// forgetting folders that are no longer configured
     pub fn update_folder_stats_if_needed(&mut self) {
-        let paths_to_update = {
-            let Ok(settings) = self.settings.try_read() else { return };
-
-            let mut paths = Vec::new();
-            if let Some(source) = &settings.source_folder {
-                if !self.folder_stats_cache.contains_key(source) && !self.folder_stats_in_progress.contains(source) {
-                    paths.push(source.clone());
-                }
-            }
-
-            if let Some(dest) = &settings.destination_folder {
-                if !self.folder_stats_cache.contains_key(dest) && !self.folder_stats_in_progress.contains(dest) {
-                    paths.push(dest.clone());
-                }
-            }
-            paths
-        };
-
-        for path in paths_to_update {
-            self.folder_stats_in_progress.insert(path.clone());
-            let path_clone = path.clone();
-
-            // Spawn background task
-            let task = tokio::spawn(async move { calculate_folder_stats_async(&path_clone).await });
-
-            self.folder_stats_tasks.insert(path, task);
+        let Ok(settings) = self.settings.try_read() else { return };
+        let folders = stats_folders(&settings);
+        drop(settings);
+        self.folder_stats.retain(&folders);
+        for path in &folders {
+            self.folder_stats.ensure(path);
         }
     }
 
+    /// Marks the folder statistics as stale after an operation changed files; they are added
+    /// up again once the changes settle
+    pub fn invalidate_folder_stats(&mut self) {
+        self.folder_stats.invalidate_all();
+    }
+
     /// Cancels all running folder stats calculations
     pub fn cancel_folder_stats_calculations(&mut self) {
-        for (_, task) in self.folder_stats_tasks.drain() {
-            task.abort();
-        }
-        self.folder_stats_in_progress.clear();
+        self.folder_stats.cancel();
+        self.folder_stats_requested.clear();
     }
 
     /// Updates the progress state for ongoing operations.
@@ -684,28 +1076,27 @@ impl App {
     /// - Any internal state updates fail during the completion check
     pub async fn check_operation_completion(&mut self) -> Result<()> {
         match self.state {
-            AppState::Scanning => {
-                if self.scanner.is_complete() {
-                    let count = self.file_manager.read().await.get_file_count();
-                    self.success_message = Some(format!("Scan complete: {count} files found"));
-                    self.state = AppState::Dashboard;
-                    self.update_statistics().await?;
-                }
+            AppState::Scanning if self.scanner.is_complete() => {
+                let count = self.file_manager.read().await.get_file_count();
+                self.success_message = Some(format!("Scan complete: {count} files found"));
+                self.state = AppState::Dashboard;
+                self.update_statistics().await?;
             }
-            AppState::Organizing => {
-                if self.organizer.is_complete().await {
-                    let result = self.organizer.get_result().await;
-                    match result {
-                        Some(Ok(count)) => {
-                            self.success_message = Some(format!("Successfully organized {count} files"));
-                        }
-                        Some(Err(e)) => {
-                            self.error_message = Some(format!("Organization failed: {e}"));
-                        }
-                        None => {}
+            AppState::Organizing
+                if self.organize_task.is_none() && self.rclone_task.is_none() && self.organizer.is_complete().await =>
+            {
+                let result = self.organizer.get_result().await;
+                match result {
+                    Some(Ok(count)) => {
+                        self.success_message = Some(format!("Successfully organized {count} files"));
+                    }
+                    Some(Err(e)) => {
+                        self.record_operation_error(format!("Organization failed: {e}"));
+                        self.error_message = Some(format!("Organization failed: {e}"));
                     }
-                    self.state = AppState::Dashboard;
+                    None => {}
                 }
+                self.state = AppState::Dashboard;
             }
             _ => {}
         }
@@ -713,105 +1104,70 @@ impl App {
     }
 }
 
-fn is_media_extension(ext: &str) -> bool {
-    matches!(
-        ext.to_lowercase().as_str(),
-        "jpg"
-            | "jpeg"
-            | "png"
-            | "gif"
-            | "bmp"
-            | "webp"
-            | "tiff"
-            | "tif"
-            | "svg"
-            | "ico"
-            | "heic"
-            | "heif"
-            | "mp4"
-            | "avi"
-            | "mkv"
-            | "mov"
-            | "wmv"
-            | "flv"
-            | "webm"
-            | "m4v"
-            | "mpg"
-            | "mpeg"
-            | "3gp"
-            | "mp3"
-            | "wav"
-            | "flac"
-            | "aac"
-            | "ogg"
-            | "wma"
-            | "m4a"
-            | "opus"
-            | "ape"
-    )
+/// The source and destination folders, whose statistics the dashboard shows
+fn stats_folders(settings: &Settings) -> Vec<PathBuf> {
+    [&settings.source_folder, &settings.destination_folder]
+        .into_iter()
+        .flatten()
+        .cloned()
+        .collect()
 }
 
-async fn calculate_folder_stats_async(path: &std::path::Path) -> FolderStats {
-    let path = path.to_path_buf();
-
-    // Use spawn_blocking for the CPU-intensive directory traversal
-    tokio::task::spawn_blocking(move || {
-        let mut stats = FolderStats::default();
-        let mut entries_processed = 0;
+async fn execute_scan_background(params: ScanParameters, settings: &Settings) -> Result<ScanOutput> {
+    let start_time = std::time::Instant::now();
+    // Every file the scan reads or finds in the file cache is marked as accessed from now on
+    let scope = FileQuery {
+        folder: Some(params.source.clone()),
+        scanned_since: Some(Local::now()),
+        ..FileQuery::default()
+    };
+
+    // Files beyond the memory budget are paged from disk as the scanner finds them
+    let mut files = match create_cache_path("visualvault", "files.db").await {
+        Ok(store_path) => FileList::with_budget(params.memory_budget, &store_path),
+        Err(e) => {
+            warn!("Cannot page scanned files from disk, keeping them in memory: {}", e);
+            FileList::default()
+        }
+    };
 
-        for entry in WalkDir::new(&path)
-            .follow_links(false)
-            .into_iter()
-            .filter_map(Result::ok)
-        {
-            if let Ok(metadata) = entry.metadata() {
-                if metadata.is_file() {
-                    stats.total_files += 1;
-                    stats.total_size += metadata.len();
-
-                    if let Some(ext) = entry.path().extension() {
-                        if let Some(ext_str) = ext.to_str() {
-                            if is_media_extension(ext_str) {
-                                stats.media_files += 1;
-                            }
-                        }
-                    }
-                } else if metadata.is_dir() {
-                    stats.total_dirs += 1;
-                }
-            }
+    info!("Starting scan of directory: {:?}", params.source);
+    // The source may be a temporary one picked for this scan only
+    let settings = Settings {
+        source_folder: Some(params.source.clone()),
+        recurse_subfolders: params.recursive,
+        ..settings.clone()
+    };
+    let duplicates = params
+        .engine
+        .find_duplicates_into(&settings, params.filter_set, &mut files, Arc::clone(&params.progress))
+        .await?;
 
-            // Yield periodically to prevent blocking too long
-            entries_processed += 1;
-            if entries_processed % 1000 == 0 {
-                std::thread::yield_now();
-            }
+    let organized_before = match params.ledger {
+        Some(ledger) => {
+            params.progress.write().await.message = "Checking for files organized before...".to_string();
+            ledger.lock().await.find_in(&files, settings.hash_algorithm).await?
         }
+        None => HashMap::new(),
+    };
 
-        stats
-    })
-    .await
-    .unwrap_or_default()
+    info!("Scan completed in {:?}", start_time.elapsed());
+    Ok((files, duplicates, organized_before, scope))
 }
 
-async fn execute_scan_background(
-    params: ScanParameters,
-    settings: &Settings,
-) -> Result<(Vec<Arc<visualvault_models::MediaFile>>, DuplicateStats)> {
-    let start_time = std::time::Instant::now();
-
-    info!("Starting scan of directory: {:?}", params.source);
-    let (files, duplicates) = params
-        .scanner
-        .scan_directory_with_duplicates(
-            &params.source,
-            params.recursive,
-            params.progress,
-            settings,
-            params.filter_set,
+/// Describes how much an organize run copies to the destination drive and what is free
+fn describe_space(projection: &SpaceProjection) -> String {
+    let required = if projection.copied_files == 0 {
+        "files stay on their drive, no extra space needed".to_string()
+    } else {
+        format!(
+            "{} to copy for {} files from other drives",
+            format_bytes(projection.required),
+            projection.copied_files
         )
-        .await?;
-
-    info!("Scan completed in {:?}", start_time.elapsed());
-    Ok((files, duplicates))
+    };
+    match projection.free {
+        Some(free) => format!("{required}, {} free", format_bytes(free)),
+        None => required,
+    }
 }
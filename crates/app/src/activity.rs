@@ -0,0 +1,35 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use tracing::error;
+use visualvault_lib::internals::models::{ActivityKind, AppState};
+
+use super::App;
+
+impl App {
+    /// Adds an entry to the persistent activity log.
+    ///
+    /// Failing to write the log never interrupts the operation being recorded. Operations
+    /// that may have changed files mark the folder statistics as stale.
+    pub fn log_activity(&mut self, kind: ActivityKind, message: impl Into<String>) {
+        if !matches!(kind, ActivityKind::Rename | ActivityKind::Verify | ActivityKind::Error) {
+            self.invalidate_folder_stats();
+        }
+        if let Err(e) = self.activity_log.record(kind, message) {
+            error!("Failed to write activity log: {}", e);
+        }
+    }
+
+    /// Handles keyboard input in the activity log view.
+    pub fn handle_activity_log_keys(&mut self, key: KeyEvent) {
+        let last = self.activity_log.len().saturating_sub(1);
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => self.state = AppState::Dashboard,
+            KeyCode::Up => self.activity_scroll = self.activity_scroll.saturating_sub(1),
+            KeyCode::Down => self.activity_scroll = (self.activity_scroll + 1).min(last),
+            KeyCode::PageUp => self.activity_scroll = self.activity_scroll.saturating_sub(10),
+            KeyCode::PageDown => self.activity_scroll = (self.activity_scroll + 10).min(last),
+            KeyCode::Home => self.activity_scroll = 0,
+            KeyCode::End => self.activity_scroll = last,
+            _ => {}
+        }
+    }
+}
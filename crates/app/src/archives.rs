@@ -0,0 +1,105 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use tracing::error;
+use visualvault_lib::internals::core::extract_archive_media;
+use visualvault_lib::internals::models::{ActivityKind, AppState};
+
+use super::App;
+
+impl App {
+    /// Opens the list of media the last scan found inside archives.
+    pub fn open_archives(&mut self) {
+        if self.archive_media.is_empty() {
+            self.success_message = Some(if self.settings_cache.inspect_archives {
+                "No media found inside archives by the last scan".to_string()
+            } else {
+                "Turn on archive inspection in the settings and scan again".to_string()
+            });
+            return;
+        }
+        self.archive_scroll = 0;
+        self.state = AppState::Archives;
+    }
+
+    /// Handles keyboard input in the archive review.
+    ///
+    /// # Errors
+    /// Returns an error if the rescan after extracting fails to start.
+    pub async fn handle_archive_keys(&mut self, key: KeyEvent) -> Result<()> {
+        let last = self.archive_media.len().saturating_sub(1);
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => self.state = AppState::Dashboard,
+            KeyCode::Up => self.archive_scroll = self.archive_scroll.saturating_sub(1),
+            KeyCode::Down => self.archive_scroll = (self.archive_scroll + 1).min(last),
+            KeyCode::PageUp => self.archive_scroll = self.archive_scroll.saturating_sub(10),
+            KeyCode::PageDown => self.archive_scroll = (self.archive_scroll + 10).min(last),
+            KeyCode::Char(' ') => {
+                if !self.selected_archive_media.remove(&self.archive_scroll) {
+                    self.selected_archive_media.insert(self.archive_scroll);
+                }
+                self.archive_scroll = (self.archive_scroll + 1).min(last);
+            }
+            KeyCode::Char('a') => {
+                if self.selected_archive_media.len() == self.archive_media.len() {
+                    self.selected_archive_media.clear();
+                } else {
+                    self.selected_archive_media = (0..self.archive_media.len()).collect();
+                }
+            }
+            KeyCode::Char('x') | KeyCode::Enter => self.extract_archive_media().await?,
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Extracts the selected media, or all of them if none are selected, next to their
+    /// archives and scans again so they are organized like any other file.
+    async fn extract_archive_media(&mut self) -> Result<()> {
+        if !self.ensure_writable() {
+            return Ok(());
+        }
+        let mut by_archive: BTreeMap<PathBuf, Vec<String>> = BTreeMap::new();
+        for (idx, media) in self.archive_media.iter().enumerate() {
+            if self.selected_archive_media.is_empty() || self.selected_archive_media.contains(&idx) {
+                by_archive
+                    .entry(media.archive.clone())
+                    .or_default()
+                    .push(media.name.clone());
+            }
+        }
+        let total: usize = by_archive.values().map(Vec::len).sum();
+
+        let extracted = tokio::task::spawn_blocking(move || {
+            by_archive
+                .into_iter()
+                .map(|(archive, names)| match extract_archive_media(&archive, &names) {
+                    Ok(extracted) => extracted.len(),
+                    Err(e) => {
+                        error!("Failed to extract from {}: {}", archive.display(), e);
+                        0
+                    }
+                })
+                .sum::<usize>()
+        })
+        .await?;
+
+        let message = format!("Extracted {extracted} of {total} media files from archives");
+        self.log_activity(ActivityKind::Scan, message.clone());
+        if extracted < total {
+            self.error_message = Some(format!(
+                "{} files were not extracted: they already exist or could not be read, see the log",
+                total - extracted
+            ));
+        }
+        self.success_message = Some(message);
+        self.selected_archive_media.clear();
+        self.state = AppState::Dashboard;
+        if extracted > 0 {
+            self.request_scan().await?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,45 @@
+use std::sync::Arc;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use visualvault_lib::internals::models::{MediaFile, MediaMetadata};
+
+use super::App;
+
+impl App {
+    /// Returns the scanned audio files ordered by artist, album and title.
+    ///
+    /// Untagged files sort after tagged ones and fall back to their file name.
+    #[must_use]
+    pub fn audio_tracks(&self) -> Vec<Arc<MediaFile>> {
+        let mut tracks = self.cached_files.audio_files();
+
+        tracks.sort_by_cached_key(|file| match &file.metadata {
+            Some(MediaMetadata::Audio(tags)) => (
+                tags.artist.is_none(),
+                tags.artist.clone().unwrap_or_default().to_lowercase(),
+                tags.album.clone().unwrap_or_default().to_lowercase(),
+                tags.title
+                    .clone()
+                    .unwrap_or_else(|| file.name.to_string())
+                    .to_lowercase(),
+            ),
+            _ => (true, String::new(), String::new(), file.name.to_lowercase()),
+        });
+        tracks
+    }
+
+    /// Handles scrolling in the dashboard's audio tab.
+    pub fn handle_audio_tab_keys(&mut self, key: KeyEvent) {
+        let last = self.cached_files.audio_files().len().saturating_sub(1);
+
+        match key.code {
+            KeyCode::Up => self.audio_scroll = self.audio_scroll.saturating_sub(1),
+            KeyCode::Down => self.audio_scroll = (self.audio_scroll + 1).min(last),
+            KeyCode::PageUp => self.audio_scroll = self.audio_scroll.saturating_sub(10),
+            KeyCode::PageDown => self.audio_scroll = (self.audio_scroll + 10).min(last),
+            KeyCode::Home => self.audio_scroll = 0,
+            KeyCode::End => self.audio_scroll = last,
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,142 @@
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use visualvault_lib::internals::config::RenameTemplate;
+use visualvault_lib::internals::core::{MoveOperation, apply_batch_rename, plan_batch_rename};
+use visualvault_lib::internals::models::{ActivityKind, BatchRename};
+
+use super::App;
+
+/// Pattern suggested when no rename template is configured
+const DEFAULT_PATTERN: &str = "{date}_{counter}";
+
+impl App {
+    /// Opens the batch rename overlay for the marked files, or the focused file if none are marked.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a paged file list cannot be read.
+    pub async fn open_batch_rename(&mut self) -> Result<()> {
+        if self.marked_or_focused_files().await?.is_empty() {
+            self.error_message = Some("No files to rename".to_string());
+            return Ok(());
+        }
+
+        let pattern = self
+            .settings_cache
+            .rename_template
+            .as_ref()
+            .map_or_else(|| DEFAULT_PATTERN.to_string(), ToString::to_string);
+        self.batch_rename = Some(BatchRename {
+            pattern,
+            ..BatchRename::default()
+        });
+        self.refresh_batch_rename_preview().await
+    }
+
+    /// Handles keyboard input while the batch rename overlay is open.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the renames cannot be recorded in the undo history.
+    pub async fn handle_batch_rename_keys(&mut self, key: KeyEvent) -> Result<()> {
+        let Some(rename) = self.batch_rename.as_mut() else {
+            return Ok(());
+        };
+
+        match key.code {
+            KeyCode::Esc => self.batch_rename = None,
+            KeyCode::Enter => return self.apply_batch_rename().await,
+            KeyCode::Up => rename.scroll = rename.scroll.saturating_sub(1),
+            KeyCode::Down => rename.scroll = (rename.scroll + 1).min(rename.preview.len().saturating_sub(1)),
+            KeyCode::Backspace => {
+                rename.pattern.pop();
+                self.refresh_batch_rename_preview().await?;
+            }
+            KeyCode::Char(c) => {
+                rename.pattern.push(c);
+                self.refresh_batch_rename_preview().await?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    async fn refresh_batch_rename_preview(&mut self) -> Result<()> {
+        let files = self.marked_or_focused_files().await?;
+        let Some(rename) = self.batch_rename.as_mut() else {
+            return Ok(());
+        };
+
+        rename.file_count = files.len();
+        rename.scroll = 0;
+        match rename.pattern.parse::<RenameTemplate>() {
+            Ok(template) => {
+                let planned = tokio::task::spawn_blocking(move || plan_batch_rename(&files, &template)).await;
+                match planned {
+                    Ok(operations) => {
+                        rename.preview = operations.into_iter().map(|op| (op.source, op.destination)).collect();
+                        rename.error = None;
+                    }
+                    Err(e) => {
+                        rename.preview.clear();
+                        rename.error = Some(format!("Failed to preview renames: {e}"));
+                    }
+                }
+            }
+            Err(e) => {
+                rename.preview.clear();
+                rename.error = Some(e);
+            }
+        }
+        Ok(())
+    }
+
+    async fn apply_batch_rename(&mut self) -> Result<()> {
+        if !self.ensure_writable() {
+            return Ok(());
+        }
+        let Some(rename) = self.batch_rename.as_ref() else {
+            return Ok(());
+        };
+        if rename.error.is_some() {
+            return Ok(());
+        }
+        if rename.preview.is_empty() {
+            self.batch_rename = None;
+            self.success_message = Some("Nothing to rename".to_string());
+            return Ok(());
+        }
+
+        let operations: Vec<MoveOperation> = rename
+            .preview
+            .iter()
+            .map(|(source, destination)| MoveOperation {
+                source: source.clone(),
+                destination: destination.clone(),
+            })
+            .collect();
+        let planned = operations.len();
+        let applied = tokio::task::spawn_blocking(move || apply_batch_rename(&operations)).await?;
+
+        self.cached_files.relocate(&applied).await?;
+        self.file_manager.write().await.relocate_files(&applied);
+        self.relocate_file_tags(&applied);
+
+        let count = applied.len();
+        if count > 0 {
+            self.organizer.undo_manager().record_batch_rename(applied).await?;
+        }
+
+        let message = if count == planned {
+            format!("Renamed {count} files")
+        } else {
+            format!("Renamed {count} of {planned} files, the others were skipped")
+        };
+        self.log_activity(ActivityKind::Rename, message.clone());
+        self.success_message = Some(message);
+        self.batch_rename = None;
+        self.marked_files.clear();
+        self.mark_anchor = None;
+        Ok(())
+    }
+}
@@ -0,0 +1,318 @@
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use visualvault_lib::internals::core::FileManager;
+use visualvault_lib::internals::models::{ActivityKind, BulkAction, BulkActionMenu, MediaFile};
+
+use super::App;
+
+/// File name suggested for exported file lists
+const EXPORT_FILE_NAME: &str = "visualvault-files.csv";
+
+impl App {
+    /// Marks or unmarks the focused file in the Files tab and moves to the next one.
+    pub fn toggle_file_mark(&mut self) {
+        let Some(file) = self.cached_files.get(self.selected_file_index) else {
+            return;
+        };
+        if !self.marked_files.remove(&file.path) {
+            self.marked_files.insert(file.path.clone());
+        }
+        self.mark_anchor = Some(self.selected_file_index);
+        self.move_selection_down();
+    }
+
+    /// Marks every file between the last toggled file and the focused one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a paged file list cannot be read.
+    pub async fn mark_file_range(&mut self) -> Result<()> {
+        let anchor = self.mark_anchor.unwrap_or(self.selected_file_index);
+        let (start, end) = if anchor <= self.selected_file_index {
+            (anchor, self.selected_file_index)
+        } else {
+            (self.selected_file_index, anchor)
+        };
+
+        for file in self.cached_files.range(start, end).await? {
+            self.marked_files.insert(file.path.clone());
+        }
+        self.mark_anchor = Some(self.selected_file_index);
+        Ok(())
+    }
+
+    /// Marks all listed files, or clears the marks when all of them are marked already.
+    ///
+    /// The list only holds files matching the active filters, so this selects what is filtered.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a paged file list cannot be read.
+    pub async fn mark_all_files(&mut self) -> Result<()> {
+        let paths = self.cached_files.paths().await?;
+        if paths.iter().all(|path| self.marked_files.contains(path)) {
+            self.marked_files.clear();
+        } else {
+            self.marked_files = paths.into_iter().collect();
+        }
+        self.mark_anchor = None;
+        Ok(())
+    }
+
+    /// Marked files in the order they are listed, or the focused file if none are marked
+    pub(crate) async fn marked_or_focused_files(&self) -> Result<Vec<Arc<MediaFile>>> {
+        if self.marked_files.is_empty() {
+            return Ok(self
+                .cached_files
+                .get(self.selected_file_index)
+                .cloned()
+                .into_iter()
+                .collect());
+        }
+        self.cached_files
+            .filter(|file| self.marked_files.contains(&file.path))
+            .await
+    }
+
+    /// Opens the bulk action menu for the marked files, or the focused file if none are marked.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a paged file list cannot be read.
+    pub async fn open_bulk_actions(&mut self) -> Result<()> {
+        let file_count = self.marked_or_focused_files().await?.len();
+        if file_count == 0 {
+            self.error_message = Some("No files selected".to_string());
+            return Ok(());
+        }
+        self.bulk_actions = Some(BulkActionMenu {
+            file_count,
+            pending: None,
+        });
+        Ok(())
+    }
+
+    /// Handles keyboard input while the bulk action menu is open.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an action cannot be recorded in the undo history or the
+    /// ignore list cannot be saved.
+    pub async fn handle_bulk_action_keys(&mut self, key: KeyEvent) -> Result<()> {
+        let Some(menu) = self.bulk_actions.as_mut() else {
+            return Ok(());
+        };
+
+        if let Some((action, input)) = menu.pending.as_mut() {
+            match key.code {
+                KeyCode::Esc => menu.pending = None,
+                KeyCode::Enter => {
+                    let action = *action;
+                    let path = PathBuf::from(input.trim());
+                    return self.run_bulk_action(action, Some(path)).await;
+                }
+                KeyCode::Backspace => {
+                    input.pop();
+                }
+                KeyCode::Char(c) => input.push(c),
+                _ => {}
+            }
+            return Ok(());
+        }
+
+        let action = match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                self.bulk_actions = None;
+                return Ok(());
+            }
+            KeyCode::Char('t') => BulkAction::Trash,
+            KeyCode::Char('m') => BulkAction::Move,
+            KeyCode::Char('i') => BulkAction::Ignore,
+            KeyCode::Char('e') => BulkAction::Export,
+            KeyCode::Char('c') => BulkAction::Collect,
+            _ => return Ok(()),
+        };
+
+        if action.needs_path() {
+            let suggestion = self.suggested_path(action);
+            if let Some(menu) = self.bulk_actions.as_mut() {
+                menu.pending = Some((action, suggestion));
+            }
+            Ok(())
+        } else {
+            self.run_bulk_action(action, None).await
+        }
+    }
+
+    fn suggested_path(&self, action: BulkAction) -> String {
+        if action == BulkAction::Collect {
+            return self
+                .collections
+                .get(self.collections_view.selected)
+                .map(|collection| collection.name.clone())
+                .unwrap_or_default();
+        }
+        let folder = self
+            .settings_cache
+            .destination_folder
+            .clone()
+            .or_else(dirs::home_dir)
+            .unwrap_or_default();
+        let path = if action == BulkAction::Export {
+            folder.join(EXPORT_FILE_NAME)
+        } else {
+            folder
+        };
+        path.display().to_string()
+    }
+
+    async fn run_bulk_action(&mut self, action: BulkAction, path: Option<PathBuf>) -> Result<()> {
+        let files = self.marked_or_focused_files().await?;
+        let paths: Vec<PathBuf> = files.iter().map(|file| file.path.clone()).collect();
+        self.bulk_actions = None;
+
+        match (action, path) {
+            (BulkAction::Trash, _) => self.trash_files(paths).await?,
+            (BulkAction::Move, Some(folder)) if !folder.as_os_str().is_empty() => {
+                self.move_files(paths, folder).await?;
+            }
+            (BulkAction::Ignore, _) => self.ignore_files(&paths).await?,
+            (BulkAction::Export, Some(target)) if !target.as_os_str().is_empty() => {
+                let count = files.len();
+                let written = target.clone();
+                match tokio::task::spawn_blocking(move || FileManager::export_list(&files, &written)).await? {
+                    Ok(()) => {
+                        self.success_message = Some(format!("Exported {count} files to {}", target.display()));
+                    }
+                    Err(e) => self.error_message = Some(format!("Export failed: {e}")),
+                }
+                return Ok(());
+            }
+            (BulkAction::Collect, Some(name)) if !name.as_os_str().is_empty() => {
+                let name = name.to_string_lossy();
+                match self.collections.add_files(&name, &paths) {
+                    Ok(added) => {
+                        if let Some(index) = self.collections.position(&name) {
+                            self.collections_view.selected = index;
+                        }
+                        self.success_message = Some(format!("Added {added} files to collection '{name}'"));
+                    }
+                    Err(e) => self.error_message = Some(format!("Cannot add to collection: {e}")),
+                }
+            }
+            _ => {
+                self.error_message = Some("A path is required".to_string());
+                return Ok(());
+            }
+        }
+
+        self.marked_files.clear();
+        self.mark_anchor = None;
+        Ok(())
+    }
+
+    async fn trash_files(&mut self, paths: Vec<PathBuf>) -> Result<()> {
+        if !self.ensure_writable() {
+            return Ok(());
+        }
+        let trash_dir = self.organizer.undo_manager().backup_dir();
+        let requested = paths.len();
+        let trashed = tokio::task::spawn_blocking(move || FileManager::trash_files(&paths, &trash_dir)).await?;
+
+        let removed: HashSet<PathBuf> = trashed.iter().map(|op| op.path.clone()).collect();
+        self.forget_files(&removed).await?;
+
+        let count = trashed.len();
+        if count > 0 {
+            self.organizer.undo_manager().record_batch_delete(trashed).await?;
+        }
+        self.report_bulk_result(
+            ActivityKind::Delete,
+            format!("Moved {count} files to trash"),
+            count,
+            requested,
+        );
+        Ok(())
+    }
+
+    pub(crate) async fn move_files(&mut self, paths: Vec<PathBuf>, folder: PathBuf) -> Result<()> {
+        if !self.ensure_writable() {
+            return Ok(());
+        }
+        let requested = paths.len();
+        let target = folder.clone();
+        let moved = match tokio::task::spawn_blocking(move || FileManager::move_files(&paths, &target)).await? {
+            Ok(moved) => moved,
+            Err(e) => {
+                self.error_message = Some(format!("Cannot move files to {}: {e}", folder.display()));
+                return Ok(());
+            }
+        };
+
+        self.cached_files.relocate(&moved).await?;
+        self.file_manager.write().await.relocate_files(&moved);
+        self.relocate_file_tags(&moved);
+
+        let count = moved.len();
+        if count > 0 {
+            self.organizer.undo_manager().record_batch_move(moved).await?;
+        }
+        self.report_bulk_result(
+            ActivityKind::Organize,
+            format!("Moved {count} files to {}", folder.display()),
+            count,
+            requested,
+        );
+        Ok(())
+    }
+
+    async fn ignore_files(&mut self, paths: &[PathBuf]) -> Result<()> {
+        let mut settings = self.settings.write().await;
+        for path in paths {
+            if !settings.ignored_paths.contains(path) {
+                settings.ignored_paths.push(path.clone());
+            }
+        }
+        settings.save()?;
+        if let Some(watcher) = self.settings_watcher.as_mut() {
+            watcher.acknowledge();
+        }
+        self.settings_cache.ignored_paths.clone_from(&settings.ignored_paths);
+        drop(settings);
+
+        self.forget_files(&paths.iter().cloned().collect()).await?;
+        self.success_message = Some(format!("Added {} files to the ignore list", paths.len()));
+        Ok(())
+    }
+
+    /// Drops files from the lists and statistics after they were trashed or ignored
+    async fn forget_files(&mut self, paths: &HashSet<PathBuf>) -> Result<()> {
+        let removed = self.cached_files.remove(paths).await?;
+        self.file_tags.retain(|path, _| !paths.contains(path));
+        let mut file_manager = self.file_manager.write().await;
+        file_manager.remove_files(paths);
+        if self.cached_files.is_paged() {
+            self.statistics.remove_files(&removed);
+        } else {
+            self.statistics.update_from_files(&file_manager.get_files());
+        }
+        drop(file_manager);
+
+        self.selected_file_index = self.selected_file_index.min(self.cached_files.len().saturating_sub(1));
+        Ok(())
+    }
+
+    fn report_bulk_result(&mut self, kind: ActivityKind, message: String, count: usize, requested: usize) {
+        let message = if count == requested {
+            message
+        } else {
+            format!("{message}, {} could not be moved", requested - count)
+        };
+        self.log_activity(kind, message.clone());
+        self.success_message = Some(message);
+    }
+}
@@ -0,0 +1,161 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use color_eyre::eyre::eyre;
+use crossterm::event::{KeyCode, KeyEvent};
+use tracing::error;
+use visualvault_lib::internals::config::CloudExport;
+use visualvault_lib::internals::core::S3Uploader;
+use visualvault_lib::internals::models::{ActivityKind, AppState, CloudUploadReport, ErrorEntry, ErrorSource};
+use visualvault_lib::internals::utils::format_bytes;
+
+use super::App;
+
+impl App {
+    /// Starts uploading the files placed by the last organize run to the cloud export bucket,
+    /// if one is configured.
+    pub(crate) async fn start_cloud_upload(&mut self) {
+        let settings = self.settings.read().await;
+        // Files organized for an rclone remote leave the staging folder before they could be uploaded
+        if !settings.cloud_export.is_enabled() || settings.dry_run || settings.rclone_remote().is_some() {
+            return;
+        }
+        let cloud = settings.cloud_export.clone();
+        drop(settings);
+
+        let Some(result) = &self.last_organize_result else {
+            return;
+        };
+        let files = result.placed_files();
+        if !files.is_empty() {
+            let root = result.destination.clone();
+            self.upload_to_cloud(&cloud, root, files).await;
+        }
+    }
+
+    /// Uploads `files` below `root` in the background; the progress shows in the upload view.
+    async fn upload_to_cloud(&mut self, cloud: &CloudExport, root: PathBuf, files: Vec<PathBuf>) {
+        if self.cloud_upload_task.is_some() {
+            self.error_message = Some("An upload is already running, press U to follow it".to_string());
+            return;
+        }
+        let uploader = match S3Uploader::new(cloud) {
+            Ok(uploader) => uploader,
+            Err(e) => {
+                self.handle_upload_error(&e);
+                return;
+            }
+        };
+
+        let count = files.len();
+        let bucket = uploader.bucket().to_string();
+        self.cloud_upload_progress.write().await.reset();
+        let progress = Arc::clone(&self.cloud_upload_progress);
+        self.cloud_upload_task = Some(tokio::task::spawn_blocking(move || {
+            uploader.upload_files(&root, &files, &progress)
+        }));
+        self.cloud_upload_report = None;
+        self.cloud_upload_scroll = 0;
+        self.success_message = Some(format!(
+            "Uploading {count} files to bucket {bucket} (press U to follow)"
+        ));
+    }
+
+    /// Collects the report of a finished upload task.
+    pub async fn check_cloud_upload_completion(&mut self) {
+        if !self
+            .cloud_upload_task
+            .as_ref()
+            .is_some_and(tokio::task::JoinHandle::is_finished)
+        {
+            return;
+        }
+        let Some(task) = self.cloud_upload_task.take() else {
+            return;
+        };
+
+        match task.await {
+            Ok(report) => {
+                let message = summarize(&report);
+                self.log_activity(ActivityKind::Upload, message.clone());
+                if report.failed.is_empty() {
+                    self.success_message = Some(message);
+                } else {
+                    self.error_center.record(report.failed.iter().map(|failure| {
+                        ErrorEntry::new(
+                            ErrorSource::Operation,
+                            Some(failure.path.clone()),
+                            format!("Upload failed: {}", failure.error),
+                        )
+                    }));
+                    self.error_message = Some(format!("{message}, press U to retry the rest"));
+                }
+                self.cloud_upload_report = Some(report);
+            }
+            Err(e) => self.handle_upload_error(&eyre!("Upload task failed: {}", e)),
+        }
+    }
+
+    /// Opens the progress of the running upload, or the report of the last one.
+    pub async fn open_cloud_upload(&mut self) {
+        if self.cloud_upload_task.is_none() && self.cloud_upload_report.is_none() {
+            self.success_message = Some(if self.settings.read().await.cloud_export.is_enabled() {
+                "Nothing uploaded yet, files are uploaded after each organize run".to_string()
+            } else {
+                "Set up [cloud_export] in the config file to upload organized files".to_string()
+            });
+            return;
+        }
+        self.cloud_upload_scroll = 0;
+        self.state = AppState::CloudUpload;
+    }
+
+    /// Handles keyboard input in the upload view.
+    pub async fn handle_cloud_upload_keys(&mut self, key: KeyEvent) {
+        let last = self
+            .cloud_upload_report
+            .as_ref()
+            .map_or(0, |report| report.failed.len().saturating_sub(1));
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => self.state = AppState::Dashboard,
+            KeyCode::Up => self.cloud_upload_scroll = self.cloud_upload_scroll.saturating_sub(1),
+            KeyCode::Down => self.cloud_upload_scroll = (self.cloud_upload_scroll + 1).min(last),
+            KeyCode::PageUp => self.cloud_upload_scroll = self.cloud_upload_scroll.saturating_sub(10),
+            KeyCode::PageDown => self.cloud_upload_scroll = (self.cloud_upload_scroll + 10).min(last),
+            KeyCode::Char('r') => self.retry_cloud_upload().await,
+            _ => {}
+        }
+    }
+
+    /// Uploads the files that failed in the last upload again
+    async fn retry_cloud_upload(&mut self) {
+        let Some(report) = self
+            .cloud_upload_report
+            .as_ref()
+            .filter(|report| !report.failed.is_empty())
+        else {
+            self.error_message = Some("No failed uploads to retry".to_string());
+            return;
+        };
+        let (root, files) = (report.root.clone(), report.failed_paths());
+        let cloud = self.settings.read().await.cloud_export.clone();
+        self.upload_to_cloud(&cloud, root, files).await;
+    }
+
+    fn handle_upload_error(&mut self, e: &color_eyre::eyre::Report) {
+        error!("Cloud upload failed: {}", e);
+        self.log_activity(ActivityKind::Error, format!("Cloud upload failed: {e}"));
+        self.record_operation_error(format!("Cloud upload failed: {e}"));
+        self.error_message = Some(format!("Cloud upload failed: {e}"));
+    }
+}
+
+fn summarize(report: &CloudUploadReport) -> String {
+    format!(
+        "Uploaded {} of {} files ({}) to bucket {}",
+        report.uploaded,
+        report.total(),
+        format_bytes(report.bytes),
+        report.bucket
+    )
+}
@@ -0,0 +1,181 @@
+use std::path::PathBuf;
+
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use visualvault_lib::internals::core::{FileManager, open_with_default_app};
+use visualvault_lib::internals::models::{ActivityKind, AppState, CollectionInput};
+
+use super::App;
+
+impl App {
+    /// Opens the collections view.
+    pub fn open_collections(&mut self) {
+        let view = &mut self.collections_view;
+        view.selected = view
+            .selected
+            .min(self.collections.collections().len().saturating_sub(1));
+        view.selected_file = None;
+        view.input = None;
+        self.state = AppState::Collections;
+    }
+
+    /// Handles keyboard input in the collections view.
+    ///
+    /// # Errors
+    /// Returns an error if the export task panics.
+    pub async fn handle_collection_keys(&mut self, key: KeyEvent) -> Result<()> {
+        if let Some((purpose, input)) = self.collections_view.input.as_mut() {
+            match key.code {
+                KeyCode::Esc => self.collections_view.input = None,
+                KeyCode::Enter => {
+                    let purpose = *purpose;
+                    let input = input.trim().to_string();
+                    self.collections_view.input = None;
+                    match purpose {
+                        CollectionInput::NewName => self.create_collection(&input),
+                        CollectionInput::ExportFolder => self.export_collection(PathBuf::from(input)).await?,
+                    }
+                }
+                KeyCode::Backspace => {
+                    input.pop();
+                }
+                KeyCode::Char(c) => input.push(c),
+                _ => {}
+            }
+            return Ok(());
+        }
+
+        if self.collections_view.selected_file.is_some() {
+            self.handle_collection_file_keys(key);
+            return Ok(());
+        }
+
+        let count = self.collections.collections().len();
+        let view = &mut self.collections_view;
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => self.state = AppState::Dashboard,
+            KeyCode::Up => view.selected = view.selected.saturating_sub(1),
+            KeyCode::Down => view.selected = (view.selected + 1).min(count.saturating_sub(1)),
+            KeyCode::Enter | KeyCode::Right
+                if self
+                    .collections
+                    .get(view.selected)
+                    .is_some_and(|collection| !collection.files.is_empty()) =>
+            {
+                view.selected_file = Some(0);
+            }
+            KeyCode::Char('n') => view.input = Some((CollectionInput::NewName, String::new())),
+            KeyCode::Char('e') => {
+                if let Some(collection) = self.collections.get(view.selected) {
+                    let folder = self
+                        .settings_cache
+                        .destination_folder
+                        .clone()
+                        .or_else(dirs::home_dir)
+                        .unwrap_or_default()
+                        .join(&collection.name);
+                    view.input = Some((CollectionInput::ExportFolder, folder.display().to_string()));
+                }
+            }
+            KeyCode::Char('d') => self.delete_collection(),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Handles keys while browsing the files of the selected collection
+    fn handle_collection_file_keys(&mut self, key: KeyEvent) {
+        let index = self.collections_view.selected;
+        let Some(collection) = self.collections.get(index) else {
+            self.collections_view.selected_file = None;
+            return;
+        };
+        let last = collection.files.len().saturating_sub(1);
+        let file = self.collections_view.selected_file.unwrap_or_default().min(last);
+        let path = collection.files.get(file).cloned();
+
+        match key.code {
+            KeyCode::Esc | KeyCode::Left | KeyCode::Char('q') => self.collections_view.selected_file = None,
+            KeyCode::Up => self.collections_view.selected_file = Some(file.saturating_sub(1)),
+            KeyCode::Down => self.collections_view.selected_file = Some((file + 1).min(last)),
+            KeyCode::Char('x') => {
+                if let Some(path) = path {
+                    if let Err(e) = open_with_default_app(&path) {
+                        self.error_message = Some(format!("Could not open {}: {e}", path.display()));
+                    }
+                }
+            }
+            KeyCode::Char('d') => match self.collections.remove_file(index, file) {
+                Ok(Some(removed)) => {
+                    let remaining = self.collections.get(index).map_or(0, |c| c.files.len());
+                    self.collections_view.selected_file = (remaining > 0).then(|| file.min(remaining - 1));
+                    self.success_message = Some(format!("Removed {} from the collection", removed.display()));
+                }
+                Ok(None) => {}
+                Err(e) => self.error_message = Some(format!("Cannot update collection: {e}")),
+            },
+            _ => {}
+        }
+    }
+
+    fn create_collection(&mut self, name: &str) {
+        match self.collections.create(name) {
+            Ok(index) => {
+                self.collections_view.selected = index;
+                self.success_message = Some(format!("Created collection '{}'", name.trim()));
+            }
+            Err(e) => self.error_message = Some(format!("Cannot create collection: {e}")),
+        }
+    }
+
+    fn delete_collection(&mut self) {
+        match self.collections.delete(self.collections_view.selected) {
+            Ok(Some(collection)) => {
+                self.collections_view.selected = self
+                    .collections_view
+                    .selected
+                    .min(self.collections.collections().len().saturating_sub(1));
+                self.success_message = Some(format!("Deleted collection '{}'", collection.name));
+            }
+            Ok(None) => {}
+            Err(e) => self.error_message = Some(format!("Cannot delete collection: {e}")),
+        }
+    }
+
+    /// Copies the files of the selected collection into `folder`
+    async fn export_collection(&mut self, folder: PathBuf) -> Result<()> {
+        if !self.ensure_writable() {
+            return Ok(());
+        }
+        let Some(collection) = self.collections.get(self.collections_view.selected) else {
+            return Ok(());
+        };
+        if folder.as_os_str().is_empty() {
+            self.error_message = Some("A folder is required".to_string());
+            return Ok(());
+        }
+
+        let name = collection.name.clone();
+        let files = collection.files.clone();
+        let requested = files.len();
+        let target = folder.clone();
+        match tokio::task::spawn_blocking(move || FileManager::copy_files(&files, &target)).await? {
+            Ok(copied) => {
+                let count = copied.len();
+                let message = if count < requested {
+                    format!(
+                        "Exported {count} files of '{name}' to {}, {} could not be copied",
+                        folder.display(),
+                        requested - count
+                    )
+                } else {
+                    format!("Exported {count} files of '{name}' to {}", folder.display())
+                };
+                self.log_activity(ActivityKind::Organize, message.clone());
+                self.success_message = Some(message);
+            }
+            Err(e) => self.error_message = Some(format!("Export failed: {e}")),
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,109 @@
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use visualvault_lib::internals::models::{AppState, CommandPalette, PaletteCommand};
+
+use super::App;
+
+/// Whether `key` is Ctrl+P, which opens and closes the command palette
+pub(crate) fn is_command_palette_toggle(key: KeyEvent) -> bool {
+    key.code == KeyCode::Char('p') && key.modifiers.contains(KeyModifiers::CONTROL)
+}
+
+impl App {
+    /// Opens the overlay listing every action with an empty search
+    pub fn open_command_palette(&mut self) {
+        self.command_palette = Some(CommandPalette::default());
+    }
+
+    /// Handles keyboard input in the command palette.
+    ///
+    /// Typing narrows the list, the arrows pick a command and Enter runs it; Esc or Ctrl+P
+    /// closes the palette.
+    ///
+    /// # Errors
+    /// Returns an error if the chosen command fails to run.
+    pub async fn handle_command_palette_keys(&mut self, key: KeyEvent) -> Result<()> {
+        let Some(palette) = self.command_palette.as_mut() else {
+            return Ok(());
+        };
+        if is_command_palette_toggle(key) {
+            self.command_palette = None;
+            return Ok(());
+        }
+
+        match key.code {
+            KeyCode::Esc => self.command_palette = None,
+            KeyCode::Enter => {
+                let command = palette.selected_command();
+                self.command_palette = None;
+                if let Some(command) = command {
+                    self.run_palette_command(command).await?;
+                }
+            }
+            KeyCode::Up => palette.selected = palette.selected.saturating_sub(1),
+            KeyCode::Down => {
+                let last = palette.matches().len().saturating_sub(1);
+                palette.selected = (palette.selected + 1).min(last);
+            }
+            KeyCode::Backspace => {
+                palette.query.pop();
+                palette.selected = 0;
+            }
+            KeyCode::Char(c) => {
+                palette.query.push(c);
+                palette.selected = 0;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Runs `command` as if its key had been pressed on the dashboard
+    async fn run_palette_command(&mut self, command: PaletteCommand) -> Result<()> {
+        match command {
+            PaletteCommand::Scan => self.request_scan().await?,
+            PaletteCommand::Organize => self.request_organize().await?,
+            PaletteCommand::UpdateFolderStats => self.update_folder_stats().await?,
+            PaletteCommand::Settings => {
+                self.state = AppState::Settings;
+                self.update_settings_cache().await?;
+            }
+            PaletteCommand::Search => self.open_search(),
+            PaletteCommand::Filters => self.open_filters(),
+            PaletteCommand::ToggleFilters => self.toggle_filter_active(),
+            PaletteCommand::Duplicates => self.state = AppState::DuplicateReview,
+            PaletteCommand::EmptyFolders => self.open_empty_folder_cleanup().await?,
+            PaletteCommand::Quarantine => self.open_quarantine(),
+            PaletteCommand::DateFix => self.open_date_fix().await?,
+            PaletteCommand::ManifestRestore => self.open_manifest_restore().await,
+            PaletteCommand::Archives => self.open_archives(),
+            PaletteCommand::CloudUpload => self.open_cloud_upload().await,
+            PaletteCommand::VerifyLibrary => self.start_library_verify().await?,
+            PaletteCommand::DeviceImport => self.open_device_import().await?,
+            PaletteCommand::FileBrowser => self.open_file_browser(),
+            PaletteCommand::Collections => self.open_collections(),
+            PaletteCommand::Profiles => self.open_profile_picker(),
+            PaletteCommand::ActivityLog => {
+                self.state = AppState::ActivityLog;
+                self.activity_scroll = 0;
+            }
+            PaletteCommand::OrganizeReport => self.open_organize_report(),
+            PaletteCommand::ExportOrganizeReport => self.export_organize_report(),
+            PaletteCommand::ErrorCenter => self.open_error_center(),
+            PaletteCommand::ExportErrors => self.export_error_report(),
+            PaletteCommand::Notifications => self.open_notifications(),
+            PaletteCommand::Performance => self.open_performance(),
+            PaletteCommand::SpaceExplorer => self.open_space_explorer().await,
+            PaletteCommand::LogViewer => self.open_log_viewer(),
+            PaletteCommand::RevealLogFile => self.reveal_log_file(),
+            PaletteCommand::Undo => self.handle_undo().await?,
+            PaletteCommand::Redo => self.handle_redo().await?,
+            PaletteCommand::Help => {
+                self.show_help = true;
+                self.help_scroll = 0;
+            }
+            PaletteCommand::Quit => self.request_quit(),
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,148 @@
+use std::path::Path;
+use std::sync::Arc;
+
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use tracing::warn;
+use visualvault_lib::internals::core::{decode_heif_image, read_exif_dates, read_heif_image};
+use visualvault_lib::internals::models::{CompareEntry, DuplicateFocus, ExifDates, FileType, MediaFile, Thumbnail};
+
+use super::App;
+
+/// Largest edge of the thumbnails shown in the compare view
+const THUMBNAIL_SIZE: u32 = 64;
+
+impl App {
+    /// Opens the side-by-side compare view for two files of the current duplicate group.
+    ///
+    /// When exactly two files are selected those are compared, otherwise the focused file is
+    /// compared against the first file of the group.
+    pub async fn open_duplicate_compare(&mut self) {
+        let Some(group) = self
+            .duplicate_stats
+            .as_ref()
+            .and_then(|stats| stats.groups.get(self.selected_duplicate_group))
+        else {
+            return;
+        };
+
+        if group.files.len() < 2 {
+            self.error_message = Some("Need at least two files to compare".to_string());
+            return;
+        }
+
+        let (left, right) = self.compare_indices(group.files.len());
+        let left_file = Arc::clone(&group.files[left]);
+        let right_file = Arc::clone(&group.files[right]);
+
+        self.duplicate_compare = Some([
+            Self::load_compare_entry(left, left_file).await,
+            Self::load_compare_entry(right, right_file).await,
+        ]);
+    }
+
+    /// Handles keyboard input while the compare view is open.
+    ///
+    /// '1' and '2' toggle the deletion mark of the left and right file.
+    pub fn handle_compare_keys(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('c' | 'q') => self.duplicate_compare = None,
+            KeyCode::Char(side @ ('1' | '2')) => {
+                let Some(entries) = &self.duplicate_compare else {
+                    return;
+                };
+                let index = entries[usize::from(side == '2')].index;
+                if !self.selected_duplicate_items.remove(&index) {
+                    self.selected_duplicate_items.insert(index);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn compare_indices(&self, group_len: usize) -> (usize, usize) {
+        let mut selected: Vec<usize> = self
+            .selected_duplicate_items
+            .iter()
+            .copied()
+            .filter(|&idx| idx < group_len)
+            .collect();
+        if selected.len() == 2 {
+            selected.sort_unstable();
+            return (selected[0], selected[1]);
+        }
+
+        let focused = if self.duplicate_focus == DuplicateFocus::FileList {
+            self.selected_file_in_group.min(group_len - 1)
+        } else {
+            0
+        };
+        if focused == 0 { (0, 1) } else { (0, focused) }
+    }
+
+    async fn load_compare_entry(index: usize, file: Arc<MediaFile>) -> CompareEntry {
+        let path = file.path.clone();
+        let is_image = file.file_type == FileType::Image;
+
+        let loaded = tokio::task::spawn_blocking(move || load_preview(&path, is_image)).await;
+        let (exif, dimensions, thumbnail) = match loaded {
+            Ok(preview) => preview,
+            Err(e) => {
+                warn!("Failed to load compare preview: {}", e);
+                (ExifDates::default(), None, None)
+            }
+        };
+
+        CompareEntry {
+            index,
+            file,
+            dimensions,
+            exif,
+            thumbnail,
+        }
+    }
+}
+
+type Preview = (ExifDates, Option<(u32, u32)>, Option<Thumbnail>);
+
+fn load_preview(path: &Path, is_image: bool) -> Preview {
+    let exif = read_exif_dates(path).unwrap_or_else(|e| {
+        warn!("Failed to read EXIF dates from {}: {}", path.display(), e);
+        ExifDates::default()
+    });
+
+    if !is_image {
+        return (exif, None, None);
+    }
+
+    match decode_thumbnail(path) {
+        Ok((dimensions, thumbnail)) => (exif, Some(dimensions), Some(thumbnail)),
+        Err(e) => {
+            warn!("Failed to decode {} for comparison: {}", path.display(), e);
+            (exif, None, None)
+        }
+    }
+}
+
+fn decode_thumbnail(path: &Path) -> Result<((u32, u32), Thumbnail)> {
+    use image::GenericImageView;
+
+    // The size of a HEIC image comes from its container, as it may be decoded from its preview
+    let (img, dimensions) = if let Some(heif) = read_heif_image(path)? {
+        (decode_heif_image(path)?, (heif.width, heif.height))
+    } else {
+        let img = image::open(path)?;
+        let dimensions = img.dimensions();
+        (img, dimensions)
+    };
+    let small = img.thumbnail(THUMBNAIL_SIZE, THUMBNAIL_SIZE).to_rgb8();
+
+    Ok((
+        dimensions,
+        Thumbnail {
+            width: small.width(),
+            height: small.height(),
+            pixels: small.pixels().map(|p| p.0).collect(),
+        },
+    ))
+}
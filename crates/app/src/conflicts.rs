@@ -0,0 +1,108 @@
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use tracing::info;
+use visualvault_lib::internals::config::ConflictPolicy;
+use visualvault_lib::internals::models::{ActivityKind, ConflictStats, FileOutcome};
+
+use super::App;
+
+impl App {
+    /// Handles keyboard input while the organize conflict dialog is open.
+    ///
+    /// Lowercase keys decide the current conflict, uppercase keys apply the decision to
+    /// every remaining conflict.
+    ///
+    /// # Errors
+    /// Returns an error if applying the collected decisions fails.
+    pub async fn handle_conflict_keys(&mut self, key: KeyEvent) -> Result<()> {
+        match key.code {
+            KeyCode::Char('s') => self.resolve_conflict(ConflictPolicy::Skip, false).await,
+            KeyCode::Char('S') | KeyCode::Esc => self.resolve_conflict(ConflictPolicy::Skip, true).await,
+            KeyCode::Char('o') => self.resolve_conflict(ConflictPolicy::OverwriteAlways, false).await,
+            KeyCode::Char('O') => self.resolve_conflict(ConflictPolicy::OverwriteAlways, true).await,
+            KeyCode::Char('n') => self.resolve_conflict(ConflictPolicy::OverwriteIfNewer, false).await,
+            KeyCode::Char('N') => self.resolve_conflict(ConflictPolicy::OverwriteIfNewer, true).await,
+            KeyCode::Char('r') => self.resolve_conflict(ConflictPolicy::Rename, false).await,
+            KeyCode::Char('R') => self.resolve_conflict(ConflictPolicy::Rename, true).await,
+            _ => Ok(()),
+        }
+    }
+
+    /// Records a decision for the current conflict, or for all remaining ones.
+    ///
+    /// Once every conflict has a decision, the decisions are applied as a single undoable operation.
+    ///
+    /// # Errors
+    /// Returns an error if applying the collected decisions fails.
+    pub async fn resolve_conflict(&mut self, policy: ConflictPolicy, apply_to_all: bool) -> Result<()> {
+        let count = if apply_to_all { self.pending_conflicts.len() } else { 1 };
+        for conflict in self.pending_conflicts.drain(..count.min(self.pending_conflicts.len())) {
+            self.conflict_resolutions.push((conflict, policy));
+        }
+
+        if self.pending_conflicts.is_empty() {
+            self.apply_conflict_resolutions().await?;
+        }
+        Ok(())
+    }
+
+    async fn apply_conflict_resolutions(&mut self) -> Result<()> {
+        if !self.ensure_writable() {
+            return Ok(());
+        }
+        let resolutions = std::mem::take(&mut self.conflict_resolutions);
+        if resolutions.is_empty() {
+            return Ok(());
+        }
+
+        let settings = self.settings.read().await.clone();
+        let result = self.organizer.resolve_conflicts(resolutions, &settings).await?;
+        info!("Resolved {} organize conflicts", result.files_total);
+
+        let message = format!(
+            "Resolved {} conflicts: {}",
+            result.files_total,
+            Self::describe_conflicts(&result.conflicts)
+        );
+        self.log_activity(ActivityKind::Organize, message.clone());
+        self.record_organize_errors(&result.files, &result.errors);
+        if result.errors.is_empty() {
+            self.success_message = Some(message);
+        } else {
+            self.error_message = Some(format!("{message} (with {} errors)", result.errors.len()));
+        }
+
+        if let Some(last) = self.last_organize_result.as_mut() {
+            last.files_organized += result.files_organized;
+            last.conflicts.deferred = 0;
+            last.conflicts.merge(&result.conflicts);
+            last.errors.extend(result.errors);
+            last.success = last.errors.is_empty();
+            // Resolved conflicts replace the entries that were awaiting a decision
+            last.files.retain(|file| file.outcome != FileOutcome::Deferred);
+            last.files.extend(result.files);
+        }
+
+        Ok(())
+    }
+
+    /// Summarizes per-policy conflict counts, e.g. "2 skipped, 1 renamed"
+    pub(crate) fn describe_conflicts(conflicts: &ConflictStats) -> String {
+        let parts: Vec<String> = [
+            (conflicts.skipped, "skipped"),
+            (conflicts.overwritten, "overwritten"),
+            (conflicts.renamed, "renamed"),
+            (conflicts.deferred, "awaiting decision"),
+        ]
+        .iter()
+        .filter(|(count, _)| *count > 0)
+        .map(|(count, label)| format!("{count} {label}"))
+        .collect();
+
+        if parts.is_empty() {
+            "none".to_string()
+        } else {
+            parts.join(", ")
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use visualvault_lib::internals::core::{TimestampField, find_date_mismatches, fix_dates};
+use visualvault_lib::internals::models::{ActivityKind, AppState, FileType};
+
+use super::App;
+
+impl App {
+    /// Looks for scanned photos whose modification time is far from their EXIF capture date
+    /// and opens the preview of the fixes.
+    ///
+    /// # Errors
+    /// Returns an error if the scanned files cannot be read or the background task panics.
+    pub async fn open_date_fix(&mut self) -> Result<()> {
+        let images = self
+            .cached_files
+            .filter(|file| file.file_type == FileType::Image)
+            .await?;
+        if images.is_empty() {
+            self.error_message = Some("Scan the source folder first".to_string());
+            return Ok(());
+        }
+
+        let mismatches = tokio::task::spawn_blocking(move || find_date_mismatches(&images)).await?;
+        if mismatches.is_empty() {
+            self.success_message = Some("All photo dates agree with their EXIF data".to_string());
+            return Ok(());
+        }
+        self.success_message = Some(format!("Found {} photos with wrong dates", mismatches.len()));
+        self.date_mismatches = mismatches;
+        self.date_fix_scroll = 0;
+        self.state = AppState::DateFix;
+        Ok(())
+    }
+
+    /// Handles keyboard input in the date fix preview.
+    ///
+    /// # Errors
+    /// Returns an error if fixing the dates or recording the change fails.
+    pub async fn handle_date_fix_keys(&mut self, key: KeyEvent) -> Result<()> {
+        let last = self.date_mismatches.len().saturating_sub(1);
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                self.state = AppState::Dashboard;
+                self.date_mismatches.clear();
+            }
+            KeyCode::Up => self.date_fix_scroll = self.date_fix_scroll.saturating_sub(1),
+            KeyCode::Down => self.date_fix_scroll = (self.date_fix_scroll + 1).min(last),
+            KeyCode::PageUp => self.date_fix_scroll = self.date_fix_scroll.saturating_sub(10),
+            KeyCode::PageDown => self.date_fix_scroll = (self.date_fix_scroll + 10).min(last),
+            KeyCode::Char('m') | KeyCode::Enter => self.fix_photo_dates(TimestampField::Modified).await?,
+            KeyCode::Char('x') => self.fix_photo_dates(TimestampField::ExifTaken).await?,
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Sets the modification times from the EXIF dates, or the EXIF dates from the
+    /// modification times, of every listed photo.
+    async fn fix_photo_dates(&mut self, field: TimestampField) -> Result<()> {
+        if !self.ensure_writable() {
+            return Ok(());
+        }
+        if self.date_mismatches.is_empty() {
+            self.error_message = Some("No photo dates to fix".to_string());
+            return Ok(());
+        }
+
+        let mismatches = self.date_mismatches.clone();
+        let total = mismatches.len();
+        let changes = tokio::task::spawn_blocking(move || fix_dates(&mismatches, field)).await?;
+
+        let fixed: HashSet<PathBuf> = changes.iter().map(|change| change.path.clone()).collect();
+        self.date_mismatches.retain(|mismatch| !fixed.contains(&mismatch.path));
+        self.date_fix_scroll = 0;
+
+        let count = changes.len();
+        if count > 0 {
+            if field == TimestampField::Modified {
+                self.cached_files.retime(&changes).await?;
+                self.file_manager.write().await.retime_files(&changes);
+            }
+            self.organizer.undo_manager().record_timestamp_changes(changes).await?;
+        }
+
+        let message = match field {
+            TimestampField::Modified => format!("Set modification times of {count} of {total} photos from EXIF"),
+            TimestampField::ExifTaken => {
+                format!("Set EXIF dates of {count} of {total} photos from modification times")
+            }
+        };
+        self.log_activity(ActivityKind::Organize, message.clone());
+        if count < total {
+            self.error_message = Some(format!("{} photos could not be fixed, see the log", total - count));
+        }
+        self.success_message = Some(format!("{message} (Ctrl+U to undo)"));
+        if self.date_mismatches.is_empty() {
+            self.state = AppState::Dashboard;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,107 @@
+use color_eyre::eyre::{Result, eyre};
+use crossterm::event::{KeyCode, KeyEvent};
+use tracing::error;
+use visualvault_lib::internals::core::{ImportedIndex, find_camera_volumes};
+use visualvault_lib::internals::models::{ActivityKind, CameraVolume, DeviceImport};
+
+use super::App;
+
+/// Folder in the source folder that device imports are copied to
+const IMPORTS_FOLDER: &str = "Imports";
+
+impl App {
+    /// Looks for camera cards and phones and opens the device import picker.
+    ///
+    /// # Errors
+    /// Returns an error if the volume search task panics.
+    pub async fn open_device_import(&mut self) -> Result<()> {
+        let volumes = tokio::task::spawn_blocking(find_camera_volumes).await?;
+        if volumes.is_empty() {
+            self.error_message = Some("No camera card or phone with a DCIM folder found".to_string());
+            return Ok(());
+        }
+        self.device_import = Some(DeviceImport { volumes, selected: 0 });
+        Ok(())
+    }
+
+    /// Handles keyboard input while the device import picker is open.
+    ///
+    /// # Errors
+    /// Returns an error if the import task panics or the scan that follows cannot start.
+    pub async fn handle_device_import_keys(&mut self, key: KeyEvent) -> Result<()> {
+        let Some(import) = self.device_import.as_mut() else {
+            return Ok(());
+        };
+
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => self.device_import = None,
+            KeyCode::Up => import.selected = import.selected.saturating_sub(1),
+            KeyCode::Down => import.selected = (import.selected + 1).min(import.volumes.len().saturating_sub(1)),
+            KeyCode::Enter => {
+                if let Some(volume) = import.selected_volume().cloned() {
+                    self.device_import = None;
+                    self.import_from_volume(volume).await?;
+                }
+            }
+            KeyCode::Char('s') => {
+                if let Some(volume) = import.selected_volume().cloned() {
+                    self.device_import = None;
+                    self.source_override = Some(volume.dcim);
+                    self.success_message = Some(format!("Scanning {} as a temporary source", volume.name));
+                    self.start_scan().await?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Copies the media of `volume` that was never imported into the source folder and
+    /// rescans it, so the new files are ready to organize.
+    async fn import_from_volume(&mut self, volume: CameraVolume) -> Result<()> {
+        if !self.ensure_writable() {
+            return Ok(());
+        }
+        let Some(source) = self.settings.read().await.source_folder.clone() else {
+            self.error_message = Some("No source folder configured to import into".to_string());
+            return Ok(());
+        };
+        let target = source.join(IMPORTS_FOLDER).join(&volume.name);
+        let dcim = volume.dcim.clone();
+
+        let result = tokio::task::spawn_blocking(move || {
+            let config_dir = dirs::config_dir().ok_or_else(|| eyre!("Could not find config directory"))?;
+            let mut index = ImportedIndex::load(&config_dir)?;
+            let summary = index.import_new_media(&dcim, &target)?;
+            index.save()?;
+            Ok::<_, color_eyre::Report>(summary)
+        })
+        .await?;
+
+        match result {
+            Ok(summary) if summary.imported == 0 => {
+                self.success_message = Some(format!(
+                    "Nothing new on {} ({} files imported before)",
+                    volume.name, summary.skipped
+                ));
+            }
+            Ok(summary) => {
+                let mut message = format!("Imported {} new files from {}", summary.imported, volume.name);
+                if summary.skipped > 0 {
+                    message = format!("{message}, skipped {} imported before", summary.skipped);
+                }
+                if summary.failed > 0 {
+                    message = format!("{message}, {} failed", summary.failed);
+                }
+                self.log_activity(ActivityKind::Scan, message.clone());
+                self.start_scan().await?;
+                self.success_message = Some(message);
+            }
+            Err(e) => {
+                error!("Import from {} failed: {}", volume.dcim.display(), e);
+                self.error_message = Some(format!("Import from {} failed: {e}", volume.name));
+            }
+        }
+        Ok(())
+    }
+}
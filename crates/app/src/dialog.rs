@@ -0,0 +1,125 @@
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use visualvault_lib::internals::models::{
+    BulkDuplicateAction, Dialog, DialogAction, DialogKind, DialogOutcome, PendingQuit, PreflightAction,
+};
+
+use super::App;
+
+impl App {
+    /// Shows `dialog` over the current view until it is answered.
+    pub(crate) fn open_dialog(&mut self, dialog: Dialog) {
+        self.dialog = Some(dialog);
+    }
+
+    /// Handles keyboard input while a dialog is open.
+    ///
+    /// Tab and the arrow keys move the focus, Enter activates the focused button or option
+    /// and Esc cancels. Confirmations also take Y and N, and choices the number of an option.
+    ///
+    /// # Errors
+    /// Returns an error if the action the dialog was answered with fails.
+    pub async fn handle_dialog_keys(&mut self, key: KeyEvent) -> Result<()> {
+        let Some(dialog) = self.dialog.as_mut() else {
+            return Ok(());
+        };
+        let outcome = match key.code {
+            KeyCode::Esc => Some(DialogOutcome::Cancelled),
+            KeyCode::Enter => dialog.activate(),
+            KeyCode::Tab | KeyCode::Down => {
+                dialog.focus_next();
+                None
+            }
+            KeyCode::BackTab | KeyCode::Up => {
+                dialog.focus_previous();
+                None
+            }
+            KeyCode::Right if !dialog.is_editing() => {
+                dialog.focus_next();
+                None
+            }
+            KeyCode::Left if !dialog.is_editing() => {
+                dialog.focus_previous();
+                None
+            }
+            KeyCode::Backspace => {
+                dialog.backspace();
+                None
+            }
+            KeyCode::Char(c) if dialog.is_editing() => {
+                dialog.type_char(c);
+                None
+            }
+            KeyCode::Char(c) => shortcut(dialog, c),
+            _ => None,
+        };
+
+        match (outcome, self.dialog.take()) {
+            (Some(outcome), Some(dialog)) => self.resolve_dialog(dialog.action, outcome).await,
+            (None, dialog) => {
+                self.dialog = dialog;
+                Ok(())
+            }
+            (Some(_), None) => Ok(()),
+        }
+    }
+
+    async fn resolve_dialog(&mut self, action: DialogAction, outcome: DialogOutcome) -> Result<()> {
+        match (action, outcome) {
+            (DialogAction::BulkDuplicates(BulkDuplicateAction::Delete), DialogOutcome::Confirmed) => {
+                self.perform_bulk_delete().await?;
+            }
+            (DialogAction::BulkDuplicates(BulkDuplicateAction::Link), DialogOutcome::Confirmed) => {
+                self.perform_bulk_link().await?;
+            }
+            (DialogAction::Organize, DialogOutcome::Chose(0)) => self.confirm_filtered_organize().await?,
+            (DialogAction::Organize, DialogOutcome::Chose(_)) => {
+                self.pending_filtered_organize = None;
+                let files = self.cached_files.to_vec().await?;
+                self.run_organize(files).await?;
+            }
+            (DialogAction::ResetSettings, DialogOutcome::Confirmed) => self.reset_settings(),
+            (DialogAction::Quit, DialogOutcome::Chose(0)) => self.quit_after(PendingQuit::AfterFinishing),
+            (DialogAction::Quit, DialogOutcome::Chose(1)) => self.quit_after(PendingQuit::AfterCancelling),
+            (DialogAction::Quit, DialogOutcome::Chose(_)) => self.should_quit = true,
+            (DialogAction::CreateProfile, DialogOutcome::Input(name)) => self.create_profile(&name).await?,
+            (DialogAction::ReloadSettings, DialogOutcome::Confirmed) => self.reload_settings().await?,
+            (DialogAction::Preflight(PreflightAction::Scan), DialogOutcome::Confirmed) => self.start_scan().await?,
+            (DialogAction::Preflight(PreflightAction::Organize), DialogOutcome::Confirmed) => {
+                self.start_organize().await?;
+            }
+            (action, _) => self.cancel_dialog(action),
+        }
+        Ok(())
+    }
+
+    fn cancel_dialog(&mut self, action: DialogAction) {
+        match action {
+            DialogAction::BulkDuplicates(BulkDuplicateAction::Delete) => {
+                self.error_message = Some("Bulk delete cancelled".to_string());
+            }
+            DialogAction::BulkDuplicates(BulkDuplicateAction::Link) => {
+                self.error_message = Some("Bulk linking cancelled".to_string());
+            }
+            DialogAction::Organize => self.cancel_filtered_organize(),
+            DialogAction::ReloadSettings => self.keep_unsaved_settings(),
+            DialogAction::Preflight(action) => self.cancel_preflight(action),
+            DialogAction::ResetSettings | DialogAction::Quit | DialogAction::CreateProfile => {}
+        }
+    }
+}
+
+/// The answer a letter or digit key gives outside of a text field
+fn shortcut(dialog: &Dialog, c: char) -> Option<DialogOutcome> {
+    match (&dialog.kind, c) {
+        (DialogKind::Confirm, 'y' | 'Y') => Some(DialogOutcome::Confirmed),
+        (_, 'n' | 'N' | 'q') => Some(DialogOutcome::Cancelled),
+        (DialogKind::Choice { options }, '1'..='9') => c
+            .to_digit(10)
+            .and_then(|digit| usize::try_from(digit).ok())
+            .map(|digit| digit - 1)
+            .filter(|index| *index < options.len())
+            .map(DialogOutcome::Chose),
+        _ => None,
+    }
+}
@@ -1,31 +1,54 @@
+use std::collections::{BTreeSet, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Instant;
+
 use color_eyre::eyre::Result;
 use crossterm::event::{KeyCode, KeyEvent};
-use visualvault_models::DuplicateFocus;
-use visualvault_utils::format_bytes;
+use visualvault_lib::internals::core::{LinkSupport, find_bursts, find_similar_videos, link_duplicates};
+use visualvault_lib::internals::models::{
+    ActivityKind, BulkDuplicateAction, Dialog, DialogAction, DuplicateFocus, DuplicateStats, EditingField, InputMode,
+    MediaFile, NotifiedOperation, OperationReport,
+};
+use visualvault_lib::internals::utils::format_bytes;
 
 use super::{App, AppState};
 
+/// Exact duplicates, probable copies, similar videos and bursts
+const DUPLICATE_TABS: usize = 4;
+
+/// Duplicate review tab listing the bursts
+const BURST_TAB: usize = 3;
+
+/// Folder in the destination that collects the archived shots of bursts
+const BURST_ARCHIVE_FOLDER: &str = "Burst archive";
+
 impl App {
     /// Starts a duplicate file scan operation.
     ///
+    /// Checks the scanned files, or the folder chosen for the review, which is scanned
+    /// by itself without touching the scanned files.
+    ///
     /// # Errors
     /// Returns an error if the duplicate detection process fails.
     pub async fn start_duplicate_scan(&mut self) -> Result<()> {
         self.error_message = None;
         self.success_message = Some("Scanning for duplicates...".to_string());
 
-        // Make sure we have files to scan
-        if self.cached_files.is_empty() {
-            self.error_message = Some("No files to scan. Run a file scan first.".to_string());
-            self.success_message = None;
+        let Some(files) = self.duplicate_scan_files().await? else {
             return Ok(());
-        }
+        };
 
-        // Use cached files for duplicate detection
-        let stats = self
-            .duplicate_detector
-            .detect_duplicates(&self.cached_files, false)
-            .await?;
+        let algorithm = self.settings.read().await.hash_algorithm;
+        self.duplicate_detector = self.scanner.duplicate_detector(algorithm);
+        let stats = if self.duplicate_folder.is_some() {
+            self.duplicate_detector.detect_duplicates(&files, false).await?
+        } else {
+            // Copies of the scanned files are grouped by the hashes in the file cache
+            self.scanner.find_duplicates_in(&self.cached_files, algorithm).await?
+        };
+        let probable = self.duplicate_detector.detect_probable_copies(&files).await?;
+        drop(files);
 
         let message = if stats.total_groups > 0 {
             format!(
@@ -37,19 +60,222 @@ impl App {
         } else {
             "No duplicates found.".to_string()
         };
+        let message = if probable.total_groups > 0 {
+            format!("{message} ({} groups of probable copies)", probable.total_groups)
+        } else {
+            message
+        };
+
+        let message = match &self.duplicate_folder {
+            Some(folder) => format!("{message} in {}", folder.display()),
+            None => message,
+        };
+        let message = match stats.hash_cache_summary() {
+            Some(summary) => format!("{message}; {summary}"),
+            None => message,
+        };
 
-        let has_groups = stats.total_groups > 0;
-        self.duplicate_stats = Some(stats);
+        self.exact_duplicates = Some(stats);
+        self.probable_copies = Some(probable);
+        self.similar_videos = None;
+        self.bursts = None;
         self.success_message = Some(message);
         self.state = AppState::DuplicateReview;
+        self.show_duplicate_tab(self.duplicate_tab);
+
+        Ok(())
+    }
+
+    /// Compares the videos among the checked files by sampled frames and shows the similar ones
+    async fn start_video_similarity_scan(&mut self) -> Result<()> {
+        self.error_message = None;
+        self.success_message = Some("Sampling video frames...".to_string());
+
+        let Some(files) = self.duplicate_scan_files().await? else {
+            return Ok(());
+        };
+        let options = self.settings.read().await.video_similarity;
+        match find_similar_videos(&files, options).await {
+            Ok(stats) => {
+                self.success_message = Some(if stats.is_empty() {
+                    "No similar videos found.".to_string()
+                } else {
+                    format!(
+                        "Found {} groups of similar videos wasting {}",
+                        stats.total_groups,
+                        format_bytes(stats.total_wasted_space)
+                    )
+                });
+                self.similar_videos = Some(stats);
+                self.state = AppState::DuplicateReview;
+                self.show_duplicate_tab(2);
+            }
+            Err(e) => {
+                self.error_message = Some(format!("Cannot compare videos: {e}"));
+                self.success_message = None;
+            }
+        }
+        Ok(())
+    }
+
+    /// Groups the photos among the checked files into bursts and shows them, sharpest first
+    async fn start_burst_scan(&mut self) -> Result<()> {
+        self.error_message = None;
+        self.success_message = Some("Looking for bursts...".to_string());
+
+        let Some(files) = self.duplicate_scan_files().await? else {
+            return Ok(());
+        };
+        let stats = find_bursts(&files).await?;
+        self.success_message = Some(if stats.is_empty() {
+            "No bursts found.".to_string()
+        } else {
+            format!(
+                "Found {} bursts; the sharpest shot of each is listed first",
+                stats.total_groups
+            )
+        });
+        self.bursts = Some(stats);
+        self.state = AppState::DuplicateReview;
+        self.show_duplicate_tab(BURST_TAB);
+        Ok(())
+    }
 
-        // Reset selection states
+    /// Checks the files again after a cleanup, including the bursts when they are shown
+    async fn refresh_duplicate_review(&mut self) -> Result<()> {
+        let tab = self.duplicate_tab;
+        self.start_duplicate_scan().await?;
+        if tab == BURST_TAB {
+            self.start_burst_scan().await?;
+        }
+        Ok(())
+    }
+
+    /// Moves the selected shots of the current burst into the burst archive, with undo support
+    async fn archive_selected_shots(&mut self) -> Result<()> {
+        let Some(group) = self
+            .duplicate_stats
+            .as_ref()
+            .and_then(|stats| stats.groups.get(self.selected_duplicate_group))
+        else {
+            return Ok(());
+        };
+        let paths: Vec<_> = self
+            .selected_duplicate_items
+            .iter()
+            .filter_map(|&idx| group.files.get(idx))
+            .map(|file| file.path.clone())
+            .collect();
+        if paths.is_empty() {
+            self.error_message = Some("No shots selected for archiving".to_string());
+            return Ok(());
+        }
+
+        let settings = self.settings.read().await;
+        let Some(library) = settings
+            .destination_folder
+            .clone()
+            .or_else(|| settings.source_folder.clone())
+        else {
+            drop(settings);
+            self.error_message = Some("No destination folder configured".to_string());
+            return Ok(());
+        };
+        drop(settings);
+
+        self.move_files(paths, library.join(BURST_ARCHIVE_FOLDER)).await?;
+        self.selected_duplicate_items.clear();
+        let message = self.success_message.take();
+        self.refresh_duplicate_review().await?;
+        self.success_message = message;
+        Ok(())
+    }
+
+    /// The files the duplicate review checks: the chosen folder, or else the scanned files.
+    ///
+    /// Returns `None` after showing an error when there is nothing to check.
+    async fn duplicate_scan_files(&mut self) -> Result<Option<Vec<Arc<MediaFile>>>> {
+        if let Some(folder) = self.duplicate_folder.clone() {
+            return match self.scan_duplicate_folder(&folder).await {
+                Ok(files) => Ok(Some(files)),
+                Err(e) => {
+                    self.error_message = Some(format!("Failed to scan {}: {e}", folder.display()));
+                    self.success_message = None;
+                    Ok(None)
+                }
+            };
+        }
+
+        // Make sure we have files to scan
+        if self.cached_files.is_empty() {
+            self.error_message = Some("No files to scan. Run a file scan first.".to_string());
+            self.success_message = None;
+            return Ok(None);
+        }
+        Ok(Some(self.cached_files.to_vec().await?))
+    }
+
+    /// Scans `folder` and everything below it for the duplicate review
+    async fn scan_duplicate_folder(&self, folder: &Path) -> Result<Vec<Arc<MediaFile>>> {
+        let settings = self.settings.read().await.clone();
+        self.engine
+            .scan_folder(folder, &settings, Arc::clone(&self.progress))
+            .await
+    }
+
+    /// Checks the destination library for duplicates, independently of the scanned files
+    async fn scan_library_duplicates(&mut self) -> Result<()> {
+        let Some(destination) = self.settings.read().await.destination_folder.clone() else {
+            self.error_message = Some("No destination folder configured".to_string());
+            return Ok(());
+        };
+        self.duplicate_folder = Some(destination);
+        self.start_duplicate_scan().await
+    }
+
+    /// Shows the exact duplicates (tab 0), the probable copies (tab 1), the similar videos (tab 2)
+    /// or the bursts (tab 3), filtered and sorted as chosen, and resets the selection
+    pub(crate) fn show_duplicate_tab(&mut self, tab: usize) {
+        self.duplicate_tab = tab;
+        let source = match tab {
+            0 => &self.exact_duplicates,
+            1 => &self.probable_copies,
+            2 => &self.similar_videos,
+            _ => &self.bursts,
+        };
+        self.duplicate_stats = source
+            .as_ref()
+            .map(|stats| stats.view(&self.duplicate_filter, self.duplicate_sort));
+
+        self.duplicate_compare = None;
+        self.duplicate_focus = DuplicateFocus::GroupList;
+        self.selected_file_in_group = 0;
         self.selected_duplicate_group = 0;
         self.selected_duplicate_items.clear();
-        self.duplicate_list_state
-            .select(if has_groups { Some(0) } else { None });
+    }
 
-        Ok(())
+    /// Handles typing in the group filter box; Enter keeps the filter and Esc clears it
+    fn handle_duplicate_filter_keys(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Enter => self.input_mode = InputMode::Normal,
+            KeyCode::Esc => {
+                self.input_mode = InputMode::Normal;
+                self.duplicate_filter.clear();
+            }
+            KeyCode::Backspace => {
+                self.duplicate_filter.pop();
+            }
+            KeyCode::Char(c) => self.duplicate_filter.push(c),
+            _ => return,
+        }
+        self.show_duplicate_tab(self.duplicate_tab);
+    }
+
+    /// Orders the groups by the next criterion
+    fn cycle_duplicate_sort(&mut self) {
+        self.duplicate_sort = self.duplicate_sort.next();
+        self.show_duplicate_tab(self.duplicate_tab);
+        self.success_message = Some(format!("Duplicate groups sorted by {}", self.duplicate_sort));
     }
 
     /// Handles keyboard input in duplicate review mode.
@@ -57,18 +283,21 @@ impl App {
     /// # Errors
     /// Returns an error if file operations (scanning, deleting) fail.
     pub async fn handle_duplicate_keys(&mut self, key: KeyEvent) -> Result<()> {
-        // Handle bulk delete confirmation first
-        if self.pending_bulk_delete {
-            match key.code {
-                KeyCode::Char('y' | 'Y') => {
-                    self.pending_bulk_delete = false;
-                    self.perform_bulk_delete().await?;
-                }
-                KeyCode::Char('n' | 'N') | KeyCode::Esc => {
-                    self.pending_bulk_delete = false;
-                    self.error_message = Some("Bulk delete cancelled".to_string());
-                }
-                _ => {}
+        if self.duplicate_compare.is_some() {
+            self.handle_compare_keys(key);
+            return Ok(());
+        }
+
+        if self.input_mode == InputMode::Insert {
+            self.handle_duplicate_filter_keys(key);
+            return Ok(());
+        }
+
+        if self.folder_picker.is_some() {
+            self.handle_folder_picker_keys(key);
+            // Choosing a folder closes the picker; check it right away
+            if self.folder_picker.is_none() && matches!(key.code, KeyCode::Char('s' | ' ')) {
+                self.start_duplicate_scan().await?;
             }
             return Ok(());
         }
@@ -80,12 +309,48 @@ impl App {
             KeyCode::Char('s') => {
                 self.start_duplicate_scan().await?;
             }
+            KeyCode::Char('l') => {
+                self.scan_library_duplicates().await?;
+            }
+            KeyCode::Char('o') => {
+                self.open_folder_picker(EditingField::DuplicateFolder);
+            }
+            KeyCode::Char('f') if self.duplicate_folder.is_some() => {
+                self.duplicate_folder = None;
+                self.start_duplicate_scan().await?;
+            }
+            KeyCode::Char('v') => {
+                self.start_video_similarity_scan().await?;
+            }
+            KeyCode::Char('b') => {
+                self.start_burst_scan().await?;
+            }
+            KeyCode::Char('A') if self.duplicate_tab == BURST_TAB => {
+                self.archive_selected_shots().await?;
+            }
+            KeyCode::Tab => {
+                self.show_duplicate_tab((self.duplicate_tab + 1) % DUPLICATE_TABS);
+            }
+            KeyCode::BackTab => {
+                self.show_duplicate_tab((self.duplicate_tab + DUPLICATE_TABS - 1) % DUPLICATE_TABS);
+            }
             KeyCode::Up => {
                 self.move_duplicate_selection_up();
             }
             KeyCode::Down => {
                 self.move_duplicate_selection_down();
             }
+            KeyCode::PageUp | KeyCode::PageDown | KeyCode::Home | KeyCode::End
+                if self.duplicate_focus == DuplicateFocus::GroupList =>
+            {
+                self.page_duplicate_groups(key.code);
+            }
+            KeyCode::Char('/') => {
+                self.input_mode = InputMode::Insert;
+            }
+            KeyCode::Char('S') => {
+                self.cycle_duplicate_sort();
+            }
             KeyCode::Left => {
                 self.switch_to_group_list();
             }
@@ -104,24 +369,45 @@ impl App {
             KeyCode::Char('D') => {
                 self.initiate_bulk_delete();
             }
+            KeyCode::Char('h') => self.link_selected_duplicates().await?,
+            KeyCode::Char('H') => self.initiate_bulk_link(),
+            KeyCode::Char('c') => {
+                self.open_duplicate_compare().await;
+            }
+            KeyCode::Char('g') => {
+                if let Some(path) = self.focused_duplicate_path() {
+                    self.jump_to_file(&path).await?;
+                }
+            }
             _ => {}
         }
         Ok(())
     }
 
+    /// Path of the highlighted file, or of the first file of the highlighted group
+    fn focused_duplicate_path(&self) -> Option<PathBuf> {
+        let group = self
+            .duplicate_stats
+            .as_ref()?
+            .groups
+            .get(self.selected_duplicate_group)?;
+        let index = match self.duplicate_focus {
+            DuplicateFocus::GroupList => 0,
+            DuplicateFocus::FileList => self.selected_file_in_group,
+        };
+        group.files.get(index).map(|file| file.path.clone())
+    }
+
     fn exit_duplicate_review(&mut self) {
         self.state = AppState::Dashboard;
         self.selected_duplicate_items.clear();
+        self.duplicate_compare = None;
     }
 
-    fn move_duplicate_selection_up(&mut self) {
+    pub(crate) fn move_duplicate_selection_up(&mut self) {
         match self.duplicate_focus {
             DuplicateFocus::GroupList => {
-                if self.selected_duplicate_group > 0 {
-                    self.selected_duplicate_group -= 1;
-                    self.duplicate_list_state.select(Some(self.selected_duplicate_group));
-                    self.selected_duplicate_items.clear();
-                }
+                self.select_duplicate_group(self.selected_duplicate_group.saturating_sub(1));
             }
             DuplicateFocus::FileList => {
                 if let Some(stats) = &self.duplicate_stats {
@@ -133,16 +419,10 @@ impl App {
         }
     }
 
-    fn move_duplicate_selection_down(&mut self) {
+    pub(crate) fn move_duplicate_selection_down(&mut self) {
         match self.duplicate_focus {
             DuplicateFocus::GroupList => {
-                if let Some(stats) = &self.duplicate_stats {
-                    if !stats.groups.is_empty() && self.selected_duplicate_group < stats.groups.len() - 1 {
-                        self.selected_duplicate_group += 1;
-                        self.duplicate_list_state.select(Some(self.selected_duplicate_group));
-                        self.selected_duplicate_items.clear();
-                    }
-                }
+                self.select_duplicate_group(self.selected_duplicate_group + 1);
             }
             DuplicateFocus::FileList => {
                 if let Some(stats) = &self.duplicate_stats {
@@ -156,6 +436,28 @@ impl App {
         }
     }
 
+    /// Selects the group at `index`, kept within the listed groups
+    pub(crate) fn select_duplicate_group(&mut self, index: usize) {
+        let len = self.duplicate_stats.as_ref().map_or(0, DuplicateStats::len);
+        let index = index.min(len.saturating_sub(1));
+        if index != self.selected_duplicate_group {
+            self.selected_duplicate_group = index;
+            self.selected_duplicate_items.clear();
+        }
+    }
+
+    /// Moves through the group list a page at a time, or to its start or end
+    fn page_duplicate_groups(&mut self, code: KeyCode) {
+        let page = self.duplicate_viewport.page();
+        let index = match code {
+            KeyCode::PageUp => self.selected_duplicate_group.saturating_sub(page),
+            KeyCode::PageDown => self.selected_duplicate_group + page,
+            KeyCode::Home => 0,
+            _ => usize::MAX,
+        };
+        self.select_duplicate_group(index);
+    }
+
     const fn switch_to_group_list(&mut self) {
         self.duplicate_focus = DuplicateFocus::GroupList;
         self.selected_file_in_group = 0;
@@ -207,23 +509,30 @@ impl App {
     }
 
     fn initiate_bulk_delete(&mut self) {
-        // Set pending and show confirmation message
         if let Some(stats) = &self.duplicate_stats {
             if stats.total_duplicates > 0 {
-                self.pending_bulk_delete = true;
-                self.error_message = Some(format!(
-                    "⚠️  Delete {} duplicates from {} groups? This will free {}. Press Y to confirm, N to cancel",
-                    stats.total_duplicates,
-                    stats.total_groups,
-                    format_bytes(stats.total_wasted_space)
-                ));
+                let dialog = Dialog::confirm(
+                    DialogAction::BulkDuplicates(BulkDuplicateAction::Delete),
+                    "Delete duplicates",
+                    format!(
+                        "Delete {} duplicates from {} groups? The first file of every group is kept. This will free {}.",
+                        stats.total_duplicates,
+                        stats.total_groups,
+                        format_bytes(stats.total_wasted_space)
+                    ),
+                )
+                .destructive();
+                self.open_dialog(dialog);
             } else {
                 self.error_message = Some("No duplicates to delete".to_string());
             }
         }
     }
 
-    async fn perform_bulk_delete(&mut self) -> Result<()> {
+    pub(crate) async fn perform_bulk_delete(&mut self) -> Result<()> {
+        if !self.ensure_writable() {
+            return Ok(());
+        }
         if let Some(stats) = &self.duplicate_stats {
             let mut paths_to_delete = Vec::new();
 
@@ -239,24 +548,41 @@ impl App {
 
             if !paths_to_delete.is_empty() {
                 let total_to_delete = paths_to_delete.len();
+                let started = Instant::now();
                 let deleted = self.duplicate_detector.delete_files(&paths_to_delete).await?;
+                self.duplicates_removed += deleted.len();
+                let freed = format_bytes(stats.total_wasted_space);
+                let report = Self::cleanup_report(started, deleted.len(), total_to_delete)
+                    .with_count("bytes_freed", stats.total_wasted_space);
 
                 self.success_message = Some(format!(
                     "✅ Successfully deleted {} of {} duplicate files, freed {}",
                     deleted.len(),
                     total_to_delete,
-                    format_bytes(stats.total_wasted_space)
+                    freed
                 ));
+                self.log_activity(
+                    ActivityKind::Delete,
+                    format!(
+                        "Deleted {} of {total_to_delete} duplicate files, freed {freed}",
+                        deleted.len()
+                    ),
+                );
+
+                self.notify(report).await;
 
                 // Clear selections and rescan
                 self.selected_duplicate_items.clear();
-                self.start_duplicate_scan().await?;
+                self.refresh_duplicate_review().await?;
             }
         }
         Ok(())
     }
 
     async fn delete_selected_duplicates(&mut self) -> Result<()> {
+        if !self.ensure_writable() {
+            return Ok(());
+        }
         if let Some(stats) = &self.duplicate_stats {
             if let Some(group) = stats.groups.get(self.selected_duplicate_group) {
                 let mut paths_to_delete = Vec::new();
@@ -268,15 +594,183 @@ impl App {
                 }
 
                 if !paths_to_delete.is_empty() {
+                    let started = Instant::now();
                     let deleted = self.duplicate_detector.delete_files(&paths_to_delete).await?;
+                    self.duplicates_removed += deleted.len();
+                    let bytes_freed = deleted
+                        .iter()
+                        .filter_map(|path| group.files.iter().find(|file| &file.path == path))
+                        .map(|file| file.size)
+                        .sum();
+                    let report = Self::cleanup_report(started, deleted.len(), paths_to_delete.len())
+                        .with_count("bytes_freed", bytes_freed);
                     self.success_message = Some(format!("Deleted {} files", deleted.len()));
+                    self.log_activity(
+                        ActivityKind::Delete,
+                        format!(
+                            "Deleted {} of {} selected duplicate files",
+                            deleted.len(),
+                            paths_to_delete.len()
+                        ),
+                    );
+                    self.notify(report).await;
 
                     // Clear selections and rescan
                     self.selected_duplicate_items.clear();
-                    self.start_duplicate_scan().await?;
+                    self.refresh_duplicate_review().await?;
                 }
             }
         }
         Ok(())
     }
+
+    fn initiate_bulk_link(&mut self) {
+        match &self.duplicate_stats {
+            Some(stats) if stats.total_duplicates > 0 => {
+                let dialog = Dialog::confirm(
+                    DialogAction::BulkDuplicates(BulkDuplicateAction::Link),
+                    "Link duplicates",
+                    format!(
+                        "Replace {} duplicates from {} groups with links to the first file? This will free {}.",
+                        stats.total_duplicates,
+                        stats.total_groups,
+                        format_bytes(stats.total_wasted_space)
+                    ),
+                )
+                .destructive();
+                self.open_dialog(dialog);
+            }
+            Some(_) => self.error_message = Some("No duplicates to link".to_string()),
+            None => {}
+        }
+    }
+
+    /// Replaces all but the first file of every group with links to it
+    pub(crate) async fn perform_bulk_link(&mut self) -> Result<()> {
+        let Some(stats) = &self.duplicate_stats else {
+            return Ok(());
+        };
+        let groups: Vec<(PathBuf, Vec<PathBuf>)> = stats
+            .groups
+            .iter()
+            .filter_map(|group| {
+                let (kept, duplicates) = group.files.split_first()?;
+                Some((
+                    kept.path.clone(),
+                    duplicates.iter().map(|file| file.path.clone()).collect(),
+                ))
+            })
+            .collect();
+        self.link_duplicate_groups(groups).await
+    }
+
+    /// Replaces the selected files of the current group with links to the first file of the
+    /// group that is not selected
+    async fn link_selected_duplicates(&mut self) -> Result<()> {
+        let Some(group) = self
+            .duplicate_stats
+            .as_ref()
+            .and_then(|stats| stats.groups.get(self.selected_duplicate_group))
+        else {
+            return Ok(());
+        };
+        let (selected, unselected): (Vec<_>, Vec<_>) = group
+            .files
+            .iter()
+            .enumerate()
+            .partition(|(idx, _)| self.selected_duplicate_items.contains(idx));
+        let Some((_, kept)) = unselected.first() else {
+            self.error_message = Some("Leave one file unselected to keep".to_string());
+            return Ok(());
+        };
+        if selected.is_empty() {
+            self.error_message = Some("No files selected for linking".to_string());
+            return Ok(());
+        }
+        let groups = vec![(
+            kept.path.clone(),
+            selected.iter().map(|(_, file)| file.path.clone()).collect(),
+        )];
+        self.link_duplicate_groups(groups).await
+    }
+
+    /// Replaces each group's duplicates with links to its kept copy and takes them out of the review
+    async fn link_duplicate_groups(&mut self, groups: Vec<(PathBuf, Vec<PathBuf>)>) -> Result<()> {
+        if !self.ensure_writable() {
+            return Ok(());
+        }
+        let requested: usize = groups.iter().map(|(_, duplicates)| duplicates.len()).sum();
+        let started = Instant::now();
+        let linked = tokio::task::spawn_blocking(move || {
+            let mut support = LinkSupport::default();
+            groups
+                .iter()
+                .flat_map(|(kept, duplicates)| link_duplicates(kept, duplicates, &mut support))
+                .collect::<Vec<_>>()
+        })
+        .await?;
+        self.duplicates_removed += linked.len();
+
+        let linked_paths: HashSet<PathBuf> = linked.iter().map(|link| link.path.clone()).collect();
+        let bytes_freed = self
+            .duplicate_stats
+            .iter()
+            .flat_map(|stats| stats.groups.iter().flat_map(|group| group.files.iter()))
+            .filter(|file| linked_paths.contains(&file.path))
+            .map(|file| file.size)
+            .sum();
+        let methods: BTreeSet<String> = linked.iter().map(|link| link.method.to_string()).collect();
+        let failed = requested - linked.len();
+        let report = OperationReport::new(NotifiedOperation::DuplicateCleanup, started.elapsed())
+            .with_count("files_linked", linked.len() as u64)
+            .with_count("files_requested", requested as u64)
+            .with_count("bytes_freed", bytes_freed)
+            .with_errors(if failed > 0 {
+                vec![format!("{failed} of {requested} files could not be linked")]
+            } else {
+                Vec::new()
+            });
+
+        let message = format!(
+            "Linked {} of {requested} duplicate files, freed {}",
+            linked.len(),
+            format_bytes(bytes_freed)
+        );
+        let message = if methods.is_empty() {
+            message
+        } else {
+            format!("{message} ({}s)", methods.into_iter().collect::<Vec<_>>().join(" and "))
+        };
+        self.log_activity(ActivityKind::Delete, message.clone());
+        if failed > 0 {
+            self.error_message = Some(format!(
+                "{failed} files could not be linked: links need the same file system, see the log"
+            ));
+        }
+        self.success_message = Some(message);
+        self.notify(report).await;
+
+        for stats in [&mut self.exact_duplicates, &mut self.probable_copies]
+            .into_iter()
+            .flatten()
+        {
+            stats.remove_files(&linked_paths);
+        }
+        self.show_duplicate_tab(self.duplicate_tab);
+        Ok(())
+    }
+
+    /// Builds the notification report of a duplicate cleanup
+    fn cleanup_report(started: Instant, deleted: usize, requested: usize) -> OperationReport {
+        let failed = requested.saturating_sub(deleted);
+        let errors = if failed > 0 {
+            vec![format!("{failed} of {requested} files could not be deleted")]
+        } else {
+            Vec::new()
+        };
+        OperationReport::new(NotifiedOperation::DuplicateCleanup, started.elapsed())
+            .with_count("files_deleted", deleted as u64)
+            .with_count("files_requested", requested as u64)
+            .with_errors(errors)
+    }
 }
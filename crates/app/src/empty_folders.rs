@@ -0,0 +1,85 @@
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use tracing::error;
+use visualvault_lib::internals::core::{find_empty_directories, remove_empty_directories};
+use visualvault_lib::internals::models::{ActivityKind, AppState};
+
+use super::App;
+
+impl App {
+    /// Scans the source folder for empty directories and opens the cleanup preview.
+    ///
+    /// # Errors
+    /// Returns an error if the background scan task panics.
+    pub async fn open_empty_folder_cleanup(&mut self) -> Result<()> {
+        let Some(source) = self.settings.read().await.source_folder.clone() else {
+            self.error_message = Some("No source folder configured".to_string());
+            return Ok(());
+        };
+
+        match tokio::task::spawn_blocking(move || find_empty_directories(&source)).await? {
+            Ok(folders) => {
+                self.success_message = Some(if folders.is_empty() {
+                    "No empty folders found".to_string()
+                } else {
+                    format!("Found {} empty folders", folders.len())
+                });
+                self.empty_folders = folders;
+                self.empty_folder_scroll = 0;
+                self.state = AppState::EmptyFolders;
+            }
+            Err(e) => {
+                error!("Empty folder scan failed: {}", e);
+                self.error_message = Some(format!("Failed to scan for empty folders: {e}"));
+            }
+        }
+        Ok(())
+    }
+
+    /// Handles keyboard input in the empty folder cleanup view.
+    ///
+    /// # Errors
+    /// Returns an error if rescanning or recording the removal fails.
+    pub async fn handle_empty_folder_keys(&mut self, key: KeyEvent) -> Result<()> {
+        let last = self.empty_folders.len().saturating_sub(1);
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                self.state = AppState::Dashboard;
+                self.empty_folders.clear();
+            }
+            KeyCode::Up => self.empty_folder_scroll = self.empty_folder_scroll.saturating_sub(1),
+            KeyCode::Down => self.empty_folder_scroll = (self.empty_folder_scroll + 1).min(last),
+            KeyCode::PageUp => self.empty_folder_scroll = self.empty_folder_scroll.saturating_sub(10),
+            KeyCode::PageDown => self.empty_folder_scroll = (self.empty_folder_scroll + 10).min(last),
+            KeyCode::Char('r') => self.open_empty_folder_cleanup().await?,
+            KeyCode::Char('d') | KeyCode::Enter => self.remove_empty_folders().await?,
+            _ => {}
+        }
+        Ok(())
+    }
+
+    async fn remove_empty_folders(&mut self) -> Result<()> {
+        if !self.ensure_writable() {
+            return Ok(());
+        }
+        if self.empty_folders.is_empty() {
+            self.error_message = Some("No empty folders to remove".to_string());
+            return Ok(());
+        }
+
+        let folders = std::mem::take(&mut self.empty_folders);
+        let total = folders.len();
+        let removed = tokio::task::spawn_blocking(move || remove_empty_directories(&folders)).await?;
+        let count = removed.len();
+
+        if count > 0 {
+            self.organizer.undo_manager().record_remove_dirs(removed).await?;
+        }
+
+        let message = format!("Removed {count} of {total} empty folders");
+        self.log_activity(ActivityKind::Delete, message.clone());
+        self.success_message = Some(format!("{message} (Ctrl+U to undo)"));
+        self.empty_folder_scroll = 0;
+        Ok(())
+    }
+}
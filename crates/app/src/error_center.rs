@@ -0,0 +1,109 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use visualvault_lib::internals::core::{FileManager, reveal_in_file_manager};
+use visualvault_lib::internals::models::{AppState, ErrorEntry, ErrorSource, OrganizedFile};
+
+use super::App;
+
+impl App {
+    /// Opens the error center on the newest error
+    pub const fn open_error_center(&mut self) {
+        self.error_center.selected = 0;
+        self.state = AppState::ErrorCenter;
+    }
+
+    /// Handles keyboard input in the error center.
+    ///
+    /// 'd' dismisses the selected error, 'c' clears them all and 'e' exports them as CSV.
+    pub fn handle_error_center_keys(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => self.state = AppState::Dashboard,
+            KeyCode::Up => self.error_center.move_selection(-1),
+            KeyCode::Down => self.error_center.move_selection(1),
+            KeyCode::PageUp => self.error_center.move_selection(-10),
+            KeyCode::PageDown => self.error_center.move_selection(10),
+            KeyCode::Home => self.error_center.selected = 0,
+            KeyCode::End => self.error_center.move_selection(isize::MAX),
+            KeyCode::Char('d') | KeyCode::Delete => {
+                self.error_center.dismiss_selected();
+            }
+            KeyCode::Char('c') => {
+                let count = self.error_center.len();
+                self.error_center.clear();
+                self.success_message = Some(format!("Dismissed {count} errors"));
+            }
+            KeyCode::Char('e') => self.export_error_report(),
+            KeyCode::Char('y') => {
+                if let Some(path) = self.error_center.selected_entry().and_then(|entry| entry.path.clone()) {
+                    self.copy_path(&path, false);
+                }
+            }
+            KeyCode::Enter => self.reveal_error_file(),
+            _ => {}
+        }
+    }
+
+    /// Records an operation that failed as a whole, such as a scan of a missing folder
+    pub(crate) fn record_operation_error(&mut self, message: impl Into<String>) {
+        self.error_center
+            .record([ErrorEntry::new(ErrorSource::Operation, None, message)]);
+    }
+
+    /// Records the files an organize run could not place, or its error if it failed as a whole
+    pub(crate) fn record_organize_errors(&mut self, files: &[OrganizedFile], errors: &[String]) {
+        let failed: Vec<ErrorEntry> = files
+            .iter()
+            .filter_map(|file| {
+                let error = file.error.as_ref()?;
+                Some(ErrorEntry::new(
+                    ErrorSource::Organize,
+                    Some(file.source.clone()),
+                    error.clone(),
+                ))
+            })
+            .collect();
+        if failed.is_empty() {
+            self.error_center.record(
+                errors
+                    .iter()
+                    .map(|error| ErrorEntry::new(ErrorSource::Operation, None, format!("Organize failed: {error}"))),
+            );
+        } else {
+            self.error_center.record(failed);
+        }
+    }
+
+    fn reveal_error_file(&mut self) {
+        let Some(path) = self.error_center.selected_entry().and_then(|entry| entry.path.clone()) else {
+            return;
+        };
+        // The file itself may be gone, so fall back to the nearest folder that still exists
+        let Some(existing) = path.ancestors().find(|p| p.exists()) else {
+            self.error_message = Some(format!("{} no longer exists", path.display()));
+            return;
+        };
+        if let Err(e) = reveal_in_file_manager(existing) {
+            tracing::warn!("Failed to show {}: {}", existing.display(), e);
+            self.error_message = Some(format!("Could not open file manager: {e}"));
+        }
+    }
+
+    pub(crate) fn export_error_report(&mut self) {
+        if self.error_center.is_empty() {
+            self.error_message = Some("No errors to export".to_string());
+            return;
+        }
+        let Some(config_dir) = dirs::config_dir() else {
+            self.error_message = Some("Could not find config directory".to_string());
+            return;
+        };
+
+        let path = config_dir
+            .join("visualvault")
+            .join("reports")
+            .join(format!("errors-{}.csv", chrono::Local::now().format("%Y%m%d-%H%M%S")));
+        match FileManager::export_error_report(&self.error_center.entries, &path) {
+            Ok(()) => self.success_message = Some(format!("Errors exported to {}", path.display())),
+            Err(e) => self.error_message = Some(format!("Failed to export errors: {e}")),
+        }
+    }
+}
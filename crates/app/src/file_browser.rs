@@ -0,0 +1,174 @@
+use std::path::{Path, PathBuf};
+
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use visualvault_lib::internals::core::{FileManager, list_folder};
+use visualvault_lib::internals::models::{ActivityKind, AppState, BrowserPane};
+
+use super::App;
+
+impl App {
+    /// Opens the two-pane file browser, with the source folder on the left and the
+    /// destination folder on the right. Panes keep their folders between visits.
+    pub fn open_file_browser(&mut self) {
+        let home = dirs::home_dir().unwrap_or_default();
+        if self.file_browser.left.dir.as_os_str().is_empty() {
+            self.file_browser.left.dir = self
+                .settings_cache
+                .source_folder
+                .clone()
+                .unwrap_or_else(|| home.clone());
+        }
+        if self.file_browser.right.dir.as_os_str().is_empty() {
+            self.file_browser.right.dir = self.settings_cache.destination_folder.clone().unwrap_or(home);
+        }
+        self.file_browser.marked.clear();
+        self.refresh_file_browser();
+        self.state = AppState::FileBrowser;
+    }
+
+    /// Handles keyboard input in the file browser.
+    ///
+    /// # Errors
+    /// Returns an error if a move or copy task panics, or the operation cannot be recorded
+    /// for undo or undone.
+    pub async fn handle_file_browser_keys(&mut self, key: KeyEvent) -> Result<()> {
+        if key.modifiers == KeyModifiers::CONTROL {
+            match key.code {
+                KeyCode::Char('z') => self.handle_undo().await?,
+                KeyCode::Char('r') => self.handle_redo().await?,
+                _ => return Ok(()),
+            }
+            self.refresh_file_browser();
+            return Ok(());
+        }
+
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => self.state = AppState::Dashboard,
+            KeyCode::Tab | KeyCode::BackTab => self.file_browser.switch_pane(),
+            KeyCode::Up => {
+                let pane = self.file_browser.active_pane_mut();
+                pane.selected = pane.selected.saturating_sub(1);
+            }
+            KeyCode::Down => {
+                let pane = self.file_browser.active_pane_mut();
+                pane.selected = (pane.selected + 1).min(pane.entries.len().saturating_sub(1));
+            }
+            KeyCode::Home => self.file_browser.active_pane_mut().selected = 0,
+            KeyCode::End => {
+                let pane = self.file_browser.active_pane_mut();
+                pane.selected = pane.entries.len().saturating_sub(1);
+            }
+            KeyCode::Enter | KeyCode::Right => {
+                let pane = self.file_browser.active_pane_mut();
+                if let Some(dir) = pane
+                    .selected_entry()
+                    .filter(|entry| entry.is_dir)
+                    .map(|entry| entry.path.clone())
+                {
+                    pane.dir = dir;
+                    pane.selected = 0;
+                    self.load_pane(None);
+                }
+            }
+            KeyCode::Backspace | KeyCode::Left => {
+                let pane = self.file_browser.active_pane_mut();
+                if let Some(parent) = pane.dir.parent().map(Path::to_path_buf) {
+                    let previous = std::mem::replace(&mut pane.dir, parent);
+                    self.load_pane(Some(&previous));
+                }
+            }
+            KeyCode::Char(' ') => {
+                self.file_browser.toggle_mark();
+                let pane = self.file_browser.active_pane_mut();
+                pane.selected = (pane.selected + 1).min(pane.entries.len().saturating_sub(1));
+            }
+            KeyCode::Char('m') => self.transfer_browser_files(false).await?,
+            KeyCode::Char('c') => self.transfer_browser_files(true).await?,
+            KeyCode::Char('r') => self.refresh_file_browser(),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Re-reads the folders of both panes
+    fn refresh_file_browser(&mut self) {
+        self.file_browser.switch_pane();
+        self.load_pane(None);
+        self.file_browser.switch_pane();
+        self.load_pane(None);
+    }
+
+    /// Lists the folder of the active pane, selecting `select` if it is in it
+    fn load_pane(&mut self, select: Option<&Path>) {
+        let pane = self.file_browser.active_pane_mut();
+        match list_folder(&pane.dir) {
+            Ok(entries) => pane.set_entries(entries, select),
+            Err(e) => {
+                let message = format!("Cannot read {}: {e}", pane.dir.display());
+                *pane = BrowserPane {
+                    dir: pane.dir.clone(),
+                    ..BrowserPane::default()
+                };
+                self.error_message = Some(message);
+            }
+        }
+    }
+
+    /// Moves or copies the marked or selected files of the active pane into the folder of
+    /// the other pane, recording the operation for undo
+    async fn transfer_browser_files(&mut self, copy: bool) -> Result<()> {
+        let paths = self.file_browser.targets();
+        if paths.is_empty() {
+            self.error_message = Some("Select or mark files to transfer".to_string());
+            return Ok(());
+        }
+        let folder = self.file_browser.other_pane().dir.clone();
+        if self.file_browser.active_pane().dir == folder {
+            self.error_message = Some("Both panes show the same folder".to_string());
+            return Ok(());
+        }
+
+        if copy {
+            self.copy_browser_files(paths, folder).await?;
+        } else {
+            self.move_files(paths, folder).await?;
+        }
+
+        self.file_browser.marked.clear();
+        self.refresh_file_browser();
+        Ok(())
+    }
+
+    async fn copy_browser_files(&mut self, paths: Vec<PathBuf>, folder: PathBuf) -> Result<()> {
+        if !self.ensure_writable() {
+            return Ok(());
+        }
+        let requested = paths.len();
+        let target = folder.clone();
+        let copied = match tokio::task::spawn_blocking(move || FileManager::copy_files(&paths, &target)).await? {
+            Ok(copied) => copied,
+            Err(e) => {
+                self.error_message = Some(format!("Cannot copy files to {}: {e}", folder.display()));
+                return Ok(());
+            }
+        };
+
+        let count = copied.len();
+        if count > 0 {
+            self.organizer.undo_manager().record_batch_copy(copied).await?;
+        }
+        let message = if count == requested {
+            format!("Copied {count} files to {}", folder.display())
+        } else {
+            format!(
+                "Copied {count} files to {}, {} could not be copied",
+                folder.display(),
+                requested - count
+            )
+        };
+        self.log_activity(ActivityKind::Organize, message.clone());
+        self.success_message = Some(message);
+        Ok(())
+    }
+}
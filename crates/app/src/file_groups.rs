@@ -0,0 +1,160 @@
+use std::sync::Arc;
+
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use visualvault_lib::internals::models::{AppState, GroupBy, GroupRow, GroupedFiles, MediaFile};
+
+use super::App;
+
+impl App {
+    /// Groups the Files tab by folder, then month, then type, then shows the flat list again
+    pub(crate) async fn cycle_file_grouping(&mut self) -> Result<()> {
+        let by = match &self.file_groups {
+            None => Some(GroupBy::Folder),
+            Some(groups) => groups.by.next(),
+        };
+        self.file_group_rows.clear();
+        let Some(by) = by else {
+            self.file_groups = None;
+            self.success_message = Some("Showing all files".to_string());
+            return Ok(());
+        };
+
+        let mut groups = self.group_files(by).await?;
+        groups.reveal(self.selected_file_index);
+        self.success_message = Some(format!("Grouped {} files by {by}", self.cached_files.len()));
+        self.file_groups = Some(groups);
+        Ok(())
+    }
+
+    /// Regroups the files after the file list changed, keeping expanded groups and the
+    /// selected file
+    pub(crate) async fn refresh_file_groups(&mut self) -> Result<()> {
+        let Some(previous) = &self.file_groups else {
+            return Ok(());
+        };
+        if previous.version == self.cached_files.version() {
+            return Ok(());
+        }
+        let selected_file =
+            matches!(previous.selected_row(), Some(GroupRow::File { .. })).then_some(self.selected_file_index);
+        let by = previous.by;
+        let groups = self.group_files(by).await?;
+        if let Some(previous) = &self.file_groups {
+            self.file_groups = Some(groups.keeping_state_of(previous, selected_file));
+        }
+        self.file_group_rows.clear();
+        Ok(())
+    }
+
+    async fn group_files(&self, by: GroupBy) -> Result<GroupedFiles> {
+        let mut files = Vec::with_capacity(self.cached_files.len());
+        self.cached_files
+            .for_each(|index, file| files.push((index, by.key(file), file.size)))
+            .await?;
+        Ok(GroupedFiles::from_keys(by, files, self.cached_files.version()))
+    }
+
+    /// Whether the selection moves through groups rather than the flat file list or search
+    /// results
+    pub(crate) fn is_grouped_view(&self) -> bool {
+        self.file_groups.is_some() && self.state != AppState::Search
+    }
+
+    /// Selected row of the list the selection moves through
+    pub(crate) fn selected_row(&self) -> usize {
+        match &self.file_groups {
+            Some(groups) if self.is_grouped_view() => groups.selected,
+            _ => self.selected_file_index,
+        }
+    }
+
+    /// Selects `row`, and the file it shows when the file list is grouped
+    pub(crate) fn select_row(&mut self, row: usize) {
+        let grouped = self.is_grouped_view();
+        match &mut self.file_groups {
+            Some(groups) if grouped => {
+                groups.selected = row;
+                if let Some(GroupRow::File { index, .. }) = groups.selected_row() {
+                    self.selected_file_index = index;
+                }
+            }
+            _ => self.selected_file_index = row,
+        }
+    }
+
+    /// Selects the file at list index `index`, expanding its group when the list is grouped
+    pub(crate) fn reveal_file(&mut self, index: usize) {
+        self.selected_file_index = index;
+        if let Some(groups) = &mut self.file_groups {
+            groups.reveal(index);
+        }
+    }
+
+    /// File at list index `index`, also when a grouped paged list shows it outside the
+    /// loaded window
+    #[must_use]
+    pub fn grouped_file(&self, index: usize) -> Option<&Arc<MediaFile>> {
+        self.cached_files
+            .get(index)
+            .or_else(|| self.file_group_rows.get(&index))
+    }
+
+    /// Loads the grouped files a paged list shows outside its loaded window
+    pub(crate) async fn load_grouped_rows(&mut self) -> Result<()> {
+        let Some(groups) = &self.file_groups else {
+            return Ok(());
+        };
+        let visible: Vec<usize> = self
+            .file_viewport
+            .visible(groups.selected, groups.row_count())
+            .filter_map(|row| match groups.row(row) {
+                Some(GroupRow::File { index, .. }) => Some(index),
+                _ => None,
+            })
+            .collect();
+        let mut rows = std::mem::take(&mut self.file_group_rows);
+        rows.retain(|index, _| visible.contains(index));
+        for index in visible {
+            if self.cached_files.get(index).is_some() || rows.contains_key(&index) {
+                continue;
+            }
+            if let Some(file) = self.cached_files.range(index, index).await?.pop() {
+                rows.insert(index, file);
+            }
+        }
+        self.file_group_rows = rows;
+        Ok(())
+    }
+
+    /// Expands or collapses the group whose header is selected. Returns `false` if no group
+    /// header is selected.
+    pub(crate) fn toggle_selected_group(&mut self) -> bool {
+        match &mut self.file_groups {
+            Some(groups) if matches!(groups.selected_row(), Some(GroupRow::Header(_))) => {
+                groups.toggle();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Handles the keys that expand and collapse groups. Returns `true` if the key was used,
+    /// including file actions ignored while a group header is selected.
+    pub(crate) async fn handle_grouped_keys(&mut self, key: KeyEvent) -> Result<bool> {
+        let Some(groups) = &mut self.file_groups else {
+            return Ok(false);
+        };
+        let header = matches!(groups.selected_row(), Some(GroupRow::Header(_)));
+        match key.code {
+            KeyCode::Enter if header => groups.toggle(),
+            KeyCode::Left => groups.set_expanded(false),
+            KeyCode::Right => groups.set_expanded(true),
+            KeyCode::Char(' ' | 'V' | 'x' | 'X' | 'y' | 'Y') if header => {}
+            _ => return Ok(false),
+        }
+        // Keeps the rows of a newly expanded group loaded
+        self.load_grouped_rows().await?;
+        Ok(true)
+    }
+}
@@ -0,0 +1,66 @@
+use color_eyre::eyre::Result;
+use tracing::warn;
+use visualvault_lib::internals::models::{FileSort, SortColumn};
+
+use super::App;
+
+impl App {
+    /// Sorts the file list by the next column, starting with the name
+    pub(crate) async fn cycle_file_sort(&mut self) -> Result<()> {
+        let column = self.file_sort.map_or(SortColumn::Name, |sort| sort.column.next());
+        self.set_file_sort(FileSort::ascending(column)).await
+    }
+
+    /// Flips the direction of the file list order
+    pub(crate) async fn reverse_file_sort(&mut self) -> Result<()> {
+        let sort = self.file_sort.unwrap_or(FileSort::ascending(SortColumn::Name));
+        self.set_file_sort(FileSort {
+            descending: !sort.descending,
+            ..sort
+        })
+        .await
+    }
+
+    /// Sorts the file list by a clicked column header, flipping the direction on a second click
+    pub(crate) async fn sort_by_column(&mut self, column: SortColumn) -> Result<()> {
+        self.set_file_sort(FileSort::toggled(self.file_sort, column)).await
+    }
+
+    /// Sorts the file list and keeps the selected file selected
+    async fn set_file_sort(&mut self, sort: FileSort) -> Result<()> {
+        let selected = self
+            .cached_files
+            .get(self.selected_file_index)
+            .map(|file| file.path.clone());
+        self.file_sort = Some(sort);
+        self.apply_file_sort().await?;
+        if let Some(path) = selected {
+            self.selected_file_index = self.cached_files.index_of(&path).await?.unwrap_or(0);
+        }
+        self.success_message = Some(format!(
+            "Sorted by {} ({})",
+            sort.column,
+            if sort.descending { "descending" } else { "ascending" }
+        ));
+        Ok(())
+    }
+
+    /// Puts the file list in the chosen order, so the order survives new scans.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a paged file list cannot be rewritten.
+    pub(crate) async fn apply_file_sort(&mut self) -> Result<()> {
+        if let Some(sort) = self.file_sort {
+            self.cached_files.sort(sort).await?;
+        }
+        Ok(())
+    }
+
+    /// Like [`Self::apply_file_sort`], keeping the scan order if sorting fails
+    pub(crate) async fn apply_file_sort_or_warn(&mut self) {
+        if let Err(e) = self.apply_file_sort().await {
+            warn!("Failed to sort the file list: {}", e);
+        }
+    }
+}
@@ -1,14 +1,23 @@
 use chrono::{Duration, Local, NaiveDate, TimeZone};
+use color_eyre::eyre::Result;
 use crossterm::event::{KeyCode, KeyEvent};
-use visualvault_models::{FilterFocus, InputMode, filters::RegexTarget};
+use visualvault_lib::internals::core::FileQuery;
+use visualvault_lib::internals::models::{FilterFocus, FilterMatches, InputMode, filters::RegexTarget};
 
 use super::{App, AppState};
 
 impl App {
-    pub fn handle_filter_keys(&mut self, key: KeyEvent) {
+    /// Handles keyboard input in the filters view.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a paged file list cannot be read while applying the filters.
+    pub async fn handle_filter_keys(&mut self, key: KeyEvent) -> Result<()> {
+        // Any change to the filters is counted again on the next tick
+        self.filter_matches = None;
         if self.input_mode == InputMode::Editing {
             self.handle_filter_editing_mode(key);
-            return;
+            return Ok(());
         }
 
         match key.code {
@@ -16,19 +25,17 @@ impl App {
                 self.state = AppState::Dashboard;
             }
             KeyCode::Tab => {
-                self.filter_tab = (self.filter_tab + 1) % 4;
+                self.filter_tab = (self.filter_tab + 1) % 6;
                 self.selected_filter_index = 0;
                 self.update_filter_focus();
             }
             KeyCode::BackTab => {
-                self.filter_tab = if self.filter_tab == 0 { 3 } else { self.filter_tab - 1 };
+                self.filter_tab = if self.filter_tab == 0 { 5 } else { self.filter_tab - 1 };
                 self.selected_filter_index = 0;
                 self.update_filter_focus();
             }
-            KeyCode::Up => {
-                if self.selected_filter_index > 0 {
-                    self.selected_filter_index -= 1;
-                }
+            KeyCode::Up if self.selected_filter_index > 0 => {
+                self.selected_filter_index -= 1;
             }
             KeyCode::Down => {
                 let max_index = self.get_max_filter_index();
@@ -53,12 +60,29 @@ impl App {
             KeyCode::Char('t') => {
                 self.toggle_filter_active();
             }
+            KeyCode::Char('o') => {
+                self.toggle_apply_to_organize();
+            }
+            KeyCode::Char('n') => {
+                self.negate_selected_filter();
+            }
+            KeyCode::Char('m') => {
+                self.filter_set.combinator = self.filter_set.combinator.toggled();
+                self.success_message = Some(format!(
+                    "Filter categories combined with {}",
+                    self.filter_set.combinator
+                ));
+            }
+            KeyCode::Char(c @ '0'..='5') if self.filter_focus == FilterFocus::Tags => {
+                self.set_min_rating(c);
+            }
             KeyCode::Enter => {
-                self.apply_filters();
+                self.apply_filters().await?;
                 self.state = AppState::Dashboard;
             }
             _ => {}
         }
+        Ok(())
     }
 
     fn handle_filter_editing_mode(&mut self, key: KeyEvent) {
@@ -87,6 +111,8 @@ impl App {
             1 => FilterFocus::SizeRange,
             2 => FilterFocus::MediaType,
             3 => FilterFocus::RegexPattern,
+            4 => FilterFocus::Tags,
+            5 => FilterFocus::Extensions,
             _ => FilterFocus::DateRange,
         };
     }
@@ -97,6 +123,8 @@ impl App {
             FilterFocus::SizeRange => self.filter_set.size_ranges.len(),
             FilterFocus::MediaType => self.filter_set.media_types.len(),
             FilterFocus::RegexPattern => self.filter_set.regex_patterns.len(),
+            FilterFocus::Tags => self.filter_set.required_tags.len(),
+            FilterFocus::Extensions => self.filter_set.extension_filters.len(),
         }
     }
 
@@ -122,12 +150,30 @@ impl App {
                 }
             }
             FilterFocus::RegexPattern => {
-                if !self.filter_input.is_empty() {
-                    self.filter_set
-                        .add_regex_pattern(self.filter_input.clone(), RegexTarget::FileName, false);
+                // A "camera:" prefix matches the camera and lens instead of the file name
+                let (pattern, target) = self
+                    .filter_input
+                    .strip_prefix("camera:")
+                    .map_or((self.filter_input.as_str(), RegexTarget::FileName), |pattern| {
+                        (pattern.trim(), RegexTarget::Camera)
+                    });
+                if !pattern.is_empty() {
+                    self.filter_set.add_regex_pattern(pattern.to_string(), target, false);
                     self.success_message = Some("Regex pattern added".to_string());
                 }
             }
+            FilterFocus::Tags => {
+                if self.filter_set.add_required_tags(&self.filter_input) > 0 {
+                    self.success_message = Some("Tag filter added".to_string());
+                }
+            }
+            FilterFocus::Extensions => {
+                if self.filter_set.add_extension_filter(&self.filter_input) {
+                    self.success_message = Some("Extension filter added".to_string());
+                } else if !self.filter_input.trim().is_empty() {
+                    self.error_message = Some("No extensions given. Use 'jpg,png,heic' or '!gif'".to_string());
+                }
+            }
             FilterFocus::MediaType => {
                 // Media types are handled differently (toggle-based)
             }
@@ -272,6 +318,26 @@ impl App {
                     }
                 }
             }
+            FilterFocus::Tags => {
+                if self.selected_filter_index < self.filter_set.required_tags.len() {
+                    self.filter_set.required_tags.remove(self.selected_filter_index);
+                    if self.selected_filter_index > 0
+                        && self.selected_filter_index >= self.filter_set.required_tags.len()
+                    {
+                        self.selected_filter_index = self.filter_set.required_tags.len().saturating_sub(1);
+                    }
+                }
+            }
+            FilterFocus::Extensions => {
+                if self.selected_filter_index < self.filter_set.extension_filters.len() {
+                    self.filter_set.extension_filters.remove(self.selected_filter_index);
+                    if self.selected_filter_index > 0
+                        && self.selected_filter_index >= self.filter_set.extension_filters.len()
+                    {
+                        self.selected_filter_index = self.filter_set.extension_filters.len().saturating_sub(1);
+                    }
+                }
+            }
             FilterFocus::MediaType => {
                 // Media types cannot be deleted, only toggled
             }
@@ -295,6 +361,13 @@ impl App {
                     self.success_message = Some(format!("Pattern {status}"));
                 }
             }
+            FilterFocus::Extensions => {
+                if let Some(ef) = self.filter_set.extension_filters.get_mut(self.selected_filter_index) {
+                    ef.enabled = !ef.enabled;
+                    let status = if ef.enabled { "enabled" } else { "disabled" };
+                    self.success_message = Some(format!("Extension filter {status}"));
+                }
+            }
             FilterFocus::DateRange => {
                 if let Some(dr) = self.filter_set.date_ranges.get_mut(self.selected_filter_index) {
                     self.success_message = Some(format!("Date range '{}'", dr.name));
@@ -305,10 +378,53 @@ impl App {
                     self.success_message = Some(format!("Size range '{}'", sr.name));
                 }
             }
+            FilterFocus::Tags => {
+                if let Some(tag) = self.filter_set.required_tags.get(self.selected_filter_index) {
+                    self.success_message = Some(format!("Tag '{tag}'"));
+                }
+            }
         }
     }
 
-    fn toggle_filter_active(&mut self) {
+    /// Marks the selected filter as NOT, so the files it matches are left out
+    fn negate_selected_filter(&mut self) {
+        let index = self.selected_filter_index;
+        let negated = match self.filter_focus {
+            FilterFocus::DateRange => self.filter_set.date_ranges.get_mut(index).map(|dr| &mut dr.negated),
+            FilterFocus::SizeRange => self.filter_set.size_ranges.get_mut(index).map(|sr| &mut sr.negated),
+            FilterFocus::MediaType => self.filter_set.media_types.get_mut(index).map(|mt| &mut mt.negated),
+            FilterFocus::RegexPattern => self.filter_set.regex_patterns.get_mut(index).map(|rp| &mut rp.negated),
+            FilterFocus::Extensions => self
+                .filter_set
+                .extension_filters
+                .get_mut(index)
+                .map(|ef| &mut ef.exclude),
+            FilterFocus::Tags => {
+                self.error_message = Some("Tag filters cannot be negated".to_string());
+                return;
+            }
+        };
+        if let Some(negated) = negated {
+            *negated = !*negated;
+            let status = if *negated { "marked NOT" } else { "no longer NOT" };
+            self.success_message = Some(format!("Filter {status}"));
+        }
+    }
+
+    /// Sets the minimum star rating from a digit key, where 0 removes it
+    fn set_min_rating(&mut self, digit: char) {
+        let stars = digit
+            .to_digit(10)
+            .and_then(|d| u8::try_from(d).ok())
+            .unwrap_or_default();
+        self.filter_set.min_rating = (stars > 0).then_some(stars);
+        self.success_message = Some(match self.filter_set.min_rating {
+            Some(stars) => format!("Only files rated {stars} stars or more"),
+            None => "Rating filter removed".to_string(),
+        });
+    }
+
+    pub(crate) fn toggle_filter_active(&mut self) {
         self.filter_set.is_active = !self.filter_set.is_active;
         let status = if self.filter_set.is_active {
             "enabled"
@@ -318,21 +434,80 @@ impl App {
         self.success_message = Some(format!("Filters {status}"));
     }
 
-    fn apply_filters(&mut self) {
+    fn toggle_apply_to_organize(&mut self) {
+        self.filter_set.apply_to_organize = !self.filter_set.apply_to_organize;
+        self.success_message = Some(if self.filter_set.apply_to_organize {
+            "Organize will only move files matching the filters".to_string()
+        } else {
+            "Organize will move all scanned files".to_string()
+        });
+    }
+
+    /// Counts the files matching the active filters, again whenever the filters or the file
+    /// list change.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a paged file list cannot be read.
+    pub(crate) async fn refresh_filter_matches(&mut self) -> Result<()> {
+        if !self.filter_set.is_active {
+            self.filter_matches = None;
+            return Ok(());
+        }
+        let version = self.cached_files.version();
+        if self.filter_matches.is_some_and(|matches| matches.version == version) {
+            return Ok(());
+        }
+        let mut matches = FilterMatches {
+            version,
+            ..FilterMatches::default()
+        };
+        if let Some(query) = self.filter_query() {
+            for extension in self.scanner.extension_stats(&query).await? {
+                matches.files += extension.files;
+                matches.size += extension.total_size;
+            }
+        } else {
+            self.cached_files
+                .for_each(|_, file| {
+                    if self.matches_filters(file) {
+                        matches.files += 1;
+                        matches.size += file.size;
+                    }
+                })
+                .await?;
+        }
+        self.filter_matches = Some(matches);
+        Ok(())
+    }
+
+    /// The query on the file cache selecting the scanned files the filters keep, or `None` if
+    /// the list has no cache scope or the filters can only be checked against the files
+    fn filter_query(&self) -> Option<FileQuery> {
+        self.cached_files.cache_scope()?.clone().with_filters(&self.filter_set)
+    }
+
+    async fn apply_filters(&mut self) -> Result<()> {
         if self.filter_set.is_active {
-            let filtered_count = self
-                .cached_files
-                .iter()
-                .filter(|file| self.filter_set.matches_file(file))
-                .count();
+            let filtered_count = match self.filter_query() {
+                Some(query) => self.scanner.count_files(&query).await?,
+                None => self.cached_files.count(|file| self.matches_filters(file)).await?,
+            };
 
+            let scope = if self.filter_set.apply_to_organize {
+                " (organize limited to matches)"
+            } else {
+                ""
+            };
             self.success_message = Some(format!(
-                "Filters applied: {} of {} files match",
+                "Filters applied: {} of {} files match{}",
                 filtered_count,
-                self.cached_files.len()
+                self.cached_files.len(),
+                scope
             ));
         } else {
             self.success_message = Some("Filters are inactive. Press 't' to toggle.".to_string());
         }
+        Ok(())
     }
 }
@@ -0,0 +1,94 @@
+use std::path::{Path, PathBuf};
+
+use crossterm::event::{KeyCode, KeyEvent};
+use visualvault_lib::internals::core::list_subfolders;
+use visualvault_lib::internals::models::{EditingField, FolderPicker};
+
+use super::App;
+
+impl App {
+    /// Opens the folder picker for the source or destination folder, starting at the
+    /// folder currently set or the nearest folder above it that exists.
+    pub fn open_folder_picker(&mut self, field: EditingField) {
+        let current = match field {
+            EditingField::SourceFolder => self.settings_cache.source_folder.clone(),
+            EditingField::DestinationFolder => self.settings_cache.destination_folder.clone(),
+            EditingField::DuplicateFolder => self
+                .duplicate_folder
+                .clone()
+                .or_else(|| self.settings_cache.destination_folder.clone()),
+            _ => return,
+        };
+        let start = current
+            .as_deref()
+            .and_then(|path| path.ancestors().find(|dir| dir.is_dir()))
+            .map(Path::to_path_buf)
+            .or_else(dirs::home_dir)
+            .unwrap_or_else(|| PathBuf::from("/"));
+
+        let mut picker = FolderPicker::new(field, start.clone());
+        self.folder_picker_show(&mut picker, start, None);
+        self.folder_picker = Some(picker);
+    }
+
+    /// Handles keyboard input while the folder picker is open.
+    pub fn handle_folder_picker_keys(&mut self, key: KeyEvent) {
+        let Some(mut picker) = self.folder_picker.take() else {
+            return;
+        };
+
+        let last = picker.folders.len().saturating_sub(1);
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => return,
+            KeyCode::Up | KeyCode::Char('k') => picker.selected = picker.selected.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => picker.selected = (picker.selected + 1).min(last),
+            KeyCode::Home => picker.selected = 0,
+            KeyCode::End => picker.selected = last,
+            KeyCode::Enter | KeyCode::Right => {
+                if let Some(folder) = picker.selected_folder().cloned() {
+                    self.folder_picker_show(&mut picker, folder, None);
+                }
+            }
+            KeyCode::Backspace | KeyCode::Left => {
+                if let Some(parent) = picker.dir.parent().map(Path::to_path_buf) {
+                    let previous = picker.dir.clone();
+                    self.folder_picker_show(&mut picker, parent, Some(&previous));
+                }
+            }
+            KeyCode::Char('h') => {
+                picker.show_hidden = !picker.show_hidden;
+                let selected = picker.selected_folder().cloned();
+                let dir = picker.dir.clone();
+                self.folder_picker_show(&mut picker, dir, selected.as_ref());
+            }
+            KeyCode::Char('s' | ' ') => {
+                let folder = picker.dir.clone();
+                match picker.field {
+                    EditingField::SourceFolder => self.settings_cache.source_folder = Some(folder.clone()),
+                    EditingField::DestinationFolder => {
+                        self.settings_cache.destination_folder = Some(folder.clone());
+                    }
+                    EditingField::DuplicateFolder => {
+                        self.duplicate_folder = Some(folder);
+                        return;
+                    }
+                    _ => {}
+                }
+                self.update_folder_overlap();
+                self.success_message = Some(format!("Selected {} (press s to save settings)", folder.display()));
+                return;
+            }
+            _ => {}
+        }
+        self.folder_picker = Some(picker);
+    }
+
+    /// Lists the subfolders of `dir` into the picker. An unreadable folder is reported and
+    /// the picker stays where it was.
+    fn folder_picker_show(&mut self, picker: &mut FolderPicker, dir: PathBuf, select: Option<&PathBuf>) {
+        match list_subfolders(&dir, picker.show_hidden) {
+            Ok(folders) => picker.show(dir, folders, select),
+            Err(e) => self.error_message = Some(format!("Cannot open {}: {e}", dir.display())),
+        }
+    }
+}
@@ -1,10 +1,20 @@
 use color_eyre::eyre::Result;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
-use visualvault_config::Settings;
-use visualvault_models::{EditingField, FileType, InputMode};
+use visualvault_lib::internals::config::{
+    DiagnosticLevel, FolderRule, OrganizeSchedule, RenameTemplate, RoutingRule, Settings, TypeMapping,
+};
+use visualvault_lib::internals::core::{
+    ClipboardMethod, copy_to_clipboard, open_with_default_app, reveal_in_file_manager,
+};
+use visualvault_lib::internals::models::{
+    ActivityKind, Dialog, DialogAction, EditingField, FileType, InputMode, MediaMetadata,
+};
+use visualvault_lib::internals::utils::log_file_path;
 
 use super::{App, AppState};
-use std::path::PathBuf;
+use crate::navigation::{FILES_TAB, METADATA_TAB};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 impl App {
     /// Handles global keyboard input events across all application states.
@@ -14,15 +24,33 @@ impl App {
     /// Returns an error if handling keys in normal or insert mode fails,
     /// typically due to file system operations or configuration updates.
     pub async fn handle_global_keys(&mut self, key: KeyEvent) -> Result<()> {
-        if self.show_help {
-            return {
-                self.handle_help_keys(key);
-                Ok(())
-            };
+        if !self.pending_conflicts.is_empty() {
+            return self.handle_conflict_keys(key).await;
+        }
+
+        if self.batch_rename.is_some() {
+            return self.handle_batch_rename_keys(key).await;
+        }
+
+        if self.bulk_actions.is_some() {
+            return self.handle_bulk_action_keys(key).await;
+        }
+
+        if self.profile_picker.is_some() {
+            return self.handle_profile_picker_keys(key).await;
+        }
+
+        if self.device_import.is_some() {
+            return self.handle_device_import_keys(key).await;
+        }
+
+        if self.folder_picker.is_some() {
+            self.handle_folder_picker_keys(key);
+            return Ok(());
         }
 
         match (key.code, key.modifiers) {
-            (KeyCode::Char('z'), KeyModifiers::CONTROL) => {
+            (KeyCode::Char('u'), KeyModifiers::CONTROL) => {
                 return self.handle_undo().await;
             }
             (KeyCode::Char('r'), KeyModifiers::CONTROL) => {
@@ -44,38 +72,27 @@ impl App {
         }
     }
 
-    fn handle_help_keys(&mut self, key: KeyEvent) {
+    /// Scrolls the help overlay by `delta` lines, stopping at its last page
+    pub(crate) fn scroll_help(&mut self, delta: isize) {
+        self.help_scroll = self.help_scroll.saturating_add_signed(delta).min(self.help_max_scroll);
+    }
+
+    pub(crate) fn handle_help_keys(&mut self, key: KeyEvent) {
         match key.code {
             KeyCode::Up => {
                 if self.help_scroll > 0 {
                     self.help_scroll -= 1;
                 }
             }
-            KeyCode::Down => {
-                let content_lines: usize = 70;
-                let visible_lines: usize = 35;
-                let max_scroll = content_lines.saturating_sub(visible_lines);
-                if self.help_scroll < max_scroll {
-                    self.help_scroll += 1;
-                }
-            }
+            KeyCode::Down => self.scroll_help(1),
             KeyCode::PageUp => {
                 self.help_scroll = self.help_scroll.saturating_sub(10);
             }
-            KeyCode::PageDown => {
-                let content_lines: usize = 70;
-                let visible_lines: usize = 35;
-                let max_scroll = content_lines.saturating_sub(visible_lines);
-                self.help_scroll = (self.help_scroll + 10).min(max_scroll);
-            }
+            KeyCode::PageDown => self.scroll_help(10),
             KeyCode::Home => {
                 self.help_scroll = 0;
             }
-            KeyCode::End => {
-                let content_lines: usize = 70;
-                let visible_lines: usize = 35;
-                self.help_scroll = content_lines.saturating_sub(visible_lines);
-            }
+            KeyCode::End => self.help_scroll = self.help_max_scroll,
             _ => {
                 self.show_help = false;
                 self.help_scroll = 0;
@@ -89,27 +106,38 @@ impl App {
     ///
     /// This function currently does not return any errors, but returns a `Result`
     /// for consistency with other key handling methods.
-    pub const fn handle_file_details_keys(&mut self, key: KeyEvent) {
+    pub async fn handle_file_details_keys(&mut self, key: KeyEvent) -> Result<()> {
+        let AppState::FileDetails(index) = self.state else {
+            return Ok(());
+        };
+        if self.input_mode == InputMode::Editing {
+            return self.handle_tag_keys(index, key).await;
+        }
         match key.code {
             KeyCode::Esc | KeyCode::Char('q') => {
                 self.state = AppState::Dashboard;
             }
-            _ => {}
+            KeyCode::Char('x') => self.open_externally(index, false),
+            KeyCode::Char('X') => self.open_externally(index, true),
+            KeyCode::Char('y') => self.copy_file_path(index, false),
+            KeyCode::Char('Y') => self.copy_file_path(index, true),
+            _ => self.handle_tag_keys(index, key).await?,
         }
+        Ok(())
     }
 
     #[allow(clippy::cognitive_complexity)]
     async fn handle_normal_mode(&mut self, key: KeyEvent) -> Result<()> {
         match key.code {
             KeyCode::Char('q') => {
-                if self.state == AppState::Dashboard && [0, 1, 2, 3].contains(&self.selected_tab) {
-                    self.should_quit = true;
+                if self.state == AppState::Dashboard && (0..=METADATA_TAB).contains(&self.selected_tab) {
+                    self.request_quit();
                 }
             }
             KeyCode::Esc => match self.state {
                 AppState::Dashboard => {
-                    if [0, 1, 2, 3].contains(&self.selected_tab) {
-                        self.should_quit = true;
+                    if (0..=METADATA_TAB).contains(&self.selected_tab) {
+                        self.request_quit();
                     }
                 }
                 _ => {
@@ -119,31 +147,43 @@ impl App {
             KeyCode::Tab => self.next_tab(),
             KeyCode::BackTab => self.previous_tab(),
             KeyCode::Char('d') => self.state = AppState::Dashboard,
+            // The Files tab sorts with s and S and renames marked files with R
+            KeyCode::Char('s' | 'S' | 'R') if self.state == AppState::Dashboard && self.selected_tab == FILES_TAB => {
+                self.handle_dashboard_keys(key).await?;
+            }
             KeyCode::Char('s') => {
                 if self.state != AppState::DuplicateReview {
                     self.state = AppState::Settings;
                     self.update_settings_cache().await?;
                 }
             }
-            KeyCode::Char('r') => self.start_scan().await?,
-            KeyCode::Char('o') => self.start_organize().await?,
+            KeyCode::Char('r') => self.request_scan().await?,
+            KeyCode::Char('o') => self.request_organize().await?,
             KeyCode::Char('u') => self.update_folder_stats().await?,
-            KeyCode::Char('f' | '/') => {
-                self.state = AppState::Search;
-                self.search_input.clear();
-                self.search_results.clear();
-                self.selected_file_index = 0;
-                self.scroll_offset = 0;
-                self.input_mode = InputMode::Normal;
-            }
+            KeyCode::Char('f' | '/') => self.open_search(),
             KeyCode::Char('D') => self.state = AppState::DuplicateReview,
-            KeyCode::Char('F') => {
-                self.state = AppState::Filters;
-                self.filter_tab = 0;
-                self.selected_filter_index = 0;
-                self.update_filter_focus();
-                self.input_mode = InputMode::Normal;
-            }
+            KeyCode::Char('L') => {
+                self.state = AppState::ActivityLog;
+                self.activity_scroll = 0;
+            }
+            KeyCode::Char('O') => self.open_organize_report(),
+            KeyCode::Char('G') => self.reveal_log_file(),
+            KeyCode::Char('!') => self.open_error_center(),
+            KeyCode::Char('N') => self.open_notifications(),
+            KeyCode::Char('W') => self.open_performance(),
+            KeyCode::Char('H') => self.open_space_explorer().await,
+            KeyCode::Char('E') => self.open_empty_folder_cleanup().await?,
+            KeyCode::Char('Q') => self.open_quarantine(),
+            KeyCode::Char('T') => self.open_date_fix().await?,
+            KeyCode::Char('R') => self.open_manifest_restore().await,
+            KeyCode::Char('Z') => self.open_archives(),
+            KeyCode::Char('I') => self.start_library_verify().await?,
+            KeyCode::Char('U') => self.open_cloud_upload().await,
+            KeyCode::Char('P') => self.open_profile_picker(),
+            KeyCode::Char('C') => self.open_collections(),
+            KeyCode::Char('B') => self.open_file_browser(),
+            KeyCode::Char('M') => self.open_device_import().await?,
+            KeyCode::Char('F') => self.open_filters(),
             _ => match self.state {
                 AppState::Settings => self.handle_settings_keys(key).await?,
                 AppState::Dashboard => self.handle_dashboard_keys(key).await?,
@@ -153,6 +193,24 @@ impl App {
         Ok(())
     }
 
+    /// Opens the search view with an empty search
+    pub(crate) fn open_search(&mut self) {
+        self.state = AppState::Search;
+        self.search_input.clear();
+        self.search_results.clear();
+        self.selected_file_index = 0;
+        self.input_mode = InputMode::Normal;
+    }
+
+    /// Opens the filter editor at its first tab
+    pub(crate) const fn open_filters(&mut self) {
+        self.state = AppState::Filters;
+        self.filter_tab = 0;
+        self.selected_filter_index = 0;
+        self.update_filter_focus();
+        self.input_mode = InputMode::Normal;
+    }
+
     async fn handle_insert_mode(&mut self, key: KeyEvent) -> Result<()> {
         match key.code {
             KeyCode::Esc => {
@@ -170,10 +228,13 @@ impl App {
             }
             KeyCode::Char(c) => {
                 self.input_buffer.push(c);
+                self.update_folder_check();
             }
             KeyCode::Backspace => {
                 self.input_buffer.pop();
+                self.update_folder_check();
             }
+            KeyCode::Tab => self.complete_folder_input(),
             _ => {}
         }
         Ok(())
@@ -193,8 +254,13 @@ impl App {
                 self.save_settings().await?;
             }
             KeyCode::Char('R' | 'r') => {
-                self.settings_cache = Settings::default();
-                self.success_message = Some("Settings reset to defaults (not saved)".to_string());
+                let dialog = Dialog::confirm(
+                    DialogAction::ResetSettings,
+                    "Reset settings",
+                    "Replace every setting with its default? Nothing is saved until you press S.",
+                )
+                .destructive();
+                self.open_dialog(dialog);
             }
             KeyCode::Enter => {
                 self.handle_settings_enter();
@@ -211,15 +277,49 @@ impl App {
             KeyCode::Char(' ') => {
                 self.toggle_setting();
             }
-            KeyCode::Up => {
-                if self.selected_setting > 0 {
-                    self.selected_setting -= 1;
+            KeyCode::Delete | KeyCode::Char('x') if self.selected_tab == 3 => {
+                self.remove_type_mapping();
+            }
+            KeyCode::Delete | KeyCode::Char('x') if self.selected_tab == 4 => {
+                self.remove_folder_rule();
+            }
+            KeyCode::Delete | KeyCode::Char('x') if self.selected_tab == 5 => {
+                self.remove_routing_rule();
+            }
+            KeyCode::Char(key @ ('K' | 'J')) if self.selected_tab == 4 && self.input_mode == InputMode::Normal => {
+                if let Some(index) = self.settings_cache.move_folder_rule(self.selected_setting, key == 'K') {
+                    self.selected_setting = index;
+                }
+            }
+            KeyCode::Char(key @ ('K' | 'J')) if self.selected_tab == 5 && self.input_mode == InputMode::Normal => {
+                if let Some(index) = self.settings_cache.move_routing_rule(self.selected_setting, key == 'K') {
+                    self.selected_setting = index;
                 }
             }
+            KeyCode::Char('t') if self.selected_tab == 5 && self.input_mode == InputMode::Normal => {
+                self.input_mode = InputMode::Insert;
+                self.editing_field = Some(EditingField::RoutingSample);
+                self.input_buffer = self.routing_sample.clone();
+            }
+            KeyCode::Char('b') if self.selected_tab == 0 && self.input_mode == InputMode::Normal => {
+                match self.selected_setting {
+                    0 => self.open_folder_picker(EditingField::SourceFolder),
+                    1 => self.open_folder_picker(EditingField::DestinationFolder),
+                    _ => {}
+                }
+            }
+            KeyCode::Up if self.selected_setting > 0 => {
+                self.selected_setting -= 1;
+            }
             KeyCode::Down => {
                 let max_setting = match self.selected_tab {
-                    0 | 2 => 5,
-                    1 => 7,
+                    0 => 8,
+                    1 => 17,
+                    2 => 14,
+                    // One row per mapping, then the row for adding one
+                    3 => self.settings_cache.type_mappings.len(),
+                    4 => self.settings_cache.folder_rules.len(),
+                    5 => self.settings_cache.routing_rules.len(),
                     _ => 0,
                 };
                 if self.selected_setting < max_setting {
@@ -243,6 +343,7 @@ impl App {
                         .as_ref()
                         .map(|p| p.display().to_string())
                         .unwrap_or_default();
+                    self.update_folder_check();
                 } else {
                     self.settings_cache.source_folder = if self.input_buffer.is_empty() {
                         None
@@ -251,6 +352,7 @@ impl App {
                     };
                     self.input_mode = InputMode::Normal;
                     self.editing_field = None;
+                    self.update_folder_overlap();
                 }
             }
             1 if self.selected_tab == 0 => {
@@ -263,6 +365,7 @@ impl App {
                         .as_ref()
                         .map(|p| p.display().to_string())
                         .unwrap_or_default();
+                    self.update_folder_check();
                 } else {
                     self.settings_cache.destination_folder = if self.input_buffer.is_empty() {
                         None
@@ -271,17 +374,98 @@ impl App {
                     };
                     self.input_mode = InputMode::Normal;
                     self.editing_field = None;
+                    self.update_folder_overlap();
                 }
             }
+            9 if self.selected_tab == 1 && self.input_mode == InputMode::Normal => {
+                self.input_mode = InputMode::Insert;
+                self.editing_field = Some(EditingField::RenameTemplate);
+                self.input_buffer = self
+                    .settings_cache
+                    .rename_template
+                    .as_ref()
+                    .map(ToString::to_string)
+                    .unwrap_or_default();
+            }
+            s if self.selected_tab == 3 && self.input_mode == InputMode::Normal => {
+                self.input_mode = InputMode::Insert;
+                self.editing_field = Some(EditingField::TypeMapping);
+                self.input_buffer = self
+                    .settings_cache
+                    .type_mappings
+                    .iter()
+                    .nth(s)
+                    .map(|(extension, mapping)| format!("{extension}={mapping}"))
+                    .unwrap_or_default();
+            }
+            s if self.selected_tab == 4 && self.input_mode == InputMode::Normal => {
+                self.input_mode = InputMode::Insert;
+                self.editing_field = Some(EditingField::FolderRule);
+                self.input_buffer = self
+                    .settings_cache
+                    .folder_rules
+                    .get(s)
+                    .map(ToString::to_string)
+                    .unwrap_or_default();
+            }
+            s if self.selected_tab == 5 && self.input_mode == InputMode::Normal => {
+                self.input_mode = InputMode::Insert;
+                self.editing_field = Some(EditingField::RoutingRule);
+                self.input_buffer = self
+                    .settings_cache
+                    .routing_rules
+                    .get(s)
+                    .map(ToString::to_string)
+                    .unwrap_or_default();
+            }
             _ => {}
         }
     }
 
+    /// Removes the folder rule on the selected row of the Rules tab
+    fn remove_folder_rule(&mut self) {
+        if self.selected_setting < self.settings_cache.folder_rules.len() {
+            let rule = self.settings_cache.folder_rules.remove(self.selected_setting);
+            self.success_message = Some(format!("Removed rule for {} (not saved)", rule.folder.display()));
+        }
+    }
+
+    /// Removes the routing rule on the selected row of the Routing tab
+    fn remove_routing_rule(&mut self) {
+        if self.selected_setting < self.settings_cache.routing_rules.len() {
+            let rule = self.settings_cache.routing_rules.remove(self.selected_setting);
+            self.success_message = Some(format!("Removed rule for {} (not saved)", rule.pattern));
+        }
+    }
+
+    /// Removes the custom type mapping on the selected row of the File Types tab
+    fn remove_type_mapping(&mut self) {
+        let selected = self
+            .settings_cache
+            .type_mappings
+            .keys()
+            .nth(self.selected_setting)
+            .cloned();
+        if let Some(extension) = selected {
+            self.settings_cache.type_mappings.remove(&extension);
+            self.success_message = Some(format!("Removed mapping for .{extension} (not saved)"));
+        }
+    }
+
     fn toggle_setting(&mut self) {
         match (self.selected_tab, self.selected_setting) {
-            (0, 2) => self.settings_cache.recurse_subfolders = !self.settings_cache.recurse_subfolders,
+            (0, 2) => self.settings_cache.cycle_scan_depth(),
             (0, 3) => self.settings_cache.verbose_output = !self.settings_cache.verbose_output,
             (0, 4) => self.settings_cache.undo_enabled = !self.settings_cache.undo_enabled,
+            (0, 5) => {
+                self.settings_cache.schedule = OrganizeSchedule::next_preset(self.settings_cache.schedule);
+            }
+            (0, 6) => self.settings_cache.notifications.desktop = !self.settings_cache.notifications.desktop,
+            (0, 7) => {
+                self.settings_cache.notifications.desktop_after_secs =
+                    self.settings_cache.notifications.next_desktop_after_secs();
+            }
+            (0, 8) => self.settings_cache.accessibility = self.settings_cache.accessibility.next(),
             (1, s) if s <= 2 => {
                 self.settings_cache.organize_by = match s {
                     1 => "monthly",
@@ -294,14 +478,62 @@ impl App {
             (1, 4) => self.settings_cache.keep_original_structure = !self.settings_cache.keep_original_structure,
             (1, 5) => self.settings_cache.rename_duplicates = !self.settings_cache.rename_duplicates,
             (1, 6) => self.settings_cache.lowercase_extensions = !self.settings_cache.lowercase_extensions,
+            (1, 7) => {
+                self.settings_cache.conflict_policy = Some(self.settings_cache.effective_conflict_policy().next());
+            }
+            (1, 8) => self.settings_cache.non_media_policy = self.settings_cache.non_media_policy.next(),
+            (1, 10) => self.settings_cache.separate_screenshots = !self.settings_cache.separate_screenshots,
+            (1, 11) => {
+                self.settings_cache.previously_organized = self.settings_cache.previously_organized.next();
+            }
+            (1, 12) => self.settings_cache.timezone_policy = self.settings_cache.timezone_policy.next(),
+            (1, 13) => self.settings_cache.manifest = self.settings_cache.manifest.next(),
+            (1, 14) => self.settings_cache.checksum_sidecars = self.settings_cache.checksum_sidecars.next(),
+            (1, 15) => self.settings_cache.skip_rules.min_size_kb = self.settings_cache.skip_rules.next_min_size_kb(),
+            (1, 16) => {
+                let skip_rules = &mut self.settings_cache.skip_rules;
+                (skip_rules.min_width, skip_rules.min_height) = skip_rules.next_min_resolution();
+            }
+            (1, 17) => self.settings_cache.skip_rules.min_age_days = self.settings_cache.skip_rules.next_min_age_days(),
             (2, 2) => self.settings_cache.enable_cache = !self.settings_cache.enable_cache,
             (2, 3) => self.settings_cache.parallel_processing = !self.settings_cache.parallel_processing,
             (2, 4) => self.settings_cache.skip_hidden_files = !self.settings_cache.skip_hidden_files,
             (2, 5) => self.settings_cache.optimize_for_ssd = !self.settings_cache.optimize_for_ssd,
+            (2, 6) => self.settings_cache.follow_symlinks = !self.settings_cache.follow_symlinks,
+            (2, 7) => self.settings_cache.memory_budget_mb = self.settings_cache.next_memory_budget_mb(),
+            (2, 8) => self.settings_cache.hash_algorithm = self.settings_cache.hash_algorithm.next(),
+            (2, 9) => self.settings_cache.check_file_headers = !self.settings_cache.check_file_headers,
+            (2, 10) => self.settings_cache.extension_mismatch = self.settings_cache.extension_mismatch.next(),
+            (2, 11) => self.settings_cache.inspect_archives = !self.settings_cache.inspect_archives,
+            (2, 12) => self.settings_cache.concurrency = self.settings_cache.concurrency.next(),
+            (2, 13) => self.settings_cache.throttle.max_mb_per_sec = self.settings_cache.throttle.next_mb_per_sec(),
+            (2, 14) => self.settings_cache.throttle.max_iops = self.settings_cache.throttle.next_iops(),
+            (3, s) => {
+                if let Some(mapping) = self.settings_cache.type_mappings.values_mut().nth(s) {
+                    *mapping = mapping.next();
+                }
+            }
+            (4, s) => {
+                if let Some(rule) = self.settings_cache.folder_rules.get_mut(s) {
+                    rule.enabled = !rule.enabled;
+                }
+            }
+            (5, s) => {
+                if let Some(rule) = self.settings_cache.routing_rules.get_mut(s) {
+                    rule.enabled = !rule.enabled;
+                }
+            }
             _ => {}
         }
     }
 
+    /// Replaces the settings being edited with the defaults, without saving them
+    pub(crate) fn reset_settings(&mut self) {
+        self.settings_cache = Settings::default();
+        self.update_folder_overlap();
+        self.success_message = Some("Settings reset to defaults (not saved)".to_string());
+    }
+
     /// Saves the current settings cache to the configuration file.
     ///
     /// # Errors
@@ -309,11 +541,29 @@ impl App {
     /// Returns an error if the settings file cannot be written to disk,
     /// typically due to file system permissions or I/O issues.
     pub async fn save_settings(&mut self) -> Result<()> {
+        self.settings_diagnostics = self.settings_cache.validate();
+        let errors = self
+            .settings_diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.level == DiagnosticLevel::Error)
+            .count();
+        if errors > 0 {
+            self.error_message = Some(format!("Settings not saved: fix the {errors} problem(s) listed below"));
+            return Ok(());
+        }
+
         let mut settings = self.settings.write().await;
         *settings = self.settings_cache.clone();
         settings.save()?;
+        if let Some(watcher) = self.settings_watcher.as_mut() {
+            watcher.acknowledge();
+        }
+        self.scheduler.set_schedule(settings.schedule, chrono::Local::now());
         drop(settings);
-        self.success_message = Some("Settings saved successfully!".to_string());
+        self.success_message = Some(match self.settings_diagnostics.len() {
+            0 => "Settings saved successfully!".to_string(),
+            warnings => format!("Settings saved with {warnings} warning(s)"),
+        });
         Ok(())
     }
 
@@ -323,6 +573,7 @@ impl App {
     ///
     /// Returns an error if the settings cannot be updated, typically due to
     /// invalid input values or file system issues when updating the configuration.
+    #[allow(clippy::too_many_lines)]
     pub async fn apply_edited_value(&mut self, field: EditingField) -> Result<()> {
         let mut settings = self.settings.write().await;
 
@@ -375,6 +626,62 @@ impl App {
                     return Ok(());
                 }
             }
+            EditingField::RenameTemplate => {
+                if self.input_buffer.trim().is_empty() {
+                    settings.rename_template = None;
+                } else {
+                    match self.input_buffer.parse::<RenameTemplate>() {
+                        Ok(template) => settings.rename_template = Some(template),
+                        Err(e) => {
+                            self.error_message = Some(e);
+                            return Ok(());
+                        }
+                    }
+                }
+            }
+            EditingField::TypeMapping => {
+                let Some((extension, mapping)) = self.input_buffer.split_once(['=', ':']) else {
+                    self.error_message = Some("Enter a mapping as extension=type, e.g. insv=video".to_string());
+                    return Ok(());
+                };
+                let mapping = match mapping.parse::<TypeMapping>() {
+                    Ok(mapping) => mapping,
+                    Err(e) => {
+                        self.error_message = Some(e);
+                        return Ok(());
+                    }
+                };
+                // Editing an existing row replaces it, even when its extension changes
+                let previous = settings.type_mappings.keys().nth(self.selected_setting).cloned();
+                let removed = previous.and_then(|previous| settings.type_mappings.remove_entry(&previous));
+                if let Err(e) = settings.set_type_mapping(extension, mapping) {
+                    if let Some((previous, mapping)) = removed {
+                        settings.type_mappings.insert(previous, mapping);
+                    }
+                    self.error_message = Some(e);
+                    return Ok(());
+                }
+            }
+            EditingField::FolderRule => match self.input_buffer.parse::<FolderRule>() {
+                Ok(rule) => settings.set_folder_rule(self.selected_setting, rule),
+                Err(e) => {
+                    self.error_message = Some(e);
+                    return Ok(());
+                }
+            },
+            EditingField::RoutingRule => match self.input_buffer.parse::<RoutingRule>() {
+                Ok(rule) => settings.set_routing_rule(self.selected_setting, rule),
+                Err(e) => {
+                    self.error_message = Some(e);
+                    return Ok(());
+                }
+            },
+            EditingField::RoutingSample => {
+                self.routing_sample = self.input_buffer.trim().to_string();
+                return Ok(());
+            }
+            // Only chosen through the folder picker, never typed
+            EditingField::DuplicateFolder => return Ok(()),
         }
 
         drop(settings);
@@ -393,82 +700,162 @@ impl App {
     /// with file system operations during navigation.
     #[allow(clippy::cognitive_complexity)]
     pub async fn handle_dashboard_keys(&mut self, key: KeyEvent) -> Result<()> {
-        if self.selected_tab == 1 {
+        if self.selected_tab == FILES_TAB {
+            if self.handle_grouped_keys(key).await? {
+                return Ok(());
+            }
             match key.code {
                 KeyCode::Up => self.move_selection_up(),
                 KeyCode::Down => self.move_selection_down(),
                 KeyCode::PageUp => self.page_up(),
                 KeyCode::PageDown => self.page_down(),
-                KeyCode::Home => {
-                    self.selected_file_index = 0;
-                    self.scroll_offset = 0;
-                }
-                KeyCode::End => {
-                    let file_count = self.cached_files.len();
-                    if file_count > 0 {
-                        self.selected_file_index = file_count - 1;
-                        if self.selected_file_index >= 20 {
-                            self.scroll_offset = self.selected_file_index - 19;
-                        }
-                    }
-                }
-                KeyCode::Enter => {
-                    if !self.cached_files.is_empty() && self.selected_file_index < self.cached_files.len() {
-                        let needs_metadata = self
-                            .cached_files
-                            .get(self.selected_file_index)
-                            .is_some_and(|f| f.file_type == FileType::Image && f.metadata.is_none());
-
-                        if needs_metadata {
-                            self.success_message = Some("Loading image metadata...".to_string());
-
-                            let path = self.cached_files.get(self.selected_file_index).map(|f| f.path.clone());
-
-                            if let Some(path) = path {
-                                match self.load_image_metadata(&path).await {
-                                    Ok(metadata) => {
-                                        if let Some(file) = self.cached_files.get_mut(self.selected_file_index) {
-                                            // Replace the Arc with a new Arc containing the updated MediaFile
-                                            let mut updated_file = (**file).clone();
-                                            updated_file.metadata = Some(metadata);
-                                            *file = std::sync::Arc::new(updated_file);
-                                        }
-                                        self.success_message = None;
-                                    }
-                                    Err(e) => {
-                                        tracing::warn!("Failed to load metadata for {}: {}", path.display(), e);
-                                        self.error_message = Some(format!("Metadata unavailable: {e}"));
-                                    }
-                                }
-                            }
-                        }
+                KeyCode::Home => self.select_row(0),
+                KeyCode::End => self.select_last(),
+                KeyCode::Char(' ') => self.toggle_file_mark(),
+                KeyCode::Char('V') => self.mark_file_range().await?,
+                KeyCode::Char('A') => self.mark_all_files().await?,
+                KeyCode::Char('b') => self.open_bulk_actions().await?,
+                KeyCode::Char('R') => self.open_batch_rename().await?,
+                KeyCode::Enter => self.open_file_details().await?,
+                KeyCode::Char('x') => self.open_externally(self.selected_file_index, false),
+                KeyCode::Char('X') => self.open_externally(self.selected_file_index, true),
+                KeyCode::Char('y') => self.copy_file_path(self.selected_file_index, false),
+                KeyCode::Char('Y') => self.copy_file_path(self.selected_file_index, true),
+                KeyCode::Char('s') => self.cycle_file_sort().await?,
+                KeyCode::Char('S') => self.reverse_file_sort().await?,
+                KeyCode::Char('g') => self.cycle_file_grouping().await?,
+                _ => {}
+            }
+        } else if self.selected_tab == 4 {
+            self.handle_audio_tab_keys(key);
+        }
+        Ok(())
+    }
 
-                        if self.success_message == Some("Loading image metadata...".to_string()) {
-                            self.success_message = None;
-                        }
+    /// Opens the details of the selected file, loading image metadata first if it is missing.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a paged file list cannot store the loaded metadata.
+    pub(crate) async fn open_file_details(&mut self) -> Result<()> {
+        let Some(file) = self.cached_files.get(self.selected_file_index) else {
+            return Ok(());
+        };
 
-                        self.state = AppState::FileDetails(self.selected_file_index);
+        // Scans only read the EXIF data, so the image header is read the first time it is shown,
+        // usually already by the prefetch of the visible rows
+        let decoded = matches!(&file.metadata, Some(MediaMetadata::Image(image)) if image.has_dimensions());
+        if file.file_type == FileType::Image && !decoded {
+            let file = Arc::clone(file);
+            match self.metadata.load(&file).await {
+                Ok(metadata) => {
+                    if let Some(file) = self.cached_files.get(self.selected_file_index) {
+                        // Replace the Arc with a new Arc containing the updated MediaFile
+                        let mut updated_file = (**file).clone();
+                        updated_file.metadata = Some(metadata);
+                        self.cached_files
+                            .replace(self.selected_file_index, Arc::new(updated_file))
+                            .await?;
                     }
                 }
-                _ => {}
+                Err(e) => {
+                    tracing::warn!("Failed to load metadata for {}: {}", file.path.display(), e);
+                    self.error_message = Some(format!("Metadata unavailable: {e}"));
+                }
             }
         }
+
+        self.state = AppState::FileDetails(self.selected_file_index);
         Ok(())
     }
 
+    /// Opens the file at `index` with its default application, or shows it in the file
+    /// manager when `reveal` is set, reporting the outcome in the status bar.
+    fn open_externally(&mut self, index: usize, reveal: bool) {
+        let Some(file) = self.cached_files.get(index) else {
+            return;
+        };
+        let result = if reveal {
+            reveal_in_file_manager(&file.path)
+        } else {
+            open_with_default_app(&file.path)
+        };
+        match result {
+            Ok(()) if reveal => self.success_message = Some(format!("Showing {} in file manager", file.name)),
+            Ok(()) => self.success_message = Some(format!("Opened {}", file.name)),
+            Err(e) => {
+                tracing::warn!("Failed to open {}: {}", file.path.display(), e);
+                self.error_message = Some(format!("Could not open {}: {e}", file.name));
+            }
+        }
+    }
+
+    /// Shows the current log file in the file manager, with its location in the status bar
+    pub(crate) fn reveal_log_file(&mut self) {
+        let Some(path) = log_file_path() else {
+            self.error_message = Some("Could not find the log folder".to_string());
+            return;
+        };
+        match reveal_in_file_manager(&path) {
+            Ok(()) => self.success_message = Some(format!("Log file: {}", path.display())),
+            Err(e) => {
+                tracing::warn!("Failed to show log file {}: {}", path.display(), e);
+                self.error_message = Some(format!(
+                    "Log file: {} (could not open file manager: {e})",
+                    path.display()
+                ));
+            }
+        }
+    }
+
+    /// Copies the full path of the file at `index`, or its folder when `directory` is set,
+    /// to the clipboard.
+    fn copy_file_path(&mut self, index: usize, directory: bool) {
+        if let Some(file) = self.cached_files.get(index) {
+            self.copy_path(&file.path.clone(), directory);
+        }
+    }
+
+    /// Copies `path`, or its folder when `directory` is set, to the clipboard and reports
+    /// the outcome in the status bar.
+    pub(crate) fn copy_path(&mut self, path: &Path, directory: bool) {
+        let path = if directory { path.parent().unwrap_or(path) } else { path };
+        let text = path.to_string_lossy();
+        match copy_to_clipboard(&text) {
+            Ok(ClipboardMethod::System) => self.success_message = Some(format!("Copied {text}")),
+            Ok(ClipboardMethod::Terminal) => {
+                self.success_message = Some(format!("Sent {text} to the terminal clipboard"));
+            }
+            Err(e) => {
+                tracing::warn!("Failed to copy {}: {}", text, e);
+                self.error_message = Some(format!("Clipboard unavailable: {e}"));
+            }
+        }
+    }
+
     /// Handles the undo operation
     ///
     /// # Errors
     ///
     /// Returns an error if the undo operation fails
     pub async fn handle_undo(&mut self) -> Result<()> {
-        if let Some(message) = self.organizer.undo_manager().undo().await? {
-            self.last_undo_result = Some(format!("✓ {message}"));
-            self.success_message = Some(format!("Undo successful: {message}"));
-            self.success_message = Some(format!("✓ Undone: {message}"));
-        } else {
-            self.success_message = Some("Nothing to undo".to_string());
-            self.error_message = Some("Nothing to undo".to_string());
+        if !self.ensure_writable() {
+            return Ok(());
+        }
+        match self.engine.undo().await {
+            Ok(Some(message)) => {
+                self.last_undo_result = Some(format!("✓ {message}"));
+                self.success_message = Some(format!("✓ Undone: {message}"));
+                self.log_activity(ActivityKind::Undo, message);
+            }
+            Ok(None) => {
+                self.success_message = Some("Nothing to undo".to_string());
+                self.error_message = Some("Nothing to undo".to_string());
+            }
+            Err(e) => {
+                self.error_message = Some(format!("Undo failed: {e}"));
+                self.log_activity(ActivityKind::Error, format!("Undo failed: {e}"));
+            }
         }
         Ok(())
     }
@@ -479,13 +866,23 @@ impl App {
     ///
     /// Returns an error if the redo operation fails
     pub async fn handle_redo(&mut self) -> Result<()> {
-        if let Some(message) = self.organizer.undo_manager().redo().await? {
-            self.last_undo_result = Some(format!("↻ {message}"));
-            self.success_message = Some(format!("Redo successful: {message}"));
-            self.success_message = Some(format!("↻ Redone: {message}"));
-        } else {
-            self.success_message = Some("Nothing to redo".to_string());
-            self.error_message = Some("Nothing to redo".to_string());
+        if !self.ensure_writable() {
+            return Ok(());
+        }
+        match self.engine.redo().await {
+            Ok(Some(message)) => {
+                self.last_undo_result = Some(format!("↻ {message}"));
+                self.success_message = Some(format!("↻ Redone: {message}"));
+                self.log_activity(ActivityKind::Redo, message);
+            }
+            Ok(None) => {
+                self.success_message = Some("Nothing to redo".to_string());
+                self.error_message = Some("Nothing to redo".to_string());
+            }
+            Err(e) => {
+                self.error_message = Some(format!("Redo failed: {e}"));
+                self.log_activity(ActivityKind::Error, format!("Redo failed: {e}"));
+            }
         }
         Ok(())
     }
@@ -1,16 +1,58 @@
 mod actions;
+mod activity;
+mod archives;
+mod audio;
+mod batch_rename;
+mod bulk_actions;
+mod cloud_upload;
+mod collections;
+mod command_palette;
+mod compare;
+mod conflicts;
+mod date_fix;
+mod device_import;
+mod dialog;
 mod duplicates;
+mod empty_folders;
+mod error_center;
+mod file_browser;
+mod file_groups;
+mod file_sort;
 mod filters;
+mod folder_picker;
 mod handlers;
+mod library_search;
+mod log_viewer;
+mod manifest_restore;
+mod metadata_stats;
+mod mouse;
 mod navigation;
+mod notifications;
+mod onboarding;
+mod organize_report;
+mod path_input;
+mod performance;
+mod preflight;
+mod profiles;
+mod quarantine;
+mod quit;
+mod rclone;
+mod schedule;
+mod settings_reload;
+mod space_explorer;
 pub mod state;
+mod tags;
+mod toasts;
+mod trends;
+mod verify;
 
 pub use state::App;
+pub use trends::{IMPORT_HISTORY_ROWS, TREND_POINTS};
 
 use color_eyre::eyre::Result;
-use crossterm::event::KeyEvent;
+use crossterm::event::{KeyEvent, MouseEvent};
 use tracing::info;
-use visualvault_models::AppState;
+use visualvault_lib::internals::models::AppState;
 
 impl App {
     /// Creates a new App instance with default settings and components.
@@ -36,24 +78,108 @@ impl App {
     /// Returns an error if the key handling operation fails, such as when
     /// updating settings, performing file operations, or state transitions.
     pub async fn on_key(&mut self, key: KeyEvent) -> Result<()> {
+        let result = self.dispatch_key(key).await;
+        self.collect_toasts();
+        result
+    }
+
+    async fn dispatch_key(&mut self, key: KeyEvent) -> Result<()> {
         self.clear_messages();
 
+        // The setup wizard takes every key until it is finished or skipped
+        if self.onboarding.is_some() {
+            return self.handle_onboarding_keys(key).await;
+        }
+
+        // A dialog takes every key until it is answered
+        if self.dialog.is_some() {
+            self.handle_dialog_keys(key).await?;
+            return self.load_visible_files().await;
+        }
+
+        // The command palette, log viewer and help overlay every view
+        if self.command_palette.is_some() {
+            self.handle_command_palette_keys(key).await?;
+            return self.load_visible_files().await;
+        }
+        if command_palette::is_command_palette_toggle(key) {
+            self.open_command_palette();
+            return Ok(());
+        }
+        if self.log_viewer.is_some() {
+            self.handle_log_viewer_keys(key);
+            return Ok(());
+        }
+        if log_viewer::is_log_viewer_toggle(key) {
+            self.open_log_viewer();
+            return Ok(());
+        }
+        if self.show_help {
+            self.handle_help_keys(key);
+            return Ok(());
+        }
+
         match self.state {
-            AppState::Search => {
-                self.handle_search_keys(key);
-                Ok(())
-            }
-            AppState::Filters => {
-                self.handle_filter_keys(key);
-                Ok(())
-            }
-            AppState::FileDetails(_) => {
-                self.handle_file_details_keys(key);
-                Ok(())
-            }
-            AppState::DuplicateReview => self.handle_duplicate_keys(key).await,
-            _ => self.handle_global_keys(key).await,
+            AppState::Search => self.handle_search_keys(key).await?,
+            AppState::Filters => self.handle_filter_keys(key).await?,
+            AppState::FileDetails(_) => self.handle_file_details_keys(key).await?,
+            AppState::DuplicateReview => self.handle_duplicate_keys(key).await?,
+            AppState::ActivityLog => self.handle_activity_log_keys(key),
+            AppState::EmptyFolders => self.handle_empty_folder_keys(key).await?,
+            AppState::Quarantine => self.handle_quarantine_keys(key).await?,
+            AppState::DateFix => self.handle_date_fix_keys(key).await?,
+            AppState::ManifestRestore => self.handle_manifest_restore_keys(key).await?,
+            AppState::Archives => self.handle_archive_keys(key).await?,
+            AppState::Verify => self.handle_verify_keys(key).await?,
+            AppState::CloudUpload => self.handle_cloud_upload_keys(key).await,
+            AppState::Collections => self.handle_collection_keys(key).await?,
+            AppState::FileBrowser => self.handle_file_browser_keys(key).await?,
+            AppState::OrganizeReport => self.handle_organize_report_keys(key),
+            AppState::ErrorCenter => self.handle_error_center_keys(key),
+            AppState::Performance => self.handle_performance_keys(key),
+            AppState::SpaceExplorer => self.handle_space_explorer_keys(key),
+            AppState::Notifications => self.handle_notification_keys(key),
+            _ => self.handle_global_keys(key).await?,
         }
+
+        self.load_visible_files().await
+    }
+
+    /// Handles mouse clicks and wheel scrolling reported by the terminal.
+    ///
+    /// # Errors
+    /// Returns an error if a double-clicked file cannot be opened or a paged file list
+    /// cannot load the rows scrolled into view.
+    pub async fn on_mouse(&mut self, mouse: MouseEvent) -> Result<()> {
+        self.handle_mouse(mouse).await?;
+        self.load_visible_files().await?;
+        self.collect_toasts();
+        Ok(())
+    }
+
+    /// Keeps the rows the file list shows loaded when it is paged from disk, and queues the
+    /// metadata of the images among them
+    async fn load_visible_files(&mut self) -> Result<()> {
+        self.refresh_file_groups().await?;
+        if self.file_groups.is_some() {
+            return self.load_grouped_rows().await;
+        }
+        let offset = self
+            .file_viewport
+            .offset(self.selected_file_index, self.cached_files.len());
+        self.cached_files.ensure_loaded(offset).await?;
+
+        if self.state == AppState::Dashboard && self.selected_tab == 1 {
+            let visible = self
+                .file_viewport
+                .visible(self.selected_file_index, self.cached_files.len());
+            self.metadata.prefetch(
+                visible
+                    .filter_map(|index| self.cached_files.get(index))
+                    .map(AsRef::as_ref),
+            );
+        }
+        Ok(())
     }
 
     /// Handles periodic updates and state transitions.
@@ -64,8 +190,25 @@ impl App {
         self.update_progress().await?;
         self.update_folder_stats_if_needed();
         self.check_scan_completion().await?;
-        self.check_folder_stats_completion().await;
+        self.refresh_file_groups().await?;
+        self.refresh_filter_matches().await?;
+        self.check_folder_stats_completion();
+        self.check_verify_completion().await;
+        self.check_cloud_upload_completion().await;
+        self.check_organize_completion().await?;
+        self.check_rclone_completion().await;
+        self.update_metadata_stats_if_needed().await?;
+        self.check_metadata_stats_completion().await;
+        self.check_space_explorer_completion().await;
+        self.check_destination_index_completion().await?;
         self.check_operation_completion().await?;
+        self.run_next_startup_action().await?;
+        self.check_scheduled_run().await?;
+        self.check_settings_file().await?;
+        self.load_stats_history_if_needed().await;
+        self.refresh_log_viewer();
+        self.check_pending_quit();
+        self.collect_toasts();
         Ok(())
     }
 }
@@ -0,0 +1,192 @@
+use color_eyre::eyre::{Result, eyre};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+use visualvault_lib::internals::core::{DestinationIndex, PAGED_SEARCH_LIMIT};
+use visualvault_lib::internals::models::{ActivityKind, AppState, FileOutcome, MediaFile};
+use visualvault_lib::internals::utils::{Progress, create_cache_path};
+
+use super::App;
+
+/// File of the destination index in the cache directory
+const DESTINATION_INDEX_FILE: &str = "destination.db";
+
+impl App {
+    /// Opens the destination index the first time it is needed
+    async fn open_destination_index(&mut self) -> Result<DestinationIndex> {
+        if let Some(index) = &self.destination_index {
+            return Ok(index.clone());
+        }
+        let path = create_cache_path("visualvault", DESTINATION_INDEX_FILE).await?;
+        let index = DestinationIndex::open(&path).await?;
+        self.destination_index_info = index.info().await?;
+        self.destination_index = Some(index.clone());
+        Ok(index)
+    }
+
+    /// Whether the destination index was built for `destination`
+    fn destination_indexed(&self, destination: &Path) -> bool {
+        self.destination_index_info
+            .as_ref()
+            .is_some_and(|info| info.root == destination)
+    }
+
+    /// Switches the search view to the next scope and searches again.
+    ///
+    /// The destination is indexed in the background the first time it is searched, or after
+    /// the destination folder changed.
+    ///
+    /// # Errors
+    /// Returns an error if the destination index cannot be opened or searched.
+    pub async fn cycle_search_scope(&mut self) -> Result<()> {
+        self.search_scope = self.search_scope.next();
+        if self.search_scope.includes_destination() {
+            self.open_destination_index().await?;
+            let destination = self.settings.read().await.destination_folder.clone();
+            if destination.is_some_and(|destination| !self.destination_indexed(&destination)) {
+                self.start_destination_indexing().await?;
+            }
+        }
+        self.perform_search().await
+    }
+
+    /// Rebuilds the destination index by scanning the destination folder in the background.
+    ///
+    /// # Errors
+    /// Returns an error if the destination index cannot be opened.
+    pub async fn start_destination_indexing(&mut self) -> Result<()> {
+        if self.destination_index_task.is_some() {
+            return Ok(());
+        }
+        let settings = self.settings.read().await.clone();
+        let Some(destination) = settings.destination_folder.clone() else {
+            self.error_message = Some("No destination folder configured".to_string());
+            return Ok(());
+        };
+        if !destination.is_dir() {
+            self.error_message = Some(format!("Destination folder not found: {}", destination.display()));
+            return Ok(());
+        }
+
+        let index = self.open_destination_index().await?;
+        self.success_message = Some(format!("Indexing {}...", destination.display()));
+        let (engine, scanner) = (Arc::clone(&self.engine), Arc::clone(&self.scanner));
+        self.destination_index_task = Some(tokio::spawn(async move {
+            let progress = Arc::new(RwLock::new(Progress::new()));
+            let files = engine.scan_folder(&destination, &settings, progress).await?;
+            let unreadable = scanner.take_failures();
+            if !unreadable.is_empty() {
+                warn!("{} library files could not be indexed", unreadable.len());
+            }
+            index.rebuild(&destination, &files).await?;
+            index
+                .info()
+                .await?
+                .ok_or_else(|| eyre!("The destination index was not written"))
+        }));
+        Ok(())
+    }
+
+    /// Takes the result of a finished destination indexing task.
+    ///
+    /// # Errors
+    /// Returns an error if the search cannot be repeated with the new index.
+    pub async fn check_destination_index_completion(&mut self) -> Result<()> {
+        if !self
+            .destination_index_task
+            .as_ref()
+            .is_some_and(tokio::task::JoinHandle::is_finished)
+        {
+            return Ok(());
+        }
+        let Some(task) = self.destination_index_task.take() else {
+            return Ok(());
+        };
+
+        let result = task.await.map_err(|e| eyre!("Indexing task failed: {}", e));
+        match result.and_then(|result| result) {
+            Ok(info) => {
+                let message = format!("Indexed {} files in {}", info.files, info.root.display());
+                info!("{}", message);
+                self.log_activity(ActivityKind::Scan, message.clone());
+                self.success_message = Some(message);
+                self.destination_index_info = Some(info);
+                if self.state == AppState::Search {
+                    self.perform_search().await?;
+                }
+            }
+            Err(e) => {
+                self.error_message = Some(format!("Failed to index the destination: {e}"));
+                self.record_operation_error(format!("Failed to index the destination: {e}"));
+            }
+        }
+        Ok(())
+    }
+
+    /// Searches the destination index, or returns nothing if it does not cover the current
+    /// destination folder yet.
+    pub(crate) async fn search_destination(&mut self, term: &str) -> Result<Vec<Arc<MediaFile>>> {
+        let Some(destination) = self.settings_cache.destination_folder.clone() else {
+            return Ok(Vec::new());
+        };
+        if !self.destination_indexed(&destination) {
+            return Ok(Vec::new());
+        }
+        self.open_destination_index()
+            .await?
+            .search(term, PAGED_SEARCH_LIMIT)
+            .await
+    }
+
+    /// Adds the files an organize run placed in the library to the destination index, so they
+    /// can be found without indexing the library again.
+    pub(crate) async fn index_organized_files(&mut self, organized: &[Arc<MediaFile>]) {
+        let index = match self.open_destination_index().await {
+            Ok(index) => index,
+            Err(e) => {
+                warn!("Cannot open the destination index: {}", e);
+                return;
+            }
+        };
+        let Some(result) = self.last_organize_result.as_ref().filter(|result| !result.rolled_back) else {
+            return;
+        };
+        // An index of another library is rebuilt the next time the destination is searched
+        if !self.destination_indexed(&result.destination) {
+            return;
+        }
+
+        let by_source: HashMap<_, _> = organized.iter().map(|file| (file.path.as_path(), file)).collect();
+        let placed: Vec<MediaFile> = result
+            .files
+            .iter()
+            .filter(|file| {
+                matches!(
+                    file.outcome,
+                    FileOutcome::Moved | FileOutcome::Renamed | FileOutcome::Overwritten
+                )
+            })
+            .filter_map(|file| {
+                let destination = file.destination.as_ref()?;
+                let mut placed = MediaFile::clone(by_source.get(file.source.as_path())?);
+                placed.name = destination.file_name()?.to_string_lossy().into();
+                placed.path.clone_from(destination);
+                Some(placed)
+            })
+            .collect();
+        if placed.is_empty() {
+            return;
+        }
+
+        if let Err(e) = index.add(&placed).await {
+            warn!("Failed to add organized files to the destination index: {}", e);
+            return;
+        }
+        match index.info().await {
+            Ok(info) => self.destination_index_info = info,
+            Err(e) => warn!("Failed to read the destination index: {}", e),
+        }
+    }
+}
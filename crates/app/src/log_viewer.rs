@@ -0,0 +1,96 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use visualvault_lib::internals::models::LogViewer;
+use visualvault_lib::internals::utils::{log_file_path, read_log_tail};
+
+use super::App;
+
+/// Bytes read from the end of the log file for the log viewer
+const LOG_TAIL_BYTES: u64 = 512 * 1024;
+
+/// Whether `key` is Ctrl+L, which opens and closes the log viewer
+pub(crate) fn is_log_viewer_toggle(key: KeyEvent) -> bool {
+    key.code == KeyCode::Char('l') && key.modifiers.contains(KeyModifiers::CONTROL)
+}
+
+impl App {
+    /// Opens the overlay showing the end of the log file
+    pub fn open_log_viewer(&mut self) {
+        self.log_viewer = Some(LogViewer::default());
+        self.refresh_log_viewer();
+    }
+
+    /// Re-reads the log file while the log viewer is open and the file has changed
+    pub fn refresh_log_viewer(&mut self) {
+        let Some(viewer) = self.log_viewer.as_mut() else {
+            return;
+        };
+        let Some(path) = log_file_path() else {
+            viewer.lines = vec!["Could not find the log folder".to_string()];
+            return;
+        };
+        let len = std::fs::metadata(&path).map_or(0, |metadata| metadata.len());
+        if len == viewer.file_len && !viewer.lines.is_empty() {
+            return;
+        }
+
+        let previous = viewer.visible_lines().len();
+        match read_log_tail(&path, LOG_TAIL_BYTES) {
+            Ok(lines) => viewer.lines = lines,
+            Err(e) => viewer.lines = vec![format!("Cannot read {}: {e}", path.display())],
+        }
+        viewer.file_len = len;
+        // Keep the lines in view while scrolled up, instead of following new ones
+        if viewer.scroll > 0 {
+            let added = viewer.visible_lines().len().saturating_sub(previous);
+            viewer.scroll += added;
+        }
+    }
+
+    /// Handles keyboard input in the log viewer.
+    ///
+    /// 'l' cycles the level filter, '/' starts a search and the arrows scroll; Esc or
+    /// Ctrl+L closes the viewer.
+    pub fn handle_log_viewer_keys(&mut self, key: KeyEvent) {
+        let Some(viewer) = self.log_viewer.as_mut() else {
+            return;
+        };
+
+        if viewer.searching {
+            match key.code {
+                KeyCode::Enter | KeyCode::Esc => viewer.searching = false,
+                KeyCode::Backspace => {
+                    viewer.search.pop();
+                }
+                KeyCode::Char(c) => viewer.search.push(c),
+                _ => {}
+            }
+            viewer.scroll = 0;
+            return;
+        }
+
+        if is_log_viewer_toggle(key) {
+            self.log_viewer = None;
+            return;
+        }
+        let last = viewer.visible_lines().len().saturating_sub(1);
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                if viewer.search.is_empty() {
+                    self.log_viewer = None;
+                } else {
+                    viewer.search.clear();
+                    viewer.scroll = 0;
+                }
+            }
+            KeyCode::Char('l') => viewer.cycle_level(),
+            KeyCode::Char('/') => viewer.searching = true,
+            KeyCode::Up => viewer.scroll = (viewer.scroll + 1).min(last),
+            KeyCode::Down => viewer.scroll = viewer.scroll.saturating_sub(1),
+            KeyCode::PageUp => viewer.scroll = (viewer.scroll + 20).min(last),
+            KeyCode::PageDown => viewer.scroll = viewer.scroll.saturating_sub(20),
+            KeyCode::Home => viewer.scroll = last,
+            KeyCode::End => viewer.scroll = 0,
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use visualvault_lib::internals::core::{plan_manifest_restore, restore_from_manifest};
+use visualvault_lib::internals::models::{ActivityKind, AppState};
+
+use super::App;
+
+impl App {
+    /// Reads the manifests in the destination folder and opens the preview of moving the files
+    /// they list back to their original paths.
+    pub async fn open_manifest_restore(&mut self) {
+        let Some(destination) = self.settings.read().await.destination_folder.clone() else {
+            self.error_message = Some("Set the library folder as destination first".to_string());
+            return;
+        };
+
+        let items = plan_manifest_restore(&destination).await;
+        if items.is_empty() {
+            self.error_message = Some(format!("No manifests found in {}", destination.display()));
+            return;
+        }
+        self.manifest_restore = items;
+        self.manifest_restore_scroll = 0;
+        self.state = AppState::ManifestRestore;
+    }
+
+    /// Handles keyboard input in the manifest restore preview.
+    ///
+    /// # Errors
+    /// Returns an error if moving the files or recording the move fails.
+    pub async fn handle_manifest_restore_keys(&mut self, key: KeyEvent) -> Result<()> {
+        let last = self.manifest_restore.len().saturating_sub(1);
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                self.state = AppState::Dashboard;
+                self.manifest_restore.clear();
+            }
+            KeyCode::Up => self.manifest_restore_scroll = self.manifest_restore_scroll.saturating_sub(1),
+            KeyCode::Down => self.manifest_restore_scroll = (self.manifest_restore_scroll + 1).min(last),
+            KeyCode::PageUp => self.manifest_restore_scroll = self.manifest_restore_scroll.saturating_sub(10),
+            KeyCode::PageDown => self.manifest_restore_scroll = (self.manifest_restore_scroll + 10).min(last),
+            KeyCode::Char('m') | KeyCode::Enter => self.restore_from_manifests().await?,
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Moves every listed file that can be restored back to its original path.
+    async fn restore_from_manifests(&mut self) -> Result<()> {
+        if !self.ensure_writable() {
+            return Ok(());
+        }
+        let items = std::mem::take(&mut self.manifest_restore);
+        let restorable = items.iter().filter(|item| item.problem.is_none()).count();
+        if restorable == 0 {
+            self.manifest_restore = items;
+            self.error_message = Some("No files can be restored".to_string());
+            return Ok(());
+        }
+
+        let moved = tokio::task::spawn_blocking(move || restore_from_manifest(&items)).await?;
+        let count = moved.len();
+        if count > 0 {
+            self.organizer.undo_manager().record_batch_move(moved).await?;
+        }
+
+        let message = format!("Restored {count} of {restorable} files to their original paths from manifests");
+        self.log_activity(ActivityKind::Organize, message.clone());
+        if count < restorable {
+            self.error_message = Some(format!(
+                "{} files could not be restored, see the log",
+                restorable - count
+            ));
+        }
+        self.success_message = Some(format!("{message} (Ctrl+U to undo)"));
+        self.state = AppState::Dashboard;
+        Ok(())
+    }
+}
@@ -0,0 +1,70 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use color_eyre::eyre::Result;
+use visualvault_lib::internals::core::collect_metadata_stats;
+use visualvault_lib::internals::models::{AppState, FileType};
+
+use super::App;
+use crate::navigation::METADATA_TAB;
+
+impl App {
+    /// Starts aggregating the EXIF data of the scanned images while the Metadata tab is shown
+    /// and the file list changed since it was last aggregated.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a paged file list cannot be read.
+    pub(crate) async fn update_metadata_stats_if_needed(&mut self) -> Result<()> {
+        let shown = self.state == AppState::Dashboard && self.selected_tab == METADATA_TAB;
+        let current = self.metadata_stats.is_some() && self.metadata_stats_version == self.cached_files.version();
+        if !shown || current || self.metadata_stats_task.is_some() {
+            return Ok(());
+        }
+
+        let paths: Vec<_> = self
+            .cached_files
+            .filter(|file| file.file_type == FileType::Image)
+            .await?
+            .into_iter()
+            .map(|file| file.path.clone())
+            .collect();
+        let processed = Arc::new(AtomicUsize::new(0));
+        self.metadata_stats_version = self.cached_files.version();
+        self.metadata_stats_progress = (Arc::clone(&processed), paths.len());
+        self.metadata_stats_task = Some(tokio::task::spawn_blocking(move || {
+            collect_metadata_stats(&paths, &processed)
+        }));
+        Ok(())
+    }
+
+    /// Images whose EXIF data has been read so far and the images being read
+    #[must_use]
+    pub fn metadata_stats_progress(&self) -> Option<(usize, usize)> {
+        self.metadata_stats_task.as_ref().map(|_| {
+            let (processed, total) = &self.metadata_stats_progress;
+            (processed.load(Ordering::Relaxed), *total)
+        })
+    }
+
+    /// Collects the aggregated EXIF data once the background task finishes
+    pub(crate) async fn check_metadata_stats_completion(&mut self) {
+        if !self
+            .metadata_stats_task
+            .as_ref()
+            .is_some_and(tokio::task::JoinHandle::is_finished)
+        {
+            return;
+        }
+        let Some(task) = self.metadata_stats_task.take() else {
+            return;
+        };
+        match task.await {
+            Ok(stats) => self.metadata_stats = Some(stats),
+            Err(e) => {
+                tracing::error!("Metadata statistics task failed: {}", e);
+                self.error_message = Some(format!("Failed to read image metadata: {e}"));
+            }
+        }
+    }
+}
@@ -0,0 +1,176 @@
+use std::time::{Duration, Instant};
+
+use color_eyre::eyre::Result;
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use visualvault_lib::internals::models::{DuplicateFocus, InputMode, MouseTargets, SortColumn};
+
+use super::{App, AppState};
+
+/// Rows moved per mouse wheel notch
+const WHEEL_STEP: isize = 3;
+/// Longest time between two clicks on the same file that opens its details
+const DOUBLE_CLICK: Duration = Duration::from_millis(400);
+
+impl App {
+    /// Handles a mouse event, using the areas recorded while drawing the last frame.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the details of a double-clicked file cannot be opened.
+    pub(crate) async fn handle_mouse(&mut self, mouse: MouseEvent) -> Result<()> {
+        let delta = match mouse.kind {
+            MouseEventKind::ScrollUp => -WHEEL_STEP,
+            MouseEventKind::ScrollDown => WHEEL_STEP,
+            MouseEventKind::Down(MouseButton::Left) => 0,
+            _ => return Ok(()),
+        };
+
+        if self.show_help {
+            self.scroll_help(delta);
+            return Ok(());
+        }
+        // Overlays and text input keep the mouse away from what is underneath
+        if self.has_overlay() || self.input_mode != InputMode::Normal {
+            return Ok(());
+        }
+
+        let targets = self
+            .mouse_targets
+            .lock()
+            .map(|targets| targets.clone())
+            .unwrap_or_default();
+        if delta == 0 {
+            self.handle_click(&targets, mouse.column, mouse.row).await
+        } else {
+            self.handle_wheel(&targets, mouse.column, mouse.row, delta);
+            Ok(())
+        }
+    }
+
+    fn has_overlay(&self) -> bool {
+        self.onboarding.is_some()
+            || self.dialog.is_some()
+            || self.command_palette.is_some()
+            || self.batch_rename.is_some()
+            || self.bulk_actions.is_some()
+            || self.profile_picker.is_some()
+            || self.folder_picker.is_some()
+            || self.device_import.is_some()
+            || !self.pending_conflicts.is_empty()
+            || self.duplicate_compare.is_some()
+            || matches!(self.state, AppState::FileDetails(_))
+    }
+
+    async fn handle_click(&mut self, targets: &MouseTargets, column: u16, row: u16) -> Result<()> {
+        if let Some(tab) = targets.tab_at(column, row) {
+            self.select_tab(tab);
+            return Ok(());
+        }
+
+        if let Some(sort_column) = targets
+            .file_column_at(column, row)
+            .and_then(|index| SortColumn::ALL.get(index))
+        {
+            return self.sort_by_column(*sort_column).await;
+        }
+
+        if let Some(line) = targets.file_rows.and_then(|area| area.line_at(column, row)) {
+            let len = self.selectable_rows();
+            let index = self.file_viewport.offset(self.selected_row(), len) + line;
+            if index >= len {
+                return Ok(());
+            }
+            let double_click = self
+                .last_click
+                .is_some_and(|(at, clicked)| clicked == index && at.elapsed() <= DOUBLE_CLICK);
+            self.select_row(index);
+            self.last_click = Some((Instant::now(), index));
+            if double_click && self.state == AppState::Dashboard {
+                self.last_click = None;
+                if self.toggle_selected_group() {
+                    return self.load_grouped_rows().await;
+                }
+                return self.open_file_details().await;
+            }
+            return Ok(());
+        }
+
+        let Some(stats) = &self.duplicate_stats else {
+            return Ok(());
+        };
+        if let Some((area, first)) = targets.duplicate_groups {
+            // Each group takes two lines: its size and its first file
+            if let Some(line) = area.line_at(column, row) {
+                let group = first + line / 2;
+                if group < stats.groups.len() {
+                    self.duplicate_focus = DuplicateFocus::GroupList;
+                    self.selected_file_in_group = 0;
+                    self.select_duplicate_group(group);
+                }
+                return Ok(());
+            }
+        }
+        if let Some(line) = targets.duplicate_files.and_then(|area| area.line_at(column, row)) {
+            let file_count = stats
+                .groups
+                .get(self.selected_duplicate_group)
+                .map_or(0, |group| group.files.len());
+            if line < file_count {
+                self.duplicate_focus = DuplicateFocus::FileList;
+                self.selected_file_in_group = line;
+            }
+        }
+        Ok(())
+    }
+
+    fn select_tab(&mut self, tab: usize) {
+        match self.state {
+            AppState::Dashboard | AppState::Settings => {
+                self.selected_tab = tab;
+                self.selected_setting = 0;
+            }
+            AppState::DuplicateReview if tab != self.duplicate_tab => self.show_duplicate_tab(tab),
+            AppState::Filters => {
+                self.filter_tab = tab;
+                self.selected_filter_index = 0;
+                self.update_filter_focus();
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_wheel(&mut self, targets: &MouseTargets, column: u16, row: u16, delta: isize) {
+        match self.state {
+            AppState::Search => self.move_selection(delta),
+            AppState::Dashboard if self.selected_tab == 1 => self.move_selection(delta),
+            AppState::Dashboard if self.selected_tab == 4 => {
+                let last = self.cached_files.audio_files().len().saturating_sub(1);
+                self.audio_scroll = self.audio_scroll.saturating_add_signed(delta).min(last);
+            }
+            AppState::ActivityLog => {
+                let last = self.activity_log.len().saturating_sub(1);
+                self.activity_scroll = self.activity_scroll.saturating_add_signed(delta).min(last);
+            }
+            AppState::OrganizeReport => self.scroll_organize_report(delta),
+            AppState::DuplicateReview => {
+                let over_files = targets.duplicate_files.is_some_and(|area| area.contains(column, row));
+                let over_groups = targets
+                    .duplicate_groups
+                    .is_some_and(|(area, _)| area.contains(column, row));
+                if over_files {
+                    self.duplicate_focus = DuplicateFocus::FileList;
+                } else if over_groups {
+                    self.duplicate_focus = DuplicateFocus::GroupList;
+                }
+                for _ in 0..delta.unsigned_abs() {
+                    if delta < 0 {
+                        self.move_duplicate_selection_up();
+                    } else {
+                        self.move_duplicate_selection_down();
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
@@ -1,7 +1,16 @@
-use visualvault_models::InputMode;
+use color_eyre::eyre::Result;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use visualvault_lib::internals::core::{FileQuery, PAGED_SEARCH_LIMIT};
+use visualvault_lib::internals::models::{AppState, InputMode, ListViewport, MediaFile};
 
 use super::App;
 
+/// Dashboard tab listing the scanned files
+pub(crate) const FILES_TAB: usize = 1;
+/// Dashboard tab aggregating the EXIF data of the scanned images
+pub(crate) const METADATA_TAB: usize = 6;
+
 impl App {
     pub const fn next_tab(&mut self) {
         let max_tabs = self.get_tab_count();
@@ -19,45 +28,48 @@ impl App {
         self.selected_setting = 0;
     }
 
-    pub const fn move_selection_up(&mut self) {
-        if self.selected_file_index > 0 {
-            self.selected_file_index -= 1;
-            if self.selected_file_index < self.scroll_offset {
-                self.scroll_offset = self.selected_file_index;
-            }
+    /// Number of rows in the list the selection moves through: search results, file groups
+    /// or scanned files
+    pub(crate) fn selectable_rows(&self) -> usize {
+        match &self.file_groups {
+            _ if self.state == AppState::Search => self.search_results.len(),
+            Some(groups) => groups.row_count(),
+            None => self.cached_files.len(),
         }
     }
 
+    /// Moves the selection by `delta` rows; the viewport keeps it centered where possible.
+    pub fn move_selection(&mut self, delta: isize) {
+        self.select_row(ListViewport::step(self.selected_row(), delta, self.selectable_rows()));
+    }
+
+    pub fn move_selection_up(&mut self) {
+        self.move_selection(-1);
+    }
+
     pub fn move_selection_down(&mut self) {
-        let file_count = self.cached_files.len();
-        if self.selected_file_index < file_count.saturating_sub(1) {
-            self.selected_file_index += 1;
-            if self.selected_file_index >= self.scroll_offset + 20 {
-                self.scroll_offset = self.selected_file_index - 19;
-            }
-        }
+        self.move_selection(1);
     }
 
-    pub const fn page_up(&mut self) {
-        if self.selected_file_index >= 10 {
-            self.selected_file_index -= 10;
-        } else {
-            self.selected_file_index = 0;
-        }
-        if self.selected_file_index < self.scroll_offset {
-            self.scroll_offset = self.selected_file_index;
-        }
+    pub fn page_up(&mut self) {
+        self.move_selection(-isize::try_from(self.file_viewport.page()).unwrap_or(isize::MAX));
     }
 
     pub fn page_down(&mut self) {
-        let file_count = self.cached_files.len();
-        self.selected_file_index = std::cmp::min(self.selected_file_index + 10, file_count.saturating_sub(1));
-        if self.selected_file_index >= self.scroll_offset + 20 {
-            self.scroll_offset = self.selected_file_index.saturating_sub(19);
-        }
+        self.move_selection(isize::try_from(self.file_viewport.page()).unwrap_or(isize::MAX));
+    }
+
+    /// Selects the last row of the list
+    pub fn select_last(&mut self) {
+        self.select_row(self.selectable_rows().saturating_sub(1));
     }
 
-    pub fn handle_search_keys(&mut self, key: crossterm::event::KeyEvent) {
+    /// Handles keyboard input in the search view.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a paged file list cannot be searched.
+    pub async fn handle_search_keys(&mut self, key: crossterm::event::KeyEvent) -> Result<()> {
         use crossterm::event::KeyCode;
 
         match self.input_mode {
@@ -70,31 +82,29 @@ impl App {
                     self.search_input.clear();
                     self.search_results.clear();
                     self.selected_file_index = 0;
-                    self.scroll_offset = 0;
                 }
-                KeyCode::Up => {
-                    if !self.search_results.is_empty() && self.selected_file_index > 0 {
-                        self.selected_file_index -= 1;
-                        if self.selected_file_index < self.scroll_offset {
-                            self.scroll_offset = self.selected_file_index;
-                        }
+                KeyCode::Up => self.move_selection_up(),
+                KeyCode::Down => self.move_selection_down(),
+                KeyCode::PageUp => self.page_up(),
+                KeyCode::PageDown => self.page_down(),
+                KeyCode::Tab => self.cycle_search_scope().await?,
+                KeyCode::Char('g') => {
+                    if let Some(file) = self.search_results.get(self.selected_file_index) {
+                        let path = file.path.clone();
+                        self.jump_to_file(&path).await?;
                     }
                 }
-                KeyCode::Down => {
-                    if !self.search_results.is_empty()
-                        && self.selected_file_index < self.search_results.len().saturating_sub(1)
-                    {
-                        self.selected_file_index += 1;
-                        if self.selected_file_index >= self.scroll_offset + 20 {
-                            self.scroll_offset = self.selected_file_index - 19;
-                        }
+                KeyCode::Char('i') => self.start_destination_indexing().await?,
+                KeyCode::Char(c @ ('y' | 'Y')) => {
+                    if let Some(file) = self.search_results.get(self.selected_file_index) {
+                        self.copy_path(&file.path.clone(), c == 'Y');
                     }
                 }
                 _ => {}
             },
             InputMode::Insert => match key.code {
                 KeyCode::Enter => {
-                    self.perform_search();
+                    self.perform_search().await?;
                     self.input_mode = InputMode::Normal;
                 }
                 KeyCode::Esc => {
@@ -102,17 +112,16 @@ impl App {
                 }
                 KeyCode::Char(c) => {
                     self.search_input.push(c);
-                    self.perform_search();
+                    self.perform_search().await?;
                 }
                 KeyCode::Backspace => {
                     self.search_input.pop();
-                    self.perform_search();
+                    self.perform_search().await?;
                 }
                 KeyCode::Delete => {
                     self.search_input.clear();
                     self.search_results.clear();
                     self.selected_file_index = 0;
-                    self.scroll_offset = 0;
                 }
                 _ => {}
             },
@@ -121,31 +130,76 @@ impl App {
                     self.input_mode = InputMode::Normal;
                 } else {
                     self.input_mode = InputMode::Insert;
-                    self.handle_search_keys(key);
+                    return Box::pin(self.handle_search_keys(key)).await;
                 }
             }
         }
+        Ok(())
     }
 
-    pub fn perform_search(&mut self) {
+    /// Searches file names and paths for the search input, ignoring case, in the files of the
+    /// last scan, the destination library or both, depending on the search scope.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a paged file list or the destination index cannot be read.
+    pub async fn perform_search(&mut self) -> Result<()> {
+        self.selected_file_index = 0;
         if self.search_input.is_empty() {
             self.search_results.clear();
-            self.selected_file_index = 0;
-            self.scroll_offset = 0;
-            return;
+            return Ok(());
         }
 
-        let search_term = self.search_input.to_lowercase();
-        self.search_results = self
-            .cached_files
-            .iter()
-            .filter(|file| {
-                file.name.to_lowercase().contains(&search_term)
-                    || file.path.to_string_lossy().to_lowercase().contains(&search_term)
-            })
-            .map(|file| (**file).clone())
+        let mut results = if self.search_scope.includes_source() {
+            self.search_source(&self.search_input).await?
+        } else {
+            Vec::new()
+        };
+        if self.search_scope.includes_destination() {
+            let term = self.search_input.clone();
+            results.extend(self.search_destination(&term).await?);
+        }
+        self.search_results = results.iter().map(|file| (**file).clone()).collect();
+        Ok(())
+    }
+
+    /// Returns the scanned files whose path, camera or lens contains `term`, asking the file
+    /// cache while it holds the same files as the list
+    async fn search_source(&self, term: &str) -> Result<Vec<Arc<MediaFile>>> {
+        let Some(scope) = self.cached_files.cache_scope() else {
+            return self.cached_files.search(term).await;
+        };
+        let query = FileQuery {
+            text: Some(term.to_string()),
+            limit: Some(PAGED_SEARCH_LIMIT),
+            ..scope.clone()
+        };
+        let paths: Vec<PathBuf> = self
+            .scanner
+            .query_files(&query)
+            .await?
+            .into_iter()
+            .map(|entry| entry.path)
             .collect();
-        self.selected_file_index = 0;
-        self.scroll_offset = 0;
+        self.cached_files.files_at(&paths).await
+    }
+
+    /// Shows the file at `path` selected in the Files tab of the dashboard, among the files
+    /// of its folder.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a paged file list cannot be read.
+    pub(crate) async fn jump_to_file(&mut self, path: &Path) -> Result<()> {
+        let Some(index) = self.cached_files.index_of(path).await? else {
+            self.error_message = Some(format!("{} is not among the scanned files", path.display()));
+            return Ok(());
+        };
+        self.state = AppState::Dashboard;
+        self.input_mode = InputMode::Normal;
+        self.selected_tab = FILES_TAB;
+        self.refresh_file_groups().await?;
+        self.reveal_file(index);
+        Ok(())
     }
 }
@@ -0,0 +1,85 @@
+use std::io::Write;
+
+use tracing::{debug, warn};
+use visualvault_lib::internals::config::Notifications;
+use visualvault_lib::internals::core::{Notifier, send_desktop_notification};
+use visualvault_lib::internals::models::{NotifiedOperation, OperationReport};
+use visualvault_lib::internals::utils::format_bytes;
+
+use super::App;
+
+impl App {
+    /// Records a focus change reported by the terminal.
+    pub const fn set_terminal_focus(&mut self, focused: bool) {
+        self.terminal_focused = Some(focused);
+    }
+
+    /// Sends `report` to the configured notification targets in the background.
+    ///
+    /// Long operations also show a desktop notification when enabled and the terminal is
+    /// not focused. Terminals that never report focus count as unfocused. Delivery failures
+    /// are logged and never interrupt the user.
+    pub(crate) async fn notify(&self, report: OperationReport) {
+        let settings = self.settings.read().await.notifications.clone();
+
+        if self.wants_desktop_notification(&settings, &report) {
+            let (title, body) = desktop_message(&report);
+            tokio::task::spawn_blocking(move || {
+                if let Err(e) = send_desktop_notification(&title, &body) {
+                    debug!("Falling back to the terminal bell: {}", e);
+                    let mut stdout = std::io::stdout();
+                    let _ = stdout.write_all(b"\x07").and_then(|()| stdout.flush());
+                }
+            });
+        }
+
+        if !settings.is_enabled() {
+            return;
+        }
+        tokio::spawn(async move {
+            let result = match Notifier::new(&settings) {
+                Ok(notifier) => notifier.send(&report).await,
+                Err(e) => Err(e),
+            };
+            if let Err(e) = result {
+                warn!("Failed to send {} notification: {}", report.operation, e);
+            }
+        });
+    }
+
+    fn wants_desktop_notification(&self, settings: &Notifications, report: &OperationReport) -> bool {
+        settings.desktop
+            && self.terminal_focused != Some(true)
+            && report.duration_ms >= settings.desktop_after_secs.saturating_mul(1000)
+    }
+}
+
+/// Title and body of the desktop notification for `report`
+fn desktop_message(report: &OperationReport) -> (String, String) {
+    let operation = match report.operation {
+        NotifiedOperation::Scan => "Scan",
+        NotifiedOperation::Organize => "Organize",
+        NotifiedOperation::DuplicateCleanup => "Duplicate cleanup",
+    };
+    let outcome = if report.success { "finished" } else { "failed" };
+
+    let mut parts: Vec<String> = report
+        .counts
+        .iter()
+        .filter(|(_, value)| **value > 0)
+        .map(|(name, value)| {
+            let label = name.replace('_', " ");
+            if name.contains("bytes") {
+                format!("{label}: {}", format_bytes(*value))
+            } else {
+                format!("{label}: {value}")
+            }
+        })
+        .collect();
+    if let Some(error) = report.errors.first() {
+        parts.push(error.clone());
+    }
+    parts.push(format!("took {}s", report.duration_ms / 1000));
+
+    (format!("VisualVault: {operation} {outcome}"), parts.join(", "))
+}
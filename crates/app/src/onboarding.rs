@@ -0,0 +1,166 @@
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use visualvault_lib::internals::config::{ConflictPolicy, OrganizationMode};
+use visualvault_lib::internals::models::{AppState, EditingField, OnboardingStep};
+
+use super::App;
+
+impl App {
+    /// Opens the setup wizard, which walks through the settings a first launch needs
+    pub fn start_onboarding(&mut self) {
+        self.show_onboarding_step(OnboardingStep::SourceFolder);
+    }
+
+    fn show_onboarding_step(&mut self, step: OnboardingStep) {
+        self.onboarding = Some(step);
+        self.path_candidates.clear();
+        let (field, folder) = match step {
+            OnboardingStep::SourceFolder => (
+                Some(EditingField::SourceFolder),
+                self.settings_cache.source_folder.as_ref(),
+            ),
+            OnboardingStep::DestinationFolder => (
+                Some(EditingField::DestinationFolder),
+                self.settings_cache.destination_folder.as_ref(),
+            ),
+            _ => (None, None),
+        };
+        self.input_buffer = folder.map(|folder| folder.display().to_string()).unwrap_or_default();
+        self.editing_field = field;
+        self.update_folder_check();
+    }
+
+    /// Handles keyboard input in the setup wizard.
+    ///
+    /// Folders are typed with Tab completing them, choices are picked with the arrows and
+    /// Enter moves on; Esc goes back a step, or skips the wizard from the first one.
+    ///
+    /// # Errors
+    /// Returns an error if the settings cannot be saved or the initial scan cannot start.
+    pub async fn handle_onboarding_keys(&mut self, key: KeyEvent) -> Result<()> {
+        let Some(step) = self.onboarding else {
+            return Ok(());
+        };
+
+        match (step, key.code) {
+            (_, KeyCode::Esc) => match step.previous() {
+                Some(previous) => self.show_onboarding_step(previous),
+                None => self.skip_onboarding(),
+            },
+            (OnboardingStep::SourceFolder | OnboardingStep::DestinationFolder, code) => {
+                self.handle_onboarding_folder_keys(step, code);
+            }
+            (OnboardingStep::OrganizeBy, KeyCode::Up | KeyCode::Down) => {
+                let current = OrganizationMode::from_str(&self.settings_cache.organize_by).unwrap_or_default();
+                let mode = step_choice(&OrganizationMode::ALL, current, key.code);
+                self.settings_cache.organize_by = mode.to_string();
+            }
+            (OnboardingStep::ConflictPolicy, KeyCode::Up | KeyCode::Down) => {
+                let current = self.settings_cache.effective_conflict_policy();
+                self.settings_cache.conflict_policy = Some(step_choice(&ConflictPolicy::ALL, current, key.code));
+            }
+            (OnboardingStep::OrganizeBy | OnboardingStep::ConflictPolicy, KeyCode::Enter) => {
+                if let Some(next) = step.next() {
+                    self.show_onboarding_step(next);
+                }
+            }
+            (OnboardingStep::InitialScan, KeyCode::Enter | KeyCode::Char('y' | 'Y')) => {
+                self.finish_onboarding(true).await?;
+            }
+            (OnboardingStep::InitialScan, KeyCode::Char('n' | 'N')) => self.finish_onboarding(false).await?,
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn handle_onboarding_folder_keys(&mut self, step: OnboardingStep, code: KeyCode) {
+        match code {
+            KeyCode::Enter => self.confirm_onboarding_folder(step),
+            KeyCode::Tab => self.complete_folder_input(),
+            KeyCode::Backspace => {
+                self.input_buffer.pop();
+                self.update_folder_check();
+            }
+            KeyCode::Char(c) => {
+                self.input_buffer.push(c);
+                self.update_folder_check();
+            }
+            _ => {}
+        }
+    }
+
+    /// Keeps the typed folder and moves on, unless the source does not exist or the path
+    /// names a file
+    fn confirm_onboarding_folder(&mut self, step: OnboardingStep) {
+        let input = self.input_buffer.trim();
+        if input.is_empty() {
+            self.error_message = Some("Type a folder, or press Esc to go back".to_string());
+            return;
+        }
+        let is_source = step == OnboardingStep::SourceFolder;
+        match &self.folder_check {
+            Some(check) if check.exists && !check.is_dir => {
+                self.error_message = Some(format!("{input} is not a folder"));
+                return;
+            }
+            Some(check) if check.exists || !is_source => {}
+            _ => {
+                self.error_message = Some(format!("{input} does not exist"));
+                return;
+            }
+        }
+
+        let folder = Some(PathBuf::from(input));
+        if is_source {
+            self.settings_cache.source_folder = folder;
+        } else {
+            self.settings_cache.destination_folder = folder;
+        }
+        self.update_folder_overlap();
+        if let Some(next) = step.next() {
+            self.show_onboarding_step(next);
+        }
+    }
+
+    /// Saves the chosen settings and closes the wizard, then scans the source if asked
+    async fn finish_onboarding(&mut self, scan: bool) -> Result<()> {
+        self.close_onboarding();
+        self.save_settings().await?;
+        if self.error_message.is_some() {
+            // The settings were not saved; show what is wrong with them
+            self.state = AppState::Settings;
+            return Ok(());
+        }
+        if scan {
+            self.request_scan().await?;
+        }
+        Ok(())
+    }
+
+    fn skip_onboarding(&mut self) {
+        self.close_onboarding();
+        self.success_message = Some("Setup skipped; set the folders in Settings (s) to start organizing".to_string());
+    }
+
+    fn close_onboarding(&mut self) {
+        self.onboarding = None;
+        self.editing_field = None;
+        self.input_buffer.clear();
+        self.folder_check = None;
+        self.path_candidates.clear();
+    }
+}
+
+/// The choice above or below `current` in `choices`, stopping at either end
+fn step_choice<T: Copy + PartialEq>(choices: &[T], current: T, code: KeyCode) -> T {
+    let index = choices.iter().position(|&choice| choice == current).unwrap_or(0);
+    let index = if code == KeyCode::Up {
+        index.saturating_sub(1)
+    } else {
+        (index + 1).min(choices.len() - 1)
+    };
+    choices[index]
+}
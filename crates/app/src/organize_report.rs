@@ -0,0 +1,70 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use visualvault_lib::internals::core::FileManager;
+use visualvault_lib::internals::models::AppState;
+
+use super::App;
+
+impl App {
+    /// Opens the per-file report of the last organize run
+    pub fn open_organize_report(&mut self) {
+        let Some(result) = self.last_organize_result.as_mut() else {
+            self.error_message = Some("No organize run to report on yet".to_string());
+            return;
+        };
+        self.organize_report_sort.sort(&mut result.files);
+        self.organize_report_scroll = 0;
+        self.state = AppState::OrganizeReport;
+    }
+
+    /// Handles keyboard input in the organize report view.
+    ///
+    /// 's' cycles the sort column and 'e' exports the report as CSV.
+    pub fn handle_organize_report_keys(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => self.state = AppState::Dashboard,
+            KeyCode::Up => self.scroll_organize_report(-1),
+            KeyCode::Down => self.scroll_organize_report(1),
+            KeyCode::PageUp => self.scroll_organize_report(-10),
+            KeyCode::PageDown => self.scroll_organize_report(10),
+            KeyCode::Home => self.organize_report_scroll = 0,
+            KeyCode::End => self.scroll_organize_report(isize::MAX),
+            KeyCode::Char('s') => {
+                self.organize_report_sort = self.organize_report_sort.next();
+                if let Some(result) = self.last_organize_result.as_mut() {
+                    self.organize_report_sort.sort(&mut result.files);
+                }
+                self.organize_report_scroll = 0;
+            }
+            KeyCode::Char('e') => self.export_organize_report(),
+            _ => {}
+        }
+    }
+
+    pub(crate) fn scroll_organize_report(&mut self, delta: isize) {
+        let last = self
+            .last_organize_result
+            .as_ref()
+            .map_or(0, |result| result.files.len().saturating_sub(1));
+        self.organize_report_scroll = self.organize_report_scroll.saturating_add_signed(delta).min(last);
+    }
+
+    pub(crate) fn export_organize_report(&mut self) {
+        let Some(result) = &self.last_organize_result else {
+            self.error_message = Some("No organize report to export".to_string());
+            return;
+        };
+        let Some(config_dir) = dirs::config_dir() else {
+            self.error_message = Some("Could not find config directory".to_string());
+            return;
+        };
+
+        let path = config_dir
+            .join("visualvault")
+            .join("reports")
+            .join(format!("organize-{}.csv", result.timestamp.format("%Y%m%d-%H%M%S")));
+        match FileManager::export_organize_report(&result.files, &path) {
+            Ok(()) => self.success_message = Some(format!("Report exported to {}", path.display())),
+            Err(e) => self.error_message = Some(format!("Failed to export report: {e}")),
+        }
+    }
+}
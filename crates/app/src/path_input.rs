@@ -0,0 +1,53 @@
+use std::path::Path;
+
+use visualvault_lib::internals::core::{check_folder, complete_path, folder_overlap};
+use visualvault_lib::internals::models::EditingField;
+
+use super::App;
+
+impl App {
+    /// Whether the text being edited is the source or destination folder
+    const fn editing_folder(&self) -> bool {
+        matches!(
+            self.editing_field,
+            Some(EditingField::SourceFolder | EditingField::DestinationFolder)
+        )
+    }
+
+    /// Re-checks the folder being typed, so the settings can show whether it exists,
+    /// is writable and how much space is free
+    pub fn update_folder_check(&mut self) {
+        self.path_candidates.clear();
+        self.folder_check = if self.editing_folder() && !self.input_buffer.is_empty() {
+            check_folder(Path::new(&self.input_buffer))
+        } else {
+            None
+        };
+    }
+
+    /// Re-checks whether the configured source and destination overlap, for the warning in Settings
+    pub fn update_folder_overlap(&mut self) {
+        self.folder_overlap = match (
+            &self.settings_cache.source_folder,
+            &self.settings_cache.destination_folder,
+        ) {
+            (Some(source), Some(destination)) => folder_overlap(source, destination),
+            _ => None,
+        };
+    }
+
+    /// Completes the last component of the folder being typed. When several folders
+    /// match they are listed under the field.
+    pub fn complete_folder_input(&mut self) {
+        if !self.editing_folder() {
+            return;
+        }
+        let Some(completion) = complete_path(&self.input_buffer) else {
+            self.path_candidates.clear();
+            return;
+        };
+        self.input_buffer = completion.completed;
+        self.update_folder_check();
+        self.path_candidates = completion.candidates;
+    }
+}
@@ -0,0 +1,69 @@
+use std::time::Duration;
+
+use chrono::Local;
+use crossterm::event::{KeyCode, KeyEvent};
+use visualvault_lib::internals::models::{AppState, NotifiedOperation, OperationProfile, StageTiming};
+use visualvault_lib::internals::utils::StageStatus;
+
+use super::App;
+
+impl App {
+    /// Opens the performance view on the newest operation
+    pub const fn open_performance(&mut self) {
+        self.performance.selected = 0;
+        self.state = AppState::Performance;
+    }
+
+    /// Handles keyboard input in the performance view.
+    ///
+    /// 'c' clears the recorded timings.
+    pub fn handle_performance_keys(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => self.state = AppState::Dashboard,
+            KeyCode::Up => self.performance.move_selection(-1),
+            KeyCode::Down => self.performance.move_selection(1),
+            KeyCode::Home => self.performance.selected = 0,
+            KeyCode::End => self.performance.move_selection(isize::MAX),
+            KeyCode::Char('c') => {
+                self.performance.clear();
+                self.success_message = Some("Cleared performance timings".to_string());
+            }
+            _ => {}
+        }
+    }
+
+    /// Records how long the stages of the operation that just finished took, along with the
+    /// settings that affect its speed
+    pub(crate) async fn record_performance(&mut self, operation: NotifiedOperation, elapsed: Duration) {
+        let stages: Vec<StageTiming> = self
+            .progress
+            .read()
+            .await
+            .stages
+            .iter()
+            .filter(|stage| stage.status == StageStatus::Done)
+            .map(|stage| StageTiming {
+                name: stage.name.to_string(),
+                items: stage.processed,
+                bytes: stage.bytes,
+                elapsed: stage.elapsed,
+            })
+            .collect();
+        if stages.is_empty() {
+            return;
+        }
+        let settings = self.settings.read().await;
+        let profile = OperationProfile {
+            operation,
+            finished_at: Local::now(),
+            elapsed,
+            worker_threads: settings.worker_threads,
+            buffer_size: settings.buffer_size,
+            parallel_processing: settings.parallel_processing,
+            hash_algorithm: settings.hash_algorithm.to_string(),
+            stages,
+        };
+        drop(settings);
+        self.performance.record(profile);
+    }
+}
@@ -0,0 +1,126 @@
+use color_eyre::eyre::Result;
+use visualvault_lib::internals::config::TimezonePolicy;
+use visualvault_lib::internals::core::{preflight_organize, preflight_scan};
+use visualvault_lib::internals::models::{
+    ActivityKind, Dialog, DialogAction, Preflight, PreflightAction, PreflightIssue, PreflightSeverity,
+};
+
+use super::App;
+
+impl App {
+    /// Scans the source folder once the preflight checks pass.
+    ///
+    /// Problems with the folders are shown in a dialog instead, where warnings can be
+    /// accepted to scan anyway.
+    ///
+    /// # Errors
+    /// Returns an error if the scan cannot be started.
+    pub async fn request_scan(&mut self) -> Result<()> {
+        let settings = self.settings.read().await;
+        let source = self.source_override.clone().or_else(|| settings.source_folder.clone());
+        let destination = settings.destination_folder.clone();
+        drop(settings);
+
+        // Without a source folder the scan itself reports what is missing
+        let Some(source) = source else {
+            return self.start_scan().await;
+        };
+        let issues = tokio::task::spawn_blocking(move || preflight_scan(&source, destination.as_deref())).await?;
+        if issues.is_empty() {
+            self.start_scan().await
+        } else {
+            self.show_preflight(PreflightAction::Scan, issues);
+            Ok(())
+        }
+    }
+
+    /// Organizes the scanned files once the preflight checks pass.
+    ///
+    /// # Errors
+    /// Returns an error if organizing fails.
+    pub async fn request_organize(&mut self) -> Result<()> {
+        let settings = self.settings.read().await;
+        let source = settings.source_folder.clone();
+        let destination = settings.local_destination();
+        let timezone_policy = settings.timezone_policy;
+        drop(settings);
+
+        // Without a destination folder organizing itself reports what is missing
+        let Some(destination) = destination else {
+            return self.start_organize().await;
+        };
+        let mut issues =
+            tokio::task::spawn_blocking(move || preflight_organize(source.as_deref(), &destination)).await?;
+        // Date folders in another time zone than usual are confirmed for every run
+        if timezone_policy != TimezonePolicy::Local {
+            issues.push(PreflightIssue::info(format!(
+                "Date folders are picked in {} (time zone policy, change it in Settings)",
+                timezone_policy.label()
+            )));
+        }
+        if issues.is_empty() {
+            self.start_organize().await
+        } else {
+            self.show_preflight(PreflightAction::Organize, issues);
+            Ok(())
+        }
+    }
+
+    fn show_preflight(&mut self, action: PreflightAction, issues: Vec<PreflightIssue>) {
+        let preflight = Preflight { action, issues };
+        let issues = preflight
+            .issues
+            .iter()
+            .map(|issue| {
+                let icon = match issue.severity {
+                    PreflightSeverity::Blocking => "❌",
+                    PreflightSeverity::Warning => "⚠",
+                    PreflightSeverity::Info => "ℹ",
+                };
+                format!("{icon} {}", issue.message)
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let verb = capitalize(&action.to_string());
+
+        let dialog = if preflight.is_blocked() {
+            self.log_activity(
+                ActivityKind::Error,
+                format!("Cannot {action}: {}", preflight.issues[0].message),
+            );
+            // Without options the dialog can only be closed
+            Dialog::choice(
+                DialogAction::Preflight(action),
+                format!("Cannot {action}"),
+                format!("{issues}\n\nFix the folders in Settings"),
+                Vec::new(),
+            )
+        } else if preflight.has_problems() {
+            Dialog::confirm(
+                DialogAction::Preflight(action),
+                format!("Check folders before you {action}"),
+                format!("{issues}\n\n{verb} anyway?"),
+            )
+        } else {
+            Dialog::confirm(
+                DialogAction::Preflight(action),
+                format!("Before you {action}"),
+                format!("{issues}\n\n{verb} now?"),
+            )
+        };
+        self.open_dialog(dialog);
+    }
+
+    /// Drops a one-off source folder, such as a camera card, once the preflight dialog is cancelled
+    pub(crate) fn cancel_preflight(&mut self, action: PreflightAction) {
+        self.source_override = None;
+        self.error_message = Some(format!("{} cancelled", capitalize(&action.to_string())));
+    }
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    chars
+        .next()
+        .map_or_else(String::new, |first| first.to_uppercase().chain(chars).collect())
+}
@@ -0,0 +1,115 @@
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use visualvault_lib::internals::config::ProfileStore;
+use visualvault_lib::internals::models::{Dialog, DialogAction, ProfilePicker};
+
+use super::App;
+
+impl App {
+    /// Opens the settings profile picker with the active profile selected.
+    pub fn open_profile_picker(&mut self) {
+        let store = match ProfileStore::open() {
+            Ok(store) => store,
+            Err(e) => {
+                self.error_message = Some(format!("Failed to open profiles: {e}"));
+                return;
+            }
+        };
+        match store.list() {
+            Ok(profiles) => self.profile_picker = Some(ProfilePicker::new(profiles, store.active())),
+            Err(e) => self.error_message = Some(format!("Failed to list profiles: {e}")),
+        }
+    }
+
+    /// Handles keyboard input while the profile picker is open.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the profile store cannot be opened.
+    pub async fn handle_profile_picker_keys(&mut self, key: KeyEvent) -> Result<()> {
+        let Some(picker) = self.profile_picker.as_mut() else {
+            return Ok(());
+        };
+
+        let last = picker.profiles.len().saturating_sub(1);
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => self.profile_picker = None,
+            KeyCode::Up | KeyCode::Char('k') => picker.selected = picker.selected.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => picker.selected = (picker.selected + 1).min(last),
+            KeyCode::Char('n') => self.open_dialog(Dialog::input(
+                DialogAction::CreateProfile,
+                "New profile",
+                "Name of the profile created from the current settings:",
+                "",
+            )),
+            KeyCode::Enter => {
+                if let Some(name) = picker.selected_profile().map(str::to_string) {
+                    self.switch_profile(&name).await?;
+                }
+            }
+            KeyCode::Char('d') => {
+                if let Some(name) = picker.selected_profile().map(str::to_string) {
+                    self.delete_profile(&name)?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Makes `name` the active profile and loads its settings.
+    ///
+    /// Settings edited but not saved are discarded.
+    async fn switch_profile(&mut self, name: &str) -> Result<()> {
+        let store = ProfileStore::open()?;
+        let settings = match store.set_active(name).and_then(|()| store.load(name)) {
+            Ok(settings) => settings,
+            Err(e) => {
+                self.error_message = Some(format!("Failed to switch profile: {e}"));
+                return Ok(());
+            }
+        };
+
+        let mut current = self.settings.write().await;
+        *current = settings;
+        self.scheduler.set_schedule(current.schedule, chrono::Local::now());
+        drop(current);
+        self.update_settings_cache().await?;
+        self.watch_active_profile();
+
+        self.profile_picker = None;
+        self.success_message = Some(format!("Switched to profile '{name}'"));
+        Ok(())
+    }
+
+    /// Creates a profile from the current settings and switches to it
+    pub(crate) async fn create_profile(&mut self, name: &str) -> Result<()> {
+        let store = ProfileStore::open()?;
+        let settings = self.settings.read().await.clone();
+        if let Err(e) = store.create(name, &settings) {
+            self.error_message = Some(e.to_string());
+            return Ok(());
+        }
+        self.switch_profile(name).await
+    }
+
+    fn delete_profile(&mut self, name: &str) -> Result<()> {
+        let store = ProfileStore::open()?;
+        if store.active() == name {
+            self.error_message = Some("Switch to another profile before deleting this one".to_string());
+            return Ok(());
+        }
+
+        match store.delete(name).and_then(|()| store.list()) {
+            Ok(profiles) => {
+                if let Some(picker) = self.profile_picker.as_mut() {
+                    picker.selected = picker.selected.min(profiles.len().saturating_sub(1));
+                    picker.profiles = profiles;
+                }
+                self.success_message = Some(format!("Deleted profile '{name}'"));
+            }
+            Err(e) => self.error_message = Some(format!("Failed to delete profile: {e}")),
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,105 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use tracing::error;
+use visualvault_lib::internals::core::FileManager;
+use visualvault_lib::internals::models::{ActivityKind, AppState};
+
+use super::App;
+
+impl App {
+    /// Opens the review of the files the last scan set aside as suspect.
+    pub fn open_quarantine(&mut self) {
+        if self.suspect_files.is_empty() {
+            self.success_message = Some("No suspect files found by the last scan".to_string());
+            return;
+        }
+        self.suspect_scroll = 0;
+        self.state = AppState::Quarantine;
+    }
+
+    /// Handles keyboard input in the quarantine review.
+    ///
+    /// # Errors
+    /// Returns an error if moving the files or recording the move fails.
+    pub async fn handle_quarantine_keys(&mut self, key: KeyEvent) -> Result<()> {
+        let last = self.suspect_files.len().saturating_sub(1);
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => self.state = AppState::Dashboard,
+            KeyCode::Up => self.suspect_scroll = self.suspect_scroll.saturating_sub(1),
+            KeyCode::Down => self.suspect_scroll = (self.suspect_scroll + 1).min(last),
+            KeyCode::PageUp => self.suspect_scroll = self.suspect_scroll.saturating_sub(10),
+            KeyCode::PageDown => self.suspect_scroll = (self.suspect_scroll + 10).min(last),
+            KeyCode::Char('m') | KeyCode::Enter => self.quarantine_suspects().await?,
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Moves every suspect file into the quarantine folder and writes a report next to them.
+    async fn quarantine_suspects(&mut self) -> Result<()> {
+        if !self.ensure_writable() {
+            return Ok(());
+        }
+        if self.suspect_files.is_empty() {
+            self.error_message = Some("No suspect files to quarantine".to_string());
+            return Ok(());
+        }
+        let Some(folder) = self.settings.read().await.quarantine_path() else {
+            self.error_message = Some("Set a destination or quarantine folder first".to_string());
+            return Ok(());
+        };
+
+        let paths: Vec<PathBuf> = self.suspect_files.iter().map(|suspect| suspect.path.clone()).collect();
+        let total = paths.len();
+        let target = folder.clone();
+        let moved = match tokio::task::spawn_blocking(move || FileManager::move_files(&paths, &target)).await? {
+            Ok(moved) => moved,
+            Err(e) => {
+                self.error_message = Some(format!("Cannot move files to {}: {e}", folder.display()));
+                return Ok(());
+            }
+        };
+
+        let destinations: HashMap<PathBuf, PathBuf> = moved
+            .iter()
+            .map(|operation| (operation.source.clone(), operation.destination.clone()))
+            .collect();
+        let (quarantined, remaining): (Vec<_>, Vec<_>) = std::mem::take(&mut self.suspect_files)
+            .into_iter()
+            .partition(|suspect| destinations.contains_key(&suspect.path));
+        self.suspect_files = remaining;
+        self.suspect_scroll = 0;
+
+        let count = moved.len();
+        if count > 0 {
+            self.organizer.undo_manager().record_batch_move(moved).await?;
+        }
+
+        let report: Vec<_> = quarantined
+            .into_iter()
+            .filter_map(|suspect| {
+                let destination = destinations.get(&suspect.path).cloned()?;
+                Some((suspect, destination))
+            })
+            .collect();
+        let report_path = folder.join(format!(
+            "quarantine-{}.csv",
+            chrono::Local::now().format("%Y%m%d-%H%M%S")
+        ));
+        if let Err(e) = FileManager::export_quarantine_report(&report, &report_path) {
+            error!("Failed to write quarantine report: {}", e);
+            self.error_message = Some(format!("Failed to write quarantine report: {e}"));
+        }
+
+        let message = format!("Quarantined {count} of {total} suspect files in {}", folder.display());
+        self.log_activity(ActivityKind::Organize, message.clone());
+        self.success_message = Some(format!("{message} (Ctrl+U to undo)"));
+        if self.suspect_files.is_empty() {
+            self.state = AppState::Dashboard;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,108 @@
+use tracing::info;
+use visualvault_lib::internals::models::{Dialog, DialogAction, PendingQuit};
+
+use super::App;
+
+impl App {
+    /// Quits, asking first while an operation is still running whether to wait for it, cancel
+    /// it or quit right away.
+    pub(crate) fn request_quit(&mut self) {
+        let Some(operation) = self.running_operation() else {
+            self.should_quit = true;
+            return;
+        };
+        let dialog = Dialog::choice(
+            DialogAction::Quit,
+            "Quit",
+            format!("{operation} is still running. Quitting right away can leave files half moved."),
+            vec![
+                "Wait for it to finish, then quit".to_string(),
+                "Cancel it, then quit".to_string(),
+                "Quit now".to_string(),
+            ],
+        )
+        .destructive();
+        self.open_dialog(dialog);
+    }
+
+    /// Handles Ctrl+C: asks like `q` does while an operation runs, and quits right away when
+    /// pressed again while the question is open.
+    pub fn interrupt(&mut self) {
+        if self
+            .dialog
+            .as_ref()
+            .is_some_and(|dialog| dialog.action == DialogAction::Quit)
+        {
+            info!("User forced quit");
+            self.should_quit = true;
+        } else {
+            self.request_quit();
+        }
+    }
+
+    /// Quits once the running operations are finished, or cancelled first.
+    pub(crate) fn quit_after(&mut self, pending: PendingQuit) {
+        let operation = self.running_operation().unwrap_or("The operation");
+        self.success_message = Some(match pending {
+            PendingQuit::AfterFinishing => format!("{operation} finishes first, then VisualVault quits"),
+            PendingQuit::AfterCancelling => format!("Cancelling: {operation} stops, then VisualVault quits"),
+        });
+        self.pending_quit = Some(pending);
+        self.check_pending_quit();
+    }
+
+    /// Quits once nothing runs in the background any more after the quit dialog chose to
+    /// wait, cancelling what still runs when it chose to cancel.
+    pub fn check_pending_quit(&mut self) {
+        let Some(pending) = self.pending_quit else {
+            return;
+        };
+        if pending == PendingQuit::AfterCancelling {
+            self.cancel_running_operations();
+        }
+        if self.running_operation().is_none() {
+            info!("Quitting after the running operations stopped");
+            self.should_quit = true;
+        }
+    }
+
+    /// Describes the operation running in the background, if any
+    const fn running_operation(&self) -> Option<&'static str> {
+        if self.organize_task.is_some() {
+            Some("Organizing")
+        } else if self.rclone_task.is_some() {
+            Some("The transfer to the rclone remote")
+        } else if self.scan_task.is_some() {
+            Some("A scan")
+        } else if self.cloud_upload_task.is_some() {
+            Some("The cloud upload")
+        } else if self.verify_task.is_some() {
+            Some("Library verification")
+        } else {
+            None
+        }
+    }
+
+    /// Stops the operations running in the background.
+    ///
+    /// Scans and organize runs stop after the file they are working on, or after the chunk
+    /// being hashed of a large one. The rclone transfer is stopped, leaving what it did not
+    /// move in the staging folder, and verification only reads files. Cloud uploads cannot
+    /// be stopped and are waited for.
+    fn cancel_running_operations(&mut self) {
+        if self.scan_task.is_some() {
+            self.scanner.cancel();
+        }
+        if self.organize_task.is_some() {
+            // Organize runs start by hashing through the scanner
+            self.scanner.cancel();
+            self.organizer.cancel();
+        }
+        if let Some(task) = self.rclone_task.take() {
+            task.abort();
+        }
+        if let Some(task) = self.verify_task.take() {
+            task.abort();
+        }
+    }
+}
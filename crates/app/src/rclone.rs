@@ -0,0 +1,90 @@
+use tracing::error;
+use visualvault_lib::internals::core::RcloneTransfer;
+use visualvault_lib::internals::models::{ActivityKind, AppState, ErrorEntry, ErrorSource};
+use visualvault_lib::internals::utils::{STAGE_TRANSFER, format_bytes};
+
+use super::App;
+
+impl App {
+    /// Starts moving the organized files from the staging folder to the rclone remote, when
+    /// the destination is one. The progress overlay stays up until the transfer finishes.
+    pub(crate) async fn start_rclone_transfer(&mut self) {
+        let settings = self.settings.read().await;
+        if settings.dry_run {
+            return;
+        }
+        let Some(transfer) = RcloneTransfer::from_settings(&settings) else {
+            return;
+        };
+        drop(settings);
+
+        self.progress.write().await.start_stage(
+            STAGE_TRANSFER,
+            0,
+            0,
+            format!("Moving files to {} with rclone...", transfer.remote()),
+        );
+        let progress = std::sync::Arc::clone(&self.progress);
+        self.rclone_task = Some(tokio::spawn(async move { transfer.run(&progress).await }));
+        self.state = AppState::Organizing;
+    }
+
+    /// Adds the outcome of a finished rclone transfer to the last organize result and shows
+    /// its report.
+    pub async fn check_rclone_completion(&mut self) {
+        if !self
+            .rclone_task
+            .as_ref()
+            .is_some_and(tokio::task::JoinHandle::is_finished)
+        {
+            return;
+        }
+        let Some(task) = self.rclone_task.take() else {
+            return;
+        };
+        self.progress.write().await.finish_stages();
+
+        let transfer = match task.await {
+            Ok(transfer) => transfer,
+            Err(e) => {
+                error!("rclone transfer task failed: {}", e);
+                self.record_operation_error(format!("rclone transfer failed: {e}"));
+                self.error_message = Some(format!("rclone transfer failed: {e}"));
+                self.state = AppState::Dashboard;
+                return;
+            }
+        };
+        let message = format!(
+            "Moved {} files ({}) to {}",
+            transfer.transferred,
+            format_bytes(transfer.bytes),
+            transfer.remote
+        );
+        self.log_activity(ActivityKind::Upload, message.clone());
+        if transfer.is_complete() {
+            self.success_message = Some(message);
+        } else {
+            self.error_center.record(
+                transfer
+                    .errors
+                    .iter()
+                    .map(|e| ErrorEntry::new(ErrorSource::Operation, None, format!("rclone: {e}"))),
+            );
+            self.error_message = Some(format!(
+                "{message}; {} errors, the rest stays in {}",
+                transfer.errors.len(),
+                transfer.staging.display()
+            ));
+        }
+
+        let has_files = self.last_organize_result.as_mut().is_some_and(|result| {
+            result.remote_transfer = Some(transfer);
+            !result.files.is_empty()
+        });
+        if has_files && self.pending_conflicts.is_empty() {
+            self.open_organize_report();
+        } else {
+            self.state = AppState::Dashboard;
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use chrono::{DateTime, Local};
+use color_eyre::eyre::Result;
+use tracing::{error, info};
+use visualvault_lib::internals::models::{ActivityKind, AppState, InputMode};
+
+use super::App;
+
+impl App {
+    /// Starts a scheduled scan and organize when one is due, and finishes it once its scan completes.
+    ///
+    /// Runs only start while the user is idle on the dashboard so they never interrupt editing.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if organizing the scanned files fails.
+    pub async fn check_scheduled_run(&mut self) -> Result<()> {
+        if let Some(files_found) = self.scheduled_organize {
+            if self.organize_task.is_none() {
+                self.scheduled_organize = None;
+                self.report_scheduled_organize(files_found);
+            }
+            return Ok(());
+        }
+
+        if let Some(started) = self.scheduled_run {
+            if self.scan_task.is_none() && self.state != AppState::Scanning {
+                self.scheduled_run = None;
+                self.finish_scheduled_run(started).await?;
+            }
+            return Ok(());
+        }
+
+        let now = Local::now();
+        if !self.scheduler.is_due(now) || !self.is_idle() {
+            return Ok(());
+        }
+
+        self.scheduler.mark_run(now);
+        self.start_scheduled_run(now).await;
+        Ok(())
+    }
+
+    fn is_idle(&self) -> bool {
+        self.state == AppState::Dashboard
+            && self.input_mode == InputMode::Normal
+            && self.scan_task.is_none()
+            && self.organize_task.is_none()
+            && self.pending_startup_actions.is_empty()
+            && self.pending_filtered_organize.is_none()
+            && self.pending_conflicts.is_empty()
+            && self.dialog.is_none()
+    }
+
+    async fn start_scheduled_run(&mut self, now: DateTime<Local>) {
+        let settings = self.settings.read().await;
+        let configured = settings.source_folder.is_some() && settings.destination_folder.is_some();
+        drop(settings);
+
+        if !configured {
+            self.log_activity(
+                ActivityKind::Error,
+                "Scheduled run skipped: source and destination folders must be configured",
+            );
+            return;
+        }
+
+        info!("Starting scheduled scan and organize");
+        self.scheduled_run = Some(now);
+        if let Err(e) = self.start_scan().await {
+            error!("Scheduled scan failed to start: {}", e);
+            self.scheduled_run = None;
+            self.log_activity(ActivityKind::Error, format!("Scheduled scan failed to start: {e}"));
+        }
+    }
+
+    async fn finish_scheduled_run(&mut self, started: DateTime<Local>) -> Result<()> {
+        let files_found = self
+            .last_scan_result
+            .as_ref()
+            .filter(|result| result.timestamp >= started)
+            .map(|result| result.files_found);
+
+        let Some(files_found) = files_found else {
+            self.log_activity(ActivityKind::Error, "Scheduled run stopped: the scan did not complete");
+            return Ok(());
+        };
+
+        let files = if self.filter_set.restricts_organize() {
+            self.cached_files.filter(|file| self.matches_filters(file)).await?
+        } else {
+            self.cached_files.to_vec().await?
+        };
+
+        if files.is_empty() {
+            self.report_scheduled_run(format!(
+                "Scheduled run: scanned {files_found} files, nothing to organize"
+            ));
+        } else {
+            self.run_organize(files).await?;
+            self.scheduled_organize = Some(files_found);
+        }
+        Ok(())
+    }
+
+    /// Reports a scheduled run once the organize run it started has finished.
+    fn report_scheduled_organize(&mut self, files_found: usize) {
+        let summary = self.last_organize_result.as_ref().map_or_else(
+            || format!("Scheduled run: scanned {files_found} files"),
+            |result| {
+                let verb = if result.cancelled {
+                    "cancelled after organizing"
+                } else {
+                    "organized"
+                };
+                let mut summary = format!(
+                    "Scheduled run: {verb} {} of {} files",
+                    result.files_organized, result.files_total
+                );
+                if result.conflicts.total() > 0 {
+                    summary = format!("{summary}; conflicts: {}", Self::describe_conflicts(&result.conflicts));
+                }
+                if !result.errors.is_empty() {
+                    summary = format!("{summary} ({} errors)", result.errors.len());
+                }
+                summary
+            },
+        );
+        self.report_scheduled_run(summary);
+    }
+
+    fn report_scheduled_run(&mut self, summary: String) {
+        info!("{}", summary);
+        self.success_message = Some(format!("⏰ {summary}"));
+        self.log_activity(ActivityKind::Scheduled, summary);
+    }
+}
@@ -0,0 +1,109 @@
+use std::time::Instant;
+
+use color_eyre::eyre::Result;
+use tracing::{info, warn};
+use visualvault_lib::internals::config::{DiagnosticLevel, Settings, SettingsWatcher};
+use visualvault_lib::internals::models::{Dialog, DialogAction, InputMode};
+
+use super::App;
+
+impl App {
+    /// Reloads the settings when their file was changed by another program.
+    ///
+    /// With unsaved edits in the Settings view the user is asked first whether to reload
+    /// or keep the edits. The file is not checked while a dialog is open.
+    ///
+    /// # Errors
+    /// Returns an error if the reloaded settings cannot be applied.
+    pub async fn check_settings_file(&mut self) -> Result<()> {
+        if self.dialog.is_some() {
+            return Ok(());
+        }
+        let Some(polled) = self
+            .settings_watcher
+            .as_mut()
+            .and_then(|watcher| watcher.poll(Instant::now()))
+        else {
+            return Ok(());
+        };
+        let settings = match polled {
+            Ok(settings) => settings,
+            Err(e) => {
+                warn!("Failed to reload settings: {}", e);
+                self.error_message = Some(format!("Settings file changed but could not be read: {e}"));
+                return Ok(());
+            }
+        };
+
+        let current = self.settings.read().await;
+        if *current == settings {
+            return Ok(());
+        }
+        let unsaved_edits = *current != self.settings_cache;
+        drop(current);
+
+        if unsaved_edits {
+            self.pending_settings_reload = Some(settings);
+            self.open_dialog(
+                Dialog::confirm(
+                    DialogAction::ReloadSettings,
+                    "Settings changed on disk",
+                    "The settings file was changed by another program, but you have unsaved edits in \
+                     Settings. Reload it and discard your edits? Keeping them overwrites the file on save.",
+                )
+                .destructive(),
+            );
+            Ok(())
+        } else {
+            self.apply_reloaded_settings(settings).await
+        }
+    }
+
+    /// Replaces the unsaved edits with the settings file changed on disk
+    pub(crate) async fn reload_settings(&mut self) -> Result<()> {
+        if let Some(settings) = self.pending_settings_reload.take() {
+            self.input_mode = InputMode::Normal;
+            self.editing_field = None;
+            self.apply_reloaded_settings(settings).await?;
+        }
+        Ok(())
+    }
+
+    /// Keeps the unsaved edits when the reload dialog is cancelled
+    pub(crate) fn keep_unsaved_settings(&mut self) {
+        self.pending_settings_reload = None;
+        self.error_message = Some("Kept your unsaved edits; saving overwrites the changed file".to_string());
+    }
+
+    /// Watches the settings file of the now active profile instead of the previous one
+    pub(crate) fn watch_active_profile(&mut self) {
+        self.pending_settings_reload = None;
+        self.settings_watcher = SettingsWatcher::active_profile()
+            .inspect_err(|e| warn!("Cannot watch settings file: {}", e))
+            .ok();
+    }
+
+    async fn apply_reloaded_settings(&mut self, settings: Settings) -> Result<()> {
+        let mut current = self.settings.write().await;
+        *current = settings;
+        self.scheduler.set_schedule(current.schedule, chrono::Local::now());
+        drop(current);
+        self.update_settings_cache().await?;
+
+        self.settings_diagnostics = self.settings_cache.validate();
+        let errors = self
+            .settings_diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.level == DiagnosticLevel::Error)
+            .count();
+        info!("Settings reloaded from disk");
+        if errors > 0 {
+            self.error_message = Some(format!(
+                "Settings reloaded with {errors} problem(s); open Settings (s) to review them"
+            ));
+        } else {
+            self.success_message = Some("Settings reloaded from disk".to_string());
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,147 @@
+use std::path::PathBuf;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use tracing::error;
+use visualvault_lib::internals::core::{explore_space, reveal_in_file_manager};
+use visualvault_lib::internals::models::{AppState, SpaceFocus};
+
+use super::App;
+
+/// Largest files listed by the space explorer
+const SPACE_EXPLORER_FILES: usize = 100;
+
+impl App {
+    /// Opens the space explorer on the destination folder
+    pub async fn open_space_explorer(&mut self) {
+        let Some(destination) = self.settings.read().await.destination_folder.clone() else {
+            self.error_message = Some("No destination folder configured".to_string());
+            return;
+        };
+        self.space_trail.clear();
+        self.state = AppState::SpaceExplorer;
+        self.explore_folder(destination, 0);
+    }
+
+    /// Handles keyboard input in the space explorer.
+    ///
+    /// Enter opens the selected folder, Backspace goes back to the folder it was opened from
+    /// and Tab switches between the folders and the files.
+    pub fn handle_space_explorer_keys(&mut self, key: KeyEvent) {
+        let last = self.space_list_len().saturating_sub(1);
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                self.state = AppState::Dashboard;
+                self.space_report = None;
+                self.space_trail.clear();
+            }
+            KeyCode::Up => self.space_selected = self.space_selected.saturating_sub(1),
+            KeyCode::Down => self.space_selected = (self.space_selected + 1).min(last),
+            KeyCode::PageUp => self.space_selected = self.space_selected.saturating_sub(10),
+            KeyCode::PageDown => self.space_selected = (self.space_selected + 10).min(last),
+            KeyCode::Home => self.space_selected = 0,
+            KeyCode::End => self.space_selected = last,
+            KeyCode::Tab | KeyCode::BackTab => {
+                self.space_focus = match self.space_focus {
+                    SpaceFocus::Folders => SpaceFocus::Files,
+                    SpaceFocus::Files => SpaceFocus::Folders,
+                };
+                self.space_selected = 0;
+            }
+            KeyCode::Enter | KeyCode::Right if self.space_focus == SpaceFocus::Folders => self.open_space_folder(),
+            KeyCode::Enter => self.reveal_space_file(),
+            KeyCode::Backspace | KeyCode::Left => {
+                if let Some((folder, selected)) = self.space_trail.pop() {
+                    self.explore_folder(folder, selected);
+                }
+            }
+            KeyCode::Char('r') => {
+                if let Some(report) = &self.space_report {
+                    let root = report.root.clone();
+                    self.explore_folder(root, self.space_selected);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Collects the report of the folder being explored once the background task finishes
+    pub(crate) async fn check_space_explorer_completion(&mut self) {
+        if !self
+            .space_task
+            .as_ref()
+            .is_some_and(tokio::task::JoinHandle::is_finished)
+        {
+            return;
+        }
+        let Some(task) = self.space_task.take() else {
+            return;
+        };
+        match task.await {
+            Ok(Ok(report)) => {
+                self.space_selected = self.space_selected.min(report.folders.len().saturating_sub(1));
+                self.space_report = Some(report);
+            }
+            Ok(Err(e)) => {
+                error!("Space explorer failed: {}", e);
+                self.error_message = Some(format!("Failed to explore the folder: {e}"));
+            }
+            Err(e) => {
+                error!("Space explorer task failed: {}", e);
+                self.error_message = Some(format!("Failed to explore the folder: {e}"));
+            }
+        }
+    }
+
+    /// Whether the space explorer is still adding up the folder it shows
+    #[must_use]
+    pub const fn is_exploring_space(&self) -> bool {
+        self.space_task.is_some()
+    }
+
+    fn explore_folder(&mut self, folder: PathBuf, selected: usize) {
+        if let Some(task) = self.space_task.take() {
+            task.abort();
+        }
+        self.space_selected = selected;
+        self.space_focus = SpaceFocus::Folders;
+        self.space_task = Some(tokio::task::spawn_blocking(move || {
+            explore_space(&folder, SPACE_EXPLORER_FILES)
+        }));
+    }
+
+    fn open_space_folder(&mut self) {
+        let Some(report) = &self.space_report else {
+            return;
+        };
+        let Some(folder) = report.folders.get(self.space_selected) else {
+            return;
+        };
+        let folder = folder.path.clone();
+        self.space_trail.push((report.root.clone(), self.space_selected));
+        self.explore_folder(folder, 0);
+    }
+
+    fn reveal_space_file(&mut self) {
+        let Some((path, _)) = self
+            .space_report
+            .as_ref()
+            .and_then(|report| report.files.get(self.space_selected))
+        else {
+            return;
+        };
+        match reveal_in_file_manager(path) {
+            Ok(()) => self.success_message = Some(format!("Showing {} in file manager", path.display())),
+            Err(e) => self.error_message = Some(format!("Could not show {}: {e}", path.display())),
+        }
+    }
+
+    fn space_list_len(&self) -> usize {
+        self.space_report.as_ref().map_or(0, |report| {
+            if self.space_focus == SpaceFocus::Files {
+                report.files.len()
+            } else {
+                report.folders.len()
+            }
+        })
+    }
+}
@@ -1,35 +1,77 @@
-use std::{collections::HashSet, path::PathBuf, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    path::PathBuf,
+    sync::{Arc, Mutex, atomic::AtomicUsize},
+    time::Instant,
+};
 
 use ahash::AHashMap;
 use color_eyre::eyre::Result;
-use ratatui::widgets::ListState;
 use tokio::{sync::RwLock, task::JoinHandle};
 use tracing::error;
-use tracing::info;
-use visualvault_config::Settings;
-use visualvault_core::DatabaseCache;
-use visualvault_core::{DuplicateDetector, FileManager, FileOrganizer, Scanner};
-use visualvault_models::{
-    AppState, DuplicateFocus, DuplicateStats, EditingField, FilterFocus, FilterSet, InputMode, MediaFile,
-    OrganizeResult, ScanResult, Statistics,
+use tracing::{info, warn};
+use visualvault_lib::Engine;
+use visualvault_lib::internals::config::{
+    ConflictPolicy, DiagnosticLevel, Settings, SettingsDiagnostic, SettingsWatcher, StartupAction,
+};
+use visualvault_lib::internals::core::DatabaseCache;
+use visualvault_lib::internals::core::{
+    ActivityLog, CollectionStore, DestinationIndex, DestinationIndexInfo, DuplicateDetector, FileList, FileManager,
+    FileOrganizer, FileQuery, FolderStatsPool, ImportRecord, InstanceLock, InstanceLockError, MetadataService, Scanner,
+    Scheduler, SpaceReport,
+};
+use visualvault_lib::internals::models::{
+    AppState, ArchiveMedia, BatchRename, BulkActionMenu, CloudUploadReport, CollectionsView, CommandPalette,
+    CompareEntry, DateMismatch, DeviceImport, Dialog, DuplicateFocus, DuplicateSort, DuplicateStats, EditingField,
+    ErrorCenter, FileBrowser, FileConflict, FileSort, FileTags, FilterFocus, FilterMatches, FilterSet, FolderCheck,
+    FolderPicker, GroupedFiles, InputMode, ListViewport, LogViewer, MediaFile, MetadataStats, MouseTargets,
+    OnboardingStep, OrganizeResult, PendingQuit, PerformanceLog, PreflightIssue, ProfilePicker, RemoteTransfer,
+    ReportSort, RestoreItem, ScanResult, SearchScope, SpaceFocus, Statistics, StatsSnapshot, SuspectFile, Toasts,
+    VerifyReport,
 };
-use visualvault_utils::{FolderStats, Progress, create_cache_path};
+use visualvault_lib::internals::utils::{Progress, create_cache_path};
+
+use crate::actions::OrganizeExecutionResult;
+
+/// Scanned files, their duplicates, where files organized before were placed by source path,
+/// and the query selecting the scanned files in the file cache
+pub type ScanOutput = (FileList, DuplicateStats, HashMap<PathBuf, PathBuf>, FileQuery);
 
 pub struct App {
     // Core state
     pub state: AppState,
     pub input_mode: InputMode,
     pub should_quit: bool,
+    /// Quit waiting for the running operations, chosen in the quit dialog
+    pub pending_quit: Option<PendingQuit>,
 
     // UI state
     pub show_help: bool,
+    // Results of the last key, click or tick, turned into toasts once it is handled
     pub error_message: Option<String>,
+    pub warning_message: Option<String>,
     pub success_message: Option<String>,
+    // Toasts on screen and the history of recent ones
+    pub toasts: Toasts,
     pub selected_tab: usize,
     pub selected_setting: usize,
     pub selected_file_index: usize,
-    pub scroll_offset: usize,
+    /// Visible rows of the file list and search results
+    pub file_viewport: ListViewport,
+    pub marked_files: HashSet<PathBuf>,
+    pub mark_anchor: Option<usize>,
+    pub batch_rename: Option<BatchRename>,
+    pub bulk_actions: Option<BulkActionMenu>,
+    pub profile_picker: Option<ProfilePicker>,
+    pub folder_picker: Option<FolderPicker>,
+    pub device_import: Option<DeviceImport>,
+    /// Modal dialog waiting for an answer, taking every key until it gets one
+    pub dialog: Option<Dialog>,
+    /// Folder the next scan reads instead of the source folder, such as a camera card
+    pub source_override: Option<PathBuf>,
     pub help_scroll: usize,
+    /// Furthest the help overlay scrolls, recorded by the UI from the length of its text
+    pub help_max_scroll: usize,
 
     // Components
     pub settings: Arc<RwLock<Settings>>,
@@ -37,51 +79,210 @@ pub struct App {
     pub scanner: Arc<Scanner>,
     pub file_manager: Arc<RwLock<FileManager>>,
     pub organizer: Arc<FileOrganizer>,
+    /// Runs scans, organize runs and undo with the scanner and organizer above
+    pub engine: Arc<Engine>,
     pub duplicate_detector: DuplicateDetector,
 
     // Data
     pub statistics: Statistics,
     pub progress: Arc<RwLock<Progress>>,
-    pub cached_files: Vec<Arc<MediaFile>>,
+    /// Scanned files, paged from disk when they exceed the memory budget
+    pub cached_files: FileList,
+    /// Image sizes and color types read on demand, prefetched for the visible rows
+    pub metadata: MetadataService,
+    /// Order of the file list, kept for the files of later scans
+    pub file_sort: Option<FileSort>,
+    /// Groups the Files tab shows instead of the flat list
+    pub file_groups: Option<GroupedFiles>,
+    /// Grouped files outside the loaded window of a paged list, by list index
+    pub file_group_rows: AHashMap<usize, Arc<MediaFile>>,
     pub search_results: Vec<MediaFile>,
     pub duplicate_groups: Option<Vec<Vec<MediaFile>>>,
     pub duplicate_stats: Option<DuplicateStats>,
-    pub folder_stats_cache: AHashMap<PathBuf, FolderStats>,
+    pub exact_duplicates: Option<DuplicateStats>,
+    pub probable_copies: Option<DuplicateStats>,
+    /// Videos grouped by sampled frames, once checked with `v` in the duplicate review
+    pub similar_videos: Option<DuplicateStats>,
+    /// Photos shot in quick succession, sharpest first, once grouped with `b` in the duplicate review
+    pub bursts: Option<DuplicateStats>,
+    pub folder_stats: FolderStatsPool,
+    /// Tags and ratings of the scanned files that have any
+    pub file_tags: AHashMap<PathBuf, FileTags>,
+    /// Scanned files whose content an earlier organize run placed, with where it went
+    pub organized_before: AHashMap<PathBuf, PathBuf>,
 
     // Search state
     pub search_input: String,
+    pub search_scope: SearchScope,
+    /// Searchable files of the destination library, opened the first time it is searched
+    pub destination_index: Option<DestinationIndex>,
+    pub destination_index_info: Option<DestinationIndexInfo>,
+    pub destination_index_task: Option<JoinHandle<Result<DestinationIndexInfo>>>,
 
     // Input state
     pub input_buffer: String,
+    /// Check of the folder typed into the source or destination setting
+    pub folder_check: Option<FolderCheck>,
+    pub folder_overlap: Option<PreflightIssue>,
+    // Problems found when the settings were last loaded or saved
+    pub settings_diagnostics: Vec<SettingsDiagnostic>,
+    // Notices external edits of the settings file; reloads that would discard edits wait for the user
+    pub settings_watcher: Option<SettingsWatcher>,
+    pub pending_settings_reload: Option<Settings>,
+    pub log_viewer: Option<LogViewer>,
+    pub command_palette: Option<CommandPalette>,
+    /// Step of the setup wizard while it is open, on first launch
+    pub onboarding: Option<OnboardingStep>,
+    /// Folders matching the last Tab completion when it was ambiguous
+    pub path_candidates: Vec<String>,
     pub editing_field: Option<EditingField>,
+    /// File name tried against the routing rules in the Routing settings tab
+    pub routing_sample: String,
 
     // Results
     pub last_scan_result: Option<ScanResult>,
     pub last_organize_result: Option<OrganizeResult>,
 
     // Duplicate state
+    pub duplicate_tab: usize,
     pub selected_duplicate_group: usize,
     pub selected_duplicate_items: HashSet<usize>,
-    pub duplicate_list_state: ListState,
+    /// Visible groups of the duplicate review's group list
+    pub duplicate_viewport: ListViewport,
+    /// Text the duplicate groups are filtered by, matched against file names and folders;
+    /// it is edited while the review is in insert mode
+    pub duplicate_filter: String,
+    pub duplicate_sort: DuplicateSort,
     pub duplicate_focus: DuplicateFocus,
     pub selected_file_in_group: usize,
-    pub pending_bulk_delete: bool,
+    pub duplicate_compare: Option<[CompareEntry; 2]>,
+    /// Folder the duplicate review scans by itself, such as the destination library,
+    /// instead of the scanned files
+    pub duplicate_folder: Option<PathBuf>,
 
     // Filter state
     pub filter_set: FilterSet,
+    /// Files matching the active filters, counted for the Files tab
+    pub filter_matches: Option<FilterMatches>,
     pub filter_tab: usize,
     pub filter_focus: FilterFocus,
     pub selected_filter_index: usize,
     pub filter_input: String,
+    /// Files matching the active filters while the organize dialog asks which files to organize
+    pub pending_filtered_organize: Option<Vec<Arc<MediaFile>>>,
+
+    // Organize conflicts waiting for a decision, and the decisions made so far
+    pub pending_conflicts: VecDeque<FileConflict>,
+    pub conflict_resolutions: Vec<(FileConflict, ConflictPolicy)>,
 
     // Undo state
     pub last_undo_result: Option<String>,
 
-    pub folder_stats_tasks: AHashMap<std::path::PathBuf, JoinHandle<FolderStats>>,
-    pub folder_stats_in_progress: HashSet<std::path::PathBuf>,
+    // Folders the user asked to update, reported once their statistics are in
+    pub folder_stats_requested: HashSet<PathBuf>,
 
-    pub scan_task: Option<JoinHandle<Result<(Vec<Arc<MediaFile>>, DuplicateStats)>>>,
+    pub scan_task: Option<JoinHandle<Result<ScanOutput>>>,
     pub scan_start_time: Option<std::time::Instant>,
+
+    // Organize run working in the background, and the files it was given
+    pub(crate) organize_task: Option<JoinHandle<OrganizeExecutionResult>>,
+    pub organize_files: Vec<Arc<MediaFile>>,
+
+    // Startup actions still waiting to run
+    pub pending_startup_actions: VecDeque<StartupAction>,
+
+    // Scheduled runs and the activity log
+    pub scheduler: Scheduler,
+    pub scheduled_run: Option<chrono::DateTime<chrono::Local>>,
+    /// Files the scan of a scheduled run found, while the run organizes them
+    pub scheduled_organize: Option<usize>,
+    pub activity_log: ActivityLog,
+
+    // Named collections of files and the state of their view
+    pub collections: CollectionStore,
+    pub collections_view: CollectionsView,
+
+    // Two-pane browser for moving and copying files by hand
+    pub file_browser: FileBrowser,
+    pub activity_scroll: usize,
+
+    // First visible row of the dashboard's audio tab
+    pub audio_scroll: usize,
+
+    // Empty folders found in the source folder, nested ones first
+    pub empty_folders: Vec<PathBuf>,
+    pub empty_folder_scroll: usize,
+    // Files the last scan set aside as suspect, waiting to be quarantined
+    pub suspect_files: Vec<SuspectFile>,
+    pub suspect_scroll: usize,
+    // Photos whose modification time disagrees with their EXIF date, largest difference first
+    pub date_mismatches: Vec<DateMismatch>,
+    pub date_fix_scroll: usize,
+    // Media the last scan found inside archives, and those selected for extraction
+    pub archive_media: Vec<ArchiveMedia>,
+    pub archive_scroll: usize,
+    pub selected_archive_media: HashSet<usize>,
+    // Files listed in the manifests of the library, to be moved back to their original paths
+    pub manifest_restore: Vec<RestoreItem>,
+    pub manifest_restore_scroll: usize,
+
+    // Library verification running in the background and its last report
+    pub verify_task: Option<JoinHandle<Result<VerifyReport>>>,
+    pub verify_report: Option<VerifyReport>,
+    pub verify_scroll: usize,
+
+    // Upload of organized files to the cloud export bucket, its progress and last report
+    pub cloud_upload_task: Option<JoinHandle<CloudUploadReport>>,
+    pub cloud_upload_progress: Arc<RwLock<Progress>>,
+    pub cloud_upload_report: Option<CloudUploadReport>,
+    pub cloud_upload_scroll: usize,
+
+    // Move of organized files from the staging folder to an rclone remote destination
+    pub rclone_task: Option<JoinHandle<RemoteTransfer>>,
+
+    // EXIF data aggregated for the Metadata tab, the file list version it was read from, and
+    // the task reading it with its progress: images read and images to read
+    pub metadata_stats: Option<MetadataStats>,
+    pub metadata_stats_version: u64,
+    pub metadata_stats_task: Option<JoinHandle<MetadataStats>>,
+    pub metadata_stats_progress: (Arc<AtomicUsize>, usize),
+
+    // Per-file report of the last organize run, its sort column and first visible row
+    pub organize_report_sort: ReportSort,
+    pub organize_report_scroll: usize,
+
+    // Recent errors of scans, organize runs and other operations
+    pub error_center: ErrorCenter,
+
+    // Stage timings of recent scans and organize runs
+    pub performance: PerformanceLog,
+
+    // Space explorer: the folder it shows and the task adding it up, the folders drilled in from
+    // with their selected rows, the selected row and the list it is in
+    pub space_report: Option<SpaceReport>,
+    pub space_task: Option<JoinHandle<Result<SpaceReport>>>,
+    pub space_trail: Vec<(PathBuf, usize)>,
+    pub space_selected: usize,
+    pub space_focus: SpaceFocus,
+
+    // Statistics snapshots for the trends on the dashboard, oldest first, and the duplicates
+    // removed since the newest one
+    pub stats_history: Vec<StatsSnapshot>,
+    pub stats_history_loaded: bool,
+    pub duplicates_removed: usize,
+    // Latest organize runs from the import history, newest first
+    pub import_history: Vec<ImportRecord>,
+
+    // Lock keeping other instances from changing files, or why this one is read-only without it
+    pub instance_lock: Option<InstanceLock>,
+    pub read_only: Option<String>,
+
+    // Whether the terminal has focus, None until the terminal reports a focus change
+    pub terminal_focused: Option<bool>,
+
+    // Where the last frame drew clickable parts, and the last clicked file row for double-clicks
+    pub mouse_targets: Mutex<MouseTargets>,
+    pub last_click: Option<(Instant, usize)>,
 }
 
 impl App {
@@ -98,12 +299,21 @@ impl App {
     /// Panics if:
     /// - The cache path cannot be converted to a string
     /// - The cache path creation fails during background initialization
+    #[allow(clippy::too_many_lines)]
     pub async fn init() -> Result<Self> {
-        let mut duplicate_list_state = ListState::default();
-        duplicate_list_state.select(Some(0));
-
+        let first_launch = !Settings::is_saved();
         let settings = Settings::load().await?;
         let settings_cache = settings.clone();
+        let settings_diagnostics = settings.validate();
+        for diagnostic in &settings_diagnostics {
+            warn!("Settings: {}", diagnostic);
+        }
+        let settings_errors = settings_diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.level == DiagnosticLevel::Error)
+            .count();
+        let pending_startup_actions = settings.on_start.iter().copied().collect();
+        let scheduler = Scheduler::new(settings.schedule, chrono::Local::now());
         let settings = Arc::new(RwLock::new(settings));
         let file_manager = Arc::new(RwLock::new(FileManager::new()));
         let database_cache = DatabaseCache::new_uninit();
@@ -111,57 +321,192 @@ impl App {
         let config_dir =
             dirs::config_dir().ok_or_else(|| color_eyre::eyre::eyre!("Could not find config directory"))?;
         let config_dir_clone = config_dir.clone();
-        let organizer = Arc::new(FileOrganizer::new(config_dir).await?);
+        let (instance_lock, read_only) = match InstanceLock::acquire(&config_dir.join("visualvault")) {
+            Ok(lock) => (Some(lock), None),
+            Err(e @ InstanceLockError::Held { .. }) => {
+                warn!("{}, opening read-only", e);
+                (None, Some(e.to_string()))
+            }
+            Err(e) => {
+                // Without a lock file there is nothing to protect against, so keep going
+                warn!("{}", e);
+                (None, None)
+            }
+        };
+        let activity_log = ActivityLog::load(&config_dir).unwrap_or_else(|e| {
+            error!("Failed to load activity log: {}", e);
+            ActivityLog::default()
+        });
+        let collections = CollectionStore::load(&config_dir).unwrap_or_else(|e| {
+            error!("Failed to load collections: {}", e);
+            CollectionStore::default()
+        });
+        let organizer = Arc::new(FileOrganizer::new(config_dir.clone()).await?);
+        let engine = Arc::new(Engine::from_parts(
+            config_dir,
+            Arc::clone(&scanner),
+            Arc::clone(&organizer),
+        ));
         let statistics = Statistics::new();
         let progress = Arc::new(RwLock::new(Progress::new()));
 
-        let app = Self {
+        let mut app = Self {
             state: AppState::Dashboard,
             input_mode: InputMode::Normal,
             should_quit: false,
+            pending_quit: None,
             show_help: false,
             error_message: None,
+            warning_message: read_only
+                .as_ref()
+                .map(|reason| format!("{reason}; this window is read-only until it exits"))
+                .or_else(|| {
+                    (settings_errors > 0).then(|| {
+                        format!("Settings have {settings_errors} problem(s); open Settings (s) to review them")
+                    })
+                }),
             success_message: None,
+            toasts: Toasts::default(),
             selected_tab: 0,
             selected_setting: 0,
             selected_file_index: 0,
-            scroll_offset: 0,
+            file_viewport: ListViewport::default(),
+            marked_files: HashSet::new(),
+            mark_anchor: None,
+            batch_rename: None,
+            bulk_actions: None,
+            profile_picker: None,
+            folder_picker: None,
+            device_import: None,
+            dialog: None,
+            source_override: None,
             help_scroll: 0,
+            help_max_scroll: 0,
             settings,
             settings_cache,
             scanner,
             file_manager,
             organizer,
+            engine,
             duplicate_detector: DuplicateDetector::new(),
             statistics,
             progress,
-            cached_files: Vec::new(),
+            cached_files: FileList::default(),
+            metadata: MetadataService::default(),
+            file_sort: None,
+            file_groups: None,
+            file_group_rows: AHashMap::new(),
             search_results: Vec::new(),
             duplicate_groups: None,
             duplicate_stats: None,
-            folder_stats_cache: AHashMap::new(),
+            exact_duplicates: None,
+            probable_copies: None,
+            similar_videos: None,
+            bursts: None,
+            folder_stats: FolderStatsPool::default(),
+            file_tags: AHashMap::new(),
+            organized_before: AHashMap::new(),
             search_input: String::new(),
+            search_scope: SearchScope::default(),
+            destination_index: None,
+            destination_index_info: None,
+            destination_index_task: None,
             input_buffer: String::new(),
+            folder_check: None,
+            folder_overlap: None,
+            settings_diagnostics,
+            settings_watcher: SettingsWatcher::active_profile()
+                .inspect_err(|e| warn!("Cannot watch settings file: {}", e))
+                .ok(),
+            pending_settings_reload: None,
+            log_viewer: None,
+            command_palette: None,
+            onboarding: None,
+            path_candidates: Vec::new(),
             editing_field: None,
+            routing_sample: String::new(),
             last_scan_result: None,
             last_organize_result: None,
+            duplicate_tab: 0,
             selected_duplicate_group: 0,
             selected_duplicate_items: HashSet::new(),
-            duplicate_list_state,
+            duplicate_viewport: ListViewport::default(),
+            duplicate_filter: String::new(),
+            duplicate_sort: DuplicateSort::default(),
             duplicate_focus: DuplicateFocus::GroupList,
             selected_file_in_group: 0,
-            pending_bulk_delete: false,
+            duplicate_compare: None,
+            duplicate_folder: None,
             filter_set: FilterSet::new(),
+            filter_matches: None,
             filter_tab: 0,
             filter_focus: FilterFocus::DateRange,
             selected_filter_index: 0,
             filter_input: String::new(),
+            pending_filtered_organize: None,
+            pending_conflicts: VecDeque::new(),
+            conflict_resolutions: Vec::new(),
             last_undo_result: None,
-            folder_stats_tasks: AHashMap::new(),
-            folder_stats_in_progress: HashSet::new(),
+            folder_stats_requested: HashSet::new(),
             scan_task: None,
             scan_start_time: None,
+            organize_task: None,
+            organize_files: Vec::new(),
+            pending_startup_actions,
+            scheduler,
+            scheduled_run: None,
+            scheduled_organize: None,
+            activity_log,
+            collections,
+            collections_view: CollectionsView::default(),
+            file_browser: FileBrowser::default(),
+            activity_scroll: 0,
+            audio_scroll: 0,
+            empty_folders: Vec::new(),
+            empty_folder_scroll: 0,
+            suspect_files: Vec::new(),
+            suspect_scroll: 0,
+            date_mismatches: Vec::new(),
+            date_fix_scroll: 0,
+            archive_media: Vec::new(),
+            archive_scroll: 0,
+            selected_archive_media: HashSet::new(),
+            manifest_restore: Vec::new(),
+            manifest_restore_scroll: 0,
+            verify_task: None,
+            verify_report: None,
+            verify_scroll: 0,
+            cloud_upload_task: None,
+            cloud_upload_progress: Arc::new(RwLock::new(Progress::new())),
+            cloud_upload_report: None,
+            cloud_upload_scroll: 0,
+            rclone_task: None,
+            metadata_stats: None,
+            metadata_stats_version: 0,
+            metadata_stats_task: None,
+            metadata_stats_progress: (Arc::new(AtomicUsize::new(0)), 0),
+            organize_report_sort: ReportSort::default(),
+            organize_report_scroll: 0,
+            error_center: ErrorCenter::default(),
+            performance: PerformanceLog::default(),
+            space_report: None,
+            space_task: None,
+            space_trail: Vec::new(),
+            space_selected: 0,
+            space_focus: SpaceFocus::Folders,
+            stats_history: Vec::new(),
+            stats_history_loaded: false,
+            duplicates_removed: 0,
+            import_history: Vec::new(),
+            instance_lock,
+            read_only,
+            terminal_focused: None,
+            mouse_targets: Mutex::new(MouseTargets::default()),
+            last_click: None,
         };
+        if first_launch {
+            app.start_onboarding();
+        }
 
         let scanner_clone = Arc::clone(&app.scanner);
         #[allow(clippy::expect_used)]
@@ -189,16 +534,26 @@ impl App {
         Ok(app)
     }
 
+    /// Refuses a change to files while another instance holds the lock, telling the user why
+    pub(crate) fn ensure_writable(&mut self) -> bool {
+        let Some(reason) = &self.read_only else {
+            return true;
+        };
+        self.error_message = Some(format!("{reason}; files cannot be changed from this window"));
+        false
+    }
+
     pub fn clear_messages(&mut self) {
         self.error_message = None;
+        self.warning_message = None;
         self.success_message = None;
     }
 
     #[must_use]
     pub const fn get_tab_count(&self) -> usize {
         match self.state {
-            AppState::Dashboard => 4,
-            AppState::Settings => 3,
+            AppState::Dashboard => 7,
+            AppState::Settings => 6,
             _ => 1,
         }
     }
@@ -213,6 +568,7 @@ impl App {
         let settings = self.settings.read().await;
         self.settings_cache = settings.clone();
         drop(settings);
+        self.update_folder_overlap();
         Ok(())
     }
 }
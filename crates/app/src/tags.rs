@@ -0,0 +1,128 @@
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use tracing::warn;
+use visualvault_lib::internals::core::{DuplicateDetector, MoveOperation};
+use visualvault_lib::internals::models::{FileTags, InputMode, MediaFile};
+
+use super::App;
+
+impl App {
+    /// Looks up the stored tags of the scanned files.
+    ///
+    /// Tags are keyed by content hash, so only files with the size of a tagged file are
+    /// hashed. This keeps tags attached to files that were moved or renamed since.
+    ///
+    /// # Errors
+    /// Returns an error if the tag store cannot be read or a file cannot be hashed.
+    pub async fn load_file_tags(&mut self) -> Result<()> {
+        self.file_tags.clear();
+        self.filter_matches = None;
+        let sizes = self.scanner.tagged_sizes().await?;
+        if sizes.is_empty() {
+            return Ok(());
+        }
+
+        let candidates = self.cached_files.filter(|file| sizes.contains(&file.size)).await?;
+        for file in candidates {
+            let hash = DuplicateDetector::calculate_file_hash(&file.path).await?;
+            if let Some(tags) = self.scanner.file_tags(&hash).await? {
+                self.file_tags.insert(file.path.clone(), tags);
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns whether `file` passes the active filters, including its tags and rating
+    pub(crate) fn matches_filters(&self, file: &MediaFile) -> bool {
+        self.filter_set
+            .matches_file_with_tags(file, self.file_tags.get(&file.path))
+    }
+
+    /// Handles rating and tag keys in the file details view.
+    ///
+    /// # Errors
+    /// Returns an error if the tags cannot be saved.
+    pub(crate) async fn handle_tag_keys(&mut self, index: usize, key: KeyEvent) -> Result<()> {
+        if self.input_mode == InputMode::Editing {
+            match key.code {
+                KeyCode::Enter => {
+                    self.input_mode = InputMode::Normal;
+                    let input = std::mem::take(&mut self.input_buffer);
+                    self.update_file_tags(index, |tags| tags.set_tags_from_input(&input))
+                        .await?;
+                }
+                KeyCode::Esc => {
+                    self.input_mode = InputMode::Normal;
+                    self.input_buffer.clear();
+                }
+                KeyCode::Backspace => {
+                    self.input_buffer.pop();
+                }
+                KeyCode::Char(c) => self.input_buffer.push(c),
+                _ => {}
+            }
+            return Ok(());
+        }
+
+        match key.code {
+            KeyCode::Char(c @ '0'..='5') => {
+                let stars = c.to_digit(10).and_then(|d| u8::try_from(d).ok()).unwrap_or_default();
+                self.update_file_tags(index, |tags| tags.toggle_rating(stars)).await?;
+            }
+            KeyCode::Char('t') => {
+                if let Some(file) = self.cached_files.get(index) {
+                    self.input_buffer = self
+                        .file_tags
+                        .get(&file.path)
+                        .map(FileTags::tags_input)
+                        .unwrap_or_default();
+                    self.input_mode = InputMode::Editing;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Changes the tags of the file at `index` and stores them under its content hash
+    async fn update_file_tags(&mut self, index: usize, change: impl FnOnce(&mut FileTags)) -> Result<()> {
+        let Some(file) = self.cached_files.get(index).cloned() else {
+            return Ok(());
+        };
+        let mut tags = self.file_tags.get(&file.path).cloned().unwrap_or_default();
+        change(&mut tags);
+
+        let hash = match DuplicateDetector::calculate_file_hash(&file.path).await {
+            Ok(hash) => hash,
+            Err(e) => {
+                warn!("Failed to hash {}: {}", file.path.display(), e);
+                self.error_message = Some(format!("Cannot tag {}: {e}", file.name));
+                return Ok(());
+            }
+        };
+        self.scanner.save_file_tags(&hash, file.size, &tags).await?;
+
+        self.success_message = Some(match (tags.rating, tags.tags.is_empty()) {
+            (None, true) => format!("Cleared tags of {}", file.name),
+            (Some(_), _) => format!("Rated {} {}", file.name, tags.stars()),
+            (None, false) => format!("Tagged {}: {}", file.name, tags.tags_input()),
+        });
+        if tags.is_empty() {
+            self.file_tags.remove(&file.path);
+        } else {
+            self.file_tags.insert(file.path.clone(), tags);
+        }
+        // Tag and rating filters may match differently now
+        self.filter_matches = None;
+        Ok(())
+    }
+
+    /// Moves tags along with files that were renamed or moved by the app
+    pub(crate) fn relocate_file_tags(&mut self, moves: &[MoveOperation]) {
+        for operation in moves {
+            if let Some(tags) = self.file_tags.remove(&operation.source) {
+                self.file_tags.insert(operation.destination.clone(), tags);
+            }
+        }
+    }
+}
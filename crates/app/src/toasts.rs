@@ -0,0 +1,60 @@
+use chrono::Local;
+use crossterm::event::{KeyCode, KeyEvent};
+use visualvault_lib::internals::models::{AppState, Toast, ToastSeverity};
+
+use super::App;
+
+impl App {
+    /// Opens the notification history on the newest toast
+    pub const fn open_notifications(&mut self) {
+        self.toasts.selected = 0;
+        self.state = AppState::Notifications;
+    }
+
+    /// Handles keyboard input in the notification history.
+    ///
+    /// 'd' takes the toasts off the screen and 'c' clears the history.
+    pub fn handle_notification_keys(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => self.state = AppState::Dashboard,
+            KeyCode::Up => self.toasts.move_selection(-1),
+            KeyCode::Down => self.toasts.move_selection(1),
+            KeyCode::PageUp => self.toasts.move_selection(-10),
+            KeyCode::PageDown => self.toasts.move_selection(10),
+            KeyCode::Home => self.toasts.selected = 0,
+            KeyCode::End => self.toasts.move_selection(isize::MAX),
+            KeyCode::Char('d') => self.toasts.dismiss_all(),
+            KeyCode::Char('c') => self.toasts.clear(),
+            _ => {}
+        }
+    }
+
+    /// The toasts on screen now, newest first
+    pub fn visible_toasts(&self) -> impl Iterator<Item = &Toast> {
+        self.toasts.visible(Local::now())
+    }
+
+    /// Turns the messages the last key, click or tick left behind into toasts, so one result
+    /// no longer replaces another before it was read.
+    ///
+    /// Success messages that end in "..." announce work that has only started and are shown
+    /// as info.
+    pub(crate) fn collect_toasts(&mut self) {
+        let now = Local::now();
+        if let Some(message) = self.success_message.take() {
+            let severity = if message.ends_with("...") {
+                ToastSeverity::Info
+            } else {
+                ToastSeverity::Success
+            };
+            self.toasts.push(severity, message, now);
+        }
+        // Pushed last so they are on top
+        if let Some(message) = self.warning_message.take() {
+            self.toasts.push(ToastSeverity::Warning, message, now);
+        }
+        if let Some(message) = self.error_message.take() {
+            self.toasts.push(ToastSeverity::Error, message, now);
+        }
+    }
+}
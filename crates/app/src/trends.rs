@@ -0,0 +1,81 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use tracing::{debug, warn};
+use visualvault_lib::internals::models::{FileOutcome, MediaFile, SnapshotKind, StatsSnapshot};
+
+use super::App;
+
+/// How many statistics snapshots the dashboard trends cover
+pub const TREND_POINTS: usize = 60;
+
+/// How many organize runs of the import history the dashboard lists
+pub const IMPORT_HISTORY_ROWS: usize = 5;
+
+impl App {
+    /// Loads the statistics and import history once the scan cache has finished opening
+    pub(crate) async fn load_stats_history_if_needed(&mut self) {
+        if self.stats_history_loaded {
+            return;
+        }
+        match self.scanner.stats_history(TREND_POINTS).await {
+            Ok(history) => {
+                self.stats_history = history;
+                self.stats_history_loaded = true;
+                match self.scanner.import_history(IMPORT_HISTORY_ROWS).await {
+                    Ok(imports) => self.import_history = imports,
+                    Err(e) => warn!("Failed to load the import history: {}", e),
+                }
+            }
+            // The cache opens in the background, so the first ticks may come too early
+            Err(e) => debug!("Statistics history not available yet: {}", e),
+        }
+    }
+
+    /// Stores a snapshot of the statistics after a scan
+    pub(crate) async fn record_scan_snapshot(&mut self) {
+        self.load_stats_history_if_needed().await;
+        let snapshot = StatsSnapshot::new(SnapshotKind::Scan, &self.statistics, self.stats_history.last());
+        self.record_stats_snapshot(snapshot).await;
+    }
+
+    /// Stores a snapshot of the statistics after an organize run, adding the files it placed in
+    /// the library
+    pub(crate) async fn record_organize_snapshot(&mut self, organized: &[Arc<MediaFile>]) {
+        self.load_stats_history_if_needed().await;
+        let Some(result) = self.last_organize_result.as_ref().filter(|result| !result.rolled_back) else {
+            return;
+        };
+        let placed: HashSet<_> = result
+            .files
+            .iter()
+            .filter(|file| {
+                matches!(
+                    file.outcome,
+                    FileOutcome::Moved | FileOutcome::Renamed | FileOutcome::Overwritten
+                )
+            })
+            .map(|file| file.source.as_path())
+            .collect();
+        let bytes = organized
+            .iter()
+            .filter(|file| placed.contains(file.path.as_path()))
+            .map(|file| file.size)
+            .sum();
+        let snapshot = StatsSnapshot::new(SnapshotKind::Organize, &self.statistics, self.stats_history.last())
+            .with_organized(placed.len(), bytes);
+        self.record_stats_snapshot(snapshot).await;
+    }
+
+    async fn record_stats_snapshot(&mut self, snapshot: StatsSnapshot) {
+        let snapshot = snapshot.with_duplicates_removed(self.duplicates_removed);
+        if let Err(e) = self.scanner.record_stats(&snapshot).await {
+            warn!("Failed to record the statistics snapshot: {}", e);
+            return;
+        }
+        self.duplicates_removed = 0;
+        self.stats_history.push(snapshot);
+        let excess = self.stats_history.len().saturating_sub(TREND_POINTS);
+        self.stats_history.drain(..excess);
+    }
+}
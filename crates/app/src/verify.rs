@@ -0,0 +1,170 @@
+use color_eyre::eyre::{Result, eyre};
+use crossterm::event::{KeyCode, KeyEvent};
+use std::path::PathBuf;
+use tracing::error;
+use visualvault_lib::internals::core::{LibraryIndex, export_verify_report};
+use visualvault_lib::internals::models::{ActivityKind, AppState, VerifyIssueKind, VerifyReport};
+
+use super::App;
+
+impl App {
+    /// Starts re-hashing the destination folder against the library index in the background.
+    ///
+    /// The results view opens right away and fills in once the task finishes.
+    ///
+    /// # Errors
+    /// Returns an error if the config directory cannot be found.
+    pub async fn start_library_verify(&mut self) -> Result<()> {
+        let Some(library) = self.settings.read().await.destination_folder.clone() else {
+            self.error_message = Some("No destination folder configured".to_string());
+            return Ok(());
+        };
+        if self.verify_task.is_some() {
+            self.state = AppState::Verify;
+            return Ok(());
+        }
+
+        let config_dir = config_dir()?;
+        self.verify_task = Some(tokio::task::spawn_blocking(move || {
+            let mut index = LibraryIndex::load(&config_dir)?;
+            let report = index.verify(&library)?;
+            index.save()?;
+            Ok(report)
+        }));
+        self.verify_report = None;
+        self.verify_scroll = 0;
+        self.state = AppState::Verify;
+        self.success_message = Some("Verifying library...".to_string());
+        Ok(())
+    }
+
+    /// Collects the report of a finished verification task.
+    pub async fn check_verify_completion(&mut self) {
+        if !self
+            .verify_task
+            .as_ref()
+            .is_some_and(tokio::task::JoinHandle::is_finished)
+        {
+            return;
+        }
+        let Some(task) = self.verify_task.take() else {
+            return;
+        };
+
+        match task.await {
+            Ok(Ok(report)) => {
+                let message = summarize(&report);
+                self.log_activity(ActivityKind::Verify, message.clone());
+                if report.is_clean() {
+                    self.success_message = Some(message);
+                } else {
+                    self.error_message = Some(message);
+                }
+                self.verify_report = Some(report);
+            }
+            Ok(Err(e)) => self.handle_verify_error(&e),
+            Err(e) => self.handle_verify_error(&eyre!("Verify task failed: {}", e)),
+        }
+    }
+
+    /// Handles keyboard input in the library verification view.
+    ///
+    /// # Errors
+    /// Returns an error if re-running, exporting or accepting the report fails unexpectedly.
+    pub async fn handle_verify_keys(&mut self, key: KeyEvent) -> Result<()> {
+        let last = self
+            .verify_report
+            .as_ref()
+            .map_or(0, |report| report.issues.len().saturating_sub(1));
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => self.state = AppState::Dashboard,
+            KeyCode::Up => self.verify_scroll = self.verify_scroll.saturating_sub(1),
+            KeyCode::Down => self.verify_scroll = (self.verify_scroll + 1).min(last),
+            KeyCode::PageUp => self.verify_scroll = self.verify_scroll.saturating_sub(10),
+            KeyCode::PageDown => self.verify_scroll = (self.verify_scroll + 10).min(last),
+            KeyCode::Char('r') => self.start_library_verify().await?,
+            KeyCode::Char('e') => self.export_verify_report()?,
+            KeyCode::Char('a') => self.accept_verify_report().await?,
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn export_verify_report(&mut self) -> Result<()> {
+        let Some(report) = &self.verify_report else {
+            self.error_message = Some("No verification report to export".to_string());
+            return Ok(());
+        };
+
+        let path = config_dir()?
+            .join("visualvault")
+            .join("reports")
+            .join(format!("verify-{}.json", report.timestamp.format("%Y%m%d-%H%M%S")));
+        match export_verify_report(report, &path) {
+            Ok(()) => self.success_message = Some(format!("Report exported to {}", path.display())),
+            Err(e) => self.error_message = Some(format!("Failed to export report: {e}")),
+        }
+        Ok(())
+    }
+
+    /// Updates the index to the current state of the files listed in the report
+    async fn accept_verify_report(&mut self) -> Result<()> {
+        let Some(report) = self.verify_report.take_if(|report| !report.is_clean()) else {
+            self.error_message = Some("No changes to accept".to_string());
+            return Ok(());
+        };
+
+        let config_dir = config_dir()?;
+        let count = report.issues.len();
+        let result = tokio::task::spawn_blocking(move || {
+            let mut index = LibraryIndex::load(&config_dir)?;
+            index.accept(&report);
+            index.save()?;
+            Ok::<_, color_eyre::eyre::Report>(report)
+        })
+        .await?;
+
+        match result {
+            Ok(mut report) => {
+                report.issues.clear();
+                let message = format!("Accepted {count} changes into the library index");
+                self.log_activity(ActivityKind::Verify, message.clone());
+                self.success_message = Some(message);
+                self.verify_report = Some(report);
+                self.verify_scroll = 0;
+            }
+            Err(e) => self.error_message = Some(format!("Failed to update library index: {e}")),
+        }
+        Ok(())
+    }
+
+    fn handle_verify_error(&mut self, e: &color_eyre::eyre::Report) {
+        error!("Library verification failed: {}", e);
+        self.log_activity(ActivityKind::Error, format!("Library verification failed: {e}"));
+        self.record_operation_error(format!("Library verification failed: {e}"));
+        self.error_message = Some(format!("Library verification failed: {e}"));
+    }
+}
+
+fn config_dir() -> Result<PathBuf> {
+    dirs::config_dir().ok_or_else(|| eyre!("Could not find config directory"))
+}
+
+fn summarize(report: &VerifyReport) -> String {
+    if report.is_clean() {
+        return format!(
+            "Verified {} files in {}, all intact ({} newly indexed)",
+            report.checked,
+            report.library.display(),
+            report.added
+        );
+    }
+    format!(
+        "Verified {} files in {}: {} missing, {} modified, {} corrupted",
+        report.checked,
+        report.library.display(),
+        report.count(VerifyIssueKind::Missing),
+        report.count(VerifyIssueKind::Modified),
+        report.count(VerifyIssueKind::Corrupted)
+    )
+}
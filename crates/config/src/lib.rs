@@ -1,4 +1,35 @@
+mod profiles;
 mod settings;
+mod validation;
+mod watcher;
 
+pub use profiles::{DEFAULT_PROFILE, ProfileStore};
+
+pub use settings::AccessibilityMode;
+pub use settings::ChecksumSidecars;
+pub use settings::CloudExport;
+pub use settings::ConcurrencyMode;
+pub use settings::ConflictPolicy;
+pub use settings::ExtensionMismatchPolicy;
+pub use settings::FolderDepth;
+pub use settings::FolderRule;
+pub use settings::HashAlgorithm;
+pub use settings::ManifestMode;
+pub use settings::NonMediaPolicy;
+pub use settings::Notifications;
 pub use settings::OrganizationMode;
+pub use settings::OrganizeSchedule;
+pub use settings::PreviouslyOrganizedPolicy;
+pub use settings::Rclone;
+pub use settings::RenameContext;
+pub use settings::RenameTemplate;
+pub use settings::RoutingRule;
 pub use settings::Settings;
+pub use settings::SkipRules;
+pub use settings::StartupAction;
+pub use settings::Throttle;
+pub use settings::TimezonePolicy;
+pub use settings::TypeMapping;
+pub use settings::VideoSimilarity;
+pub use validation::{DiagnosticLevel, MAX_BUFFER_SIZE, SettingsDiagnostic};
+pub use watcher::SettingsWatcher;
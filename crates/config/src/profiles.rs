@@ -0,0 +1,260 @@
+use color_eyre::eyre::{Result, eyre};
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+use crate::Settings;
+
+/// Name shown for the settings in `config.toml`
+pub const DEFAULT_PROFILE: &str = "Default";
+
+const PROFILES_DIR: &str = "profiles";
+const ACTIVE_PROFILE_FILE: &str = "active_profile";
+
+/// Named sets of settings, each stored as its own TOML file.
+///
+/// The default profile lives in `visualvault/config.toml` and the others in
+/// `visualvault/profiles/<name>.toml`. The name of the profile in use is kept in
+/// `visualvault/active_profile`; without it the default profile is active.
+#[derive(Debug, Clone)]
+pub struct ProfileStore {
+    root: PathBuf,
+}
+
+impl ProfileStore {
+    /// Opens the profile store in the user's config directory.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the config directory cannot be found.
+    pub fn open() -> Result<Self> {
+        let config_dir = dirs::config_dir().ok_or_else(|| eyre!("Could not find config directory"))?;
+        Ok(Self::in_dir(&config_dir))
+    }
+
+    /// Opens the profile store below `config_dir`
+    #[must_use]
+    pub fn in_dir(config_dir: &Path) -> Self {
+        Self {
+            root: config_dir.join("visualvault"),
+        }
+    }
+
+    /// Lists all profile names, the default profile first and the rest sorted by name.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the profiles directory exists but cannot be read.
+    pub fn list(&self) -> Result<Vec<String>> {
+        let mut names = Vec::new();
+        let dir = self.root.join(PROFILES_DIR);
+        if dir.exists() {
+            for entry in std::fs::read_dir(dir)? {
+                let path = entry?.path();
+                if path.extension().is_some_and(|ext| ext == "toml") {
+                    if let Some(stem) = path.file_stem() {
+                        names.push(stem.to_string_lossy().to_string());
+                    }
+                }
+            }
+        }
+        names.sort_by_key(|name| name.to_lowercase());
+        names.insert(0, DEFAULT_PROFILE.to_string());
+        Ok(names)
+    }
+
+    /// Returns the name of the profile in use.
+    ///
+    /// Falls back to the default profile if the recorded profile no longer exists.
+    #[must_use]
+    pub fn active(&self) -> String {
+        std::fs::read_to_string(self.root.join(ACTIVE_PROFILE_FILE))
+            .ok()
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty() && self.settings_path(name).exists())
+            .unwrap_or_else(|| DEFAULT_PROFILE.to_string())
+    }
+
+    /// Makes `name` the profile used by [`Settings::load`] and [`Settings::save`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the profile does not exist or the choice cannot be written.
+    pub fn set_active(&self, name: &str) -> Result<()> {
+        let path = self.root.join(ACTIVE_PROFILE_FILE);
+        if is_default(name) {
+            if path.exists() {
+                std::fs::remove_file(path)?;
+            }
+            return Ok(());
+        }
+        if !self.settings_path(name).exists() {
+            return Err(eyre!("Profile '{}' does not exist", name));
+        }
+        std::fs::create_dir_all(&self.root)?;
+        std::fs::write(path, name)?;
+        info!("Switched to settings profile '{}'", name);
+        Ok(())
+    }
+
+    /// Creates a profile named `name` holding `settings`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the name is not usable as a file name, the profile already
+    /// exists, or the file cannot be written.
+    pub fn create(&self, name: &str, settings: &Settings) -> Result<()> {
+        let name = name.trim();
+        validate_name(name)?;
+        if self.settings_path(name).exists() {
+            return Err(eyre!("Profile '{}' already exists", name));
+        }
+        self.save(name, settings)
+    }
+
+    /// Deletes the profile named `name`, switching back to the default one if it was active.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `name` is the default profile or its file cannot be removed.
+    pub fn delete(&self, name: &str) -> Result<()> {
+        if is_default(name) {
+            return Err(eyre!("The default profile cannot be deleted"));
+        }
+        let was_active = self.active() == name;
+        std::fs::remove_file(self.settings_path(name))?;
+        if was_active {
+            self.set_active(DEFAULT_PROFILE)?;
+        }
+        Ok(())
+    }
+
+    /// Reads the settings of profile `name`, or defaults if its file does not exist yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file exists but cannot be read or parsed.
+    pub fn load(&self, name: &str) -> Result<Settings> {
+        let path = self.settings_path(name);
+        if path.exists() {
+            Ok(toml::from_str(&std::fs::read_to_string(path)?)?)
+        } else {
+            Ok(Settings::default())
+        }
+    }
+
+    /// Writes `settings` as profile `name`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the settings cannot be serialized or written.
+    pub fn save(&self, name: &str, settings: &Settings) -> Result<()> {
+        let path = self.settings_path(name);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, toml::to_string_pretty(settings)?)?;
+        info!("Settings saved to {:?}", path);
+        Ok(())
+    }
+
+    /// Path of the TOML file holding profile `name`
+    #[must_use]
+    pub fn settings_path(&self, name: &str) -> PathBuf {
+        if is_default(name) {
+            self.root.join("config.toml")
+        } else {
+            self.root.join(PROFILES_DIR).join(format!("{name}.toml"))
+        }
+    }
+}
+
+fn is_default(name: &str) -> bool {
+    name.eq_ignore_ascii_case(DEFAULT_PROFILE)
+}
+
+fn validate_name(name: &str) -> Result<()> {
+    if name.is_empty() {
+        return Err(eyre!("Profile name cannot be empty"));
+    }
+    if is_default(name) {
+        return Err(eyre!("'{}' is reserved for the default profile", DEFAULT_PROFILE));
+    }
+    if name.starts_with('.')
+        || name
+            .chars()
+            .any(|c| c.is_control() || matches!(c, '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|'))
+    {
+        return Err(eyre!(
+            "Profile name '{}' contains characters not allowed in file names",
+            name
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_default_profile_uses_config_toml() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = ProfileStore::in_dir(temp_dir.path());
+
+        assert_eq!(store.list().unwrap(), vec![DEFAULT_PROFILE]);
+        assert_eq!(store.active(), DEFAULT_PROFILE);
+        assert!(store.settings_path("default").ends_with("visualvault/config.toml"));
+    }
+
+    #[test]
+    fn test_create_switch_and_delete_profiles() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = ProfileStore::in_dir(temp_dir.path());
+
+        let phone = Settings {
+            source_folder: Some(PathBuf::from("/media/phone/DCIM")),
+            ..Settings::default()
+        };
+        store.create("Phone import", &phone).unwrap();
+        store.create("archive", &Settings::default()).unwrap();
+        assert!(store.create("archive", &Settings::default()).is_err());
+
+        assert_eq!(store.list().unwrap(), vec![DEFAULT_PROFILE, "archive", "Phone import"]);
+
+        store.set_active("Phone import").unwrap();
+        assert_eq!(store.active(), "Phone import");
+        assert_eq!(
+            store.load("Phone import").unwrap().source_folder,
+            Some(PathBuf::from("/media/phone/DCIM"))
+        );
+
+        store.delete("Phone import").unwrap();
+        assert_eq!(store.active(), DEFAULT_PROFILE);
+        assert_eq!(store.list().unwrap(), vec![DEFAULT_PROFILE, "archive"]);
+        assert!(store.delete(DEFAULT_PROFILE).is_err());
+    }
+
+    #[test]
+    fn test_switching_to_missing_profile_fails() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = ProfileStore::in_dir(temp_dir.path());
+
+        assert!(store.set_active("nope").is_err());
+        assert_eq!(store.active(), DEFAULT_PROFILE);
+    }
+
+    #[test]
+    fn test_profile_names_are_validated() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = ProfileStore::in_dir(temp_dir.path());
+        let settings = Settings::default();
+
+        for name in ["", "  ", "default", "../escape", "a/b", ".hidden", "what?"] {
+            assert!(store.create(name, &settings).is_err(), "{name:?} should be rejected");
+        }
+        assert!(store.create("Archive drive", &settings).is_ok());
+    }
+}
@@ -1,24 +1,42 @@
 #![allow(clippy::unwrap_used)]
 #![allow(clippy::field_reassign_with_default)]
 
+use chrono::{DateTime, FixedOffset, Local, NaiveTime, Offset, Utc};
 use color_eyre::eyre::Result;
+use regex::Regex;
 use serde::{Deserialize, Serialize};
-use std::{fmt, path::PathBuf, str::FromStr};
+use std::{
+    collections::BTreeMap,
+    fmt,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
 use tracing::info;
 
+use crate::ProfileStore;
+
 #[allow(clippy::struct_excessive_bools)]
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Settings {
     pub source_folder: Option<PathBuf>,
     pub destination_folder: Option<PathBuf>,
     #[serde(default = "default_recurse_subfolders")]
     pub recurse_subfolders: bool,
+    /// Deepest level of subfolders scanned below the source when recursing; every level when unset
+    #[serde(default)]
+    pub max_depth: Option<usize>,
+    /// Depth limits of particular folders, taking precedence over `max_depth` inside them
+    #[serde(default)]
+    pub folder_depths: Vec<FolderDepth>,
     #[serde(default)]
     pub verbose_output: bool,
     #[serde(default = "default_organize_by")]
     pub organize_by: String,
     #[serde(default)]
     pub separate_videos: bool,
+    /// Route detected screenshots into their own Screenshots folder
+    #[serde(default)]
+    pub separate_screenshots: bool,
     #[serde(default)]
     pub dry_run: bool,
     #[serde(default)]
@@ -31,6 +49,9 @@ pub struct Settings {
     pub preserve_metadata: bool,
     #[serde(default = "default_worker_threads")]
     pub worker_threads: usize,
+    /// Whether scans use `worker_threads` or pick their parallelism from a probe of the drive
+    #[serde(default)]
+    pub concurrency: ConcurrencyMode,
     #[serde(default = "default_buffer_size")]
     pub buffer_size: usize,
     #[serde(default = "default_enable_cache")]
@@ -43,8 +64,91 @@ pub struct Settings {
     pub optimize_for_ssd: bool,
     #[serde(default = "default_undo_enabled")]
     pub undo_enabled: bool,
+    #[serde(default)]
+    pub on_start: Vec<StartupAction>,
+    #[serde(default)]
+    pub conflict_policy: Option<ConflictPolicy>,
+    /// Move every placed file back when more than this percentage of an organize run fails
+    #[serde(default)]
+    pub rollback_threshold: Option<u8>,
+    #[serde(default)]
+    pub schedule: Option<OrganizeSchedule>,
+    #[serde(default)]
+    pub non_media_policy: NonMediaPolicy,
+    /// What happens to files whose content was organized into a destination before
+    #[serde(default)]
+    pub previously_organized: PreviouslyOrganizedPolicy,
+    #[serde(default = "default_audio_template")]
+    pub audio_template: String,
+    #[serde(default)]
+    pub rename_template: Option<RenameTemplate>,
+    #[serde(default)]
+    pub ignored_paths: Vec<PathBuf>,
+    #[serde(default)]
+    pub follow_symlinks: bool,
+    /// Check the header of scanned files, setting aside empty and unreadable ones and those without the header their extension promises instead of organizing them
+    #[serde(default)]
+    pub check_file_headers: bool,
+    /// Look inside zip and tar archives in the source for media that can be extracted
+    #[serde(default)]
+    pub inspect_archives: bool,
+    /// What happens to files whose content does not match their extension
+    #[serde(default)]
+    pub extension_mismatch: ExtensionMismatchPolicy,
+    /// Time zone file dates are read in to pick their year and month folders
+    #[serde(default)]
+    pub timezone_policy: TimezonePolicy,
+    /// Where organize runs write manifests of the files they placed
+    #[serde(default)]
+    pub manifest: ManifestMode,
+    /// Checksum files organize runs keep in every folder they place files in
+    #[serde(default)]
+    pub checksum_sidecars: ChecksumSidecars,
+    /// Where suspect files are quarantined; a Quarantine folder in the destination when unset
+    #[serde(default)]
+    pub quarantine_folder: Option<PathBuf>,
+    /// Scan results estimated larger than this many MiB are paged from disk; 0 keeps them all in memory
+    #[serde(default = "default_memory_budget_mb")]
+    pub memory_budget_mb: u64,
+    #[serde(default)]
+    pub throttle: Throttle,
+    /// Conditions that leave files in the source when organizing, such as a minimum size
+    #[serde(default)]
+    pub skip_rules: SkipRules,
+    #[serde(default)]
+    pub notifications: Notifications,
+    #[serde(default)]
+    pub cloud_export: CloudExport,
+    #[serde(default)]
+    pub rclone: Rclone,
+    /// Custom classifications by lowercase extension, taking precedence over the built-in ones
+    #[serde(default)]
+    pub type_mappings: BTreeMap<String, TypeMapping>,
+    /// Destinations for the files of source subfolders, checked in order before the
+    /// organization mode
+    #[serde(default)]
+    pub folder_rules: Vec<FolderRule>,
+    /// Destinations for files whose names match a pattern, checked in order before the folder
+    /// rules
+    #[serde(default)]
+    pub routing_rules: Vec<RoutingRule>,
+    /// Programs asked about each file's destination while organizing, in order; each may keep,
+    /// rewrite or veto it
+    #[serde(default)]
+    pub organize_hooks: Vec<PathBuf>,
+    /// Content hash used to find duplicates; cached hashes of another algorithm are recalculated
+    #[serde(default)]
+    pub hash_algorithm: HashAlgorithm,
+    #[serde(default)]
+    pub video_similarity: VideoSimilarity,
+    /// Whether the UI uses ASCII labels and high-contrast basic colors instead of emoji and RGB
+    #[serde(default)]
+    pub accessibility: AccessibilityMode,
 }
 
+/// Folder in the destination that suspect files are quarantined to unless configured otherwise
+const QUARANTINE_FOLDER: &str = "Quarantine";
+
 // Default value functions for serde
 const fn default_recurse_subfolders() -> bool {
     true
@@ -52,6 +156,9 @@ const fn default_recurse_subfolders() -> bool {
 fn default_organize_by() -> String {
     "monthly".to_string()
 }
+fn default_audio_template() -> String {
+    "{artist}/{album}".to_string()
+}
 const fn default_rename_duplicates() -> bool {
     true
 }
@@ -78,41 +185,77 @@ const fn default_undo_enabled() -> bool {
     true
 }
 
+const fn default_memory_budget_mb() -> u64 {
+    512
+}
+
 impl Default for Settings {
     fn default() -> Self {
         Self {
             source_folder: None,
             destination_folder: None,
             recurse_subfolders: default_recurse_subfolders(),
+            max_depth: None,
+            folder_depths: Vec::new(),
             verbose_output: false,
             organize_by: default_organize_by(),
             separate_videos: false,
+            separate_screenshots: false,
             dry_run: false,
             keep_original_structure: false,
             rename_duplicates: default_rename_duplicates(),
             lowercase_extensions: default_lowercase_extensions(),
             preserve_metadata: default_preserve_metadata(),
             worker_threads: default_worker_threads(),
+            concurrency: ConcurrencyMode::default(),
             buffer_size: default_buffer_size(),
             enable_cache: default_enable_cache(),
             parallel_processing: default_parallel_processing(),
             skip_hidden_files: false,
             optimize_for_ssd: false,
             undo_enabled: default_undo_enabled(),
+            on_start: Vec::new(),
+            conflict_policy: None,
+            rollback_threshold: None,
+            schedule: None,
+            non_media_policy: NonMediaPolicy::default(),
+            previously_organized: PreviouslyOrganizedPolicy::default(),
+            audio_template: default_audio_template(),
+            rename_template: None,
+            ignored_paths: Vec::new(),
+            follow_symlinks: false,
+            check_file_headers: false,
+            inspect_archives: false,
+            extension_mismatch: ExtensionMismatchPolicy::default(),
+            timezone_policy: TimezonePolicy::default(),
+            manifest: ManifestMode::default(),
+            checksum_sidecars: ChecksumSidecars::default(),
+            quarantine_folder: None,
+            memory_budget_mb: default_memory_budget_mb(),
+            throttle: Throttle::default(),
+            skip_rules: SkipRules::default(),
+            notifications: Notifications::default(),
+            cloud_export: CloudExport::default(),
+            rclone: Rclone::default(),
+            type_mappings: BTreeMap::new(),
+            folder_rules: Vec::new(),
+            routing_rules: Vec::new(),
+            organize_hooks: Vec::new(),
+            hash_algorithm: HashAlgorithm::default(),
+            video_similarity: VideoSimilarity::default(),
+            accessibility: AccessibilityMode::default(),
         }
     }
 }
 
 impl Settings {
-    /// Loads the settings from the configuration file, or returns defaults if the file does not exist.
+    /// Loads the settings of the active profile, or returns defaults if its file does not exist.
     ///
     /// # Errors
     ///
     /// Returns an error if the configuration directory cannot be found, or if reading or parsing the configuration file fails.
     pub async fn load() -> Result<Self> {
-        let config_dir =
-            dirs::config_dir().ok_or_else(|| color_eyre::eyre::eyre!("Could not find config directory"))?;
-        let config_path = config_dir.join("visualvault").join("config.toml");
+        let config_path = Self::config_path()?;
 
         if config_path.exists() {
             let content = tokio::fs::read_to_string(&config_path).await?;
@@ -123,7 +266,7 @@ impl Settings {
         }
     }
 
-    /// Saves the current settings to the configuration file.
+    /// Saves the current settings to the configuration file of the active profile.
     ///
     /// # Errors
     ///
@@ -150,46 +293,1615 @@ impl Settings {
         Ok(())
     }
 
+    /// Returns the policy used when an organized file's destination already exists.
+    ///
+    /// Without an explicit `conflict_policy`, name clashes are renamed when `rename_duplicates`
+    /// is enabled and overwritten otherwise.
+    #[must_use]
+    pub const fn effective_conflict_policy(&self) -> ConflictPolicy {
+        match self.conflict_policy {
+            Some(policy) => policy,
+            None if self.rename_duplicates => ConflictPolicy::Rename,
+            None => ConflictPolicy::OverwriteAlways,
+        }
+    }
+
+    /// Returns whether scans skip `path` because it or one of its folders is on the ignore list.
+    #[must_use]
+    pub fn is_ignored(&self, path: &Path) -> bool {
+        self.ignored_paths.iter().any(|ignored| path.starts_with(ignored))
+    }
+
+    /// Folder that suspect files are moved to, or `None` without it or a destination folder
+    #[must_use]
+    pub fn quarantine_path(&self) -> Option<PathBuf> {
+        self.quarantine_folder.clone().or_else(|| {
+            self.local_destination()
+                .map(|destination| destination.join(QUARANTINE_FOLDER))
+        })
+    }
+
+    /// The rclone remote the destination names, such as `gdrive:Photos`, or `None` for a folder.
+    ///
+    /// Like rclone itself, a destination starting with a name and a colon is taken as a remote;
+    /// local folders whose name contains a colon are written as `./photos:2024`.
+    #[must_use]
+    pub fn rclone_remote(&self) -> Option<&str> {
+        let destination = self.destination_folder.as_deref()?.to_str()?;
+        let (name, _) = destination.split_once(':')?;
+        let valid_name = !name.is_empty()
+            && !name.starts_with(['-', ' '])
+            && name
+                .chars()
+                .all(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | '+' | '@' | ' '));
+        // A single letter is a drive on Windows
+        (valid_name && !(cfg!(windows) && name.len() == 1)).then_some(destination)
+    }
+
+    /// Folder organize runs place files in: the destination folder, or the staging folder that
+    /// files are moved to an rclone remote from.
+    #[must_use]
+    pub fn local_destination(&self) -> Option<PathBuf> {
+        match self.rclone_remote() {
+            Some(remote) => Some(self.rclone.staging_path(remote)),
+            None => self.destination_folder.clone(),
+        }
+    }
+
+    /// Memory budgets offered when cycling `memory_budget_mb` in the settings view, 0 meaning unlimited
+    pub const MEMORY_BUDGET_PRESETS: [u64; 6] = [0, 256, 512, 1024, 2048, 4096];
+
+    /// Returns the preset memory budget following the current one, wrapping around to unlimited.
+    #[must_use]
+    pub fn next_memory_budget_mb(&self) -> u64 {
+        Self::MEMORY_BUDGET_PRESETS
+            .iter()
+            .copied()
+            .find(|&mb| mb > self.memory_budget_mb)
+            .unwrap_or(0)
+    }
+
+    /// Scan depths Space cycles through in Settings, after which only the source folder itself is scanned.
+    pub const SCAN_DEPTH_PRESETS: [Option<usize>; 5] = [None, Some(1), Some(2), Some(3), Some(5)];
+
+    /// Moves to the scan depth preset following the current one: every level, then 1, 2, 3 and
+    /// 5 levels of subfolders, then no subfolders, wrapping around to every level.
+    pub fn cycle_scan_depth(&mut self) {
+        let next = if self.recurse_subfolders {
+            Self::SCAN_DEPTH_PRESETS
+                .iter()
+                .position(|&depth| depth == self.max_depth)
+                .and_then(|index| Self::SCAN_DEPTH_PRESETS.get(index + 1))
+        } else {
+            Self::SCAN_DEPTH_PRESETS.first()
+        };
+        self.recurse_subfolders = next.is_some();
+        self.max_depth = next.copied().flatten();
+    }
+
+    /// Returns the levels of subfolders a scan of `root` enters, or `None` for every level.
+    ///
+    /// Folder overrides deeper inside `root` are not included.
+    #[must_use]
+    pub fn scan_depth(&self, root: &Path) -> Option<usize> {
+        if self.recurse_subfolders {
+            self.depth_limit(root, root).1
+        } else {
+            Some(0)
+        }
+    }
+
+    /// Returns whether a recursive scan of `root` enters its subfolder `dir`.
+    ///
+    /// The closest folder override containing `dir` decides, counting levels from the overridden
+    /// folder; without one, `max_depth` counts levels from `root`.
+    #[must_use]
+    pub fn scans_folder(&self, root: &Path, dir: &Path) -> bool {
+        let (base, limit) = self.depth_limit(root, dir);
+        let depth = dir.components().count().saturating_sub(base.components().count());
+        limit.is_none_or(|limit| depth <= limit)
+    }
+
+    fn depth_limit<'a>(&'a self, root: &'a Path, dir: &Path) -> (&'a Path, Option<usize>) {
+        self.folder_depths
+            .iter()
+            .filter(|folder| dir.starts_with(&folder.path))
+            .max_by_key(|folder| folder.path.components().count())
+            .map_or((root, self.max_depth), |folder| {
+                (folder.path.as_path(), folder.max_depth)
+            })
+    }
+
+    /// Returns the first enabled folder rule for a file at `path`.
+    ///
+    /// Relative rule folders are taken from the source folder, and a rule covers the folders
+    /// inside its folder too.
+    #[must_use]
+    pub fn folder_rule(&self, path: &Path) -> Option<&FolderRule> {
+        self.folder_rules.iter().filter(|rule| rule.enabled).find(|rule| {
+            match (&self.source_folder, rule.folder.is_relative()) {
+                (Some(source), true) => path.starts_with(source.join(&rule.folder)),
+                (None, true) => false,
+                (_, false) => path.starts_with(&rule.folder),
+            }
+        })
+    }
+
+    /// Moves the folder rule at `index` one place earlier or later, returning its new index,
+    /// or `None` when it is already first or last.
+    pub fn move_folder_rule(&mut self, index: usize, earlier: bool) -> Option<usize> {
+        move_rule(&mut self.folder_rules, index, earlier)
+    }
+
+    /// Replaces the folder rule at `index`, keeping whether it is enabled, or adds `rule` at
+    /// the end when there is no rule at `index`.
+    pub fn set_folder_rule(&mut self, index: usize, mut rule: FolderRule) {
+        if let Some(existing) = self.folder_rules.get_mut(index) {
+            rule.enabled = existing.enabled;
+            *existing = rule;
+        } else {
+            self.folder_rules.push(rule);
+        }
+    }
+
+    /// Returns the first enabled routing rule whose pattern matches `file_name`; rules with
+    /// patterns that are not valid never match.
+    #[must_use]
+    pub fn routing_rule(&self, file_name: &str) -> Option<&RoutingRule> {
+        self.routing_rules
+            .iter()
+            .filter(|rule| rule.enabled)
+            .find(|rule| rule.regex().is_ok_and(|regex| regex.is_match(file_name)))
+    }
+
+    /// Moves the routing rule at `index` one place earlier or later, returning its new index,
+    /// or `None` when it is already first or last.
+    pub fn move_routing_rule(&mut self, index: usize, earlier: bool) -> Option<usize> {
+        move_rule(&mut self.routing_rules, index, earlier)
+    }
+
+    /// Replaces the routing rule at `index`, keeping whether it is enabled, or adds `rule` at
+    /// the end when there is no rule at `index`.
+    pub fn set_routing_rule(&mut self, index: usize, mut rule: RoutingRule) {
+        if let Some(existing) = self.routing_rules.get_mut(index) {
+            rule.enabled = existing.enabled;
+            *existing = rule;
+        } else {
+            self.routing_rules.push(rule);
+        }
+    }
+
+    /// Returns the memory budget in bytes, or `None` when scan results always stay in memory.
+    #[must_use]
+    pub const fn memory_budget_bytes(&self) -> Option<u64> {
+        match self.memory_budget_mb {
+            0 => None,
+            mb => Some(mb.saturating_mul(1024 * 1024)),
+        }
+    }
+
+    /// Returns the custom classification of files with `extension`, if one is configured.
+    #[must_use]
+    pub fn type_mapping(&self, extension: &str) -> Option<TypeMapping> {
+        self.type_mappings.get(&normalize_extension(extension)).copied()
+    }
+
+    /// Maps `extension` to `mapping`, replacing any previous mapping of the extension.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the extension is empty or contains characters other than letters and digits.
+    pub fn set_type_mapping(&mut self, extension: &str, mapping: TypeMapping) -> Result<(), String> {
+        let extension = normalize_extension(extension);
+        if extension.is_empty() || !extension.chars().all(char::is_alphanumeric) {
+            return Err(format!("Invalid extension: {extension:?}"));
+        }
+        self.type_mappings.insert(extension, mapping);
+        Ok(())
+    }
+
+    /// Returns whether the active profile has a settings file, which it does not on first launch.
+    #[must_use]
+    pub fn is_saved() -> bool {
+        Self::config_path().is_ok_and(|path| path.exists())
+    }
+
     fn config_path() -> Result<PathBuf> {
-        let config_dir =
-            dirs::config_dir().ok_or_else(|| color_eyre::eyre::eyre!("Could not find config directory"))?;
-        Ok(config_dir.join("visualvault").join("config.toml"))
+        let store = ProfileStore::open()?;
+        Ok(store.settings_path(&store.active()))
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub enum OrganizationMode {
     Yearly,
+    #[default]
     Monthly,
     ByType,
 }
 
-impl FromStr for OrganizationMode {
+impl OrganizationMode {
+    /// Every mode, in display order.
+    pub const ALL: [Self; 3] = [Self::Yearly, Self::Monthly, Self::ByType];
+}
+
+impl FromStr for OrganizationMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "yearly" => Ok(Self::Yearly),
+            "monthly" => Ok(Self::Monthly),
+            "type" => Ok(Self::ByType),
+            _ => Err(format!("Unknown organization mode: {s}")),
+        }
+    }
+}
+
+impl fmt::Display for OrganizationMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Yearly => write!(f, "yearly"),
+            Self::Monthly => write!(f, "monthly"),
+            Self::ByType => write!(f, "type"),
+        }
+    }
+}
+
+/// An action the application runs automatically right after startup.
+///
+/// Actions are configured with `on_start` in the configuration file and run in order,
+/// each one waiting for the previous operation to finish.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StartupAction {
+    /// Scan the configured source folder.
+    Scan,
+    /// Detect duplicates among the scanned files and open the duplicate review.
+    Duplicates,
+}
+
+impl FromStr for StartupAction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "scan" => Ok(Self::Scan),
+            "duplicates" => Ok(Self::Duplicates),
+            _ => Err(format!("Unknown startup action: {s}")),
+        }
+    }
+}
+
+impl fmt::Display for StartupAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Scan => write!(f, "scan"),
+            Self::Duplicates => write!(f, "duplicates"),
+        }
+    }
+}
+
+/// Scan depth of one folder and the folders inside it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FolderDepth {
+    pub path: PathBuf,
+    /// Levels of subfolders scanned below `path`; every level when unset
+    #[serde(default)]
+    pub max_depth: Option<usize>,
+}
+
+/// Organizes the files of one source folder into folders of its own instead of by the
+/// organization mode, e.g. `WhatsApp Images` into `WhatsApp/{year}`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FolderRule {
+    /// Folder the rule covers with the folders inside it; relative to the source folder unless absolute
+    pub folder: PathBuf,
+    /// Folders below the destination, where `{year}`, `{month}` and `{day}` are the file's date
+    pub target: String,
+    #[serde(default = "default_rule_enabled")]
+    pub enabled: bool,
+}
+
+const fn default_rule_enabled() -> bool {
+    true
+}
+
+impl FolderRule {
+    /// Tokens that may appear in braces within a rule's target
+    pub const TOKENS: &'static [&'static str] = &["year", "month", "day"];
+
+    /// Checks that `target` names at least one folder and only uses known tokens.
+    ///
+    /// # Errors
+    ///
+    /// Returns a description of the first problem found.
+    pub fn check_target(target: &str) -> Result<(), String> {
+        if target.split(['/', '\\']).all(|segment| segment.trim().is_empty()) {
+            return Err("Rule target is empty; use e.g. WhatsApp/{year}".to_string());
+        }
+        let mut rest = target;
+        while let Some(open) = rest.find('{') {
+            let after = &rest[open + 1..];
+            let close = after
+                .find('}')
+                .ok_or_else(|| format!("Unmatched '{{' in rule target: {target}"))?;
+            let token = &after[..close];
+            if !Self::TOKENS.contains(&token) {
+                return Err(format!(
+                    "Unknown token {{{token}}}, expected {{year}}, {{month}} or {{day}}"
+                ));
+            }
+            rest = &after[close + 1..];
+        }
+        if rest.contains('}') {
+            return Err(format!("Unmatched '}}' in rule target: {target}"));
+        }
+        Ok(())
+    }
+
+    /// Folders below the destination for a file dated `date`, empty segments left out
+    #[must_use]
+    pub fn render(&self, date: &DateTime<FixedOffset>) -> Vec<String> {
+        render_rule_target(&self.target, date)
+    }
+}
+
+/// Parses `folder=target`, as typed in the Rules settings tab; the rule starts enabled
+impl FromStr for FolderRule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some((folder, target)) = s.split_once('=') else {
+            return Err("Enter a rule as folder=target, e.g. WhatsApp Images=WhatsApp/{year}".to_string());
+        };
+        let (folder, target) = (folder.trim(), target.trim());
+        if folder.is_empty() {
+            return Err("Rule folder is empty".to_string());
+        }
+        Self::check_target(target)?;
+        Ok(Self {
+            folder: PathBuf::from(folder),
+            target: target.to_string(),
+            enabled: true,
+        })
+    }
+}
+
+impl fmt::Display for FolderRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}={}", self.folder.display(), self.target)
+    }
+}
+
+/// Sends files whose names match a regular expression into folders of their own instead of by
+/// the organization mode, e.g. `^IMG-\d{8}-WA` into `WhatsApp`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoutingRule {
+    /// Regular expression searched for in the file name, case-sensitive unless it starts with `(?i)`
+    pub pattern: String,
+    /// Folders below the destination, with the same tokens as a [`FolderRule`] target
+    pub target: String,
+    #[serde(default = "default_rule_enabled")]
+    pub enabled: bool,
+}
+
+impl RoutingRule {
+    /// Compiles the rule's pattern.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the pattern is not a valid regular expression.
+    pub fn regex(&self) -> Result<Regex, regex::Error> {
+        Regex::new(&self.pattern)
+    }
+
+    /// Folders below the destination for a file dated `date`, empty segments left out
+    #[must_use]
+    pub fn render(&self, date: &DateTime<FixedOffset>) -> Vec<String> {
+        render_rule_target(&self.target, date)
+    }
+}
+
+/// Parses `pattern=target`, as typed in the Routing settings tab; the rule starts enabled.
+///
+/// The target follows the last `=`, so patterns may contain one.
+impl FromStr for RoutingRule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some((pattern, target)) = s.rsplit_once('=') else {
+            return Err("Enter a rule as pattern=target, e.g. ^IMG-\\d{8}-WA=WhatsApp".to_string());
+        };
+        let (pattern, target) = (pattern.trim(), target.trim());
+        if pattern.is_empty() {
+            return Err("Rule pattern is empty".to_string());
+        }
+        Regex::new(pattern).map_err(|e| format!("Invalid pattern {pattern}: {e}"))?;
+        FolderRule::check_target(target)?;
+        Ok(Self {
+            pattern: pattern.to_string(),
+            target: target.to_string(),
+            enabled: true,
+        })
+    }
+}
+
+impl fmt::Display for RoutingRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}={}", self.pattern, self.target)
+    }
+}
+
+/// Folders a rule `target` names for a file dated `date`, empty segments left out
+fn render_rule_target(target: &str, date: &DateTime<FixedOffset>) -> Vec<String> {
+    target
+        .split(['/', '\\'])
+        .map(|segment| {
+            segment
+                .replace("{year}", &date.format("%Y").to_string())
+                .replace("{month}", &date.format("%m").to_string())
+                .replace("{day}", &date.format("%d").to_string())
+                .trim()
+                .to_string()
+        })
+        .filter(|segment| !segment.is_empty())
+        .collect()
+}
+
+/// Moves the rule at `index` of `rules` one place earlier or later, returning its new index
+fn move_rule<T>(rules: &mut [T], index: usize, earlier: bool) -> Option<usize> {
+    let target = if earlier { index.checked_sub(1)? } else { index + 1 };
+    if target >= rules.len() || index >= rules.len() {
+        return None;
+    }
+    rules.swap(index, target);
+    Some(target)
+}
+
+/// What to do when an organized file's destination path is already taken.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ConflictPolicy {
+    /// Leave the source file where it is.
+    Skip,
+    /// Replace the existing file only when the incoming file was modified more recently.
+    OverwriteIfNewer,
+    /// Always replace the existing file.
+    OverwriteAlways,
+    /// Keep both files by adding a numeric suffix to the incoming one.
+    Rename,
+    /// Defer the decision to the user.
+    Ask,
+}
+
+impl ConflictPolicy {
+    /// Every policy, in display order.
+    pub const ALL: [Self; 5] = [
+        Self::Skip,
+        Self::OverwriteIfNewer,
+        Self::OverwriteAlways,
+        Self::Rename,
+        Self::Ask,
+    ];
+
+    /// Returns the next policy in display order, wrapping around.
+    #[must_use]
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Skip => Self::OverwriteIfNewer,
+            Self::OverwriteIfNewer => Self::OverwriteAlways,
+            Self::OverwriteAlways => Self::Rename,
+            Self::Rename => Self::Ask,
+            Self::Ask => Self::Skip,
+        }
+    }
+}
+
+impl FromStr for ConflictPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "skip" => Ok(Self::Skip),
+            "overwrite-if-newer" => Ok(Self::OverwriteIfNewer),
+            "overwrite-always" | "overwrite" => Ok(Self::OverwriteAlways),
+            "rename" => Ok(Self::Rename),
+            "ask" => Ok(Self::Ask),
+            _ => Err(format!("Unknown conflict policy: {s}")),
+        }
+    }
+}
+
+impl fmt::Display for ConflictPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Skip => write!(f, "skip"),
+            Self::OverwriteIfNewer => write!(f, "overwrite-if-newer"),
+            Self::OverwriteAlways => write!(f, "overwrite-always"),
+            Self::Rename => write!(f, "rename"),
+            Self::Ask => write!(f, "ask"),
+        }
+    }
+}
+
+/// The content hash used to detect duplicate files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HashAlgorithm {
+    /// Cryptographic SHA-256, the slowest but compatible with hashes from earlier versions.
+    #[default]
+    Sha256,
+    /// BLAKE3, cryptographic and hashed on several threads for large files.
+    Blake3,
+    /// XXH3, a fast non-cryptographic 128-bit hash.
+    Xxh3,
+}
+
+impl HashAlgorithm {
+    /// Returns the next algorithm in display order, wrapping around.
+    #[must_use]
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Sha256 => Self::Blake3,
+            Self::Blake3 => Self::Xxh3,
+            Self::Xxh3 => Self::Sha256,
+        }
+    }
+}
+
+impl FromStr for HashAlgorithm {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().replace('-', "").as_str() {
+            "sha256" => Ok(Self::Sha256),
+            "blake3" => Ok(Self::Blake3),
+            "xxh3" | "xxhash" | "xxhash3" => Ok(Self::Xxh3),
+            _ => Err(format!("Unknown hash algorithm: {s}")),
+        }
+    }
+}
+
+impl fmt::Display for HashAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Sha256 => write!(f, "sha256"),
+            Self::Blake3 => write!(f, "blake3"),
+            Self::Xxh3 => write!(f, "xxh3"),
+        }
+    }
+}
+
+/// How videos are compared when looking for re-encoded copies in the duplicate review.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VideoSimilarity {
+    /// Frames sampled evenly across each video, between 1 and 64
+    #[serde(default = "default_video_frames")]
+    pub frames: usize,
+    /// Percentage of matching frame signature bits for two videos to count as similar
+    #[serde(default = "default_video_threshold")]
+    pub threshold: u8,
+}
+
+const fn default_video_frames() -> usize {
+    8
+}
+const fn default_video_threshold() -> u8 {
+    90
+}
+
+impl Default for VideoSimilarity {
+    fn default() -> Self {
+        Self {
+            frames: default_video_frames(),
+            threshold: default_video_threshold(),
+        }
+    }
+}
+
+/// What happens to files that are neither images, videos nor documents when organizing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NonMediaPolicy {
+    /// Leave non-media files in the source folder.
+    Ignore,
+    /// Move non-media files into a flat `Unsorted` folder in the destination.
+    Unsorted,
+    /// Organize non-media files with the same rules as media files, e.g. under `Others` by type.
+    #[default]
+    Include,
+}
+
+impl NonMediaPolicy {
+    /// Returns the next policy in display order, wrapping around.
+    #[must_use]
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Ignore => Self::Unsorted,
+            Self::Unsorted => Self::Include,
+            Self::Include => Self::Ignore,
+        }
+    }
+}
+
+impl FromStr for NonMediaPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "ignore" => Ok(Self::Ignore),
+            "unsorted" => Ok(Self::Unsorted),
+            "include" => Ok(Self::Include),
+            _ => Err(format!("Unknown non-media policy: {s}")),
+        }
+    }
+}
+
+impl fmt::Display for NonMediaPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Ignore => write!(f, "ignore"),
+            Self::Unsorted => write!(f, "unsorted"),
+            Self::Include => write!(f, "include"),
+        }
+    }
+}
+
+/// What happens to files whose content is already in the organized ledger.
+///
+/// Unless off, every file placed in a destination is recorded in the ledger, so later imports
+/// from the same card or folder only bring in the new files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PreviouslyOrganizedPolicy {
+    /// Neither record organized files nor look them up.
+    #[default]
+    Off,
+    /// Leave previously organized files in the source folder.
+    Skip,
+    /// Remove previously organized files from the source folder.
+    Delete,
+}
+
+impl PreviouslyOrganizedPolicy {
+    /// Returns the next policy in display order, wrapping around.
+    #[must_use]
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Off => Self::Skip,
+            Self::Skip => Self::Delete,
+            Self::Delete => Self::Off,
+        }
+    }
+
+    /// Whether organized files are recorded and looked up at all
+    #[must_use]
+    pub const fn is_enabled(self) -> bool {
+        !matches!(self, Self::Off)
+    }
+}
+
+impl FromStr for PreviouslyOrganizedPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "off" => Ok(Self::Off),
+            "skip" => Ok(Self::Skip),
+            "delete" => Ok(Self::Delete),
+            _ => Err(format!("Unknown previously organized policy: {s}")),
+        }
+    }
+}
+
+impl fmt::Display for PreviouslyOrganizedPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Off => write!(f, "off"),
+            Self::Skip => write!(f, "skip"),
+            Self::Delete => write!(f, "delete"),
+        }
+    }
+}
+
+/// What happens to files whose content is in another known format than their extension
+/// promises, such as a PNG image named `.jpg`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExtensionMismatchPolicy {
+    /// Keep the files in the scan results, flagged in the dashboard.
+    #[default]
+    Flag,
+    /// Flag the files and give them the extension of their content when organizing.
+    Fix,
+    /// Set the files aside with the other suspect files.
+    Quarantine,
+}
+
+impl ExtensionMismatchPolicy {
+    /// Returns the next policy in display order, wrapping around.
+    #[must_use]
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Flag => Self::Fix,
+            Self::Fix => Self::Quarantine,
+            Self::Quarantine => Self::Flag,
+        }
+    }
+}
+
+impl FromStr for ExtensionMismatchPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "flag" => Ok(Self::Flag),
+            "fix" => Ok(Self::Fix),
+            "quarantine" => Ok(Self::Quarantine),
+            _ => Err(format!("Unknown extension mismatch policy: {s}")),
+        }
+    }
+}
+
+impl fmt::Display for ExtensionMismatchPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Flag => write!(f, "flag"),
+            Self::Fix => write!(f, "fix"),
+            Self::Quarantine => write!(f, "quarantine"),
+        }
+    }
+}
+
+/// Where organize runs write manifests listing the files they placed, their original paths
+/// and hashes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ManifestMode {
+    /// Write no manifests.
+    #[default]
+    Off,
+    /// One manifest in the destination folder for the whole library.
+    Root,
+    /// One manifest in every folder files were placed in.
+    Folders,
+}
+
+impl ManifestMode {
+    /// Returns the next mode in display order, wrapping around.
+    #[must_use]
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Off => Self::Root,
+            Self::Root => Self::Folders,
+            Self::Folders => Self::Off,
+        }
+    }
+}
+
+impl FromStr for ManifestMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "off" => Ok(Self::Off),
+            "root" => Ok(Self::Root),
+            "folders" => Ok(Self::Folders),
+            _ => Err(format!("Unknown manifest mode: {s}")),
+        }
+    }
+}
+
+impl fmt::Display for ManifestMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Off => write!(f, "off"),
+            Self::Root => write!(f, "root"),
+            Self::Folders => write!(f, "folders"),
+        }
+    }
+}
+
+/// Checksum files organize runs keep next to the files they place, so other tools can verify
+/// the library later.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChecksumSidecars {
+    /// Write no checksum files.
+    #[default]
+    Off,
+    /// A `SHA256SUMS` file as written by `sha256sum`.
+    Sha256Sums,
+    /// A `checksums.sfv` file with CRC32 checksums.
+    Sfv,
+}
+
+impl ChecksumSidecars {
+    /// Returns the next format in display order, wrapping around.
+    #[must_use]
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Off => Self::Sha256Sums,
+            Self::Sha256Sums => Self::Sfv,
+            Self::Sfv => Self::Off,
+        }
+    }
+}
+
+impl FromStr for ChecksumSidecars {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "off" => Ok(Self::Off),
+            "sha256sums" => Ok(Self::Sha256Sums),
+            "sfv" => Ok(Self::Sfv),
+            _ => Err(format!("Unknown checksum sidecar format: {s}")),
+        }
+    }
+}
+
+impl fmt::Display for ChecksumSidecars {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Off => write!(f, "off"),
+            Self::Sha256Sums => write!(f, "SHA256SUMS"),
+            Self::Sfv => write!(f, "SFV"),
+        }
+    }
+}
+
+/// Whether the UI swaps emoji for ASCII labels and RGB colors for high-contrast basic ones,
+/// for terminals and screen readers that handle them poorly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AccessibilityMode {
+    /// On when `NO_COLOR` is set or `TERM` names a terminal without emoji or true color.
+    #[default]
+    Auto,
+    On,
+    Off,
+}
+
+impl AccessibilityMode {
+    /// Returns the next mode in display order, wrapping around.
+    #[must_use]
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Auto => Self::On,
+            Self::On => Self::Off,
+            Self::Off => Self::Auto,
+        }
+    }
+
+    /// Returns whether the accessible UI is used, given the `TERM` and `NO_COLOR` variables.
+    #[must_use]
+    pub fn is_on(self, term: Option<&str>, no_color: Option<&str>) -> bool {
+        match self {
+            Self::On => true,
+            Self::Off => false,
+            Self::Auto => {
+                // https://no-color.org: set to any non-empty value
+                let no_color = no_color.is_some_and(|value| !value.is_empty());
+                // The Linux console and serial terminals draw neither emoji nor RGB colors
+                let basic_term = term.is_some_and(|term| matches!(term, "dumb" | "linux") || term.starts_with("vt"));
+                no_color || basic_term
+            }
+        }
+    }
+}
+
+impl FromStr for AccessibilityMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "auto" => Ok(Self::Auto),
+            "on" => Ok(Self::On),
+            "off" => Ok(Self::Off),
+            _ => Err(format!("Unknown accessibility mode: {s}")),
+        }
+    }
+}
+
+impl fmt::Display for AccessibilityMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Auto => write!(f, "auto"),
+            Self::On => write!(f, "on"),
+            Self::Off => write!(f, "off"),
+        }
+    }
+}
+
+/// How many files scans read and hash at once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConcurrencyMode {
+    /// `worker_threads` files are read at once and hashed one at a time.
+    #[default]
+    Fixed,
+    /// Sample reads at the start of each scan tell SSDs, hard drives and network shares
+    /// apart, and reading and hashing are spread over as many workers as suit the drive.
+    Auto,
+}
+
+impl ConcurrencyMode {
+    /// Returns the next mode in display order, wrapping around.
+    #[must_use]
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Fixed => Self::Auto,
+            Self::Auto => Self::Fixed,
+        }
+    }
+}
+
+impl FromStr for ConcurrencyMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "fixed" => Ok(Self::Fixed),
+            "auto" => Ok(Self::Auto),
+            _ => Err(format!("Unknown concurrency mode: {s}")),
+        }
+    }
+}
+
+impl fmt::Display for ConcurrencyMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Fixed => write!(f, "fixed"),
+            Self::Auto => write!(f, "auto"),
+        }
+    }
+}
+
+/// Time zone in which the modification times of files are read to pick their date folders.
+///
+/// Stored as `local`, `exif` or a UTC offset such as `+09:00`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum TimezonePolicy {
+    /// The time zone of this computer.
+    #[default]
+    Local,
+    /// The UTC offset a photo's EXIF data records for its capture, or local time without one.
+    Exif,
+    /// A fixed UTC offset, e.g. the time zone of a trip the photos were taken on.
+    Fixed(FixedOffset),
+}
+
+impl TimezonePolicy {
+    /// Furthest UTC offsets in use, in hours
+    const OFFSET_HOURS: (i32, i32) = (-12, 14);
+
+    /// Returns the next policy in display order, wrapping around: local, EXIF, then every
+    /// whole-hour offset from UTC-12 to UTC+14.
+    #[must_use]
+    pub fn next(self) -> Self {
+        let (first, last) = Self::OFFSET_HOURS;
+        let hours = match self {
+            Self::Local => return Self::Exif,
+            Self::Exif => first,
+            Self::Fixed(offset) => offset.local_minus_utc().div_euclid(3600) + 1,
+        };
+        if hours > last {
+            return Self::Local;
+        }
+        FixedOffset::east_opt(hours * 3600).map_or(Self::Local, Self::Fixed)
+    }
+
+    /// Describes the policy for people, e.g. "EXIF offset" or "UTC+09:00"
+    #[must_use]
+    pub fn label(self) -> String {
+        match self {
+            Self::Local => "local time".to_string(),
+            Self::Exif => "EXIF offset".to_string(),
+            Self::Fixed(offset) => format!("UTC{offset}"),
+        }
+    }
+}
+
+impl FromStr for TimezonePolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "local" => Ok(Self::Local),
+            "exif" => Ok(Self::Exif),
+            "utc" | "z" => Ok(Self::Fixed(Utc.fix())),
+            offset => offset
+                .strip_prefix("utc")
+                .unwrap_or(offset)
+                .parse::<FixedOffset>()
+                .map(Self::Fixed)
+                .map_err(|_| format!("Unknown time zone policy: {s} (use local, exif or an offset such as +09:00)")),
+        }
+    }
+}
+
+impl TryFrom<String> for TimezonePolicy {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<TimezonePolicy> for String {
+    fn from(policy: TimezonePolicy) -> Self {
+        policy.to_string()
+    }
+}
+
+impl fmt::Display for TimezonePolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Local => write!(f, "local"),
+            Self::Exif => write!(f, "exif"),
+            Self::Fixed(offset) => write!(f, "{offset}"),
+        }
+    }
+}
+
+/// When the application scans and organizes automatically while it is running.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OrganizeSchedule {
+    /// Every given number of hours, counted from startup.
+    EveryHours(u32),
+    /// Once a day at the given local time.
+    DailyAt(NaiveTime),
+}
+
+impl OrganizeSchedule {
+    /// Returns the preset following `current` when cycling through schedules in the settings view.
+    ///
+    /// `None` stands for "no schedule" and is part of the cycle.
+    #[must_use]
+    pub fn next_preset(current: Option<Self>) -> Option<Self> {
+        let nightly = NaiveTime::from_hms_opt(3, 0, 0).unwrap_or(NaiveTime::MIN);
+        match current {
+            None => Some(Self::EveryHours(1)),
+            Some(Self::EveryHours(hours)) if hours < 6 => Some(Self::EveryHours(6)),
+            Some(Self::EveryHours(hours)) if hours < 12 => Some(Self::EveryHours(12)),
+            Some(Self::EveryHours(hours)) if hours < 24 => Some(Self::EveryHours(24)),
+            Some(Self::EveryHours(_)) => Some(Self::DailyAt(nightly)),
+            Some(Self::DailyAt(_)) => None,
+        }
+    }
+}
+
+impl FromStr for OrganizeSchedule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().to_lowercase();
+        if let Some(hours) = s.strip_prefix("every ").and_then(|rest| rest.strip_suffix('h')) {
+            return match hours.trim().parse::<u32>() {
+                Ok(hours) if hours > 0 => Ok(Self::EveryHours(hours)),
+                _ => Err(format!("Invalid schedule interval: {s}")),
+            };
+        }
+        if let Some(time) = s.strip_prefix("daily at ") {
+            return NaiveTime::parse_from_str(time.trim(), "%H:%M")
+                .map(Self::DailyAt)
+                .map_err(|_| format!("Invalid schedule time: {s}"));
+        }
+        Err(format!("Unknown schedule: {s}"))
+    }
+}
+
+impl fmt::Display for OrganizeSchedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EveryHours(hours) => write!(f, "every {hours}h"),
+            Self::DailyAt(time) => write!(f, "daily at {}", time.format("%H:%M")),
+        }
+    }
+}
+
+/// Limits on how hard scans and organize runs use the drives, so they can run in the
+/// background of a NAS or a disk shared with other work.
+///
+/// Both limits are off when 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct Throttle {
+    /// Most MB read or copied per second
+    #[serde(default)]
+    pub max_mb_per_sec: u64,
+    /// Most files opened, read or moved per second
+    #[serde(default)]
+    pub max_iops: u64,
+}
+
+impl Throttle {
+    /// Throughput limits offered when cycling `max_mb_per_sec` in the settings view
+    pub const MB_PER_SEC_PRESETS: [u64; 6] = [0, 10, 25, 50, 100, 250];
+    /// Operation limits offered when cycling `max_iops` in the settings view
+    pub const IOPS_PRESETS: [u64; 6] = [0, 25, 50, 100, 250, 1000];
+
+    /// Returns whether either limit is set.
+    #[must_use]
+    pub const fn is_enabled(&self) -> bool {
+        self.max_mb_per_sec > 0 || self.max_iops > 0
+    }
+
+    /// Returns the preset throughput limit following the current one, wrapping around to unlimited.
+    #[must_use]
+    pub fn next_mb_per_sec(&self) -> u64 {
+        next_preset(&Self::MB_PER_SEC_PRESETS, self.max_mb_per_sec)
+    }
+
+    /// Returns the preset operation limit following the current one, wrapping around to unlimited.
+    #[must_use]
+    pub fn next_iops(&self) -> u64 {
+        next_preset(&Self::IOPS_PRESETS, self.max_iops)
+    }
+}
+
+impl fmt::Display for Throttle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.max_mb_per_sec, self.max_iops) {
+            (0, 0) => write!(f, "unlimited"),
+            (mb, 0) => write!(f, "{mb} MB/s"),
+            (0, iops) => write!(f, "{iops} IOPS"),
+            (mb, iops) => write!(f, "{mb} MB/s, {iops} IOPS"),
+        }
+    }
+}
+
+/// Files organize runs leave in the source, such as thumbnails or photos still being edited.
+///
+/// Every condition is off when 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct SkipRules {
+    /// Files smaller than this many KB are skipped
+    #[serde(default)]
+    pub min_size_kb: u64,
+    /// Images narrower than this are skipped, measured along their longer side
+    #[serde(default)]
+    pub min_width: u32,
+    /// Images lower than this are skipped, measured along their shorter side
+    #[serde(default)]
+    pub min_height: u32,
+    /// Files modified fewer than this many days ago are skipped
+    #[serde(default)]
+    pub min_age_days: u64,
+}
+
+impl SkipRules {
+    /// Minimum sizes offered when cycling `min_size_kb` in the settings view
+    pub const MIN_SIZE_KB_PRESETS: [u64; 5] = [0, 10, 50, 100, 500];
+    /// Minimum resolutions offered when cycling `min_width` and `min_height` in the settings view
+    pub const RESOLUTION_PRESETS: [(u32, u32); 5] = [(0, 0), (320, 240), (640, 480), (1280, 720), (1920, 1080)];
+    /// Minimum ages offered when cycling `min_age_days` in the settings view
+    pub const MIN_AGE_DAYS_PRESETS: [u64; 5] = [0, 1, 3, 7, 30];
+
+    /// Returns whether any condition is set.
+    #[must_use]
+    pub const fn is_enabled(&self) -> bool {
+        self.min_size_kb > 0 || self.has_min_resolution() || self.min_age_days > 0
+    }
+
+    /// Returns whether images are checked against a minimum resolution.
+    #[must_use]
+    pub const fn has_min_resolution(&self) -> bool {
+        self.min_width > 0 || self.min_height > 0
+    }
+
+    /// Returns whether an image of `width` by `height` pixels, in either orientation, is below
+    /// the minimum resolution.
+    #[must_use]
+    pub fn below_min_resolution(&self, width: u32, height: u32) -> bool {
+        width.max(height) < self.min_width || width.min(height) < self.min_height
+    }
+
+    /// Returns the preset minimum size following the current one, wrapping around to off.
+    #[must_use]
+    pub fn next_min_size_kb(&self) -> u64 {
+        next_preset(&Self::MIN_SIZE_KB_PRESETS, self.min_size_kb)
+    }
+
+    /// Returns the preset minimum resolution following the current one, wrapping around to off.
+    #[must_use]
+    pub fn next_min_resolution(&self) -> (u32, u32) {
+        Self::RESOLUTION_PRESETS
+            .iter()
+            .copied()
+            .find(|&(width, _)| width > self.min_width)
+            .unwrap_or((0, 0))
+    }
+
+    /// Returns the preset minimum age following the current one, wrapping around to off.
+    #[must_use]
+    pub fn next_min_age_days(&self) -> u64 {
+        next_preset(&Self::MIN_AGE_DAYS_PRESETS, self.min_age_days)
+    }
+}
+
+fn next_preset(presets: &[u64], current: u64) -> u64 {
+    presets.iter().copied().find(|&preset| preset > current).unwrap_or(0)
+}
+
+/// Where to report finished scans, organize runs and duplicate cleanups.
+///
+/// Both targets are optional and only used when set, so home servers can react to
+/// `VisualVault` runs, for example through Home Assistant automations.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Notifications {
+    /// `http://` URL receiving each report as a JSON `POST`
+    #[serde(default)]
+    pub webhook_url: Option<String>,
+    /// MQTT broker as `host` or `host:port`, port 1883 when left out. An `mqtts://` prefix
+    /// connects over TLS, on port 8883 unless given.
+    #[serde(default)]
+    pub mqtt_broker: Option<String>,
+    #[serde(default = "default_mqtt_topic")]
+    pub mqtt_topic: String,
+    #[serde(default)]
+    pub mqtt_username: Option<String>,
+    #[serde(default)]
+    pub mqtt_password: Option<String>,
+    /// MQTT `QoS` level reports are published with: 0, 1 or 2
+    #[serde(default = "default_mqtt_qos")]
+    pub mqtt_qos: u8,
+    /// Shows a desktop notification when a long operation finishes while the terminal is unfocused
+    #[serde(default)]
+    pub desktop: bool,
+    /// Shortest operation, in seconds, that triggers a desktop notification
+    #[serde(default = "default_desktop_after_secs")]
+    pub desktop_after_secs: u64,
+}
+
+fn default_mqtt_topic() -> String {
+    "visualvault/events".to_string()
+}
+const fn default_mqtt_qos() -> u8 {
+    1
+}
+const fn default_desktop_after_secs() -> u64 {
+    30
+}
+
+impl Default for Notifications {
+    fn default() -> Self {
+        Self {
+            webhook_url: None,
+            mqtt_broker: None,
+            mqtt_topic: default_mqtt_topic(),
+            mqtt_username: None,
+            mqtt_password: None,
+            mqtt_qos: default_mqtt_qos(),
+            desktop: false,
+            desktop_after_secs: default_desktop_after_secs(),
+        }
+    }
+}
+
+impl Notifications {
+    /// Thresholds offered when cycling `desktop_after_secs` in the settings view
+    pub const DESKTOP_AFTER_PRESETS: [u64; 5] = [10, 30, 60, 300, 900];
+
+    /// Returns whether a webhook or MQTT broker is configured.
+    #[must_use]
+    pub const fn is_enabled(&self) -> bool {
+        self.webhook_url.is_some() || self.mqtt_broker.is_some()
+    }
+
+    /// Returns the preset threshold following the current one, wrapping around.
+    #[must_use]
+    pub fn next_desktop_after_secs(&self) -> u64 {
+        Self::DESKTOP_AFTER_PRESETS
+            .iter()
+            .copied()
+            .find(|&secs| secs > self.desktop_after_secs)
+            .unwrap_or(Self::DESKTOP_AFTER_PRESETS[0])
+    }
+}
+
+/// S3-compatible bucket that the files placed by each organize run are uploaded to.
+///
+/// Objects are addressed path-style (`endpoint/bucket/key`), which AWS, `MinIO`, Backblaze B2,
+/// Wasabi and Cloudflare R2 all accept.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CloudExport {
+    /// Service URL such as `https://s3.eu-north-1.amazonaws.com` or `http://nas.local:9000`
+    #[serde(default)]
+    pub endpoint: Option<String>,
+    #[serde(default)]
+    pub bucket: String,
+    #[serde(default = "default_cloud_region")]
+    pub region: String,
+    /// Access key ID, read from `AWS_ACCESS_KEY_ID` when unset
+    #[serde(default)]
+    pub access_key: Option<String>,
+    /// Secret access key, read from `AWS_SECRET_ACCESS_KEY` when unset
+    #[serde(default)]
+    pub secret_key: Option<String>,
+    /// Put in front of the path of each file below the destination to form its object key
+    #[serde(default)]
+    pub prefix: String,
+}
+
+fn default_cloud_region() -> String {
+    "us-east-1".to_string()
+}
+
+impl Default for CloudExport {
+    fn default() -> Self {
+        Self {
+            endpoint: None,
+            bucket: String::new(),
+            region: default_cloud_region(),
+            access_key: None,
+            secret_key: None,
+            prefix: String::new(),
+        }
+    }
+}
+
+impl CloudExport {
+    /// Returns whether an endpoint and bucket are configured.
+    #[must_use]
+    pub fn is_enabled(&self) -> bool {
+        self.endpoint.is_some() && !self.bucket.is_empty()
+    }
+}
+
+/// How organize runs reach an rclone remote given as the destination folder.
+///
+/// Files are organized into a local staging folder first and then moved to the remote by the
+/// `rclone` binary, using the remotes set up with `rclone config`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Rclone {
+    /// The rclone executable, looked up on the `PATH` unless it is a path
+    #[serde(default = "default_rclone_binary")]
+    pub binary: String,
+    /// Where files wait for the transfer; a folder in the cache directory when unset
+    #[serde(default)]
+    pub staging_folder: Option<PathBuf>,
+}
+
+fn default_rclone_binary() -> String {
+    "rclone".to_string()
+}
+
+impl Default for Rclone {
+    fn default() -> Self {
+        Self {
+            binary: default_rclone_binary(),
+            staging_folder: None,
+        }
+    }
+}
+
+impl Rclone {
+    /// Staging folder of `remote`, named after it so each remote keeps its own files
+    #[must_use]
+    pub fn staging_path(&self, remote: &str) -> PathBuf {
+        let folder: String = remote
+            .chars()
+            .map(|c| {
+                if c.is_alphanumeric() || matches!(c, '-' | '.') {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        self.staging_folder
+            .clone()
+            .unwrap_or_else(|| {
+                dirs::cache_dir()
+                    .unwrap_or_else(std::env::temp_dir)
+                    .join("visualvault")
+                    .join("rclone-staging")
+            })
+            .join(folder)
+    }
+}
+
+/// How files with a custom-mapped extension are classified when scanning.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TypeMapping {
+    Image,
+    Video,
+    Audio,
+    Document,
+    Other,
+    /// Leave files with the extension out of scans entirely.
+    Ignore,
+}
+
+impl TypeMapping {
+    /// Returns the next mapping in display order, wrapping around.
+    #[must_use]
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Image => Self::Video,
+            Self::Video => Self::Audio,
+            Self::Audio => Self::Document,
+            Self::Document => Self::Other,
+            Self::Other => Self::Ignore,
+            Self::Ignore => Self::Image,
+        }
+    }
+
+    /// Returns whether files mapped this way are scanned as media outside "by type" organizing.
+    #[must_use]
+    pub const fn is_media(self) -> bool {
+        matches!(self, Self::Image | Self::Video | Self::Audio)
+    }
+}
+
+impl FromStr for TypeMapping {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "image" => Ok(Self::Image),
+            "video" => Ok(Self::Video),
+            "audio" => Ok(Self::Audio),
+            "document" => Ok(Self::Document),
+            "other" => Ok(Self::Other),
+            "ignore" => Ok(Self::Ignore),
+            _ => Err(format!(
+                "Unknown file type: {s}, expected image, video, audio, document, other or ignore"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for TypeMapping {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Image => write!(f, "image"),
+            Self::Video => write!(f, "video"),
+            Self::Audio => write!(f, "audio"),
+            Self::Document => write!(f, "document"),
+            Self::Other => write!(f, "other"),
+            Self::Ignore => write!(f, "ignore"),
+        }
+    }
+}
+
+/// Lowercases an extension and strips a leading dot, so `.INSV` and `insv` map the same way
+fn normalize_extension(extension: &str) -> String {
+    extension.trim().trim_start_matches('.').to_lowercase()
+}
+
+/// A file name pattern applied while organizing, such as `{date}_{time}_{original}`.
+///
+/// The original extension is always kept. Templates are validated when parsed, so only
+/// known tokens and balanced braces are accepted.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct RenameTemplate(String);
+
+/// Values substituted into a [`RenameTemplate`] for one file
+#[derive(Debug, Clone, Copy)]
+pub struct RenameContext<'a> {
+    pub modified: DateTime<Local>,
+    /// The original file name without its extension
+    pub original: &'a str,
+    pub camera: Option<&'a str>,
+    /// Position of the file within the organize run, starting at 1
+    pub counter: usize,
+}
+
+impl RenameTemplate {
+    /// Tokens that may appear in braces within a template
+    pub const TOKENS: &'static [&'static str] =
+        &["date", "time", "year", "month", "day", "original", "camera", "counter"];
+
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns whether the template contains `{token}`
+    #[must_use]
+    pub fn uses(&self, token: &str) -> bool {
+        self.0.contains(&format!("{{{token}}}"))
+    }
+
+    /// Renders the new file name without extension.
+    ///
+    /// A missing camera becomes "Unknown" and the counter is padded to four digits.
+    /// Characters that are not allowed in file names are replaced with underscores.
+    #[must_use]
+    pub fn render(&self, context: &RenameContext<'_>) -> String {
+        let rendered = self
+            .0
+            .replace("{date}", &context.modified.format("%Y-%m-%d").to_string())
+            .replace("{time}", &context.modified.format("%H%M%S").to_string())
+            .replace("{year}", &context.modified.format("%Y").to_string())
+            .replace("{month}", &context.modified.format("%m").to_string())
+            .replace("{day}", &context.modified.format("%d").to_string())
+            .replace("{original}", context.original)
+            .replace("{camera}", context.camera.unwrap_or("Unknown"))
+            .replace("{counter}", &format!("{:04}", context.counter));
+
+        rendered
+            .chars()
+            .map(|c| match c {
+                '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+                c if c.is_control() => '_',
+                c => c,
+            })
+            .collect::<String>()
+            .trim()
+            .to_string()
+    }
+}
+
+impl FromStr for RenameTemplate {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.to_lowercase().as_str() {
-            "yearly" => Ok(Self::Yearly),
-            "monthly" => Ok(Self::Monthly),
-            "type" => Ok(Self::ByType),
-            _ => Err(format!("Unknown organization mode: {s}")),
+        let template = s.trim();
+        if template.is_empty() {
+            return Err("Rename template is empty".to_string());
+        }
+        if template.contains(['/', '\\']) {
+            return Err("Rename template must not contain path separators".to_string());
+        }
+
+        let mut rest = template;
+        let mut has_token = false;
+        while let Some(open) = rest.find(['{', '}']) {
+            if rest[open..].starts_with('}') {
+                return Err(format!("Unmatched '}}' in rename template: {template}"));
+            }
+            let after = &rest[open + 1..];
+            let close = after
+                .find(['{', '}'])
+                .filter(|&close| after[close..].starts_with('}'))
+                .ok_or_else(|| format!("Unmatched '{{' in rename template: {template}"))?;
+            let token = &after[..close];
+            if !Self::TOKENS.contains(&token) {
+                return Err(format!(
+                    "Unknown token {{{token}}}, expected one of: {}",
+                    Self::TOKENS
+                        .iter()
+                        .map(|t| format!("{{{t}}}"))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ));
+            }
+            has_token = true;
+            rest = &after[close + 1..];
+        }
+
+        if !has_token {
+            return Err("Rename template needs at least one token, e.g. {original}".to_string());
         }
+        Ok(Self(template.to_string()))
     }
 }
 
-impl Default for OrganizationMode {
-    fn default() -> Self {
-        Self::Monthly
+impl TryFrom<String> for RenameTemplate {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
     }
 }
 
-impl fmt::Display for OrganizationMode {
+impl From<RenameTemplate> for String {
+    fn from(template: RenameTemplate) -> Self {
+        template.0
+    }
+}
+
+impl fmt::Display for RenameTemplate {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            Self::Yearly => write!(f, "yearly"),
-            Self::Monthly => write!(f, "monthly"),
-            Self::ByType => write!(f, "type"),
-        }
+        f.write_str(&self.0)
     }
 }
 
@@ -223,6 +1935,11 @@ mod tests {
         assert!(settings.parallel_processing);
         assert!(!settings.skip_hidden_files);
         assert!(!settings.optimize_for_ssd);
+        assert!(settings.on_start.is_empty());
+        assert_eq!(settings.conflict_policy, None);
+        assert_eq!(settings.schedule, None);
+        assert_eq!(settings.non_media_policy, NonMediaPolicy::Include);
+        assert_eq!(settings.audio_template, "{artist}/{album}");
     }
 
     #[test]
@@ -316,14 +2033,18 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::too_many_lines)]
     fn test_settings_serialization_deserialization() {
         let settings = Settings {
             source_folder: Some(PathBuf::from("/source")),
             destination_folder: Some(PathBuf::from("/dest")),
             recurse_subfolders: false,
+            max_depth: Some(2),
+            folder_depths: Vec::new(),
             verbose_output: true,
             organize_by: "daily".to_string(),
             separate_videos: true,
+            separate_screenshots: true,
             dry_run: false,
             keep_original_structure: true,
             rename_duplicates: false,
@@ -336,6 +2057,68 @@ mod tests {
             skip_hidden_files: true,
             optimize_for_ssd: true,
             undo_enabled: true,
+            on_start: vec![StartupAction::Scan],
+            conflict_policy: Some(ConflictPolicy::OverwriteIfNewer),
+            rollback_threshold: Some(25),
+            schedule: Some(OrganizeSchedule::EveryHours(6)),
+            non_media_policy: NonMediaPolicy::Unsorted,
+            previously_organized: PreviouslyOrganizedPolicy::Skip,
+            audio_template: "{year}/{artist}".to_string(),
+            rename_template: RenameTemplate::from_str("{date}_{original}").ok(),
+            ignored_paths: vec![PathBuf::from("/test/source/private")],
+            follow_symlinks: true,
+            check_file_headers: false,
+            inspect_archives: true,
+            extension_mismatch: ExtensionMismatchPolicy::Fix,
+            timezone_policy: TimezonePolicy::Exif,
+            manifest: ManifestMode::Folders,
+            checksum_sidecars: ChecksumSidecars::Sha256Sums,
+            quarantine_folder: Some(PathBuf::from("/test/quarantine")),
+            memory_budget_mb: 1024,
+            throttle: Throttle {
+                max_mb_per_sec: 50,
+                max_iops: 100,
+            },
+            skip_rules: SkipRules {
+                min_size_kb: 50,
+                min_width: 640,
+                min_height: 480,
+                min_age_days: 7,
+            },
+            notifications: Notifications {
+                webhook_url: Some("http://homeassistant.local:8123/api/webhook/visualvault".to_string()),
+                mqtt_broker: Some("homeassistant.local".to_string()),
+                ..Notifications::default()
+            },
+            cloud_export: CloudExport {
+                endpoint: Some("http://nas.local:9000".to_string()),
+                bucket: "photos".to_string(),
+                prefix: "library".to_string(),
+                ..CloudExport::default()
+            },
+            rclone: Rclone {
+                binary: "/usr/local/bin/rclone".to_string(),
+                staging_folder: Some(PathBuf::from("/staging")),
+            },
+            type_mappings: BTreeMap::from([("insv".to_string(), TypeMapping::Video)]),
+            folder_rules: vec![FolderRule {
+                folder: PathBuf::from("WhatsApp Images"),
+                target: "WhatsApp/{year}".to_string(),
+                enabled: false,
+            }],
+            routing_rules: vec![RoutingRule {
+                pattern: r"^IMG-\d{8}-WA".to_string(),
+                target: "WhatsApp".to_string(),
+                enabled: true,
+            }],
+            organize_hooks: vec![PathBuf::from("/home/user/.config/visualvault/hooks/by-camera.py")],
+            hash_algorithm: HashAlgorithm::Blake3,
+            video_similarity: VideoSimilarity {
+                frames: 12,
+                threshold: 85,
+            },
+            accessibility: AccessibilityMode::On,
+            concurrency: ConcurrencyMode::Auto,
         };
 
         // Serialize to TOML
@@ -351,6 +2134,7 @@ mod tests {
         assert_eq!(settings.verbose_output, deserialized.verbose_output);
         assert_eq!(settings.organize_by, deserialized.organize_by);
         assert_eq!(settings.separate_videos, deserialized.separate_videos);
+        assert_eq!(settings.separate_screenshots, deserialized.separate_screenshots);
         assert_eq!(settings.dry_run, deserialized.dry_run);
         assert_eq!(settings.keep_original_structure, deserialized.keep_original_structure);
         assert_eq!(settings.rename_duplicates, deserialized.rename_duplicates);
@@ -362,6 +2146,29 @@ mod tests {
         assert_eq!(settings.parallel_processing, deserialized.parallel_processing);
         assert_eq!(settings.skip_hidden_files, deserialized.skip_hidden_files);
         assert_eq!(settings.optimize_for_ssd, deserialized.optimize_for_ssd);
+        assert_eq!(settings.on_start, deserialized.on_start);
+        assert_eq!(settings.conflict_policy, deserialized.conflict_policy);
+        assert_eq!(settings.rollback_threshold, deserialized.rollback_threshold);
+        assert_eq!(settings.schedule, deserialized.schedule);
+        assert_eq!(settings.non_media_policy, deserialized.non_media_policy);
+        assert_eq!(settings.audio_template, deserialized.audio_template);
+        assert_eq!(settings.rename_template, deserialized.rename_template);
+        assert_eq!(settings.ignored_paths, deserialized.ignored_paths);
+        assert_eq!(settings.follow_symlinks, deserialized.follow_symlinks);
+        assert_eq!(settings.memory_budget_mb, deserialized.memory_budget_mb);
+        assert_eq!(settings.throttle, deserialized.throttle);
+        assert_eq!(settings.skip_rules, deserialized.skip_rules);
+        assert_eq!(settings.notifications, deserialized.notifications);
+        assert_eq!(settings.cloud_export, deserialized.cloud_export);
+        assert_eq!(settings.rclone, deserialized.rclone);
+        assert_eq!(settings.type_mappings, deserialized.type_mappings);
+        assert_eq!(settings.folder_rules, deserialized.folder_rules);
+        assert_eq!(settings.routing_rules, deserialized.routing_rules);
+        assert_eq!(settings.organize_hooks, deserialized.organize_hooks);
+        assert_eq!(settings.hash_algorithm, deserialized.hash_algorithm);
+        assert_eq!(settings.video_similarity, deserialized.video_similarity);
+        assert_eq!(settings.accessibility, deserialized.accessibility);
+        assert_eq!(settings.concurrency, deserialized.concurrency);
     }
 
     #[test]
@@ -385,6 +2192,654 @@ mod tests {
         assert_eq!(settings.worker_threads, num_cpus::get());
     }
 
+    #[test]
+    fn test_memory_budget() {
+        let mut settings = Settings::default();
+        assert_eq!(settings.memory_budget_bytes(), Some(512 * 1024 * 1024));
+
+        let mut seen = Vec::new();
+        for _ in 0..Settings::MEMORY_BUDGET_PRESETS.len() {
+            settings.memory_budget_mb = settings.next_memory_budget_mb();
+            seen.push(settings.memory_budget_mb);
+        }
+        assert_eq!(seen, vec![1024, 2048, 4096, 0, 256, 512]);
+
+        settings.memory_budget_mb = 0;
+        assert_eq!(settings.memory_budget_bytes(), None);
+    }
+
+    #[test]
+    fn test_scan_depth() {
+        let mut settings = Settings::default();
+        let mut seen = Vec::new();
+        for _ in 0..=Settings::SCAN_DEPTH_PRESETS.len() {
+            settings.cycle_scan_depth();
+            seen.push(settings.scan_depth(Path::new("/photos")));
+        }
+        assert_eq!(seen, [Some(1), Some(2), Some(3), Some(5), Some(0), None]);
+
+        let toml_str = r#"
+            max_depth = 1
+
+            [[folder_depths]]
+            path = "/photos/camera"
+
+            [[folder_depths]]
+            path = "/photos/downloads"
+            max_depth = 0
+        "#;
+        let settings: Settings = toml::from_str(toml_str).unwrap();
+        let root = Path::new("/photos");
+        assert!(settings.scans_folder(root, Path::new("/photos/trips")));
+        assert!(!settings.scans_folder(root, Path::new("/photos/trips/2024")));
+        assert!(settings.scans_folder(root, Path::new("/photos/camera/2024/05")));
+        assert!(settings.scans_folder(root, Path::new("/photos/downloads")));
+        assert!(!settings.scans_folder(root, Path::new("/photos/downloads/zip")));
+        assert_eq!(settings.scan_depth(Path::new("/photos/camera")), None);
+    }
+
+    #[test]
+    fn test_notifications_deserialization() {
+        let settings: Settings = toml::from_str("").unwrap();
+        assert!(!settings.notifications.is_enabled());
+
+        let toml_str = r#"
+            [notifications]
+            mqtt_broker = "192.168.1.10:1884"
+        "#;
+        let settings: Settings = toml::from_str(toml_str).unwrap();
+        assert!(settings.notifications.is_enabled());
+        assert_eq!(settings.notifications.mqtt_broker.as_deref(), Some("192.168.1.10:1884"));
+        assert_eq!(settings.notifications.mqtt_topic, "visualvault/events");
+        assert_eq!(settings.notifications.mqtt_qos, 1);
+        assert_eq!(settings.notifications.webhook_url, None);
+        assert!(!settings.notifications.desktop);
+        assert_eq!(settings.notifications.desktop_after_secs, 30);
+    }
+
+    #[test]
+    fn test_cloud_export_deserialization() {
+        let settings: Settings = toml::from_str("").unwrap();
+        assert!(!settings.cloud_export.is_enabled());
+
+        let toml_str = r#"
+            [cloud_export]
+            endpoint = "https://s3.eu-north-1.amazonaws.com"
+            bucket = "family-photos"
+        "#;
+        let settings: Settings = toml::from_str(toml_str).unwrap();
+        assert!(settings.cloud_export.is_enabled());
+        assert_eq!(settings.cloud_export.region, "us-east-1");
+        assert_eq!(settings.cloud_export.access_key, None);
+        assert_eq!(settings.cloud_export.prefix, "");
+    }
+
+    #[test]
+    fn test_rclone_remote_destination() {
+        let mut settings = Settings::default();
+        settings.rclone.staging_folder = Some(PathBuf::from("/staging"));
+        for (destination, remote) in [
+            ("gdrive:Photos", Some("gdrive:Photos")),
+            ("my-nas:", Some("my-nas:")),
+            ("/library", None),
+            ("./photos:2024", None),
+            (":s3:bucket", None),
+        ] {
+            settings.destination_folder = Some(PathBuf::from(destination));
+            assert_eq!(settings.rclone_remote(), remote, "{destination}");
+        }
+
+        settings.destination_folder = Some(PathBuf::from("gdrive:Photos/2024"));
+        assert_eq!(
+            settings.local_destination(),
+            Some(PathBuf::from("/staging/gdrive_Photos_2024"))
+        );
+        assert_eq!(
+            settings.quarantine_path(),
+            Some(PathBuf::from("/staging/gdrive_Photos_2024/Quarantine"))
+        );
+        settings.destination_folder = Some(PathBuf::from("/library"));
+        assert_eq!(settings.local_destination(), Some(PathBuf::from("/library")));
+    }
+
+    #[test]
+    fn test_desktop_threshold_cycles_through_presets() {
+        let mut notifications = Notifications::default();
+        let mut seen = Vec::new();
+        for _ in 0..Notifications::DESKTOP_AFTER_PRESETS.len() {
+            notifications.desktop_after_secs = notifications.next_desktop_after_secs();
+            seen.push(notifications.desktop_after_secs);
+        }
+        assert_eq!(seen, vec![60, 300, 900, 10, 30]);
+
+        // Hand-edited values move on to the next larger preset
+        notifications.desktop_after_secs = 45;
+        assert_eq!(notifications.next_desktop_after_secs(), 60);
+    }
+
+    #[test]
+    fn test_skip_rules() {
+        let mut rules = SkipRules::default();
+        assert!(!rules.is_enabled());
+        assert!(!rules.below_min_resolution(1, 1));
+
+        let mut seen = Vec::new();
+        for _ in 0..SkipRules::RESOLUTION_PRESETS.len() {
+            (rules.min_width, rules.min_height) = rules.next_min_resolution();
+            seen.push((rules.min_width, rules.min_height));
+        }
+        assert_eq!(seen, vec![(320, 240), (640, 480), (1280, 720), (1920, 1080), (0, 0)]);
+
+        rules.min_width = 640;
+        rules.min_height = 480;
+        assert!(rules.is_enabled());
+        assert!(!rules.below_min_resolution(640, 480));
+        assert!(!rules.below_min_resolution(480, 640));
+        assert!(rules.below_min_resolution(639, 480));
+        assert!(rules.below_min_resolution(1000, 400));
+
+        rules.min_size_kb = 60;
+        assert_eq!(rules.next_min_size_kb(), 100);
+        assert_eq!(rules.next_min_age_days(), 1);
+
+        let parsed: SkipRules = toml::from_str("min_age_days = 3").unwrap();
+        assert_eq!(parsed.min_age_days, 3);
+        assert!(!parsed.has_min_resolution());
+    }
+
+    #[test]
+    fn test_throttle() {
+        let mut throttle = Throttle::default();
+        assert!(!throttle.is_enabled());
+        assert_eq!(throttle.to_string(), "unlimited");
+
+        let mut seen = Vec::new();
+        for _ in 0..Throttle::MB_PER_SEC_PRESETS.len() {
+            throttle.max_mb_per_sec = throttle.next_mb_per_sec();
+            seen.push(throttle.max_mb_per_sec);
+        }
+        assert_eq!(seen, vec![10, 25, 50, 100, 250, 0]);
+
+        throttle.max_iops = 60;
+        assert_eq!(throttle.next_iops(), 100);
+        assert!(throttle.is_enabled());
+        assert_eq!(throttle.to_string(), "60 IOPS");
+        throttle.max_mb_per_sec = 25;
+        assert_eq!(throttle.to_string(), "25 MB/s, 60 IOPS");
+
+        let parsed: Throttle = toml::from_str("max_mb_per_sec = 40").unwrap();
+        assert_eq!(parsed.max_mb_per_sec, 40);
+        assert_eq!(parsed.max_iops, 0);
+    }
+
+    #[test]
+    fn test_startup_actions_deserialization() {
+        let toml_str = r#"
+            on_start = ["scan", "duplicates"]
+        "#;
+
+        let settings: Settings = toml::from_str(toml_str).unwrap();
+        assert_eq!(settings.on_start, vec![StartupAction::Scan, StartupAction::Duplicates]);
+
+        // Unknown actions are rejected rather than silently ignored
+        assert!(toml::from_str::<Settings>(r#"on_start = ["organize"]"#).is_err());
+    }
+
+    #[test]
+    fn test_startup_action_from_str() {
+        assert_eq!(StartupAction::from_str("scan").unwrap(), StartupAction::Scan);
+        assert_eq!(
+            StartupAction::from_str("Duplicates").unwrap(),
+            StartupAction::Duplicates
+        );
+        assert!(StartupAction::from_str("organize").is_err());
+        assert_eq!(StartupAction::Scan.to_string(), "scan");
+        assert_eq!(StartupAction::Duplicates.to_string(), "duplicates");
+    }
+
+    #[test]
+    fn test_conflict_policy_from_str() {
+        assert_eq!(ConflictPolicy::from_str("skip").unwrap(), ConflictPolicy::Skip);
+        assert_eq!(
+            ConflictPolicy::from_str("Overwrite-If-Newer").unwrap(),
+            ConflictPolicy::OverwriteIfNewer
+        );
+        assert_eq!(
+            ConflictPolicy::from_str("overwrite").unwrap(),
+            ConflictPolicy::OverwriteAlways
+        );
+        assert!(ConflictPolicy::from_str("merge").is_err());
+        assert_eq!(ConflictPolicy::OverwriteAlways.to_string(), "overwrite-always");
+
+        let settings: Settings = toml::from_str(r#"conflict_policy = "ask""#).unwrap();
+        assert_eq!(settings.conflict_policy, Some(ConflictPolicy::Ask));
+    }
+
+    #[test]
+    fn test_effective_conflict_policy() {
+        let mut settings = Settings::default();
+        assert_eq!(settings.effective_conflict_policy(), ConflictPolicy::Rename);
+
+        settings.rename_duplicates = false;
+        assert_eq!(settings.effective_conflict_policy(), ConflictPolicy::OverwriteAlways);
+
+        settings.conflict_policy = Some(ConflictPolicy::Skip);
+        assert_eq!(settings.effective_conflict_policy(), ConflictPolicy::Skip);
+    }
+
+    #[test]
+    fn test_non_media_policy() {
+        assert_eq!(NonMediaPolicy::from_str("Ignore").unwrap(), NonMediaPolicy::Ignore);
+        assert_eq!(NonMediaPolicy::from_str("unsorted").unwrap(), NonMediaPolicy::Unsorted);
+        assert!(NonMediaPolicy::from_str("delete").is_err());
+        assert_eq!(NonMediaPolicy::Include.next(), NonMediaPolicy::Ignore);
+
+        let settings: Settings = toml::from_str(r#"non_media_policy = "unsorted""#).unwrap();
+        assert_eq!(settings.non_media_policy, NonMediaPolicy::Unsorted);
+    }
+
+    #[test]
+    fn test_extension_mismatch_policy() {
+        assert_eq!(Settings::default().extension_mismatch, ExtensionMismatchPolicy::Flag);
+        assert_eq!(
+            ExtensionMismatchPolicy::from_str("Fix").unwrap(),
+            ExtensionMismatchPolicy::Fix
+        );
+        assert!(ExtensionMismatchPolicy::from_str("rename").is_err());
+        assert_eq!(
+            ExtensionMismatchPolicy::Quarantine.next(),
+            ExtensionMismatchPolicy::Flag
+        );
+
+        let settings: Settings = toml::from_str(r#"extension_mismatch = "quarantine""#).unwrap();
+        assert_eq!(settings.extension_mismatch, ExtensionMismatchPolicy::Quarantine);
+    }
+
+    #[test]
+    fn test_inspect_archives_defaults_off() {
+        assert!(!Settings::default().inspect_archives);
+        let settings: Settings = toml::from_str("inspect_archives = true").unwrap();
+        assert!(settings.inspect_archives);
+    }
+
+    #[test]
+    fn test_manifest_mode() {
+        assert_eq!(Settings::default().manifest, ManifestMode::Off);
+        assert_eq!(ManifestMode::from_str("Root").unwrap(), ManifestMode::Root);
+        assert!(ManifestMode::from_str("csv").is_err());
+        assert_eq!(ManifestMode::Folders.next(), ManifestMode::Off);
+
+        let settings: Settings = toml::from_str(r#"manifest = "folders""#).unwrap();
+        assert_eq!(settings.manifest, ManifestMode::Folders);
+    }
+
+    #[test]
+    fn test_checksum_sidecars() {
+        assert_eq!(Settings::default().checksum_sidecars, ChecksumSidecars::Off);
+        assert_eq!(
+            ChecksumSidecars::from_str("SHA256SUMS").unwrap(),
+            ChecksumSidecars::Sha256Sums
+        );
+        assert!(ChecksumSidecars::from_str("md5").is_err());
+        assert_eq!(ChecksumSidecars::Sfv.next(), ChecksumSidecars::Off);
+
+        let settings: Settings = toml::from_str(r#"checksum_sidecars = "sfv""#).unwrap();
+        assert_eq!(settings.checksum_sidecars, ChecksumSidecars::Sfv);
+    }
+
+    #[test]
+    fn test_accessibility_mode() {
+        assert_eq!(Settings::default().accessibility, AccessibilityMode::Auto);
+        assert_eq!(AccessibilityMode::from_str("ON").unwrap(), AccessibilityMode::On);
+        assert!(AccessibilityMode::from_str("high").is_err());
+        assert_eq!(AccessibilityMode::Off.next(), AccessibilityMode::Auto);
+
+        let auto = AccessibilityMode::Auto;
+        assert!(!auto.is_on(Some("xterm-256color"), None));
+        assert!(!auto.is_on(Some("xterm-256color"), Some("")));
+        assert!(auto.is_on(Some("xterm-256color"), Some("1")));
+        assert!(auto.is_on(Some("linux"), None));
+        assert!(auto.is_on(Some("vt220"), None));
+        assert!(AccessibilityMode::On.is_on(None, None));
+        assert!(!AccessibilityMode::Off.is_on(Some("dumb"), Some("1")));
+
+        let settings: Settings = toml::from_str(r#"accessibility = "off""#).unwrap();
+        assert_eq!(settings.accessibility, AccessibilityMode::Off);
+    }
+
+    #[test]
+    fn test_concurrency_mode() {
+        assert_eq!(Settings::default().concurrency, ConcurrencyMode::Fixed);
+        assert_eq!(ConcurrencyMode::from_str("AUTO").unwrap(), ConcurrencyMode::Auto);
+        assert!(ConcurrencyMode::from_str("fast").is_err());
+        assert_eq!(ConcurrencyMode::Auto.next(), ConcurrencyMode::Fixed);
+
+        let settings: Settings = toml::from_str(r#"concurrency = "auto""#).unwrap();
+        assert_eq!(settings.concurrency, ConcurrencyMode::Auto);
+    }
+
+    #[test]
+    fn test_timezone_policy() {
+        let tokyo = TimezonePolicy::Fixed(FixedOffset::east_opt(9 * 3600).unwrap());
+        assert_eq!(Settings::default().timezone_policy, TimezonePolicy::Local);
+        assert_eq!(TimezonePolicy::from_str("EXIF").unwrap(), TimezonePolicy::Exif);
+        assert_eq!(TimezonePolicy::from_str("+09:00").unwrap(), tokyo);
+        assert_eq!(TimezonePolicy::from_str("UTC+09:00").unwrap(), tokyo);
+        assert_eq!(
+            TimezonePolicy::from_str("utc").unwrap(),
+            TimezonePolicy::Fixed(FixedOffset::east_opt(0).unwrap())
+        );
+        assert!(TimezonePolicy::from_str("Asia/Tokyo").is_err());
+        assert_eq!(tokyo.to_string(), "+09:00");
+        assert_eq!(tokyo.label(), "UTC+09:00");
+
+        assert_eq!(TimezonePolicy::Local.next(), TimezonePolicy::Exif);
+        assert_eq!(
+            TimezonePolicy::Exif.next(),
+            TimezonePolicy::Fixed(FixedOffset::west_opt(12 * 3600).unwrap())
+        );
+        assert_eq!(
+            tokyo.next(),
+            TimezonePolicy::Fixed(FixedOffset::east_opt(10 * 3600).unwrap())
+        );
+        let india = TimezonePolicy::from_str("+05:30").unwrap();
+        assert_eq!(
+            india.next(),
+            TimezonePolicy::Fixed(FixedOffset::east_opt(6 * 3600).unwrap())
+        );
+        assert_eq!(
+            TimezonePolicy::from_str("+14:00").unwrap().next(),
+            TimezonePolicy::Local
+        );
+
+        let settings: Settings = toml::from_str(r#"timezone_policy = "-05:00""#).unwrap();
+        assert_eq!(settings.timezone_policy.to_string(), "-05:00");
+        assert!(toml::from_str::<Settings>(r#"timezone_policy = "mars""#).is_err());
+    }
+
+    #[test]
+    fn test_previously_organized_policy() {
+        assert_eq!(Settings::default().previously_organized, PreviouslyOrganizedPolicy::Off);
+        assert!(!PreviouslyOrganizedPolicy::Off.is_enabled());
+        assert_eq!(
+            PreviouslyOrganizedPolicy::from_str("Delete").unwrap(),
+            PreviouslyOrganizedPolicy::Delete
+        );
+        assert!(PreviouslyOrganizedPolicy::from_str("move").is_err());
+        assert_eq!(PreviouslyOrganizedPolicy::Delete.next(), PreviouslyOrganizedPolicy::Off);
+
+        let settings: Settings = toml::from_str(r#"previously_organized = "skip""#).unwrap();
+        assert_eq!(settings.previously_organized, PreviouslyOrganizedPolicy::Skip);
+    }
+
+    #[test]
+    fn test_schedule_deserialization() {
+        let settings: Settings = toml::from_str("schedule = { every_hours = 4 }").unwrap();
+        assert_eq!(settings.schedule, Some(OrganizeSchedule::EveryHours(4)));
+
+        let settings: Settings = toml::from_str(r#"schedule = { daily_at = "03:30" }"#).unwrap();
+        assert_eq!(
+            settings.schedule,
+            Some(OrganizeSchedule::DailyAt(NaiveTime::from_hms_opt(3, 30, 0).unwrap()))
+        );
+    }
+
+    #[test]
+    fn test_schedule_from_str_and_presets() {
+        assert_eq!(
+            OrganizeSchedule::from_str("every 6h").unwrap(),
+            OrganizeSchedule::EveryHours(6)
+        );
+        assert_eq!(
+            OrganizeSchedule::from_str("Daily at 22:15").unwrap().to_string(),
+            "daily at 22:15"
+        );
+        assert!(OrganizeSchedule::from_str("every 0h").is_err());
+        assert!(OrganizeSchedule::from_str("weekly").is_err());
+
+        // Cycling through presets eventually turns the schedule off again
+        let mut preset = OrganizeSchedule::next_preset(None);
+        let mut steps = 1;
+        while preset.is_some() {
+            preset = OrganizeSchedule::next_preset(preset);
+            steps += 1;
+        }
+        assert_eq!(steps, 6);
+    }
+
+    #[test]
+    fn test_is_ignored_matches_files_and_folders() {
+        let settings = Settings {
+            ignored_paths: vec![PathBuf::from("/photos/private"), PathBuf::from("/photos/a.jpg")],
+            ..Settings::default()
+        };
+
+        assert!(settings.is_ignored(Path::new("/photos/private/b.jpg")));
+        assert!(settings.is_ignored(Path::new("/photos/a.jpg")));
+        assert!(!settings.is_ignored(Path::new("/photos/private2/b.jpg")));
+        assert!(!settings.is_ignored(Path::new("/photos/a.jpg.bak")));
+    }
+
+    #[test]
+    fn test_quarantine_path() {
+        let mut settings = Settings::default();
+        assert!(!settings.check_file_headers);
+        assert_eq!(settings.quarantine_path(), None);
+
+        settings.destination_folder = Some(PathBuf::from("/library"));
+        assert_eq!(settings.quarantine_path(), Some(PathBuf::from("/library/Quarantine")));
+
+        settings.quarantine_folder = Some(PathBuf::from("/suspect"));
+        assert_eq!(settings.quarantine_path(), Some(PathBuf::from("/suspect")));
+    }
+
+    #[test]
+    fn test_type_mappings() {
+        let toml_str = r#"
+            [type_mappings]
+            insv = "video"
+            gpx = "ignore"
+        "#;
+        let mut settings: Settings = toml::from_str(toml_str).unwrap();
+        assert_eq!(settings.type_mapping("insv"), Some(TypeMapping::Video));
+        assert_eq!(settings.type_mapping(".GPX"), Some(TypeMapping::Ignore));
+        assert_eq!(settings.type_mapping("jpg"), None);
+        assert!(toml::from_str::<Settings>("type_mappings = { dng = \"raw\" }").is_err());
+
+        settings.set_type_mapping(".DNG", TypeMapping::Image).unwrap();
+        assert_eq!(settings.type_mappings.get("dng"), Some(&TypeMapping::Image));
+        assert!(settings.set_type_mapping("", TypeMapping::Image).is_err());
+        assert!(settings.set_type_mapping("tar.gz", TypeMapping::Other).is_err());
+
+        assert_eq!(TypeMapping::from_str(" Audio ").unwrap(), TypeMapping::Audio);
+        assert_eq!(TypeMapping::Ignore.next(), TypeMapping::Image);
+        assert!(TypeMapping::Video.is_media());
+        assert!(!TypeMapping::Document.is_media());
+    }
+
+    #[test]
+    fn test_folder_rules() {
+        use chrono::TimeZone;
+
+        let toml_str = r#"
+            source_folder = "/photos/inbox"
+
+            [[folder_rules]]
+            folder = "WhatsApp Images"
+            target = "WhatsApp/{year}"
+
+            [[folder_rules]]
+            folder = "Screenshots"
+            target = "Screenshots"
+            enabled = false
+
+            [[folder_rules]]
+            folder = "/mnt/camera"
+            target = "Camera/{year}/{month}"
+        "#;
+        let mut settings: Settings = toml::from_str(toml_str).unwrap();
+        let target =
+            |settings: &Settings, path: &str| settings.folder_rule(Path::new(path)).map(|rule| rule.target.clone());
+        assert_eq!(
+            target(&settings, "/photos/inbox/WhatsApp Images/Sent/a.jpg").as_deref(),
+            Some("WhatsApp/{year}")
+        );
+        assert_eq!(target(&settings, "/photos/inbox/Screenshots/a.png"), None);
+        assert_eq!(
+            target(&settings, "/mnt/camera/DCIM/a.jpg").as_deref(),
+            Some("Camera/{year}/{month}")
+        );
+        assert_eq!(target(&settings, "/photos/inbox/a.jpg"), None);
+
+        assert_eq!(settings.move_folder_rule(2, true), Some(1));
+        assert_eq!(settings.folder_rules[1].folder, PathBuf::from("/mnt/camera"));
+        assert_eq!(settings.move_folder_rule(0, true), None);
+        assert_eq!(settings.move_folder_rule(2, false), None);
+
+        let date = FixedOffset::east_opt(3600)
+            .unwrap()
+            .with_ymd_and_hms(2024, 3, 5, 12, 0, 0)
+            .unwrap();
+        assert_eq!(settings.folder_rules[1].render(&date), ["Camera", "2024", "03"]);
+
+        let rule = FolderRule::from_str(" WhatsApp Video = WhatsApp/{year}/Video ").unwrap();
+        assert_eq!(rule.to_string(), "WhatsApp Video=WhatsApp/{year}/Video");
+        assert!(rule.enabled);
+        settings.set_folder_rule(2, rule.clone());
+        assert_eq!(settings.folder_rules[2].target, "WhatsApp/{year}/Video");
+        assert!(!settings.folder_rules[2].enabled);
+        settings.set_folder_rule(3, rule);
+        assert_eq!(settings.folder_rules.len(), 4);
+        assert!(FolderRule::from_str("WhatsApp").is_err());
+        assert!(FolderRule::from_str("=WhatsApp").is_err());
+        assert!(FolderRule::from_str("WhatsApp=").is_err());
+        assert!(FolderRule::from_str("WhatsApp=WhatsApp/{camera}").is_err());
+        assert!(FolderRule::from_str("WhatsApp=WhatsApp/{year").is_err());
+    }
+
+    #[test]
+    fn test_routing_rules() {
+        use chrono::TimeZone;
+
+        let toml_str = r#"
+            [[routing_rules]]
+            pattern = '^IMG-\d{8}-WA'
+            target = "WhatsApp/{year}"
+
+            [[routing_rules]]
+            pattern = "(?i)^screenshot"
+            target = "Screenshots"
+            enabled = false
+
+            [[routing_rules]]
+            pattern = "^IMG-("
+            target = "Broken"
+
+            [[routing_rules]]
+            pattern = "^(IMG|VID)"
+            target = "Camera"
+        "#;
+        let mut settings: Settings = toml::from_str(toml_str).unwrap();
+        let target = |settings: &Settings, name: &str| settings.routing_rule(name).map(|rule| rule.target.clone());
+        assert_eq!(
+            target(&settings, "IMG-20240315-WA0007.jpg").as_deref(),
+            Some("WhatsApp/{year}")
+        );
+        assert_eq!(target(&settings, "Screenshot_2024.png"), None);
+        assert_eq!(target(&settings, "IMG_0001.jpg").as_deref(), Some("Camera"));
+        assert_eq!(target(&settings, "holiday.jpg"), None);
+
+        assert_eq!(settings.move_routing_rule(3, true), Some(2));
+        assert_eq!(settings.move_routing_rule(2, true), Some(1));
+        assert_eq!(settings.move_routing_rule(1, true), Some(0));
+        assert_eq!(target(&settings, "IMG-20240315-WA0007.jpg").as_deref(), Some("Camera"));
+        assert_eq!(settings.move_routing_rule(0, true), None);
+        assert_eq!(settings.move_routing_rule(3, false), None);
+
+        let rule = RoutingRule::from_str(r" ^VID-\d{8}-WA = WhatsApp/{year}/Video ").unwrap();
+        assert_eq!(rule.to_string(), r"^VID-\d{8}-WA=WhatsApp/{year}/Video");
+        assert!(rule.enabled);
+        let date = FixedOffset::east_opt(0)
+            .unwrap()
+            .with_ymd_and_hms(2024, 3, 5, 12, 0, 0)
+            .unwrap();
+        assert_eq!(rule.render(&date), ["WhatsApp", "2024", "Video"]);
+        settings.set_routing_rule(0, rule);
+        assert_eq!(settings.routing_rules[0].pattern, r"^VID-\d{8}-WA");
+        assert_eq!(settings.routing_rules.len(), 4);
+
+        assert_eq!(
+            RoutingRule::from_str("(?P<kind>a|b)=x=y").unwrap().pattern,
+            "(?P<kind>a|b)=x"
+        );
+        assert!(RoutingRule::from_str("WhatsApp").is_err());
+        assert!(RoutingRule::from_str("=WhatsApp").is_err());
+        assert!(RoutingRule::from_str("^IMG-(=WhatsApp").is_err());
+        assert!(RoutingRule::from_str("^IMG=WhatsApp/{camera}").is_err());
+    }
+
+    #[test]
+    fn test_hash_algorithm() {
+        assert_eq!(Settings::default().hash_algorithm, HashAlgorithm::Sha256);
+        let settings: Settings = toml::from_str(r#"hash_algorithm = "xxh3""#).unwrap();
+        assert_eq!(settings.hash_algorithm, HashAlgorithm::Xxh3);
+        assert!(toml::from_str::<Settings>(r#"hash_algorithm = "md5""#).is_err());
+
+        assert_eq!(HashAlgorithm::from_str("BLAKE3").unwrap(), HashAlgorithm::Blake3);
+        assert_eq!(HashAlgorithm::from_str("xxhash").unwrap(), HashAlgorithm::Xxh3);
+        assert_eq!(HashAlgorithm::from_str("sha-256").unwrap(), HashAlgorithm::Sha256);
+        for algorithm in [HashAlgorithm::Sha256, HashAlgorithm::Blake3, HashAlgorithm::Xxh3] {
+            assert_eq!(HashAlgorithm::from_str(&algorithm.to_string()).unwrap(), algorithm);
+        }
+        assert_eq!(HashAlgorithm::Xxh3.next(), HashAlgorithm::Sha256);
+    }
+
+    #[test]
+    fn test_rename_template_validation() {
+        assert!(RenameTemplate::from_str("{date}_{time}_{original}").is_ok());
+        assert!(RenameTemplate::from_str("{camera}_{counter}").is_ok());
+
+        assert!(RenameTemplate::from_str("").is_err());
+        assert!(RenameTemplate::from_str("photo").is_err());
+        assert!(RenameTemplate::from_str("{date}/{original}").is_err());
+        assert!(RenameTemplate::from_str("{date").is_err());
+        assert!(RenameTemplate::from_str("date}").is_err());
+        assert!(RenameTemplate::from_str("{{date}}").is_err());
+        let err = RenameTemplate::from_str("{lens}_{original}").unwrap_err();
+        assert!(err.contains("{lens}"));
+
+        assert!(toml::from_str::<Settings>(r#"rename_template = "{nope}""#).is_err());
+        let settings: Settings = toml::from_str(r#"rename_template = "{year}-{original}""#).unwrap();
+        assert_eq!(settings.rename_template.unwrap().as_str(), "{year}-{original}");
+    }
+
+    #[test]
+    fn test_rename_template_render() {
+        use chrono::TimeZone;
+
+        let context = RenameContext {
+            modified: Local.with_ymd_and_hms(2024, 3, 15, 14, 30, 22).unwrap(),
+            original: "IMG_0001",
+            camera: None,
+            counter: 7,
+        };
+
+        let template = RenameTemplate::from_str("{date}_{time}_{original}").unwrap();
+        assert_eq!(template.render(&context), "2024-03-15_143022_IMG_0001");
+
+        let template = RenameTemplate::from_str("{camera}_{counter}").unwrap();
+        assert!(template.uses("camera"));
+        assert!(!template.uses("date"));
+        assert_eq!(template.render(&context), "Unknown_0007");
+        assert_eq!(
+            template.render(&RenameContext {
+                camera: Some("DC/2"),
+                ..context
+            }),
+            "DC_2_0007"
+        );
+    }
+
     #[test]
     fn test_config_path() {
         let temp_dir = TempDir::new().unwrap();
@@ -0,0 +1,459 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{ConflictPolicy, FolderRule, OrganizationMode, Settings};
+
+/// Largest read/write buffer accepted for file transfers
+pub const MAX_BUFFER_SIZE: usize = 1024 * 1024 * 1024;
+
+/// Tokens that may appear in braces within the audio folder template
+const AUDIO_TOKENS: &[&str] = &["artist", "album", "title", "year"];
+
+/// How serious a problem found by [`Settings::validate`] is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DiagnosticLevel {
+    /// The settings work, but probably not as intended
+    Warning,
+    /// An operation using the settings would fail
+    Error,
+}
+
+/// A problem with the settings, with the setting it concerns and how to fix it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SettingsDiagnostic {
+    pub level: DiagnosticLevel,
+    /// Name of the setting in the configuration file
+    pub field: &'static str,
+    pub message: String,
+}
+
+impl SettingsDiagnostic {
+    fn error(field: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            level: DiagnosticLevel::Error,
+            field,
+            message: message.into(),
+        }
+    }
+
+    fn warning(field: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            level: DiagnosticLevel::Warning,
+            field,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for SettingsDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
+}
+
+impl Settings {
+    /// Checks the settings for problems that would otherwise only show up mid-operation.
+    ///
+    /// Covers the configured folders, thread and buffer bounds, the organization mode, the
+    /// audio folder template, folder rules, organize hooks and options that contradict each
+    /// other. Errors come first.
+    #[must_use]
+    pub fn validate(&self) -> Vec<SettingsDiagnostic> {
+        let mut diagnostics = Vec::new();
+        self.validate_folders(&mut diagnostics);
+        self.validate_performance(&mut diagnostics);
+        self.validate_organization(&mut diagnostics);
+        diagnostics.sort_by_key(|diagnostic| std::cmp::Reverse(diagnostic.level));
+        diagnostics
+    }
+
+    fn validate_folders(&self, diagnostics: &mut Vec<SettingsDiagnostic>) {
+        if let Some(source) = &self.source_folder {
+            if !source.exists() {
+                diagnostics.push(SettingsDiagnostic::error(
+                    "source_folder",
+                    format!("{} does not exist; pick an existing folder", source.display()),
+                ));
+            } else if !source.is_dir() {
+                diagnostics.push(SettingsDiagnostic::error(
+                    "source_folder",
+                    format!("{} is a file, not a folder", source.display()),
+                ));
+            }
+            for folder in &self.folder_depths {
+                if !folder.path.starts_with(source) && !source.starts_with(&folder.path) {
+                    diagnostics.push(SettingsDiagnostic::warning(
+                        "folder_depths",
+                        format!(
+                            "The depth of {} never applies because it is outside the source folder",
+                            folder.path.display()
+                        ),
+                    ));
+                }
+            }
+        } else if self.schedule.is_some() || !self.on_start.is_empty() {
+            diagnostics.push(SettingsDiagnostic::warning(
+                "source_folder",
+                "Scheduled and startup scans need a source folder",
+            ));
+        }
+
+        // rclone remotes are checked by rclone when files are moved there
+        if let Some(destination) = self
+            .destination_folder
+            .as_ref()
+            .filter(|_| self.rclone_remote().is_none())
+        {
+            if destination.exists() && !destination.is_dir() {
+                diagnostics.push(SettingsDiagnostic::error(
+                    "destination_folder",
+                    format!("{} is a file, not a folder", destination.display()),
+                ));
+            } else if !destination.exists() {
+                diagnostics.push(SettingsDiagnostic::warning(
+                    "destination_folder",
+                    format!(
+                        "{} does not exist yet and is created when organizing",
+                        destination.display()
+                    ),
+                ));
+            }
+        }
+    }
+
+    fn validate_performance(&self, diagnostics: &mut Vec<SettingsDiagnostic>) {
+        let max_threads = num_cpus::get() * 2;
+        if self.worker_threads == 0 || self.worker_threads > max_threads {
+            diagnostics.push(SettingsDiagnostic::error(
+                "worker_threads",
+                format!(
+                    "{} is out of range; use 1 to {max_threads} on this machine",
+                    self.worker_threads
+                ),
+            ));
+        }
+
+        if self.buffer_size == 0 || self.buffer_size > MAX_BUFFER_SIZE {
+            diagnostics.push(SettingsDiagnostic::error(
+                "buffer_size",
+                format!("{} bytes is out of range; use 1 to 1024 MB", self.buffer_size),
+            ));
+        }
+
+        if !(1..=64).contains(&self.video_similarity.frames) {
+            diagnostics.push(SettingsDiagnostic::error(
+                "video_similarity.frames",
+                format!(
+                    "{} is out of range; sample 1 to 64 frames",
+                    self.video_similarity.frames
+                ),
+            ));
+        }
+        if self.video_similarity.threshold > 100 {
+            diagnostics.push(SettingsDiagnostic::error(
+                "video_similarity.threshold",
+                format!("{}% is not a percentage", self.video_similarity.threshold),
+            ));
+        }
+    }
+
+    fn validate_organization(&self, diagnostics: &mut Vec<SettingsDiagnostic>) {
+        let mode = match OrganizationMode::from_str(&self.organize_by) {
+            Ok(mode) => Some(mode),
+            Err(e) => {
+                diagnostics.push(SettingsDiagnostic::error(
+                    "organize_by",
+                    format!("{e}; use yearly, monthly or type"),
+                ));
+                None
+            }
+        };
+
+        if let Err(e) = validate_audio_template(&self.audio_template) {
+            diagnostics.push(SettingsDiagnostic::error("audio_template", e));
+        }
+
+        if mode == Some(OrganizationMode::ByType) {
+            if self.keep_original_structure {
+                diagnostics.push(SettingsDiagnostic::warning(
+                    "keep_original_structure",
+                    "Has no effect when organizing by type; files are grouped into type folders",
+                ));
+            }
+            if self.separate_videos {
+                diagnostics.push(SettingsDiagnostic::warning(
+                    "separate_videos",
+                    "Has no effect when organizing by type; videos already get their own folder",
+                ));
+            }
+        }
+
+        if let Some(threshold) = self.rollback_threshold {
+            let overwrites = matches!(
+                self.effective_conflict_policy(),
+                ConflictPolicy::OverwriteIfNewer | ConflictPolicy::OverwriteAlways
+            );
+            if threshold > 100 {
+                diagnostics.push(SettingsDiagnostic::error(
+                    "rollback_threshold",
+                    format!("{threshold}% is not a percentage"),
+                ));
+            } else if overwrites && !self.undo_enabled {
+                diagnostics.push(SettingsDiagnostic::warning(
+                    "rollback_threshold",
+                    "Files replaced by overwriting are not backed up while undo_enabled is off, so a rollback cannot restore them",
+                ));
+            }
+        }
+
+        if self.conflict_policy.is_some() && self.rename_duplicates {
+            diagnostics.push(SettingsDiagnostic::warning(
+                "rename_duplicates",
+                "Ignored for name clashes because conflict_policy is set",
+            ));
+        }
+
+        for rule in &self.folder_rules {
+            if let Err(e) = FolderRule::check_target(&rule.target) {
+                diagnostics.push(SettingsDiagnostic::error(
+                    "folder_rules",
+                    format!("{}: {e}", rule.folder.display()),
+                ));
+            } else if rule.folder.is_relative() && self.source_folder.is_none() {
+                diagnostics.push(SettingsDiagnostic::warning(
+                    "folder_rules",
+                    format!(
+                        "{} is relative to the source folder, which is not set",
+                        rule.folder.display()
+                    ),
+                ));
+            }
+        }
+
+        for rule in &self.routing_rules {
+            let problem = rule
+                .regex()
+                .map_err(|e| e.to_string())
+                .and_then(|_| FolderRule::check_target(&rule.target));
+            if let Err(e) = problem {
+                diagnostics.push(SettingsDiagnostic::error(
+                    "routing_rules",
+                    format!("{}: {e}", rule.pattern),
+                ));
+            }
+        }
+
+        for hook in self.organize_hooks.iter().filter(|hook| !hook.is_file()) {
+            diagnostics.push(SettingsDiagnostic::error(
+                "organize_hooks",
+                format!(
+                    "{} does not exist; organizing would fail for every file",
+                    hook.display()
+                ),
+            ));
+        }
+    }
+}
+
+/// Checks that the audio template only uses known tokens in balanced braces
+fn validate_audio_template(template: &str) -> Result<(), String> {
+    if template.trim().is_empty() {
+        return Err("Template is empty; use e.g. {artist}/{album}".to_string());
+    }
+
+    let mut rest = template;
+    while let Some(open) = rest.find(['{', '}']) {
+        if rest[open..].starts_with('}') {
+            return Err(format!("Unmatched '}}' in {template}"));
+        }
+        let after = &rest[open + 1..];
+        let close = after
+            .find(['{', '}'])
+            .filter(|&close| after[close..].starts_with('}'))
+            .ok_or_else(|| format!("Unmatched '{{' in {template}"))?;
+        let token = &after[..close];
+        if !AUDIO_TOKENS.contains(&token) {
+            return Err(format!(
+                "Unknown token {{{token}}}; use {}",
+                AUDIO_TOKENS
+                    .iter()
+                    .map(|t| format!("{{{t}}}"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+        rest = &after[close + 1..];
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+    use crate::RoutingRule;
+    use tempfile::TempDir;
+
+    fn fields(diagnostics: &[SettingsDiagnostic]) -> Vec<&str> {
+        diagnostics.iter().map(|d| d.field).collect()
+    }
+
+    #[test]
+    fn test_default_settings_are_valid() {
+        assert!(Settings::default().validate().is_empty());
+    }
+
+    #[test]
+    fn test_validate_reports_errors_before_warnings() {
+        let temp_dir = TempDir::new().unwrap();
+        let settings = Settings {
+            source_folder: Some(temp_dir.path().join("missing")),
+            destination_folder: Some(temp_dir.path().join("library")),
+            worker_threads: 0,
+            buffer_size: 0,
+            organize_by: "type".to_string(),
+            keep_original_structure: true,
+            separate_videos: false,
+            audio_template: "{artist}/{genre}".to_string(),
+            conflict_policy: Some(ConflictPolicy::Skip),
+            rename_duplicates: true,
+            ..Default::default()
+        };
+
+        let diagnostics = settings.validate();
+        assert_eq!(
+            fields(&diagnostics),
+            [
+                "source_folder",
+                "worker_threads",
+                "buffer_size",
+                "audio_template",
+                "destination_folder",
+                "keep_original_structure",
+                "rename_duplicates",
+            ]
+        );
+        assert_eq!(diagnostics[0].level, DiagnosticLevel::Error);
+        assert_eq!(diagnostics[4].level, DiagnosticLevel::Warning);
+    }
+
+    #[test]
+    fn test_validate_folder_depths() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().to_path_buf();
+        let settings = Settings {
+            source_folder: Some(source.clone()),
+            folder_depths: vec![
+                crate::FolderDepth {
+                    path: source.join("downloads"),
+                    max_depth: Some(1),
+                },
+                crate::FolderDepth {
+                    path: temp_dir.path().with_file_name("elsewhere"),
+                    max_depth: None,
+                },
+            ],
+            ..Default::default()
+        };
+        assert_eq!(fields(&settings.validate()), ["folder_depths"]);
+    }
+
+    #[test]
+    fn test_validate_organize_by() {
+        let settings = Settings {
+            organize_by: "weekly".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(fields(&settings.validate()), ["organize_by"]);
+    }
+
+    #[test]
+    fn test_validate_rollback_threshold() {
+        let settings = Settings {
+            rollback_threshold: Some(150),
+            ..Default::default()
+        };
+        assert_eq!(fields(&settings.validate()), ["rollback_threshold"]);
+
+        let settings = Settings {
+            rollback_threshold: Some(10),
+            rename_duplicates: false,
+            undo_enabled: false,
+            ..Default::default()
+        };
+        let diagnostics = settings.validate();
+        assert_eq!(fields(&diagnostics), ["rollback_threshold"]);
+        assert_eq!(diagnostics[0].level, DiagnosticLevel::Warning);
+    }
+
+    #[test]
+    fn test_validate_routing_rules() {
+        let rule = |pattern: &str, target: &str| RoutingRule {
+            pattern: pattern.to_string(),
+            target: target.to_string(),
+            enabled: true,
+        };
+        let settings = Settings {
+            routing_rules: vec![rule(r"^IMG-\d{8}-WA", "WhatsApp/{year}")],
+            ..Default::default()
+        };
+        assert!(settings.validate().is_empty());
+
+        let settings = Settings {
+            routing_rules: vec![rule("^IMG-(", "WhatsApp"), rule("^Screenshot", "{week}")],
+            ..Default::default()
+        };
+        let diagnostics = settings.validate();
+        assert_eq!(fields(&diagnostics), ["routing_rules", "routing_rules"]);
+        assert_eq!(diagnostics[0].level, DiagnosticLevel::Error);
+    }
+
+    #[test]
+    fn test_validate_folder_rules() {
+        let rule = |folder: &str, target: &str| FolderRule {
+            folder: folder.into(),
+            target: target.to_string(),
+            enabled: true,
+        };
+        let settings = Settings {
+            folder_rules: vec![
+                rule("/photos/WhatsApp", "WhatsApp/{year}"),
+                rule("/photos/Screens", "{week}"),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(fields(&settings.validate()), ["folder_rules"]);
+
+        let settings = Settings {
+            folder_rules: vec![rule("Screenshots", "Screenshots")],
+            ..Default::default()
+        };
+        let diagnostics = settings.validate();
+        assert_eq!(fields(&diagnostics), ["folder_rules"]);
+        assert_eq!(diagnostics[0].level, DiagnosticLevel::Warning);
+    }
+
+    #[test]
+    fn test_validate_organize_hooks() {
+        let temp_dir = TempDir::new().unwrap();
+        let hook = temp_dir.path().join("hook.sh");
+        std::fs::write(&hook, "#!/bin/sh\n").unwrap();
+        let settings = Settings {
+            organize_hooks: vec![hook, temp_dir.path().join("missing.sh")],
+            ..Default::default()
+        };
+        let diagnostics = settings.validate();
+        assert_eq!(fields(&diagnostics), ["organize_hooks"]);
+        assert!(diagnostics[0].message.contains("missing.sh"));
+    }
+
+    #[test]
+    fn test_validate_audio_template() {
+        assert!(validate_audio_template("{artist}/{album}").is_ok());
+        assert!(validate_audio_template("Music/{year}").is_ok());
+        assert!(validate_audio_template("{artist").is_err());
+        assert!(validate_audio_template("artist}").is_err());
+        assert!(validate_audio_template("{genre}").is_err());
+        assert!(validate_audio_template(" ").is_err());
+    }
+}
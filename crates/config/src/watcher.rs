@@ -0,0 +1,136 @@
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+use color_eyre::eyre::Result;
+
+use crate::{ProfileStore, Settings};
+
+/// How often the settings file is checked for changes
+const CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Notices when the settings file of a profile is changed by another program.
+///
+/// The file's modification time is polled, which also catches editors that save by
+/// writing a new file and renaming it over the old one.
+#[derive(Debug)]
+pub struct SettingsWatcher {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    last_check: Option<Instant>,
+}
+
+impl SettingsWatcher {
+    /// Watches the settings file at `path`, taking its current contents as already loaded
+    #[must_use]
+    pub fn new(path: PathBuf) -> Self {
+        let modified = modified_time(&path);
+        Self {
+            path,
+            modified,
+            last_check: None,
+        }
+    }
+
+    /// Watches the settings file of the active profile.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the configuration directory cannot be found.
+    pub fn active_profile() -> Result<Self> {
+        let store = ProfileStore::open()?;
+        Ok(Self::new(store.settings_path(&store.active())))
+    }
+
+    /// Returns the settings read from the file if it changed since the last poll.
+    ///
+    /// Checks at most once per second. A file that was removed is not reported, so the
+    /// settings in use stay as they are.
+    pub fn poll(&mut self, now: Instant) -> Option<Result<Settings>> {
+        if self
+            .last_check
+            .is_some_and(|last| now.duration_since(last) < CHECK_INTERVAL)
+        {
+            return None;
+        }
+        self.last_check = Some(now);
+
+        let modified = modified_time(&self.path);
+        if modified == self.modified {
+            return None;
+        }
+        self.modified = modified;
+        modified?;
+
+        Some(
+            std::fs::read_to_string(&self.path)
+                .map_err(Into::into)
+                .and_then(|content| toml::from_str(&content).map_err(Into::into)),
+        )
+    }
+
+    /// Takes the file's current contents as loaded, after the app saved it itself
+    pub fn acknowledge(&mut self) {
+        self.modified = modified_time(&self.path);
+    }
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|metadata| metadata.modified()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+    use std::fs::File;
+    use tempfile::TempDir;
+
+    fn write_settings(path: &Path, content: &str, modified: SystemTime) {
+        std::fs::write(path, content).unwrap();
+        File::options()
+            .write(true)
+            .open(path)
+            .unwrap()
+            .set_modified(modified)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_poll_reports_external_changes() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("config.toml");
+        let start = SystemTime::now() - Duration::from_secs(60);
+        write_settings(&path, "organize_by = \"monthly\"\n", start);
+
+        let mut watcher = SettingsWatcher::new(path.clone());
+        let now = Instant::now();
+        assert!(watcher.poll(now).is_none());
+
+        write_settings(&path, "organize_by = \"yearly\"\n", start + Duration::from_secs(10));
+        // Checked again only after the interval
+        assert!(watcher.poll(now).is_none());
+        let later = now + CHECK_INTERVAL;
+        let settings = watcher.poll(later).unwrap().unwrap();
+        assert_eq!(settings.organize_by, "yearly");
+        assert!(watcher.poll(later + CHECK_INTERVAL).is_none());
+
+        write_settings(&path, "organize_by = ", start + Duration::from_secs(20));
+        assert!(watcher.poll(later + CHECK_INTERVAL * 2).unwrap().is_err());
+    }
+
+    #[test]
+    fn test_acknowledge_ignores_own_saves() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("config.toml");
+        let start = SystemTime::now() - Duration::from_secs(60);
+        write_settings(&path, "organize_by = \"monthly\"\n", start);
+
+        let mut watcher = SettingsWatcher::new(path.clone());
+        write_settings(&path, "organize_by = \"type\"\n", start + Duration::from_secs(10));
+        watcher.acknowledge();
+        assert!(watcher.poll(Instant::now()).is_none());
+
+        std::fs::remove_file(&path).unwrap();
+        assert!(watcher.poll(Instant::now() + CHECK_INTERVAL).is_none());
+    }
+}
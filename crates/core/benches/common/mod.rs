@@ -0,0 +1,132 @@
+//! Synthetic media library generator shared by the core benchmarks.
+
+use chrono::{Duration, Local, TimeZone};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use visualvault_models::{FileType, MediaFile};
+use visualvault_utils::media_types::determine_file_type;
+
+/// Library sizes used by every benchmark group.
+///
+/// The 100k library takes a while to generate on disk, so file system benchmarks only
+/// include it when `VISUALVAULT_BENCH_FULL` is set.
+pub const SIZES: [usize; 3] = [1_000, 10_000, 100_000];
+
+/// Files per generated folder, roughly what a camera writes into one DCIM directory
+const FILES_PER_FOLDER: usize = 500;
+
+/// Shape of a generated library
+#[derive(Debug, Clone, Copy)]
+pub struct LibrarySpec {
+    pub files: usize,
+    pub duplicate_ratio: f32,
+    pub file_size: usize,
+}
+
+impl LibrarySpec {
+    pub const fn new(files: usize) -> Self {
+        Self {
+            files,
+            duplicate_ratio: 0.2,
+            file_size: 4 * 1024,
+        }
+    }
+}
+
+/// Returns the sizes to use for benchmarks that generate files on disk.
+pub fn disk_sizes() -> Vec<usize> {
+    let full = std::env::var_os("VISUALVAULT_BENCH_FULL").is_some();
+    SIZES.into_iter().filter(|&size| full || size < 100_000).collect()
+}
+
+const fn extension_for(index: usize) -> &'static str {
+    match index % 10 {
+        0 => "mp4",
+        1 | 2 => "png",
+        3 => "heic",
+        _ => "jpg",
+    }
+}
+
+/// Deterministic file contents, unique per `seed`
+#[allow(clippy::cast_possible_truncation)]
+fn file_contents(seed: usize, size: usize) -> Vec<u8> {
+    let mut data = Vec::with_capacity(size);
+    data.extend_from_slice(&(seed as u64).to_le_bytes());
+    let mut state = seed as u32 | 1;
+    while data.len() < size {
+        // xorshift keeps the payload cheap to generate but not trivially compressible
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
+        data.extend_from_slice(&state.to_le_bytes());
+    }
+    data.truncate(size);
+    data
+}
+
+/// Writes a synthetic library under `root` and returns the total number of bytes written.
+///
+/// A `duplicate_ratio` share of the files are byte-for-byte copies of earlier files so the
+/// duplicate detector has real work to do.
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    clippy::cast_precision_loss
+)]
+pub fn generate_library(root: &Path, spec: LibrarySpec) -> u64 {
+    let unique = ((spec.files as f32) * (1.0 - spec.duplicate_ratio)).max(1.0) as usize;
+    let mut total_bytes = 0;
+
+    for index in 0..spec.files {
+        let folder = root.join(format!("DCIM/{:03}", index / FILES_PER_FOLDER));
+        if index % FILES_PER_FOLDER == 0 {
+            fs::create_dir_all(&folder).unwrap();
+        }
+
+        let seed = if index < unique { index } else { index % unique };
+        let contents = file_contents(seed, spec.file_size);
+        let name = format!("IMG_{index:06}.{}", extension_for(index));
+        fs::write(folder.join(name), &contents).unwrap();
+        total_bytes += contents.len() as u64;
+    }
+
+    total_bytes
+}
+
+/// Builds in-memory media records spread over several years, for benchmarks that
+/// do not need files on disk.
+#[allow(clippy::cast_possible_wrap)]
+pub fn synthetic_media_files(count: usize) -> Vec<Arc<MediaFile>> {
+    let epoch = Local.with_ymd_and_hms(2018, 1, 1, 12, 0, 0).unwrap();
+
+    (0..count)
+        .map(|index| {
+            let extension = extension_for(index);
+            let name = format!("IMG_{index:06}.{extension}");
+            let modified = epoch + Duration::hours(index as i64 * 7);
+            let file_type = determine_file_type(extension);
+            let size = if file_type == FileType::Video {
+                50 * 1024 * 1024
+            } else {
+                4 * 1024 * 1024
+            };
+
+            Arc::new(MediaFile {
+                path: PathBuf::from(format!("/library/DCIM/{:03}/{name}", index / FILES_PER_FOLDER)),
+                name: name.into(),
+                extension: extension.into(),
+                file_type,
+                size,
+                created: modified,
+                modified,
+                hash: None,
+                metadata: None,
+                via_symlink: false,
+                live_photo_pair: None,
+                content_extension: None,
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,202 @@
+#![allow(clippy::unwrap_used)]
+#![allow(clippy::expect_used)]
+#![allow(clippy::panic)]
+#![allow(clippy::significant_drop_tightening)]
+//! Benchmarks for the core hot paths: directory walking, hashing, duplicate grouping
+//! and organize planning, measured against synthetic libraries of 1k/10k/100k files.
+//! The worker thread and hash algorithm sweeps show what the matching settings buy on the
+//! machine running them.
+//!
+//! Run with `cargo bench -p visualvault-core`. Set `VISUALVAULT_BENCH_FULL=1` to include
+//! the 100k-file library in the benchmarks that generate files on disk.
+
+mod common;
+
+use common::{LibrarySpec, SIZES, disk_sizes, generate_library, synthetic_media_files};
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use std::hint::black_box;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tempfile::TempDir;
+use tokio::runtime::Runtime;
+use tokio::sync::RwLock;
+use visualvault_config::{HashAlgorithm, Settings};
+use visualvault_core::{DatabaseCache, DuplicateDetector, FileOrganizer, Scanner};
+use visualvault_models::MediaFile;
+use visualvault_utils::Progress;
+
+async fn new_scanner() -> Scanner {
+    let database_cache = DatabaseCache::new(":memory:")
+        .await
+        .expect("Failed to initialize database cache");
+    Scanner::new(database_cache)
+}
+
+async fn scan(root: &Path) -> Vec<Arc<MediaFile>> {
+    scan_with(root, &Settings::default()).await
+}
+
+async fn scan_with(root: &Path, settings: &Settings) -> Vec<Arc<MediaFile>> {
+    let scanner = new_scanner().await;
+    let progress = Arc::new(RwLock::new(Progress::default()));
+    scanner
+        .scan_directory(root, true, progress, settings, None)
+        .await
+        .unwrap()
+}
+
+fn benchmark_directory_walk(c: &mut Criterion) {
+    let rt = Runtime::new().unwrap();
+    let mut group = c.benchmark_group("core/directory_walk");
+    group.sample_size(10);
+
+    for size in disk_sizes() {
+        let library = TempDir::new().unwrap();
+        generate_library(library.path(), LibrarySpec::new(size));
+
+        group.throughput(Throughput::Elements(size as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(size), &size, |b, _| {
+            b.iter(|| rt.block_on(scan(black_box(library.path()))));
+        });
+    }
+
+    group.finish();
+}
+
+fn benchmark_scan_workers(c: &mut Criterion) {
+    let rt = Runtime::new().unwrap();
+    let mut group = c.benchmark_group("core/scan_workers");
+    group.sample_size(10);
+
+    let size = SIZES[1];
+    let library = TempDir::new().unwrap();
+    generate_library(library.path(), LibrarySpec::new(size));
+
+    group.throughput(Throughput::Elements(size as u64));
+    for workers in [1, 2, 4, 8] {
+        let settings = Settings {
+            parallel_processing: true,
+            worker_threads: workers,
+            ..Default::default()
+        };
+        group.bench_with_input(BenchmarkId::from_parameter(workers), &settings, |b, settings| {
+            b.iter(|| rt.block_on(scan_with(black_box(library.path()), settings)));
+        });
+    }
+
+    group.finish();
+}
+
+fn benchmark_hashing_throughput(c: &mut Criterion) {
+    let rt = Runtime::new().unwrap();
+    let mut group = c.benchmark_group("core/hashing");
+    group.sample_size(10);
+
+    for size in disk_sizes() {
+        let library = TempDir::new().unwrap();
+        // Every file has the same size, so the detector has to fully hash all of them
+        let total_bytes = generate_library(library.path(), LibrarySpec::new(size));
+        let files = rt.block_on(scan(library.path()));
+        let detector = DuplicateDetector::new();
+
+        group.throughput(Throughput::Bytes(total_bytes));
+        group.bench_with_input(BenchmarkId::from_parameter(size), &files, |b, files| {
+            b.iter(|| {
+                rt.block_on(detector.detect_duplicates(black_box(files), false))
+                    .unwrap()
+            });
+        });
+    }
+
+    group.finish();
+}
+
+fn benchmark_hash_algorithms(c: &mut Criterion) {
+    let rt = Runtime::new().unwrap();
+    let mut group = c.benchmark_group("core/hash_algorithms");
+    group.sample_size(10);
+
+    let library = TempDir::new().unwrap();
+    // Large files, so reading and hashing them outweighs opening them
+    let spec = LibrarySpec {
+        file_size: 1024 * 1024,
+        ..LibrarySpec::new(64)
+    };
+    let total_bytes = generate_library(library.path(), spec);
+    let files = rt.block_on(scan(library.path()));
+
+    group.throughput(Throughput::Bytes(total_bytes));
+    for algorithm in [HashAlgorithm::Sha256, HashAlgorithm::Blake3, HashAlgorithm::Xxh3] {
+        let detector = DuplicateDetector::with_algorithm(algorithm);
+        group.bench_with_input(BenchmarkId::from_parameter(algorithm), &files, |b, files| {
+            b.iter(|| {
+                rt.block_on(detector.detect_duplicates(black_box(files), false))
+                    .unwrap()
+            });
+        });
+    }
+
+    group.finish();
+}
+
+fn benchmark_duplicate_grouping(c: &mut Criterion) {
+    let rt = Runtime::new().unwrap();
+    let mut group = c.benchmark_group("core/duplicate_grouping");
+    group.sample_size(10);
+
+    for size in disk_sizes() {
+        let library = TempDir::new().unwrap();
+        generate_library(library.path(), LibrarySpec::new(size));
+        let files = rt.block_on(scan(library.path()));
+        let detector = DuplicateDetector::new();
+
+        group.throughput(Throughput::Elements(size as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(size), &files, |b, files| {
+            b.iter(|| rt.block_on(detector.detect_duplicates(black_box(files), true)).unwrap());
+        });
+    }
+
+    group.finish();
+}
+
+fn benchmark_organize_planning(c: &mut Criterion) {
+    let mut group = c.benchmark_group("core/organize_planning");
+
+    let destination = PathBuf::from("/organized");
+    for mode in ["yearly", "monthly", "type"] {
+        let settings = Settings {
+            destination_folder: Some(destination.clone()),
+            organize_by: mode.to_string(),
+            separate_videos: true,
+            ..Default::default()
+        };
+
+        for size in SIZES {
+            let files = synthetic_media_files(size);
+
+            group.throughput(Throughput::Elements(size as u64));
+            group.bench_with_input(BenchmarkId::new(mode, size), &files, |b, files| {
+                b.iter(|| {
+                    for file in files {
+                        black_box(
+                            FileOrganizer::determine_target_directory(file, None, &destination, &settings).unwrap(),
+                        );
+                    }
+                });
+            });
+        }
+    }
+
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    benchmark_directory_walk,
+    benchmark_scan_workers,
+    benchmark_hashing_throughput,
+    benchmark_hash_algorithms,
+    benchmark_duplicate_grouping,
+    benchmark_organize_planning
+);
+criterion_main!(benches);
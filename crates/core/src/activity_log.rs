@@ -0,0 +1,209 @@
+use color_eyre::Result;
+use std::collections::VecDeque;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tracing::warn;
+use visualvault_models::{ActivityEntry, ActivityKind};
+
+const ACTIVITY_LOG_FILE: &str = "activity_log.jsonl";
+const MAX_ACTIVITY_ENTRIES: usize = 1000;
+
+/// A bounded, persistent log of user-visible operations.
+///
+/// Entries are kept in a ring buffer in memory and appended as JSON lines to
+/// `activity_log.jsonl` in the config directory. The file is rewritten with only the
+/// retained entries once it grows to twice the buffer size.
+#[derive(Debug)]
+pub struct ActivityLog {
+    entries: VecDeque<ActivityEntry>,
+    capacity: usize,
+    path: Option<PathBuf>,
+    lines_on_disk: usize,
+}
+
+impl Default for ActivityLog {
+    fn default() -> Self {
+        Self::new(MAX_ACTIVITY_ENTRIES)
+    }
+}
+
+impl ActivityLog {
+    /// Creates an in-memory log that keeps at most `capacity` entries
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: VecDeque::with_capacity(capacity),
+            capacity: capacity.max(1),
+            path: None,
+            lines_on_disk: 0,
+        }
+    }
+
+    /// Loads the log stored in `config_dir`, keeping the most recent entries.
+    ///
+    /// Lines that cannot be parsed are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the log file exists but cannot be read.
+    pub fn load(config_dir: &Path) -> Result<Self> {
+        let path = config_dir.join("visualvault").join(ACTIVITY_LOG_FILE);
+        let mut log = Self::default();
+
+        if path.exists() {
+            let content = fs::read_to_string(&path)?;
+            for line in content.lines().filter(|line| !line.trim().is_empty()) {
+                log.lines_on_disk += 1;
+                match serde_json::from_str::<ActivityEntry>(line) {
+                    Ok(entry) => log.push(entry),
+                    Err(e) => warn!("Skipping malformed activity log line: {}", e),
+                }
+            }
+        }
+
+        log.path = Some(path);
+        Ok(log)
+    }
+
+    /// Records a new entry and appends it to the log file when the log is persistent.
+    ///
+    /// The entry is kept in memory even if writing it to disk fails.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the entry cannot be written to the log file.
+    pub fn record(&mut self, kind: ActivityKind, message: impl Into<String>) -> Result<()> {
+        let entry = ActivityEntry::new(kind, message);
+        let line = serde_json::to_string(&entry)?;
+        self.push(entry);
+
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+
+        if self.lines_on_disk >= self.capacity * 2 {
+            return self.compact();
+        }
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        writeln!(file, "{line}")?;
+        self.lines_on_disk += 1;
+        Ok(())
+    }
+
+    #[must_use]
+    pub const fn entries(&self) -> &VecDeque<ActivityEntry> {
+        &self.entries
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn push(&mut self, entry: ActivityEntry) {
+        self.entries.push_back(entry);
+        while self.entries.len() > self.capacity {
+            self.entries.pop_front();
+        }
+    }
+
+    /// Rewrites the log file with only the retained entries
+    fn compact(&mut self) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+
+        let mut content = String::new();
+        for entry in &self.entries {
+            content.push_str(&serde_json::to_string(entry)?);
+            content.push('\n');
+        }
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, content)?;
+        self.lines_on_disk = self.entries.len();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    #![allow(clippy::panic_in_result_fn)]
+    #![allow(clippy::unwrap_in_result)]
+
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_in_memory_log_is_bounded() -> Result<()> {
+        let mut log = ActivityLog::new(3);
+        for i in 0..5 {
+            log.record(ActivityKind::Scan, format!("scan {i}"))?;
+        }
+
+        assert_eq!(log.len(), 3);
+        assert_eq!(log.entries().front().unwrap().message, "scan 2");
+        assert_eq!(log.entries().back().unwrap().message, "scan 4");
+        Ok(())
+    }
+
+    #[test]
+    fn test_log_persists_across_loads() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+
+        let mut log = ActivityLog::load(temp_dir.path())?;
+        assert!(log.is_empty());
+        log.record(ActivityKind::Organize, "Organized 3 files")?;
+        log.record(ActivityKind::Error, "Scan failed")?;
+
+        let reloaded = ActivityLog::load(temp_dir.path())?;
+        assert_eq!(reloaded.len(), 2);
+        assert_eq!(reloaded.entries()[0].kind, ActivityKind::Organize);
+        assert_eq!(reloaded.entries()[1].message, "Scan failed");
+        Ok(())
+    }
+
+    #[test]
+    fn test_log_file_is_compacted() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let mut log = ActivityLog::load(temp_dir.path())?;
+        log.capacity = 2;
+
+        for i in 0..10 {
+            log.record(ActivityKind::Undo, format!("undo {i}"))?;
+        }
+
+        let path = temp_dir.path().join("visualvault").join(ACTIVITY_LOG_FILE);
+        let lines = fs::read_to_string(path)?.lines().count();
+        assert!(lines <= 4, "log file should stay bounded, found {lines} lines");
+        assert_eq!(log.entries().back().unwrap().message, "undo 9");
+        Ok(())
+    }
+
+    #[test]
+    fn test_malformed_lines_are_skipped() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let dir = temp_dir.path().join("visualvault");
+        fs::create_dir_all(&dir)?;
+        let entry = serde_json::to_string(&ActivityEntry::new(ActivityKind::Delete, "Deleted 1 file"))?;
+        fs::write(dir.join(ACTIVITY_LOG_FILE), format!("not json\n{entry}\n"))?;
+
+        let log = ActivityLog::load(temp_dir.path())?;
+        assert_eq!(log.len(), 1);
+        assert_eq!(log.entries()[0].kind, ActivityKind::Delete);
+        Ok(())
+    }
+}
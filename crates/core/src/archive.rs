@@ -0,0 +1,303 @@
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::io::{self, BufReader, Read};
+use std::path::{Component, Path, PathBuf};
+
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone};
+use color_eyre::eyre::{Result, eyre};
+use flate2::read::MultiGzDecoder;
+use tar::EntryType;
+use tracing::warn;
+use visualvault_models::{ArchiveMedia, extraction_folder};
+use zip::ZipArchive;
+use zip::read::ZipFile;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ArchiveKind {
+    Zip,
+    Tar,
+    TarGz,
+}
+
+impl ArchiveKind {
+    fn of(path: &Path) -> Option<Self> {
+        const SUFFIXES: [(&str, ArchiveKind); 4] = [
+            (".zip", ArchiveKind::Zip),
+            (".tar", ArchiveKind::Tar),
+            (".tar.gz", ArchiveKind::TarGz),
+            (".tgz", ArchiveKind::TarGz),
+        ];
+        let name = path.file_name()?.to_string_lossy().to_lowercase();
+        SUFFIXES
+            .into_iter()
+            .find(|(suffix, _)| name.ends_with(suffix))
+            .map(|(_, kind)| kind)
+    }
+}
+
+/// Whether `path` is named like a zip, tar or gzipped tar archive
+#[must_use]
+pub fn is_archive(path: &Path) -> bool {
+    ArchiveKind::of(path).is_some()
+}
+
+/// Lists the files in a zip, tar or gzipped tar archive without extracting anything.
+///
+/// Only the central directory of zip archives is read; tar archives are read through once.
+///
+/// # Errors
+///
+/// Returns an error if the archive cannot be read or is not in a supported format.
+pub fn list_archive(archive: &Path) -> Result<Vec<ArchiveMedia>> {
+    let media = |name: String, size: u64, modified: Option<NaiveDateTime>| ArchiveMedia {
+        archive: archive.to_path_buf(),
+        name,
+        size,
+        modified,
+    };
+    let mut files = Vec::new();
+    match ArchiveKind::of(archive) {
+        Some(ArchiveKind::Zip) => {
+            let mut zip = ZipArchive::new(File::open(archive)?)?;
+            for index in 0..zip.len() {
+                let entry = zip.by_index_raw(index)?;
+                if entry.is_dir() {
+                    continue;
+                }
+                if entry.encrypted() {
+                    warn!("Skipping encrypted {} in zip archive", entry.name());
+                    continue;
+                }
+                files.push(media(entry.name().to_string(), entry.size(), zip_modified(&entry)));
+            }
+        }
+        Some(kind) => walk_tar(archive, kind, |name, entry| {
+            files.push(media(name, entry.size(), tar_modified(entry)));
+            Ok(())
+        })?,
+        None => return Err(eyre!("{} is not a zip or tar archive", archive.display())),
+    }
+    Ok(files)
+}
+
+/// Extracts the files called `names` from an archive into its extraction folder next to it,
+/// keeping their folders and modification times, so a rescan picks them up for organizing.
+///
+/// Files that already exist are left alone, and entries whose path would leave the
+/// extraction folder are refused. Returns the files written; failures are logged and skipped.
+///
+/// # Errors
+///
+/// Returns an error if the archive cannot be read or is not in a supported format.
+pub fn extract_archive_media(archive: &Path, names: &[String]) -> Result<Vec<PathBuf>> {
+    let folder = extraction_folder(archive);
+    let mut extracted = Vec::new();
+    let mut record = |name: &str, written: Result<Option<PathBuf>>| match written {
+        Ok(Some(path)) => extracted.push(path),
+        Ok(None) => {}
+        Err(e) => warn!("Failed to extract {} from {}: {}", name, archive.display(), e),
+    };
+    match ArchiveKind::of(archive) {
+        Some(ArchiveKind::Zip) => {
+            let mut zip = ZipArchive::new(File::open(archive)?)?;
+            for name in names {
+                let written = zip.by_name(name).map_err(Into::into).and_then(|mut entry| {
+                    let modified = zip_modified(&entry);
+                    extract_entry(&folder, name, modified, &mut entry)
+                });
+                record(name, written);
+            }
+        }
+        Some(kind) => {
+            let names: HashSet<&str> = names.iter().map(String::as_str).collect();
+            walk_tar(archive, kind, |name, entry| {
+                if names.contains(name.as_str()) {
+                    let modified = tar_modified(entry);
+                    record(&name, extract_entry(&folder, &name, modified, entry));
+                }
+                Ok(())
+            })?;
+        }
+        None => return Err(eyre!("{} is not a zip or tar archive", archive.display())),
+    }
+    Ok(extracted)
+}
+
+/// Writes one entry below `folder`, returning where, or `None` if it was skipped
+fn extract_entry(
+    folder: &Path,
+    name: &str,
+    modified: Option<NaiveDateTime>,
+    content: &mut dyn Read,
+) -> Result<Option<PathBuf>> {
+    let relative = Path::new(name);
+    if !relative
+        .components()
+        .all(|component| matches!(component, Component::Normal(_)))
+    {
+        warn!("Refusing to extract {}: the path leaves the extraction folder", name);
+        return Ok(None);
+    }
+    let target = folder.join(relative);
+    if target.exists() {
+        warn!("Not extracting {}: {} already exists", name, target.display());
+        return Ok(None);
+    }
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    // The zip reader checks the CRC of an entry once it is read to the end
+    let mut write = || -> Result<()> {
+        let mut file = File::create_new(&target)?;
+        io::copy(content, &mut file)?;
+        if let Some(time) = modified.and_then(|modified| Local.from_local_datetime(&modified).earliest()) {
+            file.set_modified(time.into())?;
+        }
+        Ok(())
+    };
+    if let Err(e) = write() {
+        let _ = fs::remove_file(&target);
+        return Err(e);
+    }
+    Ok(Some(target))
+}
+
+fn zip_modified(entry: &ZipFile<'_>) -> Option<NaiveDateTime> {
+    entry
+        .last_modified()
+        .and_then(|time| NaiveDateTime::try_from(time).ok())
+}
+
+/// Reads through a tar archive, handing every regular file and its name to `visit`.
+///
+/// Content `visit` does not read is skipped.
+fn walk_tar(
+    archive: &Path,
+    kind: ArchiveKind,
+    mut visit: impl FnMut(String, &mut tar::Entry<'_, Box<dyn Read>>) -> Result<()>,
+) -> Result<()> {
+    let file = BufReader::new(File::open(archive)?);
+    let reader: Box<dyn Read> = match kind {
+        ArchiveKind::TarGz => Box::new(MultiGzDecoder::new(file)),
+        _ => Box::new(file),
+    };
+    for entry in tar::Archive::new(reader).entries()? {
+        let mut entry = entry?;
+        if matches!(entry.header().entry_type(), EntryType::Regular | EntryType::Continuous) {
+            let name = entry.path()?.to_string_lossy().into_owned();
+            visit(name, &mut entry)?;
+        }
+    }
+    Ok(())
+}
+
+fn tar_modified(entry: &tar::Entry<'_, impl Read>) -> Option<NaiveDateTime> {
+    let seconds = i64::try_from(entry.header().mtime().ok()?).ok()?;
+    let utc = DateTime::from_timestamp(seconds, 0)?;
+    Some(utc.with_timezone(&Local).naive_local())
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use chrono::NaiveDate;
+    use flate2::Compression;
+    use flate2::write::GzEncoder;
+    use std::io::{Cursor, Write};
+    use zip::write::SimpleFileOptions;
+
+    /// A zip archive of deflated `files`, dated 2024-03-15 10:30:00
+    fn zip_archive(files: &[(&str, &[u8])]) -> Vec<u8> {
+        let options = SimpleFileOptions::default()
+            .compression_method(zip::CompressionMethod::Deflated)
+            .last_modified_time(zip::DateTime::from_date_and_time(2024, 3, 15, 10, 30, 0).unwrap());
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, content) in files {
+            writer.start_file(*name, options).unwrap();
+            writer.write_all(content).unwrap();
+        }
+        writer.finish().unwrap().into_inner()
+    }
+
+    /// A tar archive of `files`, modified at the start of 2024 UTC
+    fn tar_archive(files: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut builder = tar::Builder::new(Vec::new());
+        for (name, content) in files {
+            let mut header = tar::Header::new_ustar();
+            header.set_size(content.len() as u64);
+            header.set_mtime(1_704_067_200);
+            header.set_mode(0o644);
+            builder.append_data(&mut header, name, *content).unwrap();
+        }
+        builder.into_inner().unwrap()
+    }
+
+    #[test]
+    fn test_list_and_extract_zip() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = dir.path().join("Trip.zip");
+        fs::write(
+            &archive,
+            zip_archive(&[
+                ("DCIM/a.jpg", b"first photo"),
+                ("notes.txt", b"notes"),
+                ("../evil.jpg", b"x"),
+            ]),
+        )
+        .unwrap();
+
+        let files = list_archive(&archive).unwrap();
+        let names: Vec<_> = files.iter().map(|file| file.name.as_str()).collect();
+        assert_eq!(names, ["DCIM/a.jpg", "notes.txt", "../evil.jpg"]);
+        assert_eq!(files[0].size, 11);
+        assert_eq!(
+            files[0].modified,
+            NaiveDate::from_ymd_opt(2024, 3, 15).unwrap().and_hms_opt(10, 30, 0)
+        );
+
+        let wanted = ["DCIM/a.jpg".to_string(), "../evil.jpg".to_string()];
+        let extracted = extract_archive_media(&archive, &wanted).unwrap();
+        assert_eq!(extracted, [dir.path().join("Trip/DCIM/a.jpg")]);
+        assert_eq!(fs::read(&extracted[0]).unwrap(), b"first photo");
+        assert!(!dir.path().join("evil.jpg").exists());
+
+        // Extracting again leaves the existing file alone
+        assert!(extract_archive_media(&archive, &wanted).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_list_and_extract_gzipped_tar() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = dir.path().join("dump.tar.gz");
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder
+            .write_all(&tar_archive(&[("a.jpg", b"first photo"), ("clips/b.mp4", &[7; 600])]))
+            .unwrap();
+        fs::write(&archive, encoder.finish().unwrap()).unwrap();
+
+        let files = list_archive(&archive).unwrap();
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[1].name, "clips/b.mp4");
+        assert_eq!(files[1].size, 600);
+        assert_eq!(
+            files[1].modified,
+            DateTime::from_timestamp(1_704_067_200, 0).map(|utc| utc.with_timezone(&Local).naive_local())
+        );
+
+        let extracted = extract_archive_media(&archive, &["clips/b.mp4".to_string()]).unwrap();
+        assert_eq!(extracted, [dir.path().join("dump/clips/b.mp4")]);
+        assert_eq!(fs::read(&extracted[0]).unwrap(), [7; 600]);
+    }
+
+    #[test]
+    fn test_is_archive() {
+        assert!(is_archive(Path::new("photos.ZIP")));
+        assert!(is_archive(Path::new("photos.tar.gz")));
+        assert!(is_archive(Path::new("photos.tgz")));
+        assert!(!is_archive(Path::new("photo.jpg")));
+        assert!(list_archive(Path::new("photo.jpg")).is_err());
+    }
+}
@@ -0,0 +1,290 @@
+use color_eyre::eyre::Result;
+use std::fs::File;
+use std::io::{ErrorKind, Read, Seek, SeekFrom};
+use std::path::Path;
+use symphonia_core::errors::Error as SymphoniaError;
+use symphonia_core::io::{BufReader, MediaSourceStream, MediaSourceStreamOptions};
+use symphonia_core::meta::{MetadataBuilder, StandardTagKey, Tag};
+use symphonia_metadata::{flac, id3v1, id3v2};
+use visualvault_models::AudioMetadata;
+
+const ID3V1_SIZE: usize = 128;
+const ID3V1_OFFSET: i64 = -128;
+const FLAC_VORBIS_COMMENT: u8 = 4;
+
+/// Reads artist, album, title and year tags from an audio file.
+///
+/// `ID3v2` and `ID3v1` tags are supported for any format, Vorbis comments for FLAC.
+/// Fields missing from the `ID3v2` tag are filled from an `ID3v1` tag when both are present.
+/// Returns `None` when the file carries no recognizable tags.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be opened or read.
+pub fn read_audio_metadata(path: &Path) -> Result<Option<AudioMetadata>> {
+    let mut file = File::open(path)?;
+
+    let mut magic = Vec::with_capacity(4);
+    file.by_ref().take(4).read_to_end(&mut magic)?;
+    file.seek(SeekFrom::Start(0))?;
+
+    // Tags in order of precedence, so the ID3v1 tag only fills in what the others leave out
+    let mut tags = Vec::new();
+    if magic.starts_with(b"ID3") {
+        let mut builder = MetadataBuilder::new();
+        keep_tags_read(id3v2::read_id3v2(&mut stream(&file)?, &mut builder))?;
+        tags.extend_from_slice(builder.metadata().tags());
+    } else if magic == b"fLaC" {
+        tags.extend(read_flac_comments(&mut file)?);
+    }
+    tags.extend(read_id3v1(&mut file)?);
+
+    let text = |key| {
+        tags.iter()
+            .filter(|tag| tag.std_key == Some(key))
+            .find_map(|tag| clean(&tag.value.to_string()))
+    };
+    let metadata = AudioMetadata {
+        artist: text(StandardTagKey::Artist),
+        album: text(StandardTagKey::Album),
+        title: text(StandardTagKey::TrackTitle),
+        year: text(StandardTagKey::Date).as_deref().and_then(parse_year),
+    };
+    Ok((!metadata.is_empty()).then_some(metadata))
+}
+
+/// Reads the file from its current position, sharing the cursor with `file`
+fn stream(file: &File) -> Result<MediaSourceStream> {
+    Ok(MediaSourceStream::new(
+        Box::new(file.try_clone()?),
+        MediaSourceStreamOptions::default(),
+    ))
+}
+
+/// Ignores malformed or truncated tags, keeping what was read before the problem, but not
+/// failures to read the file
+fn keep_tags_read(result: symphonia_core::errors::Result<()>) -> Result<()> {
+    match result {
+        Err(SymphoniaError::IoError(e)) if e.kind() != ErrorKind::UnexpectedEof => Err(e.into()),
+        _ => Ok(()),
+    }
+}
+
+/// Reads the Vorbis comments of a FLAC file, seeking past the metadata blocks before them
+fn read_flac_comments(file: &mut File) -> Result<Vec<Tag>> {
+    file.seek(SeekFrom::Start(4))?;
+    loop {
+        let mut header = [0u8; 4];
+        file.read_exact(&mut header)?;
+        let length = u32::from_be_bytes([0, header[1], header[2], header[3]]);
+
+        if header[0] & 0x7f == FLAC_VORBIS_COMMENT {
+            let mut block = vec![0; length as usize];
+            file.read_exact(&mut block)?;
+            let mut builder = MetadataBuilder::new();
+            keep_tags_read(flac::read_comment_block(&mut BufReader::new(&block), &mut builder))?;
+            return Ok(builder.metadata().tags().to_vec());
+        }
+        // The high bit marks the last metadata block
+        if header[0] & 0x80 != 0 {
+            return Ok(Vec::new());
+        }
+        file.seek(SeekFrom::Current(i64::from(length)))?;
+    }
+}
+
+fn read_id3v1(file: &mut File) -> Result<Vec<Tag>> {
+    if file.metadata()?.len() < ID3V1_SIZE as u64 {
+        return Ok(Vec::new());
+    }
+
+    let mut tag = [0u8; ID3V1_SIZE];
+    file.seek(SeekFrom::End(ID3V1_OFFSET))?;
+    file.read_exact(&mut tag)?;
+    let mut builder = MetadataBuilder::new();
+    // Fails on files whose last bytes are not an ID3v1 tag
+    if id3v1::read_id3v1(&mut BufReader::new(&tag), &mut builder).is_err() {
+        return Ok(Vec::new());
+    }
+    Ok(builder.metadata().tags().to_vec())
+}
+
+fn clean(value: &str) -> Option<String> {
+    let value = value.trim_matches(|c: char| c == '\0' || c.is_whitespace());
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+/// Takes the year from values like "1998", "1998-04-01" or "1998-04-01T10:00"
+fn parse_year(value: &str) -> Option<i32> {
+    let year = value.trim().get(..4)?;
+    year.bytes()
+        .all(|b| b.is_ascii_digit())
+        .then(|| year.parse().ok())
+        .flatten()
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    #![allow(clippy::panic_in_result_fn)]
+    #![allow(clippy::unwrap_in_result)]
+    #![allow(clippy::cast_possible_truncation)]
+
+    use super::*;
+    use tempfile::TempDir;
+
+    fn text_frame(id: [u8; 4], encoding: u8, text: &[u8], synchsafe_size: bool) -> Vec<u8> {
+        let size = text.len() as u32 + 1;
+        let size_bytes = if synchsafe_size {
+            [
+                ((size >> 21) & 0x7f) as u8,
+                ((size >> 14) & 0x7f) as u8,
+                ((size >> 7) & 0x7f) as u8,
+                (size & 0x7f) as u8,
+            ]
+        } else {
+            size.to_be_bytes()
+        };
+
+        let mut frame = id.to_vec();
+        frame.extend_from_slice(&size_bytes);
+        frame.extend_from_slice(&[0, 0, encoding]);
+        frame.extend_from_slice(text);
+        frame
+    }
+
+    fn id3v2_tag(version: u8, frames: &[Vec<u8>]) -> Vec<u8> {
+        let mut body: Vec<u8> = frames.concat();
+        body.extend_from_slice(&[0; 16]); // padding
+        let size = body.len() as u32;
+
+        let mut tag = vec![b'I', b'D', b'3', version, 0, 0];
+        tag.extend_from_slice(&[
+            ((size >> 21) & 0x7f) as u8,
+            ((size >> 14) & 0x7f) as u8,
+            ((size >> 7) & 0x7f) as u8,
+            (size & 0x7f) as u8,
+        ]);
+        tag.extend(body);
+        tag
+    }
+
+    fn write_file(dir: &TempDir, name: &str, content: &[u8]) -> std::path::PathBuf {
+        let path = dir.path().join(name);
+        std::fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_id3v23_tags() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let utf16: Vec<u8> = [0xff, 0xfe]
+            .into_iter()
+            .chain("Björk".encode_utf16().flat_map(u16::to_le_bytes))
+            .collect();
+
+        let mut content = id3v2_tag(
+            3,
+            &[
+                text_frame(*b"TPE1", 1, &utf16, false),
+                text_frame(
+                    *b"APIC",
+                    0,
+                    &[b"image/jpeg\0\x03\0".as_slice(), &[0xff; 300]].concat(),
+                    false,
+                ),
+                text_frame(*b"TALB", 0, b"Homogenic", false),
+                text_frame(*b"TYER", 0, b"1997", false),
+            ],
+        );
+        content.extend_from_slice(&[0xff, 0xfb, 0x90, 0x00]);
+        let path = write_file(&temp_dir, "song.mp3", &content);
+
+        let metadata = read_audio_metadata(&path)?.unwrap();
+        assert_eq!(metadata.artist.as_deref(), Some("Björk"));
+        assert_eq!(metadata.album.as_deref(), Some("Homogenic"));
+        assert_eq!(metadata.year, Some(1997));
+        assert_eq!(metadata.title, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_id3v24_utf8_and_recording_date() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let content = id3v2_tag(
+            4,
+            &[
+                text_frame(*b"TIT2", 3, "Jóga\0Alt".as_bytes(), true),
+                text_frame(*b"TDRC", 3, b"1997-09-22", true),
+            ],
+        );
+        let path = write_file(&temp_dir, "song.mp3", &content);
+
+        let metadata = read_audio_metadata(&path)?.unwrap();
+        assert_eq!(metadata.title.as_deref(), Some("Jóga"));
+        assert_eq!(metadata.year, Some(1997));
+        Ok(())
+    }
+
+    #[test]
+    fn test_id3v1_fills_missing_fields() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let mut content = id3v2_tag(3, &[text_frame(*b"TPE1", 0, b"Air", false)]);
+        content.extend_from_slice(&[0; 512]);
+
+        let mut v1 = vec![0u8; 128];
+        v1[..3].copy_from_slice(b"TAG");
+        v1[3..11].copy_from_slice(b"La Femme");
+        v1[33..39].copy_from_slice(b"Other ");
+        v1[63..74].copy_from_slice(b"Moon Safari");
+        v1[93..97].copy_from_slice(b"1998");
+        content.extend(v1);
+        let path = write_file(&temp_dir, "song.mp3", &content);
+
+        let metadata = read_audio_metadata(&path)?.unwrap();
+        assert_eq!(metadata.artist.as_deref(), Some("Air"));
+        assert_eq!(metadata.album.as_deref(), Some("Moon Safari"));
+        assert_eq!(metadata.title.as_deref(), Some("La Femme"));
+        assert_eq!(metadata.year, Some(1998));
+        Ok(())
+    }
+
+    #[test]
+    fn test_flac_vorbis_comments() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let comments = ["ARTIST=Daft Punk", "album=Discovery", "DATE=2001-03-12"];
+
+        let mut block = Vec::new();
+        block.extend_from_slice(&6u32.to_le_bytes());
+        block.extend_from_slice(b"vendor");
+        block.extend_from_slice(&(comments.len() as u32).to_le_bytes());
+        for comment in comments {
+            block.extend_from_slice(&(comment.len() as u32).to_le_bytes());
+            block.extend_from_slice(comment.as_bytes());
+        }
+
+        let mut content = b"fLaC".to_vec();
+        // STREAMINFO block, skipped
+        content.extend_from_slice(&[0, 0, 0, 34]);
+        content.extend_from_slice(&[0; 34]);
+        content.push(0x80 | FLAC_VORBIS_COMMENT);
+        content.extend_from_slice(&(block.len() as u32).to_be_bytes()[1..]);
+        content.extend(block);
+        let path = write_file(&temp_dir, "song.flac", &content);
+
+        let metadata = read_audio_metadata(&path)?.unwrap();
+        assert_eq!(metadata.artist.as_deref(), Some("Daft Punk"));
+        assert_eq!(metadata.album.as_deref(), Some("Discovery"));
+        assert_eq!(metadata.year, Some(2001));
+        Ok(())
+    }
+
+    #[test]
+    fn test_untagged_file() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let path = write_file(&temp_dir, "raw.wav", b"RIFF....WAVEfmt ");
+
+        assert!(read_audio_metadata(&path)?.is_none());
+        Ok(())
+    }
+}
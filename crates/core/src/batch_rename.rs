@@ -0,0 +1,199 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use tracing::warn;
+use visualvault_config::{RenameContext, RenameTemplate};
+use visualvault_models::MediaFile;
+use visualvault_utils::sanitize_path_component;
+
+use crate::read_exif_camera;
+use crate::undo_manager::MoveOperation;
+
+/// Plans renaming `files` in place, numbering them from 1 in the given order.
+///
+/// Only files whose name changes are returned. A new name that is already taken, on disk or
+/// by an earlier file of the batch, gets a " (n)" suffix. The camera is read from EXIF data
+/// when the template uses it, so this blocks on file I/O.
+#[must_use]
+pub fn plan_batch_rename(files: &[Arc<MediaFile>], template: &RenameTemplate) -> Vec<MoveOperation> {
+    let mut taken = HashSet::new();
+    let mut operations = Vec::new();
+
+    for (idx, file) in files.iter().enumerate() {
+        let camera = if template.uses("camera") {
+            read_exif_camera(&file.path).unwrap_or_else(|e| {
+                warn!("Failed to read camera from {}: {}", file.path.display(), e);
+                None
+            })
+        } else {
+            None
+        };
+
+        let name = render_file_name(template, file, camera.as_deref(), idx + 1);
+        let dir = file.path.parent().unwrap_or_else(|| Path::new(""));
+        let destination = unique_destination(dir, &name, &file.path, &taken);
+        taken.insert(destination.clone());
+
+        if destination != file.path {
+            operations.push(MoveOperation {
+                source: file.path.clone(),
+                destination,
+            });
+        }
+    }
+
+    operations
+}
+
+/// Renames files as planned, skipping any whose new name was taken in the meantime.
+///
+/// Returns the renames that were actually applied.
+#[must_use]
+pub fn apply_batch_rename(operations: &[MoveOperation]) -> Vec<MoveOperation> {
+    operations
+        .iter()
+        .filter(|op| {
+            if op.destination.exists() {
+                warn!(
+                    "Not renaming {}: {} exists",
+                    op.source.display(),
+                    op.destination.display()
+                );
+                return false;
+            }
+            match fs::rename(&op.source, &op.destination) {
+                Ok(()) => true,
+                Err(e) => {
+                    warn!("Failed to rename {}: {}", op.source.display(), e);
+                    false
+                }
+            }
+        })
+        .cloned()
+        .collect()
+}
+
+/// Renders the template for a file, keeping its extension.
+///
+/// The name is made safe for every platform; if nothing usable remains, the original stem is kept.
+pub(crate) fn render_file_name(
+    template: &RenameTemplate,
+    file: &MediaFile,
+    camera: Option<&str>,
+    counter: usize,
+) -> String {
+    let path = Path::new(file.name.as_ref());
+    let original = path
+        .file_stem()
+        .map_or_else(|| file.name.to_string(), |s| s.to_string_lossy().to_string());
+    let stem = sanitize_path_component(&template.render(&RenameContext {
+        modified: file.modified,
+        original: &original,
+        camera,
+        counter,
+    }));
+    let stem = if stem.is_empty() { original } else { stem };
+
+    match path.extension() {
+        Some(ext) => format!("{stem}.{}", ext.to_string_lossy()),
+        None => stem,
+    }
+}
+
+pub(crate) fn unique_destination(dir: &Path, name: &str, source: &Path, taken: &HashSet<PathBuf>) -> PathBuf {
+    let is_free = |candidate: &PathBuf| candidate == source || (!taken.contains(candidate) && !candidate.exists());
+
+    let candidate = dir.join(name);
+    if is_free(&candidate) {
+        return candidate;
+    }
+
+    let path = Path::new(name);
+    let stem = path
+        .file_stem()
+        .map_or_else(String::new, |s| s.to_string_lossy().to_string());
+    let extension = path.extension().map(|e| e.to_string_lossy().to_string());
+
+    (1..=999)
+        .map(|n| {
+            dir.join(match &extension {
+                Some(ext) => format!("{stem} ({n}).{ext}"),
+                None => format!("{stem} ({n})"),
+            })
+        })
+        .find(|candidate| is_free(candidate))
+        .unwrap_or(candidate)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    #![allow(clippy::panic_in_result_fn)]
+    #![allow(clippy::unwrap_in_result)]
+
+    use super::*;
+    use crate::test_support::media_file;
+    use chrono::{DateTime, Local, TimeZone};
+    use color_eyre::Result;
+    use tempfile::TempDir;
+
+    fn taken() -> DateTime<Local> {
+        Local.with_ymd_and_hms(2024, 3, 15, 14, 30, 22).unwrap()
+    }
+
+    #[test]
+    fn test_plan_numbers_files_and_avoids_existing_names() -> Result<()> {
+        let temp = TempDir::new()?;
+        let files: Vec<_> = ["b.jpg", "a.JPG", "c.png"]
+            .iter()
+            .map(|name| {
+                let path = temp.path().join(name);
+                fs::write(&path, b"img").unwrap();
+                media_file(path).modified(taken()).arc()
+            })
+            .collect();
+        fs::write(temp.path().join("2024-03-15_0002.JPG"), b"other")?;
+
+        let template: RenameTemplate = "{date}_{counter}".parse().unwrap();
+        let operations = plan_batch_rename(&files, &template);
+
+        let names: Vec<_> = operations
+            .iter()
+            .map(|op| op.destination.file_name().unwrap().to_string_lossy().to_string())
+            .collect();
+        assert_eq!(
+            names,
+            ["2024-03-15_0001.jpg", "2024-03-15_0002 (1).JPG", "2024-03-15_0003.png"]
+        );
+
+        let applied = apply_batch_rename(&operations);
+        assert_eq!(applied.len(), 3);
+        assert!(temp.path().join("2024-03-15_0001.jpg").exists());
+        assert!(!temp.path().join("b.jpg").exists());
+        Ok(())
+    }
+
+    #[test]
+    fn test_plan_skips_unchanged_names_and_dedupes_within_batch() -> Result<()> {
+        let temp = TempDir::new()?;
+        let files: Vec<_> = ["one.jpg", "two.jpg"]
+            .iter()
+            .map(|name| {
+                let path = temp.path().join(name);
+                fs::write(&path, b"img").unwrap();
+                media_file(path).modified(taken()).arc()
+            })
+            .collect();
+
+        let unchanged: RenameTemplate = "{original}".parse().unwrap();
+        assert!(plan_batch_rename(&files, &unchanged).is_empty());
+
+        let same: RenameTemplate = "{date}".parse().unwrap();
+        let operations = plan_batch_rename(&files, &same);
+        assert_eq!(operations[0].destination, temp.path().join("2024-03-15.jpg"));
+        assert_eq!(operations[1].destination, temp.path().join("2024-03-15 (1).jpg"));
+        Ok(())
+    }
+}
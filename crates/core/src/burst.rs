@@ -0,0 +1,229 @@
+use std::path::Path;
+use std::sync::Arc;
+
+use chrono::NaiveDateTime;
+use color_eyre::eyre::{Result, eyre};
+use image::GrayImage;
+use rayon::prelude::{IntoParallelIterator, ParallelIterator};
+use smallvec::SmallVec;
+use tracing::{info, warn};
+use visualvault_models::{DuplicateGroup, DuplicateStats, FileType, MediaFile, MediaMetadata};
+
+use crate::{decode_heif_image, read_exif_dates, read_heif_image};
+
+/// Shots taken at most this many seconds after the previous one by the same camera belong to one burst
+const BURST_GAP_SECONDS: i64 = 2;
+
+/// Images are scaled to fit this edge length before their sharpness is measured
+const SHARPNESS_SIZE: u32 = 512;
+
+/// A photo with the camera and the time it was taken
+#[derive(Debug, Clone)]
+struct Shot {
+    file: Arc<MediaFile>,
+    camera: Option<String>,
+    taken: NaiveDateTime,
+}
+
+/// Groups photos taken in quick succession by the same camera, sharpest photo first.
+///
+/// Photos belong to a burst when they were taken within two seconds of the previous one,
+/// using the EXIF capture time or else the modification time. Every photo of a burst is
+/// scored by the variance of its Laplacian; photos that cannot be decoded score zero.
+///
+/// # Errors
+///
+/// Returns an error if the background work reading the photos fails.
+pub async fn find_bursts(files: &[Arc<MediaFile>]) -> Result<DuplicateStats> {
+    let images: Vec<_> = files
+        .iter()
+        .filter(|file| file.file_type == FileType::Image)
+        .cloned()
+        .collect();
+    if images.len() < 2 {
+        return Ok(DuplicateStats::new());
+    }
+
+    info!("Looking for bursts among {} photos", images.len());
+    let stats = tokio::task::spawn_blocking(move || {
+        let shots = images.into_par_iter().map(read_shot).collect();
+        let bursts = group_bursts(shots);
+        let scored = bursts
+            .into_par_iter()
+            .map(|burst| burst.into_iter().map(|file| (sharpness(&file.path), file)).collect())
+            .collect();
+        build_stats(scored)
+    })
+    .await
+    .map_err(|e| eyre!("Burst detection failed: {e}"))?;
+
+    info!("Found {} bursts", stats.total_groups);
+    Ok(stats)
+}
+
+fn read_shot(file: Arc<MediaFile>) -> Shot {
+    let taken = read_exif_dates(&file.path)
+        .ok()
+        .and_then(|dates| dates.taken)
+        .unwrap_or_else(|| file.modified.naive_local());
+    let camera = match &file.metadata {
+        Some(MediaMetadata::Image(image)) => image.capture.camera(),
+        _ => None,
+    };
+    Shot { file, camera, taken }
+}
+
+/// Splits the shots of every camera into runs without gaps longer than [`BURST_GAP_SECONDS`]
+fn group_bursts(mut shots: Vec<Shot>) -> Vec<Vec<Arc<MediaFile>>> {
+    shots.sort_by(|a, b| a.camera.cmp(&b.camera).then(a.taken.cmp(&b.taken)));
+
+    let mut bursts: Vec<(NaiveDateTime, Vec<Arc<MediaFile>>)> = Vec::new();
+    let mut previous: Option<Shot> = None;
+    for shot in shots {
+        let continues = previous.as_ref().is_some_and(|last| {
+            last.camera == shot.camera && (shot.taken - last.taken).num_seconds() <= BURST_GAP_SECONDS
+        });
+        match bursts.last_mut() {
+            Some((_, files)) if continues => files.push(Arc::clone(&shot.file)),
+            _ => bursts.push((shot.taken, vec![Arc::clone(&shot.file)])),
+        }
+        previous = Some(shot);
+    }
+
+    bursts.retain(|(_, files)| files.len() > 1);
+    bursts.sort_by_key(|(started, _)| *started);
+    bursts.into_iter().map(|(_, files)| files).collect()
+}
+
+/// Builds the stats of the bursts, keeping the sharpest photo of each first
+fn build_stats(bursts: Vec<Vec<(f64, Arc<MediaFile>)>>) -> DuplicateStats {
+    let mut stats = DuplicateStats::new();
+    for mut burst in bursts {
+        burst.sort_by(|(a, _), (b, _)| b.total_cmp(a));
+        let files: SmallVec<[Arc<MediaFile>; 4]> = burst.into_iter().map(|(_, file)| file).collect();
+        let wasted_space = files.iter().skip(1).map(|file| file.size).sum();
+        stats.total_duplicates += files.len() - 1;
+        stats.total_wasted_space += wasted_space;
+        stats.groups.push(DuplicateGroup::new(files, wasted_space));
+    }
+    stats.total_groups = stats.groups.len();
+    stats
+}
+
+/// Sharpness of a photo, or zero if it cannot be decoded
+fn sharpness(path: &Path) -> f64 {
+    match decode_gray(path) {
+        Ok(gray) => laplacian_variance(&gray),
+        Err(e) => {
+            warn!("Failed to decode {} to measure its sharpness: {}", path.display(), e);
+            0.0
+        }
+    }
+}
+
+fn decode_gray(path: &Path) -> Result<GrayImage> {
+    let image = if read_heif_image(path)?.is_some() {
+        decode_heif_image(path)?
+    } else {
+        image::open(path)?
+    };
+    Ok(image.thumbnail(SHARPNESS_SIZE, SHARPNESS_SIZE).to_luma8())
+}
+
+/// Variance of the 4-neighbour Laplacian; blurry photos have few edges and score low
+fn laplacian_variance(gray: &GrayImage) -> f64 {
+    let (width, height) = gray.dimensions();
+    if width < 3 || height < 3 {
+        return 0.0;
+    }
+
+    let pixel = |x: u32, y: u32| f64::from(gray.get_pixel(x, y)[0]);
+    let mut sum = 0.0;
+    let mut sum_of_squares = 0.0;
+    for y in 1..height - 1 {
+        for x in 1..width - 1 {
+            let laplacian = pixel(x - 1, y) + pixel(x + 1, y) + pixel(x, y - 1) + pixel(x, y + 1) - 4.0 * pixel(x, y);
+            sum += laplacian;
+            sum_of_squares += laplacian * laplacian;
+        }
+    }
+
+    let count = f64::from((width - 2) * (height - 2));
+    let mean = sum / count;
+    sum_of_squares / count - mean * mean
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+    use crate::test_support::media_file;
+
+    fn photo(name: &str, size: u64) -> Arc<MediaFile> {
+        media_file(format!("/photos/{name}")).size(size).arc()
+    }
+
+    fn shot(name: &str, camera: &str, time: &str) -> Shot {
+        Shot {
+            file: photo(name, 100),
+            camera: Some(camera.to_string()),
+            taken: NaiveDateTime::parse_from_str(time, "%Y-%m-%d %H:%M:%S").unwrap(),
+        }
+    }
+
+    fn names(files: &[Arc<MediaFile>]) -> Vec<String> {
+        files.iter().map(|file| file.name.to_string()).collect()
+    }
+
+    #[test]
+    fn test_group_bursts() {
+        let bursts = group_bursts(vec![
+            shot("c.jpg", "Canon EOS R5", "2024-03-15 10:00:03"),
+            shot("a.jpg", "Canon EOS R5", "2024-03-15 10:00:00"),
+            shot("b.jpg", "Canon EOS R5", "2024-03-15 10:00:01"),
+            shot("later.jpg", "Canon EOS R5", "2024-03-15 10:00:06"),
+            shot("phone.jpg", "Apple iPhone 15", "2024-03-15 10:00:02"),
+            shot("x.jpg", "Apple iPhone 15", "2024-03-15 09:00:00"),
+            shot("y.jpg", "Apple iPhone 15", "2024-03-15 09:00:00"),
+        ]);
+
+        assert_eq!(bursts.len(), 2);
+        assert_eq!(names(&bursts[0]), ["x.jpg", "y.jpg"]);
+        assert_eq!(names(&bursts[1]), ["a.jpg", "b.jpg", "c.jpg"]);
+    }
+
+    #[test]
+    fn test_build_stats_keeps_sharpest_first() {
+        let stats = build_stats(vec![vec![
+            (10.0, photo("blurry.jpg", 300)),
+            (250.0, photo("sharp.jpg", 200)),
+            (80.0, photo("shaky.jpg", 100)),
+        ]]);
+
+        assert_eq!(stats.total_groups, 1);
+        assert_eq!(stats.total_duplicates, 2);
+        assert_eq!(stats.total_wasted_space, 400);
+        assert_eq!(names(&stats.groups[0].files), ["sharp.jpg", "shaky.jpg", "blurry.jpg"]);
+    }
+
+    #[test]
+    fn test_laplacian_variance() {
+        let flat = GrayImage::from_pixel(16, 16, image::Luma([128]));
+        assert!(laplacian_variance(&flat).abs() < f64::EPSILON);
+
+        let checkered = GrayImage::from_fn(16, 16, |x, y| image::Luma([if (x + y) % 2 == 0 { 0 } else { 255 }]));
+        let soft = GrayImage::from_fn(16, 16, |x, _| image::Luma([u8::try_from(x * 8).unwrap()]));
+        assert!(laplacian_variance(&checkered) > laplacian_variance(&soft));
+        assert!(laplacian_variance(&GrayImage::new(2, 2)).abs() < f64::EPSILON);
+    }
+
+    #[tokio::test]
+    async fn test_find_bursts_keeps_unreadable_photos() {
+        let stats = find_bursts(&[photo("missing-1.jpg", 10), photo("missing-2.jpg", 10)])
+            .await
+            .unwrap();
+        // Both fall back to the same modification time and camera, so they form a burst
+        assert_eq!(stats.total_groups, 1);
+        assert_eq!(stats.groups[0].files.len(), 2);
+    }
+}
@@ -1,8 +1,10 @@
-use crate::database_cache::{CacheEntry, CacheStats};
+use crate::database_cache::{CacheEntry, CacheStats, ExtensionStats, FileQuery, ImportRecord};
 use async_trait::async_trait;
 use chrono::{DateTime, Local};
 use color_eyre::Result;
+use std::collections::HashSet;
 use std::path::{Path, PathBuf};
+use visualvault_models::{FileTags, StatsSnapshot};
 
 /// Cache trait for abstracting different cache implementations
 #[async_trait]
@@ -14,9 +16,20 @@ pub trait Cache: Send + Sync {
     async fn remove_stale_entries(&self) -> Result<usize>;
     async fn len(&self) -> Result<usize>;
     async fn is_empty(&self) -> Result<bool>;
+    async fn get_file_tags(&self, hash: &str) -> Result<Option<FileTags>>;
+    async fn set_file_tags(&self, hash: &str, size: u64, tags: &FileTags) -> Result<()>;
+    async fn tagged_sizes(&self) -> Result<HashSet<u64>>;
+    async fn query_files(&self, query: &FileQuery) -> Result<Vec<CacheEntry>>;
+    async fn count_files(&self, query: &FileQuery) -> Result<usize>;
+    async fn duplicate_groups(&self, query: &FileQuery) -> Result<Vec<Vec<CacheEntry>>>;
+    async fn extension_stats(&self, query: &FileQuery) -> Result<Vec<ExtensionStats>>;
+    async fn record_import(&self, record: &ImportRecord) -> Result<()>;
+    async fn import_history(&self, limit: usize) -> Result<Vec<ImportRecord>>;
+    async fn record_stats(&self, snapshot: &StatsSnapshot) -> Result<()>;
+    async fn stats_history(&self, limit: usize) -> Result<Vec<StatsSnapshot>>;
 }
 
-/// Implement the Cache trait for DatabaseCache
+/// Implement the Cache trait for `DatabaseCache`
 #[async_trait]
 impl Cache for crate::DatabaseCache {
     async fn get(&self, path: &Path, size: u64, modified: &DateTime<Local>) -> Result<Option<CacheEntry>> {
@@ -45,4 +58,48 @@ impl Cache for crate::DatabaseCache {
     async fn is_empty(&self) -> Result<bool> {
         Ok(self.len().await? == 0)
     }
+
+    async fn get_file_tags(&self, hash: &str) -> Result<Option<FileTags>> {
+        self.get_file_tags(hash).await
+    }
+
+    async fn set_file_tags(&self, hash: &str, size: u64, tags: &FileTags) -> Result<()> {
+        self.set_file_tags(hash, size, tags).await
+    }
+
+    async fn tagged_sizes(&self) -> Result<HashSet<u64>> {
+        self.tagged_sizes().await
+    }
+
+    async fn query_files(&self, query: &FileQuery) -> Result<Vec<CacheEntry>> {
+        self.query_files(query).await
+    }
+
+    async fn count_files(&self, query: &FileQuery) -> Result<usize> {
+        self.count_files(query).await
+    }
+
+    async fn duplicate_groups(&self, query: &FileQuery) -> Result<Vec<Vec<CacheEntry>>> {
+        self.duplicate_groups(query).await
+    }
+
+    async fn extension_stats(&self, query: &FileQuery) -> Result<Vec<ExtensionStats>> {
+        self.extension_stats(query).await
+    }
+
+    async fn record_import(&self, record: &ImportRecord) -> Result<()> {
+        self.record_import(record).await
+    }
+
+    async fn import_history(&self, limit: usize) -> Result<Vec<ImportRecord>> {
+        self.import_history(limit).await
+    }
+
+    async fn record_stats(&self, snapshot: &StatsSnapshot) -> Result<()> {
+        self.record_stats(snapshot).await
+    }
+
+    async fn stats_history(&self, limit: usize) -> Result<Vec<StatsSnapshot>> {
+        self.stats_history(limit).await
+    }
 }
@@ -0,0 +1,214 @@
+use std::collections::{BTreeMap, HashMap};
+use std::ffi::OsStr;
+use std::fmt::Write as _;
+use std::hash::BuildHasher;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use color_eyre::eyre::Result;
+use tokio::fs;
+use tracing::{error, warn};
+use visualvault_config::{ChecksumSidecars, HashAlgorithm};
+
+use crate::DuplicateDetector;
+
+/// Checksum file in the format of `sha256sum`, checked with `sha256sum -c SHA256SUMS`
+pub const SHA256SUMS_FILE: &str = "SHA256SUMS";
+/// Checksum file in Simple File Verification format, with CRC32 checksums
+pub const SFV_FILE: &str = "checksums.sfv";
+
+/// Returns whether `name` is the name of a checksum file written by organize runs.
+#[must_use]
+pub fn is_checksum_sidecar(name: &OsStr) -> bool {
+    name == SHA256SUMS_FILE || name == SFV_FILE
+}
+
+/// Writes checksum files into every folder an organize run placed files in, given as source
+/// and destination pairs.
+///
+/// SHA-256 hashes already calculated while looking for duplicates are taken from `hashes`, by
+/// source path, so only the other files are read. Checksums are merged into the file already
+/// in a folder, replacing those of files with the same name, so repeated runs keep every file
+/// of the folder listed. Returns how many checksum files were written.
+pub async fn write_checksum_sidecars<S: BuildHasher>(
+    placed: &[(PathBuf, PathBuf)],
+    hashes: &HashMap<PathBuf, Arc<str>, S>,
+    format: ChecksumSidecars,
+) -> usize {
+    if format == ChecksumSidecars::Off || placed.is_empty() {
+        return 0;
+    }
+
+    let mut folders: BTreeMap<PathBuf, BTreeMap<String, String>> = BTreeMap::new();
+    for (source, placed_at) in placed {
+        let (Some(folder), Some(name)) = (placed_at.parent(), placed_at.file_name()) else {
+            continue;
+        };
+        let name = name.to_string_lossy();
+        // Neither format can list names spanning lines
+        if name.contains(['\n', '\r']) {
+            continue;
+        }
+        match checksum(placed_at, hashes.get(source), format).await {
+            Ok(checksum) => {
+                folders
+                    .entry(folder.to_path_buf())
+                    .or_default()
+                    .insert(name.into_owned(), checksum);
+            }
+            Err(e) => warn!("Could not checksum {}: {}", placed_at.display(), e),
+        }
+    }
+
+    let mut written = 0;
+    for (folder, checksums) in folders {
+        match update_sidecar(&folder, checksums, format).await {
+            Ok(()) => written += 1,
+            Err(e) => error!("Failed to write the checksums in {}: {}", folder.display(), e),
+        }
+    }
+    written
+}
+
+/// Checksum of the file at `path` in `format`, reusing `known`, a hash tagged with its
+/// algorithm, when it is a SHA-256 hash
+async fn checksum(path: &Path, known: Option<&Arc<str>>, format: ChecksumSidecars) -> Result<String> {
+    if format == ChecksumSidecars::Sfv {
+        let path = path.to_path_buf();
+        return Ok(tokio::task::spawn_blocking(move || crc32(&path)).await??);
+    }
+    let known = known.and_then(|hash| {
+        (DuplicateDetector::algorithm_of(hash) == Some(HashAlgorithm::Sha256))
+            .then(|| hash.split_once(':').map(|(_, hex)| hex.to_string()))
+            .flatten()
+    });
+    match known {
+        Some(hex) => Ok(hex),
+        None => DuplicateDetector::calculate_file_hash(path).await,
+    }
+}
+
+fn crc32(path: &Path) -> std::io::Result<String> {
+    let mut file = std::fs::File::open(path)?;
+    let mut hasher = crc32fast::Hasher::new();
+    let mut buffer = vec![0; 64 * 1024];
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(format!("{:08X}", hasher.finalize()))
+}
+
+/// Merges `checksums`, by file name, into the checksum file of `folder`
+async fn update_sidecar(folder: &Path, checksums: BTreeMap<String, String>, format: ChecksumSidecars) -> Result<()> {
+    let path = folder.join(match format {
+        ChecksumSidecars::Sfv => SFV_FILE,
+        _ => SHA256SUMS_FILE,
+    });
+    let mut merged = match fs::read_to_string(&path).await {
+        Ok(content) => parse_sidecar(&content, format),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => BTreeMap::new(),
+        Err(e) => return Err(e.into()),
+    };
+    merged.extend(checksums);
+
+    let mut content = String::new();
+    if format == ChecksumSidecars::Sfv {
+        content.push_str("; Generated by VisualVault\n");
+    }
+    for (name, checksum) in &merged {
+        let _ = match format {
+            ChecksumSidecars::Sfv => writeln!(content, "{name} {checksum}"),
+            _ => writeln!(content, "{checksum}  {name}"),
+        };
+    }
+    fs::write(&path, content).await?;
+    Ok(())
+}
+
+/// Reads the checksums of a checksum file by file name, skipping lines it does not understand
+fn parse_sidecar(content: &str, format: ChecksumSidecars) -> BTreeMap<String, String> {
+    content
+        .lines()
+        .filter(|line| !line.trim().is_empty() && !line.starts_with(';'))
+        .filter_map(|line| match format {
+            ChecksumSidecars::Sfv => line
+                .rsplit_once(' ')
+                .map(|(name, checksum)| (name.to_string(), checksum.to_string())),
+            // `sha256sum` marks files read in binary mode with '*'
+            _ => line
+                .split_once("  ")
+                .or_else(|| line.split_once(" *"))
+                .map(|(checksum, name)| (name.to_string(), checksum.to_string())),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    #![allow(clippy::panic_in_result_fn)]
+    #![allow(clippy::unwrap_in_result)]
+
+    use super::*;
+
+    /// SHA-256 of "abc"
+    const ABC_SHA256: &str = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+
+    #[tokio::test]
+    async fn test_sha256sums_reuse_hashes_and_merge() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let folder = dir.path().join("2024").join("03");
+        std::fs::create_dir_all(&folder)?;
+        std::fs::write(folder.join("a.jpg"), b"abc")?;
+        std::fs::write(folder.join("b.jpg"), b"abc")?;
+        std::fs::write(folder.join(SHA256SUMS_FILE), format!("{}  old.jpg\n", "0".repeat(64)))?;
+
+        // A known hash is trusted as is, so a made-up one shows it was not recalculated
+        let known = format!("sha256:{}", "f".repeat(64));
+        let hashes = HashMap::from([(PathBuf::from("/src/a.jpg"), Arc::from(known.as_str()))]);
+        let placed = vec![
+            (PathBuf::from("/src/a.jpg"), folder.join("a.jpg")),
+            (PathBuf::from("/src/b.jpg"), folder.join("b.jpg")),
+        ];
+        let written = write_checksum_sidecars(&placed, &hashes, ChecksumSidecars::Sha256Sums).await;
+        assert_eq!(written, 1);
+
+        let sums = std::fs::read_to_string(folder.join(SHA256SUMS_FILE))?;
+        assert_eq!(
+            sums,
+            format!(
+                "{}  a.jpg\n{ABC_SHA256}  b.jpg\n{}  old.jpg\n",
+                "f".repeat(64),
+                "0".repeat(64)
+            )
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_sfv_ignores_other_hashes() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        std::fs::write(dir.path().join("a.jpg"), b"abc")?;
+        let hashes = HashMap::from([(PathBuf::from("/src/a.jpg"), Arc::from("blake3:0000"))]);
+        let placed = vec![(PathBuf::from("/src/a.jpg"), dir.path().join("a.jpg"))];
+
+        assert_eq!(
+            write_checksum_sidecars(&placed, &hashes, ChecksumSidecars::Off).await,
+            0
+        );
+        assert_eq!(
+            write_checksum_sidecars(&placed, &hashes, ChecksumSidecars::Sfv).await,
+            1
+        );
+        let sfv = std::fs::read_to_string(dir.path().join(SFV_FILE))?;
+        assert_eq!(sfv, "; Generated by VisualVault\na.jpg 352441C2\n");
+        assert_eq!(parse_sidecar(&sfv, ChecksumSidecars::Sfv)["a.jpg"], "352441C2");
+        assert!(is_checksum_sidecar(OsStr::new(SFV_FILE)));
+        Ok(())
+    }
+}
@@ -0,0 +1,78 @@
+use std::cell::RefCell;
+use std::io::Write;
+
+use arboard::Clipboard;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use color_eyre::eyre::Result;
+use tracing::{debug, info};
+
+/// How copied text reached the clipboard
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClipboardMethod {
+    /// The clipboard of the operating system
+    System,
+    /// An OSC 52 escape sequence asking the terminal to set its clipboard
+    Terminal,
+}
+
+thread_local! {
+    /// The system clipboard, kept open once used: on X11 and Wayland copied text is only
+    /// available for as long as the clipboard that set it is alive.
+    static CLIPBOARD: RefCell<Option<Clipboard>> = const { RefCell::new(None) };
+}
+
+/// Copies `text` to the system clipboard.
+///
+/// Over SSH, or when the system clipboard cannot be reached, the text is handed to the
+/// terminal with an OSC 52 escape sequence instead, which most modern terminals copy to the
+/// clipboard of the machine they run on. Terminals without OSC 52 support ignore it.
+///
+/// # Errors
+///
+/// Returns an error if the escape sequence cannot be written to the terminal.
+pub fn copy_to_clipboard(text: &str) -> Result<ClipboardMethod> {
+    let remote = ["SSH_CONNECTION", "SSH_TTY"]
+        .iter()
+        .any(|name| std::env::var_os(name).is_some());
+    if !remote {
+        match copy_to_system_clipboard(text) {
+            Ok(()) => {
+                info!("Copied {} bytes to the clipboard", text.len());
+                return Ok(ClipboardMethod::System);
+            }
+            Err(e) => debug!("System clipboard unavailable: {}", e),
+        }
+    }
+
+    let mut stdout = std::io::stdout();
+    stdout.write_all(osc52_sequence(text).as_bytes())?;
+    stdout.flush()?;
+    info!("Sent {} bytes to the terminal clipboard", text.len());
+    Ok(ClipboardMethod::Terminal)
+}
+
+fn copy_to_system_clipboard(text: &str) -> Result<(), arboard::Error> {
+    CLIPBOARD.with_borrow_mut(|slot| {
+        let clipboard = match slot.take() {
+            Some(clipboard) => clipboard,
+            None => Clipboard::new()?,
+        };
+        slot.insert(clipboard).set_text(text)
+    })
+}
+
+/// OSC 52 sequence that sets the terminal's clipboard to `text`
+fn osc52_sequence(text: &str) -> String {
+    format!("\x1b]52;c;{}\x07", STANDARD.encode(text))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_osc52_sequence_encodes_text() {
+        assert_eq!(osc52_sequence("/photos/a.jpg"), "\x1b]52;c;L3Bob3Rvcy9hLmpwZw==\x07");
+    }
+}
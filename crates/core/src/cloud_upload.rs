@@ -0,0 +1,600 @@
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use color_eyre::eyre::{Result, bail, eyre};
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+use visualvault_config::CloudExport;
+use visualvault_models::{CloudUploadReport, UploadFailure};
+use visualvault_utils::Progress;
+
+/// Size of each part of a multi-part upload; S3 requires at least 5 MiB for all but the last
+const PART_SIZE: u64 = 16 * 1024 * 1024;
+/// Files larger than this many parts are uploaded in parts, the others with a single request
+const MULTIPART_PARTS: u64 = 4;
+/// Attempts of each request before the file is given up on
+const MAX_ATTEMPTS: u32 = 4;
+/// Wait before the first retry, doubled for every further one
+const RETRY_DELAY: Duration = Duration::from_secs(1);
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+/// Longest a single part may take to send or answer
+const TRANSFER_TIMEOUT: Duration = Duration::from_secs(300);
+const SIGNED_HEADERS: &str = "host;x-amz-content-sha256;x-amz-date";
+
+/// Uploads files to an S3-compatible bucket, signing every request with AWS Signature
+/// Version 4.
+///
+/// Files up to 64 MiB are sent with a single `PUT`, larger ones such as videos as a
+/// multi-part upload in 16 MiB parts so a failure only resends one part. Requests failing
+/// with a connection error, `429` or a `5xx` status are retried with exponential backoff.
+pub struct S3Uploader {
+    agent: ureq::Agent,
+    /// Endpoint URL without a trailing slash
+    endpoint: String,
+    /// Authority of the endpoint, as sent in the `Host` header
+    host: String,
+    bucket: String,
+    region: String,
+    access_key: String,
+    secret_key: String,
+    prefix: String,
+    part_size: u64,
+}
+
+/// What the bucket answered to a successful request
+struct Reply {
+    etag: Option<String>,
+    body: String,
+}
+
+impl S3Uploader {
+    /// Creates an uploader for the bucket in `settings`, taking missing credentials from the
+    /// `AWS_ACCESS_KEY_ID` and `AWS_SECRET_ACCESS_KEY` environment variables.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no endpoint or bucket is set, the endpoint is not an `http://` or
+    /// `https://` URL without a path, or credentials are missing.
+    pub fn new(settings: &CloudExport) -> Result<Self> {
+        let endpoint = settings
+            .endpoint
+            .as_deref()
+            .ok_or_else(|| eyre!("No cloud export endpoint configured"))?
+            .trim_end_matches('/');
+        let host = endpoint
+            .strip_prefix("https://")
+            .or_else(|| endpoint.strip_prefix("http://"))
+            .ok_or_else(|| eyre!("Cloud export endpoint '{endpoint}' is not an http:// or https:// URL"))?;
+        if host.is_empty() || host.contains('/') {
+            bail!("Cloud export endpoint '{endpoint}' must not contain a path, set the bucket instead");
+        }
+        if settings.bucket.is_empty() {
+            bail!("No cloud export bucket configured");
+        }
+        let access_key = credential(settings.access_key.as_ref(), "access_key", "AWS_ACCESS_KEY_ID")?;
+        let secret_key = credential(settings.secret_key.as_ref(), "secret_key", "AWS_SECRET_ACCESS_KEY")?;
+
+        Ok(Self {
+            agent: ureq::AgentBuilder::new()
+                .timeout_connect(CONNECT_TIMEOUT)
+                .timeout_read(TRANSFER_TIMEOUT)
+                .timeout_write(TRANSFER_TIMEOUT)
+                .build(),
+            endpoint: endpoint.to_string(),
+            host: host.to_string(),
+            bucket: settings.bucket.clone(),
+            region: settings.region.clone(),
+            access_key,
+            secret_key,
+            prefix: settings.prefix.trim_matches('/').to_string(),
+            part_size: PART_SIZE,
+        })
+    }
+
+    #[must_use]
+    pub fn bucket(&self) -> &str {
+        &self.bucket
+    }
+
+    /// Object key of `path`: its path below `root` joined with `/`, after the configured prefix.
+    ///
+    /// Returns `None` for paths outside `root`.
+    #[must_use]
+    pub fn object_key(&self, root: &Path, path: &Path) -> Option<String> {
+        let parts = path
+            .strip_prefix(root)
+            .ok()?
+            .components()
+            .map(|component| match component {
+                Component::Normal(part) => Some(part.to_string_lossy()),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>()?;
+        if parts.is_empty() {
+            return None;
+        }
+        let key = parts.join("/");
+        Some(if self.prefix.is_empty() {
+            key
+        } else {
+            format!("{}/{key}", self.prefix)
+        })
+    }
+
+    /// Uploads `files`, which lie below `root`, one after another and reports the bytes sent
+    /// to `progress` as they go.
+    ///
+    /// Blocks until every file was uploaded or given up on, so call it from a blocking task.
+    /// A failing file does not stop the others.
+    pub fn upload_files(&self, root: &Path, files: &[PathBuf], progress: &Arc<RwLock<Progress>>) -> CloudUploadReport {
+        let sizes: Vec<u64> = files
+            .iter()
+            .map(|path| std::fs::metadata(path).map_or(0, |metadata| metadata.len()))
+            .collect();
+        progress.blocking_write().start_phase(
+            files.len(),
+            sizes.iter().sum(),
+            format!("Uploading to bucket {}...", self.bucket),
+        );
+
+        let mut report = CloudUploadReport {
+            bucket: self.bucket.clone(),
+            root: root.to_path_buf(),
+            ..CloudUploadReport::default()
+        };
+        let mut bytes_done = 0;
+        for (idx, (path, &size)) in files.iter().zip(&sizes).enumerate() {
+            let name = path.file_name().map_or_else(
+                || path.display().to_string(),
+                |name| name.to_string_lossy().into_owned(),
+            );
+            let mut on_sent = |sent: u64| {
+                progress
+                    .blocking_write()
+                    .set_file_progress(idx, bytes_done + sent, name.as_str());
+            };
+            let uploaded = self
+                .object_key(root, path)
+                .ok_or_else(|| eyre!("Not inside {}", root.display()))
+                .and_then(|key| self.upload_file(path, &key, size, &mut on_sent));
+
+            match uploaded {
+                Ok(()) => {
+                    report.uploaded += 1;
+                    report.bytes += size;
+                }
+                Err(e) => {
+                    warn!("Failed to upload {}: {}", path.display(), e);
+                    report.failed.push(UploadFailure {
+                        path: path.clone(),
+                        error: e.to_string(),
+                    });
+                }
+            }
+            bytes_done += size;
+            progress.blocking_write().set_file_progress(idx + 1, bytes_done, name);
+        }
+
+        progress.blocking_write().is_complete = true;
+        info!(
+            "Uploaded {} of {} files to bucket {}",
+            report.uploaded,
+            report.total(),
+            self.bucket
+        );
+        report
+    }
+
+    /// Uploads the file at `path` as `key`, calling `on_sent` with the bytes sent so far
+    fn upload_file(&self, path: &Path, key: &str, size: u64, on_sent: &mut dyn FnMut(u64)) -> Result<()> {
+        let mut file = File::open(path)?;
+        if size <= MULTIPART_PARTS * self.part_size {
+            let mut body = Vec::new();
+            file.read_to_end(&mut body)?;
+            self.send("PUT", key, "", &body)?;
+            on_sent(size);
+            return Ok(());
+        }
+
+        let created = self.send("POST", key, "uploads=", &[])?;
+        let upload_id =
+            xml_value(&created.body, "UploadId").ok_or_else(|| eyre!("The bucket returned no upload ID for {key}"))?;
+        let upload_query = format!("uploadId={}", uri_encode(&upload_id, true));
+
+        let completed = self
+            .upload_parts(&mut file, key, &upload_id, on_sent)
+            .and_then(|etags| self.send("POST", key, &upload_query, complete_body(&etags).as_bytes()))
+            .and_then(|reply| {
+                // Failures while assembling the parts may still come with a 200 status
+                if reply.body.contains("<Error>") {
+                    bail!(
+                        "Completing the upload of {key} failed: {}",
+                        xml_value(&reply.body, "Message").unwrap_or_default()
+                    );
+                }
+                Ok(())
+            });
+        if completed.is_err() {
+            if let Err(e) = self.send("DELETE", key, &upload_query, &[]) {
+                warn!("Failed to abort the upload of {}: {}", key, e);
+            }
+        }
+        completed
+    }
+
+    /// Sends the parts of a multi-part upload and returns their `ETag`s in order
+    fn upload_parts(
+        &self,
+        file: &mut File,
+        key: &str,
+        upload_id: &str,
+        on_sent: &mut dyn FnMut(u64),
+    ) -> Result<Vec<String>> {
+        let mut etags = Vec::new();
+        let mut sent = 0;
+        let mut part = Vec::new();
+        loop {
+            part.clear();
+            file.by_ref().take(self.part_size).read_to_end(&mut part)?;
+            if part.is_empty() {
+                return Ok(etags);
+            }
+            let query = format!(
+                "partNumber={}&uploadId={}",
+                etags.len() + 1,
+                uri_encode(upload_id, true)
+            );
+            let reply = self.send("PUT", key, &query, &part)?;
+            etags.push(
+                reply
+                    .etag
+                    .ok_or_else(|| eyre!("The bucket returned no ETag for part {} of {key}", etags.len() + 1))?,
+            );
+            sent += part.len() as u64;
+            on_sent(sent);
+        }
+    }
+
+    /// Sends a signed request for `key`, retrying connection errors, throttling and server errors
+    fn send(&self, method: &str, key: &str, query: &str, body: &[u8]) -> Result<Reply> {
+        let mut delay = RETRY_DELAY;
+        let mut attempt = 1;
+        loop {
+            match self.request(method, key, query, body, Utc::now())?.send_bytes(body) {
+                Ok(response) => {
+                    return Ok(Reply {
+                        etag: response.header("ETag").map(str::to_string),
+                        body: response.into_string()?,
+                    });
+                }
+                Err(e) if attempt < MAX_ATTEMPTS && is_retryable(&e) => {
+                    warn!(
+                        "{} of {} failed (attempt {} of {}), retrying in {}s: {}",
+                        method,
+                        key,
+                        attempt,
+                        MAX_ATTEMPTS,
+                        delay.as_secs(),
+                        e
+                    );
+                    std::thread::sleep(delay);
+                    delay *= 2;
+                    attempt += 1;
+                }
+                Err(ureq::Error::Status(status, response)) => {
+                    let body = response.into_string().unwrap_or_default();
+                    let reason = xml_value(&body, "Message")
+                        .or_else(|| xml_value(&body, "Code"))
+                        .unwrap_or_default();
+                    bail!("{method} of {key} failed with status {status} {reason}");
+                }
+                Err(e) => bail!("{method} of {key} failed: {e}"),
+            }
+        }
+    }
+
+    /// Builds a request for `key` signed at `now`
+    fn request(&self, method: &str, key: &str, query: &str, body: &[u8], now: DateTime<Utc>) -> Result<ureq::Request> {
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date = now.format("%Y%m%d").to_string();
+        let payload_hash = format!("{:x}", Sha256::digest(body));
+        let path = format!("/{}/{}", uri_encode(&self.bucket, true), uri_encode(key, false));
+
+        let canonical_request = format!(
+            "{method}\n{path}\n{query}\nhost:{}\nx-amz-content-sha256:{payload_hash}\nx-amz-date:{amz_date}\n\n{SIGNED_HEADERS}\n{payload_hash}",
+            self.host
+        );
+        let scope = format!("{date}/{}/s3/aws4_request", self.region);
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{amz_date}\n{scope}\n{:x}",
+            Sha256::digest(canonical_request.as_bytes())
+        );
+        let signing_key = signing_key(&self.secret_key, &date, &self.region, "s3")?;
+        let signature = hex(&hmac_sha256(&signing_key, string_to_sign.as_bytes())?);
+
+        let url = if query.is_empty() {
+            format!("{}{path}", self.endpoint)
+        } else {
+            format!("{}{path}?{query}", self.endpoint)
+        };
+        Ok(self
+            .agent
+            .request(method, &url)
+            .set("Host", &self.host)
+            .set("x-amz-date", &amz_date)
+            .set("x-amz-content-sha256", &payload_hash)
+            .set(
+                "Authorization",
+                &format!(
+                    "AWS4-HMAC-SHA256 Credential={}/{scope}, SignedHeaders={SIGNED_HEADERS}, Signature={signature}",
+                    self.access_key
+                ),
+            ))
+    }
+}
+
+fn credential(configured: Option<&String>, setting: &str, variable: &str) -> Result<String> {
+    configured
+        .cloned()
+        .or_else(|| std::env::var(variable).ok())
+        .filter(|value| !value.is_empty())
+        .ok_or_else(|| eyre!("No cloud export credentials, set {setting} or {variable}"))
+}
+
+const fn is_retryable(error: &ureq::Error) -> bool {
+    match error {
+        ureq::Error::Status(status, _) => *status == 429 || *status >= 500,
+        ureq::Error::Transport(_) => true,
+    }
+}
+
+/// Key that signs requests to `service` in `region` on `date` (`YYYYMMDD`)
+fn signing_key(secret_key: &str, date: &str, region: &str, service: &str) -> Result<Vec<u8>> {
+    [date, region, service, "aws4_request"]
+        .iter()
+        .try_fold(format!("AWS4{secret_key}").into_bytes(), |key, part| {
+            hmac_sha256(&key, part.as_bytes())
+        })
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Result<Vec<u8>> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).map_err(|e| eyre!("Invalid signing key: {e}"))?;
+    mac.update(data);
+    Ok(mac.finalize().into_bytes().to_vec())
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .fold(String::with_capacity(bytes.len() * 2), |mut hex, byte| {
+            let _ = write!(hex, "{byte:02x}");
+            hex
+        })
+}
+
+/// Percent-encodes everything but unreserved characters, keeping `/` unless `encode_slash`
+fn uri_encode(value: &str, encode_slash: bool) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => encoded.push(char::from(byte)),
+            b'/' if !encode_slash => encoded.push('/'),
+            _ => {
+                let _ = write!(encoded, "%{byte:02X}");
+            }
+        }
+    }
+    encoded
+}
+
+/// Text of the first `<tag>` element in `xml`
+fn xml_value(xml: &str, tag: &str) -> Option<String> {
+    let start = xml.find(&format!("<{tag}>"))? + tag.len() + 2;
+    let end = start + xml[start..].find(&format!("</{tag}>"))?;
+    Some(
+        xml[start..end]
+            .replace("&quot;", "\"")
+            .replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&amp;", "&"),
+    )
+}
+
+fn complete_body(etags: &[String]) -> String {
+    let mut body = "<CompleteMultipartUpload>".to_string();
+    for (idx, etag) in etags.iter().enumerate() {
+        let _ = write!(
+            body,
+            "<Part><PartNumber>{}</PartNumber><ETag>{etag}</ETag></Part>",
+            idx + 1
+        );
+    }
+    body.push_str("</CompleteMultipartUpload>");
+    body
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    #![allow(clippy::panic_in_result_fn)]
+
+    use super::*;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::sync::Mutex;
+
+    /// A request received by the fake bucket
+    #[derive(Debug)]
+    struct Received {
+        method: String,
+        target: String,
+        body: Vec<u8>,
+        signed: bool,
+    }
+
+    /// Serves S3 responses on a local port, failing the first request with `503`
+    fn fake_bucket() -> (String, Arc<Mutex<Vec<Received>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let log = Arc::clone(&received);
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                loop {
+                    let mut request_line = String::new();
+                    if reader.read_line(&mut request_line).unwrap_or(0) == 0 {
+                        break;
+                    }
+                    let mut parts = request_line.split_whitespace();
+                    let method = parts.next().unwrap().to_string();
+                    let target = parts.next().unwrap().to_string();
+                    let (mut length, mut signed) = (0, false);
+                    loop {
+                        let mut header = String::new();
+                        reader.read_line(&mut header).unwrap();
+                        let header = header.trim_end().to_lowercase();
+                        if header.is_empty() {
+                            break;
+                        }
+                        if let Some(value) = header.strip_prefix("content-length:") {
+                            length = value.trim().parse().unwrap();
+                        }
+                        signed |= header.starts_with("authorization: aws4-hmac-sha256 credential=key/");
+                    }
+                    let mut body = vec![0; length];
+                    reader.read_exact(&mut body).unwrap();
+
+                    let mut log = log.lock().unwrap();
+                    let (status, etag, reply) = if log.is_empty() {
+                        ("503 Slow Down", String::new(), String::new())
+                    } else if target.ends_with("?uploads=") {
+                        (
+                            "200 OK",
+                            String::new(),
+                            "<Result><UploadId>up/1</UploadId></Result>".to_string(),
+                        )
+                    } else if method == "PUT" {
+                        ("200 OK", format!("ETag: \"etag-{}\"\r\n", log.len()), String::new())
+                    } else {
+                        ("200 OK", String::new(), "<CompleteMultipartUploadResult/>".to_string())
+                    };
+                    log.push(Received {
+                        method,
+                        target,
+                        body,
+                        signed,
+                    });
+                    drop(log);
+                    write!(
+                        stream,
+                        "HTTP/1.1 {status}\r\n{etag}Content-Length: {}\r\n\r\n{reply}",
+                        reply.len()
+                    )
+                    .unwrap();
+                }
+            }
+        });
+        (endpoint, received)
+    }
+
+    fn uploader(endpoint: &str) -> S3Uploader {
+        S3Uploader::new(&CloudExport {
+            endpoint: Some(endpoint.to_string()),
+            bucket: "photos".to_string(),
+            access_key: Some("key".to_string()),
+            secret_key: Some("secret".to_string()),
+            prefix: "/library/".to_string(),
+            ..CloudExport::default()
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_signing_key() {
+        // Example from the AWS Signature Version 4 documentation
+        let key = signing_key(
+            "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY",
+            "20120215",
+            "us-east-1",
+            "iam",
+        )
+        .unwrap();
+        assert_eq!(
+            hex(&key),
+            "f4780e2d9f65fa895f9c67b32ce1baf0b0d8a43505a000a1a9e090d414db404d"
+        );
+    }
+
+    #[test]
+    fn test_object_keys() {
+        let uploader = uploader("https://s3.example.com/");
+        let root = Path::new("/library");
+        assert_eq!(
+            uploader.object_key(root, Path::new("/library/2024/03/beach day.jpg")),
+            Some("library/2024/03/beach day.jpg".to_string())
+        );
+        assert_eq!(uploader.object_key(root, Path::new("/elsewhere/a.jpg")), None);
+        assert_eq!(
+            uri_encode("library/2024/beach day+1.jpg", false),
+            "library/2024/beach%20day%2B1.jpg"
+        );
+        assert_eq!(uri_encode("up/1", true), "up%2F1");
+        assert!(S3Uploader::new(&CloudExport::default()).is_err());
+    }
+
+    #[test]
+    fn test_upload_files() -> Result<()> {
+        let (endpoint, received) = fake_bucket();
+        let mut uploader = uploader(&endpoint);
+        uploader.part_size = 2;
+
+        let dir = tempfile::tempdir()?;
+        let small = dir.path().join("2024").join("a.jpg");
+        let large = dir.path().join("2024").join("clip.mp4");
+        std::fs::create_dir_all(dir.path().join("2024"))?;
+        std::fs::write(&small, b"abc")?;
+        std::fs::write(&large, b"0123456789")?;
+
+        let progress = Arc::new(RwLock::new(Progress::new()));
+        let report = uploader.upload_files(dir.path(), &[small, large, dir.path().join("missing.jpg")], &progress);
+        assert_eq!(report.uploaded, 2);
+        assert_eq!(report.bytes, 13);
+        assert_eq!(report.failed.len(), 1);
+        assert_eq!(progress.blocking_read().bytes_processed, 13);
+
+        let received = std::mem::take(&mut *received.lock().map_err(|e| eyre!("{e}"))?);
+        assert!(received.iter().all(|request| request.signed));
+        let requests: Vec<String> = received
+            .iter()
+            .map(|request| format!("{} {}", request.method, request.target))
+            .collect();
+        assert_eq!(
+            requests,
+            [
+                "PUT /photos/library/2024/a.jpg",
+                "PUT /photos/library/2024/a.jpg",
+                "POST /photos/library/2024/clip.mp4?uploads=",
+                "PUT /photos/library/2024/clip.mp4?partNumber=1&uploadId=up%2F1",
+                "PUT /photos/library/2024/clip.mp4?partNumber=2&uploadId=up%2F1",
+                "PUT /photos/library/2024/clip.mp4?partNumber=3&uploadId=up%2F1",
+                "PUT /photos/library/2024/clip.mp4?partNumber=4&uploadId=up%2F1",
+                "PUT /photos/library/2024/clip.mp4?partNumber=5&uploadId=up%2F1",
+                "POST /photos/library/2024/clip.mp4?uploadId=up%2F1",
+            ]
+        );
+        assert_eq!(received[1].body, b"abc");
+        assert_eq!(received[4].body, b"23");
+        let complete = String::from_utf8_lossy(&received[8].body);
+        assert!(complete.contains("<Part><PartNumber>5</PartNumber><ETag>\"etag-7\"</ETag></Part>"));
+        Ok(())
+    }
+}
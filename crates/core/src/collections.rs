@@ -0,0 +1,187 @@
+use color_eyre::eyre::{Result, eyre};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::info;
+use visualvault_models::Collection;
+
+const COLLECTIONS_FILE: &str = "collections.json";
+
+/// Named collections of files, kept in `collections.json` in the config directory.
+///
+/// Collections only reference files by path; nothing is copied or moved until a
+/// collection is exported. Every change is written to disk right away.
+#[derive(Debug, Default)]
+pub struct CollectionStore {
+    collections: Vec<Collection>,
+    path: Option<PathBuf>,
+}
+
+impl CollectionStore {
+    /// Loads the collections stored in `config_dir`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the collections file exists but cannot be read or parsed.
+    pub fn load(config_dir: &Path) -> Result<Self> {
+        let path = config_dir.join("visualvault").join(COLLECTIONS_FILE);
+        let collections = if path.exists() {
+            serde_json::from_str(&fs::read_to_string(&path)?)?
+        } else {
+            Vec::new()
+        };
+        Ok(Self {
+            collections,
+            path: Some(path),
+        })
+    }
+
+    #[must_use]
+    pub fn collections(&self) -> &[Collection] {
+        &self.collections
+    }
+
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<&Collection> {
+        self.collections.get(index)
+    }
+
+    /// Creates an empty collection named `name`, sorted in among the others by name,
+    /// and returns its index.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the name is empty or taken, or the collections cannot be saved.
+    pub fn create(&mut self, name: &str) -> Result<usize> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(eyre!("Collection name cannot be empty"));
+        }
+        if self.position(name).is_some() {
+            return Err(eyre!("Collection '{}' already exists", name));
+        }
+
+        let index = self
+            .collections
+            .partition_point(|collection| collection.name.to_lowercase() < name.to_lowercase());
+        self.collections.insert(index, Collection::new(name));
+        self.save()?;
+        info!("Created collection '{}'", name);
+        Ok(index)
+    }
+
+    /// Deletes the collection at `index`. The files themselves are left alone.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the collections cannot be saved.
+    pub fn delete(&mut self, index: usize) -> Result<Option<Collection>> {
+        if index >= self.collections.len() {
+            return Ok(None);
+        }
+        let removed = self.collections.remove(index);
+        self.save()?;
+        Ok(Some(removed))
+    }
+
+    /// Adds `paths` to the collection named `name`, creating it if needed, and returns
+    /// how many files were new to it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the collection cannot be created or the collections cannot be saved.
+    pub fn add_files(&mut self, name: &str, paths: &[PathBuf]) -> Result<usize> {
+        let index = match self.position(name) {
+            Some(index) => index,
+            None => self.create(name)?,
+        };
+        let added = self.collections[index].add_files(paths.iter().map(PathBuf::as_path));
+        if added > 0 {
+            self.save()?;
+        }
+        Ok(added)
+    }
+
+    /// Removes the file at `file` from the collection at `index`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the collections cannot be saved.
+    pub fn remove_file(&mut self, index: usize, file: usize) -> Result<Option<PathBuf>> {
+        let Some(collection) = self.collections.get_mut(index) else {
+            return Ok(None);
+        };
+        if file >= collection.files.len() {
+            return Ok(None);
+        }
+        let removed = collection.files.remove(file);
+        self.save()?;
+        Ok(Some(removed))
+    }
+
+    /// Index of the collection named `name`, ignoring case
+    #[must_use]
+    pub fn position(&self, name: &str) -> Option<usize> {
+        let name = name.trim();
+        self.collections
+            .iter()
+            .position(|collection| collection.name.eq_ignore_ascii_case(name))
+    }
+
+    fn save(&self) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(&self.collections)?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    #![allow(clippy::panic_in_result_fn)]
+    #![allow(clippy::unwrap_in_result)]
+
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_collections_are_saved() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let mut store = CollectionStore::load(temp_dir.path())?;
+        assert!(store.collections().is_empty());
+
+        store.create("Travel")?;
+        assert_eq!(store.create("Birthdays")?, 0);
+        assert!(store.create("travel").is_err());
+        assert!(store.create("  ").is_err());
+
+        let files = vec![PathBuf::from("/photos/a.jpg"), PathBuf::from("/photos/b.jpg")];
+        assert_eq!(store.add_files("Travel", &files)?, 2);
+        assert_eq!(store.add_files("travel", &files)?, 0);
+        assert_eq!(store.add_files("Pets", &files[..1])?, 1);
+
+        let reloaded = CollectionStore::load(temp_dir.path())?;
+        let names: Vec<&str> = reloaded.collections().iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["Birthdays", "Pets", "Travel"]);
+        assert_eq!(reloaded.get(2).unwrap().files, files);
+        Ok(())
+    }
+
+    #[test]
+    fn test_remove_file_and_delete() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let mut store = CollectionStore::load(temp_dir.path())?;
+        store.add_files("Travel", &[PathBuf::from("/photos/a.jpg")])?;
+
+        assert_eq!(store.remove_file(0, 0)?, Some(PathBuf::from("/photos/a.jpg")));
+        assert_eq!(store.remove_file(0, 0)?, None);
+        assert_eq!(store.delete(0)?.map(|c| c.name), Some("Travel".to_string()));
+        assert!(store.delete(0)?.is_none());
+        assert!(CollectionStore::load(temp_dir.path())?.collections().is_empty());
+        Ok(())
+    }
+}
@@ -0,0 +1,353 @@
+use std::fmt;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use tracing::{info, warn};
+use visualvault_config::{ConcurrencyMode, Settings};
+use visualvault_utils::format_bytes;
+
+/// Files read to probe the drive, spread over the files found
+const PROBE_FILES: usize = 8;
+/// Bytes read from each probed file to measure throughput
+const PROBE_BYTES: usize = 512 * 1024;
+/// Bytes read first from each probed file, whose latency tells seeking drives apart
+const PROBE_FIRST_READ: usize = 4 * 1024;
+/// Median time to open a file and read its first bytes above which the drive seeks
+const ROTATIONAL_LATENCY: Duration = Duration::from_millis(3);
+/// File systems that keep their files on another machine
+const NETWORK_FILE_SYSTEMS: &[&str] = &[
+    "nfs",
+    "nfs4",
+    "cifs",
+    "smb3",
+    "smbfs",
+    "afs",
+    "9p",
+    "ceph",
+    "glusterfs",
+    "fuse.sshfs",
+    "fuse.rclone",
+    "davfs",
+];
+
+/// The kind of drive the scanned files are on, as far as a probe can tell
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StorageKind {
+    SolidState,
+    Rotational,
+    Network,
+    /// Nothing could be read to probe the drive
+    Unknown,
+}
+
+impl fmt::Display for StorageKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SolidState => write!(f, "SSD"),
+            Self::Rotational => write!(f, "hard drive"),
+            Self::Network => write!(f, "network share"),
+            Self::Unknown => write!(f, "unknown drive"),
+        }
+    }
+}
+
+/// How many files a scan reads and hashes at once, and why
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ConcurrencyPlan {
+    /// Files whose metadata is read at once
+    pub scan_workers: usize,
+    /// Files hashed at once while looking for duplicates
+    pub hash_workers: usize,
+    /// The drive the probe found, `None` when the workers come from `worker_threads`
+    pub storage: Option<StorageKind>,
+    /// Bytes per second the probe read, if it read anything
+    pub throughput: Option<f64>,
+}
+
+impl ConcurrencyPlan {
+    /// The parallelism `worker_threads` and parallel processing ask for
+    #[must_use]
+    pub fn fixed(settings: &Settings) -> Self {
+        Self {
+            scan_workers: if settings.parallel_processing {
+                settings.worker_threads.max(1)
+            } else {
+                1
+            },
+            hash_workers: 1,
+            storage: None,
+            throughput: None,
+        }
+    }
+
+    /// The parallelism that suits `storage` on a machine with `cpus` cores.
+    ///
+    /// Hard drives lose more to seeking between files than they gain from queueing reads,
+    /// while network shares hide their latency behind many reads in flight.
+    #[must_use]
+    pub fn for_storage(storage: StorageKind, cpus: usize) -> Self {
+        let (scan_workers, hash_workers) = match storage {
+            StorageKind::SolidState => (cpus.clamp(2, 32), (cpus / 2).clamp(2, 8)),
+            StorageKind::Rotational => (2, 1),
+            StorageKind::Network => ((cpus * 2).clamp(8, 32), 4),
+            StorageKind::Unknown => (cpus.clamp(1, 32), 1),
+        };
+        Self {
+            scan_workers,
+            hash_workers,
+            storage: Some(storage),
+            throughput: None,
+        }
+    }
+
+    /// Plans the scan of `paths`: from the settings in fixed mode or with parallel processing
+    /// off, otherwise from sample reads of a few of the files.
+    pub async fn for_scan(settings: &Settings, paths: &[PathBuf]) -> Self {
+        if settings.concurrency == ConcurrencyMode::Fixed || !settings.parallel_processing {
+            return Self::fixed(settings);
+        }
+        let samples = sample_paths(paths, PROBE_FILES);
+        let probe = match tokio::task::spawn_blocking(move || probe_drive(&samples)).await {
+            Ok(probe) => probe,
+            Err(e) => {
+                warn!("Drive probe failed: {}", e);
+                DriveProbe::default()
+            }
+        };
+        let cpus = std::thread::available_parallelism().map_or(1, std::num::NonZeroUsize::get);
+        let plan = Self {
+            throughput: probe.throughput,
+            ..Self::for_storage(probe.storage, cpus)
+        };
+        info!("Concurrency: {} (first read in {:?})", plan, probe.latency);
+        plan
+    }
+}
+
+impl fmt::Display for ConcurrencyPlan {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let plural = |count: usize| if count == 1 { "" } else { "s" };
+        write!(
+            f,
+            "{} reader{}, {} hasher{}",
+            self.scan_workers,
+            plural(self.scan_workers),
+            self.hash_workers,
+            plural(self.hash_workers)
+        )?;
+        match (self.storage, self.throughput) {
+            (Some(storage), Some(rate)) => write!(f, " ({storage}, {}/s)", format_bytes(rate as u64)),
+            (Some(storage), None) => write!(f, " ({storage})"),
+            (None, _) => write!(f, " (fixed)"),
+        }
+    }
+}
+
+/// What sample reads found out about a drive
+#[derive(Debug, Clone, Copy)]
+struct DriveProbe {
+    storage: StorageKind,
+    /// Median time to open a file and read its first bytes
+    latency: Option<Duration>,
+    throughput: Option<f64>,
+}
+
+impl Default for DriveProbe {
+    fn default() -> Self {
+        Self {
+            storage: StorageKind::Unknown,
+            latency: None,
+            throughput: None,
+        }
+    }
+}
+
+/// Up to `count` of `paths`, spread evenly so they likely sit in different folders
+fn sample_paths(paths: &[PathBuf], count: usize) -> Vec<PathBuf> {
+    let step = paths.len().div_ceil(count).max(1);
+    paths.iter().step_by(step).take(count).cloned().collect()
+}
+
+/// Reads the start of each of `samples` and tells the drive they are on from how long that
+/// took.
+///
+/// Files in the page cache read as fast as an SSD, which is harmless: a repeated scan of the
+/// same files is then served from memory anyway.
+#[allow(clippy::cast_precision_loss)]
+fn probe_drive(samples: &[PathBuf]) -> DriveProbe {
+    let Some(first) = samples.first() else {
+        return DriveProbe::default();
+    };
+    let network = is_network_path(first);
+
+    let mut latencies = Vec::with_capacity(samples.len());
+    let mut bytes = 0;
+    let mut elapsed = Duration::ZERO;
+    let mut buffer = vec![0; PROBE_BYTES];
+    for path in samples {
+        let started = Instant::now();
+        let Ok((latency, read)) = read_sample(path, &mut buffer) else {
+            continue;
+        };
+        latencies.push(latency);
+        bytes += read;
+        elapsed += started.elapsed();
+    }
+    latencies.sort_unstable();
+    let latency = latencies.get(latencies.len() / 2).copied();
+
+    DriveProbe {
+        storage: classify(latency, network),
+        latency,
+        throughput: (bytes > 0 && !elapsed.is_zero()).then(|| bytes as f64 / elapsed.as_secs_f64()),
+    }
+}
+
+/// Reads up to a buffer's worth of `path`, returning how long the first bytes took and how
+/// many bytes were read
+fn read_sample(path: &Path, buffer: &mut [u8]) -> std::io::Result<(Duration, usize)> {
+    let started = Instant::now();
+    let mut file = std::fs::File::open(path)?;
+    let mut read = file.read(&mut buffer[..PROBE_FIRST_READ])?;
+    let latency = started.elapsed();
+    while read < buffer.len() {
+        match file.read(&mut buffer[read..])? {
+            0 => break,
+            n => read += n,
+        }
+    }
+    Ok((latency, read))
+}
+
+/// Tells the drive apart by whether it is mounted from the network and how long a file takes
+/// to start reading
+fn classify(latency: Option<Duration>, network: bool) -> StorageKind {
+    match latency {
+        _ if network => StorageKind::Network,
+        None => StorageKind::Unknown,
+        Some(latency) if latency >= ROTATIONAL_LATENCY => StorageKind::Rotational,
+        Some(_) => StorageKind::SolidState,
+    }
+}
+
+/// Whether `path` is on a file system mounted from another machine
+fn is_network_path(path: &Path) -> bool {
+    if cfg!(windows) {
+        // UNC paths such as \\server\share
+        return path.to_string_lossy().starts_with(r"\\");
+    }
+    std::fs::read_to_string("/proc/mounts")
+        .is_ok_and(|mounts| mount_type(&mounts, path).is_some_and(|fs_type| NETWORK_FILE_SYSTEMS.contains(&fs_type)))
+}
+
+/// The file system type of the deepest mount in `mounts`, in `/proc/mounts` format, that
+/// holds `path`
+fn mount_type<'a>(mounts: &'a str, path: &Path) -> Option<&'a str> {
+    mounts
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let (_, mount_point, fs_type) = (fields.next()?, fields.next()?, fields.next()?);
+            // Spaces in mount points are escaped as \040
+            let mount_point = mount_point.replace("\\040", " ");
+            path.starts_with(&mount_point).then_some((mount_point.len(), fs_type))
+        })
+        .max_by_key(|(depth, _)| *depth)
+        .map(|(_, fs_type)| fs_type)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::panic_in_result_fn)]
+    #![allow(clippy::unwrap_in_result)]
+    use super::*;
+    use color_eyre::eyre::Result;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_classify() {
+        assert_eq!(classify(None, false), StorageKind::Unknown);
+        assert_eq!(classify(None, true), StorageKind::Network);
+        assert_eq!(
+            classify(Some(Duration::from_micros(80)), false),
+            StorageKind::SolidState
+        );
+        assert_eq!(classify(Some(Duration::from_millis(9)), false), StorageKind::Rotational);
+        assert_eq!(classify(Some(Duration::from_micros(80)), true), StorageKind::Network);
+    }
+
+    #[test]
+    fn test_plans_per_storage() {
+        let hdd = ConcurrencyPlan::for_storage(StorageKind::Rotational, 16);
+        assert_eq!((hdd.scan_workers, hdd.hash_workers), (2, 1));
+        let ssd = ConcurrencyPlan::for_storage(StorageKind::SolidState, 16);
+        assert_eq!((ssd.scan_workers, ssd.hash_workers), (16, 8));
+        let network = ConcurrencyPlan::for_storage(StorageKind::Network, 2);
+        assert_eq!((network.scan_workers, network.hash_workers), (8, 4));
+        assert_eq!(ConcurrencyPlan::for_storage(StorageKind::SolidState, 1).scan_workers, 2);
+        assert_eq!(ssd.to_string(), "16 readers, 8 hashers (SSD)");
+    }
+
+    #[test]
+    fn test_fixed_plan_follows_settings() {
+        let mut settings = Settings {
+            worker_threads: 6,
+            parallel_processing: true,
+            ..Settings::default()
+        };
+        let plan = ConcurrencyPlan::fixed(&settings);
+        assert_eq!((plan.scan_workers, plan.hash_workers), (6, 1));
+        assert_eq!(plan.to_string(), "6 readers, 1 hasher (fixed)");
+
+        settings.parallel_processing = false;
+        assert_eq!(ConcurrencyPlan::fixed(&settings).scan_workers, 1);
+    }
+
+    #[test]
+    fn test_mount_type_picks_the_deepest_mount() {
+        let mounts = "/dev/sda1 / ext4 rw 0 0\n\
+                      server:/photos /mnt/nas nfs4 rw 0 0\n\
+                      //box/share /mnt/my\\040share cifs rw 0 0\n";
+        assert_eq!(mount_type(mounts, Path::new("/mnt/nas/2024/a.jpg")), Some("nfs4"));
+        assert_eq!(mount_type(mounts, Path::new("/mnt/my share/a.jpg")), Some("cifs"));
+        assert_eq!(mount_type(mounts, Path::new("/home/a.jpg")), Some("ext4"));
+        assert_eq!(mount_type(mounts, Path::new("/mnt/nasty/a.jpg")), Some("ext4"));
+    }
+
+    #[test]
+    fn test_sample_paths_spread_over_the_list() {
+        let paths: Vec<PathBuf> = (0..20).map(|i| PathBuf::from(format!("{i}.jpg"))).collect();
+        let samples = sample_paths(&paths, 8);
+        assert_eq!(samples.len(), 7);
+        assert_eq!(samples[1], PathBuf::from("3.jpg"));
+        assert_eq!(sample_paths(&paths[..3], 8).len(), 3);
+        assert!(sample_paths(&[], 8).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_auto_plan_probes_the_files() -> Result<()> {
+        let dir = TempDir::new()?;
+        let paths: Vec<PathBuf> = (0..4)
+            .map(|i| {
+                let path = dir.path().join(format!("{i}.jpg"));
+                std::fs::write(&path, vec![0u8; 64 * 1024])?;
+                Ok(path)
+            })
+            .collect::<std::io::Result<_>>()?;
+        let settings = Settings {
+            concurrency: ConcurrencyMode::Auto,
+            parallel_processing: true,
+            ..Settings::default()
+        };
+
+        let plan = ConcurrencyPlan::for_scan(&settings, &paths).await;
+        assert!(plan.storage.is_some_and(|storage| storage != StorageKind::Unknown));
+        assert!(plan.throughput.is_some_and(|rate| rate > 0.0));
+
+        let nothing = ConcurrencyPlan::for_scan(&settings, &[]).await;
+        assert_eq!(nothing.storage, Some(StorageKind::Unknown));
+        Ok(())
+    }
+}
@@ -4,11 +4,15 @@
 use chrono::{DateTime, Local};
 use color_eyre::eyre::Result;
 use serde::{Deserialize, Serialize};
-use sqlx::{Row, SqlitePool, sqlite::SqlitePoolOptions};
-use std::path::{Path, PathBuf};
+use sqlx::sqlite::{Sqlite, SqlitePoolOptions, SqliteRow};
+use sqlx::{QueryBuilder, Row, SqlitePool};
+use std::collections::HashSet;
+use std::path::{MAIN_SEPARATOR, Path, PathBuf};
 use std::str::FromStr;
 use tracing::{debug, info};
-use visualvault_models::{FileType, MediaFile, MediaMetadata};
+use visualvault_models::{
+    FileTags, FileType, FilterCombinator, FilterSet, MediaFile, MediaMetadata, MediaType, OrganizeResult, StatsSnapshot,
+};
 
 #[derive(Debug, Clone)]
 pub struct DatabaseCache {
@@ -163,6 +167,8 @@ impl DatabaseCache {
 
         self.set_size_limits().await?;
 
+        self.init_user_tables().await?;
+
         // Add a trigger to limit total entries
         let trigger_query = format!(
             "CREATE TRIGGER IF NOT EXISTS limit_entries
@@ -184,6 +190,57 @@ impl DatabaseCache {
         Ok(())
     }
 
+    /// Creates the tables holding the user's own data, which are never cleaned up
+    async fn init_user_tables(&self) -> Result<()> {
+        // Tags are the user's own data, so they live outside the versioned cache tables and
+        // are never cleaned up. They are keyed by content hash to survive moves and renames.
+        sqlx::query(
+            "CREATE TABLE IF NOT EXISTS file_tags (
+                hash TEXT PRIMARY KEY,
+                size INTEGER NOT NULL,
+                rating INTEGER,
+                tags TEXT NOT NULL
+            )",
+        )
+        .execute(&self.pool)
+        .await?;
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_file_tags_size ON file_tags(size)")
+            .execute(&self.pool)
+            .await?;
+
+        // Organize runs, kept for the same reason as the tags
+        sqlx::query(
+            "CREATE TABLE IF NOT EXISTS import_history (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                finished INTEGER NOT NULL,
+                source TEXT NOT NULL,
+                destination TEXT NOT NULL,
+                files INTEGER NOT NULL,
+                errors INTEGER NOT NULL
+            )",
+        )
+        .execute(&self.pool)
+        .await?;
+
+        // Statistics after each scan and organize run, for the trends on the dashboard
+        sqlx::query(
+            "CREATE TABLE IF NOT EXISTS stats_history (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                taken INTEGER NOT NULL,
+                kind TEXT NOT NULL,
+                files INTEGER NOT NULL,
+                bytes INTEGER NOT NULL,
+                duplicates INTEGER NOT NULL,
+                library_files INTEGER NOT NULL,
+                library_bytes INTEGER NOT NULL,
+                duplicates_removed INTEGER NOT NULL
+            )",
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
     /// Get entry from cache
     ///
     /// # Errors
@@ -216,21 +273,7 @@ impl DatabaseCache {
             .execute(&self.pool)
             .await?;
 
-            let path: String = row.get("path");
-            let modified_ts: i64 = row.get("modified");
-            let metadata_json: Option<String> = row.get("metadata");
-
-            Ok(Some(CacheEntry {
-                path: PathBuf::from(path),
-                name: row.get("name"),
-                extension: row.get("extension"),
-                size: row.get::<i64, _>("size") as u64,
-                modified: DateTime::from_timestamp(modified_ts, 0)
-                    .unwrap_or_else(|| Local::now().into())
-                    .into(),
-                hash: row.get("hash"),
-                metadata: metadata_json.and_then(|json| serde_json::from_str(&json).ok()),
-            }))
+            Ok(Some(entry_from_row(&row)))
         } else {
             Ok(None)
         }
@@ -283,6 +326,62 @@ impl DatabaseCache {
         Ok(())
     }
 
+    /// Get the tags and rating stored for the file with content hash `hash`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails or the stored tags cannot be decoded.
+    pub async fn get_file_tags(&self, hash: &str) -> Result<Option<FileTags>> {
+        let row = sqlx::query("SELECT rating, tags FROM file_tags WHERE hash = ?")
+            .bind(hash)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        row.map(|row| {
+            Ok(FileTags {
+                rating: row.get::<Option<i64>, _>("rating").map(|rating| rating as u8),
+                tags: serde_json::from_str(&row.get::<String, _>("tags"))?,
+            })
+        })
+        .transpose()
+    }
+
+    /// Store the tags and rating of the file with content hash `hash`, removing them when empty
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the tags cannot be serialized or the database query fails.
+    pub async fn set_file_tags(&self, hash: &str, size: u64, tags: &FileTags) -> Result<()> {
+        if tags.is_empty() {
+            sqlx::query("DELETE FROM file_tags WHERE hash = ?")
+                .bind(hash)
+                .execute(&self.pool)
+                .await?;
+            return Ok(());
+        }
+
+        sqlx::query("INSERT OR REPLACE INTO file_tags (hash, size, rating, tags) VALUES (?, ?, ?, ?)")
+            .bind(hash)
+            .bind(size as i64)
+            .bind(tags.rating.map(i64::from))
+            .bind(serde_json::to_string(&tags.tags)?)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Get the sizes of all tagged files, which narrows down the files worth hashing
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails or there's a database connection issue.
+    pub async fn tagged_sizes(&self) -> Result<HashSet<u64>> {
+        let sizes: Vec<i64> = sqlx::query_scalar("SELECT DISTINCT size FROM file_tags")
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(sizes.into_iter().map(|size| size as u64).collect())
+    }
+
     /// Remove stale entries that no longer exist on disk
     ///
     /// # Errors
@@ -415,27 +514,199 @@ impl DatabaseCache {
 
         let rows = query_builder.fetch_all(&self.pool).await?;
 
-        let entries = rows
-            .into_iter()
-            .map(|row| {
-                let modified_ts: i64 = row.get("modified");
-                let metadata_json: Option<String> = row.get("metadata");
-
-                CacheEntry {
-                    path: PathBuf::from(row.get::<String, _>("path")),
-                    name: row.get("name"),
-                    extension: row.get("extension"),
-                    size: row.get::<i64, _>("size") as u64,
-                    modified: DateTime::from_timestamp(modified_ts, 0)
-                        .unwrap_or_else(|| Local::now().into())
-                        .into(),
-                    hash: row.get("hash"),
-                    metadata: metadata_json.and_then(|json| serde_json::from_str(&json).ok()),
-                }
+        Ok(rows.iter().map(entry_from_row).collect())
+    }
+
+    /// Get the cached files matching `query`, most recently modified first
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails or there's a database connection issue.
+    pub async fn query_files(&self, query: &FileQuery) -> Result<Vec<CacheEntry>> {
+        let mut builder = QueryBuilder::new(
+            "SELECT path, name, extension, size, modified, hash, metadata FROM file_cache WHERE 1 = 1",
+        );
+        query.push_filters(&mut builder);
+        builder.push(" ORDER BY modified DESC, path");
+        builder
+            .push(" LIMIT ")
+            .push_bind(query.limit.map_or(-1, |limit| limit as i64));
+        builder.push(" OFFSET ").push_bind(query.offset as i64);
+
+        let rows = builder.build().fetch_all(&self.pool).await?;
+        Ok(rows.iter().map(entry_from_row).collect())
+    }
+
+    /// Count the cached files matching `query`, ignoring its limit and offset
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails or there's a database connection issue.
+    pub async fn count_files(&self, query: &FileQuery) -> Result<usize> {
+        let mut builder = QueryBuilder::new("SELECT COUNT(*) FROM file_cache WHERE 1 = 1");
+        query.push_filters(&mut builder);
+        let count: i64 = builder.build_query_scalar().fetch_one(&self.pool).await?;
+        Ok(count as usize)
+    }
+
+    /// Get groups of the cached files matching `query` that have the same hash, largest files
+    /// first; the limit and offset of `query` are ignored
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails or there's a database connection issue.
+    pub async fn duplicate_groups(&self, query: &FileQuery) -> Result<Vec<Vec<CacheEntry>>> {
+        let mut builder = QueryBuilder::new(
+            "WITH matching AS (
+                 SELECT path, name, extension, size, modified, hash, metadata FROM file_cache
+                 WHERE hash IS NOT NULL",
+        );
+        query.push_filters(&mut builder);
+        builder.push(
+            ")
+             SELECT * FROM matching
+             WHERE hash IN (SELECT hash FROM matching GROUP BY hash HAVING COUNT(*) > 1)
+             ORDER BY size DESC, hash, path",
+        );
+        let rows = builder.build().fetch_all(&self.pool).await?;
+
+        let mut groups: Vec<Vec<CacheEntry>> = Vec::new();
+        for entry in rows.iter().map(entry_from_row) {
+            match groups.last_mut() {
+                Some(group) if group[0].hash == entry.hash => group.push(entry),
+                _ => groups.push(vec![entry]),
+            }
+        }
+        Ok(groups)
+    }
+
+    /// Get the number and total size of the cached files matching `query` per lowercase
+    /// extension, largest total first; the limit and offset of `query` are ignored
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails or there's a database connection issue.
+    pub async fn extension_stats(&self, query: &FileQuery) -> Result<Vec<ExtensionStats>> {
+        let mut builder = QueryBuilder::new(
+            "SELECT lower(extension) AS extension, COUNT(*) AS files, COALESCE(SUM(size), 0) AS total_size
+             FROM file_cache WHERE 1 = 1",
+        );
+        query.push_filters(&mut builder);
+        builder.push(" GROUP BY lower(extension) ORDER BY total_size DESC, extension");
+        let rows = builder.build().fetch_all(&self.pool).await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| ExtensionStats {
+                extension: row.get("extension"),
+                files: row.get::<i64, _>("files") as usize,
+                total_size: row.get::<i64, _>("total_size") as u64,
             })
-            .collect();
+            .collect())
+    }
 
-        Ok(entries)
+    /// Add a finished organize run to the import history
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails or there's a database connection issue.
+    pub async fn record_import(&self, record: &ImportRecord) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO import_history (finished, source, destination, files, errors)
+             VALUES (?, ?, ?, ?, ?)",
+        )
+        .bind(record.finished.timestamp())
+        .bind(record.source.to_string_lossy().as_ref())
+        .bind(record.destination.to_string_lossy().as_ref())
+        .bind(record.files as i64)
+        .bind(record.errors as i64)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Get up to `limit` organize runs from the import history, newest first
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails or there's a database connection issue.
+    pub async fn import_history(&self, limit: usize) -> Result<Vec<ImportRecord>> {
+        let rows = sqlx::query(
+            "SELECT finished, source, destination, files, errors
+             FROM import_history
+             ORDER BY finished DESC, id DESC
+             LIMIT ?",
+        )
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| ImportRecord {
+                finished: timestamp_to_local(row.get("finished")),
+                source: PathBuf::from(row.get::<String, _>("source")),
+                destination: PathBuf::from(row.get::<String, _>("destination")),
+                files: row.get::<i64, _>("files") as usize,
+                errors: row.get::<i64, _>("errors") as usize,
+            })
+            .collect())
+    }
+
+    /// Add a statistics snapshot to the history
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails or there's a database connection issue.
+    pub async fn record_stats(&self, snapshot: &StatsSnapshot) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO stats_history
+                (taken, kind, files, bytes, duplicates, library_files, library_bytes, duplicates_removed)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(snapshot.taken.timestamp())
+        .bind(snapshot.kind.to_string())
+        .bind(snapshot.files as i64)
+        .bind(snapshot.bytes as i64)
+        .bind(snapshot.duplicates as i64)
+        .bind(snapshot.library_files as i64)
+        .bind(snapshot.library_bytes as i64)
+        .bind(snapshot.duplicates_removed as i64)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Get the latest `limit` statistics snapshots, oldest first
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails or there's a database connection issue.
+    pub async fn stats_history(&self, limit: usize) -> Result<Vec<StatsSnapshot>> {
+        let rows = sqlx::query(
+            "SELECT taken, kind, files, bytes, duplicates, library_files, library_bytes, duplicates_removed
+             FROM (SELECT * FROM stats_history ORDER BY taken DESC, id DESC LIMIT ?)
+             ORDER BY taken ASC, id ASC",
+        )
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .iter()
+            .filter_map(|row| {
+                Some(StatsSnapshot {
+                    taken: timestamp_to_local(row.get("taken")),
+                    kind: row.get::<String, _>("kind").parse().ok()?,
+                    files: row.get::<i64, _>("files") as usize,
+                    bytes: row.get::<i64, _>("bytes") as u64,
+                    duplicates: row.get::<i64, _>("duplicates") as usize,
+                    library_files: row.get::<i64, _>("library_files") as usize,
+                    library_bytes: row.get::<i64, _>("library_bytes") as u64,
+                    duplicates_removed: row.get::<i64, _>("duplicates_removed") as usize,
+                })
+            })
+            .collect())
     }
 
     fn get_cache_path() -> Result<PathBuf> {
@@ -597,6 +868,316 @@ impl DatabaseCache {
     }
 }
 
+fn timestamp_to_local(timestamp: i64) -> DateTime<Local> {
+    DateTime::from_timestamp(timestamp, 0)
+        .unwrap_or_else(|| Local::now().into())
+        .into()
+}
+
+fn entry_from_row(row: &SqliteRow) -> CacheEntry {
+    let metadata_json: Option<String> = row.get("metadata");
+    CacheEntry {
+        path: PathBuf::from(row.get::<String, _>("path")),
+        name: row.get("name"),
+        extension: row.get("extension"),
+        size: row.get::<i64, _>("size") as u64,
+        modified: timestamp_to_local(row.get("modified")),
+        hash: row.get("hash"),
+        metadata: metadata_json.and_then(|json| serde_json::from_str(&json).ok()),
+    }
+}
+
+/// Filters of [`DatabaseCache::query_files`]; filters left empty match every file
+#[derive(Debug, Clone, Default)]
+pub struct FileQuery {
+    /// Text the file name contains, ignoring ASCII case
+    pub name_contains: Option<String>,
+    /// Text the path, camera or lens contains, ignoring ASCII case
+    pub text: Option<String>,
+    /// Only files in this folder or below it
+    pub folder: Option<PathBuf>,
+    /// Only files looked up or stored since then, so a query can be limited to the files of
+    /// the latest scan and leave out deleted ones
+    pub scanned_since: Option<DateTime<Local>>,
+    /// Extensions without the dot, ignoring case
+    pub extensions: Vec<String>,
+    pub min_size: Option<u64>,
+    pub max_size: Option<u64>,
+    pub modified_after: Option<DateTime<Local>>,
+    pub modified_before: Option<DateTime<Local>>,
+    /// Only files tagged with this lowercase tag
+    pub tag: Option<String>,
+    /// Only files whose hash has been calculated
+    pub hashed_only: bool,
+    /// Only files the filter set keeps; set through [`FileQuery::with_filters`]
+    pub filters: Option<FilterSet>,
+    pub limit: Option<usize>,
+    pub offset: usize,
+}
+
+impl FileQuery {
+    /// Narrows the query to the files `filters` keep.
+    ///
+    /// Returns `None` if they use regex patterns or the screenshot filter, which can only be
+    /// checked against the files themselves.
+    #[must_use]
+    pub fn with_filters(mut self, filters: &FilterSet) -> Option<Self> {
+        if !filters.is_active {
+            return Some(self);
+        }
+        let needs_files = filters.regex_patterns.iter().any(|pattern| pattern.enabled)
+            || filters
+                .media_types
+                .iter()
+                .any(|filter| filter.enabled && filter.media_type == MediaType::Screenshot);
+        if needs_files {
+            return None;
+        }
+        self.filters = Some(filters.clone());
+        Some(self)
+    }
+
+    fn push_filters(&self, builder: &mut QueryBuilder<'_, Sqlite>) {
+        if let Some(text) = &self.name_contains {
+            let escaped = text.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+            builder
+                .push(" AND name LIKE ")
+                .push_bind(format!("%{escaped}%"))
+                .push(" ESCAPE '\\'");
+        }
+        if let Some(text) = &self.text {
+            builder
+                .push(
+                    " AND instr(lower(path || ' ' || COALESCE(json_extract(metadata, '$.Image.capture.make'), '')
+                     || ' ' || COALESCE(json_extract(metadata, '$.Image.capture.model'), '')
+                     || ' ' || COALESCE(json_extract(metadata, '$.Image.capture.lens'), '')), ",
+                )
+                .push_bind(text.to_lowercase())
+                .push(") > 0");
+        }
+        if let Some(folder) = &self.folder {
+            let folder = folder.to_string_lossy();
+            let prefix = format!("{}{}", folder.trim_end_matches(MAIN_SEPARATOR), MAIN_SEPARATOR);
+            builder
+                .push(" AND substr(path, 1, ")
+                .push_bind(prefix.chars().count() as i64)
+                .push(") = ")
+                .push_bind(prefix);
+        }
+        if let Some(since) = self.scanned_since {
+            builder.push(" AND last_accessed >= ").push_bind(since.timestamp());
+        }
+        if !self.extensions.is_empty() {
+            builder.push(" AND ");
+            Condition::Extension(&self.extensions).push(builder);
+        }
+        if let Some(min_size) = self.min_size {
+            builder.push(" AND size >= ").push_bind(min_size as i64);
+        }
+        if let Some(max_size) = self.max_size {
+            builder.push(" AND size <= ").push_bind(max_size as i64);
+        }
+        if let Some(after) = self.modified_after {
+            builder.push(" AND modified >= ").push_bind(after.timestamp());
+        }
+        if let Some(before) = self.modified_before {
+            builder.push(" AND modified < ").push_bind(before.timestamp());
+        }
+        if let Some(tag) = &self.tag {
+            builder.push(" AND ");
+            Condition::Tagged(&tag.to_lowercase()).push(builder);
+        }
+        if self.hashed_only {
+            builder.push(" AND hash IS NOT NULL");
+        }
+        if let Some(filters) = &self.filters {
+            push_filter_set(builder, filters);
+        }
+    }
+}
+
+/// One condition of a filter set on the rows of `file_cache`
+enum Condition<'a> {
+    Modified(Option<DateTime<Local>>, Option<DateTime<Local>>),
+    Size(Option<u64>, Option<u64>),
+    Extension(&'a [String]),
+    Tagged(&'a str),
+    RatedAtLeast(u8),
+}
+
+impl Condition<'_> {
+    fn push(&self, builder: &mut QueryBuilder<'_, Sqlite>) {
+        match self {
+            Self::Modified(from, to) => {
+                builder.push("(1 = 1");
+                if let Some(from) = from {
+                    builder.push(" AND modified >= ").push_bind(from.timestamp());
+                }
+                if let Some(to) = to {
+                    builder.push(" AND modified <= ").push_bind(to.timestamp());
+                }
+                builder.push(")");
+            }
+            Self::Size(min, max) => {
+                builder.push("(1 = 1");
+                if let Some(min) = min {
+                    builder.push(" AND size >= ").push_bind(*min as i64);
+                }
+                if let Some(max) = max {
+                    builder.push(" AND size <= ").push_bind(*max as i64);
+                }
+                builder.push(")");
+            }
+            Self::Extension([]) => {
+                builder.push("0 = 1");
+            }
+            Self::Extension(extensions) => {
+                builder.push("lower(extension) IN (");
+                let mut separated = builder.separated(", ");
+                for extension in *extensions {
+                    separated.push_bind(extension.trim_start_matches('.').to_lowercase());
+                }
+                separated.push_unseparated(")");
+            }
+            Self::Tagged(tag) => {
+                builder
+                    .push("hash IN (SELECT hash FROM file_tags, json_each(file_tags.tags) WHERE json_each.value = ")
+                    .push_bind(tag.to_string())
+                    .push(")");
+            }
+            Self::RatedAtLeast(rating) => {
+                builder
+                    .push("hash IN (SELECT hash FROM file_tags WHERE rating >= ")
+                    .push_bind(i64::from(*rating))
+                    .push(")");
+            }
+        }
+    }
+}
+
+/// Pushes the condition of a filter set, evaluated like [`FilterSet::matches_file_with_tags`].
+///
+/// Every category with filters must hold, or one of them with [`FilterCombinator::Any`]. Within
+/// a category a file must meet one of the filters that are not negated, or all of them for tags,
+/// and none of the negated ones.
+fn push_filter_set(builder: &mut QueryBuilder<'_, Sqlite>, filters: &FilterSet) {
+    let mut categories: Vec<(Vec<(bool, Condition<'_>)>, bool)> = vec![
+        (
+            filters
+                .date_ranges
+                .iter()
+                .map(|range| (range.negated, Condition::Modified(range.from, range.to)))
+                .collect(),
+            false,
+        ),
+        (
+            filters
+                .size_ranges
+                .iter()
+                .map(|range| (range.negated, Condition::Size(range.min_bytes, range.max_bytes)))
+                .collect(),
+            false,
+        ),
+        (
+            filters
+                .media_types
+                .iter()
+                .filter(|filter| filter.enabled)
+                .map(|filter| (filter.negated, Condition::Extension(&filter.extensions)))
+                .collect(),
+            false,
+        ),
+        (
+            filters
+                .extension_filters
+                .iter()
+                .filter(|filter| filter.enabled)
+                .map(|filter| (filter.exclude, Condition::Extension(&filter.extensions)))
+                .collect(),
+            false,
+        ),
+        (
+            filters
+                .required_tags
+                .iter()
+                .map(|tag| (false, Condition::Tagged(tag)))
+                .chain(
+                    filters
+                        .min_rating
+                        .map(|rating| (false, Condition::RatedAtLeast(rating))),
+                )
+                .collect(),
+            true,
+        ),
+    ];
+    categories.retain(|(conditions, _)| !conditions.is_empty());
+    if categories.is_empty() {
+        return;
+    }
+
+    let joiner = match filters.combinator {
+        FilterCombinator::All => " AND ",
+        FilterCombinator::Any => " OR ",
+    };
+    builder.push(" AND (");
+    for (index, (conditions, every)) in categories.iter().enumerate() {
+        if index > 0 {
+            builder.push(joiner);
+        }
+        builder.push("((");
+        let mut positives = conditions.iter().filter(|(negated, _)| !negated).peekable();
+        if positives.peek().is_none() {
+            builder.push("1 = 1");
+        }
+        for (position, (_, condition)) in positives.enumerate() {
+            if position > 0 {
+                builder.push(if *every { " AND " } else { " OR " });
+            }
+            condition.push(builder);
+        }
+        builder.push(")");
+        for (_, condition) in conditions.iter().filter(|(negated, _)| *negated) {
+            builder.push(" AND NOT (");
+            condition.push(builder);
+            builder.push(")");
+        }
+        builder.push(")");
+    }
+    builder.push(")");
+}
+
+/// Number and total size of the cached files with one extension
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtensionStats {
+    pub extension: String,
+    pub files: usize,
+    pub total_size: u64,
+}
+
+/// An organize run in the import history
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportRecord {
+    pub finished: DateTime<Local>,
+    pub source: PathBuf,
+    pub destination: PathBuf,
+    /// Files placed in the destination
+    pub files: usize,
+    pub errors: usize,
+}
+
+impl ImportRecord {
+    #[must_use]
+    pub fn new(source: &Path, result: &OrganizeResult) -> Self {
+        Self {
+            finished: result.timestamp,
+            source: source.to_path_buf(),
+            destination: result.destination.clone(),
+            files: result.files_organized,
+            errors: result.errors.len(),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct CacheStats {
     pub total_entries: usize,
@@ -618,6 +1199,7 @@ pub struct CacheEntry {
 
 impl CacheEntry {
     /// Convert cache entry to `MediaFile`
+    #[must_use]
     pub fn to_media_file(&self, file_type: FileType, created: DateTime<Local>) -> MediaFile {
         MediaFile {
             path: self.path.clone(),
@@ -629,6 +1211,9 @@ impl CacheEntry {
             modified: self.modified,
             hash: self.hash.as_ref().map(|h| std::sync::Arc::<str>::from(h.as_str())),
             metadata: self.metadata.clone(),
+            via_symlink: false,
+            live_photo_pair: None,
+            content_extension: None,
         }
     }
 }
@@ -653,11 +1238,11 @@ mod tests {
     #![allow(clippy::expect_used)]
     #![allow(clippy::float_cmp)] // For comparing floats in tests
     #![allow(clippy::panic)]
-    #![allow(clippy::unwrap_used)]
-    #![allow(clippy::expect_used)]
     #![allow(clippy::panic_in_result_fn)]
+    #![allow(clippy::unwrap_in_result)]
     use std::sync::Arc;
 
+    use visualvault_models::{ImageMetadata, SnapshotKind};
     use visualvault_utils::create_cache_path;
 
     use super::*;
@@ -1106,4 +1691,321 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_file_tags_round_trip() -> Result<()> {
+        let cache = create_test_cache().await?;
+        assert!(cache.get_file_tags("hash1").await?.is_none());
+
+        let tags = FileTags {
+            rating: Some(4),
+            tags: visualvault_models::parse_tags("beach, family"),
+        };
+        cache.set_file_tags("hash1", 1024, &tags).await?;
+        assert_eq!(cache.get_file_tags("hash1").await?, Some(tags));
+        assert_eq!(cache.tagged_sizes().await?, HashSet::from([1024]));
+
+        // Clearing the tags removes the row
+        cache.set_file_tags("hash1", 1024, &FileTags::default()).await?;
+        assert!(cache.get_file_tags("hash1").await?.is_none());
+        assert!(cache.tagged_sizes().await?.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_query_files_filters() -> Result<()> {
+        let cache = create_test_cache().await?;
+        let mut beach = create_test_entry("beach_100%.JPG", 5_000, Some("hash1".to_string()));
+        beach.extension = "JPG".to_string();
+        beach.modified = Local::now() - chrono::Duration::days(30);
+        let clip = CacheEntry {
+            extension: "mp4".to_string(),
+            ..create_test_entry("clip.mp4", 50_000, None)
+        };
+        let other = create_test_entry("beach_1.jpg", 500, Some("hash2".to_string()));
+        for entry in [beach, clip, other] {
+            cache.insert(entry.path.clone(), entry).await?;
+        }
+
+        let names = |entries: Vec<CacheEntry>| entries.into_iter().map(|e| e.name).collect::<Vec<_>>();
+        let all = FileQuery::default();
+        assert_eq!(cache.count_files(&all).await?, 3);
+
+        let query = FileQuery {
+            name_contains: Some("100%".to_string()),
+            ..FileQuery::default()
+        };
+        assert_eq!(names(cache.query_files(&query).await?), ["beach_100%.JPG"]);
+
+        let query = FileQuery {
+            extensions: vec![".jpg".to_string()],
+            min_size: Some(1_000),
+            ..FileQuery::default()
+        };
+        assert_eq!(names(cache.query_files(&query).await?), ["beach_100%.JPG"]);
+
+        let query = FileQuery {
+            modified_after: Some(Local::now() - chrono::Duration::days(1)),
+            hashed_only: true,
+            ..FileQuery::default()
+        };
+        assert_eq!(names(cache.query_files(&query).await?), ["beach_1.jpg"]);
+
+        let query = FileQuery {
+            limit: Some(1),
+            offset: 1,
+            ..FileQuery::default()
+        };
+        assert_eq!(cache.query_files(&query).await?.len(), 1);
+        assert_eq!(cache.count_files(&query).await?, 3);
+
+        cache
+            .set_file_tags(
+                "hash2",
+                500,
+                &FileTags {
+                    rating: None,
+                    tags: visualvault_models::parse_tags("family"),
+                },
+            )
+            .await?;
+        let query = FileQuery {
+            tag: Some("Family".to_string()),
+            ..FileQuery::default()
+        };
+        assert_eq!(names(cache.query_files(&query).await?), ["beach_1.jpg"]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_query_files_by_folder_text_and_scan() -> Result<()> {
+        let cache = create_test_cache().await?;
+        let camera = MediaMetadata::Image(ImageMetadata::from_capture(
+            "JPEG".to_string(),
+            visualvault_models::CaptureInfo {
+                make: Some("Canon".to_string()),
+                model: Some("Canon EOS R5".to_string()),
+                ..Default::default()
+            },
+        ));
+        for (path, metadata) in [
+            ("/photos/trip/a.jpg", Some(camera)),
+            ("/photos/trip/b.jpg", None),
+            ("/photos/trip-2/c.jpg", None),
+        ] {
+            let entry = CacheEntry {
+                path: PathBuf::from(path),
+                metadata,
+                ..create_test_entry("x.jpg", 100, None)
+            };
+            cache.insert(entry.path.clone(), entry).await?;
+        }
+        let paths = |entries: Vec<CacheEntry>| {
+            let mut paths: Vec<_> = entries.into_iter().map(|e| e.path).collect();
+            paths.sort();
+            paths
+        };
+
+        let in_trip = FileQuery {
+            folder: Some(PathBuf::from("/photos/trip/")),
+            ..FileQuery::default()
+        };
+        assert_eq!(
+            paths(cache.query_files(&in_trip).await?),
+            [PathBuf::from("/photos/trip/a.jpg"), PathBuf::from("/photos/trip/b.jpg")]
+        );
+
+        let canon = FileQuery {
+            text: Some("EOS r5".to_string()),
+            ..FileQuery::default()
+        };
+        assert_eq!(
+            paths(cache.query_files(&canon).await?),
+            [PathBuf::from("/photos/trip/a.jpg")]
+        );
+
+        // Files not looked up since the scan started belong to an earlier scan
+        sqlx::query("UPDATE file_cache SET last_accessed = last_accessed - 100 WHERE path LIKE '%c.jpg'")
+            .execute(&cache.pool)
+            .await?;
+        let scanned = FileQuery {
+            scanned_since: Some(Local::now() - chrono::Duration::seconds(10)),
+            ..FileQuery::default()
+        };
+        assert_eq!(cache.count_files(&scanned).await?, 2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_query_files_with_filter_set() -> Result<()> {
+        use visualvault_models::filters::RegexTarget;
+
+        let cache = create_test_cache().await?;
+        let mut files = Vec::new();
+        for (name, size, days_old, hash) in [
+            ("a.jpg", 500, 1, "h1"),
+            ("b.png", 5_000, 1, "h2"),
+            ("c.mp4", 50_000, 40, "h3"),
+            ("d.mp3", 5_000, 40, "h4"),
+            ("e.jpg", 50_000, 40, "h5"),
+        ] {
+            let entry = CacheEntry {
+                extension: name.rsplit('.').next().unwrap_or_default().to_string(),
+                modified: Local::now() - chrono::Duration::days(days_old),
+                ..create_test_entry(name, size, Some(hash.to_string()))
+            };
+            cache.insert(entry.path.clone(), entry.clone()).await?;
+            files.push(entry);
+        }
+        cache
+            .set_file_tags(
+                "h5",
+                50_000,
+                &FileTags {
+                    rating: Some(4),
+                    tags: visualvault_models::parse_tags("family"),
+                },
+            )
+            .await?;
+
+        let mut filters = FilterSet {
+            is_active: true,
+            ..FilterSet::default()
+        };
+        filters.add_size_range("big".to_string(), Some(0.001), None);
+        filters.add_date_range("old".to_string(), None, Some(Local::now() - chrono::Duration::days(30)));
+        filters.date_ranges[0].negated = true;
+        let names = |entries: Vec<CacheEntry>| {
+            let mut names: Vec<_> = entries.into_iter().map(|e| e.name).collect();
+            names.sort();
+            names
+        };
+        // Images and videos, larger than 1 KB and not older than 30 days
+        let query = FileQuery::default().with_filters(&filters).unwrap();
+        assert_eq!(names(cache.query_files(&query).await?), ["b.png"]);
+
+        filters.combinator = FilterCombinator::Any;
+        let query = FileQuery::default().with_filters(&filters).unwrap();
+        assert_eq!(cache.count_files(&query).await?, 5);
+
+        filters.combinator = FilterCombinator::All;
+        filters.date_ranges.clear();
+        filters.required_tags = vec!["family".to_string()];
+        filters.min_rating = Some(3);
+        let query = FileQuery::default().with_filters(&filters).unwrap();
+        assert_eq!(names(cache.query_files(&query).await?), ["e.jpg"]);
+
+        // Regex patterns need the files themselves
+        filters.regex_patterns.push(visualvault_models::filters::RegexPattern {
+            pattern: "^e".to_string(),
+            target: RegexTarget::FileName,
+            case_sensitive: false,
+            enabled: true,
+            negated: false,
+        });
+        assert!(FileQuery::default().with_filters(&filters).is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_groups_and_extension_stats() -> Result<()> {
+        let cache = create_test_cache().await?;
+        for (name, size, hash) in [
+            ("a.jpg", 2_000, Some("big")),
+            ("b.jpg", 2_000, Some("big")),
+            ("c.jpg", 100, Some("small")),
+            ("d.jpg", 100, Some("small")),
+            ("e.jpg", 100, None),
+            ("f.jpg", 300, Some("unique")),
+        ] {
+            let entry = create_test_entry(name, size, hash.map(str::to_string));
+            cache.insert(entry.path.clone(), entry).await?;
+        }
+
+        let all = FileQuery::default();
+        let groups = cache.duplicate_groups(&all).await?;
+        assert_eq!(groups.len(), 2);
+        assert_eq!(
+            groups[0].iter().map(|e| e.name.as_str()).collect::<Vec<_>>(),
+            ["a.jpg", "b.jpg"]
+        );
+        assert_eq!(groups[1].len(), 2);
+        let large = FileQuery {
+            min_size: Some(1_000),
+            ..FileQuery::default()
+        };
+        assert_eq!(cache.duplicate_groups(&large).await?.len(), 1);
+        // A copy outside the query does not make a group
+        let without_b = FileQuery {
+            name_contains: Some("a.".to_string()),
+            ..FileQuery::default()
+        };
+        assert!(cache.duplicate_groups(&without_b).await?.is_empty());
+
+        assert_eq!(
+            cache.extension_stats(&all).await?,
+            [ExtensionStats {
+                extension: "jpg".to_string(),
+                files: 6,
+                total_size: 4_600,
+            }]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_import_history() -> Result<()> {
+        let cache = create_test_cache().await?;
+        for files in [10, 20] {
+            let record = ImportRecord {
+                finished: DateTime::from_timestamp(1_700_000_000 + files as i64, 0)
+                    .unwrap()
+                    .into(),
+                source: PathBuf::from("/camera"),
+                destination: PathBuf::from("/library"),
+                files,
+                errors: 1,
+            };
+            cache.record_import(&record).await?;
+        }
+
+        let history = cache.import_history(10).await?;
+        assert_eq!(history.iter().map(|r| r.files).collect::<Vec<_>>(), [20, 10]);
+        assert_eq!(history[0].destination, PathBuf::from("/library"));
+        assert_eq!(cache.import_history(1).await?.len(), 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_stats_history() -> Result<()> {
+        let cache = create_test_cache().await?;
+        for files in [10, 20, 30] {
+            let snapshot = StatsSnapshot {
+                taken: DateTime::from_timestamp(1_700_000_000 + files as i64, 0)
+                    .unwrap()
+                    .into(),
+                kind: SnapshotKind::Scan,
+                files,
+                bytes: 1024,
+                duplicates: 1,
+                library_files: files * 2,
+                library_bytes: 2048,
+                duplicates_removed: 3,
+            };
+            cache.record_stats(&snapshot).await?;
+        }
+
+        let history = cache.stats_history(2).await?;
+        assert_eq!(history.iter().map(|s| s.files).collect::<Vec<_>>(), [20, 30]);
+        assert_eq!(history[1].library_files, 60);
+        assert_eq!(history[1].kind, SnapshotKind::Scan);
+
+        Ok(())
+    }
 }
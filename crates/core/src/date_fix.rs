@@ -0,0 +1,190 @@
+use std::fs::File;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use chrono::{Local, NaiveDateTime, TimeDelta, TimeZone};
+use color_eyre::eyre::{Result, eyre};
+use rayon::prelude::{IntoParallelRefIterator, ParallelIterator};
+use tracing::warn;
+use visualvault_models::{DateMismatch, FileType, MediaFile};
+
+use crate::read_exif_dates;
+use crate::undo_manager::{TimestampField, TimestampOperation};
+use crate::write_exif_taken;
+
+/// Photos whose modification time is further than this from their capture date are reported
+pub const DATE_MISMATCH_THRESHOLD: TimeDelta = TimeDelta::days(1);
+
+/// Finds photos whose modification time differs from their EXIF capture date by more than
+/// [`DATE_MISMATCH_THRESHOLD`], largest difference first.
+///
+/// Files without a capture date are skipped.
+#[must_use]
+pub fn find_date_mismatches(files: &[Arc<MediaFile>]) -> Vec<DateMismatch> {
+    let mut mismatches: Vec<DateMismatch> = files
+        .par_iter()
+        .filter(|file| file.file_type == FileType::Image)
+        .filter_map(|file| {
+            let taken = read_exif_dates(&file.path).ok()?.taken?;
+            let mismatch = DateMismatch {
+                path: file.path.clone(),
+                modified: file.modified,
+                taken,
+            };
+            (mismatch.difference() > DATE_MISMATCH_THRESHOLD).then_some(mismatch)
+        })
+        .collect();
+    mismatches.sort_by(|a, b| b.difference().cmp(&a.difference()).then_with(|| a.path.cmp(&b.path)));
+    mismatches
+}
+
+/// Makes the dates of each mismatched photo agree by changing `field`: the modification time
+/// is set to the capture date, or the capture date to the modification time.
+///
+/// Returns the changes that were made; failures are logged and skipped.
+#[must_use]
+pub fn fix_dates(mismatches: &[DateMismatch], field: TimestampField) -> Vec<TimestampOperation> {
+    mismatches
+        .iter()
+        .filter_map(|mismatch| {
+            let modified = mismatch.modified.naive_local();
+            let (before, after) = match field {
+                TimestampField::Modified => (modified, mismatch.taken),
+                TimestampField::ExifTaken => (mismatch.taken, modified),
+            };
+            match set_timestamp(&mismatch.path, field, after) {
+                Ok(()) => Some(TimestampOperation {
+                    path: mismatch.path.clone(),
+                    field,
+                    before,
+                    after,
+                }),
+                Err(e) => {
+                    warn!("Failed to fix the date of {}: {}", mismatch.path.display(), e);
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+/// Sets one date of the file at `path` to `value`, a local time.
+///
+/// Writing the capture date keeps the modification time of the file unchanged.
+pub(crate) fn set_timestamp(path: &Path, field: TimestampField, value: NaiveDateTime) -> Result<()> {
+    match field {
+        TimestampField::Modified => set_modified(path, system_time(value)?),
+        TimestampField::ExifTaken => {
+            let modified = std::fs::metadata(path)?.modified()?;
+            write_exif_taken(path, value)?;
+            set_modified(path, modified)
+        }
+    }
+}
+
+fn set_modified(path: &Path, time: SystemTime) -> Result<()> {
+    File::options().write(true).open(path)?.set_modified(time)?;
+    Ok(())
+}
+
+fn system_time(value: NaiveDateTime) -> Result<SystemTime> {
+    Local
+        .from_local_datetime(&value)
+        .earliest()
+        .map(SystemTime::from)
+        .ok_or_else(|| eyre!("{value} does not exist in the local time zone"))
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    #![allow(clippy::panic_in_result_fn)]
+
+    use super::*;
+    use crate::test_support::media_file;
+    use chrono::{DateTime, NaiveDate};
+
+    /// A little-endian TIFF whose Exif IFD records `taken` as the capture date
+    fn tiff_with_date(taken: &str) -> Vec<u8> {
+        let mut tiff = b"II*\0".to_vec();
+        tiff.extend_from_slice(&8u32.to_le_bytes());
+        // IFD0 with only a pointer to the Exif IFD at offset 26
+        tiff.extend_from_slice(&1u16.to_le_bytes());
+        tiff.extend_from_slice(&0x8769u16.to_le_bytes());
+        tiff.extend_from_slice(&4u16.to_le_bytes());
+        tiff.extend_from_slice(&1u32.to_le_bytes());
+        tiff.extend_from_slice(&26u32.to_le_bytes());
+        tiff.extend_from_slice(&0u32.to_le_bytes());
+        // Exif IFD with DateTimeOriginal stored at offset 44
+        tiff.extend_from_slice(&1u16.to_le_bytes());
+        tiff.extend_from_slice(&0x9003u16.to_le_bytes());
+        tiff.extend_from_slice(&2u16.to_le_bytes());
+        tiff.extend_from_slice(&20u32.to_le_bytes());
+        tiff.extend_from_slice(&44u32.to_le_bytes());
+        tiff.extend_from_slice(&0u32.to_le_bytes());
+        tiff.extend_from_slice(taken.as_bytes());
+        tiff.push(0);
+        tiff
+    }
+
+    fn at(day: u32, hour: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2024, 3, day)
+            .unwrap()
+            .and_hms_opt(hour, 0, 0)
+            .unwrap()
+    }
+
+    fn modified_of(path: &Path) -> NaiveDateTime {
+        DateTime::<Local>::from(std::fs::metadata(path).unwrap().modified().unwrap()).naive_local()
+    }
+
+    #[test]
+    fn test_find_and_fix_date_mismatches() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let wrong = dir.path().join("wrong.tif");
+        let close = dir.path().join("close.tif");
+        std::fs::write(&wrong, tiff_with_date("2024:03:15 10:00:00"))?;
+        std::fs::write(&close, tiff_with_date("2024:03:15 10:00:00"))?;
+        set_timestamp(&wrong, TimestampField::Modified, at(25, 12))?;
+        set_timestamp(&close, TimestampField::Modified, at(15, 18))?;
+        let local = |path: &Path| Local.from_local_datetime(&modified_of(path)).unwrap();
+        let files = vec![
+            media_file(&wrong).modified(local(&wrong)).arc(),
+            media_file(&close).modified(local(&close)).arc(),
+        ];
+
+        let mismatches = find_date_mismatches(&files);
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(mismatches[0].path, wrong);
+        assert_eq!(mismatches[0].taken, at(15, 10));
+
+        let changes = fix_dates(&mismatches, TimestampField::Modified);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].before, at(25, 12));
+        assert_eq!(modified_of(&wrong), at(15, 10));
+        Ok(())
+    }
+
+    #[test]
+    fn test_fix_exif_keeps_modification_time() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("photo.tif");
+        std::fs::write(&path, tiff_with_date("2024:03:15 10:00:00"))?;
+        set_timestamp(&path, TimestampField::Modified, at(20, 9))?;
+        let mismatch = DateMismatch {
+            path: path.clone(),
+            modified: Local.from_local_datetime(&at(20, 9)).unwrap(),
+            taken: at(15, 10),
+        };
+
+        let changes = fix_dates(&[mismatch], TimestampField::ExifTaken);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(read_exif_dates(&path)?.taken, Some(at(20, 9)));
+        assert_eq!(modified_of(&path), at(20, 9));
+
+        set_timestamp(&path, TimestampField::ExifTaken, changes[0].before)?;
+        assert_eq!(read_exif_dates(&path)?.taken, Some(at(15, 10)));
+        Ok(())
+    }
+}
@@ -0,0 +1,48 @@
+use color_eyre::eyre::{Result, eyre};
+use notify_rust::{Notification, Timeout};
+use tracing::info;
+
+const APP_NAME: &str = "VisualVault";
+
+/// How long the notification stays up where the notification server honors it
+const DISPLAY_MILLIS: u32 = 10_000;
+
+/// Shows a desktop notification through the platform's notification service.
+///
+/// Linux and the BSDs talk to the freedesktop notification server over D-Bus, macOS uses the
+/// Notification Center and Windows shows a toast. This blocks until the notification has
+/// been handed over.
+///
+/// # Errors
+///
+/// Returns an error if the notification service is unavailable or rejects the notification.
+pub fn send_desktop_notification(title: &str, body: &str) -> Result<()> {
+    notification(title, body)
+        .show()
+        .map_err(|e| eyre!("Failed to show a desktop notification: {}", e))?;
+    info!("Desktop notification shown: {}", title);
+    Ok(())
+}
+
+fn notification(title: &str, body: &str) -> Notification {
+    let mut notification = Notification::new();
+    notification
+        .appname(APP_NAME)
+        .summary(title)
+        .body(body)
+        .timeout(Timeout::Milliseconds(DISPLAY_MILLIS));
+    notification
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_notification_content() {
+        let notification = notification("Scan finished", "120 files found");
+        assert_eq!(notification.appname, APP_NAME);
+        assert_eq!(notification.summary, "Scan finished");
+        assert_eq!(notification.body, "120 files found");
+    }
+}
@@ -0,0 +1,210 @@
+#![allow(clippy::cast_possible_wrap)]
+#![allow(clippy::cast_sign_loss)]
+#![allow(clippy::cast_possible_truncation)]
+use chrono::{DateTime, Local, TimeZone};
+use color_eyre::eyre::Result;
+use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions};
+use sqlx::{Row, SqlitePool};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tracing::info;
+use visualvault_models::MediaFile;
+
+use crate::file_store::{decode, search_key};
+
+/// Rows written per transaction while rebuilding the index
+const INSERT_BATCH: usize = 1000;
+
+/// Which library the index covers and when it was last built
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DestinationIndexInfo {
+    pub root: PathBuf,
+    pub indexed_at: DateTime<Local>,
+    pub files: usize,
+}
+
+/// Files of the destination library, kept in an `SQLite` file so they can still be searched
+/// after the source files were organized away.
+///
+/// The index covers one library root at a time. It is rebuilt by scanning the destination
+/// and extended with the files of every organize run in between.
+#[derive(Debug, Clone)]
+pub struct DestinationIndex {
+    pool: SqlitePool,
+}
+
+impl DestinationIndex {
+    /// Opens the index at `path`, creating it if needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database cannot be opened or its tables cannot be created.
+    pub async fn open(path: &Path) -> Result<Self> {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(2)
+            .connect_with(
+                SqliteConnectOptions::new()
+                    .filename(path)
+                    .create_if_missing(true)
+                    .journal_mode(SqliteJournalMode::Wal),
+            )
+            .await?;
+
+        sqlx::query(
+            "CREATE TABLE IF NOT EXISTS library_files (
+                path TEXT PRIMARY KEY,
+                search TEXT NOT NULL,
+                data TEXT NOT NULL
+            )",
+        )
+        .execute(&pool)
+        .await?;
+        sqlx::query(
+            "CREATE TABLE IF NOT EXISTS library_info (
+                id INTEGER PRIMARY KEY CHECK (id = 0),
+                root TEXT NOT NULL,
+                indexed_at INTEGER NOT NULL
+            )",
+        )
+        .execute(&pool)
+        .await?;
+
+        Ok(Self { pool })
+    }
+
+    /// Replaces the whole index with `files`, the current contents of the library at `root`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a file cannot be serialized or the database cannot be written.
+    pub async fn rebuild(&self, root: &Path, files: &[Arc<MediaFile>]) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("DELETE FROM library_files").execute(&mut *tx).await?;
+        sqlx::query("INSERT OR REPLACE INTO library_info (id, root, indexed_at) VALUES (0, ?, ?)")
+            .bind(root.to_string_lossy().as_ref())
+            .bind(Local::now().timestamp())
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+
+        for batch in files.chunks(INSERT_BATCH) {
+            self.insert(batch.iter().map(AsRef::as_ref)).await?;
+        }
+        info!("Indexed {} library files in {}", files.len(), root.display());
+        Ok(())
+    }
+
+    /// Adds or updates `files`, which were just placed in the library.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a file cannot be serialized or the database cannot be written.
+    pub async fn add(&self, files: &[MediaFile]) -> Result<()> {
+        for batch in files.chunks(INSERT_BATCH) {
+            self.insert(batch.iter()).await?;
+        }
+        Ok(())
+    }
+
+    async fn insert(&self, files: impl Iterator<Item = &MediaFile>) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+        for file in files {
+            sqlx::query("INSERT OR REPLACE INTO library_files (path, search, data) VALUES (?, ?, ?)")
+                .bind(file.path.to_string_lossy().as_ref())
+                .bind(search_key(file))
+                .bind(serde_json::to_string(file)?)
+                .execute(&mut *tx)
+                .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Returns up to `limit` library files whose lowercase path, camera or lens contains
+    /// `term`, ordered by path.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails or a stored file cannot be decoded.
+    pub async fn search(&self, term: &str, limit: usize) -> Result<Vec<Arc<MediaFile>>> {
+        let rows = sqlx::query("SELECT data FROM library_files WHERE instr(search, ?) > 0 ORDER BY path LIMIT ?")
+            .bind(term.to_lowercase())
+            .bind(limit as i64)
+            .fetch_all(&self.pool)
+            .await?;
+        rows.iter().map(|row| decode(&row.get::<String, _>("data"))).collect()
+    }
+
+    /// Describes the indexed library, or returns `None` if the index was never built.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a query fails.
+    pub async fn info(&self) -> Result<Option<DestinationIndexInfo>> {
+        let Some(row) = sqlx::query("SELECT root, indexed_at FROM library_info WHERE id = 0")
+            .fetch_optional(&self.pool)
+            .await?
+        else {
+            return Ok(None);
+        };
+        let files: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM library_files")
+            .fetch_one(&self.pool)
+            .await?;
+        Ok(Some(DestinationIndexInfo {
+            root: PathBuf::from(row.get::<String, _>("root")),
+            indexed_at: Local
+                .timestamp_opt(row.get("indexed_at"), 0)
+                .single()
+                .unwrap_or_else(Local::now),
+            files: files as usize,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    #![allow(clippy::panic_in_result_fn)]
+    #![allow(clippy::unwrap_in_result)]
+    use super::*;
+    use crate::test_support::media_file;
+    use tempfile::TempDir;
+    use visualvault_models::FileType;
+
+    #[tokio::test]
+    async fn test_rebuild_add_and_search() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let path = temp_dir.path().join("destination.db");
+        let index = DestinationIndex::open(&path).await?;
+        assert!(index.info().await?.is_none());
+
+        let files = vec![
+            media_file("/library/2024/01/IMG_0001.jpg").arc(),
+            media_file("/library/2024/02/CLIP_0002.mp4")
+                .file_type(FileType::Video)
+                .arc(),
+        ];
+        index.rebuild(Path::new("/library"), &files).await?;
+        index
+            .add(&[media_file("/library/2024/03/IMG_0003.jpg").build()])
+            .await?;
+        drop(index);
+
+        // The index outlives the session that built it
+        let index = DestinationIndex::open(&path).await?;
+        let info = index.info().await?.unwrap();
+        assert_eq!(info.root, PathBuf::from("/library"));
+        assert_eq!(info.files, 3);
+
+        let found = index.search("img_", 10).await?;
+        assert_eq!(
+            found.iter().map(|file| file.name.to_string()).collect::<Vec<_>>(),
+            vec!["IMG_0001.jpg", "IMG_0003.jpg"]
+        );
+
+        index.rebuild(Path::new("/other"), &files[..1]).await?;
+        assert_eq!(index.info().await?.unwrap().files, 1);
+        assert!(index.search("clip", 10).await?.is_empty());
+        Ok(())
+    }
+}
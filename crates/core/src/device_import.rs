@@ -0,0 +1,281 @@
+use color_eyre::Result;
+use std::collections::{BTreeSet, HashSet};
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+use visualvault_models::{CameraVolume, ImportSummary};
+use visualvault_utils::media_types::MEDIA_EXTENSIONS;
+use walkdir::WalkDir;
+
+use crate::batch_rename::unique_destination;
+use crate::library_index::hash_file;
+
+const IMPORTED_INDEX_FILE: &str = "imported_media.json";
+
+/// Finds mounted volumes with a `DCIM` folder, such as camera cards and phones.
+#[must_use]
+pub fn find_camera_volumes() -> Vec<CameraVolume> {
+    camera_volumes_in(&mount_roots(std::env::consts::OS))
+}
+
+/// Folders the system mounts removable volumes in, on `os`
+fn mount_roots(os: &str) -> Vec<PathBuf> {
+    match os {
+        "macos" => vec![PathBuf::from("/Volumes")],
+        // Drive letters after the system drive; each is a volume of its own
+        "windows" => ('D'..='Z')
+            .map(|letter| PathBuf::from(format!("{letter}:\\")))
+            .collect(),
+        _ => {
+            let mut roots = Vec::new();
+            if let Some(user) = std::env::var_os("USER") {
+                roots.push(Path::new("/media").join(&user));
+                roots.push(Path::new("/run/media").join(&user));
+            }
+            roots.push(PathBuf::from("/media"));
+            roots.push(PathBuf::from("/mnt"));
+            roots
+        }
+    }
+}
+
+/// Volumes below `roots` with a `DCIM` folder. A root holding `DCIM` itself, such as a
+/// Windows drive, is a volume too.
+fn camera_volumes_in(roots: &[PathBuf]) -> Vec<CameraVolume> {
+    let mut seen = HashSet::new();
+    let mut volumes = Vec::new();
+    for root in roots {
+        let mut candidates = vec![root.clone()];
+        if let Ok(entries) = fs::read_dir(root) {
+            let mut children: Vec<PathBuf> = entries
+                .filter_map(std::result::Result::ok)
+                .filter(|entry| entry.file_type().is_ok_and(|t| t.is_dir()))
+                .map(|entry| entry.path())
+                .collect();
+            children.sort();
+            candidates.extend(children);
+        }
+
+        for volume in candidates {
+            let Some(dcim) = dcim_folder(&volume) else {
+                continue;
+            };
+            if seen.insert(dcim.clone()) {
+                let name = volume.file_name().map_or_else(
+                    || volume.display().to_string(),
+                    |name| name.to_string_lossy().to_string(),
+                );
+                volumes.push(CameraVolume { name, dcim });
+            }
+        }
+    }
+    volumes
+}
+
+/// The `DCIM` folder of `volume`, matched without regard to case
+fn dcim_folder(volume: &Path) -> Option<PathBuf> {
+    fs::read_dir(volume)
+        .ok()?
+        .filter_map(std::result::Result::ok)
+        .find(|entry| entry.file_name().eq_ignore_ascii_case("dcim") && entry.file_type().is_ok_and(|t| t.is_dir()))
+        .map(|entry| entry.path())
+}
+
+/// Content hashes of every file imported from a device, so later imports skip them.
+///
+/// Stored as `imported_media.json` in the config directory. Files stay known even after
+/// they are organized, renamed or deleted from the library.
+#[derive(Debug, Default)]
+pub struct ImportedIndex {
+    hashes: BTreeSet<String>,
+    path: Option<PathBuf>,
+}
+
+impl ImportedIndex {
+    /// Loads the index stored in `config_dir`, or an empty one if there is none yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the index file exists but cannot be read or parsed.
+    pub fn load(config_dir: &Path) -> Result<Self> {
+        let path = config_dir.join("visualvault").join(IMPORTED_INDEX_FILE);
+        let hashes = if path.exists() {
+            serde_json::from_str(&fs::read_to_string(&path)?)?
+        } else {
+            BTreeSet::new()
+        };
+        Ok(Self {
+            hashes,
+            path: Some(path),
+        })
+    }
+
+    /// Writes the index back to the file it was loaded from.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the index cannot be serialized or written.
+    pub fn save(&self) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string(&self.hashes)?)?;
+        Ok(())
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.hashes.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.hashes.is_empty()
+    }
+
+    /// Copies the media below `dcim` that was never imported into `target`, keeping the
+    /// camera's folder layout and modification times, and remembers the copied files.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `target` cannot be created. Files that cannot be read or copied
+    /// are logged and counted as failed.
+    pub fn import_new_media(&mut self, dcim: &Path, target: &Path) -> Result<ImportSummary> {
+        fs::create_dir_all(target)?;
+        let mut summary = ImportSummary::default();
+
+        let files = WalkDir::new(dcim)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_map(std::result::Result::ok)
+            .filter(|entry| entry.file_type().is_file())
+            .filter(|entry| entry.path().to_str().is_some_and(|s| MEDIA_EXTENSIONS.is_match(s)));
+
+        for entry in files {
+            let source = entry.path();
+            let hash = match hash_file(source) {
+                Ok(indexed) => indexed.sha256,
+                Err(e) => {
+                    warn!("Cannot read {}: {}", source.display(), e);
+                    summary.failed += 1;
+                    continue;
+                }
+            };
+            if self.hashes.contains(&hash) {
+                summary.skipped += 1;
+                continue;
+            }
+
+            let relative = source.strip_prefix(dcim).unwrap_or(source);
+            let folder = relative
+                .parent()
+                .map_or_else(|| target.to_path_buf(), |parent| target.join(parent));
+            match copy_with_mtime(source, &folder) {
+                Ok(()) => {
+                    self.hashes.insert(hash);
+                    summary.imported += 1;
+                }
+                Err(e) => {
+                    warn!("Failed to import {}: {}", source.display(), e);
+                    summary.failed += 1;
+                }
+            }
+        }
+
+        info!(
+            "Imported {} new files from {} ({} already imported, {} failed)",
+            summary.imported,
+            dcim.display(),
+            summary.skipped,
+            summary.failed
+        );
+        Ok(summary)
+    }
+}
+
+/// Copies `source` into `folder` under a free name, keeping its modification time,
+/// which organizing falls back to when a file has no EXIF date
+fn copy_with_mtime(source: &Path, folder: &Path) -> std::io::Result<()> {
+    fs::create_dir_all(folder)?;
+    let name = source
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let destination = unique_destination(folder, &name, source, &HashSet::new());
+    let modified = fs::metadata(source)?.modified()?;
+    fs::copy(source, &destination)?;
+    File::options().write(true).open(&destination)?.set_modified(modified)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    #![allow(clippy::panic_in_result_fn)]
+    #![allow(clippy::unwrap_in_result)]
+
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_finds_volumes_with_dcim() -> Result<()> {
+        let temp = TempDir::new()?;
+        let media = temp.path().join("media");
+        fs::create_dir_all(media.join("EOS_DIGITAL").join("DCIM"))?;
+        fs::create_dir_all(media.join("phone").join("dcim"))?;
+        fs::create_dir_all(media.join("backup").join("Photos"))?;
+        let drive = temp.path().join("drive");
+        fs::create_dir_all(drive.join("DCIM"))?;
+
+        let volumes = camera_volumes_in(&[media.clone(), drive, temp.path().join("missing")]);
+        let names: Vec<&str> = volumes.iter().map(|volume| volume.name.as_str()).collect();
+        assert_eq!(names, vec!["EOS_DIGITAL", "phone", "drive"]);
+        assert_eq!(volumes[1].dcim, media.join("phone").join("dcim"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_mount_roots_per_platform() {
+        assert_eq!(mount_roots("macos"), vec![PathBuf::from("/Volumes")]);
+        assert_eq!(mount_roots("windows").first(), Some(&PathBuf::from("D:\\")));
+        assert!(mount_roots("linux").contains(&PathBuf::from("/media")));
+    }
+
+    #[test]
+    fn test_import_only_copies_new_media() -> Result<()> {
+        let temp = TempDir::new()?;
+        let dcim = temp.path().join("card").join("DCIM");
+        fs::create_dir_all(dcim.join("100CANON"))?;
+        fs::write(dcim.join("100CANON").join("IMG_0001.JPG"), b"first")?;
+        fs::write(dcim.join("100CANON").join("notes.txt"), b"not media")?;
+
+        let config = temp.path().join("config");
+        let target = temp.path().join("inbox");
+        let mut index = ImportedIndex::load(&config)?;
+        let summary = index.import_new_media(&dcim, &target)?;
+        assert_eq!(summary.imported, 1);
+        assert!(target.join("100CANON").join("IMG_0001.JPG").exists());
+        assert!(!target.join("100CANON").join("notes.txt").exists());
+        index.save()?;
+
+        // A new shot is imported, the old one is skipped even after it was moved away
+        fs::remove_dir_all(&target)?;
+        fs::write(dcim.join("100CANON").join("IMG_0002.JPG"), b"second")?;
+        let mut index = ImportedIndex::load(&config)?;
+        let summary = index.import_new_media(&dcim, &target)?;
+        assert_eq!(
+            summary,
+            ImportSummary {
+                imported: 1,
+                skipped: 1,
+                failed: 0
+            }
+        );
+        assert!(target.join("100CANON").join("IMG_0002.JPG").exists());
+        assert!(!target.join("100CANON").join("IMG_0001.JPG").exists());
+        assert_eq!(index.len(), 2);
+        Ok(())
+    }
+}
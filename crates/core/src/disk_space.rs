@@ -0,0 +1,123 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use visualvault_models::{MediaFile, SpaceProjection};
+
+/// Works out how much space organizing `files` into `destination` takes on the
+/// destination drive and how much is free there.
+///
+/// Files already on the destination drive are renamed into place, so only files on
+/// other drives count. A destination that does not exist yet is judged by the nearest
+/// folder above it.
+#[must_use]
+pub fn project_space(files: &[Arc<MediaFile>], destination: &Path) -> SpaceProjection {
+    let Some(existing) = destination.ancestors().find(|dir| dir.is_dir()) else {
+        // Nothing of the destination exists, so every byte is new to it
+        return SpaceProjection {
+            required: files.iter().map(|file| file.size).sum(),
+            copied_files: files.len(),
+            free: None,
+        };
+    };
+    let destination_volume = volume_of(existing);
+
+    // Files in one folder share a drive, so each folder is only looked up once
+    let mut folder_volumes: HashMap<PathBuf, Option<u64>> = HashMap::new();
+    let mut projection = SpaceProjection {
+        free: free_space(existing),
+        ..SpaceProjection::default()
+    };
+    for file in files {
+        let folder = file.path.parent().unwrap_or(&file.path);
+        let volume = *folder_volumes
+            .entry(folder.to_path_buf())
+            .or_insert_with(|| volume_of(folder));
+        if destination_volume.is_none() || volume != destination_volume {
+            projection.required += file.size;
+            projection.copied_files += 1;
+        }
+    }
+    projection
+}
+
+/// Identifies the drive `path` lives on, if it can be told
+#[cfg(unix)]
+pub(crate) fn volume_of(path: &Path) -> Option<u64> {
+    use std::os::unix::fs::MetadataExt;
+    std::fs::metadata(path).ok().map(|metadata| metadata.dev())
+}
+
+/// Identifies the drive `path` lives on by its drive letter or share
+#[cfg(not(unix))]
+pub(crate) fn volume_of(path: &Path) -> Option<u64> {
+    use std::hash::{Hash, Hasher};
+    let std::path::Component::Prefix(prefix) = path.components().next()? else {
+        return None;
+    };
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    prefix.as_os_str().to_ascii_lowercase().hash(&mut hasher);
+    Some(hasher.finish())
+}
+
+#[cfg(unix)]
+pub(crate) fn c_path(path: &Path) -> Option<std::ffi::CString> {
+    use std::os::unix::ffi::OsStrExt;
+    std::ffi::CString::new(path.as_os_str().as_bytes()).ok()
+}
+
+/// Bytes free for unprivileged users on the drive of `dir`
+#[cfg(unix)]
+#[allow(clippy::useless_conversion)] // The field types differ between platforms
+pub(crate) fn free_space(dir: &Path) -> Option<u64> {
+    let path = c_path(dir)?;
+    let mut stat = std::mem::MaybeUninit::<libc::statvfs>::uninit();
+    // SAFETY: `path` is a NUL-terminated string and `stat` is valid for writes
+    if unsafe { libc::statvfs(path.as_ptr(), stat.as_mut_ptr()) } != 0 {
+        return None;
+    }
+    // SAFETY: `statvfs` filled in `stat` as it returned success
+    let stat = unsafe { stat.assume_init() };
+    Some(u64::from(stat.f_bavail).saturating_mul(u64::from(stat.f_frsize)))
+}
+
+#[cfg(not(unix))]
+pub(crate) const fn free_space(_dir: &Path) -> Option<u64> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use crate::test_support::media_file;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_same_drive_needs_no_space() {
+        let temp = TempDir::new().unwrap();
+        let source = temp.path().join("source");
+        std::fs::create_dir(&source).unwrap();
+        let files = vec![
+            media_file(source.join("a.jpg")).size(1000).arc(),
+            media_file(source.join("b.jpg")).size(500).arc(),
+        ];
+
+        let projection = project_space(&files, &temp.path().join("organized").join("photos"));
+        assert_eq!(projection.required, 0);
+        assert_eq!(projection.copied_files, 0);
+        assert!(projection.fits());
+        if cfg!(unix) {
+            assert!(projection.free.is_some());
+        }
+    }
+
+    #[test]
+    fn test_missing_destination_counts_everything() {
+        let files = vec![media_file("/photos/a.jpg").size(1000).arc()];
+        let projection = project_space(&files, Path::new("relative/missing"));
+        assert_eq!(projection.required, 1000);
+        assert_eq!(projection.free, None);
+    }
+}
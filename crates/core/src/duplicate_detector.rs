@@ -2,32 +2,141 @@ use ahash::AHashMap;
 use color_eyre::Result;
 use sha2::{Digest, Sha256};
 use smallvec::SmallVec;
+use std::io::{self, Read};
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, mpsc};
 use tokio::fs::File;
 use tokio::io::{AsyncReadExt, BufReader};
+use tokio::sync::RwLock;
+use tokio::task::JoinSet;
 use tracing::{info, warn};
+use visualvault_config::HashAlgorithm;
 use visualvault_models::{DuplicateGroup, DuplicateStats, MediaFile};
-
-pub struct DuplicateDetector;
+use visualvault_utils::{Progress, format_bytes};
+
+use crate::cache::Cache;
+use crate::database_cache::CacheEntry;
+use crate::{IoThrottle, ScanCancelled};
+
+/// Suffixes that file managers, phones and editors append to copies of a file
+const COPY_SUFFIXES: &[&str] = &[" - copy", " copy", "_copy", "-copy", "-edited", "_edited", " edited"];
+
+/// Files at least this large are streamed through the ring of buffers on a blocking thread,
+/// with progress reports and cancel checks along the way
+const LARGE_FILE_THRESHOLD: u64 = 64 * 1024 * 1024;
+
+/// Buffers in the ring used to hash large files
+const RING_SLOTS: usize = 4;
+
+/// Size of each buffer in the ring, whatever `buffer_size` is set to
+const RING_CHUNK: usize = 1024 * 1024;
+
+/// Chunks at least this large are hashed with BLAKE3 on all cores instead of a single one
+const BLAKE3_RAYON_MIN: usize = 128 * 1024;
+
+#[derive(Clone)]
+pub struct DuplicateDetector {
+    algorithm: HashAlgorithm,
+    cache: Option<Arc<RwLock<Box<dyn Cache>>>>,
+    /// Files hashed at once
+    concurrency: usize,
+    throttle: IoThrottle,
+    /// Set to stop hashing, even in the middle of a large file
+    cancel: Option<Arc<AtomicBool>>,
+    /// Shows how far hashing of large files got
+    progress: Option<Arc<RwLock<Progress>>>,
+}
 
 impl Default for DuplicateDetector {
     fn default() -> Self {
-        Self
+        Self::new()
     }
 }
 
 impl DuplicateDetector {
     #[must_use]
     pub const fn new() -> Self {
-        Self
+        Self::with_algorithm(HashAlgorithm::Sha256)
+    }
+
+    /// Creates a detector that compares full file contents with `algorithm`
+    #[must_use]
+    pub const fn with_algorithm(algorithm: HashAlgorithm) -> Self {
+        Self {
+            algorithm,
+            cache: None,
+            concurrency: 1,
+            throttle: IoThrottle::unlimited(),
+            cancel: None,
+            progress: None,
+        }
+    }
+
+    /// Reuses the full hashes stored in `cache` for files whose path, size and modification
+    /// time are unchanged, and stores the hashes it calculates there.
+    #[must_use]
+    pub fn with_cache(mut self, cache: Arc<RwLock<Box<dyn Cache>>>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Hashes up to `concurrency` files at once instead of one after another
+    #[must_use]
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Holds the reads of files being hashed to the limits of `throttle`
+    #[must_use]
+    pub fn with_throttle(mut self, throttle: IoThrottle) -> Self {
+        self.throttle = throttle;
+        self
+    }
+
+    /// Stops hashing once `cancel` is set, checking it between files and after every chunk of
+    /// a large file; detection then ends with [`ScanCancelled`]
+    #[must_use]
+    pub fn with_cancel(mut self, cancel: Arc<AtomicBool>) -> Self {
+        self.cancel = Some(cancel);
+        self
+    }
+
+    /// Reports hashed bytes of large files through the progress message
+    #[must_use]
+    pub fn with_progress(mut self, progress: Arc<RwLock<Progress>>) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancel
+            .as_ref()
+            .is_some_and(|cancel| cancel.load(Ordering::Acquire))
+    }
+
+    #[must_use]
+    pub const fn algorithm(&self) -> HashAlgorithm {
+        self.algorithm
+    }
+
+    /// Returns the algorithm a hash produced by [`Self::hash_file`] was calculated with.
+    ///
+    /// Hashes are stored as `algorithm:hex`, so cached hashes from another algorithm, or from
+    /// versions that stored bare hex, are recognized as stale and recalculated.
+    #[must_use]
+    pub fn algorithm_of(hash: &str) -> Option<HashAlgorithm> {
+        let (algorithm, _) = hash.split_once(':')?;
+        algorithm.parse().ok()
     }
 
     /// Detect duplicates in a collection of media files
     ///
     /// # Errors
     ///
-    /// This function will return an error if file I/O operations fail while calculating hashes.
+    /// This function will return an error if file I/O operations fail while calculating hashes,
+    /// or [`ScanCancelled`] when the detector is cancelled.
     pub async fn detect_duplicates(&self, files: &[Arc<MediaFile>], use_quick_hash: bool) -> Result<DuplicateStats> {
         info!("Starting duplicate detection for {} files", files.len());
 
@@ -37,10 +146,16 @@ impl DuplicateDetector {
             potential_duplicates.len()
         );
 
-        let hash_groups = self
+        let (hash_groups, cache_hits, cache_misses, hashed_bytes) = self
             .calculate_hashes_for_groups(potential_duplicates, use_quick_hash)
             .await;
-        let duplicate_stats = Self::build_duplicate_stats(hash_groups);
+        if self.is_cancelled() {
+            return Err(ScanCancelled.into());
+        }
+        let mut duplicate_stats = Self::build_duplicate_stats(hash_groups);
+        duplicate_stats.hash_cache_hits = cache_hits;
+        duplicate_stats.hash_cache_misses = cache_misses;
+        duplicate_stats.hashed_bytes = hashed_bytes;
 
         info!(
             "Found {} duplicate groups with {} total duplicates wasting {} bytes",
@@ -50,6 +165,76 @@ impl DuplicateDetector {
         Ok(duplicate_stats)
     }
 
+    /// Calculates the full hashes of the files sharing their size with another one, reusing and
+    /// filling the hash cache, so [`Cache::duplicate_groups`] can group them.
+    ///
+    /// The returned stats have no groups; they count the hashes taken from the cache and the
+    /// ones calculated.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ScanCancelled`] when the detector is cancelled.
+    pub async fn hash_candidates(&self, files: &[Arc<MediaFile>]) -> Result<DuplicateStats> {
+        let (_, cache_hits, cache_misses, hashed_bytes) = self
+            .calculate_hashes_for_groups(Self::group_files_by_size(files), false)
+            .await;
+        if self.is_cancelled() {
+            return Err(ScanCancelled.into());
+        }
+        Ok(DuplicateStats {
+            hash_cache_hits: cache_hits,
+            hash_cache_misses: cache_misses,
+            hashed_bytes,
+            ..DuplicateStats::default()
+        })
+    }
+
+    /// Groups files that look like copies of each other, such as `IMG_1234.jpg` and `IMG_1234 (1).jpg`.
+    ///
+    /// Files are grouped when their names match once copy suffixes like "(1)", " copy" or
+    /// "-edited" are removed and they have the same size and quick hash of their first and last
+    /// bytes. Full content hashes are never calculated.
+    ///
+    /// # Errors
+    ///
+    /// This function currently never fails; unreadable files are skipped.
+    pub async fn detect_probable_copies(&self, files: &[Arc<MediaFile>]) -> Result<DuplicateStats> {
+        info!("Looking for probable copies among {} files", files.len());
+
+        let mut name_groups: AHashMap<(String, u64), SmallVec<[Arc<MediaFile>; 8]>> = AHashMap::new();
+        for file in files {
+            let key = (copy_base_name(&file.name), file.size);
+            name_groups.entry(key).or_default().push(Arc::clone(file));
+        }
+
+        let mut prefix_groups: AHashMap<String, SmallVec<[Arc<MediaFile>; 4]>> = AHashMap::new();
+        for ((base_name, size), group) in name_groups {
+            if group.len() < 2 {
+                continue;
+            }
+            for file in group {
+                if let Some((hashed_file, _)) = self.calculate_and_update_hash(file, size, true).await {
+                    if let Some(hash) = &hashed_file.hash {
+                        prefix_groups
+                            .entry(format!("{base_name}:{hash}"))
+                            .or_default()
+                            .push(hashed_file);
+                    }
+                }
+            }
+        }
+
+        let mut stats = Self::build_duplicate_stats(prefix_groups);
+        // Put the likely original, the name without a copy suffix, first
+        for group in &mut stats.groups {
+            group
+                .files
+                .sort_by(|a, b| a.name.len().cmp(&b.name.len()).then_with(|| a.name.cmp(&b.name)));
+        }
+        info!("Found {} groups of probable copies", stats.total_groups);
+        Ok(stats)
+    }
+
     /// Groups files by size, returning only groups with multiple files
     fn group_files_by_size(files: &[Arc<MediaFile>]) -> Vec<(u64, SmallVec<[Arc<MediaFile>; 8]>)> {
         let mut size_groups: AHashMap<u64, SmallVec<[Arc<MediaFile>; 8]>> = AHashMap::new();
@@ -61,46 +246,108 @@ impl DuplicateDetector {
         size_groups.into_iter().filter(|(_, group)| group.len() > 1).collect()
     }
 
-    /// Calculates hashes for all files in the given size groups
+    /// Calculates hashes for all files in the given size groups, along with how many were
+    /// reused from the hash cache and how many had to be calculated
     async fn calculate_hashes_for_groups(
         &self,
         size_groups: Vec<(u64, SmallVec<[Arc<MediaFile>; 8]>)>,
         use_quick_hash: bool,
-    ) -> AHashMap<String, SmallVec<[Arc<MediaFile>; 4]>> {
+    ) -> (AHashMap<String, SmallVec<[Arc<MediaFile>; 4]>>, usize, usize, u64) {
         let mut hash_groups: AHashMap<String, SmallVec<[Arc<MediaFile>; 4]>> = AHashMap::new();
-
-        for (size, group) in size_groups {
-            for file in group {
-                if let Some(hashed_file) = self.calculate_and_update_hash(file, size, use_quick_hash).await {
-                    if let Some(hash) = &hashed_file.hash {
-                        hash_groups.entry(hash.to_string()).or_default().push(hashed_file);
-                    }
+        let mut cache_hits = 0;
+        let mut cache_misses = 0;
+        let mut hashed_bytes = 0;
+
+        let jobs: Vec<(u64, Arc<MediaFile>)> = size_groups
+            .into_iter()
+            .flat_map(|(size, group)| group.into_iter().map(move |file| (size, file)))
+            .collect();
+        let hashed = if self.concurrency > 1 {
+            self.hash_concurrently(jobs, use_quick_hash).await
+        } else {
+            let mut hashed = Vec::with_capacity(jobs.len());
+            for (size, file) in jobs {
+                if self.is_cancelled() {
+                    break;
                 }
+                hashed.push((size, self.calculate_and_update_hash(file, size, use_quick_hash).await));
+            }
+            hashed
+        };
+
+        for (size, (hashed_file, cached)) in hashed.into_iter().filter_map(|(size, result)| Some((size, result?))) {
+            if cached {
+                cache_hits += 1;
+            } else if !use_quick_hash {
+                cache_misses += 1;
+                hashed_bytes += size;
+            }
+            if let Some(hash) = &hashed_file.hash {
+                hash_groups.entry(hash.to_string()).or_default().push(hashed_file);
             }
         }
 
-        hash_groups
+        (hash_groups, cache_hits, cache_misses, hashed_bytes)
+    }
+
+    /// Hashes `jobs` with up to [`DuplicateDetector::with_concurrency`] files at once,
+    /// returning the results in the order of the jobs so groups list their files the same way
+    /// on every run
+    async fn hash_concurrently(
+        &self,
+        jobs: Vec<(u64, Arc<MediaFile>)>,
+        use_quick_hash: bool,
+    ) -> Vec<(u64, Option<(Arc<MediaFile>, bool)>)> {
+        let detector = Arc::new(self.clone());
+        let mut join_set = JoinSet::new();
+        let mut hashed = Vec::with_capacity(jobs.len());
+        let mut jobs = jobs.into_iter().enumerate();
+        loop {
+            while join_set.len() < self.concurrency && !self.is_cancelled() {
+                let Some((index, (size, file))) = jobs.next() else {
+                    break;
+                };
+                let detector = Arc::clone(&detector);
+                join_set.spawn(async move {
+                    let result = detector.calculate_and_update_hash(file, size, use_quick_hash).await;
+                    (index, size, result)
+                });
+            }
+            match join_set.join_next().await {
+                Some(Ok(done)) => hashed.push(done),
+                Some(Err(e)) => warn!("Hashing task failed: {}", e),
+                None => break,
+            }
+        }
+        hashed.sort_unstable_by_key(|(index, _, _)| *index);
+        hashed.into_iter().map(|(_, size, result)| (size, result)).collect()
     }
 
-    /// Calculates hash for a single file and returns updated `MediaFile`
+    /// Calculates hash for a single file and returns updated `MediaFile`, and whether the hash
+    /// came from the hash cache
     async fn calculate_and_update_hash(
         &self,
         file: Arc<MediaFile>,
         size: u64,
         use_quick_hash: bool,
-    ) -> Option<Arc<MediaFile>> {
+    ) -> Option<(Arc<MediaFile>, bool)> {
         let hash_result = if use_quick_hash {
-            Self::calculate_quick_hash(&file.path, size).await
+            // The first and last 4 KiB
+            self.throttle.acquire(1, size.min(8192)).await;
+            Self::calculate_quick_hash(&file.path, size)
+                .await
+                .map(|hash| (hash, false))
         } else {
-            Self::calculate_file_hash(&file.path).await
+            self.cached_full_hash(&file).await
         };
 
         match hash_result {
-            Ok(hash) => {
+            Ok((hash, cached)) => {
                 let mut media_file = Arc::try_unwrap(file).unwrap_or_else(|arc| (*arc).clone());
                 media_file.hash = Some(Arc::from(hash.as_str()));
-                Some(Arc::new(media_file))
+                Some((Arc::new(media_file), cached))
             }
+            Err(e) if e.is::<ScanCancelled>() => None,
             Err(e) => {
                 warn!("Failed to hash file {:?}: {}", file.path, e);
                 None
@@ -109,7 +356,9 @@ impl DuplicateDetector {
     }
 
     /// Builds `DuplicateStats` from hash groups
-    fn build_duplicate_stats(hash_groups: AHashMap<String, SmallVec<[Arc<MediaFile>; 4]>>) -> DuplicateStats {
+    pub(crate) fn build_duplicate_stats(
+        hash_groups: AHashMap<String, SmallVec<[Arc<MediaFile>; 4]>>,
+    ) -> DuplicateStats {
         let mut groups = Vec::new();
         let mut total_duplicates = 0;
         let mut total_wasted_space = 0;
@@ -124,13 +373,14 @@ impl DuplicateDetector {
         }
 
         // Sort groups by wasted space (largest first)
-        groups.sort_by(|a, b| b.wasted_space.cmp(&a.wasted_space));
+        groups.sort_by_key(|g| std::cmp::Reverse(g.wasted_space));
 
         DuplicateStats {
             total_groups: groups.len(),
             total_duplicates,
             total_wasted_space,
             groups,
+            ..DuplicateStats::default()
         }
     }
 
@@ -141,7 +391,11 @@ impl DuplicateDetector {
     }
 
     /// Calculate SHA256 hash of a file
-    async fn calculate_file_hash(path: &Path) -> Result<String> {
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be opened or read.
+    pub async fn calculate_file_hash(path: &Path) -> Result<String> {
         let file = File::open(path).await?;
         let mut reader = BufReader::with_capacity(65536, file);
         let mut hasher = Sha256::new();
@@ -158,6 +412,167 @@ impl DuplicateDetector {
         Ok(format!("{:x}", hasher.finalize()))
     }
 
+    /// Hash the full contents of a file with the detector's algorithm, tagged as `algorithm:hex`
+    ///
+    /// Files of at least 64 MiB are streamed through a small fixed ring of buffers, with their
+    /// progress reported and the cancel flag checked after every chunk.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be opened or read, or [`ScanCancelled`] when the
+    /// detector is cancelled while hashing a large file.
+    pub async fn hash_file(&self, path: &Path, size: u64) -> Result<String> {
+        let hex = if size >= LARGE_FILE_THRESHOLD {
+            self.hash_large_file(path, size).await?
+        } else {
+            let mut hasher = StreamHasher::new(self.algorithm);
+            Self::stream_file(path, |chunk| hasher.update(chunk)).await?;
+            hasher.finish()
+        };
+
+        Ok(format!("{}:{hex}", self.algorithm))
+    }
+
+    /// Full hash of `file`, taken from the hash cache while its path, size and modification
+    /// time are unchanged, and whether it was
+    async fn cached_full_hash(&self, file: &MediaFile) -> Result<(String, bool)> {
+        let Some(cache) = &self.cache else {
+            self.throttle.acquire(1, file.size).await;
+            return Ok((self.hash_file(&file.path, file.size).await?, false));
+        };
+
+        // Cache failures only cost a re-hash, so they never fail the detection
+        let entry = cache
+            .read()
+            .await
+            .get(&file.path, file.size, &file.modified)
+            .await
+            .unwrap_or_else(|e| {
+                warn!("Failed to read cached hash of {:?}: {}", file.path, e);
+                None
+            });
+        if let Some(hash) = entry
+            .as_ref()
+            .and_then(|entry| entry.hash.as_deref())
+            .filter(|hash| Self::algorithm_of(hash) == Some(self.algorithm))
+        {
+            return Ok((hash.to_string(), true));
+        }
+
+        self.throttle.acquire(1, file.size).await;
+        let hash = self.hash_file(&file.path, file.size).await?;
+        let stored = if entry.is_some() {
+            cache.read().await.update_hash(&file.path, &hash).await
+        } else {
+            let mut entry = CacheEntry::from(file);
+            entry.hash = Some(hash.clone());
+            cache.read().await.insert(file.path.clone(), entry).await
+        };
+        if let Err(e) = stored {
+            warn!("Failed to cache hash of {:?}: {}", file.path, e);
+        }
+
+        Ok((hash, false))
+    }
+
+    /// Feeds the contents of a file to `update` in 64 KiB chunks
+    async fn stream_file(path: &Path, mut update: impl FnMut(&[u8])) -> Result<()> {
+        let file = File::open(path).await?;
+        let mut reader = BufReader::with_capacity(65536, file);
+        let mut buffer = vec![0; 65536];
+
+        loop {
+            let bytes_read = reader.read(&mut buffer).await?;
+            if bytes_read == 0 {
+                return Ok(());
+            }
+            update(&buffer[..bytes_read]);
+        }
+    }
+
+    /// Hashes a large file through a fixed ring of [`RING_SLOTS`] buffers of [`RING_CHUNK`]
+    /// bytes: a reader thread fills the next buffers while this one hashes, so memory stays
+    /// the same whatever the file or buffer size.
+    ///
+    /// `on_chunk` gets the bytes hashed so far after every chunk and stops hashing by
+    /// returning `false`, in which case `None` is returned.
+    fn hash_streamed(
+        path: &Path,
+        algorithm: HashAlgorithm,
+        mut on_chunk: impl FnMut(u64) -> bool,
+    ) -> Result<Option<String>> {
+        let mut file = std::fs::File::open(path)?;
+        let (filled_tx, filled_rx) = mpsc::sync_channel::<io::Result<Vec<u8>>>(RING_SLOTS);
+        let (free_tx, free_rx) = mpsc::sync_channel::<Vec<u8>>(RING_SLOTS);
+        for _ in 0..RING_SLOTS {
+            free_tx.send(vec![0; RING_CHUNK])?;
+        }
+
+        // The channels move into the closure so they are dropped, and the reader hangs up,
+        // before the scope waits for it
+        std::thread::scope(move |scope| {
+            scope.spawn(move || {
+                while let Ok(mut buffer) = free_rx.recv() {
+                    buffer.resize(RING_CHUNK, 0);
+                    let read = fill_buffer(&mut file, &mut buffer);
+                    let last = !matches!(read, Ok(filled) if filled > 0);
+                    let chunk = read.map(|filled| {
+                        buffer.truncate(filled);
+                        buffer
+                    });
+                    if filled_tx.send(chunk).is_err() || last {
+                        break;
+                    }
+                }
+            });
+
+            let mut hasher = StreamHasher::new(algorithm);
+            let mut hashed = 0;
+            loop {
+                let chunk = filled_rx
+                    .recv()
+                    .map_err(|_| io::Error::other("file reader stopped"))??;
+                if chunk.is_empty() {
+                    return Ok(Some(hasher.finish()));
+                }
+                hasher.update(&chunk);
+                hashed += chunk.len() as u64;
+                if !on_chunk(hashed) {
+                    return Ok(None);
+                }
+                // The reader may already be done with the file
+                let _ = free_tx.send(chunk);
+            }
+        })
+    }
+
+    /// Hashes a file of at least [`LARGE_FILE_THRESHOLD`] bytes off the async runtime,
+    /// reporting its progress and stopping early when the detector is cancelled
+    async fn hash_large_file(&self, path: &Path, size: u64) -> Result<String> {
+        let path = path.to_path_buf();
+        let algorithm = self.algorithm;
+        let cancel = self.cancel.clone();
+        let progress = self.progress.clone();
+        let name = path
+            .file_name()
+            .map_or_else(String::new, |n| n.to_string_lossy().to_string());
+
+        let hex = tokio::task::spawn_blocking(move || {
+            let mut reported_percent = None;
+            Self::hash_streamed(&path, algorithm, |hashed| {
+                if cancel.as_ref().is_some_and(|cancel| cancel.load(Ordering::Acquire)) {
+                    return false;
+                }
+                if let Some(progress) = &progress {
+                    reported_percent = report_hashed(progress, &name, hashed, size, reported_percent);
+                }
+                true
+            })
+        })
+        .await??;
+        hex.ok_or_else(|| ScanCancelled.into())
+    }
+
     /// Quick hash based on file size and first/last bytes
     async fn calculate_quick_hash(path: &Path, size: u64) -> Result<String> {
         if size == 0 {
@@ -211,6 +626,126 @@ impl DuplicateDetector {
     }
 }
 
+/// Incremental hasher for any [`HashAlgorithm`], producing the same hex as hashing the whole
+/// file at once
+enum StreamHasher {
+    Sha256(Sha256),
+    Blake3(Box<blake3::Hasher>),
+    Xxh3(Box<xxhash_rust::xxh3::Xxh3>),
+}
+
+impl StreamHasher {
+    fn new(algorithm: HashAlgorithm) -> Self {
+        match algorithm {
+            HashAlgorithm::Sha256 => Self::Sha256(Sha256::new()),
+            HashAlgorithm::Blake3 => Self::Blake3(Box::new(blake3::Hasher::new())),
+            HashAlgorithm::Xxh3 => Self::Xxh3(Box::new(xxhash_rust::xxh3::Xxh3::new())),
+        }
+    }
+
+    fn update(&mut self, chunk: &[u8]) {
+        match self {
+            Self::Sha256(hasher) => hasher.update(chunk),
+            Self::Blake3(hasher) if chunk.len() >= BLAKE3_RAYON_MIN => {
+                hasher.update_rayon(chunk);
+            }
+            Self::Blake3(hasher) => {
+                hasher.update(chunk);
+            }
+            Self::Xxh3(hasher) => hasher.update(chunk),
+        }
+    }
+
+    fn finish(self) -> String {
+        match self {
+            Self::Sha256(hasher) => format!("{:x}", hasher.finalize()),
+            Self::Blake3(hasher) => hasher.finalize().to_hex().to_string(),
+            Self::Xxh3(hasher) => format!("{:032x}", hasher.digest128()),
+        }
+    }
+}
+
+/// Reads until `buffer` is full or the file ends, returning how much was read
+fn fill_buffer(file: &mut std::fs::File, buffer: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buffer.len() {
+        match file.read(&mut buffer[filled..]) {
+            Ok(0) => break,
+            Ok(read) => filled += read,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(filled)
+}
+
+/// Shows how far hashing of a large file got in the progress message, returning the percent
+/// shown
+fn report_hashed(
+    progress: &RwLock<Progress>,
+    name: &str,
+    hashed: u64,
+    total: u64,
+    last_percent: Option<u64>,
+) -> Option<u64> {
+    let percent = (hashed * 100).checked_div(total).unwrap_or(100);
+    if last_percent == Some(percent) {
+        return last_percent;
+    }
+
+    // Never hold up hashing on a busy progress lock
+    if let Ok(mut prog) = progress.try_write() {
+        prog.message = format!(
+            "Hashing {name}: {percent}% ({} / {})",
+            format_bytes(hashed),
+            format_bytes(total)
+        );
+        Some(percent)
+    } else {
+        last_percent
+    }
+}
+
+/// Normalizes a file name by removing copy suffixes, e.g. `IMG_1234 (1).JPG` becomes `img_1234.jpg`
+fn copy_base_name(name: &str) -> String {
+    let name = name.to_lowercase();
+    let (stem, extension) = match name.rsplit_once('.') {
+        Some((stem, extension)) if !stem.is_empty() => (stem, Some(extension)),
+        _ => (name.as_str(), None),
+    };
+
+    let mut stem = stem.trim_end();
+    while let Some(stripped) = strip_copy_suffix(stem) {
+        stem = stripped;
+    }
+
+    match extension {
+        Some(extension) => format!("{stem}.{extension}"),
+        None => stem.to_string(),
+    }
+}
+
+/// Removes one trailing "(2)", " copy", " copy 2" or "-edited" style suffix, never leaving an empty name
+fn strip_copy_suffix(stem: &str) -> Option<&str> {
+    let counter_start = stem.trim_end_matches(|c: char| c.is_ascii_digit());
+    let stripped = if let Some(open) = stem.strip_suffix(')').and_then(|s| s.rfind('(')) {
+        let counter = &stem[open + 1..stem.len() - 1];
+        (!counter.is_empty() && counter.chars().all(|c| c.is_ascii_digit())).then(|| &stem[..open])
+    } else {
+        None
+    }
+    .or_else(|| COPY_SUFFIXES.iter().find_map(|suffix| stem.strip_suffix(suffix)))
+    .or_else(|| {
+        // "copy 2", "copy2"
+        let without_number = counter_start.trim_end();
+        (counter_start.len() < stem.len() && COPY_SUFFIXES.iter().any(|suffix| without_number.ends_with(suffix)))
+            .then_some(without_number)
+    })?
+    .trim_end();
+
+    (!stripped.is_empty()).then_some(stripped)
+}
+
 #[cfg(test)]
 mod tests {
     #![allow(clippy::unwrap_used)]
@@ -218,28 +753,15 @@ mod tests {
     #![allow(clippy::float_cmp)] // For comparing floats in tests
     #![allow(clippy::panic)]
     #![allow(clippy::panic_in_result_fn)] // For passing MediaFile by value in tests
+    #![allow(clippy::unwrap_in_result)]
     use super::*;
-    use chrono::Local;
+    use crate::test_support::media_file;
     use tempfile::TempDir;
     use tokio::fs;
-    use visualvault_models::FileType;
 
     // Helper function to create a test media file
     fn create_test_media_file(path: PathBuf, size: u64, _content_id: u8) -> Arc<MediaFile> {
-        let name = path.file_name().unwrap().to_string_lossy().to_string();
-        let extension = path.extension().unwrap_or_default().to_string_lossy().to_string();
-
-        Arc::new(MediaFile {
-            path,
-            name: name.into(),
-            extension: extension.into(),
-            file_type: FileType::Image,
-            size,
-            created: Local::now(),
-            modified: Local::now(),
-            hash: None,
-            metadata: None,
-        })
+        media_file(path).size(size).arc()
     }
 
     // Helper function to create a file with specific content
@@ -266,9 +788,179 @@ mod tests {
 
     #[test]
     fn test_duplicate_detector_default() {
-        let detector = DuplicateDetector;
-        // Just ensure it creates without panic
-        let _ = detector;
+        let detector = DuplicateDetector::default();
+        assert_eq!(detector.algorithm(), HashAlgorithm::Sha256);
+    }
+
+    #[tokio::test]
+    async fn test_hash_file_with_each_algorithm() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let file_path = temp_dir.path().join("test.txt");
+        create_file_with_content(&file_path, b"Hello, World!".to_vec()).await?;
+
+        let sha = DuplicateDetector::new().hash_file(&file_path, 13).await?;
+        assert_eq!(
+            sha,
+            "sha256:dffd6021bb2bd5b0af676290809ec3a53191dd81c7f70a4b28688a362182986f"
+        );
+
+        let blake = DuplicateDetector::with_algorithm(HashAlgorithm::Blake3)
+            .hash_file(&file_path, 13)
+            .await?;
+        assert_eq!(blake, format!("blake3:{}", blake3::hash(b"Hello, World!").to_hex()));
+
+        let xxh = DuplicateDetector::with_algorithm(HashAlgorithm::Xxh3)
+            .hash_file(&file_path, 13)
+            .await?;
+        assert_eq!(
+            xxh,
+            format!("xxh3:{:032x}", xxhash_rust::xxh3::xxh3_128(b"Hello, World!"))
+        );
+
+        assert_eq!(DuplicateDetector::algorithm_of(&sha), Some(HashAlgorithm::Sha256));
+        assert_eq!(DuplicateDetector::algorithm_of(&blake), Some(HashAlgorithm::Blake3));
+        assert_eq!(DuplicateDetector::algorithm_of(&xxh), Some(HashAlgorithm::Xxh3));
+        // Bare hex from earlier versions has no algorithm
+        assert_eq!(DuplicateDetector::algorithm_of("dffd6021bb2bd5b0"), None);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_detect_duplicates_reuses_cached_hashes() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let file1 = temp_dir.path().join("dup1.jpg");
+        let file2 = temp_dir.path().join("dup2.jpg");
+        create_file_with_content(&file1, b"duplicate content".to_vec()).await?;
+        create_file_with_content(&file2, b"duplicate content".to_vec()).await?;
+        let files = vec![
+            create_test_media_file(file1.clone(), 17, 1),
+            create_test_media_file(file2, 17, 1),
+        ];
+
+        let cache: Box<dyn Cache> = Box::new(crate::DatabaseCache::new(":memory:").await?);
+        let cache = Arc::new(RwLock::new(cache));
+        let detector = DuplicateDetector::with_algorithm(HashAlgorithm::Xxh3).with_cache(Arc::clone(&cache));
+
+        let stats = detector.detect_duplicates(&files, false).await?;
+        assert_eq!(stats.total_groups, 1);
+        assert_eq!((stats.hash_cache_hits, stats.hash_cache_misses), (0, 2));
+        assert_eq!(stats.hashed_bytes, files.iter().map(|file| file.size).sum::<u64>());
+
+        // Unchanged files are not read again, even when their content is
+        create_file_with_content(&file1, b"different content".to_vec()).await?;
+        let stats = detector.detect_duplicates(&files, false).await?;
+        assert_eq!(stats.total_groups, 1);
+        assert_eq!((stats.hash_cache_hits, stats.hash_cache_misses), (2, 0));
+        assert_eq!(stats.hash_cache_summary().as_deref(), Some("2 of 2 hashes from cache"));
+        assert_eq!(stats.hashed_bytes, 0);
+
+        // A changed modification time is a cache miss
+        let mut touched = (*files[0]).clone();
+        touched.modified += chrono::Duration::seconds(5);
+        let files = vec![Arc::new(touched), Arc::clone(&files[1])];
+        let stats = detector.detect_duplicates(&files, false).await?;
+        assert_eq!(stats.total_groups, 0);
+        assert_eq!((stats.hash_cache_hits, stats.hash_cache_misses), (1, 1));
+
+        // Hashes of another algorithm are recalculated
+        let detector = DuplicateDetector::with_algorithm(HashAlgorithm::Blake3).with_cache(cache);
+        let stats = detector.detect_duplicates(&files, false).await?;
+        assert_eq!((stats.hash_cache_hits, stats.hash_cache_misses), (0, 2));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_hashing_matches_sequential() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let mut files = Vec::new();
+        for idx in 0..12u8 {
+            let path = temp_dir.path().join(format!("photo{idx}.jpg"));
+            create_file_with_content(&path, vec![idx % 3; 64]).await?;
+            files.push(create_test_media_file(path, 64, idx % 3));
+        }
+
+        let sequential = DuplicateDetector::new().detect_duplicates(&files, false).await?;
+        let concurrent = DuplicateDetector::new()
+            .with_concurrency(4)
+            .detect_duplicates(&files, false)
+            .await?;
+        assert_eq!(concurrent.total_groups, 3);
+        assert_eq!(concurrent.total_duplicates, sequential.total_duplicates);
+        assert_eq!(concurrent.hashed_bytes, sequential.hashed_bytes);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_streamed_hash_matches_whole_file() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let file_path = temp_dir.path().join("video.bin");
+        let content: Vec<u8> = (0..RING_CHUNK * (RING_SLOTS + 1) + 12345)
+            .map(|i| (i % 251).to_le_bytes()[0])
+            .collect();
+        std::fs::write(&file_path, &content)?;
+
+        let mut reports = Vec::new();
+        let blake = DuplicateDetector::hash_streamed(&file_path, HashAlgorithm::Blake3, |hashed| {
+            reports.push(hashed);
+            true
+        })?;
+        assert_eq!(blake, Some(blake3::hash(&content).to_hex().to_string()));
+        assert_eq!(reports.len(), RING_SLOTS + 2);
+        assert_eq!(reports.last().copied(), Some(content.len() as u64));
+
+        let sha = DuplicateDetector::hash_streamed(&file_path, HashAlgorithm::Sha256, |_| true)?;
+        assert_eq!(sha, Some(format!("{:x}", Sha256::digest(&content))));
+        let xxh = DuplicateDetector::hash_streamed(&file_path, HashAlgorithm::Xxh3, |_| true)?;
+        assert_eq!(xxh, Some(format!("{:032x}", xxhash_rust::xxh3::xxh3_128(&content))));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_streamed_hash_stops_after_the_current_chunk() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let file_path = temp_dir.path().join("video.bin");
+        std::fs::write(&file_path, vec![7u8; RING_CHUNK * 10])?;
+
+        let mut chunks = 0;
+        let hash = DuplicateDetector::hash_streamed(&file_path, HashAlgorithm::Sha256, |_| {
+            chunks += 1;
+            chunks < 2
+        })?;
+        assert_eq!(hash, None);
+        assert_eq!(chunks, 2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_detection_stops() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let mut files = Vec::new();
+        for idx in 0..2u8 {
+            let path = temp_dir.path().join(format!("dup{idx}.jpg"));
+            create_file_with_content(&path, b"duplicate content".to_vec()).await?;
+            files.push(create_test_media_file(path, 17, 1));
+        }
+
+        let cancel = Arc::new(AtomicBool::new(true));
+        let result = DuplicateDetector::new()
+            .with_cancel(Arc::clone(&cancel))
+            .detect_duplicates(&files, false)
+            .await;
+        assert!(result.is_err_and(|e| e.is::<ScanCancelled>()));
+
+        cancel.store(false, Ordering::Release);
+        let stats = DuplicateDetector::new()
+            .with_cancel(cancel)
+            .detect_duplicates(&files, false)
+            .await?;
+        assert_eq!(stats.total_groups, 1);
+
+        Ok(())
     }
 
     #[tokio::test]
@@ -650,4 +1342,52 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_copy_base_name() {
+        assert_eq!(copy_base_name("IMG_1234.jpg"), "img_1234.jpg");
+        assert_eq!(copy_base_name("IMG_1234 (1).jpg"), "img_1234.jpg");
+        assert_eq!(copy_base_name("IMG_1234(12).JPG"), "img_1234.jpg");
+        assert_eq!(copy_base_name("IMG_1234 copy.jpg"), "img_1234.jpg");
+        assert_eq!(copy_base_name("IMG_1234 - Copy (2).jpg"), "img_1234.jpg");
+        assert_eq!(copy_base_name("IMG_1234 copy 3.jpg"), "img_1234.jpg");
+        assert_eq!(copy_base_name("IMG_1234-edited.jpg"), "img_1234.jpg");
+
+        // Numbers that are part of the name are kept
+        assert_eq!(copy_base_name("IMG_1235.jpg"), "img_1235.jpg");
+        assert_eq!(copy_base_name("photocopy2.jpg"), "photocopy2.jpg");
+        assert_eq!(copy_base_name("(1).jpg"), "(1).jpg");
+        assert_eq!(copy_base_name("-edited"), "-edited");
+    }
+
+    #[tokio::test]
+    async fn test_detect_probable_copies() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+
+        let original = temp_dir.path().join("IMG_1234.jpg");
+        let numbered = temp_dir.path().join("IMG_1234 (1).jpg");
+        let edited = temp_dir.path().join("IMG_1234-edited.jpg");
+        let other_content = temp_dir.path().join("IMG_1234 copy.jpg");
+        let other_name = temp_dir.path().join("IMG_9999.jpg");
+
+        for path in [&original, &numbered, &edited, &other_name] {
+            create_file_with_size(path, 2048, 0xAA).await?;
+        }
+        create_file_with_size(&other_content, 2048, 0xBB).await?;
+
+        let files: Vec<_> = [&original, &numbered, &edited, &other_content, &other_name]
+            .into_iter()
+            .map(|path| create_test_media_file(path.clone(), 2048, 0))
+            .collect();
+
+        let detector = DuplicateDetector::new();
+        let stats = detector.detect_probable_copies(&files).await?;
+
+        assert_eq!(stats.total_groups, 1);
+        assert_eq!(stats.total_duplicates, 2);
+        let names: Vec<_> = stats.groups[0].files.iter().map(|f| f.name.to_string()).collect();
+        assert_eq!(names, ["IMG_1234.jpg", "IMG_1234 (1).jpg", "IMG_1234-edited.jpg"]);
+
+        Ok(())
+    }
 }
@@ -0,0 +1,122 @@
+use color_eyre::Result;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+/// Finds directories below `root` that contain no files, directly or in any subdirectory.
+///
+/// Nested empty directories are returned before their parents, so the list can be removed
+/// in order. `root` itself is never included and symbolic links are not followed.
+///
+/// # Errors
+///
+/// Returns an error if `root` cannot be read. Unreadable subdirectories are treated as
+/// non-empty and skipped.
+pub fn find_empty_directories(root: &Path) -> Result<Vec<PathBuf>> {
+    let mut empty = Vec::new();
+    for entry in fs::read_dir(root)? {
+        let entry = entry?;
+        if entry.file_type()?.is_dir() {
+            collect_empty(&entry.path(), &mut empty);
+        }
+    }
+    Ok(empty)
+}
+
+/// Returns whether `dir` holds no files, recording it and its empty subdirectories
+fn collect_empty(dir: &Path, empty: &mut Vec<PathBuf>) -> bool {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) => {
+            warn!("Skipping unreadable directory {}: {}", dir.display(), e);
+            return false;
+        }
+    };
+
+    let mut is_empty = true;
+    for entry in entries {
+        let Ok(entry) = entry else {
+            is_empty = false;
+            continue;
+        };
+        let is_dir = entry.file_type().is_ok_and(|t| t.is_dir());
+        if !is_dir || !collect_empty(&entry.path(), empty) {
+            is_empty = false;
+        }
+    }
+
+    if is_empty {
+        empty.push(dir.to_path_buf());
+    }
+    is_empty
+}
+
+/// Removes the given directories in order, skipping any that are no longer empty.
+///
+/// Returns the directories that were actually removed.
+#[must_use]
+pub fn remove_empty_directories(dirs: &[PathBuf]) -> Vec<PathBuf> {
+    dirs.iter()
+        .filter(|dir| match fs::remove_dir(dir) {
+            Ok(()) => true,
+            Err(e) => {
+                warn!("Failed to remove empty directory {}: {}", dir.display(), e);
+                false
+            }
+        })
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    #![allow(clippy::panic_in_result_fn)]
+    #![allow(clippy::unwrap_in_result)]
+
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_find_empty_directories_nested_first() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("2023/01/raw"))?;
+        fs::create_dir_all(root.join("2023/02"))?;
+        fs::create_dir_all(root.join("photos"))?;
+        fs::write(root.join("photos/a.jpg"), b"data")?;
+        fs::create_dir_all(root.join("photos/empty"))?;
+
+        let empty = find_empty_directories(root)?;
+
+        assert_eq!(empty.len(), 5);
+        for dir in ["2023", "2023/01", "2023/01/raw", "2023/02", "photos/empty"] {
+            assert!(empty.contains(&root.join(dir)), "{dir} should be reported");
+        }
+        assert!(!empty.contains(&root.join("photos")));
+
+        let position = |dir: &str| empty.iter().position(|d| d == &root.join(dir)).unwrap();
+        assert!(position("2023/01/raw") < position("2023/01"));
+        assert!(position("2023/01") < position("2023"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_remove_empty_directories_skips_non_empty() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("a/b"))?;
+        fs::create_dir_all(root.join("c"))?;
+
+        let empty = find_empty_directories(root)?;
+        // A file appears after the preview was taken
+        fs::write(root.join("c/new.jpg"), b"data")?;
+
+        let removed = remove_empty_directories(&empty);
+
+        assert_eq!(removed, vec![root.join("a/b"), root.join("a")]);
+        assert!(!root.join("a").exists());
+        assert!(root.join("c/new.jpg").exists());
+        Ok(())
+    }
+}
@@ -0,0 +1,641 @@
+use chrono::{FixedOffset, NaiveDateTime};
+use color_eyre::eyre::{Result, bail};
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+use visualvault_models::{CaptureInfo, ExifDates};
+
+use crate::heif::{is_heif, read_heif_exif};
+
+/// EXIF blocks live near the start of JPEG and TIFF-based raw files
+const MAX_HEADER_SIZE: u64 = 1024 * 1024;
+
+const TAG_MAKE: u16 = 0x010f;
+const TAG_MODEL: u16 = 0x0110;
+const TAG_DATE_TIME: u16 = 0x0132;
+const TAG_EXIF_IFD: u16 = 0x8769;
+const TAG_GPS_IFD: u16 = 0x8825;
+const TAG_GPS_LATITUDE: u16 = 0x0002;
+const TAG_DATE_TIME_ORIGINAL: u16 = 0x9003;
+const TAG_DATE_TIME_DIGITIZED: u16 = 0x9004;
+const TAG_OFFSET_TIME_ORIGINAL: u16 = 0x9011;
+const TAG_MAKER_NOTE: u16 = 0x927c;
+const TAG_EXPOSURE_TIME: u16 = 0x829a;
+const TAG_F_NUMBER: u16 = 0x829d;
+const TAG_ISO: u16 = 0x8827;
+const TAG_FOCAL_LENGTH: u16 = 0x920a;
+const TAG_LENS_MODEL: u16 = 0xa434;
+const TAG_THUMBNAIL_OFFSET: u16 = 0x0201;
+const TAG_THUMBNAIL_LENGTH: u16 = 0x0202;
+const TYPE_ASCII: u16 = 2;
+const TYPE_SHORT: u16 = 3;
+const TYPE_LONG: u16 = 4;
+const TYPE_RATIONAL: u16 = 5;
+
+/// Format of EXIF dates, which are always 19 characters followed by a NUL
+const EXIF_DATE_FORMAT: &str = "%Y:%m:%d %H:%M:%S";
+
+/// Apple maker notes start with this signature, followed by a big-endian IFD at offset 14
+const APPLE_MAKER_NOTE: &[u8] = b"Apple iOS\0";
+/// Apple maker note tag holding the identifier shared by a Live Photo's still and video
+const TAG_APPLE_CONTENT_IDENTIFIER: u16 = 0x0011;
+
+/// Reads the capture, digitization and modification dates from a photo's EXIF data.
+///
+/// JPEG files and TIFF-based raw formats (such as DNG, CR2 and NEF) are supported. Files
+/// without EXIF data yield empty dates.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be opened or read.
+pub fn read_exif_dates(path: &Path) -> Result<ExifDates> {
+    Ok(read_exif_tags(path)?.dates)
+}
+
+/// Reads the camera that took a photo, e.g. "Canon EOS R5", from its EXIF make and model.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be opened or read.
+pub fn read_exif_camera(path: &Path) -> Result<Option<String>> {
+    Ok(read_exif_tags(path)?.capture.camera())
+}
+
+/// Reads the camera, lens and exposure settings a photo was taken with from its EXIF data.
+///
+/// Files without EXIF data yield empty capture information.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be opened or read.
+pub fn read_exif_capture(path: &Path) -> Result<CaptureInfo> {
+    Ok(read_exif_tags(path)?.capture)
+}
+
+/// Reads the JPEG preview embedded in a photo's EXIF data.
+///
+/// Cameras and phones store a small preview next to the main image, which makes it
+/// possible to show formats such as HEIC whose image data cannot be decoded here.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be opened or read.
+pub fn read_exif_thumbnail(path: &Path) -> Result<Option<Vec<u8>>> {
+    Ok(read_exif_tags(path)?.thumbnail)
+}
+
+/// Reads the content identifier Apple devices store in the still image of a Live Photo
+pub(crate) fn read_exif_content_identifier(path: &Path) -> Result<Option<String>> {
+    Ok(read_exif_tags(path)?.content_identifier)
+}
+
+/// Overwrites the capture date in a photo's EXIF data, returning the date it replaced.
+///
+/// The new date takes the place of the old one, so nothing else in the file moves. Only JPEG
+/// and TIFF-based files whose EXIF data already records a capture date are supported.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read or written, or has no capture date to replace.
+pub fn write_exif_taken(path: &Path, taken: NaiveDateTime) -> Result<NaiveDateTime> {
+    let mut file = File::options().read(true).write(true).open(path)?;
+    let mut header = Vec::new();
+    file.by_ref().take(MAX_HEADER_SIZE).read_to_end(&mut header)?;
+
+    let tiff_start = if header.starts_with(&[0xff, 0xd8]) {
+        jpeg_exif_offset(&header)
+    } else if header.starts_with(b"II*\0") || header.starts_with(b"MM\0*") {
+        Some(0)
+    } else {
+        None
+    };
+    let Some(tiff_start) = tiff_start else {
+        bail!("{} has no EXIF data that can be written", path.display());
+    };
+    let Some((offset, previous)) = find_taken_value(&header[tiff_start..]) else {
+        bail!("{} records no capture date to replace", path.display());
+    };
+
+    file.seek(SeekFrom::Start((tiff_start + offset) as u64))?;
+    std::io::Write::write_all(&mut file, taken.format(EXIF_DATE_FORMAT).to_string().as_bytes())?;
+    Ok(previous)
+}
+
+/// Finds where the capture date is stored in a TIFF structure, returning its offset and value
+fn find_taken_value(tiff: &[u8]) -> Option<(usize, NaiveDateTime)> {
+    let reader = TiffReader {
+        data: tiff,
+        little_endian: tiff.starts_with(b"II"),
+    };
+    let mut exif_ifd = None;
+    reader.for_each_entry(reader.u32(4)? as usize, |tag, _, _, value_pos| {
+        if tag == TAG_EXIF_IFD {
+            exif_ifd = reader.u32(value_pos);
+        }
+    });
+
+    let mut found = None;
+    reader.for_each_entry(exif_ifd? as usize, |tag, kind, count, value_pos| {
+        // Dates are 20 bytes with the NUL, so they are always stored at an offset
+        if tag == TAG_DATE_TIME_ORIGINAL && count >= 20 {
+            let date = reader.date(kind, count, value_pos);
+            found = date
+                .zip(reader.u32(value_pos))
+                .map(|(date, offset)| (offset as usize, date));
+        }
+    });
+    found
+}
+
+#[derive(Default)]
+pub(crate) struct ExifTags {
+    pub(crate) dates: ExifDates,
+    pub(crate) capture: CaptureInfo,
+    /// Whether the GPS data records a latitude
+    pub(crate) has_location: bool,
+    content_identifier: Option<String>,
+    thumbnail: Option<Vec<u8>>,
+}
+
+pub(crate) fn read_exif_tags(path: &Path) -> Result<ExifTags> {
+    let mut file = File::open(path)?;
+    let mut header = Vec::new();
+    file.by_ref().take(MAX_HEADER_SIZE).read_to_end(&mut header)?;
+
+    if is_heif(&header) {
+        return Ok(read_heif_exif(&mut file)?
+            .map(|tiff| parse_tiff(&tiff))
+            .unwrap_or_default());
+    }
+
+    let tiff = if header.starts_with(&[0xff, 0xd8]) {
+        find_jpeg_exif(&header)
+    } else if header.starts_with(b"II*\0") || header.starts_with(b"MM\0*") {
+        Some(header.as_slice())
+    } else {
+        None
+    };
+
+    Ok(tiff.map(parse_tiff).unwrap_or_default())
+}
+
+/// Returns the TIFF structure inside the JPEG's APP1 Exif segment
+fn find_jpeg_exif(data: &[u8]) -> Option<&[u8]> {
+    data.get(jpeg_exif_offset(data)?..)
+}
+
+/// Returns where the TIFF structure inside the JPEG's APP1 Exif segment starts
+fn jpeg_exif_offset(data: &[u8]) -> Option<usize> {
+    let mut pos = 2;
+    while pos + 4 <= data.len() {
+        if data[pos] != 0xff {
+            return None;
+        }
+        let marker = data[pos + 1];
+        // Start of scan: image data follows, no more metadata segments
+        if marker == 0xda {
+            return None;
+        }
+        let length = usize::from(u16::from_be_bytes([data[pos + 2], data[pos + 3]]));
+        let segment = data.get(pos + 4..pos + 2 + length)?;
+        if marker == 0xe1 && segment.starts_with(b"Exif\0\0") {
+            return Some(pos + 4 + 6);
+        }
+        pos += 2 + length;
+    }
+    None
+}
+
+fn parse_tiff(tiff: &[u8]) -> ExifTags {
+    let reader = TiffReader {
+        data: tiff,
+        little_endian: tiff.starts_with(b"II"),
+    };
+    let mut tags = ExifTags::default();
+
+    let Some(ifd0) = reader.u32(4) else {
+        return tags;
+    };
+
+    let (mut exif_ifd, mut gps_ifd) = (None, None);
+    reader.for_each_entry(ifd0 as usize, |tag, kind, count, value_pos| match tag {
+        TAG_MAKE => tags.capture.make = reader.ascii(kind, count, value_pos),
+        TAG_MODEL => tags.capture.model = reader.ascii(kind, count, value_pos),
+        TAG_DATE_TIME => tags.dates.modified = reader.date(kind, count, value_pos),
+        TAG_EXIF_IFD => exif_ifd = reader.u32(value_pos),
+        TAG_GPS_IFD => gps_ifd = reader.u32(value_pos),
+        _ => {}
+    });
+
+    if let Some(gps_ifd) = gps_ifd {
+        reader.for_each_entry(gps_ifd as usize, |tag, _, _, _| {
+            tags.has_location |= tag == TAG_GPS_LATITUDE;
+        });
+    }
+
+    if let Some(exif_ifd) = exif_ifd {
+        let mut maker_note = None;
+        reader.for_each_entry(exif_ifd as usize, |tag, kind, count, value_pos| match tag {
+            TAG_DATE_TIME_ORIGINAL => tags.dates.taken = reader.date(kind, count, value_pos),
+            TAG_DATE_TIME_DIGITIZED => tags.dates.digitized = reader.date(kind, count, value_pos),
+            TAG_OFFSET_TIME_ORIGINAL => {
+                tags.dates.offset = reader
+                    .ascii(kind, count, value_pos)
+                    .and_then(|offset| offset.parse::<FixedOffset>().ok());
+            }
+            TAG_MAKER_NOTE => maker_note = reader.bytes(count, value_pos),
+            TAG_EXPOSURE_TIME => tags.capture.shutter_speed = reader.rational(kind, value_pos),
+            TAG_F_NUMBER => tags.capture.aperture = reader.rational(kind, value_pos),
+            TAG_ISO => tags.capture.iso = reader.unsigned(kind, value_pos),
+            TAG_FOCAL_LENGTH => tags.capture.focal_length = reader.rational(kind, value_pos),
+            TAG_LENS_MODEL => tags.capture.lens = reader.ascii(kind, count, value_pos),
+            _ => {}
+        });
+        tags.content_identifier = maker_note.and_then(parse_apple_maker_note);
+    }
+
+    // IFD1 follows IFD0 and describes the embedded preview
+    if let Some(ifd1) = reader.next_ifd(ifd0 as usize).filter(|&ifd1| ifd1 != 0) {
+        let (mut offset, mut length) = (None, None);
+        reader.for_each_entry(ifd1 as usize, |tag, _, _, value_pos| match tag {
+            TAG_THUMBNAIL_OFFSET => offset = reader.u32(value_pos),
+            TAG_THUMBNAIL_LENGTH => length = reader.u32(value_pos),
+            _ => {}
+        });
+        if let (Some(offset), Some(length)) = (offset, length) {
+            let (offset, length) = (offset as usize, length as usize);
+            tags.thumbnail = reader
+                .data
+                .get(offset..offset.saturating_add(length))
+                .map(<[u8]>::to_vec);
+        }
+    }
+
+    tags
+}
+
+/// Reads the Live Photo content identifier from an Apple maker note.
+///
+/// Offsets inside the note are relative to its start.
+fn parse_apple_maker_note(note: &[u8]) -> Option<String> {
+    if !note.starts_with(APPLE_MAKER_NOTE) {
+        return None;
+    }
+    let reader = TiffReader {
+        data: note,
+        little_endian: note.get(12..14)? == b"II",
+    };
+
+    let mut identifier = None;
+    reader.for_each_entry(14, |tag, kind, count, value_pos| {
+        if tag == TAG_APPLE_CONTENT_IDENTIFIER {
+            identifier = reader.ascii(kind, count, value_pos);
+        }
+    });
+    identifier
+}
+
+struct TiffReader<'a> {
+    data: &'a [u8],
+    little_endian: bool,
+}
+
+impl TiffReader<'_> {
+    fn u16(&self, pos: usize) -> Option<u16> {
+        let bytes: [u8; 2] = self.data.get(pos..pos + 2)?.try_into().ok()?;
+        Some(if self.little_endian {
+            u16::from_le_bytes(bytes)
+        } else {
+            u16::from_be_bytes(bytes)
+        })
+    }
+
+    fn u32(&self, pos: usize) -> Option<u32> {
+        let bytes: [u8; 4] = self.data.get(pos..pos + 4)?.try_into().ok()?;
+        Some(if self.little_endian {
+            u32::from_le_bytes(bytes)
+        } else {
+            u32::from_be_bytes(bytes)
+        })
+    }
+
+    /// Calls `visit` with the tag, type, count and value position of each IFD entry
+    fn for_each_entry(&self, ifd: usize, mut visit: impl FnMut(u16, u16, u32, usize)) {
+        let Some(count) = self.u16(ifd) else {
+            return;
+        };
+        for i in 0..usize::from(count) {
+            let entry = ifd + 2 + i * 12;
+            let (Some(tag), Some(kind), Some(values)) = (self.u16(entry), self.u16(entry + 2), self.u32(entry + 4))
+            else {
+                return;
+            };
+            visit(tag, kind, values, entry + 8);
+        }
+    }
+
+    /// Returns the offset of the IFD following the one at `ifd`
+    fn next_ifd(&self, ifd: usize) -> Option<u32> {
+        let count = usize::from(self.u16(ifd)?);
+        self.u32(ifd + 2 + count * 12)
+    }
+
+    /// Returns `count` bytes of a value, which is stored at an offset if longer than four bytes
+    fn bytes(&self, count: u32, value_pos: usize) -> Option<&[u8]> {
+        let count = count as usize;
+        let start = if count > 4 {
+            self.u32(value_pos)? as usize
+        } else {
+            value_pos
+        };
+        self.data.get(start..start.checked_add(count)?)
+    }
+
+    /// Reads a trimmed ASCII value, skipping empty ones
+    fn ascii(&self, kind: u16, count: u32, value_pos: usize) -> Option<String> {
+        if kind != TYPE_ASCII {
+            return None;
+        }
+        let raw = self.bytes(count, value_pos)?;
+        let text = std::str::from_utf8(raw).ok()?.trim_end_matches('\0').trim();
+        (!text.is_empty()).then(|| text.to_string())
+    }
+
+    /// Reads the first value of an unsigned SHORT or LONG entry
+    fn unsigned(&self, kind: u16, value_pos: usize) -> Option<u32> {
+        match kind {
+            TYPE_SHORT => self.u16(value_pos).map(u32::from),
+            TYPE_LONG => self.u32(value_pos),
+            _ => None,
+        }
+    }
+
+    /// Reads the first value of a RATIONAL entry, which is always stored at an offset
+    fn rational(&self, kind: u16, value_pos: usize) -> Option<f64> {
+        if kind != TYPE_RATIONAL {
+            return None;
+        }
+        let offset = self.u32(value_pos)? as usize;
+        let (numerator, denominator) = (self.u32(offset)?, self.u32(offset + 4)?);
+        (denominator != 0).then(|| f64::from(numerator) / f64::from(denominator))
+    }
+
+    /// Reads an EXIF date such as "2024:03:15 10:30:00"
+    fn date(&self, kind: u16, count: u32, value_pos: usize) -> Option<NaiveDateTime> {
+        let text = self.ascii(kind, count, value_pos)?;
+        NaiveDateTime::parse_from_str(&text, EXIF_DATE_FORMAT).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    #![allow(clippy::panic_in_result_fn)]
+    #![allow(clippy::unwrap_in_result)]
+    #![allow(clippy::cast_possible_truncation)]
+
+    use super::*;
+    use chrono::NaiveDate;
+    use tempfile::TempDir;
+
+    /// Builds a little-endian TIFF block with make, model and `DateTime` in IFD0 and both EXIF dates
+    fn tiff_block() -> Vec<u8> {
+        let dates = [
+            b"2024:03:20 08:00:00\0",
+            b"2024:03:15 10:30:00\0",
+            b"2024:03:15 10:30:05\0",
+        ];
+        let make = b"Canon\0";
+        let model = b"Canon EOS R5\0";
+        let offset = b"+09:00\0";
+
+        let mut tiff = b"II*\0".to_vec();
+        tiff.extend_from_slice(&8u32.to_le_bytes());
+
+        let entry = |tag: u16, kind: u16, count: u32, value: u32| -> Vec<u8> {
+            let mut bytes = tag.to_le_bytes().to_vec();
+            bytes.extend_from_slice(&kind.to_le_bytes());
+            bytes.extend_from_slice(&count.to_le_bytes());
+            bytes.extend_from_slice(&value.to_le_bytes());
+            bytes
+        };
+
+        // IFD0 at 8: four entries and the next IFD offset, followed by the Exif IFD
+        let exif_ifd = 8 + 2 + 4 * 12 + 4;
+        let data_start = exif_ifd + 2 + 3 * 12 + 4;
+        tiff.extend_from_slice(&4u16.to_le_bytes());
+        tiff.extend(entry(TAG_MAKE, TYPE_ASCII, make.len() as u32, data_start + 60));
+        tiff.extend(entry(TAG_MODEL, TYPE_ASCII, model.len() as u32, data_start + 66));
+        tiff.extend(entry(TAG_DATE_TIME, TYPE_ASCII, 20, data_start));
+        tiff.extend(entry(TAG_EXIF_IFD, 4, 1, exif_ifd));
+        tiff.extend_from_slice(&0u32.to_le_bytes());
+
+        tiff.extend_from_slice(&3u16.to_le_bytes());
+        tiff.extend(entry(TAG_DATE_TIME_ORIGINAL, TYPE_ASCII, 20, data_start + 20));
+        tiff.extend(entry(TAG_DATE_TIME_DIGITIZED, TYPE_ASCII, 20, data_start + 40));
+        tiff.extend(entry(
+            TAG_OFFSET_TIME_ORIGINAL,
+            TYPE_ASCII,
+            offset.len() as u32,
+            data_start + 79,
+        ));
+        tiff.extend_from_slice(&0u32.to_le_bytes());
+
+        for date in dates {
+            tiff.extend_from_slice(date);
+        }
+        tiff.extend_from_slice(make);
+        tiff.extend_from_slice(model);
+        tiff.extend_from_slice(offset);
+        tiff
+    }
+
+    fn at(day: u32, hour: u32, minute: u32, second: u32) -> Option<NaiveDateTime> {
+        NaiveDate::from_ymd_opt(2024, 3, day).and_then(|d| d.and_hms_opt(hour, minute, second))
+    }
+
+    /// Wraps a TIFF block in a minimal JPEG with an Exif segment
+    fn jpeg_block(tiff: Vec<u8>) -> Vec<u8> {
+        let mut jpeg = vec![0xff, 0xd8];
+        // An unrelated APP0 segment before the Exif one
+        jpeg.extend_from_slice(&[0xff, 0xe0, 0, 4, 0, 0]);
+        jpeg.extend_from_slice(&[0xff, 0xe1]);
+        jpeg.extend_from_slice(&((tiff.len() + 8) as u16).to_be_bytes());
+        jpeg.extend_from_slice(b"Exif\0\0");
+        jpeg.extend(tiff);
+        jpeg.extend_from_slice(&[0xff, 0xda, 0, 2, 0xff, 0xd9]);
+        jpeg
+    }
+
+    #[test]
+    fn test_jpeg_exif_dates() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let path = temp_dir.path().join("photo.jpg");
+        std::fs::write(&path, jpeg_block(tiff_block()))?;
+
+        let dates = read_exif_dates(&path)?;
+        assert_eq!(dates.taken, at(15, 10, 30, 0));
+        assert_eq!(dates.digitized, at(15, 10, 30, 5));
+        assert_eq!(dates.modified, at(20, 8, 0, 0));
+        assert_eq!(dates.offset, FixedOffset::east_opt(9 * 3600));
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_exif_taken() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let path = temp_dir.path().join("photo.jpg");
+        let original = jpeg_block(tiff_block());
+        std::fs::write(&path, &original)?;
+
+        let new_date = at(1, 12, 0, 0).unwrap();
+        assert_eq!(write_exif_taken(&path, new_date)?, at(15, 10, 30, 0).unwrap());
+        let dates = read_exif_dates(&path)?;
+        assert_eq!(dates.taken, Some(new_date));
+        assert_eq!(dates.digitized, at(15, 10, 30, 5));
+        assert_eq!(std::fs::metadata(&path)?.len(), original.len() as u64);
+
+        let other = temp_dir.path().join("other.png");
+        std::fs::write(&other, b"\x89PNG\r\n\x1a\n")?;
+        assert!(write_exif_taken(&other, new_date).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_tiff_based_raw() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let path = temp_dir.path().join("photo.dng");
+        std::fs::write(&path, tiff_block())?;
+
+        assert_eq!(read_exif_dates(&path)?.taken, at(15, 10, 30, 0));
+        assert_eq!(read_exif_camera(&path)?.as_deref(), Some("Canon EOS R5"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_embedded_thumbnail() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let preview = [0xff, 0xd8, 1, 2, 3, 0xff, 0xd9];
+
+        // An empty IFD0 at 8 whose next IFD describes the preview
+        let ifd1: u32 = 8 + 2 + 4;
+        let preview_start = ifd1 + 2 + 2 * 12 + 4;
+        let mut tiff = b"MM\0*".to_vec();
+        tiff.extend_from_slice(&8u32.to_be_bytes());
+        tiff.extend_from_slice(&0u16.to_be_bytes());
+        tiff.extend_from_slice(&ifd1.to_be_bytes());
+        tiff.extend_from_slice(&2u16.to_be_bytes());
+        for (tag, value) in [(TAG_THUMBNAIL_OFFSET, preview_start), (TAG_THUMBNAIL_LENGTH, 7)] {
+            tiff.extend_from_slice(&tag.to_be_bytes());
+            tiff.extend_from_slice(&4u16.to_be_bytes());
+            tiff.extend_from_slice(&1u32.to_be_bytes());
+            tiff.extend_from_slice(&value.to_be_bytes());
+        }
+        tiff.extend_from_slice(&0u32.to_be_bytes());
+        tiff.extend_from_slice(&preview);
+
+        let path = temp_dir.path().join("photo.heic");
+        std::fs::write(&path, crate::heif::tests::heic_file(&tiff))?;
+
+        assert_eq!(read_exif_thumbnail(&path)?.as_deref(), Some(preview.as_slice()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_capture_settings() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let make = b"Canon\0";
+        let model = b"Canon EOS R5\0";
+        let lens = b"RF50mm F1.2 L USM\0";
+
+        let entry = |tag: u16, kind: u16, count: u32, value: u32| -> Vec<u8> {
+            let mut bytes = tag.to_le_bytes().to_vec();
+            bytes.extend_from_slice(&kind.to_le_bytes());
+            bytes.extend_from_slice(&count.to_le_bytes());
+            bytes.extend_from_slice(&value.to_le_bytes());
+            bytes
+        };
+
+        // IFD0 at 8 with three entries, then the Exif IFD with five, then the values
+        let exif_ifd = 8 + 2 + 3 * 12 + 4;
+        let data_start = exif_ifd + 2 + 5 * 12 + 4;
+        let mut tiff = b"II*\0".to_vec();
+        tiff.extend_from_slice(&8u32.to_le_bytes());
+        tiff.extend_from_slice(&3u16.to_le_bytes());
+        tiff.extend(entry(TAG_MAKE, TYPE_ASCII, make.len() as u32, data_start + 24));
+        tiff.extend(entry(TAG_MODEL, TYPE_ASCII, model.len() as u32, data_start + 30));
+        tiff.extend(entry(TAG_EXIF_IFD, TYPE_LONG, 1, exif_ifd));
+        tiff.extend_from_slice(&0u32.to_le_bytes());
+
+        tiff.extend_from_slice(&5u16.to_le_bytes());
+        tiff.extend(entry(TAG_EXPOSURE_TIME, TYPE_RATIONAL, 1, data_start));
+        tiff.extend(entry(TAG_F_NUMBER, TYPE_RATIONAL, 1, data_start + 8));
+        tiff.extend(entry(TAG_ISO, TYPE_SHORT, 1, 400));
+        tiff.extend(entry(TAG_FOCAL_LENGTH, TYPE_RATIONAL, 1, data_start + 16));
+        tiff.extend(entry(TAG_LENS_MODEL, TYPE_ASCII, lens.len() as u32, data_start + 43));
+        tiff.extend_from_slice(&0u32.to_le_bytes());
+
+        for value in [1u32, 250, 28, 10, 50, 1] {
+            tiff.extend_from_slice(&value.to_le_bytes());
+        }
+        tiff.extend_from_slice(make);
+        tiff.extend_from_slice(model);
+        tiff.extend_from_slice(lens);
+
+        let path = temp_dir.path().join("photo.dng");
+        std::fs::write(&path, tiff)?;
+
+        let capture = read_exif_capture(&path)?;
+        assert_eq!(capture.camera().as_deref(), Some("Canon EOS R5"));
+        assert_eq!(capture.lens.as_deref(), Some("RF50mm F1.2 L USM"));
+        assert_eq!(capture.iso, Some(400));
+        assert_eq!(capture.aperture_label().as_deref(), Some("f/2.8"));
+        assert_eq!(capture.shutter_speed_label().as_deref(), Some("1/250 s"));
+        assert_eq!(capture.focal_length_label().as_deref(), Some("50 mm"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_gps_location() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let entry = |tag: u16, kind: u16, count: u32, value: u32| -> Vec<u8> {
+            let mut bytes = tag.to_le_bytes().to_vec();
+            bytes.extend_from_slice(&kind.to_le_bytes());
+            bytes.extend_from_slice(&count.to_le_bytes());
+            bytes.extend_from_slice(&value.to_le_bytes());
+            bytes
+        };
+
+        // IFD0 at 8 pointing at a GPS IFD with a latitude reference and the latitude itself
+        let gps_ifd = 8 + 2 + 12 + 4;
+        let mut tiff = b"II*\0".to_vec();
+        tiff.extend_from_slice(&8u32.to_le_bytes());
+        tiff.extend_from_slice(&1u16.to_le_bytes());
+        tiff.extend(entry(TAG_GPS_IFD, TYPE_LONG, 1, gps_ifd));
+        tiff.extend_from_slice(&0u32.to_le_bytes());
+        tiff.extend_from_slice(&2u16.to_le_bytes());
+        tiff.extend(entry(0x0001, TYPE_ASCII, 2, u32::from_le_bytes(*b"N\0\0\0")));
+        tiff.extend(entry(TAG_GPS_LATITUDE, TYPE_RATIONAL, 3, 0));
+        tiff.extend_from_slice(&0u32.to_le_bytes());
+
+        let path = temp_dir.path().join("photo.dng");
+        std::fs::write(&path, &tiff)?;
+        assert!(read_exif_tags(&path)?.has_location);
+
+        std::fs::write(&path, tiff_block())?;
+        assert!(!read_exif_tags(&path)?.has_location);
+        Ok(())
+    }
+
+    #[test]
+    fn test_file_without_exif() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let path = temp_dir.path().join("photo.png");
+        std::fs::write(&path, b"\x89PNG\r\n\x1a\n")?;
+
+        assert!(read_exif_dates(&path)?.is_empty());
+        assert!(read_exif_camera(&path)?.is_none());
+        assert!(read_exif_capture(&path)?.is_empty());
+        Ok(())
+    }
+}
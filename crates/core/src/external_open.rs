@@ -0,0 +1,131 @@
+use std::ffi::OsString;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use color_eyre::eyre::{Result, eyre};
+use tracing::{debug, info};
+
+/// Opens `path` with the application the system associates with it.
+///
+/// Uses `xdg-open` on Linux and the BSDs, `open` on macOS and Explorer on Windows. The
+/// opener runs in the background; only a failure to start it is reported.
+///
+/// # Errors
+///
+/// Returns an error if the platform has no known opener or the opener cannot be started.
+pub fn open_with_default_app(path: &Path) -> Result<()> {
+    let (program, args) = open_command(std::env::consts::OS, path)
+        .ok_or_else(|| eyre!("Opening files is not supported on {}", std::env::consts::OS))?;
+    spawn(program, &args)?;
+    info!("Opened {} with {}", path.display(), program);
+    Ok(())
+}
+
+/// Shows `path` in the system file manager, selecting it where the platform allows.
+///
+/// macOS and Windows select the file in Finder and Explorer. Other platforms have no common
+/// way to select a file, so the folder containing it is opened instead.
+///
+/// # Errors
+///
+/// Returns an error if the platform has no known file manager or it cannot be started.
+pub fn reveal_in_file_manager(path: &Path) -> Result<()> {
+    let (program, args) = reveal_command(std::env::consts::OS, path)
+        .ok_or_else(|| eyre!("Revealing files is not supported on {}", std::env::consts::OS))?;
+    spawn(program, &args)?;
+    info!("Revealed {} with {}", path.display(), program);
+    Ok(())
+}
+
+fn spawn(program: &'static str, args: &[OsString]) -> Result<()> {
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| eyre!("Failed to run {program}: {e}"))?;
+
+    // Reap the opener once it exits so it does not linger as a zombie process
+    std::thread::spawn(move || match child.wait() {
+        Ok(status) if !status.success() => debug!("{} exited with {}", program, status),
+        Err(e) => debug!("Waiting for {} failed: {}", program, e),
+        Ok(_) => {}
+    });
+    Ok(())
+}
+
+/// Program and arguments that open `path` with its default application on `os`
+fn open_command(os: &str, path: &Path) -> Option<(&'static str, Vec<OsString>)> {
+    let program = match os {
+        "linux" | "freebsd" | "openbsd" | "netbsd" | "dragonfly" => "xdg-open",
+        "macos" => "open",
+        "windows" => "explorer",
+        _ => return None,
+    };
+    Some((program, vec![path.as_os_str().to_owned()]))
+}
+
+/// Program and arguments that show `path` in the file manager on `os`
+fn reveal_command(os: &str, path: &Path) -> Option<(&'static str, Vec<OsString>)> {
+    match os {
+        "macos" => Some(("open", vec!["-R".into(), path.as_os_str().to_owned()])),
+        "windows" => {
+            let mut select = OsString::from("/select,");
+            select.push(path.as_os_str());
+            Some(("explorer", vec![select]))
+        }
+        _ => {
+            let folder = path
+                .parent()
+                .filter(|parent| !parent.as_os_str().is_empty())
+                .unwrap_or_else(|| Path::new("."));
+            open_command(os, folder)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_open_uses_platform_opener() {
+        let path = Path::new("/photos/IMG_0001.jpg");
+        let (program, args) = open_command("linux", path).unwrap_or_else(|| unreachable!());
+        assert_eq!(program, "xdg-open");
+        assert_eq!(args, vec![OsString::from("/photos/IMG_0001.jpg")]);
+
+        assert_eq!(open_command("macos", path).map(|(program, _)| program), Some("open"));
+        assert_eq!(
+            open_command("windows", path).map(|(program, _)| program),
+            Some("explorer")
+        );
+        assert!(open_command("plan9", path).is_none());
+    }
+
+    #[test]
+    fn test_reveal_selects_file_or_opens_its_folder() {
+        let path = Path::new("/photos/IMG_0001.jpg");
+        assert_eq!(
+            reveal_command("macos", path),
+            Some((
+                "open",
+                vec![OsString::from("-R"), OsString::from("/photos/IMG_0001.jpg")]
+            ))
+        );
+        assert_eq!(
+            reveal_command("windows", path),
+            Some(("explorer", vec![OsString::from("/select,/photos/IMG_0001.jpg")]))
+        );
+        assert_eq!(
+            reveal_command("linux", path),
+            Some(("xdg-open", vec![OsString::from("/photos")]))
+        );
+        assert_eq!(
+            reveal_command("linux", Path::new("IMG_0001.jpg")),
+            Some(("xdg-open", vec![OsString::from(".")]))
+        );
+        assert!(reveal_command("plan9", path).is_none());
+    }
+}
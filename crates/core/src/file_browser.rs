@@ -0,0 +1,101 @@
+use color_eyre::Result;
+use std::fs;
+use std::path::{Path, PathBuf};
+use visualvault_models::BrowserEntry;
+
+/// Lists the folders and files directly inside `dir`, folders first, each sorted by name
+/// without regard to case. Hidden entries are left out.
+///
+/// # Errors
+///
+/// Returns an error if `dir` cannot be read. Entries that cannot be inspected are skipped.
+pub fn list_folder(dir: &Path) -> Result<Vec<BrowserEntry>> {
+    let mut entries: Vec<BrowserEntry> = fs::read_dir(dir)?
+        .filter_map(std::result::Result::ok)
+        .filter(|entry| !entry.file_name().to_string_lossy().starts_with('.'))
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok()?;
+            Some(BrowserEntry {
+                path: entry.path(),
+                is_dir: metadata.is_dir(),
+                size: if metadata.is_dir() { 0 } else { metadata.len() },
+            })
+        })
+        .collect();
+
+    entries.sort_by_cached_key(|entry| (!entry.is_dir, entry.name().to_lowercase()));
+    Ok(entries)
+}
+
+/// Lists the folders directly inside `dir`, sorted by name without regard to case.
+/// Folders starting with a dot are only listed with `include_hidden`.
+///
+/// # Errors
+///
+/// Returns an error if `dir` cannot be read.
+pub fn list_subfolders(dir: &Path, include_hidden: bool) -> Result<Vec<PathBuf>> {
+    let mut folders: Vec<PathBuf> = fs::read_dir(dir)?
+        .filter_map(std::result::Result::ok)
+        .filter(|entry| include_hidden || !entry.file_name().to_string_lossy().starts_with('.'))
+        .filter(|entry| entry.path().is_dir())
+        .map(|entry| entry.path())
+        .collect();
+
+    folders.sort_by_cached_key(|folder| {
+        folder
+            .file_name()
+            .map(|name| name.to_string_lossy().to_lowercase())
+            .unwrap_or_default()
+    });
+    Ok(folders)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    #![allow(clippy::panic_in_result_fn)]
+    #![allow(clippy::unwrap_in_result)]
+
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_list_folder_puts_folders_first() -> Result<()> {
+        let temp = TempDir::new()?;
+        fs::write(temp.path().join("b.jpg"), b"12345")?;
+        fs::write(temp.path().join("A.png"), b"1")?;
+        fs::write(temp.path().join(".hidden"), b"")?;
+        fs::create_dir(temp.path().join("zoo"))?;
+        fs::create_dir(temp.path().join("2024"))?;
+
+        let entries = list_folder(temp.path())?;
+        let names: Vec<String> = entries.iter().map(BrowserEntry::name).collect();
+        assert_eq!(names, vec!["2024", "zoo", "A.png", "b.jpg"]);
+        assert!(entries[0].is_dir);
+        assert_eq!(entries[3].size, 5);
+        assert!(list_folder(&temp.path().join("missing")).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_list_subfolders_hides_dot_folders() -> Result<()> {
+        let temp = TempDir::new()?;
+        fs::create_dir(temp.path().join("Photos"))?;
+        fs::create_dir(temp.path().join("archive"))?;
+        fs::create_dir(temp.path().join(".cache"))?;
+        fs::write(temp.path().join("notes.txt"), b"")?;
+
+        let names = |folders: Vec<PathBuf>| -> Vec<String> {
+            folders
+                .iter()
+                .map(|folder| folder.file_name().unwrap().to_string_lossy().to_string())
+                .collect()
+        };
+        assert_eq!(names(list_subfolders(temp.path(), false)?), vec!["archive", "Photos"]);
+        assert_eq!(
+            names(list_subfolders(temp.path(), true)?),
+            vec![".cache", "archive", "Photos"]
+        );
+        Ok(())
+    }
+}
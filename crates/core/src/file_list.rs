@@ -0,0 +1,848 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use chrono::Datelike;
+use color_eyre::eyre::Result;
+use tracing::{info, warn};
+use visualvault_models::{FileSort, FileType, MediaFile};
+
+use crate::database_cache::FileQuery;
+use crate::file_manager::FileManager;
+use crate::file_store::FileStore;
+use crate::undo_manager::{MoveOperation, TimestampOperation};
+
+/// Rows kept loaded around the visible part of a paged list
+const WINDOW_SIZE: usize = 1000;
+/// Rows that stay loaded before and after the first visible row before the window moves
+const WINDOW_MARGIN: usize = 200;
+/// Most search results read from a paged list
+pub const PAGED_SEARCH_LIMIT: usize = 10_000;
+
+/// Files and total size per year the files were created
+pub type YearTotals = BTreeMap<i32, (usize, u64)>;
+
+/// Source of [`FileList::version`], shared by all lists so a new list never reuses one
+static NEXT_VERSION: AtomicU64 = AtomicU64::new(1);
+
+fn next_version() -> u64 {
+    NEXT_VERSION.fetch_add(1, Ordering::Relaxed)
+}
+
+/// The scanned files shown by the app.
+///
+/// A scan adds its files as it finds them. While they fit the memory budget they stay in
+/// memory; once they outgrow it they move to a [`FileStore`], which takes every later file, and
+/// only a window of rows around the visible ones is kept, together with the audio files and
+/// per-year totals the dashboard shows in full.
+#[derive(Debug)]
+pub struct FileList {
+    storage: Storage,
+    version: u64,
+    cache_scope: Option<FileQuery>,
+    budget: Option<Budget>,
+}
+
+/// Where a list kept in memory moves once it outgrows its memory budget
+#[derive(Debug)]
+struct Budget {
+    bytes: u64,
+    used: u64,
+    store_path: PathBuf,
+}
+
+#[derive(Debug)]
+enum Storage {
+    Memory(Vec<Arc<MediaFile>>),
+    Paged(Box<Paged>),
+}
+
+#[derive(Debug)]
+struct Paged {
+    store: FileStore,
+    window: Vec<Arc<MediaFile>>,
+    window_start: usize,
+    audio: Vec<Arc<MediaFile>>,
+    years: YearTotals,
+}
+
+impl Default for FileList {
+    fn default() -> Self {
+        Self::new(Vec::new())
+    }
+}
+
+impl FileList {
+    /// Keeps all `files` in memory
+    #[must_use]
+    pub fn new(files: Vec<Arc<MediaFile>>) -> Self {
+        Self {
+            storage: Storage::Memory(files),
+            version: next_version(),
+            cache_scope: None,
+            budget: None,
+        }
+    }
+
+    /// Starts an empty list that keeps files in memory until they take more than `budget`
+    /// bytes, and pages them from a store at `store_path` from then on. `None` means no limit.
+    #[must_use]
+    pub fn with_budget(budget: Option<u64>, store_path: &Path) -> Self {
+        Self {
+            budget: budget.map(|bytes| Budget {
+                bytes,
+                used: 0,
+                store_path: store_path.to_path_buf(),
+            }),
+            ..Self::default()
+        }
+    }
+
+    /// Changes whenever files are added, removed, moved or reordered, so views holding
+    /// indexes into the list can tell when to rebuild
+    #[must_use]
+    pub const fn version(&self) -> u64 {
+        self.version
+    }
+
+    /// The query on the file cache that selects the same files as the list, so counts, searches
+    /// and duplicate groups can be read from the cache. Moving, renaming, retiming or removing
+    /// files drops it.
+    #[must_use]
+    pub const fn cache_scope(&self) -> Option<&FileQuery> {
+        self.cache_scope.as_ref()
+    }
+
+    /// Sets the query selecting the files of the list in the file cache, see
+    /// [`Self::cache_scope`]
+    pub fn set_cache_scope(&mut self, scope: FileQuery) {
+        self.cache_scope = Some(scope);
+    }
+
+    /// Adds `files` at the end of the list, moving it to its store once it outgrows the memory
+    /// budget.
+    ///
+    /// If the store cannot be created the files stay in memory.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a paged list cannot be written.
+    pub async fn extend(&mut self, files: Vec<Arc<MediaFile>>) -> Result<()> {
+        if files.is_empty() {
+            return Ok(());
+        }
+        self.version = next_version();
+        match &mut self.storage {
+            Storage::Memory(list) => {
+                let added: usize = files.iter().map(|file| file.estimated_memory()).sum();
+                list.extend(files);
+                if let Some(budget) = &mut self.budget {
+                    budget.used += added as u64;
+                    if budget.used > budget.bytes {
+                        self.spill().await?;
+                    }
+                }
+            }
+            Storage::Paged(paged) => {
+                paged.store.append(&files).await?;
+                for file in &files {
+                    add_to_years(&mut paged.years, file);
+                }
+                paged
+                    .audio
+                    .extend(files.into_iter().filter(|file| file.file_type == FileType::Audio));
+                if paged.window.len() < WINDOW_SIZE {
+                    let start = paged.window_start;
+                    self.load_window(start).await?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes the files kept in memory to the store of the budget and pages them from there
+    async fn spill(&mut self) -> Result<()> {
+        let (Some(budget), Storage::Memory(files)) = (self.budget.take(), &mut self.storage) else {
+            return Ok(());
+        };
+        info!(
+            "{} scanned files take about {} bytes, paging them from {}",
+            files.len(),
+            budget.used,
+            budget.store_path.display()
+        );
+        let store = match FileStore::create(&budget.store_path, files).await {
+            Ok(store) => store,
+            Err(e) => {
+                warn!("Cannot page scanned files from disk, keeping them in memory: {}", e);
+                return Ok(());
+            }
+        };
+        let files = std::mem::take(files);
+        let mut years = YearTotals::new();
+        for file in &files {
+            add_to_years(&mut years, file);
+        }
+        let audio = files
+            .into_iter()
+            .filter(|file| file.file_type == FileType::Audio)
+            .collect();
+
+        self.storage = Storage::Paged(Box::new(Paged {
+            store,
+            window: Vec::new(),
+            window_start: 0,
+            audio,
+            years,
+        }));
+        self.load_window(0).await
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        match &self.storage {
+            Storage::Memory(files) => files.len(),
+            Storage::Paged(paged) => paged.store.len(),
+        }
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Whether the files are read from disk a page at a time
+    #[must_use]
+    pub const fn is_paged(&self) -> bool {
+        matches!(self.storage, Storage::Paged(_))
+    }
+
+    /// Returns the file at `index`, or `None` if a paged list has not loaded it yet.
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<&Arc<MediaFile>> {
+        match &self.storage {
+            Storage::Memory(files) => files.get(index),
+            Storage::Paged(paged) => paged.window.get(index.checked_sub(paged.window_start)?),
+        }
+    }
+
+    /// Makes sure a paged list has the rows around `first_visible` loaded.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the page cannot be read.
+    pub async fn ensure_loaded(&mut self, first_visible: usize) -> Result<()> {
+        let Storage::Paged(paged) = &self.storage else {
+            return Ok(());
+        };
+        let window_end = paged.window_start + paged.window.len();
+        let wanted_end = (first_visible + WINDOW_MARGIN).min(paged.store.len());
+        if first_visible >= paged.window_start && wanted_end <= window_end {
+            return Ok(());
+        }
+        self.load_window(first_visible.saturating_sub(WINDOW_MARGIN)).await
+    }
+
+    async fn load_window(&mut self, start: usize) -> Result<()> {
+        if let Storage::Paged(paged) = &mut self.storage {
+            paged.window = paged.store.page(start, WINDOW_SIZE).await?;
+            paged.window_start = start;
+        }
+        Ok(())
+    }
+
+    /// Audio files in list order, which a paged list keeps in memory
+    #[must_use]
+    pub fn audio_files(&self) -> Vec<Arc<MediaFile>> {
+        match &self.storage {
+            Storage::Memory(files) => files
+                .iter()
+                .filter(|file| file.file_type == FileType::Audio)
+                .cloned()
+                .collect(),
+            Storage::Paged(paged) => paged.audio.clone(),
+        }
+    }
+
+    /// Files and total size per year the files were created
+    #[must_use]
+    pub fn year_totals(&self) -> YearTotals {
+        match &self.storage {
+            Storage::Memory(files) => {
+                let mut years = YearTotals::new();
+                for file in files {
+                    add_to_years(&mut years, file);
+                }
+                years
+            }
+            Storage::Paged(paged) => paged.years.clone(),
+        }
+    }
+
+    /// Returns all files. A paged list reads them from disk, so prefer [`Self::filter`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a paged list cannot be read.
+    pub async fn to_vec(&self) -> Result<Vec<Arc<MediaFile>>> {
+        self.filter(|_| true).await
+    }
+
+    /// Returns the files matching `predicate` in list order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a paged list cannot be read.
+    pub async fn filter(&self, predicate: impl Fn(&MediaFile) -> bool) -> Result<Vec<Arc<MediaFile>>> {
+        match &self.storage {
+            Storage::Memory(files) => Ok(files.iter().filter(|file| predicate(file)).cloned().collect()),
+            Storage::Paged(paged) => {
+                let mut matching = Vec::new();
+                paged
+                    .store
+                    .for_each(|file| {
+                        if predicate(&file) {
+                            matching.push(file);
+                        }
+                    })
+                    .await?;
+                Ok(matching)
+            }
+        }
+    }
+
+    /// Calls `visit` with the index and contents of every file in list order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a paged list cannot be read.
+    pub async fn for_each(&self, mut visit: impl FnMut(usize, &MediaFile)) -> Result<()> {
+        match &self.storage {
+            Storage::Memory(files) => {
+                for (index, file) in files.iter().enumerate() {
+                    visit(index, file);
+                }
+                Ok(())
+            }
+            Storage::Paged(paged) => {
+                let mut index = 0;
+                paged
+                    .store
+                    .for_each(|file| {
+                        visit(index, &file);
+                        index += 1;
+                    })
+                    .await
+            }
+        }
+    }
+
+    /// Counts the files matching `predicate`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a paged list cannot be read.
+    pub async fn count(&self, predicate: impl Fn(&MediaFile) -> bool) -> Result<usize> {
+        match &self.storage {
+            Storage::Memory(files) => Ok(files.iter().filter(|file| predicate(file)).count()),
+            Storage::Paged(paged) => {
+                let mut count = 0;
+                paged
+                    .store
+                    .for_each(|file| {
+                        if predicate(&file) {
+                            count += 1;
+                        }
+                    })
+                    .await?;
+                Ok(count)
+            }
+        }
+    }
+
+    /// Returns the files from `start` up to and including `end`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a paged list cannot be read.
+    pub async fn range(&self, start: usize, end: usize) -> Result<Vec<Arc<MediaFile>>> {
+        match &self.storage {
+            Storage::Memory(files) => Ok(files.iter().take(end + 1).skip(start).cloned().collect()),
+            Storage::Paged(paged) => paged.store.page(start, (end + 1).saturating_sub(start)).await,
+        }
+    }
+
+    /// Returns the paths of all files in list order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a paged list cannot be read.
+    pub async fn paths(&self) -> Result<Vec<PathBuf>> {
+        match &self.storage {
+            Storage::Memory(files) => Ok(files.iter().map(|file| file.path.clone()).collect()),
+            Storage::Paged(paged) => paged.store.paths().await,
+        }
+    }
+
+    /// Puts the files in the order of `sort`; a paged list reloads its window from the start.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a paged list cannot be rewritten.
+    pub async fn sort(&mut self, sort: FileSort) -> Result<()> {
+        self.version = next_version();
+        match &mut self.storage {
+            Storage::Memory(files) => {
+                files.sort_by(|a, b| sort.compare(a, b));
+                Ok(())
+            }
+            Storage::Paged(paged) => {
+                paged.store.sort(sort).await?;
+                paged.audio.sort_by(|a, b| sort.compare(a, b));
+                self.load_window(0).await
+            }
+        }
+    }
+
+    /// Returns the index of the file at `path`, or `None` if it is not in the list.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a paged list cannot be read.
+    pub async fn index_of(&self, path: &Path) -> Result<Option<usize>> {
+        match &self.storage {
+            Storage::Memory(files) => Ok(files.iter().position(|file| file.path == path)),
+            Storage::Paged(paged) => paged.store.index_of(path).await,
+        }
+    }
+
+    /// Returns the files whose name or path contains `term`, ignoring case.
+    ///
+    /// A paged list returns at most [`PAGED_SEARCH_LIMIT`] files.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a paged list cannot be read.
+    pub async fn search(&self, term: &str) -> Result<Vec<Arc<MediaFile>>> {
+        let term = term.to_lowercase();
+        match &self.storage {
+            Storage::Memory(files) => Ok(files
+                .iter()
+                .filter(|file| {
+                    file.name.to_lowercase().contains(&term)
+                        || file.path.to_string_lossy().to_lowercase().contains(&term)
+                        || file
+                            .capture()
+                            .is_some_and(|capture| capture.search_text().to_lowercase().contains(&term))
+                })
+                .cloned()
+                .collect()),
+            Storage::Paged(paged) => paged.store.search(&term, PAGED_SEARCH_LIMIT).await,
+        }
+    }
+
+    /// Returns the files at `paths` that are in the list, in the order of `paths`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a paged list cannot be read.
+    pub async fn files_at(&self, paths: &[PathBuf]) -> Result<Vec<Arc<MediaFile>>> {
+        match &self.storage {
+            Storage::Memory(files) => {
+                let wanted: HashSet<&Path> = paths.iter().map(PathBuf::as_path).collect();
+                let found: HashMap<&Path, &Arc<MediaFile>> = files
+                    .iter()
+                    .filter(|file| wanted.contains(file.path.as_path()))
+                    .map(|file| (file.path.as_path(), file))
+                    .collect();
+                Ok(paths
+                    .iter()
+                    .filter_map(|path| found.get(path.as_path()).map(|file| Arc::clone(file)))
+                    .collect())
+            }
+            Storage::Paged(paged) => {
+                let mut found = Vec::with_capacity(paths.len());
+                for path in paths {
+                    found.extend(paged.store.get(path).await?);
+                }
+                Ok(found)
+            }
+        }
+    }
+
+    /// Replaces the file at `index`, for example after its metadata was loaded.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a paged list cannot be updated.
+    pub async fn replace(&mut self, index: usize, file: Arc<MediaFile>) -> Result<()> {
+        match &mut self.storage {
+            Storage::Memory(files) => {
+                if let Some(slot) = files.get_mut(index) {
+                    *slot = file;
+                }
+            }
+            Storage::Paged(paged) => {
+                let Some(slot) = index
+                    .checked_sub(paged.window_start)
+                    .and_then(|offset| paged.window.get_mut(offset))
+                else {
+                    return Ok(());
+                };
+                paged.store.update(&slot.path, &file).await?;
+                *slot = file;
+            }
+        }
+        Ok(())
+    }
+
+    /// Applies `update` to every file whose path is a key of `updates`, along with its value.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a paged list cannot be updated.
+    pub async fn update_each<T>(
+        &mut self,
+        updates: &HashMap<PathBuf, T>,
+        update: impl Fn(&mut MediaFile, &T),
+    ) -> Result<()> {
+        if updates.is_empty() {
+            return Ok(());
+        }
+        self.version = next_version();
+        match &mut self.storage {
+            Storage::Memory(files) => {
+                for file in files.iter_mut() {
+                    if let Some(value) = updates.get(&file.path) {
+                        update(Arc::make_mut(file), value);
+                    }
+                }
+            }
+            Storage::Paged(paged) => {
+                for (path, value) in updates {
+                    if let Some(file) = paged.store.get(path).await? {
+                        let mut file = Arc::unwrap_or_clone(file);
+                        update(&mut file, value);
+                        paged.store.update(path, &file).await?;
+                    }
+                }
+                for file in &mut paged.audio {
+                    if let Some(value) = updates.get(&file.path) {
+                        update(Arc::make_mut(file), value);
+                    }
+                }
+                let start = paged.window_start;
+                self.load_window(start).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Points moved or renamed files at their new location
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a paged list cannot be updated.
+    pub async fn relocate(&mut self, moves: &[MoveOperation]) -> Result<()> {
+        self.version = next_version();
+        self.cache_scope = None;
+        match &mut self.storage {
+            Storage::Memory(files) => {
+                for file in files.iter_mut() {
+                    *file = FileManager::relocated(file, moves);
+                }
+            }
+            Storage::Paged(paged) => {
+                for op in moves {
+                    if let Some(file) = paged.store.get(&op.source).await? {
+                        paged
+                            .store
+                            .update(&op.source, &FileManager::relocated(&file, moves))
+                            .await?;
+                    }
+                }
+                for file in &mut paged.audio {
+                    *file = FileManager::relocated(file, moves);
+                }
+                let start = paged.window_start;
+                self.load_window(start).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Updates the modification time of files whose dates were fixed
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a paged list cannot be updated.
+    pub async fn retime(&mut self, changes: &[TimestampOperation]) -> Result<()> {
+        self.version = next_version();
+        self.cache_scope = None;
+        match &mut self.storage {
+            Storage::Memory(files) => {
+                for file in files.iter_mut() {
+                    *file = FileManager::retimed(file, changes);
+                }
+            }
+            Storage::Paged(paged) => {
+                for change in changes {
+                    if let Some(file) = paged.store.get(&change.path).await? {
+                        paged
+                            .store
+                            .update(&change.path, &FileManager::retimed(&file, changes))
+                            .await?;
+                    }
+                }
+                let start = paged.window_start;
+                self.load_window(start).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Drops the files at `paths` from the list and returns them.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a paged list cannot be updated.
+    pub async fn remove(&mut self, paths: &HashSet<PathBuf>) -> Result<Vec<Arc<MediaFile>>> {
+        self.version = next_version();
+        self.cache_scope = None;
+        match &mut self.storage {
+            Storage::Memory(files) => {
+                let (removed, kept) = std::mem::take(files)
+                    .into_iter()
+                    .partition(|file| paths.contains(&file.path));
+                *files = kept;
+                Ok(removed)
+            }
+            Storage::Paged(paged) => {
+                let removed = paged.store.remove(paths).await?;
+                for file in &removed {
+                    remove_from_years(&mut paged.years, file);
+                }
+                paged.audio.retain(|file| !paths.contains(&file.path));
+                let start = paged.window_start.min(paged.store.len().saturating_sub(WINDOW_MARGIN));
+                self.load_window(start).await?;
+                Ok(removed)
+            }
+        }
+    }
+}
+
+fn add_to_years(years: &mut YearTotals, file: &MediaFile) {
+    let entry = years.entry(file.created.year()).or_default();
+    entry.0 += 1;
+    entry.1 += file.size;
+}
+
+fn remove_from_years(years: &mut YearTotals, file: &MediaFile) {
+    let year = file.created.year();
+    if let Some(entry) = years.get_mut(&year) {
+        entry.0 = entry.0.saturating_sub(1);
+        entry.1 = entry.1.saturating_sub(file.size);
+        if entry.0 == 0 {
+            years.remove(&year);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    #![allow(clippy::panic_in_result_fn)]
+    #![allow(clippy::unwrap_in_result)]
+
+    use super::*;
+    use crate::test_support::media_file;
+    use tempfile::TempDir;
+    use visualvault_models::{CaptureInfo, ImageMetadata, MediaMetadata, SortColumn};
+
+    fn files(count: usize) -> Vec<Arc<MediaFile>> {
+        (0..count)
+            .map(|i| {
+                let file_type = if i % 10 == 0 { FileType::Audio } else { FileType::Image };
+                media_file(format!("/library/file_{i:05}.dat"))
+                    .file_type(file_type)
+                    .arc()
+            })
+            .collect()
+    }
+
+    async fn budgeted(files: Vec<Arc<MediaFile>>, budget: Option<u64>, path: &Path) -> Result<FileList> {
+        let mut list = FileList::with_budget(budget, path);
+        list.extend(files).await?;
+        Ok(list)
+    }
+
+    async fn paged(temp_dir: &TempDir, count: usize) -> Result<FileList> {
+        budgeted(files(count), Some(1), &temp_dir.path().join("files.db")).await
+    }
+
+    #[tokio::test]
+    async fn test_stays_in_memory_within_budget() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let path = temp_dir.path().join("files.db");
+
+        let list = budgeted(files(10), None, &path).await?;
+        assert!(!list.is_paged());
+        let list = budgeted(files(10), Some(1024 * 1024), &path).await?;
+        assert!(!list.is_paged());
+        assert_eq!(list.len(), 10);
+        assert!(!path.exists());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_moves_to_store_once_over_budget() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let path = temp_dir.path().join("files.db");
+        let files = files(30);
+        let budget = files[..15].iter().map(|file| file.estimated_memory() as u64).sum();
+        let mut list = FileList::with_budget(Some(budget), &path);
+
+        list.extend(files[..10].to_vec()).await?;
+        assert!(!list.is_paged());
+        let version = list.version();
+        list.extend(files[10..20].to_vec()).await?;
+        assert!(list.is_paged());
+        assert_ne!(list.version(), version);
+        list.extend(files[20..].to_vec()).await?;
+
+        assert_eq!(list.len(), 30);
+        assert_eq!(list.get(25).unwrap().name.as_ref(), "file_00025.dat");
+        assert_eq!(list.audio_files().len(), 3);
+        assert_eq!(list.year_totals().values().map(|(count, _)| count).sum::<usize>(), 30);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_paged_list_loads_window_on_demand() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let mut list = paged(&temp_dir, 3000).await?;
+
+        assert!(list.is_paged());
+        assert_eq!(list.len(), 3000);
+        assert!(list.get(5).is_some());
+        assert!(list.get(2500).is_none());
+
+        list.ensure_loaded(2500).await?;
+        assert_eq!(list.get(2500).unwrap().name.as_ref(), "file_02500.dat");
+        assert!(list.get(2299).is_none());
+        assert_eq!(list.get(2999).unwrap().name.as_ref(), "file_02999.dat");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_paged_list_keeps_search_and_filters() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let list = paged(&temp_dir, 100).await?;
+        let memory = FileList::new(files(100));
+
+        for list in [&list, &memory] {
+            assert_eq!(list.search("FILE_0009").await?.len(), 10);
+            assert_eq!(list.count(|file| file.file_type == FileType::Audio).await?, 10);
+            assert_eq!(list.audio_files().len(), 10);
+            assert_eq!(list.range(10, 12).await?.len(), 3);
+            assert_eq!(list.index_of(Path::new("/library/file_00042.dat")).await?, Some(42));
+            assert_eq!(list.index_of(Path::new("/library/missing.dat")).await?, None);
+            assert_eq!(list.year_totals().values().map(|(count, _)| count).sum::<usize>(), 100);
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_paged_and_memory_lists_sort_alike() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let mut files = files(50);
+        for (file, i) in files.iter_mut().zip(0u32..) {
+            let file = Arc::make_mut(file);
+            file.size = u64::from(i * 37 % 11);
+            file.modified -= chrono::Duration::hours(i64::from(i * 13 % 7));
+        }
+        let mut paged = budgeted(files.clone(), Some(1), &temp_dir.path().join("files.db")).await?;
+        let mut memory = FileList::new(files);
+
+        for column in SortColumn::ALL {
+            for descending in [false, true] {
+                let sort = FileSort { column, descending };
+                paged.sort(sort).await?;
+                memory.sort(sort).await?;
+                assert_eq!(
+                    paged.paths().await?,
+                    memory.paths().await?,
+                    "{column} descending: {descending}"
+                );
+                assert_eq!(
+                    paged.get(0).map(|file| &file.path),
+                    memory.get(0).map(|file| &file.path)
+                );
+            }
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_search_matches_camera_and_lens() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let mut files = files(100);
+        let mut photo = (*files[42]).clone();
+        photo.metadata = Some(MediaMetadata::Image(ImageMetadata::from_capture(
+            "DAT".into(),
+            CaptureInfo {
+                make: Some("FUJIFILM".into()),
+                model: Some("X-T4".into()),
+                lens: Some("XF23mmF2 R WR".into()),
+                ..CaptureInfo::default()
+            },
+        )));
+        files[42] = Arc::new(photo);
+
+        let list = budgeted(files.clone(), Some(1), &temp_dir.path().join("files.db")).await?;
+        let memory = FileList::new(files);
+        for list in [&list, &memory] {
+            assert_eq!(list.search("fujifilm x-t4").await?.len(), 1);
+            assert_eq!(list.search("XF23MM").await?[0].name.as_ref(), "file_00042.dat");
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_paged_list_relocates_and_removes() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let mut list = paged(&temp_dir, 20).await?;
+        let version = list.version();
+
+        list.relocate(&[MoveOperation {
+            source: PathBuf::from("/library/file_00001.dat"),
+            destination: PathBuf::from("/sorted/renamed.dat"),
+        }])
+        .await?;
+        assert_eq!(list.get(1).unwrap().path, PathBuf::from("/sorted/renamed.dat"));
+        assert_ne!(list.version(), version);
+
+        let removed = list
+            .remove(
+                &[
+                    PathBuf::from("/sorted/renamed.dat"),
+                    PathBuf::from("/library/file_00000.dat"),
+                ]
+                .into(),
+            )
+            .await?;
+        assert_eq!(removed.len(), 2);
+        assert_eq!(list.len(), 18);
+        assert_eq!(list.audio_files().len(), 1);
+        assert_eq!(list.get(0).unwrap().name.as_ref(), "file_00002.dat");
+        assert_eq!(list.year_totals().values().map(|(count, _)| count).sum::<usize>(), 18);
+
+        let mut visited = Vec::new();
+        list.for_each(|index, file| visited.push((index, file.name.to_string())))
+            .await?;
+        assert_eq!(visited.len(), 18);
+        assert_eq!(visited[17], (17, "file_00019.dat".to_string()));
+        Ok(())
+    }
+}
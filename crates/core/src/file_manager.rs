@@ -1,6 +1,16 @@
+use std::collections::HashSet;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
-use visualvault_models::MediaFile;
+use chrono::{Local, TimeZone};
+use color_eyre::eyre::Result;
+use tracing::warn;
+use visualvault_models::{ErrorEntry, MediaFile, OrganizedFile, SuspectFile};
+
+use crate::batch_rename::unique_destination;
+use crate::transfer::move_file_sync;
+use crate::undo_manager::{DeleteOperation, MoveOperation, TimestampField, TimestampOperation};
 
 #[derive(Default)]
 pub struct FileManager {
@@ -29,6 +39,276 @@ impl FileManager {
     pub fn get_file_count(&self) -> usize {
         self.files.len()
     }
+
+    /// Drops files from the list, e.g. after they were trashed, moved away or ignored
+    pub fn remove_files(&mut self, paths: &HashSet<PathBuf>) {
+        self.files = self
+            .files
+            .iter()
+            .filter(|file| !paths.contains(&file.path))
+            .cloned()
+            .collect();
+    }
+
+    /// Points moved or renamed files at their new location
+    pub fn relocate_files(&mut self, moves: &[MoveOperation]) {
+        self.files = self.files.iter().map(|file| Self::relocated(file, moves)).collect();
+    }
+
+    /// Returns `file` pointed at its new location if it, or its Live Photo pair, is one of `moves`
+    #[must_use]
+    pub fn relocated(file: &Arc<MediaFile>, moves: &[MoveOperation]) -> Arc<MediaFile> {
+        let destination_of = |path: &Path| moves.iter().find(|op| op.source == path).map(|op| &op.destination);
+        let own = destination_of(&file.path);
+        let pair = file.live_photo_pair.as_deref().and_then(destination_of);
+        if own.is_none() && pair.is_none() {
+            return Arc::clone(file);
+        }
+
+        let mut moved = (**file).clone();
+        if let Some(destination) = own {
+            if let Some(name) = destination.file_name() {
+                moved.name = name.to_string_lossy().into();
+            }
+            moved.path.clone_from(destination);
+        }
+        if let Some(destination) = pair {
+            moved.live_photo_pair = Some(destination.clone());
+        }
+        Arc::new(moved)
+    }
+
+    /// Updates the modification time of files whose dates were fixed
+    pub fn retime_files(&mut self, changes: &[TimestampOperation]) {
+        self.files = self.files.iter().map(|file| Self::retimed(file, changes)).collect();
+    }
+
+    /// Returns `file` with its new modification time if it is one of `changes`
+    #[must_use]
+    pub fn retimed(file: &Arc<MediaFile>, changes: &[TimestampOperation]) -> Arc<MediaFile> {
+        let modified = changes
+            .iter()
+            .find(|change| change.field == TimestampField::Modified && change.path == file.path)
+            .and_then(|change| Local.from_local_datetime(&change.after).earliest());
+        let Some(modified) = modified else {
+            return Arc::clone(file);
+        };
+
+        let mut retimed = (**file).clone();
+        retimed.modified = modified;
+        Arc::new(retimed)
+    }
+
+    /// Moves files into `trash_dir`, from where undo can restore them.
+    ///
+    /// Returns the files that were trashed; failures are logged and skipped.
+    #[must_use]
+    pub fn trash_files(paths: &[PathBuf], trash_dir: &Path) -> Vec<DeleteOperation> {
+        if let Err(e) = std::fs::create_dir_all(trash_dir) {
+            warn!("Failed to create trash folder {}: {}", trash_dir.display(), e);
+            return Vec::new();
+        }
+
+        paths
+            .iter()
+            .filter_map(|path| {
+                let name = path.file_name()?.to_string_lossy();
+                let backup = trash_dir.join(format!("{}-{name}", uuid::Uuid::new_v4()));
+                match move_file_sync(path, &backup) {
+                    Ok(()) => Some(DeleteOperation {
+                        path: path.clone(),
+                        backup_path: Some(backup),
+                    }),
+                    Err(e) => {
+                        warn!("Failed to move {} to trash: {}", path.display(), e);
+                        None
+                    }
+                }
+            })
+            .collect()
+    }
+
+    /// Moves files into `folder`, giving names that are already taken a " (n)" suffix.
+    ///
+    /// Returns the moves that succeeded; failures are logged and skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `folder` cannot be created.
+    pub fn move_files(paths: &[PathBuf], folder: &Path) -> Result<Vec<MoveOperation>> {
+        std::fs::create_dir_all(folder)?;
+
+        let mut taken = HashSet::new();
+        let mut moved = Vec::new();
+        for path in paths {
+            let Some(name) = path.file_name() else {
+                continue;
+            };
+            let destination = unique_destination(folder, &name.to_string_lossy(), path, &taken);
+            if destination == *path {
+                continue;
+            }
+
+            match move_file_sync(path, &destination) {
+                Ok(()) => {
+                    taken.insert(destination.clone());
+                    moved.push(MoveOperation {
+                        source: path.clone(),
+                        destination,
+                    });
+                }
+                Err(e) => warn!("Failed to move {}: {}", path.display(), e),
+            }
+        }
+        Ok(moved)
+    }
+
+    /// Copies files into `folder`, keeping their names and numbering them on conflicts.
+    /// Returns the copies that were written; failures are logged and skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `folder` cannot be created.
+    pub fn copy_files(paths: &[PathBuf], folder: &Path) -> Result<Vec<MoveOperation>> {
+        std::fs::create_dir_all(folder)?;
+
+        let mut copied = Vec::new();
+        for path in paths {
+            let Some(name) = path.file_name() else {
+                continue;
+            };
+            let destination = unique_destination(folder, &name.to_string_lossy(), path, &HashSet::new());
+            if destination == *path {
+                continue;
+            }
+
+            match std::fs::copy(path, &destination) {
+                Ok(_) => copied.push(MoveOperation {
+                    source: path.clone(),
+                    destination,
+                }),
+                Err(e) => warn!("Failed to copy {}: {}", path.display(), e),
+            }
+        }
+        Ok(copied)
+    }
+
+    /// Writes the files as CSV with their path, type, size in bytes and modification time.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    pub fn export_list(files: &[Arc<MediaFile>], target: &Path) -> Result<()> {
+        let mut csv = String::from("path,type,size,modified\n");
+        for file in files {
+            let _ = writeln!(
+                csv,
+                "{},{},{},{}",
+                csv_field(&file.path.display().to_string()),
+                file.file_type,
+                file.size,
+                file.modified.to_rfc3339()
+            );
+        }
+
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(target, csv)?;
+        Ok(())
+    }
+
+    /// Writes the files of an organize run as CSV with their source, destination, outcome and error.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    pub fn export_organize_report(files: &[OrganizedFile], target: &Path) -> Result<()> {
+        let mut csv = String::from("source,destination,outcome,error\n");
+        for file in files {
+            let _ = writeln!(
+                csv,
+                "{},{},{},{}",
+                csv_field(&file.source.display().to_string()),
+                csv_field(
+                    &file
+                        .destination
+                        .as_ref()
+                        .map(|d| d.display().to_string())
+                        .unwrap_or_default()
+                ),
+                file.outcome,
+                csv_field(file.error.as_deref().unwrap_or_default())
+            );
+        }
+
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(target, csv)?;
+        Ok(())
+    }
+
+    /// Writes the errors of the error center as CSV with their time, source, path, message
+    /// and suggested fix.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    pub fn export_error_report(entries: &[ErrorEntry], target: &Path) -> Result<()> {
+        let mut csv = String::from("time,source,path,message,remediation\n");
+        for entry in entries {
+            let _ = writeln!(
+                csv,
+                "{},{},{},{},{}",
+                entry.timestamp.format("%Y-%m-%d %H:%M:%S"),
+                entry.source,
+                csv_field(&entry.path.as_ref().map(|p| p.display().to_string()).unwrap_or_default()),
+                csv_field(&entry.message),
+                csv_field(entry.remediation())
+            );
+        }
+
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(target, csv)?;
+        Ok(())
+    }
+
+    /// Writes quarantined files as CSV with where each came from, where it is now and why it
+    /// was set aside.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    pub fn export_quarantine_report(files: &[(SuspectFile, PathBuf)], target: &Path) -> Result<()> {
+        let mut csv = String::from("source,quarantined,size,reason\n");
+        for (suspect, quarantined) in files {
+            let _ = writeln!(
+                csv,
+                "{},{},{},{}",
+                csv_field(&suspect.path.display().to_string()),
+                csv_field(&quarantined.display().to_string()),
+                suspect.size,
+                csv_field(&suspect.reason.to_string())
+            );
+        }
+
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(target, csv)?;
+        Ok(())
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
 }
 
 // ...existing code...
@@ -40,22 +320,14 @@ mod tests {
     #![allow(clippy::float_cmp)] // For comparing floats in tests
     #![allow(clippy::panic)]
     use super::*;
-    use chrono::Local;
+    use crate::test_support::media_file;
     use std::path::PathBuf;
-    use visualvault_models::FileType;
 
     fn create_test_media_file(name: &str, size: u64) -> Arc<MediaFile> {
-        Arc::new(MediaFile {
-            path: PathBuf::from(format!("/test/{name}")),
-            name: name.to_string().into(),
-            size,
-            modified: Local::now(),
-            created: Local::now(),
-            file_type: FileType::Image,
-            extension: "jpg".to_string().into(),
-            hash: Some(format!("hash_{name}").into()),
-            metadata: None,
-        })
+        media_file(format!("/test/{name}"))
+            .size(size)
+            .hash(&format!("hash_{name}"))
+            .arc()
     }
 
     #[test]
@@ -215,4 +487,129 @@ mod tests {
         // This should be very fast due to Arc cloning
         assert!(duration.as_millis() < 100, "Arc cloning should be fast");
     }
+
+    #[test]
+    fn test_remove_and_relocate_files() {
+        let mut manager = FileManager::new();
+        manager.set_files(vec![
+            create_test_media_file("a.jpg", 1000),
+            create_test_media_file("b.jpg", 2000),
+            create_test_media_file("c.jpg", 3000),
+        ]);
+
+        manager.remove_files(&HashSet::from([PathBuf::from("/test/b.jpg")]));
+        manager.relocate_files(&[MoveOperation {
+            source: PathBuf::from("/test/c.jpg"),
+            destination: PathBuf::from("/other/d.jpg"),
+        }]);
+
+        let files = manager.get_files();
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].path, PathBuf::from("/test/a.jpg"));
+        assert_eq!(files[1].path, PathBuf::from("/other/d.jpg"));
+        assert_eq!(files[1].name, "d.jpg".into());
+    }
+
+    #[test]
+    fn test_trash_and_move_files() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let a = temp.path().join("a.jpg");
+        let b = temp.path().join("b.jpg");
+        std::fs::write(&a, b"a").unwrap();
+        std::fs::write(&b, b"b").unwrap();
+
+        let folder = temp.path().join("picked");
+        std::fs::create_dir(&folder).unwrap();
+        std::fs::write(folder.join("b.jpg"), b"existing").unwrap();
+
+        let moved = FileManager::move_files(std::slice::from_ref(&b), &folder).unwrap();
+        assert_eq!(moved[0].destination, folder.join("b (1).jpg"));
+        assert!(!b.exists());
+
+        let trash = temp.path().join("trash");
+        let trashed = FileManager::trash_files(&[a.clone(), temp.path().join("missing.jpg")], &trash);
+        assert_eq!(trashed.len(), 1);
+        assert!(!a.exists());
+        assert!(trashed[0].backup_path.as_ref().unwrap().exists());
+    }
+
+    #[test]
+    fn test_copy_files_keeps_originals() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let a = temp.path().join("a.jpg");
+        std::fs::write(&a, b"a").unwrap();
+
+        let folder = temp.path().join("album");
+        let copied =
+            FileManager::copy_files(&[a.clone(), a.clone(), temp.path().join("missing.jpg")], &folder).unwrap();
+        let destinations: Vec<&PathBuf> = copied.iter().map(|op| &op.destination).collect();
+        assert_eq!(destinations, vec![&folder.join("a.jpg"), &folder.join("a (1).jpg")]);
+        assert!(copied.iter().all(|op| op.source == a));
+        assert!(a.exists());
+        assert_eq!(std::fs::read(&copied[1].destination).unwrap(), b"a");
+    }
+
+    #[test]
+    fn test_export_list_quotes_paths() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let mut file = (*create_test_media_file("a,b.jpg", 1234)).clone();
+        file.path = PathBuf::from("/test/a,b.jpg");
+        let target = temp.path().join("export").join("files.csv");
+
+        FileManager::export_list(&[Arc::new(file), create_test_media_file("c.jpg", 5)], &target).unwrap();
+
+        let csv = std::fs::read_to_string(&target).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "path,type,size,modified");
+        assert!(lines[1].starts_with("\"/test/a,b.jpg\",Image,1234,"));
+        assert!(lines[2].starts_with("/test/c.jpg,Image,5,"));
+    }
+
+    #[test]
+    fn test_export_organize_report() {
+        use visualvault_models::FileOutcome;
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let target = temp.path().join("organize.csv");
+        let files = [
+            OrganizedFile::new("/src/a.jpg".into(), Some("/dest/2024/a.jpg".into()), FileOutcome::Moved),
+            OrganizedFile::new("/src/b.jpg".into(), None, FileOutcome::SkippedDuplicate),
+            OrganizedFile::failed("/src/c.jpg".into(), "denied, read-only".to_string()),
+        ];
+
+        FileManager::export_organize_report(&files, &target).unwrap();
+
+        let csv = std::fs::read_to_string(&target).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "source,destination,outcome,error");
+        assert_eq!(lines[1], "/src/a.jpg,/dest/2024/a.jpg,moved,");
+        assert_eq!(lines[2], "/src/b.jpg,,skipped duplicate,");
+        assert_eq!(lines[3], "/src/c.jpg,,error,\"denied, read-only\"");
+    }
+
+    #[test]
+    fn test_export_error_report() {
+        use visualvault_models::ErrorSource;
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let target = temp.path().join("errors.csv");
+        let entries = [
+            ErrorEntry::new(
+                ErrorSource::Organize,
+                Some("/src/a.jpg".into()),
+                "Permission denied (os error 13)",
+            ),
+            ErrorEntry::new(ErrorSource::Operation, None, "Scan failed: Path does not exist"),
+        ];
+
+        FileManager::export_error_report(&entries, &target).unwrap();
+
+        let csv = std::fs::read_to_string(&target).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "time,source,path,message,remediation");
+        assert!(lines[1].ends_with(
+            ",organize,/src/a.jpg,Permission denied (os error 13),Check that you may read and write the file and its folder"
+        ));
+        assert!(lines[2].contains(",operation,,Scan failed: Path does not exist,"));
+    }
 }
@@ -0,0 +1,391 @@
+#![allow(clippy::cast_possible_wrap)]
+#![allow(clippy::cast_sign_loss)]
+#![allow(clippy::cast_possible_truncation)]
+use color_eyre::eyre::Result;
+use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions, SqliteSynchronous};
+use sqlx::{Row, SqlitePool};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tracing::info;
+use visualvault_models::{FileSort, MediaFile, SortColumn};
+
+/// Rows written per transaction while filling the store
+const INSERT_BATCH: usize = 1000;
+/// Rows read per query while walking the whole store
+const READ_BATCH: i64 = 2000;
+
+/// Scanned files kept in an `SQLite` file instead of memory, read back a page at a time.
+///
+/// Rows keep the scan order, so pages line up with the indexes of the file list. The file is
+/// scratch data: it is recreated for every scan and never migrated.
+#[derive(Debug, Clone)]
+pub struct FileStore {
+    pool: SqlitePool,
+    len: usize,
+}
+
+impl FileStore {
+    /// Creates the store at `path`, replacing any earlier one, and writes `files` to it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database cannot be created or a file cannot be serialized,
+    /// for example because its path is not valid UTF-8.
+    pub async fn create(path: &Path, files: &[Arc<MediaFile>]) -> Result<Self> {
+        // Start from an empty file so pages never mix two scans
+        for suffix in ["", "-wal", "-shm"] {
+            let mut file = path.as_os_str().to_owned();
+            file.push(suffix);
+            if Path::new(&file).exists() {
+                std::fs::remove_file(&file)?;
+            }
+        }
+
+        let pool = SqlitePoolOptions::new()
+            .max_connections(2)
+            .connect_with(
+                SqliteConnectOptions::new()
+                    .filename(path)
+                    .create_if_missing(true)
+                    .journal_mode(SqliteJournalMode::Wal)
+                    .synchronous(SqliteSynchronous::Off),
+            )
+            .await?;
+
+        sqlx::query(
+            "CREATE TABLE files (
+                position INTEGER PRIMARY KEY,
+                path TEXT NOT NULL,
+                search TEXT NOT NULL,
+                data TEXT NOT NULL
+            )",
+        )
+        .execute(&pool)
+        .await?;
+        sqlx::query("CREATE INDEX idx_files_path ON files(path)")
+            .execute(&pool)
+            .await?;
+
+        let mut store = Self { pool, len: 0 };
+        store.append(files).await?;
+        info!("Stored {} scanned files in {}", files.len(), path.display());
+        Ok(store)
+    }
+
+    /// Adds `files` after the ones already stored, as a scan finds them.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a file cannot be serialized or written.
+    pub async fn append(&mut self, files: &[Arc<MediaFile>]) -> Result<()> {
+        for batch in files.chunks(INSERT_BATCH) {
+            let mut tx = self.pool.begin().await?;
+            for file in batch {
+                // Positions continue after the highest one, even once files were removed
+                sqlx::query("INSERT INTO files (path, search, data) VALUES (?, ?, ?)")
+                    .bind(file.path.to_string_lossy().as_ref())
+                    .bind(search_key(file))
+                    .bind(serde_json::to_string(file.as_ref())?)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+            tx.commit().await?;
+            self.len += batch.len();
+        }
+        Ok(())
+    }
+
+    /// Number of files in the store
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Reads up to `limit` files starting at index `offset`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails or a stored file cannot be decoded.
+    pub async fn page(&self, offset: usize, limit: usize) -> Result<Vec<Arc<MediaFile>>> {
+        let rows = sqlx::query("SELECT data FROM files ORDER BY position LIMIT ? OFFSET ?")
+            .bind(limit as i64)
+            .bind(offset as i64)
+            .fetch_all(&self.pool)
+            .await?;
+        rows.iter().map(|row| decode(&row.get::<String, _>("data"))).collect()
+    }
+
+    /// Calls `f` with every file in list order, reading them in batches.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a query fails or a stored file cannot be decoded.
+    pub async fn for_each(&self, mut f: impl FnMut(Arc<MediaFile>)) -> Result<()> {
+        let mut last = -1i64;
+        loop {
+            let rows = sqlx::query("SELECT position, data FROM files WHERE position > ? ORDER BY position LIMIT ?")
+                .bind(last)
+                .bind(READ_BATCH)
+                .fetch_all(&self.pool)
+                .await?;
+            let Some(row) = rows.last() else {
+                return Ok(());
+            };
+            last = row.get("position");
+            for row in &rows {
+                f(decode(&row.get::<String, _>("data"))?);
+            }
+        }
+    }
+
+    /// Returns up to `limit` files whose lowercase path contains `term`, in list order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails or a stored file cannot be decoded.
+    pub async fn search(&self, term: &str, limit: usize) -> Result<Vec<Arc<MediaFile>>> {
+        let rows = sqlx::query("SELECT data FROM files WHERE instr(search, ?) > 0 ORDER BY position LIMIT ?")
+            .bind(term.to_lowercase())
+            .bind(limit as i64)
+            .fetch_all(&self.pool)
+            .await?;
+        rows.iter().map(|row| decode(&row.get::<String, _>("data"))).collect()
+    }
+
+    /// Returns the paths of all files in list order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn paths(&self) -> Result<Vec<PathBuf>> {
+        let paths: Vec<String> = sqlx::query_scalar("SELECT path FROM files ORDER BY position")
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(paths.into_iter().map(PathBuf::from).collect())
+    }
+
+    /// Reads the file stored under `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails or the stored file cannot be decoded.
+    pub async fn get(&self, path: &Path) -> Result<Option<Arc<MediaFile>>> {
+        let data: Option<String> = sqlx::query_scalar("SELECT data FROM files WHERE path = ?")
+            .bind(path.to_string_lossy().as_ref())
+            .fetch_optional(&self.pool)
+            .await?;
+        data.map(|data| decode(&data)).transpose()
+    }
+
+    /// Renumbers the rows in the order of `sort`, which pages and searches follow from then on.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the rows cannot be rewritten.
+    pub async fn sort(&self, sort: FileSort) -> Result<()> {
+        let key = match sort.column {
+            SortColumn::Name => None,
+            SortColumn::Type => Some("json_extract(data, '$.file_type')"),
+            SortColumn::Size => Some("json_extract(data, '$.size')"),
+            SortColumn::Modified => Some("julianday(json_extract(data, '$.modified'))"),
+        };
+        let direction = if sort.descending { "DESC" } else { "ASC" };
+        // Same tie-breakers as `FileSort::compare`
+        let order = key
+            .into_iter()
+            .chain(["lower(json_extract(data, '$.name'))", "path"])
+            .map(|key| format!("{key} {direction}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("CREATE TABLE sorted_files (position INTEGER PRIMARY KEY, path TEXT NOT NULL, search TEXT NOT NULL, data TEXT NOT NULL)")
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query(&format!(
+            "INSERT INTO sorted_files (path, search, data) SELECT path, search, data FROM files ORDER BY {order}"
+        ))
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query("DROP TABLE files").execute(&mut *tx).await?;
+        sqlx::query("ALTER TABLE sorted_files RENAME TO files")
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query("CREATE INDEX idx_files_path ON files(path)")
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Returns the list index of the file stored under `path`, counting the rows before it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn index_of(&self, path: &Path) -> Result<Option<usize>> {
+        let index: Option<i64> = sqlx::query_scalar(
+            "SELECT (SELECT COUNT(*) FROM files AS earlier WHERE earlier.position < file.position)
+             FROM files AS file WHERE file.path = ?",
+        )
+        .bind(path.to_string_lossy().as_ref())
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(index.map(|index| index as usize))
+    }
+
+    /// Replaces the file stored under `path` with `file`, which may have a new path.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be serialized or the update fails.
+    pub async fn update(&self, path: &Path, file: &MediaFile) -> Result<()> {
+        sqlx::query("UPDATE files SET path = ?, search = ?, data = ? WHERE path = ?")
+            .bind(file.path.to_string_lossy().as_ref())
+            .bind(search_key(file))
+            .bind(serde_json::to_string(file)?)
+            .bind(path.to_string_lossy().as_ref())
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Deletes the files stored under `paths` and returns them.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the deletion fails or a stored file cannot be decoded.
+    pub async fn remove(&mut self, paths: &HashSet<PathBuf>) -> Result<Vec<Arc<MediaFile>>> {
+        let mut removed = Vec::new();
+        let mut tx = self.pool.begin().await?;
+        for path in paths {
+            let data: Option<String> = sqlx::query_scalar("DELETE FROM files WHERE path = ? RETURNING data")
+                .bind(path.to_string_lossy().as_ref())
+                .fetch_optional(&mut *tx)
+                .await?;
+            if let Some(data) = data {
+                removed.push(decode(&data)?);
+            }
+        }
+        tx.commit().await?;
+
+        self.len = self.len.saturating_sub(removed.len());
+        Ok(removed)
+    }
+}
+
+/// Lowercase text searched by [`FileStore::search`]: the path, which includes the file name,
+/// followed by the camera and lens
+pub(crate) fn search_key(file: &MediaFile) -> String {
+    let mut key = file.path.to_string_lossy().to_lowercase();
+    if let Some(capture) = file.capture() {
+        key.push('\n');
+        key.push_str(&capture.search_text().to_lowercase());
+    }
+    key
+}
+
+pub(crate) fn decode(data: &str) -> Result<Arc<MediaFile>> {
+    Ok(Arc::new(serde_json::from_str(data)?))
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    #![allow(clippy::panic_in_result_fn)]
+    #![allow(clippy::unwrap_in_result)]
+
+    use super::*;
+    use crate::test_support::media_file;
+    use tempfile::TempDir;
+
+    fn files(count: usize) -> Vec<Arc<MediaFile>> {
+        (0..count)
+            .map(|i| media_file(format!("/photos/{}/IMG_{i:05}.jpg", i % 3)).arc())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_pages_follow_scan_order() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let files = files(2500);
+        let store = FileStore::create(&temp_dir.path().join("files.db"), &files).await?;
+
+        assert_eq!(store.len(), 2500);
+        let page = store.page(1999, 3).await?;
+        assert_eq!(
+            page.iter().map(|file| file.name.to_string()).collect::<Vec<_>>(),
+            vec!["IMG_01999.jpg", "IMG_02000.jpg", "IMG_02001.jpg"]
+        );
+        assert!(store.page(2500, 10).await?.is_empty());
+
+        let mut count = 0;
+        store.for_each(|_| count += 1).await?;
+        assert_eq!(count, 2500);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_create_replaces_previous_scan() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let path = temp_dir.path().join("files.db");
+        let first = FileStore::create(&path, &files(10)).await?;
+        drop(first);
+
+        let store = FileStore::create(&path, &files(4)).await?;
+        assert_eq!(store.len(), 4);
+        assert_eq!(store.paths().await?.len(), 4);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_search_is_case_insensitive() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let store = FileStore::create(&temp_dir.path().join("files.db"), &files(30)).await?;
+
+        let found = store.search("/PHOTOS/1/img_0001", 100).await?;
+        assert_eq!(
+            found.iter().map(|file| file.name.to_string()).collect::<Vec<_>>(),
+            vec!["IMG_00010.jpg", "IMG_00013.jpg", "IMG_00016.jpg", "IMG_00019.jpg"]
+        );
+        assert_eq!(store.search("img_", 5).await?.len(), 5);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_update_and_remove() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let files = files(5);
+        let mut store = FileStore::create(&temp_dir.path().join("files.db"), &files).await?;
+
+        let mut moved = (*files[1]).clone();
+        moved.path = PathBuf::from("/library/IMG_00001.jpg");
+        store.update(&files[1].path, &moved).await?;
+        assert!(store.get(&files[1].path).await?.is_none());
+        assert_eq!(store.get(&moved.path).await?.unwrap().path, moved.path);
+
+        let removed = store
+            .remove(
+                &[files[0].path.clone(), PathBuf::from("/missing.jpg")]
+                    .into_iter()
+                    .collect(),
+            )
+            .await?;
+        assert_eq!(removed.len(), 1);
+        assert_eq!(store.len(), 4);
+        assert_eq!(store.page(0, 1).await?[0].path, moved.path);
+        assert_eq!(store.index_of(&files[3].path).await?, Some(2));
+        assert_eq!(store.index_of(&files[0].path).await?, None);
+
+        store.append(&files[..1]).await?;
+        assert_eq!(store.len(), 5);
+        assert_eq!(store.index_of(&files[0].path).await?, Some(4));
+        Ok(())
+    }
+}
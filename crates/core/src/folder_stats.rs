@@ -0,0 +1,330 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use ahash::AHashMap;
+use tokio::sync::Semaphore;
+use tokio::task::{AbortHandle, Id, JoinError, JoinSet};
+use tracing::error;
+use visualvault_models::FileType;
+use visualvault_utils::FolderStats;
+use visualvault_utils::media_types::determine_file_type;
+use walkdir::WalkDir;
+
+/// How long a folder must go without changes before its statistics are added up again
+pub const FOLDER_STATS_DEBOUNCE: Duration = Duration::from_secs(2);
+
+/// Folders added up at the same time; the rest wait for a free slot
+const MAX_CONCURRENT_FOLDERS: usize = 2;
+
+/// Counts the files, folders and media files below `path` and adds up their size.
+///
+/// Symbolic links are not followed and unreadable entries are skipped.
+#[must_use]
+pub fn calculate_folder_stats(path: &Path) -> FolderStats {
+    let mut stats = FolderStats::default();
+    for entry in WalkDir::new(path)
+        .follow_links(false)
+        .into_iter()
+        .filter_map(Result::ok)
+    {
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        if metadata.is_file() {
+            stats.total_files += 1;
+            stats.total_size += metadata.len();
+            stats.media_files += usize::from(is_media_path(entry.path()));
+        } else if metadata.is_dir() {
+            stats.total_dirs += 1;
+        }
+    }
+    stats
+}
+
+/// Whether `path` has the extension of an image, video or audio file
+pub(crate) fn is_media_path(path: &Path) -> bool {
+    path.extension().and_then(|ext| ext.to_str()).is_some_and(|ext| {
+        matches!(
+            determine_file_type(ext),
+            FileType::Image | FileType::Video | FileType::Audio
+        )
+    })
+}
+
+/// A folder whose statistics were added up, or failed to be
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FolderStatsUpdate {
+    Updated(PathBuf),
+    Failed(PathBuf),
+}
+
+/// Folder statistics added up in the background and kept until the folder changes.
+///
+/// Folders are walked on the blocking thread pool, at most two at a time, so the UI only
+/// starts work and collects results. A folder that changed is marked stale with
+/// [`FolderStatsPool::invalidate`] and walked again once it stayed unchanged for the
+/// debounce time, keeping its old statistics until then.
+#[derive(Debug)]
+pub struct FolderStatsPool {
+    cache: AHashMap<PathBuf, FolderStats>,
+    running: AHashMap<PathBuf, AbortHandle>,
+    tasks: JoinSet<(PathBuf, Result<FolderStats, JoinError>)>,
+    /// Folders that changed, with when they last changed
+    stale: AHashMap<PathBuf, Instant>,
+    permits: Arc<Semaphore>,
+    debounce: Duration,
+}
+
+impl Default for FolderStatsPool {
+    fn default() -> Self {
+        Self::new(FOLDER_STATS_DEBOUNCE)
+    }
+}
+
+impl FolderStatsPool {
+    #[must_use]
+    pub fn new(debounce: Duration) -> Self {
+        Self {
+            cache: AHashMap::new(),
+            running: AHashMap::new(),
+            tasks: JoinSet::new(),
+            stale: AHashMap::new(),
+            permits: Arc::new(Semaphore::new(MAX_CONCURRENT_FOLDERS)),
+            debounce,
+        }
+    }
+
+    /// The last statistics of `path`, which may be stale while it is added up again
+    #[must_use]
+    pub fn get(&self, path: &Path) -> Option<&FolderStats> {
+        self.cache.get(path)
+    }
+
+    /// Whether the statistics of `path` are being added up or waiting to be
+    #[must_use]
+    pub fn is_computing(&self, path: &Path) -> bool {
+        self.running.contains_key(path) || self.stale.contains_key(path)
+    }
+
+    /// Whether no folder is being added up or waiting to be
+    #[must_use]
+    pub fn is_idle(&self) -> bool {
+        self.running.is_empty() && self.stale.is_empty()
+    }
+
+    /// Starts adding up `path` unless its statistics are known or on their way.
+    ///
+    /// Must be called within a Tokio runtime.
+    pub fn ensure(&mut self, path: &Path) {
+        if !self.cache.contains_key(path) && !self.is_computing(path) {
+            self.start(path.to_path_buf());
+        }
+    }
+
+    /// Adds up `path` again right away, replacing a walk that is still running.
+    ///
+    /// Must be called within a Tokio runtime.
+    pub fn refresh(&mut self, path: &Path) {
+        self.stale.remove(path);
+        self.start(path.to_path_buf());
+    }
+
+    /// Marks the statistics of `path` as stale after its files changed
+    pub fn invalidate(&mut self, path: &Path) {
+        self.stale.insert(path.to_path_buf(), Instant::now());
+    }
+
+    /// Marks the statistics of every known folder as stale
+    pub fn invalidate_all(&mut self) {
+        let now = Instant::now();
+        for path in self.cache.keys().chain(self.running.keys()) {
+            self.stale.insert(path.clone(), now);
+        }
+    }
+
+    /// Forgets every folder but `folders`, stopping their walks
+    pub fn retain(&mut self, folders: &[PathBuf]) {
+        self.cache.retain(|path, _| folders.contains(path));
+        self.stale.retain(|path, _| folders.contains(path));
+        self.running.retain(|path, task| {
+            let keep = folders.contains(path);
+            if !keep {
+                task.abort();
+            }
+            keep
+        });
+    }
+
+    /// Stops every walk and forgets the stale folders, keeping the statistics already known
+    pub fn cancel(&mut self) {
+        self.tasks.abort_all();
+        self.running.clear();
+        self.stale.clear();
+    }
+
+    /// Starts the stale folders that stayed unchanged for the debounce time and collects the
+    /// walks that finished, without waiting for any.
+    ///
+    /// Must be called within a Tokio runtime.
+    pub fn poll(&mut self) -> Vec<FolderStatsUpdate> {
+        let settled: Vec<PathBuf> = self
+            .stale
+            .iter()
+            .filter(|(_, changed)| changed.elapsed() >= self.debounce)
+            .map(|(path, _)| path.clone())
+            .collect();
+        for path in settled {
+            self.stale.remove(&path);
+            self.start(path);
+        }
+
+        let mut updates = Vec::new();
+        while let Some(result) = self.tasks.try_join_next_with_id() {
+            match result {
+                Ok((id, (path, walked))) => {
+                    if self.take_running(id).is_none() {
+                        continue;
+                    }
+                    match walked {
+                        Ok(stats) => {
+                            self.cache.insert(path.clone(), stats);
+                            updates.push(FolderStatsUpdate::Updated(path));
+                        }
+                        Err(e) => {
+                            error!("Failed to calculate stats for {:?}: {}", path, e);
+                            updates.push(FolderStatsUpdate::Failed(path));
+                        }
+                    }
+                }
+                Err(e) if e.is_cancelled() => {}
+                Err(e) => {
+                    if let Some(path) = self.take_running(e.id()) {
+                        error!("Failed to calculate stats for {:?}: {}", path, e);
+                        updates.push(FolderStatsUpdate::Failed(path));
+                    }
+                }
+            }
+        }
+        updates
+    }
+
+    fn start(&mut self, path: PathBuf) {
+        if let Some(task) = self.running.remove(&path) {
+            task.abort();
+        }
+        let permits = Arc::clone(&self.permits);
+        let walked = path.clone();
+        let task = self.tasks.spawn(async move {
+            // The semaphore is never closed, so a permit always comes
+            let _permit = permits.acquire_owned().await;
+            let stats = tokio::task::spawn_blocking({
+                let walked = walked.clone();
+                move || calculate_folder_stats(&walked)
+            })
+            .await;
+            (walked, stats)
+        });
+        self.running.insert(path, task);
+    }
+
+    /// Forgets the walk with task `id`, returning its folder if it is still the current walk
+    fn take_running(&mut self, id: Id) -> Option<PathBuf> {
+        let path = self
+            .running
+            .iter()
+            .find(|(_, task)| task.id() == id)
+            .map(|(path, _)| path.clone())?;
+        self.running.remove(&path);
+        Some(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::panic_in_result_fn)]
+    #![allow(clippy::unwrap_in_result)]
+
+    use super::*;
+    use color_eyre::eyre::Result;
+    use std::fs;
+    use tempfile::TempDir;
+
+    async fn wait_until_idle(pool: &mut FolderStatsPool) -> Vec<FolderStatsUpdate> {
+        let mut updates = Vec::new();
+        while !pool.is_idle() {
+            updates.extend(pool.poll());
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        updates
+    }
+
+    #[test]
+    fn test_calculate_folder_stats() -> Result<()> {
+        let dir = TempDir::new()?;
+        fs::create_dir(dir.path().join("2024"))?;
+        fs::write(dir.path().join("2024/a.jpg"), vec![0u8; 100])?;
+        fs::write(dir.path().join("b.MOV"), vec![0u8; 50])?;
+        fs::write(dir.path().join("notes.txt"), vec![0u8; 10])?;
+
+        let stats = calculate_folder_stats(dir.path());
+        assert_eq!(stats.total_files, 3);
+        assert_eq!(stats.media_files, 2);
+        assert_eq!(stats.total_size, 160);
+        // The folder itself is counted along with its subfolder
+        assert_eq!(stats.total_dirs, 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_pool_caches_until_invalidated() -> Result<()> {
+        let dir = TempDir::new()?;
+        fs::write(dir.path().join("a.jpg"), vec![0u8; 100])?;
+        let mut pool = FolderStatsPool::new(Duration::ZERO);
+
+        pool.ensure(dir.path());
+        assert!(pool.is_computing(dir.path()));
+        let updates = wait_until_idle(&mut pool).await;
+        assert_eq!(updates, vec![FolderStatsUpdate::Updated(dir.path().to_path_buf())]);
+        assert_eq!(pool.get(dir.path()).map(|stats| stats.total_size), Some(100));
+
+        // Known statistics are not walked again until the folder changes
+        pool.ensure(dir.path());
+        assert!(pool.is_idle());
+
+        fs::write(dir.path().join("b.jpg"), vec![0u8; 50])?;
+        pool.invalidate(dir.path());
+        assert!(pool.is_computing(dir.path()));
+        // The old statistics stay until the new ones are in
+        assert_eq!(pool.get(dir.path()).map(|stats| stats.total_size), Some(100));
+        wait_until_idle(&mut pool).await;
+        assert_eq!(pool.get(dir.path()).map(|stats| stats.total_files), Some(2));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_invalidation_is_debounced() -> Result<()> {
+        let dir = TempDir::new()?;
+        let mut pool = FolderStatsPool::new(Duration::from_secs(3600));
+
+        pool.invalidate(dir.path());
+        assert!(pool.poll().is_empty());
+        assert!(pool.is_computing(dir.path()));
+        assert!(pool.get(dir.path()).is_none());
+
+        // Refreshing skips the wait
+        pool.refresh(dir.path());
+        let updates = wait_until_idle(&mut pool).await;
+        assert_eq!(updates, vec![FolderStatsUpdate::Updated(dir.path().to_path_buf())]);
+
+        pool.invalidate_all();
+        assert!(pool.is_computing(dir.path()));
+        pool.cancel();
+        assert!(pool.is_idle());
+        assert!(pool.get(dir.path()).is_some());
+
+        pool.retain(&[]);
+        assert!(pool.get(dir.path()).is_none());
+        Ok(())
+    }
+}
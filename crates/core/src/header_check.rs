@@ -0,0 +1,249 @@
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+use image::{ImageFormat, ImageReader};
+use visualvault_models::SuspectReason;
+
+/// Bytes read from the start of a file to recognize its format, enough for `infer` to find
+/// the document type of Matroska files
+const HEADER_LEN: u64 = 8192;
+
+/// Top-level atoms that old `QuickTime` files may start with, which `infer` does not recognize
+const QUICKTIME_ATOMS: &[&[u8]] = &[b"wide", b"free", b"skip", b"pnot"];
+
+/// A media format and the extensions its files go by, the usual one first
+struct Format {
+    name: &'static str,
+    extensions: &'static [&'static str],
+    /// Format to decode files with, for images the `image` crate reads
+    decoder: Option<ImageFormat>,
+}
+
+/// Formats whose content is checked against the extension. Extensions for the same kind of
+/// container share a format, so e.g. an MP4 named `.mov` or a TIFF-based raw file is not
+/// reported as mislabeled.
+const FORMATS: &[Format] = &[
+    Format {
+        name: "JPEG",
+        extensions: &["jpg", "jpeg", "jpe", "jfif"],
+        decoder: Some(ImageFormat::Jpeg),
+    },
+    Format {
+        name: "PNG",
+        extensions: &["png"],
+        decoder: Some(ImageFormat::Png),
+    },
+    Format {
+        name: "GIF",
+        extensions: &["gif"],
+        decoder: Some(ImageFormat::Gif),
+    },
+    Format {
+        name: "WebP",
+        extensions: &["webp"],
+        decoder: Some(ImageFormat::WebP),
+    },
+    Format {
+        name: "BMP",
+        extensions: &["bmp"],
+        decoder: Some(ImageFormat::Bmp),
+    },
+    Format {
+        name: "TIFF",
+        extensions: &["tif", "tiff", "dng", "cr2", "nef", "arw", "pef", "srw"],
+        decoder: Some(ImageFormat::Tiff),
+    },
+    Format {
+        name: "HEIF",
+        extensions: &["heic", "heif"],
+        decoder: None,
+    },
+    Format {
+        name: "AVIF",
+        extensions: &["avif"],
+        decoder: None,
+    },
+    Format {
+        name: "MP4",
+        extensions: &["mp4", "m4v", "mov", "qt", "3gp", "3g2"],
+        decoder: None,
+    },
+    Format {
+        name: "AVI",
+        extensions: &["avi"],
+        decoder: None,
+    },
+    Format {
+        name: "Matroska",
+        extensions: &["mkv", "webm"],
+        decoder: None,
+    },
+];
+
+/// Checks that a file starts with the header of the format its extension promises.
+///
+/// JPEG, PNG, GIF, WebP, BMP and TIFF images with an intact header are then decoded, so one
+/// that was cut short or damaged is reported too. Other formats are only checked by their first
+/// bytes, so a broken video or HEIF image with an intact header passes.
+///
+/// Returns why the file is suspect, or `None` if it looks fine. Only empty and unreadable
+/// files are reported for extensions without a known format, such as most camera raw files.
+#[must_use]
+pub fn check_file_header(path: &Path, extension: &str, size: u64) -> Option<SuspectReason> {
+    if size == 0 {
+        return Some(SuspectReason::Empty);
+    }
+
+    let mut header = Vec::new();
+    let read = File::open(path).and_then(|file| file.take(HEADER_LEN).read_to_end(&mut header));
+    if let Err(e) = read {
+        return Some(SuspectReason::Unreadable(e.to_string()));
+    }
+
+    let expected = format_of_extension(extension)?;
+    match detect_format(&header) {
+        Some(detected) if detected.name == expected.name => decode_error(path, expected, extension),
+        Some(detected) => Some(SuspectReason::ExtensionMismatch {
+            extension: extension.to_lowercase(),
+            detected: detected.name,
+        }),
+        None => Some(SuspectReason::UnknownHeader {
+            expected: expected.name,
+        }),
+    }
+}
+
+/// Reads the dimensions and then the pixels of an image the `image` crate decodes, returning why
+/// that failed, if it did. TIFF-based raw files are left alone.
+fn decode_error(path: &Path, format: &Format, extension: &str) -> Option<SuspectReason> {
+    let decoder = format.decoder?;
+    if decoder == ImageFormat::Tiff && !matches!(extension.to_lowercase().as_str(), "tif" | "tiff") {
+        return None;
+    }
+    let reader = || {
+        ImageReader::open(path).map(|mut reader| {
+            reader.set_format(decoder);
+            reader
+        })
+    };
+    let decoded = reader()
+        .map_err(image::ImageError::from)
+        .and_then(ImageReader::into_dimensions)
+        .and_then(|_| reader().map_err(image::ImageError::from))
+        .and_then(ImageReader::decode);
+    decoded.err().map(|e| SuspectReason::Undecodable(e.to_string()))
+}
+
+/// The usual extension of files in the format named `format`, as reported by
+/// [`check_file_header`]
+#[must_use]
+pub fn format_extension(format: &str) -> Option<&'static str> {
+    FORMATS
+        .iter()
+        .find(|candidate| candidate.name == format)
+        .and_then(|format| format.extensions.first().copied())
+}
+
+fn format_of_extension(extension: &str) -> Option<&'static Format> {
+    let extension = extension.to_lowercase();
+    FORMATS
+        .iter()
+        .find(|format| format.extensions.contains(&extension.as_str()))
+}
+
+/// Recognizes the format of a file from its first bytes
+fn detect_format(header: &[u8]) -> Option<&'static Format> {
+    let extension = match infer::get(header) {
+        Some(kind) => kind.extension(),
+        // Other ISO media brands and old QuickTime layouts are still MP4 containers
+        None if header.get(4..8) == Some(b"ftyp") => "mp4",
+        None if QUICKTIME_ATOMS.iter().any(|atom| header.get(4..8) == Some(*atom)) => "mov",
+        None => return None,
+    };
+    format_of_extension(extension)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use std::io::Cursor;
+
+    /// A small image encoded in `format`
+    fn encoded(format: ImageFormat) -> Vec<u8> {
+        let mut bytes = Cursor::new(Vec::new());
+        image::RgbImage::new(16, 12).write_to(&mut bytes, format).unwrap();
+        bytes.into_inner()
+    }
+
+    fn check(name: &str, content: &[u8]) -> Option<SuspectReason> {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(name);
+        std::fs::write(&path, content).unwrap();
+        let (_, extension) = name.rsplit_once('.').unwrap_or_default();
+        check_file_header(&path, extension, content.len() as u64)
+    }
+
+    #[test]
+    fn test_check_file_header() {
+        assert_eq!(check("photo.jpg", &encoded(ImageFormat::Jpeg)), None);
+        assert_eq!(check("photo.PNG", &encoded(ImageFormat::Png)), None);
+        assert_eq!(check("photo.tif", &encoded(ImageFormat::Tiff)), None);
+        assert_eq!(check("clip.mov", b"\0\0\0\x14ftypqt  \0\0\0\0"), None);
+        assert_eq!(check("clip.mov", b"\0\0\0\x14ftypisom\0\0\0\0"), None);
+        assert_eq!(check("clip.3gp", b"\0\0\0\x14ftyp3gp4\0\0\0\0"), None);
+        assert_eq!(check("photo.raf", b"anything at all"), None);
+        assert_eq!(check("photo.jpg", b""), Some(SuspectReason::Empty));
+        assert_eq!(
+            check("photo.jpg", b"<html>not found</html>"),
+            Some(SuspectReason::UnknownHeader { expected: "JPEG" })
+        );
+        assert_eq!(
+            check("photo.jpg", b"\0\0\0\x18ftypheic\0\0\0\0mif1heic"),
+            Some(SuspectReason::ExtensionMismatch {
+                extension: "jpg".to_string(),
+                detected: "HEIF",
+            })
+        );
+        assert_eq!(
+            check("photo.png", &[0xFF, 0xD8, 0xFF, 0xDB]),
+            Some(SuspectReason::ExtensionMismatch {
+                extension: "png".to_string(),
+                detected: "JPEG",
+            })
+        );
+    }
+
+    #[test]
+    fn test_undecodable_image_is_suspect() {
+        let png = encoded(ImageFormat::Png);
+        assert!(matches!(
+            check("photo.png", &png[..png.len() / 2]),
+            Some(SuspectReason::Undecodable(_))
+        ));
+        assert!(matches!(
+            check("photo.jpg", &[0xFF, 0xD8, 0xFF, 0xE0, 0, 0x10]),
+            Some(SuspectReason::Undecodable(_))
+        ));
+        // Raw files are TIFF-based but not decoded
+        assert_eq!(check("photo.dng", b"II*\0 rest of a raw file"), None);
+    }
+
+    #[test]
+    fn test_unreadable_file_is_suspect() {
+        let missing = Path::new("/nonexistent/visualvault/photo.jpg");
+        assert!(matches!(
+            check_file_header(missing, "jpg", 10),
+            Some(SuspectReason::Unreadable(_))
+        ));
+    }
+
+    #[test]
+    fn test_format_extension() {
+        assert_eq!(format_extension("JPEG"), Some("jpg"));
+        assert_eq!(format_extension("HEIF"), Some("heic"));
+        assert_eq!(format_extension("PDF"), None);
+    }
+}
@@ -0,0 +1,315 @@
+use color_eyre::eyre::{Result, eyre};
+use image::DynamicImage;
+use imagesize::{Compression, ImageType};
+use std::fs::File;
+use std::io::{BufReader, ErrorKind, Read, Seek, SeekFrom};
+use std::path::Path;
+
+use crate::read_exif_thumbnail;
+
+/// Bytes read to recognize a HEIF image by its `ftyp` box
+const HEADER_SIZE: u64 = 64;
+
+/// Major brands of HEIF images whose codec is not known from the `ftyp` box
+const HEIF_BRANDS: &[&[u8; 4]] = &[b"mif1", b"msf1", b"mif2", b"miaf"];
+
+/// Dimensions of a HEIF image's primary item
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeifImage {
+    pub width: u32,
+    pub height: u32,
+    /// "HEIC" or "AVIF" when the brand names the codec, otherwise "HEIF"
+    pub format: &'static str,
+}
+
+/// Reads the dimensions of a HEIF (HEIC) image without decoding it.
+///
+/// Returns `None` if the file is not a HEIF image or declares no size for its primary item.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be opened or read.
+pub fn read_heif_image(path: &Path) -> Result<Option<HeifImage>> {
+    let mut header = Vec::new();
+    File::open(path)?.take(HEADER_SIZE).read_to_end(&mut header)?;
+    let Some(compression) = heif_compression(&header) else {
+        return Ok(None);
+    };
+
+    let size = match imagesize::size(path) {
+        Ok(size) => size,
+        // Running out of data means the file declares no size
+        Err(imagesize::ImageError::IoError(e)) if e.kind() != ErrorKind::UnexpectedEof => return Err(e.into()),
+        Err(_) => return Ok(None),
+    };
+    let (Ok(width), Ok(height)) = (u32::try_from(size.width), u32::try_from(size.height)) else {
+        return Ok(None);
+    };
+    let format = match compression {
+        Compression::Hevc => "HEIC",
+        Compression::Av1 => "AVIF",
+        Compression::Jpeg | Compression::Unknown => "HEIF",
+    };
+    Ok(Some(HeifImage { width, height, format }))
+}
+
+/// Decodes the primary image of a HEIF (HEIC) file.
+///
+/// With the `heif` feature the image is decoded with libheif. Without it, or when libheif cannot
+/// decode the file, e.g. for lack of an HEVC decoder plugin, the JPEG preview embedded in its
+/// EXIF data is decoded instead, which is much smaller than the image itself.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read, or cannot be decoded and has no preview.
+pub fn decode_heif_image(path: &Path) -> Result<DynamicImage> {
+    #[cfg(feature = "heif")]
+    {
+        match libheif::decode(path) {
+            Ok(image) => return Ok(image),
+            Err(e) => tracing::debug!("libheif could not decode {}: {}", path.display(), e),
+        }
+    }
+
+    let preview = read_exif_thumbnail(path)?.ok_or_else(|| eyre!("HEIF image has no embedded preview"))?;
+    Ok(image::load_from_memory(&preview)?)
+}
+
+/// Channels and bit depth of a HEIF image's primary item, such as "RGB 10-bit".
+///
+/// Only known with the `heif` feature, as the container does not tell.
+#[cfg(feature = "heif")]
+pub(crate) fn heif_color_type(path: &Path) -> Option<String> {
+    libheif::color_type(path)
+}
+
+/// Channels and bit depth of a HEIF image's primary item, such as "RGB 10-bit".
+///
+/// Only known with the `heif` feature, as the container does not tell.
+#[cfg(not(feature = "heif"))]
+pub(crate) const fn heif_color_type(_path: &Path) -> Option<String> {
+    None
+}
+
+#[cfg(feature = "heif")]
+mod libheif {
+    use color_eyre::eyre::{Result, eyre};
+    use image::{DynamicImage, RgbImage};
+    use libheif_rs::{ColorSpace, HeifContext, LibHeif, RgbChroma};
+    use std::path::Path;
+
+    fn open(path: &Path) -> Result<HeifContext<'static>> {
+        let name = path
+            .to_str()
+            .ok_or_else(|| eyre!("{} is not valid UTF-8", path.display()))?;
+        Ok(HeifContext::read_from_file(name)?)
+    }
+
+    /// Decodes the primary item to 8-bit RGB, applying its rotation, mirroring and cropping
+    pub(super) fn decode(path: &Path) -> Result<DynamicImage> {
+        let handle = open(path)?.primary_image_handle()?;
+        let image = LibHeif::new().decode(&handle, ColorSpace::Rgb(RgbChroma::Rgb), None)?;
+        let plane = image
+            .planes()
+            .interleaved
+            .ok_or_else(|| eyre!("libheif returned no interleaved RGB plane"))?;
+
+        // Rows may be padded beyond their pixels
+        let row_bytes = usize::try_from(plane.width)? * 3;
+        let mut pixels = Vec::with_capacity(row_bytes * usize::try_from(plane.height)?);
+        for row in plane.data.chunks(plane.stride).take(usize::try_from(plane.height)?) {
+            pixels.extend_from_slice(
+                row.get(..row_bytes)
+                    .ok_or_else(|| eyre!("short row in decoded image"))?,
+            );
+        }
+        let rgb = RgbImage::from_raw(plane.width, plane.height, pixels)
+            .ok_or_else(|| eyre!("decoded image does not match its size"))?;
+        Ok(DynamicImage::ImageRgb8(rgb))
+    }
+
+    pub(super) fn color_type(path: &Path) -> Option<String> {
+        let handle = open(path).ok()?.primary_image_handle().ok()?;
+        let channels = if handle.has_alpha_channel() { "RGBA" } else { "RGB" };
+        Some(format!("{channels} {}-bit", handle.luma_bits_per_pixel()))
+    }
+}
+
+/// Reads the TIFF structure of a HEIF image's Exif item, if it has one
+pub(crate) fn read_heif_exif(file: &mut File) -> Result<Option<Vec<u8>>> {
+    file.seek(SeekFrom::Start(0))?;
+    // The `exif` crate, not this crate's module of the same name
+    match ::exif::Reader::new().read_from_container(&mut BufReader::new(file)) {
+        Ok(exif) => Ok(Some(exif.buf().to_vec())),
+        Err(::exif::Error::Io(e)) => Err(e.into()),
+        Err(_) => Ok(None),
+    }
+}
+
+/// Returns true if the `ftyp` box at the start of `header` names a HEIF brand
+pub(crate) fn is_heif(header: &[u8]) -> bool {
+    heif_compression(header).is_some()
+}
+
+/// Codec of the HEIF image `header` starts, or `None` when it is not a HEIF image
+fn heif_compression(header: &[u8]) -> Option<Compression> {
+    match imagesize::image_type(header) {
+        // Any other ISO media file, such as an MP4 video, is reported as HEIF of unknown coding
+        Ok(ImageType::Heif(Compression::Unknown)) => header
+            .get(8..12)
+            .is_some_and(|brand| HEIF_BRANDS.iter().any(|heif| brand == heif.as_slice()))
+            .then_some(Compression::Unknown),
+        Ok(ImageType::Heif(compression)) => Some(compression),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    #![allow(clippy::unwrap_used)]
+    #![allow(clippy::panic_in_result_fn)]
+    #![allow(clippy::unwrap_in_result)]
+    #![allow(clippy::cast_possible_truncation)]
+
+    use super::*;
+    use tempfile::TempDir;
+
+    pub(crate) fn make_box(kind: [u8; 4], body: &[u8]) -> Vec<u8> {
+        let mut data = ((body.len() + 8) as u32).to_be_bytes().to_vec();
+        data.extend_from_slice(&kind);
+        data.extend_from_slice(body);
+        data
+    }
+
+    pub(crate) fn full_box(kind: [u8; 4], version: u8, body: &[u8]) -> Vec<u8> {
+        let mut data = vec![version, 0, 0, 0];
+        data.extend_from_slice(body);
+        make_box(kind, &data)
+    }
+
+    /// Builds a HEIC file with a 4032x3024 primary image and an Exif item holding `tiff`
+    pub(crate) fn heic_file(tiff: &[u8]) -> Vec<u8> {
+        let ftyp = make_box(*b"ftyp", b"heic\0\0\0\0mif1heic");
+
+        let infe = |id: u16, kind: &[u8; 4]| {
+            let mut body = id.to_be_bytes().to_vec();
+            body.extend_from_slice(&[0, 0]);
+            body.extend_from_slice(kind);
+            full_box(*b"infe", 2, &body)
+        };
+        let mut iinf_body = 2u16.to_be_bytes().to_vec();
+        iinf_body.extend(infe(1, b"hvc1"));
+        iinf_body.extend(infe(2, b"Exif"));
+        let iinf = full_box(*b"iinf", 0, &iinf_body);
+
+        let pitm = full_box(*b"pitm", 0, &1u16.to_be_bytes());
+
+        let mut ispe_body = 4032u32.to_be_bytes().to_vec();
+        ispe_body.extend_from_slice(&3024u32.to_be_bytes());
+        let mut ipco = full_box(*b"ispe", 0, &ispe_body);
+        ipco.extend(full_box(*b"pixi", 0, &[3, 10, 10, 10]));
+        let mut ipma_body = 1u32.to_be_bytes().to_vec();
+        ipma_body.extend_from_slice(&1u16.to_be_bytes());
+        ipma_body.extend_from_slice(&[2, 0x81, 0x02]);
+        let mut iprp = make_box(*b"ipco", &ipco);
+        iprp.extend(full_box(*b"ipma", 0, &ipma_body));
+        let iprp = make_box(*b"iprp", &iprp);
+
+        let mut exif = 6u32.to_be_bytes().to_vec();
+        exif.extend_from_slice(b"Exif\0\0");
+        exif.extend_from_slice(tiff);
+
+        // iloc with 4-byte offsets and lengths; the offset is patched once the layout is known
+        let iloc_len = 8 + 4 + 2 + 2 + 2 * (2 + 2 + 2 + 8);
+        let meta_len = 8 + 4 + pitm.len() + iinf.len() + iprp.len() + iloc_len;
+        let exif_offset = (ftyp.len() + meta_len + 8) as u32;
+
+        let mut iloc_body = vec![0x44, 0x00];
+        iloc_body.extend_from_slice(&2u16.to_be_bytes());
+        for (id, offset, length) in [(1u16, exif_offset, 0u32), (2, exif_offset, exif.len() as u32)] {
+            iloc_body.extend_from_slice(&id.to_be_bytes());
+            iloc_body.extend_from_slice(&0u16.to_be_bytes());
+            iloc_body.extend_from_slice(&1u16.to_be_bytes());
+            iloc_body.extend_from_slice(&offset.to_be_bytes());
+            iloc_body.extend_from_slice(&length.to_be_bytes());
+        }
+        let iloc = full_box(*b"iloc", 0, &iloc_body);
+
+        let mut meta_body = pitm;
+        meta_body.extend(iinf);
+        meta_body.extend(iprp);
+        meta_body.extend(iloc);
+        let meta = full_box(*b"meta", 0, &meta_body);
+        assert_eq!(meta.len(), meta_len);
+
+        let mut file = ftyp;
+        file.extend(meta);
+        file.extend(make_box(*b"mdat", &exif));
+        file
+    }
+
+    #[test]
+    fn test_read_heif_image() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let path = temp_dir.path().join("IMG_0001.HEIC");
+        // A little-endian TIFF with an empty IFD0
+        let tiff = b"II*\0\x08\0\0\0\0\0\0\0\0\0";
+        std::fs::write(&path, heic_file(tiff))?;
+
+        let image = read_heif_image(&path)?.unwrap();
+        assert_eq!((image.width, image.height, image.format), (4032, 3024, "HEIC"));
+
+        let exif = read_heif_exif(&mut File::open(&path)?)?;
+        assert_eq!(exif.as_deref(), Some(tiff.as_slice()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_falls_back_to_preview() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let mut preview = std::io::Cursor::new(Vec::new());
+        image::RgbImage::new(160, 120).write_to(&mut preview, image::ImageFormat::Jpeg)?;
+        let preview = preview.into_inner();
+
+        // An empty IFD0 at 8 whose next IFD describes the preview
+        let ifd1: u32 = 8 + 2 + 4;
+        let preview_start = ifd1 + 2 + 2 * 12 + 4;
+        let mut tiff = b"MM\0*".to_vec();
+        tiff.extend_from_slice(&8u32.to_be_bytes());
+        tiff.extend_from_slice(&0u16.to_be_bytes());
+        tiff.extend_from_slice(&ifd1.to_be_bytes());
+        tiff.extend_from_slice(&2u16.to_be_bytes());
+        for (tag, value) in [(0x0201u16, preview_start), (0x0202, preview.len() as u32)] {
+            tiff.extend_from_slice(&tag.to_be_bytes());
+            tiff.extend_from_slice(&4u16.to_be_bytes());
+            tiff.extend_from_slice(&1u32.to_be_bytes());
+            tiff.extend_from_slice(&value.to_be_bytes());
+        }
+        tiff.extend_from_slice(&0u32.to_be_bytes());
+        tiff.extend_from_slice(&preview);
+
+        // The primary image has no coded data, so even libheif cannot decode it
+        let path = temp_dir.path().join("IMG_0001.HEIC");
+        std::fs::write(&path, heic_file(&tiff))?;
+        let image = decode_heif_image(&path)?;
+        assert_eq!((image.width(), image.height()), (160, 120));
+
+        let path = temp_dir.path().join("IMG_0002.HEIC");
+        std::fs::write(&path, heic_file(b"II*\0\x08\0\0\0\0\0\0\0\0\0"))?;
+        assert!(decode_heif_image(&path).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_non_heif_files() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let path = temp_dir.path().join("video.mp4");
+        std::fs::write(&path, make_box(*b"ftyp", b"isom\0\0\0\0isomavc1"))?;
+        assert!(read_heif_image(&path)?.is_none());
+
+        let path = temp_dir.path().join("photo.jpg");
+        std::fs::write(&path, [0xff, 0xd8, 0xff, 0xd9])?;
+        assert!(read_heif_image(&path)?.is_none());
+        Ok(())
+    }
+}
@@ -0,0 +1,152 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+/// Name of the lock file in the config directory
+pub const LOCK_FILE_NAME: &str = "visualvault.lock";
+
+#[derive(Debug, thiserror::Error)]
+pub enum InstanceLockError {
+    /// Another running instance holds the lock
+    #[error("{}", held_message(*pid))]
+    Held { pid: Option<u32> },
+    #[error("Could not lock {path}: {source}")]
+    Io { path: PathBuf, source: io::Error },
+}
+
+fn held_message(pid: Option<u32>) -> String {
+    pid.map_or_else(
+        || "Another VisualVault instance is running".to_string(),
+        |pid| format!("Another VisualVault instance is running (pid {pid})"),
+    )
+}
+
+/// Keeps other instances from changing files while this one runs.
+///
+/// The lock is an advisory lock on a file in the config directory that holds the process id
+/// of its owner. The operating system releases it when the process exits, so a crash never
+/// leaves a stale lock behind.
+#[derive(Debug)]
+pub struct InstanceLock {
+    _file: File,
+    path: PathBuf,
+}
+
+impl InstanceLock {
+    /// Takes the lock of `config_dir`, creating the lock file if needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InstanceLockError::Held`] if another instance holds the lock, or
+    /// [`InstanceLockError::Io`] if the lock file cannot be created or written.
+    pub fn acquire(config_dir: &Path) -> Result<Self, InstanceLockError> {
+        let path = config_dir.join(LOCK_FILE_NAME);
+        let io_error = |source| InstanceLockError::Io {
+            path: path.clone(),
+            source,
+        };
+
+        fs::create_dir_all(config_dir).map_err(io_error)?;
+        let mut file = match open_locked(&path) {
+            Ok(Some(file)) => file,
+            Ok(None) => return Err(InstanceLockError::Held { pid: read_pid(&path) }),
+            Err(e) => return Err(io_error(e)),
+        };
+
+        file.set_len(0)
+            .and_then(|()| file.seek(SeekFrom::Start(0)))
+            .and_then(|_| write!(file, "{}", std::process::id()))
+            .and_then(|()| file.flush())
+            .map_err(io_error)?;
+        Ok(Self { _file: file, path })
+    }
+
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+/// Reads the process id written by the instance holding the lock
+fn read_pid(path: &Path) -> Option<u32> {
+    let mut contents = String::new();
+    File::open(path).ok()?.read_to_string(&mut contents).ok()?;
+    contents.trim().parse().ok()
+}
+
+/// Opens the lock file and locks it, or returns `None` if another process holds the lock
+#[cfg(unix)]
+fn open_locked(path: &Path) -> io::Result<Option<File>> {
+    use std::os::fd::AsRawFd;
+
+    let file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)?;
+    // SAFETY: the descriptor belongs to `file`, which outlives the call
+    if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX | libc::LOCK_NB) } == 0 {
+        return Ok(Some(file));
+    }
+    let error = io::Error::last_os_error();
+    if error.kind() == io::ErrorKind::WouldBlock {
+        Ok(None)
+    } else {
+        Err(error)
+    }
+}
+
+/// Opens the lock file without sharing, which other processes see as a sharing violation
+/// until this one exits
+#[cfg(windows)]
+fn open_locked(path: &Path) -> io::Result<Option<File>> {
+    use std::os::windows::fs::OpenOptionsExt;
+
+    const ERROR_SHARING_VIOLATION: i32 = 32;
+    match OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .share_mode(0)
+        .open(path)
+    {
+        Ok(file) => Ok(Some(file)),
+        Err(e) if e.raw_os_error() == Some(ERROR_SHARING_VIOLATION) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+fn open_locked(path: &Path) -> io::Result<Option<File>> {
+    OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
+        .map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_second_lock_is_refused_until_first_is_dropped() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_dir = temp_dir.path().join("visualvault");
+
+        let lock = InstanceLock::acquire(&config_dir).unwrap();
+        assert_eq!(fs::read_to_string(lock.path()).unwrap(), std::process::id().to_string());
+
+        let second = InstanceLock::acquire(&config_dir);
+        assert!(matches!(second, Err(InstanceLockError::Held { pid }) if pid == Some(std::process::id())));
+
+        drop(lock);
+        assert!(InstanceLock::acquire(&config_dir).is_ok());
+    }
+}
@@ -1,15 +1,107 @@
+mod activity_log;
+mod archive;
+mod audio_tags;
+mod batch_rename;
+mod burst;
 mod cache;
+mod checksums;
+mod clipboard;
+mod cloud_upload;
+mod collections;
+mod concurrency;
 mod database_cache;
+mod date_fix;
+mod desktop_notification;
+mod destination_index;
+mod device_import;
+mod disk_space;
 mod duplicate_detector;
+mod empty_dirs;
+mod exif;
+mod external_open;
+mod file_browser;
+mod file_list;
 mod file_manager;
+mod file_store;
+mod folder_stats;
+mod header_check;
+mod heif;
+mod instance_lock;
+mod library_index;
+mod link_dedupe;
+mod live_photo;
+mod manifest;
+mod metadata_service;
+mod metadata_stats;
+mod notifier;
+mod organize_hooks;
+mod organized_ledger;
 mod organizer;
+mod path_input;
+mod png;
+mod preflight;
+mod rclone;
 mod scanner;
+mod scheduler;
+mod skip_rules;
+mod space_explorer;
+#[cfg(test)]
+mod test_support;
+mod throttle;
+mod transfer;
+mod undo_journal;
 mod undo_manager;
+mod video_similarity;
 
+pub use activity_log::ActivityLog;
+pub use archive::{extract_archive_media, is_archive, list_archive};
+pub use audio_tags::read_audio_metadata;
+pub use batch_rename::{apply_batch_rename, plan_batch_rename};
+pub use burst::find_bursts;
 pub use cache::Cache;
-pub use database_cache::DatabaseCache;
+pub use checksums::{SFV_FILE, SHA256SUMS_FILE, is_checksum_sidecar, write_checksum_sidecars};
+pub use clipboard::{ClipboardMethod, copy_to_clipboard};
+pub use cloud_upload::S3Uploader;
+pub use collections::CollectionStore;
+pub use concurrency::{ConcurrencyPlan, StorageKind};
+pub use database_cache::{CacheEntry, DatabaseCache, ExtensionStats, FileQuery, ImportRecord};
+pub use date_fix::{DATE_MISMATCH_THRESHOLD, find_date_mismatches, fix_dates};
+pub use desktop_notification::send_desktop_notification;
+pub use destination_index::{DestinationIndex, DestinationIndexInfo};
+pub use device_import::{ImportedIndex, find_camera_volumes};
+pub use disk_space::project_space;
 pub use duplicate_detector::DuplicateDetector;
+pub use empty_dirs::{find_empty_directories, remove_empty_directories};
+pub use exif::{read_exif_camera, read_exif_capture, read_exif_dates, read_exif_thumbnail, write_exif_taken};
+pub use external_open::{open_with_default_app, reveal_in_file_manager};
+pub use file_browser::{list_folder, list_subfolders};
+pub use file_list::{FileList, PAGED_SEARCH_LIMIT, YearTotals};
 pub use file_manager::FileManager;
+pub use file_store::FileStore;
+pub use folder_stats::{FOLDER_STATS_DEBOUNCE, FolderStatsPool, FolderStatsUpdate, calculate_folder_stats};
+pub use header_check::{check_file_header, format_extension};
+pub use heif::{HeifImage, decode_heif_image, read_heif_image};
+pub use instance_lock::{InstanceLock, InstanceLockError, LOCK_FILE_NAME};
+pub use library_index::{LibraryIndex, export_verify_report};
+pub use link_dedupe::{LinkSupport, LinkedDuplicate, link_duplicates};
+pub use live_photo::{is_live_photo_part, live_photo_partners, pair_live_photos, read_live_photo_id};
+pub use manifest::{
+    MANIFEST_FILE, find_manifest_folders, plan_manifest_restore, read_manifest, restore_from_manifest, write_manifests,
+};
+pub use metadata_service::{METADATA_CACHE_CAPACITY, MetadataKey, MetadataService, read_image_metadata};
+pub use metadata_stats::collect_metadata_stats;
+pub use notifier::Notifier;
+pub use organize_hooks::{HOOK_PROTOCOL_VERSION, HOOK_TIMEOUT, HookDecision, OrganizeHooks};
+pub use organized_ledger::OrganizedLedger;
 pub use organizer::FileOrganizer;
-pub use scanner::Scanner;
-pub use undo_manager::UndoManager;
+pub use path_input::{PathCompletion, check_folder, complete_path};
+pub use png::{PngImage, read_png_image};
+pub use preflight::{folder_overlap, preflight_organize, preflight_scan};
+pub use rclone::RcloneTransfer;
+pub use scanner::{ScanCancelled, Scanner};
+pub use scheduler::Scheduler;
+pub use space_explorer::{FolderUsage, SpaceReport, explore_space};
+pub use throttle::IoThrottle;
+pub use transfer::{Transfer, TransferMethod};
+pub use undo_manager::{DeleteOperation, MoveOperation, TimestampField, TimestampOperation, UndoManager};
+pub use video_similarity::find_similar_videos;
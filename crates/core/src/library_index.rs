@@ -0,0 +1,341 @@
+use chrono::{DateTime, Local};
+use color_eyre::Result;
+use rayon::prelude::{IntoParallelIterator, ParallelIterator};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use tracing::warn;
+use visualvault_models::{IndexedFile, VerifyIssue, VerifyIssueKind, VerifyReport};
+use walkdir::WalkDir;
+
+use crate::is_checksum_sidecar;
+
+const LIBRARY_INDEX_FILE: &str = "library_index.json";
+
+/// Content hashes of the files in each organized library, used to detect damage later.
+///
+/// The index is stored as `library_index.json` in the config directory and holds one set of
+/// entries per library root, keyed by the path relative to that root.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct LibraryIndex {
+    libraries: BTreeMap<PathBuf, BTreeMap<PathBuf, IndexedFile>>,
+    #[serde(skip)]
+    path: Option<PathBuf>,
+}
+
+impl LibraryIndex {
+    /// Loads the index stored in `config_dir`, or an empty one if there is none yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the index file exists but cannot be read or parsed.
+    pub fn load(config_dir: &Path) -> Result<Self> {
+        let path = config_dir.join("visualvault").join(LIBRARY_INDEX_FILE);
+        let mut index = if path.exists() {
+            serde_json::from_str(&fs::read_to_string(&path)?)?
+        } else {
+            Self::default()
+        };
+        index.path = Some(path);
+        Ok(index)
+    }
+
+    /// Writes the index back to the file it was loaded from.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the index cannot be serialized or written.
+    pub fn save(&self) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string(self)?)?;
+        Ok(())
+    }
+
+    /// Number of files indexed for `library`
+    #[must_use]
+    pub fn len(&self, library: &Path) -> usize {
+        self.libraries.get(library).map_or(0, BTreeMap::len)
+    }
+
+    /// Re-hashes every file under `library` and compares it with the stored index.
+    ///
+    /// Files not yet in the index are hashed and added, so the first run records a baseline.
+    /// Entries of files that fail verification are left as they were until the changes are
+    /// accepted with [`LibraryIndex::accept`]. Hidden files are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `library` is not a readable directory.
+    pub fn verify(&mut self, library: &Path) -> Result<VerifyReport> {
+        if !library.is_dir() {
+            return Err(color_eyre::eyre::eyre!(
+                "Library folder not found: {}",
+                library.display()
+            ));
+        }
+
+        let on_disk = library_files(library);
+        let entries = self.libraries.entry(library.to_path_buf()).or_default();
+        let mut report = VerifyReport::new(library.to_path_buf());
+
+        for (relative, expected) in entries.iter() {
+            if !on_disk.contains_key(relative) {
+                report.issues.push(VerifyIssue {
+                    path: relative.clone(),
+                    kind: VerifyIssueKind::Missing,
+                    expected: expected.clone(),
+                    actual: None,
+                    error: None,
+                });
+            }
+        }
+
+        let hashed: Vec<_> = on_disk
+            .into_par_iter()
+            .map(|(relative, path)| {
+                let actual = hash_file(&path);
+                (relative, actual)
+            })
+            .collect();
+
+        for (relative, actual) in hashed {
+            let Some(expected) = entries.get_mut(&relative) else {
+                match actual {
+                    Ok(actual) => {
+                        entries.insert(relative, actual);
+                        report.added += 1;
+                    }
+                    Err(e) => warn!("Could not index {}: {}", relative.display(), e),
+                }
+                continue;
+            };
+
+            match actual {
+                Ok(actual) if actual.sha256 == expected.sha256 => *expected = actual,
+                Ok(actual) => {
+                    let untouched = actual.size == expected.size && actual.modified == expected.modified;
+                    report.issues.push(VerifyIssue {
+                        path: relative,
+                        kind: if untouched {
+                            VerifyIssueKind::Corrupted
+                        } else {
+                            VerifyIssueKind::Modified
+                        },
+                        expected: expected.clone(),
+                        actual: Some(actual),
+                        error: None,
+                    });
+                }
+                Err(e) => report.issues.push(VerifyIssue {
+                    path: relative,
+                    kind: VerifyIssueKind::Corrupted,
+                    expected: expected.clone(),
+                    actual: None,
+                    error: Some(e.to_string()),
+                }),
+            }
+        }
+
+        report.checked = entries.len() - report.added;
+        report.issues.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(report)
+    }
+
+    /// Makes the index match what `report` found on disk.
+    ///
+    /// Missing and unreadable files are dropped from the index and changed files are stored
+    /// with their current hash.
+    pub fn accept(&mut self, report: &VerifyReport) {
+        let Some(entries) = self.libraries.get_mut(&report.library) else {
+            return;
+        };
+        for issue in &report.issues {
+            match &issue.actual {
+                Some(actual) => {
+                    entries.insert(issue.path.clone(), actual.clone());
+                }
+                None => {
+                    entries.remove(&issue.path);
+                }
+            }
+        }
+    }
+}
+
+/// Writes `report` to `path` as pretty-printed JSON, creating parent directories as needed.
+///
+/// # Errors
+///
+/// Returns an error if the report cannot be serialized or written.
+pub fn export_verify_report(report: &VerifyReport, path: &Path) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, serde_json::to_string_pretty(report)?)?;
+    Ok(())
+}
+
+/// Lists the regular files below `library`, keyed by their path relative to it. Checksum files
+/// change with every organize run, so they are left out.
+fn library_files(library: &Path) -> BTreeMap<PathBuf, PathBuf> {
+    WalkDir::new(library)
+        .into_iter()
+        .filter_entry(|entry| entry.depth() == 0 || !entry.file_name().to_string_lossy().starts_with('.'))
+        .filter_map(|entry| match entry {
+            Ok(entry) => Some(entry),
+            Err(e) => {
+                warn!("Skipping unreadable library entry: {}", e);
+                None
+            }
+        })
+        .filter(|entry| entry.file_type().is_file() && !is_checksum_sidecar(entry.file_name()))
+        .filter_map(|entry| {
+            let relative = entry.path().strip_prefix(library).ok()?.to_path_buf();
+            Some((relative, entry.into_path()))
+        })
+        .collect()
+}
+
+pub(crate) fn hash_file(path: &Path) -> std::io::Result<IndexedFile> {
+    let mut file = File::open(path)?;
+    let metadata = file.metadata()?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0; 65536];
+
+    loop {
+        let bytes_read = file.read(&mut buffer)?;
+        if bytes_read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..bytes_read]);
+    }
+
+    Ok(IndexedFile {
+        size: metadata.len(),
+        modified: DateTime::<Local>::from(metadata.modified()?),
+        sha256: format!("{:x}", hasher.finalize()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    #![allow(clippy::panic_in_result_fn)]
+    #![allow(clippy::unwrap_in_result)]
+
+    use super::*;
+    use std::time::{Duration, SystemTime};
+    use tempfile::TempDir;
+
+    fn set_modified(path: &Path, time: SystemTime) -> Result<()> {
+        File::options().write(true).open(path)?.set_modified(time)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_first_verify_records_baseline() -> Result<()> {
+        let config = TempDir::new()?;
+        let library = TempDir::new()?;
+        fs::create_dir_all(library.path().join("2024/01"))?;
+        fs::write(library.path().join("2024/01/a.jpg"), b"a")?;
+        fs::write(library.path().join("2024/01/b.jpg"), b"b")?;
+        fs::write(library.path().join(".DS_Store"), b"ignored")?;
+
+        let mut index = LibraryIndex::load(config.path())?;
+        let report = index.verify(library.path())?;
+        assert_eq!(report.added, 2);
+        assert_eq!(report.checked, 0);
+        assert!(report.is_clean());
+
+        index.save()?;
+        let reloaded = LibraryIndex::load(config.path())?;
+        assert_eq!(reloaded.len(library.path()), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_verify_reports_missing_modified_and_corrupted() -> Result<()> {
+        let config = TempDir::new()?;
+        let library = TempDir::new()?;
+        let dir = library.path();
+        let baseline = SystemTime::now() - Duration::from_secs(3600);
+        for name in ["intact.jpg", "missing.jpg", "modified.jpg", "corrupted.jpg"] {
+            fs::write(dir.join(name), b"original")?;
+            set_modified(&dir.join(name), baseline)?;
+        }
+
+        let mut index = LibraryIndex::load(config.path())?;
+        index.verify(dir)?;
+
+        fs::remove_file(dir.join("missing.jpg"))?;
+        fs::write(dir.join("modified.jpg"), b"edited in place")?;
+        // Same size and timestamp, different bytes
+        fs::write(dir.join("corrupted.jpg"), b"origXnal")?;
+        set_modified(&dir.join("corrupted.jpg"), baseline)?;
+        fs::write(dir.join("new.jpg"), b"new")?;
+
+        let report = index.verify(dir)?;
+        assert_eq!(report.checked, 4);
+        assert_eq!(report.added, 1);
+        let kinds: Vec<_> = report
+            .issues
+            .iter()
+            .map(|issue| (issue.path.to_string_lossy().to_string(), issue.kind))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("corrupted.jpg".to_string(), VerifyIssueKind::Corrupted),
+                ("missing.jpg".to_string(), VerifyIssueKind::Missing),
+                ("modified.jpg".to_string(), VerifyIssueKind::Modified),
+            ]
+        );
+
+        // Issues are reported again until accepted
+        assert_eq!(index.verify(dir)?.issues.len(), 3);
+        index.accept(&report);
+        let report = index.verify(dir)?;
+        assert!(report.is_clean());
+        assert_eq!(index.len(dir), 4);
+        Ok(())
+    }
+
+    #[test]
+    fn test_export_verify_report() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let mut report = VerifyReport::new(temp_dir.path().to_path_buf());
+        report.issues.push(VerifyIssue {
+            path: PathBuf::from("photo.jpg"),
+            kind: VerifyIssueKind::Missing,
+            expected: IndexedFile {
+                size: 1,
+                modified: Local::now(),
+                sha256: "abc".to_string(),
+            },
+            actual: None,
+            error: None,
+        });
+
+        let path = temp_dir.path().join("reports/verify.json");
+        export_verify_report(&report, &path)?;
+
+        let json: serde_json::Value = serde_json::from_str(&fs::read_to_string(&path)?)?;
+        assert_eq!(json["issues"][0]["kind"], "missing");
+        assert_eq!(json["issues"][0]["path"], "photo.jpg");
+        Ok(())
+    }
+
+    #[test]
+    fn test_verify_requires_existing_library() {
+        let mut index = LibraryIndex::default();
+        assert!(index.verify(Path::new("/nonexistent/library")).is_err());
+    }
+}
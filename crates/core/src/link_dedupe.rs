@@ -0,0 +1,211 @@
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io;
+use std::path::{Path, PathBuf};
+
+use tracing::warn;
+use visualvault_models::LinkMethod;
+
+use crate::disk_space::volume_of;
+
+/// Name of the file created next to duplicates to find out how their file system links files
+const PROBE_FILE: &str = ".visualvault-link-probe";
+
+/// A duplicate that now shares the storage of the copy that was kept
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinkedDuplicate {
+    pub path: PathBuf,
+    pub kept: PathBuf,
+    pub method: LinkMethod,
+}
+
+/// How duplicates can be linked on each file system, probed once per file system by
+/// linking a kept copy next to a duplicate.
+///
+/// Reflinks are preferred, as the linked files stay independent; file systems without them
+/// fall back to hardlinks, and those without either, such as FAT, cannot be linked at all.
+#[derive(Debug, Default)]
+pub struct LinkSupport {
+    volumes: HashMap<u64, Option<LinkMethod>>,
+}
+
+impl LinkSupport {
+    /// How `duplicate` can be replaced by a link to `kept`, or `None` if the files are on
+    /// different file systems or links are not supported
+    pub fn method(&mut self, kept: &Path, duplicate: &Path) -> Option<LinkMethod> {
+        let folder = duplicate.parent()?;
+        let volume = volume_of(folder)?;
+        if volume_of(kept)? != volume {
+            return None;
+        }
+        *self.volumes.entry(volume).or_insert_with(|| probe(kept, folder))
+    }
+}
+
+/// Replaces each duplicate with a link to `kept`, so every path stays in place while the
+/// space of the copies is reclaimed.
+///
+/// Each link is made under a temporary name and renamed over the duplicate, so a duplicate is
+/// never lost halfway. Duplicates already linked to `kept` are skipped; failures are logged
+/// and skipped.
+#[must_use]
+pub fn link_duplicates(kept: &Path, duplicates: &[PathBuf], support: &mut LinkSupport) -> Vec<LinkedDuplicate> {
+    duplicates
+        .iter()
+        .filter(|duplicate| *duplicate != kept && !is_same_file(kept, duplicate))
+        .filter_map(|duplicate| {
+            let Some(method) = support.method(kept, duplicate) else {
+                warn!(
+                    "Cannot link {} to {}: not on the same file system or links are not supported",
+                    duplicate.display(),
+                    kept.display()
+                );
+                return None;
+            };
+            match replace_with_link(kept, duplicate, method) {
+                Ok(()) => Some(LinkedDuplicate {
+                    path: duplicate.clone(),
+                    kept: kept.to_path_buf(),
+                    method,
+                }),
+                Err(e) => {
+                    warn!("Failed to link {} to {}: {}", duplicate.display(), kept.display(), e);
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+fn replace_with_link(kept: &Path, duplicate: &Path, method: LinkMethod) -> io::Result<()> {
+    let name = duplicate
+        .file_name()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "duplicate has no file name"))?;
+    let temporary = duplicate.with_file_name(format!(".{}.vvlink", name.to_string_lossy()));
+    let modified = fs::metadata(duplicate)?.modified()?;
+
+    let linked = create_link(kept, &temporary, method).and_then(|()| {
+        // A reflink is a file of its own, which keeps the date of the duplicate it replaces
+        if method == LinkMethod::Reflink {
+            File::options().write(true).open(&temporary)?.set_modified(modified)?;
+        }
+        fs::rename(&temporary, duplicate)
+    });
+    if linked.is_err() {
+        let _ = fs::remove_file(&temporary);
+    }
+    linked
+}
+
+fn create_link(kept: &Path, link: &Path, method: LinkMethod) -> io::Result<()> {
+    match method {
+        LinkMethod::Reflink => reflink(kept, link),
+        LinkMethod::Hardlink => fs::hard_link(kept, link),
+    }
+}
+
+/// Finds out how files are linked in `folder` by linking `kept` into it
+fn probe(kept: &Path, folder: &Path) -> Option<LinkMethod> {
+    let probe = folder.join(PROBE_FILE);
+    let _ = fs::remove_file(&probe);
+    let method = [LinkMethod::Reflink, LinkMethod::Hardlink]
+        .into_iter()
+        .find(|method| create_link(kept, &probe, *method).is_ok() && fs::remove_file(&probe).is_ok());
+    tracing::info!(
+        "Duplicates in {} can be linked with {}",
+        folder.display(),
+        method.map_or_else(
+            || "neither reflinks nor hardlinks".to_string(),
+            |method| format!("{method}s")
+        )
+    );
+    method
+}
+
+#[cfg(unix)]
+fn is_same_file(a: &Path, b: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    match (fs::metadata(a), fs::metadata(b)) {
+        (Ok(a), Ok(b)) => a.dev() == b.dev() && a.ino() == b.ino(),
+        _ => false,
+    }
+}
+
+#[cfg(not(unix))]
+const fn is_same_file(_a: &Path, _b: &Path) -> bool {
+    false
+}
+
+/// Clones `source` into a new file at `target` sharing its storage
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn reflink(source: &Path, target: &Path) -> io::Result<()> {
+    use std::os::fd::AsRawFd;
+
+    let source = File::open(source)?;
+    let target_file = File::options().write(true).create_new(true).open(target)?;
+    // SAFETY: both descriptors belong to files that outlive the call
+    if unsafe { libc::ioctl(target_file.as_raw_fd(), libc::FICLONE, source.as_raw_fd()) } == 0 {
+        return Ok(());
+    }
+    let error = io::Error::last_os_error();
+    drop(target_file);
+    let _ = fs::remove_file(target);
+    Err(error)
+}
+
+/// Clones `source` into a new file at `target` sharing its storage
+#[cfg(target_os = "macos")]
+fn reflink(source: &Path, target: &Path) -> io::Result<()> {
+    use crate::disk_space::c_path;
+
+    let invalid = || io::Error::new(io::ErrorKind::InvalidInput, "path contains a NUL byte");
+    let source = c_path(source).ok_or_else(invalid)?;
+    let target = c_path(target).ok_or_else(invalid)?;
+    // SAFETY: both paths are NUL-terminated strings
+    if unsafe { libc::clonefile(source.as_ptr(), target.as_ptr(), 0) } == 0 {
+        Ok(())
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android", target_os = "macos")))]
+fn reflink(_source: &Path, _target: &Path) -> io::Result<()> {
+    Err(io::Error::new(io::ErrorKind::Unsupported, "reflinks are not supported"))
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    #[test]
+    fn test_link_duplicates_keeps_every_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let kept = dir.path().join("kept.jpg");
+        let copies: Vec<PathBuf> = ["copy.jpg", "nested/copy.jpg"]
+            .iter()
+            .map(|name| dir.path().join(name))
+            .collect();
+        fs::write(&kept, b"same photo").unwrap();
+        for copy in &copies {
+            fs::create_dir_all(copy.parent().unwrap()).unwrap();
+            fs::write(copy, b"same photo").unwrap();
+        }
+
+        let mut support = LinkSupport::default();
+        let linked = link_duplicates(&kept, &copies, &mut support);
+        assert_eq!(linked.len(), 2);
+        for copy in &copies {
+            assert_eq!(fs::read(copy).unwrap(), b"same photo");
+            assert!(!copy.with_file_name(".copy.jpg.vvlink").exists());
+        }
+        assert!(!dir.path().join(PROBE_FILE).exists());
+        // Hardlinked copies are recognized, so linking again finds nothing left to do
+        if cfg!(unix) && linked[0].method == LinkMethod::Hardlink {
+            assert!(is_same_file(&kept, &copies[0]));
+            assert!(link_duplicates(&kept, &copies, &mut support).is_empty());
+        }
+    }
+}
@@ -0,0 +1,343 @@
+use color_eyre::eyre::Result;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tracing::debug;
+use visualvault_models::MediaFile;
+
+use crate::exif::read_exif_content_identifier;
+
+/// Extensions of the still image of a Live Photo
+const STILL_EXTENSIONS: &[&str] = &["heic", "heif", "jpg", "jpeg"];
+
+/// Extension of the video of a Live Photo
+const VIDEO_EXTENSION: &str = "mov";
+
+/// Movie metadata key holding the identifier shared with the still image
+const CONTENT_IDENTIFIER_KEY: &[u8] = b"com.apple.quicktime.content.identifier";
+
+/// Top-level boxes larger than this are not loaded into memory
+const MAX_BOX_SIZE: u64 = 32 * 1024 * 1024;
+
+/// Reads the identifier Apple devices give both halves of a Live Photo.
+///
+/// Still images carry it in their EXIF maker note and videos in their movie metadata.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be opened or read.
+pub fn read_live_photo_id(path: &Path) -> Result<Option<String>> {
+    if is_video(path) {
+        read_quicktime_content_identifier(path)
+    } else {
+        read_exif_content_identifier(path)
+    }
+}
+
+/// Links the still image and video of each Live Photo among `files`.
+///
+/// This reads the headers of the candidates, so it blocks on file I/O; see
+/// [`live_photo_partners`].
+pub fn pair_live_photos(files: &mut [Arc<MediaFile>]) {
+    let mut partners = live_photo_partners(files.iter().map(|file| file.path.as_path()));
+    for file in files {
+        if let Some(partner) = partners.remove(&file.path) {
+            Arc::make_mut(file).live_photo_pair = Some(partner);
+        }
+    }
+}
+
+/// Finds the Live Photos among `paths`, mapping the path of each half to that of the other.
+///
+/// Candidates share a folder and file stem, such as `IMG_0001.HEIC` and `IMG_0001.MOV`, and
+/// are only paired when both carry the same content identifier. This reads the headers of
+/// the candidates, so it blocks on file I/O.
+pub fn live_photo_partners<'a>(paths: impl IntoIterator<Item = &'a Path>) -> HashMap<PathBuf, PathBuf> {
+    let mut candidates: HashMap<(PathBuf, String), (Option<&Path>, Option<&Path>)> = HashMap::new();
+    for path in paths {
+        if !is_live_photo_part(path) {
+            continue;
+        }
+        let (Some(parent), Some(stem)) = (path.parent(), path.file_stem()) else {
+            continue;
+        };
+
+        let entry = candidates
+            .entry((parent.to_path_buf(), stem.to_string_lossy().to_lowercase()))
+            .or_default();
+        if is_video(path) {
+            entry.1 = Some(path);
+        } else {
+            entry.0 = Some(path);
+        }
+    }
+
+    let mut partners = HashMap::new();
+    for (still, video) in candidates.into_values() {
+        let (Some(still), Some(video)) = (still, video) else {
+            continue;
+        };
+        if same_content_identifier(still, video) {
+            partners.insert(still.to_path_buf(), video.to_path_buf());
+            partners.insert(video.to_path_buf(), still.to_path_buf());
+        }
+    }
+    partners
+}
+
+/// Whether `path` could be the still image or video of a Live Photo, going by its extension
+#[must_use]
+pub fn is_live_photo_part(path: &Path) -> bool {
+    is_video(path)
+        || path
+            .extension()
+            .is_some_and(|ext| STILL_EXTENSIONS.iter().any(|still| ext.eq_ignore_ascii_case(still)))
+}
+
+fn is_video(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case(VIDEO_EXTENSION))
+}
+
+fn same_content_identifier(still: &Path, video: &Path) -> bool {
+    let read = |path: &Path| {
+        read_live_photo_id(path).unwrap_or_else(|e| {
+            debug!("Could not read Live Photo identifier of {}: {}", path.display(), e);
+            None
+        })
+    };
+    read(still).is_some_and(|id| read(video).is_some_and(|other| id == other))
+}
+
+fn read_quicktime_content_identifier(path: &Path) -> Result<Option<String>> {
+    let mut file = File::open(path)?;
+    let Some(moov) = read_top_level_box(&mut file, *b"moov")? else {
+        return Ok(None);
+    };
+    Ok(find_box(&moov, *b"meta").and_then(parse_quicktime_meta))
+}
+
+/// Looks up the content identifier in the `keys` and `ilst` boxes of movie metadata
+fn parse_quicktime_meta(meta: &[u8]) -> Option<String> {
+    // QuickTime's `meta` box has no version and flags, unlike the ISO one
+    let children = if meta.get(4..8) == Some(b"hdlr".as_slice()) {
+        meta
+    } else {
+        meta.get(4..)?
+    };
+    let keys = find_box(children, *b"keys")?;
+    let ilst = find_box(children, *b"ilst")?;
+
+    // Version and flags, the entry count, then (size, namespace, name) entries
+    let count = u32::from_be_bytes(keys.get(4..8)?.try_into().ok()?);
+    let mut pos = 8;
+    let mut index = None;
+    for key_index in 1..=count {
+        let size = u32::from_be_bytes(keys.get(pos..pos + 4)?.try_into().ok()?) as usize;
+        if keys.get(pos + 8..pos + size)? == CONTENT_IDENTIFIER_KEY {
+            index = Some(key_index);
+            break;
+        }
+        pos += size.max(8);
+    }
+    let index = index?;
+
+    // Items are boxes whose type is the 1-based key index
+    let item = boxes(ilst)
+        .find(|(kind, _)| u32::from_be_bytes(*kind) == index)
+        .map(|(_, body)| body)?;
+    // The value follows the type indicator and locale
+    let value = find_box(item, *b"data")?.get(8..)?;
+    let value = std::str::from_utf8(value).ok()?.trim_end_matches('\0').trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+/// Iterates the boxes in `data` as (type, body) pairs, stopping at the first malformed one
+fn boxes(data: &[u8]) -> impl Iterator<Item = ([u8; 4], &[u8])> {
+    let mut pos = 0;
+    std::iter::from_fn(move || {
+        let header = data.get(pos..pos + 8)?;
+        let kind: [u8; 4] = header[4..8].try_into().ok()?;
+        let (start, end) = match u32::from_be_bytes([header[0], header[1], header[2], header[3]]) {
+            0 => (pos + 8, data.len()),
+            1 => {
+                let size = u64::from_be_bytes(data.get(pos + 8..pos + 16)?.try_into().ok()?);
+                (pos + 16, pos.checked_add(usize::try_from(size).ok()?)?)
+            }
+            size => (pos + 8, pos + size as usize),
+        };
+        let body = data.get(start..end)?;
+        pos = end;
+        Some((kind, body))
+    })
+}
+
+/// Returns the body of the first child box of the given type
+fn find_box(data: &[u8], kind: [u8; 4]) -> Option<&[u8]> {
+    boxes(data).find(|(k, _)| *k == kind).map(|(_, body)| body)
+}
+
+/// Loads the body of the first top-level box of the given type, seeking past the others
+fn read_top_level_box(file: &mut File, kind: [u8; 4]) -> Result<Option<Vec<u8>>> {
+    let file_len = file.metadata()?.len();
+    let mut pos = 0;
+
+    while pos + 8 <= file_len {
+        file.seek(SeekFrom::Start(pos))?;
+        let mut header = [0u8; 8];
+        file.read_exact(&mut header)?;
+        let (header_len, size) = match u32::from_be_bytes([header[0], header[1], header[2], header[3]]) {
+            0 => (8, file_len - pos),
+            1 => {
+                let mut large = [0u8; 8];
+                file.read_exact(&mut large)?;
+                (16, u64::from_be_bytes(large))
+            }
+            size => (8, u64::from(size)),
+        };
+        if size < header_len {
+            return Ok(None);
+        }
+
+        if header[4..8] == kind {
+            let body_len = size - header_len;
+            if body_len > MAX_BOX_SIZE {
+                return Ok(None);
+            }
+            let mut body = Vec::new();
+            file.by_ref().take(body_len).read_to_end(&mut body)?;
+            return Ok(Some(body));
+        }
+        pos += size;
+    }
+
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    #![allow(clippy::panic_in_result_fn)]
+    #![allow(clippy::unwrap_in_result)]
+    #![allow(clippy::cast_possible_truncation)]
+
+    use super::*;
+    use crate::heif::tests::{full_box, heic_file, make_box};
+    use crate::test_support::media_file;
+    use tempfile::TempDir;
+    use visualvault_models::FileType;
+
+    /// Builds a little-endian TIFF block whose Exif IFD holds an Apple maker note with `id`
+    fn tiff_with_content_identifier(id: &str) -> Vec<u8> {
+        let id = format!("{id}\0");
+
+        let mut note = b"Apple iOS\0\0\x01MM".to_vec();
+        note.extend_from_slice(&1u16.to_be_bytes());
+        note.extend_from_slice(&0x0011u16.to_be_bytes());
+        note.extend_from_slice(&2u16.to_be_bytes());
+        note.extend_from_slice(&(id.len() as u32).to_be_bytes());
+        note.extend_from_slice(&((note.len() + 8) as u32).to_be_bytes());
+        note.extend_from_slice(&0u32.to_be_bytes());
+        note.extend_from_slice(id.as_bytes());
+
+        let entry = |tag: u16, kind: u16, count: u32, value: u32| -> Vec<u8> {
+            let mut bytes = tag.to_le_bytes().to_vec();
+            bytes.extend_from_slice(&kind.to_le_bytes());
+            bytes.extend_from_slice(&count.to_le_bytes());
+            bytes.extend_from_slice(&value.to_le_bytes());
+            bytes
+        };
+
+        // IFD0 at 8 points at the Exif IFD, whose single entry points at the maker note
+        let exif_ifd = 8 + 2 + 12 + 4;
+        let note_start = exif_ifd + 2 + 12 + 4;
+        let mut tiff = b"II*\0".to_vec();
+        tiff.extend_from_slice(&8u32.to_le_bytes());
+        tiff.extend_from_slice(&1u16.to_le_bytes());
+        tiff.extend(entry(0x8769, 4, 1, exif_ifd));
+        tiff.extend_from_slice(&0u32.to_le_bytes());
+        tiff.extend_from_slice(&1u16.to_le_bytes());
+        tiff.extend(entry(0x927c, 7, note.len() as u32, note_start));
+        tiff.extend_from_slice(&0u32.to_le_bytes());
+        tiff.extend(note);
+        tiff
+    }
+
+    /// Builds a movie whose metadata holds `id` as the content identifier
+    fn mov_with_content_identifier(id: &str) -> Vec<u8> {
+        let mut keys_body = 2u32.to_be_bytes().to_vec();
+        for key in [b"com.apple.quicktime.make".as_slice(), CONTENT_IDENTIFIER_KEY] {
+            keys_body.extend_from_slice(&((key.len() + 8) as u32).to_be_bytes());
+            keys_body.extend_from_slice(b"mdta");
+            keys_body.extend_from_slice(key);
+        }
+
+        let mut data = 1u32.to_be_bytes().to_vec();
+        data.extend_from_slice(&0u32.to_be_bytes());
+        data.extend_from_slice(id.as_bytes());
+        let ilst = make_box(*b"ilst", &make_box(2u32.to_be_bytes(), &make_box(*b"data", &data)));
+
+        let mut meta = make_box(*b"hdlr", &[0; 25]);
+        meta.extend(full_box(*b"keys", 0, &keys_body));
+        meta.extend(ilst);
+
+        let mut file = make_box(*b"ftyp", b"qt  \0\0\0\0qt  ");
+        file.extend(make_box(*b"mdat", b"video"));
+        file.extend(make_box(*b"moov", &make_box(*b"meta", &meta)));
+        file
+    }
+
+    #[test]
+    fn test_read_live_photo_ids() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let id = "7E2F9C1A-4B3D-4E8F-9A1B-2C3D4E5F6A7B";
+        let still = temp_dir.path().join("IMG_0001.HEIC");
+        let video = temp_dir.path().join("IMG_0001.MOV");
+        std::fs::write(&still, heic_file(&tiff_with_content_identifier(id)))?;
+        std::fs::write(&video, mov_with_content_identifier(id))?;
+
+        assert_eq!(read_live_photo_id(&still)?.as_deref(), Some(id));
+        assert_eq!(read_live_photo_id(&video)?.as_deref(), Some(id));
+        Ok(())
+    }
+
+    fn uuid(fill: &str) -> String {
+        format!("{}-4B3D-4E8F-9A1B-2C3D4E5F6A7B", fill.repeat(8))
+    }
+
+    #[test]
+    fn test_pair_live_photos_requires_matching_identifier() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let dir = temp_dir.path();
+        std::fs::write(
+            dir.join("IMG_0001.HEIC"),
+            heic_file(&tiff_with_content_identifier(&uuid("A"))),
+        )?;
+        std::fs::write(dir.join("IMG_0001.MOV"), mov_with_content_identifier(&uuid("A")))?;
+        std::fs::write(
+            dir.join("IMG_0002.HEIC"),
+            heic_file(&tiff_with_content_identifier(&uuid("B"))),
+        )?;
+        std::fs::write(dir.join("IMG_0002.MOV"), mov_with_content_identifier(&uuid("C")))?;
+        std::fs::write(dir.join("IMG_0003.MOV"), mov_with_content_identifier(&uuid("D")))?;
+
+        let mut files: Vec<_> = [
+            ("IMG_0001.HEIC", FileType::Image),
+            ("IMG_0001.MOV", FileType::Video),
+            ("IMG_0002.HEIC", FileType::Image),
+            ("IMG_0002.MOV", FileType::Video),
+            ("IMG_0003.MOV", FileType::Video),
+        ]
+        .into_iter()
+        .map(|(name, file_type)| media_file(dir.join(name)).file_type(file_type).arc())
+        .collect();
+        pair_live_photos(&mut files);
+
+        assert_eq!(files[0].live_photo_pair, Some(dir.join("IMG_0001.MOV")));
+        assert_eq!(files[1].live_photo_pair, Some(dir.join("IMG_0001.HEIC")));
+        assert!(files[2..].iter().all(|file| file.live_photo_pair.is_none()));
+        Ok(())
+    }
+}
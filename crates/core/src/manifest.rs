@@ -0,0 +1,335 @@
+use chrono::{DateTime, Local};
+use color_eyre::eyre::Result;
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use tokio::fs;
+use tracing::{error, warn};
+use visualvault_config::{HashAlgorithm, ManifestMode};
+use visualvault_models::{Manifest, ManifestEntry, RestoreItem, RestoreProblem};
+use walkdir::WalkDir;
+
+use crate::DuplicateDetector;
+use crate::transfer::move_file_sync;
+use crate::undo_manager::MoveOperation;
+
+/// Name of the manifest written into destination folders. It is hidden, so it is not taken
+/// for a file of the library when the destination is verified or scanned.
+pub const MANIFEST_FILE: &str = ".visualvault-manifest.json";
+
+/// Writes manifests for the files an organize run placed, given as source and destination pairs.
+///
+/// Entries are merged into any manifest already in the folder, so repeated runs build up the
+/// full history of the folder. A manifest that cannot be parsed is left alone rather than
+/// overwritten. Returns how many manifests were written.
+pub async fn write_manifests(
+    placed: &[(PathBuf, PathBuf)],
+    destination: &Path,
+    mode: ManifestMode,
+    algorithm: HashAlgorithm,
+    organized: DateTime<Local>,
+) -> usize {
+    if mode == ManifestMode::Off || placed.is_empty() {
+        return 0;
+    }
+
+    let detector = DuplicateDetector::with_algorithm(algorithm);
+    let mut folders: BTreeMap<PathBuf, Vec<ManifestEntry>> = BTreeMap::new();
+    for (original, placed_at) in placed {
+        let folder = match mode {
+            ManifestMode::Root if placed_at.starts_with(destination) => destination,
+            _ => placed_at.parent().unwrap_or(destination),
+        };
+        let Ok(file) = placed_at.strip_prefix(folder) else {
+            continue;
+        };
+        match fs::metadata(placed_at).await {
+            Ok(metadata) => {
+                let size = metadata.len();
+                let hash = detector
+                    .hash_file(placed_at, size)
+                    .await
+                    .inspect_err(|e| warn!("Could not hash {} for the manifest: {}", placed_at.display(), e))
+                    .ok();
+                folders.entry(folder.to_path_buf()).or_default().push(ManifestEntry {
+                    file: file.to_path_buf(),
+                    original: original.clone(),
+                    size,
+                    hash,
+                    organized,
+                });
+            }
+            Err(e) => warn!("Could not add {} to the manifest: {}", placed_at.display(), e),
+        }
+    }
+
+    let mut written = 0;
+    for (folder, entries) in folders {
+        match update_manifest(&folder, entries).await {
+            Ok(()) => written += 1,
+            Err(e) => error!("Failed to write the manifest in {}: {}", folder.display(), e),
+        }
+    }
+    written
+}
+
+/// Reads the manifest in `folder`, if there is one.
+///
+/// # Errors
+///
+/// Returns an error if the manifest exists but cannot be read or parsed.
+pub async fn read_manifest(folder: &Path) -> Result<Option<Manifest>> {
+    let path = folder.join(MANIFEST_FILE);
+    if !fs::try_exists(&path).await? {
+        return Ok(None);
+    }
+    Ok(Some(serde_json::from_str(&fs::read_to_string(&path).await?)?))
+}
+
+/// Finds the folders below `root`, `root` included, that hold a manifest.
+#[must_use]
+pub fn find_manifest_folders(root: &Path) -> Vec<PathBuf> {
+    WalkDir::new(root)
+        .into_iter()
+        .filter_map(|entry| {
+            entry
+                .inspect_err(|e| warn!("Skipping unreadable path while looking for manifests: {}", e))
+                .ok()
+        })
+        .filter(|entry| entry.file_type().is_file() && entry.file_name() == MANIFEST_FILE)
+        .filter_map(|entry| entry.path().parent().map(Path::to_path_buf))
+        .collect()
+}
+
+/// Reads every manifest below `root` and checks which of the listed files can be moved back
+/// to their original paths.
+///
+/// The manifests are all that is needed, so a library can be restored after the undo history
+/// is gone or on another machine. Files are checked against their recorded hashes, and
+/// manifests that cannot be read are logged and skipped.
+pub async fn plan_manifest_restore(root: &Path) -> Vec<RestoreItem> {
+    let root = root.to_path_buf();
+    let folders = tokio::task::spawn_blocking(move || find_manifest_folders(&root))
+        .await
+        .unwrap_or_default();
+
+    let mut items = Vec::new();
+    let mut originals = HashSet::new();
+    for folder in folders {
+        let manifest = match read_manifest(&folder).await {
+            Ok(manifest) => manifest.unwrap_or_default(),
+            Err(e) => {
+                warn!("Skipping unreadable manifest in {}: {}", folder.display(), e);
+                continue;
+            }
+        };
+        for entry in manifest.entries {
+            let current = folder.join(&entry.file);
+            let problem = if !fs::try_exists(&current).await.unwrap_or(false) {
+                Some(RestoreProblem::Missing)
+            } else if !matches_hash(&current, &entry).await {
+                Some(RestoreProblem::Changed)
+            } else if fs::try_exists(&entry.original).await.unwrap_or(true) || !originals.insert(entry.original.clone())
+            {
+                Some(RestoreProblem::OriginalTaken)
+            } else {
+                None
+            };
+            items.push(RestoreItem {
+                folder: folder.clone(),
+                entry,
+                problem,
+            });
+        }
+    }
+    items.sort_by(|a, b| a.entry.original.cmp(&b.entry.original));
+    items
+}
+
+/// Moves the files that have no problem back to their original paths, creating the folders
+/// they were in. Manifests are left as they are, so undoing the restore makes them valid again.
+///
+/// Returns the moves that succeeded; failures are logged and skipped.
+#[must_use]
+pub fn restore_from_manifest(items: &[RestoreItem]) -> Vec<MoveOperation> {
+    items
+        .iter()
+        .filter(|item| item.problem.is_none())
+        .filter_map(|item| {
+            let source = item.current();
+            let destination = item.original().to_path_buf();
+            let restored = if destination.exists() {
+                Err(std::io::Error::from(std::io::ErrorKind::AlreadyExists))
+            } else {
+                destination
+                    .parent()
+                    .map_or(Ok(()), std::fs::create_dir_all)
+                    .and_then(|()| move_file_sync(&source, &destination))
+            };
+            match restored {
+                Ok(()) => Some(MoveOperation { source, destination }),
+                Err(e) => {
+                    warn!(
+                        "Failed to restore {} to {}: {}",
+                        source.display(),
+                        destination.display(),
+                        e
+                    );
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+/// Returns whether the file has the content recorded in the manifest. Entries without a hash,
+/// or hashed with an unknown algorithm, only have their size checked.
+async fn matches_hash(path: &Path, entry: &ManifestEntry) -> bool {
+    let size = fs::metadata(path).await.map(|metadata| metadata.len()).ok();
+    if size != Some(entry.size) {
+        return false;
+    }
+    let Some((expected, algorithm)) = entry
+        .hash
+        .as_deref()
+        .and_then(|hash| Some((hash, DuplicateDetector::algorithm_of(hash)?)))
+    else {
+        return true;
+    };
+    DuplicateDetector::with_algorithm(algorithm)
+        .hash_file(path, entry.size)
+        .await
+        .is_ok_and(|hash| hash == expected)
+}
+
+async fn update_manifest(folder: &Path, entries: Vec<ManifestEntry>) -> Result<()> {
+    let mut manifest = read_manifest(folder).await?.unwrap_or_default();
+    manifest.merge(entries);
+    fs::write(folder.join(MANIFEST_FILE), serde_json::to_string_pretty(&manifest)?).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    async fn place(destination: &Path, relative: &str, content: &[u8]) -> (PathBuf, PathBuf) {
+        let placed_at = destination.join(relative);
+        fs::create_dir_all(placed_at.parent().unwrap()).await.unwrap();
+        fs::write(&placed_at, content).await.unwrap();
+        (PathBuf::from("/source").join(placed_at.file_name().unwrap()), placed_at)
+    }
+
+    #[tokio::test]
+    async fn test_write_manifests_per_folder() {
+        let dir = tempfile::tempdir().unwrap();
+        let placed = [
+            place(dir.path(), "2024/03/a.jpg", b"first").await,
+            place(dir.path(), "2024/03/b.jpg", b"second").await,
+            place(dir.path(), "2023/12/c.jpg", b"third").await,
+        ];
+
+        let written = write_manifests(
+            &placed,
+            dir.path(),
+            ManifestMode::Folders,
+            HashAlgorithm::Blake3,
+            Local::now(),
+        )
+        .await;
+        assert_eq!(written, 2);
+
+        let march = read_manifest(&dir.path().join("2024/03")).await.unwrap().unwrap();
+        assert_eq!(march.entries.len(), 2);
+        assert_eq!(march.entries[0].file, PathBuf::from("a.jpg"));
+        assert_eq!(march.entries[0].original, PathBuf::from("/source/a.jpg"));
+        assert_eq!(march.entries[0].size, 5);
+        assert!(march.entries[0].hash.as_deref().unwrap().starts_with("blake3:"));
+        assert!(read_manifest(dir.path()).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_write_manifest_at_root_merges_runs() {
+        let dir = tempfile::tempdir().unwrap();
+        let first = [place(dir.path(), "2024/03/a.jpg", b"first").await];
+        let second = [place(dir.path(), "2023/12/c.jpg", b"third").await];
+
+        for placed in [first, second] {
+            write_manifests(
+                &placed,
+                dir.path(),
+                ManifestMode::Root,
+                HashAlgorithm::Blake3,
+                Local::now(),
+            )
+            .await;
+        }
+
+        let manifest = read_manifest(dir.path()).await.unwrap().unwrap();
+        let files: Vec<_> = manifest.entries.iter().map(|entry| entry.file.clone()).collect();
+        assert_eq!(files, ["2023/12/c.jpg", "2024/03/a.jpg"].map(PathBuf::from));
+    }
+
+    #[tokio::test]
+    async fn test_restore_from_manifest() {
+        let source = tempfile::tempdir().unwrap();
+        let library = tempfile::tempdir().unwrap();
+        let mut placed = Vec::new();
+        for name in ["a.jpg", "b.jpg", "c.jpg", "d.jpg"] {
+            let placed_at = library.path().join("2024").join(name);
+            fs::create_dir_all(placed_at.parent().unwrap()).await.unwrap();
+            fs::write(&placed_at, name).await.unwrap();
+            placed.push((source.path().join("trip").join(name), placed_at));
+        }
+        write_manifests(
+            &placed,
+            library.path(),
+            ManifestMode::Folders,
+            HashAlgorithm::Blake3,
+            Local::now(),
+        )
+        .await;
+
+        fs::remove_file(&placed[1].1).await.unwrap();
+        fs::write(&placed[2].1, b"edited").await.unwrap();
+        fs::create_dir_all(source.path().join("trip")).await.unwrap();
+        fs::write(&placed[3].0, b"already back").await.unwrap();
+
+        let items = plan_manifest_restore(library.path()).await;
+        let problems: Vec<_> = items.iter().map(|item| item.problem).collect();
+        assert_eq!(
+            problems,
+            [
+                None,
+                Some(RestoreProblem::Missing),
+                Some(RestoreProblem::Changed),
+                Some(RestoreProblem::OriginalTaken)
+            ]
+        );
+
+        let restored = restore_from_manifest(&items);
+        assert_eq!(restored.len(), 1);
+        assert_eq!(restored[0].destination, placed[0].0);
+        assert_eq!(fs::read(&placed[0].0).await.unwrap(), b"a.jpg");
+        assert!(!placed[0].1.exists());
+    }
+
+    #[tokio::test]
+    async fn test_unreadable_manifest_is_not_overwritten() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join(MANIFEST_FILE), b"not json").await.unwrap();
+        let placed = [place(dir.path(), "a.jpg", b"first").await];
+
+        let written = write_manifests(
+            &placed,
+            dir.path(),
+            ManifestMode::Root,
+            HashAlgorithm::Blake3,
+            Local::now(),
+        )
+        .await;
+        assert_eq!(written, 0);
+        assert_eq!(fs::read(dir.path().join(MANIFEST_FILE)).await.unwrap(), b"not json");
+    }
+}
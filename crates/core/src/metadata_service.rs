@@ -0,0 +1,384 @@
+//! Image metadata loaded on demand instead of during scans.
+//!
+//! Scans only read EXIF data, so the size and color type of an image are read the first time
+//! something shows them. [`MetadataService`] keeps what it read in a small LRU cache keyed by
+//! content hash and reads the rows a list is about to show in the background, so opening a
+//! file's details rarely waits on the disk.
+
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, Weak};
+
+use color_eyre::eyre::Result;
+use image::{ImageDecoder, ImageReader};
+use tokio::sync::mpsc;
+use tracing::debug;
+use visualvault_models::{FileType, ImageMetadata, MediaFile, MediaMetadata};
+
+use crate::heif::heif_color_type;
+use crate::{read_exif_capture, read_heif_image};
+
+/// Images whose metadata is kept by default; each entry takes a few hundred bytes
+pub const METADATA_CACHE_CAPACITY: usize = 1024;
+
+/// What an image's metadata is cached under: its content hash once known, otherwise its path,
+/// size and modification time, so edited files are read again
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum MetadataKey {
+    Hash(Arc<str>),
+    File(PathBuf, u64, i64),
+}
+
+impl MetadataKey {
+    #[must_use]
+    pub fn of(file: &MediaFile) -> Self {
+        file.hash.as_ref().map_or_else(
+            || Self::File(file.path.clone(), file.size, file.modified.timestamp()),
+            |hash| Self::Hash(Arc::clone(hash)),
+        )
+    }
+}
+
+/// Reads the size, color type and camera settings of an image from its header, without
+/// decoding its pixels.
+///
+/// HEIC images are measured from their container; their color type is only read with the
+/// `heif` feature.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read or is not an image the `image` crate knows.
+pub fn read_image_metadata(path: &Path) -> Result<MediaMetadata> {
+    // Missing or unreadable EXIF data only leaves the capture settings empty
+    let capture = read_exif_capture(path).unwrap_or_default();
+
+    if let Some(image) = read_heif_image(path)? {
+        return Ok(MediaMetadata::Image(ImageMetadata {
+            width: image.width,
+            height: image.height,
+            format: image.format.to_string(),
+            color_type: heif_color_type(path).unwrap_or_else(|| "Unknown".to_string()),
+            capture,
+        }));
+    }
+
+    let decoder = ImageReader::open(path)?.with_guessed_format()?.into_decoder()?;
+    let (width, height) = decoder.dimensions();
+    let color_type = match decoder.color_type() {
+        image::ColorType::L8 => "Grayscale 8-bit",
+        image::ColorType::La8 => "Grayscale + Alpha 8-bit",
+        image::ColorType::Rgb8 => "RGB 8-bit",
+        image::ColorType::Rgba8 => "RGBA 8-bit",
+        image::ColorType::L16 => "Grayscale 16-bit",
+        image::ColorType::La16 => "Grayscale + Alpha 16-bit",
+        image::ColorType::Rgb16 => "RGB 16-bit",
+        image::ColorType::Rgba16 => "RGBA 16-bit",
+        _ => "Unknown",
+    };
+    let format = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map_or_else(|| "Unknown".to_string(), str::to_uppercase);
+
+    Ok(MediaMetadata::Image(ImageMetadata {
+        width,
+        height,
+        format,
+        color_type: color_type.to_string(),
+        capture,
+    }))
+}
+
+/// Loads image metadata when it is first needed, caching it and prefetching the rows a list
+/// shows next.
+///
+/// Clones share the cache and the queue.
+#[derive(Clone)]
+pub struct MetadataService {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    cache: Mutex<LruCache>,
+    /// Images waiting for the background worker, most wanted first
+    queue: Mutex<VecDeque<(MetadataKey, PathBuf)>>,
+    /// Wakes the worker, started with the first prefetch; it stops once the service is dropped
+    wake: Mutex<Option<mpsc::UnboundedSender<()>>>,
+}
+
+impl Default for MetadataService {
+    fn default() -> Self {
+        Self::new(METADATA_CACHE_CAPACITY)
+    }
+}
+
+impl MetadataService {
+    /// Creates a service that keeps the metadata of up to `capacity` images
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                cache: Mutex::new(LruCache::new(capacity)),
+                queue: Mutex::new(VecDeque::new()),
+                wake: Mutex::new(None),
+            }),
+        }
+    }
+
+    /// Metadata of `file` with its size read, from the file itself or the cache, without
+    /// touching the disk
+    #[must_use]
+    pub fn cached(&self, file: &MediaFile) -> Option<MediaMetadata> {
+        if let Some(metadata) = decoded(file) {
+            return Some(metadata.clone());
+        }
+        self.inner.cache.lock().ok()?.get(&MetadataKey::of(file))
+    }
+
+    /// Metadata of `file` with its size read, reading the image header unless it is cached.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the image cannot be read.
+    pub async fn load(&self, file: &MediaFile) -> Result<MediaMetadata> {
+        if let Some(metadata) = self.cached(file) {
+            return Ok(metadata);
+        }
+        let path = file.path.clone();
+        let metadata = tokio::task::spawn_blocking(move || read_image_metadata(&path)).await??;
+        self.inner.insert(MetadataKey::of(file), metadata.clone());
+        Ok(metadata)
+    }
+
+    /// Reads the metadata of the images among `files` in the background, in order, dropping
+    /// what was queued for rows no longer shown.
+    pub fn prefetch<'a>(&self, files: impl IntoIterator<Item = &'a MediaFile>) {
+        let wanted: VecDeque<(MetadataKey, PathBuf)> = files
+            .into_iter()
+            .filter(|file| file.file_type == FileType::Image && decoded(file).is_none())
+            .map(|file| (MetadataKey::of(file), file.path.clone()))
+            .filter(|(key, _)| !self.inner.contains(key))
+            .collect();
+        let Ok(mut queue) = self.inner.queue.lock() else {
+            return;
+        };
+        *queue = wanted;
+        let has_work = !queue.is_empty();
+        drop(queue);
+        if has_work {
+            self.wake_worker();
+        }
+    }
+
+    /// Number of images whose metadata is cached
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.inner.cache.lock().map_or(0, |cache| cache.entries.len())
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Forgets the cached metadata and the queued requests
+    pub fn clear(&self) {
+        if let Ok(mut cache) = self.inner.cache.lock() {
+            cache.entries.clear();
+        }
+        if let Ok(mut queue) = self.inner.queue.lock() {
+            queue.clear();
+        }
+    }
+
+    fn wake_worker(&self) {
+        let Ok(mut wake) = self.inner.wake.lock() else {
+            return;
+        };
+        let sender = wake.get_or_insert_with(|| {
+            let (sender, receiver) = mpsc::unbounded_channel();
+            tokio::spawn(run_worker(Arc::downgrade(&self.inner), receiver));
+            sender
+        });
+        let _ = sender.send(());
+    }
+}
+
+impl Inner {
+    fn contains(&self, key: &MetadataKey) -> bool {
+        self.cache.lock().is_ok_and(|cache| cache.entries.contains_key(key))
+    }
+
+    fn insert(&self, key: MetadataKey, metadata: MediaMetadata) {
+        if let Ok(mut cache) = self.cache.lock() {
+            cache.insert(key, metadata);
+        }
+    }
+
+    fn next_request(&self) -> Option<(MetadataKey, PathBuf)> {
+        let mut queue = self.queue.lock().ok()?;
+        std::iter::from_fn(|| queue.pop_front()).find(|(key, _)| !self.contains(key))
+    }
+}
+
+/// Reads queued images until the queue is empty, then waits to be woken again
+async fn run_worker(inner: Weak<Inner>, mut wake: mpsc::UnboundedReceiver<()>) {
+    while wake.recv().await.is_some() {
+        while let Some((key, path)) = inner.upgrade().and_then(|inner| inner.next_request()) {
+            let read_path = path.clone();
+            match tokio::task::spawn_blocking(move || read_image_metadata(&read_path)).await {
+                Ok(Ok(metadata)) => {
+                    if let Some(inner) = inner.upgrade() {
+                        inner.insert(key, metadata);
+                    }
+                }
+                Ok(Err(e)) => debug!("No metadata for {}: {}", path.display(), e),
+                Err(e) => debug!("Metadata task for {} failed: {}", path.display(), e),
+            }
+        }
+    }
+}
+
+/// Metadata the file already carries with its size read
+fn decoded(file: &MediaFile) -> Option<&MediaMetadata> {
+    file.metadata
+        .as_ref()
+        .filter(|metadata| matches!(metadata, MediaMetadata::Image(image) if image.has_dimensions()))
+}
+
+/// Metadata of the most recently used images, dropping the least recently used beyond
+/// its capacity
+struct LruCache {
+    entries: HashMap<MetadataKey, (MediaMetadata, u64)>,
+    capacity: usize,
+    /// Incremented on every use, recording when each entry was last used
+    clock: u64,
+}
+
+impl LruCache {
+    fn new(capacity: usize) -> Self {
+        Self {
+            entries: HashMap::new(),
+            capacity: capacity.max(1),
+            clock: 0,
+        }
+    }
+
+    fn get(&mut self, key: &MetadataKey) -> Option<MediaMetadata> {
+        self.clock += 1;
+        let (metadata, used) = self.entries.get_mut(key)?;
+        *used = self.clock;
+        Some(metadata.clone())
+    }
+
+    fn insert(&mut self, key: MetadataKey, metadata: MediaMetadata) {
+        self.clock += 1;
+        if self.entries.len() >= self.capacity && !self.entries.contains_key(&key) {
+            if let Some(oldest) = self
+                .entries
+                .iter()
+                .min_by_key(|(_, (_, used))| *used)
+                .map(|(key, _)| key.clone())
+            {
+                self.entries.remove(&oldest);
+            }
+        }
+        self.entries.insert(key, (metadata, self.clock));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::panic_in_result_fn)]
+    #![allow(clippy::unwrap_in_result)]
+
+    use super::*;
+    use crate::test_support::media_file;
+    use std::time::Duration;
+    use tempfile::TempDir;
+    use visualvault_models::CaptureInfo;
+
+    fn write_png(path: &Path, width: u32, height: u32) -> Result<()> {
+        image::RgbImage::new(width, height).save(path)?;
+        Ok(())
+    }
+
+    fn dimensions(metadata: Option<&MediaMetadata>) -> Option<(u32, u32)> {
+        match metadata? {
+            MediaMetadata::Image(image) => Some((image.width, image.height)),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_read_image_metadata_from_header() -> Result<()> {
+        let dir = TempDir::new()?;
+        let path = dir.path().join("photo.png");
+        write_png(&path, 32, 24)?;
+
+        let metadata = read_image_metadata(&path)?;
+        assert_eq!(dimensions(Some(&metadata)), Some((32, 24)));
+        assert!(
+            matches!(&metadata, MediaMetadata::Image(image) if image.format == "PNG" && image.color_type == "RGB 8-bit")
+        );
+        assert!(read_image_metadata(&dir.path().join("missing.png")).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_lru_drops_least_recently_used() {
+        let mut cache = LruCache::new(2);
+        let key = |name: &str| MetadataKey::Hash(Arc::from(name));
+        let metadata = MediaMetadata::Image(ImageMetadata::from_capture("PNG".to_string(), CaptureInfo::default()));
+
+        cache.insert(key("a"), metadata.clone());
+        cache.insert(key("b"), metadata.clone());
+        assert!(cache.get(&key("a")).is_some());
+        cache.insert(key("c"), metadata);
+
+        assert!(cache.get(&key("a")).is_some());
+        assert!(cache.get(&key("b")).is_none());
+        assert!(cache.get(&key("c")).is_some());
+    }
+
+    #[test]
+    fn test_key_prefers_the_content_hash() {
+        let mut file = media_file("/photos/a.png").build();
+        assert!(matches!(MetadataKey::of(&file), MetadataKey::File(..)));
+        file.hash = Some(Arc::from("blake3:abc"));
+        let mut moved = file.clone();
+        moved.path = PathBuf::from("/library/a.png");
+        assert_eq!(MetadataKey::of(&file), MetadataKey::of(&moved));
+    }
+
+    #[tokio::test]
+    async fn test_load_caches_and_prefetch_reads_in_background() -> Result<()> {
+        let dir = TempDir::new()?;
+        let first = dir.path().join("first.png");
+        let second = dir.path().join("second.png");
+        write_png(&first, 8, 6)?;
+        write_png(&second, 4, 2)?;
+        let first = media_file(first).size_on_disk().build();
+        let second = media_file(second).size_on_disk().build();
+
+        let service = MetadataService::new(16);
+        assert!(service.cached(&first).is_none());
+        let loaded = service.load(&first).await?;
+        assert_eq!(dimensions(Some(&loaded)), Some((8, 6)));
+        assert_eq!(dimensions(service.cached(&first).as_ref()), Some((8, 6)));
+
+        service.prefetch([&first, &second]);
+        for _ in 0..200 {
+            if service.cached(&second).is_some() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(dimensions(service.cached(&second).as_ref()), Some((4, 2)));
+        assert_eq!(service.len(), 2);
+
+        service.clear();
+        assert!(service.is_empty());
+        Ok(())
+    }
+}
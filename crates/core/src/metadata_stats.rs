@@ -0,0 +1,53 @@
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use rayon::prelude::{IntoParallelRefIterator, ParallelIterator};
+use visualvault_models::MetadataStats;
+
+use crate::exif::read_exif_tags;
+
+/// Reads the EXIF data of the images at `paths` and aggregates it, counting each image read
+/// in `processed` so a caller can show progress.
+#[must_use]
+pub fn collect_metadata_stats(paths: &[PathBuf], processed: &AtomicUsize) -> MetadataStats {
+    paths
+        .par_iter()
+        .fold(MetadataStats::default, |mut stats, path| {
+            match read_exif_tags(path) {
+                Ok(tags) => stats.add(&tags.capture, &tags.dates, tags.has_location),
+                Err(e) => {
+                    tracing::debug!("Could not read EXIF data of {}: {}", path.display(), e);
+                    stats.add_unreadable();
+                }
+            }
+            processed.fetch_add(1, Ordering::Relaxed);
+            stats
+        })
+        .reduce(MetadataStats::default, |mut total, stats| {
+            total.merge(stats);
+            total
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_counts_images_without_exif_and_unreadable_ones() {
+        let temp_dir = TempDir::new().unwrap();
+        let png = temp_dir.path().join("photo.png");
+        std::fs::write(&png, b"\x89PNG\r\n\x1a\n").unwrap();
+        let paths = [png, temp_dir.path().join("missing.jpg")];
+
+        let processed = AtomicUsize::new(0);
+        let stats = collect_metadata_stats(&paths, &processed);
+        assert_eq!(processed.load(Ordering::Relaxed), 2);
+        assert_eq!(stats.images, 2);
+        assert_eq!(stats.unreadable, 1);
+        assert_eq!(stats.missing_dates, 2);
+        assert!(stats.cameras.is_empty());
+    }
+}
@@ -0,0 +1,435 @@
+use std::time::Duration;
+
+use color_eyre::eyre::{Result, bail, eyre};
+use rumqttc::{AsyncClient, ConnectionError, Event, MqttOptions, Outgoing, Packet, QoS, Transport};
+use tracing::info;
+use visualvault_config::Notifications;
+use visualvault_models::OperationReport;
+
+/// Longest a single target may take to accept a report
+const SEND_TIMEOUT: Duration = Duration::from_secs(10);
+const DEFAULT_MQTT_PORT: u16 = 1883;
+const DEFAULT_MQTTS_PORT: u16 = 8883;
+const MQTT_KEEP_ALIVE: Duration = Duration::from_secs(30);
+const MQTT_MAX_PACKET_BYTES: usize = 1024 * 1024;
+
+/// Sends [`OperationReport`]s to the webhook and MQTT broker configured in the settings.
+///
+/// Webhooks receive the report as a JSON `POST` over HTTP or HTTPS. MQTT reports are published
+/// using MQTT 3.1.1, over TLS for `mqtts://` brokers, at the configured `QoS` on a connection
+/// that is closed again once the broker has acknowledged the report.
+#[derive(Debug, Clone, Default)]
+pub struct Notifier {
+    webhook: Option<Webhook>,
+    mqtt: Option<MqttTarget>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Webhook {
+    url: String,
+    /// Authority of the URL, to name the webhook in logs
+    host: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct MqttTarget {
+    host: String,
+    port: u16,
+    tls: bool,
+    topic: String,
+    qos: QoS,
+    username: Option<String>,
+    password: Option<String>,
+}
+
+impl Notifier {
+    /// Creates a notifier for the targets in `settings`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the webhook URL is not an `http://` or `https://` URL or the MQTT broker
+    /// address, topic or `QoS` is invalid.
+    pub fn new(settings: &Notifications) -> Result<Self> {
+        let webhook = settings.webhook_url.as_deref().map(parse_webhook_url).transpose()?;
+        let mqtt = settings
+            .mqtt_broker
+            .as_deref()
+            .map(|broker| {
+                let (address, tls) = match broker.strip_prefix("mqtts://") {
+                    Some(address) => (address, true),
+                    None => (broker.trim_start_matches("mqtt://"), false),
+                };
+                let default_port = if tls { DEFAULT_MQTTS_PORT } else { DEFAULT_MQTT_PORT };
+                let (host, port) = split_host_port(address, default_port)?;
+                if settings.mqtt_topic.is_empty() || settings.mqtt_topic.contains(['+', '#']) {
+                    bail!("Invalid MQTT topic '{}'", settings.mqtt_topic);
+                }
+                let qos = rumqttc::qos(settings.mqtt_qos)
+                    .map_err(|_| eyre!("Invalid MQTT QoS {}, expected 0, 1 or 2", settings.mqtt_qos))?;
+                Ok(MqttTarget {
+                    host,
+                    port,
+                    tls,
+                    topic: settings.mqtt_topic.clone(),
+                    qos,
+                    username: settings.mqtt_username.clone(),
+                    password: settings.mqtt_password.clone(),
+                })
+            })
+            .transpose()?;
+        Ok(Self { webhook, mqtt })
+    }
+
+    /// Returns whether any target is configured.
+    #[must_use]
+    pub const fn is_enabled(&self) -> bool {
+        self.webhook.is_some() || self.mqtt.is_some()
+    }
+
+    /// Sends `report` to every configured target.
+    ///
+    /// A failing target does not keep the report from reaching the others.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming every target that could not be reached or rejected the report.
+    pub async fn send(&self, report: &OperationReport) -> Result<()> {
+        let payload = serde_json::to_vec(report)?;
+        let mut failures = Vec::new();
+
+        if let Some(webhook) = &self.webhook {
+            let send = {
+                let webhook = webhook.clone();
+                let payload = payload.clone();
+                async move { tokio::task::spawn_blocking(move || post_json(&webhook, &payload)).await? }
+            };
+            match with_timeout(send).await {
+                Ok(()) => info!("Sent {} report to webhook {}", report.operation, webhook.host),
+                Err(e) => failures.push(format!("webhook: {e}")),
+            }
+        }
+        if let Some(mqtt) = &self.mqtt {
+            match with_timeout(publish_mqtt(mqtt, &payload)).await {
+                Ok(()) => info!("Published {} report to MQTT topic {}", report.operation, mqtt.topic),
+                Err(e) => failures.push(format!("MQTT: {e}")),
+            }
+        }
+
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(eyre!("Failed to send notification ({})", failures.join(", ")))
+        }
+    }
+}
+
+async fn with_timeout(send: impl Future<Output = Result<()>>) -> Result<()> {
+    tokio::time::timeout(SEND_TIMEOUT, send)
+        .await
+        .map_err(|_| eyre!("timed out after {}s", SEND_TIMEOUT.as_secs()))?
+}
+
+fn parse_webhook_url(url: &str) -> Result<Webhook> {
+    let rest = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+        .ok_or_else(|| eyre!("Webhook URL '{}' must start with http:// or https://", url))?;
+    let authority = rest.split(['/', '?']).next().unwrap_or_default();
+    // Only checked here, the request itself is built from the whole URL
+    split_host_port(authority, 0)?;
+    Ok(Webhook {
+        url: url.to_string(),
+        host: authority.to_string(),
+    })
+}
+
+/// Splits `host:port`, using `default_port` when no port is given
+fn split_host_port(address: &str, default_port: u16) -> Result<(String, u16)> {
+    let (host, port) = match address.rsplit_once(':') {
+        // A colon inside brackets belongs to an IPv6 address
+        Some((host, port)) if !host.contains(':') || host.ends_with(']') => {
+            let port = port.parse().map_err(|_| eyre!("Invalid port in '{}'", address))?;
+            (host, port)
+        }
+        _ => (address, default_port),
+    };
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    if host.is_empty() {
+        bail!("Missing host in '{}'", address);
+    }
+    Ok((host.to_string(), port))
+}
+
+fn post_json(webhook: &Webhook, payload: &[u8]) -> Result<()> {
+    let agent = ureq::AgentBuilder::new().timeout(SEND_TIMEOUT).build();
+    let response = agent
+        .post(&webhook.url)
+        .set("User-Agent", concat!("visualvault/", env!("CARGO_PKG_VERSION")))
+        .set("Content-Type", "application/json")
+        .send_bytes(payload);
+    match response {
+        Ok(_) => Ok(()),
+        Err(ureq::Error::Status(status, response)) => {
+            bail!("server answered {} {}", status, response.status_text())
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Publishes `payload` to the target's topic, waits until the broker has acknowledged it at the
+/// configured `QoS` and disconnects again.
+async fn publish_mqtt(target: &MqttTarget, payload: &[u8]) -> Result<()> {
+    let mut options = MqttOptions::new(format!("visualvault-{}", std::process::id()), &target.host, target.port);
+    options
+        .set_keep_alive(MQTT_KEEP_ALIVE)
+        .set_clean_session(true)
+        .set_max_packet_size(MQTT_MAX_PACKET_BYTES, MQTT_MAX_PACKET_BYTES);
+    if let Some(username) = &target.username {
+        options.set_credentials(username, target.password.as_deref().unwrap_or_default());
+    }
+    if target.tls {
+        options.set_transport(Transport::tls_with_default_config());
+    }
+
+    let (client, mut event_loop) = AsyncClient::new(options, 2);
+    client
+        .publish(&target.topic, target.qos, false, payload.to_vec())
+        .await?;
+    loop {
+        let delivered = match event_loop.poll().await {
+            Ok(Event::Outgoing(Outgoing::Publish(_))) => target.qos == QoS::AtMostOnce,
+            Ok(Event::Incoming(Packet::PubAck(_) | Packet::PubComp(_))) => true,
+            Ok(_) => false,
+            Err(ConnectionError::ConnectionRefused(code)) => bail!("broker refused the connection ({:?})", code),
+            Err(e) => return Err(e.into()),
+        };
+        if delivered {
+            break;
+        }
+    }
+
+    client.disconnect().await?;
+    while !matches!(event_loop.poll().await?, Event::Outgoing(Outgoing::Disconnect)) {}
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    #![allow(clippy::panic_in_result_fn)]
+    #![allow(clippy::unwrap_in_result)]
+
+    use super::*;
+    use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::{TcpListener, TcpStream};
+    use visualvault_models::NotifiedOperation;
+
+    fn report() -> OperationReport {
+        OperationReport::new(NotifiedOperation::Organize, Duration::from_secs(2)).with_count("files_organized", 12)
+    }
+
+    async fn read_mqtt_packet(stream: &mut TcpStream) -> (u8, Vec<u8>) {
+        let header = stream.read_u8().await.unwrap();
+        let mut len = 0usize;
+        let mut multiplier = 1;
+        loop {
+            let byte = stream.read_u8().await.unwrap();
+            len += usize::from(byte & 0x7F) * multiplier;
+            multiplier *= 128;
+            if byte & 0x80 == 0 {
+                break;
+            }
+        }
+        let mut body = vec![0; len];
+        stream.read_exact(&mut body).await.unwrap();
+        (header, body)
+    }
+
+    #[test]
+    fn test_parse_webhook_url() {
+        let webhook = parse_webhook_url("http://homeassistant.local:8123/api/webhook/vv").unwrap();
+        assert_eq!(
+            webhook,
+            Webhook {
+                url: "http://homeassistant.local:8123/api/webhook/vv".to_string(),
+                host: "homeassistant.local:8123".to_string(),
+            }
+        );
+
+        let webhook = parse_webhook_url("https://hooks.example.com/services/T0?token=1").unwrap();
+        assert_eq!(webhook.host, "hooks.example.com");
+        assert_eq!(parse_webhook_url("http://[::1]:8080").unwrap().host, "[::1]:8080");
+
+        assert!(parse_webhook_url("example.com/hook").is_err());
+        assert!(parse_webhook_url("ftp://example.com/hook").is_err());
+        assert!(parse_webhook_url("http://:8080/hook").is_err());
+    }
+
+    #[test]
+    fn test_broker_address_and_topic() {
+        let settings = Notifications {
+            mqtt_broker: Some("mqtt://broker.lan".to_string()),
+            ..Notifications::default()
+        };
+        let notifier = Notifier::new(&settings).unwrap();
+        let mqtt = notifier.mqtt.unwrap();
+        assert_eq!((mqtt.host.as_str(), mqtt.port), ("broker.lan", DEFAULT_MQTT_PORT));
+        assert!(!mqtt.tls);
+        assert_eq!(mqtt.qos, QoS::AtLeastOnce);
+        assert!(notifier.webhook.is_none());
+
+        let settings = Notifications {
+            mqtt_broker: Some("mqtts://broker.lan".to_string()),
+            mqtt_qos: 2,
+            ..Notifications::default()
+        };
+        let mqtt = Notifier::new(&settings).unwrap().mqtt.unwrap();
+        assert_eq!(
+            (mqtt.port, mqtt.tls, mqtt.qos),
+            (DEFAULT_MQTTS_PORT, true, QoS::ExactlyOnce)
+        );
+
+        let settings = Notifications {
+            mqtt_broker: Some("broker.lan".to_string()),
+            mqtt_qos: 3,
+            ..Notifications::default()
+        };
+        assert!(Notifier::new(&settings).is_err());
+
+        let settings = Notifications {
+            mqtt_broker: Some("broker.lan:1884".to_string()),
+            mqtt_topic: "visualvault/#".to_string(),
+            ..Notifications::default()
+        };
+        assert!(Notifier::new(&settings).is_err());
+        assert!(!Notifier::new(&Notifications::default()).unwrap().is_enabled());
+    }
+
+    #[tokio::test]
+    async fn test_webhook_receives_report() -> Result<()> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let port = listener.local_addr()?.port();
+        let server = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).await.unwrap();
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).await.unwrap();
+                if let Some(value) = line.strip_prefix("Content-Length: ") {
+                    content_length = value.trim().parse().unwrap();
+                }
+                if line == "\r\n" {
+                    break;
+                }
+            }
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).await.unwrap();
+            reader
+                .get_mut()
+                .write_all(b"HTTP/1.1 204 No Content\r\n\r\n")
+                .await
+                .unwrap();
+            (request_line, body)
+        });
+
+        let settings = Notifications {
+            webhook_url: Some(format!("http://127.0.0.1:{port}/hook")),
+            ..Notifications::default()
+        };
+        Notifier::new(&settings)?.send(&report()).await?;
+
+        let (request_line, body) = server.await?;
+        assert_eq!(request_line, "POST /hook HTTP/1.1\r\n");
+        let json: serde_json::Value = serde_json::from_slice(&body)?;
+        assert_eq!(json["operation"], "organize");
+        assert_eq!(json["counts"]["files_organized"], 12);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_webhook_error_status_fails() -> Result<()> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let port = listener.local_addr()?.port();
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut buf = [0u8; 1024];
+            let _ = stream.read(&mut buf).await;
+            stream.write_all(b"HTTP/1.1 404 Not Found\r\n\r\n").await.unwrap();
+        });
+
+        let settings = Notifications {
+            webhook_url: Some(format!("http://127.0.0.1:{port}/missing")),
+            ..Notifications::default()
+        };
+        let error = Notifier::new(&settings)?.send(&report()).await.unwrap_err();
+        assert!(error.to_string().contains("404"), "{error}");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_mqtt_publishes_report() -> Result<()> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let port = listener.local_addr()?.port();
+        let broker = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let connect = read_mqtt_packet(&mut stream).await;
+            stream.write_all(&[0x20, 0x02, 0x00, 0x00]).await.unwrap();
+            let publish = read_mqtt_packet(&mut stream).await;
+            // Acknowledge the QoS 1 publish by its packet id
+            let topic_len = usize::from(u16::from_be_bytes([publish.1[0], publish.1[1]]));
+            let packet_id = &publish.1[2 + topic_len..4 + topic_len];
+            stream
+                .write_all(&[0x40, 0x02, packet_id[0], packet_id[1]])
+                .await
+                .unwrap();
+            let disconnect = read_mqtt_packet(&mut stream).await;
+            (connect, publish, disconnect)
+        });
+
+        let settings = Notifications {
+            mqtt_broker: Some(format!("127.0.0.1:{port}")),
+            mqtt_topic: "home/visualvault".to_string(),
+            mqtt_username: Some("ha".to_string()),
+            mqtt_password: Some("secret".to_string()),
+            ..Notifications::default()
+        };
+        Notifier::new(&settings)?.send(&report()).await?;
+
+        let ((connect_header, connect), (publish_header, publish), (disconnect_header, _)) = broker.await?;
+        assert_eq!(connect_header, 0x10);
+        assert_eq!(&connect[..7], b"\x00\x04MQTT\x04");
+        assert_eq!(connect[7], 0xC2, "clean session with username and password");
+
+        assert_eq!(publish_header, 0x32, "QoS 1 publish");
+        let topic_len = usize::from(u16::from_be_bytes([publish[0], publish[1]]));
+        assert_eq!(&publish[2..2 + topic_len], b"home/visualvault");
+        let json: serde_json::Value = serde_json::from_slice(&publish[4 + topic_len..])?;
+        assert_eq!(json["counts"]["files_organized"], 12);
+
+        assert_eq!(disconnect_header, 0xE0);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_refused_mqtt_connection_fails() -> Result<()> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let port = listener.local_addr()?.port();
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            read_mqtt_packet(&mut stream).await;
+            // Return code 5: not authorized
+            stream.write_all(&[0x20, 0x02, 0x00, 0x05]).await.unwrap();
+        });
+
+        let settings = Notifications {
+            mqtt_broker: Some(format!("127.0.0.1:{port}")),
+            ..Notifications::default()
+        };
+        let error = Notifier::new(&settings)?.send(&report()).await.unwrap_err();
+        assert!(error.to_string().contains("NotAuthorized"), "{error}");
+        Ok(())
+    }
+}
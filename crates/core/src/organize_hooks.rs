@@ -0,0 +1,261 @@
+use std::path::{Component, Path, PathBuf};
+use std::process::Stdio;
+use std::time::Duration;
+
+use color_eyre::eyre::{Result, eyre};
+use serde::{Deserialize, Serialize};
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+use visualvault_models::MediaFile;
+
+/// Version of the JSON exchanged with hooks, sent with every request
+pub const HOOK_PROTOCOL_VERSION: u32 = 1;
+
+/// How long a hook may take to answer for one file before that file fails
+pub const HOOK_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// What a hook is sent on stdin, as one JSON object
+#[derive(Debug, Serialize)]
+struct HookRequest<'a> {
+    version: u32,
+    /// Destination folder of the run, which rewritten destinations must stay inside
+    library: &'a Path,
+    /// Where the file goes unless the hook says otherwise, after earlier hooks had their say
+    destination: &'a Path,
+    file: &'a MediaFile,
+}
+
+/// What a hook prints on stdout; printing nothing keeps the destination
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+#[serde(tag = "action", rename_all = "snake_case")]
+enum HookResponse {
+    Keep,
+    Rewrite {
+        destination: PathBuf,
+    },
+    Veto {
+        #[serde(default)]
+        reason: Option<String>,
+    },
+}
+
+/// Where a file goes after the organize hooks saw it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HookDecision {
+    Place(PathBuf),
+    /// The file stays in the source, for the given reason
+    Veto(String),
+}
+
+/// Runs the user's organize hooks, programs that keep, rewrite or veto the destination of each
+/// file.
+///
+/// Every hook is started once per file and sent a JSON request on stdin; it answers with a
+/// JSON object on stdout such as `{"action": "rewrite", "destination": "Trips/2024/a.jpg"}`.
+/// Relative destinations are taken from the library folder. A hook that exits with an error,
+/// answers with something else or takes longer than [`HOOK_TIMEOUT`] fails the file.
+#[derive(Debug, Clone, Default)]
+pub struct OrganizeHooks {
+    hooks: Vec<PathBuf>,
+}
+
+impl OrganizeHooks {
+    #[must_use]
+    pub fn new(hooks: &[PathBuf]) -> Self {
+        Self { hooks: hooks.to_vec() }
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.hooks.is_empty()
+    }
+
+    /// Asks each hook in turn about `destination`, stopping at the first veto.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a hook cannot be run, fails, times out, gives an answer that is not
+    /// valid or a destination outside `library`.
+    pub async fn decide(&self, file: &MediaFile, destination: PathBuf, library: &Path) -> Result<HookDecision> {
+        let mut destination = destination;
+        for hook in &self.hooks {
+            let request = HookRequest {
+                version: HOOK_PROTOCOL_VERSION,
+                library,
+                destination: &destination,
+                file,
+            };
+            match Self::ask(hook, &request).await? {
+                HookResponse::Keep => {}
+                HookResponse::Rewrite { destination: rewritten } => {
+                    destination = Self::within_library(library, &rewritten)
+                        .ok_or_else(|| eyre!("{} moved the file outside the library", hook.display()))?;
+                }
+                HookResponse::Veto { reason } => {
+                    let hook_name = hook.file_name().unwrap_or(hook.as_os_str()).to_string_lossy();
+                    return Ok(HookDecision::Veto(reason.map_or_else(
+                        || format!("Vetoed by {hook_name}"),
+                        |reason| format!("Vetoed by {hook_name}: {reason}"),
+                    )));
+                }
+            }
+        }
+        Ok(HookDecision::Place(destination))
+    }
+
+    async fn ask(hook: &Path, request: &HookRequest<'_>) -> Result<HookResponse> {
+        let mut child = Command::new(hook)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| eyre!("Could not run organize hook {}: {e}", hook.display()))?;
+
+        let mut input = serde_json::to_vec(request)?;
+        input.push(b'\n');
+        if let Some(mut stdin) = child.stdin.take() {
+            // A hook that decides without reading its input closes the pipe early
+            let _ = stdin.write_all(&input).await;
+        }
+
+        let output = tokio::time::timeout(HOOK_TIMEOUT, child.wait_with_output())
+            .await
+            .map_err(|_| eyre!("{} did not answer within {}s", hook.display(), HOOK_TIMEOUT.as_secs()))??;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(eyre!(
+                "{} exited with {}: {}",
+                hook.display(),
+                output.status,
+                stderr.lines().next().unwrap_or_default()
+            ));
+        }
+        Self::parse(&output.stdout).map_err(|e| eyre!("{} gave an answer that is not valid: {e}", hook.display()))
+    }
+
+    fn parse(stdout: &[u8]) -> serde_json::Result<HookResponse> {
+        if stdout.iter().all(u8::is_ascii_whitespace) {
+            return Ok(HookResponse::Keep);
+        }
+        serde_json::from_slice(stdout)
+    }
+
+    /// `destination` taken from `library` when relative, or `None` when it leaves the library
+    fn within_library(library: &Path, destination: &Path) -> Option<PathBuf> {
+        let path = library.join(destination);
+        let escapes = path
+            .components()
+            .any(|component| matches!(component, Component::ParentDir));
+        (!escapes && path.starts_with(library) && path != library).then_some(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::panic_in_result_fn)]
+    #![allow(clippy::unwrap_in_result)]
+
+    use super::*;
+    use crate::test_support::media_file;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_responses() -> Result<()> {
+        assert_eq!(OrganizeHooks::parse(b"")?, HookResponse::Keep);
+        assert_eq!(OrganizeHooks::parse(b" \n")?, HookResponse::Keep);
+        assert_eq!(OrganizeHooks::parse(br#"{"action":"keep"}"#)?, HookResponse::Keep);
+        assert_eq!(
+            OrganizeHooks::parse(br#"{"action":"rewrite","destination":"Trips/a.jpg"}"#)?,
+            HookResponse::Rewrite {
+                destination: PathBuf::from("Trips/a.jpg")
+            }
+        );
+        assert_eq!(
+            OrganizeHooks::parse(br#"{"action":"veto"}"#)?,
+            HookResponse::Veto { reason: None }
+        );
+        assert!(OrganizeHooks::parse(b"yes").is_err());
+        assert!(OrganizeHooks::parse(br#"{"action":"delete"}"#).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_rewrites_stay_in_the_library() {
+        let library = Path::new("/library");
+        assert_eq!(
+            OrganizeHooks::within_library(library, Path::new("Trips/a.jpg")),
+            Some(PathBuf::from("/library/Trips/a.jpg"))
+        );
+        assert_eq!(
+            OrganizeHooks::within_library(library, Path::new("/library/2024/a.jpg")),
+            Some(PathBuf::from("/library/2024/a.jpg"))
+        );
+        assert_eq!(OrganizeHooks::within_library(library, Path::new("../a.jpg")), None);
+        assert_eq!(OrganizeHooks::within_library(library, Path::new("/tmp/a.jpg")), None);
+        assert_eq!(OrganizeHooks::within_library(library, Path::new("")), None);
+    }
+
+    #[cfg(unix)]
+    fn write_hook(dir: &Path, name: &str, script: &str) -> Result<PathBuf> {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = dir.join(name);
+        std::fs::write(&path, format!("#!/bin/sh\n{script}\n"))?;
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755))?;
+        Ok(path)
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_hooks_run_in_order() -> Result<()> {
+        let dir = TempDir::new()?;
+        let library = dir.path().join("library");
+        let rewrite = write_hook(
+            dir.path(),
+            "rewrite.sh",
+            r#"cat > /dev/null; echo '{"action":"rewrite","destination":"Trips/a.jpg"}'"#,
+        )?;
+        // Vetoes whatever was rewritten into Trips, proving it sees the earlier answer
+        let veto = write_hook(
+            dir.path(),
+            "veto.sh",
+            r#"grep -q Trips && echo '{"action":"veto","reason":"no trips"}'"#,
+        )?;
+        let keep = write_hook(dir.path(), "keep.sh", "cat > /dev/null")?;
+        let file = media_file("/source/a.jpg").build();
+
+        let hooks = OrganizeHooks::new(&[keep.clone(), rewrite.clone()]);
+        assert_eq!(
+            hooks.decide(&file, library.join("2024/a.jpg"), &library).await?,
+            HookDecision::Place(library.join("Trips/a.jpg"))
+        );
+
+        let hooks = OrganizeHooks::new(&[rewrite, veto, keep]);
+        assert_eq!(
+            hooks.decide(&file, library.join("2024/a.jpg"), &library).await?,
+            HookDecision::Veto("Vetoed by veto.sh: no trips".to_string())
+        );
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_failing_hooks_are_errors() -> Result<()> {
+        let dir = TempDir::new()?;
+        let library = dir.path().join("library");
+        let file = media_file("/source/a.jpg").build();
+        let failing = write_hook(dir.path(), "fail.sh", "echo 'no camera' >&2; exit 2")?;
+        let escaping = write_hook(
+            dir.path(),
+            "escape.sh",
+            r#"echo '{"action":"rewrite","destination":"../a.jpg"}'"#,
+        )?;
+
+        for hook in [failing, escaping, dir.path().join("missing.sh")] {
+            let hooks = OrganizeHooks::new(&[hook]);
+            assert!(hooks.decide(&file, library.join("a.jpg"), &library).await.is_err());
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,203 @@
+use color_eyre::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tracing::warn;
+use visualvault_config::HashAlgorithm;
+use visualvault_models::MediaFile;
+
+use crate::{DuplicateDetector, FileList};
+
+const LEDGER_FILE: &str = "organized_ledger.json";
+
+/// Where content recorded in the ledger was placed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct LedgerEntry {
+    size: u64,
+    destination: PathBuf,
+}
+
+/// Content hashes of the files placed into a destination, so files imported once are
+/// recognized in the source later, whatever they are called there.
+///
+/// The ledger is stored as `organized_ledger.json` in the config directory. Hashes are tagged
+/// with their algorithm, so entries recorded with another algorithm simply do not match.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct OrganizedLedger {
+    entries: BTreeMap<String, LedgerEntry>,
+    #[serde(skip)]
+    path: Option<PathBuf>,
+}
+
+impl OrganizedLedger {
+    /// Loads the ledger stored in `config_dir`, or an empty one if there is none yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the ledger file exists but cannot be read or parsed.
+    pub fn load(config_dir: &Path) -> Result<Self> {
+        let path = config_dir.join("visualvault").join(LEDGER_FILE);
+        let mut ledger = if path.exists() {
+            serde_json::from_str(&fs::read_to_string(&path)?)?
+        } else {
+            Self::default()
+        };
+        ledger.path = Some(path);
+        Ok(ledger)
+    }
+
+    /// Writes the ledger back to the file it was loaded from.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the ledger cannot be serialized or written.
+    pub fn save(&self) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string(self)?)?;
+        Ok(())
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Hashes the files placed at `destinations` and records them, returning how many were added.
+    ///
+    /// Files that cannot be read are logged and left out.
+    pub async fn record(&mut self, destinations: &[PathBuf], algorithm: HashAlgorithm) -> usize {
+        let detector = DuplicateDetector::with_algorithm(algorithm);
+        let mut added = 0;
+        for destination in destinations {
+            let hashed = match tokio::fs::metadata(destination).await {
+                Ok(metadata) => detector
+                    .hash_file(destination, metadata.len())
+                    .await
+                    .map(|hash| (hash, metadata.len())),
+                Err(e) => Err(e.into()),
+            };
+            match hashed {
+                Ok((hash, size)) => {
+                    let entry = LedgerEntry {
+                        size,
+                        destination: destination.clone(),
+                    };
+                    if self.entries.insert(hash, entry).is_none() {
+                        added += 1;
+                    }
+                }
+                Err(e) => warn!("Could not record {} as organized: {}", destination.display(), e),
+            }
+        }
+        added
+    }
+
+    /// Finds the files whose content is in the ledger, returning where each was organized to by
+    /// source path.
+    ///
+    /// Only files as large as a recorded entry are hashed, so a ledger of other content costs
+    /// next to nothing. Files that cannot be read are treated as new, and so are files whose
+    /// organized copy is gone, e.g. after the run was undone, so no last copy is ever removed.
+    pub async fn find(&self, files: &[Arc<MediaFile>], algorithm: HashAlgorithm) -> HashMap<PathBuf, PathBuf> {
+        let sizes: HashSet<u64> = self.entries.values().map(|entry| entry.size).collect();
+        let detector = DuplicateDetector::with_algorithm(algorithm);
+        let mut found = HashMap::new();
+        for file in files.iter().filter(|file| sizes.contains(&file.size)) {
+            match detector.hash_file(&file.path, file.size).await {
+                Ok(hash) => {
+                    if let Some(entry) = self.entries.get(&hash).filter(|entry| entry.destination.exists()) {
+                        found.insert(file.path.clone(), entry.destination.clone());
+                    }
+                }
+                Err(e) => warn!("Could not check whether {} was organized: {}", file.path.display(), e),
+            }
+        }
+        found
+    }
+
+    /// Finds the files of `files` whose content is in the ledger like [`Self::find`], reading
+    /// only those as large as a recorded entry from a paged list.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a paged list cannot be read.
+    pub async fn find_in(&self, files: &FileList, algorithm: HashAlgorithm) -> Result<HashMap<PathBuf, PathBuf>> {
+        let sizes: HashSet<u64> = self.entries.values().map(|entry| entry.size).collect();
+        let candidates = files.filter(|file| sizes.contains(&file.size)).await?;
+        Ok(self.find(&candidates, algorithm).await)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use crate::test_support::media_file;
+
+    #[tokio::test]
+    async fn test_find_previously_organized() {
+        let config = tempfile::tempdir().unwrap();
+        let files = tempfile::tempdir().unwrap();
+        let placed = files.path().join("placed.jpg");
+        let copy = files.path().join("IMG_0001.jpg");
+        let other = files.path().join("other.jpg");
+        fs::write(&placed, b"organized photo").unwrap();
+        fs::write(&copy, b"organized photo").unwrap();
+        fs::write(&other, b"a different one").unwrap();
+
+        let mut ledger = OrganizedLedger::load(config.path()).unwrap();
+        assert_eq!(
+            ledger
+                .record(std::slice::from_ref(&placed), HashAlgorithm::Blake3)
+                .await,
+            1
+        );
+        assert_eq!(
+            ledger
+                .record(std::slice::from_ref(&placed), HashAlgorithm::Blake3)
+                .await,
+            0
+        );
+        ledger.save().unwrap();
+
+        let ledger = OrganizedLedger::load(config.path()).unwrap();
+        assert_eq!(ledger.len(), 1);
+        let found = ledger
+            .find(
+                &[
+                    media_file(&copy).size_on_disk().arc(),
+                    media_file(&other).size_on_disk().arc(),
+                ],
+                HashAlgorithm::Blake3,
+            )
+            .await;
+        assert_eq!(found.len(), 1);
+        assert_eq!(found.get(&copy), Some(&placed));
+
+        // Entries recorded with another algorithm do not match
+        let found = ledger
+            .find(&[media_file(&copy).size_on_disk().arc()], HashAlgorithm::Sha256)
+            .await;
+        assert!(found.is_empty());
+
+        // Neither do entries whose organized copy was removed
+        fs::remove_file(&placed).unwrap();
+        let found = ledger
+            .find(&[media_file(&copy).size_on_disk().arc()], HashAlgorithm::Blake3)
+            .await;
+        assert!(found.is_empty());
+    }
+}
@@ -1,27 +1,185 @@
+use chrono::{DateTime, FixedOffset};
 use color_eyre::eyre::Result;
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use tokio::fs;
 use tokio::sync::{Mutex, RwLock};
-use tracing::error;
-use visualvault_config::{OrganizationMode, Settings};
-use visualvault_models::{DuplicateStats, FileType, MediaFile, OrganizeResult};
-use visualvault_utils::Progress;
-
+use tracing::{error, info};
+use visualvault_config::{
+    ConflictPolicy, ExtensionMismatchPolicy, NonMediaPolicy, OrganizationMode, PreviouslyOrganizedPolicy, Settings,
+    TimezonePolicy,
+};
+use visualvault_models::{
+    AudioMetadata, ConflictStats, DuplicateStats, FileConflict, FileOutcome, FileType, MediaFile, MediaMetadata,
+    NonMediaStats, OrganizeResult, OrganizedFile, SkipRuleStats,
+};
+use visualvault_utils::{Progress, STAGE_ORGANIZE, extended_length_path, sanitize_path_component};
+
+use crate::IoThrottle;
+use crate::OrganizedLedger;
 use crate::UndoManager;
-use crate::undo_manager::{FileOperation, MoveOperation};
-
+use crate::batch_rename::render_file_name;
+use crate::checksums::write_checksum_sidecars;
+use crate::exif::{read_exif_camera, read_exif_dates};
+use crate::manifest::write_manifests;
+use crate::organize_hooks::{HookDecision, OrganizeHooks};
+use crate::skip_rules::SkipReason;
+use crate::transfer::Transfer;
+use crate::undo_manager::{DeleteOperation, FileOperation, MoveOperation};
+
+/// Folder in the destination that collects non-media files under [`NonMediaPolicy::Unsorted`]
+const UNSORTED_FOLDER: &str = "Unsorted";
+
+/// Folder in the destination that collects screenshots when they are kept apart
+const SCREENSHOTS_FOLDER: &str = "Screenshots";
+
+#[derive(Default)]
 struct OrganizeBatchResult {
     operations: Vec<FileOperation>,
     moved_files: usize,
     errors: Vec<String>,
+    conflicts: ConflictStats,
+    pending_conflicts: Vec<FileConflict>,
+    non_media_placed: usize,
+    /// Where each organized file was placed, by source path
+    placed: HashMap<PathBuf, PathBuf>,
+    /// Content hashes calculated for placed files while looking for duplicates, by source path
+    hashes: HashMap<PathBuf, Arc<str>>,
+    files: Vec<OrganizedFile>,
+    rolled_back: bool,
+    /// Whether the run stopped before every file was organized
+    cancelled: bool,
+}
+
+impl OrganizeBatchResult {
+    fn record(&mut self, file: &MediaFile, outcome: Result<PlacementOutcome>) {
+        let entry = self.report_entry(file.path.clone(), &outcome);
+        self.files.push(entry);
+
+        match outcome {
+            Ok(
+                PlacementOutcome::Moved(path) | PlacementOutcome::Renamed(path) | PlacementOutcome::Overwritten(path),
+            ) => {
+                self.moved_files += 1;
+                if file.file_type == FileType::Other {
+                    self.non_media_placed += 1;
+                }
+                tracing::info!("Organized {} to {}", file.name, path.display());
+                if let Some(hash) = &file.hash {
+                    self.hashes.insert(file.path.clone(), Arc::clone(hash));
+                }
+                self.placed.insert(file.path.clone(), path);
+            }
+            Ok(PlacementOutcome::Skipped) => self.conflicts.skipped += 1,
+            Ok(PlacementOutcome::InPlace) => tracing::debug!("{} is already organized", file.name),
+            Ok(PlacementOutcome::Deferred(conflict)) => {
+                self.conflicts.deferred += 1;
+                self.pending_conflicts.push(conflict);
+            }
+            // Logged by the hook check, and listed in the report with its reason
+            Ok(PlacementOutcome::Vetoed(..)) => {}
+            Err(e) => {
+                tracing::error!("Failed to organize {}: {}", file.name, e);
+                self.errors.push(format!("{}: {}", file.name, e));
+            }
+        }
+    }
+
+    /// The organize report entry of a file, counting renames and overwrites as conflicts
+    fn report_entry(&mut self, source: PathBuf, outcome: &Result<PlacementOutcome>) -> OrganizedFile {
+        match outcome {
+            Ok(PlacementOutcome::Moved(path)) => OrganizedFile::new(source, Some(path.clone()), FileOutcome::Moved),
+            Ok(PlacementOutcome::Renamed(path)) => {
+                self.conflicts.renamed += 1;
+                OrganizedFile::new(source, Some(path.clone()), FileOutcome::Renamed)
+            }
+            Ok(PlacementOutcome::Overwritten(path)) => {
+                self.conflicts.overwritten += 1;
+                OrganizedFile::new(source, Some(path.clone()), FileOutcome::Overwritten)
+            }
+            Ok(PlacementOutcome::Skipped) => OrganizedFile::new(source, None, FileOutcome::SkippedConflict),
+            Ok(PlacementOutcome::InPlace) => OrganizedFile::new(source, None, FileOutcome::AlreadyOrganized),
+            Ok(PlacementOutcome::Deferred(conflict)) => {
+                OrganizedFile::new(source, Some(conflict.target.clone()), FileOutcome::Deferred)
+            }
+            Ok(PlacementOutcome::Vetoed(target, reason)) => {
+                OrganizedFile::vetoed(source, target.clone(), reason.clone())
+            }
+            Err(e) => OrganizedFile::failed(source, e.to_string()),
+        }
+    }
+
+    /// Moves every placed file back to where it came from when more than `threshold` percent
+    /// of the files failed, so a run either mostly succeeds or leaves the library as it was.
+    ///
+    /// Conflicts waiting for a decision are dropped along with the rest of the run.
+    fn roll_back_above(&mut self, threshold: u8) {
+        let total = self.files.len();
+        let failed = self
+            .files
+            .iter()
+            .filter(|file| file.outcome == FileOutcome::Error)
+            .count();
+        if failed == 0 || failed * 100 <= usize::from(threshold) * total {
+            return;
+        }
+
+        tracing::warn!(
+            "{} of {} files failed, more than {}%; rolling back the organize run",
+            failed,
+            total,
+            threshold
+        );
+        match UndoManager::revert_organize(std::mem::take(&mut self.operations)) {
+            Ok(message) => tracing::info!("Rollback: {}", message),
+            Err(e) => tracing::error!("Rollback failed: {}", e),
+        }
+
+        for file in &mut self.files {
+            file.outcome = match file.outcome {
+                FileOutcome::Moved | FileOutcome::Renamed | FileOutcome::Overwritten => FileOutcome::RolledBack,
+                FileOutcome::Deferred => FileOutcome::SkippedConflict,
+                outcome => outcome,
+            };
+        }
+        self.errors.insert(
+            0,
+            format!("Rolled back the run because {failed} of {total} files failed (more than {threshold}%)"),
+        );
+        self.moved_files = 0;
+        self.non_media_placed = 0;
+        self.placed.clear();
+        self.hashes.clear();
+        self.pending_conflicts.clear();
+        self.conflicts = ConflictStats::default();
+        self.rolled_back = true;
+    }
+}
+
+/// Where a single file ended up after organizing
+#[derive(Debug)]
+enum PlacementOutcome {
+    Moved(PathBuf),
+    Renamed(PathBuf),
+    Overwritten(PathBuf),
+    Skipped,
+    /// The file already is at its target, e.g. when the source folder contains the destination
+    InPlace,
+    Deferred(FileConflict),
+    /// An organize hook refused the destination, for the given reason
+    Vetoed(PathBuf, String),
 }
 
 pub struct FileOrganizer {
     is_organizing: Arc<Mutex<bool>>,
     result: Arc<Mutex<Option<Result<usize>>>>,
     undo_manager: Arc<UndoManager>,
+    ledger: Arc<Mutex<OrganizedLedger>>,
+    /// Set to stop the running organize before its next file
+    cancelled: Arc<AtomicBool>,
 }
 
 impl FileOrganizer {
@@ -31,10 +189,16 @@ impl FileOrganizer {
     ///
     /// Returns an error if the undo manager fails to initialize with the provided config directory.
     pub async fn new(config_dir: PathBuf) -> Result<Self> {
+        let ledger = OrganizedLedger::load(&config_dir).unwrap_or_else(|e| {
+            error!("Failed to load the organized ledger: {}", e);
+            OrganizedLedger::default()
+        });
         Ok(Self {
             is_organizing: Arc::new(Mutex::new(false)),
             result: Arc::new(Mutex::new(None)),
             undo_manager: Arc::new(UndoManager::new_with_history(config_dir).await?),
+            ledger: Arc::new(Mutex::new(ledger)),
+            cancelled: Arc::new(AtomicBool::new(false)),
         })
     }
 
@@ -44,6 +208,12 @@ impl FileOrganizer {
         &self.undo_manager
     }
 
+    /// The ledger of content placed in a destination by earlier runs
+    #[must_use]
+    pub const fn ledger(&self) -> &Arc<Mutex<OrganizedLedger>> {
+        &self.ledger
+    }
+
     /// Organizes files into the configured destination folder, handling duplicates according to settings.
     ///
     /// # Errors
@@ -58,42 +228,91 @@ impl FileOrganizer {
         duplicates: DuplicateStats,
         settings: &Settings,
         progress: Arc<RwLock<Progress>>,
+    ) -> Result<OrganizeResult> {
+        let result = self.organize(files, duplicates, settings, progress).await;
+        // A cancel asked for during this run never stops the next one
+        self.cancelled.store(false, Ordering::Release);
+        result
+    }
+
+    /// Asks the running organize to stop before its next file.
+    ///
+    /// The file being moved is finished first, so no file is left half moved. Files placed
+    /// until then stay organized and are recorded for undo, manifests and the ledger like
+    /// those of a finished run.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Release);
+    }
+
+    async fn organize(
+        &self,
+        files: Vec<Arc<MediaFile>>,
+        duplicates: DuplicateStats,
+        settings: &Settings,
+        progress: Arc<RwLock<Progress>>,
     ) -> Result<OrganizeResult> {
         let dest_folder = Self::validate_destination(settings)?;
 
         let (files_to_organize, skipped_duplicates) =
             Self::filter_files_for_organization(files.clone(), &duplicates, settings);
-
-        self.initialize_progress(&progress, files_to_organize.len()).await;
-
-        let organize_result = self
-            .organize_files_batch(files_to_organize, &dest_folder, settings, &progress)
+        let skipped: Vec<_> = skipped_duplicates
+            .iter()
+            .map(|file| OrganizedFile::new(file.path.clone(), None, FileOutcome::SkippedDuplicate))
+            .collect();
+        let (files_to_organize, ignored_non_media) = Self::filter_non_media(files_to_organize, settings);
+        let (files_to_organize, skipped_by_rules, skip_stats) =
+            Self::filter_skip_rules(files_to_organize, settings).await?;
+        let (files_to_organize, organized_before) = self.split_organized_before(files_to_organize, settings).await;
+
+        self.initialize_progress(&progress, &files_to_organize).await;
+
+        let transfer = Transfer::new(settings.buffer_size)
+            .with_progress(Arc::clone(&progress))
+            .with_throttle(IoThrottle::new(&settings.throttle));
+        let mut organize_result = self
+            .organize_files_batch(files_to_organize, &dest_folder, settings, &transfer, &progress)
             .await?;
+        // Sources of files organized before are only removed by runs that finish
+        if !organize_result.cancelled {
+            self.handle_organized_before(organized_before, settings, &mut organize_result)
+                .await;
+        }
+        progress.write().await.finish_stages();
+        if let Some(threshold) = settings.rollback_threshold {
+            organize_result.roll_back_above(threshold);
+        }
 
-        self.finalize_organization(organize_result, files.len(), dest_folder, skipped_duplicates, settings)
-            .await
+        let mut result = self
+            .finalize_organization(organize_result, files.len(), dest_folder, skipped.len(), settings)
+            .await?;
+        result.non_media.ignored = ignored_non_media;
+        result.skipped_by_rules = skip_stats;
+        result.files.extend(skipped);
+        result.files.extend(skipped_by_rules);
+        Ok(result)
     }
 
-    /// Validates that a destination folder is configured
+    /// Validates that a destination folder is configured, returning the local folder files
+    /// are placed in
     fn validate_destination(settings: &Settings) -> Result<PathBuf> {
         settings
-            .destination_folder
-            .clone()
+            .local_destination()
             .ok_or_else(|| color_eyre::eyre::eyre!("Destination folder not configured"))
     }
 
-    /// Filters files based on duplicate handling settings
+    /// Filters files based on duplicate handling settings, returning the files to organize and
+    /// the duplicates left out
     fn filter_files_for_organization(
         files: Vec<Arc<MediaFile>>,
         duplicates: &DuplicateStats,
         settings: &Settings,
-    ) -> (Vec<Arc<MediaFile>>, usize) {
+    ) -> (Vec<Arc<MediaFile>>, Vec<Arc<MediaFile>>) {
         if settings.rename_duplicates || duplicates.is_empty() {
-            return (files, 0);
+            return (files, Vec::new());
         }
 
         let mut files_to_organize = Vec::new();
-        let mut skipped_duplicates = 0;
+        let mut skipped_duplicates = Vec::new();
 
         // Process duplicate groups - keep only the oldest file from each group
         let processed_duplicates = Self::process_duplicate_groups(duplicates, &mut skipped_duplicates);
@@ -106,15 +325,137 @@ impl FileOrganizer {
         (files_to_organize, skipped_duplicates)
     }
 
+    /// Leaves non-media files out of the run when the non-media policy ignores them
+    fn filter_non_media(files: Vec<Arc<MediaFile>>, settings: &Settings) -> (Vec<Arc<MediaFile>>, usize) {
+        if settings.non_media_policy != NonMediaPolicy::Ignore {
+            return (files, 0);
+        }
+
+        let total = files.len();
+        let media: Vec<_> = files
+            .into_iter()
+            .filter(|file| file.file_type != FileType::Other)
+            .collect();
+        let ignored = total - media.len();
+        (media, ignored)
+    }
+
+    /// Leaves the files the skip rules exclude in the source, returning the rest, report entries
+    /// for those left and how many each rule excluded
+    async fn filter_skip_rules(
+        files: Vec<Arc<MediaFile>>,
+        settings: &Settings,
+    ) -> Result<(Vec<Arc<MediaFile>>, Vec<OrganizedFile>, SkipRuleStats)> {
+        let rules = settings.skip_rules;
+        if !rules.is_enabled() {
+            return Ok((files, Vec::new(), SkipRuleStats::default()));
+        }
+
+        // The resolution rule reads image headers
+        let filtered = tokio::task::spawn_blocking(move || {
+            let now = chrono::Local::now();
+            let mut stats = SkipRuleStats::default();
+            let mut skipped = Vec::new();
+            let kept: Vec<_> = files
+                .into_iter()
+                .filter(|file| {
+                    let Some(reason) = SkipReason::check(&rules, file, now) else {
+                        return true;
+                    };
+                    reason.count(&mut stats);
+                    skipped.push(OrganizedFile::skipped_by_rule(
+                        file.path.clone(),
+                        reason.describe(&rules),
+                    ));
+                    false
+                })
+                .collect();
+            (kept, skipped, stats)
+        })
+        .await?;
+        if filtered.2.total() > 0 {
+            info!("Skip rules left {} files in the source", filtered.2.total());
+        }
+        Ok(filtered)
+    }
+
+    /// Separates the files whose content an earlier run placed in a destination, returning the
+    /// rest and where each of those was organized to
+    async fn split_organized_before(
+        &self,
+        files: Vec<Arc<MediaFile>>,
+        settings: &Settings,
+    ) -> (Vec<Arc<MediaFile>>, HashMap<PathBuf, PathBuf>) {
+        if !settings.previously_organized.is_enabled() {
+            return (files, HashMap::new());
+        }
+
+        let organized_before = self.ledger.lock().await.find(&files, settings.hash_algorithm).await;
+        let files = files
+            .into_iter()
+            .filter(|file| !organized_before.contains_key(&file.path))
+            .collect();
+        (files, organized_before)
+    }
+
+    /// Leaves files organized by an earlier run in the source, or removes them from it under
+    /// [`PreviouslyOrganizedPolicy::Delete`], keeping a backup when undo is enabled
+    async fn handle_organized_before(
+        &self,
+        organized_before: HashMap<PathBuf, PathBuf>,
+        settings: &Settings,
+        batch: &mut OrganizeBatchResult,
+    ) {
+        for (source, destination) in organized_before {
+            let outcome = if settings.previously_organized == PreviouslyOrganizedPolicy::Delete {
+                if let Err(e) = self.remove_source(&source, settings, &mut batch.operations).await {
+                    let name = source.file_name().unwrap_or_default().to_string_lossy().to_string();
+                    tracing::error!("Failed to delete {}: {}", name, e);
+                    batch.errors.push(format!("{name}: {e}"));
+                    batch.files.push(OrganizedFile::failed(source, e.to_string()));
+                    continue;
+                }
+                FileOutcome::DeletedOrganizedBefore
+            } else {
+                FileOutcome::SkippedOrganizedBefore
+            };
+            batch.files.push(OrganizedFile::new(source, Some(destination), outcome));
+        }
+    }
+
+    /// Removes a source file, moving it to the undo backups when undo is enabled
+    async fn remove_source(
+        &self,
+        source: &Path,
+        settings: &Settings,
+        operations: &mut Vec<FileOperation>,
+    ) -> Result<()> {
+        if !settings.undo_enabled {
+            fs::remove_file(source).await?;
+            return Ok(());
+        }
+
+        let (_, name) = Self::split_target(source)?;
+        let backup_dir = self.undo_manager.backup_dir();
+        fs::create_dir_all(&backup_dir).await?;
+        let backup = backup_dir.join(format!("{}-{name}", uuid::Uuid::new_v4()));
+        Transfer::new(settings.buffer_size).move_file(source, &backup).await?;
+        operations.push(FileOperation::Delete(DeleteOperation {
+            path: source.to_path_buf(),
+            backup_path: Some(backup),
+        }));
+        Ok(())
+    }
+
     /// Processes duplicate groups and returns the files to keep
-    fn process_duplicate_groups(duplicates: &DuplicateStats, skipped_count: &mut usize) -> Vec<Arc<MediaFile>> {
+    fn process_duplicate_groups(duplicates: &DuplicateStats, skipped: &mut Vec<Arc<MediaFile>>) -> Vec<Arc<MediaFile>> {
         let mut files_to_keep = Vec::new();
 
         for group in &duplicates.groups {
             if group.files.len() > 1 {
                 if let Some(oldest_file) = group.files.iter().min_by_key(|f| f.modified) {
                     files_to_keep.push(Arc::clone(oldest_file));
-                    *skipped_count += group.files.len() - 1;
+                    skipped.extend(group.files.iter().filter(|f| f.path != oldest_file.path).cloned());
                 }
             }
         }
@@ -140,11 +481,12 @@ impl FileOrganizer {
     }
 
     /// Initializes progress tracking
-    async fn initialize_progress(&self, progress: &Arc<RwLock<Progress>>, total_files: usize) {
-        let mut prog = progress.write().await;
-        prog.total = total_files;
-        prog.current = 0;
-        prog.message = "Organizing files...".to_string();
+    async fn initialize_progress(&self, progress: &Arc<RwLock<Progress>>, files: &[Arc<MediaFile>]) {
+        let total_bytes = files.iter().map(|file| file.size).sum();
+        progress
+            .write()
+            .await
+            .start_stage(STAGE_ORGANIZE, files.len(), total_bytes, "Organizing files...");
     }
 
     /// Organizes a batch of files
@@ -153,59 +495,89 @@ impl FileOrganizer {
         files: Vec<Arc<MediaFile>>,
         destination: &Path,
         settings: &Settings,
+        transfer: &Transfer,
         progress: &Arc<RwLock<Progress>>,
     ) -> Result<OrganizeBatchResult> {
-        let mut operations = Vec::new();
-        let mut moved_files = 0;
-        let mut errors = Vec::new();
-
-        for (idx, file) in files.iter().enumerate() {
-            self.organize_single_file(
-                file,
-                destination,
-                settings,
-                &mut operations,
-                &mut moved_files,
-                &mut errors,
-            )
-            .await;
+        let mut batch = OrganizeBatchResult::default();
+        let mut bytes_processed = 0;
+
+        for (idx, file) in Self::live_photo_videos_last(files).iter().enumerate() {
+            if self.cancelled.load(Ordering::Acquire) {
+                info!("Organizing cancelled after {} files", idx);
+                batch.cancelled = true;
+                break;
+            }
+            let outcome = match Self::live_photo_target(file, &batch.placed, settings) {
+                Some(target) => {
+                    self.place_file(file, target, settings, transfer, &mut batch.operations)
+                        .await
+                }
+                None => {
+                    self.organize_file(file, idx + 1, destination, settings, transfer, &mut batch.operations)
+                        .await
+                }
+            };
+            batch.record(file, outcome);
 
-            self.update_progress(progress, idx + 1).await;
+            bytes_processed += file.size;
+            self.update_progress(progress, idx + 1, bytes_processed, file).await;
         }
 
-        Ok(OrganizeBatchResult {
-            operations,
-            moved_files,
-            errors,
-        })
+        Ok(batch)
     }
 
-    /// Organizes a single file
-    async fn organize_single_file(
+    /// Applies the user's decisions for conflicts deferred by [`ConflictPolicy::Ask`].
+    ///
+    /// A resolution of [`ConflictPolicy::Ask`] is treated as [`ConflictPolicy::Skip`].
+    /// All resolved files are recorded as a single undoable operation.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the destination folder is not configured in settings.
+    pub async fn resolve_conflicts(
         &self,
-        file: &Arc<MediaFile>,
-        destination: &Path,
+        resolutions: Vec<(FileConflict, ConflictPolicy)>,
         settings: &Settings,
-        operations: &mut Vec<FileOperation>,
-        moved_count: &mut usize,
-        errors: &mut Vec<String>,
-    ) {
-        match self.organize_file(file, destination, settings, operations).await {
-            Ok(dest_path) => {
-                *moved_count += 1;
-                tracing::info!("Organized {} to {}", file.name, dest_path.display());
-            }
-            Err(e) => {
-                tracing::error!("Failed to organize {}: {}", file.name, e);
-                errors.push(format!("{}: {}", file.name, e));
-            }
+    ) -> Result<OrganizeResult> {
+        let dest_folder = Self::validate_destination(settings)?;
+        let total = resolutions.len();
+        let transfer = Transfer::new(settings.buffer_size).with_throttle(IoThrottle::new(&settings.throttle));
+        let mut batch = OrganizeBatchResult::default();
+
+        for (conflict, policy) in resolutions {
+            let policy = if policy == ConflictPolicy::Ask {
+                ConflictPolicy::Skip
+            } else {
+                policy
+            };
+            let outcome = self
+                .place_conflicting(
+                    &conflict.file,
+                    conflict.target.clone(),
+                    policy,
+                    settings,
+                    &transfer,
+                    &mut batch.operations,
+                )
+                .await;
+            batch.record(&conflict.file, outcome);
         }
+
+        self.finalize_organization(batch, total, dest_folder, 0, settings).await
     }
 
     /// Updates progress tracking
-    async fn update_progress(&self, progress: &Arc<RwLock<Progress>>, current: usize) {
+    async fn update_progress(
+        &self,
+        progress: &Arc<RwLock<Progress>>,
+        current: usize,
+        bytes_processed: u64,
+        file: &MediaFile,
+    ) {
         let mut prog = progress.write().await;
-        prog.current = current;
+        prog.set_file_progress(current, bytes_processed, file.name.to_string());
+        // Cross-device copies report their bytes through the message; restore it per file
+        prog.message = "Organizing files...".to_string();
     }
 
     /// Finalizes the organization process
@@ -224,93 +596,429 @@ impl FileOrganizer {
             }
         }
 
+        self.record_in_ledger(&batch_result.placed, settings).await;
+        let placed: Vec<_> = batch_result.placed.into_iter().collect();
+        Self::write_library_records(&placed, &batch_result.hashes, &destination, settings).await;
+
         // Clear organizing flag
         *self.is_organizing.lock().await = false;
 
         // Store result
         *self.result.lock().await = Some(Ok(batch_result.moved_files));
 
+        let mut non_media = NonMediaStats::default();
+        match settings.non_media_policy {
+            NonMediaPolicy::Unsorted => non_media.unsorted = batch_result.non_media_placed,
+            NonMediaPolicy::Include => non_media.included = batch_result.non_media_placed,
+            NonMediaPolicy::Ignore => {}
+        }
+
         Ok(OrganizeResult {
             files_organized: batch_result.moved_files,
             files_total: total_files,
             destination,
             success: batch_result.errors.is_empty(),
+            rolled_back: batch_result.rolled_back,
+            cancelled: batch_result.cancelled,
             timestamp: chrono::Local::now(),
             skipped_duplicates,
             errors: batch_result.errors,
+            conflicts: batch_result.conflicts,
+            pending_conflicts: batch_result.pending_conflicts,
+            non_media,
+            skipped_by_rules: SkipRuleStats::default(),
+            files: batch_result.files,
+            remote_transfer: None,
         })
     }
 
+    /// Writes the manifests and checksum files the settings ask for into the folders files
+    /// were placed in
+    async fn write_library_records(
+        placed: &[(PathBuf, PathBuf)],
+        hashes: &HashMap<PathBuf, Arc<str>>,
+        destination: &Path,
+        settings: &Settings,
+    ) {
+        let manifests = write_manifests(
+            placed,
+            destination,
+            settings.manifest,
+            settings.hash_algorithm,
+            chrono::Local::now(),
+        )
+        .await;
+        if manifests > 0 {
+            tracing::info!("Wrote {} organization manifests", manifests);
+        }
+        let sidecars = write_checksum_sidecars(placed, hashes, settings.checksum_sidecars).await;
+        if sidecars > 0 {
+            tracing::info!("Wrote {} checksum files", sidecars);
+        }
+    }
+
+    /// Adds the files placed by a run to the organized ledger, unless the ledger is off
+    async fn record_in_ledger(&self, placed: &HashMap<PathBuf, PathBuf>, settings: &Settings) {
+        if !settings.previously_organized.is_enabled() || placed.is_empty() {
+            return;
+        }
+
+        let destinations: Vec<PathBuf> = placed.values().cloned().collect();
+        let mut ledger = self.ledger.lock().await;
+        let added = ledger.record(&destinations, settings.hash_algorithm).await;
+        if let Err(e) = ledger.save() {
+            error!("Failed to save the organized ledger: {}", e);
+        }
+        drop(ledger);
+        tracing::info!("Recorded {} organized files in the ledger", added);
+    }
+
     async fn organize_file(
         &self,
         file: &MediaFile,
+        sequence: usize,
         destination: &Path,
         settings: &Settings,
+        transfer: &Transfer,
         operations: &mut Vec<FileOperation>,
-    ) -> Result<PathBuf> {
-        let target_dir = Self::determine_target_directory(file, destination, settings)?;
+    ) -> Result<PlacementOutcome> {
+        let exif_offset = Self::exif_offset(file, settings).await;
+        let target_dir = Self::determine_target_directory(file, exif_offset, destination, settings)?;
+        let file_name = Self::renamed_file_name(file, sequence, settings).await;
+        let file_name = Self::fixed_extension(file_name, file, settings);
+        let target_path = target_dir.join(Self::target_file_name(&file_name, settings));
+
+        let hooks = OrganizeHooks::new(&settings.organize_hooks);
+        let target_path = match hooks.decide(file, target_path.clone(), destination).await? {
+            HookDecision::Place(hooked_path) => hooked_path,
+            HookDecision::Veto(reason) => {
+                tracing::info!("{} left in place: {}", file.name, reason);
+                return Ok(PlacementOutcome::Vetoed(target_path, reason));
+            }
+        };
 
         // Create target directory if it doesn't exist
-        fs::create_dir_all(&target_dir).await?;
+        let target_dir = target_path.parent().unwrap_or(destination);
+        fs::create_dir_all(extended_length_path(target_dir))
+            .await
+            .map_err(|e| color_eyre::eyre::eyre!("Failed to create {}: {e}", target_dir.display()))?;
 
-        // Handle file naming
-        let file_name = if settings.rename_duplicates {
-            // Check if file exists in target directory
-            if target_dir.join(&*file.name).exists() {
-                &Self::generate_unique_name(&target_dir, &file.name)?
-            } else {
-                &*file.name
-            }
+        self.place_file(file, target_path, settings, transfer, operations).await
+    }
+
+    /// Moves a file to `target_path`, applying the conflict policy if the target exists
+    async fn place_file(
+        &self,
+        file: &MediaFile,
+        target_path: PathBuf,
+        settings: &Settings,
+        transfer: &Transfer,
+        operations: &mut Vec<FileOperation>,
+    ) -> Result<PlacementOutcome> {
+        // Moving a file onto itself would only rename it as a conflict with itself
+        if Self::is_same_file(&file.path, &target_path).await {
+            return Ok(PlacementOutcome::InPlace);
+        }
+
+        if !target_path.exists() {
+            Self::move_file(&file.path, &target_path, transfer, operations).await?;
+            return Ok(PlacementOutcome::Moved(target_path));
+        }
+
+        // A template can give several files of this run the same name; number them
+        // instead of treating the earlier file as a conflict
+        if settings.rename_template.is_some() && Self::placed_in_this_run(&target_path, operations) {
+            let (dir, name) = Self::split_target(&target_path)?;
+            let renamed = dir.join(Self::generate_unique_name(dir, name)?);
+            Self::move_file(&file.path, &renamed, transfer, operations).await?;
+            return Ok(PlacementOutcome::Moved(renamed));
+        }
+
+        self.place_conflicting(
+            file,
+            target_path,
+            settings.effective_conflict_policy(),
+            settings,
+            transfer,
+            operations,
+        )
+        .await
+    }
+
+    async fn is_same_file(path: &Path, target: &Path) -> bool {
+        if path == target {
+            return true;
+        }
+        match (fs::canonicalize(path).await, fs::canonicalize(target).await) {
+            (Ok(path), Ok(target)) => path == target,
+            _ => false,
+        }
+    }
+
+    /// Moves the videos of Live Photos after all other files, so their still image is placed first
+    fn live_photo_videos_last(files: Vec<Arc<MediaFile>>) -> Vec<Arc<MediaFile>> {
+        let (videos, mut others): (Vec<_>, Vec<_>) = files
+            .into_iter()
+            .partition(|file| file.file_type == FileType::Video && file.live_photo_pair.is_some());
+        others.extend(videos);
+        others
+    }
+
+    /// Places the video of a Live Photo next to its still image, under the same name.
+    ///
+    /// Returns `None` for other files, and for videos whose still image was not organized.
+    fn live_photo_target(file: &MediaFile, placed: &HashMap<PathBuf, PathBuf>, settings: &Settings) -> Option<PathBuf> {
+        if file.file_type != FileType::Video {
+            return None;
+        }
+        let still_target = placed.get(file.live_photo_pair.as_ref()?)?;
+        let stem = still_target.file_stem()?.to_string_lossy();
+        let name = match Path::new(file.name.as_ref()).extension() {
+            Some(ext) => format!("{stem}.{}", ext.to_string_lossy()),
+            None => stem.to_string(),
+        };
+        let name = Self::fixed_extension(name, file, settings);
+        Some(still_target.with_file_name(Self::target_file_name(&name, settings)))
+    }
+
+    /// Gives a file name the extension of the file's content when the mismatch policy fixes them
+    fn fixed_extension(file_name: String, file: &MediaFile, settings: &Settings) -> String {
+        let Some(extension) = file
+            .content_extension
+            .as_deref()
+            .filter(|_| settings.extension_mismatch == ExtensionMismatchPolicy::Fix)
+        else {
+            return file_name;
+        };
+        let stem = Path::new(&file_name)
+            .file_stem()
+            .map_or_else(|| file_name.clone(), |stem| stem.to_string_lossy().to_string());
+        format!("{stem}.{extension}")
+    }
+
+    /// Applies the configured rename template, keeping the original extension
+    async fn renamed_file_name(file: &MediaFile, sequence: usize, settings: &Settings) -> String {
+        let Some(template) = &settings.rename_template else {
+            return file.name.to_string();
+        };
+
+        let camera = if template.uses("camera") {
+            let path = file.path.clone();
+            tokio::task::spawn_blocking(move || read_exif_camera(&path))
+                .await
+                .ok()
+                .and_then(Result::ok)
+                .flatten()
         } else {
-            &file.name
+            None
         };
 
-        // Apply lowercase extension if configured
-        let final_name = if settings.lowercase_extensions {
-            let stem = Path::new(&file_name)
-                .file_stem()
-                .and_then(|s| s.to_str())
-                .unwrap_or(file_name);
-            let ext = Path::new(&file_name).extension().and_then(|e| e.to_str()).unwrap_or("");
-            if ext.is_empty() {
-                file_name
-            } else {
-                &format!("{}.{}", stem, ext.to_lowercase())
+        render_file_name(template, file, camera.as_deref(), sequence)
+    }
+
+    fn placed_in_this_run(target: &Path, operations: &[FileOperation]) -> bool {
+        operations
+            .iter()
+            .any(|op| matches!(op, FileOperation::Move(move_op) if move_op.destination == target))
+    }
+
+    /// Applies the configured naming rules to a file name
+    fn target_file_name(file_name: &str, settings: &Settings) -> String {
+        if !settings.lowercase_extensions {
+            return file_name.to_string();
+        }
+
+        let path = Path::new(file_name);
+        let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or(file_name);
+        match path.extension().and_then(|e| e.to_str()) {
+            Some(ext) if !ext.is_empty() => format!("{}.{}", stem, ext.to_lowercase()),
+            _ => file_name.to_string(),
+        }
+    }
+
+    /// Places a file whose target path is already taken, according to `policy`
+    async fn place_conflicting(
+        &self,
+        file: &MediaFile,
+        target: PathBuf,
+        policy: ConflictPolicy,
+        settings: &Settings,
+        transfer: &Transfer,
+        operations: &mut Vec<FileOperation>,
+    ) -> Result<PlacementOutcome> {
+        match policy {
+            ConflictPolicy::Skip => Ok(PlacementOutcome::Skipped),
+            ConflictPolicy::Ask => Ok(PlacementOutcome::Deferred(FileConflict {
+                file: Arc::new(file.clone()),
+                target,
+            })),
+            ConflictPolicy::Rename => {
+                let (dir, name) = Self::split_target(&target)?;
+                let renamed = dir.join(Self::generate_unique_name(dir, name)?);
+                Self::move_file(&file.path, &renamed, transfer, operations).await?;
+                Ok(PlacementOutcome::Renamed(renamed))
+            }
+            ConflictPolicy::OverwriteIfNewer => {
+                let incoming = fs::metadata(&file.path).await?.modified()?;
+                let existing = fs::metadata(&target).await?.modified()?;
+                if incoming > existing {
+                    self.overwrite_file(file, target, settings, transfer, operations).await
+                } else {
+                    Ok(PlacementOutcome::Skipped)
+                }
+            }
+            ConflictPolicy::OverwriteAlways => self.overwrite_file(file, target, settings, transfer, operations).await,
+        }
+    }
+
+    /// Replaces `target` with `file`, keeping a backup of the replaced file when undo is enabled.
+    ///
+    /// The incoming file is first moved to a temporary name beside `target` and then renamed over
+    /// it, so a failed move never costs the existing file. Should the final rename fail, the
+    /// backup is put back and the incoming file returned to where it came from.
+    async fn overwrite_file(
+        &self,
+        file: &MediaFile,
+        target: PathBuf,
+        settings: &Settings,
+        transfer: &Transfer,
+        operations: &mut Vec<FileOperation>,
+    ) -> Result<PlacementOutcome> {
+        let (dir, name) = Self::split_target(&target)?;
+        let incoming = dir.join(format!(".{name}.{}.incoming", uuid::Uuid::new_v4()));
+        transfer.move_file(&file.path, &incoming).await?;
+
+        let backup = if settings.undo_enabled {
+            let backup_dir = self.undo_manager.backup_dir();
+            let backup = backup_dir.join(format!("{}-{name}", uuid::Uuid::new_v4()));
+            let moved = match fs::create_dir_all(&backup_dir).await {
+                Ok(()) => transfer.move_file(&target, &backup).await.map(|_| ()),
+                Err(e) => Err(e.into()),
+            };
+            if let Err(e) = moved {
+                Self::restore_after_failed_overwrite(&incoming, &file.path, transfer).await;
+                return Err(e);
             }
+            Some(backup)
         } else {
-            file_name
+            None
         };
 
-        let target_path = target_dir.join(final_name);
-
-        // Move the file
-        fs::rename(&file.path, &target_path).await?;
+        if let Err(e) = fs::rename(&incoming, &target).await {
+            if let Some(backup) = &backup {
+                Self::restore_after_failed_overwrite(backup, &target, transfer).await;
+            }
+            Self::restore_after_failed_overwrite(&incoming, &file.path, transfer).await;
+            return Err(e.into());
+        }
 
+        if let Some(backup) = backup {
+            operations.push(FileOperation::Delete(DeleteOperation {
+                path: target.clone(),
+                backup_path: Some(backup),
+            }));
+        }
         operations.push(FileOperation::Move(MoveOperation {
             source: file.path.clone(),
-            destination: target_path.clone(),
+            destination: target.clone(),
+        }));
+        Ok(PlacementOutcome::Overwritten(target))
+    }
+
+    /// Moves a file back after an overwrite failed part way, logging if even that fails
+    async fn restore_after_failed_overwrite(from: &Path, to: &Path, transfer: &Transfer) {
+        if let Err(e) = transfer.move_file(from, to).await {
+            error!("Failed to move {} back to {}: {}", from.display(), to.display(), e);
+        }
+    }
+
+    fn split_target(target: &Path) -> Result<(&Path, &str)> {
+        let dir = target
+            .parent()
+            .ok_or_else(|| color_eyre::eyre::eyre!("Invalid target path: {}", target.display()))?;
+        let name = target
+            .file_name()
+            .and_then(|n| n.to_str())
+            .ok_or_else(|| color_eyre::eyre::eyre!("Invalid target path: {}", target.display()))?;
+        Ok((dir, name))
+    }
+
+    async fn move_file(
+        source: &Path,
+        target: &Path,
+        transfer: &Transfer,
+        operations: &mut Vec<FileOperation>,
+    ) -> Result<()> {
+        transfer.move_file(source, target).await?;
+
+        operations.push(FileOperation::Move(MoveOperation {
+            source: source.to_path_buf(),
+            destination: target.to_path_buf(),
         }));
 
-        Ok(target_path)
+        Ok(())
     }
 
-    fn determine_target_directory(file: &MediaFile, destination: &Path, settings: &Settings) -> Result<PathBuf> {
+    /// Plans the directory a file is moved into, without touching the file system.
+    ///
+    /// The first enabled routing rule matching the file's name decides before the organization
+    /// mode, followed by the first enabled folder rule covering the file.
+    /// Date folders follow the time zone policy, where `exif_offset` is the UTC offset the
+    /// file's EXIF data records, if it was read.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the organization mode in settings is invalid.
+    pub fn determine_target_directory(
+        file: &MediaFile,
+        exif_offset: Option<FixedOffset>,
+        destination: &Path,
+        settings: &Settings,
+    ) -> Result<PathBuf> {
         let mut path = destination.to_path_buf();
+        let date = Self::folder_date(file, settings.timezone_policy, exif_offset);
+
+        // Rules for the file's name, then for its source folder, win over everything the mode would do
+        let rule_target = settings
+            .routing_rule(&file.name)
+            .map(|rule| rule.render(&date))
+            .or_else(|| settings.folder_rule(&file.path).map(|rule| rule.render(&date)));
+        if let Some(segments) = rule_target {
+            path.extend(segments.iter().map(|segment| sanitize_path_component(segment)));
+            return Ok(path);
+        }
+
+        if file.file_type == FileType::Other && settings.non_media_policy == NonMediaPolicy::Unsorted {
+            path.push(UNSORTED_FOLDER);
+            return Ok(path);
+        }
 
         if settings.separate_videos && file.file_type == FileType::Video && settings.organize_by != "type" {
             path.push("Videos");
         }
 
+        let screenshot = settings.separate_screenshots && file.is_screenshot();
+        if screenshot && settings.organize_by != "type" {
+            path.push(SCREENSHOTS_FOLDER);
+        }
+
         match OrganizationMode::from_str(&settings.organize_by) {
             Ok(OrganizationMode::Yearly) => {
-                path.push(file.modified.format("%Y").to_string());
+                path.push(date.format("%Y").to_string());
             }
             Ok(OrganizationMode::Monthly) => {
-                path.push(file.modified.format("%Y").to_string());
-                path.push(file.modified.format("%m-%B").to_string());
+                path.push(date.format("%Y").to_string());
+                path.push(date.format("%m-%B").to_string());
             }
             Ok(OrganizationMode::ByType) => {
-                path.push(Self::get_type_folder(file));
+                if screenshot {
+                    path.push(SCREENSHOTS_FOLDER);
+                } else {
+                    path.push(Self::get_type_folder(file));
+                }
+                if file.file_type == FileType::Audio {
+                    path.extend(Self::render_audio_template(file, &settings.audio_template));
+                }
             }
             Err(e) => {
                 error!("Invalid organization mode: {}", e);
@@ -320,19 +1028,83 @@ impl FileOrganizer {
         Ok(path)
     }
 
+    /// The modification time of a file in the time zone its date folders are picked in.
+    ///
+    /// Under [`TimezonePolicy::Exif`] files without a recorded `exif_offset` use local time.
+    #[must_use]
+    pub fn folder_date(
+        file: &MediaFile,
+        policy: TimezonePolicy,
+        exif_offset: Option<FixedOffset>,
+    ) -> DateTime<FixedOffset> {
+        let offset = match policy {
+            TimezonePolicy::Local => None,
+            TimezonePolicy::Exif => exif_offset,
+            TimezonePolicy::Fixed(offset) => Some(offset),
+        };
+        offset.map_or_else(
+            || file.modified.fixed_offset(),
+            |offset| file.modified.with_timezone(&offset),
+        )
+    }
+
+    /// Reads the UTC offset recorded in a photo's EXIF data when the time zone policy uses it
+    async fn exif_offset(file: &MediaFile, settings: &Settings) -> Option<FixedOffset> {
+        if settings.timezone_policy != TimezonePolicy::Exif || file.file_type != FileType::Image {
+            return None;
+        }
+        let path = file.path.clone();
+        tokio::task::spawn_blocking(move || read_exif_dates(&path))
+            .await
+            .ok()
+            .and_then(Result::ok)
+            .and_then(|dates| dates.offset)
+    }
+
     fn get_type_folder(file: &MediaFile) -> String {
         match file.file_type {
             FileType::Image => "Images".to_string(),
             FileType::Video => "Videos".to_string(),
+            FileType::Audio => "Audio".to_string(),
             FileType::Document => "Documents".to_string(),
             FileType::Other => "Others".to_string(),
         }
     }
 
-    fn generate_unique_name(dir: &Path, original_name: &str) -> Result<String> {
-        let mut counter = 1;
-        let stem = Path::new(original_name)
-            .file_stem()
+    /// Expands an audio folder template such as `{artist}/{album}` into path components.
+    ///
+    /// Supports `{artist}`, `{album}`, `{title}` and `{year}`. Missing tags fall back to
+    /// "Unknown Artist", "Unknown Album", the file stem and the modification year.
+    fn render_audio_template(file: &MediaFile, template: &str) -> Vec<String> {
+        let tags = match &file.metadata {
+            Some(MediaMetadata::Audio(tags)) => tags.clone(),
+            _ => AudioMetadata::default(),
+        };
+        let stem = Path::new(file.name.as_ref())
+            .file_stem()
+            .map_or_else(|| file.name.to_string(), |s| s.to_string_lossy().to_string());
+        let year = tags
+            .year
+            .map_or_else(|| file.modified.format("%Y").to_string(), |y| y.to_string());
+
+        template
+            .split('/')
+            .map(|segment| {
+                let rendered = segment
+                    .replace("{artist}", tags.artist.as_deref().unwrap_or("Unknown Artist"))
+                    .replace("{album}", tags.album.as_deref().unwrap_or("Unknown Album"))
+                    .replace("{title}", tags.title.as_deref().unwrap_or(&stem))
+                    .replace("{year}", &year);
+                sanitize_path_component(&rendered)
+            })
+            .filter(|segment| !segment.is_empty())
+            .collect()
+    }
+
+    fn generate_unique_name(dir: &Path, original_name: &str) -> Result<String> {
+        let mut counter = 1;
+        let stem = Path::new(original_name)
+            .file_stem()
             .and_then(|s| s.to_str())
             .unwrap_or("");
         let extension = Path::new(original_name)
@@ -373,15 +1145,16 @@ mod tests {
     #![allow(clippy::expect_used)]
     #![allow(clippy::float_cmp)] // For comparing floats in tests
     #![allow(clippy::panic)]
-    #![allow(clippy::unwrap_used)]
-    #![allow(clippy::expect_used)]
     #![allow(clippy::panic_in_result_fn)]
+    #![allow(clippy::unwrap_in_result)]
 
     use super::*;
+    use crate::test_support::media_file;
     use crate::undo_manager::OperationType::OrganizeFiles;
-    use chrono::{DateTime, Local, TimeZone};
+    use chrono::{Local, TimeZone, Utc};
     use tempfile::TempDir;
     use tokio::fs;
+    use visualvault_config::{FolderRule, RoutingRule, SkipRules};
     use visualvault_models::DuplicateGroup;
 
     // Helper function to create a test media file
@@ -392,18 +1165,14 @@ mod tests {
         modified: DateTime<Local>,
         hash: Option<String>,
     ) -> Arc<MediaFile> {
-        let extension = path.extension().unwrap_or_default().to_string_lossy().to_string();
-
+        let file = media_file(path).file_type(file_type).modified(modified);
+        let file = match hash {
+            Some(hash) => file.hash(&hash),
+            None => file,
+        };
         Arc::new(MediaFile {
-            path,
             name: name.into(),
-            extension: extension.into(),
-            file_type,
-            size: 1024,
-            created: modified,
-            modified,
-            hash: hash.map(std::convert::Into::into),
-            metadata: None,
+            ..file.build()
         })
     }
 
@@ -419,6 +1188,15 @@ mod tests {
         }
     }
 
+    fn placed_path(outcome: PlacementOutcome) -> PathBuf {
+        match outcome {
+            PlacementOutcome::Moved(path) | PlacementOutcome::Renamed(path) | PlacementOutcome::Overwritten(path) => {
+                path
+            }
+            other => panic!("Expected file to be placed, got {other:?}"),
+        }
+    }
+
     // Helper function to create a test file on disk
     async fn create_test_file(path: &Path, content: &[u8]) -> Result<()> {
         if let Some(parent) = path.parent() {
@@ -454,7 +1232,7 @@ mod tests {
             None,
         );
 
-        let target_dir = FileOrganizer::determine_target_directory(&file, destination, &settings)?;
+        let target_dir = FileOrganizer::determine_target_directory(&file, None, destination, &settings)?;
         assert_eq!(target_dir, destination.join("2024"));
 
         Ok(())
@@ -478,12 +1256,53 @@ mod tests {
             None,
         );
 
-        let target_dir = FileOrganizer::determine_target_directory(&file, destination, &settings)?;
+        let target_dir = FileOrganizer::determine_target_directory(&file, None, destination, &settings)?;
         assert_eq!(target_dir, destination.join("2024").join("03-March"));
 
         Ok(())
     }
 
+    #[test]
+    fn test_determine_target_directory_timezone_policy() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let destination = temp_dir.path();
+        let tokyo = FixedOffset::east_opt(9 * 3600).unwrap();
+        let new_york = FixedOffset::west_opt(5 * 3600).unwrap();
+        let settings = Settings {
+            organize_by: "monthly".to_string(),
+            separate_videos: false,
+            timezone_policy: TimezonePolicy::Fixed(tokyo),
+            ..create_test_settings(destination.to_path_buf())
+        };
+
+        // Late on the last evening of January in UTC, already February in Tokyo
+        let file = create_test_media_file(
+            PathBuf::from("/source/image.jpg"),
+            "image.jpg".to_string(),
+            FileType::Image,
+            Utc.with_ymd_and_hms(2024, 1, 31, 22, 0, 0)
+                .unwrap()
+                .with_timezone(&Local),
+            None,
+        );
+
+        let target_dir = FileOrganizer::determine_target_directory(&file, Some(new_york), destination, &settings)?;
+        assert_eq!(target_dir, destination.join("2024").join("02-February"));
+
+        let exif = Settings {
+            timezone_policy: TimezonePolicy::Exif,
+            ..settings
+        };
+        let target_dir = FileOrganizer::determine_target_directory(&file, Some(new_york), destination, &exif)?;
+        assert_eq!(target_dir, destination.join("2024").join("01-January"));
+        assert_eq!(
+            FileOrganizer::folder_date(&file, TimezonePolicy::Exif, None),
+            file.modified.fixed_offset()
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn test_determine_target_directory_by_type() -> Result<()> {
         let temp_dir = TempDir::new()?;
@@ -502,7 +1321,7 @@ mod tests {
             None,
         );
 
-        let target_dir = FileOrganizer::determine_target_directory(&file, destination, &settings)?;
+        let target_dir = FileOrganizer::determine_target_directory(&file, None, destination, &settings)?;
         assert_eq!(target_dir, destination.join("Images"));
 
         Ok(())
@@ -526,12 +1345,143 @@ mod tests {
             None,
         );
 
-        let target_dir = FileOrganizer::determine_target_directory(&video_file, destination, &settings)?;
+        let target_dir = FileOrganizer::determine_target_directory(&video_file, None, destination, &settings)?;
         assert_eq!(target_dir, destination.join("Videos").join("2024").join("03-March"));
 
         Ok(())
     }
 
+    #[test]
+    fn test_determine_target_directory_separate_screenshots() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let destination = temp_dir.path();
+        let settings = Settings {
+            separate_screenshots: true,
+            ..create_test_settings(destination.to_path_buf())
+        };
+        let date = Local.with_ymd_and_hms(2024, 3, 15, 10, 0, 0).unwrap();
+
+        let screenshot = create_test_media_file(
+            PathBuf::from("/source/Screenshot_20240315-100000.png"),
+            "Screenshot_20240315-100000.png".to_string(),
+            FileType::Image,
+            date,
+            None,
+        );
+        let photo = create_test_media_file(
+            PathBuf::from("/source/photo.jpg"),
+            "photo.jpg".to_string(),
+            FileType::Image,
+            date,
+            None,
+        );
+
+        let target_dir = FileOrganizer::determine_target_directory(&screenshot, None, destination, &settings)?;
+        assert_eq!(
+            target_dir,
+            destination.join("Screenshots").join("2024").join("03-March")
+        );
+        let target_dir = FileOrganizer::determine_target_directory(&photo, None, destination, &settings)?;
+        assert_eq!(target_dir, destination.join("2024").join("03-March"));
+
+        let by_type = Settings {
+            organize_by: "type".to_string(),
+            ..settings.clone()
+        };
+        let target_dir = FileOrganizer::determine_target_directory(&screenshot, None, destination, &by_type)?;
+        assert_eq!(target_dir, destination.join("Screenshots"));
+
+        let mixed = Settings {
+            separate_screenshots: false,
+            ..settings
+        };
+        let target_dir = FileOrganizer::determine_target_directory(&screenshot, None, destination, &mixed)?;
+        assert_eq!(target_dir, destination.join("2024").join("03-March"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_determine_target_directory_folder_rules() -> Result<()> {
+        let destination = PathBuf::from("/dest");
+        let mut settings = create_test_settings(destination.clone());
+        settings.source_folder = Some(PathBuf::from("/src"));
+        settings.folder_rules = vec![
+            FolderRule::from_str("WhatsApp Images=WhatsApp/{year}").unwrap(),
+            FolderRule::from_str("Screenshots=Screenshots").unwrap(),
+        ];
+        let modified = Local.with_ymd_and_hms(2024, 3, 15, 10, 0, 0).unwrap();
+        let target = |path: &str, file_type: FileType, settings: &Settings| {
+            let file = create_test_media_file(PathBuf::from(path), "a.jpg".to_string(), file_type, modified, None);
+            FileOrganizer::determine_target_directory(&file, None, &destination, settings)
+        };
+
+        assert_eq!(
+            target("/src/WhatsApp Images/a.jpg", FileType::Image, &settings)?,
+            destination.join("WhatsApp").join("2024")
+        );
+        // Rules apply before separate_videos and the organization mode
+        assert_eq!(
+            target("/src/Screenshots/b.mp4", FileType::Video, &settings)?,
+            destination.join("Screenshots")
+        );
+        assert_eq!(
+            target("/src/Camera/a.jpg", FileType::Image, &settings)?,
+            destination.join("2024").join("03-March")
+        );
+
+        settings.folder_rules[0].enabled = false;
+        assert_eq!(
+            target("/src/WhatsApp Images/a.jpg", FileType::Image, &settings)?,
+            destination.join("2024").join("03-March")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_determine_target_directory_routing_rules() -> Result<()> {
+        let destination = PathBuf::from("/dest");
+        let mut settings = create_test_settings(destination.clone());
+        settings.source_folder = Some(PathBuf::from("/src"));
+        settings.routing_rules = vec![
+            RoutingRule::from_str(r"^IMG-\d{8}-WA=WhatsApp/{year}").unwrap(),
+            RoutingRule::from_str("^Screenshot=Screenshots").unwrap(),
+        ];
+        settings.folder_rules = vec![FolderRule::from_str("Camera=Camera").unwrap()];
+        let modified = Local.with_ymd_and_hms(2024, 3, 15, 10, 0, 0).unwrap();
+        let target = |path: &str, settings: &Settings| {
+            let path = PathBuf::from(path);
+            let name = path.file_name().unwrap().to_string_lossy().to_string();
+            let file = create_test_media_file(path, name, FileType::Image, modified, None);
+            FileOrganizer::determine_target_directory(&file, None, &destination, settings)
+        };
+
+        // Name rules come before folder rules
+        assert_eq!(
+            target("/src/Camera/IMG-20240315-WA0007.jpg", &settings)?,
+            destination.join("WhatsApp").join("2024")
+        );
+        assert_eq!(target("/src/Camera/a.jpg", &settings)?, destination.join("Camera"));
+        assert_eq!(
+            target("/src/Screenshot_1.png", &settings)?,
+            destination.join("Screenshots")
+        );
+        assert_eq!(
+            target("/src/a.jpg", &settings)?,
+            destination.join("2024").join("03-March")
+        );
+
+        // Earlier rules have priority
+        settings
+            .routing_rules
+            .insert(0, RoutingRule::from_str("WA=Chats").unwrap());
+        assert_eq!(
+            target("/src/IMG-20240315-WA0007.jpg", &settings)?,
+            destination.join("Chats")
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_generate_unique_name() -> Result<()> {
         let temp_dir = TempDir::new()?;
@@ -629,8 +1579,9 @@ mod tests {
         let config_dir = temp_dir.path().to_path_buf();
         let organizer = FileOrganizer::new(config_dir).await.unwrap();
         let result = organizer
-            .organize_file(&file, &dest_dir, &settings, &mut Vec::new())
+            .organize_file(&file, 1, &dest_dir, &settings, &Transfer::default(), &mut Vec::new())
             .await?;
+        let result = placed_path(result);
 
         // Check file was moved to correct location
         assert_eq!(result, dest_dir.join("2024").join("03-March").join("image.jpg"));
@@ -667,8 +1618,9 @@ mod tests {
         let config_dir = temp_dir.path().to_path_buf();
         let organizer = FileOrganizer::new(config_dir).await.unwrap();
         let result = organizer
-            .organize_file(&file, &dest_dir, &settings, &mut Vec::new())
+            .organize_file(&file, 1, &dest_dir, &settings, &Transfer::default(), &mut Vec::new())
             .await?;
+        let result = placed_path(result);
 
         // Check file was renamed with lowercase extension
         assert_eq!(result, dest_dir.join("2024").join("03-March").join("IMAGE.jpg"));
@@ -709,8 +1661,9 @@ mod tests {
         let config_dir = temp_dir.path().to_path_buf();
         let organizer = FileOrganizer::new(config_dir).await.unwrap();
         let result = organizer
-            .organize_file(&file, &dest_dir, &settings, &mut Vec::new())
+            .organize_file(&file, 1, &dest_dir, &settings, &Transfer::default(), &mut Vec::new())
             .await?;
+        let result = placed_path(result);
 
         // Check file was renamed
         assert_eq!(result, target_dir.join("image (1).jpg"));
@@ -808,6 +1761,67 @@ mod tests {
         assert!(!dest_dir.join("2024").join("02-February").join("image2.jpg").exists());
         assert!(dest_dir.join("2024").join("02-February").join("unique.jpg").exists());
 
+        // The report lists every file with its outcome
+        assert_eq!(result.files.len(), 3);
+        let outcome_of = |path: &PathBuf| result.files.iter().find(|f| &f.source == path).map(|f| f.outcome);
+        assert_eq!(outcome_of(&file1_path), Some(FileOutcome::Moved));
+        assert_eq!(outcome_of(&file2_path), Some(FileOutcome::SkippedDuplicate));
+        assert_eq!(outcome_of(&file3_path), Some(FileOutcome::Moved));
+        let unique = result.files.iter().find(|f| f.source == file3_path).unwrap();
+        assert_eq!(
+            unique.destination.as_deref(),
+            Some(dest_dir.join("2024").join("02-February").join("unique.jpg").as_path())
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_live_photo_video_follows_still_image() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let source_dir = temp_dir.path().join("source");
+        let dest_dir = temp_dir.path().join("dest");
+
+        let still_path = source_dir.join("IMG_0001.HEIC");
+        let video_path = source_dir.join("IMG_0001.MOV");
+        create_test_file(&still_path, b"still").await?;
+        create_test_file(&video_path, b"video").await?;
+
+        // The video was written a second later, just past the end of the month
+        let still_modified = Local.with_ymd_and_hms(2024, 1, 31, 23, 59, 59).unwrap();
+        let video_modified = Local.with_ymd_and_hms(2024, 2, 1, 0, 0, 0).unwrap();
+        let mut still = create_test_media_file(
+            still_path.clone(),
+            "IMG_0001.HEIC".to_string(),
+            FileType::Image,
+            still_modified,
+            None,
+        );
+        let mut video = create_test_media_file(
+            video_path.clone(),
+            "IMG_0001.MOV".to_string(),
+            FileType::Video,
+            video_modified,
+            None,
+        );
+        Arc::make_mut(&mut still).live_photo_pair = Some(video_path);
+        Arc::make_mut(&mut video).live_photo_pair = Some(still_path);
+
+        let mut settings = create_test_settings(dest_dir.clone());
+        settings.rename_template = Some("{date}_{counter}".parse().unwrap());
+
+        let organizer = FileOrganizer::new(temp_dir.path().to_path_buf()).await?;
+        let progress = Arc::new(RwLock::new(Progress::default()));
+        // The video comes first, yet must not be placed on its own
+        let result = organizer
+            .organize_files_with_duplicates(vec![video, still], DuplicateStats::new(), &settings, progress)
+            .await?;
+
+        assert_eq!(result.files_organized, 2);
+        let month = dest_dir.join("2024").join("01-January");
+        assert!(month.join("2024-01-31_0001.HEIC").exists());
+        assert!(month.join("2024-01-31_0001.MOV").exists());
+        assert!(!dest_dir.join("Videos").exists());
         Ok(())
     }
 
@@ -888,6 +1902,61 @@ mod tests {
         Ok(())
     }
 
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_organize_hooks_rewrite_and_veto() -> Result<()> {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = TempDir::new()?;
+        let source_dir = temp_dir.path().join("source");
+        let dest_dir = temp_dir.path().join("dest");
+        let keep = source_dir.join("keep.jpg");
+        let trip = source_dir.join("trip.jpg");
+        create_test_file(&keep, b"keep").await?;
+        create_test_file(&trip, b"trip").await?;
+
+        // Sends trip photos to their own folder and refuses everything else
+        let hook = temp_dir.path().join("hook.sh");
+        fs::write(
+            &hook,
+            "#!/bin/sh\nif grep -q trip.jpg; then echo '{\"action\":\"rewrite\",\"destination\":\"Trips/trip.jpg\"}'; \
+             else echo '{\"action\":\"veto\",\"reason\":\"not a trip\"}'; fi\n",
+        )
+        .await?;
+        fs::set_permissions(&hook, std::fs::Permissions::from_mode(0o755)).await?;
+
+        let modified = Local.with_ymd_and_hms(2024, 1, 1, 10, 0, 0).unwrap();
+        let files = vec![
+            create_test_media_file(keep.clone(), "keep.jpg".to_string(), FileType::Image, modified, None),
+            create_test_media_file(trip.clone(), "trip.jpg".to_string(), FileType::Image, modified, None),
+        ];
+        let mut settings = create_test_settings(dest_dir.clone());
+        settings.organize_hooks = vec![hook];
+
+        let organizer = FileOrganizer::new(temp_dir.path().to_path_buf()).await?;
+        let result = organizer
+            .organize_files_with_duplicates(
+                files,
+                DuplicateStats::new(),
+                &settings,
+                Arc::new(RwLock::new(Progress::default())),
+            )
+            .await?;
+
+        assert_eq!(result.files_organized, 1);
+        assert!(result.errors.is_empty());
+        assert!(dest_dir.join("Trips").join("trip.jpg").exists());
+        assert!(keep.exists());
+        let vetoed = result.files.iter().find(|f| f.source == keep).unwrap();
+        assert_eq!(vetoed.outcome, FileOutcome::Vetoed);
+        assert_eq!(vetoed.error.as_deref(), Some("Vetoed by hook.sh: not a trip"));
+        assert_eq!(
+            vetoed.destination,
+            Some(dest_dir.join("2024").join("01-January").join("keep.jpg"))
+        );
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_organize_files_no_destination() -> Result<()> {
         let settings = Settings {
@@ -928,7 +1997,7 @@ mod tests {
             None,
         );
 
-        let result = FileOrganizer::determine_target_directory(&file, temp_dir.path(), &settings);
+        let result = FileOrganizer::determine_target_directory(&file, None, temp_dir.path(), &settings);
         assert!(result.is_err());
 
         Ok(())
@@ -968,6 +2037,10 @@ mod tests {
         let prog = progress.read().await;
         assert_eq!(prog.total, 5);
         assert_eq!(prog.current, 5);
+        assert_eq!(prog.stages.len(), 1);
+        assert_eq!(prog.stages[0].name, STAGE_ORGANIZE);
+        assert_eq!(prog.stages[0].processed, 5);
+        assert_eq!(prog.overall_ratio(), 1.0);
         assert_eq!(result.files_organized, 5);
         drop(prog);
 
@@ -1731,4 +2804,646 @@ mod tests {
 
         Ok(())
     }
+
+    async fn setup_conflict(temp_dir: &TempDir) -> Result<(Arc<MediaFile>, PathBuf, Settings)> {
+        let source_file = temp_dir.path().join("source").join("image.jpg");
+        let dest_dir = temp_dir.path().join("dest");
+        let existing = dest_dir.join("2024").join("image.jpg");
+        fs::create_dir_all(existing.parent().unwrap()).await?;
+        create_test_file(&existing, b"existing").await?;
+        fs::create_dir_all(source_file.parent().unwrap()).await?;
+        create_test_file(&source_file, b"incoming").await?;
+
+        let file = create_test_media_file(
+            source_file,
+            "image.jpg".to_string(),
+            FileType::Image,
+            Local.with_ymd_and_hms(2024, 3, 15, 10, 0, 0).unwrap(),
+            None,
+        );
+        let settings = Settings {
+            organize_by: "yearly".to_string(),
+            undo_enabled: true,
+            ..create_test_settings(dest_dir)
+        };
+
+        Ok((file, existing, settings))
+    }
+
+    #[tokio::test]
+    async fn test_conflict_policy_skip() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let (file, existing, mut settings) = setup_conflict(&temp_dir).await?;
+        settings.conflict_policy = Some(ConflictPolicy::Skip);
+
+        let organizer = FileOrganizer::new(temp_dir.path().to_path_buf()).await?;
+        let progress = Arc::new(RwLock::new(Progress::default()));
+        let result = organizer
+            .organize_files_with_duplicates(vec![Arc::clone(&file)], DuplicateStats::new(), &settings, progress)
+            .await?;
+
+        assert_eq!(result.files_organized, 0);
+        assert_eq!(result.conflicts.skipped, 1);
+        assert!(file.path.exists());
+        assert_eq!(fs::read(&existing).await?, b"existing");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_rollback_threshold_moves_placed_files_back() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let source = temp_dir.path().join("source");
+        let destination = temp_dir.path().join("dest");
+        let modified = Local.with_ymd_and_hms(2024, 3, 15, 10, 0, 0).unwrap();
+
+        let mut files = Vec::new();
+        for name in ["a.jpg", "b.jpg"] {
+            create_test_file(&source.join(name), b"image").await?;
+            files.push(create_test_media_file(
+                source.join(name),
+                name.to_string(),
+                FileType::Image,
+                modified,
+                None,
+            ));
+        }
+        // Removed after the scan, so moving it fails
+        files.push(create_test_media_file(
+            source.join("gone.jpg"),
+            "gone.jpg".to_string(),
+            FileType::Image,
+            modified,
+            None,
+        ));
+
+        let organizer = FileOrganizer::new(temp_dir.path().to_path_buf()).await?;
+        let mut settings = create_test_settings(destination.clone());
+        settings.undo_enabled = true;
+
+        // One of three files failing is within a 50% threshold
+        settings.rollback_threshold = Some(50);
+        let progress = Arc::new(RwLock::new(Progress::default()));
+        let result = organizer
+            .organize_files_with_duplicates(files.clone(), DuplicateStats::new(), &settings, progress)
+            .await?;
+        assert!(!result.rolled_back);
+        assert_eq!(result.files_organized, 2);
+        organizer.undo_manager.undo().await?;
+        assert!(source.join("a.jpg").exists());
+
+        settings.rollback_threshold = Some(20);
+        let history = organizer.undo_manager.get_history().await.len();
+        let progress = Arc::new(RwLock::new(Progress::default()));
+        let result = organizer
+            .organize_files_with_duplicates(files, DuplicateStats::new(), &settings, progress)
+            .await?;
+
+        assert!(result.rolled_back);
+        assert!(!result.success);
+        assert_eq!(result.files_organized, 0);
+        assert!(result.errors[0].starts_with("Rolled back the run because 1 of 3 files failed"));
+        let outcomes: Vec<FileOutcome> = result.files.iter().map(|file| file.outcome).collect();
+        assert_eq!(
+            outcomes,
+            [FileOutcome::RolledBack, FileOutcome::RolledBack, FileOutcome::Error]
+        );
+        assert!(source.join("a.jpg").exists());
+        assert!(source.join("b.jpg").exists());
+        assert!(!destination.join("2024").join("03-March").join("a.jpg").exists());
+        // Nothing is left to undo
+        assert_eq!(organizer.undo_manager.get_history().await.len(), history);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_cancel_stops_before_the_next_file_once() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let source = temp_dir.path().join("source");
+        let destination = temp_dir.path().join("dest");
+        let modified = Local.with_ymd_and_hms(2024, 3, 15, 10, 0, 0).unwrap();
+
+        let mut files = Vec::new();
+        for name in ["a.jpg", "b.jpg"] {
+            create_test_file(&source.join(name), b"image").await?;
+            files.push(create_test_media_file(
+                source.join(name),
+                name.to_string(),
+                FileType::Image,
+                modified,
+                None,
+            ));
+        }
+        let organizer = FileOrganizer::new(temp_dir.path().to_path_buf()).await?;
+        let settings = create_test_settings(destination.clone());
+
+        organizer.cancel();
+        let progress = Arc::new(RwLock::new(Progress::default()));
+        let result = organizer
+            .organize_files_with_duplicates(files.clone(), DuplicateStats::new(), &settings, progress)
+            .await?;
+        assert!(result.cancelled);
+        assert_eq!(result.files_organized, 0);
+        assert!(source.join("a.jpg").exists());
+
+        // The cancel only applied to the run it was asked for
+        let progress = Arc::new(RwLock::new(Progress::default()));
+        let result = organizer
+            .organize_files_with_duplicates(files, DuplicateStats::new(), &settings, progress)
+            .await?;
+        assert!(!result.cancelled);
+        assert_eq!(result.files_organized, 2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_organized_file_is_left_in_place() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let (_, existing, mut settings) = setup_conflict(&temp_dir).await?;
+        settings.conflict_policy = Some(ConflictPolicy::Rename);
+
+        // The file was organized by an earlier run and scanned again
+        let file = create_test_media_file(
+            existing.clone(),
+            "image.jpg".to_string(),
+            FileType::Image,
+            Local.with_ymd_and_hms(2024, 3, 15, 10, 0, 0).unwrap(),
+            None,
+        );
+
+        let organizer = FileOrganizer::new(temp_dir.path().to_path_buf()).await?;
+        let progress = Arc::new(RwLock::new(Progress::default()));
+        let result = organizer
+            .organize_files_with_duplicates(vec![file], DuplicateStats::new(), &settings, progress)
+            .await?;
+
+        assert_eq!(result.files_organized, 0);
+        assert_eq!(result.conflicts.renamed, 0);
+        assert_eq!(result.files[0].outcome, FileOutcome::AlreadyOrganized);
+        assert_eq!(fs::read(&existing).await?, b"existing");
+        assert_eq!(std::fs::read_dir(existing.parent().unwrap())?.count(), 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_conflict_policy_overwrite_can_be_undone() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let (file, existing, mut settings) = setup_conflict(&temp_dir).await?;
+        settings.conflict_policy = Some(ConflictPolicy::OverwriteAlways);
+
+        let organizer = FileOrganizer::new(temp_dir.path().to_path_buf()).await?;
+        let progress = Arc::new(RwLock::new(Progress::default()));
+        let result = organizer
+            .organize_files_with_duplicates(vec![Arc::clone(&file)], DuplicateStats::new(), &settings, progress)
+            .await?;
+
+        assert_eq!(result.files_organized, 1);
+        assert_eq!(result.conflicts.overwritten, 1);
+        assert!(!file.path.exists());
+        assert_eq!(fs::read(&existing).await?, b"incoming");
+
+        organizer.undo_manager.undo().await?;
+
+        assert_eq!(fs::read(&file.path).await?, b"incoming");
+        assert_eq!(fs::read(&existing).await?, b"existing");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_conflict_policy_overwrite_keeps_existing_when_move_fails() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let (file, existing, settings) = setup_conflict(&temp_dir).await?;
+        fs::remove_file(&file.path).await?;
+
+        let organizer = FileOrganizer::new(temp_dir.path().to_path_buf()).await?;
+        let mut operations = Vec::new();
+        let result = organizer
+            .overwrite_file(
+                &file,
+                existing.clone(),
+                &settings,
+                &Transfer::default(),
+                &mut operations,
+            )
+            .await;
+
+        assert!(result.is_err());
+        assert!(operations.is_empty());
+        assert_eq!(fs::read(&existing).await?, b"existing");
+        assert_eq!(std::fs::read_dir(existing.parent().unwrap())?.count(), 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_conflict_policy_overwrite_if_newer_keeps_newer_existing() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let (file, existing, mut settings) = setup_conflict(&temp_dir).await?;
+        settings.conflict_policy = Some(ConflictPolicy::OverwriteIfNewer);
+
+        // Make the existing file newer than the incoming one
+        let older = std::time::SystemTime::now() - std::time::Duration::from_secs(3600);
+        std::fs::File::options()
+            .write(true)
+            .open(&file.path)?
+            .set_modified(older)?;
+
+        let organizer = FileOrganizer::new(temp_dir.path().to_path_buf()).await?;
+        let outcome = organizer
+            .organize_file(
+                &file,
+                1,
+                &settings.destination_folder.clone().unwrap(),
+                &settings,
+                &Transfer::default(),
+                &mut Vec::new(),
+            )
+            .await?;
+
+        assert!(matches!(outcome, PlacementOutcome::Skipped));
+        assert_eq!(fs::read(&existing).await?, b"existing");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_conflict_policy_ask_defers_until_resolved() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let (file, existing, mut settings) = setup_conflict(&temp_dir).await?;
+        settings.conflict_policy = Some(ConflictPolicy::Ask);
+
+        let organizer = FileOrganizer::new(temp_dir.path().to_path_buf()).await?;
+        let progress = Arc::new(RwLock::new(Progress::default()));
+        let result = organizer
+            .organize_files_with_duplicates(vec![Arc::clone(&file)], DuplicateStats::new(), &settings, progress)
+            .await?;
+
+        assert_eq!(result.conflicts.deferred, 1);
+        assert_eq!(result.pending_conflicts.len(), 1);
+        assert_eq!(result.pending_conflicts[0].target, existing);
+        assert!(file.path.exists());
+
+        let resolutions = result
+            .pending_conflicts
+            .into_iter()
+            .map(|conflict| (conflict, ConflictPolicy::Rename))
+            .collect();
+        let resolved = organizer.resolve_conflicts(resolutions, &settings).await?;
+
+        assert_eq!(resolved.files_organized, 1);
+        assert_eq!(resolved.conflicts.renamed, 1);
+        assert!(existing.with_file_name("image (1).jpg").exists());
+        assert!(!file.path.exists());
+
+        Ok(())
+    }
+
+    async fn setup_non_media(temp_dir: &TempDir) -> Result<(Vec<Arc<MediaFile>>, PathBuf)> {
+        let source_dir = temp_dir.path().join("source");
+        fs::create_dir_all(&source_dir).await?;
+        let modified = Local.with_ymd_and_hms(2024, 3, 15, 10, 0, 0).unwrap();
+
+        let mut files = Vec::new();
+        for (name, file_type) in [("photo.jpg", FileType::Image), ("notes.zip", FileType::Other)] {
+            let path = source_dir.join(name);
+            create_test_file(&path, b"content").await?;
+            files.push(create_test_media_file(
+                path,
+                name.to_string(),
+                file_type,
+                modified,
+                None,
+            ));
+        }
+
+        Ok((files, temp_dir.path().join("dest")))
+    }
+
+    #[tokio::test]
+    async fn test_non_media_policy_ignore() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let (files, dest_dir) = setup_non_media(&temp_dir).await?;
+        let settings = Settings {
+            organize_by: "yearly".to_string(),
+            non_media_policy: NonMediaPolicy::Ignore,
+            ..create_test_settings(dest_dir.clone())
+        };
+
+        let organizer = FileOrganizer::new(temp_dir.path().to_path_buf()).await?;
+        let progress = Arc::new(RwLock::new(Progress::default()));
+        let result = organizer
+            .organize_files_with_duplicates(files.clone(), DuplicateStats::new(), &settings, progress)
+            .await?;
+
+        assert_eq!(result.files_organized, 1);
+        assert_eq!(result.non_media.ignored, 1);
+        assert_eq!(result.non_media.total(), 1);
+        assert!(files[1].path.exists());
+        assert!(dest_dir.join("2024").join("photo.jpg").exists());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_skip_rules() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let source_dir = temp_dir.path().join("source");
+        fs::create_dir_all(&source_dir).await?;
+        let dest_dir = temp_dir.path().join("dest");
+        let old = Local.with_ymd_and_hms(2024, 3, 15, 10, 0, 0).unwrap();
+
+        let mut files = Vec::new();
+        for (name, size, modified) in [
+            ("thumb.jpg", 100, old),
+            ("photo.jpg", 4096, old),
+            ("new.jpg", 4096, Local::now()),
+        ] {
+            let path = source_dir.join(name);
+            create_test_file(&path, &vec![0; size]).await?;
+            files.push(media_file(path).size_on_disk().modified(modified).arc());
+        }
+        let settings = Settings {
+            organize_by: "yearly".to_string(),
+            skip_rules: SkipRules {
+                min_size_kb: 1,
+                min_age_days: 7,
+                ..SkipRules::default()
+            },
+            ..create_test_settings(dest_dir.clone())
+        };
+
+        let organizer = FileOrganizer::new(temp_dir.path().to_path_buf()).await?;
+        let progress = Arc::new(RwLock::new(Progress::default()));
+        let result = organizer
+            .organize_files_with_duplicates(files.clone(), DuplicateStats::new(), &settings, progress)
+            .await?;
+
+        assert_eq!(result.files_organized, 1);
+        assert_eq!(result.files_total, 3);
+        assert_eq!(result.skipped_by_rules.too_small, 1);
+        assert_eq!(result.skipped_by_rules.too_new, 1);
+        assert_eq!(result.skipped_by_rules.total(), 2);
+        assert!(files[0].path.exists());
+        assert!(files[2].path.exists());
+        assert!(dest_dir.join("2024").join("photo.jpg").exists());
+
+        let skipped: Vec<_> = result
+            .files
+            .iter()
+            .filter(|file| file.outcome == FileOutcome::SkippedByRule)
+            .collect();
+        assert_eq!(skipped.len(), 2);
+        assert_eq!(skipped[0].source, files[0].path);
+        assert_eq!(skipped[0].error.as_deref(), Some("Smaller than 1 KB"));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_non_media_policy_unsorted() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let (files, dest_dir) = setup_non_media(&temp_dir).await?;
+        let settings = Settings {
+            organize_by: "yearly".to_string(),
+            non_media_policy: NonMediaPolicy::Unsorted,
+            ..create_test_settings(dest_dir.clone())
+        };
+
+        let organizer = FileOrganizer::new(temp_dir.path().to_path_buf()).await?;
+        let progress = Arc::new(RwLock::new(Progress::default()));
+        let result = organizer
+            .organize_files_with_duplicates(files, DuplicateStats::new(), &settings, progress)
+            .await?;
+
+        assert_eq!(result.files_organized, 2);
+        assert_eq!(result.non_media.unsorted, 1);
+        assert_eq!(result.non_media.included, 0);
+        assert!(dest_dir.join(UNSORTED_FOLDER).join("notes.zip").exists());
+        assert!(dest_dir.join("2024").join("photo.jpg").exists());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_non_media_policy_include() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let (files, dest_dir) = setup_non_media(&temp_dir).await?;
+        let settings = Settings {
+            organize_by: "type".to_string(),
+            ..create_test_settings(dest_dir.clone())
+        };
+
+        let organizer = FileOrganizer::new(temp_dir.path().to_path_buf()).await?;
+        let progress = Arc::new(RwLock::new(Progress::default()));
+        let result = organizer
+            .organize_files_with_duplicates(files, DuplicateStats::new(), &settings, progress)
+            .await?;
+
+        assert_eq!(result.non_media.included, 1);
+        assert!(dest_dir.join("Others").join("notes.zip").exists());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_audio_template_by_type() -> Result<()> {
+        let settings = Settings {
+            organize_by: "type".to_string(),
+            ..create_test_settings(PathBuf::from("/dest"))
+        };
+        let modified = Local.with_ymd_and_hms(2021, 5, 1, 12, 0, 0).unwrap();
+
+        let mut tagged = MediaFile::clone(&create_test_media_file(
+            PathBuf::from("/src/track.mp3"),
+            "track.mp3".to_string(),
+            FileType::Audio,
+            modified,
+            None,
+        ));
+        tagged.metadata = Some(MediaMetadata::Audio(AudioMetadata {
+            artist: Some("AC/DC".to_string()),
+            album: Some("Back in Black".to_string()),
+            title: None,
+            year: None,
+        }));
+        let target = FileOrganizer::determine_target_directory(&tagged, None, Path::new("/dest"), &settings)?;
+        assert_eq!(target, PathBuf::from("/dest/Audio/AC_DC/Back in Black"));
+
+        let untagged = create_test_media_file(
+            PathBuf::from("/src/memo.m4a"),
+            "memo.m4a".to_string(),
+            FileType::Audio,
+            modified,
+            None,
+        );
+        let target = FileOrganizer::determine_target_directory(&untagged, None, Path::new("/dest"), &settings)?;
+        assert_eq!(target, PathBuf::from("/dest/Audio/Unknown Artist/Unknown Album"));
+
+        let settings = Settings {
+            audio_template: "{year}/{title}".to_string(),
+            ..settings
+        };
+        let target = FileOrganizer::determine_target_directory(&untagged, None, Path::new("/dest"), &settings)?;
+        assert_eq!(target, PathBuf::from("/dest/Audio/2021/memo"));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_rename_template_numbers_collisions_and_undo_restores_names() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let source_dir = temp_dir.path().join("source");
+        let dest_dir = temp_dir.path().join("dest");
+        let modified = Local.with_ymd_and_hms(2024, 3, 15, 14, 30, 22).unwrap();
+
+        let mut files = Vec::new();
+        for name in ["IMG_0001.JPG", "IMG_0002.JPG"] {
+            let path = source_dir.join(name);
+            create_test_file(&path, name.as_bytes()).await?;
+            files.push(create_test_media_file(
+                path,
+                name.to_string(),
+                FileType::Image,
+                modified,
+                None,
+            ));
+        }
+
+        let settings = Settings {
+            destination_folder: Some(dest_dir.clone()),
+            organize_by: "yearly".to_string(),
+            conflict_policy: Some(ConflictPolicy::Skip),
+            rename_template: Some("{date}_{time}".parse().unwrap()),
+            ..Default::default()
+        };
+
+        let organizer = FileOrganizer::new(temp_dir.path().to_path_buf()).await?;
+        let progress = Arc::new(RwLock::new(Progress::default()));
+        let result = organizer
+            .organize_files_with_duplicates(files, DuplicateStats::new(), &settings, progress)
+            .await?;
+
+        // Both files render to the same name; the second is numbered rather than skipped
+        assert_eq!(result.files_organized, 2);
+        assert!(dest_dir.join("2024/2024-03-15_143022.jpg").exists());
+        assert!(dest_dir.join("2024/2024-03-15_143022 (1).jpg").exists());
+
+        organizer.undo_manager.undo().await?;
+        assert_eq!(fs::read(source_dir.join("IMG_0001.JPG")).await?, b"IMG_0001.JPG");
+        assert_eq!(fs::read(source_dir.join("IMG_0002.JPG")).await?, b"IMG_0002.JPG");
+        assert!(!dest_dir.join("2024").exists());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_rename_template_counter_keeps_extension() -> Result<()> {
+        let file = create_test_media_file(
+            PathBuf::from("/photos/holiday.tar.gz"),
+            "holiday.tar.gz".to_string(),
+            FileType::Other,
+            Local::now(),
+            None,
+        );
+        let settings = Settings {
+            rename_template: Some("{camera}_{counter}_{original}".parse().unwrap()),
+            ..Default::default()
+        };
+
+        let name = FileOrganizer::renamed_file_name(&file, 12, &settings).await;
+        assert_eq!(name, "Unknown_0012_holiday.tar.gz");
+
+        let settings = Settings::default();
+        assert_eq!(
+            FileOrganizer::renamed_file_name(&file, 12, &settings).await,
+            "holiday.tar.gz"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_previously_organized_files_are_deleted_from_source() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let source = temp_dir.path().join("source");
+        let dest_dir = temp_dir.path().join("dest");
+        let content = vec![7u8; 1024];
+        let settings = Settings {
+            organize_by: "yearly".to_string(),
+            previously_organized: PreviouslyOrganizedPolicy::Delete,
+            ..create_test_settings(dest_dir.clone())
+        };
+        let taken = Local.with_ymd_and_hms(2024, 3, 15, 10, 0, 0).unwrap();
+        let organizer = FileOrganizer::new(temp_dir.path().to_path_buf()).await?;
+
+        let first = source.join("photo.jpg");
+        create_test_file(&first, &content).await?;
+        let file = create_test_media_file(first, "photo.jpg".to_string(), FileType::Image, taken, None);
+        let progress = Arc::new(RwLock::new(Progress::default()));
+        let result = organizer
+            .organize_files_with_duplicates(vec![file], DuplicateStats::new(), &settings, progress)
+            .await?;
+        assert_eq!(result.files_organized, 1);
+        assert_eq!(organizer.ledger().lock().await.len(), 1);
+
+        // The same photo is imported again from the card under another name
+        let again = source.join("IMG_0001.jpg");
+        create_test_file(&again, &content).await?;
+        let file = create_test_media_file(again.clone(), "IMG_0001.jpg".to_string(), FileType::Image, taken, None);
+        let progress = Arc::new(RwLock::new(Progress::default()));
+        let result = organizer
+            .organize_files_with_duplicates(vec![file], DuplicateStats::new(), &settings, progress)
+            .await?;
+
+        assert_eq!(result.files_organized, 0);
+        assert_eq!(result.files[0].outcome, FileOutcome::DeletedOrganizedBefore);
+        assert_eq!(
+            result.files[0].destination,
+            Some(dest_dir.join("2024").join("photo.jpg"))
+        );
+        assert!(!again.exists());
+        assert!(!dest_dir.join("2024").join("IMG_0001.jpg").exists());
+
+        organizer.undo_manager.undo().await?;
+        assert_eq!(fs::read(&again).await?, content);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_mismatched_extensions_are_fixed() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let dest_dir = temp_dir.path().join("dest");
+        let taken = Local.with_ymd_and_hms(2024, 3, 15, 10, 0, 0).unwrap();
+        let organizer = FileOrganizer::new(temp_dir.path().to_path_buf()).await?;
+
+        let mut files = Vec::new();
+        for name in ["screenshot.jpg", "holiday.jpg"] {
+            let path = temp_dir.path().join("source").join(name);
+            create_test_file(&path, b"png content").await?;
+            let mut file = create_test_media_file(path, name.to_string(), FileType::Image, taken, None);
+            if name == "screenshot.jpg" {
+                Arc::make_mut(&mut file).content_extension = Some("png".into());
+            }
+            files.push(file);
+        }
+
+        let settings = Settings {
+            organize_by: "yearly".to_string(),
+            extension_mismatch: ExtensionMismatchPolicy::Fix,
+            ..create_test_settings(dest_dir.clone())
+        };
+        let progress = Arc::new(RwLock::new(Progress::default()));
+        let result = organizer
+            .organize_files_with_duplicates(files, DuplicateStats::new(), &settings, progress)
+            .await?;
+
+        assert_eq!(result.files_organized, 2);
+        assert!(dest_dir.join("2024").join("screenshot.png").exists());
+        assert!(dest_dir.join("2024").join("holiday.jpg").exists());
+        Ok(())
+    }
 }
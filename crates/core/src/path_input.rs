@@ -0,0 +1,178 @@
+use std::fs;
+use std::path::{MAIN_SEPARATOR, Path, PathBuf, is_separator};
+
+use visualvault_models::FolderCheck;
+
+#[cfg(unix)]
+use crate::disk_space::c_path;
+use crate::disk_space::free_space;
+
+/// Outcome of completing a typed folder path
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathCompletion {
+    /// The typed text, extended as far as all matching folders agree
+    pub completed: String,
+    /// Names of the matching folders when more than one matches
+    pub candidates: Vec<String>,
+}
+
+/// Completes the last component of `input` against the folders on disk, like a shell.
+///
+/// A single match is completed with a trailing separator so the next Tab descends into
+/// it. With several matches the input is extended to their common prefix and the names
+/// are returned as candidates. Folders starting with a dot only match when the typed
+/// component does too, and a leading `~` stands for the home folder.
+/// Returns `None` if nothing matches.
+#[must_use]
+pub fn complete_path(input: &str) -> Option<PathCompletion> {
+    if input.is_empty() {
+        return None;
+    }
+    let split = input.rfind(is_separator).map_or(0, |i| i + 1);
+    let (typed_dir, partial) = input.split_at(split);
+    let dir = if typed_dir.is_empty() {
+        PathBuf::from(".")
+    } else {
+        expand_home(typed_dir)
+    };
+
+    let mut names: Vec<String> = fs::read_dir(dir)
+        .ok()?
+        .filter_map(std::result::Result::ok)
+        .filter(|entry| entry.path().is_dir())
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .filter(|name| name.starts_with(partial) && (partial.starts_with('.') || !name.starts_with('.')))
+        .collect();
+    names.sort();
+
+    let (first, rest) = names.split_first()?;
+    if rest.is_empty() {
+        return Some(PathCompletion {
+            completed: format!("{typed_dir}{first}{MAIN_SEPARATOR}"),
+            candidates: Vec::new(),
+        });
+    }
+
+    let common = rest.iter().fold(first.as_str(), |common, name| {
+        let len = common
+            .char_indices()
+            .zip(name.chars())
+            .find(|((_, a), b)| a != b)
+            .map_or_else(|| common.len().min(name.len()), |((i, _), _)| i);
+        &common[..len]
+    });
+    Some(PathCompletion {
+        completed: format!("{typed_dir}{common}"),
+        candidates: names.clone(),
+    })
+}
+
+/// Checks whether `path` can be used as a source or destination folder.
+///
+/// A folder that does not exist yet is judged by the nearest folder above it, which is
+/// where it would be created. Returns `None` if no part of a relative path exists.
+#[must_use]
+pub fn check_folder(path: &Path) -> Option<FolderCheck> {
+    let path = expand_home(&path.to_string_lossy());
+    let existing = path
+        .ancestors()
+        .find(|dir| !dir.as_os_str().is_empty() && dir.is_dir())?
+        .to_path_buf();
+    Some(FolderCheck {
+        exists: path.exists(),
+        is_dir: path.is_dir(),
+        writable: is_writable(&existing),
+        free_space: free_space(&existing),
+        existing,
+    })
+}
+
+/// Replaces a leading `~` with the home folder
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix('~'), dirs::home_dir()) {
+        (Some(rest), Some(home)) if rest.is_empty() || rest.starts_with(is_separator) => {
+            home.join(rest.trim_start_matches(is_separator))
+        }
+        _ => PathBuf::from(path),
+    }
+}
+
+#[cfg(unix)]
+pub(crate) fn is_writable(dir: &Path) -> bool {
+    let Some(path) = c_path(dir) else {
+        return false;
+    };
+    // SAFETY: `path` is a NUL-terminated string that outlives the call
+    unsafe { libc::access(path.as_ptr(), libc::W_OK) == 0 }
+}
+
+#[cfg(not(unix))]
+pub(crate) fn is_writable(dir: &Path) -> bool {
+    fs::metadata(dir).is_ok_and(|metadata| !metadata.permissions().readonly())
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use tempfile::TempDir;
+
+    fn typed(dir: &Path, rest: &str) -> String {
+        format!("{}{MAIN_SEPARATOR}{rest}", dir.display())
+    }
+
+    #[test]
+    fn test_complete_single_and_common_prefix() {
+        let temp = TempDir::new().unwrap();
+        for name in ["Photos", "Pictures", "Videos", ".private"] {
+            fs::create_dir(temp.path().join(name)).unwrap();
+        }
+        fs::write(temp.path().join("Vacation.jpg"), b"").unwrap();
+
+        let single = complete_path(&typed(temp.path(), "V")).unwrap();
+        assert_eq!(single.completed, typed(temp.path(), &format!("Videos{MAIN_SEPARATOR}")));
+        assert!(single.candidates.is_empty());
+
+        let several = complete_path(&typed(temp.path(), "P")).unwrap();
+        assert_eq!(several.completed, typed(temp.path(), "P"));
+        assert_eq!(several.candidates, vec!["Photos", "Pictures"]);
+
+        let hidden = complete_path(&typed(temp.path(), ".")).unwrap();
+        assert_eq!(hidden.candidates, Vec::<String>::new());
+        assert!(hidden.completed.ends_with(&format!(".private{MAIN_SEPARATOR}")));
+
+        assert!(complete_path(&typed(temp.path(), "x")).is_none());
+        assert!(complete_path("").is_none());
+    }
+
+    #[test]
+    fn test_common_prefix_extends_input() {
+        let temp = TempDir::new().unwrap();
+        fs::create_dir(temp.path().join("archive-2023")).unwrap();
+        fs::create_dir(temp.path().join("archive-2024")).unwrap();
+
+        let completion = complete_path(&typed(temp.path(), "a")).unwrap();
+        assert_eq!(completion.completed, typed(temp.path(), "archive-202"));
+    }
+
+    #[test]
+    fn test_check_folder_uses_nearest_existing_parent() {
+        let temp = TempDir::new().unwrap();
+        let check = check_folder(temp.path()).unwrap();
+        assert!(check.exists && check.is_dir && check.writable);
+        assert_eq!(check.existing, temp.path());
+        if cfg!(unix) {
+            assert!(check.free_space.is_some_and(|free| free > 0));
+        }
+
+        let missing = check_folder(&temp.path().join("new").join("folder")).unwrap();
+        assert!(!missing.exists);
+        assert_eq!(missing.existing, temp.path());
+
+        let file = temp.path().join("photo.jpg");
+        fs::write(&file, b"").unwrap();
+        let check = check_folder(&file).unwrap();
+        assert!(check.exists && !check.is_dir);
+    }
+}
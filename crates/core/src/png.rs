@@ -0,0 +1,110 @@
+use color_eyre::eyre::Result;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+/// Every PNG file starts with this signature, followed by its `IHDR` chunk
+const PNG_SIGNATURE: &[u8; 8] = b"\x89PNG\r\n\x1a\n";
+
+/// Dimensions and color layout of a PNG image, as declared in its header
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PngImage {
+    pub width: u32,
+    pub height: u32,
+    /// Color layout in the same wording as decoded images, e.g. "RGBA 8-bit"
+    pub color_type: String,
+}
+
+/// Reads the dimensions of a PNG image from its header without decoding it.
+///
+/// Returns `None` if the file is not a PNG image.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be opened or read.
+pub fn read_png_image(path: &Path) -> Result<Option<PngImage>> {
+    let mut header = [0u8; 26];
+    let mut file = File::open(path)?;
+    let mut filled = 0;
+    while filled < header.len() {
+        let read = file.read(&mut header[filled..])?;
+        if read == 0 {
+            return Ok(None);
+        }
+        filled += read;
+    }
+    Ok(parse_png_header(&header))
+}
+
+fn parse_png_header(header: &[u8; 26]) -> Option<PngImage> {
+    if &header[..8] != PNG_SIGNATURE || &header[12..16] != b"IHDR" {
+        return None;
+    }
+    let width = u32::from_be_bytes(header[16..20].try_into().ok()?);
+    let height = u32::from_be_bytes(header[20..24].try_into().ok()?);
+    let bits = header[24];
+    let layout = match header[25] {
+        0 => "Grayscale",
+        2 => "RGB",
+        3 => "Indexed",
+        4 => "Grayscale + Alpha",
+        6 => "RGBA",
+        _ => return None,
+    };
+
+    (width > 0 && height > 0).then(|| PngImage {
+        width,
+        height,
+        color_type: format!("{layout} {bits}-bit"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    #![allow(clippy::panic_in_result_fn)]
+    use super::*;
+    use tempfile::TempDir;
+
+    fn header(width: u32, height: u32, bits: u8, color: u8) -> [u8; 26] {
+        let mut header = [0u8; 26];
+        header[..8].copy_from_slice(PNG_SIGNATURE);
+        header[8..12].copy_from_slice(&13u32.to_be_bytes());
+        header[12..16].copy_from_slice(b"IHDR");
+        header[16..20].copy_from_slice(&width.to_be_bytes());
+        header[20..24].copy_from_slice(&height.to_be_bytes());
+        header[24] = bits;
+        header[25] = color;
+        header
+    }
+
+    #[test]
+    fn test_parse_png_header() {
+        let image = parse_png_header(&header(1170, 2532, 8, 6)).unwrap();
+        assert_eq!((image.width, image.height), (1170, 2532));
+        assert_eq!(image.color_type, "RGBA 8-bit");
+        assert_eq!(
+            parse_png_header(&header(16, 16, 16, 0)).unwrap().color_type,
+            "Grayscale 16-bit"
+        );
+
+        assert_eq!(parse_png_header(&header(0, 10, 8, 2)), None);
+        assert_eq!(parse_png_header(&header(10, 10, 8, 5)), None);
+        let mut jpeg = header(10, 10, 8, 2);
+        jpeg[..3].copy_from_slice(&[0xFF, 0xD8, 0xFF]);
+        assert_eq!(parse_png_header(&jpeg), None);
+    }
+
+    #[test]
+    fn test_read_png_image() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let png = temp_dir.path().join("screen.png");
+        std::fs::write(&png, header(1920, 1080, 8, 2))?;
+        assert_eq!(read_png_image(&png)?.map(|image| image.width), Some(1920));
+
+        let short = temp_dir.path().join("short.png");
+        std::fs::write(&short, PNG_SIGNATURE)?;
+        assert_eq!(read_png_image(&short)?, None);
+        Ok(())
+    }
+}
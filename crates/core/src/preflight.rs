@@ -0,0 +1,247 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use visualvault_models::PreflightIssue;
+
+use crate::path_input::is_writable;
+
+/// Entries of a folder looked at to tell whether its file system ignores letter case
+const CASE_PROBE_ENTRIES: usize = 200;
+
+/// Checks the folders before scanning `source`.
+///
+/// The source must be a readable folder. When a destination is configured, it must not
+/// be the source itself, and nesting one inside the other is reported as a warning.
+#[must_use]
+pub fn preflight_scan(source: &Path, destination: Option<&Path>) -> Vec<PreflightIssue> {
+    let mut issues = check_source(source);
+    if let Some(destination) = destination {
+        issues.extend(folder_overlap(source, destination));
+    }
+    issues
+}
+
+/// Checks the folders before organizing files from `source` into `destination`.
+///
+/// The destination must be a writable folder or be creatable. Besides the overlap checks
+/// of [`preflight_scan`], a read-only source and a destination that ignores letter case
+/// while the source does not are reported as warnings.
+#[must_use]
+pub fn preflight_organize(source: Option<&Path>, destination: &Path) -> Vec<PreflightIssue> {
+    let mut issues = check_destination(destination);
+    let Some(source) = source else {
+        return issues;
+    };
+
+    if source.is_dir() && !is_writable(source) {
+        issues.push(PreflightIssue::warning(format!(
+            "Source folder {} is read-only, so files cannot be moved out of it",
+            source.display()
+        )));
+    }
+    issues.extend(folder_overlap(source, destination));
+
+    if let (Some(false), Some(true)) = (ignores_case(source), ignores_case(destination)) {
+        issues.push(PreflightIssue::warning(
+            "The destination ignores letter case but the source does not; files whose names differ only in case will conflict",
+        ));
+    }
+    issues
+}
+
+fn check_source(source: &Path) -> Vec<PreflightIssue> {
+    if !source.exists() {
+        return vec![PreflightIssue::blocking(format!(
+            "Source folder {} does not exist",
+            source.display()
+        ))];
+    }
+    if !source.is_dir() {
+        return vec![PreflightIssue::blocking(format!(
+            "Source {} is not a folder",
+            source.display()
+        ))];
+    }
+    match fs::read_dir(source) {
+        Ok(_) => Vec::new(),
+        Err(e) => vec![PreflightIssue::blocking(format!(
+            "Cannot read source folder {}: {e}",
+            source.display()
+        ))],
+    }
+}
+
+fn check_destination(destination: &Path) -> Vec<PreflightIssue> {
+    if destination.exists() && !destination.is_dir() {
+        return vec![PreflightIssue::blocking(format!(
+            "Destination {} is not a folder",
+            destination.display()
+        ))];
+    }
+    // A missing destination is created inside the nearest folder above it
+    let Some(existing) = destination.ancestors().find(|dir| dir.is_dir()) else {
+        return vec![PreflightIssue::blocking(format!(
+            "Destination folder {} cannot be created",
+            destination.display()
+        ))];
+    };
+    if is_writable(existing) {
+        Vec::new()
+    } else {
+        vec![PreflightIssue::blocking(format!(
+            "Destination folder {} is read-only",
+            existing.display()
+        ))]
+    }
+}
+
+/// Reports whether `source` and `destination` are the same folder or nested inside each other.
+///
+/// Scans leave a destination inside the source alone and organizing leaves files that already
+/// are at their target in place, so nesting is only a warning.
+#[must_use]
+pub fn folder_overlap(source: &Path, destination: &Path) -> Option<PreflightIssue> {
+    let source = resolve(source);
+    let destination = resolve(destination);
+
+    if source == destination {
+        Some(PreflightIssue::blocking(
+            "Source and destination are the same folder; organizing would move files onto themselves",
+        ))
+    } else if destination.starts_with(&source) {
+        Some(PreflightIssue::warning(
+            "The destination is inside the source folder; scans skip it",
+        ))
+    } else if source.starts_with(&destination) {
+        Some(PreflightIssue::warning(
+            "The source is inside the destination folder; files already organized may be moved again",
+        ))
+    } else {
+        None
+    }
+}
+
+/// Resolves links and relative parts of `path`, also when its last folders do not exist yet
+pub(crate) fn resolve(path: &Path) -> PathBuf {
+    for existing in path.ancestors() {
+        if let Ok(resolved) = existing.canonicalize() {
+            let rest = path.strip_prefix(existing).unwrap_or_else(|_| Path::new(""));
+            return resolved.join(rest);
+        }
+    }
+    path.to_path_buf()
+}
+
+/// Whether the file system holding `path` treats names that differ only in case as the same.
+///
+/// Judged by looking up an entry of the nearest existing folder with its letter case
+/// swapped; `None` if no entry has letters to swap.
+fn ignores_case(path: &Path) -> Option<bool> {
+    let dir = path.ancestors().find(|dir| dir.is_dir())?;
+    for dir in dir.ancestors() {
+        let Ok(entries) = fs::read_dir(dir) else {
+            continue;
+        };
+        let names: HashSet<String> = entries
+            .filter_map(std::result::Result::ok)
+            .take(CASE_PROBE_ENTRIES)
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            .collect();
+
+        let swappable = names.iter().find_map(|name| {
+            let swapped = swap_case(name);
+            (swapped != *name && !names.contains(&swapped)).then_some(swapped)
+        });
+        if let Some(swapped) = swappable {
+            return Some(dir.join(swapped).exists());
+        }
+    }
+    None
+}
+
+fn swap_case(name: &str) -> String {
+    name.chars()
+        .flat_map(|c| {
+            if c.is_uppercase() {
+                c.to_lowercase().collect::<Vec<_>>()
+            } else {
+                c.to_uppercase().collect()
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+    use tempfile::TempDir;
+    use visualvault_models::PreflightSeverity;
+
+    fn severities(issues: &[PreflightIssue]) -> Vec<PreflightSeverity> {
+        issues.iter().map(|issue| issue.severity).collect()
+    }
+
+    #[test]
+    fn test_preflight_scan() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("photos");
+        assert_eq!(
+            severities(&preflight_scan(&source, None)),
+            [PreflightSeverity::Blocking]
+        );
+
+        std::fs::create_dir(&source).unwrap();
+        assert!(preflight_scan(&source, None).is_empty());
+        assert!(preflight_scan(&source, Some(&temp_dir.path().join("library"))).is_empty());
+
+        // The destination does not exist yet but would be created inside the source
+        let nested = source.join("organized");
+        assert_eq!(
+            severities(&preflight_scan(&source, Some(&nested))),
+            [PreflightSeverity::Warning]
+        );
+        assert_eq!(
+            severities(&preflight_scan(&source, Some(&source.join(".")))),
+            [PreflightSeverity::Blocking]
+        );
+    }
+
+    #[test]
+    fn test_preflight_organize() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("photos");
+        std::fs::create_dir(&source).unwrap();
+        std::fs::write(source.join("Image.jpg"), b"data").unwrap();
+
+        assert!(preflight_organize(Some(&source), &temp_dir.path().join("new").join("library")).is_empty());
+        assert!(preflight_organize(None, temp_dir.path()).is_empty());
+
+        let file = temp_dir.path().join("notes.txt");
+        std::fs::write(&file, b"notes").unwrap();
+        assert_eq!(
+            severities(&preflight_organize(Some(&source), &file)),
+            [PreflightSeverity::Blocking]
+        );
+        assert_eq!(
+            severities(&preflight_organize(Some(&source.join("album")), &source)),
+            [PreflightSeverity::Warning]
+        );
+    }
+
+    #[test]
+    fn test_swap_case() {
+        assert_eq!(swap_case("IMG_0001.jpg"), "img_0001.JPG");
+        assert_eq!(swap_case("2024"), "2024");
+    }
+
+    #[test]
+    fn test_ignores_case_is_consistent_within_a_folder() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("Photo.jpg"), b"data").unwrap();
+        let direct = ignores_case(temp_dir.path());
+        assert!(direct.is_some());
+        assert_eq!(ignores_case(&temp_dir.path().join("missing")), direct);
+    }
+}
@@ -0,0 +1,264 @@
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::Arc;
+
+use color_eyre::eyre::{Result, eyre};
+use serde::Deserialize;
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::Command;
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+use visualvault_config::Settings;
+use visualvault_models::RemoteTransfer;
+use visualvault_utils::Progress;
+
+/// Errors kept from the rclone log; the rest are only counted
+const MAX_KEPT_ERRORS: usize = 50;
+
+/// Moves organized files from their staging folder to an rclone remote by running the
+/// `rclone` binary, following the statistics it logs once a second.
+#[derive(Debug, Clone)]
+pub struct RcloneTransfer {
+    binary: String,
+    remote: String,
+    staging: PathBuf,
+    /// Folder below the staging folder that is never transferred
+    quarantine: Option<PathBuf>,
+}
+
+/// One line of `rclone --use-json-log` output
+#[derive(Debug, Deserialize)]
+struct LogLine {
+    level: String,
+    msg: String,
+    #[serde(default)]
+    stats: Option<TransferStats>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TransferStats {
+    bytes: u64,
+    total_bytes: u64,
+    transfers: usize,
+    total_transfers: usize,
+    #[serde(default)]
+    transferring: Option<Vec<Transferring>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Transferring {
+    name: String,
+}
+
+impl RcloneTransfer {
+    /// The transfer to the rclone remote `settings` name as destination, if they do.
+    #[must_use]
+    pub fn from_settings(settings: &Settings) -> Option<Self> {
+        let remote = settings.rclone_remote()?;
+        let staging = settings.rclone.staging_path(remote);
+        let quarantine = settings
+            .quarantine_path()
+            .and_then(|path| path.strip_prefix(&staging).ok().map(Path::to_path_buf));
+        Some(Self {
+            binary: settings.rclone.binary.clone(),
+            remote: remote.to_string(),
+            staging,
+            quarantine,
+        })
+    }
+
+    #[must_use]
+    pub fn remote(&self) -> &str {
+        &self.remote
+    }
+
+    #[must_use]
+    pub fn staging(&self) -> &Path {
+        &self.staging
+    }
+
+    /// Moves everything in the staging folder except quarantined files to the remote.
+    ///
+    /// Files left behind by an earlier failed transfer are moved along. What rclone could not
+    /// move stays in the staging folder; its errors, or why rclone could not run, are listed
+    /// in the result.
+    pub async fn run(&self, progress: &Arc<RwLock<Progress>>) -> RemoteTransfer {
+        let mut transfer = RemoteTransfer {
+            remote: self.remote.clone(),
+            staging: self.staging.clone(),
+            ..RemoteTransfer::default()
+        };
+        if self.staging.exists() {
+            info!("Moving {} to {} with rclone", self.staging.display(), self.remote);
+            if let Err(e) = self.move_files(&mut transfer, progress).await {
+                warn!("rclone transfer failed: {}", e);
+                transfer.errors.push(e.to_string());
+            }
+        }
+        transfer
+    }
+
+    async fn move_files(&self, transfer: &mut RemoteTransfer, progress: &Arc<RwLock<Progress>>) -> Result<()> {
+        let mut child = Command::new(&self.binary)
+            .args(self.args())
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| {
+                eyre!(
+                    "Could not run {}: {e}; install rclone or set [rclone] binary",
+                    self.binary
+                )
+            })?;
+
+        if let Some(stderr) = child.stderr.take() {
+            let mut lines = BufReader::new(stderr).lines();
+            while let Some(line) = lines.next_line().await? {
+                apply_log_line(&line, transfer, &mut *progress.write().await);
+            }
+        }
+
+        let status = child.wait().await?;
+        if !status.success() && transfer.errors.is_empty() {
+            return Err(eyre!("rclone exited with {status}"));
+        }
+        Ok(())
+    }
+
+    fn args(&self) -> Vec<String> {
+        let mut args = vec![
+            "move".to_string(),
+            self.staging.to_string_lossy().into_owned(),
+            self.remote.clone(),
+            "--delete-empty-src-dirs".to_string(),
+            "--use-json-log".to_string(),
+            "--stats".to_string(),
+            "1s".to_string(),
+            "--stats-log-level".to_string(),
+            "NOTICE".to_string(),
+        ];
+        if let Some(quarantine) = &self.quarantine {
+            args.push("--exclude".to_string());
+            args.push(format!("/{}/**", quarantine.to_string_lossy().replace('\\', "/")));
+        }
+        args
+    }
+}
+
+/// Updates `transfer` and `progress` from one line rclone logged
+fn apply_log_line(line: &str, transfer: &mut RemoteTransfer, progress: &mut Progress) {
+    let Ok(entry) = serde_json::from_str::<LogLine>(line) else {
+        // rclone prints a few plain lines, such as failures to parse its flags
+        if !line.trim().is_empty() {
+            warn!("rclone: {}", line.trim());
+        }
+        return;
+    };
+
+    if let Some(stats) = entry.stats {
+        transfer.transferred = stats.transfers;
+        transfer.bytes = stats.bytes;
+        progress.total = stats.total_transfers;
+        progress.total_bytes = stats.total_bytes;
+        let file = stats
+            .transferring
+            .and_then(|files| files.into_iter().next())
+            .map(|file| file.name)
+            .or_else(|| progress.current_file.clone())
+            .unwrap_or_default();
+        progress.set_file_progress(stats.transfers, stats.bytes, file);
+    } else if matches!(entry.level.as_str(), "error" | "critical") {
+        warn!("rclone: {}", entry.msg);
+        if transfer.errors.len() < MAX_KEPT_ERRORS {
+            transfer.errors.push(entry.msg);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use visualvault_config::Rclone;
+
+    fn transfer(staging: &Path, binary: &str) -> RcloneTransfer {
+        let settings = Settings {
+            destination_folder: Some(PathBuf::from("gdrive:Photos")),
+            rclone: Rclone {
+                binary: binary.to_string(),
+                staging_folder: Some(staging.to_path_buf()),
+            },
+            ..Settings::default()
+        };
+        RcloneTransfer::from_settings(&settings).unwrap()
+    }
+
+    #[test]
+    fn test_apply_log_lines() {
+        let mut transfer = RemoteTransfer::default();
+        let mut progress = Progress::new();
+        let lines = [
+            r#"{"level":"notice","msg":"stats","stats":{"bytes":1024,"totalBytes":4096,"transfers":1,"totalTransfers":3,"transferring":[{"name":"2024/03/b.jpg","bytes":10,"size":2048}]}}"#,
+            r#"{"level":"error","msg":"2024/03/c.jpg: Failed to copy: quota exceeded","object":"2024/03/c.jpg"}"#,
+            r#"{"level":"notice","msg":"stats","stats":{"bytes":3072,"totalBytes":4096,"transfers":2,"totalTransfers":3,"transferring":null}}"#,
+            "Usage: rclone move",
+        ];
+        for line in lines {
+            apply_log_line(line, &mut transfer, &mut progress);
+        }
+
+        assert_eq!(transfer.transferred, 2);
+        assert_eq!(transfer.bytes, 3072);
+        assert_eq!(transfer.errors, ["2024/03/c.jpg: Failed to copy: quota exceeded"]);
+        assert!(!transfer.is_complete());
+        assert_eq!(progress.total, 3);
+        assert_eq!(progress.total_bytes, 4096);
+        assert_eq!(progress.current, 2);
+        assert_eq!(progress.current_file.as_deref(), Some("2024/03/b.jpg"));
+    }
+
+    #[test]
+    fn test_quarantine_is_excluded() {
+        let staging = PathBuf::from("/staging");
+        let args = transfer(&staging, "rclone").args();
+        assert_eq!(args[..3], ["move", "/staging/gdrive_Photos", "gdrive:Photos"]);
+        assert!(args.ends_with(&["--exclude".to_string(), "/Quarantine/**".to_string()]));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_run_reports_rclone_progress() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let script = dir.path().join("rclone");
+        std::fs::write(
+            &script,
+            "#!/bin/sh\n\
+             echo \"$@\" > \"$(dirname \"$0\")/args\"\n\
+             echo '{\"level\":\"notice\",\"msg\":\"\",\"stats\":{\"bytes\":3,\"totalBytes\":3,\"transfers\":1,\"totalTransfers\":1}}' >&2\n\
+             exit 0\n",
+        ).unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+        let rclone = transfer(&dir.path().join("staging"), &script.to_string_lossy());
+        std::fs::create_dir_all(rclone.staging().join("2024")).unwrap();
+        std::fs::write(rclone.staging().join("2024").join("a.jpg"), b"abc").unwrap();
+
+        let progress = Arc::new(RwLock::new(Progress::new()));
+        let result = rclone.run(&progress).await;
+        assert!(result.is_complete());
+        assert_eq!(result.transferred, 1);
+        assert_eq!(result.remote, "gdrive:Photos");
+        assert_eq!(progress.read().await.bytes_processed, 3);
+        let args = std::fs::read_to_string(dir.path().join("args")).unwrap();
+        assert!(args.starts_with(&format!("move {} gdrive:Photos", rclone.staging().display())));
+
+        let missing = transfer(&dir.path().join("staging"), "/nonexistent/rclone");
+        let failed = missing.run(&progress).await;
+        assert!(failed.errors[0].starts_with("Could not run /nonexistent/rclone"));
+    }
+}
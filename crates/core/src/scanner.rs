@@ -1,25 +1,55 @@
+use ahash::AHashMap;
 use chrono::Local;
 use color_eyre::eyre::Result;
+use rayon::prelude::{IntoParallelIterator, ParallelIterator};
+use smallvec::SmallVec;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
-use std::sync::Arc;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 use std::{path::Path, sync::atomic::AtomicUsize};
 use tokio::sync::RwLock;
-use tracing::{error, info};
-use visualvault_config::Settings;
-use visualvault_models::{DuplicateStats, FilterSet, MediaFile};
-use visualvault_utils::Progress;
+use tracing::{debug, error, info};
+use visualvault_config::{ExtensionMismatchPolicy, HashAlgorithm, Settings, TypeMapping};
+use visualvault_models::{
+    ArchiveMedia, CaptureInfo, DuplicateStats, ErrorEntry, ErrorSource, FileTags, FileType, FilterSet, ImageMetadata,
+    MediaFile, MediaMetadata, StatsSnapshot, SuspectFile, SuspectReason,
+};
 use visualvault_utils::datetime::system_time_to_datetime;
 use visualvault_utils::media_types::{MEDIA_EXTENSIONS, determine_file_type};
+use visualvault_utils::{Progress, STAGE_HASH, STAGE_METADATA, STAGE_WALK};
 use walkdir::WalkDir;
 
-use crate::database_cache::CacheEntry;
-use crate::{Cache, DuplicateDetector};
+use crate::database_cache::{CacheEntry, ExtensionStats, FileQuery, ImportRecord};
+use crate::{
+    Cache, ConcurrencyPlan, DuplicateDetector, FileList, IoThrottle, check_file_header, format_extension, is_archive,
+    is_live_photo_part, list_archive, live_photo_partners, read_audio_metadata, read_exif_capture, read_png_image,
+};
+
+/// Files read before the scan hands them to its file list
+const SCAN_BATCH: usize = 1000;
+
+/// Error of a scan stopped by [`Scanner::cancel`]
+#[derive(Debug, thiserror::Error)]
+#[error("Scan cancelled")]
+pub struct ScanCancelled;
 
 #[derive(Clone)]
 pub struct Scanner {
     pub is_scanning: Arc<AtomicBool>,
+    /// Set to stop the running scan at the next file
+    cancelled: Arc<AtomicBool>,
     cache: Arc<RwLock<Box<dyn Cache>>>,
+    /// Files and folders that could not be read during the current scan
+    failures: Arc<Mutex<Vec<ErrorEntry>>>,
+    /// Files of the current scan that failed the header check and were left out of its results
+    suspects: Arc<Mutex<Vec<SuspectFile>>>,
+    /// Media found inside archives by the current scan
+    archive_media: Arc<Mutex<Vec<ArchiveMedia>>>,
+    /// How many files the last scan read and hashed at once
+    concurrency: Arc<Mutex<Option<ConcurrencyPlan>>>,
+    /// Limits on the reads of the current scan and the duplicate hashing that follows it
+    throttle: Arc<Mutex<IoThrottle>>,
 }
 
 impl Scanner {
@@ -27,10 +57,42 @@ impl Scanner {
     pub fn new<C: Cache + 'static>(cache: C) -> Self {
         Self {
             is_scanning: Arc::new(AtomicBool::new(false)),
+            cancelled: Arc::new(AtomicBool::new(false)),
             cache: Arc::new(RwLock::new(Box::new(cache))),
+            failures: Arc::new(Mutex::new(Vec::new())),
+            suspects: Arc::new(Mutex::new(Vec::new())),
+            archive_media: Arc::new(Mutex::new(Vec::new())),
+            concurrency: Arc::new(Mutex::new(None)),
+            throttle: Arc::new(Mutex::new(IoThrottle::unlimited())),
         }
     }
 
+    /// Creates a duplicate detector that shares the scanner's cache for full hashes, hashes
+    /// as many files at once as the last scan planned, keeps to its throttle and stops when
+    /// the scanner is cancelled
+    #[must_use]
+    pub fn duplicate_detector(&self, algorithm: HashAlgorithm) -> DuplicateDetector {
+        let hash_workers = self.concurrency().map_or(1, |plan| plan.hash_workers);
+        DuplicateDetector::with_algorithm(algorithm)
+            .with_cache(Arc::clone(&self.cache))
+            .with_concurrency(hash_workers)
+            .with_throttle(self.throttle())
+            .with_cancel(Arc::clone(&self.cancelled))
+    }
+
+    /// How many files the last scan read and hashed at once, if it got that far
+    #[must_use]
+    pub fn concurrency(&self) -> Option<ConcurrencyPlan> {
+        self.concurrency.lock().ok().and_then(|plan| *plan)
+    }
+
+    /// The limits on reads the last scan started with
+    fn throttle(&self) -> IoThrottle {
+        self.throttle
+            .lock()
+            .map_or_else(|_| IoThrottle::unlimited(), |throttle| throttle.clone())
+    }
+
     /// Initializes the scanner's cache by loading data from the database.
     ///
     /// # Errors
@@ -68,8 +130,219 @@ impl Scanner {
         cache_lock.len().await
     }
 
+    /// Returns the tags stored for the file with content hash `hash`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache cannot be read.
+    pub async fn file_tags(&self, hash: &str) -> Result<Option<FileTags>> {
+        self.cache.read().await.get_file_tags(hash).await
+    }
+
+    /// Stores the tags of the file with content hash `hash` and the given size.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache cannot be written.
+    pub async fn save_file_tags(&self, hash: &str, size: u64, tags: &FileTags) -> Result<()> {
+        self.cache.read().await.set_file_tags(hash, size, tags).await
+    }
+
+    /// Returns the sizes of all tagged files.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache cannot be read.
+    pub async fn tagged_sizes(&self) -> Result<HashSet<u64>> {
+        self.cache.read().await.tagged_sizes().await
+    }
+
+    /// Returns the cached files matching `query` without loading the whole library.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache cannot be read.
+    pub async fn query_files(&self, query: &FileQuery) -> Result<Vec<CacheEntry>> {
+        self.cache.read().await.query_files(query).await
+    }
+
+    /// Returns the number of cached files matching `query`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache cannot be read.
+    pub async fn count_files(&self, query: &FileQuery) -> Result<usize> {
+        self.cache.read().await.count_files(query).await
+    }
+
+    /// Finds the exact copies among `files`.
+    ///
+    /// Only the files sharing their size with another one are held in memory and hashed,
+    /// reusing and filling the hash cache. A list with a [`FileList::cache_scope`] is then
+    /// grouped by the cache; any other list by the detector.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the list or the cache cannot be read, or [`ScanCancelled`] when the
+    /// scan is cancelled.
+    pub async fn find_duplicates_in(&self, files: &FileList, algorithm: HashAlgorithm) -> Result<DuplicateStats> {
+        self.group_duplicates_in(files, self.duplicate_detector(algorithm))
+            .await
+    }
+
+    async fn group_duplicates_in(&self, files: &FileList, detector: DuplicateDetector) -> Result<DuplicateStats> {
+        let algorithm = detector.algorithm();
+        let mut sizes: HashMap<u64, usize> = HashMap::new();
+        files
+            .for_each(|_, file| *sizes.entry(file.size).or_default() += 1)
+            .await?;
+        let candidates = files
+            .filter(|file| sizes.get(&file.size).is_some_and(|&count| count > 1))
+            .await?;
+        drop(sizes);
+
+        let Some(scope) = files.cache_scope() else {
+            return detector.detect_duplicates(&candidates, false).await;
+        };
+        let mut stats = detector.hash_candidates(&candidates).await?;
+        let mut candidates: HashMap<PathBuf, Arc<MediaFile>> =
+            candidates.into_iter().map(|file| (file.path.clone(), file)).collect();
+        let mut hash_groups = AHashMap::new();
+        for group in self.cache.read().await.duplicate_groups(scope).await? {
+            let Some(hash) = group[0].hash.clone() else {
+                continue;
+            };
+            // Hashes from another algorithm belong to files that could not be hashed again
+            if DuplicateDetector::algorithm_of(&hash) != Some(algorithm) {
+                continue;
+            }
+            let copies: SmallVec<[Arc<MediaFile>; 4]> = group
+                .iter()
+                .filter_map(|entry| candidates.remove(&entry.path))
+                .map(|file| {
+                    Arc::new(MediaFile {
+                        hash: Some(Arc::from(hash.as_str())),
+                        ..Arc::unwrap_or_clone(file)
+                    })
+                })
+                .collect();
+            hash_groups.insert(hash, copies);
+        }
+        let grouped = DuplicateDetector::build_duplicate_stats(hash_groups);
+        stats.groups = grouped.groups;
+        stats.total_groups = grouped.total_groups;
+        stats.total_duplicates = grouped.total_duplicates;
+        stats.total_wasted_space = grouped.total_wasted_space;
+        info!(
+            "Found {} duplicate groups with {} total duplicates in the cache",
+            stats.total_groups, stats.total_duplicates
+        );
+        Ok(stats)
+    }
+
+    /// Returns the number and size of the cached files matching `query` per extension.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache cannot be read.
+    pub async fn extension_stats(&self, query: &FileQuery) -> Result<Vec<ExtensionStats>> {
+        self.cache.read().await.extension_stats(query).await
+    }
+
+    /// Adds a finished organize run to the import history.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache cannot be written.
+    pub async fn record_import(&self, record: &ImportRecord) -> Result<()> {
+        self.cache.read().await.record_import(record).await
+    }
+
+    /// Returns up to `limit` organize runs, newest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache cannot be read.
+    pub async fn import_history(&self, limit: usize) -> Result<Vec<ImportRecord>> {
+        self.cache.read().await.import_history(limit).await
+    }
+
+    /// Adds a statistics snapshot to the history.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache cannot be written.
+    pub async fn record_stats(&self, snapshot: &StatsSnapshot) -> Result<()> {
+        self.cache.read().await.record_stats(snapshot).await
+    }
+
+    /// Returns the latest `limit` statistics snapshots, oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache cannot be read.
+    pub async fn stats_history(&self, limit: usize) -> Result<Vec<StatsSnapshot>> {
+        self.cache.read().await.stats_history(limit).await
+    }
+
+    /// Takes the errors of the last scan: files and folders that could not be read, and
+    /// files whose metadata could not be parsed.
+    #[must_use]
+    pub fn take_failures(&self) -> Vec<ErrorEntry> {
+        self.failures
+            .lock()
+            .map(|mut failures| std::mem::take(&mut *failures))
+            .unwrap_or_default()
+    }
+
+    /// Takes the files the last scan set aside as suspect, leaving none behind
+    #[must_use]
+    pub fn take_suspects(&self) -> Vec<SuspectFile> {
+        self.suspects
+            .lock()
+            .map(|mut suspects| std::mem::take(&mut *suspects))
+            .unwrap_or_default()
+    }
+
+    /// Takes the media the last scan found inside archives, leaving none behind
+    #[must_use]
+    pub fn take_archive_media(&self) -> Vec<ArchiveMedia> {
+        self.archive_media
+            .lock()
+            .map(|mut media| std::mem::take(&mut *media))
+            .unwrap_or_default()
+    }
+
+    fn record_failure(&self, entry: ErrorEntry) {
+        if let Ok(mut failures) = self.failures.lock() {
+            failures.push(entry);
+        }
+    }
+
     /// Scans a directory for media files and returns a list of `MediaFile` objects.
     ///
+    /// Keeps every file in memory; see [`Scanner::scan_into`] to page them from disk.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error in the same cases as [`Scanner::scan_into`].
+    pub async fn scan_directory(
+        &self,
+        path: &Path,
+        recursive: bool,
+        progress: Arc<RwLock<Progress>>,
+        settings: &Settings,
+        filter_set: Option<FilterSet>,
+    ) -> Result<Vec<Arc<MediaFile>>> {
+        let mut files = FileList::default();
+        self.scan_into(path, recursive, progress, settings, filter_set, &mut files)
+            .await?;
+        files.to_vec().await
+    }
+
+    /// Scans a directory for media files, adding them to `files` a batch at a time as they are
+    /// read, so a list with a memory budget moves them to disk while the scan runs.
+    ///
     /// # Arguments
     ///
     /// * `path` - The directory path to scan
@@ -84,16 +357,26 @@ impl Scanner {
     /// - The specified path does not exist
     /// - There are I/O errors while reading directory contents or file metadata
     /// - File processing fails during parallel or sequential processing
+    /// - The scan is cancelled with [`Scanner::cancel`], returning [`ScanCancelled`]
+    /// * `files` - List the scanned files are added to
     #[allow(clippy::cognitive_complexity)]
-    pub async fn scan_directory(
+    pub async fn scan_into(
         &self,
         path: &Path,
         recursive: bool,
         progress: Arc<RwLock<Progress>>,
         settings: &Settings,
         filter_set: Option<FilterSet>,
-    ) -> Result<Vec<Arc<MediaFile>>> {
+        files: &mut FileList,
+    ) -> Result<()> {
         info!("Scanner: Starting scan of {:?}", path);
+        // Failures and suspects of an earlier scan no longer apply, nor does its cancel
+        let _ = self.take_failures();
+        let _ = self.take_suspects();
+        self.cancelled.store(false, Ordering::Release);
+        if let Ok(mut throttle) = self.throttle.lock() {
+            *throttle = IoThrottle::new(&settings.throttle);
+        }
 
         if !path.exists() {
             error!("Scanner: Path does not exist: {:?}", path);
@@ -102,138 +385,414 @@ impl Scanner {
 
         let scan_all_types = matches!(settings.organize_by.as_str(), "type");
 
-        // Get cache stats
-        let cache_stats = {
-            let cache_lock = self.cache.read().await;
-            cache_lock.get_stats().await?
-        };
-        info!(
-            "Scanner: Cache has {} entries ({} with hashes)",
-            cache_stats.total_entries, cache_stats.entries_with_hash
-        );
-
-        // Remove stale entries if cache is large
-        if cache_stats.total_entries > 10000 {
-            let removed = {
-                let cache_lock = self.cache.read().await;
-                cache_lock.remove_stale_entries().await?
-            };
-            info!("Scanner: Removed {} stale cache entries", removed);
-        }
+        self.prune_cache().await?;
 
-        // Update progress message
         {
             let mut prog = progress.write().await;
-            prog.message = "Discovering files...".to_string();
+            prog.plan_stages(&[STAGE_WALK, STAGE_METADATA]);
+            prog.start_stage(STAGE_WALK, 0, 0, "Discovering files...");
         }
 
         // Collect all paths first with progress updates
-        let paths: Vec<PathBuf> = if recursive {
+        let (paths, linked) = if recursive {
             let path_clone = path.to_path_buf();
             let settings_clone = settings.clone();
             let progress_clone = Arc::clone(&progress);
+            let scanner = self.clone();
 
             // Use spawn_blocking for the file system traversal
             tokio::task::spawn_blocking(move || {
-                let mut paths = Vec::new();
-                let mut count = 0;
-
-                for entry in WalkDir::new(&path_clone)
-                    .into_iter()
-                    .filter_map(std::result::Result::ok)
-                {
-                    if entry.file_type().is_file() {
-                        if settings_clone.skip_hidden_files && is_hidden_in_path(entry.path()) {
-                            continue;
-                        }
-
-                        if scan_all_types || Self::is_media_file(entry.path()) {
-                            paths.push(entry.path().to_path_buf());
-                            count += 1;
-
-                            // Update progress every 100 files
-                            if count % 100 == 0 {
-                                if let Ok(mut prog) = progress_clone.try_write() {
-                                    prog.current = count;
-                                    prog.message = format!("Discovering files... {count}");
-                                }
-                                std::thread::yield_now();
-                            }
-                        }
-                    }
-                }
-
-                paths
+                scanner.walk_directory(&path_clone, &settings_clone, scan_all_types, &progress_clone)
             })
             .await?
         } else {
-            std::fs::read_dir(path)?
+            let mut linked = HashSet::new();
+            let paths = std::fs::read_dir(path)?
                 .filter_map(std::result::Result::ok)
-                .filter(|e| e.file_type().map(|ft| ft.is_file()).unwrap_or(false))
+                .filter(|e| {
+                    e.file_type().is_ok_and(|ft| {
+                        ft.is_file() || (settings.follow_symlinks && ft.is_symlink() && e.path().is_file())
+                    })
+                })
                 .map(|e| e.path())
                 .filter(|p| !(settings.skip_hidden_files && is_hidden_in_path(p)))
-                .filter(|p| if scan_all_types { true } else { Self::is_media_file(p) })
-                .collect()
+                .filter(|p| !settings.is_ignored(p))
+                .filter(|p| Self::is_discovered(p, settings, scan_all_types))
+                .inspect(|p| {
+                    if p.is_symlink() {
+                        linked.insert(p.clone());
+                    }
+                })
+                .collect();
+            (paths, linked)
         };
 
+        self.check_cancelled()?;
+        let mut paths = self.inspect_archives(paths, settings, scan_all_types).await?;
+        Self::retain_filtered_extensions(&mut paths, filter_set.as_ref());
+
         info!("Scanner: Found {} files in {:?}", paths.len(), path);
 
-        // Update progress total and message
+        // Spread the reads over as many workers as suit the drive, or as the settings ask for
+        let plan = ConcurrencyPlan::for_scan(settings, &paths).await;
+        if let Ok(mut concurrency) = self.concurrency.lock() {
+            *concurrency = Some(plan);
+        }
         {
             let mut prog = progress.write().await;
-            prog.total = paths.len();
-            prog.current = 0;
-            prog.message = "Processing files...".to_string();
+            prog.start_stage(STAGE_METADATA, paths.len(), 0, "Processing files...");
+            prog.parallelism = Some(plan.to_string());
         }
 
-        // Process files with cache support
-        let files = if settings.parallel_processing && settings.worker_threads > 1 {
-            self.process_files_parallel(&paths, progress, settings, filter_set)
-                .await?
-        } else {
-            self.process_files_sequential(&paths, progress, filter_set).await?
-        };
-
-        // Log file type distribution if organizing by type
-        if scan_all_types && !files.is_empty() {
-            let mut type_counts = std::collections::HashMap::new();
-            for file in &files {
+        // Process files in batches, handing each to the list before reading the next
+        let mut type_counts = HashMap::new();
+        let mut live_photo_parts = Vec::new();
+        let mut done = (0, 0);
+        for batch in paths.chunks(SCAN_BATCH) {
+            let found = self
+                .scan_batch(
+                    batch,
+                    &mut done,
+                    &linked,
+                    Arc::clone(&progress),
+                    settings,
+                    filter_set.as_ref(),
+                )
+                .await?;
+            for file in &found {
                 *type_counts.entry(file.file_type.clone()).or_insert(0) += 1;
+                if is_live_photo_part(&file.path) {
+                    live_photo_parts.push(file.path.clone());
+                }
             }
+            files.extend(found).await?;
+        }
+
+        // Link the still image and video of Live Photos so they stay together
+        let partners =
+            tokio::task::spawn_blocking(move || live_photo_partners(live_photo_parts.iter().map(PathBuf::as_path)))
+                .await?;
+        files
+            .update_each(&partners, |file, partner| file.live_photo_pair = Some(partner.clone()))
+            .await?;
 
+        // Log file type distribution if organizing by type
+        if scan_all_types && !type_counts.is_empty() {
             info!("Scanner: File type distribution:");
             for (file_type, count) in type_counts {
                 info!("  {}: {} files", file_type, count);
             }
         }
 
-        Ok(files)
+        progress.write().await.finish_stages();
+        Ok(())
+    }
+
+    /// Reads one batch of files, which follow the `done` files and bytes of the scan that are
+    /// then counted on, and returns those that pass the filters and header check
+    async fn scan_batch(
+        &self,
+        batch: &[PathBuf],
+        done: &mut (usize, u64),
+        linked: &HashSet<PathBuf>,
+        progress: Arc<RwLock<Progress>>,
+        settings: &Settings,
+        filter_set: Option<&FilterSet>,
+    ) -> Result<Vec<Arc<MediaFile>>> {
+        let workers = self.concurrency().map_or(1, |plan| plan.scan_workers);
+        let mut found = if workers > 1 {
+            self.process_files_parallel(batch, *done, progress, settings, filter_set, workers)
+                .await?
+        } else {
+            self.process_files_sequential(batch, *done, progress, settings, filter_set)
+                .await?
+        };
+        self.check_cancelled()?;
+        done.0 += batch.len();
+        done.1 += found.iter().map(|file| file.size).sum::<u64>();
+
+        if !linked.is_empty() {
+            for file in &mut found {
+                if linked.contains(&file.path) {
+                    Arc::make_mut(file).via_symlink = true;
+                }
+            }
+        }
+
+        if settings.check_file_headers {
+            found = self.set_aside_suspects(found, settings.extension_mismatch).await?;
+        }
+        Ok(found)
+    }
+
+    /// Lists the media inside the archives among `paths` for [`Scanner::take_archive_media`],
+    /// returning the paths without the archives unless they are scanned themselves.
+    ///
+    /// Media listed by an earlier scan are dropped, and archives that cannot be read are
+    /// recorded as failures.
+    async fn inspect_archives(
+        &self,
+        paths: Vec<PathBuf>,
+        settings: &Settings,
+        scan_all_types: bool,
+    ) -> Result<Vec<PathBuf>> {
+        let _ = self.take_archive_media();
+        let archives: Vec<PathBuf> = paths.iter().filter(|path| is_archive(path)).cloned().collect();
+        if !settings.inspect_archives || archives.is_empty() {
+            return Ok(paths);
+        }
+
+        let listed = tokio::task::spawn_blocking(move || {
+            archives
+                .into_par_iter()
+                .map(|archive| {
+                    let files = list_archive(&archive);
+                    (archive, files)
+                })
+                .collect::<Vec<_>>()
+        })
+        .await?;
+
+        let mut media = Vec::new();
+        for (archive, files) in listed {
+            match files {
+                Ok(files) => media.extend(
+                    files
+                        .into_iter()
+                        .filter(|file| Self::is_scanned(Path::new(&file.name), settings, false)),
+                ),
+                Err(e) => {
+                    tracing::warn!("Scanner: Cannot read archive {}: {}", archive.display(), e);
+                    self.record_failure(ErrorEntry::new(ErrorSource::Scan, Some(archive), e.to_string()));
+                }
+            }
+        }
+        if !media.is_empty() {
+            info!("Scanner: Found {} media files inside archives", media.len());
+        }
+        if let Ok(mut stored) = self.archive_media.lock() {
+            *stored = media;
+        }
+        Ok(paths
+            .into_iter()
+            .filter(|path| Self::is_scanned(path, settings, scan_all_types))
+            .collect())
+    }
+
+    /// Checks the header of every file against its extension, returning the files that pass and
+    /// adding the rest to the suspects for [`Scanner::take_suspects`].
+    ///
+    /// Files in another known format than their extension promises are returned flagged with
+    /// the extension of their content, unless `mismatches` quarantines them.
+    async fn set_aside_suspects(
+        &self,
+        files: Vec<Arc<MediaFile>>,
+        mismatches: ExtensionMismatchPolicy,
+    ) -> Result<Vec<Arc<MediaFile>>> {
+        let checked: Vec<_> = tokio::task::spawn_blocking(move || {
+            files
+                .into_par_iter()
+                .map(|file| {
+                    let reason = check_file_header(&file.path, &file.extension, file.size);
+                    (file, reason)
+                })
+                .collect()
+        })
+        .await?;
+
+        let mut valid = Vec::with_capacity(checked.len());
+        let mut suspects = Vec::new();
+        for (file, reason) in checked {
+            match reason {
+                Some(SuspectReason::ExtensionMismatch { detected, .. })
+                    if mismatches != ExtensionMismatchPolicy::Quarantine =>
+                {
+                    let mut flagged = Arc::unwrap_or_clone(file);
+                    flagged.content_extension = format_extension(detected).map(Arc::from);
+                    valid.push(Arc::new(flagged));
+                }
+                Some(reason) => suspects.push(SuspectFile {
+                    path: file.path.clone(),
+                    size: file.size,
+                    reason,
+                }),
+                None => valid.push(file),
+            }
+        }
+        if !suspects.is_empty() {
+            info!("Scanner: Set aside {} suspect files", suspects.len());
+        }
+        if let Ok(mut stored) = self.suspects.lock() {
+            stored.extend(suspects);
+        }
+        Ok(valid)
+    }
+
+    /// Walks `root` and collects the files to scan, along with those reached through a symlink.
+    ///
+    /// With `follow_symlinks` set, symlinked directories are descended into. Every directory is
+    /// identified by device and inode and entered only once, so links that point back at an
+    /// ancestor, or at a directory that was already walked, cannot loop or duplicate files.
+    ///
+    /// A destination folder inside `root` is not entered, so files that were already organized
+    /// are not scanned and organized again, and neither are folders below the configured depth. Entries that cannot be read are recorded as failures.
+    fn walk_directory(
+        &self,
+        root: &Path,
+        settings: &Settings,
+        scan_all_types: bool,
+        progress: &Arc<RwLock<Progress>>,
+    ) -> (Vec<PathBuf>, HashSet<PathBuf>) {
+        let mut paths = Vec::new();
+        let mut linked = HashSet::new();
+        let mut linked_dirs: Vec<PathBuf> = Vec::new();
+        let mut visited = HashSet::new();
+        let mut count = 0;
+        // The destination and quarantine folders hold files already dealt with
+        let excluded: Vec<_> = [settings.destination_folder.clone(), settings.quarantine_path()]
+            .iter()
+            .flatten()
+            .filter_map(|folder| directory_key(folder))
+            .collect();
+
+        let walker = WalkDir::new(root)
+            .follow_links(settings.follow_symlinks)
+            // Walk real entries before links so files are reported under their own path when possible
+            .sort_by(|a, b| {
+                a.path_is_symlink()
+                    .cmp(&b.path_is_symlink())
+                    .then_with(|| a.file_name().cmp(b.file_name()))
+            })
+            .into_iter()
+            .filter_entry(|entry| {
+                if !entry.file_type().is_dir() {
+                    return true;
+                }
+                let Some(key) = directory_key(entry.path()) else {
+                    return true;
+                };
+                if entry.depth() > 0 && excluded.contains(&key) {
+                    debug!("Scanner: Skipping output folder {}", entry.path().display());
+                    return false;
+                }
+                if entry.depth() > 0 && !settings.scans_folder(root, entry.path()) {
+                    debug!("Scanner: Skipping {} below the scan depth", entry.path().display());
+                    return false;
+                }
+                if visited.insert(key) {
+                    true
+                } else {
+                    debug!("Scanner: Skipping already visited directory {}", entry.path().display());
+                    false
+                }
+            });
+
+        for entry in walker {
+            if self.is_cancelled() {
+                break;
+            }
+            let entry = match entry {
+                Ok(entry) => entry,
+                // Links back to an ancestor are expected when following symlinks
+                Err(e) if e.loop_ancestor().is_some() => {
+                    debug!("Scanner: {}", e);
+                    continue;
+                }
+                Err(e) => {
+                    tracing::warn!("Scanner: Cannot read entry: {}", e);
+                    self.record_failure(ErrorEntry::new(
+                        ErrorSource::Scan,
+                        e.path().map(Path::to_path_buf),
+                        e.to_string(),
+                    ));
+                    continue;
+                }
+            };
+            let via_link = entry.path_is_symlink() || linked_dirs.iter().any(|dir| entry.path().starts_with(dir));
+            if entry.file_type().is_dir() {
+                if entry.path_is_symlink() {
+                    linked_dirs.push(entry.path().to_path_buf());
+                }
+                continue;
+            }
+            if !entry.file_type().is_file() {
+                continue;
+            }
+
+            if settings.skip_hidden_files && is_hidden_in_path(entry.path()) {
+                continue;
+            }
+
+            if settings.is_ignored(entry.path()) {
+                continue;
+            }
+
+            if Self::is_discovered(entry.path(), settings, scan_all_types) {
+                if via_link {
+                    linked.insert(entry.path().to_path_buf());
+                }
+                paths.push(entry.path().to_path_buf());
+                count += 1;
+
+                // Update progress every 100 files
+                if count % 100 == 0 {
+                    if let Ok(mut prog) = progress.try_write() {
+                        prog.current = count;
+                        prog.message = format!("Discovering files... {count}");
+                    }
+                    std::thread::yield_now();
+                }
+            }
+        }
+
+        (paths, linked)
+    }
+
+    /// Drops the paths the extension filters leave out, which is settled by the path alone, so
+    /// those files are never read
+    fn retain_filtered_extensions(paths: &mut Vec<PathBuf>, filter_set: Option<&FilterSet>) {
+        if let Some(filters) = filter_set.filter(|filters| filters.filters_by_extension()) {
+            paths.retain(|p| {
+                let ext = p.extension().and_then(|ext| ext.to_str()).unwrap_or_default();
+                filters.matches_extension(&ext.to_lowercase())
+            });
+        }
     }
 
+    /// Reads the files at `paths`, which follow `done` files and bytes the scan already read
     async fn process_files_sequential(
         &self,
         paths: &[PathBuf],
+        done: (usize, u64),
         progress: Arc<RwLock<Progress>>,
-        filter_set: Option<FilterSet>,
+        settings: &Settings,
+        filter_set: Option<&FilterSet>,
     ) -> Result<Vec<Arc<MediaFile>>> {
         let mut files: Vec<Arc<MediaFile>> = Vec::new();
+        let mut bytes_processed = done.1;
 
         for (idx, path) in paths.iter().enumerate() {
-            match self.process_file_with_cache(path).await {
+            if self.is_cancelled() {
+                break;
+            }
+            match self.process_file_with_cache(path, settings).await {
                 Ok(file) => {
-                    if let Some(filters) = &filter_set {
+                    if let Some(filters) = filter_set {
                         if filters.is_active && !filters.matches_file(&file) {
                             continue; // Skip files that don't match filters
                         }
                     }
+                    bytes_processed += file.size;
+                    let name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
                     files.push(file.into());
 
                     let mut prog = progress.write().await;
-                    prog.current = idx + 1;
-                    prog.message = format!("Scanning: {}", path.file_name().unwrap_or_default().to_string_lossy());
+                    prog.message = format!("Scanning: {name}");
+                    prog.set_file_progress(done.0 + idx + 1, bytes_processed, name);
                 }
                 Err(e) => {
                     tracing::warn!("Failed to process file {:?}: {}", path, e);
+                    self.record_failure(ErrorEntry::new(ErrorSource::Scan, Some(path.clone()), e.to_string()));
                 }
             }
         }
@@ -241,13 +800,17 @@ impl Scanner {
         Ok(files)
     }
 
+    /// Reads the files at `paths` on `workers` tasks, following `done` files and bytes the
+    /// scan already read
     #[allow(clippy::cognitive_complexity)]
     async fn process_files_parallel(
         &self,
         paths: &[PathBuf],
+        done: (usize, u64),
         progress: Arc<RwLock<Progress>>,
         settings: &Settings,
-        filter_set: Option<FilterSet>,
+        filter_set: Option<&FilterSet>,
+        workers: usize,
     ) -> Result<Vec<Arc<MediaFile>>> {
         use tokio::task::JoinSet;
 
@@ -255,23 +818,33 @@ impl Scanner {
 
         let mut join_set = JoinSet::new();
         let scanner = Arc::new(self.clone());
-        let progress_counter = Arc::new(AtomicUsize::new(0));
+        let progress_counter = Arc::new(AtomicUsize::new(done.0));
+        let bytes_counter = Arc::new(AtomicU64::new(done.1));
         let mut files: Vec<Arc<MediaFile>> = Vec::new();
+        let shared_settings = Arc::new(settings.clone());
 
         // Process files in chunks
-        let chunk_size = settings.worker_threads * 10;
+        let chunk_size = workers * 10;
 
         for chunk in paths.chunks(chunk_size) {
+            if self.is_cancelled() {
+                break;
+            }
             // Spawn tasks for this chunk
             for path in chunk {
                 let scanner_clone = Arc::clone(&scanner);
                 let progress_clone = Arc::clone(&progress);
                 let progress_counter_clone = Arc::clone(&progress_counter);
+                let bytes_counter_clone = Arc::clone(&bytes_counter);
                 let path_clone = path.clone();
-                let filter_set_clone = filter_set.clone();
+                let filter_set_clone = filter_set.cloned();
+                let settings_clone = Arc::clone(&shared_settings);
 
                 join_set.spawn(async move {
-                    match scanner_clone.process_file_with_cache(&path_clone).await {
+                    match scanner_clone
+                        .process_file_with_cache(&path_clone, &settings_clone)
+                        .await
+                    {
                         Ok(file) => {
                             if let Some(filters) = &filter_set_clone {
                                 if filters.is_active && !filters.matches_file(&file) {
@@ -280,16 +853,22 @@ impl Scanner {
                             }
 
                             let current = progress_counter_clone.fetch_add(1, Ordering::SeqCst) + 1;
+                            let bytes = bytes_counter_clone.fetch_add(file.size, Ordering::SeqCst) + file.size;
 
                             if let Ok(mut prog) = progress_clone.try_write() {
-                                prog.current = current;
                                 prog.message = format!("Scanning: {}", file.name);
+                                prog.set_file_progress(current, bytes, file.name.to_string());
                             }
 
                             Some(file)
                         }
                         Err(e) => {
                             tracing::warn!("Failed to process file {:?}: {}", path_clone, e);
+                            scanner_clone.record_failure(ErrorEntry::new(
+                                ErrorSource::Scan,
+                                Some(path_clone),
+                                e.to_string(),
+                            ));
                             None
                         }
                     }
@@ -316,7 +895,9 @@ impl Scanner {
         Ok(files)
     }
 
-    async fn process_file_with_cache(&self, path: &Path) -> Result<MediaFile> {
+    async fn process_file_with_cache(&self, path: &Path, settings: &Settings) -> Result<MediaFile> {
+        let throttle = self.throttle();
+        throttle.acquire(1, 0).await;
         let metadata = tokio::fs::metadata(path).await?;
         let size = metadata.len();
         let modified =
@@ -324,18 +905,40 @@ impl Scanner {
 
         let cache_lock = self.cache.read().await;
         if let Some(entry) = cache_lock.get(path, size, &modified).await? {
-            let file_type = determine_file_type(&entry.extension);
+            let file_type = classify(settings, &entry.extension);
             let created =
                 system_time_to_datetime(metadata.created()).map_or_else(|| modified, |dt| dt.with_timezone(&Local));
 
             tracing::trace!("Cache hit for: {}", path.display());
-            return Ok(entry.to_media_file(file_type, created));
+            let mut file = entry.to_media_file(file_type, created);
+            // A hash from another algorithm can't be compared with fresh ones
+            if file
+                .hash
+                .as_deref()
+                .is_some_and(|hash| DuplicateDetector::algorithm_of(hash) != Some(settings.hash_algorithm))
+            {
+                file.hash = None;
+            }
+            return Ok(file);
         }
         drop(cache_lock); // Release the lock before processing
 
-        // Cache miss - process file
+        // Cache miss - process file, reading its header
         tracing::trace!("Cache miss for: {}", path.display());
-        let file = Self::process_file(path, &metadata, size, modified);
+        throttle.acquire(1, 0).await;
+        let mut file = Self::process_file(path, &metadata, size, modified);
+        file.file_type = classify(settings, &file.extension);
+        match file.file_type {
+            FileType::Audio => file.metadata = self.read_audio_tags(path).await,
+            FileType::Image => {
+                file.metadata = self.read_capture_info(path, &file.extension).await;
+                // Screenshots carry no EXIF data, but their size tells them apart
+                if file.metadata.is_none() && file.extension.as_ref() == "png" {
+                    file.metadata = self.read_png_dimensions(path).await;
+                }
+            }
+            _ => {}
+        }
 
         // Update cache asynchronously
         let entry = CacheEntry::from(&file);
@@ -351,7 +954,8 @@ impl Scanner {
     /// # Arguments
     ///
     /// * `files` - Mutable slice of media files to check for duplicates
-    /// * `_progress` - Progress tracker (currently unused)
+    /// * `algorithm` - Hash algorithm used to compare file contents
+    /// * `progress` - Shows the hashing status, including how far large files got
     ///
     /// # Returns
     ///
@@ -362,63 +966,37 @@ impl Scanner {
     /// This function will return an error if:
     /// - The duplicate detection process fails
     /// - Hash calculation for files fails during duplicate detection
-    /// - Cache operations fail when updating file hashes
+    /// - The scan is cancelled with [`Scanner::cancel`], returning [`ScanCancelled`]
     #[allow(clippy::cognitive_complexity)]
-    pub async fn find_duplicates<F>(
+    pub async fn find_duplicates(
         &self,
         files: &mut [Arc<MediaFile>],
-        mut progress_callback: F,
-    ) -> Result<DuplicateStats>
-    where
-        F: FnMut(usize, Option<String>) + Send + 'static,
-    {
+        algorithm: HashAlgorithm,
+        progress: Arc<RwLock<Progress>>,
+    ) -> Result<DuplicateStats> {
         info!(
             "Scanner: Using DuplicateDetector to find duplicates for {} files",
             files.len()
         );
 
-        progress_callback(0, Some("Calculating hashes for potential duplicates...".to_string()));
-
-        // Create a new DuplicateDetector instance
-        let detector = DuplicateDetector::new();
+        {
+            let mut prog = progress.write().await;
+            prog.current = 0;
+            prog.message = "Calculating hashes for potential duplicates...".to_string();
+        }
 
-        // Use the DuplicateDetector to find duplicates
-        // Use full hash (false) for accuracy, matching what works in the UI
+        // Hashes of unchanged files are reused from the cache and new ones are stored there
+        let detector = self.duplicate_detector(algorithm).with_progress(Arc::clone(&progress));
         let duplicate_stats = detector.detect_duplicates(files, false).await?;
 
         info!(
             "DuplicateDetector found {} duplicate groups",
             duplicate_stats.groups.len()
         );
-
-        progress_callback(0, Some("Updating cache...".to_string()));
-
-        // Update cache with the calculated hashes
-        if !duplicate_stats.is_empty() {
-            progress_callback(0, Some("Updating hash cache...".to_string()));
-
-            progress_callback(0, Some("Updating hash cache...".to_string()));
-
-            let mut updates = 0;
-            for (idx, file) in files.iter().enumerate() {
-                if let Some(hash) = &file.hash {
-                    // Only update if we don't have a hash in cache
-                    let cache_lock: tokio::sync::RwLockReadGuard<'_, Box<dyn Cache>> = self.cache.read().await;
-                    if let Some(existing) = cache_lock.get(&file.path, file.size, &file.modified).await? {
-                        if existing.hash.is_none() {
-                            cache_lock.update_hash(&file.path, hash).await?;
-                            updates += 1;
-                        }
-                    }
-                    drop(cache_lock);
-                }
-
-                if idx % 100 == 0 {
-                    progress_callback(idx, Some(format!("Updated {updates} hashes...")));
-                }
-            }
-
-            info!("Scanner: Updated {} hashes in cache", updates);
+        if let Some(summary) = duplicate_stats.hash_cache_summary() {
+            let mut prog = progress.write().await;
+            prog.current = files.len();
+            prog.message = format!("Hashed files, {summary}");
         }
 
         info!("Scanner: Converted to {} duplicate groups", duplicate_stats.len());
@@ -467,6 +1045,11 @@ impl Scanner {
         settings: &Settings,
         filter_set: Option<FilterSet>,
     ) -> Result<(Vec<Arc<MediaFile>>, DuplicateStats)> {
+        progress
+            .write()
+            .await
+            .plan_stages(&[STAGE_WALK, STAGE_METADATA, STAGE_HASH]);
+
         // First, scan all files
         let mut files = self
             .scan_directory(path, recursive, progress.clone(), settings, filter_set)
@@ -475,37 +1058,81 @@ impl Scanner {
         info!("Scanner: Found {} files, checking for duplicates...", files.len());
 
         // Reset progress for duplicate detection
+        progress
+            .write()
+            .await
+            .start_stage(STAGE_HASH, files.len(), 0, "Detecting duplicates...");
+
+        // Find duplicates using DuplicateDetector
+        let duplicates = self
+            .find_duplicates(&mut files, settings.hash_algorithm, Arc::clone(&progress))
+            .await?;
+        self.check_cancelled()?;
+
+        self.set_scanning(false);
+
         {
             let mut prog = progress.write().await;
-            prog.current = 0;
-            prog.total = files.len();
-            prog.message = "Detecting duplicates...".to_string();
+            prog.current = prog.total;
+            prog.bytes_processed = duplicates.hashed_bytes;
+            prog.message = format!("Found {} duplicate groups", duplicates.len());
+            prog.finish_stages();
         }
 
-        let progress_clone = Arc::clone(&progress);
-        let progress_callback = move |current: usize, message: Option<String>| {
-            if let Ok(mut prog) = progress_clone.try_write() {
-                prog.current = current;
-                if let Some(msg) = message {
-                    prog.message = msg;
-                }
-            }
-        };
+        info!("Scanner: Found {} duplicate groups", duplicates.len());
 
-        // Find duplicates using DuplicateDetector
-        let duplicates = self.find_duplicates(&mut files, progress_callback).await?;
+        Ok((files, duplicates))
+    }
+
+    /// Scans a directory into `files` like [`Scanner::scan_into`] and detects the duplicates
+    /// among them, holding only the files that share their size with another one in memory.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the scan fails, or if the list or the cache cannot be read while
+    /// looking for duplicates.
+    pub async fn scan_with_duplicates_into(
+        &self,
+        path: &Path,
+        recursive: bool,
+        progress: Arc<RwLock<Progress>>,
+        settings: &Settings,
+        filter_set: Option<FilterSet>,
+        files: &mut FileList,
+    ) -> Result<DuplicateStats> {
+        progress
+            .write()
+            .await
+            .plan_stages(&[STAGE_WALK, STAGE_METADATA, STAGE_HASH]);
+
+        self.scan_into(path, recursive, progress.clone(), settings, filter_set, files)
+            .await?;
+
+        info!("Scanner: Found {} files, checking for duplicates...", files.len());
+        progress
+            .write()
+            .await
+            .start_stage(STAGE_HASH, files.len(), 0, "Detecting duplicates...");
+
+        let detector = self
+            .duplicate_detector(settings.hash_algorithm)
+            .with_progress(Arc::clone(&progress));
+        let duplicates = self.group_duplicates_in(files, detector).await?;
+        self.check_cancelled()?;
 
         self.set_scanning(false);
 
         {
             let mut prog = progress.write().await;
             prog.current = prog.total;
+            prog.bytes_processed = duplicates.hashed_bytes;
             prog.message = format!("Found {} duplicate groups", duplicates.len());
+            prog.finish_stages();
         }
 
         info!("Scanner: Found {} duplicate groups", duplicates.len());
 
-        Ok((files, duplicates))
+        Ok(duplicates)
     }
 
     fn process_file(
@@ -533,20 +1160,158 @@ impl Scanner {
             modified,
             hash: None,
             metadata: None, // Skip metadata extraction for performance
+            via_symlink: false,
+            live_photo_pair: None,
+            content_extension: None,
         }
     }
 
-    fn is_media_file(path: &Path) -> bool {
-        path.to_str().is_some_and(|s| MEDIA_EXTENSIONS.is_match(s))
-    }
+    /// Reads audio tags off the async runtime; untagged or unreadable files get no metadata
+    async fn read_audio_tags(&self, path: &Path) -> Option<MediaMetadata> {
+        let owned = path.to_path_buf();
+        match tokio::task::spawn_blocking(move || read_audio_metadata(&owned)).await {
+            Ok(Ok(tags)) => tags.map(MediaMetadata::Audio),
+            Ok(Err(e)) => {
+                tracing::debug!("Could not read audio tags: {}", e);
+                self.record_metadata_failure(path, &format!("Could not read audio tags: {e}"));
+                None
+            }
+            Err(_) => None,
+        }
+    }
+
+    /// Reads a photo's camera settings off the async runtime; files without them get no metadata
+    async fn read_capture_info(&self, path: &Path, extension: &str) -> Option<MediaMetadata> {
+        let owned = path.to_path_buf();
+        match tokio::task::spawn_blocking(move || read_exif_capture(&owned)).await {
+            Ok(Ok(capture)) if !capture.is_empty() => Some(MediaMetadata::Image(ImageMetadata::from_capture(
+                extension.to_uppercase(),
+                capture,
+            ))),
+            Ok(Err(e)) => {
+                tracing::debug!("Could not read EXIF data: {}", e);
+                self.record_metadata_failure(path, &format!("Could not read EXIF data: {e}"));
+                None
+            }
+            _ => None,
+        }
+    }
+
+    /// Reads the size of a PNG image from its header off the async runtime
+    async fn read_png_dimensions(&self, path: &Path) -> Option<MediaMetadata> {
+        let owned = path.to_path_buf();
+        match tokio::task::spawn_blocking(move || read_png_image(&owned)).await {
+            Ok(Ok(image)) => image.map(|image| {
+                MediaMetadata::Image(ImageMetadata {
+                    width: image.width,
+                    height: image.height,
+                    format: "PNG".to_string(),
+                    color_type: image.color_type,
+                    capture: CaptureInfo::default(),
+                })
+            }),
+            Ok(Err(e)) => {
+                tracing::debug!("Could not read PNG header: {}", e);
+                self.record_metadata_failure(path, &format!("Could not read PNG header: {e}"));
+                None
+            }
+            Err(_) => None,
+        }
+    }
+
+    fn record_metadata_failure(&self, path: &Path, message: &str) {
+        self.record_failure(ErrorEntry::new(
+            ErrorSource::Metadata,
+            Some(path.to_path_buf()),
+            message,
+        ));
+    }
+
+    fn is_media_file(path: &Path) -> bool {
+        path.to_str().is_some_and(|s| MEDIA_EXTENSIONS.is_match(s))
+    }
+
+    /// Returns whether a scan picks up `path`, honoring custom type mappings before the built-in media list
+    fn is_scanned(path: &Path, settings: &Settings, scan_all_types: bool) -> bool {
+        let mapping = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .and_then(|ext| settings.type_mapping(ext));
+        match mapping {
+            Some(TypeMapping::Ignore) => false,
+            Some(mapping) => scan_all_types || mapping.is_media(),
+            None => scan_all_types || Self::is_media_file(path),
+        }
+    }
+
+    /// Whether discovery collects `path`: a file to scan, or an archive whose media the scan lists
+    fn is_discovered(path: &Path, settings: &Settings, scan_all_types: bool) -> bool {
+        Self::is_scanned(path, settings, scan_all_types) || (settings.inspect_archives && is_archive(path))
+    }
+
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        !self.is_scanning.load(Ordering::Acquire)
+    }
+
+    pub fn set_scanning(&self, scanning: bool) {
+        self.is_scanning.store(scanning, Ordering::Release);
+    }
+
+    /// Asks the running scan to stop.
+    ///
+    /// Files being read are finished, then the scan ends with [`ScanCancelled`]. Nothing is
+    /// changed on disk, and files already read stay in the cache for the next scan.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Release);
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Acquire)
+    }
+
+    fn check_cancelled(&self) -> Result<()> {
+        if self.is_cancelled() {
+            info!("Scanner: Scan cancelled");
+            self.set_scanning(false);
+            return Err(ScanCancelled.into());
+        }
+        Ok(())
+    }
+
+    /// Logs the size of the cache and removes stale entries once it grows large.
+    async fn prune_cache(&self) -> Result<()> {
+        let cache_stats = {
+            let cache_lock = self.cache.read().await;
+            cache_lock.get_stats().await?
+        };
+        info!(
+            "Scanner: Cache has {} entries ({} with hashes)",
+            cache_stats.total_entries, cache_stats.entries_with_hash
+        );
 
-    #[must_use]
-    pub fn is_complete(&self) -> bool {
-        !self.is_scanning.load(Ordering::Acquire)
+        if cache_stats.total_entries > 10000 {
+            let removed = {
+                let cache_lock = self.cache.read().await;
+                cache_lock.remove_stale_entries().await?
+            };
+            info!("Scanner: Removed {} stale cache entries", removed);
+        }
+        Ok(())
     }
+}
 
-    pub fn set_scanning(&self, scanning: bool) {
-        self.is_scanning.store(scanning, Ordering::Release);
+/// Returns the type of files with `extension`, preferring a custom mapping over the built-in list.
+///
+/// Ignored extensions never reach this point because discovery already skips them.
+fn classify(settings: &Settings, extension: &str) -> FileType {
+    match settings.type_mapping(extension) {
+        Some(TypeMapping::Image) => FileType::Image,
+        Some(TypeMapping::Video) => FileType::Video,
+        Some(TypeMapping::Audio) => FileType::Audio,
+        Some(TypeMapping::Document) => FileType::Document,
+        Some(TypeMapping::Other | TypeMapping::Ignore) => FileType::Other,
+        None => determine_file_type(extension),
     }
 }
 
@@ -561,15 +1326,30 @@ fn is_hidden_in_path(path: &Path) -> bool {
     })
 }
 
+/// Identifies a directory by the device and inode it resolves to
+#[cfg(unix)]
+fn directory_key(path: &Path) -> Option<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+
+    std::fs::metadata(path)
+        .ok()
+        .map(|metadata| (metadata.dev(), metadata.ino()))
+}
+
+/// Identifies a directory by its canonical path where inodes are not available
+#[cfg(not(unix))]
+fn directory_key(path: &Path) -> Option<PathBuf> {
+    std::fs::canonicalize(path).ok()
+}
+
 #[cfg(test)]
 mod tests {
     #![allow(clippy::unwrap_used)]
     #![allow(clippy::expect_used)]
     #![allow(clippy::float_cmp)] // For comparing floats in tests
     #![allow(clippy::panic)]
-    #![allow(clippy::unwrap_used)]
-    #![allow(clippy::expect_used)]
     #![allow(clippy::panic_in_result_fn)]
+    #![allow(clippy::unwrap_in_result)]
 
     use crate::DatabaseCache;
 
@@ -693,6 +1473,35 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_scan_depth_limits() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let root = temp_dir.path();
+
+        create_test_file(&root.join("image1.jpg"), b"JPG_DATA").await?;
+        create_test_file(&root.join("trips/image2.jpg"), b"JPG_DATA").await?;
+        create_test_file(&root.join("trips/2024/image3.jpg"), b"JPG_DATA").await?;
+        create_test_file(&root.join("camera/2024/05/image4.jpg"), b"JPG_DATA").await?;
+
+        let scanner = create_test_scanner().await?;
+        let progress = Arc::new(RwLock::new(Progress::default()));
+        let settings = Settings {
+            max_depth: Some(1),
+            folder_depths: vec![visualvault_config::FolderDepth {
+                path: root.join("camera"),
+                max_depth: None,
+            }],
+            ..Default::default()
+        };
+
+        let files = scanner.scan_directory(root, true, progress, &settings, None).await?;
+
+        let mut names: Vec<&str> = files.iter().map(|f| f.name.as_ref()).collect();
+        names.sort_unstable();
+        assert_eq!(names, ["image1.jpg", "image2.jpg", "image4.jpg"]);
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_hidden_files_handling() -> Result<()> {
         let temp_dir = TempDir::new()?;
@@ -732,6 +1541,217 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_ignored_paths_are_skipped() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let root = temp_dir.path();
+
+        create_test_file(&root.join("keep.jpg"), b"JPG_DATA").await?;
+        create_test_file(&root.join("skip.jpg"), b"JPG_DATA").await?;
+        create_test_file(&root.join("private/image.jpg"), b"JPG_DATA").await?;
+
+        let scanner = create_test_scanner().await?;
+        let progress = Arc::new(RwLock::new(Progress::default()));
+        let settings = Settings {
+            recurse_subfolders: true,
+            ignored_paths: vec![root.join("skip.jpg"), root.join("private")],
+            ..Default::default()
+        };
+
+        let files = scanner
+            .scan_directory(root, true, progress.clone(), &settings, None)
+            .await?;
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].name.as_ref(), "keep.jpg");
+
+        let files = scanner.scan_directory(root, false, progress, &settings, None).await?;
+        assert_eq!(files.len(), 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_destination_inside_source_is_skipped() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let root = temp_dir.path();
+
+        create_test_file(&root.join("new.jpg"), b"JPG_DATA").await?;
+        create_test_file(&root.join("organized/2024/old.jpg"), b"JPG_DATA").await?;
+
+        let scanner = create_test_scanner().await?;
+        let progress = Arc::new(RwLock::new(Progress::default()));
+        let settings = Settings {
+            recurse_subfolders: true,
+            destination_folder: Some(root.join("organized")),
+            ..Default::default()
+        };
+
+        let files = scanner
+            .scan_directory(root, true, progress.clone(), &settings, None)
+            .await?;
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].name.as_ref(), "new.jpg");
+
+        // Scanning the destination itself still finds its files
+        let files = scanner
+            .scan_directory(&root.join("organized"), true, progress, &settings, None)
+            .await?;
+        assert_eq!(files.len(), 1);
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_follow_symlinks_handles_loops() -> Result<()> {
+        use std::os::unix::fs::symlink;
+
+        let temp_dir = TempDir::new()?;
+        let root = temp_dir.path().join("photos");
+        let outside = temp_dir.path().join("outside");
+
+        create_test_file(&root.join("a.jpg"), b"JPG_DATA").await?;
+        create_test_file(&root.join("nested/b.jpg"), b"JPG_DATA").await?;
+        create_test_file(&outside.join("c.jpg"), b"JPG_DATA").await?;
+        symlink(&root, root.join("nested/loop"))?;
+        symlink(root.join("nested"), root.join("again"))?;
+        symlink(&outside, root.join("linked"))?;
+
+        let scanner = create_test_scanner().await?;
+        let progress = Arc::new(RwLock::new(Progress::default()));
+
+        let settings = Settings::default();
+        let files = scanner
+            .scan_directory(&root, true, progress.clone(), &settings, None)
+            .await?;
+        assert_eq!(files.len(), 2);
+        assert!(files.iter().all(|f| !f.via_symlink));
+
+        let settings = Settings {
+            follow_symlinks: true,
+            ..Default::default()
+        };
+        let files = scanner.scan_directory(&root, true, progress, &settings, None).await?;
+        let mut names: Vec<_> = files.iter().map(|f| (f.name.to_string(), f.via_symlink)).collect();
+        names.sort();
+        assert_eq!(
+            names,
+            [
+                ("a.jpg".to_string(), false),
+                ("b.jpg".to_string(), false),
+                ("c.jpg".to_string(), true)
+            ]
+        );
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_unreadable_entries_are_recorded_as_failures() -> Result<()> {
+        use std::os::unix::fs::symlink;
+
+        let temp_dir = TempDir::new()?;
+        let root = temp_dir.path();
+        create_test_file(&root.join("a.jpg"), b"JPG_DATA").await?;
+        symlink(root.join("missing.jpg"), root.join("broken.jpg"))?;
+        symlink(root, root.join("loop"))?;
+
+        let scanner = create_test_scanner().await?;
+        let progress = Arc::new(RwLock::new(Progress::default()));
+        let settings = Settings {
+            follow_symlinks: true,
+            ..Default::default()
+        };
+        let files = scanner.scan_directory(root, true, progress, &settings, None).await?;
+        assert_eq!(files.len(), 1);
+
+        // The dangling link is reported, the loop is not
+        let failures = scanner.take_failures();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].source, ErrorSource::Scan);
+        assert_eq!(failures[0].path.as_deref(), Some(root.join("broken.jpg").as_path()));
+        assert!(scanner.take_failures().is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_archives_are_inspected() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let root = temp_dir.path();
+        create_test_file(&root.join("photo.jpg"), b"JPG_DATA").await?;
+        let mut tar = tar::Builder::new(Vec::new());
+        for (name, content) in [("inside.jpg", b"JPG"), ("notes.txt", b"TXT")] {
+            let mut header = tar::Header::new_ustar();
+            header.set_size(content.len() as u64);
+            header.set_mode(0o644);
+            tar.append_data(&mut header, name, &content[..])?;
+        }
+        create_test_file(&root.join("dump.tar"), &tar.into_inner()?).await?;
+
+        let scanner = create_test_scanner().await?;
+        let settings = Settings {
+            inspect_archives: true,
+            ..Default::default()
+        };
+        let progress = Arc::new(RwLock::new(Progress::default()));
+        let files = scanner.scan_directory(root, true, progress, &settings, None).await?;
+        assert_eq!(files.len(), 1);
+        let media = scanner.take_archive_media();
+        assert_eq!(media.len(), 1);
+        assert_eq!(media[0].archive, root.join("dump.tar"));
+        assert_eq!(media[0].name, "inside.jpg");
+
+        // Archives are left alone unless inspecting them is enabled
+        let progress = Arc::new(RwLock::new(Progress::default()));
+        scanner
+            .scan_directory(root, true, progress, &Settings::default(), None)
+            .await?;
+        assert!(scanner.take_archive_media().is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_suspect_files_are_set_aside() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let root = temp_dir.path();
+        image::RgbImage::new(4, 4).save(root.join("good.jpg"))?;
+        create_test_file(&root.join("empty.jpg"), b"").await?;
+        create_test_file(&root.join("broken.png"), b"JPG_DATA").await?;
+        create_test_file(&root.join("mislabeled.png"), &[0xFF, 0xD8, 0xFF, 0xDB]).await?;
+
+        let scanner = create_test_scanner().await?;
+        let progress = Arc::new(RwLock::new(Progress::default()));
+        let settings = Settings {
+            check_file_headers: true,
+            ..Settings::default()
+        };
+        let mut files = scanner.scan_directory(root, false, progress, &settings, None).await?;
+        files.sort_by(|a, b| a.name.cmp(&b.name));
+        assert_eq!(files.len(), 2);
+        assert_eq!(&*files[0].name, "good.jpg");
+        assert_eq!(files[0].content_extension, None);
+        // Mislabeled files are flagged rather than set aside
+        assert_eq!(&*files[1].name, "mislabeled.png");
+        assert_eq!(files[1].content_extension.as_deref(), Some("jpg"));
+
+        let mut suspects = scanner.take_suspects();
+        suspects.sort_by(|a, b| a.path.cmp(&b.path));
+        assert_eq!(suspects.len(), 2);
+        assert_eq!(suspects[0].path, root.join("broken.png"));
+        assert_eq!(suspects[0].reason, SuspectReason::UnknownHeader { expected: "PNG" });
+        assert_eq!(suspects[1].reason, SuspectReason::Empty);
+        assert!(scanner.take_suspects().is_empty());
+
+        // Unless they are quarantined too
+        let settings = Settings {
+            extension_mismatch: ExtensionMismatchPolicy::Quarantine,
+            ..settings
+        };
+        let progress = Arc::new(RwLock::new(Progress::default()));
+        let files = scanner.scan_directory(root, false, progress, &settings, None).await?;
+        assert_eq!(files.len(), 1);
+        assert_eq!(scanner.take_suspects().len(), 3);
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_filter_set_application() -> Result<()> {
         let temp_dir = TempDir::new()?;
@@ -760,6 +1780,42 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_extension_filters() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let root = temp_dir.path();
+
+        create_test_file(&root.join("a.jpg"), b"JPG_DATA").await?;
+        create_test_file(&root.join("b.PNG"), b"PNG_DATA").await?;
+        create_test_file(&root.join("c.gif"), b"GIF_DATA").await?;
+        create_test_file(&root.join("d.mp4"), b"MP4_DATA").await?;
+
+        let scanner = create_test_scanner().await?;
+        let settings = Settings::default();
+        let names = |filter: FilterSet| {
+            let scanner = scanner.clone();
+            let settings = settings.clone();
+            async move {
+                let progress = Arc::new(RwLock::new(Progress::default()));
+                let files = scanner
+                    .scan_directory(root, false, progress, &settings, Some(filter))
+                    .await?;
+                let mut names: Vec<_> = files.iter().map(|f| f.name.to_string()).collect();
+                names.sort();
+                Ok::<_, color_eyre::eyre::Error>(names)
+            }
+        };
+
+        let mut include = FilterSet::new();
+        include.add_extension_filter("jpg,png,heic");
+        assert_eq!(names(include).await?, vec!["a.jpg", "b.PNG"]);
+
+        let mut exclude = FilterSet::new();
+        exclude.add_extension_filter("!gif");
+        assert_eq!(names(exclude).await?, vec!["a.jpg", "b.PNG", "d.mp4"]);
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_cache_functionality() -> Result<()> {
         let temp_dir = TempDir::new()?;
@@ -791,9 +1847,11 @@ mod tests {
     async fn test_find_duplicates_empty_list() -> Result<()> {
         let scanner = create_test_scanner().await?;
         let mut files = vec![];
+        let progress = Arc::new(RwLock::new(Progress::default()));
 
-        let progress_callback = |_: usize, _: Option<String>| {};
-        let duplicates = scanner.find_duplicates(&mut files, progress_callback).await?;
+        let duplicates = scanner
+            .find_duplicates(&mut files, HashAlgorithm::Sha256, progress)
+            .await?;
         assert!(duplicates.is_empty());
         Ok(())
     }
@@ -816,8 +1874,9 @@ mod tests {
             .scan_directory(root, false, progress.clone(), &settings, None)
             .await?;
 
-        let progress_callback = |_: usize, _: Option<String>| {};
-        let duplicates = scanner.find_duplicates(&mut files, progress_callback).await?;
+        let duplicates = scanner
+            .find_duplicates(&mut files, HashAlgorithm::Sha256, progress.clone())
+            .await?;
 
         // Check the duplicate stats
         assert_eq!(duplicates.total_groups, 1);
@@ -847,6 +1906,69 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_find_duplicates_in_cache_scope() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let root = temp_dir.path().join("inbox");
+        let elsewhere = temp_dir.path().join("elsewhere");
+        create_test_file(&root.join("a.jpg"), b"DUPLICATE").await?;
+        create_test_file(&root.join("b.jpg"), b"DUPLICATE").await?;
+        create_test_file(&root.join("c.jpg"), b"DIFFERENT").await?;
+        create_test_file(&elsewhere.join("d.jpg"), b"DUPLICATE").await?;
+
+        let scanner = create_test_scanner().await?;
+        let progress = Arc::new(RwLock::new(Progress::default()));
+        let settings = Settings::default();
+        scanner
+            .scan_directory_with_duplicates(temp_dir.path(), true, progress.clone(), &settings, None)
+            .await?;
+
+        let scope = FileQuery {
+            folder: Some(root.clone()),
+            scanned_since: Some(Local::now() - chrono::Duration::seconds(60)),
+            ..FileQuery::default()
+        };
+        let files = scanner.scan_directory(&root, false, progress, &settings, None).await?;
+        let mut list = FileList::new(files);
+        list.set_cache_scope(scope);
+
+        let duplicates = scanner.find_duplicates_in(&list, settings.hash_algorithm).await?;
+        assert_eq!(duplicates.total_groups, 1);
+        let mut names: Vec<_> = duplicates.groups[0].files.iter().map(|f| f.name.to_string()).collect();
+        names.sort();
+        // The copy outside the scanned folder is left out
+        assert_eq!(names, ["a.jpg", "b.jpg"]);
+        assert!(duplicates.groups[0].files.iter().all(|f| f.hash.is_some()));
+        assert_eq!(duplicates.hash_cache_hits, 3);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_scan_into_pages_files_over_budget() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let root = temp_dir.path().join("inbox");
+        create_test_file(&root.join("a.jpg"), b"DUPLICATE").await?;
+        create_test_file(&root.join("b.jpg"), b"DUPLICATE").await?;
+        create_test_file(&root.join("c.jpg"), b"DIFFERENT").await?;
+        create_test_file(&root.join("song.mp3"), b"ID3").await?;
+
+        let scanner = create_test_scanner().await?;
+        let progress = Arc::new(RwLock::new(Progress::default()));
+        let mut files = FileList::with_budget(Some(1), &temp_dir.path().join("files.db"));
+        let duplicates = scanner
+            .scan_with_duplicates_into(&root, true, progress, &Settings::default(), None, &mut files)
+            .await?;
+
+        assert!(files.is_paged());
+        assert_eq!(files.len(), 4);
+        assert_eq!(files.audio_files().len(), 1);
+        assert_eq!(duplicates.total_groups, 1);
+        assert_eq!(duplicates.total_duplicates, 1);
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_scan_directory_with_duplicates() -> Result<()> {
         let temp_dir = TempDir::new()?;
@@ -873,6 +1995,54 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_changing_hash_algorithm_replaces_cached_hashes() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let root = temp_dir.path();
+        create_test_file(&root.join("dup1.jpg"), b"DUPLICATE").await?;
+        create_test_file(&root.join("dup2.jpg"), b"DUPLICATE").await?;
+
+        let scanner = create_test_scanner().await?;
+        let progress = Arc::new(RwLock::new(Progress::default()));
+        let mut settings = Settings {
+            parallel_processing: false,
+            hash_algorithm: HashAlgorithm::Xxh3,
+            ..Default::default()
+        };
+
+        scanner
+            .scan_directory_with_duplicates(root, false, Arc::clone(&progress), &settings, None)
+            .await?;
+        let files = scanner
+            .scan_directory(root, false, Arc::clone(&progress), &settings, None)
+            .await?;
+        assert!(
+            files
+                .iter()
+                .all(|f| f.hash.as_deref().is_some_and(|h| h.starts_with("xxh3:")))
+        );
+
+        // Cached XXH3 hashes are dropped on the next scan and replaced once hashed with BLAKE3
+        settings.hash_algorithm = HashAlgorithm::Blake3;
+        let files = scanner
+            .scan_directory(root, false, Arc::clone(&progress), &settings, None)
+            .await?;
+        assert!(files.iter().all(|f| f.hash.is_none()));
+
+        let (_, duplicates) = scanner
+            .scan_directory_with_duplicates(root, false, Arc::clone(&progress), &settings, None)
+            .await?;
+        assert_eq!(duplicates.len(), 1);
+        let files = scanner.scan_directory(root, false, progress, &settings, None).await?;
+        assert!(
+            files
+                .iter()
+                .all(|f| f.hash.as_deref().is_some_and(|h| h.starts_with("blake3:")))
+        );
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_parallel_vs_sequential_processing() -> Result<()> {
         let temp_dir = TempDir::new()?;
@@ -924,6 +2094,41 @@ mod tests {
         assert!(!Scanner::is_media_file(Path::new("test")));
     }
 
+    #[tokio::test]
+    async fn test_scan_applies_custom_type_mappings() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let root = temp_dir.path();
+        create_test_file(&root.join("clip.insv"), b"360 video").await?;
+        create_test_file(&root.join("photo.jpg"), b"image").await?;
+        create_test_file(&root.join("track.gpx"), b"<gpx/>").await?;
+
+        let scanner = create_test_scanner().await?;
+        let mut settings = Settings::default();
+        settings.set_type_mapping("insv", TypeMapping::Video).unwrap();
+        settings.set_type_mapping("jpg", TypeMapping::Ignore).unwrap();
+        settings.set_type_mapping("gpx", TypeMapping::Other).unwrap();
+
+        let files = scanner
+            .scan_directory(root, false, Arc::new(RwLock::new(Progress::default())), &settings, None)
+            .await?;
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].name.as_ref(), "clip.insv");
+        assert_eq!(files[0].file_type, FileType::Video);
+
+        // Organizing by type also picks up non-media mappings, but still skips ignored ones
+        settings.organize_by = "type".to_string();
+        let mut files = scanner
+            .scan_directory(root, true, Arc::new(RwLock::new(Progress::default())), &settings, None)
+            .await?;
+        files.sort_by(|a, b| a.name.cmp(&b.name));
+        let types: Vec<_> = files.iter().map(|f| (f.name.as_ref(), f.file_type.clone())).collect();
+        assert_eq!(
+            types,
+            vec![("clip.insv", FileType::Video), ("track.gpx", FileType::Other)]
+        );
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_is_hidden_in_path() {
         assert!(is_hidden_in_path(Path::new(".hidden")));
@@ -1066,10 +2271,15 @@ mod tests {
             7,
             "Should find 7 document files"
         );
+        assert_eq!(
+            type_counts.get(&FileType::Audio).copied().unwrap_or(0),
+            2,
+            "Should find 2 audio files"
+        );
         assert_eq!(
             type_counts.get(&FileType::Other).copied().unwrap_or(0),
-            6,
-            "Should find 6 other files"
+            4,
+            "Should find 4 other files"
         );
 
         // Test with default mode (not organize by type) - should only scan media files
@@ -1082,18 +2292,18 @@ mod tests {
             .scan_directory(root, false, progress.clone(), &settings_default, None)
             .await?;
 
-        // Should only find image and video files (5 files)
+        // Should only find image, video and audio files (7 files)
         assert_eq!(
             files_default.len(),
-            5,
+            7,
             "Should only scan media files when organize_by != 'type'"
         );
 
         // Verify only media files are found
         for file in &files_default {
             assert!(
-                matches!(file.file_type, FileType::Image | FileType::Video),
-                "Should only find image and video files in default mode"
+                matches!(file.file_type, FileType::Image | FileType::Video | FileType::Audio),
+                "Should only find image, video and audio files in default mode"
             );
         }
 
@@ -1177,7 +2387,7 @@ mod tests {
         let file_types = vec![
             ("jpg", "IMAGE", FileType::Image),
             ("pdf", "PDF", FileType::Document),
-            ("mp3", "AUDIO", FileType::Other),
+            ("mp3", "AUDIO", FileType::Audio),
             ("zip", "ARCHIVE", FileType::Other),
             ("mp4", "VIDEO", FileType::Video),
         ];
@@ -1218,8 +2428,9 @@ mod tests {
             match expected_type {
                 FileType::Image => assert_eq!(type_counts.get(&FileType::Image).copied().unwrap_or(0), 20),
                 FileType::Video => assert_eq!(type_counts.get(&FileType::Video).copied().unwrap_or(0), 20),
+                FileType::Audio => assert_eq!(type_counts.get(&FileType::Audio).copied().unwrap_or(0), 20),
                 FileType::Document => assert_eq!(type_counts.get(&FileType::Document).copied().unwrap_or(0), 20),
-                FileType::Other => assert_eq!(type_counts.get(&FileType::Other).copied().unwrap_or(0), 40),
+                FileType::Other => assert_eq!(type_counts.get(&FileType::Other).copied().unwrap_or(0), 20),
             }
         }
 
@@ -1239,10 +2450,10 @@ mod tests {
             .await?;
         let duration_media = start_media.elapsed();
 
-        // Should only find image and video files (40 files)
-        assert_eq!(files_media.len(), 40, "Should only scan media files");
+        // Should only find image, video and audio files (60 files)
+        assert_eq!(files_media.len(), 60, "Should only scan media files");
 
-        println!("Scanned 40 media files in {duration_media:?}");
+        println!("Scanned 60 media files in {duration_media:?}");
 
         Ok(())
     }
@@ -0,0 +1,120 @@
+use chrono::{DateTime, Duration, Local, NaiveTime, TimeZone};
+use visualvault_config::OrganizeSchedule;
+
+/// Decides when the next automatic scan and organize run is due.
+///
+/// The scheduler does not run anything itself; the application polls [`Scheduler::is_due`]
+/// from its tick loop and calls [`Scheduler::mark_run`] once it starts a run.
+#[derive(Debug, Clone, Default)]
+pub struct Scheduler {
+    schedule: Option<OrganizeSchedule>,
+    next_run: Option<DateTime<Local>>,
+}
+
+impl Scheduler {
+    #[must_use]
+    pub fn new(schedule: Option<OrganizeSchedule>, now: DateTime<Local>) -> Self {
+        Self {
+            schedule,
+            next_run: schedule.map(|schedule| Self::next_after(schedule, now)),
+        }
+    }
+
+    /// Replaces the schedule, keeping the pending run when the schedule is unchanged
+    pub fn set_schedule(&mut self, schedule: Option<OrganizeSchedule>, now: DateTime<Local>) {
+        if self.schedule != schedule {
+            *self = Self::new(schedule, now);
+        }
+    }
+
+    #[must_use]
+    pub const fn schedule(&self) -> Option<OrganizeSchedule> {
+        self.schedule
+    }
+
+    #[must_use]
+    pub const fn next_run(&self) -> Option<DateTime<Local>> {
+        self.next_run
+    }
+
+    #[must_use]
+    pub fn is_due(&self, now: DateTime<Local>) -> bool {
+        self.next_run.is_some_and(|next| now >= next)
+    }
+
+    /// Records that a run started at `now` and plans the following one
+    pub fn mark_run(&mut self, now: DateTime<Local>) {
+        self.next_run = self.schedule.map(|schedule| Self::next_after(schedule, now));
+    }
+
+    fn next_after(schedule: OrganizeSchedule, now: DateTime<Local>) -> DateTime<Local> {
+        match schedule {
+            OrganizeSchedule::EveryHours(hours) => now + Duration::hours(i64::from(hours.max(1))),
+            OrganizeSchedule::DailyAt(time) => {
+                let today = Self::local_at(now, time);
+                match today {
+                    Some(today) if today > now => today,
+                    _ => Self::local_at(now + Duration::days(1), time).unwrap_or_else(|| now + Duration::days(1)),
+                }
+            }
+        }
+    }
+
+    /// Resolves `time` on the day of `day`, picking the earlier instant when DST makes it ambiguous
+    fn local_at(day: DateTime<Local>, time: NaiveTime) -> Option<DateTime<Local>> {
+        Local.from_local_datetime(&day.date_naive().and_time(time)).earliest()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    fn at(hour: u32, minute: u32) -> DateTime<Local> {
+        Local.with_ymd_and_hms(2024, 6, 10, hour, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn test_no_schedule_is_never_due() {
+        let scheduler = Scheduler::new(None, at(10, 0));
+        assert!(scheduler.next_run().is_none());
+        assert!(!scheduler.is_due(at(23, 59)));
+    }
+
+    #[test]
+    fn test_every_hours() {
+        let mut scheduler = Scheduler::new(Some(OrganizeSchedule::EveryHours(2)), at(10, 0));
+        assert_eq!(scheduler.next_run(), Some(at(12, 0)));
+        assert!(!scheduler.is_due(at(11, 59)));
+        assert!(scheduler.is_due(at(12, 0)));
+
+        scheduler.mark_run(at(12, 5));
+        assert_eq!(scheduler.next_run(), Some(at(14, 5)));
+    }
+
+    #[test]
+    fn test_daily_at_later_today_or_tomorrow() {
+        let three = NaiveTime::from_hms_opt(15, 0, 0).unwrap();
+
+        let scheduler = Scheduler::new(Some(OrganizeSchedule::DailyAt(three)), at(10, 0));
+        assert_eq!(scheduler.next_run(), Some(at(15, 0)));
+
+        let scheduler = Scheduler::new(Some(OrganizeSchedule::DailyAt(three)), at(16, 0));
+        assert_eq!(scheduler.next_run(), Some(at(15, 0) + Duration::days(1)));
+    }
+
+    #[test]
+    fn test_set_schedule_keeps_pending_run_when_unchanged() {
+        let mut scheduler = Scheduler::new(Some(OrganizeSchedule::EveryHours(1)), at(10, 0));
+        scheduler.set_schedule(Some(OrganizeSchedule::EveryHours(1)), at(10, 30));
+        assert_eq!(scheduler.next_run(), Some(at(11, 0)));
+
+        scheduler.set_schedule(Some(OrganizeSchedule::EveryHours(3)), at(10, 30));
+        assert_eq!(scheduler.next_run(), Some(at(13, 30)));
+
+        scheduler.set_schedule(None, at(10, 30));
+        assert!(scheduler.next_run().is_none());
+    }
+}
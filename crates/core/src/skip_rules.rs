@@ -0,0 +1,161 @@
+use chrono::{DateTime, Local};
+use visualvault_config::SkipRules;
+use visualvault_models::{FileType, MediaFile, MediaMetadata, SkipRuleStats};
+
+use crate::metadata_service::read_image_metadata;
+
+/// The skip rule that leaves a file in the source
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SkipReason {
+    TooSmall,
+    LowResolution { width: u32, height: u32 },
+    TooNew,
+}
+
+impl SkipReason {
+    /// Returns the first rule `file` breaks at `now`, or `None` when it is organized.
+    ///
+    /// Images the scan did not measure have their header read; images that cannot be measured
+    /// are not skipped for their resolution.
+    pub(crate) fn check(rules: &SkipRules, file: &MediaFile, now: DateTime<Local>) -> Option<Self> {
+        if file.size < rules.min_size_kb.saturating_mul(1024) {
+            return Some(Self::TooSmall);
+        }
+        let min_age_days = i64::try_from(rules.min_age_days).unwrap_or(i64::MAX);
+        if min_age_days > 0 && (now - file.modified).num_days() < min_age_days {
+            return Some(Self::TooNew);
+        }
+        if rules.has_min_resolution() && file.file_type == FileType::Image {
+            let (width, height) = Self::dimensions(file)?;
+            if rules.below_min_resolution(width, height) {
+                return Some(Self::LowResolution { width, height });
+            }
+        }
+        None
+    }
+
+    /// Adds the file to the count of its rule
+    pub(crate) const fn count(self, stats: &mut SkipRuleStats) {
+        match self {
+            Self::TooSmall => stats.too_small += 1,
+            Self::LowResolution { .. } => stats.low_resolution += 1,
+            Self::TooNew => stats.too_new += 1,
+        }
+    }
+
+    /// Explains the skip in the organize report, e.g. "320×240, below 640×480"
+    pub(crate) fn describe(self, rules: &SkipRules) -> String {
+        match self {
+            Self::TooSmall => format!("Smaller than {} KB", rules.min_size_kb),
+            Self::LowResolution { width, height } => {
+                format!("{width}×{height}, below {}×{}", rules.min_width, rules.min_height)
+            }
+            Self::TooNew => format!("Modified within the last {} days", rules.min_age_days),
+        }
+    }
+
+    fn dimensions(file: &MediaFile) -> Option<(u32, u32)> {
+        if let Some(MediaMetadata::Image(image)) = &file.metadata {
+            if image.width > 0 && image.height > 0 {
+                return Some((image.width, image.height));
+            }
+        }
+        match read_image_metadata(&file.path).ok()? {
+            MediaMetadata::Image(image) => Some((image.width, image.height)),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::panic_in_result_fn)]
+    #![allow(clippy::unwrap_in_result)]
+
+    use super::*;
+    use crate::test_support::media_file;
+    use chrono::Duration;
+    use color_eyre::eyre::Result;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_size_and_age() {
+        let now = Local::now();
+        let rules = SkipRules {
+            min_size_kb: 50,
+            min_age_days: 7,
+            ..SkipRules::default()
+        };
+        let old = now - Duration::days(30);
+        let path = Path::new("/photos/a.png");
+
+        assert_eq!(
+            SkipReason::check(&rules, &media_file(path).size(20 * 1024).modified(old).build(), now),
+            Some(SkipReason::TooSmall)
+        );
+        assert_eq!(
+            SkipReason::check(
+                &rules,
+                &media_file(path)
+                    .size(80 * 1024)
+                    .modified(now - Duration::days(2))
+                    .build(),
+                now
+            ),
+            Some(SkipReason::TooNew)
+        );
+        assert_eq!(
+            SkipReason::check(&rules, &media_file(path).size(80 * 1024).modified(old).build(), now),
+            None
+        );
+        assert_eq!(
+            SkipReason::check(
+                &SkipRules::default(),
+                &media_file(path).size(0).modified(now).build(),
+                now
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_resolution() -> Result<()> {
+        let dir = TempDir::new()?;
+        let small = dir.path().join("small.png");
+        image::RgbImage::new(320, 240).save(&small)?;
+        let large = dir.path().join("large.png");
+        image::RgbImage::new(480, 640).save(&large)?;
+        let now = Local::now();
+        let rules = SkipRules {
+            min_width: 640,
+            min_height: 480,
+            ..SkipRules::default()
+        };
+
+        let reason = SkipReason::check(&rules, &media_file(&small).build(), now);
+        assert_eq!(
+            reason,
+            Some(SkipReason::LowResolution {
+                width: 320,
+                height: 240
+            })
+        );
+        assert_eq!(
+            reason.map(|r| r.describe(&rules)).as_deref(),
+            Some("320×240, below 640×480")
+        );
+        assert_eq!(SkipReason::check(&rules, &media_file(&large).build(), now), None);
+        // Images that cannot be measured are organized
+        assert_eq!(
+            SkipReason::check(&rules, &media_file(dir.path().join("missing.png")).build(), now),
+            None
+        );
+
+        let mut stats = SkipRuleStats::default();
+        SkipReason::TooSmall.count(&mut stats);
+        SkipReason::LowResolution { width: 1, height: 1 }.count(&mut stats);
+        assert_eq!((stats.too_small, stats.low_resolution, stats.total()), (1, 1, 2));
+        Ok(())
+    }
+}
@@ -0,0 +1,194 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+use color_eyre::eyre::{Result, eyre};
+use visualvault_utils::FolderStats;
+use walkdir::WalkDir;
+
+use crate::folder_stats::is_media_path;
+
+/// Disk usage of one folder, counted with everything below it
+#[derive(Debug, Clone)]
+pub struct FolderUsage {
+    pub path: PathBuf,
+    pub stats: FolderStats,
+}
+
+/// Where the disk space below a folder goes
+#[derive(Debug, Clone, Default)]
+pub struct SpaceReport {
+    pub root: PathBuf,
+    /// Everything below `root`
+    pub total: FolderStats,
+    /// Immediate subfolders of `root`, largest first
+    pub folders: Vec<FolderUsage>,
+    /// Largest files anywhere below `root` with their sizes, largest first
+    pub files: Vec<(PathBuf, u64)>,
+}
+
+/// Ranks the subfolders of `root` by their total size and finds its `max_files` largest files.
+///
+/// One pass over the tree adds every file to the [`FolderStats`] of the immediate subfolder it
+/// is in, so drilling into a subfolder means exploring it again. Files directly in `root` only
+/// count towards the total. Symbolic links are not followed and unreadable entries are skipped.
+///
+/// # Errors
+///
+/// Returns an error if `root` is not a folder.
+pub fn explore_space(root: &Path, max_files: usize) -> Result<SpaceReport> {
+    if !root.is_dir() {
+        return Err(eyre!("{} is not a folder", root.display()));
+    }
+
+    let mut total = FolderStats::default();
+    let mut folders: HashMap<OsString, FolderStats> = HashMap::new();
+    // Smallest of the largest files on top, so it is the one replaced
+    let mut largest: BinaryHeap<Reverse<(u64, PathBuf)>> = BinaryHeap::with_capacity(max_files + 1);
+
+    for entry in WalkDir::new(root)
+        .min_depth(1)
+        .follow_links(false)
+        .into_iter()
+        .filter_map(Result::ok)
+    {
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        let folder = entry
+            .path()
+            .strip_prefix(root)
+            .ok()
+            .and_then(|relative| relative.components().next())
+            .filter(|_| entry.depth() > 1 || metadata.is_dir())
+            .map(|component| folders.entry(component.as_os_str().to_os_string()).or_default());
+
+        if metadata.is_dir() {
+            total.total_dirs += 1;
+            if let Some(stats) = folder.filter(|_| entry.depth() > 1) {
+                stats.total_dirs += 1;
+            }
+        } else if metadata.is_file() {
+            let size = metadata.len();
+            let is_media = is_media_path(entry.path());
+            for stats in std::iter::once(&mut total).chain(folder) {
+                stats.total_files += 1;
+                stats.total_size += size;
+                stats.media_files += usize::from(is_media);
+            }
+
+            if max_files > 0 {
+                largest.push(Reverse((size, entry.into_path())));
+                if largest.len() > max_files {
+                    largest.pop();
+                }
+            }
+        }
+    }
+
+    let mut folders: Vec<FolderUsage> = folders
+        .into_iter()
+        .map(|(name, stats)| FolderUsage {
+            path: root.join(name),
+            stats,
+        })
+        .collect();
+    folders.sort_by(|a, b| {
+        b.stats
+            .total_size
+            .cmp(&a.stats.total_size)
+            .then_with(|| a.path.cmp(&b.path))
+    });
+
+    let files = largest
+        .into_sorted_vec()
+        .into_iter()
+        .map(|Reverse((size, path))| (path, size))
+        .collect();
+
+    Ok(SpaceReport {
+        root: root.to_path_buf(),
+        total,
+        folders,
+        files,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::panic_in_result_fn)]
+    #![allow(clippy::unwrap_in_result)]
+
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn write(path: &Path, size: usize) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, vec![0u8; size])?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_folders_and_files_are_ranked() -> Result<()> {
+        let dir = TempDir::new()?;
+        let root = dir.path();
+        write(&root.join("2023/01/a.jpg"), 300)?;
+        write(&root.join("2023/02/b.mp4"), 500)?;
+        write(&root.join("2024/c.jpg"), 100)?;
+        write(&root.join("2024/notes.txt"), 50)?;
+        write(&root.join("loose.png"), 1000)?;
+        fs::create_dir(root.join("empty"))?;
+
+        let report = explore_space(root, 3)?;
+        assert_eq!(report.total.total_files, 5);
+        assert_eq!(report.total.media_files, 4);
+        assert_eq!(report.total.total_size, 1950);
+        assert_eq!(report.total.total_dirs, 5);
+
+        let folders: Vec<_> = report
+            .folders
+            .iter()
+            .map(|folder| (folder.path.clone(), folder.stats.total_size))
+            .collect();
+        assert_eq!(
+            folders,
+            vec![
+                (root.join("2023"), 800),
+                (root.join("2024"), 150),
+                (root.join("empty"), 0)
+            ]
+        );
+        let year = &report.folders[0].stats;
+        assert_eq!((year.total_files, year.media_files, year.total_dirs), (2, 2, 2));
+        assert_eq!(report.folders[1].stats.media_files, 1);
+
+        assert_eq!(
+            report.files,
+            vec![
+                (root.join("loose.png"), 1000),
+                (root.join("2023/02/b.mp4"), 500),
+                (root.join("2023/01/a.jpg"), 300),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_drill_down_and_missing_root() -> Result<()> {
+        let dir = TempDir::new()?;
+        write(&dir.path().join("2023/01/a.jpg"), 300)?;
+        write(&dir.path().join("2023/02/b.jpg"), 200)?;
+
+        let report = explore_space(&dir.path().join("2023"), 0)?;
+        assert_eq!(report.folders.len(), 2);
+        assert_eq!(report.folders[0].path, dir.path().join("2023/01"));
+        assert!(report.files.is_empty());
+
+        assert!(explore_space(&dir.path().join("missing"), 10).is_err());
+        Ok(())
+    }
+}
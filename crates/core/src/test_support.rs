@@ -0,0 +1,75 @@
+//! Builders shared by the unit tests of this crate
+
+use std::path::Path;
+use std::sync::Arc;
+
+use chrono::{DateTime, Local};
+use visualvault_models::{FileType, MediaFile};
+
+/// Builds a [`MediaFile`] for tests, see [`media_file`]
+pub(crate) struct MediaFileBuilder(MediaFile);
+
+/// Starts a [`MediaFile`] for `path`, named after it: a 1 KiB image created and modified now
+/// that is not hashed yet.
+pub(crate) fn media_file(path: impl AsRef<Path>) -> MediaFileBuilder {
+    let path = path.as_ref().to_path_buf();
+    let now = Local::now();
+    MediaFileBuilder(MediaFile {
+        name: path
+            .file_name()
+            .map(|name| name.to_string_lossy().into())
+            .unwrap_or_default(),
+        extension: path
+            .extension()
+            .map(|extension| extension.to_string_lossy().into())
+            .unwrap_or_default(),
+        path,
+        file_type: FileType::Image,
+        size: 1024,
+        created: now,
+        modified: now,
+        hash: None,
+        metadata: None,
+        via_symlink: false,
+        live_photo_pair: None,
+        content_extension: None,
+    })
+}
+
+impl MediaFileBuilder {
+    pub(crate) const fn file_type(mut self, file_type: FileType) -> Self {
+        self.0.file_type = file_type;
+        self
+    }
+
+    pub(crate) const fn size(mut self, size: u64) -> Self {
+        self.0.size = size;
+        self
+    }
+
+    /// Sets the size to that of the file on disk, or 0 if it does not exist
+    pub(crate) fn size_on_disk(self) -> Self {
+        let size = std::fs::metadata(&self.0.path).map_or(0, |metadata| metadata.len());
+        self.size(size)
+    }
+
+    /// Sets both the creation and the modification time
+    pub(crate) const fn modified(mut self, modified: DateTime<Local>) -> Self {
+        self.0.created = modified;
+        self.0.modified = modified;
+        self
+    }
+
+    pub(crate) fn hash(mut self, hash: &str) -> Self {
+        self.0.hash = Some(hash.into());
+        self
+    }
+
+    pub(crate) fn build(self) -> MediaFile {
+        self.0
+    }
+
+    pub(crate) fn arc(self) -> Arc<MediaFile> {
+        Arc::new(self.0)
+    }
+}
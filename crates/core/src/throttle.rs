@@ -0,0 +1,174 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use visualvault_config::Throttle;
+
+/// Share of a second's allowance that may be used at once after an idle spell
+const BURST_SECS: f64 = 0.1;
+
+/// Holds scans and organize runs to the throughput and operation limits of
+/// [`Throttle`] settings.
+///
+/// Clones share their allowance, so every worker of a scan draws from the same budget.
+/// Callers that ask for more than is left wait until it refills; a single large read goes
+/// into debt that later callers wait out, so limits hold on average whatever the chunk size.
+#[derive(Debug, Clone)]
+pub struct IoThrottle {
+    bytes: Option<Arc<Mutex<Bucket>>>,
+    operations: Option<Arc<Mutex<Bucket>>>,
+}
+
+impl IoThrottle {
+    /// A throttle that never waits
+    #[must_use]
+    pub const fn unlimited() -> Self {
+        Self {
+            bytes: None,
+            operations: None,
+        }
+    }
+
+    #[must_use]
+    pub fn new(throttle: &Throttle) -> Self {
+        let bucket = |rate: u64| (rate > 0).then(|| Arc::new(Mutex::new(Bucket::new(rate, Instant::now()))));
+        Self {
+            bytes: bucket(throttle.max_mb_per_sec.saturating_mul(1024 * 1024)),
+            operations: bucket(throttle.max_iops),
+        }
+    }
+
+    /// Returns whether any limit applies.
+    #[must_use]
+    pub const fn is_limited(&self) -> bool {
+        self.bytes.is_some() || self.operations.is_some()
+    }
+
+    /// Waits until `operations` file operations moving `bytes` fit within the limits.
+    pub async fn acquire(&self, operations: u64, bytes: u64) {
+        let now = Instant::now();
+        let wait = Self::reserve(self.bytes.as_ref(), bytes, now).max(Self::reserve(
+            self.operations.as_ref(),
+            operations,
+            now,
+        ));
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    fn reserve(bucket: Option<&Arc<Mutex<Bucket>>>, amount: u64, now: Instant) -> Duration {
+        match bucket {
+            Some(bucket) if amount > 0 => bucket
+                .lock()
+                .map_or(Duration::ZERO, |mut bucket| bucket.reserve(amount, now)),
+            _ => Duration::ZERO,
+        }
+    }
+}
+
+impl Default for IoThrottle {
+    fn default() -> Self {
+        Self::unlimited()
+    }
+}
+
+/// Token bucket refilled at `rate` per second
+#[derive(Debug)]
+struct Bucket {
+    rate: f64,
+    capacity: f64,
+    /// Tokens left, negative while callers are waiting out earlier reservations
+    available: f64,
+    updated: Instant,
+}
+
+impl Bucket {
+    #[allow(clippy::cast_precision_loss)]
+    fn new(rate: u64, now: Instant) -> Self {
+        let rate = rate as f64;
+        let capacity = (rate * BURST_SECS).max(1.0);
+        Self {
+            rate,
+            capacity,
+            available: capacity,
+            updated: now,
+        }
+    }
+
+    /// Takes `amount` tokens and returns how long to wait before using them
+    #[allow(clippy::cast_precision_loss)]
+    fn reserve(&mut self, amount: u64, now: Instant) -> Duration {
+        let refill = now.saturating_duration_since(self.updated).as_secs_f64() * self.rate;
+        self.available = (self.available + refill).min(self.capacity);
+        self.updated = self.updated.max(now);
+        self.available -= amount as f64;
+        if self.available >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-self.available / self.rate)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bucket_spreads_reservations_over_time() {
+        let start = Instant::now();
+        let mut bucket = Bucket::new(100, start);
+
+        // The burst allowance of a tenth of a second goes out right away
+        assert_eq!(bucket.reserve(10, start), Duration::ZERO);
+        assert_eq!(bucket.reserve(10, start), Duration::from_millis(100));
+        assert_eq!(bucket.reserve(10, start), Duration::from_millis(200));
+
+        // Waiting refills the bucket, but never past the burst allowance
+        let later = start + Duration::from_secs(5);
+        assert_eq!(bucket.reserve(10, later), Duration::ZERO);
+        assert_eq!(bucket.reserve(1, later), Duration::from_millis(10));
+    }
+
+    #[test]
+    fn test_large_reads_go_into_debt() {
+        let start = Instant::now();
+        let mut bucket = Bucket::new(1000, start);
+        assert_eq!(bucket.reserve(2100, start), Duration::from_secs(2));
+        assert_eq!(
+            bucket.reserve(100, start + Duration::from_secs(1)),
+            Duration::from_millis(1100)
+        );
+    }
+
+    #[test]
+    fn test_limits_from_settings() {
+        assert!(!IoThrottle::new(&Throttle::default()).is_limited());
+        assert!(!IoThrottle::default().is_limited());
+        let throttle = IoThrottle::new(&Throttle {
+            max_mb_per_sec: 0,
+            max_iops: 50,
+        });
+        assert!(throttle.is_limited());
+        assert!(throttle.bytes.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_acquire_waits_for_the_operation_limit() {
+        let unlimited = IoThrottle::default();
+        let start = Instant::now();
+        unlimited.acquire(1000, 1 << 30).await;
+        assert!(start.elapsed() < Duration::from_millis(50));
+
+        let throttle = IoThrottle::new(&Throttle {
+            max_mb_per_sec: 0,
+            max_iops: 100,
+        });
+        let start = Instant::now();
+        for _ in 0..20 {
+            throttle.clone().acquire(1, 1 << 20).await;
+        }
+        // 10 operations fit in the burst, the other 10 take a tenth of a second
+        assert!(start.elapsed() >= Duration::from_millis(90));
+    }
+}
@@ -0,0 +1,386 @@
+use color_eyre::eyre::Result;
+use std::future::Future;
+use std::io::{self, ErrorKind};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::fs;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+use visualvault_utils::{Progress, extended_length_path, format_bytes};
+
+use crate::IoThrottle;
+
+const DEFAULT_MAX_RETRIES: u32 = 3;
+const DEFAULT_INITIAL_BACKOFF: Duration = Duration::from_millis(250);
+const MIN_BUFFER_SIZE: usize = 64 * 1024;
+const PARTIAL_SUFFIX: &str = ".vvpart";
+
+/// How a file reached its destination
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransferMethod {
+    /// The file was renamed in place on the same file system
+    Renamed,
+    /// The file was copied to another file system and the source removed
+    Copied,
+}
+
+/// Moves files between locations that may live on different file systems, such as a NAS share.
+///
+/// A plain rename is tried first. When the destination is on another device the file is copied
+/// through a buffer into a temporary file, flushed to disk, renamed into place and only then is
+/// the source removed. Transient I/O errors, common on network mounts, are retried with
+/// exponential backoff.
+#[derive(Debug, Clone)]
+pub struct Transfer {
+    buffer_size: usize,
+    max_retries: u32,
+    initial_backoff: Duration,
+    progress: Option<Arc<RwLock<Progress>>>,
+    throttle: IoThrottle,
+}
+
+impl Default for Transfer {
+    fn default() -> Self {
+        Self::new(MIN_BUFFER_SIZE)
+    }
+}
+
+impl Transfer {
+    #[must_use]
+    pub fn new(buffer_size: usize) -> Self {
+        Self {
+            buffer_size: buffer_size.max(MIN_BUFFER_SIZE),
+            max_retries: DEFAULT_MAX_RETRIES,
+            initial_backoff: DEFAULT_INITIAL_BACKOFF,
+            progress: None,
+            throttle: IoThrottle::default(),
+        }
+    }
+
+    /// Reports copied bytes of cross-device moves through the progress message
+    #[must_use]
+    pub fn with_progress(mut self, progress: Arc<RwLock<Progress>>) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+
+    /// Sets how often a transient failure is retried and the delay before the first retry
+    #[must_use]
+    pub const fn with_retries(mut self, max_retries: u32, initial_backoff: Duration) -> Self {
+        self.max_retries = max_retries;
+        self.initial_backoff = initial_backoff;
+        self
+    }
+
+    /// Holds moves and copies to the limits of `throttle`
+    #[must_use]
+    pub fn with_throttle(mut self, throttle: IoThrottle) -> Self {
+        self.throttle = throttle;
+        self
+    }
+
+    /// Moves `source` to `target`, copying across devices when a rename is not possible.
+    ///
+    /// Paths longer than `MAX_PATH` are opened with the extended-length prefix on Windows.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be renamed or copied, or if a transient error
+    /// persists after all retries. A failed copy leaves the source untouched.
+    pub async fn move_file(&self, source: &Path, target: &Path) -> Result<TransferMethod> {
+        let (source, target) = (&extended_length_path(source), &extended_length_path(target));
+        self.throttle.acquire(1, 0).await;
+        match self.retry(|| fs::rename(source, target)).await {
+            Ok(()) => Ok(TransferMethod::Renamed),
+            Err(e) if is_cross_device(&e) => {
+                info!(
+                    "{} and {} are on different devices, copying instead",
+                    source.display(),
+                    target.display()
+                );
+                self.copy_across_devices(source, target).await?;
+                Ok(TransferMethod::Copied)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn copy_across_devices(&self, source: &Path, target: &Path) -> Result<()> {
+        let partial = partial_path(target)?;
+
+        if let Err(e) = self.retry(|| self.copy_to_partial(source, &partial)).await {
+            let _ = fs::remove_file(&partial).await;
+            return Err(e.into());
+        }
+
+        self.retry(|| fs::rename(&partial, target)).await?;
+
+        // Only drop the source once the copy is safely in place
+        if let Err(e) = self.retry(|| fs::remove_file(source)).await {
+            let _ = fs::remove_file(target).await;
+            return Err(e.into());
+        }
+
+        Ok(())
+    }
+
+    async fn copy_to_partial(&self, source: &Path, partial: &Path) -> io::Result<()> {
+        let mut reader = fs::File::open(source).await?;
+        let metadata = reader.metadata().await?;
+        let total = metadata.len();
+        let name = source
+            .file_name()
+            .map_or_else(String::new, |n| n.to_string_lossy().to_string());
+
+        let mut writer = fs::File::create(partial).await?;
+        let mut buffer = vec![0; self.buffer_size];
+        let mut copied = 0u64;
+        let mut reported_percent = None;
+
+        loop {
+            let read = reader.read(&mut buffer).await?;
+            if read == 0 {
+                break;
+            }
+            self.throttle.acquire(0, read as u64).await;
+            writer.write_all(&buffer[..read]).await?;
+            copied += read as u64;
+            reported_percent = self.report_bytes(&name, copied, total, reported_percent);
+        }
+
+        writer.flush().await?;
+        writer.sync_all().await?;
+
+        // Keep the original timestamps so date-based organization stays stable
+        let writer = writer.into_std().await;
+        writer.set_modified(metadata.modified()?)?;
+
+        Ok(())
+    }
+
+    fn report_bytes(&self, name: &str, copied: u64, total: u64, last_percent: Option<u64>) -> Option<u64> {
+        let Some(progress) = &self.progress else {
+            return last_percent;
+        };
+
+        let percent = (copied * 100).checked_div(total).unwrap_or(100);
+        if last_percent == Some(percent) {
+            return last_percent;
+        }
+
+        // Never block the copy on a busy progress lock
+        if let Ok(mut prog) = progress.try_write() {
+            prog.message = format!(
+                "Copying {name}: {percent}% ({} / {})",
+                format_bytes(copied),
+                format_bytes(total)
+            );
+            Some(percent)
+        } else {
+            last_percent
+        }
+    }
+
+    async fn retry<T, F, Fut>(&self, mut operation: F) -> io::Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = io::Result<T>>,
+    {
+        let mut backoff = self.initial_backoff;
+        let mut attempt = 0;
+
+        loop {
+            match operation().await {
+                Err(e) if is_transient(&e) && attempt < self.max_retries => {
+                    attempt += 1;
+                    warn!(
+                        "Transient I/O error ({}), retrying in {:?} (attempt {}/{})",
+                        e, backoff, attempt, self.max_retries
+                    );
+                    tokio::time::sleep(backoff).await;
+                    backoff *= 2;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+/// Moves a file synchronously, copying across devices when a rename is not possible.
+///
+/// Used where async transfers are not available, such as undo and redo.
+///
+/// # Errors
+///
+/// Returns an error if the file can neither be renamed nor copied and removed.
+pub fn move_file_sync(source: &Path, target: &Path) -> io::Result<()> {
+    match std::fs::rename(source, target) {
+        Err(e) if is_cross_device(&e) => {
+            let partial = partial_path(target).map_err(io::Error::other)?;
+            let copy = || -> io::Result<()> {
+                let modified = std::fs::metadata(source)?.modified()?;
+                std::fs::copy(source, &partial)?;
+                let file = std::fs::File::options().write(true).open(&partial)?;
+                file.sync_all()?;
+                file.set_modified(modified)?;
+                std::fs::rename(&partial, target)
+            };
+
+            if let Err(e) = copy() {
+                let _ = std::fs::remove_file(&partial);
+                return Err(e);
+            }
+
+            // Like the async move, never leave the file in both places
+            if let Err(e) = std::fs::remove_file(source) {
+                let _ = std::fs::remove_file(target);
+                return Err(e);
+            }
+            Ok(())
+        }
+        result => result,
+    }
+}
+
+fn partial_path(target: &Path) -> Result<PathBuf> {
+    let name = target
+        .file_name()
+        .ok_or_else(|| color_eyre::eyre::eyre!("Invalid target path: {}", target.display()))?;
+    Ok(target.with_file_name(format!(".{}{PARTIAL_SUFFIX}", name.to_string_lossy())))
+}
+
+fn is_cross_device(e: &io::Error) -> bool {
+    e.kind() == ErrorKind::CrossesDevices
+}
+
+fn is_transient(e: &io::Error) -> bool {
+    matches!(
+        e.kind(),
+        ErrorKind::Interrupted
+            | ErrorKind::TimedOut
+            | ErrorKind::WouldBlock
+            | ErrorKind::ConnectionReset
+            | ErrorKind::ConnectionAborted
+            | ErrorKind::NotConnected
+            | ErrorKind::BrokenPipe
+            | ErrorKind::ResourceBusy
+            | ErrorKind::StaleNetworkFileHandle
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    #![allow(clippy::panic_in_result_fn)]
+    #![allow(clippy::unwrap_in_result)]
+
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::time::SystemTime;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_move_file_renames_on_same_device() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let source = temp_dir.path().join("a.jpg");
+        let target = temp_dir.path().join("b.jpg");
+        fs::write(&source, b"data").await?;
+
+        let method = Transfer::default().move_file(&source, &target).await?;
+
+        assert_eq!(method, TransferMethod::Renamed);
+        assert!(!source.exists());
+        assert_eq!(fs::read(&target).await?, b"data");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_copy_across_devices_preserves_content_and_mtime() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let source = temp_dir.path().join("video.mp4");
+        let target = temp_dir.path().join("nas").join("video.mp4");
+        fs::create_dir_all(target.parent().unwrap()).await?;
+
+        let content: Vec<u8> = (0..200_000u32).map(|i| (i % 251) as u8).collect();
+        fs::write(&source, &content).await?;
+        let modified = SystemTime::now() - Duration::from_secs(86_400);
+        std::fs::File::options()
+            .write(true)
+            .open(&source)?
+            .set_modified(modified)?;
+
+        let progress = Arc::new(RwLock::new(Progress::default()));
+        let transfer = Transfer::new(0).with_progress(Arc::clone(&progress));
+        transfer.copy_across_devices(&source, &target).await?;
+
+        assert!(!source.exists());
+        assert!(!partial_path(&target)?.exists());
+        assert_eq!(fs::read(&target).await?, content);
+        assert_eq!(fs::metadata(&target).await?.modified()?, modified);
+        assert!(progress.read().await.message.contains("100%"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_failed_copy_keeps_source() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let source = temp_dir.path().join("a.jpg");
+        let target = temp_dir.path().join("missing-dir").join("a.jpg");
+        fs::write(&source, b"data").await?;
+
+        assert!(Transfer::default().copy_across_devices(&source, &target).await.is_err());
+        assert!(source.exists());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_retry_recovers_from_transient_errors() {
+        let attempts = AtomicU32::new(0);
+        let transfer = Transfer::default().with_retries(3, Duration::from_millis(1));
+
+        let result = transfer
+            .retry(|| async {
+                if attempts.fetch_add(1, Ordering::SeqCst) < 2 {
+                    Err(io::Error::from(ErrorKind::TimedOut))
+                } else {
+                    Ok(42)
+                }
+            })
+            .await;
+
+        assert_eq!(result.unwrap(), 42);
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_retry_gives_up_on_permanent_errors() {
+        let attempts = AtomicU32::new(0);
+        let transfer = Transfer::default().with_retries(3, Duration::from_millis(1));
+
+        let result: io::Result<()> = transfer
+            .retry(|| async {
+                attempts.fetch_add(1, Ordering::SeqCst);
+                Err(io::Error::from(ErrorKind::PermissionDenied))
+            })
+            .await;
+
+        assert!(result.is_err());
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_move_file_sync_same_device() -> io::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let source = temp_dir.path().join("a.jpg");
+        let target = temp_dir.path().join("b.jpg");
+        std::fs::write(&source, b"data")?;
+
+        move_file_sync(&source, &target)?;
+
+        assert!(!source.exists());
+        assert!(target.exists());
+        Ok(())
+    }
+}
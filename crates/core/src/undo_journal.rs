@@ -0,0 +1,240 @@
+use std::collections::VecDeque;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::Path;
+
+use color_eyre::{Result, eyre::bail};
+use flate2::{Compression, read::MultiGzDecoder, write::GzEncoder};
+use serde::{Deserialize, Serialize};
+
+use crate::undo_manager::UndoableOperation;
+
+/// Bytes every undo journal starts with, followed by the format version as a little-endian u16
+const MAGIC: &[u8; 6] = b"VVUNDO";
+
+/// Version of the entries written after the header
+pub const JOURNAL_VERSION: u16 = 1;
+
+/// A change to the undo history, appended to the journal as it happens
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum JournalEntry {
+    Recorded(UndoableOperation),
+    Undone(String),
+    Redone(String),
+}
+
+/// The undo history replayed from a journal
+#[derive(Debug, Default)]
+pub struct LoadedJournal {
+    pub operations: VecDeque<UndoableOperation>,
+    /// Entries read from the file, to tell when it is worth compacting
+    pub entries: usize,
+    /// Whether the file ended in an entry that could not be read
+    pub truncated: bool,
+}
+
+/// Appends `entries` to the journal at `path` as one gzip member of msgpack values,
+/// writing the header first if the file is new.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be written or an entry cannot be encoded.
+pub fn append(path: &Path, entries: &[JournalEntry]) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    if file.metadata()?.len() == 0 {
+        write_header(&mut file)?;
+    }
+    write_entries(&mut file, entries)
+}
+
+/// Replaces the journal at `path` with one holding only `operations`.
+///
+/// The new journal is written next to the old one and renamed over it, so a crash leaves
+/// one of the two intact.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be written or an operation cannot be encoded.
+pub fn write_snapshot<'a>(path: &Path, operations: impl IntoIterator<Item = &'a UndoableOperation>) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let temp_path = path.with_extension("tmp");
+    let mut file = File::create(&temp_path)?;
+    write_header(&mut file)?;
+    let entries: Vec<JournalEntry> = operations
+        .into_iter()
+        .map(|operation| JournalEntry::Recorded(operation.clone()))
+        .collect();
+    write_entries(&mut file, &entries)?;
+    file.sync_all()?;
+    drop(file);
+    fs::rename(&temp_path, path)?;
+    Ok(())
+}
+
+/// Reads the journal at `path` and replays it, keeping at most `max_operations`.
+///
+/// An entry cut off by a crash ends the replay with a warning instead of failing it.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read, is not an undo journal or was written by a
+/// newer version.
+pub fn load(path: &Path, max_operations: usize) -> Result<LoadedJournal> {
+    let mut file = File::open(path)?;
+    let mut header = [0u8; MAGIC.len() + 2];
+    if file.read_exact(&mut header).is_err() || !header.starts_with(MAGIC) {
+        bail!("{} is not an undo history file", path.display());
+    }
+    let version = u16::from_le_bytes([header[MAGIC.len()], header[MAGIC.len() + 1]]);
+    if version > JOURNAL_VERSION {
+        bail!(
+            "{} was written by a newer version of VisualVault (format {version})",
+            path.display()
+        );
+    }
+
+    let mut journal = LoadedJournal::default();
+    let mut reader = BufReader::new(MultiGzDecoder::new(BufReader::new(file)));
+    loop {
+        match reader.fill_buf() {
+            Ok([]) => break,
+            Ok(_) => {}
+            Err(e) => {
+                tracing::warn!("Ignoring the unreadable end of {}: {}", path.display(), e);
+                journal.truncated = true;
+                break;
+            }
+        }
+        match rmp_serde::from_read::<_, JournalEntry>(&mut reader) {
+            Ok(entry) => {
+                journal.entries += 1;
+                replay(&mut journal.operations, entry, max_operations);
+            }
+            Err(e) => {
+                tracing::warn!("Ignoring the unreadable end of {}: {}", path.display(), e);
+                journal.truncated = true;
+                break;
+            }
+        }
+    }
+    Ok(journal)
+}
+
+fn replay(operations: &mut VecDeque<UndoableOperation>, entry: JournalEntry, max_operations: usize) {
+    match entry {
+        JournalEntry::Recorded(operation) => {
+            operations.push_back(operation);
+            while operations.len() > max_operations {
+                operations.pop_front();
+            }
+        }
+        JournalEntry::Undone(id) => set_undone(operations, &id, true),
+        JournalEntry::Redone(id) => set_undone(operations, &id, false),
+    }
+}
+
+fn set_undone(operations: &mut VecDeque<UndoableOperation>, id: &str, undone: bool) {
+    if let Some(operation) = operations.iter_mut().rev().find(|operation| operation.id == id) {
+        operation.undone = undone;
+    }
+}
+
+fn write_header(file: &mut File) -> Result<()> {
+    file.write_all(MAGIC)?;
+    file.write_all(&JOURNAL_VERSION.to_le_bytes())?;
+    Ok(())
+}
+
+fn write_entries(file: &mut File, entries: &[JournalEntry]) -> Result<()> {
+    let mut encoder = GzEncoder::new(file, Compression::default());
+    for entry in entries {
+        // Named fields keep older journals readable when an operation gains a field
+        rmp_serde::encode::write_named(&mut encoder, entry)?;
+    }
+    encoder.finish()?.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+    use crate::undo_manager::{MoveOperation, OperationType};
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+
+    fn operation(name: &str) -> UndoableOperation {
+        let mut operation = UndoableOperation::new(
+            OperationType::BatchMove {
+                operations: vec![MoveOperation {
+                    source: PathBuf::from(format!("/src/{name}")),
+                    destination: PathBuf::from(format!("/dst/{name}")),
+                }],
+            },
+            format!("Moved {name}"),
+        );
+        operation.metadata = Some(serde_json::json!({ "files": 1 }));
+        operation
+    }
+
+    #[test]
+    fn test_replays_appended_entries() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("history").join("undo_history.bin");
+        let (first, second, third) = (operation("a.jpg"), operation("b.jpg"), operation("c.jpg"));
+
+        append(&path, &[JournalEntry::Recorded(first)]).unwrap();
+        append(&path, &[JournalEntry::Recorded(second.clone())]).unwrap();
+        append(&path, &[JournalEntry::Undone(second.id.clone())]).unwrap();
+        append(&path, &[JournalEntry::Recorded(third.clone())]).unwrap();
+
+        let journal = load(&path, 2).unwrap();
+        assert_eq!(journal.entries, 4);
+        assert!(!journal.truncated);
+        let ids: Vec<&str> = journal.operations.iter().map(|op| op.id.as_str()).collect();
+        assert_eq!(ids, [second.id.as_str(), third.id.as_str()]);
+        assert!(journal.operations[0].undone);
+        assert_eq!(journal.operations[0].metadata, second.metadata);
+
+        write_snapshot(&path, &journal.operations).unwrap();
+        let compacted = load(&path, 10).unwrap();
+        assert_eq!(compacted.entries, 2);
+        assert!(compacted.operations[0].undone);
+    }
+
+    #[test]
+    fn test_load_stops_at_truncated_entry() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("undo_history.bin");
+        append(&path, &[JournalEntry::Recorded(operation("a.jpg"))]).unwrap();
+        let intact = fs::metadata(&path).unwrap().len();
+        append(&path, &[JournalEntry::Recorded(operation("b.jpg"))]).unwrap();
+
+        let file = OpenOptions::new().write(true).open(&path).unwrap();
+        file.set_len(intact + 10).unwrap();
+
+        let journal = load(&path, 10).unwrap();
+        assert_eq!(journal.operations.len(), 1);
+        assert!(journal.truncated);
+    }
+
+    #[test]
+    fn test_load_rejects_foreign_and_newer_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("undo_history.bin");
+
+        fs::write(&path, b"[]").unwrap();
+        assert!(load(&path, 10).is_err());
+
+        let mut header = MAGIC.to_vec();
+        header.extend_from_slice(&(JOURNAL_VERSION + 1).to_le_bytes());
+        fs::write(&path, header).unwrap();
+        let error = load(&path, 10).unwrap_err().to_string();
+        assert!(error.contains("newer version"));
+    }
+}
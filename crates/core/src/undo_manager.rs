@@ -1,13 +1,24 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDateTime, Utc};
 use color_eyre::Result;
 use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use tokio::sync::RwLock;
+
+use crate::transfer::move_file_sync;
+use crate::undo_journal::{self, JournalEntry};
 const MAX_UNDO_HISTORY: usize = 10000;
-const UNDO_HISTORY_FILE: &str = "undo_history.json";
+const UNDO_HISTORY_FILE: &str = "undo_history.bin";
+/// History file of earlier versions, converted to the journal the first time it is loaded
+const LEGACY_UNDO_HISTORY_FILE: &str = "undo_history.json";
+/// Journal size below which it is never rewritten, however many entries are stale
+const MIN_COMPACT_ENTRIES: usize = 256;
+const BACKUP_DIR: &str = "backups";
+/// Days the backups of replaced and deleted files are kept for undo before they are removed
+const BACKUP_RETENTION_DAYS: i64 = 30;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum OperationType {
@@ -32,6 +43,31 @@ pub enum OperationType {
     OrganizeFiles {
         operations: Vec<FileOperation>,
     },
+    /// Empty directories removed in order, nested directories before their parents
+    BatchRemoveDirs {
+        directories: Vec<PathBuf>,
+    },
+    TimestampChange {
+        changes: Vec<TimestampOperation>,
+    },
+}
+
+impl OperationType {
+    /// Backups the operation keeps of the files it replaced or deleted
+    fn backup_paths(&self) -> Vec<&Path> {
+        match self {
+            Self::Delete { backup_path, .. } => backup_path.as_deref().into_iter().collect(),
+            Self::BatchDelete { operations } => operations.iter().filter_map(|op| op.backup_path.as_deref()).collect(),
+            Self::OrganizeFiles { operations } => operations
+                .iter()
+                .filter_map(|op| match op {
+                    FileOperation::Delete(delete) => delete.backup_path.as_deref(),
+                    FileOperation::Move(_) | FileOperation::Copy { .. } => None,
+                })
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -46,6 +82,24 @@ pub struct DeleteOperation {
     pub backup_path: Option<PathBuf>,
 }
 
+/// Which date of a file a [`TimestampOperation`] changed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TimestampField {
+    /// The modification time in the file system
+    Modified,
+    /// The capture date in the EXIF data
+    ExifTaken,
+}
+
+/// A date of a file changed from `before` to `after`, both in local time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimestampOperation {
+    pub path: PathBuf,
+    pub field: TimestampField,
+    pub before: NaiveDateTime,
+    pub after: NaiveDateTime,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum FileOperation {
     Move(MoveOperation),
@@ -88,6 +142,8 @@ pub struct UndoManager {
     history: Arc<RwLock<VecDeque<UndoableOperation>>>,
     redo_stack: Arc<RwLock<Vec<UndoableOperation>>>,
     config_dir: PathBuf,
+    /// Entries in the history file, compared to the history length to decide when to rewrite it
+    journal_entries: AtomicUsize,
 }
 
 impl UndoManager {
@@ -104,6 +160,7 @@ impl UndoManager {
             history: Arc::new(RwLock::new(VecDeque::with_capacity(MAX_UNDO_HISTORY))),
             redo_stack: Arc::new(RwLock::new(Vec::new())),
             config_dir,
+            journal_entries: AtomicUsize::new(0),
         }
     }
 
@@ -114,7 +171,8 @@ impl UndoManager {
     /// This function will return an error if:
     /// - The undo manager cannot be initialized
     /// - The history file cannot be read from disk
-    /// - The history file contains invalid JSON data
+    /// - The history file is not an undo history or was written by a newer version
+    /// - The history file of an earlier version contains invalid JSON data
     /// - File system operations fail during history loading
     pub async fn new_with_history(config_dir: PathBuf) -> Result<Self> {
         let mut manager = Self::new(config_dir);
@@ -122,13 +180,19 @@ impl UndoManager {
         Ok(manager)
     }
 
+    /// Directory where files replaced by undoable operations are kept until restored
+    #[must_use]
+    pub fn backup_dir(&self) -> PathBuf {
+        self.config_dir.join("visualvault").join(BACKUP_DIR)
+    }
+
     /// Record a new operation in the undo history
     ///
     /// # Errors
     ///
     /// This function will return an error if:
     /// - The history cannot be saved to disk due to file system errors
-    /// - JSON serialization of the history fails
+    /// - Encoding the history fails
     /// - Directory creation fails when saving the history file
     pub async fn record_operation(&self, operation: UndoableOperation) -> Result<()> {
         let mut history = self.history.write().await;
@@ -137,16 +201,18 @@ impl UndoManager {
         self.redo_stack.write().await.clear();
 
         // Add new operation
-        history.push_back(operation);
+        history.push_back(operation.clone());
 
         // Maintain max history size
         while history.len() > MAX_UNDO_HISTORY {
-            history.pop_front();
+            if let Some(evicted) = history.pop_front() {
+                remove_backups(&evicted);
+            }
         }
 
         // Save to disk
+        self.persist(&history, JournalEntry::Recorded(operation))?;
         drop(history);
-        self.save_history().await?;
 
         Ok(())
     }
@@ -157,7 +223,7 @@ impl UndoManager {
     ///
     /// This function will return an error if:
     /// - The history cannot be saved to disk due to file system errors
-    /// - JSON serialization of the history fails
+    /// - Encoding the history fails
     /// - Directory creation fails when saving the history file
     #[allow(dead_code)]
     pub async fn record_move(&self, source: &Path, destination: &Path) -> Result<()> {
@@ -178,7 +244,7 @@ impl UndoManager {
     ///
     /// This function will return an error if:
     /// - The history cannot be saved to disk due to file system errors
-    /// - JSON serialization of the history fails
+    /// - Encoding the history fails
     /// - Directory creation fails when saving the history file
     pub async fn record_organize(&self, operations: Vec<FileOperation>) -> Result<()> {
         let count = operations.len();
@@ -190,6 +256,129 @@ impl UndoManager {
         self.record_operation(operation).await
     }
 
+    /// Record files moved to the trash folder
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The history cannot be saved to disk due to file system errors
+    /// - Encoding the history fails
+    /// - Directory creation fails when saving the history file
+    pub async fn record_batch_delete(&self, operations: Vec<DeleteOperation>) -> Result<()> {
+        let count = operations.len();
+        let operation = UndoableOperation::new(
+            OperationType::BatchDelete { operations },
+            format!("Moved {count} files to trash"),
+        );
+
+        self.record_operation(operation).await
+    }
+
+    /// Record files moved to another folder
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The history cannot be saved to disk due to file system errors
+    /// - Encoding the history fails
+    /// - Directory creation fails when saving the history file
+    pub async fn record_batch_move(&self, operations: Vec<MoveOperation>) -> Result<()> {
+        let count = operations.len();
+        let operation = UndoableOperation::new(OperationType::BatchMove { operations }, format!("Moved {count} files"));
+
+        self.record_operation(operation).await
+    }
+
+    /// Record files copied to another folder. Undoing removes the copies.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The history cannot be saved to disk due to file system errors
+    /// - Encoding the history fails
+    /// - Directory creation fails when saving the history file
+    pub async fn record_batch_copy(&self, operations: Vec<MoveOperation>) -> Result<()> {
+        let count = operations.len();
+        let operations = operations
+            .into_iter()
+            .map(|op| FileOperation::Copy {
+                source: op.source,
+                destination: op.destination,
+            })
+            .collect();
+        let operation = UndoableOperation::new(
+            OperationType::OrganizeFiles { operations },
+            format!("Copied {count} files"),
+        );
+
+        self.record_operation(operation).await
+    }
+
+    /// Record files renamed in place
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The history cannot be saved to disk due to file system errors
+    /// - Encoding the history fails
+    /// - Directory creation fails when saving the history file
+    pub async fn record_batch_rename(&self, operations: Vec<MoveOperation>) -> Result<()> {
+        let count = operations.len();
+        let operation = UndoableOperation::new(
+            OperationType::BatchMove { operations },
+            format!("Renamed {count} files"),
+        );
+
+        self.record_operation(operation).await
+    }
+
+    /// Record the removal of empty directories
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The history cannot be saved to disk due to file system errors
+    /// - Encoding the history fails
+    /// - Directory creation fails when saving the history file
+    pub async fn record_remove_dirs(&self, directories: Vec<PathBuf>) -> Result<()> {
+        let count = directories.len();
+        let operation = UndoableOperation::new(
+            OperationType::BatchRemoveDirs { directories },
+            format!("Removed {count} empty folders"),
+        );
+
+        self.record_operation(operation).await
+    }
+
+    /// Record corrected file dates
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The history cannot be saved to disk due to file system errors
+    /// - Encoding the history fails
+    /// - Directory creation fails when saving the history file
+    pub async fn record_timestamp_changes(&self, changes: Vec<TimestampOperation>) -> Result<()> {
+        let count = changes.len();
+        let operation = UndoableOperation::new(
+            OperationType::TimestampChange { changes },
+            format!("Fixed dates of {count} files"),
+        );
+
+        self.record_operation(operation).await
+    }
+
+    /// Reverses the operations of an organize run that is not in the history, newest first.
+    ///
+    /// # Errors
+    ///
+    /// This function currently does not return any errors; files that cannot be moved back
+    /// are counted in the returned message.
+    pub fn revert_organize(operations: Vec<FileOperation>) -> Result<String> {
+        let operation = UndoableOperation::new(OperationType::OrganizeFiles { operations }, String::new());
+        Self::undo_operation(&operation)
+    }
+
     /// Undo the last operation
     ///
     /// # Errors
@@ -217,12 +406,12 @@ impl UndoManager {
             history[pos].undone = true;
             operation.undone = true;
 
-            // Add to redo stack
-            self.redo_stack.write().await.push(operation);
-
             // Save history
+            self.persist(&history, JournalEntry::Undone(operation.id.clone()))?;
             drop(history);
-            self.save_history().await?;
+
+            // Add to redo stack
+            self.redo_stack.write().await.push(operation);
 
             Ok(Some(result))
         } else {
@@ -251,8 +440,8 @@ impl UndoManager {
                 history[pos].undone = false;
             }
 
+            self.persist(&history, JournalEntry::Redone(op.id))?;
             drop(history);
-            self.save_history().await?;
 
             Ok(Some(result))
         } else {
@@ -302,7 +491,7 @@ impl UndoManager {
             OperationType::Move { source, destination } => {
                 // Undo move by moving back
                 if destination.exists() {
-                    fs::rename(destination, source)?;
+                    move_file_sync(destination, source)?;
 
                     // Clean up empty directories left behind
                     if let Some(parent) = destination.parent() {
@@ -338,7 +527,7 @@ impl UndoManager {
                 // Undo delete by restoring from backup
                 if let Some(backup) = backup_path {
                     if backup.exists() {
-                        fs::rename(backup, path)?;
+                        move_file_sync(backup, path)?;
                         Ok(format!("Restored {} from backup", path.display()))
                     } else {
                         Err(VisualVaultError::UndoError {
@@ -361,7 +550,7 @@ impl UndoManager {
 
                 for op in operations.iter().rev() {
                     if op.destination.exists() {
-                        match fs::rename(&op.destination, &op.source) {
+                        match move_file_sync(&op.destination, &op.source) {
                             Ok(()) => {
                                 success_count += 1;
 
@@ -398,7 +587,7 @@ impl UndoManager {
                 for op in operations {
                     if let Some(backup) = &op.backup_path {
                         if backup.exists() {
-                            fs::rename(backup, &op.path)?;
+                            move_file_sync(backup, &op.path)?;
                             restored_count += 1;
                         }
                     }
@@ -416,7 +605,7 @@ impl UndoManager {
                     match op {
                         FileOperation::Move(move_op) => {
                             if move_op.destination.exists() {
-                                match fs::rename(&move_op.destination, &move_op.source) {
+                                match move_file_sync(&move_op.destination, &move_op.source) {
                                     Ok(()) => {
                                         success_count += 1;
 
@@ -447,7 +636,7 @@ impl UndoManager {
                         FileOperation::Delete(del_op) => {
                             if let Some(backup) = &del_op.backup_path {
                                 if backup.exists() {
-                                    match fs::rename(backup, &del_op.path) {
+                                    match move_file_sync(backup, &del_op.path) {
                                         Ok(()) => success_count += 1,
                                         Err(e) => errors.push(format!("{}: {}", del_op.path.display(), e)),
                                     }
@@ -468,6 +657,24 @@ impl UndoManager {
                     Ok(format!("Undid {} operations ({} errors)", success_count, errors.len()))
                 }
             }
+
+            OperationType::BatchRemoveDirs { directories } => {
+                // Parents were removed last, so recreate them first
+                for dir in directories.iter().rev() {
+                    fs::create_dir_all(dir)?;
+                }
+
+                Ok(format!("Restored {} empty folders", directories.len()))
+            }
+
+            OperationType::TimestampChange { changes } => {
+                let restored = changes
+                    .iter()
+                    .filter(|change| crate::date_fix::set_timestamp(&change.path, change.field, change.before).is_ok())
+                    .count();
+
+                Ok(format!("Restored dates of {restored} of {} files", changes.len()))
+            }
         }
     }
 
@@ -478,7 +685,7 @@ impl UndoManager {
         match &operation.operation {
             OperationType::Move { source, destination } => {
                 if source.exists() {
-                    fs::rename(source, destination)?;
+                    move_file_sync(source, destination)?;
                     Ok(format!("Redid move to {}", destination.display()))
                 } else {
                     Err(VisualVaultError::UndoError {
@@ -520,7 +727,7 @@ impl UndoManager {
 
                 for op in operations {
                     if op.source.exists() {
-                        match fs::rename(&op.source, &op.destination) {
+                        match move_file_sync(&op.source, &op.destination) {
                             Ok(()) => success_count += 1,
                             Err(e) => errors.push(format!("{}: {}", op.source.display(), e)),
                         }
@@ -570,7 +777,7 @@ impl UndoManager {
                                     fs::create_dir_all(parent)?;
                                 }
 
-                                match fs::rename(&move_op.source, &move_op.destination) {
+                                match move_file_sync(&move_op.source, &move_op.destination) {
                                     Ok(()) => success_count += 1,
                                     Err(e) => errors.push(format!("{}: {}", move_op.source.display(), e)),
                                 }
@@ -610,6 +817,22 @@ impl UndoManager {
                     Ok(format!("Redid {} operations ({} errors)", success_count, errors.len()))
                 }
             }
+
+            OperationType::BatchRemoveDirs { directories } => {
+                // Directories that gained files since are left alone
+                let removed = directories.iter().filter(|dir| fs::remove_dir(dir).is_ok()).count();
+
+                Ok(format!("Redid removal of {removed} empty folders"))
+            }
+
+            OperationType::TimestampChange { changes } => {
+                let fixed = changes
+                    .iter()
+                    .filter(|change| crate::date_fix::set_timestamp(&change.path, change.field, change.after).is_ok())
+                    .count();
+
+                Ok(format!("Redid date fixes of {fixed} of {} files", changes.len()))
+            }
         }
     }
 
@@ -631,59 +854,94 @@ impl UndoManager {
             .collect()
     }
 
-    /// Save history to disk
-    #[allow(clippy::unwrap_used)]
-    async fn save_history(&self) -> Result<()> {
-        let history_file = self.config_dir.join("visualvault").join(UNDO_HISTORY_FILE);
-        if !history_file.exists() {
-            let parent = history_file.parent().ok_or_else(|| VisualVaultError::UndoError {
-                message: format!(
-                    "SAVE HISTORY FAILURE: could not get parent path for {}",
-                    history_file.display()
-                ),
-            })?;
-            fs::create_dir_all(parent)?;
-            fs::File::create(&history_file)?;
-        }
-        let history: Vec<UndoableOperation> = self.history.read().await.iter().cloned().collect();
-
-        let json = serde_json::to_string_pretty(&history)?;
-        fs::write(history_file, json)?;
+    fn history_file(&self) -> PathBuf {
+        self.config_dir.join("visualvault").join(UNDO_HISTORY_FILE)
+    }
 
-        Ok(())
+    /// Appends `entry` to the history file, or rewrites the file from `history` once most of
+    /// its entries are stale. Called with the history lock held so entries keep their order.
+    fn persist(&self, history: &VecDeque<UndoableOperation>, entry: JournalEntry) -> Result<()> {
+        let history_file = self.history_file();
+        let entries = self.journal_entries.fetch_add(1, Ordering::Relaxed) + 1;
+        if entries > history.len().max(MIN_COMPACT_ENTRIES) * 2 {
+            undo_journal::write_snapshot(&history_file, history)?;
+            self.journal_entries.store(history.len(), Ordering::Relaxed);
+            Ok(())
+        } else {
+            undo_journal::append(&history_file, &[entry])
+        }
     }
 
-    /// Load history from disk
+    /// Load history from disk, converting the JSON history of earlier versions
     async fn load_history(&mut self) -> Result<()> {
-        let history_file = self.config_dir.join("visualvault").join(UNDO_HISTORY_FILE);
+        let history_file = self.history_file();
+        let legacy_file = self.config_dir.join("visualvault").join(LEGACY_UNDO_HISTORY_FILE);
+        let mut history = self.history.write().await;
 
         if history_file.exists() {
-            let json = fs::read_to_string(history_file)?;
+            let journal = undo_journal::load(&history_file, MAX_UNDO_HISTORY)?;
+            history.extend(journal.operations);
+            if journal.truncated || journal.entries > history.len().max(MIN_COMPACT_ENTRIES) * 2 {
+                // Rewrite the file so new entries are not appended after an unreadable one
+                undo_journal::write_snapshot(&history_file, history.iter())?;
+                self.journal_entries.store(history.len(), Ordering::Relaxed);
+            } else {
+                self.journal_entries.store(journal.entries, Ordering::Relaxed);
+            }
+        } else if legacy_file.exists() {
+            let json = fs::read_to_string(&legacy_file)?;
             let operations: Vec<UndoableOperation> = serde_json::from_str(&json)?;
-
-            let mut history = self.history.write().await;
             history.extend(operations);
 
             // Maintain max size
             while history.len() > MAX_UNDO_HISTORY {
-                history.pop_front();
+                if let Some(evicted) = history.pop_front() {
+                    remove_backups(&evicted);
+                }
             }
-            drop(history);
+
+            undo_journal::write_snapshot(&history_file, history.iter())?;
+            self.journal_entries.store(history.len(), Ordering::Relaxed);
+            let backup = legacy_file.with_extension("json.bak");
+            fs::rename(&legacy_file, &backup)?;
+            tracing::info!(
+                "Converted the undo history to {}, the old file is kept as {}",
+                history_file.display(),
+                backup.display()
+            );
         }
 
+        let cutoff = Utc::now() - chrono::Duration::days(BACKUP_RETENTION_DAYS);
+        history
+            .iter()
+            .filter(|op| op.timestamp < cutoff)
+            .for_each(remove_backups);
+        drop(history);
+
         Ok(())
     }
 }
 
+/// Removes the backups kept for undoing `operation`, which can then no longer restore those files
+fn remove_backups(operation: &UndoableOperation) {
+    for backup in operation.operation.backup_paths() {
+        match fs::remove_file(backup) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                tracing::warn!("Failed to remove the undo backup {}: {}", backup.display(), e);
+            }
+            _ => {}
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #![allow(clippy::unwrap_used)]
     #![allow(clippy::expect_used)]
     #![allow(clippy::float_cmp)] // For comparing floats in tests
     #![allow(clippy::panic)]
-    #![allow(clippy::unwrap_used)]
-    #![allow(clippy::expect_used)]
     #![allow(clippy::panic_in_result_fn)]
+    #![allow(clippy::unwrap_in_result)]
 
     use super::*;
     use tempfile::TempDir;
@@ -1011,6 +1269,78 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_old_backups_are_removed_on_load() -> Result<()> {
+        let (manager, temp_dir) = create_test_manager().await?;
+        let backup_dir = manager.backup_dir();
+        fs::create_dir_all(&backup_dir).await?;
+
+        for (name, age_days) in [("old.jpg", BACKUP_RETENTION_DAYS + 1), ("recent.jpg", 1)] {
+            let backup = backup_dir.join(name);
+            fs::write(&backup, "replaced").await?;
+            let mut operation = UndoableOperation::new(
+                OperationType::Delete {
+                    path: temp_dir.path().join(name),
+                    backup_path: Some(backup),
+                },
+                format!("Replace {name}"),
+            );
+            operation.timestamp = Utc::now() - chrono::Duration::days(age_days);
+            manager.record_operation(operation).await?;
+        }
+
+        let manager = UndoManager::new_with_history(temp_dir.path().join("config")).await?;
+        assert_eq!(manager.get_history().await.len(), 2);
+        assert!(!backup_dir.join("old.jpg").exists());
+        assert!(backup_dir.join("recent.jpg").exists());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_converts_json_history() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let config_dir = temp_dir.path().join("config");
+        let history_dir = config_dir.join("visualvault");
+        fs::create_dir_all(&history_dir).await?;
+
+        let mut undone = UndoableOperation::new(
+            OperationType::Move {
+                source: PathBuf::from("/a.jpg"),
+                destination: PathBuf::from("/b.jpg"),
+            },
+            "Moved a.jpg".to_string(),
+        );
+        undone.undone = true;
+        let operations = vec![
+            UndoableOperation::new(
+                OperationType::BatchMove { operations: vec![] },
+                "Moved nothing".to_string(),
+            ),
+            undone,
+        ];
+        fs::write(
+            history_dir.join(LEGACY_UNDO_HISTORY_FILE),
+            serde_json::to_string_pretty(&operations)?,
+        )
+        .await?;
+
+        let manager = UndoManager::new_with_history(config_dir.clone()).await?;
+        assert_eq!(manager.get_history().await.len(), 2);
+        assert!(history_dir.join(UNDO_HISTORY_FILE).exists());
+        assert!(!history_dir.join(LEGACY_UNDO_HISTORY_FILE).exists());
+        assert!(history_dir.join("undo_history.json.bak").exists());
+
+        manager.record_remove_dirs(vec![PathBuf::from("/empty")]).await?;
+        drop(manager);
+        let history = UndoManager::new_with_history(config_dir).await?.get_history().await;
+        assert_eq!(history.len(), 3);
+        assert!(history[1].undone);
+        assert_eq!(history[2].description, "Removed 1 empty folders");
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_get_undoable_operations() -> Result<()> {
         let (manager, temp_dir) = create_test_manager().await?;
@@ -1119,6 +1449,115 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_undo_and_redo_remove_dirs() -> Result<()> {
+        let (manager, temp_dir) = create_test_manager().await?;
+        let nested = temp_dir.path().join("source").join("2023").join("raw");
+        let parent = nested.parent().unwrap().to_path_buf();
+        fs::create_dir_all(&nested).await?;
+
+        let removed = crate::remove_empty_directories(&[nested.clone(), parent.clone()]);
+        assert_eq!(removed.len(), 2);
+        manager.record_remove_dirs(removed).await?;
+        assert!(!parent.exists());
+
+        let message = manager.undo().await?.unwrap();
+        assert!(message.contains("Restored 2 empty folders"));
+        assert!(nested.is_dir());
+
+        // A folder that gained a file is kept on redo
+        fs::write(parent.join("new.jpg"), b"data").await?;
+        let message = manager.redo().await?.unwrap();
+        assert!(message.contains("removal of 1 empty folders"));
+        assert!(!nested.exists());
+        assert!(parent.exists());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_undo_and_redo_timestamp_changes() -> Result<()> {
+        let (manager, temp_dir) = create_test_manager().await?;
+        let path = create_test_file(temp_dir.path(), "photo.jpg", "content").await?;
+        let at = |day| {
+            chrono::NaiveDate::from_ymd_opt(2024, 3, day)
+                .unwrap()
+                .and_hms_opt(10, 0, 0)
+                .unwrap()
+        };
+        let modified = || chrono::DateTime::<chrono::Local>::from(path.metadata().unwrap().modified().unwrap());
+
+        crate::date_fix::set_timestamp(&path, TimestampField::Modified, at(15))?;
+        manager
+            .record_timestamp_changes(vec![TimestampOperation {
+                path: path.clone(),
+                field: TimestampField::Modified,
+                before: at(20),
+                after: at(15),
+            }])
+            .await?;
+
+        let message = manager.undo().await?.unwrap();
+        assert!(message.contains("Restored dates of 1 of 1 files"));
+        assert_eq!(modified().naive_local(), at(20));
+
+        manager.redo().await?;
+        assert_eq!(modified().naive_local(), at(15));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_undo_batch_rename_restores_names() -> Result<()> {
+        let (manager, temp_dir) = create_test_manager().await?;
+        let first = create_test_file(temp_dir.path(), "a.jpg", "a").await?;
+        let second = create_test_file(temp_dir.path(), "b.jpg", "b").await?;
+
+        let operations = vec![
+            MoveOperation {
+                source: first.clone(),
+                destination: temp_dir.path().join("trip_0001.jpg"),
+            },
+            MoveOperation {
+                source: second.clone(),
+                destination: temp_dir.path().join("trip_0002.jpg"),
+            },
+        ];
+        let applied = crate::apply_batch_rename(&operations);
+        manager.record_batch_rename(applied).await?;
+        assert!(!first.exists());
+
+        manager.undo().await?.unwrap();
+        assert!(first.exists());
+        assert!(second.exists());
+        assert!(temp_dir.path().exists());
+
+        manager.redo().await?.unwrap();
+        assert!(temp_dir.path().join("trip_0002.jpg").exists());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_undo_batch_copy_removes_copies() -> Result<()> {
+        let (manager, temp_dir) = create_test_manager().await?;
+        let original = create_test_file(temp_dir.path(), "a.jpg", "a").await?;
+        let folder = temp_dir.path().join("album");
+
+        let copied = crate::FileManager::copy_files(std::slice::from_ref(&original), &folder)?;
+        manager.record_batch_copy(copied).await?;
+        assert_eq!(manager.get_history().await[0].description, "Copied 1 files");
+
+        manager.undo().await?.unwrap();
+        assert!(original.exists());
+        assert!(!folder.join("a.jpg").exists());
+
+        manager.redo().await?.unwrap();
+        assert!(folder.join("a.jpg").exists());
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_undo_cleans_up_empty_directories() -> Result<()> {
         let (manager, temp_dir) = create_test_manager().await?;
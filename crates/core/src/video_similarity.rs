@@ -0,0 +1,281 @@
+use std::path::Path;
+use std::process::Stdio;
+use std::sync::Arc;
+
+use color_eyre::eyre::{Result, eyre};
+use smallvec::SmallVec;
+use tokio::process::Command;
+use tracing::{info, warn};
+use visualvault_config::VideoSimilarity;
+use visualvault_models::{DuplicateGroup, DuplicateStats, FileType, MediaFile};
+
+/// Sampled frames are scaled to this many grayscale pixels for their difference hash
+const FRAME_WIDTH: usize = 9;
+const FRAME_HEIGHT: usize = 8;
+
+/// Videos whose durations differ by more than this fraction are never compared
+const MAX_DURATION_DIFFERENCE: f64 = 0.02;
+
+/// Difference hashes of frames sampled evenly across a video
+#[derive(Debug, Clone, PartialEq)]
+struct VideoSignature {
+    duration: f64,
+    frames: Vec<u64>,
+}
+
+impl VideoSignature {
+    /// Fraction of matching bits between the frame hashes of both videos, from 0.0 to 1.0
+    #[allow(clippy::cast_precision_loss)]
+    fn similarity(&self, other: &Self) -> f64 {
+        if self.frames.is_empty() || self.frames.len() != other.frames.len() {
+            return 0.0;
+        }
+        let differing: u32 = self
+            .frames
+            .iter()
+            .zip(&other.frames)
+            .map(|(a, b)| (a ^ b).count_ones())
+            .sum();
+        1.0 - f64::from(differing) / (self.frames.len() * 64) as f64
+    }
+
+    fn duration_matches(&self, other: &Self) -> bool {
+        let longest = self.duration.max(other.duration);
+        longest <= 0.0 || (self.duration - other.duration).abs() / longest <= MAX_DURATION_DIFFERENCE
+    }
+}
+
+/// Groups videos that look alike even when their bytes differ, such as re-encoded or resized copies.
+///
+/// Samples `options.frames` frames of every video with `ffmpeg` and compares their perceptual
+/// hashes. Videos of about the same length whose frames match at least `options.threshold`
+/// percent end up in the same group, largest file first. Videos that cannot be decoded are skipped.
+///
+/// # Errors
+///
+/// Returns an error if `ffprobe` or `ffmpeg` cannot be run.
+pub async fn find_similar_videos(files: &[Arc<MediaFile>], options: VideoSimilarity) -> Result<DuplicateStats> {
+    let videos: Vec<_> = files.iter().filter(|file| file.file_type == FileType::Video).collect();
+    if videos.len() < 2 {
+        return Ok(DuplicateStats::new());
+    }
+    ensure_ffmpeg().await?;
+
+    let frames = options.frames.clamp(1, 64);
+    info!("Sampling {} frames of {} videos", frames, videos.len());
+    let mut signatures = Vec::with_capacity(videos.len());
+    for video in videos {
+        match video_signature(&video.path, frames).await {
+            Ok(signature) => signatures.push((Arc::clone(video), signature)),
+            Err(e) => warn!("Failed to sample frames of {:?}: {}", video.path, e),
+        }
+    }
+
+    let threshold = f64::from(options.threshold.min(100)) / 100.0;
+    let stats = build_stats(group_similar(signatures, threshold));
+    info!("Found {} groups of similar videos", stats.total_groups);
+    Ok(stats)
+}
+
+/// Checks that the ffmpeg tools are installed before sampling any video
+async fn ensure_ffmpeg() -> Result<()> {
+    for program in ["ffprobe", "ffmpeg"] {
+        Command::new(program)
+            .arg("-version")
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .await
+            .map_err(|_| eyre!("{program} was not found; install ffmpeg to compare videos"))?;
+    }
+    Ok(())
+}
+
+async fn video_signature(path: &Path, frames: usize) -> Result<VideoSignature> {
+    let duration = probe_duration(path).await?;
+
+    let mut hashes = Vec::with_capacity(frames);
+    for index in 0..frames {
+        #[allow(clippy::cast_precision_loss)]
+        let timestamp = duration * (index as f64 + 0.5) / frames as f64;
+        hashes.push(sample_frame(path, timestamp).await?);
+    }
+
+    Ok(VideoSignature {
+        duration,
+        frames: hashes,
+    })
+}
+
+/// Length of the video in seconds, as reported by `ffprobe`
+async fn probe_duration(path: &Path) -> Result<f64> {
+    let output = Command::new("ffprobe")
+        .args(["-v", "error", "-show_entries", "format=duration", "-of", "csv=p=0"])
+        .arg(path)
+        .stdin(Stdio::null())
+        .output()
+        .await?;
+    if !output.status.success() {
+        return Err(eyre!(
+            "ffprobe failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    let duration: f64 = String::from_utf8_lossy(&output.stdout).trim().parse()?;
+    if duration.is_finite() && duration > 0.0 {
+        Ok(duration)
+    } else {
+        Err(eyre!("Video has no duration"))
+    }
+}
+
+/// Difference hash of the frame shown `timestamp` seconds into the video
+async fn sample_frame(path: &Path, timestamp: f64) -> Result<u64> {
+    let output = Command::new("ffmpeg")
+        .args(["-v", "error", "-nostdin", "-ss", &format!("{timestamp:.3}"), "-i"])
+        .arg(path)
+        .args([
+            "-frames:v",
+            "1",
+            "-vf",
+            &format!("scale={FRAME_WIDTH}:{FRAME_HEIGHT}:flags=area,format=gray"),
+            "-f",
+            "rawvideo",
+            "-",
+        ])
+        .stdin(Stdio::null())
+        .output()
+        .await?;
+    if !output.status.success() {
+        return Err(eyre!(
+            "ffmpeg failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    difference_hash(&output.stdout).ok_or_else(|| eyre!("No frame at {timestamp:.1}s"))
+}
+
+/// 64-bit hash with one bit per pixel that is darker than its right neighbour
+fn difference_hash(pixels: &[u8]) -> Option<u64> {
+    if pixels.len() < FRAME_WIDTH * FRAME_HEIGHT {
+        return None;
+    }
+    let mut hash = 0u64;
+    for row in pixels.chunks_exact(FRAME_WIDTH).take(FRAME_HEIGHT) {
+        for pair in row.windows(2) {
+            hash = (hash << 1) | u64::from(pair[0] < pair[1]);
+        }
+    }
+    Some(hash)
+}
+
+/// Groups videos whose signature matches the first video of a group, comparing by length first
+fn group_similar(
+    mut signatures: Vec<(Arc<MediaFile>, VideoSignature)>,
+    threshold: f64,
+) -> Vec<SmallVec<[Arc<MediaFile>; 4]>> {
+    signatures.sort_by(|(_, a), (_, b)| a.duration.total_cmp(&b.duration));
+
+    let mut groups: Vec<(VideoSignature, SmallVec<[Arc<MediaFile>; 4]>)> = Vec::new();
+    for (file, signature) in signatures {
+        let matching = groups
+            .iter_mut()
+            .rev()
+            .find(|(first, _)| first.duration_matches(&signature) && first.similarity(&signature) >= threshold);
+        match matching {
+            Some((_, files)) => files.push(file),
+            None => groups.push((signature, SmallVec::from_elem(file, 1))),
+        }
+    }
+
+    groups
+        .into_iter()
+        .map(|(_, files)| files)
+        .filter(|files| files.len() > 1)
+        .collect()
+}
+
+/// Builds the stats of similar video groups, keeping the largest and likely best copy first
+fn build_stats(groups: Vec<SmallVec<[Arc<MediaFile>; 4]>>) -> DuplicateStats {
+    let mut stats = DuplicateStats::new();
+    for mut files in groups {
+        files.sort_by_key(|file| std::cmp::Reverse(file.size));
+        let wasted_space = files.iter().skip(1).map(|file| file.size).sum();
+        stats.total_duplicates += files.len() - 1;
+        stats.total_wasted_space += wasted_space;
+        stats.groups.push(DuplicateGroup::new(files, wasted_space));
+    }
+    stats.groups.sort_by_key(|group| std::cmp::Reverse(group.wasted_space));
+    stats.total_groups = stats.groups.len();
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+    use crate::test_support::media_file;
+
+    fn video(name: &str, size: u64) -> Arc<MediaFile> {
+        media_file(format!("/videos/{name}"))
+            .file_type(FileType::Video)
+            .size(size)
+            .arc()
+    }
+
+    fn signature(duration: f64, frames: &[u64]) -> VideoSignature {
+        VideoSignature {
+            duration,
+            frames: frames.to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_difference_hash() {
+        let rising: Vec<u8> = (0..72).map(|i| u8::try_from(i % 9).unwrap()).collect();
+        assert_eq!(difference_hash(&rising), Some(u64::MAX));
+        let falling: Vec<u8> = rising.iter().map(|p| 8 - p).collect();
+        assert_eq!(difference_hash(&falling), Some(0));
+        assert_eq!(difference_hash(&[0; 10]), None);
+    }
+
+    #[test]
+    fn test_similarity() {
+        let original = signature(60.0, &[0, u64::MAX]);
+        assert!((original.similarity(&original) - 1.0).abs() < f64::EPSILON);
+        // Four of 128 bits differ
+        let reencoded = signature(60.2, &[0b11, u64::MAX ^ 0b11]);
+        assert!((original.similarity(&reencoded) - 124.0 / 128.0).abs() < 1e-9);
+        assert!(original.duration_matches(&reencoded));
+        assert!(!original.duration_matches(&signature(90.0, &[0, u64::MAX])));
+        assert!(original.similarity(&signature(60.0, &[0])).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_group_similar_videos() {
+        let signatures = vec![
+            (video("original.mp4", 900), signature(60.0, &[0x0F0F, 0xF0F0])),
+            (video("other.mp4", 500), signature(60.5, &[!0x0F0F, !0xF0F0])),
+            (video("small.mp4", 300), signature(60.1, &[0x0F0F, 0xF0F1])),
+            (video("longer.mp4", 800), signature(120.0, &[0x0F0F, 0xF0F0])),
+        ];
+
+        let stats = build_stats(group_similar(signatures, 0.9));
+        assert_eq!(stats.total_groups, 1);
+        assert_eq!(stats.total_duplicates, 1);
+        assert_eq!(stats.total_wasted_space, 300);
+        let names: Vec<_> = stats.groups[0].files.iter().map(|f| f.name.to_string()).collect();
+        assert_eq!(names, ["original.mp4", "small.mp4"]);
+    }
+
+    #[tokio::test]
+    async fn test_fewer_than_two_videos_need_no_ffmpeg() {
+        let stats = find_similar_videos(&[video("only.mp4", 100)], VideoSimilarity::default())
+            .await
+            .unwrap();
+        assert!(stats.is_empty());
+    }
+}
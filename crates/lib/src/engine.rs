@@ -0,0 +1,314 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use color_eyre::eyre::{Result, eyre};
+use tokio::sync::{OnceCell, RwLock};
+use tracing::warn;
+use visualvault_config::Settings;
+use visualvault_core::{DatabaseCache, FileList, FileOrganizer, ImportRecord, Scanner};
+use visualvault_models::{DuplicateStats, FilterSet, MediaFile, OrganizeResult};
+use visualvault_utils::{Progress, create_cache_path};
+
+/// Scans, organizes and undoes with one cache and one undo history.
+///
+/// Every operation takes the [`Settings`] to run with and a [`Progress`] it updates while it
+/// runs, so callers can poll it from another task. Operations run one at a time.
+pub struct Engine {
+    scanner: Arc<Scanner>,
+    /// Folder holding the undo history and organized ledger, usually the user's config folder
+    config_dir: PathBuf,
+    organizer: OnceCell<Arc<FileOrganizer>>,
+}
+
+impl Engine {
+    /// Opens the engine with the scan cache the terminal UI uses.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache folder cannot be created or the cache cannot be opened.
+    pub async fn open(config_dir: PathBuf) -> Result<Self> {
+        let cache_path = create_cache_path("visualvault", "cache.db").await?;
+        let cache_path = cache_path
+            .to_str()
+            .ok_or_else(|| eyre!("Cache path is not valid UTF-8"))?;
+        Self::with_cache(config_dir, cache_path).await
+    }
+
+    /// Opens the engine with the scan cache at `cache_path`; `":memory:"` keeps it in memory.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache cannot be opened.
+    pub async fn with_cache(config_dir: PathBuf, cache_path: &str) -> Result<Self> {
+        Ok(Self {
+            scanner: Arc::new(Scanner::new(DatabaseCache::new(cache_path).await?)),
+            config_dir,
+            organizer: OnceCell::new(),
+        })
+    }
+
+    /// Builds the engine around a scanner and organizer the caller keeps using for everything
+    /// else, as the terminal UI does.
+    #[doc(hidden)]
+    #[must_use]
+    pub fn from_parts(config_dir: PathBuf, scanner: Arc<Scanner>, organizer: Arc<FileOrganizer>) -> Self {
+        Self {
+            scanner,
+            config_dir,
+            organizer: OnceCell::new_with(Some(organizer)),
+        }
+    }
+
+    /// Folder holding the undo history
+    #[must_use]
+    pub fn config_dir(&self) -> &Path {
+        &self.config_dir
+    }
+
+    /// Finds the media files in the source folder of `settings`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no source folder is set or it cannot be read, and
+    /// [`ScanCancelled`](crate::ScanCancelled) when [`Engine::cancel`] stops the scan.
+    pub async fn scan(&self, settings: &Settings, progress: Arc<RwLock<Progress>>) -> Result<Vec<Arc<MediaFile>>> {
+        let source = Self::source(settings)?;
+        self.scanner
+            .scan_directory(source, settings.recurse_subfolders, progress, settings, None)
+            .await
+    }
+
+    /// Finds the media files in `folder` and all folders below it, such as the destination
+    /// library, instead of the source folder.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the folder cannot be read, and
+    /// [`ScanCancelled`](crate::ScanCancelled) when [`Engine::cancel`] stops the scan.
+    pub async fn scan_folder(
+        &self,
+        folder: &Path,
+        settings: &Settings,
+        progress: Arc<RwLock<Progress>>,
+    ) -> Result<Vec<Arc<MediaFile>>> {
+        self.scanner
+            .scan_directory(folder, true, progress, settings, None)
+            .await
+    }
+
+    /// Finds the media files in the source folder of `settings` and which of them are
+    /// duplicates, hashing them with the configured algorithm.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no source folder is set, it cannot be read or hashing fails, and
+    /// [`ScanCancelled`](crate::ScanCancelled) when [`Engine::cancel`] stops the scan.
+    pub async fn find_duplicates(
+        &self,
+        settings: &Settings,
+        progress: Arc<RwLock<Progress>>,
+    ) -> Result<(Vec<Arc<MediaFile>>, DuplicateStats)> {
+        let source = Self::source(settings)?;
+        self.scanner
+            .scan_directory_with_duplicates(source, settings.recurse_subfolders, progress, settings, None)
+            .await
+    }
+
+    /// Finds the media files in the source folder of `settings` that match `filter_set`, adding
+    /// them to `files` as they are read, and which of them are duplicates.
+    ///
+    /// A [`FileList`] with a memory budget moves the files to disk once they outgrow it, so
+    /// scanning large folders takes no more memory than the budget allows.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no source folder is set, it cannot be read, hashing fails or the list
+    /// cannot be written, and [`ScanCancelled`](crate::ScanCancelled) when [`Engine::cancel`]
+    /// stops the scan.
+    pub async fn find_duplicates_into(
+        &self,
+        settings: &Settings,
+        filter_set: Option<FilterSet>,
+        files: &mut FileList,
+        progress: Arc<RwLock<Progress>>,
+    ) -> Result<DuplicateStats> {
+        let source = Self::source(settings)?;
+        self.scanner
+            .scan_with_duplicates_into(
+                source,
+                settings.recurse_subfolders,
+                progress,
+                settings,
+                filter_set,
+                files,
+            )
+            .await
+    }
+
+    /// Organizes `files` into the destination folder of `settings`, skipping the copies listed
+    /// in `duplicates` when settings ask for it.
+    ///
+    /// The run is recorded in the undo history, and in the import history unless it was rolled back.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no destination folder is set or organizing cannot start; files that
+    /// fail on their own are listed in the result instead.
+    pub async fn organize(
+        &self,
+        files: Vec<Arc<MediaFile>>,
+        duplicates: DuplicateStats,
+        settings: &Settings,
+        progress: Arc<RwLock<Progress>>,
+    ) -> Result<OrganizeResult> {
+        let result = self
+            .organizer()
+            .await?
+            .organize_files_with_duplicates(files, duplicates, settings, progress)
+            .await?;
+        if let Some(source) = settings.source_folder.as_deref().filter(|_| !result.rolled_back) {
+            if let Err(e) = self.scanner.record_import(&ImportRecord::new(source, &result)).await {
+                warn!("Failed to record the organize run in the import history: {}", e);
+            }
+        }
+        Ok(result)
+    }
+
+    /// Undoes the most recent operation that is not undone yet, returning what was undone, or
+    /// `None` when there is nothing to undo.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the undo history cannot be read or files cannot be moved back.
+    pub async fn undo(&self) -> Result<Option<String>> {
+        self.organizer().await?.undo_manager().undo().await
+    }
+
+    /// Redoes the most recently undone operation of this engine.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the files cannot be moved again.
+    pub async fn redo(&self) -> Result<Option<String>> {
+        self.organizer().await?.undo_manager().redo().await
+    }
+
+    /// Asks the running scan or organize run to stop; organizing finishes the file it is moving.
+    pub fn cancel(&self) {
+        self.scanner.cancel();
+        if let Some(organizer) = self.organizer.get() {
+            organizer.cancel();
+        }
+    }
+
+    /// The organizer, loading the undo history the first time it is needed
+    async fn organizer(&self) -> Result<&FileOrganizer> {
+        let organizer = self
+            .organizer
+            .get_or_try_init(|| async { FileOrganizer::new(self.config_dir.clone()).await.map(Arc::new) })
+            .await?;
+        Ok(organizer)
+    }
+
+    fn source(settings: &Settings) -> Result<&Path> {
+        settings
+            .source_folder
+            .as_deref()
+            .ok_or_else(|| eyre!("Source folder not configured"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::panic_in_result_fn)]
+    #![allow(clippy::unwrap_in_result)]
+
+    use super::*;
+    use tempfile::TempDir;
+    use visualvault_models::filters::ExtensionFilter;
+
+    /// Writes a small JPEG-named file, with content that tells copies apart
+    fn write_file(dir: &Path, name: &str, content: &[u8]) -> Result<()> {
+        std::fs::create_dir_all(dir)?;
+        std::fs::write(dir.join(name), content)?;
+        Ok(())
+    }
+
+    fn settings(root: &Path) -> Settings {
+        Settings {
+            source_folder: Some(root.join("source")),
+            destination_folder: Some(root.join("library")),
+            ..Settings::default()
+        }
+    }
+
+    fn progress() -> Arc<RwLock<Progress>> {
+        Arc::new(RwLock::new(Progress::new()))
+    }
+
+    #[tokio::test]
+    async fn test_scan_organize_and_undo() -> Result<()> {
+        let root = TempDir::new()?;
+        let source = root.path().join("source");
+        write_file(&source, "a.jpg", b"first photo")?;
+        write_file(&source, "b.jpg", b"second photo")?;
+        write_file(&source, "copy.jpg", b"first photo")?;
+        let settings = settings(root.path());
+        let engine = Engine::with_cache(root.path().join("config"), ":memory:").await?;
+
+        assert_eq!(engine.scan(&settings, progress()).await?.len(), 3);
+        let (files, duplicates) = engine.find_duplicates(&settings, progress()).await?;
+        assert_eq!(files.len(), 3);
+        assert_eq!(duplicates.total_groups, 1);
+
+        let result = engine.organize(files, duplicates, &settings, progress()).await?;
+        assert!(result.success);
+        assert_eq!(result.files_organized, 3);
+        assert!(!source.join("a.jpg").exists());
+
+        assert!(engine.undo().await?.is_some());
+        assert!(source.join("a.jpg").exists());
+        assert!(source.join("copy.jpg").exists());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_find_duplicates_into_pages_over_budget() -> Result<()> {
+        let root = TempDir::new()?;
+        let source = root.path().join("source");
+        write_file(&source, "a.jpg", b"first photo")?;
+        write_file(&source, "copy.jpg", b"first photo")?;
+        write_file(&source, "b.png", b"second photo")?;
+        let settings = settings(root.path());
+        let engine = Engine::with_cache(root.path().join("config"), ":memory:").await?;
+
+        let mut files = FileList::with_budget(Some(1), &root.path().join("files.db"));
+        let filters = FilterSet {
+            is_active: true,
+            extension_filters: vec![ExtensionFilter {
+                extensions: vec!["png".to_string()],
+                exclude: true,
+                enabled: true,
+            }],
+            ..FilterSet::default()
+        };
+        let duplicates = engine
+            .find_duplicates_into(&settings, Some(filters), &mut files, progress())
+            .await?;
+        assert!(files.is_paged());
+        assert_eq!(files.len(), 2);
+        assert_eq!(duplicates.total_groups, 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_missing_folders_are_errors() -> Result<()> {
+        let root = TempDir::new()?;
+        let engine = Engine::with_cache(root.path().join("config"), ":memory:").await?;
+        let settings = Settings::default();
+        assert!(engine.scan(&settings, progress()).await.is_err());
+        assert!(engine.find_duplicates(&settings, progress()).await.is_err());
+        assert!(engine.undo().await?.is_none());
+        Ok(())
+    }
+}
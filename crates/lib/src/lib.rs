@@ -0,0 +1,55 @@
+//! The `VisualVault` engine for programs that embed it.
+//!
+//! [`Engine`] scans folders for media files, finds duplicates, organizes files into the
+//! destination folder and undoes what it did, with the same settings, cache and undo history
+//! as the terminal UI. Nothing here depends on a terminal, so a GUI or another tool can drive
+//! the engine and show its [`Progress`] however it likes.
+//!
+//! ```no_run
+//! use std::sync::Arc;
+//! use tokio::sync::RwLock;
+//! use visualvault_lib::{Engine, Progress, Settings};
+//!
+//! # async fn run() -> color_eyre::Result<()> {
+//! let engine = Engine::open(std::env::temp_dir()).await?;
+//! let settings = Settings {
+//!     source_folder: Some("/photos/inbox".into()),
+//!     destination_folder: Some("/photos/library".into()),
+//!     ..Settings::default()
+//! };
+//! let progress = Arc::new(RwLock::new(Progress::new()));
+//! let (files, duplicates) = engine.find_duplicates(&settings, Arc::clone(&progress)).await?;
+//! let result = engine.organize(files, duplicates, &settings, progress).await?;
+//! println!("Organized {} of {} files", result.files_organized, result.files_total);
+//! # Ok(())
+//! # }
+//! ```
+
+mod engine;
+
+pub use engine::Engine;
+
+// Settings and the profiles they are stored in
+pub use visualvault_config::{CloudExport, Notifications, ProfileStore, Settings, TimezonePolicy};
+
+// Files, results and reports
+pub use visualvault_models::{
+    ActivityKind, CloudUploadReport, DuplicateStats, FileType, FilterSet, MediaFile, NotifiedOperation,
+    OperationReport, OrganizeResult, RemoteTransfer, SpaceProjection,
+};
+
+// Progress reporting and formatting
+pub use visualvault_utils::{Progress, STAGE_TRANSFER, format_bytes};
+
+// Scanned files paged from disk, and services around a run: history and notifications
+pub use visualvault_core::{ActivityLog, FileList, Notifier, ScanCancelled, project_space};
+
+/// The crates the engine is built from, for the terminal UI that ships with it, which needs far
+/// more of them than the facade above. Not a stable API: embed the engine through the facade.
+#[doc(hidden)]
+pub mod internals {
+    pub use visualvault_config as config;
+    pub use visualvault_core as core;
+    pub use visualvault_models as models;
+    pub use visualvault_utils as utils;
+}
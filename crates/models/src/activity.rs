@@ -0,0 +1,37 @@
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+/// What kind of event an activity log entry records
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ActivityKind {
+    Scan,
+    Organize,
+    Delete,
+    Rename,
+    Undo,
+    Redo,
+    Scheduled,
+    Verify,
+    Upload,
+    Error,
+}
+
+/// A single timestamped line in the activity log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActivityEntry {
+    pub timestamp: DateTime<Local>,
+    pub kind: ActivityKind,
+    pub message: String,
+}
+
+impl ActivityEntry {
+    #[must_use]
+    pub fn new(kind: ActivityKind, message: impl Into<String>) -> Self {
+        Self {
+            timestamp: Local::now(),
+            kind,
+            message: message.into(),
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use chrono::NaiveDateTime;
+use std::path::{Path, PathBuf};
+
+/// A media file inside a zip or tar archive found while scanning
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchiveMedia {
+    pub archive: PathBuf,
+    /// Path of the file inside the archive, with `/` separators
+    pub name: String,
+    pub size: u64,
+    /// Modification time recorded in the archive, in local time
+    pub modified: Option<NaiveDateTime>,
+}
+
+impl ArchiveMedia {
+    /// The file name without the folders inside the archive
+    #[must_use]
+    pub fn file_name(&self) -> &str {
+        self.name.rsplit('/').next().unwrap_or(&self.name)
+    }
+
+    /// Folder the archive is extracted into: next to it, named after it without its extensions
+    #[must_use]
+    pub fn extraction_folder(&self) -> PathBuf {
+        extraction_folder(&self.archive)
+    }
+}
+
+/// Folder an archive is extracted into: next to it, named after it without its extensions,
+/// e.g. `Trip.tar.gz` is extracted into `Trip`
+#[must_use]
+pub fn extraction_folder(archive: &Path) -> PathBuf {
+    let name = archive.file_name().unwrap_or_default().to_string_lossy();
+    let stem = name.split('.').find(|part| !part.is_empty()).unwrap_or("archive");
+    archive.with_file_name(stem)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extraction_folder() {
+        assert_eq!(
+            extraction_folder(Path::new("/photos/Trip.tar.gz")),
+            PathBuf::from("/photos/Trip")
+        );
+        assert_eq!(
+            extraction_folder(Path::new("/photos/export.zip")),
+            PathBuf::from("/photos/export")
+        );
+        assert_eq!(
+            extraction_folder(Path::new("/photos/.hidden.zip")),
+            PathBuf::from("/photos/hidden")
+        );
+    }
+}
@@ -0,0 +1,15 @@
+use std::path::PathBuf;
+
+/// State of the batch rename overlay on the dashboard's Files tab
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BatchRename {
+    /// Rename pattern as typed, using the same tokens as the organize rename template
+    pub pattern: String,
+    /// Planned renames as (current path, new path) for the files whose name changes
+    pub preview: Vec<(PathBuf, PathBuf)>,
+    /// Number of files the pattern is applied to
+    pub file_count: usize,
+    /// Why the pattern cannot be applied
+    pub error: Option<String>,
+    pub scroll: usize,
+}
@@ -0,0 +1,44 @@
+use std::fmt;
+
+/// An action applied to all files marked in the dashboard's Files tab
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BulkAction {
+    /// Move the files to the trash folder, from where undo restores them
+    Trash,
+    /// Move the files to a folder typed by the user
+    Move,
+    /// Add the files to the ignore list so scans skip them
+    Ignore,
+    /// Write the file list to a CSV file
+    Export,
+    /// Add the files to a named collection, creating it if needed
+    Collect,
+}
+
+impl BulkAction {
+    /// Whether the action asks for a path, or a collection name, before it runs
+    #[must_use]
+    pub const fn needs_path(self) -> bool {
+        matches!(self, Self::Move | Self::Export | Self::Collect)
+    }
+}
+
+impl fmt::Display for BulkAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Trash => write!(f, "Move to trash"),
+            Self::Move => write!(f, "Move to folder"),
+            Self::Ignore => write!(f, "Add to ignore list"),
+            Self::Export => write!(f, "Export list"),
+            Self::Collect => write!(f, "Add to collection"),
+        }
+    }
+}
+
+/// State of the bulk action menu for the marked files
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BulkActionMenu {
+    pub file_count: usize,
+    /// Action waiting for its path, with the path typed so far
+    pub pending: Option<(BulkAction, String)>,
+}
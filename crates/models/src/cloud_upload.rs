@@ -0,0 +1,36 @@
+use std::path::PathBuf;
+
+use serde::Serialize;
+
+/// A file that could not be uploaded to the cloud export bucket
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct UploadFailure {
+    pub path: PathBuf,
+    pub error: String,
+}
+
+/// Outcome of uploading organized files to the cloud export bucket
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct CloudUploadReport {
+    pub bucket: String,
+    /// Folder the object keys are relative to
+    pub root: PathBuf,
+    pub uploaded: usize,
+    /// Bytes of the uploaded files
+    pub bytes: u64,
+    pub failed: Vec<UploadFailure>,
+}
+
+impl CloudUploadReport {
+    /// Files the upload was asked for
+    #[must_use]
+    pub fn total(&self) -> usize {
+        self.uploaded + self.failed.len()
+    }
+
+    /// Paths of the files that failed, for uploading them again
+    #[must_use]
+    pub fn failed_paths(&self) -> Vec<PathBuf> {
+        self.failed.iter().map(|failure| failure.path.clone()).collect()
+    }
+}
@@ -0,0 +1,74 @@
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+/// A named, hand-picked group of files, such as an album
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Collection {
+    pub name: String,
+    /// Files in the order they were added
+    #[serde(default)]
+    pub files: Vec<PathBuf>,
+}
+
+impl Collection {
+    #[must_use]
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            files: Vec::new(),
+        }
+    }
+
+    /// Adds the files not in the collection yet and returns how many were added
+    pub fn add_files<'a>(&mut self, paths: impl IntoIterator<Item = &'a Path>) -> usize {
+        let before = self.files.len();
+        for path in paths {
+            if !self.contains(path) {
+                self.files.push(path.to_path_buf());
+            }
+        }
+        self.files.len() - before
+    }
+
+    #[must_use]
+    pub fn contains(&self, path: &Path) -> bool {
+        self.files.iter().any(|file| file == path)
+    }
+}
+
+/// What is being typed in the collections view
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CollectionInput {
+    /// Name of a new collection
+    NewName,
+    /// Folder the selected collection is copied to
+    ExportFolder,
+}
+
+/// State of the collections view
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CollectionsView {
+    pub selected: usize,
+    /// Selected file of the selected collection while browsing its files
+    pub selected_file: Option<usize>,
+    /// Text being typed, with what it is for
+    pub input: Option<(CollectionInput, String)>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_files_skips_members() {
+        let mut collection = Collection::new("Holiday");
+        let a = Path::new("/photos/a.jpg");
+        let b = Path::new("/photos/b.jpg");
+
+        assert_eq!(collection.add_files([a, b]), 2);
+        assert_eq!(collection.add_files([b, a]), 0);
+        assert!(collection.contains(a));
+        assert_eq!(collection.files, vec![a.to_path_buf(), b.to_path_buf()]);
+    }
+}
@@ -0,0 +1,242 @@
+use std::fmt;
+
+/// An action that can be run from the command palette
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaletteCommand {
+    Scan,
+    Organize,
+    UpdateFolderStats,
+    Settings,
+    Search,
+    Filters,
+    ToggleFilters,
+    Duplicates,
+    EmptyFolders,
+    Quarantine,
+    DateFix,
+    ManifestRestore,
+    Archives,
+    VerifyLibrary,
+    CloudUpload,
+    DeviceImport,
+    FileBrowser,
+    Collections,
+    Profiles,
+    ActivityLog,
+    OrganizeReport,
+    ExportOrganizeReport,
+    ErrorCenter,
+    ExportErrors,
+    Notifications,
+    Performance,
+    SpaceExplorer,
+    LogViewer,
+    RevealLogFile,
+    Undo,
+    Redo,
+    Help,
+    Quit,
+}
+
+impl PaletteCommand {
+    /// Every command, in the order the palette lists them before anything is typed
+    pub const ALL: [Self; 33] = [
+        Self::Scan,
+        Self::Organize,
+        Self::UpdateFolderStats,
+        Self::Settings,
+        Self::Search,
+        Self::Filters,
+        Self::ToggleFilters,
+        Self::Duplicates,
+        Self::EmptyFolders,
+        Self::Quarantine,
+        Self::DateFix,
+        Self::ManifestRestore,
+        Self::Archives,
+        Self::VerifyLibrary,
+        Self::CloudUpload,
+        Self::DeviceImport,
+        Self::FileBrowser,
+        Self::Collections,
+        Self::Profiles,
+        Self::ActivityLog,
+        Self::OrganizeReport,
+        Self::ExportOrganizeReport,
+        Self::ErrorCenter,
+        Self::ExportErrors,
+        Self::Notifications,
+        Self::Performance,
+        Self::SpaceExplorer,
+        Self::LogViewer,
+        Self::RevealLogFile,
+        Self::Undo,
+        Self::Redo,
+        Self::Help,
+        Self::Quit,
+    ];
+
+    /// The key that runs the command outside the palette, if it has one
+    #[must_use]
+    pub const fn key(self) -> &'static str {
+        match self {
+            Self::Scan => "r",
+            Self::Organize => "o",
+            Self::UpdateFolderStats => "u",
+            Self::Settings => "s",
+            Self::Search => "f",
+            Self::Filters => "F",
+            Self::ToggleFilters => "F, t",
+            Self::Duplicates => "D",
+            Self::EmptyFolders => "E",
+            Self::Quarantine => "Q",
+            Self::DateFix => "T",
+            Self::ManifestRestore => "R",
+            Self::Archives => "Z",
+            Self::VerifyLibrary => "I",
+            Self::CloudUpload => "U",
+            Self::DeviceImport => "M",
+            Self::FileBrowser => "B",
+            Self::Collections => "C",
+            Self::Profiles => "P",
+            Self::ActivityLog => "L",
+            Self::OrganizeReport => "O",
+            Self::ExportOrganizeReport => "O, e",
+            Self::ErrorCenter => "!",
+            Self::ExportErrors => "!, e",
+            Self::Notifications => "N",
+            Self::Performance => "W",
+            Self::SpaceExplorer => "H",
+            Self::LogViewer => "Ctrl+L",
+            Self::RevealLogFile => "G",
+            Self::Undo => "Ctrl+U",
+            Self::Redo => "Ctrl+R",
+            Self::Help => "?",
+            Self::Quit => "q",
+        }
+    }
+}
+
+impl fmt::Display for PaletteCommand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            Self::Scan => "Scan source folder",
+            Self::Organize => "Organize files",
+            Self::UpdateFolderStats => "Update folder statistics",
+            Self::Settings => "Open settings",
+            Self::Search => "Search files",
+            Self::Filters => "Edit filters",
+            Self::ToggleFilters => "Toggle filters on or off",
+            Self::Duplicates => "Review duplicates",
+            Self::EmptyFolders => "Clean up empty folders",
+            Self::Quarantine => "Review suspect files",
+            Self::DateFix => "Fix photo dates",
+            Self::ManifestRestore => "Restore from manifest",
+            Self::Archives => "Review media in archives",
+            Self::VerifyLibrary => "Verify library integrity",
+            Self::CloudUpload => "Show cloud upload",
+            Self::DeviceImport => "Import from a device",
+            Self::FileBrowser => "Browse folders",
+            Self::Collections => "Open collections",
+            Self::Profiles => "Switch profile",
+            Self::ActivityLog => "Show activity log",
+            Self::OrganizeReport => "Show organize report",
+            Self::ExportOrganizeReport => "Export organize report as CSV",
+            Self::ErrorCenter => "Show errors",
+            Self::ExportErrors => "Export errors as CSV",
+            Self::Notifications => "Show notification history",
+            Self::Performance => "Show performance timings",
+            Self::SpaceExplorer => "Explore disk space",
+            Self::LogViewer => "Show log",
+            Self::RevealLogFile => "Reveal log file",
+            Self::Undo => "Undo last operation",
+            Self::Redo => "Redo last undone operation",
+            Self::Help => "Show help",
+            Self::Quit => "Quit",
+        };
+        f.write_str(label)
+    }
+}
+
+/// State of the command palette overlay
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CommandPalette {
+    pub query: String,
+    /// Index into the matching commands
+    pub selected: usize,
+}
+
+impl CommandPalette {
+    /// Returns the commands whose name matches the query, best matches first
+    #[must_use]
+    pub fn matches(&self) -> Vec<PaletteCommand> {
+        let mut scored: Vec<(u32, PaletteCommand)> = PaletteCommand::ALL
+            .into_iter()
+            .filter_map(|command| fuzzy_score(&self.query, &command.to_string()).map(|score| (score, command)))
+            .collect();
+        // The sort is stable, so equally good matches keep their listed order
+        scored.sort_by_key(|&(score, _)| std::cmp::Reverse(score));
+        scored.into_iter().map(|(_, command)| command).collect()
+    }
+
+    /// The highlighted command, if any command matches
+    #[must_use]
+    pub fn selected_command(&self) -> Option<PaletteCommand> {
+        self.matches().get(self.selected).copied()
+    }
+}
+
+/// Scores how well `text` matches `query`, ignoring case, or returns `None` if the query's
+/// characters do not all appear in `text` in order.
+///
+/// Characters matched right after the previous one or at the start of a word score higher,
+/// so "org rep" prefers "Show organize report" over names merely containing those letters.
+fn fuzzy_score(query: &str, text: &str) -> Option<u32> {
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut position = 0;
+    let mut previous: Option<usize> = None;
+    for c in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let found = position + text[position..].iter().position(|&t| t == c)?;
+        score += 1;
+        if previous.is_some_and(|previous| previous + 1 == found) {
+            score += 3;
+        }
+        if found == 0 || text[found - 1] == ' ' {
+            score += 2;
+        }
+        previous = Some(found);
+        position = found + 1;
+    }
+    Some(score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fuzzy_score() {
+        assert_eq!(fuzzy_score("", "Quit"), Some(0));
+        assert!(fuzzy_score("qt", "Quit").is_some());
+        assert!(fuzzy_score("tq", "Quit").is_none());
+        assert!(fuzzy_score("SCAN", "Scan source folder").is_some());
+        assert!(fuzzy_score("sca", "Scan source folder") > fuzzy_score("sca", "Show errors as CSV"));
+    }
+
+    #[test]
+    fn test_matches_rank_best_first() {
+        let mut palette = CommandPalette::default();
+        assert_eq!(palette.matches().len(), PaletteCommand::ALL.len());
+        assert_eq!(palette.selected_command(), Some(PaletteCommand::Scan));
+
+        palette.query = "org rep".to_string();
+        let matches = palette.matches();
+        assert_eq!(matches[0], PaletteCommand::OrganizeReport);
+        assert!(matches.contains(&PaletteCommand::ExportOrganizeReport));
+        assert!(!matches.contains(&PaletteCommand::Quit));
+
+        palette.query = "zzz".to_string();
+        assert_eq!(palette.selected_command(), None);
+    }
+}
@@ -0,0 +1,74 @@
+use std::sync::Arc;
+
+use chrono::{FixedOffset, NaiveDateTime};
+
+use crate::MediaFile;
+
+/// Dates recorded in a photo's EXIF data, in the camera's local time
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ExifDates {
+    pub taken: Option<NaiveDateTime>,
+    pub digitized: Option<NaiveDateTime>,
+    pub modified: Option<NaiveDateTime>,
+    /// UTC offset of the camera's local time when the photo was taken, if it recorded one
+    pub offset: Option<FixedOffset>,
+}
+
+impl ExifDates {
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.taken.is_none() && self.digitized.is_none() && self.modified.is_none()
+    }
+}
+
+/// A small RGB rendition of an image, stored row by row
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Thumbnail {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<[u8; 3]>,
+}
+
+impl Thumbnail {
+    /// Returns the pixel at `x`, `y`, or black outside the image
+    #[must_use]
+    pub fn pixel(&self, x: u32, y: u32) -> [u8; 3] {
+        if x >= self.width || y >= self.height {
+            return [0; 3];
+        }
+        self.pixels
+            .get((y * self.width + x) as usize)
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
+/// One file of a duplicate group shown in the side-by-side compare view
+#[derive(Debug, Clone)]
+pub struct CompareEntry {
+    /// Position of the file within its duplicate group
+    pub index: usize,
+    pub file: Arc<MediaFile>,
+    pub dimensions: Option<(u32, u32)>,
+    pub exif: ExifDates,
+    pub thumbnail: Option<Thumbnail>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_thumbnail_pixel_bounds() {
+        let thumbnail = Thumbnail {
+            width: 2,
+            height: 1,
+            pixels: vec![[255, 0, 0], [0, 0, 255]],
+        };
+
+        assert_eq!(thumbnail.pixel(0, 0), [255, 0, 0]);
+        assert_eq!(thumbnail.pixel(1, 0), [0, 0, 255]);
+        assert_eq!(thumbnail.pixel(2, 0), [0; 3]);
+        assert_eq!(thumbnail.pixel(0, 1), [0; 3]);
+    }
+}
@@ -0,0 +1,41 @@
+use chrono::{DateTime, Local, NaiveDateTime, TimeDelta};
+use std::path::PathBuf;
+
+/// A photo whose modification time is far from the capture date in its EXIF data
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DateMismatch {
+    pub path: PathBuf,
+    pub modified: DateTime<Local>,
+    /// Capture date from the EXIF data, in the camera's local time
+    pub taken: NaiveDateTime,
+}
+
+impl DateMismatch {
+    /// How far apart the modification time and the capture date are
+    #[must_use]
+    pub fn difference(&self) -> TimeDelta {
+        (self.modified.naive_local() - self.taken).abs()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use chrono::{NaiveDate, TimeZone};
+
+    #[test]
+    fn test_difference() {
+        let taken = NaiveDate::from_ymd_opt(2024, 3, 15)
+            .unwrap()
+            .and_hms_opt(10, 0, 0)
+            .unwrap();
+        let mismatch = DateMismatch {
+            path: PathBuf::from("photo.jpg"),
+            modified: Local.with_ymd_and_hms(2024, 3, 12, 10, 0, 0).unwrap(),
+            taken,
+        };
+        assert_eq!(mismatch.difference(), TimeDelta::days(3));
+    }
+}
@@ -0,0 +1,35 @@
+use std::path::PathBuf;
+
+/// A mounted volume with a camera's `DCIM` folder, such as a memory card or a phone
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CameraVolume {
+    /// Volume label as shown by the system, taken from its mount point
+    pub name: String,
+    /// The `DCIM` folder holding the photos and videos
+    pub dcim: PathBuf,
+}
+
+/// State of the device import picker
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeviceImport {
+    pub volumes: Vec<CameraVolume>,
+    pub selected: usize,
+}
+
+impl DeviceImport {
+    #[must_use]
+    pub fn selected_volume(&self) -> Option<&CameraVolume> {
+        self.volumes.get(self.selected)
+    }
+}
+
+/// Outcome of copying the new media of a volume
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportSummary {
+    /// Files copied because they were not imported before
+    pub imported: usize,
+    /// Files skipped because an earlier import already copied them
+    pub skipped: usize,
+    /// Files that could not be read or copied
+    pub failed: usize,
+}
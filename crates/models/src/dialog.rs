@@ -0,0 +1,245 @@
+use crate::{BulkDuplicateAction, PreflightAction};
+
+/// What happens once a dialog is answered
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DialogAction {
+    /// Clean up every duplicate group
+    BulkDuplicates(BulkDuplicateAction),
+    /// Organize the files matching the active filters, or all scanned files
+    Organize,
+    /// Replace the settings being edited with the defaults
+    ResetSettings,
+    /// Quit while an operation is still running: wait for it, cancel it or quit right away
+    Quit,
+    /// Create a settings profile with the typed name
+    CreateProfile,
+    /// Replace unsaved edits with the settings file changed on disk
+    ReloadSettings,
+    /// Scan or organize despite the problems the preflight checks found
+    Preflight(PreflightAction),
+}
+
+/// The question a dialog asks
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DialogKind {
+    /// Yes or no
+    Confirm,
+    /// A line of text
+    Input { value: String },
+    /// One of several options
+    Choice { options: Vec<String> },
+}
+
+/// How a dialog was answered
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DialogOutcome {
+    Confirmed,
+    Input(String),
+    /// Index of the chosen option
+    Chose(usize),
+    Cancelled,
+}
+
+/// Part of a dialog that has the focus
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DialogFocus {
+    /// The text field of an input dialog
+    Field,
+    /// An option of a choice dialog
+    Option(usize),
+    /// The button answering yes, or accepting the typed text
+    Accept,
+    Cancel,
+}
+
+/// A modal dialog that takes every key until it is answered
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dialog {
+    pub action: DialogAction,
+    pub kind: DialogKind,
+    pub title: String,
+    pub message: String,
+    /// Whether accepting deletes or replaces something, shown with a warning
+    pub destructive: bool,
+    /// Index of the focused part, in the order of [`Dialog::focusables`]
+    pub focus: usize,
+}
+
+impl Dialog {
+    /// A dialog asking yes or no, with the focus on the cancel button
+    #[must_use]
+    pub fn confirm(action: DialogAction, title: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            action,
+            kind: DialogKind::Confirm,
+            title: title.into(),
+            message: message.into(),
+            destructive: false,
+            focus: 1,
+        }
+    }
+
+    /// A dialog asking for a line of text, starting with `value`
+    #[must_use]
+    pub fn input(
+        action: DialogAction,
+        title: impl Into<String>,
+        message: impl Into<String>,
+        value: impl Into<String>,
+    ) -> Self {
+        Self {
+            action,
+            kind: DialogKind::Input { value: value.into() },
+            title: title.into(),
+            message: message.into(),
+            destructive: false,
+            focus: 0,
+        }
+    }
+
+    /// A dialog asking to choose one of `options`, with the focus on the first one
+    #[must_use]
+    pub fn choice(
+        action: DialogAction,
+        title: impl Into<String>,
+        message: impl Into<String>,
+        options: Vec<String>,
+    ) -> Self {
+        Self {
+            action,
+            kind: DialogKind::Choice { options },
+            title: title.into(),
+            message: message.into(),
+            destructive: false,
+            focus: 0,
+        }
+    }
+
+    /// Marks accepting the dialog as deleting or replacing something
+    #[must_use]
+    pub const fn destructive(mut self) -> Self {
+        self.destructive = true;
+        self
+    }
+
+    /// The parts of the dialog that can have the focus, in the order Tab moves through them
+    #[must_use]
+    pub fn focusables(&self) -> Vec<DialogFocus> {
+        match &self.kind {
+            DialogKind::Confirm => vec![DialogFocus::Accept, DialogFocus::Cancel],
+            DialogKind::Input { .. } => vec![DialogFocus::Field, DialogFocus::Accept, DialogFocus::Cancel],
+            DialogKind::Choice { options } => (0..options.len())
+                .map(DialogFocus::Option)
+                .chain([DialogFocus::Cancel])
+                .collect(),
+        }
+    }
+
+    #[must_use]
+    pub fn focused(&self) -> DialogFocus {
+        self.focusables()
+            .get(self.focus)
+            .copied()
+            .unwrap_or(DialogFocus::Cancel)
+    }
+
+    pub fn focus_next(&mut self) {
+        self.focus = (self.focus + 1) % self.focusables().len();
+    }
+
+    pub fn focus_previous(&mut self) {
+        let count = self.focusables().len();
+        self.focus = (self.focus + count - 1) % count;
+    }
+
+    /// Whether keys are typed into the text field
+    #[must_use]
+    pub fn is_editing(&self) -> bool {
+        self.focused() == DialogFocus::Field
+    }
+
+    /// Types `c` into the text field, if it has the focus
+    pub fn type_char(&mut self, c: char) {
+        if let (DialogFocus::Field, DialogKind::Input { value }) = (self.focused(), &mut self.kind) {
+            value.push(c);
+        }
+    }
+
+    /// Removes the last character of the text field, if it has the focus
+    pub fn backspace(&mut self) {
+        if let (DialogFocus::Field, DialogKind::Input { value }) = (self.focused(), &mut self.kind) {
+            value.pop();
+        }
+    }
+
+    /// The answer given by activating the focused part.
+    ///
+    /// Returns `None` when accepting an input dialog whose text is blank.
+    #[must_use]
+    pub fn activate(&self) -> Option<DialogOutcome> {
+        match (self.focused(), &self.kind) {
+            (DialogFocus::Cancel, _) => Some(DialogOutcome::Cancelled),
+            (DialogFocus::Option(index), _) => Some(DialogOutcome::Chose(index)),
+            (DialogFocus::Field | DialogFocus::Accept, DialogKind::Input { value }) => {
+                let value = value.trim();
+                (!value.is_empty()).then(|| DialogOutcome::Input(value.to_string()))
+            }
+            (DialogFocus::Field | DialogFocus::Accept, _) => Some(DialogOutcome::Confirmed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_confirm_focus_wraps_and_starts_on_cancel() {
+        let mut dialog = Dialog::confirm(DialogAction::ResetSettings, "Reset", "Reset settings?");
+        assert_eq!(dialog.activate(), Some(DialogOutcome::Cancelled));
+
+        dialog.focus_next();
+        assert_eq!(dialog.focused(), DialogFocus::Accept);
+        assert_eq!(dialog.activate(), Some(DialogOutcome::Confirmed));
+        dialog.focus_previous();
+        assert_eq!(dialog.focused(), DialogFocus::Cancel);
+    }
+
+    #[test]
+    fn test_input_types_only_into_the_field() {
+        let mut dialog = Dialog::input(DialogAction::CreateProfile, "New profile", "Name:", "");
+        assert_eq!(dialog.activate(), None);
+
+        for c in " work ".chars() {
+            dialog.type_char(c);
+        }
+        dialog.backspace();
+        assert_eq!(dialog.activate(), Some(DialogOutcome::Input("work".to_string())));
+
+        dialog.focus_next();
+        dialog.type_char('x');
+        assert_eq!(dialog.focused(), DialogFocus::Accept);
+        assert_eq!(
+            dialog.kind,
+            DialogKind::Input {
+                value: " work".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_choice_lists_options_before_cancel() {
+        let mut dialog = Dialog::choice(
+            DialogAction::Organize,
+            "Organize",
+            "Which files?",
+            vec!["Matching".to_string(), "All".to_string()],
+        );
+        dialog.focus_next();
+        assert_eq!(dialog.activate(), Some(DialogOutcome::Chose(1)));
+        dialog.focus_next();
+        assert_eq!(dialog.activate(), Some(DialogOutcome::Cancelled));
+        dialog.focus_next();
+        assert_eq!(dialog.focused(), DialogFocus::Option(0));
+    }
+}
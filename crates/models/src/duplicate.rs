@@ -1,3 +1,6 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::path::PathBuf;
 use std::sync::Arc;
 
 use smallvec::SmallVec;
@@ -19,6 +22,75 @@ impl DuplicateGroup {
             wasted_space,
         }
     }
+
+    /// Whether a file's name or folder contains `term`, which must be lowercase
+    #[must_use]
+    pub fn matches(&self, term: &str) -> bool {
+        self.files
+            .iter()
+            .any(|file| file.path.to_string_lossy().to_lowercase().contains(term))
+    }
+}
+
+/// Order of the groups in the duplicate review
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DuplicateSort {
+    /// The order the groups were found in
+    #[default]
+    Found,
+    /// Most wasted space first
+    WastedSpace,
+    /// Most files first
+    GroupSize,
+}
+
+impl DuplicateSort {
+    #[must_use]
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Found => Self::WastedSpace,
+            Self::WastedSpace => Self::GroupSize,
+            Self::GroupSize => Self::Found,
+        }
+    }
+}
+
+impl fmt::Display for DuplicateSort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Found => write!(f, "scan order"),
+            Self::WastedSpace => write!(f, "wasted space"),
+            Self::GroupSize => write!(f, "group size"),
+        }
+    }
+}
+
+/// How a duplicate is replaced by a link to the copy that is kept
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkMethod {
+    /// A copy-on-write clone sharing the storage of the kept copy until either is changed,
+    /// on file systems such as Btrfs, XFS and APFS
+    Reflink,
+    /// Another name for the kept copy; changing one changes both
+    Hardlink,
+}
+
+impl fmt::Display for LinkMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Reflink => write!(f, "reflink"),
+            Self::Hardlink => write!(f, "hardlink"),
+        }
+    }
+}
+
+/// A cleanup of every duplicate group, waiting to be confirmed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BulkDuplicateAction {
+    /// Delete all but the first file of each group
+    Delete,
+    /// Replace all but the first file of each group with links to it
+    Link,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -27,6 +99,12 @@ pub struct DuplicateStats {
     pub total_duplicates: usize,
     pub total_wasted_space: u64,
     pub groups: Vec<DuplicateGroup>,
+    /// Files whose full hash was reused from the persistent hash cache
+    pub hash_cache_hits: usize,
+    /// Files that had to be read and hashed
+    pub hash_cache_misses: usize,
+    /// Bytes of the files that had to be read and hashed
+    pub hashed_bytes: u64,
 }
 
 impl DuplicateStats {
@@ -60,4 +138,129 @@ impl DuplicateStats {
     pub fn total_files(&self) -> usize {
         self.groups.iter().map(|g| g.files.len()).sum()
     }
+
+    /// The groups with a file whose name or folder contains `filter`, ignoring case, in the
+    /// order of `sort`, with the totals counted over those groups
+    #[must_use]
+    pub fn view(&self, filter: &str, sort: DuplicateSort) -> Self {
+        let filter = filter.trim().to_lowercase();
+        let mut groups: Vec<DuplicateGroup> = self
+            .groups
+            .iter()
+            .filter(|group| filter.is_empty() || group.matches(&filter))
+            .cloned()
+            .collect();
+        match sort {
+            DuplicateSort::Found => {}
+            DuplicateSort::WastedSpace => groups.sort_by_key(|group| std::cmp::Reverse(group.wasted_space)),
+            DuplicateSort::GroupSize => groups.sort_by_key(|group| std::cmp::Reverse(group.files.len())),
+        }
+        Self {
+            total_groups: groups.len(),
+            total_duplicates: groups.iter().map(|group| group.files.len().saturating_sub(1)).sum(),
+            total_wasted_space: groups.iter().map(|group| group.wasted_space).sum(),
+            groups,
+            ..self.clone()
+        }
+    }
+
+    /// Takes files out of their groups, e.g. after they were replaced by links, dropping the
+    /// groups left with a single file and recounting the totals
+    pub fn remove_files(&mut self, paths: &HashSet<PathBuf>) {
+        for group in &mut self.groups {
+            let before = group.files.len();
+            group.files.retain(|file| !paths.contains(&file.path));
+            if group.files.len() < before && before > 1 {
+                let per_copy = group.wasted_space / (before as u64 - 1);
+                group.wasted_space = per_copy * group.files.len().saturating_sub(1) as u64;
+            }
+        }
+        self.groups.retain(|group| group.files.len() > 1);
+        self.total_groups = self.groups.len();
+        self.total_duplicates = self.groups.iter().map(|group| group.files.len() - 1).sum();
+        self.total_wasted_space = self.total_size();
+    }
+
+    /// Describes how many full hashes came from the cache, e.g. "12 of 15 hashes from cache"
+    #[must_use]
+    pub fn hash_cache_summary(&self) -> Option<String> {
+        let hashed = self.hash_cache_hits + self.hash_cache_misses;
+        (hashed > 0).then(|| format!("{} of {hashed} hashes from cache", self.hash_cache_hits))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FileType;
+    use chrono::Local;
+    use std::path::PathBuf;
+
+    fn group(paths: &[&str], wasted_space: u64) -> DuplicateGroup {
+        let files: Vec<Arc<MediaFile>> = paths
+            .iter()
+            .map(|path| {
+                Arc::new(MediaFile {
+                    path: PathBuf::from(path),
+                    name: Arc::from(path.rsplit('/').next().unwrap_or(path)),
+                    extension: Arc::from("jpg"),
+                    file_type: FileType::Image,
+                    size: wasted_space,
+                    created: Local::now(),
+                    modified: Local::now(),
+                    hash: None,
+                    metadata: None,
+                    via_symlink: false,
+                    live_photo_pair: None,
+                    content_extension: None,
+                })
+            })
+            .collect();
+        DuplicateGroup::new(files, wasted_space)
+    }
+
+    #[test]
+    fn test_remove_files() {
+        let mut stats = DuplicateStats {
+            groups: vec![
+                group(&["/photos/a.jpg", "/backup/a.jpg"], 10),
+                group(&["/photos/b.jpg", "/backup/b.jpg", "/old/b.jpg"], 10),
+            ],
+            ..DuplicateStats::default()
+        };
+
+        stats.remove_files(&HashSet::from([
+            PathBuf::from("/backup/a.jpg"),
+            PathBuf::from("/old/b.jpg"),
+        ]));
+        assert_eq!(stats.total_groups, 1);
+        assert_eq!(stats.total_duplicates, 1);
+        assert_eq!(stats.total_wasted_space, 5);
+        assert_eq!(stats.groups[0].files.len(), 2);
+    }
+
+    #[test]
+    fn test_view_filters_sorts_and_recounts() {
+        let stats = DuplicateStats {
+            groups: vec![
+                group(&["/photos/trip/a.jpg", "/backup/a.jpg"], 10),
+                group(&["/photos/home/b.jpg", "/backup/b.jpg", "/old/b.jpg"], 5),
+                group(&["/photos/trip/c.jpg", "/photos/trip/c copy.jpg"], 30),
+            ],
+            hash_cache_hits: 4,
+            ..DuplicateStats::default()
+        };
+
+        let trip = stats.view("TRIP", DuplicateSort::WastedSpace);
+        assert_eq!(trip.total_groups, 2);
+        assert_eq!(trip.total_duplicates, 2);
+        assert_eq!(trip.total_wasted_space, 40);
+        assert_eq!(trip.groups[0].wasted_space, 30);
+        assert_eq!(trip.hash_cache_hits, 4);
+
+        let by_size = stats.view(" ", DuplicateSort::GroupSize);
+        assert_eq!(by_size.total_groups, 3);
+        assert_eq!(by_size.groups[0].files.len(), 3);
+        assert_eq!(by_size.total_duplicates, 4);
+    }
 }
@@ -0,0 +1,202 @@
+use std::fmt;
+use std::path::PathBuf;
+
+use chrono::{DateTime, Local};
+
+/// Most errors kept in the error center; the oldest are dropped first
+pub const MAX_ERROR_ENTRIES: usize = 500;
+
+/// The kind of work an error came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorSource {
+    /// A file or folder could not be read while scanning
+    Scan,
+    /// A file was scanned, but its tags or EXIF data could not be parsed
+    Metadata,
+    /// A file could not be moved or copied into the destination
+    Organize,
+    /// A whole operation failed rather than a single file
+    Operation,
+}
+
+impl fmt::Display for ErrorSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Scan => write!(f, "scan"),
+            Self::Metadata => write!(f, "metadata"),
+            Self::Organize => write!(f, "organize"),
+            Self::Operation => write!(f, "operation"),
+        }
+    }
+}
+
+/// An error recorded for the error center, with the file it concerns if any
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErrorEntry {
+    pub timestamp: DateTime<Local>,
+    pub source: ErrorSource,
+    pub path: Option<PathBuf>,
+    pub message: String,
+}
+
+impl ErrorEntry {
+    #[must_use]
+    pub fn new(source: ErrorSource, path: Option<PathBuf>, message: impl Into<String>) -> Self {
+        Self {
+            timestamp: Local::now(),
+            source,
+            path,
+            message: message.into(),
+        }
+    }
+
+    /// Suggests how to fix the error, judged by the operating system error in the message
+    /// and otherwise by where the error came from
+    #[must_use]
+    pub fn remediation(&self) -> &'static str {
+        let message = self.message.to_lowercase();
+        if message.contains("permission denied") || message.contains("access is denied") {
+            "Check that you may read and write the file and its folder"
+        } else if message.contains("no space left") || message.contains("not enough space") {
+            "Free up space on the destination drive or pick another destination"
+        } else if message.contains("read-only file system") {
+            "The drive is mounted read-only; remount it writable or pick another folder"
+        } else if message.contains("file name too long") || message.contains("filename too long") {
+            "Shorten the file name or use a destination folder with a shorter path"
+        } else if message.contains("no such file") || message.contains("does not exist") {
+            "The file was moved or deleted after the scan; scan the source folder again"
+        } else {
+            match self.source {
+                ErrorSource::Scan => "Check that the file is readable, then scan again",
+                ErrorSource::Metadata => {
+                    "The file may be damaged or in an unsupported format; it is organized by its file dates"
+                }
+                ErrorSource::Organize => "Check the destination folder, then organize again",
+                ErrorSource::Operation => "Open the log viewer (Ctrl+L) for details",
+            }
+        }
+    }
+}
+
+/// Recent errors of scans, organize runs and other operations, newest first
+#[derive(Debug, Clone, Default)]
+pub struct ErrorCenter {
+    pub entries: Vec<ErrorEntry>,
+    pub selected: usize,
+}
+
+impl ErrorCenter {
+    /// Adds errors in the order they happened, dropping the oldest past [`MAX_ERROR_ENTRIES`]
+    pub fn record(&mut self, entries: impl IntoIterator<Item = ErrorEntry>) {
+        let mut added: Vec<ErrorEntry> = entries.into_iter().collect();
+        if added.is_empty() {
+            return;
+        }
+        added.reverse();
+        added.append(&mut self.entries);
+        added.truncate(MAX_ERROR_ENTRIES);
+        self.entries = added;
+        self.selected = 0;
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Number of errors that came from `source`
+    #[must_use]
+    pub fn count(&self, source: ErrorSource) -> usize {
+        self.entries.iter().filter(|entry| entry.source == source).count()
+    }
+
+    #[must_use]
+    pub fn selected_entry(&self) -> Option<&ErrorEntry> {
+        self.entries.get(self.selected)
+    }
+
+    pub fn move_selection(&mut self, delta: isize) {
+        self.selected = self
+            .selected
+            .saturating_add_signed(delta)
+            .min(self.entries.len().saturating_sub(1));
+    }
+
+    /// Removes the selected error, keeping the selection on the same row
+    pub fn dismiss_selected(&mut self) -> Option<ErrorEntry> {
+        if self.selected >= self.entries.len() {
+            return None;
+        }
+        let entry = self.entries.remove(self.selected);
+        self.move_selection(0);
+        Some(entry)
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.selected = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(source: ErrorSource, message: &str) -> ErrorEntry {
+        ErrorEntry::new(source, Some(PathBuf::from("/photos/a.jpg")), message)
+    }
+
+    #[test]
+    fn test_record_keeps_newest_first_within_limit() {
+        let mut center = ErrorCenter::default();
+        center.record([entry(ErrorSource::Scan, "first"), entry(ErrorSource::Scan, "second")]);
+        center.record([entry(ErrorSource::Organize, "third")]);
+        let messages: Vec<&str> = center.entries.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(messages, ["third", "second", "first"]);
+        assert_eq!(center.count(ErrorSource::Scan), 2);
+
+        center.record((0..MAX_ERROR_ENTRIES).map(|i| entry(ErrorSource::Metadata, &i.to_string())));
+        assert_eq!(center.len(), MAX_ERROR_ENTRIES);
+        assert_eq!(center.count(ErrorSource::Scan), 0);
+    }
+
+    #[test]
+    fn test_dismiss_selected() {
+        let mut center = ErrorCenter::default();
+        center.record([
+            entry(ErrorSource::Scan, "a"),
+            entry(ErrorSource::Scan, "b"),
+            entry(ErrorSource::Scan, "c"),
+        ]);
+        center.move_selection(10);
+        assert_eq!(center.selected, 2);
+        assert_eq!(center.dismiss_selected().map(|e| e.message), Some("a".to_string()));
+        assert_eq!(center.selected, 1);
+        center.clear();
+        assert!(center.dismiss_selected().is_none());
+    }
+
+    #[test]
+    fn test_remediation() {
+        assert!(
+            entry(ErrorSource::Organize, "Permission denied (os error 13)")
+                .remediation()
+                .contains("read and write")
+        );
+        assert!(
+            entry(ErrorSource::Organize, "No space left on device (os error 28)")
+                .remediation()
+                .contains("Free up space")
+        );
+        assert!(
+            entry(ErrorSource::Metadata, "unexpected end of tag")
+                .remediation()
+                .contains("damaged")
+        );
+    }
+}
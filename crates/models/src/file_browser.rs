@@ -0,0 +1,191 @@
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+/// A folder or file listed in a browser pane
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BrowserEntry {
+    pub path: PathBuf,
+    pub is_dir: bool,
+    /// Size in bytes, zero for folders
+    pub size: u64,
+}
+
+impl BrowserEntry {
+    #[must_use]
+    pub fn name(&self) -> String {
+        self.path.file_name().map_or_else(
+            || self.path.display().to_string(),
+            |name| name.to_string_lossy().to_string(),
+        )
+    }
+}
+
+/// One side of the two-pane file browser
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BrowserPane {
+    pub dir: PathBuf,
+    /// Folders first, then files, as listed by `dir`
+    pub entries: Vec<BrowserEntry>,
+    pub selected: usize,
+}
+
+impl BrowserPane {
+    #[must_use]
+    pub fn selected_entry(&self) -> Option<&BrowserEntry> {
+        self.entries.get(self.selected)
+    }
+
+    /// Replaces the listing, keeping `select` selected if it is still there
+    pub fn set_entries(&mut self, entries: Vec<BrowserEntry>, select: Option<&Path>) {
+        self.entries = entries;
+        self.selected = select
+            .and_then(|path| self.entries.iter().position(|entry| entry.path == path))
+            .unwrap_or_else(|| self.selected.min(self.entries.len().saturating_sub(1)));
+    }
+}
+
+/// Which pane of the file browser has focus
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BrowserSide {
+    #[default]
+    Left,
+    Right,
+}
+
+/// State of the two-pane file browser used to move and copy files by hand
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FileBrowser {
+    pub left: BrowserPane,
+    pub right: BrowserPane,
+    pub active: BrowserSide,
+    /// Files marked for the next move or copy, in either pane
+    pub marked: BTreeSet<PathBuf>,
+}
+
+impl FileBrowser {
+    #[must_use]
+    pub const fn active_pane(&self) -> &BrowserPane {
+        match self.active {
+            BrowserSide::Left => &self.left,
+            BrowserSide::Right => &self.right,
+        }
+    }
+
+    pub const fn active_pane_mut(&mut self) -> &mut BrowserPane {
+        match self.active {
+            BrowserSide::Left => &mut self.left,
+            BrowserSide::Right => &mut self.right,
+        }
+    }
+
+    /// The pane files are moved or copied into
+    #[must_use]
+    pub const fn other_pane(&self) -> &BrowserPane {
+        match self.active {
+            BrowserSide::Left => &self.right,
+            BrowserSide::Right => &self.left,
+        }
+    }
+
+    pub const fn switch_pane(&mut self) {
+        self.active = match self.active {
+            BrowserSide::Left => BrowserSide::Right,
+            BrowserSide::Right => BrowserSide::Left,
+        };
+    }
+
+    /// Marks or unmarks the selected file. Folders cannot be marked.
+    pub fn toggle_mark(&mut self) {
+        let Some(entry) = self.active_pane().selected_entry() else {
+            return;
+        };
+        if entry.is_dir {
+            return;
+        }
+        let path = entry.path.clone();
+        if !self.marked.remove(&path) {
+            self.marked.insert(path);
+        }
+    }
+
+    /// Files a move or copy applies to: the marked files listed in the active pane,
+    /// or the selected file when none of them are marked
+    #[must_use]
+    pub fn targets(&self) -> Vec<PathBuf> {
+        let pane = self.active_pane();
+        let marked: Vec<PathBuf> = pane
+            .entries
+            .iter()
+            .filter(|entry| self.marked.contains(&entry.path))
+            .map(|entry| entry.path.clone())
+            .collect();
+        if !marked.is_empty() {
+            return marked;
+        }
+        pane.selected_entry()
+            .filter(|entry| !entry.is_dir)
+            .map(|entry| vec![entry.path.clone()])
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(path: &str, is_dir: bool) -> BrowserEntry {
+        BrowserEntry {
+            path: PathBuf::from(path),
+            is_dir,
+            size: 0,
+        }
+    }
+
+    fn browser() -> FileBrowser {
+        FileBrowser {
+            left: BrowserPane {
+                dir: PathBuf::from("/photos"),
+                entries: vec![
+                    entry("/photos/2024", true),
+                    entry("/photos/a.jpg", false),
+                    entry("/photos/b.jpg", false),
+                ],
+                selected: 1,
+            },
+            right: BrowserPane {
+                dir: PathBuf::from("/album"),
+                ..BrowserPane::default()
+            },
+            ..FileBrowser::default()
+        }
+    }
+
+    #[test]
+    fn test_targets_prefer_marked_files() {
+        let mut browser = browser();
+        assert_eq!(browser.targets(), vec![PathBuf::from("/photos/a.jpg")]);
+
+        browser.left.selected = 2;
+        browser.toggle_mark();
+        browser.left.selected = 0;
+        browser.toggle_mark();
+        assert_eq!(browser.marked.len(), 1);
+        assert_eq!(browser.targets(), vec![PathBuf::from("/photos/b.jpg")]);
+
+        // Marks only apply to the pane they were made in
+        browser.switch_pane();
+        assert!(browser.targets().is_empty());
+        assert_eq!(browser.other_pane().dir, PathBuf::from("/photos"));
+    }
+
+    #[test]
+    fn test_set_entries_keeps_selection() {
+        let mut pane = browser().left;
+        let entries = vec![entry("/photos/b.jpg", false), entry("/photos/c.jpg", false)];
+        pane.set_entries(entries.clone(), Some(Path::new("/photos/c.jpg")));
+        assert_eq!(pane.selected, 1);
+
+        pane.set_entries(entries[..1].to_vec(), Some(Path::new("/photos/gone.jpg")));
+        assert_eq!(pane.selected, 0);
+    }
+}
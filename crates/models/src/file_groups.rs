@@ -0,0 +1,318 @@
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+
+use crate::MediaFile;
+
+/// What the Files tab groups the scanned files by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GroupBy {
+    Folder,
+    /// Month the file was created, newest first
+    Month,
+    Type,
+}
+
+impl GroupBy {
+    /// The grouping after this one, or `None` to go back to the flat list
+    #[must_use]
+    pub const fn next(self) -> Option<Self> {
+        match self {
+            Self::Folder => Some(Self::Month),
+            Self::Month => Some(Self::Type),
+            Self::Type => None,
+        }
+    }
+
+    /// Title of the group `file` belongs to
+    #[must_use]
+    pub fn key(self, file: &MediaFile) -> String {
+        match self {
+            Self::Folder => file
+                .path
+                .parent()
+                .map(|folder| folder.display().to_string())
+                .unwrap_or_default(),
+            Self::Month => file.created.format("%Y-%m").to_string(),
+            Self::Type => file.file_type.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for GroupBy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Folder => write!(f, "folder"),
+            Self::Month => write!(f, "month"),
+            Self::Type => write!(f, "type"),
+        }
+    }
+}
+
+/// Files sharing a folder, month or type
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileGroup {
+    pub key: String,
+    /// Indexes of the files in the file list, in list order
+    pub files: Vec<usize>,
+    pub size: u64,
+    pub expanded: bool,
+}
+
+/// Row of the grouped file list
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GroupRow {
+    /// Header of the group at this position
+    Header(usize),
+    /// File of a group, by its index in the file list
+    File { group: usize, index: usize },
+}
+
+/// The Files tab shown as collapsible groups.
+///
+/// Groups hold indexes into the file list, so they are rebuilt whenever the list changes;
+/// `version` is the version of the list they were built from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GroupedFiles {
+    pub by: GroupBy,
+    pub groups: Vec<FileGroup>,
+    pub version: u64,
+    /// Selected row, counting headers and the files of expanded groups
+    pub selected: usize,
+}
+
+impl GroupedFiles {
+    /// Groups `files`, given with their list indexes, with every group collapsed
+    pub fn new<'a>(by: GroupBy, files: impl IntoIterator<Item = (usize, &'a MediaFile)>, version: u64) -> Self {
+        Self::from_keys(
+            by,
+            files.into_iter().map(|(index, file)| (index, by.key(file), file.size)),
+            version,
+        )
+    }
+
+    /// Groups files given as list index, [`GroupBy::key`] and size, with every group collapsed
+    pub fn from_keys(by: GroupBy, files: impl IntoIterator<Item = (usize, String, u64)>, version: u64) -> Self {
+        let mut groups: BTreeMap<String, FileGroup> = BTreeMap::new();
+        for (index, key, size) in files {
+            let group = groups.entry(key).or_insert_with_key(|key| FileGroup {
+                key: key.clone(),
+                files: Vec::new(),
+                size: 0,
+                expanded: false,
+            });
+            group.files.push(index);
+            group.size += size;
+        }
+        let mut groups: Vec<FileGroup> = groups.into_values().collect();
+        if by == GroupBy::Month {
+            groups.reverse();
+        }
+        Self {
+            by,
+            groups,
+            version,
+            selected: 0,
+        }
+    }
+
+    /// Takes over which groups were expanded and which file was selected in `previous`
+    #[must_use]
+    pub fn keeping_state_of(mut self, previous: &Self, selected_file: Option<usize>) -> Self {
+        if previous.by != self.by {
+            return self;
+        }
+        let expanded: HashSet<&str> = previous
+            .groups
+            .iter()
+            .filter(|group| group.expanded)
+            .map(|group| group.key.as_str())
+            .collect();
+        for group in &mut self.groups {
+            group.expanded = expanded.contains(group.key.as_str());
+        }
+        self.selected = selected_file
+            .and_then(|index| self.row_of_file(index))
+            .unwrap_or_else(|| previous.selected.min(self.row_count().saturating_sub(1)));
+        self
+    }
+
+    /// Number of rows: one header per group and the files of expanded groups
+    #[must_use]
+    pub fn row_count(&self) -> usize {
+        self.groups
+            .iter()
+            .map(|group| 1 + if group.expanded { group.files.len() } else { 0 })
+            .sum()
+    }
+
+    /// What is shown in `row`
+    #[must_use]
+    pub fn row(&self, mut row: usize) -> Option<GroupRow> {
+        for (position, group) in self.groups.iter().enumerate() {
+            if row == 0 {
+                return Some(GroupRow::Header(position));
+            }
+            row -= 1;
+            if group.expanded {
+                if let Some(&index) = group.files.get(row) {
+                    return Some(GroupRow::File { group: position, index });
+                }
+                row -= group.files.len();
+            }
+        }
+        None
+    }
+
+    #[must_use]
+    pub fn selected_row(&self) -> Option<GroupRow> {
+        self.row(self.selected)
+    }
+
+    /// Position of the group holding the selected row
+    #[must_use]
+    pub fn selected_group(&self) -> Option<usize> {
+        match self.selected_row()? {
+            GroupRow::Header(position) | GroupRow::File { group: position, .. } => Some(position),
+        }
+    }
+
+    /// Row showing the file at list index `index`, if its group is expanded
+    #[must_use]
+    pub fn row_of_file(&self, index: usize) -> Option<usize> {
+        let mut row = 0;
+        for group in &self.groups {
+            row += 1;
+            if group.expanded {
+                if let Some(offset) = group.files.iter().position(|&file| file == index) {
+                    return Some(row + offset);
+                }
+                row += group.files.len();
+            }
+        }
+        None
+    }
+
+    /// Row of the header of the group at `position`
+    fn header_row(&self, position: usize) -> usize {
+        self.groups[..position]
+            .iter()
+            .map(|group| 1 + if group.expanded { group.files.len() } else { 0 })
+            .sum()
+    }
+
+    /// Expands the group holding the file at list index `index` and selects the file.
+    /// Returns `false` if no group holds it.
+    pub fn reveal(&mut self, index: usize) -> bool {
+        let Some(position) = self.groups.iter().position(|group| group.files.contains(&index)) else {
+            return false;
+        };
+        self.groups[position].expanded = true;
+        self.selected = self.row_of_file(index).unwrap_or_default();
+        true
+    }
+
+    /// Expands or collapses the selected group, selecting its header
+    pub fn set_expanded(&mut self, expanded: bool) {
+        let Some(position) = self.selected_group() else {
+            return;
+        };
+        self.groups[position].expanded = expanded;
+        self.selected = self.header_row(position);
+    }
+
+    /// Expands a collapsed selected group or collapses an expanded one
+    pub fn toggle(&mut self) {
+        if let Some(GroupRow::Header(position)) = self.selected_row() {
+            let expanded = self.groups[position].expanded;
+            self.set_expanded(!expanded);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+    use crate::FileType;
+    use chrono::{Local, TimeZone};
+    use std::path::PathBuf;
+    use std::sync::Arc;
+
+    fn file(path: &str, month: u32) -> MediaFile {
+        let created = Local.with_ymd_and_hms(2024, month, 10, 12, 0, 0).unwrap();
+        MediaFile {
+            path: PathBuf::from(path),
+            name: Arc::from(path.rsplit('/').next().unwrap()),
+            extension: Arc::from("jpg"),
+            file_type: FileType::Image,
+            size: 10,
+            created,
+            modified: created,
+            hash: None,
+            metadata: None,
+            via_symlink: false,
+            live_photo_pair: None,
+            content_extension: None,
+        }
+    }
+
+    fn files() -> Vec<MediaFile> {
+        vec![
+            file("/photos/b/1.jpg", 1),
+            file("/photos/a/2.jpg", 2),
+            file("/photos/b/3.jpg", 2),
+        ]
+    }
+
+    #[test]
+    fn test_groups_by_folder_and_month() {
+        let files = files();
+        let by_folder = GroupedFiles::new(GroupBy::Folder, files.iter().enumerate(), 1);
+        assert_eq!(by_folder.groups.len(), 2);
+        assert_eq!(by_folder.groups[1].key, "/photos/b");
+        assert_eq!(by_folder.groups[1].files, [0, 2]);
+        assert_eq!(by_folder.groups[1].size, 20);
+
+        let by_month = GroupedFiles::new(GroupBy::Month, files.iter().enumerate(), 1);
+        let keys: Vec<&str> = by_month.groups.iter().map(|group| group.key.as_str()).collect();
+        assert_eq!(keys, ["2024-02", "2024-01"]);
+    }
+
+    #[test]
+    fn test_rows_follow_expanded_groups() {
+        let files = files();
+        let mut grouped = GroupedFiles::new(GroupBy::Folder, files.iter().enumerate(), 1);
+        assert_eq!(grouped.row_count(), 2);
+
+        grouped.selected = 1;
+        grouped.toggle();
+        assert_eq!(grouped.row_count(), 4);
+        assert_eq!(grouped.row(2), Some(GroupRow::File { group: 1, index: 0 }));
+        assert_eq!(grouped.row(3), Some(GroupRow::File { group: 1, index: 2 }));
+        assert_eq!(grouped.row(4), None);
+        assert_eq!(grouped.row_of_file(2), Some(3));
+
+        // Collapsing from a file selects the group header
+        grouped.selected = 3;
+        grouped.set_expanded(false);
+        assert_eq!(grouped.selected, 1);
+        assert_eq!(grouped.row_count(), 2);
+
+        assert!(grouped.reveal(1));
+        assert_eq!(grouped.selected_row(), Some(GroupRow::File { group: 0, index: 1 }));
+        assert!(!grouped.reveal(7));
+    }
+
+    #[test]
+    fn test_rebuild_keeps_expanded_groups_and_selection() {
+        let files = files();
+        let mut previous = GroupedFiles::new(GroupBy::Folder, files.iter().enumerate(), 1);
+        previous.selected = 1;
+        previous.set_expanded(true);
+
+        let rebuilt =
+            GroupedFiles::new(GroupBy::Folder, files.iter().enumerate(), 2).keeping_state_of(&previous, Some(2));
+        assert!(rebuilt.groups[1].expanded);
+        assert_eq!(rebuilt.selected, 3);
+    }
+}
@@ -0,0 +1,151 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+use crate::MediaFile;
+
+/// Column of the dashboard file list the files can be sorted by, in column order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SortColumn {
+    Name,
+    Type,
+    Size,
+    Modified,
+}
+
+impl SortColumn {
+    pub const ALL: [Self; 4] = [Self::Name, Self::Type, Self::Size, Self::Modified];
+
+    /// The column after this one, wrapping around
+    #[must_use]
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Name => Self::Type,
+            Self::Type => Self::Size,
+            Self::Size => Self::Modified,
+            Self::Modified => Self::Name,
+        }
+    }
+}
+
+impl fmt::Display for SortColumn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Name => write!(f, "Name"),
+            Self::Type => write!(f, "Type"),
+            Self::Size => write!(f, "Size"),
+            Self::Modified => write!(f, "Modified"),
+        }
+    }
+}
+
+/// Order of the dashboard file list
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileSort {
+    pub column: SortColumn,
+    pub descending: bool,
+}
+
+impl FileSort {
+    #[must_use]
+    pub const fn ascending(column: SortColumn) -> Self {
+        Self {
+            column,
+            descending: false,
+        }
+    }
+
+    /// Sorts by `column`, or flips the direction if the list is already sorted by it
+    #[must_use]
+    pub fn toggled(current: Option<Self>, column: SortColumn) -> Self {
+        match current {
+            Some(sort) if sort.column == column => Self {
+                column,
+                descending: !sort.descending,
+            },
+            _ => Self::ascending(column),
+        }
+    }
+
+    /// Compares two files by the sort column, then by name ignoring case and by path, so the
+    /// order is the same on every run
+    #[must_use]
+    pub fn compare(&self, a: &MediaFile, b: &MediaFile) -> Ordering {
+        let by_column = match self.column {
+            SortColumn::Name => Ordering::Equal,
+            SortColumn::Type => a.file_type.to_string().cmp(&b.file_type.to_string()),
+            SortColumn::Size => a.size.cmp(&b.size),
+            SortColumn::Modified => a.modified.cmp(&b.modified),
+        };
+        let ordering = by_column
+            .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+            .then_with(|| a.path.cmp(&b.path));
+        if self.descending { ordering.reverse() } else { ordering }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FileType;
+    use chrono::{Duration, Local};
+    use std::path::PathBuf;
+    use std::sync::Arc;
+
+    fn file(name: &str, file_type: FileType, size: u64, age_days: i64) -> MediaFile {
+        let modified = Local::now() - Duration::days(age_days);
+        MediaFile {
+            path: PathBuf::from(format!("/photos/{name}")),
+            name: Arc::from(name),
+            extension: Arc::from(""),
+            file_type,
+            size,
+            created: modified,
+            modified,
+            hash: None,
+            metadata: None,
+            via_symlink: false,
+            live_photo_pair: None,
+            content_extension: None,
+        }
+    }
+
+    fn sorted(files: &[MediaFile], sort: FileSort) -> Vec<&str> {
+        let mut files: Vec<&MediaFile> = files.iter().collect();
+        files.sort_by(|a, b| sort.compare(a, b));
+        files.iter().map(|file| file.name.as_ref()).collect()
+    }
+
+    #[test]
+    fn test_sorts_by_each_column() {
+        let files = [
+            file("b.mp4", FileType::Video, 300, 1),
+            file("A.jpg", FileType::Image, 100, 3),
+            file("c.jpg", FileType::Image, 200, 2),
+        ];
+
+        assert_eq!(
+            sorted(&files, FileSort::ascending(SortColumn::Name)),
+            ["A.jpg", "b.mp4", "c.jpg"]
+        );
+        assert_eq!(
+            sorted(&files, FileSort::ascending(SortColumn::Type)),
+            ["A.jpg", "c.jpg", "b.mp4"]
+        );
+        assert_eq!(
+            sorted(&files, FileSort::ascending(SortColumn::Size)),
+            ["A.jpg", "c.jpg", "b.mp4"]
+        );
+        assert_eq!(
+            sorted(&files, FileSort::ascending(SortColumn::Modified)),
+            ["A.jpg", "c.jpg", "b.mp4"]
+        );
+
+        let descending = FileSort::toggled(Some(FileSort::ascending(SortColumn::Size)), SortColumn::Size);
+        assert!(descending.descending);
+        assert_eq!(sorted(&files, descending), ["b.mp4", "c.jpg", "A.jpg"]);
+        assert_eq!(
+            FileSort::toggled(Some(descending), SortColumn::Name),
+            FileSort::ascending(SortColumn::Name)
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use crate::MediaFile;
+use crate::{CaptureInfo, FileTags, MediaFile};
 use chrono::{DateTime, Local};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
@@ -11,6 +11,29 @@ pub struct FilterSet {
     pub media_types: Vec<MediaTypeFilter>,
     pub regex_patterns: Vec<RegexPattern>,
     pub is_active: bool,
+    #[serde(default)]
+    pub apply_to_organize: bool,
+    /// Tags a file must all carry to match
+    #[serde(default)]
+    pub required_tags: Vec<String>,
+    /// Fewest stars a file must be rated with to match
+    #[serde(default)]
+    pub min_rating: Option<u8>,
+    /// Extension lists a file must be in, or must not be in when they exclude
+    #[serde(default)]
+    pub extension_filters: Vec<ExtensionFilter>,
+    /// Whether a file must match every filter category or just one of them
+    #[serde(default)]
+    pub combinator: FilterCombinator,
+}
+
+/// Files of the file list matching the active filters
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FilterMatches {
+    /// Version of the file list that was counted
+    pub version: u64,
+    pub files: usize,
+    pub size: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -18,6 +41,9 @@ pub struct DateRange {
     pub from: Option<DateTime<Local>>,
     pub to: Option<DateTime<Local>>,
     pub name: String,
+    /// Files matching this filter are left out instead of kept
+    #[serde(default)]
+    pub negated: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -25,6 +51,9 @@ pub struct SizeRange {
     pub min_bytes: Option<u64>,
     pub max_bytes: Option<u64>,
     pub name: String,
+    /// Files matching this filter are left out instead of kept
+    #[serde(default)]
+    pub negated: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -32,6 +61,9 @@ pub struct MediaTypeFilter {
     pub media_type: MediaType,
     pub extensions: Vec<String>,
     pub enabled: bool,
+    /// Files matching this filter are left out instead of kept
+    #[serde(default)]
+    pub negated: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -40,6 +72,29 @@ pub struct RegexPattern {
     pub target: RegexTarget,
     pub case_sensitive: bool,
     pub enabled: bool,
+    /// Files matching this filter are left out instead of kept
+    #[serde(default)]
+    pub negated: bool,
+}
+
+/// How the filter categories (dates, sizes, media types, regex, extensions and tags) combine
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FilterCombinator {
+    /// A file must match every category that has filters
+    #[default]
+    All,
+    /// A file must match at least one category that has filters
+    Any,
+}
+
+/// A list of file extensions, such as "jpg,png,heic", that files must or must not have
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExtensionFilter {
+    /// Lowercase extensions without the leading dot
+    pub extensions: Vec<String>,
+    /// Files with one of the extensions are left out instead of kept
+    pub exclude: bool,
+    pub enabled: bool,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -49,6 +104,8 @@ pub enum MediaType {
     Audio,
     Document,
     Archive,
+    /// Screen captures, recognized by their names and sizes rather than by extension
+    Screenshot,
     Other,
 }
 
@@ -57,6 +114,8 @@ pub enum RegexTarget {
     FileName,
     FilePath,
     Extension,
+    /// Camera and lens names from the photo's EXIF data
+    Camera,
 }
 
 impl Default for FilterSet {
@@ -67,6 +126,11 @@ impl Default for FilterSet {
             media_types: Self::default_media_types(),
             regex_patterns: vec![],
             is_active: false,
+            apply_to_organize: false,
+            required_tags: vec![],
+            min_rating: None,
+            extension_filters: vec![],
+            combinator: FilterCombinator::All,
         }
     }
 }
@@ -86,6 +150,7 @@ impl FilterSet {
                     .map(String::from)
                     .collect(),
                 enabled: true,
+                negated: false,
             },
             MediaTypeFilter {
                 media_type: MediaType::Video,
@@ -94,6 +159,7 @@ impl FilterSet {
                     .map(String::from)
                     .collect(),
                 enabled: true,
+                negated: false,
             },
             MediaTypeFilter {
                 media_type: MediaType::Audio,
@@ -102,6 +168,7 @@ impl FilterSet {
                     .map(String::from)
                     .collect(),
                 enabled: false,
+                negated: false,
             },
             MediaTypeFilter {
                 media_type: MediaType::Document,
@@ -110,6 +177,7 @@ impl FilterSet {
                     .map(String::from)
                     .collect(),
                 enabled: false,
+                negated: false,
             },
             MediaTypeFilter {
                 media_type: MediaType::Archive,
@@ -118,59 +186,181 @@ impl FilterSet {
                     .map(String::from)
                     .collect(),
                 enabled: false,
+                negated: false,
+            },
+            MediaTypeFilter {
+                media_type: MediaType::Screenshot,
+                extensions: vec![],
+                enabled: false,
+                negated: false,
             },
         ]
     }
 
+    /// Returns `true` when organization should only touch the files matching this filter set.
+    #[must_use]
+    pub const fn restricts_organize(&self) -> bool {
+        self.is_active && self.apply_to_organize
+    }
+
+    /// Whether `file` matches the filter expression.
+    ///
+    /// Tags are stored apart from the scanned files, so the tag filters are taken to match here;
+    /// [`Self::matches_file_with_tags`] evaluates them as well.
     #[must_use]
     pub fn matches_file(&self, file: &MediaFile) -> bool {
+        let tags = self.tag_result(None).map(|_| true);
+        self.evaluate(file, tags)
+    }
+
+    /// Whether `file`, carrying `tags`, matches the whole filter expression
+    #[must_use]
+    pub fn matches_file_with_tags(&self, file: &MediaFile, tags: Option<&FileTags>) -> bool {
+        self.evaluate(file, self.tag_result(tags))
+    }
+
+    fn evaluate(&self, file: &MediaFile, tags: Option<bool>) -> bool {
         if !self.is_active {
             return true;
         }
 
-        self.matches_date(file)
-            && self.matches_size(file)
-            && self.matches_media_type(file)
-            && self.matches_regex_patterns(file)
+        let ext = Self::get_file_extension(file);
+        let mut results = [
+            self.date_result(file),
+            self.size_result(file),
+            self.media_type_result(file, &ext),
+            self.regex_result(file),
+            self.extension_result(&ext),
+            tags,
+        ]
+        .into_iter()
+        .flatten()
+        .peekable();
+        match self.combinator {
+            FilterCombinator::All => results.all(|matched| matched),
+            FilterCombinator::Any => results.peek().is_none() || results.any(|matched| matched),
+        }
     }
 
-    fn matches_date(&self, file: &MediaFile) -> bool {
-        if self.date_ranges.is_empty() {
-            return true;
+    /// Whether a file with the lowercase extension `ext` passes the extension filters.
+    ///
+    /// A file passes when no enabled exclude list holds its extension and, if there are enabled
+    /// include lists, one of them does.
+    #[must_use]
+    pub fn matches_extension(&self, ext: &str) -> bool {
+        !self.is_active || self.extension_result(ext).unwrap_or(true)
+    }
+
+    /// Whether the extension filters alone can leave a file out, so the scanner can drop its
+    /// path before reading it; with [`FilterCombinator::Any`] another category could still match
+    #[must_use]
+    pub fn filters_by_extension(&self) -> bool {
+        self.is_active
+            && self.combinator == FilterCombinator::All
+            && self.extension_filters.iter().any(|filter| filter.enabled)
+    }
+
+    /// Whether a file's tags and rating satisfy the tag filters; a file without tags only matches
+    /// when no tag filter is set.
+    #[must_use]
+    pub fn matches_tags(&self, tags: Option<&FileTags>) -> bool {
+        !self.is_active || self.tag_result(tags).unwrap_or(true)
+    }
+
+    /// Combines the filters of one category, given whether each is negated and whether it
+    /// matched. The file must match one of the filters that are not negated, or all of them with
+    /// `every`, and none of the negated ones. `None` when the category has no filters.
+    fn category_result(results: impl IntoIterator<Item = (bool, bool)>, every: bool) -> Option<bool> {
+        let mut any_filter = false;
+        let mut positives = 0;
+        let mut positive_matches = 0;
+        for (negated, matched) in results {
+            any_filter = true;
+            if negated {
+                if matched {
+                    return Some(false);
+                }
+            } else {
+                positives += 1;
+                positive_matches += usize::from(matched);
+            }
         }
+        let matched = if every {
+            positive_matches == positives
+        } else {
+            positives == 0 || positive_matches > 0
+        };
+        any_filter.then_some(matched)
+    }
 
+    fn date_result(&self, file: &MediaFile) -> Option<bool> {
         let file_date = file.modified;
-        self.date_ranges.iter().any(|range| {
-            let after_from = range.from.is_none_or(|from| file_date >= from);
-            let before_to = range.to.is_none_or(|to| file_date <= to);
-            after_from && before_to
-        })
+        Self::category_result(
+            self.date_ranges.iter().map(|range| {
+                let after_from = range.from.is_none_or(|from| file_date >= from);
+                let before_to = range.to.is_none_or(|to| file_date <= to);
+                (range.negated, after_from && before_to)
+            }),
+            false,
+        )
     }
 
-    fn matches_size(&self, file: &MediaFile) -> bool {
-        if self.size_ranges.is_empty() {
-            return true;
-        }
+    fn size_result(&self, file: &MediaFile) -> Option<bool> {
+        Self::category_result(
+            self.size_ranges.iter().map(|range| {
+                let above_min = range.min_bytes.is_none_or(|min| file.size >= min);
+                let below_max = range.max_bytes.is_none_or(|max| file.size <= max);
+                (range.negated, above_min && below_max)
+            }),
+            false,
+        )
+    }
 
-        self.size_ranges.iter().any(|range| {
-            let above_min = range.min_bytes.is_none_or(|min| file.size >= min);
-            let below_max = range.max_bytes.is_none_or(|max| file.size <= max);
-            above_min && below_max
-        })
+    fn media_type_result(&self, file: &MediaFile, file_ext: &str) -> Option<bool> {
+        Self::category_result(
+            self.media_types.iter().filter(|mt| mt.enabled).map(|mt| {
+                let matched = (mt.media_type == MediaType::Screenshot && file.is_screenshot())
+                    || mt.extensions.iter().any(|ext| ext.to_lowercase() == file_ext);
+                (mt.negated, matched)
+            }),
+            false,
+        )
     }
 
-    fn matches_media_type(&self, file: &MediaFile) -> bool {
-        let enabled_types: Vec<_> = self.media_types.iter().filter(|mt| mt.enabled).collect();
+    fn regex_result(&self, file: &MediaFile) -> Option<bool> {
+        Self::category_result(
+            self.regex_patterns
+                .iter()
+                .filter(|pattern| pattern.enabled)
+                // Invalid patterns are skipped
+                .filter_map(|pattern| Some((pattern.negated, Self::matches_single_regex(pattern, file)?))),
+            true,
+        )
+    }
 
-        if enabled_types.is_empty() {
-            return true;
-        }
+    fn extension_result(&self, ext: &str) -> Option<bool> {
+        Self::category_result(
+            self.extension_filters
+                .iter()
+                .filter(|filter| filter.enabled)
+                .map(|filter| (filter.exclude, filter.extensions.iter().any(|e| e == ext))),
+            false,
+        )
+    }
 
-        let file_ext = Self::get_file_extension(file);
+    fn tag_result(&self, tags: Option<&FileTags>) -> Option<bool> {
+        if self.required_tags.is_empty() && self.min_rating.is_none() {
+            return None;
+        }
 
-        enabled_types
+        let has_tags = self
+            .required_tags
             .iter()
-            .any(|mt| mt.extensions.iter().any(|ext| ext.to_lowercase() == file_ext))
+            .all(|tag| tags.is_some_and(|tags| tags.tags.contains(tag)));
+        let rated = self
+            .min_rating
+            .is_none_or(|min| tags.and_then(|tags| tags.rating).is_some_and(|rating| rating >= min));
+        Some(has_tags && rated)
     }
 
     fn get_file_extension(file: &MediaFile) -> String {
@@ -181,22 +371,10 @@ impl FilterSet {
             .to_lowercase()
     }
 
-    fn matches_regex_patterns(&self, file: &MediaFile) -> bool {
-        self.regex_patterns
-            .iter()
-            .filter(|pattern| pattern.enabled)
-            .all(|pattern| Self::matches_single_regex(pattern, file))
-    }
-
-    fn matches_single_regex(pattern: &RegexPattern, file: &MediaFile) -> bool {
-        let regex_result = Self::build_regex(pattern);
-
-        let Ok(regex) = regex_result else {
-            return true; // Skip invalid regex patterns
-        };
-
+    fn matches_single_regex(pattern: &RegexPattern, file: &MediaFile) -> Option<bool> {
+        let regex = Self::build_regex(pattern).ok()?;
         let text = Self::get_regex_target_text(pattern, file);
-        regex.is_match(&text)
+        Some(regex.is_match(&text))
     }
 
     fn build_regex(pattern: &RegexPattern) -> Result<Regex, regex::Error> {
@@ -212,6 +390,7 @@ impl FilterSet {
             RegexTarget::FileName => Arc::clone(&file.name),
             RegexTarget::FilePath => file.path.to_str().unwrap_or("").into(),
             RegexTarget::Extension => file.path.extension().and_then(|ext| ext.to_str()).unwrap_or("").into(),
+            RegexTarget::Camera => file.capture().map(CaptureInfo::search_text).unwrap_or_default().into(),
         }
     }
 
@@ -222,6 +401,11 @@ impl FilterSet {
         // Reset media types to default
         self.media_types = Self::default_media_types();
         self.is_active = false;
+        self.apply_to_organize = false;
+        self.required_tags.clear();
+        self.min_rating = None;
+        self.extension_filters.clear();
+        self.combinator = FilterCombinator::All;
     }
 
     #[must_use]
@@ -231,8 +415,90 @@ impl FilterSet {
         count += self.size_ranges.len();
         count += self.media_types.iter().filter(|mt| mt.enabled).count();
         count += self.regex_patterns.iter().filter(|rp| rp.enabled).count();
+        count += self.required_tags.len();
+        count += usize::from(self.min_rating.is_some());
+        count += self.extension_filters.iter().filter(|ef| ef.enabled).count();
         count
     }
+
+    /// The expression files are matched against, e.g. `(Images OR Videos) AND NOT size >10MB`
+    #[must_use]
+    pub fn expression(&self) -> String {
+        let categories: Vec<String> = [
+            Self::category_expression(
+                self.date_ranges
+                    .iter()
+                    .map(|range| (range.negated, format!("date {}", range.name))),
+                false,
+            ),
+            Self::category_expression(
+                self.size_ranges
+                    .iter()
+                    .map(|range| (range.negated, format!("size {}", range.name))),
+                false,
+            ),
+            Self::category_expression(
+                self.media_types
+                    .iter()
+                    .filter(|mt| mt.enabled)
+                    .map(|mt| (mt.negated, mt.media_type.to_string())),
+                false,
+            ),
+            Self::category_expression(
+                self.regex_patterns.iter().filter(|rp| rp.enabled).map(|rp| {
+                    let target = rp.target.to_string().to_lowercase();
+                    (rp.negated, format!("{target} ~ /{}/", rp.pattern))
+                }),
+                true,
+            ),
+            Self::category_expression(
+                self.extension_filters
+                    .iter()
+                    .filter(|ef| ef.enabled)
+                    .map(|ef| (ef.exclude, format!("ext {}", ef.extensions.join("|")))),
+                false,
+            ),
+            Self::category_expression(
+                self.required_tags
+                    .iter()
+                    .map(|tag| (false, format!("tag {tag}")))
+                    .chain(self.min_rating.map(|stars| (false, format!("rating ≥{stars}")))),
+                true,
+            ),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+
+        if categories.is_empty() {
+            return "all files".to_string();
+        }
+        categories.join(&format!(" {} ", self.combinator))
+    }
+
+    /// Writes one category like [`Self::category_result`] evaluates it, or `None` without filters
+    fn category_expression(terms: impl IntoIterator<Item = (bool, String)>, every: bool) -> Option<String> {
+        let (negated, positive): (Vec<_>, Vec<_>) = terms.into_iter().partition(|(negated, _)| *negated);
+        let mut parts = Vec::new();
+        if !positive.is_empty() {
+            let joined = positive
+                .iter()
+                .map(|(_, term)| term.as_str())
+                .collect::<Vec<_>>()
+                .join(if every { " AND " } else { " OR " });
+            parts.push(if positive.len() > 1 {
+                format!("({joined})")
+            } else {
+                joined
+            });
+        }
+        parts.extend(negated.into_iter().map(|(_, term)| format!("NOT {term}")));
+        match parts.len() {
+            0 => None,
+            1 => parts.pop(),
+            _ => Some(format!("({})", parts.join(" AND "))),
+        }
+    }
 }
 
 impl fmt::Display for MediaType {
@@ -243,17 +509,46 @@ impl fmt::Display for MediaType {
             MediaType::Audio => write!(f, "Audio"),
             MediaType::Document => write!(f, "Documents"),
             MediaType::Archive => write!(f, "Archives"),
+            MediaType::Screenshot => write!(f, "Screenshots"),
             MediaType::Other => write!(f, "Other"),
         }
     }
 }
 
+impl FilterCombinator {
+    /// Switches between matching every category and matching any of them
+    #[must_use]
+    pub const fn toggled(self) -> Self {
+        match self {
+            Self::All => Self::Any,
+            Self::Any => Self::All,
+        }
+    }
+}
+
+impl fmt::Display for FilterCombinator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::All => write!(f, "AND"),
+            Self::Any => write!(f, "OR"),
+        }
+    }
+}
+
+impl fmt::Display for ExtensionFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mode = if self.exclude { "Exclude" } else { "Include" };
+        write!(f, "{mode}: {}", self.extensions.join(", "))
+    }
+}
+
 impl fmt::Display for RegexTarget {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             RegexTarget::FileName => write!(f, "File Name"),
             RegexTarget::FilePath => write!(f, "Full Path"),
             RegexTarget::Extension => write!(f, "Extension"),
+            RegexTarget::Camera => write!(f, "Camera"),
         }
     }
 }
@@ -261,7 +556,12 @@ impl fmt::Display for RegexTarget {
 // Helper functions for creating common filters
 impl FilterSet {
     pub fn add_date_range(&mut self, name: String, from: Option<DateTime<Local>>, to: Option<DateTime<Local>>) {
-        self.date_ranges.push(DateRange { from, to, name });
+        self.date_ranges.push(DateRange {
+            from,
+            to,
+            name,
+            negated: false,
+        });
         self.is_active = true;
     }
 
@@ -274,8 +574,51 @@ impl FilterSet {
             min_bytes,
             max_bytes,
             name,
+            negated: false,
+        });
+        self.is_active = true;
+    }
+
+    /// Requires the comma-separated tags in `input`, returning how many were new
+    pub fn add_required_tags(&mut self, input: &str) -> usize {
+        let before = self.required_tags.len();
+        for tag in crate::parse_tags(input) {
+            if !self.required_tags.contains(&tag) {
+                self.required_tags.push(tag);
+            }
+        }
+        let added = self.required_tags.len() - before;
+        if added > 0 {
+            self.is_active = true;
+        }
+        added
+    }
+
+    /// Adds a list of extensions such as "jpg, png, .heic", or "!gif" to exclude them.
+    ///
+    /// Returns `false` when `input` names no extension.
+    pub fn add_extension_filter(&mut self, input: &str) -> bool {
+        let (exclude, list) = match input.trim().strip_prefix('!') {
+            Some(list) => (true, list),
+            None => (false, input),
+        };
+        let mut extensions: Vec<String> = Vec::new();
+        for ext in list.split([',', ' ']) {
+            let ext = ext.trim().trim_start_matches('.').to_lowercase();
+            if !ext.is_empty() && !extensions.contains(&ext) {
+                extensions.push(ext);
+            }
+        }
+        if extensions.is_empty() {
+            return false;
+        }
+        self.extension_filters.push(ExtensionFilter {
+            extensions,
+            exclude,
+            enabled: true,
         });
         self.is_active = true;
+        true
     }
 
     pub fn add_regex_pattern(&mut self, pattern: String, target: RegexTarget, case_sensitive: bool) {
@@ -284,6 +627,7 @@ impl FilterSet {
             target,
             case_sensitive,
             enabled: true,
+            negated: false,
         });
         self.is_active = true;
     }
@@ -313,6 +657,9 @@ mod tests {
             modified: Local::now(),
             hash: None,
             metadata: None,
+            via_symlink: false,
+            live_photo_pair: None,
+            content_extension: None,
         }
     }
 
@@ -327,7 +674,7 @@ mod tests {
         assert!(!filter_set.is_active);
 
         // Check default media types
-        assert_eq!(filter_set.media_types.len(), 5);
+        assert_eq!(filter_set.media_types.len(), 6);
 
         // Images and Videos should be enabled by default
         assert!(filter_set.media_types[0].enabled); // Images
@@ -337,6 +684,30 @@ mod tests {
         assert!(!filter_set.media_types[4].enabled); // Archives
     }
 
+    #[test]
+    fn test_restricts_organize() {
+        let mut filter_set = FilterSet::new();
+        assert!(!filter_set.restricts_organize());
+
+        filter_set.apply_to_organize = true;
+        assert!(!filter_set.restricts_organize()); // Inactive filters never restrict
+
+        filter_set.is_active = true;
+        assert!(filter_set.restricts_organize());
+
+        filter_set.clear_all();
+        assert!(!filter_set.apply_to_organize);
+        assert!(!filter_set.restricts_organize());
+    }
+
+    #[test]
+    fn test_apply_to_organize_defaults_when_missing() {
+        let json = r#"{"date_ranges":[],"size_ranges":[],"media_types":[],"regex_patterns":[],"is_active":true}"#;
+        let filter_set: FilterSet = serde_json::from_str(json).unwrap();
+        assert!(filter_set.is_active);
+        assert!(!filter_set.apply_to_organize);
+    }
+
     #[test]
     fn test_filter_set_new() {
         let filter_set = FilterSet::new();
@@ -435,6 +806,24 @@ mod tests {
         assert!(filter_set.matches_file(&file));
     }
 
+    #[test]
+    fn test_matches_file_screenshots() {
+        let mut filter_set = FilterSet::new();
+        filter_set.is_active = true;
+        filter_set.media_types[0].enabled = false;
+        filter_set.media_types[1].enabled = false;
+        filter_set.media_types[5].enabled = true;
+
+        let mut file = create_test_media_file();
+        file.metadata = None;
+        assert!(!filter_set.matches_file(&file));
+
+        file.name = "Screenshot 2024-03-15 at 14.30.22.png".into();
+        file.path = PathBuf::from("/test/Screenshot 2024-03-15 at 14.30.22.png");
+        assert!(filter_set.matches_file(&file));
+        assert_eq!(MediaType::Screenshot.to_string(), "Screenshots");
+    }
+
     #[test]
     fn test_matches_file_regex_patterns() {
         let mut filter_set = FilterSet::new();
@@ -516,7 +905,7 @@ mod tests {
         assert!(filter_set.regex_patterns.is_empty());
 
         // Media types should be reset to defaults
-        assert_eq!(filter_set.media_types.len(), 5);
+        assert_eq!(filter_set.media_types.len(), 6);
         assert!(filter_set.media_types[0].enabled); // Images
         assert!(filter_set.media_types[1].enabled); // Videos
     }
@@ -562,13 +951,149 @@ mod tests {
         assert_eq!(RegexTarget::FileName.to_string(), "File Name");
         assert_eq!(RegexTarget::FilePath.to_string(), "Full Path");
         assert_eq!(RegexTarget::Extension.to_string(), "Extension");
+        assert_eq!(RegexTarget::Camera.to_string(), "Camera");
+    }
+
+    #[test]
+    fn test_matches_tags() {
+        let mut filter_set = FilterSet::new();
+        let tags = FileTags {
+            rating: Some(4),
+            tags: crate::parse_tags("beach, family"),
+        };
+        assert!(filter_set.matches_tags(None));
+
+        assert_eq!(filter_set.add_required_tags("Beach, beach"), 1);
+        assert!(filter_set.is_active);
+        assert!(filter_set.matches_tags(Some(&tags)));
+        assert!(!filter_set.matches_tags(None));
+
+        filter_set.min_rating = Some(5);
+        assert!(!filter_set.matches_tags(Some(&tags)));
+        filter_set.min_rating = Some(3);
+        assert!(filter_set.matches_tags(Some(&tags)));
+
+        filter_set.add_required_tags("zoo");
+        assert!(!filter_set.matches_tags(Some(&tags)));
+        assert_eq!(filter_set.active_filter_count(), 5);
+
+        filter_set.clear_all();
+        assert!(filter_set.required_tags.is_empty());
+        assert!(filter_set.min_rating.is_none());
+    }
+
+    #[test]
+    fn test_negated_filters() {
+        let mut filter_set = FilterSet::new();
+        let file = create_test_media_file(); // 5MB image.jpg
+
+        filter_set.add_size_range("Large".to_string(), Some(4.0), None);
+        assert!(filter_set.matches_file(&file));
+        filter_set.size_ranges[0].negated = true;
+        assert!(!filter_set.matches_file(&file));
+
+        // A negated filter alongside a plain one in the same category
+        filter_set.add_size_range("Small".to_string(), None, Some(10.0));
+        filter_set.size_ranges[0].max_bytes = Some(1024);
+        assert!(filter_set.matches_file(&file));
+
+        // Negated patterns must not match, invalid ones are still skipped
+        filter_set.add_regex_pattern("^image".to_string(), RegexTarget::FileName, false);
+        filter_set.regex_patterns[0].negated = true;
+        assert!(!filter_set.matches_file(&file));
+        filter_set.regex_patterns[0].pattern = "[invalid".to_string();
+        assert!(filter_set.matches_file(&file));
+
+        // Leaving images out
+        filter_set.media_types[0].negated = true;
+        assert!(!filter_set.matches_file(&file));
+    }
+
+    #[test]
+    fn test_any_combinator() {
+        let mut filter_set = FilterSet::new();
+        let file = create_test_media_file(); // 5MB image.jpg
+        filter_set.media_types[0].enabled = false; // Only videos
+        filter_set.add_size_range("Large".to_string(), Some(4.0), None);
+        assert!(!filter_set.matches_file(&file));
+
+        filter_set.combinator = filter_set.combinator.toggled();
+        assert_eq!(filter_set.combinator, FilterCombinator::Any);
+        assert!(filter_set.matches_file(&file));
+        filter_set.size_ranges[0].negated = true;
+        assert!(!filter_set.matches_file(&file));
+
+        // Tags count as one more category
+        let tags = FileTags {
+            rating: None,
+            tags: crate::parse_tags("beach"),
+        };
+        filter_set.add_required_tags("beach");
+        assert!(filter_set.matches_file_with_tags(&file, Some(&tags)));
+        assert!(!filter_set.matches_file_with_tags(&file, None));
+        // Without the tags at hand, the file may still match through them
+        assert!(filter_set.matches_file(&file));
+
+        // Extensions cannot drop paths on their own when another category could match
+        filter_set.add_extension_filter("!jpg");
+        assert!(!filter_set.filters_by_extension());
+        assert!(filter_set.matches_file_with_tags(&file, Some(&tags)));
+    }
+
+    #[test]
+    fn test_expression() {
+        let mut filter_set = FilterSet::new();
+        filter_set.media_types[1].enabled = false;
+        assert_eq!(filter_set.expression(), "Images");
+
+        filter_set.add_size_range(">10MB".to_string(), Some(10.0), None);
+        filter_set.size_ranges[0].negated = true;
+        filter_set.add_extension_filter("jpg,png");
+        filter_set.add_extension_filter("!gif");
+        assert_eq!(
+            filter_set.expression(),
+            "NOT size >10MB AND Images AND (ext jpg|png AND NOT ext gif)"
+        );
+
+        filter_set.combinator = FilterCombinator::Any;
+        filter_set.media_types[1].enabled = true;
+        filter_set.add_required_tags("beach");
+        filter_set.min_rating = Some(3);
+        assert_eq!(
+            filter_set.expression(),
+            "NOT size >10MB OR (Images OR Videos) OR (ext jpg|png AND NOT ext gif) OR (tag beach AND rating ≥3)"
+        );
+
+        filter_set.clear_all();
+        filter_set.media_types.iter_mut().for_each(|mt| mt.enabled = false);
+        assert_eq!(filter_set.expression(), "all files");
+    }
+
+    #[test]
+    fn test_camera_regex_matches_capture_info() {
+        let mut filter_set = FilterSet::new();
+        let mut file = create_test_media_file();
+        filter_set.add_regex_pattern("eos r5|rf50".to_string(), RegexTarget::Camera, false);
+
+        // Files without EXIF data have no camera to match
+        assert!(!filter_set.matches_file(&file));
+
+        file.metadata = Some(crate::MediaMetadata::Image(crate::ImageMetadata::from_capture(
+            "JPG".into(),
+            CaptureInfo {
+                make: Some("Canon".into()),
+                model: Some("Canon EOS R5".into()),
+                ..CaptureInfo::default()
+            },
+        )));
+        assert!(filter_set.matches_file(&file));
     }
 
     #[test]
     fn test_default_media_types() {
         let media_types = FilterSet::default_media_types();
 
-        assert_eq!(media_types.len(), 5);
+        assert_eq!(media_types.len(), 6);
 
         // Check Image extensions
         let image_filter = &media_types[0];
@@ -675,6 +1200,7 @@ mod tests {
         filter_set.add_date_range("Test".to_string(), Some(Local::now()), None);
         filter_set.add_size_range("Test".to_string(), Some(1.0), Some(10.0));
         filter_set.add_regex_pattern("test".to_string(), RegexTarget::FileName, true);
+        filter_set.add_extension_filter("!gif");
 
         // Serialize to JSON
         let json = serde_json::to_string(&filter_set).unwrap();
@@ -685,6 +1211,44 @@ mod tests {
         assert_eq!(filter_set.date_ranges.len(), deserialized.date_ranges.len());
         assert_eq!(filter_set.size_ranges.len(), deserialized.size_ranges.len());
         assert_eq!(filter_set.regex_patterns.len(), deserialized.regex_patterns.len());
+        assert_eq!(filter_set.extension_filters, deserialized.extension_filters);
         assert_eq!(filter_set.is_active, deserialized.is_active);
     }
+
+    #[test]
+    fn test_extension_filters() {
+        let mut filter_set = FilterSet::new();
+        assert!(!filter_set.add_extension_filter(" , . "));
+        assert!(!filter_set.add_extension_filter("!"));
+        assert!(!filter_set.is_active);
+
+        assert!(filter_set.add_extension_filter("JPG, .png heic,jpg"));
+        assert!(filter_set.is_active);
+        assert_eq!(filter_set.extension_filters[0].extensions, vec!["jpg", "png", "heic"]);
+        assert_eq!(filter_set.extension_filters[0].to_string(), "Include: jpg, png, heic");
+        assert!(filter_set.matches_file(&create_test_media_file()));
+        assert!(filter_set.matches_extension("heic"));
+        assert!(!filter_set.matches_extension("gif"));
+
+        // Exclude lists win over include lists
+        assert!(filter_set.add_extension_filter("!jpg"));
+        assert!(filter_set.extension_filters[1].exclude);
+        assert!(!filter_set.matches_file(&create_test_media_file()));
+        assert!(filter_set.matches_extension("png"));
+
+        // Disabled lists are ignored
+        filter_set.extension_filters[1].enabled = false;
+        assert!(filter_set.matches_extension("jpg"));
+        assert_eq!(filter_set.active_filter_count(), 3);
+
+        filter_set.extension_filters.clear();
+        assert!(filter_set.add_extension_filter("!gif"));
+        assert!(filter_set.filters_by_extension());
+        assert!(filter_set.matches_extension("mov"));
+        assert!(!filter_set.matches_extension("gif"));
+
+        filter_set.clear_all();
+        assert!(filter_set.extension_filters.is_empty());
+        assert!(!filter_set.filters_by_extension());
+    }
 }
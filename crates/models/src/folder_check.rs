@@ -0,0 +1,16 @@
+use std::path::PathBuf;
+
+/// What is known about a folder typed into the source or destination setting
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FolderCheck {
+    /// The folder itself, or the nearest folder above it that exists
+    pub existing: PathBuf,
+    /// Whether the typed folder exists
+    pub exists: bool,
+    /// Whether the typed path names a folder rather than a file
+    pub is_dir: bool,
+    /// Whether new files can be written to `existing`
+    pub writable: bool,
+    /// Bytes free for unprivileged users on the volume of `existing`, if the platform says
+    pub free_space: Option<u64>,
+}
@@ -0,0 +1,65 @@
+use std::path::PathBuf;
+
+use crate::EditingField;
+
+/// State of the folder picker overlay used to choose the source or destination folder
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FolderPicker {
+    /// Setting the chosen folder is written to
+    pub field: EditingField,
+    /// Folder being shown
+    pub dir: PathBuf,
+    /// Subfolders of `dir`, sorted by name
+    pub folders: Vec<PathBuf>,
+    pub selected: usize,
+    /// Whether folders starting with a dot are listed
+    pub show_hidden: bool,
+}
+
+impl FolderPicker {
+    #[must_use]
+    pub const fn new(field: EditingField, dir: PathBuf) -> Self {
+        Self {
+            field,
+            dir,
+            folders: Vec::new(),
+            selected: 0,
+            show_hidden: false,
+        }
+    }
+
+    #[must_use]
+    pub fn selected_folder(&self) -> Option<&PathBuf> {
+        self.folders.get(self.selected)
+    }
+
+    /// Shows `dir` with `folders` listed, selecting `select` if it is among them
+    pub fn show(&mut self, dir: PathBuf, folders: Vec<PathBuf>, select: Option<&PathBuf>) {
+        self.selected = select
+            .and_then(|path| folders.iter().position(|folder| folder == path))
+            .unwrap_or(0);
+        self.dir = dir;
+        self.folders = folders;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_show_selects_previous_folder() {
+        let mut picker = FolderPicker::new(EditingField::SourceFolder, PathBuf::from("/photos/2024"));
+        let folders = vec![PathBuf::from("/photos/2023"), PathBuf::from("/photos/2024")];
+
+        picker.show(
+            PathBuf::from("/photos"),
+            folders.clone(),
+            Some(&PathBuf::from("/photos/2024")),
+        );
+        assert_eq!(picker.selected_folder(), Some(&folders[1]));
+
+        picker.show(PathBuf::from("/photos"), folders, Some(&PathBuf::from("/elsewhere")));
+        assert_eq!(picker.selected, 0);
+    }
+}
@@ -1,11 +1,80 @@
+mod activity;
+mod archive;
+mod batch_rename;
+mod bulk_action;
+mod cloud_upload;
+mod collection;
+mod command_palette;
+mod compare;
+mod date_fix;
+mod device_import;
+mod dialog;
 mod duplicate;
+mod error_center;
+mod file_browser;
+mod file_groups;
+mod file_sort;
 pub mod filters;
+mod folder_check;
+mod folder_picker;
+mod log_viewer;
+mod manifest;
 mod media_file;
+mod metadata_stats;
+mod mouse;
+mod notification;
+mod organize_report;
+mod performance;
+mod preflight;
+mod profile;
+mod remote_transfer;
+mod space;
 mod state;
 mod statistics;
+mod suspect;
+mod tags;
+mod toast;
+mod verify;
+mod viewport;
 
-pub use duplicate::{DuplicateGroup, DuplicateStats};
-pub use filters::FilterSet;
-pub use media_file::{FileType, ImageMetadata, MediaFile, MediaMetadata};
-pub use state::{AppState, DuplicateFocus, EditingField, FilterFocus, InputMode, OrganizeResult, ScanResult};
-pub use statistics::Statistics;
+pub use activity::{ActivityEntry, ActivityKind};
+pub use archive::{ArchiveMedia, extraction_folder};
+pub use batch_rename::BatchRename;
+pub use bulk_action::{BulkAction, BulkActionMenu};
+pub use cloud_upload::{CloudUploadReport, UploadFailure};
+pub use collection::{Collection, CollectionInput, CollectionsView};
+pub use command_palette::{CommandPalette, PaletteCommand};
+pub use compare::{CompareEntry, ExifDates, Thumbnail};
+pub use date_fix::DateMismatch;
+pub use device_import::{CameraVolume, DeviceImport, ImportSummary};
+pub use dialog::{Dialog, DialogAction, DialogFocus, DialogKind, DialogOutcome};
+pub use duplicate::{BulkDuplicateAction, DuplicateGroup, DuplicateSort, DuplicateStats, LinkMethod};
+pub use error_center::{ErrorCenter, ErrorEntry, ErrorSource, MAX_ERROR_ENTRIES};
+pub use file_browser::{BrowserEntry, BrowserPane, BrowserSide, FileBrowser};
+pub use file_groups::{FileGroup, GroupBy, GroupRow, GroupedFiles};
+pub use file_sort::{FileSort, SortColumn};
+pub use filters::{FilterCombinator, FilterMatches, FilterSet, MediaType};
+pub use folder_check::FolderCheck;
+pub use folder_picker::FolderPicker;
+pub use log_viewer::{LogLevel, LogViewer};
+pub use manifest::{Manifest, ManifestEntry, RestoreItem, RestoreProblem};
+pub use media_file::{AudioMetadata, CaptureInfo, FileType, ImageMetadata, MediaFile, MediaMetadata};
+pub use metadata_stats::{FOCAL_LENGTH_RANGES, MetadataStats, focal_length_range};
+pub use mouse::{MouseTargets, ScreenArea};
+pub use notification::{MAX_REPORTED_ERRORS, NotifiedOperation, OperationReport};
+pub use organize_report::{FileOutcome, OrganizedFile, ReportSort};
+pub use performance::{MAX_PERFORMANCE_ENTRIES, OperationProfile, PerformanceLog, StageTiming};
+pub use preflight::{Preflight, PreflightAction, PreflightIssue, PreflightSeverity};
+pub use profile::ProfilePicker;
+pub use remote_transfer::RemoteTransfer;
+pub use space::SpaceProjection;
+pub use state::{
+    AppState, ConflictStats, DuplicateFocus, EditingField, FileConflict, FilterFocus, InputMode, NonMediaStats,
+    OnboardingStep, OrganizeResult, PendingQuit, ScanResult, SearchScope, SkipRuleStats, SpaceFocus,
+};
+pub use statistics::{SnapshotKind, Statistics, StatsSnapshot};
+pub use suspect::{SuspectFile, SuspectReason};
+pub use tags::{FileTags, MAX_RATING, TagSummary, parse_tags};
+pub use toast::{MAX_TOASTS, MAX_VISIBLE_TOASTS, Toast, ToastSeverity, Toasts};
+pub use verify::{IndexedFile, VerifyIssue, VerifyIssueKind, VerifyReport};
+pub use viewport::ListViewport;
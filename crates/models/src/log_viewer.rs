@@ -0,0 +1,130 @@
+use std::fmt;
+
+/// Level of a line in the log file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl LogLevel {
+    /// Reads the level of a log line as written by the tracing formatter, where it follows
+    /// the timestamp
+    #[must_use]
+    pub fn of_line(line: &str) -> Option<Self> {
+        match line.split_whitespace().nth(1)? {
+            "TRACE" => Some(Self::Trace),
+            "DEBUG" => Some(Self::Debug),
+            "INFO" => Some(Self::Info),
+            "WARN" => Some(Self::Warn),
+            "ERROR" => Some(Self::Error),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for LogLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Trace => write!(f, "trace"),
+            Self::Debug => write!(f, "debug"),
+            Self::Info => write!(f, "info"),
+            Self::Warn => write!(f, "warn"),
+            Self::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// State of the overlay showing the end of the log file
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LogViewer {
+    pub lines: Vec<String>,
+    /// Size of the log file when it was last read, to notice new lines
+    pub file_len: u64,
+    /// Least severe level shown, or every line when `None`
+    pub min_level: Option<LogLevel>,
+    pub search: String,
+    /// Whether keys are typed into the search
+    pub searching: bool,
+    /// Lines scrolled up from the newest one; 0 follows new lines as they are logged
+    pub scroll: usize,
+}
+
+impl LogViewer {
+    /// Returns the lines passing the level filter and containing the search text, ignoring case.
+    ///
+    /// Lines without a level, such as the rest of a multi-line message, take the level of
+    /// the line before them.
+    #[must_use]
+    pub fn visible_lines(&self) -> Vec<(Option<LogLevel>, &str)> {
+        let search = self.search.to_lowercase();
+        let mut level = None;
+        self.lines
+            .iter()
+            .filter_map(|line| {
+                level = LogLevel::of_line(line).or(level);
+                let shown = self.min_level.is_none_or(|min| level.is_some_and(|level| level >= min))
+                    && (search.is_empty() || line.to_lowercase().contains(&search));
+                shown.then_some((level, line.as_str()))
+            })
+            .collect()
+    }
+
+    /// Shows only more severe lines, wrapping around to every line after errors only
+    pub const fn cycle_level(&mut self) {
+        self.min_level = match self.min_level {
+            None => Some(LogLevel::Debug),
+            Some(LogLevel::Trace | LogLevel::Debug) => Some(LogLevel::Info),
+            Some(LogLevel::Info) => Some(LogLevel::Warn),
+            Some(LogLevel::Warn) => Some(LogLevel::Error),
+            Some(LogLevel::Error) => None,
+        };
+        self.scroll = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn viewer() -> LogViewer {
+        LogViewer {
+            lines: vec![
+                "2025-06-01T10:00:00.000000Z DEBUG visualvault_core::scanner: 120: Scanning /photos".to_string(),
+                "2025-06-01T10:00:01.000000Z  WARN visualvault_core::scanner: 300: Skipping IMG_1.HEIC".to_string(),
+                "caused by: unsupported format".to_string(),
+                "2025-06-01T10:00:02.000000Z ERROR visualvault_core::organizer: 88: Failed to move a.jpg".to_string(),
+            ],
+            ..LogViewer::default()
+        }
+    }
+
+    #[test]
+    fn test_log_level_of_line() {
+        let viewer = viewer();
+        assert_eq!(LogLevel::of_line(&viewer.lines[0]), Some(LogLevel::Debug));
+        assert_eq!(LogLevel::of_line(&viewer.lines[1]), Some(LogLevel::Warn));
+        assert_eq!(LogLevel::of_line(&viewer.lines[2]), None);
+    }
+
+    #[test]
+    fn test_visible_lines_filter_by_level_and_search() {
+        let mut viewer = viewer();
+        assert_eq!(viewer.visible_lines().len(), 4);
+
+        viewer.min_level = Some(LogLevel::Warn);
+        let lines = viewer.visible_lines();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[1], (Some(LogLevel::Warn), "caused by: unsupported format"));
+
+        viewer.search = "HEIC".to_lowercase();
+        assert_eq!(viewer.visible_lines().len(), 1);
+
+        viewer.cycle_level();
+        viewer.cycle_level();
+        assert_eq!(viewer.min_level, None);
+    }
+}
@@ -0,0 +1,105 @@
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// A file placed in a destination folder by an organize run, as recorded in a manifest
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    /// Where the file was placed, relative to the folder holding the manifest
+    pub file: PathBuf,
+    /// Where the file was before it was organized
+    pub original: PathBuf,
+    pub size: u64,
+    /// Content hash tagged with its algorithm, e.g. `blake3:…`; missing if the file could not be read
+    pub hash: Option<String>,
+    /// When the organize run placed the file
+    pub organized: DateTime<Local>,
+}
+
+/// The files organize runs placed in or below a folder, written next to them so where they
+/// came from is known without the undo history
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Manifest {
+    pub entries: Vec<ManifestEntry>,
+}
+
+impl Manifest {
+    /// Adds entries, replacing earlier ones for the same file
+    pub fn merge(&mut self, entries: Vec<ManifestEntry>) {
+        self.entries
+            .retain(|existing| !entries.iter().any(|entry| entry.file == existing.file));
+        self.entries.extend(entries);
+        self.entries.sort_by(|a, b| a.file.cmp(&b.file));
+    }
+}
+
+/// Why a file listed in a manifest cannot be put back where it came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RestoreProblem {
+    /// The file is no longer where the manifest says it was placed
+    Missing,
+    /// The content of the file no longer matches the hash in the manifest
+    Changed,
+    /// Another file already exists at the original path
+    OriginalTaken,
+}
+
+impl RestoreProblem {
+    #[must_use]
+    pub const fn description(self) -> &'static str {
+        match self {
+            Self::Missing => "missing from the library",
+            Self::Changed => "changed since it was organized",
+            Self::OriginalTaken => "original path already exists",
+        }
+    }
+}
+
+/// A file listed in a manifest, to be moved back to its original path
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RestoreItem {
+    /// The folder holding the manifest the entry was read from
+    pub folder: PathBuf,
+    pub entry: ManifestEntry,
+    pub problem: Option<RestoreProblem>,
+}
+
+impl RestoreItem {
+    /// Where the file is now
+    #[must_use]
+    pub fn current(&self) -> PathBuf {
+        self.folder.join(&self.entry.file)
+    }
+
+    #[must_use]
+    pub fn original(&self) -> &Path {
+        &self.entry.original
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(file: &str, original: &str) -> ManifestEntry {
+        ManifestEntry {
+            file: PathBuf::from(file),
+            original: PathBuf::from(original),
+            size: 1,
+            hash: None,
+            organized: Local::now(),
+        }
+    }
+
+    #[test]
+    fn test_merge_replaces_entries_for_the_same_file() {
+        let mut manifest = Manifest {
+            entries: vec![entry("b.jpg", "/old/b.jpg"), entry("c.jpg", "/old/c.jpg")],
+        };
+        manifest.merge(vec![entry("b.jpg", "/new/b.jpg"), entry("a.jpg", "/new/a.jpg")]);
+
+        let files: Vec<_> = manifest.entries.iter().map(|entry| entry.file.clone()).collect();
+        assert_eq!(files, ["a.jpg", "b.jpg", "c.jpg"].map(PathBuf::from));
+        assert_eq!(manifest.entries[1].original, PathBuf::from("/new/b.jpg"));
+    }
+}
@@ -8,6 +8,7 @@ use std::sync::Arc;
 pub enum FileType {
     Image,
     Video,
+    Audio,
     Document,
     Other,
 }
@@ -17,6 +18,7 @@ impl fmt::Display for FileType {
         match self {
             FileType::Image => write!(f, "Image"),
             FileType::Video => write!(f, "Video"),
+            FileType::Audio => write!(f, "Audio"),
             FileType::Document => write!(f, "Document"),
             FileType::Other => write!(f, "Others"),
         }
@@ -34,20 +36,133 @@ pub struct MediaFile {
     pub modified: DateTime<Local>,
     pub hash: Option<Arc<str>>,
     pub metadata: Option<MediaMetadata>,
+    /// Whether the scan reached the file through a symbolic link
+    #[serde(default)]
+    pub via_symlink: bool,
+    /// The other half of a Live Photo: the video for a still image, or the still image for a video
+    #[serde(default)]
+    pub live_photo_pair: Option<PathBuf>,
+    /// The extension of the format the content is in, when that differs from the file's own
+    #[serde(default)]
+    pub content_extension: Option<Arc<str>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum MediaMetadata {
     Image(ImageMetadata),
     Video(VideoMetadata),
+    Audio(AudioMetadata),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ImageMetadata {
+    /// Zero until the image itself has been decoded; scans only read the EXIF data
     pub width: u32,
     pub height: u32,
     pub format: String,
     pub color_type: String,
+    #[serde(default)]
+    pub capture: CaptureInfo,
+}
+
+impl ImageMetadata {
+    /// Metadata for an image whose EXIF data was read but whose pixels were not decoded
+    #[must_use]
+    pub const fn from_capture(format: String, capture: CaptureInfo) -> Self {
+        Self {
+            width: 0,
+            height: 0,
+            format,
+            color_type: String::new(),
+            capture,
+        }
+    }
+
+    /// Whether the width, height and color type were read from the decoded image
+    #[must_use]
+    pub const fn has_dimensions(&self) -> bool {
+        self.width > 0 && self.height > 0
+    }
+}
+
+/// Camera settings a photo was taken with, from its EXIF data; every field is optional.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct CaptureInfo {
+    pub make: Option<String>,
+    pub model: Option<String>,
+    pub lens: Option<String>,
+    pub iso: Option<u32>,
+    /// F-number, e.g. 2.8 for f/2.8
+    pub aperture: Option<f64>,
+    /// Exposure time in seconds
+    pub shutter_speed: Option<f64>,
+    /// Focal length in millimeters
+    pub focal_length: Option<f64>,
+}
+
+impl CaptureInfo {
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.make.is_none()
+            && self.model.is_none()
+            && self.lens.is_none()
+            && self.iso.is_none()
+            && self.aperture.is_none()
+            && self.shutter_speed.is_none()
+            && self.focal_length.is_none()
+    }
+
+    /// Camera that took the photo, e.g. "Canon EOS R5", from its make and model
+    #[must_use]
+    pub fn camera(&self) -> Option<String> {
+        match (&self.make, &self.model) {
+            // Most cameras repeat the make in the model name
+            (Some(make), Some(model)) if model.to_lowercase().starts_with(&make.to_lowercase()) => Some(model.clone()),
+            (Some(make), Some(model)) => Some(format!("{make} {model}")),
+            (make, model) => model.clone().or_else(|| make.clone()),
+        }
+    }
+
+    /// Aperture as an f-stop, e.g. "f/2.8"
+    #[must_use]
+    pub fn aperture_label(&self) -> Option<String> {
+        self.aperture.map(|aperture| format!("f/{}", trim_decimal(aperture)))
+    }
+
+    /// Exposure time as photographers write it, e.g. "1/250 s" or "2 s"
+    #[must_use]
+    pub fn shutter_speed_label(&self) -> Option<String> {
+        self.shutter_speed.filter(|&seconds| seconds > 0.0).map(|seconds| {
+            if seconds >= 1.0 {
+                format!("{} s", trim_decimal(seconds))
+            } else {
+                format!("1/{} s", (1.0 / seconds).round())
+            }
+        })
+    }
+
+    /// Focal length, e.g. "50 mm"
+    #[must_use]
+    pub fn focal_length_label(&self) -> Option<String> {
+        self.focal_length
+            .map(|focal_length| format!("{} mm", trim_decimal(focal_length)))
+    }
+
+    /// Camera and lens names, which search and camera filters match against
+    #[must_use]
+    pub fn search_text(&self) -> String {
+        [self.camera(), self.lens.clone()]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Formats `value` with one decimal, dropping it when it is zero
+fn trim_decimal(value: f64) -> String {
+    let text = format!("{value:.1}");
+    text.strip_suffix(".0").map_or_else(|| text.clone(), str::to_string)
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -59,6 +174,129 @@ pub struct VideoMetadata {
     pub codec: String,
 }
 
+/// Tags read from an audio file; every field is optional since tagging is often incomplete.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AudioMetadata {
+    pub artist: Option<String>,
+    pub album: Option<String>,
+    pub title: Option<String>,
+    pub year: Option<i32>,
+}
+
+impl AudioMetadata {
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.artist.is_none() && self.album.is_none() && self.title.is_none() && self.year.is_none()
+    }
+}
+
+impl MediaFile {
+    /// Capture settings read from the file's EXIF data, if it is a photo that has them
+    #[must_use]
+    pub const fn capture(&self) -> Option<&CaptureInfo> {
+        match &self.metadata {
+            Some(MediaMetadata::Image(image)) if !image.capture.is_empty() => Some(&image.capture),
+            _ => None,
+        }
+    }
+
+    /// Approximate number of bytes this file takes in memory, including its heap allocations.
+    ///
+    /// Used to decide whether a scan fits the memory budget, so it only needs to be close.
+    #[must_use]
+    pub fn estimated_memory(&self) -> usize {
+        let metadata = match &self.metadata {
+            Some(MediaMetadata::Image(image)) => {
+                image.format.len() + image.color_type.len() + image.capture.search_text().len()
+            }
+            Some(MediaMetadata::Video(video)) => video.codec.len(),
+            Some(MediaMetadata::Audio(audio)) => [&audio.artist, &audio.album, &audio.title]
+                .iter()
+                .filter_map(|tag| tag.as_ref().map(String::len))
+                .sum(),
+            None => 0,
+        };
+        std::mem::size_of::<Self>()
+            + self.path.as_os_str().len()
+            + self.name.len()
+            + self.extension.len()
+            + self.hash.as_ref().map_or(0, |hash| hash.len())
+            + self.live_photo_pair.as_ref().map_or(0, |pair| pair.as_os_str().len())
+            + metadata
+    }
+
+    /// Whether the file looks like a screenshot rather than a photo.
+    ///
+    /// Either its name follows a screenshot tool's pattern, such as `Screenshot_20240315.png`,
+    /// or it is a PNG without camera data whose size matches a common screen resolution.
+    #[must_use]
+    pub fn is_screenshot(&self) -> bool {
+        if self.file_type != FileType::Image {
+            return false;
+        }
+        let name = self.name.to_lowercase();
+        if SCREENSHOT_NAME_PREFIXES.iter().any(|prefix| name.starts_with(prefix)) {
+            return true;
+        }
+
+        match &self.metadata {
+            Some(MediaMetadata::Image(image)) if image.format == "PNG" && image.capture.is_empty() => {
+                let size = (image.width.max(image.height), image.width.min(image.height));
+                SCREEN_RESOLUTIONS.contains(&size)
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Lowercase file name prefixes used by screenshot tools on desktops and phones
+const SCREENSHOT_NAME_PREFIXES: &[&str] = &[
+    "screenshot",
+    "screen shot",
+    "screen_shot",
+    "screen-shot",
+    "bildschirmfoto",
+    "capture d'écran",
+    "captura de pantalla",
+    "schermafbeelding",
+    "schermata",
+    "snímek obrazovky",
+];
+
+/// Common monitor and phone screen resolutions, longest side first
+const SCREEN_RESOLUTIONS: &[(u32, u32)] = &[
+    (1280, 720),
+    (1280, 800),
+    (1334, 750),
+    (1366, 768),
+    (1440, 900),
+    (1536, 864),
+    (1600, 720),
+    (1600, 900),
+    (1680, 1050),
+    (1792, 828),
+    (1920, 1080),
+    (1920, 1200),
+    (2340, 1080),
+    (2400, 1080),
+    (2436, 1125),
+    (2532, 1170),
+    (2556, 1179),
+    (2560, 1080),
+    (2560, 1440),
+    (2560, 1600),
+    (2688, 1242),
+    (2778, 1284),
+    (2796, 1290),
+    (2880, 1800),
+    (3024, 1964),
+    (3120, 1440),
+    (3200, 1440),
+    (3456, 2234),
+    (3840, 2160),
+    (5120, 2880),
+];
+
 // ... existing code ...
 
 #[cfg(test)]
@@ -85,7 +323,11 @@ mod tests {
                 height: 1080,
                 format: "JPEG".into(),
                 color_type: "RGB".into(),
+                capture: CaptureInfo::default(),
             })),
+            via_symlink: false,
+            live_photo_pair: None,
+            content_extension: None,
         }
     }
 
@@ -93,6 +335,7 @@ mod tests {
     fn test_file_type_display() {
         assert_eq!(FileType::Image.to_string(), "Image");
         assert_eq!(FileType::Video.to_string(), "Video");
+        assert_eq!(FileType::Audio.to_string(), "Audio");
         assert_eq!(FileType::Document.to_string(), "Document");
         assert_eq!(FileType::Other.to_string(), "Others");
     }
@@ -103,7 +346,13 @@ mod tests {
         assert_ne!(FileType::Image, FileType::Video);
 
         // Test all combinations
-        let types = [FileType::Image, FileType::Video, FileType::Document, FileType::Other];
+        let types = [
+            FileType::Image,
+            FileType::Video,
+            FileType::Audio,
+            FileType::Document,
+            FileType::Other,
+        ];
         for (i, type1) in types.iter().enumerate() {
             for (j, type2) in types.iter().enumerate() {
                 if i == j {
@@ -153,6 +402,9 @@ mod tests {
             modified: Local::now(),
             hash: None,
             metadata: None,
+            via_symlink: false,
+            live_photo_pair: None,
+            content_extension: None,
         };
 
         assert_eq!(file.name, "document.pdf".into());
@@ -185,6 +437,7 @@ mod tests {
             height: 2160,
             format: "PNG".to_string(),
             color_type: "RGBA".to_string(),
+            capture: CaptureInfo::default(),
         };
 
         assert_eq!(metadata.width, 3840);
@@ -232,6 +485,7 @@ mod tests {
             height: 600,
             format: "BMP".to_string(),
             color_type: "RGB".to_string(),
+            capture: CaptureInfo::default(),
         });
 
         if let MediaMetadata::Image(meta) = &image_meta {
@@ -258,6 +512,24 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_audio_metadata() {
+        assert!(AudioMetadata::default().is_empty());
+
+        let metadata = AudioMetadata {
+            artist: Some("Nina Simone".to_string()),
+            album: Some("Pastel Blues".to_string()),
+            title: None,
+            year: Some(1965),
+        };
+        assert!(!metadata.is_empty());
+
+        // Older cache entries have no audio variant, new ones must round-trip
+        let json = serde_json::to_string(&MediaMetadata::Audio(metadata.clone())).unwrap();
+        let deserialized: MediaMetadata = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, MediaMetadata::Audio(metadata));
+    }
+
     #[test]
     fn test_media_file_clone() {
         let original = create_test_media_file();
@@ -284,6 +556,9 @@ mod tests {
             modified: Local::now(),
             hash: Some(String::new().into()),
             metadata: None,
+            via_symlink: false,
+            live_photo_pair: None,
+            content_extension: None,
         };
 
         assert_eq!(file.name, "".into());
@@ -302,6 +577,9 @@ mod tests {
             modified: Local::now(),
             hash: None,
             metadata: None,
+            via_symlink: false,
+            live_photo_pair: None,
+            content_extension: None,
         };
 
         assert_eq!(large_file.size, u64::MAX);
@@ -361,14 +639,108 @@ mod tests {
         let mut set = HashSet::new();
         set.insert(FileType::Image);
         set.insert(FileType::Video);
+        set.insert(FileType::Audio);
         set.insert(FileType::Document);
         set.insert(FileType::Other);
 
         // All types should be unique in the set
-        assert_eq!(set.len(), 4);
+        assert_eq!(set.len(), 5);
 
         // Test that we can find items
         assert!(set.contains(&FileType::Image));
         assert!(set.contains(&FileType::Video));
     }
+
+    #[test]
+    fn test_estimated_memory_counts_heap_data() {
+        let file = create_test_media_file();
+        let bare = MediaFile {
+            hash: None,
+            metadata: None,
+            ..file.clone()
+        };
+
+        assert!(bare.estimated_memory() > std::mem::size_of::<MediaFile>());
+        assert_eq!(
+            file.estimated_memory() - bare.estimated_memory(),
+            "abc123def456".len() + "JPEG".len() + "RGB".len()
+        );
+    }
+
+    #[test]
+    fn test_capture_labels() {
+        let capture = CaptureInfo {
+            make: Some("Canon".into()),
+            model: Some("Canon EOS R5".into()),
+            lens: Some("RF50mm F1.2 L USM".into()),
+            iso: Some(100),
+            aperture: Some(2.8),
+            shutter_speed: Some(0.004),
+            focal_length: Some(50.0),
+        };
+
+        assert_eq!(capture.camera().as_deref(), Some("Canon EOS R5"));
+        assert_eq!(capture.aperture_label().as_deref(), Some("f/2.8"));
+        assert_eq!(capture.shutter_speed_label().as_deref(), Some("1/250 s"));
+        assert_eq!(capture.focal_length_label().as_deref(), Some("50 mm"));
+        assert_eq!(capture.search_text(), "Canon EOS R5 RF50mm F1.2 L USM");
+
+        let long_exposure = CaptureInfo {
+            make: Some("FUJIFILM".into()),
+            model: Some("X-T4".into()),
+            shutter_speed: Some(2.0),
+            ..CaptureInfo::default()
+        };
+        assert_eq!(long_exposure.camera().as_deref(), Some("FUJIFILM X-T4"));
+        assert_eq!(long_exposure.shutter_speed_label().as_deref(), Some("2 s"));
+        assert!(CaptureInfo::default().is_empty());
+    }
+
+    #[test]
+    fn test_image_metadata_without_capture_deserializes() {
+        let json = r#"{"width":800,"height":600,"format":"PNG","color_type":"RGB"}"#;
+        let metadata: ImageMetadata = serde_json::from_str(json).unwrap();
+        assert!(metadata.capture.is_empty());
+        assert!(metadata.has_dimensions());
+        assert!(!ImageMetadata::from_capture("JPG".into(), CaptureInfo::default()).has_dimensions());
+    }
+
+    #[test]
+    fn test_is_screenshot() {
+        let mut file = create_test_media_file();
+        file.metadata = None;
+        assert!(!file.is_screenshot());
+
+        file.name = "Screenshot_20240315-143022.png".into();
+        assert!(file.is_screenshot());
+        file.name = "Bildschirmfoto 2024-03-15 um 14.30.22.png".into();
+        assert!(file.is_screenshot());
+
+        file.name = "IMG_0001.png".into();
+        let png = |width, height, capture| {
+            Some(MediaMetadata::Image(ImageMetadata {
+                width,
+                height,
+                format: "PNG".into(),
+                color_type: "RGBA 8-bit".into(),
+                capture,
+            }))
+        };
+        file.metadata = png(1170, 2532, CaptureInfo::default());
+        assert!(file.is_screenshot());
+        file.metadata = png(1920, 1080, CaptureInfo::default());
+        assert!(file.is_screenshot());
+        file.metadata = png(1000, 1000, CaptureInfo::default());
+        assert!(!file.is_screenshot());
+        let camera = CaptureInfo {
+            make: Some("Apple".into()),
+            ..CaptureInfo::default()
+        };
+        file.metadata = png(1920, 1080, camera);
+        assert!(!file.is_screenshot());
+
+        file.name = "screenshot.mp4".into();
+        file.file_type = FileType::Video;
+        assert!(!file.is_screenshot());
+    }
 }
@@ -0,0 +1,137 @@
+use ahash::AHashMap;
+
+use crate::{CaptureInfo, ExifDates};
+
+/// Focal length ranges the Metadata tab counts photos in, with their upper bound in millimeters
+pub const FOCAL_LENGTH_RANGES: [(&str, f64); 6] = [
+    ("< 24 mm", 24.0),
+    ("24-35 mm", 35.0),
+    ("35-70 mm", 70.0),
+    ("70-135 mm", 135.0),
+    ("135-300 mm", 300.0),
+    ("300+ mm", f64::INFINITY),
+];
+
+/// EXIF data aggregated over the scanned images
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MetadataStats {
+    /// Images read, including those whose EXIF data could not be read
+    pub images: usize,
+    pub unreadable: usize,
+    /// Photos per camera, e.g. "Canon EOS R5"
+    pub cameras: AHashMap<String, usize>,
+    /// Photos per range of [`FOCAL_LENGTH_RANGES`]
+    pub focal_lengths: [usize; FOCAL_LENGTH_RANGES.len()],
+    /// Images without an EXIF capture date, whose date comes from the file system instead
+    pub missing_dates: usize,
+    /// Images recording where they were taken
+    pub with_location: usize,
+}
+
+impl MetadataStats {
+    /// Counts an image with the given EXIF data
+    pub fn add(&mut self, capture: &CaptureInfo, dates: &ExifDates, has_location: bool) {
+        self.images += 1;
+        if let Some(camera) = capture.camera() {
+            *self.cameras.entry(camera).or_default() += 1;
+        }
+        if let Some(range) = capture.focal_length.and_then(focal_length_range) {
+            self.focal_lengths[range] += 1;
+        }
+        if dates.taken.is_none() {
+            self.missing_dates += 1;
+        }
+        if has_location {
+            self.with_location += 1;
+        }
+    }
+
+    /// Counts an image whose EXIF data could not be read
+    pub const fn add_unreadable(&mut self) {
+        self.images += 1;
+        self.unreadable += 1;
+        self.missing_dates += 1;
+    }
+
+    /// Adds the counts of `other`, collected over other images
+    pub fn merge(&mut self, other: Self) {
+        self.images += other.images;
+        self.unreadable += other.unreadable;
+        for (camera, count) in other.cameras {
+            *self.cameras.entry(camera).or_default() += count;
+        }
+        for (total, count) in self.focal_lengths.iter_mut().zip(other.focal_lengths) {
+            *total += count;
+        }
+        self.missing_dates += other.missing_dates;
+        self.with_location += other.with_location;
+    }
+
+    /// The `limit` cameras with the most photos, most used first
+    #[must_use]
+    pub fn top_cameras(&self, limit: usize) -> Vec<(&str, usize)> {
+        let mut cameras: Vec<(&str, usize)> = self
+            .cameras
+            .iter()
+            .map(|(camera, count)| (camera.as_str(), *count))
+            .collect();
+        cameras.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        cameras.truncate(limit);
+        cameras
+    }
+}
+
+/// Index of the range of [`FOCAL_LENGTH_RANGES`] holding `millimeters`
+#[must_use]
+pub fn focal_length_range(millimeters: f64) -> Option<usize> {
+    if !millimeters.is_finite() || millimeters <= 0.0 {
+        return None;
+    }
+    FOCAL_LENGTH_RANGES.iter().position(|(_, upper)| millimeters < *upper)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn capture(model: &str, focal_length: f64) -> CaptureInfo {
+        CaptureInfo {
+            make: Some("Canon".into()),
+            model: Some(model.into()),
+            focal_length: Some(focal_length),
+            ..CaptureInfo::default()
+        }
+    }
+
+    #[test]
+    fn test_counts_cameras_focal_lengths_dates_and_locations() {
+        let dated = ExifDates {
+            taken: NaiveDate::from_ymd_opt(2024, 3, 15).and_then(|date| date.and_hms_opt(10, 0, 0)),
+            ..ExifDates::default()
+        };
+        let mut stats = MetadataStats::default();
+        stats.add(&capture("Canon EOS R5", 50.0), &dated, true);
+        stats.add(&capture("Canon EOS R5", 16.0), &ExifDates::default(), false);
+
+        let mut other = MetadataStats::default();
+        other.add(&capture("EOS M6", 200.0), &dated, true);
+        other.add_unreadable();
+        stats.merge(other);
+
+        assert_eq!(stats.images, 4);
+        assert_eq!(stats.unreadable, 1);
+        assert_eq!(stats.top_cameras(5), [("Canon EOS R5", 2), ("Canon EOS M6", 1)]);
+        assert_eq!(stats.focal_lengths, [1, 0, 1, 0, 1, 0]);
+        assert_eq!(stats.missing_dates, 2);
+        assert_eq!(stats.with_location, 2);
+    }
+
+    #[test]
+    fn test_focal_length_range_bounds() {
+        assert_eq!(focal_length_range(23.9), Some(0));
+        assert_eq!(focal_length_range(24.0), Some(1));
+        assert_eq!(focal_length_range(600.0), Some(5));
+        assert_eq!(focal_length_range(0.0), None);
+    }
+}
@@ -0,0 +1,111 @@
+/// Rectangle of terminal cells, recorded by the UI for mouse hit testing
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ScreenArea {
+    pub x: u16,
+    pub y: u16,
+    pub width: u16,
+    pub height: u16,
+}
+
+impl ScreenArea {
+    #[must_use]
+    pub const fn contains(self, column: u16, row: u16) -> bool {
+        column >= self.x
+            && row >= self.y
+            && (column as u32) < self.x as u32 + self.width as u32
+            && (row as u32) < self.y as u32 + self.height as u32
+    }
+
+    /// Line within the area at the given position, if the position is inside it
+    #[must_use]
+    pub const fn line_at(self, column: u16, row: u16) -> Option<usize> {
+        if self.contains(column, row) {
+            Some((row - self.y) as usize)
+        } else {
+            None
+        }
+    }
+}
+
+/// Clickable parts of the last drawn frame
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MouseTargets {
+    /// Titles of the current view's tab bar, in tab order
+    pub tabs: Vec<ScreenArea>,
+    /// Rows of the dashboard file list or the search results
+    pub file_rows: Option<ScreenArea>,
+    /// Header cells of the dashboard file list, in column order
+    pub file_columns: Vec<ScreenArea>,
+    /// Rows of the duplicate group list and the index of the first group shown
+    pub duplicate_groups: Option<(ScreenArea, usize)>,
+    /// Rows of the files in the selected duplicate group
+    pub duplicate_files: Option<ScreenArea>,
+}
+
+impl MouseTargets {
+    /// Tab whose title is at the given position
+    #[must_use]
+    pub fn tab_at(&self, column: u16, row: u16) -> Option<usize> {
+        self.tabs.iter().position(|area| area.contains(column, row))
+    }
+
+    /// File list column whose header is at the given position
+    #[must_use]
+    pub fn file_column_at(&self, column: u16, row: u16) -> Option<usize> {
+        self.file_columns.iter().position(|area| area.contains(column, row))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AREA: ScreenArea = ScreenArea {
+        x: 2,
+        y: 5,
+        width: 10,
+        height: 3,
+    };
+
+    #[test]
+    fn test_contains_excludes_far_edges() {
+        assert!(AREA.contains(2, 5));
+        assert!(AREA.contains(11, 7));
+        assert!(!AREA.contains(12, 7));
+        assert!(!AREA.contains(11, 8));
+        assert!(!AREA.contains(1, 5));
+    }
+
+    #[test]
+    fn test_line_at_counts_from_area_top() {
+        assert_eq!(AREA.line_at(4, 5), Some(0));
+        assert_eq!(AREA.line_at(4, 7), Some(2));
+        assert_eq!(AREA.line_at(4, 4), None);
+    }
+
+    #[test]
+    fn test_tab_at_finds_clicked_title() {
+        let targets = MouseTargets {
+            tabs: vec![
+                ScreenArea {
+                    x: 1,
+                    y: 1,
+                    width: 8,
+                    height: 1,
+                },
+                ScreenArea {
+                    x: 10,
+                    y: 1,
+                    width: 6,
+                    height: 1,
+                },
+            ],
+            ..MouseTargets::default()
+        };
+
+        assert_eq!(targets.tab_at(3, 1), Some(0));
+        assert_eq!(targets.tab_at(12, 1), Some(1));
+        assert_eq!(targets.tab_at(9, 1), None);
+        assert_eq!(targets.tab_at(12, 2), None);
+    }
+}
@@ -0,0 +1,119 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+/// Most error messages included in a report; the total is kept in the `errors` count
+pub const MAX_REPORTED_ERRORS: usize = 10;
+
+/// Operation whose completion is sent to the notification targets
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotifiedOperation {
+    Scan,
+    Organize,
+    DuplicateCleanup,
+}
+
+impl std::fmt::Display for NotifiedOperation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Scan => write!(f, "scan"),
+            Self::Organize => write!(f, "organize"),
+            Self::DuplicateCleanup => write!(f, "duplicate_cleanup"),
+        }
+    }
+}
+
+/// Summary of a finished operation, sent as JSON to webhooks and MQTT
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OperationReport {
+    pub operation: NotifiedOperation,
+    /// Whether the operation finished without errors
+    pub success: bool,
+    pub timestamp: DateTime<Local>,
+    pub duration_ms: u64,
+    /// Named counts such as `files_found` or `files_organized`
+    pub counts: BTreeMap<String, u64>,
+    /// The first [`MAX_REPORTED_ERRORS`] error messages
+    pub errors: Vec<String>,
+}
+
+impl OperationReport {
+    #[must_use]
+    pub fn new(operation: NotifiedOperation, duration: Duration) -> Self {
+        Self {
+            operation,
+            success: true,
+            timestamp: Local::now(),
+            duration_ms: u64::try_from(duration.as_millis()).unwrap_or(u64::MAX),
+            counts: BTreeMap::new(),
+            errors: Vec::new(),
+        }
+    }
+
+    /// Report of an operation that stopped with `error`
+    #[must_use]
+    pub fn failed(operation: NotifiedOperation, duration: Duration, error: impl Into<String>) -> Self {
+        Self::new(operation, duration).with_errors(vec![error.into()])
+    }
+
+    #[must_use]
+    pub fn with_count(mut self, name: &str, value: u64) -> Self {
+        self.counts.insert(name.to_string(), value);
+        self
+    }
+
+    /// Adds error messages, marking the operation as failed if there are any
+    #[must_use]
+    pub fn with_errors(mut self, mut errors: Vec<String>) -> Self {
+        if errors.is_empty() {
+            return self;
+        }
+        self.success = false;
+        self.counts.insert("errors".to_string(), errors.len() as u64);
+        errors.truncate(MAX_REPORTED_ERRORS);
+        self.errors = errors;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    #[test]
+    fn test_report_without_errors_succeeds() {
+        let report = OperationReport::new(NotifiedOperation::Scan, Duration::from_millis(1500))
+            .with_count("files_found", 42)
+            .with_errors(Vec::new());
+
+        assert!(report.success);
+        assert_eq!(report.duration_ms, 1500);
+        assert_eq!(report.counts.get("files_found"), Some(&42));
+        assert!(!report.counts.contains_key("errors"));
+    }
+
+    #[test]
+    fn test_errors_are_truncated_but_counted() {
+        let errors = (0..25).map(|i| format!("error {i}")).collect();
+        let report = OperationReport::new(NotifiedOperation::Organize, Duration::ZERO).with_errors(errors);
+
+        assert!(!report.success);
+        assert_eq!(report.errors.len(), MAX_REPORTED_ERRORS);
+        assert_eq!(report.counts.get("errors"), Some(&25));
+    }
+
+    #[test]
+    fn test_report_serializes_operation_in_snake_case() {
+        let report = OperationReport::failed(NotifiedOperation::DuplicateCleanup, Duration::ZERO, "disk full");
+        let json = serde_json::to_value(&report).unwrap();
+
+        assert_eq!(json["operation"], "duplicate_cleanup");
+        assert_eq!(json["success"], false);
+        assert_eq!(json["errors"][0], "disk full");
+    }
+}
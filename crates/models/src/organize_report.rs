@@ -0,0 +1,173 @@
+use std::fmt;
+use std::path::PathBuf;
+
+/// What happened to a single file during an organize run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum FileOutcome {
+    Moved,
+    /// Placed under a new name because the target already existed
+    Renamed,
+    Overwritten,
+    /// Left in place because another copy of it was organized
+    SkippedDuplicate,
+    /// Left in place because the target already existed
+    SkippedConflict,
+    /// Left in place because it already is where organizing would put it
+    AlreadyOrganized,
+    /// Left in the source because its content was organized in an earlier run
+    SkippedOrganizedBefore,
+    /// Left in the source because a skip rule, such as a minimum size, excluded it
+    SkippedByRule,
+    /// Left in place because an organize hook refused its destination
+    Vetoed,
+    /// Removed from the source because its content was organized in an earlier run
+    DeletedOrganizedBefore,
+    /// Waiting for a decision in the conflict dialog
+    Deferred,
+    /// Placed, then moved back because too many files of the run failed
+    RolledBack,
+    Error,
+}
+
+impl fmt::Display for FileOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Moved => write!(f, "moved"),
+            Self::Renamed => write!(f, "renamed"),
+            Self::Overwritten => write!(f, "overwritten"),
+            Self::SkippedDuplicate => write!(f, "skipped duplicate"),
+            Self::SkippedConflict => write!(f, "skipped conflict"),
+            Self::AlreadyOrganized => write!(f, "already organized"),
+            Self::SkippedOrganizedBefore => write!(f, "skipped, organized before"),
+            Self::SkippedByRule => write!(f, "skipped by rule"),
+            Self::Vetoed => write!(f, "vetoed by hook"),
+            Self::DeletedOrganizedBefore => write!(f, "deleted, organized before"),
+            Self::Deferred => write!(f, "awaiting decision"),
+            Self::RolledBack => write!(f, "rolled back"),
+            Self::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// A file of an organize run with where it went and how
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrganizedFile {
+    pub source: PathBuf,
+    /// Where the file was placed, or would have been for conflicts
+    pub destination: Option<PathBuf>,
+    pub outcome: FileOutcome,
+    pub error: Option<String>,
+}
+
+impl OrganizedFile {
+    #[must_use]
+    pub const fn new(source: PathBuf, destination: Option<PathBuf>, outcome: FileOutcome) -> Self {
+        Self {
+            source,
+            destination,
+            outcome,
+            error: None,
+        }
+    }
+
+    /// A file left in place by an organize hook, with the hook's reason as its error
+    #[must_use]
+    pub const fn vetoed(source: PathBuf, destination: PathBuf, reason: String) -> Self {
+        Self {
+            source,
+            destination: Some(destination),
+            outcome: FileOutcome::Vetoed,
+            error: Some(reason),
+        }
+    }
+
+    /// A file left in the source by a skip rule, with the rule it broke as its error
+    #[must_use]
+    pub const fn skipped_by_rule(source: PathBuf, reason: String) -> Self {
+        Self {
+            source,
+            destination: None,
+            outcome: FileOutcome::SkippedByRule,
+            error: Some(reason),
+        }
+    }
+
+    #[must_use]
+    pub const fn failed(source: PathBuf, error: String) -> Self {
+        Self {
+            source,
+            destination: None,
+            outcome: FileOutcome::Error,
+            error: Some(error),
+        }
+    }
+}
+
+/// Column the organize report is sorted by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReportSort {
+    #[default]
+    Source,
+    Destination,
+    Outcome,
+}
+
+impl ReportSort {
+    #[must_use]
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Source => Self::Destination,
+            Self::Destination => Self::Outcome,
+            Self::Outcome => Self::Source,
+        }
+    }
+
+    /// Sorts the files by this column, keeping the source path order within equal values
+    pub fn sort(self, files: &mut [OrganizedFile]) {
+        match self {
+            Self::Source => files.sort_by(|a, b| a.source.cmp(&b.source)),
+            Self::Destination => files.sort_by(|a, b| a.destination.cmp(&b.destination).then(a.source.cmp(&b.source))),
+            Self::Outcome => files.sort_by(|a, b| a.outcome.cmp(&b.outcome).then(a.source.cmp(&b.source))),
+        }
+    }
+}
+
+impl fmt::Display for ReportSort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Source => write!(f, "source"),
+            Self::Destination => write!(f, "destination"),
+            Self::Outcome => write!(f, "outcome"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(source: &str, destination: Option<&str>, outcome: FileOutcome) -> OrganizedFile {
+        OrganizedFile::new(source.into(), destination.map(PathBuf::from), outcome)
+    }
+
+    #[test]
+    fn test_report_sort() {
+        let mut files = vec![
+            file("/src/c.jpg", Some("/dest/2024/c.jpg"), FileOutcome::Moved),
+            file("/src/a.jpg", None, FileOutcome::SkippedDuplicate),
+            file("/src/b.jpg", Some("/dest/2023/b (1).jpg"), FileOutcome::Renamed),
+        ];
+        let sources =
+            |files: &[OrganizedFile]| -> Vec<String> { files.iter().map(|f| f.source.display().to_string()).collect() };
+
+        ReportSort::Source.sort(&mut files);
+        assert_eq!(sources(&files), ["/src/a.jpg", "/src/b.jpg", "/src/c.jpg"]);
+        ReportSort::Destination.sort(&mut files);
+        assert_eq!(sources(&files), ["/src/a.jpg", "/src/b.jpg", "/src/c.jpg"]);
+        ReportSort::Outcome.sort(&mut files);
+        assert_eq!(sources(&files), ["/src/c.jpg", "/src/b.jpg", "/src/a.jpg"]);
+
+        assert_eq!(ReportSort::Outcome.next(), ReportSort::Source);
+        assert_eq!(FileOutcome::SkippedDuplicate.to_string(), "skipped duplicate");
+    }
+}
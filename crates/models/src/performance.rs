@@ -0,0 +1,185 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Local};
+
+use crate::NotifiedOperation;
+
+/// Most operations kept in the performance view; the oldest are dropped first
+pub const MAX_PERFORMANCE_ENTRIES: usize = 20;
+
+/// How long one stage of an operation ran and how much it handled
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StageTiming {
+    pub name: String,
+    pub items: usize,
+    /// Bytes the stage read or wrote, 0 when it does not count them
+    pub bytes: u64,
+    pub elapsed: Duration,
+}
+
+impl StageTiming {
+    /// Items handled per second, or `None` when the stage took no measurable time
+    #[allow(clippy::cast_precision_loss)]
+    #[must_use]
+    pub fn items_per_sec(&self) -> Option<f64> {
+        let secs = self.elapsed.as_secs_f64();
+        (self.items > 0 && secs > 0.0).then(|| self.items as f64 / secs)
+    }
+
+    /// Bytes handled per second, or `None` when the stage counted no bytes
+    #[allow(clippy::cast_precision_loss)]
+    #[must_use]
+    pub fn bytes_per_sec(&self) -> Option<f64> {
+        let secs = self.elapsed.as_secs_f64();
+        (self.bytes > 0 && secs > 0.0).then(|| self.bytes as f64 / secs)
+    }
+}
+
+/// Timings of a finished scan or organize run, with the settings that affect its speed
+#[derive(Debug, Clone, PartialEq)]
+pub struct OperationProfile {
+    pub operation: NotifiedOperation,
+    pub finished_at: DateTime<Local>,
+    pub elapsed: Duration,
+    pub worker_threads: usize,
+    pub buffer_size: usize,
+    pub parallel_processing: bool,
+    pub hash_algorithm: String,
+    pub stages: Vec<StageTiming>,
+}
+
+impl OperationProfile {
+    /// The stage called `name`, if the operation ran it
+    #[must_use]
+    pub fn stage(&self, name: &str) -> Option<&StageTiming> {
+        self.stages.iter().find(|stage| stage.name == name)
+    }
+
+    /// Whether `other` ran with the same speed settings
+    #[must_use]
+    pub fn same_settings(&self, other: &Self) -> bool {
+        self.worker_threads == other.worker_threads
+            && self.buffer_size == other.buffer_size
+            && self.parallel_processing == other.parallel_processing
+            && self.hash_algorithm == other.hash_algorithm
+    }
+}
+
+/// Timings of recent operations, newest first
+#[derive(Debug, Clone, Default)]
+pub struct PerformanceLog {
+    pub entries: Vec<OperationProfile>,
+    pub selected: usize,
+}
+
+impl PerformanceLog {
+    /// Adds a finished operation, dropping the oldest past [`MAX_PERFORMANCE_ENTRIES`]
+    pub fn record(&mut self, profile: OperationProfile) {
+        self.entries.insert(0, profile);
+        self.entries.truncate(MAX_PERFORMANCE_ENTRIES);
+        self.selected = 0;
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    #[must_use]
+    pub fn selected_entry(&self) -> Option<&OperationProfile> {
+        self.entries.get(self.selected)
+    }
+
+    /// The run of the same operation before the entry at `index`, to compare against
+    #[must_use]
+    pub fn previous(&self, index: usize) -> Option<&OperationProfile> {
+        let operation = self.entries.get(index)?.operation;
+        self.entries
+            .iter()
+            .skip(index + 1)
+            .find(|profile| profile.operation == operation)
+    }
+
+    pub fn move_selection(&mut self, delta: isize) {
+        self.selected = self
+            .selected
+            .saturating_add_signed(delta)
+            .min(self.entries.len().saturating_sub(1));
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.selected = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(operation: NotifiedOperation, worker_threads: usize) -> OperationProfile {
+        OperationProfile {
+            operation,
+            finished_at: Local::now(),
+            elapsed: Duration::from_secs(4),
+            worker_threads,
+            buffer_size: 8192,
+            parallel_processing: true,
+            hash_algorithm: "blake3".to_string(),
+            stages: vec![StageTiming {
+                name: "Hashing".to_string(),
+                items: 10,
+                bytes: 4_000_000,
+                elapsed: Duration::from_secs(2),
+            }],
+        }
+    }
+
+    #[test]
+    fn test_stage_rates() {
+        let run = profile(NotifiedOperation::Scan, 4);
+        let hashing = run.stage("Hashing");
+        assert_eq!(hashing.and_then(StageTiming::items_per_sec), Some(5.0));
+        assert_eq!(hashing.and_then(StageTiming::bytes_per_sec), Some(2_000_000.0));
+        assert!(run.stage("Organizing").is_none());
+
+        let idle = StageTiming {
+            name: "Walking folders".to_string(),
+            items: 0,
+            bytes: 0,
+            elapsed: Duration::ZERO,
+        };
+        assert_eq!(idle.items_per_sec(), None);
+        assert_eq!(idle.bytes_per_sec(), None);
+    }
+
+    #[test]
+    fn test_record_keeps_newest_first_within_limit() {
+        let mut log = PerformanceLog::default();
+        for workers in 1..=MAX_PERFORMANCE_ENTRIES + 2 {
+            log.record(profile(NotifiedOperation::Scan, workers));
+        }
+        assert_eq!(log.entries.len(), MAX_PERFORMANCE_ENTRIES);
+        assert_eq!(log.entries[0].worker_threads, MAX_PERFORMANCE_ENTRIES + 2);
+        assert_eq!(log.entries.last().map(|p| p.worker_threads), Some(3));
+    }
+
+    #[test]
+    fn test_previous_run_of_the_same_operation() {
+        let mut log = PerformanceLog::default();
+        log.record(profile(NotifiedOperation::Scan, 2));
+        log.record(profile(NotifiedOperation::Organize, 4));
+        log.record(profile(NotifiedOperation::Scan, 8));
+
+        let previous = log.previous(0);
+        assert_eq!(previous.map(|p| p.worker_threads), Some(2));
+        assert!(previous.is_some_and(|previous| !log.entries[0].same_settings(previous)));
+        assert!(log.previous(1).is_none());
+
+        log.move_selection(10);
+        assert_eq!(log.selected, 2);
+        log.clear();
+        assert!(log.is_empty());
+        assert!(log.selected_entry().is_none());
+    }
+}
@@ -0,0 +1,84 @@
+use std::fmt;
+
+/// The operation the preflight checks ran for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PreflightAction {
+    Scan,
+    Organize,
+}
+
+impl fmt::Display for PreflightAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Scan => write!(f, "scan"),
+            Self::Organize => write!(f, "organize"),
+        }
+    }
+}
+
+/// How serious a problem found by the preflight checks is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PreflightSeverity {
+    /// Something worth knowing before the operation runs
+    Info,
+    /// The operation can run, but probably not as intended
+    Warning,
+    /// The operation cannot run until the folders are fixed
+    Blocking,
+}
+
+/// A problem with the source or destination folder found before scanning or organizing
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PreflightIssue {
+    pub severity: PreflightSeverity,
+    pub message: String,
+}
+
+impl PreflightIssue {
+    #[must_use]
+    pub fn info(message: impl Into<String>) -> Self {
+        Self {
+            severity: PreflightSeverity::Info,
+            message: message.into(),
+        }
+    }
+
+    #[must_use]
+    pub fn warning(message: impl Into<String>) -> Self {
+        Self {
+            severity: PreflightSeverity::Warning,
+            message: message.into(),
+        }
+    }
+
+    #[must_use]
+    pub fn blocking(message: impl Into<String>) -> Self {
+        Self {
+            severity: PreflightSeverity::Blocking,
+            message: message.into(),
+        }
+    }
+}
+
+/// Problems found before a scan or organize, waiting for the user to continue or cancel
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Preflight {
+    pub action: PreflightAction,
+    pub issues: Vec<PreflightIssue>,
+}
+
+impl Preflight {
+    /// Whether any issue is a problem rather than information
+    #[must_use]
+    pub fn has_problems(&self) -> bool {
+        self.issues.iter().any(|issue| issue.severity > PreflightSeverity::Info)
+    }
+
+    /// Whether any problem prevents the operation from running
+    #[must_use]
+    pub fn is_blocked(&self) -> bool {
+        self.issues
+            .iter()
+            .any(|issue| issue.severity == PreflightSeverity::Blocking)
+    }
+}
@@ -0,0 +1,26 @@
+/// State of the settings profile picker overlay
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProfilePicker {
+    /// Profile names, the default profile first
+    pub profiles: Vec<String>,
+    /// Name of the profile in use
+    pub active: String,
+    pub selected: usize,
+}
+
+impl ProfilePicker {
+    #[must_use]
+    pub fn new(profiles: Vec<String>, active: String) -> Self {
+        let selected = profiles.iter().position(|name| *name == active).unwrap_or(0);
+        Self {
+            profiles,
+            active,
+            selected,
+        }
+    }
+
+    #[must_use]
+    pub fn selected_profile(&self) -> Option<&str> {
+        self.profiles.get(self.selected).map(String::as_str)
+    }
+}
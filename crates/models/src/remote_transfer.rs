@@ -0,0 +1,35 @@
+use std::path::PathBuf;
+
+use serde::Serialize;
+
+/// Outcome of moving organized files from the staging folder to an rclone remote
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct RemoteTransfer {
+    /// The remote the files were moved to, such as `gdrive:Photos`
+    pub remote: String,
+    /// Local folder the files were organized into before the transfer
+    pub staging: PathBuf,
+    pub transferred: usize,
+    /// Bytes of the transferred files
+    pub bytes: u64,
+    /// Errors rclone reported; files it could not move stay in the staging folder
+    pub errors: Vec<String>,
+}
+
+impl RemoteTransfer {
+    /// Returns whether rclone moved every file without errors
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Explains which files undoing the organize run can still reach
+    #[must_use]
+    pub fn undo_boundary(&self) -> String {
+        format!(
+            "Undo only covers the local stage: files still in {} go back to the source, files on {} are left alone",
+            self.staging.display(),
+            self.remote
+        )
+    }
+}
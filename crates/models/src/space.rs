@@ -0,0 +1,54 @@
+/// Disk space an organize run needs on the destination drive, compared with what is free
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SpaceProjection {
+    /// Bytes of the files on another drive than the destination, which are copied there.
+    /// Files on the same drive are renamed and take no extra space.
+    pub required: u64,
+    /// Number of files that are copied across drives
+    pub copied_files: usize,
+    /// Bytes free on the destination drive, if the platform says
+    pub free: Option<u64>,
+}
+
+impl SpaceProjection {
+    /// Whether the run fits in the free space. Unknown free space is assumed to fit.
+    #[must_use]
+    pub fn fits(&self) -> bool {
+        self.free.is_none_or(|free| self.required <= free)
+    }
+
+    /// Whether the run fits but would use more than 95% of the free space
+    #[must_use]
+    pub fn is_tight(&self) -> bool {
+        self.free
+            .is_some_and(|free| self.required <= free && self.required > free / 20 * 19)
+    }
+
+    /// Bytes left free after the run, if known and the run fits
+    #[must_use]
+    pub fn remaining(&self) -> Option<u64> {
+        self.free.and_then(|free| free.checked_sub(self.required))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fits_and_tight() {
+        let projection = |required, free| SpaceProjection {
+            required,
+            copied_files: 1,
+            free,
+        };
+
+        assert!(projection(50, Some(100)).fits());
+        assert!(!projection(50, Some(100)).is_tight());
+        assert!(projection(97, Some(100)).is_tight());
+        assert!(!projection(101, Some(100)).fits());
+        assert!(!projection(101, Some(100)).is_tight());
+        assert_eq!(projection(101, Some(100)).remaining(), None);
+        assert!(projection(u64::MAX, None).fits());
+    }
+}
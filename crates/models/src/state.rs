@@ -1,7 +1,10 @@
 use std::path::PathBuf;
+use std::sync::Arc;
 
 use chrono::{DateTime, Local};
 
+use crate::{FileOutcome, MediaFile, OrganizedFile, RemoteTransfer};
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum AppState {
     Dashboard,
@@ -12,6 +15,30 @@ pub enum AppState {
     FileDetails(usize),
     DuplicateReview,
     Filters,
+    ActivityLog,
+    EmptyFolders,
+    Verify,
+    Collections,
+    FileBrowser,
+    OrganizeReport,
+    ErrorCenter,
+    Performance,
+    Quarantine,
+    DateFix,
+    ManifestRestore,
+    Archives,
+    CloudUpload,
+    SpaceExplorer,
+    Notifications,
+}
+
+/// How a quit asked for while an operation runs waits for it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PendingQuit {
+    /// Quit once the running operations are finished
+    AfterFinishing,
+    /// Cancel the running operations and quit once they stopped
+    AfterCancelling,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -21,12 +48,105 @@ pub enum InputMode {
     Editing,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+/// Step of the setup wizard shown on first launch
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OnboardingStep {
+    SourceFolder,
+    DestinationFolder,
+    OrganizeBy,
+    ConflictPolicy,
+    InitialScan,
+}
+
+impl OnboardingStep {
+    /// Every step, in the order the wizard goes through them
+    pub const ALL: [Self; 5] = [
+        Self::SourceFolder,
+        Self::DestinationFolder,
+        Self::OrganizeBy,
+        Self::ConflictPolicy,
+        Self::InitialScan,
+    ];
+
+    /// Position of the step, counting from 1
+    #[must_use]
+    pub fn number(self) -> usize {
+        Self::ALL
+            .iter()
+            .position(|&step| step == self)
+            .map_or(0, |index| index + 1)
+    }
+
+    #[must_use]
+    pub fn next(self) -> Option<Self> {
+        Self::ALL.get(self.number()).copied()
+    }
+
+    #[must_use]
+    pub fn previous(self) -> Option<Self> {
+        Self::ALL.get(self.number().checked_sub(2)?).copied()
+    }
+}
+
+/// Files the search view looks through
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SearchScope {
+    /// Files of the last source scan
+    #[default]
+    Source,
+    /// Files of the destination library
+    Destination,
+    Both,
+}
+
+impl SearchScope {
+    /// The scope after this one, wrapping around
+    #[must_use]
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Source => Self::Destination,
+            Self::Destination => Self::Both,
+            Self::Both => Self::Source,
+        }
+    }
+
+    #[must_use]
+    pub const fn includes_source(self) -> bool {
+        matches!(self, Self::Source | Self::Both)
+    }
+
+    #[must_use]
+    pub const fn includes_destination(self) -> bool {
+        matches!(self, Self::Destination | Self::Both)
+    }
+}
+
+impl std::fmt::Display for SearchScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Source => write!(f, "Source"),
+            Self::Destination => write!(f, "Destination"),
+            Self::Both => write!(f, "Both"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum EditingField {
     SourceFolder,
     DestinationFolder,
     WorkerThreads,
     BufferSize,
+    RenameTemplate,
+    TypeMapping,
+    /// A per-folder rule, typed as `folder=target`
+    FolderRule,
+    /// A routing rule, typed as `pattern=target`
+    RoutingRule,
+    /// File name tried against the routing rules, never saved
+    RoutingSample,
+    /// Folder the duplicate review scans on its own
+    DuplicateFolder,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -35,6 +155,13 @@ pub enum DuplicateFocus {
     FileList,
 }
 
+/// List of the space explorer that has the keyboard
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpaceFocus {
+    Folders,
+    Files,
+}
+
 #[derive(Debug, Clone)]
 pub struct ScanResult {
     pub files_found: usize,
@@ -48,9 +175,108 @@ pub struct OrganizeResult {
     pub files_total: usize,
     pub destination: PathBuf,
     pub success: bool,
+    /// Whether the placed files were moved back because too many files failed
+    pub rolled_back: bool,
+    /// Whether the run was cancelled before every file was organized
+    pub cancelled: bool,
     pub timestamp: DateTime<Local>,
     pub skipped_duplicates: usize,
     pub errors: Vec<String>,
+    pub conflicts: ConflictStats,
+    pub pending_conflicts: Vec<FileConflict>,
+    pub non_media: NonMediaStats,
+    /// Files left in the source by the skip rules, per rule
+    pub skipped_by_rules: SkipRuleStats,
+    /// Every file of the run with its destination and outcome
+    pub files: Vec<OrganizedFile>,
+    /// Transfer of the placed files to an rclone remote, once it finished
+    pub remote_transfer: Option<RemoteTransfer>,
+}
+
+impl OrganizeResult {
+    /// Where the files placed by the run ended up, none if it was rolled back
+    #[must_use]
+    pub fn placed_files(&self) -> Vec<PathBuf> {
+        if self.rolled_back {
+            return Vec::new();
+        }
+        self.files
+            .iter()
+            .filter(|file| {
+                matches!(
+                    file.outcome,
+                    FileOutcome::Moved | FileOutcome::Renamed | FileOutcome::Overwritten
+                )
+            })
+            .filter_map(|file| file.destination.clone())
+            .collect()
+    }
+}
+
+/// Per-policy counts of destination conflicts met while organizing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ConflictStats {
+    pub skipped: usize,
+    pub overwritten: usize,
+    pub renamed: usize,
+    pub deferred: usize,
+}
+
+impl ConflictStats {
+    #[must_use]
+    pub const fn total(&self) -> usize {
+        self.skipped + self.overwritten + self.renamed + self.deferred
+    }
+
+    pub const fn merge(&mut self, other: &Self) {
+        self.skipped += other.skipped;
+        self.overwritten += other.overwritten;
+        self.renamed += other.renamed;
+        self.deferred += other.deferred;
+    }
+}
+
+/// Counts of non-media files by how the non-media policy handled them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NonMediaStats {
+    pub ignored: usize,
+    pub unsorted: usize,
+    pub included: usize,
+}
+
+impl NonMediaStats {
+    #[must_use]
+    pub const fn total(&self) -> usize {
+        self.ignored + self.unsorted + self.included
+    }
+
+    pub const fn merge(&mut self, other: &Self) {
+        self.ignored += other.ignored;
+        self.unsorted += other.unsorted;
+        self.included += other.included;
+    }
+}
+
+/// Counts of files left in the source by each organize skip rule.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SkipRuleStats {
+    pub too_small: usize,
+    pub low_resolution: usize,
+    pub too_new: usize,
+}
+
+impl SkipRuleStats {
+    #[must_use]
+    pub const fn total(&self) -> usize {
+        self.too_small + self.low_resolution + self.too_new
+    }
+}
+
+/// A file whose destination was already taken and is waiting for the user to decide.
+#[derive(Debug, Clone)]
+pub struct FileConflict {
+    pub file: Arc<MediaFile>,
+    pub target: PathBuf,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -59,4 +285,6 @@ pub enum FilterFocus {
     SizeRange,
     MediaType,
     RegexPattern,
+    Tags,
+    Extensions,
 }
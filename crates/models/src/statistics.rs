@@ -1,7 +1,9 @@
 use ahash::AHashMap;
 use chrono::Datelike;
 use chrono::{DateTime, Local};
+use std::fmt;
 use std::path::PathBuf;
+use std::str::FromStr;
 use std::sync::Arc;
 
 use crate::MediaFile;
@@ -66,19 +68,63 @@ impl Statistics {
 
         // Find largest files
         let mut sorted_by_size: Vec<_> = files.iter().map(|f| (f.path.clone(), f.size)).collect();
-        sorted_by_size.sort_by(|a, b| b.1.cmp(&a.1));
+        sorted_by_size.sort_by_key(|b| std::cmp::Reverse(b.1));
         self.largest_files = sorted_by_size.into_iter().take(10).collect();
 
         // Find most recent files
         let mut sorted_by_date: Vec<_> = files.iter().map(|f| (f.path.clone(), f.modified)).collect();
-        sorted_by_date.sort_by(|a, b| b.1.cmp(&a.1));
+        sorted_by_date.sort_by_key(|b| std::cmp::Reverse(b.1));
         self.most_recent_files = sorted_by_date.into_iter().take(10).collect();
     }
 
+    /// Takes files that left the library out of the totals and breakdowns.
+    ///
+    /// Cheaper than [`Statistics::update_from_files`] when the remaining files are not at hand.
+    pub fn remove_files(&mut self, files: &[Arc<MediaFile>]) {
+        fn decrement<K: std::hash::Hash + Eq, V: std::ops::SubAssign + PartialEq + Default + Copy>(
+            map: &mut AHashMap<K, V>,
+            key: &K,
+            by: V,
+        ) {
+            if let Some(value) = map.get_mut(key) {
+                *value -= by;
+                if *value == V::default() {
+                    map.remove(key);
+                }
+            }
+        }
+
+        for file in files {
+            self.total_files = self.total_files.saturating_sub(1);
+            self.total_size = self.total_size.saturating_sub(file.size);
+            decrement(&mut self.media_types, &file.file_type.to_string(), 1);
+            decrement(&mut self.type_sizes, &file.file_type.to_string(), file.size);
+            decrement(&mut self.file_types, &file.file_type, 1);
+            decrement(&mut self.files_by_date, &file.modified.format("%Y-%m").to_string(), 1);
+            #[allow(clippy::cast_sign_loss)]
+            decrement(&mut self.files_by_year, &(file.modified.year() as u32), 1);
+            if let Some(ext) = file.path.extension() {
+                decrement(&mut self.files_by_extension, &ext.to_string_lossy().to_lowercase(), 1);
+            }
+        }
+
+        let removed: std::collections::HashSet<&PathBuf> = files.iter().map(|file| &file.path).collect();
+        self.largest_files.retain(|(path, _)| !removed.contains(path));
+        self.most_recent_files.retain(|(path, _)| !removed.contains(path));
+    }
+
     pub fn update_from_scan_results(&mut self, files: &[Arc<MediaFile>], duplicates: &DuplicateStats) {
-        // Reset statistics
-        self.total_files = files.len();
-        self.total_size = files.iter().map(|f| f.size).sum();
+        self.start_scan_results(duplicates);
+        for file in files {
+            self.add_scanned_file(file);
+        }
+    }
+
+    /// Clears the totals of the last scan and counts the duplicates of a new one, whose files
+    /// are then counted one at a time with [`Statistics::add_scanned_file`]
+    pub fn start_scan_results(&mut self, duplicates: &DuplicateStats) {
+        self.total_files = 0;
+        self.total_size = 0;
         self.file_types.clear();
         self.media_types.clear();
         self.type_sizes.clear();
@@ -95,17 +141,94 @@ impl Statistics {
                 }
             }
         }
+    }
 
-        // Count file types
-        for file in files {
-            *self.file_types.entry(file.file_type.clone()).or_insert(0) += 1;
-            *self.media_types.entry(file.file_type.to_string()).or_insert(0) += 1;
-            *self.type_sizes.entry(file.file_type.to_string()).or_insert(0) += file.size;
+    /// Adds a scanned file to the totals and type breakdowns
+    pub fn add_scanned_file(&mut self, file: &MediaFile) {
+        self.total_files += 1;
+        self.total_size += file.size;
+        *self.file_types.entry(file.file_type.clone()).or_insert(0) += 1;
+        *self.media_types.entry(file.file_type.to_string()).or_insert(0) += 1;
+        *self.type_sizes.entry(file.file_type.to_string()).or_insert(0) += file.size;
+    }
+}
+
+/// What had just finished when a statistics snapshot was taken
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapshotKind {
+    Scan,
+    Organize,
+}
+
+impl fmt::Display for SnapshotKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Scan => write!(f, "scan"),
+            Self::Organize => write!(f, "organize"),
         }
     }
 }
 
-// ... existing code ...
+impl FromStr for SnapshotKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "scan" => Ok(Self::Scan),
+            "organize" => Ok(Self::Organize),
+            _ => Err(format!("Unknown snapshot kind: {s}")),
+        }
+    }
+}
+
+/// The statistics after a scan or organize run, kept in the cache to show how the library
+/// grows over time
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatsSnapshot {
+    pub taken: DateTime<Local>,
+    pub kind: SnapshotKind,
+    /// Files, bytes and duplicates the dashboard counted at the time
+    pub files: usize,
+    pub bytes: u64,
+    pub duplicates: usize,
+    /// Files and bytes placed in the library by every organize run so far
+    pub library_files: usize,
+    pub library_bytes: u64,
+    /// Duplicates deleted or replaced with links so far
+    pub duplicates_removed: usize,
+}
+
+impl StatsSnapshot {
+    /// Takes a snapshot of `stats`, carrying the running totals on from `previous`
+    #[must_use]
+    pub fn new(kind: SnapshotKind, stats: &Statistics, previous: Option<&Self>) -> Self {
+        Self {
+            taken: Local::now(),
+            kind,
+            files: stats.total_files,
+            bytes: stats.total_size,
+            duplicates: stats.duplicate_count,
+            library_files: previous.map_or(0, |previous| previous.library_files),
+            library_bytes: previous.map_or(0, |previous| previous.library_bytes),
+            duplicates_removed: previous.map_or(0, |previous| previous.duplicates_removed),
+        }
+    }
+
+    /// Adds the files an organize run placed in the library
+    #[must_use]
+    pub const fn with_organized(mut self, files: usize, bytes: u64) -> Self {
+        self.library_files += files;
+        self.library_bytes += bytes;
+        self
+    }
+
+    /// Adds the duplicates removed since the previous snapshot
+    #[must_use]
+    pub const fn with_duplicates_removed(mut self, removed: usize) -> Self {
+        self.duplicates_removed += removed;
+        self
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -134,6 +257,9 @@ mod tests {
             modified,
             hash: None,
             metadata: None,
+            via_symlink: false,
+            live_photo_pair: None,
+            content_extension: None,
         })
     }
 
@@ -190,6 +316,33 @@ mod tests {
         assert!(stats.file_types.is_empty());
     }
 
+    #[test]
+    fn test_remove_files_matches_recomputing() {
+        let files = create_test_files();
+        let (removed, kept) = files.split_at(2);
+
+        let mut stats = Statistics::new();
+        stats.update_from_files(&files);
+        stats.remove_files(removed);
+
+        let mut expected = Statistics::new();
+        expected.update_from_files(kept);
+
+        assert_eq!(stats.total_files, expected.total_files);
+        assert_eq!(stats.total_size, expected.total_size);
+        assert_eq!(stats.media_types, expected.media_types);
+        assert_eq!(stats.type_sizes, expected.type_sizes);
+        assert_eq!(stats.files_by_date, expected.files_by_date);
+        assert_eq!(stats.files_by_year, expected.files_by_year);
+        assert_eq!(stats.files_by_extension, expected.files_by_extension);
+        assert!(
+            stats
+                .largest_files
+                .iter()
+                .all(|(path, _)| kept.iter().any(|file| &file.path == path))
+        );
+    }
+
     #[test]
     fn test_update_from_files() {
         let mut stats = Statistics::new();
@@ -327,6 +480,27 @@ mod tests {
         assert_eq!(stats.duplicate_size, 1024 * 1024 * 20); // 20MB
     }
 
+    #[test]
+    fn test_snapshots_carry_running_totals() {
+        let mut stats = Statistics::new();
+        stats.update_from_files(&create_test_files());
+
+        let first = StatsSnapshot::new(SnapshotKind::Scan, &stats, None).with_duplicates_removed(2);
+        assert_eq!((first.files, first.bytes), (5, stats.total_size));
+        assert_eq!((first.library_files, first.duplicates_removed), (0, 2));
+
+        let second = StatsSnapshot::new(SnapshotKind::Organize, &Statistics::new(), Some(&first))
+            .with_organized(5, 1024)
+            .with_duplicates_removed(1);
+        assert_eq!(second.files, 0);
+        assert_eq!((second.library_files, second.library_bytes), (5, 1024));
+        assert_eq!(second.duplicates_removed, 3);
+
+        assert_eq!("organize".parse::<SnapshotKind>(), Ok(SnapshotKind::Organize));
+        assert_eq!(SnapshotKind::Scan.to_string(), "scan");
+        assert!("sync".parse::<SnapshotKind>().is_err());
+    }
+
     #[test]
     fn test_empty_files() {
         let mut stats = Statistics::new();
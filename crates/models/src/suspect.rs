@@ -0,0 +1,66 @@
+use std::fmt;
+use std::path::PathBuf;
+
+/// Why a scanned file was set aside instead of being organized
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SuspectReason {
+    /// The file has no content at all
+    Empty,
+    /// The file could not be opened or read
+    Unreadable(String),
+    /// The file does not start with the header of the format its extension promises, nor of
+    /// any other known format
+    UnknownHeader { expected: &'static str },
+    /// The header is of a known format other than the one its extension promises
+    ExtensionMismatch { extension: String, detected: &'static str },
+    /// The header is intact, but the image cannot be decoded, e.g. because it was cut short
+    Undecodable(String),
+}
+
+impl fmt::Display for SuspectReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "empty file"),
+            Self::Unreadable(error) => write!(f, "unreadable: {error}"),
+            Self::UnknownHeader { expected } => write!(f, "no {expected} header"),
+            Self::ExtensionMismatch { extension, detected } => {
+                write!(f, "{detected} content with a .{extension} extension")
+            }
+            Self::Undecodable(error) => write!(f, "cannot be decoded: {error}"),
+        }
+    }
+}
+
+/// A scanned file that failed the header check
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SuspectFile {
+    pub path: PathBuf,
+    pub size: u64,
+    pub reason: SuspectReason,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_suspect_reason_display() {
+        assert_eq!(SuspectReason::Empty.to_string(), "empty file");
+        assert_eq!(
+            SuspectReason::UnknownHeader { expected: "JPEG" }.to_string(),
+            "no JPEG header"
+        );
+        assert_eq!(
+            SuspectReason::ExtensionMismatch {
+                extension: "jpg".to_string(),
+                detected: "HEIF",
+            }
+            .to_string(),
+            "HEIF content with a .jpg extension"
+        );
+        assert_eq!(
+            SuspectReason::Undecodable("unexpected end of file".to_string()).to_string(),
+            "cannot be decoded: unexpected end of file"
+        );
+    }
+}
@@ -0,0 +1,170 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Deserialize, Serialize};
+
+/// Highest star rating a file can have
+pub const MAX_RATING: u8 = 5;
+
+/// Tags and star rating given to a file by the user
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileTags {
+    /// Stars from 1 to [`MAX_RATING`]
+    pub rating: Option<u8>,
+    /// Lowercase tag names
+    pub tags: BTreeSet<String>,
+}
+
+impl FileTags {
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.rating.is_none() && self.tags.is_empty()
+    }
+
+    /// Rates the file with `stars`, or clears the rating if it already has that many.
+    /// Zero stars clears the rating.
+    pub fn toggle_rating(&mut self, stars: u8) {
+        let stars = stars.min(MAX_RATING);
+        self.rating = (stars > 0 && self.rating != Some(stars)).then_some(stars);
+    }
+
+    /// Replaces the tags with the comma-separated names in `input`
+    pub fn set_tags_from_input(&mut self, input: &str) {
+        self.tags = parse_tags(input);
+    }
+
+    /// Tags as they are typed when editing them, e.g. "beach, family"
+    #[must_use]
+    pub fn tags_input(&self) -> String {
+        self.tags.iter().map(String::as_str).collect::<Vec<_>>().join(", ")
+    }
+
+    /// Rating drawn as filled and empty stars, e.g. "★★★☆☆", or an empty string if unrated
+    #[must_use]
+    pub fn stars(&self) -> String {
+        self.rating.map_or_else(String::new, |rating| {
+            let filled = usize::from(rating);
+            "★".repeat(filled) + &"☆".repeat(usize::from(MAX_RATING).saturating_sub(filled))
+        })
+    }
+}
+
+/// Splits comma-separated tag names, trimming and lowercasing them and skipping empty ones
+#[must_use]
+pub fn parse_tags(input: &str) -> BTreeSet<String> {
+    input
+        .split(',')
+        .map(|tag| tag.trim().to_lowercase())
+        .filter(|tag| !tag.is_empty())
+        .collect()
+}
+
+/// How tags and ratings are spread over a set of files
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TagSummary {
+    /// Files with at least one tag or a rating
+    pub tagged_files: usize,
+    /// Tag names with the number of files carrying them, most used first
+    pub tags: Vec<(String, usize)>,
+    /// Number of files rated with one to five stars, by rating minus one
+    pub ratings: [usize; MAX_RATING as usize],
+}
+
+impl TagSummary {
+    #[must_use]
+    pub fn new<'a>(files: impl IntoIterator<Item = &'a FileTags>) -> Self {
+        let mut summary = Self::default();
+        let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+        for file in files {
+            if file.is_empty() {
+                continue;
+            }
+            summary.tagged_files += 1;
+            for tag in &file.tags {
+                *counts.entry(tag).or_default() += 1;
+            }
+            if let Some(slot) = file
+                .rating
+                .and_then(|rating| summary.ratings.get_mut(usize::from(rating).wrapping_sub(1)))
+            {
+                *slot += 1;
+            }
+        }
+
+        summary.tags = counts
+            .into_iter()
+            .map(|(tag, count)| (tag.to_string(), count))
+            .collect();
+        // Stable sort keeps tags with equal counts in alphabetical order
+        summary.tags.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+        summary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tagged(rating: Option<u8>, tags: &str) -> FileTags {
+        FileTags {
+            rating,
+            tags: parse_tags(tags),
+        }
+    }
+
+    #[test]
+    fn test_parse_tags_normalizes_names() {
+        let tags = parse_tags(" Beach, family ,, BEACH,summer 2024 ");
+        assert_eq!(
+            tags.into_iter().collect::<Vec<_>>(),
+            vec!["beach", "family", "summer 2024"]
+        );
+    }
+
+    #[test]
+    fn test_toggle_rating() {
+        let mut tags = FileTags::default();
+        tags.toggle_rating(4);
+        assert_eq!(tags.rating, Some(4));
+        assert_eq!(tags.stars(), "★★★★☆");
+
+        tags.toggle_rating(4);
+        assert_eq!(tags.rating, None);
+        assert_eq!(tags.stars(), "");
+
+        tags.toggle_rating(9);
+        assert_eq!(tags.rating, Some(MAX_RATING));
+        tags.toggle_rating(0);
+        assert!(tags.is_empty());
+    }
+
+    #[test]
+    fn test_tags_input_round_trips() {
+        let mut tags = tagged(None, "family, beach");
+        assert_eq!(tags.tags_input(), "beach, family");
+
+        tags.set_tags_from_input(&tags.tags_input());
+        assert_eq!(tags, tagged(None, "beach, family"));
+    }
+
+    #[test]
+    fn test_summary_counts_tags_and_ratings() {
+        let files = [
+            tagged(Some(5), "beach, family"),
+            tagged(Some(3), "beach"),
+            tagged(None, "family, zoo"),
+            FileTags::default(),
+        ];
+        let summary = TagSummary::new(&files);
+
+        assert_eq!(summary.tagged_files, 3);
+        assert_eq!(
+            summary.tags,
+            vec![
+                ("beach".to_string(), 2),
+                ("family".to_string(), 2),
+                ("zoo".to_string(), 1)
+            ]
+        );
+        assert_eq!(summary.ratings, [0, 0, 1, 0, 1]);
+    }
+}
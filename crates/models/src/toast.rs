@@ -0,0 +1,195 @@
+use std::fmt;
+
+use chrono::{DateTime, Duration, Local};
+
+/// Most toasts kept in the history; the oldest are dropped first
+pub const MAX_TOASTS: usize = 50;
+
+/// Most toasts shown on screen at once
+pub const MAX_VISIBLE_TOASTS: usize = 4;
+
+/// How serious the result a toast reports is, which decides how long it stays up
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToastSeverity {
+    /// Something started or is under way
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl ToastSeverity {
+    /// How long toasts of this severity stay on screen
+    #[must_use]
+    pub const fn lifetime(self) -> Duration {
+        match self {
+            Self::Info => Duration::seconds(3),
+            Self::Success => Duration::seconds(5),
+            Self::Warning => Duration::seconds(8),
+            Self::Error => Duration::seconds(12),
+        }
+    }
+}
+
+impl fmt::Display for ToastSeverity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Info => write!(f, "info"),
+            Self::Success => write!(f, "success"),
+            Self::Warning => write!(f, "warning"),
+            Self::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// The result of an operation, shown for a while and kept in the history
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Toast {
+    /// When the toast was last raised
+    pub timestamp: DateTime<Local>,
+    pub severity: ToastSeverity,
+    pub message: String,
+    /// Times the same message was raised in a row
+    pub repeats: usize,
+    /// Taken off the screen before it expired
+    pub dismissed: bool,
+}
+
+impl Toast {
+    /// Whether the toast is still on screen at `now`
+    #[must_use]
+    pub fn is_visible(&self, now: DateTime<Local>) -> bool {
+        !self.dismissed && now - self.timestamp < self.severity.lifetime()
+    }
+}
+
+/// Toasts of recent results, newest first.
+///
+/// Toasts expire on their own instead of being replaced by the next message or cleared by the
+/// next key, so several results can be on screen together without taking the keyboard.
+#[derive(Debug, Clone, Default)]
+pub struct Toasts {
+    pub entries: Vec<Toast>,
+    pub selected: usize,
+}
+
+impl Toasts {
+    /// Raises a toast at `now`, dropping the oldest past [`MAX_TOASTS`].
+    ///
+    /// Raising the message of the newest toast again while it is on screen puts it back up
+    /// and counts the repeat instead of adding another toast.
+    pub fn push(&mut self, severity: ToastSeverity, message: impl Into<String>, now: DateTime<Local>) {
+        let message = message.into();
+        if let Some(newest) = self.entries.first_mut() {
+            if newest.severity == severity && newest.message == message && newest.is_visible(now) {
+                newest.timestamp = now;
+                newest.repeats += 1;
+                return;
+            }
+        }
+        self.entries.insert(
+            0,
+            Toast {
+                timestamp: now,
+                severity,
+                message,
+                repeats: 1,
+                dismissed: false,
+            },
+        );
+        self.entries.truncate(MAX_TOASTS);
+        self.selected = 0;
+    }
+
+    /// The toasts on screen at `now`, newest first and at most [`MAX_VISIBLE_TOASTS`]
+    pub fn visible(&self, now: DateTime<Local>) -> impl Iterator<Item = &Toast> {
+        self.entries
+            .iter()
+            .filter(move |toast| toast.is_visible(now))
+            .take(MAX_VISIBLE_TOASTS)
+    }
+
+    /// Takes every toast off the screen, keeping them in the history
+    pub fn dismiss_all(&mut self) {
+        for toast in &mut self.entries {
+            toast.dismissed = true;
+        }
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn move_selection(&mut self, delta: isize) {
+        self.selected = self
+            .selected
+            .saturating_add_signed(delta)
+            .min(self.entries.len().saturating_sub(1));
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.selected = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toasts_expire_by_severity() {
+        let now = Local::now();
+        let mut toasts = Toasts::default();
+        toasts.push(ToastSeverity::Error, "Organize failed", now);
+        toasts.push(ToastSeverity::Success, "Scanned 10 files", now + Duration::seconds(1));
+
+        let shown: Vec<&str> = toasts
+            .visible(now + Duration::seconds(2))
+            .map(|t| t.message.as_str())
+            .collect();
+        assert_eq!(shown, ["Scanned 10 files", "Organize failed"]);
+
+        // The success toast is gone while the error is still up
+        let later = now + Duration::seconds(7);
+        let shown: Vec<&str> = toasts.visible(later).map(|t| t.message.as_str()).collect();
+        assert_eq!(shown, ["Organize failed"]);
+        assert_eq!(toasts.visible(now + Duration::seconds(13)).count(), 0);
+        assert_eq!(toasts.len(), 2);
+
+        toasts.dismiss_all();
+        assert_eq!(toasts.visible(later).count(), 0);
+        assert_eq!(toasts.len(), 2);
+    }
+
+    #[test]
+    fn test_repeats_and_history_limit() {
+        let now = Local::now();
+        let mut toasts = Toasts::default();
+        toasts.push(ToastSeverity::Info, "Calculating...", now);
+        toasts.push(ToastSeverity::Info, "Calculating...", now + Duration::seconds(2));
+        assert_eq!(toasts.len(), 1);
+        assert_eq!(toasts.entries[0].repeats, 2);
+        // Once it expired the same message is a new toast
+        toasts.push(ToastSeverity::Info, "Calculating...", now + Duration::seconds(10));
+        assert_eq!(toasts.len(), 2);
+
+        for i in 0..MAX_TOASTS {
+            toasts.push(ToastSeverity::Success, format!("Done {i}"), now);
+        }
+        assert_eq!(toasts.len(), MAX_TOASTS);
+        assert_eq!(toasts.entries[0].message, format!("Done {}", MAX_TOASTS - 1));
+        assert_eq!(toasts.visible(now).count(), MAX_VISIBLE_TOASTS);
+
+        toasts.move_selection(100);
+        assert_eq!(toasts.selected, MAX_TOASTS - 1);
+        toasts.clear();
+        assert!(toasts.is_empty());
+    }
+}
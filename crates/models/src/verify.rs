@@ -0,0 +1,84 @@
+use std::path::PathBuf;
+
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+/// The size, modification time and content hash recorded for a library file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndexedFile {
+    pub size: u64,
+    pub modified: DateTime<Local>,
+    pub sha256: String,
+}
+
+/// What went wrong with a file during library verification
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VerifyIssueKind {
+    /// The file is in the index but no longer on disk
+    Missing,
+    /// The content changed along with the modification time
+    Modified,
+    /// The content changed while size and modification time did not, or it could not be read
+    Corrupted,
+}
+
+impl std::fmt::Display for VerifyIssueKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Missing => write!(f, "Missing"),
+            Self::Modified => write!(f, "Modified"),
+            Self::Corrupted => write!(f, "Corrupted"),
+        }
+    }
+}
+
+/// A single file that failed verification
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerifyIssue {
+    /// Path relative to the library root
+    pub path: PathBuf,
+    pub kind: VerifyIssueKind,
+    pub expected: IndexedFile,
+    /// What is on disk now, if the file could be read
+    pub actual: Option<IndexedFile>,
+    /// Why the file could not be read
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// The outcome of re-hashing a library against its stored index
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerifyReport {
+    pub library: PathBuf,
+    pub timestamp: DateTime<Local>,
+    /// Indexed files that were checked
+    pub checked: usize,
+    /// Files seen for the first time and added to the index
+    pub added: usize,
+    pub issues: Vec<VerifyIssue>,
+}
+
+impl VerifyReport {
+    #[must_use]
+    pub fn new(library: PathBuf) -> Self {
+        Self {
+            library,
+            timestamp: Local::now(),
+            checked: 0,
+            added: 0,
+            issues: Vec::new(),
+        }
+    }
+
+    /// Number of issues of the given kind
+    #[must_use]
+    pub fn count(&self, kind: VerifyIssueKind) -> usize {
+        self.issues.iter().filter(|issue| issue.kind == kind).count()
+    }
+
+    #[must_use]
+    pub fn is_clean(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
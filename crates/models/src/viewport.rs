@@ -0,0 +1,104 @@
+use std::ops::Range;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Rows assumed before a list has been drawn for the first time
+const DEFAULT_ROWS: usize = 20;
+
+/// Visible part of a list that shows as many rows as fit its area.
+///
+/// The first visible row follows from the selection, which stays centered unless the list is
+/// scrolled to its start or end. The row count is recorded while the list is drawn, which
+/// only has shared access to the app state, so it is stored atomically.
+#[derive(Debug, Default)]
+pub struct ListViewport {
+    rows: AtomicUsize,
+}
+
+impl ListViewport {
+    /// Number of rows that fit the list's area when it was last drawn
+    #[must_use]
+    pub fn rows(&self) -> usize {
+        match self.rows.load(Ordering::Relaxed) {
+            0 => DEFAULT_ROWS,
+            rows => rows,
+        }
+    }
+
+    /// Records how many rows fit the list's area.
+    pub fn set_rows(&self, rows: usize) {
+        self.rows.store(rows.max(1), Ordering::Relaxed);
+    }
+
+    /// First visible row of a list of `len` rows with `selected` selected
+    #[must_use]
+    pub fn offset(&self, selected: usize, len: usize) -> usize {
+        let rows = self.rows();
+        selected.saturating_sub(rows / 2).min(len.saturating_sub(rows))
+    }
+
+    /// Rows shown for a list of `len` rows with `selected` selected
+    #[must_use]
+    pub fn visible(&self, selected: usize, len: usize) -> Range<usize> {
+        let offset = self.offset(selected, len);
+        offset..(offset + self.rows()).min(len)
+    }
+
+    /// Row selected after moving `delta` rows from `selected`, kept within `len` rows
+    #[must_use]
+    pub fn step(selected: usize, delta: isize, len: usize) -> usize {
+        selected.saturating_add_signed(delta).min(len.saturating_sub(1))
+    }
+
+    /// Rows moved by Page Up and Page Down, keeping one row of context
+    #[must_use]
+    pub fn page(&self) -> usize {
+        self.rows().saturating_sub(1).max(1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn viewport(rows: usize) -> ListViewport {
+        let viewport = ListViewport::default();
+        viewport.set_rows(rows);
+        viewport
+    }
+
+    #[test]
+    fn test_rows_default_until_drawn() {
+        let viewport = ListViewport::default();
+        assert_eq!(viewport.rows(), DEFAULT_ROWS);
+
+        viewport.set_rows(7);
+        assert_eq!(viewport.rows(), 7);
+        assert_eq!(viewport.page(), 6);
+    }
+
+    #[test]
+    fn test_selection_stays_centered() {
+        let viewport = viewport(10);
+
+        assert_eq!(viewport.visible(50, 100), 45..55);
+        assert_eq!(viewport.visible(51, 100), 46..56);
+    }
+
+    #[test]
+    fn test_offset_is_clamped_at_both_ends() {
+        let viewport = viewport(10);
+
+        assert_eq!(viewport.visible(2, 100), 0..10);
+        assert_eq!(viewport.visible(98, 100), 90..100);
+        assert_eq!(viewport.visible(3, 4), 0..4);
+        assert_eq!(viewport.visible(0, 0), 0..0);
+    }
+
+    #[test]
+    fn test_step_stays_within_list() {
+        assert_eq!(ListViewport::step(5, -10, 100), 0);
+        assert_eq!(ListViewport::step(5, 3, 100), 8);
+        assert_eq!(ListViewport::step(98, 3, 100), 99);
+        assert_eq!(ListViewport::step(0, 1, 0), 0);
+    }
+}
@@ -0,0 +1,221 @@
+//! Accessible rendering: ASCII labels instead of emoji and high-contrast basic colors instead
+//! of RGB ones.
+//!
+//! The views are drawn as usual and the finished frame is rewritten cell by cell, so every
+//! view, message and overlay is covered without each of them knowing about the mode.
+
+use std::sync::OnceLock;
+
+use ratatui::{
+    buffer::Buffer,
+    style::{Color, Modifier},
+};
+use unicode_width::UnicodeWidthStr;
+use visualvault_app::App;
+
+/// ASCII labels of the emoji the views use, as wide as the emoji
+const LABELS: &[(&str, &str)] = &[
+    ("✅", "OK"),
+    ("✔", "OK"),
+    ("❌", "XX"),
+    ("⛔", "XX"),
+    ("✖", "XX"),
+    ("⚠", "!!"),
+    ("🚨", "!!"),
+    ("❓", "??"),
+    ("❔", "??"),
+    ("⬜", "[]"),
+    ("⚪", "()"),
+    ("🔘", "()"),
+    ("📁", "D/"),
+    ("📂", "D/"),
+    ("🗂", "D/"),
+    ("🗄", "D/"),
+    ("📄", "F "),
+    ("📜", "F "),
+    ("📝", "F "),
+    ("📋", "F "),
+    ("🧾", "F "),
+    ("📷", "Im"),
+    ("📸", "Im"),
+    ("🖼", "Im"),
+    ("🎬", "Vi"),
+    ("🎵", "Au"),
+    ("🔍", "/?"),
+    ("🔎", "/?"),
+    ("📊", "##"),
+    ("📈", "##"),
+    ("💾", "HD"),
+    ("💿", "HD"),
+    ("🔄", "<>"),
+    ("🔀", "<>"),
+    ("⏰", "@ "),
+    ("🕒", "@ "),
+    ("⏱", "@ "),
+    ("⏳", ".."),
+    ("📦", "Ar"),
+    ("📅", "Dt"),
+    ("📆", "Dt"),
+    ("⚙", "Cf"),
+    ("🔧", "Cf"),
+    ("🗑", "Rm"),
+    ("🧹", "Rm"),
+    ("🏷", "Tg"),
+    ("📎", "Ln"),
+    ("🔗", "Ln"),
+    ("☁", "Cl"),
+    ("📥", "In"),
+    ("📤", "Up"),
+    ("💡", "i "),
+    ("🚀", ">>"),
+    ("🔒", "Lk"),
+    ("🔏", "Lk"),
+    ("🛡", "Lk"),
+    ("🔔", "Nt"),
+    ("⭐", "* "),
+    ("✏", "Ed"),
+    ("♿", "A "),
+];
+
+/// Label of emoji missing from [`LABELS`]
+const FALLBACK_LABEL: &str = "* ";
+
+/// Returns whether the accessible rendering is used, following the settings being edited so a
+/// change shows right away.
+pub fn is_on(app: &App) -> bool {
+    static ENVIRONMENT: OnceLock<(Option<String>, Option<String>)> = OnceLock::new();
+    let (term, no_color) = ENVIRONMENT.get_or_init(|| (std::env::var("TERM").ok(), std::env::var("NO_COLOR").ok()));
+    app.settings_cache
+        .accessibility
+        .is_on(term.as_deref(), no_color.as_deref())
+}
+
+/// Rewrites a drawn frame for the accessible mode.
+pub fn apply(buf: &mut Buffer) {
+    let area = buf.area;
+    for y in area.top()..area.bottom() {
+        let mut x = area.left();
+        while x < area.right() {
+            x += replace_emoji(buf, x, y);
+        }
+    }
+    for cell in &mut buf.content {
+        let lit_bg = high_contrast_bg(cell.bg);
+        cell.fg = match lit_bg {
+            // Text on a colored background is black or white, whichever stands out more
+            Some(Color::Yellow | Color::Green | Color::Cyan) => Color::Black,
+            Some(_) => Color::White,
+            None => high_contrast_fg(cell.fg),
+        };
+        cell.bg = lit_bg.unwrap_or(match cell.bg {
+            Color::Rgb(..) => Color::Reset,
+            bg => bg,
+        });
+        cell.modifier
+            .remove(Modifier::SLOW_BLINK | Modifier::RAPID_BLINK | Modifier::DIM);
+    }
+}
+
+/// Replaces the emoji at `x` with its label, filling the cells it covered, and returns how
+/// many cells to move on.
+fn replace_emoji(buf: &mut Buffer, x: u16, y: u16) -> u16 {
+    let cell = &buf[(x, y)];
+    if !is_emoji(cell.symbol()) {
+        return 1;
+    }
+    let width = u16::try_from(cell.symbol().width().max(1)).unwrap_or(1);
+    let label = LABELS
+        .iter()
+        .find(|(emoji, _)| cell.symbol().starts_with(emoji))
+        .map_or(FALLBACK_LABEL, |(_, label)| label);
+    // Cells hidden behind a wide emoji were reset, so they take its style
+    let style = cell.style();
+    let right = buf.area.right();
+    for (offset, c) in (0..width).zip(label.chars().chain(std::iter::repeat(' '))) {
+        if x + offset < right {
+            buf[(x + offset, y)].set_char(c).set_style(style);
+        }
+    }
+    width
+}
+
+/// Returns whether `symbol` is drawn as an emoji, rather than as text
+fn is_emoji(symbol: &str) -> bool {
+    let Some(first) = symbol.chars().next() else {
+        return false;
+    };
+    // Pictographs, emoji presentation selectors and the wide symbols of the technical,
+    // miscellaneous symbols and dingbats blocks; wide letters such as CJK stay as they are
+    first >= '\u{1F000}'
+        || symbol.contains('\u{FE0F}')
+        || (('\u{2300}'..='\u{2BFF}').contains(&first) && symbol.width() == 2)
+}
+
+/// Brightness and hue of `color`, or `None` for colors that are already basic ones
+fn analyze(color: Color) -> Option<(u8, Option<Color>)> {
+    let Color::Rgb(r, g, b) = color else {
+        return None;
+    };
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let spread = f32::from(max - min);
+    // Grays, and colors so pale they read as gray
+    if max == 0 || spread / f32::from(max) < 0.42 {
+        return Some((max, None));
+    }
+    let (red, green, blue) = (f32::from(r), f32::from(g), f32::from(b));
+    let hue = if max == r {
+        60.0 * ((green - blue) / spread).rem_euclid(6.0)
+    } else if max == g {
+        60.0 * ((blue - red) / spread + 2.0)
+    } else {
+        60.0 * ((red - green) / spread + 4.0)
+    };
+    let hue = match hue {
+        h if !(30.0..330.0).contains(&h) => Color::Red,
+        h if h < 75.0 => Color::Yellow,
+        h if h < 165.0 => Color::Green,
+        h if h < 200.0 => Color::Cyan,
+        h if h < 255.0 => Color::Blue,
+        _ => Color::Magenta,
+    };
+    Some((max, Some(hue)))
+}
+
+/// Bright basic color for text on the terminal's own background
+fn high_contrast_fg(color: Color) -> Color {
+    match analyze(color) {
+        None if color == Color::DarkGray => Color::Gray,
+        None => color,
+        Some((brightness, None)) => {
+            if brightness >= 200 {
+                Color::White
+            } else {
+                Color::Gray
+            }
+        }
+        Some((_, Some(hue))) => match hue {
+            Color::Red => Color::LightRed,
+            Color::Yellow => Color::LightYellow,
+            Color::Green => Color::LightGreen,
+            Color::Cyan => Color::LightCyan,
+            Color::Blue => Color::LightBlue,
+            _ => Color::LightMagenta,
+        },
+    }
+}
+
+/// Basic color for a background that stands out, such as a selection, or `None` for the dark
+/// backgrounds that give way to the terminal's own
+fn high_contrast_bg(color: Color) -> Option<Color> {
+    match analyze(color) {
+        Some((brightness, _)) if brightness < 60 => None,
+        // Gray highlights, like the selected row
+        Some((_, None)) => Some(Color::Blue),
+        Some((_, hue)) => hue,
+        None => match color {
+            Color::Reset | Color::Black => None,
+            other => Some(other),
+        },
+    }
+}
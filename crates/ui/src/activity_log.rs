@@ -0,0 +1,106 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph},
+};
+use visualvault_app::App;
+use visualvault_models::ActivityKind;
+
+use super::{ACCENT_COLOR, BACKGROUND_ALT, ERROR_COLOR, MUTED_COLOR, SUCCESS_COLOR, WARNING_COLOR};
+
+pub fn draw(f: &mut Frame, area: Rect, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(3), // Schedule
+            Constraint::Min(0),    // Entries
+        ])
+        .split(area);
+
+    draw_schedule(f, chunks[0], app);
+    draw_entries(f, chunks[1], app);
+}
+
+fn draw_schedule(f: &mut Frame, area: Rect, app: &App) {
+    let schedule = app.scheduler.schedule().map_or_else(
+        || {
+            vec![Span::styled(
+                "No scheduled runs configured",
+                Style::default().fg(MUTED_COLOR),
+            )]
+        },
+        |schedule| {
+            let next = app.scheduler.next_run().map_or_else(String::new, |next| {
+                next.format(" — next run %Y-%m-%d %H:%M").to_string()
+            });
+            vec![
+                Span::styled("⏰ Organize ", Style::default().fg(ACCENT_COLOR)),
+                Span::styled(
+                    schedule.to_string(),
+                    Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(next, Style::default().fg(MUTED_COLOR)),
+            ]
+        },
+    );
+
+    let paragraph = Paragraph::new(Line::from(schedule)).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(MUTED_COLOR)),
+    );
+    f.render_widget(paragraph, area);
+}
+
+fn draw_entries(f: &mut Frame, area: Rect, app: &App) {
+    let lines: Vec<Line> = if app.activity_log.is_empty() {
+        vec![Line::from(Span::styled(
+            "No activity yet",
+            Style::default().fg(MUTED_COLOR).add_modifier(Modifier::ITALIC),
+        ))]
+    } else {
+        // Newest entries first
+        app.activity_log
+            .entries()
+            .iter()
+            .rev()
+            .skip(app.activity_scroll)
+            .map(|entry| {
+                let (icon, color) = match entry.kind {
+                    ActivityKind::Scan => ("🔍", ACCENT_COLOR),
+                    ActivityKind::Organize => ("📁", SUCCESS_COLOR),
+                    ActivityKind::Delete => ("🗑", WARNING_COLOR),
+                    ActivityKind::Rename => ("✏", SUCCESS_COLOR),
+                    ActivityKind::Undo => ("↩", ACCENT_COLOR),
+                    ActivityKind::Redo => ("↻", ACCENT_COLOR),
+                    ActivityKind::Scheduled => ("⏰", SUCCESS_COLOR),
+                    ActivityKind::Verify => ("🛡", ACCENT_COLOR),
+                    ActivityKind::Upload => ("☁", ACCENT_COLOR),
+                    ActivityKind::Error => ("🚨", ERROR_COLOR),
+                };
+                Line::from(vec![
+                    Span::styled(
+                        entry.timestamp.format("%Y-%m-%d %H:%M:%S ").to_string(),
+                        Style::default().fg(MUTED_COLOR),
+                    ),
+                    Span::styled(format!("{icon} "), Style::default().fg(color)),
+                    Span::styled(&entry.message, Style::default().fg(Color::White)),
+                ])
+            })
+            .collect()
+    };
+
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .title(format!(" 📜 Activity Log ({}) ", app.activity_log.len()))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(MUTED_COLOR))
+            .style(Style::default().bg(BACKGROUND_ALT)),
+    );
+    f.render_widget(paragraph, area);
+}
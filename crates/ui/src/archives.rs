@@ -0,0 +1,105 @@
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph},
+};
+use visualvault_app::App;
+use visualvault_utils::format_bytes;
+
+use super::{ACCENT_COLOR, BACKGROUND_ALT, MUTED_COLOR, SUCCESS_COLOR, WARNING_COLOR};
+
+pub fn draw(f: &mut Frame, area: Rect, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Min(0),    // Media in archives
+            Constraint::Length(3), // Help
+        ])
+        .split(area);
+
+    draw_media(f, chunks[0], app);
+    draw_help(f, chunks[1]);
+}
+
+fn draw_media(f: &mut Frame, area: Rect, app: &App) {
+    let source = app.settings_cache.source_folder.as_deref();
+
+    let lines: Vec<Line> = app
+        .archive_media
+        .iter()
+        .enumerate()
+        .skip(app.archive_scroll)
+        .take(usize::from(area.height))
+        .map(|(idx, media)| {
+            let archive = source
+                .and_then(|source| media.archive.strip_prefix(source).ok())
+                .unwrap_or(&media.archive);
+            let marker = if app.selected_archive_media.contains(&idx) {
+                Span::styled("☑ ", Style::default().fg(WARNING_COLOR))
+            } else {
+                Span::styled("☐ ", Style::default().fg(MUTED_COLOR))
+            };
+            let mut spans = vec![
+                marker,
+                Span::styled(format!("{}: ", archive.display()), Style::default().fg(MUTED_COLOR)),
+                Span::styled(media.name.clone(), Style::default().fg(Color::White)),
+                Span::styled(
+                    format!("  {}", format_bytes(media.size)),
+                    Style::default().fg(MUTED_COLOR),
+                ),
+            ];
+            if let Some(modified) = media.modified {
+                spans.push(Span::styled(
+                    format!("  {}", modified.format("%Y-%m-%d %H:%M")),
+                    Style::default().fg(ACCENT_COLOR),
+                ));
+            }
+            if media.extraction_folder().join(&media.name).exists() {
+                spans.push(Span::styled("  ✔ extracted", Style::default().fg(SUCCESS_COLOR)));
+            }
+            let line = Line::from(spans);
+            if idx == app.archive_scroll {
+                line.style(Style::default().add_modifier(Modifier::REVERSED))
+            } else {
+                line
+            }
+        })
+        .collect();
+
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .title(format!(
+                " 📦 Media in Archives ({} selected of {}) ",
+                app.selected_archive_media.len(),
+                app.archive_media.len()
+            ))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(MUTED_COLOR))
+            .style(Style::default().bg(BACKGROUND_ALT)),
+    );
+    f.render_widget(paragraph, area);
+}
+
+fn draw_help(f: &mut Frame, area: Rect) {
+    let help = Paragraph::new(Line::from(vec![
+        Span::styled("Space", Style::default().fg(ACCENT_COLOR)),
+        Span::raw(" - Select | "),
+        Span::styled("a", Style::default().fg(ACCENT_COLOR)),
+        Span::raw(" - All | "),
+        Span::styled("x", Style::default().fg(WARNING_COLOR).add_modifier(Modifier::BOLD)),
+        Span::raw(" - Extract next to the archive (all if none selected) | "),
+        Span::styled("Esc", Style::default().fg(ACCENT_COLOR)),
+        Span::raw(" - Back"),
+    ]))
+    .alignment(Alignment::Center)
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(MUTED_COLOR)),
+    );
+    f.render_widget(help, area);
+}
@@ -0,0 +1,131 @@
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Row, Table},
+};
+use visualvault_app::App;
+use visualvault_models::BatchRename;
+
+use super::{ACCENT_COLOR, BACKGROUND_ALT, ERROR_COLOR, MUTED_COLOR, SUCCESS_COLOR, centered_rect};
+
+/// Draws the batch rename overlay with the pattern and a preview of the new names.
+pub fn draw_batch_rename(f: &mut Frame, app: &App) {
+    let Some(rename) = &app.batch_rename else {
+        return;
+    };
+
+    let area = centered_rect(80, 70, f.area());
+    f.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(format!(" ✏️  Rename {} files ", rename.file_count))
+        .title_style(Style::default().fg(ACCENT_COLOR).add_modifier(Modifier::BOLD))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(ACCENT_COLOR))
+        .style(Style::default().bg(BACKGROUND_ALT));
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([
+            Constraint::Length(3), // Pattern
+            Constraint::Min(3),    // Preview
+            Constraint::Length(2), // Key help
+        ])
+        .split(area);
+
+    draw_pattern(f, chunks[0], rename);
+    draw_preview(f, chunks[1], rename);
+
+    let help = Paragraph::new(vec![
+        Line::from(vec![
+            Span::styled("Enter", Style::default().fg(ACCENT_COLOR).add_modifier(Modifier::BOLD)),
+            Span::raw(" rename  "),
+            Span::styled("Esc", Style::default().fg(ACCENT_COLOR).add_modifier(Modifier::BOLD)),
+            Span::raw(" cancel  "),
+            Span::styled("↑↓", Style::default().fg(ACCENT_COLOR).add_modifier(Modifier::BOLD)),
+            Span::raw(" scroll"),
+        ]),
+        Line::from(Span::styled(
+            "Tokens: {date} {time} {year} {month} {day} {original} {camera} {counter}",
+            Style::default().fg(MUTED_COLOR).add_modifier(Modifier::ITALIC),
+        )),
+    ])
+    .alignment(Alignment::Center);
+    f.render_widget(help, chunks[2]);
+}
+
+fn draw_pattern(f: &mut Frame, area: Rect, rename: &BatchRename) {
+    let (status, color) = rename
+        .error
+        .as_ref()
+        .map_or((String::new(), ACCENT_COLOR), |e| (format!(" {e} "), ERROR_COLOR));
+
+    let pattern = Paragraph::new(Line::from(vec![
+        Span::raw(rename.pattern.as_str()),
+        Span::styled("│", Style::default().fg(ACCENT_COLOR)),
+    ]))
+    .block(
+        Block::default()
+            .title(" Pattern ")
+            .title_bottom(Line::from(Span::styled(status, Style::default().fg(ERROR_COLOR))))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(color)),
+    );
+    f.render_widget(pattern, area);
+}
+
+fn draw_preview(f: &mut Frame, area: Rect, rename: &BatchRename) {
+    let file_name = |path: &std::path::Path| {
+        path.file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default()
+    };
+
+    let rows: Vec<Row> = rename
+        .preview
+        .iter()
+        .skip(rename.scroll)
+        .take(area.height.saturating_sub(3) as usize)
+        .map(|(source, destination)| {
+            Row::new(vec![
+                Span::raw(file_name(source)),
+                Span::styled("→", Style::default().fg(MUTED_COLOR)),
+                Span::styled(file_name(destination), Style::default().fg(SUCCESS_COLOR)),
+            ])
+        })
+        .collect();
+
+    let unchanged = rename.file_count.saturating_sub(rename.preview.len());
+    let title = if unchanged > 0 && rename.error.is_none() {
+        format!(" Preview ({} renamed, {unchanged} unchanged) ", rename.preview.len())
+    } else {
+        format!(" Preview ({} renamed) ", rename.preview.len())
+    };
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Percentage(47),
+            Constraint::Length(2),
+            Constraint::Percentage(47),
+        ],
+    )
+    .header(
+        Row::new(vec!["Current name", "", "New name"])
+            .style(Style::default().fg(ACCENT_COLOR).add_modifier(Modifier::BOLD)),
+    )
+    .block(
+        Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(MUTED_COLOR)),
+    );
+    f.render_widget(table, area);
+}
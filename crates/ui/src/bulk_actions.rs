@@ -0,0 +1,80 @@
+use ratatui::{
+    Frame,
+    layout::Alignment,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+};
+use visualvault_app::App;
+use visualvault_models::BulkAction;
+
+use super::{ACCENT_COLOR, BACKGROUND_ALT, MUTED_COLOR, centered_rect};
+
+/// Draws the bulk action menu for the marked files, or the path prompt of the chosen action.
+pub fn draw_bulk_actions(f: &mut Frame, app: &App) {
+    let Some(menu) = &app.bulk_actions else {
+        return;
+    };
+
+    let area = centered_rect(50, 30, f.area());
+    f.render_widget(Clear, area);
+
+    let key_style = Style::default().fg(ACCENT_COLOR).add_modifier(Modifier::BOLD);
+    let lines = match &menu.pending {
+        None => {
+            let mut lines = vec![Line::from("")];
+            for (key, action) in [
+                ("t", BulkAction::Trash),
+                ("m", BulkAction::Move),
+                ("i", BulkAction::Ignore),
+                ("e", BulkAction::Export),
+                ("c", BulkAction::Collect),
+            ] {
+                lines.push(Line::from(vec![
+                    Span::styled(format!("  {key}  "), key_style),
+                    Span::raw(action.to_string()),
+                ]));
+            }
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                "Trash and move can be undone with Ctrl+U • Esc closes",
+                Style::default().fg(MUTED_COLOR).add_modifier(Modifier::ITALIC),
+            )));
+            lines
+        }
+        Some((action, input)) => {
+            let label = match action {
+                BulkAction::Export => "CSV file:",
+                BulkAction::Collect => "Collection:",
+                _ => "Folder:",
+            };
+            vec![
+                Line::from(""),
+                Line::from(Span::styled(format!("  {action}"), key_style)),
+                Line::from(""),
+                Line::from(vec![
+                    Span::styled(format!("  {label} "), Style::default().fg(MUTED_COLOR)),
+                    Span::raw(input.as_str()),
+                    Span::styled("│", Style::default().fg(ACCENT_COLOR)),
+                ]),
+                Line::from(""),
+                Line::from(Span::styled(
+                    "Enter confirms • Esc goes back",
+                    Style::default().fg(MUTED_COLOR).add_modifier(Modifier::ITALIC),
+                )),
+            ]
+        }
+    };
+
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .title(format!(" ☑ {} selected files ", menu.file_count))
+            .title_alignment(Alignment::Center)
+            .title_style(key_style)
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(ACCENT_COLOR))
+            .style(Style::default().bg(BACKGROUND_ALT)),
+    );
+    f.render_widget(paragraph, area);
+}
@@ -0,0 +1,152 @@
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Gauge, Paragraph},
+};
+use visualvault_app::App;
+
+use super::progress::{stats_text, time_text};
+use super::{ACCENT_COLOR, BACKGROUND_ALT, ERROR_COLOR, MUTED_COLOR, SUCCESS_COLOR, WARNING_COLOR};
+
+pub fn draw(f: &mut Frame, area: Rect, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(7), // Transfer progress
+            Constraint::Min(0),    // Failed files
+            Constraint::Length(3), // Help
+        ])
+        .split(area);
+
+    draw_transfer(f, chunks[0], app);
+    draw_failures(f, chunks[1], app);
+    draw_help(f, chunks[2]);
+}
+
+#[allow(clippy::significant_drop_tightening)]
+fn draw_transfer(f: &mut Frame, area: Rect, app: &App) {
+    let block = Block::default()
+        .title(" ☁ Cloud Upload ")
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(MUTED_COLOR))
+        .style(Style::default().bg(BACKGROUND_ALT));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+    let Ok(progress) = app.cloud_upload_progress.try_read() else {
+        return;
+    };
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1); 5])
+        .split(inner);
+
+    let status = if app.cloud_upload_task.is_some() {
+        Span::styled(progress.message.clone(), Style::default().fg(ACCENT_COLOR))
+    } else {
+        Span::styled("Upload finished", Style::default().fg(SUCCESS_COLOR))
+    };
+    f.render_widget(Paragraph::new(Line::from(status)).alignment(Alignment::Center), rows[0]);
+
+    #[allow(clippy::cast_precision_loss)]
+    let ratio = if progress.total_bytes > 0 {
+        (progress.bytes_processed as f64 / progress.total_bytes as f64).min(1.0)
+    } else {
+        progress.percentage() / 100.0
+    };
+    let gauge = Gauge::default()
+        .gauge_style(Style::default().fg(ACCENT_COLOR).bg(Color::Rgb(40, 40, 40)))
+        .ratio(ratio)
+        .label(format!("{:.0}%", ratio * 100.0))
+        .use_unicode(true);
+    f.render_widget(gauge, rows[1]);
+
+    f.render_widget(
+        Paragraph::new(Line::from(Span::styled(
+            stats_text(&progress),
+            Style::default().fg(WARNING_COLOR),
+        )))
+        .alignment(Alignment::Center),
+        rows[2],
+    );
+    if let Some(file) = &progress.current_file {
+        f.render_widget(
+            Paragraph::new(Line::from(vec![
+                Span::styled("📄 ", Style::default().fg(ACCENT_COLOR)),
+                Span::styled(file.as_str(), Style::default().fg(Color::White)),
+            ]))
+            .alignment(Alignment::Center),
+            rows[3],
+        );
+    }
+    f.render_widget(
+        Paragraph::new(Line::from(Span::styled(
+            time_text(&progress),
+            Style::default().fg(SUCCESS_COLOR),
+        )))
+        .alignment(Alignment::Center),
+        rows[4],
+    );
+}
+
+fn draw_failures(f: &mut Frame, area: Rect, app: &App) {
+    let failed = app
+        .cloud_upload_report
+        .as_ref()
+        .map_or(&[][..], |report| &report.failed);
+    let lines: Vec<Line> = if failed.is_empty() {
+        vec![Line::from(Span::styled(
+            if app.cloud_upload_task.is_some() {
+                "Failed files are listed here once the upload finishes"
+            } else {
+                "Every file was uploaded"
+            },
+            Style::default().fg(MUTED_COLOR).add_modifier(Modifier::ITALIC),
+        ))]
+    } else {
+        failed
+            .iter()
+            .skip(app.cloud_upload_scroll)
+            .take(usize::from(area.height))
+            .map(|failure| {
+                Line::from(vec![
+                    Span::styled("✖ ", Style::default().fg(ERROR_COLOR)),
+                    Span::styled(failure.path.display().to_string(), Style::default().fg(Color::White)),
+                    Span::styled(format!("  {}", failure.error), Style::default().fg(WARNING_COLOR)),
+                ])
+            })
+            .collect()
+    };
+
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .title(format!(" ✖ Failed Uploads ({}) ", failed.len()))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(MUTED_COLOR))
+            .style(Style::default().bg(BACKGROUND_ALT)),
+    );
+    f.render_widget(paragraph, area);
+}
+
+fn draw_help(f: &mut Frame, area: Rect) {
+    let help = Paragraph::new(Line::from(vec![
+        Span::styled("r", Style::default().fg(WARNING_COLOR).add_modifier(Modifier::BOLD)),
+        Span::raw(" - Retry failed files | "),
+        Span::styled("↑↓", Style::default().fg(ACCENT_COLOR)),
+        Span::raw(" - Scroll | "),
+        Span::styled("Esc", Style::default().fg(ACCENT_COLOR)),
+        Span::raw(" - Back (the upload continues)"),
+    ]))
+    .alignment(Alignment::Center)
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(MUTED_COLOR)),
+    );
+    f.render_widget(help, area);
+}
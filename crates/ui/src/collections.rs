@@ -0,0 +1,188 @@
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph},
+};
+use visualvault_app::App;
+use visualvault_models::CollectionInput;
+
+use super::{ACCENT_COLOR, BACKGROUND_ALT, ERROR_COLOR, MUTED_COLOR, SUCCESS_COLOR, WARNING_COLOR};
+
+pub fn draw(f: &mut Frame, area: Rect, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Min(0),    // Collections and their files
+            Constraint::Length(3), // Input or help
+        ])
+        .split(area);
+
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(35), Constraint::Percentage(65)])
+        .split(chunks[0]);
+
+    draw_collections(f, columns[0], app);
+    draw_files(f, columns[1], app);
+    draw_input_or_help(f, chunks[1], app);
+}
+
+fn draw_collections(f: &mut Frame, area: Rect, app: &App) {
+    let view = &app.collections_view;
+    let collections = app.collections.collections();
+    let focused = view.selected_file.is_none();
+
+    let lines: Vec<Line> = if collections.is_empty() {
+        vec![Line::from(Span::styled(
+            "No collections yet. Press n to create one, or b → c in the Files tab",
+            Style::default().fg(MUTED_COLOR).add_modifier(Modifier::ITALIC),
+        ))]
+    } else {
+        let offset = view.selected.saturating_sub(visible_rows(area).saturating_sub(1));
+        collections
+            .iter()
+            .enumerate()
+            .skip(offset)
+            .map(|(idx, collection)| {
+                let style = if idx == view.selected {
+                    let color = if focused { ACCENT_COLOR } else { MUTED_COLOR };
+                    Style::default()
+                        .fg(color)
+                        .add_modifier(Modifier::BOLD | Modifier::REVERSED)
+                } else {
+                    Style::default().fg(Color::White)
+                };
+                Line::from(vec![
+                    Span::styled(format!("📚 {}", collection.name), style),
+                    Span::styled(
+                        format!("  {} files", collection.files.len()),
+                        Style::default().fg(MUTED_COLOR),
+                    ),
+                ])
+            })
+            .collect()
+    };
+
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .title(format!(" 📚 Collections ({}) ", collections.len()))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(if focused { ACCENT_COLOR } else { MUTED_COLOR }))
+            .style(Style::default().bg(BACKGROUND_ALT)),
+    );
+    f.render_widget(paragraph, area);
+}
+
+fn draw_files(f: &mut Frame, area: Rect, app: &App) {
+    let view = &app.collections_view;
+    let collection = app.collections.get(view.selected);
+    let files = collection
+        .map(|collection| collection.files.as_slice())
+        .unwrap_or_default();
+
+    let lines: Vec<Line> = if files.is_empty() {
+        vec![Line::from(Span::styled(
+            "This collection is empty",
+            Style::default().fg(MUTED_COLOR).add_modifier(Modifier::ITALIC),
+        ))]
+    } else {
+        let selected = view.selected_file.unwrap_or_default();
+        let offset = selected.saturating_sub(visible_rows(area).saturating_sub(1));
+        files
+            .iter()
+            .enumerate()
+            .skip(offset)
+            .map(|(idx, path)| {
+                let missing = !path.exists();
+                let style = if view.selected_file == Some(idx) {
+                    Style::default()
+                        .fg(ACCENT_COLOR)
+                        .add_modifier(Modifier::BOLD | Modifier::REVERSED)
+                } else if missing {
+                    Style::default().fg(ERROR_COLOR)
+                } else {
+                    Style::default().fg(Color::White)
+                };
+                let mut spans = vec![Span::styled(path.display().to_string(), style)];
+                if missing {
+                    spans.push(Span::styled("  (missing)", Style::default().fg(ERROR_COLOR)));
+                }
+                Line::from(spans)
+            })
+            .collect()
+    };
+
+    let title = collection.map_or_else(
+        || " Files ".to_string(),
+        |collection| format!(" {} ({} files) ", collection.name, files.len()),
+    );
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(if view.selected_file.is_some() {
+                ACCENT_COLOR
+            } else {
+                MUTED_COLOR
+            }))
+            .style(Style::default().bg(BACKGROUND_ALT)),
+    );
+    f.render_widget(paragraph, area);
+}
+
+fn draw_input_or_help(f: &mut Frame, area: Rect, app: &App) {
+    let view = &app.collections_view;
+    let line = if let Some((purpose, input)) = &view.input {
+        let label = match purpose {
+            CollectionInput::NewName => "New collection name: ",
+            CollectionInput::ExportFolder => "Copy files to folder: ",
+        };
+        Line::from(vec![
+            Span::styled(label, Style::default().fg(WARNING_COLOR).add_modifier(Modifier::BOLD)),
+            Span::raw(input.as_str()),
+            Span::styled("│", Style::default().fg(ACCENT_COLOR)),
+            Span::styled("  Enter confirms • Esc cancels", Style::default().fg(MUTED_COLOR)),
+        ])
+    } else if view.selected_file.is_some() {
+        Line::from(vec![
+            Span::styled("x", Style::default().fg(SUCCESS_COLOR)),
+            Span::raw(" - Open | "),
+            Span::styled("d", Style::default().fg(ERROR_COLOR).add_modifier(Modifier::BOLD)),
+            Span::raw(" - Remove from collection | "),
+            Span::styled("↑↓", Style::default().fg(ACCENT_COLOR)),
+            Span::raw(" - Navigate | "),
+            Span::styled("Esc", Style::default().fg(ACCENT_COLOR)),
+            Span::raw(" - Back to collections"),
+        ])
+    } else {
+        Line::from(vec![
+            Span::styled("n", Style::default().fg(SUCCESS_COLOR)),
+            Span::raw(" - New | "),
+            Span::styled("Enter", Style::default().fg(ACCENT_COLOR)),
+            Span::raw(" - Browse files | "),
+            Span::styled("e", Style::default().fg(WARNING_COLOR)),
+            Span::raw(" - Export (copy files) | "),
+            Span::styled("d", Style::default().fg(ERROR_COLOR).add_modifier(Modifier::BOLD)),
+            Span::raw(" - Delete | "),
+            Span::styled("Esc", Style::default().fg(ACCENT_COLOR)),
+            Span::raw(" - Back"),
+        ])
+    };
+
+    let help = Paragraph::new(line).alignment(Alignment::Center).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(MUTED_COLOR)),
+    );
+    f.render_widget(help, area);
+}
+
+/// Rows a bordered list in `area` can show
+fn visible_rows(area: Rect) -> usize {
+    usize::from(area.height.saturating_sub(2))
+}
@@ -0,0 +1,80 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, List, ListItem, ListState, Paragraph},
+};
+use visualvault_app::App;
+
+use super::{ACCENT_COLOR, BACKGROUND_ALT, MUTED_COLOR, WARNING_COLOR, centered_rect};
+
+/// Draws the command palette: the search line above the matching commands and their keys.
+pub fn draw_command_palette(f: &mut Frame, app: &App) {
+    let Some(palette) = &app.command_palette else {
+        return;
+    };
+
+    let area = centered_rect(60, 60, f.area());
+    f.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(" ⌘ Command Palette ")
+        .title_style(Style::default().fg(ACCENT_COLOR).add_modifier(Modifier::BOLD))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(ACCENT_COLOR))
+        .style(Style::default().bg(BACKGROUND_ALT));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(2), Constraint::Min(1), Constraint::Length(1)])
+        .split(inner);
+
+    let search = Line::from(vec![
+        Span::styled("> ", Style::default().fg(ACCENT_COLOR).add_modifier(Modifier::BOLD)),
+        Span::styled(format!("{}│", palette.query), Style::default().fg(WARNING_COLOR)),
+    ]);
+    f.render_widget(Paragraph::new(search), chunks[0]);
+
+    let matches = palette.matches();
+    if matches.is_empty() {
+        f.render_widget(
+            Paragraph::new(Span::styled(
+                "No matching commands",
+                Style::default().fg(MUTED_COLOR).add_modifier(Modifier::ITALIC),
+            )),
+            chunks[1],
+        );
+    } else {
+        let label_width = usize::from(chunks[1].width).saturating_sub(10);
+        let items: Vec<ListItem> = matches
+            .iter()
+            .map(|command| {
+                ListItem::new(Line::from(vec![
+                    Span::raw(format!(" {:<label_width$}", command.to_string())),
+                    Span::styled(format!("{:>8}", command.key()), Style::default().fg(MUTED_COLOR)),
+                ]))
+            })
+            .collect();
+        let list = List::new(items).highlight_style(
+            Style::default()
+                .fg(ACCENT_COLOR)
+                .add_modifier(Modifier::BOLD | Modifier::REVERSED),
+        );
+        let mut state = ListState::default().with_selected(Some(palette.selected));
+        f.render_stateful_widget(list, chunks[1], &mut state);
+    }
+
+    let help = Line::from(vec![
+        Span::styled("↑↓", Style::default().fg(ACCENT_COLOR).add_modifier(Modifier::BOLD)),
+        Span::raw(" choose  "),
+        Span::styled("Enter", Style::default().fg(ACCENT_COLOR).add_modifier(Modifier::BOLD)),
+        Span::raw(" run  "),
+        Span::styled("Esc", Style::default().fg(ACCENT_COLOR).add_modifier(Modifier::BOLD)),
+        Span::raw(" close"),
+    ]);
+    f.render_widget(Paragraph::new(help), chunks[2]);
+}
@@ -0,0 +1,127 @@
+use chrono::{DateTime, Local};
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Row, Table},
+};
+use visualvault_app::App;
+use visualvault_models::FileConflict;
+use visualvault_utils::format_bytes;
+
+use super::{ACCENT_COLOR, BACKGROUND_ALT, MUTED_COLOR, WARNING_COLOR, centered_rect};
+
+/// Draws the dialog asking what to do with a file whose destination already exists.
+pub fn draw_conflict_modal(f: &mut Frame, app: &App) {
+    let Some(conflict) = app.pending_conflicts.front() else {
+        return;
+    };
+
+    let area = centered_rect(70, 50, f.area());
+    f.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(format!(
+            " ⚠️  Destination exists ({} remaining) ",
+            app.pending_conflicts.len()
+        ))
+        .title_style(Style::default().fg(WARNING_COLOR).add_modifier(Modifier::BOLD))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(WARNING_COLOR))
+        .style(Style::default().bg(BACKGROUND_ALT));
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([
+            Constraint::Length(2), // Target path
+            Constraint::Min(5),    // Comparison
+            Constraint::Length(3), // Key help
+        ])
+        .split(area);
+
+    let target = Paragraph::new(Line::from(vec![
+        Span::styled("Target: ", Style::default().fg(MUTED_COLOR)),
+        Span::raw(conflict.target.display().to_string()),
+    ]));
+    f.render_widget(target, chunks[0]);
+
+    draw_comparison(f, chunks[1], conflict);
+
+    let help = Paragraph::new(vec![
+        Line::from(vec![
+            Span::styled("s", Style::default().fg(ACCENT_COLOR).add_modifier(Modifier::BOLD)),
+            Span::raw(" skip  "),
+            Span::styled("o", Style::default().fg(ACCENT_COLOR).add_modifier(Modifier::BOLD)),
+            Span::raw(" overwrite  "),
+            Span::styled("n", Style::default().fg(ACCENT_COLOR).add_modifier(Modifier::BOLD)),
+            Span::raw(" overwrite if newer  "),
+            Span::styled("r", Style::default().fg(ACCENT_COLOR).add_modifier(Modifier::BOLD)),
+            Span::raw(" rename"),
+        ]),
+        Line::from(Span::styled(
+            "Shift+key applies to all remaining conflicts • Esc skips all",
+            Style::default().fg(MUTED_COLOR).add_modifier(Modifier::ITALIC),
+        )),
+    ])
+    .alignment(Alignment::Center);
+    f.render_widget(help, chunks[2]);
+}
+
+fn draw_comparison(f: &mut Frame, area: ratatui::layout::Rect, conflict: &FileConflict) {
+    let existing = std::fs::metadata(&conflict.target).ok();
+    let existing_size = existing
+        .as_ref()
+        .map_or_else(|| "-".to_string(), |m| format_bytes(m.len()));
+    let existing_modified = existing
+        .and_then(|m| m.modified().ok())
+        .map_or_else(|| "-".to_string(), |t| format_time(t.into()));
+
+    let rows = vec![
+        Row::new(vec![
+            "Name".to_string(),
+            conflict.file.name.to_string(),
+            conflict
+                .target
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default(),
+        ]),
+        Row::new(vec![
+            "Size".to_string(),
+            format_bytes(conflict.file.size),
+            existing_size,
+        ]),
+        Row::new(vec![
+            "Modified".to_string(),
+            format_time(conflict.file.modified),
+            existing_modified,
+        ]),
+    ];
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(10),
+            Constraint::Percentage(45),
+            Constraint::Percentage(45),
+        ],
+    )
+    .header(
+        Row::new(vec!["", "Incoming", "Existing"])
+            .style(Style::default().fg(ACCENT_COLOR).add_modifier(Modifier::BOLD)),
+    )
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(MUTED_COLOR)),
+    );
+    f.render_widget(table, area);
+}
+
+fn format_time(time: DateTime<Local>) -> String {
+    time.format("%Y-%m-%d %H:%M:%S").to_string()
+}
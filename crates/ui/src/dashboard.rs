@@ -1,7 +1,8 @@
 #![allow(clippy::cast_sign_loss)]
 #![allow(clippy::cast_possible_truncation)]
 #![allow(clippy::cast_precision_loss)]
-use ahash::AHashMap;
+use std::path::Path;
+
 use ratatui::{
     Frame,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
@@ -9,13 +10,18 @@ use ratatui::{
     symbols,
     text::{Line, Span},
     widgets::{
-        Bar, BarChart, BarGroup, Block, BorderType, Borders, Cell, Gauge, List, ListItem, Paragraph, Row, Table, Tabs,
+        Bar, BarChart, BarGroup, Block, BorderType, Borders, Cell, Gauge, List, ListItem, Paragraph, Row, Sparkline,
+        Table, Tabs,
     },
 };
 
 use visualvault_app::App;
-use visualvault_models::AppState;
-use visualvault_utils::format_bytes;
+use visualvault_config::Settings;
+use visualvault_models::{
+    AppState, AudioMetadata, FOCAL_LENGTH_RANGES, FileType, GroupRow, GroupedFiles, MAX_RATING, MediaFile,
+    MediaMetadata, MetadataStats, SortColumn, TagSummary,
+};
+use visualvault_utils::{format_bytes, format_count};
 
 // Beautiful color palette
 const ACCENT_COLOR: Color = Color::Rgb(139, 233, 253); // Cyan
@@ -25,12 +31,15 @@ const ERROR_COLOR: Color = Color::Rgb(255, 85, 85); // Red
 const MUTED_COLOR: Color = Color::Rgb(98, 114, 164); // Gray
 const BACKGROUND_ALT: Color = Color::Rgb(30, 30, 46); // Dark background
 
+/// Latest results listed in the recent activity panel
+const RECENT_RESULTS: usize = 3;
+
 pub fn draw(f: &mut Frame, area: Rect, app: &App) {
     // Add a subtle background
     let background = Block::default().style(Style::default().bg(Color::Rgb(24, 24, 37)));
     f.render_widget(background, area);
 
-    let tabs = vec!["📊 Overview", "📁 Files", "📈 Types", "📅 Timeline"];
+    let tabs = tab_titles(app);
     let selected_tab = app.selected_tab;
 
     let chunks = Layout::default()
@@ -39,6 +48,7 @@ pub fn draw(f: &mut Frame, area: Rect, app: &App) {
         .split(area);
 
     // Draw enhanced tabs
+    super::record_tabs(app, chunks[0], &tabs);
     let tabs_widget = Tabs::new(tabs)
         .select(selected_tab)
         .block(
@@ -63,16 +73,61 @@ pub fn draw(f: &mut Frame, area: Rect, app: &App) {
         1 => draw_files_list(f, chunks[1], app),
         2 => draw_types_chart(f, chunks[1], app),
         3 => draw_timeline(f, chunks[1], app),
+        4 => draw_audio(f, chunks[1], app),
+        5 => draw_tags(f, chunks[1], app),
+        6 => draw_metadata(f, chunks[1], app),
         _ => {}
     }
 }
 
+/// Tab titles with the number and size of the files each tab shows
+fn tab_titles(app: &App) -> Vec<String> {
+    let stats = &app.statistics;
+    let badge = |count: usize, size: u64| format!("({} • {})", format_count(count), format_bytes(size));
+
+    let files = match app.filter_matches {
+        Some(matches) => format!(
+            "({} of {} • {})",
+            format_count(matches.files),
+            format_count(stats.total_files),
+            format_bytes(matches.size)
+        ),
+        None => badge(stats.total_files, stats.total_size),
+    };
+    let audio_type = FileType::Audio.to_string();
+    let audio = badge(
+        stats.media_types.get(&audio_type).copied().unwrap_or_default(),
+        stats.type_sizes.get(&audio_type).copied().unwrap_or_default(),
+    );
+    let tagged = app.file_tags.len();
+
+    vec![
+        "📊 Overview".to_string(),
+        format!("📁 Files {files}"),
+        format!("📈 Types ({})", stats.media_types.len()),
+        "📅 Timeline".to_string(),
+        format!("🎵 Audio {audio}"),
+        format!("🏷 Tags ({} tagged)", format_count(tagged)),
+        format!(
+            "📷 Metadata ({} images)",
+            format_count(
+                stats
+                    .media_types
+                    .get(&FileType::Image.to_string())
+                    .copied()
+                    .unwrap_or_default()
+            )
+        ),
+    ]
+}
+
 fn draw_overview(f: &mut Frame, area: Rect, app: &App) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(9),  // Stats cards
             Constraint::Length(14), // Charts (increased height)
+            Constraint::Length(6),  // Trends
             Constraint::Min(0),     // Recent activity
         ])
         .split(area);
@@ -90,8 +145,73 @@ fn draw_overview(f: &mut Frame, area: Rect, app: &App) {
     draw_storage_gauge(f, chart_chunks[0], app);
     draw_file_type_distribution(f, chart_chunks[1], app);
 
+    // Library growth over the saved snapshots
+    draw_trends(f, chunks[2], app);
+
     // Recent activity with icons
-    draw_recent_activity(f, chunks[2], app);
+    draw_recent_activity(f, chunks[3], app);
+}
+
+/// Sparklines of the library files, library size and duplicates removed over the statistics
+/// snapshots taken after each scan and organize run
+fn draw_trends(f: &mut Frame, area: Rect, app: &App) {
+    let history = &app.stats_history;
+    if history.len() < 2 {
+        let block = panel(" 📈 Trends ");
+        let hint = Paragraph::new("Trends appear here after a few scans and organize runs")
+            .style(Style::default().fg(MUTED_COLOR))
+            .alignment(Alignment::Center)
+            .block(block);
+        f.render_widget(hint, area);
+        return;
+    }
+
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Ratio(1, 3),
+            Constraint::Ratio(1, 3),
+            Constraint::Ratio(1, 3),
+        ])
+        .split(area);
+    let latest = history.last();
+    let library_files = latest.map_or(0, |snapshot| snapshot.library_files);
+    let library_bytes = latest.map_or(0, |snapshot| snapshot.library_bytes);
+    let removed = latest.map_or(0, |snapshot| snapshot.duplicates_removed);
+
+    let trends = [
+        (
+            format!(" 📚 Library files: {} ", format_count(library_files)),
+            history
+                .iter()
+                .map(|snapshot| snapshot.library_files as u64)
+                .collect::<Vec<_>>(),
+            ACCENT_COLOR,
+        ),
+        (
+            format!(" 💾 Library size: {} ", format_bytes(library_bytes)),
+            history.iter().map(|snapshot| snapshot.library_bytes).collect(),
+            SUCCESS_COLOR,
+        ),
+        (
+            format!(" 🗑 Duplicates removed: {} ", format_count(removed)),
+            history
+                .iter()
+                .map(|snapshot| snapshot.duplicates_removed as u64)
+                .collect(),
+            WARNING_COLOR,
+        ),
+    ];
+    for ((title, data, color), area) in trends.into_iter().zip(chunks.iter()) {
+        let block = panel(&title);
+        // Only the newest snapshots that fit are drawn
+        let width = usize::from(block.inner(*area).width);
+        let sparkline = Sparkline::default()
+            .block(block)
+            .data(&data[data.len().saturating_sub(width)..])
+            .style(Style::default().fg(color));
+        f.render_widget(sparkline, *area);
+    }
 }
 
 fn draw_stats_cards(f: &mut Frame, area: Rect, app: &App) {
@@ -238,7 +358,8 @@ fn draw_folder_stats_enhanced(f: &mut Frame, area: Rect, app: &App, is_source: b
 
         let content = if let Some(path) = folder_path {
             let folder_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("Unknown");
-            let stats = app.folder_stats_cache.get(path);
+            let stats = app.folder_stats.get(path);
+            let computing = app.folder_stats.is_computing(path);
 
             let mut lines = vec![Line::from(vec![
                 Span::styled(format!("{icon} "), Style::default().fg(color)),
@@ -273,22 +394,35 @@ fn draw_folder_stats_enhanced(f: &mut Frame, area: Rect, app: &App, is_source: b
                     ),
                 ]));
 
-                lines.push(Line::from(vec![
+                let mut size_line = vec![
                     Span::raw("   "),
                     Span::styled("💾 ", Style::default().fg(Color::Magenta)),
                     Span::styled(
                         format_bytes(stats.total_size),
                         Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD),
                     ),
-                ]));
+                ];
+                if computing {
+                    size_line.push(Span::styled(
+                        format!("  {} updating", folder_stats_spinner()),
+                        Style::default().fg(MUTED_COLOR).add_modifier(Modifier::ITALIC),
+                    ));
+                }
+                lines.push(Line::from(size_line));
+
+                if is_source {
+                    lines.push(Line::from(vec![
+                        Span::raw("   "),
+                        Span::styled("🔽 ", Style::default().fg(ACCENT_COLOR)),
+                        Span::styled(scan_depth_label(&settings, path), Style::default().fg(MUTED_COLOR)),
+                    ]));
+                }
             } else {
                 lines.push(Line::from(vec![
                     Span::raw("   "),
                     Span::styled(
-                        "⏳ Calculating...",
-                        Style::default()
-                            .fg(MUTED_COLOR)
-                            .add_modifier(Modifier::ITALIC | Modifier::SLOW_BLINK),
+                        format!("{} Calculating...", folder_stats_spinner()),
+                        Style::default().fg(MUTED_COLOR).add_modifier(Modifier::ITALIC),
                     ),
                 ]));
             }
@@ -316,10 +450,33 @@ fn draw_folder_stats_enhanced(f: &mut Frame, area: Rect, app: &App, is_source: b
     }
 }
 
+/// Spinner frame shown while folder statistics are added up in the background
+fn folder_stats_spinner() -> &'static str {
+    const FRAMES: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+    let frame = chrono::Local::now().timestamp_millis() / 100;
+    FRAMES[frame.unsigned_abs() as usize % FRAMES.len()]
+}
+
+/// How deep scans of the source go, as set by the depth settings
+fn scan_depth_label(settings: &Settings, source: &Path) -> String {
+    let depth = match settings.scan_depth(source) {
+        Some(0) => "Top folder only".to_string(),
+        Some(1) => "Scans 1 level of subfolders".to_string(),
+        Some(levels) => format!("Scans {levels} levels of subfolders"),
+        None => "Scans all subfolders".to_string(),
+    };
+    match settings.folder_depths.len() {
+        0 => depth,
+        _ if !settings.recurse_subfolders => depth,
+        1 => format!("{depth} • 1 folder override"),
+        count => format!("{depth} • {count} folder overrides"),
+    }
+}
+
 fn draw_file_type_distribution(f: &mut Frame, area: Rect, app: &App) {
     let stats = &app.statistics;
     let mut data: Vec<(&str, u64)> = stats.media_types.iter().map(|(k, v)| (k.as_str(), *v as u64)).collect();
-    data.sort_by(|a, b| b.1.cmp(&a.1));
+    data.sort_by_key(|d| std::cmp::Reverse(d.1));
     data.truncate(5);
 
     // Use emoji icons for file types
@@ -415,6 +572,36 @@ fn draw_recent_activity(f: &mut Frame, area: Rect, app: &App) {
         ])));
     }
 
+    // Earlier organize runs from the import history, leaving out the one shown above
+    let shown = app.last_organize_result.as_ref().map(|result| result.timestamp);
+    for import in app
+        .import_history
+        .iter()
+        .filter(|import| Some(import.finished) != shown)
+    {
+        let source = import.source.file_name().and_then(|n| n.to_str()).unwrap_or("source");
+        let mut spans = vec![
+            Span::styled("📥 ", Style::default().fg(MUTED_COLOR)),
+            Span::raw("Imported "),
+            Span::styled(
+                format_count(import.files),
+                Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(format!(" files from {source}")),
+        ];
+        if import.errors > 0 {
+            spans.push(Span::styled(
+                format!(" ({} errors)", import.errors),
+                Style::default().fg(ERROR_COLOR),
+            ));
+        }
+        spans.push(Span::styled(
+            format!(" • {}", format_relative_time(import.finished)),
+            Style::default().fg(MUTED_COLOR).add_modifier(Modifier::DIM),
+        ));
+        activities.push(ListItem::new(Line::from(spans)));
+    }
+
     // Add duplicate detection activity
     if let Some(ref duplicate_groups) = app.duplicate_groups {
         if !duplicate_groups.is_empty() {
@@ -519,26 +706,17 @@ fn draw_recent_activity(f: &mut Frame, area: Rect, app: &App) {
         }
     }
 
-    // Add error messages if any
-    if let Some(error) = &app.error_message {
-        activities.insert(
+    // The latest results, newest on top, kept after their toasts expire
+    for toast in app.toasts.entries.iter().take(RECENT_RESULTS).rev() {
+        let mut line = super::toasts::toast_line(toast);
+        line.spans.insert(
             0,
-            ListItem::new(Line::from(vec![
-                Span::styled("🚨 ", Style::default().fg(ERROR_COLOR)),
-                Span::styled(error, Style::default().fg(ERROR_COLOR).add_modifier(Modifier::BOLD)),
-            ])),
-        );
-    }
-
-    // Add success messages if any
-    if let Some(success) = &app.success_message {
-        activities.insert(
-            0,
-            ListItem::new(Line::from(vec![
-                Span::styled("🎉 ", Style::default().fg(SUCCESS_COLOR)),
-                Span::styled(success, Style::default().fg(SUCCESS_COLOR).add_modifier(Modifier::BOLD)),
-            ])),
+            Span::styled(
+                format!("{} ", toast.timestamp.format("%H:%M")),
+                Style::default().fg(MUTED_COLOR),
+            ),
         );
+        activities.insert(0, ListItem::new(line));
     }
 
     // If no activities, show placeholder with helpful hint
@@ -606,76 +784,183 @@ fn format_relative_time(timestamp: chrono::DateTime<chrono::Local>) -> String {
 fn draw_files_list(f: &mut Frame, area: Rect, app: &App) {
     let files = &app.cached_files;
 
-    // Create a beautiful file list with icons
-    let rows: Vec<Row> = files
-        .iter()
-        .skip(app.scroll_offset)
-        .take((area.height as usize).saturating_sub(4))
-        .enumerate()
-        .map(|(idx, file)| {
-            let is_selected = app.selected_file_index == app.scroll_offset + idx;
-
-            let style = if is_selected {
-                Style::default().bg(Color::Rgb(69, 71, 90)).add_modifier(Modifier::BOLD)
-            } else {
-                Style::default()
-            };
-
-            let type_icon = match file.file_type.to_string().to_lowercase().as_str() {
-                "image" => "🖼️",
-                "video" => "🎬",
-                "audio" => "🎵",
-                "raw" => "📸",
-                _ => "📄",
-            };
+    // Borders, header and header margin take four lines
+    app.file_viewport.set_rows((area.height as usize).saturating_sub(4));
+    let rows_area = Rect {
+        x: area.x + 1,
+        y: area.y + 3,
+        width: area.width.saturating_sub(2),
+        height: area.height.saturating_sub(4),
+    };
+    super::record_mouse_targets(app, |targets| targets.file_rows = Some(super::screen_area(rows_area)));
 
-            Row::new(vec![
-                Cell::from(format!("{} {}", type_icon, file.name)),
-                Cell::from(file.file_type.to_string())
-                    .style(Style::default().fg(get_enhanced_type_color(&file.file_type.to_string()))),
-                Cell::from(format_bytes(file.size)).style(Style::default().fg(Color::Cyan)),
-                Cell::from(file.modified.format("%Y-%m-%d %H:%M").to_string()).style(Style::default().fg(MUTED_COLOR)),
-            ])
-            .style(style)
-        })
-        .collect();
+    let rows: Vec<Row> = if let Some(groups) = &app.file_groups {
+        grouped_file_rows(app, groups)
+    } else {
+        let visible = app.file_viewport.visible(app.selected_file_index, files.len());
+        let first_visible = visible.start;
+        visible
+            .map_while(|index| files.get(index))
+            .enumerate()
+            .map(|(idx, file)| file_row(app, file, app.selected_file_index == first_visible + idx, ""))
+            .collect()
+    };
 
     let header_style = Style::default()
         .fg(ACCENT_COLOR)
         .add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
 
-    let table = Table::new(
-        rows.clone(),
-        [
-            Constraint::Percentage(40),
-            Constraint::Percentage(15),
-            Constraint::Percentage(15),
-            Constraint::Percentage(30),
-        ],
-    )
-    .header(
-        Row::new(vec!["Name", "Type", "Size", "Modified"])
-            .style(header_style)
-            .bottom_margin(1),
-    )
-    .block(
-        Block::default()
-            .title(format!(
-                " 📁 Files ({}/{}) ",
-                app.scroll_offset + rows.len().min(1),
-                files.len()
-            ))
-            .borders(Borders::ALL)
-            .border_type(BorderType::Rounded)
-            .border_style(Style::default().fg(MUTED_COLOR))
-            .style(Style::default().bg(BACKGROUND_ALT)),
-    )
-    .row_highlight_style(Style::default().bg(Color::Rgb(69, 71, 90)).add_modifier(Modifier::BOLD))
-    .highlight_symbol("▶ ");
+    let widths = [
+        Constraint::Percentage(40),
+        Constraint::Percentage(15),
+        Constraint::Percentage(15),
+        Constraint::Percentage(30),
+    ];
+    let headers = file_list_headers(
+        app,
+        Rect {
+            y: area.y + 1,
+            height: 1,
+            ..rows_area
+        },
+        widths,
+    );
+    let position = match &app.file_groups {
+        Some(groups) => format!(
+            "by {} ({}/{} groups)",
+            groups.by,
+            groups.selected_group().map_or(0, |position| position + 1),
+            groups.groups.len()
+        ),
+        None => format!("({}/{})", app.selected_file_index + rows.len().min(1), files.len()),
+    };
+    let table = Table::new(rows, widths)
+        .header(Row::new(headers).style(header_style).bottom_margin(1))
+        .block(
+            Block::default()
+                .title(if app.marked_files.is_empty() {
+                    format!(" 📁 Files {position} ")
+                } else {
+                    format!(
+                        " 📁 Files {position} • {} marked, b for actions, R to rename ",
+                        app.marked_files.len()
+                    )
+                })
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(MUTED_COLOR))
+                .style(Style::default().bg(BACKGROUND_ALT)),
+        )
+        .row_highlight_style(Style::default().bg(Color::Rgb(69, 71, 90)).add_modifier(Modifier::BOLD))
+        .highlight_symbol("▶ ");
 
     f.render_widget(table, area);
 }
 
+/// Visible rows of the grouped file list: group headers and the files of expanded groups
+fn grouped_file_rows<'a>(app: &'a App, groups: &GroupedFiles) -> Vec<Row<'a>> {
+    let selected_style = Style::default().bg(Color::Rgb(69, 71, 90)).add_modifier(Modifier::BOLD);
+    app.file_viewport
+        .visible(groups.selected, groups.row_count())
+        .filter_map(|row| {
+            let is_selected = row == groups.selected;
+            match groups.row(row)? {
+                GroupRow::Header(position) => {
+                    let group = &groups.groups[position];
+                    let header = Row::new(vec![
+                        Cell::from(format!("{} {}", if group.expanded { "▾" } else { "▸" }, group.key))
+                            .style(Style::default().fg(ACCENT_COLOR).add_modifier(Modifier::BOLD)),
+                        Cell::from(format!("{} files", group.files.len())).style(Style::default().fg(MUTED_COLOR)),
+                        Cell::from(format_bytes(group.size)).style(Style::default().fg(Color::Cyan)),
+                        Cell::from(""),
+                    ]);
+                    Some(if is_selected {
+                        header.style(selected_style)
+                    } else {
+                        header
+                    })
+                }
+                GroupRow::File { index, .. } => app
+                    .grouped_file(index)
+                    .map(|file| file_row(app, file, is_selected, "  ")),
+            }
+        })
+        .collect()
+}
+
+/// Row of the file list for `file`, its name indented by `indent`
+fn file_row<'a>(app: &App, file: &'a MediaFile, is_selected: bool, indent: &str) -> Row<'a> {
+    let is_marked = app.marked_files.contains(&file.path);
+
+    let style = if is_selected {
+        Style::default().bg(Color::Rgb(69, 71, 90)).add_modifier(Modifier::BOLD)
+    } else if is_marked {
+        Style::default().fg(Color::Yellow)
+    } else {
+        Style::default()
+    };
+
+    let type_icon = match file.file_type.to_string().to_lowercase().as_str() {
+        "image" => "🖼️",
+        "video" => "🎬",
+        "audio" => "🎵",
+        "raw" => "📸",
+        _ => "📄",
+    };
+
+    let mut name = vec![Span::raw(format!(
+        "{indent}{}{} {}",
+        if is_marked { "● " } else { "" },
+        type_icon,
+        file.name
+    ))];
+    if let Some(MediaMetadata::Image(image)) = app.metadata.cached(file) {
+        name.push(Span::styled(
+            format!(" {}×{}", image.width, image.height),
+            Style::default().fg(MUTED_COLOR),
+        ));
+    }
+    if file.live_photo_pair.is_some() {
+        name.push(Span::styled(" ◎ LIVE", Style::default().fg(Color::Yellow)));
+    }
+    if app.organized_before.contains_key(&file.path) {
+        name.push(Span::styled(" ✓ ORGANIZED", Style::default().fg(MUTED_COLOR)));
+    }
+    if let Some(extension) = &file.content_extension {
+        name.push(Span::styled(
+            format!(" ⚠ IS .{extension}"),
+            Style::default().fg(WARNING_COLOR),
+        ));
+    }
+
+    Row::new(vec![
+        Cell::from(Line::from(name)),
+        Cell::from(file.file_type.to_string())
+            .style(Style::default().fg(get_enhanced_type_color(&file.file_type.to_string()))),
+        Cell::from(format_bytes(file.size)).style(Style::default().fg(Color::Cyan)),
+        Cell::from(file.modified.format("%Y-%m-%d %H:%M").to_string()).style(Style::default().fg(MUTED_COLOR)),
+    ])
+    .style(style)
+}
+
+/// Column titles of the file list, marking the sort column, with the header cells recorded
+/// so clicking them sorts by their column
+fn file_list_headers(app: &App, header_area: Rect, widths: [Constraint; 4]) -> Vec<String> {
+    let header_cells = Layout::horizontal(widths).spacing(1).split(header_area);
+    super::record_mouse_targets(app, |targets| {
+        targets.file_columns = header_cells.iter().map(|cell| super::screen_area(*cell)).collect();
+    });
+    SortColumn::ALL
+        .iter()
+        .map(|column| match app.file_sort {
+            Some(sort) if sort.column == *column => {
+                format!("{column} {}", if sort.descending { "▼" } else { "▲" })
+            }
+            _ => column.to_string(),
+        })
+        .collect()
+}
+
 fn draw_types_chart(f: &mut Frame, area: Rect, app: &App) {
     let stats = &app.statistics;
     let mut type_data: Vec<(String, usize, u64)> = stats
@@ -683,7 +968,7 @@ fn draw_types_chart(f: &mut Frame, area: Rect, app: &App) {
         .iter()
         .map(|(k, v)| (k.clone(), *v, stats.type_sizes.get(k).copied().unwrap_or(0)))
         .collect();
-    type_data.sort_by(|a, b| b.1.cmp(&a.1));
+    type_data.sort_by_key(|d| std::cmp::Reverse(d.1));
 
     #[allow(clippy::cast_precision_loss)]
     let rows: Vec<Row> = type_data
@@ -754,25 +1039,332 @@ fn draw_types_chart(f: &mut Frame, area: Rect, app: &App) {
     f.render_widget(table, area);
 }
 
-fn draw_timeline(f: &mut Frame, area: Rect, app: &App) {
-    let stats = &app.statistics;
+fn draw_audio(f: &mut Frame, area: Rect, app: &App) {
+    let tracks = app.audio_tracks();
+    let artists: ahash::AHashSet<&str> = tracks
+        .iter()
+        .filter_map(|file| match &file.metadata {
+            Some(MediaMetadata::Audio(tags)) => tags.artist.as_deref(),
+            _ => None,
+        })
+        .collect();
+
+    let rows: Vec<Row> = tracks
+        .iter()
+        .skip(app.audio_scroll)
+        .take((area.height as usize).saturating_sub(4))
+        .enumerate()
+        .map(|(idx, file)| {
+            let tags = match &file.metadata {
+                Some(MediaMetadata::Audio(tags)) => tags.clone(),
+                _ => AudioMetadata::default(),
+            };
+            let style = if idx % 2 == 0 {
+                Style::default().bg(Color::Rgb(40, 42, 54))
+            } else {
+                Style::default()
+            };
+
+            Row::new(vec![
+                Cell::from(tags.artist.unwrap_or_else(|| "Unknown Artist".to_string()))
+                    .style(Style::default().fg(get_enhanced_type_color("audio"))),
+                Cell::from(tags.album.unwrap_or_else(|| "Unknown Album".to_string())),
+                Cell::from(format!("🎵 {}", tags.title.unwrap_or_else(|| file.name.to_string()))),
+                Cell::from(tags.year.map(|y| y.to_string()).unwrap_or_default())
+                    .style(Style::default().fg(MUTED_COLOR)),
+                Cell::from(format_bytes(file.size)).style(Style::default().fg(Color::Cyan)),
+            ])
+            .style(style)
+        })
+        .collect();
+
+    let header_style = Style::default()
+        .fg(ACCENT_COLOR)
+        .add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Percentage(25),
+            Constraint::Percentage(25),
+            Constraint::Percentage(30),
+            Constraint::Percentage(8),
+            Constraint::Percentage(12),
+        ],
+    )
+    .header(
+        Row::new(vec!["Artist", "Album", "Title", "Year", "Size"])
+            .style(header_style)
+            .bottom_margin(1),
+    )
+    .block(
+        Block::default()
+            .title(format!(
+                " 🎵 Audio ({} tracks, {} artists) ",
+                tracks.len(),
+                artists.len()
+            ))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(MUTED_COLOR))
+            .style(Style::default().bg(BACKGROUND_ALT)),
+    );
 
-    // Group files by year
-    let mut files_by_year: AHashMap<String, (usize, u64)> = AHashMap::new();
+    f.render_widget(table, area);
+}
+
+fn draw_tags(f: &mut Frame, area: Rect, app: &App) {
+    let summary = TagSummary::new(app.file_tags.values());
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+        .split(area);
 
-    for file in &app.cached_files {
-        let year = file.created.format("%Y").to_string();
-        let entry = files_by_year.entry(year).or_insert((0, 0));
-        entry.0 += 1;
-        entry.1 += file.size;
+    let rows: Vec<Row> = summary
+        .tags
+        .iter()
+        .enumerate()
+        .map(|(idx, (tag, count))| {
+            let style = if idx % 2 == 0 {
+                Style::default().bg(Color::Rgb(40, 42, 54))
+            } else {
+                Style::default()
+            };
+            let percentage = *count as f64 / summary.tagged_files.max(1) as f64 * 100.0;
+            Row::new(vec![
+                Cell::from(format!("🏷 {tag}")).style(Style::default().fg(ACCENT_COLOR)),
+                Cell::from(format!("{count}")).style(Style::default().fg(Color::White).add_modifier(Modifier::BOLD)),
+                Cell::from(create_mini_bar(percentage)).style(Style::default().fg(SUCCESS_COLOR)),
+            ])
+            .style(style)
+        })
+        .collect();
+
+    let header_style = Style::default()
+        .fg(ACCENT_COLOR)
+        .add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Percentage(45),
+            Constraint::Percentage(15),
+            Constraint::Percentage(40),
+        ],
+    )
+    .header(
+        Row::new(vec!["Tag", "Files", "Share"])
+            .style(header_style)
+            .bottom_margin(1),
+    )
+    .block(
+        Block::default()
+            .title(format!(
+                " 🏷 Tags ({} tags on {} files) ",
+                summary.tags.len(),
+                summary.tagged_files
+            ))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(MUTED_COLOR))
+            .style(Style::default().bg(BACKGROUND_ALT)),
+    );
+    f.render_widget(table, chunks[0]);
+
+    let bars: Vec<Bar> = (1..=MAX_RATING)
+        .zip(summary.ratings)
+        .map(|(stars, count)| {
+            Bar::default()
+                .value(count as u64)
+                .label(Line::from("★".repeat(usize::from(stars))))
+                .style(Style::default().fg(WARNING_COLOR))
+                .value_style(Style::default().fg(Color::White).add_modifier(Modifier::BOLD))
+        })
+        .collect();
+
+    let chart = BarChart::default()
+        .data(BarGroup::default().bars(&bars))
+        .bar_width(5)
+        .bar_gap(1)
+        .block(
+            Block::default()
+                .title(" ⭐ Ratings ")
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(MUTED_COLOR))
+                .style(Style::default().bg(BACKGROUND_ALT)),
+        );
+    f.render_widget(chart, chunks[1]);
+}
+
+/// EXIF data aggregated over the scanned images, with the progress of reading it
+fn draw_metadata(f: &mut Frame, area: Rect, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(0)])
+        .split(area);
+
+    if let Some((processed, total)) = app.metadata_stats_progress() {
+        let gauge = Gauge::default()
+            .block(panel(" 📷 Reading EXIF data "))
+            .gauge_style(Style::default().fg(ACCENT_COLOR).bg(Color::Rgb(40, 40, 55)))
+            .ratio(if total == 0 {
+                1.0
+            } else {
+                (processed as f64 / total as f64).min(1.0)
+            })
+            .label(format!("{} / {} images", format_count(processed), format_count(total)));
+        f.render_widget(gauge, chunks[0]);
+    }
+
+    let Some(stats) = &app.metadata_stats else {
+        if app.metadata_stats_progress().is_none() {
+            let message = Paragraph::new("Scan a folder to see the cameras, focal lengths and locations of its photos")
+                .style(Style::default().fg(MUTED_COLOR))
+                .alignment(Alignment::Center)
+                .block(panel(" 📷 Metadata "));
+            f.render_widget(message, area);
+        }
+        return;
+    };
+
+    if app.metadata_stats_progress().is_none() {
+        f.render_widget(
+            Paragraph::new(metadata_summary(stats))
+                .alignment(Alignment::Center)
+                .block(panel(" 📷 Metadata ")),
+            chunks[0],
+        );
     }
 
-    let mut timeline_data: Vec<(String, usize, u64)> = files_by_year
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+        .split(chunks[1]);
+
+    draw_cameras(f, columns[0], stats);
+
+    let bars: Vec<Bar> = FOCAL_LENGTH_RANGES
+        .iter()
+        .zip(stats.focal_lengths)
+        .map(|((label, _), count)| {
+            Bar::default()
+                .value(count as u64)
+                .label(Line::from(*label))
+                .style(Style::default().fg(ACCENT_COLOR))
+                .value_style(Style::default().fg(Color::White).add_modifier(Modifier::BOLD))
+        })
+        .collect();
+    let chart = BarChart::default()
+        .data(BarGroup::default().bars(&bars))
+        .direction(Direction::Horizontal)
+        .bar_width(1)
+        .bar_gap(1)
+        .block(panel(" 🔭 Focal Lengths "));
+    f.render_widget(chart, columns[1]);
+}
+
+/// Bordered panel of the Metadata tab
+fn panel(title: &str) -> Block<'_> {
+    Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(MUTED_COLOR))
+        .style(Style::default().bg(BACKGROUND_ALT))
+}
+
+/// One-line summary of how many images record a location and a capture date
+fn metadata_summary(stats: &MetadataStats) -> Line<'static> {
+    let share = |count: usize| count as f64 / stats.images.max(1) as f64 * 100.0;
+    Line::from(vec![
+        Span::styled(
+            format!("{} images", format_count(stats.images)),
+            Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
+        ),
+        Span::raw("  •  "),
+        Span::styled(
+            format!(
+                "📍 {} with GPS location ({:.1}%)",
+                format_count(stats.with_location),
+                share(stats.with_location)
+            ),
+            Style::default().fg(SUCCESS_COLOR),
+        ),
+        Span::raw("  •  "),
+        Span::styled(
+            format!(
+                "📅 {} without EXIF date ({:.1}%)",
+                format_count(stats.missing_dates),
+                share(stats.missing_dates)
+            ),
+            Style::default().fg(WARNING_COLOR),
+        ),
+        Span::raw("  •  "),
+        Span::styled(
+            format!("{} unreadable", format_count(stats.unreadable)),
+            Style::default().fg(if stats.unreadable == 0 {
+                MUTED_COLOR
+            } else {
+                ERROR_COLOR
+            }),
+        ),
+    ])
+}
+
+/// Cameras with the most photos, as many as fit `area`
+fn draw_cameras(f: &mut Frame, area: Rect, stats: &MetadataStats) {
+    let with_camera: usize = stats.cameras.values().sum();
+    let rows: Vec<Row> = stats
+        .top_cameras((area.height as usize).saturating_sub(4))
         .into_iter()
-        .map(|(year, (count, size))| (year, count, size))
+        .enumerate()
+        .map(|(idx, (camera, count))| {
+            let style = if idx % 2 == 0 {
+                Style::default().bg(Color::Rgb(40, 42, 54))
+            } else {
+                Style::default()
+            };
+            let percentage = count as f64 / with_camera.max(1) as f64 * 100.0;
+            Row::new(vec![
+                Cell::from(format!("📷 {camera}")).style(Style::default().fg(ACCENT_COLOR)),
+                Cell::from(format_count(count)).style(Style::default().fg(Color::White).add_modifier(Modifier::BOLD)),
+                Cell::from(create_mini_bar(percentage)).style(Style::default().fg(SUCCESS_COLOR)),
+            ])
+            .style(style)
+        })
         .collect();
+    let header_style = Style::default()
+        .fg(ACCENT_COLOR)
+        .add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Percentage(50),
+            Constraint::Percentage(15),
+            Constraint::Percentage(35),
+        ],
+    )
+    .header(
+        Row::new(vec!["Camera", "Photos", "Share"])
+            .style(header_style)
+            .bottom_margin(1),
+    )
+    .block(panel(" 📷 Cameras "));
+    f.render_widget(table, area);
+}
 
-    timeline_data.sort_by(|a, b| b.0.cmp(&a.0));
+fn draw_timeline(f: &mut Frame, area: Rect, app: &App) {
+    let stats = &app.statistics;
+
+    // Group files by year, newest first
+    let timeline_data: Vec<(String, usize, u64)> = app
+        .cached_files
+        .year_totals()
+        .into_iter()
+        .rev()
+        .map(|(year, (count, size))| (year.to_string(), count, size))
+        .collect();
 
     // Create layout with two sections
     let chunks = Layout::default()
@@ -944,10 +1536,13 @@ fn get_enhanced_type_color(file_type: &str) -> Color {
 
 // Helper function to truncate long paths
 fn truncate_path(path: &str, max_width: usize) -> String {
-    if path.len() <= max_width {
+    let length = path.chars().count();
+    if length <= max_width {
         path.to_string()
     } else if max_width > 3 {
-        format!("...{}", &path[path.len() - (max_width - 3)..])
+        // Counted in characters, so narrow windows never cut a character in half
+        let tail: String = path.chars().skip(length - (max_width - 3)).collect();
+        format!("...{tail}")
     } else {
         "...".to_string()
     }
@@ -0,0 +1,94 @@
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph},
+};
+use visualvault_app::App;
+
+use super::{ACCENT_COLOR, BACKGROUND_ALT, MUTED_COLOR, WARNING_COLOR};
+
+const DATE_FORMAT: &str = "%Y-%m-%d %H:%M";
+
+pub fn draw(f: &mut Frame, area: Rect, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Min(0),    // Mismatched photos
+            Constraint::Length(3), // Help
+        ])
+        .split(area);
+
+    draw_mismatches(f, chunks[0], app);
+    draw_help(f, chunks[1]);
+}
+
+fn draw_mismatches(f: &mut Frame, area: Rect, app: &App) {
+    let source = app.settings_cache.source_folder.as_deref();
+
+    let lines: Vec<Line> = if app.date_mismatches.is_empty() {
+        vec![Line::from(Span::styled(
+            "No photo dates left to fix",
+            Style::default().fg(MUTED_COLOR).add_modifier(Modifier::ITALIC),
+        ))]
+    } else {
+        app.date_mismatches
+            .iter()
+            .skip(app.date_fix_scroll)
+            .take(usize::from(area.height))
+            .map(|mismatch| {
+                let shown = source
+                    .and_then(|source| mismatch.path.strip_prefix(source).ok())
+                    .unwrap_or(&mismatch.path);
+                Line::from(vec![
+                    Span::styled("🕒 ", Style::default().fg(WARNING_COLOR)),
+                    Span::styled(shown.display().to_string(), Style::default().fg(Color::White)),
+                    Span::styled(
+                        format!("  modified {}", mismatch.modified.format(DATE_FORMAT)),
+                        Style::default().fg(MUTED_COLOR),
+                    ),
+                    Span::styled(
+                        format!("  taken {}", mismatch.taken.format(DATE_FORMAT)),
+                        Style::default().fg(ACCENT_COLOR),
+                    ),
+                    Span::styled(
+                        format!("  {} days apart", mismatch.difference().num_days()),
+                        Style::default().fg(WARNING_COLOR),
+                    ),
+                ])
+            })
+            .collect()
+    };
+
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .title(format!(" 🕒 Wrong Photo Dates ({}) ", app.date_mismatches.len()))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(MUTED_COLOR))
+            .style(Style::default().bg(BACKGROUND_ALT)),
+    );
+    f.render_widget(paragraph, area);
+}
+
+fn draw_help(f: &mut Frame, area: Rect) {
+    let help = Paragraph::new(Line::from(vec![
+        Span::styled("m", Style::default().fg(WARNING_COLOR).add_modifier(Modifier::BOLD)),
+        Span::raw(" - Set modification times from EXIF | "),
+        Span::styled("x", Style::default().fg(WARNING_COLOR).add_modifier(Modifier::BOLD)),
+        Span::raw(" - Write EXIF dates from modification times | "),
+        Span::styled("↑↓", Style::default().fg(ACCENT_COLOR)),
+        Span::raw(" - Scroll | "),
+        Span::styled("Esc", Style::default().fg(ACCENT_COLOR)),
+        Span::raw(" - Back"),
+    ]))
+    .alignment(Alignment::Center)
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(MUTED_COLOR)),
+    );
+    f.render_widget(help, area);
+}
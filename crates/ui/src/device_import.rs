@@ -0,0 +1,59 @@
+use ratatui::{
+    Frame,
+    layout::Alignment,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+};
+use visualvault_app::App;
+
+use super::{ACCENT_COLOR, BACKGROUND_ALT, MUTED_COLOR, centered_rect};
+
+/// Draws the list of camera cards and phones that can be imported from.
+pub fn draw_device_import(f: &mut Frame, app: &App) {
+    let Some(import) = &app.device_import else {
+        return;
+    };
+
+    let area = centered_rect(60, 40, f.area());
+    f.render_widget(Clear, area);
+
+    let key_style = Style::default().fg(ACCENT_COLOR).add_modifier(Modifier::BOLD);
+    let hint_style = Style::default().fg(MUTED_COLOR).add_modifier(Modifier::ITALIC);
+    let mut lines = vec![Line::from("")];
+
+    for (i, volume) in import.volumes.iter().enumerate() {
+        let marker = if i == import.selected { "▶ " } else { "  " };
+        let style = if i == import.selected {
+            key_style
+        } else {
+            Style::default().fg(Color::White)
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {marker}💾 {}", volume.name), style),
+            Span::styled(format!("  {}", volume.dcim.display()), Style::default().fg(MUTED_COLOR)),
+        ]));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "Enter copies media not imported before into the source folder",
+        hint_style,
+    )));
+    lines.push(Line::from(Span::styled(
+        "s scans the device as a temporary source • Esc closes",
+        hint_style,
+    )));
+
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .title(" 📷 Import from Device ")
+            .title_alignment(Alignment::Center)
+            .title_style(key_style)
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(ACCENT_COLOR))
+            .style(Style::default().bg(BACKGROUND_ALT)),
+    );
+    f.render_widget(paragraph, area);
+}
@@ -0,0 +1,134 @@
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
+};
+use visualvault_app::App;
+use visualvault_models::{Dialog, DialogFocus, DialogKind};
+
+use super::{ACCENT_COLOR, BACKGROUND_ALT, MUTED_COLOR, WARNING_COLOR, centered_rect};
+
+/// Draws the open confirmation, input or choice dialog over everything else.
+pub fn draw_dialog(f: &mut Frame, app: &App) {
+    let Some(dialog) = &app.dialog else {
+        return;
+    };
+    let color = if dialog.destructive {
+        WARNING_COLOR
+    } else {
+        ACCENT_COLOR
+    };
+
+    let area = centered_rect(60, 35, f.area());
+    f.render_widget(Clear, area);
+
+    let icon = if dialog.destructive { "⚠️ " } else { "❔" };
+    let block = Block::default()
+        .title(format!(" {icon} {} ", dialog.title))
+        .title_alignment(Alignment::Center)
+        .title_style(Style::default().fg(color).add_modifier(Modifier::BOLD))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(color))
+        .style(Style::default().bg(BACKGROUND_ALT));
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([
+            Constraint::Min(2),                      // Message
+            Constraint::Length(body_height(dialog)), // Field, options or buttons
+            Constraint::Length(1),                   // Key help
+        ])
+        .split(area);
+
+    let message = Paragraph::new(dialog.message.as_str())
+        .style(Style::default().fg(Color::White))
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+    f.render_widget(message, chunks[0]);
+
+    let body = Paragraph::new(body_lines(dialog, color)).alignment(Alignment::Center);
+    f.render_widget(body, chunks[1]);
+
+    let help = Paragraph::new(Line::from(Span::styled(
+        help_text(dialog),
+        Style::default().fg(MUTED_COLOR).add_modifier(Modifier::ITALIC),
+    )))
+    .alignment(Alignment::Center);
+    f.render_widget(help, chunks[2]);
+}
+
+fn body_height(dialog: &Dialog) -> u16 {
+    match &dialog.kind {
+        DialogKind::Confirm => 1,
+        DialogKind::Input { .. } => 3,
+        DialogKind::Choice { options } => u16::try_from(options.len() + 2).unwrap_or(u16::MAX),
+    }
+}
+
+fn body_lines(dialog: &Dialog, color: Color) -> Vec<Line<'static>> {
+    let focused = dialog.focused();
+    let style_for = |focus: DialogFocus| {
+        if focus == focused {
+            Style::default().fg(Color::Black).bg(color).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::White)
+        }
+    };
+    let button = |label: &str, focus: DialogFocus| Span::styled(format!("[ {label} ]"), style_for(focus));
+
+    match &dialog.kind {
+        DialogKind::Confirm => vec![Line::from(vec![
+            button("Yes", DialogFocus::Accept),
+            Span::raw("   "),
+            button("No", DialogFocus::Cancel),
+        ])],
+        DialogKind::Input { value } => {
+            let field_style = if focused == DialogFocus::Field {
+                Style::default().fg(color)
+            } else {
+                Style::default().fg(MUTED_COLOR)
+            };
+            vec![
+                Line::from(vec![
+                    Span::styled("▏", field_style),
+                    Span::styled(value.clone(), Style::default().fg(Color::White)),
+                    Span::styled("│", field_style),
+                ]),
+                Line::from(""),
+                Line::from(vec![
+                    button("OK", DialogFocus::Accept),
+                    Span::raw("   "),
+                    button("Cancel", DialogFocus::Cancel),
+                ]),
+            ]
+        }
+        DialogKind::Choice { options } => {
+            let mut lines: Vec<Line> = options
+                .iter()
+                .enumerate()
+                .map(|(index, option)| {
+                    Line::from(Span::styled(
+                        format!(" {}. {option} ", index + 1),
+                        style_for(DialogFocus::Option(index)),
+                    ))
+                })
+                .collect();
+            lines.push(Line::from(""));
+            lines.push(Line::from(button("Cancel", DialogFocus::Cancel)));
+            lines
+        }
+    }
+}
+
+const fn help_text(dialog: &Dialog) -> &'static str {
+    match dialog.kind {
+        DialogKind::Confirm => "Y yes • N/Esc no • Tab/←→ move • Enter activates",
+        DialogKind::Input { .. } => "Type the text • Tab moves • Enter accepts • Esc cancels",
+        DialogKind::Choice { .. } => "1-9 chooses • ↑↓/Tab move • Enter activates • Esc cancels",
+    }
+}
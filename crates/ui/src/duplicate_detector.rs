@@ -1,35 +1,77 @@
 use ratatui::{
     Frame,
-    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
     style::{Color, Modifier, Style},
+    symbols,
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, Paragraph, Row, Table},
+    widgets::{Block, BorderType, Borders, Clear, List, ListItem, ListState, Paragraph, Row, Table, Tabs, Wrap},
 };
 use visualvault_app::App;
-use visualvault_models::{DuplicateFocus, DuplicateGroup, DuplicateStats};
+use visualvault_models::{
+    CompareEntry, DuplicateFocus, DuplicateGroup, DuplicateSort, DuplicateStats, InputMode, Thumbnail,
+};
 use visualvault_utils::format_bytes;
 
+use super::{ACCENT_COLOR, BACKGROUND_ALT, MUTED_COLOR, WARNING_COLOR, centered_rect};
+
+const DATE_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
 pub fn draw(f: &mut Frame, area: Rect, app: &App) {
     // Remove the header since it's now handled by the main UI
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
+            Constraint::Length(3), // Tabs
             Constraint::Length(5), // Stats
             Constraint::Min(10),   // Duplicate groups
             Constraint::Length(3), // Help
         ])
         .split(area);
 
+    draw_tabs(f, chunks[0], app);
+
     // Stats section
     if let Some(stats) = &app.duplicate_stats {
-        draw_stats(f, chunks[0], stats);
-        draw_duplicate_groups(f, chunks[1], stats, app);
+        draw_stats(f, chunks[1], stats);
+        draw_duplicate_groups(f, chunks[2], stats, app);
     } else {
-        draw_no_scan(f, chunks[0]);
+        draw_no_scan(f, chunks[1], app.duplicate_tab);
     }
 
     // Help section
-    draw_help(f, chunks[2]);
+    draw_help(f, chunks[3]);
+
+    if let Some(entries) = &app.duplicate_compare {
+        draw_compare(f, entries, app);
+    }
+}
+
+fn draw_tabs(f: &mut Frame, area: Rect, app: &App) {
+    let count = |stats: Option<&DuplicateStats>| stats.map_or_else(String::new, |s| format!(" ({})", s.total_groups));
+    let titles = vec![
+        format!("🔄 Exact duplicates{}", count(app.exact_duplicates.as_ref())),
+        format!("🧬 Probable copies{}", count(app.probable_copies.as_ref())),
+        format!("🎬 Similar videos{}", count(app.similar_videos.as_ref())),
+        format!("📸 Bursts{}", count(app.bursts.as_ref())),
+    ];
+    let scope = app.duplicate_folder.as_ref().map_or_else(
+        || " Scanned files ".to_string(),
+        |folder| format!(" 📂 {} ", truncate_path(&folder.display().to_string(), 50)),
+    );
+
+    super::record_tabs(app, area, &titles);
+    let tabs = Tabs::new(titles)
+        .select(app.duplicate_tab)
+        .block(
+            Block::default()
+                .title(Line::from(scope).right_aligned())
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Gray)),
+        )
+        .highlight_style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))
+        .divider(symbols::DOT);
+
+    f.render_widget(tabs, area);
 }
 
 fn draw_stats(f: &mut Frame, area: Rect, stats: &DuplicateStats) {
@@ -97,12 +139,18 @@ fn draw_duplicate_groups(f: &mut Frame, area: Rect, stats: &DuplicateStats, app:
         .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
         .split(area);
 
-    // Left: Group list
-    let items: Vec<ListItem> = stats
-        .groups
+    // Left: Group list, of which only the visible groups are built
+    let groups_area = chunks[0].inner(Margin::new(1, 1));
+    // Each group takes two lines: its size and its first file
+    app.duplicate_viewport.set_rows(usize::from(groups_area.height) / 2);
+    let visible = app
+        .duplicate_viewport
+        .visible(app.selected_duplicate_group, stats.groups.len());
+    let first_visible = visible.start;
+    let items: Vec<ListItem> = stats.groups[visible]
         .iter()
-        .enumerate()
-        .map(|(idx, group)| {
+        .zip(first_visible..)
+        .map(|(group, idx)| {
             let selected = app.selected_duplicate_group == idx;
             let style = if selected {
                 Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
@@ -125,16 +173,13 @@ fn draw_duplicate_groups(f: &mut Frame, area: Rect, stats: &DuplicateStats, app:
         })
         .collect();
 
+    let active = app.duplicate_focus == DuplicateFocus::GroupList;
     let list = List::new(items)
         .block(
             Block::default()
-                .title(if app.duplicate_focus == DuplicateFocus::GroupList {
-                    " Duplicate Groups [ACTIVE] "
-                } else {
-                    " Duplicate Groups "
-                })
+                .title(group_list_title(app, stats, active))
                 .borders(Borders::ALL)
-                .border_style(if app.duplicate_focus == DuplicateFocus::GroupList {
+                .border_style(if active {
                     Style::default().fg(Color::Yellow)
                 } else {
                     Style::default().fg(Color::Gray)
@@ -142,7 +187,11 @@ fn draw_duplicate_groups(f: &mut Frame, area: Rect, stats: &DuplicateStats, app:
         )
         .highlight_style(Style::default().bg(Color::DarkGray));
 
-    f.render_stateful_widget(list, chunks[0], &mut app.duplicate_list_state.clone());
+    let mut list_state = ListState::default().with_selected(app.selected_duplicate_group.checked_sub(first_visible));
+    f.render_stateful_widget(list, chunks[0], &mut list_state);
+    super::record_mouse_targets(app, |targets| {
+        targets.duplicate_groups = Some((super::screen_area(groups_area), first_visible));
+    });
 
     // Right: Selected group details
     if let Some(group) = stats.groups.get(app.selected_duplicate_group) {
@@ -150,6 +199,26 @@ fn draw_duplicate_groups(f: &mut Frame, area: Rect, stats: &DuplicateStats, app:
     }
 }
 
+/// Title of the group list with the position of the selected group, the filter and the order
+fn group_list_title(app: &App, stats: &DuplicateStats, active: bool) -> String {
+    let position = (app.selected_duplicate_group + 1).min(stats.groups.len());
+    let mut parts = vec![format!("Duplicate Groups ({position}/{})", stats.groups.len())];
+    if app.input_mode == InputMode::Insert {
+        parts.push(format!("🔍 {}▏", app.duplicate_filter));
+    } else if !app.duplicate_filter.is_empty() {
+        parts.push(format!("🔍 {}", app.duplicate_filter));
+    }
+    if app.duplicate_sort != DuplicateSort::Found {
+        parts.push(format!("by {}", app.duplicate_sort));
+    }
+    let title = parts.join(" • ");
+    if active {
+        format!(" {title} [ACTIVE] ")
+    } else {
+        format!(" {title} ")
+    }
+}
+
 fn draw_group_details(f: &mut Frame, area: Rect, group: &DuplicateGroup, app: &App) {
     let rows: Vec<Row> = group
         .files
@@ -206,32 +275,225 @@ fn draw_group_details(f: &mut Frame, area: Rect, group: &DuplicateGroup, app: &A
     .row_highlight_style(Style::default().bg(Color::DarkGray));
 
     f.render_widget(table, area);
+
+    // Rows start below the top border and the header
+    let rows_area = Rect {
+        x: area.x + 1,
+        y: area.y + 2,
+        width: area.width.saturating_sub(2),
+        height: area.height.saturating_sub(3),
+    };
+    super::record_mouse_targets(app, |targets| {
+        targets.duplicate_files = Some(super::screen_area(rows_area));
+    });
+}
+
+/// Draws two files of the current group side by side with their differences highlighted.
+fn draw_compare(f: &mut Frame, entries: &[CompareEntry; 2], app: &App) {
+    let area = centered_rect(90, 80, f.area());
+    f.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(" 🔍 Compare Duplicates ")
+        .title_style(Style::default().fg(ACCENT_COLOR).add_modifier(Modifier::BOLD))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(ACCENT_COLOR))
+        .style(Style::default().bg(BACKGROUND_ALT));
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([Constraint::Min(10), Constraint::Length(1)])
+        .split(area);
+
+    let panels = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(chunks[0]);
+
+    let details = entries.each_ref().map(compare_details);
+    for (side, entry) in entries.iter().enumerate() {
+        let other = &details[1 - side];
+        draw_compare_panel(f, panels[side], entry, side, &details[side], other, app);
+    }
+
+    let help = Paragraph::new(Line::from(vec![
+        Span::styled("1", Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)),
+        Span::raw(" / "),
+        Span::styled("2", Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)),
+        Span::raw(" - Mark left / right for deletion | "),
+        Span::styled("Esc", Style::default().fg(Color::Yellow)),
+        Span::raw(" - Close"),
+    ]))
+    .alignment(Alignment::Center);
+    f.render_widget(help, chunks[1]);
+}
+
+fn draw_compare_panel(
+    f: &mut Frame,
+    area: Rect,
+    entry: &CompareEntry,
+    side: usize,
+    details: &[(&'static str, String)],
+    other: &[(&'static str, String)],
+    app: &App,
+) {
+    let marked = app.selected_duplicate_items.contains(&entry.index);
+    let title = format!(
+        " {} {} {}",
+        side + 1,
+        entry.file.name,
+        if marked { "☑ marked for deletion " } else { "" }
+    );
+
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(if marked { Color::Red } else { MUTED_COLOR }));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    // Leave room for the details, plus a spare row for a wrapped path
+    let detail_rows = u16::try_from(details.len() + 2).unwrap_or(u16::MAX);
+    let thumbnail_rows = entry
+        .thumbnail
+        .as_ref()
+        .map_or(0, |_| inner.height.saturating_sub(detail_rows));
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(thumbnail_rows), Constraint::Min(0)])
+        .split(inner);
+
+    if let Some(thumbnail) = &entry.thumbnail {
+        f.render_widget(Paragraph::new(thumbnail_lines(thumbnail, chunks[0])), chunks[0]);
+    }
+
+    let lines: Vec<Line> = details
+        .iter()
+        .zip(other)
+        .map(|((label, value), (_, other_value))| {
+            let style = if value == other_value {
+                Style::default()
+            } else {
+                Style::default().fg(WARNING_COLOR).add_modifier(Modifier::BOLD)
+            };
+            Line::from(vec![
+                Span::styled(format!("{label:<16}"), Style::default().fg(MUTED_COLOR)),
+                Span::styled(value.clone(), style),
+            ])
+        })
+        .collect();
+
+    f.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), chunks[1]);
+}
+
+fn compare_details(entry: &CompareEntry) -> Vec<(&'static str, String)> {
+    let date = |date: Option<chrono::NaiveDateTime>| {
+        date.map_or_else(|| "-".to_string(), |d| d.format(DATE_FORMAT).to_string())
+    };
+
+    vec![
+        ("Size", format_bytes(entry.file.size)),
+        (
+            "Dimensions",
+            entry
+                .dimensions
+                .map_or_else(|| "-".to_string(), |(w, h)| format!("{w} × {h}")),
+        ),
+        ("Taken", date(entry.exif.taken)),
+        ("Digitized", date(entry.exif.digitized)),
+        ("EXIF modified", date(entry.exif.modified)),
+        ("File modified", entry.file.modified.format(DATE_FORMAT).to_string()),
+        ("Path", entry.file.path.display().to_string()),
+    ]
+}
+
+/// Renders a thumbnail with half blocks, two pixel rows per terminal row
+fn thumbnail_lines(thumbnail: &Thumbnail, area: Rect) -> Vec<Line<'static>> {
+    if thumbnail.width == 0 || thumbnail.height == 0 || area.width == 0 || area.height == 0 {
+        return Vec::new();
+    }
+
+    // Scale to fit while keeping the aspect ratio
+    let max_width = u32::from(area.width);
+    let max_height = u32::from(area.height) * 2;
+    let scale = f64::min(
+        f64::from(max_width) / f64::from(thumbnail.width),
+        f64::from(max_height) / f64::from(thumbnail.height),
+    );
+    let width = ((f64::from(thumbnail.width) * scale) as u32).clamp(1, max_width);
+    let height = ((f64::from(thumbnail.height) * scale) as u32).clamp(1, max_height);
+
+    let sample = |x: u32, y: u32| {
+        let [r, g, b] = thumbnail.pixel(x * thumbnail.width / width, y * thumbnail.height / height);
+        Color::Rgb(r, g, b)
+    };
+
+    (0..height.div_ceil(2))
+        .map(|row| {
+            let spans: Vec<Span> = (0..width)
+                .map(|x| {
+                    let top = sample(x, row * 2);
+                    let bottom = if row * 2 + 1 < height {
+                        sample(x, row * 2 + 1)
+                    } else {
+                        BACKGROUND_ALT
+                    };
+                    Span::styled("▀", Style::default().fg(top).bg(bottom))
+                })
+                .collect();
+            Line::from(spans).alignment(Alignment::Center)
+        })
+        .collect()
 }
 
 fn truncate_path(path: &str, max_width: usize) -> String {
-    if path.len() <= max_width {
+    let length = path.chars().count();
+    if length <= max_width {
         path.to_string()
     } else if max_width > 3 {
-        format!("...{}", &path[path.len() - (max_width - 3)..])
+        // Counted in characters, so narrow windows never cut a character in half
+        let tail: String = path.chars().skip(length - (max_width - 3)).collect();
+        format!("...{tail}")
     } else {
         "...".to_string()
     }
 }
 
-fn draw_no_scan(f: &mut Frame, area: Rect) {
-    let message = Paragraph::new(vec![
-        Line::from(""),
-        Line::from("No duplicate scan performed yet."),
-        Line::from(""),
-        Line::from("Press 's' to start scanning for duplicates."),
-    ])
-    .style(Style::default().fg(Color::Gray))
-    .alignment(Alignment::Center)
-    .block(
-        Block::default()
-            .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Gray)),
-    );
+fn draw_no_scan(f: &mut Frame, area: Rect, tab: usize) {
+    let lines = if tab == 2 {
+        vec![
+            Line::from(""),
+            Line::from("Videos have not been compared yet."),
+            Line::from(""),
+            Line::from("Press 'v' to find re-encoded copies by sampling frames with ffmpeg."),
+        ]
+    } else if tab == 3 {
+        vec![
+            Line::from(""),
+            Line::from("Photos have not been grouped into bursts yet."),
+            Line::from(""),
+            Line::from("Press 'b' to group shots taken within 2 seconds by the same camera, sharpest first."),
+        ]
+    } else {
+        vec![
+            Line::from(""),
+            Line::from("No duplicate scan performed yet."),
+            Line::from(""),
+            Line::from("Press 's' to check the scanned files, 'l' the destination library or 'o' any folder."),
+        ]
+    };
+    let message = Paragraph::new(lines)
+        .style(Style::default().fg(Color::Gray))
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Gray)),
+        );
 
     f.render_widget(message, area);
 }
@@ -240,18 +502,38 @@ fn draw_help(f: &mut Frame, area: Rect) {
     let help_text = vec![Line::from(vec![
         Span::styled("s", Style::default().fg(Color::Yellow)),
         Span::raw(" - Scan | "),
-        Span::styled("↑↓", Style::default().fg(Color::Yellow)),
+        Span::styled("l/o/f", Style::default().fg(Color::Yellow)),
+        Span::raw(" - Library/Folder/Scanned files | "),
+        Span::styled("Tab", Style::default().fg(Color::Yellow)),
+        Span::raw(" - Exact/Probable/Videos/Bursts | "),
+        Span::styled("v", Style::default().fg(Color::Yellow)),
+        Span::raw(" - Compare videos | "),
+        Span::styled("b", Style::default().fg(Color::Yellow)),
+        Span::raw(" - Find bursts | "),
+        Span::styled("A", Style::default().fg(Color::Yellow)),
+        Span::raw(" - Archive shots | "),
+        Span::styled("↑↓/PgUp/PgDn", Style::default().fg(Color::Yellow)),
         Span::raw(" - Navigate | "),
+        Span::styled("/", Style::default().fg(Color::Yellow)),
+        Span::raw(" - Filter groups | "),
+        Span::styled("S", Style::default().fg(Color::Yellow)),
+        Span::raw(" - Sort | "),
         Span::styled("←→", Style::default().fg(Color::Yellow)),
         Span::raw(" - Switch panes | "),
         Span::styled("Space", Style::default().fg(Color::Yellow)),
         Span::raw(" - Select | "),
         Span::styled("a", Style::default().fg(Color::Yellow)),
         Span::raw(" - Select all but first | "),
+        Span::styled("c", Style::default().fg(Color::Yellow)),
+        Span::raw(" - Compare | "),
+        Span::styled("g", Style::default().fg(Color::Yellow)),
+        Span::raw(" - Go to file | "),
         Span::styled("d", Style::default().fg(Color::Red)),
         Span::raw(" - Delete selected | "),
         Span::styled("D", Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)),
         Span::raw(" - DELETE ALL DUPLICATES | "),
+        Span::styled("h/H", Style::default().fg(Color::Yellow)),
+        Span::raw(" - Link selected/all | "),
         Span::styled("Esc", Style::default().fg(Color::Yellow)),
         Span::raw(" - Back"),
     ])];
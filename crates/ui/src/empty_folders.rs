@@ -0,0 +1,77 @@
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph},
+};
+use visualvault_app::App;
+
+use super::{ACCENT_COLOR, BACKGROUND_ALT, ERROR_COLOR, MUTED_COLOR, WARNING_COLOR};
+
+pub fn draw(f: &mut Frame, area: Rect, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Min(0),    // Folders
+            Constraint::Length(3), // Help
+        ])
+        .split(area);
+
+    draw_folders(f, chunks[0], app);
+    draw_help(f, chunks[1]);
+}
+
+fn draw_folders(f: &mut Frame, area: Rect, app: &App) {
+    let source = app.settings_cache.source_folder.as_deref();
+
+    let lines: Vec<Line> = if app.empty_folders.is_empty() {
+        vec![Line::from(Span::styled(
+            "No empty folders in the source folder",
+            Style::default().fg(MUTED_COLOR).add_modifier(Modifier::ITALIC),
+        ))]
+    } else {
+        app.empty_folders
+            .iter()
+            .skip(app.empty_folder_scroll)
+            .map(|dir| {
+                let shown = source.and_then(|source| dir.strip_prefix(source).ok()).unwrap_or(dir);
+                Line::from(vec![
+                    Span::styled("📂 ", Style::default().fg(WARNING_COLOR)),
+                    Span::styled(shown.display().to_string(), Style::default().fg(Color::White)),
+                ])
+            })
+            .collect()
+    };
+
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .title(format!(" 🧹 Empty Folders ({}) ", app.empty_folders.len()))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(MUTED_COLOR))
+            .style(Style::default().bg(BACKGROUND_ALT)),
+    );
+    f.render_widget(paragraph, area);
+}
+
+fn draw_help(f: &mut Frame, area: Rect) {
+    let help = Paragraph::new(Line::from(vec![
+        Span::styled("d", Style::default().fg(ERROR_COLOR).add_modifier(Modifier::BOLD)),
+        Span::raw(" - Remove all listed | "),
+        Span::styled("r", Style::default().fg(ACCENT_COLOR)),
+        Span::raw(" - Rescan | "),
+        Span::styled("↑↓", Style::default().fg(ACCENT_COLOR)),
+        Span::raw(" - Scroll | "),
+        Span::styled("Esc", Style::default().fg(ACCENT_COLOR)),
+        Span::raw(" - Back"),
+    ]))
+    .alignment(Alignment::Center)
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(MUTED_COLOR)),
+    );
+    f.render_widget(help, area);
+}
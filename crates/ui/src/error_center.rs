@@ -0,0 +1,203 @@
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph, Wrap},
+};
+use visualvault_app::App;
+use visualvault_models::{ErrorCenter, ErrorEntry, ErrorSource};
+
+use super::{ACCENT_COLOR, BACKGROUND_ALT, ERROR_COLOR, MUTED_COLOR, SUCCESS_COLOR, WARNING_COLOR};
+
+pub fn draw(f: &mut Frame, area: Rect, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(3), // Summary
+            Constraint::Min(0),    // Errors
+            Constraint::Length(7), // Selected error
+            Constraint::Length(3), // Help
+        ])
+        .split(area);
+
+    draw_summary(f, chunks[0], &app.error_center);
+    draw_errors(f, chunks[1], &app.error_center);
+    draw_details(f, chunks[2], app.error_center.selected_entry());
+    draw_help(f, chunks[3]);
+}
+
+fn draw_summary(f: &mut Frame, area: Rect, center: &ErrorCenter) {
+    let line = if center.is_empty() {
+        Line::from(Span::styled(
+            "✔ No errors since the app started",
+            Style::default().fg(SUCCESS_COLOR),
+        ))
+    } else {
+        let sources = [
+            ErrorSource::Scan,
+            ErrorSource::Metadata,
+            ErrorSource::Organize,
+            ErrorSource::Operation,
+        ];
+        let mut spans = Vec::new();
+        for source in sources {
+            if !spans.is_empty() {
+                spans.push(Span::raw(" | "));
+            }
+            spans.push(Span::styled(
+                format!("{} {source}", center.count(source)),
+                Style::default().fg(source_color(source)),
+            ));
+        }
+        Line::from(spans)
+    };
+
+    let paragraph = Paragraph::new(line).block(
+        Block::default()
+            .title(" 🚨 Error Center ")
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(MUTED_COLOR))
+            .style(Style::default().bg(BACKGROUND_ALT)),
+    );
+    f.render_widget(paragraph, area);
+}
+
+fn draw_errors(f: &mut Frame, area: Rect, center: &ErrorCenter) {
+    let lines: Vec<Line> = if center.is_empty() {
+        vec![Line::from(Span::styled(
+            "Scan, metadata and organize errors show up here",
+            Style::default().fg(MUTED_COLOR).add_modifier(Modifier::ITALIC),
+        ))]
+    } else {
+        let offset = center.selected.saturating_sub(visible_rows(area).saturating_sub(1));
+        center
+            .entries
+            .iter()
+            .enumerate()
+            .skip(offset)
+            .take(visible_rows(area))
+            .map(|(idx, entry)| {
+                let style = if idx == center.selected {
+                    Style::default()
+                        .fg(ACCENT_COLOR)
+                        .add_modifier(Modifier::BOLD | Modifier::REVERSED)
+                } else {
+                    Style::default().fg(Color::White)
+                };
+                let subject = entry.path.as_ref().map_or_else(
+                    || entry.message.clone(),
+                    |path| {
+                        path.file_name()
+                            .unwrap_or(path.as_os_str())
+                            .to_string_lossy()
+                            .to_string()
+                    },
+                );
+                Line::from(vec![
+                    Span::styled(
+                        format!("{} ", entry.timestamp.format("%H:%M:%S")),
+                        Style::default().fg(MUTED_COLOR),
+                    ),
+                    Span::styled(
+                        format!("{:<10}", entry.source.to_string()),
+                        Style::default().fg(source_color(entry.source)),
+                    ),
+                    Span::styled(subject, style),
+                    Span::styled(
+                        entry
+                            .path
+                            .as_ref()
+                            .map(|_| format!("  {}", entry.message))
+                            .unwrap_or_default(),
+                        Style::default().fg(MUTED_COLOR),
+                    ),
+                ])
+            })
+            .collect()
+    };
+
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .title(format!(" Errors ({}) ", center.len()))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(MUTED_COLOR))
+            .style(Style::default().bg(BACKGROUND_ALT)),
+    );
+    f.render_widget(paragraph, area);
+}
+
+fn draw_details(f: &mut Frame, area: Rect, entry: Option<&ErrorEntry>) {
+    let lines = entry.map_or_else(Vec::new, |entry| {
+        let mut lines = Vec::new();
+        if let Some(path) = &entry.path {
+            lines.push(Line::from(vec![
+                Span::styled("File: ", Style::default().fg(MUTED_COLOR)),
+                Span::styled(path.display().to_string(), Style::default().fg(Color::White)),
+            ]));
+        }
+        lines.push(Line::from(vec![
+            Span::styled("Error: ", Style::default().fg(MUTED_COLOR)),
+            Span::styled(entry.message.clone(), Style::default().fg(ERROR_COLOR)),
+        ]));
+        lines.push(Line::from(vec![
+            Span::styled("Fix: ", Style::default().fg(MUTED_COLOR)),
+            Span::styled(entry.remediation(), Style::default().fg(SUCCESS_COLOR)),
+        ]));
+        lines.push(Line::from(Span::styled(
+            entry.timestamp.format("%Y-%m-%d %H:%M:%S").to_string(),
+            Style::default().fg(MUTED_COLOR),
+        )));
+        lines
+    });
+
+    let paragraph = Paragraph::new(lines).wrap(Wrap { trim: true }).block(
+        Block::default()
+            .title(" Details ")
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(MUTED_COLOR))
+            .style(Style::default().bg(BACKGROUND_ALT)),
+    );
+    f.render_widget(paragraph, area);
+}
+
+fn draw_help(f: &mut Frame, area: Rect) {
+    let help = Paragraph::new(Line::from(vec![
+        Span::styled("d", Style::default().fg(WARNING_COLOR).add_modifier(Modifier::BOLD)),
+        Span::raw(" - Dismiss | "),
+        Span::styled("c", Style::default().fg(ERROR_COLOR)),
+        Span::raw(" - Clear all | "),
+        Span::styled("e", Style::default().fg(ACCENT_COLOR)),
+        Span::raw(" - Export CSV | "),
+        Span::styled("Enter", Style::default().fg(ACCENT_COLOR)),
+        Span::raw(" - Show file | "),
+        Span::styled("y", Style::default().fg(ACCENT_COLOR)),
+        Span::raw(" - Copy path | "),
+        Span::styled("Esc", Style::default().fg(ACCENT_COLOR)),
+        Span::raw(" - Back"),
+    ]))
+    .alignment(Alignment::Center)
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(MUTED_COLOR)),
+    );
+    f.render_widget(help, area);
+}
+
+const fn source_color(source: ErrorSource) -> Color {
+    match source {
+        ErrorSource::Scan | ErrorSource::Organize => ERROR_COLOR,
+        ErrorSource::Metadata => WARNING_COLOR,
+        ErrorSource::Operation => ACCENT_COLOR,
+    }
+}
+
+/// Rows a bordered list in `area` can show
+fn visible_rows(area: Rect) -> usize {
+    usize::from(area.height.saturating_sub(2))
+}
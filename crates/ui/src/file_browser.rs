@@ -0,0 +1,123 @@
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph},
+};
+use visualvault_app::App;
+use visualvault_models::{BrowserPane, BrowserSide};
+use visualvault_utils::format_bytes;
+
+use super::{ACCENT_COLOR, BACKGROUND_ALT, ERROR_COLOR, MUTED_COLOR, SUCCESS_COLOR, WARNING_COLOR};
+
+pub fn draw(f: &mut Frame, area: Rect, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Min(0),    // Source and destination panes
+            Constraint::Length(3), // Help
+        ])
+        .split(area);
+
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(chunks[0]);
+
+    let browser = &app.file_browser;
+    draw_pane(f, columns[0], app, &browser.left, browser.active == BrowserSide::Left);
+    draw_pane(f, columns[1], app, &browser.right, browser.active == BrowserSide::Right);
+    draw_help(f, chunks[1], app);
+}
+
+fn draw_pane(f: &mut Frame, area: Rect, app: &App, pane: &BrowserPane, focused: bool) {
+    let marked = &app.file_browser.marked;
+    let width = usize::from(area.width.saturating_sub(4));
+
+    let lines: Vec<Line> = if pane.entries.is_empty() {
+        vec![Line::from(Span::styled(
+            "Empty folder",
+            Style::default().fg(MUTED_COLOR).add_modifier(Modifier::ITALIC),
+        ))]
+    } else {
+        let rows = usize::from(area.height.saturating_sub(2));
+        let offset = pane.selected.saturating_sub(rows.saturating_sub(1));
+        pane.entries
+            .iter()
+            .enumerate()
+            .skip(offset)
+            .take(rows)
+            .map(|(idx, entry)| {
+                let is_marked = marked.contains(&entry.path);
+                let mut style = if entry.is_dir {
+                    Style::default().fg(ACCENT_COLOR)
+                } else if is_marked {
+                    Style::default().fg(WARNING_COLOR)
+                } else {
+                    Style::default().fg(Color::White)
+                };
+                if idx == pane.selected && focused {
+                    style = style.add_modifier(Modifier::BOLD | Modifier::REVERSED);
+                } else if idx == pane.selected {
+                    style = style.add_modifier(Modifier::UNDERLINED);
+                }
+
+                let (icon, size) = if entry.is_dir {
+                    ("📁", String::new())
+                } else {
+                    ("  ", format_bytes(entry.size))
+                };
+                let mark = if is_marked { "✓ " } else { "  " };
+                let name = format!("{mark}{icon} {}", entry.name());
+                let padding = width.saturating_sub(name.chars().count() + size.chars().count()).max(1);
+                Line::from(vec![
+                    Span::styled(name, style),
+                    Span::raw(" ".repeat(padding)),
+                    Span::styled(size, Style::default().fg(MUTED_COLOR)),
+                ])
+            })
+            .collect()
+    };
+
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .title(format!(" {} ", pane.dir.display()))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(if focused { ACCENT_COLOR } else { MUTED_COLOR }))
+            .style(Style::default().bg(BACKGROUND_ALT)),
+    );
+    f.render_widget(paragraph, area);
+}
+
+fn draw_help(f: &mut Frame, area: Rect, app: &App) {
+    let browser = &app.file_browser;
+    let targets = browser.targets().len();
+    let line = Line::from(vec![
+        Span::styled(
+            format!("{targets} selected → {}  ", browser.other_pane().dir.display()),
+            Style::default().fg(WARNING_COLOR),
+        ),
+        Span::styled("Space", Style::default().fg(ACCENT_COLOR)),
+        Span::raw(" - Mark | "),
+        Span::styled("m", Style::default().fg(ERROR_COLOR).add_modifier(Modifier::BOLD)),
+        Span::raw(" - Move | "),
+        Span::styled("c", Style::default().fg(SUCCESS_COLOR)),
+        Span::raw(" - Copy | "),
+        Span::styled("Enter/⌫", Style::default().fg(ACCENT_COLOR)),
+        Span::raw(" - Open/Up | "),
+        Span::styled("Tab", Style::default().fg(ACCENT_COLOR)),
+        Span::raw(" - Switch pane | "),
+        Span::styled("Esc", Style::default().fg(ACCENT_COLOR)),
+        Span::raw(" - Back"),
+    ]);
+
+    let help = Paragraph::new(line).alignment(Alignment::Center).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(MUTED_COLOR)),
+    );
+    f.render_widget(help, area);
+}
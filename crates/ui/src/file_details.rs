@@ -6,11 +6,14 @@ use ratatui::{
     widgets::{Block, Borders, Clear, Paragraph, Row, Table},
 };
 use tracing::info;
-use visualvault_models::{FileType, MediaFile, MediaMetadata};
+use visualvault_models::{CaptureInfo, FileTags, FileType, MediaFile, MediaMetadata};
 use visualvault_utils::format_bytes;
 
+use super::WARNING_COLOR;
+
+/// Draws the details of `file`, with the tags being typed in `tag_input` while they are edited
 #[allow(clippy::too_many_lines)]
-pub fn draw_modal(f: &mut Frame, file: &MediaFile) {
+pub fn draw_modal(f: &mut Frame, file: &MediaFile, tags: Option<&FileTags>, tag_input: Option<&str>) {
     let area = centered_rect(70, 80, f.area());
 
     // Clear the area first
@@ -24,7 +27,7 @@ pub fn draw_modal(f: &mut Frame, file: &MediaFile) {
             Constraint::Length(3),  // Title
             Constraint::Length(10), // Basic info
             Constraint::Length(8),  // File system info
-            Constraint::Min(5),     // Metadata (if available)
+            Constraint::Min(8),     // Metadata (if available)
             Constraint::Length(3),  // Help text
         ])
         .split(area);
@@ -43,6 +46,7 @@ pub fn draw_modal(f: &mut Frame, file: &MediaFile) {
     let icon = match file.file_type {
         FileType::Image => "🖼️",
         FileType::Video => "🎬",
+        FileType::Audio => "🎵",
         FileType::Document => "📄",
         FileType::Other => "📎",
     };
@@ -63,12 +67,25 @@ pub fn draw_modal(f: &mut Frame, file: &MediaFile) {
     let size = format_bytes(file.size);
     let created = file.created.format("%Y-%m-%d %H:%M:%S").to_string();
     let modified = file.modified.format("%Y-%m-%d %H:%M:%S").to_string();
+    let rating = tags.map(FileTags::stars).filter(|stars| !stars.is_empty());
+    let tag_list = tags.map(FileTags::tags_input).filter(|list| !list.is_empty());
+    let extension = file.content_extension.as_ref().map_or_else(
+        || file.extension.to_string(),
+        |content| format!("{} (content is .{content})", file.extension),
+    );
+    let extension_style = if file.content_extension.is_some() {
+        Style::default().fg(WARNING_COLOR)
+    } else {
+        Style::default()
+    };
     let basic_info = vec![
         Row::new(vec!["Type", &file_type]),
         Row::new(vec!["Size", &size]),
-        Row::new(vec!["Extension", &file.extension]),
+        Row::new(vec!["Extension", &extension]).style(extension_style),
         Row::new(vec!["Created", &created]),
         Row::new(vec!["Modified", &modified]),
+        Row::new(vec!["Rating", rating.as_deref().unwrap_or("Not rated")]).style(Style::default().fg(Color::Yellow)),
+        Row::new(vec!["Tags", tag_list.as_deref().unwrap_or("None")]).style(Style::default().fg(Color::Cyan)),
     ];
 
     let basic_table = Table::new(basic_info, [Constraint::Percentage(30), Constraint::Percentage(70)])
@@ -113,11 +130,22 @@ pub fn draw_modal(f: &mut Frame, file: &MediaFile) {
             .unwrap_or_else(|| "Unknown".to_string())
     };
 
-    let fs_info = vec![
+    let mut fs_info = vec![
         Row::new(vec!["Full Path", &full_path]),
         Row::new(vec!["Directory", &parent]),
         Row::new(vec!["Permissions", &permissions]),
     ];
+    if file.via_symlink {
+        fs_info.push(Row::new(vec!["Reached via", "Symlink"]).style(Style::default().fg(Color::Yellow)));
+    }
+    let live_photo_pair = file
+        .live_photo_pair
+        .as_ref()
+        .and_then(|pair| pair.file_name())
+        .map(|name| name.to_string_lossy().to_string());
+    if let Some(pair) = &live_photo_pair {
+        fs_info.push(Row::new(vec!["Live Photo", pair.as_str()]).style(Style::default().fg(Color::Yellow)));
+    }
 
     let fs_table = Table::new(fs_info, [Constraint::Percentage(30), Constraint::Percentage(70)])
         .block(
@@ -134,13 +162,26 @@ pub fn draw_modal(f: &mut Frame, file: &MediaFile) {
 
     // Metadata section (for images)
     if file.file_type == FileType::Image {
+        let sections = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(45), Constraint::Percentage(55)])
+            .split(chunks[3]);
+        draw_capture(f, sections[1], file.capture());
+
         if let Some(MediaMetadata::Image(metadata)) = &file.metadata {
-            let metadata_text = vec![
-                Line::from(format!("Width: {} px", metadata.width)),
-                Line::from(format!("Height: {} px", metadata.height)),
-                Line::from(format!("Format: {}", metadata.format)),
-                Line::from(format!("Color Type: {}", metadata.color_type)),
-            ];
+            let metadata_text = if metadata.has_dimensions() {
+                vec![
+                    Line::from(format!("Width: {} px", metadata.width)),
+                    Line::from(format!("Height: {} px", metadata.height)),
+                    Line::from(format!("Format: {}", metadata.format)),
+                    Line::from(format!("Color Type: {}", metadata.color_type)),
+                ]
+            } else {
+                vec![
+                    Line::from("Dimensions unavailable"),
+                    Line::from(format!("Format: {}", metadata.format)),
+                ]
+            };
 
             let metadata_paragraph = Paragraph::new(metadata_text)
                 .block(
@@ -151,7 +192,7 @@ pub fn draw_modal(f: &mut Frame, file: &MediaFile) {
                 )
                 .alignment(Alignment::Left);
 
-            f.render_widget(metadata_paragraph, chunks[3]);
+            f.render_widget(metadata_paragraph, sections[0]);
         } else {
             let no_metadata = Paragraph::new("No image metadata available")
                 .block(
@@ -162,8 +203,31 @@ pub fn draw_modal(f: &mut Frame, file: &MediaFile) {
                 )
                 .alignment(Alignment::Center);
 
-            f.render_widget(no_metadata, chunks[3]);
+            f.render_widget(no_metadata, sections[0]);
         }
+    } else if file.file_type == FileType::Audio {
+        let text = if let Some(MediaMetadata::Audio(tags)) = &file.metadata {
+            let unknown = || "Unknown".to_string();
+            vec![
+                Line::from(format!("Artist: {}", tags.artist.clone().unwrap_or_else(unknown))),
+                Line::from(format!("Album: {}", tags.album.clone().unwrap_or_else(unknown))),
+                Line::from(format!("Title: {}", tags.title.clone().unwrap_or_else(unknown))),
+                Line::from(format!("Year: {}", tags.year.map_or_else(unknown, |y| y.to_string()))),
+            ]
+        } else {
+            vec![Line::from("No audio tags found")]
+        };
+
+        let tags_paragraph = Paragraph::new(text)
+            .block(
+                Block::default()
+                    .title(" Audio Tags ")
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Gray)),
+            )
+            .alignment(Alignment::Left);
+
+        f.render_widget(tags_paragraph, chunks[3]);
     } else {
         // For non-images, show file content preview or other relevant info
         let preview = Paragraph::new("No additional metadata available for this file type")
@@ -178,12 +242,39 @@ pub fn draw_modal(f: &mut Frame, file: &MediaFile) {
         f.render_widget(preview, chunks[3]);
     }
 
+    if let Some(input) = tag_input {
+        let editor = Paragraph::new(Line::from(vec![
+            Span::styled("Tags: ", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+            Span::raw(input),
+            Span::styled("█", Style::default().fg(Color::Cyan)),
+            Span::styled(
+                "  (comma separated, Enter to save, Esc to cancel)",
+                Style::default().fg(Color::Rgb(150, 150, 150)),
+            ),
+        ]))
+        .alignment(Alignment::Center);
+        f.render_widget(editor, chunks[4]);
+        return;
+    }
+
     // Help text
     let help = Paragraph::new(vec![Line::from(vec![
         Span::styled("ESC", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
         Span::raw(" or "),
         Span::styled("q", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
-        Span::raw(" to close"),
+        Span::raw(" to close  "),
+        Span::styled("x", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+        Span::raw(" open  "),
+        Span::styled("X", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+        Span::raw(" show in file manager  "),
+        Span::styled("y", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+        Span::raw("/"),
+        Span::styled("Y", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+        Span::raw(" copy path/folder  "),
+        Span::styled("1-5", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+        Span::raw(" rate  "),
+        Span::styled("t", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+        Span::raw(" tags"),
     ])])
     .alignment(Alignment::Center)
     .style(Style::default().fg(Color::Rgb(150, 150, 150)));
@@ -191,6 +282,49 @@ pub fn draw_modal(f: &mut Frame, file: &MediaFile) {
     f.render_widget(help, chunks[4]);
 }
 
+/// Camera, lens and exposure settings the photo was taken with
+fn draw_capture(f: &mut Frame, area: Rect, capture: Option<&CaptureInfo>) {
+    let block = Block::default()
+        .title(" Capture ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Gray));
+
+    let Some(capture) = capture else {
+        let empty = Paragraph::new("No camera information")
+            .block(block)
+            .alignment(Alignment::Center);
+        f.render_widget(empty, area);
+        return;
+    };
+
+    let unknown = || "—".to_string();
+    let rows = vec![
+        Row::new(vec!["Camera".to_string(), capture.camera().unwrap_or_else(unknown)]),
+        Row::new(vec!["Lens".to_string(), capture.lens.clone().unwrap_or_else(unknown)]),
+        Row::new(vec![
+            "ISO".to_string(),
+            capture.iso.map_or_else(unknown, |iso| iso.to_string()),
+        ]),
+        Row::new(vec![
+            "Aperture".to_string(),
+            capture.aperture_label().unwrap_or_else(unknown),
+        ]),
+        Row::new(vec![
+            "Shutter".to_string(),
+            capture.shutter_speed_label().unwrap_or_else(unknown),
+        ]),
+        Row::new(vec![
+            "Focal Length".to_string(),
+            capture.focal_length_label().unwrap_or_else(unknown),
+        ]),
+    ];
+
+    let table = Table::new(rows, [Constraint::Length(13), Constraint::Min(10)])
+        .block(block)
+        .column_spacing(1);
+    f.render_widget(table, area);
+}
+
 fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
     let popup_layout = Layout::default()
         .direction(Direction::Vertical)
@@ -9,6 +9,7 @@ use ratatui::{
 use visualvault_app::App;
 use visualvault_models::FilterFocus;
 use visualvault_models::InputMode;
+use visualvault_models::MediaType;
 use visualvault_utils::format_bytes;
 
 pub fn draw(f: &mut Frame, area: Rect, app: &App) {
@@ -16,6 +17,7 @@ pub fn draw(f: &mut Frame, area: Rect, app: &App) {
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(3), // Header
+            Constraint::Length(3), // Expression
             Constraint::Length(3), // Tabs
             Constraint::Min(10),   // Content
             Constraint::Length(4), // Help
@@ -25,25 +27,32 @@ pub fn draw(f: &mut Frame, area: Rect, app: &App) {
     // Header
     draw_header(f, chunks[0], app);
 
+    // Effective filter expression
+    draw_expression(f, chunks[1], app);
+
     // Tabs
-    draw_tabs(f, chunks[1], app);
+    draw_tabs(f, chunks[2], app);
 
     // Content based on selected tab
     match app.filter_tab {
-        0 => draw_date_filters(f, chunks[2], app),
-        1 => draw_size_filters(f, chunks[2], app),
-        2 => draw_type_filters(f, chunks[2], app),
-        3 => draw_regex_filters(f, chunks[2], app),
+        0 => draw_date_filters(f, chunks[3], app),
+        1 => draw_size_filters(f, chunks[3], app),
+        2 => draw_type_filters(f, chunks[3], app),
+        3 => draw_regex_filters(f, chunks[3], app),
+        4 => draw_tag_filters(f, chunks[3], app),
+        5 => draw_extension_filters(f, chunks[3], app),
         _ => {}
     }
 
     // Help
-    draw_help(f, chunks[3], app);
+    draw_help(f, chunks[4], app);
 }
 
 fn draw_header(f: &mut Frame, area: Rect, app: &App) {
     let active_count = app.filter_set.active_filter_count();
-    let status = if app.filter_set.is_active {
+    let status = if app.filter_set.restricts_organize() {
+        format!(" ({active_count} active filters, organize matches only)")
+    } else if app.filter_set.is_active {
         format!(" ({active_count} active filters)")
     } else {
         " (inactive)".to_string()
@@ -60,8 +69,32 @@ fn draw_header(f: &mut Frame, area: Rect, app: &App) {
     f.render_widget(header, area);
 }
 
+fn draw_expression(f: &mut Frame, area: Rect, app: &App) {
+    let style = if app.filter_set.is_active {
+        Style::default().fg(Color::White)
+    } else {
+        Style::default().fg(Color::DarkGray)
+    };
+    let expression = Paragraph::new(Span::styled(app.filter_set.expression(), style)).block(
+        Block::default()
+            .title(format!(" Matching: {} (n: NOT, m: AND/OR) ", app.filter_set.combinator))
+            .borders(Borders::ALL),
+    );
+    f.render_widget(expression, area);
+}
+
+/// "NOT " in front of filters that leave their matches out
+fn negated_span(negated: bool) -> Span<'static> {
+    if negated {
+        Span::styled("NOT ", Style::default().fg(Color::Red).add_modifier(Modifier::BOLD))
+    } else {
+        Span::raw("")
+    }
+}
+
 fn draw_tabs(f: &mut Frame, area: Rect, app: &App) {
-    let titles = vec!["Date Range", "Size", "Media Type", "Regex"];
+    let titles = vec!["Date Range", "Size", "Media Type", "Regex", "Tags", "Extensions"];
+    super::record_tabs(app, area, &titles);
     let tabs = Tabs::new(titles)
         .style(Style::default().fg(Color::White))
         .highlight_style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))
@@ -92,6 +125,7 @@ fn draw_date_filters(f: &mut Frame, area: Rect, app: &App) {
                 let selected = app.filter_focus == FilterFocus::DateRange && app.selected_filter_index == idx;
 
                 ListItem::new(vec![Line::from(vec![
+                    negated_span(range.negated),
                     Span::raw(&range.name),
                     Span::styled(" | ", Style::default().fg(Color::DarkGray)),
                     Span::raw(format!("{from_str} → {to_str}")),
@@ -156,6 +190,7 @@ fn draw_size_filters(f: &mut Frame, area: Rect, app: &App) {
             let selected = app.filter_focus == FilterFocus::SizeRange && app.selected_filter_index == idx;
 
             ListItem::new(vec![Line::from(vec![
+                negated_span(range.negated),
                 Span::raw(&range.name),
                 Span::styled(" | ", Style::default().fg(Color::DarkGray)),
                 Span::raw(format!("{min_str} → {max_str}")),
@@ -205,11 +240,16 @@ fn draw_type_filters(f: &mut Frame, area: Rect, app: &App) {
         .map(|(idx, mt)| {
             let checkbox = if mt.enabled { "☑" } else { "☐" };
             let selected = app.filter_focus == FilterFocus::MediaType && app.selected_filter_index == idx;
-            let extensions = mt.extensions.join(", ");
+            let extensions = if mt.media_type == MediaType::Screenshot {
+                "detected by file name or screen-sized PNG".to_string()
+            } else {
+                mt.extensions.join(", ")
+            };
 
             ListItem::new(vec![
                 Line::from(vec![
                     Span::raw(format!("{checkbox} ")),
+                    negated_span(mt.negated),
                     Span::styled(
                         mt.media_type.to_string(),
                         if mt.enabled {
@@ -262,6 +302,7 @@ fn draw_regex_filters(f: &mut Frame, area: Rect, app: &App) {
 
             ListItem::new(vec![Line::from(vec![
                 Span::raw(format!("{checkbox} ")),
+                negated_span(pattern.negated),
                 Span::styled(&pattern.pattern, Style::default().fg(Color::Cyan)),
                 Span::styled(
                     format!(" [{}] [{}]", pattern.target, case),
@@ -297,13 +338,136 @@ fn draw_regex_input(f: &mut Frame, area: Rect, app: &App) {
         .style(Style::default().fg(Color::Yellow))
         .block(
             Block::default()
-                .title(" Enter regex pattern (e.g., '.*\\.tmp$' for temp files) ")
+                .title(" Enter regex pattern (e.g., '.*\\.tmp$' for temp files, 'camera:EOS R5' for a camera) ")
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(Color::Yellow)),
         );
     f.render_widget(input, area);
 }
 
+fn draw_tag_filters(f: &mut Frame, area: Rect, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(5), Constraint::Length(3)])
+        .split(area);
+
+    let rating = app.filter_set.min_rating.map_or_else(
+        || "Any rating".to_string(),
+        |stars| format!("{} or more", "★".repeat(usize::from(stars))),
+    );
+    let rating = Paragraph::new(Span::styled(rating, Style::default().fg(Color::Yellow))).block(
+        Block::default()
+            .title(" Minimum Rating (1-5: set, 0: any) ")
+            .borders(Borders::ALL),
+    );
+    f.render_widget(rating, chunks[0]);
+
+    // Tags every matching file must carry
+    let items: Vec<ListItem> = app
+        .filter_set
+        .required_tags
+        .iter()
+        .enumerate()
+        .map(|(idx, tag)| {
+            let selected = app.filter_focus == FilterFocus::Tags && app.selected_filter_index == idx;
+            ListItem::new(Line::from(vec![
+                Span::raw("🏷 "),
+                Span::styled(tag, Style::default().fg(Color::Cyan)),
+            ]))
+            .style(if selected {
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            })
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .title(" Required Tags (a: add, d: delete) ")
+                .borders(Borders::ALL),
+        )
+        .highlight_style(Style::default().bg(Color::DarkGray));
+
+    f.render_widget(list, chunks[1]);
+
+    if app.input_mode == InputMode::Editing && app.filter_focus == FilterFocus::Tags {
+        let input = Paragraph::new(app.filter_input.as_str())
+            .style(Style::default().fg(Color::Yellow))
+            .block(
+                Block::default()
+                    .title(" Enter tags, separated by commas (e.g., 'beach, family') ")
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Yellow)),
+            );
+        f.render_widget(input, chunks[2]);
+    }
+}
+
+fn draw_extension_filters(f: &mut Frame, area: Rect, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(5), Constraint::Length(3)])
+        .split(area);
+
+    // Extension lists to keep or leave out
+    let items: Vec<ListItem> = if app.filter_set.extension_filters.is_empty() {
+        vec![
+            ListItem::new(" No extension filters set. Press 'a' to add one.")
+                .style(Style::default().fg(Color::DarkGray)),
+        ]
+    } else {
+        app.filter_set
+            .extension_filters
+            .iter()
+            .enumerate()
+            .map(|(idx, filter)| {
+                let checkbox = if filter.enabled { "☑" } else { "☐" };
+                let selected = app.filter_focus == FilterFocus::Extensions && app.selected_filter_index == idx;
+                let (mode, color) = if filter.exclude {
+                    ("Exclude", Color::Red)
+                } else {
+                    ("Include", Color::Green)
+                };
+
+                ListItem::new(Line::from(vec![
+                    Span::raw(format!("{checkbox} ")),
+                    Span::styled(format!("{mode:<8}"), Style::default().fg(color)),
+                    Span::styled(filter.extensions.join(", "), Style::default().fg(Color::Cyan)),
+                ]))
+                .style(if selected {
+                    Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default()
+                })
+            })
+            .collect()
+    };
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .title(" Extensions (a: add, d: delete, space: toggle) ")
+                .borders(Borders::ALL),
+        )
+        .highlight_style(Style::default().bg(Color::DarkGray));
+
+    f.render_widget(list, chunks[0]);
+
+    if app.input_mode == InputMode::Editing && app.filter_focus == FilterFocus::Extensions {
+        let input = Paragraph::new(app.filter_input.as_str())
+            .style(Style::default().fg(Color::Yellow))
+            .block(
+                Block::default()
+                    .title(" Enter extensions to include (e.g., 'jpg,png,heic'), or '!gif' to exclude ")
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Yellow)),
+            );
+        f.render_widget(input, chunks[1]);
+    }
+}
+
 fn draw_help(f: &mut Frame, area: Rect, app: &App) {
     let help_text = if app.input_mode == InputMode::Editing {
         vec![Line::from(vec![
@@ -324,10 +488,16 @@ fn draw_help(f: &mut Frame, area: Rect, app: &App) {
             Span::raw(" - Add | "),
             Span::styled("d", Style::default().fg(Color::Red)),
             Span::raw(" - Delete | "),
+            Span::styled("n", Style::default().fg(Color::Red)),
+            Span::raw(" - NOT | "),
+            Span::styled("m", Style::default().fg(Color::Yellow)),
+            Span::raw(" - AND/OR | "),
             Span::styled("c", Style::default().fg(Color::Yellow)),
             Span::raw(" - Clear all | "),
             Span::styled("t", Style::default().fg(Color::Yellow)),
             Span::raw(" - Toggle filters | "),
+            Span::styled("o", Style::default().fg(Color::Yellow)),
+            Span::raw(" - Organize matches only | "),
             Span::styled("Enter", Style::default().fg(Color::Green)),
             Span::raw(" - Apply | "),
             Span::styled("Esc", Style::default().fg(Color::Yellow)),
@@ -0,0 +1,99 @@
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+};
+use visualvault_app::App;
+use visualvault_models::EditingField;
+
+use super::{ACCENT_COLOR, BACKGROUND_ALT, MUTED_COLOR, WARNING_COLOR, centered_rect};
+
+/// Draws the folder picker for the source or destination folder.
+pub fn draw_folder_picker(f: &mut Frame, app: &App) {
+    let Some(picker) = &app.folder_picker else {
+        return;
+    };
+
+    let area = centered_rect(60, 60, f.area());
+    f.render_widget(Clear, area);
+
+    let key_style = Style::default().fg(ACCENT_COLOR).add_modifier(Modifier::BOLD);
+    let hint_style = Style::default().fg(MUTED_COLOR).add_modifier(Modifier::ITALIC);
+    let title = match picker.field {
+        EditingField::DestinationFolder => " 📤 Choose Destination Folder ",
+        EditingField::DuplicateFolder => " 🔍 Choose Folder to Check for Duplicates ",
+        _ => " 📥 Choose Source Folder ",
+    };
+    let block = Block::default()
+        .title(title)
+        .title_alignment(Alignment::Center)
+        .title_style(key_style)
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(ACCENT_COLOR))
+        .style(Style::default().bg(BACKGROUND_ALT));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2), // Folder being shown
+            Constraint::Min(0),    // Subfolders
+            Constraint::Length(2), // Hints
+        ])
+        .split(inner);
+
+    let header = Paragraph::new(Line::from(vec![
+        Span::styled(" 📂 ", Style::default().fg(WARNING_COLOR)),
+        Span::styled(
+            picker.dir.display().to_string(),
+            Style::default().fg(WARNING_COLOR).add_modifier(Modifier::BOLD),
+        ),
+    ]));
+    f.render_widget(header, chunks[0]);
+
+    let lines: Vec<Line> = if picker.folders.is_empty() {
+        vec![Line::from(Span::styled("  No subfolders", hint_style))]
+    } else {
+        let rows = usize::from(chunks[1].height);
+        let offset = picker.selected.saturating_sub(rows.saturating_sub(1));
+        picker
+            .folders
+            .iter()
+            .enumerate()
+            .skip(offset)
+            .take(rows)
+            .map(|(i, folder)| {
+                let name = folder.file_name().map_or_else(
+                    || folder.display().to_string(),
+                    |name| name.to_string_lossy().to_string(),
+                );
+                let marker = if i == picker.selected { "▶ " } else { "  " };
+                let style = if i == picker.selected {
+                    key_style
+                } else {
+                    Style::default().fg(Color::White)
+                };
+                Line::from(Span::styled(format!("  {marker}📁 {name}"), style))
+            })
+            .collect()
+    };
+    f.render_widget(Paragraph::new(lines), chunks[1]);
+
+    let hidden = if picker.show_hidden { "hide" } else { "show" };
+    let hints = Paragraph::new(vec![
+        Line::from(Span::styled(
+            format!("Enter opens • Backspace goes up • h {hidden}s hidden folders"),
+            hint_style,
+        )),
+        Line::from(Span::styled(
+            "s selects the folder shown above • Esc cancels",
+            hint_style,
+        )),
+    ])
+    .alignment(Alignment::Center);
+    f.render_widget(hints, chunks[2]);
+}
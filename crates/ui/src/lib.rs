@@ -3,23 +3,49 @@
 #![allow(clippy::cast_precision_loss)]
 use ratatui::{
     Frame,
-    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, BorderType, Borders, Clear, Gauge, Padding, Paragraph},
+    widgets::{Block, BorderType, Borders, Clear, Gauge, Padding, Paragraph, Wrap},
 };
 use tracing::info;
 use visualvault_app::App;
-use visualvault_models::AppState;
+use visualvault_models::{AppState, InputMode, MouseTargets, ScreenArea};
 use visualvault_utils::format_bytes;
 
+mod accessibility;
+mod activity_log;
+mod archives;
+mod batch_rename;
+mod bulk_actions;
+mod cloud_upload;
+mod collections;
+mod command_palette;
+mod conflict;
 mod dashboard;
+mod date_fix;
+mod device_import;
+mod dialog;
 mod duplicate_detector;
+mod empty_folders;
+mod error_center;
+mod file_browser;
 mod file_details;
 mod filtering;
+mod folder_picker;
+mod log_viewer;
+mod manifest_restore;
+mod onboarding;
+mod organize_report;
+mod performance;
+mod profiles;
 mod progress;
+mod quarantine;
 mod search;
 mod settings;
+mod space_explorer;
+mod toasts;
+mod verify;
 
 // Beautiful color palette (matching dashboard)
 const ACCENT_COLOR: Color = Color::Rgb(139, 233, 253); // Cyan
@@ -30,12 +56,31 @@ const MUTED_COLOR: Color = Color::Rgb(98, 114, 164); // Gray
 const BACKGROUND_ALT: Color = Color::Rgb(30, 30, 46); // Dark background
 const BACKGROUND_MAIN: Color = Color::Rgb(24, 24, 37); // Main background
 const VERSION: &str = "0.8.0"; // Updated version
+/// Smallest terminal the views are laid out for; smaller ones show a notice instead
+const MIN_WIDTH: u16 = 60;
+const MIN_HEIGHT: u16 = 20;
 
 pub fn draw(f: &mut Frame, app: &mut App) {
+    draw_screen(f, app);
+    if accessibility::is_on(app) {
+        accessibility::apply(f.buffer_mut());
+    }
+}
+
+fn draw_screen(f: &mut Frame, app: &mut App) {
+    if let Ok(targets) = app.mouse_targets.get_mut() {
+        *targets = MouseTargets::default();
+    }
+
     // Draw main background
     let background = Block::default().style(Style::default().bg(BACKGROUND_MAIN));
     f.render_widget(background, f.area());
 
+    if f.area().width < MIN_WIDTH || f.area().height < MIN_HEIGHT {
+        draw_too_small(f);
+        return;
+    }
+
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .margin(0)
@@ -59,7 +104,8 @@ pub fn draw(f: &mut Frame, app: &mut App) {
             dashboard::draw(f, chunks[1], app);
             // Draw file details modal on top
             if let Some(file) = app.cached_files.get(file_idx) {
-                file_details::draw_modal(f, file);
+                let tag_input = (app.input_mode == InputMode::Editing).then_some(app.input_buffer.as_str());
+                file_details::draw_modal(f, file, app.file_tags.get(&file.path), tag_input);
             }
         }
         AppState::Scanning | AppState::Organizing => {
@@ -71,15 +117,83 @@ pub fn draw(f: &mut Frame, app: &mut App) {
         }
         AppState::DuplicateReview => duplicate_detector::draw(f, chunks[1], app),
         AppState::Filters => filtering::draw(f, chunks[1], app),
+        AppState::ActivityLog => activity_log::draw(f, chunks[1], app),
+        AppState::EmptyFolders => empty_folders::draw(f, chunks[1], app),
+        AppState::Quarantine => quarantine::draw(f, chunks[1], app),
+        AppState::DateFix => date_fix::draw(f, chunks[1], app),
+        AppState::ManifestRestore => manifest_restore::draw(f, chunks[1], app),
+        AppState::Archives => archives::draw(f, chunks[1], app),
+        AppState::Verify => verify::draw(f, chunks[1], app),
+        AppState::CloudUpload => cloud_upload::draw(f, chunks[1], app),
+        AppState::Collections => collections::draw(f, chunks[1], app),
+        AppState::FileBrowser => file_browser::draw(f, chunks[1], app),
+        AppState::OrganizeReport => organize_report::draw(f, chunks[1], app),
+        AppState::ErrorCenter => error_center::draw(f, chunks[1], app),
+        AppState::Performance => performance::draw(f, chunks[1], app),
+        AppState::SpaceExplorer => space_explorer::draw(f, chunks[1], app),
+        AppState::Notifications => toasts::draw(f, chunks[1], app),
     }
 
     // Draw enhanced status bar
     draw_enhanced_status_bar(f, chunks[2], app);
 
+    // Draw the batch rename preview while a pattern is being edited
+    if app.batch_rename.is_some() {
+        batch_rename::draw_batch_rename(f, app);
+    }
+
+    // Draw the bulk action menu for the marked files
+    if app.bulk_actions.is_some() {
+        bulk_actions::draw_bulk_actions(f, app);
+    }
+
+    // Draw the settings profile picker
+    if app.profile_picker.is_some() {
+        profiles::draw_profile_picker(f, app);
+    }
+
+    // Draw the folder picker for the source or destination folder
+    if app.folder_picker.is_some() {
+        folder_picker::draw_folder_picker(f, app);
+    }
+
+    // Draw the camera card and phone picker
+    if app.device_import.is_some() {
+        device_import::draw_device_import(f, app);
+    }
+
+    // Draw the organize conflict dialog while decisions are pending
+    if !app.pending_conflicts.is_empty() {
+        conflict::draw_conflict_modal(f, app);
+    }
+
+    // Draw the log viewer over any view
+    if app.log_viewer.is_some() {
+        log_viewer::draw_log_viewer(f, app);
+    }
+
     // Draw help overlay if needed
     if app.show_help {
         draw_help_overlay(f, app);
     }
+
+    // The setup wizard covers the dashboard until it is finished or skipped
+    if app.onboarding.is_some() {
+        onboarding::draw_onboarding(f, app);
+    }
+
+    // The command palette opens over everything else
+    if app.command_palette.is_some() {
+        command_palette::draw_command_palette(f, app);
+    }
+
+    // A dialog waiting for an answer takes every key, so it is drawn on top
+    if app.dialog.is_some() {
+        dialog::draw_dialog(f, app);
+    }
+
+    // Toasts never take keys, so they can sit above everything
+    toasts::draw_toasts(f, chunks[1], app);
 }
 
 #[allow(clippy::too_many_lines)]
@@ -179,6 +293,21 @@ fn draw_enhanced_header(f: &mut Frame, area: Rect, app: &App) {
         AppState::FileDetails(_) => ("📄", "Details", Color::White, "File information"),
         AppState::DuplicateReview => ("🔄", "Duplicates", Color::Magenta, "Review duplicates"),
         AppState::Filters => ("🔧", "Filters", Color::Magenta, "Advanced filtering"),
+        AppState::ActivityLog => ("📜", "Activity", ACCENT_COLOR, "Recent operations"),
+        AppState::EmptyFolders => ("🧹", "Cleanup", WARNING_COLOR, "Empty folders"),
+        AppState::Quarantine => ("🧪", "Quarantine", WARNING_COLOR, "Suspect files"),
+        AppState::DateFix => ("🕒", "Date Fix", WARNING_COLOR, "Wrong photo dates"),
+        AppState::ManifestRestore => ("📜", "Restore", WARNING_COLOR, "Back to original paths"),
+        AppState::Archives => ("📦", "Archives", ACCENT_COLOR, "Media inside archives"),
+        AppState::Verify => ("🛡", "Verify", ACCENT_COLOR, "Library integrity"),
+        AppState::CloudUpload => ("☁", "Upload", ACCENT_COLOR, "Cloud export"),
+        AppState::Collections => ("📚", "Collections", SUCCESS_COLOR, "Albums of files"),
+        AppState::FileBrowser => ("🗂", "Browser", WARNING_COLOR, "Move files by hand"),
+        AppState::OrganizeReport => ("📋", "Report", SUCCESS_COLOR, "Last organize run"),
+        AppState::ErrorCenter => ("🚨", "Errors", ERROR_COLOR, "Recent errors"),
+        AppState::Performance => ("⏱", "Performance", ACCENT_COLOR, "Operation timings"),
+        AppState::SpaceExplorer => ("💽", "Space", WARNING_COLOR, "Largest files and folders"),
+        AppState::Notifications => ("🔔", "Notifications", ACCENT_COLOR, "Recent results"),
     };
 
     let state_lines = vec![
@@ -206,7 +335,7 @@ fn draw_enhanced_header(f: &mut Frame, area: Rect, app: &App) {
 
     // Add version number in top-right corner
     let version_area = Rect {
-        x: area.x + area.width - VERSION.len() as u16 - 2,
+        x: (area.x + area.width).saturating_sub(VERSION.len() as u16 + 2),
         y: area.y,
         width: VERSION.len() as u16 + 1,
         height: 1,
@@ -263,6 +392,81 @@ fn draw_enhanced_status_bar(f: &mut Frame, area: Rect, app: &App) {
             ("🗑", "d", "Delete", ERROR_COLOR),
             ("☑", "a", "Select", WARNING_COLOR),
         ],
+        AppState::ActivityLog => vec![
+            ("◀", "q", "Back", MUTED_COLOR),
+            ("↕", "↑↓", "Scroll", ACCENT_COLOR),
+            ("", "", "", Color::default()),
+        ],
+        AppState::EmptyFolders => vec![
+            ("◀", "q", "Back", MUTED_COLOR),
+            ("🗑", "d", "Remove", ERROR_COLOR),
+            ("↻", "r", "Rescan", ACCENT_COLOR),
+        ],
+        AppState::Quarantine => vec![
+            ("◀", "q", "Back", MUTED_COLOR),
+            ("🧪", "m", "Quarantine", WARNING_COLOR),
+            ("↕", "↑↓", "Scroll", ACCENT_COLOR),
+        ],
+        AppState::DateFix => vec![
+            ("◀", "q", "Back", MUTED_COLOR),
+            ("🕒", "m", "From EXIF", WARNING_COLOR),
+            ("✎", "x", "Write EXIF", WARNING_COLOR),
+        ],
+        AppState::ManifestRestore => vec![
+            ("◀", "q", "Back", MUTED_COLOR),
+            ("📜", "m", "Restore", WARNING_COLOR),
+            ("↕", "↑↓", "Scroll", ACCENT_COLOR),
+        ],
+        AppState::Archives => vec![
+            ("◀", "q", "Back", MUTED_COLOR),
+            ("☑", "Space", "Select", ACCENT_COLOR),
+            ("📦", "x", "Extract", WARNING_COLOR),
+        ],
+        AppState::CloudUpload => vec![
+            ("◀", "q", "Back", MUTED_COLOR),
+            ("↻", "r", "Retry", WARNING_COLOR),
+            ("↕", "↑↓", "Scroll", ACCENT_COLOR),
+        ],
+        AppState::Verify => vec![
+            ("◀", "q", "Back", MUTED_COLOR),
+            ("💾", "e", "Export", ACCENT_COLOR),
+            ("✔", "a", "Accept", WARNING_COLOR),
+        ],
+        AppState::Collections => vec![
+            ("◀", "q", "Back", MUTED_COLOR),
+            ("➕", "n", "New", SUCCESS_COLOR),
+            ("💾", "e", "Export", ACCENT_COLOR),
+        ],
+        AppState::FileBrowser => vec![
+            ("◀", "q", "Back", MUTED_COLOR),
+            ("➜", "m", "Move", ERROR_COLOR),
+            ("⧉", "c", "Copy", SUCCESS_COLOR),
+        ],
+        AppState::OrganizeReport => vec![
+            ("◀", "q", "Back", MUTED_COLOR),
+            ("⇅", "s", "Sort", ACCENT_COLOR),
+            ("💾", "e", "Export", SUCCESS_COLOR),
+        ],
+        AppState::ErrorCenter => vec![
+            ("◀", "q", "Back", MUTED_COLOR),
+            ("✖", "d", "Dismiss", WARNING_COLOR),
+            ("💾", "e", "Export", SUCCESS_COLOR),
+        ],
+        AppState::Performance => vec![
+            ("◀", "q", "Back", MUTED_COLOR),
+            ("⇅", "↑↓", "Select", ACCENT_COLOR),
+            ("✖", "c", "Clear", ERROR_COLOR),
+        ],
+        AppState::SpaceExplorer => vec![
+            ("◀", "q", "Back", MUTED_COLOR),
+            ("↵", "Enter", "Open", ACCENT_COLOR),
+            ("⇥", "Tab", "Files", WARNING_COLOR),
+        ],
+        AppState::Notifications => vec![
+            ("◀", "q", "Back", MUTED_COLOR),
+            ("✖", "d", "Hide", ACCENT_COLOR),
+            ("🗑", "c", "Clear", ERROR_COLOR),
+        ],
         _ => vec![
             ("◀", "q", "Quit", MUTED_COLOR),
             ("❓", "?", "Help", ACCENT_COLOR),
@@ -290,97 +494,85 @@ fn draw_enhanced_status_bar(f: &mut Frame, area: Rect, app: &App) {
     let left = Paragraph::new(Line::from(shortcut_spans)).alignment(Alignment::Left);
     f.render_widget(left, chunks[0]);
 
-    // Center section - Enhanced messages with animations
-    let center_content = if let Some(error) = &app.error_message {
-        vec![Line::from(vec![
-            Span::styled("🚨 ", Style::default().fg(ERROR_COLOR)),
-            Span::styled(error, Style::default().fg(ERROR_COLOR).add_modifier(Modifier::BOLD)),
-        ])]
-    } else if let Some(success) = &app.success_message {
-        vec![Line::from(vec![
-            Span::styled("✨ ", Style::default().fg(SUCCESS_COLOR)),
-            Span::styled(success, Style::default().fg(SUCCESS_COLOR).add_modifier(Modifier::BOLD)),
-        ])]
-    } else {
-        match app.state {
-            AppState::FileDetails(idx) => {
-                if let Some(file) = app.cached_files.get(idx) {
-                    vec![Line::from(vec![
-                        Span::styled("📋 ", Style::default().fg(ACCENT_COLOR)),
-                        Span::raw("Viewing: "),
-                        Span::styled(
-                            file.name.to_string(),
-                            Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
-                        ),
-                    ])]
-                } else {
-                    vec![Line::from(vec![Span::styled(
-                        "✓ Ready",
-                        Style::default().fg(SUCCESS_COLOR),
-                    )])]
-                }
+    // Center section - what the current view is doing; results show up as toasts
+    let center_content = match app.state {
+        AppState::FileDetails(idx) => {
+            if let Some(file) = app.cached_files.get(idx) {
+                vec![Line::from(vec![
+                    Span::styled("📋 ", Style::default().fg(ACCENT_COLOR)),
+                    Span::raw("Viewing: "),
+                    Span::styled(
+                        file.name.to_string(),
+                        Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
+                    ),
+                ])]
+            } else {
+                vec![Line::from(vec![Span::styled(
+                    "✓ Ready",
+                    Style::default().fg(SUCCESS_COLOR),
+                )])]
             }
-            AppState::Scanning => {
-                let progress = app.progress.try_read();
-                if let Ok(progress) = progress {
-                    vec![Line::from(vec![
-                        Span::styled(
-                            "🔍 ",
-                            Style::default().fg(ACCENT_COLOR).add_modifier(Modifier::SLOW_BLINK),
-                        ),
-                        Span::raw("Found "),
-                        Span::styled(
-                            format!("{}", progress.current),
-                            Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
-                        ),
-                        Span::raw(" files..."),
-                    ])]
-                } else {
-                    vec![Line::from(vec![Span::styled(
-                        "🔍 Scanning...",
-                        Style::default().fg(ACCENT_COLOR),
-                    )])]
-                }
+        }
+        AppState::Scanning => {
+            let progress = app.progress.try_read();
+            if let Ok(progress) = progress {
+                vec![Line::from(vec![
+                    Span::styled(
+                        "🔍 ",
+                        Style::default().fg(ACCENT_COLOR).add_modifier(Modifier::SLOW_BLINK),
+                    ),
+                    Span::raw("Found "),
+                    Span::styled(
+                        format!("{}", progress.current),
+                        Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
+                    ),
+                    Span::raw(" files..."),
+                ])]
+            } else {
+                vec![Line::from(vec![Span::styled(
+                    "🔍 Scanning...",
+                    Style::default().fg(ACCENT_COLOR),
+                )])]
             }
-            AppState::Organizing => {
-                let progress = app.progress.try_read();
-                if let Ok(progress) = progress {
-                    let percentage = if progress.total > 0 {
-                        (progress.current as f32 / progress.total as f32 * 100.0) as u8
-                    } else {
-                        0
-                    };
-                    vec![Line::from(vec![
-                        Span::styled(
-                            "📦 ",
-                            Style::default().fg(SUCCESS_COLOR).add_modifier(Modifier::SLOW_BLINK),
-                        ),
-                        Span::raw("Organizing: "),
-                        Span::styled(
-                            format!("{}/{}", progress.current, progress.total),
-                            Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
-                        ),
-                        Span::raw(" ("),
-                        Span::styled(
-                            format!("{percentage}%"),
-                            Style::default().fg(SUCCESS_COLOR).add_modifier(Modifier::BOLD),
-                        ),
-                        Span::raw(")"),
-                    ])]
+        }
+        AppState::Organizing => {
+            let progress = app.progress.try_read();
+            if let Ok(progress) = progress {
+                let percentage = if progress.total > 0 {
+                    (progress.current as f32 / progress.total as f32 * 100.0) as u8
                 } else {
-                    vec![Line::from(vec![Span::styled(
-                        "📦 Organizing...",
-                        Style::default().fg(SUCCESS_COLOR),
-                    )])]
-                }
-            }
-            _ => {
+                    0
+                };
                 vec![Line::from(vec![
-                    Span::styled("✓ ", Style::default().fg(SUCCESS_COLOR)),
-                    Span::styled("Ready", Style::default().fg(Color::White)),
+                    Span::styled(
+                        "📦 ",
+                        Style::default().fg(SUCCESS_COLOR).add_modifier(Modifier::SLOW_BLINK),
+                    ),
+                    Span::raw("Organizing: "),
+                    Span::styled(
+                        format!("{}/{}", progress.current, progress.total),
+                        Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
+                    ),
+                    Span::raw(" ("),
+                    Span::styled(
+                        format!("{percentage}%"),
+                        Style::default().fg(SUCCESS_COLOR).add_modifier(Modifier::BOLD),
+                    ),
+                    Span::raw(")"),
                 ])]
+            } else {
+                vec![Line::from(vec![Span::styled(
+                    "📦 Organizing...",
+                    Style::default().fg(SUCCESS_COLOR),
+                )])]
             }
         }
+        _ => {
+            vec![Line::from(vec![
+                Span::styled("✓ ", Style::default().fg(SUCCESS_COLOR)),
+                Span::styled("Ready", Style::default().fg(Color::White)),
+            ])]
+        }
     };
 
     let center = Paragraph::new(center_content).alignment(Alignment::Center);
@@ -449,6 +641,16 @@ fn draw_enhanced_status_bar(f: &mut Frame, area: Rect, app: &App) {
                 .label("");
             f.render_widget(mini_gauge, stats_chunks[1]);
         }
+    } else if app.read_only.is_some() {
+        let badge = Paragraph::new("🔒 read-only")
+            .alignment(Alignment::Right)
+            .style(Style::default().fg(WARNING_COLOR));
+        f.render_widget(badge, stats_chunks[1]);
+    } else if !app.error_center.is_empty() {
+        let badge = Paragraph::new(format!("🚨 {} errors (!)", app.error_center.len()))
+            .alignment(Alignment::Right)
+            .style(Style::default().fg(ERROR_COLOR));
+        f.render_widget(badge, stats_chunks[1]);
     }
 }
 
@@ -471,7 +673,7 @@ fn format_number(n: usize) -> String {
 }
 
 #[allow(clippy::too_many_lines)]
-fn draw_help_overlay(f: &mut Frame, app: &App) {
+fn draw_help_overlay(f: &mut Frame, app: &mut App) {
     let area = centered_rect(90, 85, f.area());
     f.render_widget(Clear, area);
 
@@ -490,6 +692,16 @@ fn draw_help_overlay(f: &mut Frame, app: &App) {
         Line::from("  ↑/↓           - Navigate items in current tab"),
         Line::from("  PgUp/PgDn     - Navigate pages quickly"),
         Line::from("  Enter         - View file details"),
+        Line::from("  x / X         - Open the file with its default app / show it in the file manager"),
+        Line::from("  y / Y         - Copy the file's path / folder to the clipboard"),
+        Line::from("  1-5 / 0 / t   - In file details: rate the file / clear rating / edit its tags"),
+        Line::from("  s / S         - Sort the Files tab by the next column / flip the order"),
+        Line::from("  g             - Group the Files tab by folder, month or type"),
+        Line::from("  Enter / ← / → - Toggle / collapse / expand the selected group"),
+        Line::from("  Space         - Mark/unmark a file in the Files tab"),
+        Line::from("  V / A         - Mark the range from the last marked file / all listed files"),
+        Line::from("  b             - Bulk actions: trash, move, ignore or export the marked files"),
+        Line::from("  R             - Batch rename marked files (or the focused one) with a pattern"),
         Line::from(""),
         Line::from(vec![Span::styled(
             "🔍 Core Operations",
@@ -501,19 +713,56 @@ fn draw_help_overlay(f: &mut Frame, app: &App) {
         Line::from("  F             - Advanced filters (date, size, type, regex)"),
         Line::from("  u             - Update folder statistics"),
         Line::from("  D             - Duplicate detector and cleanup"),
-        Line::from("  Ctrl+Z        - Undo last operation (if enabled, see settings)"),
+        Line::from("  L             - Activity log (scheduled runs and their results)"),
+        Line::from("  G             - Show the log file in the file manager"),
+        Line::from("  Ctrl+L        - Log viewer (l level filter, / search)"),
+        Line::from("  Ctrl+P        - Command palette: search every action and run it"),
+        Line::from("  O             - Per-file report of the last organize run"),
+        Line::from("  !             - Error center: recent errors with fixes, dismiss or export them"),
+        Line::from("  N             - Notifications: the last 50 results shown as toasts"),
+        Line::from("  W             - Performance: stage timings of recent scans and organize runs"),
+        Line::from("  H             - Space explorer: largest folders and files of the library, Enter drills in"),
+        Line::from("  E             - Find and remove empty folders in the source folder"),
+        Line::from("  Q             - Review suspect files and move them to quarantine"),
+        Line::from("  T             - Fix photos whose file date disagrees with the EXIF date"),
+        Line::from("  R             - Restore organized files to their original paths from manifests"),
+        Line::from("  Z             - Review media found inside zip and tar archives and extract them"),
+        Line::from("  I             - Verify destination files against the library index"),
+        Line::from("  U             - Progress of the upload to the cloud export bucket, retry failed files"),
+        Line::from("  P             - Switch, create or delete settings profiles"),
+        Line::from("  C             - Collections: browse, create, delete or export (copy) albums"),
+        Line::from("  M             - Import new media from a camera card or phone (DCIM folder)"),
+        Line::from("  B             - Two-pane browser: move (m) or copy (c) files between any folders"),
+        Line::from("  Ctrl+U        - Undo last operation (if enabled, see settings)"),
         Line::from("  Ctrl+R        - Redo last undone operation (if enabled, see settings)"),
         Line::from(""),
+        Line::from(vec![Span::styled(
+            "⚠️  Organize Conflicts (conflict policy \"ask\")",
+            Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+        )]),
+        Line::from("  s / o / n / r - Skip, overwrite, overwrite if newer, rename"),
+        Line::from("  Shift+key     - Apply the choice to all remaining conflicts"),
+        Line::from("  Esc           - Skip all remaining conflicts"),
+        Line::from(""),
         Line::from(vec![Span::styled(
             "🔄 Duplicate Management",
             Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD),
         )]),
         Line::from("  s             - Scan for duplicates (in duplicate view)"),
+        Line::from("  Tab           - Switch between exact duplicates, probable copies, videos and bursts"),
+        Line::from("  b             - Group burst shots, sharpest first"),
+        Line::from("  A             - Archive the selected burst shots"),
         Line::from("  ←/→           - Switch between group list and file list"),
+        Line::from("  PgUp/PgDn     - Page through the groups"),
+        Line::from("  /             - Filter groups by file name or folder"),
+        Line::from("  S             - Sort groups by scan order, wasted space or size"),
         Line::from("  Space         - Select/deselect individual files"),
         Line::from("  a             - Select all but first file in group"),
+        Line::from("  c             - Compare two files side by side"),
+        Line::from("  g             - Show the file in the dashboard's file list"),
         Line::from("  d             - Delete selected duplicate files"),
         Line::from("  D             - Delete ALL duplicates from ALL groups"),
+        Line::from("  h/H           - Replace selected/ALL duplicates with links to the kept file"),
         Line::from(""),
         Line::from(vec![Span::styled(
             "🔧 Advanced Filters (Press F)",
@@ -524,6 +773,7 @@ fn draw_help_overlay(f: &mut Frame, app: &App) {
         Line::from("  d             - Delete selected filter"),
         Line::from("  Space         - Toggle filter on/off"),
         Line::from("  t             - Toggle all filters active/inactive"),
+        Line::from("  o             - Limit organizing to files matching the filters"),
         Line::from("  c             - Clear all filters"),
         Line::from("  Enter         - Apply filters and return to dashboard"),
         Line::from(""),
@@ -534,6 +784,7 @@ fn draw_help_overlay(f: &mut Frame, app: &App) {
         Line::from("  Dates: 'today', 'last 7 days', '2024-01-01 to 2024-12-31'"),
         Line::from("  Sizes: '>10MB', '<1GB', '10MB-100MB'"),
         Line::from("  Regex: '.*\\.tmp$' (temp files), 'IMG_.*' (camera files)"),
+        Line::from("  Tags: 'beach, family' (files with all tags), 1-5 for a minimum rating"),
         Line::from(""),
         Line::from(vec![Span::styled(
             "🔍 Search & File Details",
@@ -543,6 +794,10 @@ fn draw_help_overlay(f: &mut Frame, app: &App) {
         Line::from("  Esc           - Clear search and return to dashboard"),
         Line::from("  ↑/↓           - Navigate search results"),
         Line::from("  Enter         - View file details from search"),
+        Line::from("  y / Y         - Copy the result's path / folder to the clipboard"),
+        Line::from("  Tab           - Search the source, the destination library or both"),
+        Line::from("  i             - Re-index the destination library"),
+        Line::from("  g             - Show the result in the dashboard's file list"),
         Line::from(""),
         Line::from(vec![Span::styled(
             "⚙️  Settings & Configuration",
@@ -554,6 +809,7 @@ fn draw_help_overlay(f: &mut Frame, app: &App) {
         Line::from("  Tab           - Switch settings tabs"),
         Line::from("  Space         - Toggle checkboxes"),
         Line::from("  Enter         - Edit text fields"),
+        Line::from("  b             - Browse for the source or destination folder"),
         Line::from(""),
         Line::from(vec![Span::styled(
             "🎯 Quick Actions",
@@ -563,7 +819,8 @@ fn draw_help_overlay(f: &mut Frame, app: &App) {
         Line::from("  ?/F1          - Toggle this help"),
         Line::from("  q             - Quit application"),
         Line::from("  Esc           - Cancel current action/go back"),
-        Line::from("  Ctrl+C        - Force quit"),
+        Line::from("  Ctrl+C        - Quit; press twice to force while an operation runs"),
+        Line::from("  Ctrl+Z        - Suspend to the shell; resume with fg"),
         Line::from(""),
         Line::from(vec![Span::styled(
             "📊 Status Indicators",
@@ -597,6 +854,7 @@ fn draw_help_overlay(f: &mut Frame, app: &App) {
     let visible_height = area.height.saturating_sub(2) as usize; // Account for borders
     let max_scroll = content_height.saturating_sub(visible_height);
     let scroll_offset = app.help_scroll.min(max_scroll);
+    app.help_max_scroll = max_scroll;
 
     #[allow(clippy::cast_possible_truncation)]
     let help = Paragraph::new(help_text)
@@ -604,11 +862,7 @@ fn draw_help_overlay(f: &mut Frame, app: &App) {
             Block::default()
                 .title(format!(
                     " VisualVault Help & Keyboard Shortcuts [{}%] ",
-                    if max_scroll == 0 {
-                        100
-                    } else {
-                        (scroll_offset * 100) / max_scroll
-                    }
+                    (scroll_offset * 100).checked_div(max_scroll).unwrap_or(100)
                 ))
                 .title_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
                 .borders(Borders::ALL)
@@ -627,7 +881,7 @@ fn draw_help_overlay(f: &mut Frame, app: &App) {
                 .style(Style::default().fg(Color::Yellow))
                 .alignment(Alignment::Center);
             let up_area = Rect {
-                x: area.x + area.width - 2,
+                x: (area.x + area.width).saturating_sub(2),
                 y: area.y + 1,
                 width: 1,
                 height: 1,
@@ -641,8 +895,8 @@ fn draw_help_overlay(f: &mut Frame, app: &App) {
                 .style(Style::default().fg(Color::Yellow))
                 .alignment(Alignment::Center);
             let down_area = Rect {
-                x: area.x + area.width - 2,
-                y: area.y + area.height - 2,
+                x: (area.x + area.width).saturating_sub(2),
+                y: (area.y + area.height).saturating_sub(2),
                 width: 1,
                 height: 1,
             };
@@ -651,6 +905,87 @@ fn draw_help_overlay(f: &mut Frame, app: &App) {
     }
 }
 
+/// Converts a layout rectangle for mouse hit testing
+const fn screen_area(area: Rect) -> ScreenArea {
+    ScreenArea {
+        x: area.x,
+        y: area.y,
+        width: area.width,
+        height: area.height,
+    }
+}
+
+/// Records where clickable parts of the frame were drawn
+fn record_mouse_targets(app: &App, update: impl FnOnce(&mut MouseTargets)) {
+    if let Ok(mut targets) = app.mouse_targets.lock() {
+        update(&mut targets);
+    }
+}
+
+/// Records the title areas of a bordered tab bar, laid out the way `Tabs` draws them:
+/// one cell of padding around each title and a one-cell divider between titles.
+fn record_tabs<S: AsRef<str>>(app: &App, area: Rect, titles: &[S]) {
+    let inner = area.inner(Margin::new(1, 1));
+    let mut x = inner.x;
+    let tabs = titles
+        .iter()
+        .map(|title| {
+            let width = Line::from(title.as_ref()).width() as u16 + 2;
+            let tab = ScreenArea {
+                x,
+                y: inner.y,
+                width,
+                height: 1,
+            };
+            x = x.saturating_add(width + 1);
+            tab
+        })
+        .collect();
+    record_mouse_targets(app, |targets| targets.tabs = tabs);
+}
+
+/// Asks for a larger terminal, instead of squeezing the views into one too small for them
+fn draw_too_small(f: &mut Frame) {
+    let area = f.area();
+    let size_style = |fits: bool| {
+        Style::default()
+            .fg(if fits { SUCCESS_COLOR } else { ERROR_COLOR })
+            .add_modifier(Modifier::BOLD)
+    };
+    let lines = vec![
+        Line::from(Span::styled(
+            "Terminal too small",
+            Style::default().fg(WARNING_COLOR).add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(area.width.to_string(), size_style(area.width >= MIN_WIDTH)),
+            Span::raw(" × "),
+            Span::styled(area.height.to_string(), size_style(area.height >= MIN_HEIGHT)),
+        ]),
+        Line::from(Span::styled(
+            format!("needs {MIN_WIDTH} × {MIN_HEIGHT}"),
+            Style::default().fg(MUTED_COLOR),
+        )),
+        Line::from(""),
+        Line::from(Span::styled(
+            "Enlarge the window to continue",
+            Style::default().fg(MUTED_COLOR).add_modifier(Modifier::ITALIC),
+        )),
+    ];
+    // Centered vertically when there is room, from the top otherwise
+    let top = area.height.saturating_sub(lines.len() as u16) / 2;
+    let text_area = Rect {
+        y: area.y + top,
+        height: area.height - top,
+        ..area
+    };
+    let notice = Paragraph::new(lines)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+    f.render_widget(notice, text_area);
+}
+
 fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
     let popup_layout = Layout::default()
         .direction(Direction::Vertical)
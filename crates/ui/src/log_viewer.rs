@@ -0,0 +1,94 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+};
+use visualvault_app::App;
+use visualvault_models::LogLevel;
+
+use super::{ACCENT_COLOR, BACKGROUND_ALT, ERROR_COLOR, MUTED_COLOR, SUCCESS_COLOR, WARNING_COLOR, centered_rect};
+
+/// Draws the overlay showing the end of the log file, newest lines at the bottom.
+pub fn draw_log_viewer(f: &mut Frame, app: &App) {
+    let Some(viewer) = &app.log_viewer else {
+        return;
+    };
+
+    let area = centered_rect(90, 85, f.area());
+    f.render_widget(Clear, area);
+
+    let filter = viewer
+        .min_level
+        .map_or_else(|| "all levels".to_string(), |level| format!("{level} and above"));
+    let block = Block::default()
+        .title(format!(" 📜 Log ({filter}) "))
+        .title_style(Style::default().fg(ACCENT_COLOR).add_modifier(Modifier::BOLD))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(ACCENT_COLOR))
+        .style(Style::default().bg(BACKGROUND_ALT));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .split(inner);
+
+    let visible = viewer.visible_lines();
+    let end = visible.len().saturating_sub(viewer.scroll);
+    let start = end.saturating_sub(usize::from(chunks[0].height));
+    let lines: Vec<Line> = if visible.is_empty() {
+        vec![Line::from(Span::styled(
+            "No log lines match",
+            Style::default().fg(MUTED_COLOR).add_modifier(Modifier::ITALIC),
+        ))]
+    } else {
+        visible[start..end]
+            .iter()
+            .map(|(level, line)| Line::from(Span::styled(*line, Style::default().fg(level_color(*level)))))
+            .collect()
+    };
+    f.render_widget(Paragraph::new(lines), chunks[0]);
+
+    let mut help = vec![
+        Span::styled("l", Style::default().fg(ACCENT_COLOR).add_modifier(Modifier::BOLD)),
+        Span::raw(" level  "),
+        Span::styled("/", Style::default().fg(ACCENT_COLOR).add_modifier(Modifier::BOLD)),
+        Span::raw(" search  "),
+        Span::styled(
+            "↑↓ PgUp/PgDn",
+            Style::default().fg(ACCENT_COLOR).add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(" scroll  "),
+        Span::styled("End", Style::default().fg(ACCENT_COLOR).add_modifier(Modifier::BOLD)),
+        Span::raw(" follow  "),
+        Span::styled("Esc", Style::default().fg(ACCENT_COLOR).add_modifier(Modifier::BOLD)),
+        Span::raw(" close"),
+    ];
+    if viewer.searching || !viewer.search.is_empty() {
+        help.push(Span::styled("   Search: ", Style::default().fg(MUTED_COLOR)));
+        help.push(Span::styled(
+            format!("{}{}", viewer.search, if viewer.searching { "│" } else { "" }),
+            Style::default().fg(WARNING_COLOR),
+        ));
+    }
+    if viewer.scroll > 0 {
+        help.push(Span::styled(
+            format!("   ↑ {} newer lines", viewer.scroll),
+            Style::default().fg(MUTED_COLOR),
+        ));
+    }
+    f.render_widget(Paragraph::new(Line::from(help)), chunks[1]);
+}
+
+const fn level_color(level: Option<LogLevel>) -> Color {
+    match level {
+        Some(LogLevel::Error) => ERROR_COLOR,
+        Some(LogLevel::Warn) => WARNING_COLOR,
+        Some(LogLevel::Info) => SUCCESS_COLOR,
+        Some(LogLevel::Debug | LogLevel::Trace) | None => MUTED_COLOR,
+    }
+}
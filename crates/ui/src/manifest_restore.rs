@@ -0,0 +1,87 @@
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph},
+};
+use visualvault_app::App;
+
+use super::{ACCENT_COLOR, BACKGROUND_ALT, ERROR_COLOR, MUTED_COLOR, SUCCESS_COLOR, WARNING_COLOR};
+
+pub fn draw(f: &mut Frame, area: Rect, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Min(0),    // Files to restore
+            Constraint::Length(3), // Help
+        ])
+        .split(area);
+
+    draw_items(f, chunks[0], app);
+    draw_help(f, chunks[1]);
+}
+
+fn draw_items(f: &mut Frame, area: Rect, app: &App) {
+    let lines: Vec<Line> = app
+        .manifest_restore
+        .iter()
+        .skip(app.manifest_restore_scroll)
+        .take(usize::from(area.height))
+        .map(|item| {
+            let mut spans = vec![
+                match item.problem {
+                    None => Span::styled("↩ ", Style::default().fg(SUCCESS_COLOR)),
+                    Some(_) => Span::styled("✖ ", Style::default().fg(ERROR_COLOR)),
+                },
+                Span::styled(item.current().display().to_string(), Style::default().fg(Color::White)),
+                Span::styled(" → ", Style::default().fg(MUTED_COLOR)),
+                Span::styled(item.original().display().to_string(), Style::default().fg(ACCENT_COLOR)),
+            ];
+            if let Some(problem) = item.problem {
+                spans.push(Span::styled(
+                    format!("  {}", problem.description()),
+                    Style::default().fg(WARNING_COLOR),
+                ));
+            }
+            Line::from(spans)
+        })
+        .collect();
+
+    let restorable = app
+        .manifest_restore
+        .iter()
+        .filter(|item| item.problem.is_none())
+        .count();
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .title(format!(
+                " 📜 Restore from Manifest ({restorable} of {} files) ",
+                app.manifest_restore.len()
+            ))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(MUTED_COLOR))
+            .style(Style::default().bg(BACKGROUND_ALT)),
+    );
+    f.render_widget(paragraph, area);
+}
+
+fn draw_help(f: &mut Frame, area: Rect) {
+    let help = Paragraph::new(Line::from(vec![
+        Span::styled("m", Style::default().fg(WARNING_COLOR).add_modifier(Modifier::BOLD)),
+        Span::raw(" - Move files back to their original paths | "),
+        Span::styled("↑↓", Style::default().fg(ACCENT_COLOR)),
+        Span::raw(" - Scroll | "),
+        Span::styled("Esc", Style::default().fg(ACCENT_COLOR)),
+        Span::raw(" - Back"),
+    ]))
+    .alignment(Alignment::Center)
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(MUTED_COLOR)),
+    );
+    f.render_widget(help, area);
+}
@@ -0,0 +1,200 @@
+use std::str::FromStr;
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Margin, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
+};
+use visualvault_app::App;
+use visualvault_config::{ConflictPolicy, OrganizationMode};
+use visualvault_models::OnboardingStep;
+
+use super::settings::{folder_check_line, folder_overlap_line};
+use super::{ACCENT_COLOR, BACKGROUND_ALT, MUTED_COLOR, SUCCESS_COLOR, WARNING_COLOR, centered_rect};
+
+/// Draws the setup wizard shown on first launch, one step at a time.
+pub fn draw_onboarding(f: &mut Frame, app: &App) {
+    let Some(step) = app.onboarding else {
+        return;
+    };
+
+    let area = centered_rect(70, 70, f.area());
+    f.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(format!(
+            " 👋 Welcome to VisualVault • Step {} of {} ",
+            step.number(),
+            OnboardingStep::ALL.len()
+        ))
+        .title_style(Style::default().fg(ACCENT_COLOR).add_modifier(Modifier::BOLD))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(ACCENT_COLOR))
+        .style(Style::default().bg(BACKGROUND_ALT));
+    let inner = block.inner(area).inner(Margin::new(2, 1));
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(1), Constraint::Length(1)])
+        .split(inner);
+
+    let (heading, explanation) = step_text(step);
+    f.render_widget(
+        Paragraph::new(vec![
+            Line::from(Span::styled(
+                heading,
+                Style::default().fg(ACCENT_COLOR).add_modifier(Modifier::BOLD),
+            )),
+            Line::from(Span::styled(explanation, Style::default().fg(MUTED_COLOR))),
+        ])
+        .wrap(Wrap { trim: true }),
+        chunks[0],
+    );
+
+    let body = match step {
+        OnboardingStep::SourceFolder | OnboardingStep::DestinationFolder => folder_lines(app, step),
+        OnboardingStep::OrganizeBy => {
+            let current = OrganizationMode::from_str(&app.settings_cache.organize_by).unwrap_or_default();
+            OrganizationMode::ALL
+                .iter()
+                .map(|&mode| choice_line(mode == current, mode_label(mode)))
+                .collect()
+        }
+        OnboardingStep::ConflictPolicy => {
+            let current = app.settings_cache.effective_conflict_policy();
+            ConflictPolicy::ALL
+                .iter()
+                .map(|&policy| choice_line(policy == current, policy_label(policy)))
+                .collect()
+        }
+        OnboardingStep::InitialScan => summary_lines(app),
+    };
+    f.render_widget(Paragraph::new(body).wrap(Wrap { trim: false }), chunks[1]);
+
+    draw_keys(f, chunks[2], step);
+}
+
+const fn step_text(step: OnboardingStep) -> (&'static str, &'static str) {
+    match step {
+        OnboardingStep::SourceFolder => (
+            "Where are your photos and videos?",
+            "VisualVault scans this folder and its subfolders for media files.",
+        ),
+        OnboardingStep::DestinationFolder => (
+            "Where should the organized library go?",
+            "Files are moved into dated or typed folders here. It is created if it does not exist.",
+        ),
+        OnboardingStep::OrganizeBy => (
+            "How should files be arranged?",
+            "This can be changed later in Settings.",
+        ),
+        OnboardingStep::ConflictPolicy => (
+            "What if a file with the same name is already there?",
+            "Duplicates found by content are handled separately in the duplicate review.",
+        ),
+        OnboardingStep::InitialScan => (
+            "Ready to go",
+            "The settings below are saved when you continue. Scanning only reads files; nothing is moved until you organize.",
+        ),
+    }
+}
+
+fn folder_lines(app: &App, step: OnboardingStep) -> Vec<Line<'static>> {
+    let is_destination = step == OnboardingStep::DestinationFolder;
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled("> ", Style::default().fg(ACCENT_COLOR).add_modifier(Modifier::BOLD)),
+            Span::styled(format!("{}│", app.input_buffer), Style::default().fg(WARNING_COLOR)),
+        ]),
+        folder_check_line(app, is_destination),
+    ];
+    if is_destination {
+        lines.push(folder_overlap_line(app));
+    }
+    lines
+}
+
+fn choice_line(selected: bool, (name, description): (&'static str, &'static str)) -> Line<'static> {
+    let (marker, style) = if selected {
+        ("● ", Style::default().fg(SUCCESS_COLOR).add_modifier(Modifier::BOLD))
+    } else {
+        ("○ ", Style::default().fg(MUTED_COLOR))
+    };
+    Line::from(vec![
+        Span::styled(marker, style),
+        Span::styled(format!("{name:<22}"), style),
+        Span::styled(description, Style::default().fg(MUTED_COLOR)),
+    ])
+}
+
+const fn mode_label(mode: OrganizationMode) -> (&'static str, &'static str) {
+    match mode {
+        OrganizationMode::Yearly => ("Yearly", "2024/photo.jpg"),
+        OrganizationMode::Monthly => ("Monthly", "2024/03-March/photo.jpg"),
+        OrganizationMode::ByType => ("By type", "Images/photo.jpg"),
+    }
+}
+
+const fn policy_label(policy: ConflictPolicy) -> (&'static str, &'static str) {
+    match policy {
+        ConflictPolicy::Skip => ("Skip", "Leave the new file in the source"),
+        ConflictPolicy::OverwriteIfNewer => ("Overwrite if newer", "Replace it when the new file is newer"),
+        ConflictPolicy::OverwriteAlways => ("Overwrite", "Always replace the existing file"),
+        ConflictPolicy::Rename => ("Rename", "Keep both, numbering the new file"),
+        ConflictPolicy::Ask => ("Ask", "Ask for each clash while organizing"),
+    }
+}
+
+fn summary_lines(app: &App) -> Vec<Line<'static>> {
+    let settings = &app.settings_cache;
+    let folder = |folder: Option<&std::path::PathBuf>| {
+        folder.map_or_else(|| "Not configured".to_string(), |folder| folder.display().to_string())
+    };
+    let row = |label: &'static str, value: String| {
+        Line::from(vec![
+            Span::styled(format!("{label:<14}"), Style::default().fg(MUTED_COLOR)),
+            Span::raw(value),
+        ])
+    };
+    vec![
+        row("Source", folder(settings.source_folder.as_ref())),
+        row("Destination", folder(settings.destination_folder.as_ref())),
+        row("Organize", settings.organize_by.clone()),
+        row("On conflict", settings.effective_conflict_policy().to_string()),
+        Line::from(""),
+        Line::from(Span::styled(
+            "Scan the source folder now?",
+            Style::default().fg(ACCENT_COLOR).add_modifier(Modifier::BOLD),
+        )),
+    ]
+}
+
+fn draw_keys(f: &mut Frame, area: Rect, step: OnboardingStep) {
+    let key = |key: &'static str| Span::styled(key, Style::default().fg(ACCENT_COLOR).add_modifier(Modifier::BOLD));
+    let back = if step == OnboardingStep::SourceFolder {
+        " skip setup"
+    } else {
+        " back"
+    };
+    let mut spans = match step {
+        OnboardingStep::SourceFolder | OnboardingStep::DestinationFolder => {
+            vec![key("Tab"), Span::raw(" complete  "), key("Enter"), Span::raw(" next  ")]
+        }
+        OnboardingStep::OrganizeBy | OnboardingStep::ConflictPolicy => {
+            vec![key("↑↓"), Span::raw(" choose  "), key("Enter"), Span::raw(" next  ")]
+        }
+        OnboardingStep::InitialScan => vec![
+            key("y/Enter"),
+            Span::raw(" save and scan  "),
+            key("n"),
+            Span::raw(" save only  "),
+        ],
+    };
+    spans.push(key("Esc"));
+    spans.push(Span::raw(back));
+    f.render_widget(Paragraph::new(Line::from(spans)), area);
+}
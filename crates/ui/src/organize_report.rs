@@ -0,0 +1,238 @@
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph},
+};
+use visualvault_app::App;
+use visualvault_models::{FileOutcome, OrganizeResult, OrganizedFile, RemoteTransfer};
+use visualvault_utils::format_bytes;
+
+use super::{ACCENT_COLOR, BACKGROUND_ALT, ERROR_COLOR, MUTED_COLOR, SUCCESS_COLOR, WARNING_COLOR};
+
+pub fn draw(f: &mut Frame, area: Rect, app: &App) {
+    // Transfers to an rclone remote add their outcome and what undo can still reach
+    let summary_height = if app
+        .last_organize_result
+        .as_ref()
+        .is_some_and(|result| result.remote_transfer.is_some())
+    {
+        6
+    } else {
+        4
+    };
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(summary_height), // Summary
+            Constraint::Min(0),                 // Files
+            Constraint::Length(3),              // Help
+        ])
+        .split(area);
+
+    draw_summary(f, chunks[0], app.last_organize_result.as_ref());
+    draw_files(f, chunks[1], app);
+    draw_help(f, chunks[2]);
+}
+
+fn draw_summary(f: &mut Frame, area: Rect, result: Option<&OrganizeResult>) {
+    let lines = result.map_or_else(
+        || {
+            vec![Line::from(Span::styled(
+                "No organize run yet",
+                Style::default().fg(MUTED_COLOR).add_modifier(Modifier::ITALIC),
+            ))]
+        },
+        |result| {
+            let count = |outcomes: &[FileOutcome]| {
+                result
+                    .files
+                    .iter()
+                    .filter(|file| outcomes.contains(&file.outcome))
+                    .count()
+            };
+            let mut lines = vec![
+                Line::from(vec![
+                    Span::styled(
+                        result.destination.display().to_string(),
+                        Style::default().fg(Color::White),
+                    ),
+                    Span::styled(
+                        format!("  {}", result.timestamp.format("%Y-%m-%d %H:%M:%S")),
+                        Style::default().fg(MUTED_COLOR),
+                    ),
+                    Span::styled(
+                        if result.rolled_back { "  ↩ rolled back" } else { "" },
+                        Style::default().fg(WARNING_COLOR).add_modifier(Modifier::BOLD),
+                    ),
+                ]),
+                Line::from(vec![
+                    Span::styled(
+                        format!("{} moved", count(&[FileOutcome::Moved])),
+                        Style::default().fg(SUCCESS_COLOR),
+                    ),
+                    Span::raw(" | "),
+                    Span::styled(
+                        format!(
+                            "{} renamed or overwritten",
+                            count(&[FileOutcome::Renamed, FileOutcome::Overwritten])
+                        ),
+                        Style::default().fg(ACCENT_COLOR),
+                    ),
+                    Span::raw(" | "),
+                    Span::styled(
+                        format!(
+                            "{} skipped",
+                            count(&[
+                                FileOutcome::SkippedDuplicate,
+                                FileOutcome::SkippedConflict,
+                                FileOutcome::AlreadyOrganized,
+                                FileOutcome::SkippedOrganizedBefore,
+                                FileOutcome::SkippedByRule,
+                                FileOutcome::Vetoed
+                            ])
+                        ),
+                        Style::default().fg(WARNING_COLOR),
+                    ),
+                    Span::styled(
+                        match count(&[FileOutcome::DeletedOrganizedBefore]) {
+                            0 => String::new(),
+                            deleted => format!(" | {deleted} deleted from source"),
+                        },
+                        Style::default().fg(WARNING_COLOR),
+                    ),
+                    Span::raw(" | "),
+                    Span::styled(
+                        format!("{} errors", count(&[FileOutcome::Error])),
+                        Style::default().fg(ERROR_COLOR),
+                    ),
+                ]),
+            ];
+            if let Some(transfer) = &result.remote_transfer {
+                lines.push(transfer_line(transfer));
+                lines.push(Line::from(Span::styled(
+                    transfer.undo_boundary(),
+                    Style::default().fg(MUTED_COLOR).add_modifier(Modifier::ITALIC),
+                )));
+            }
+            lines
+        },
+    );
+
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .title(" 📋 Organize Report ")
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(MUTED_COLOR))
+            .style(Style::default().bg(BACKGROUND_ALT)),
+    );
+    f.render_widget(paragraph, area);
+}
+
+fn transfer_line(transfer: &RemoteTransfer) -> Line<'static> {
+    let mut spans = vec![Span::styled(
+        format!(
+            "☁ {} moved to {} ({})",
+            transfer.transferred,
+            transfer.remote,
+            format_bytes(transfer.bytes)
+        ),
+        Style::default().fg(ACCENT_COLOR),
+    )];
+    if let Some(error) = transfer.errors.first() {
+        spans.push(Span::styled(
+            format!(" | {} errors, first: {error}", transfer.errors.len()),
+            Style::default().fg(ERROR_COLOR),
+        ));
+    }
+    Line::from(spans)
+}
+
+fn draw_files(f: &mut Frame, area: Rect, app: &App) {
+    let files = app
+        .last_organize_result
+        .as_ref()
+        .map_or(&[][..], |result| &result.files[..]);
+
+    let lines: Vec<Line> = if files.is_empty() {
+        vec![Line::from(Span::styled(
+            "No files in this run",
+            Style::default().fg(MUTED_COLOR).add_modifier(Modifier::ITALIC),
+        ))]
+    } else {
+        files
+            .iter()
+            .skip(app.organize_report_scroll)
+            .take(usize::from(area.height))
+            .map(file_line)
+            .collect()
+    };
+
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .title(format!(
+                " Files ({}) sorted by {} ",
+                files.len(),
+                app.organize_report_sort
+            ))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(MUTED_COLOR))
+            .style(Style::default().bg(BACKGROUND_ALT)),
+    );
+    f.render_widget(paragraph, area);
+}
+
+fn file_line(file: &OrganizedFile) -> Line<'_> {
+    let (icon, color) = match file.outcome {
+        FileOutcome::Moved => ("✔", SUCCESS_COLOR),
+        FileOutcome::Renamed | FileOutcome::Overwritten => ("✏", ACCENT_COLOR),
+        FileOutcome::SkippedDuplicate
+        | FileOutcome::SkippedConflict
+        | FileOutcome::AlreadyOrganized
+        | FileOutcome::SkippedOrganizedBefore
+        | FileOutcome::SkippedByRule
+        | FileOutcome::Vetoed
+        | FileOutcome::Deferred => ("⏭", WARNING_COLOR),
+        FileOutcome::DeletedOrganizedBefore => ("🗑", WARNING_COLOR),
+        FileOutcome::RolledBack => ("↩", WARNING_COLOR),
+        FileOutcome::Error => ("❌", ERROR_COLOR),
+    };
+    let detail = match (&file.destination, &file.error) {
+        (_, Some(error)) => error.clone(),
+        (Some(destination), None) => format!("→ {}", destination.display()),
+        (None, None) => String::new(),
+    };
+
+    Line::from(vec![
+        Span::styled(
+            format!("{icon} {:<18}", file.outcome.to_string()),
+            Style::default().fg(color),
+        ),
+        Span::styled(file.source.display().to_string(), Style::default().fg(Color::White)),
+        Span::styled(format!("  {detail}"), Style::default().fg(MUTED_COLOR)),
+    ])
+}
+
+fn draw_help(f: &mut Frame, area: Rect) {
+    let help = Paragraph::new(Line::from(vec![
+        Span::styled("s", Style::default().fg(ACCENT_COLOR).add_modifier(Modifier::BOLD)),
+        Span::raw(" - Sort by source/destination/outcome | "),
+        Span::styled("e", Style::default().fg(ACCENT_COLOR)),
+        Span::raw(" - Export CSV | "),
+        Span::styled("↑↓", Style::default().fg(ACCENT_COLOR)),
+        Span::raw(" - Scroll | "),
+        Span::styled("Esc", Style::default().fg(ACCENT_COLOR)),
+        Span::raw(" - Back"),
+    ]))
+    .alignment(Alignment::Center)
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(MUTED_COLOR)),
+    );
+    f.render_widget(help, area);
+}
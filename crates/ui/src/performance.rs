@@ -0,0 +1,253 @@
+use std::time::Duration;
+
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph},
+};
+use visualvault_app::App;
+use visualvault_models::{OperationProfile, PerformanceLog, StageTiming};
+use visualvault_utils::{STAGE_HASH, STAGE_ORGANIZE, STAGE_WALK, format_bytes, format_count};
+
+use super::{ACCENT_COLOR, BACKGROUND_ALT, ERROR_COLOR, MUTED_COLOR, SUCCESS_COLOR, WARNING_COLOR};
+
+pub fn draw(f: &mut Frame, area: Rect, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(3), // Summary
+            Constraint::Min(0),    // Operations
+            Constraint::Length(9), // Stages of the selected operation
+            Constraint::Length(3), // Help
+        ])
+        .split(area);
+
+    draw_summary(f, chunks[0], app);
+    draw_operations(f, chunks[1], &app.performance);
+    draw_stages(f, chunks[2], &app.performance);
+    draw_help(f, chunks[3]);
+}
+
+fn draw_summary(f: &mut Frame, area: Rect, app: &App) {
+    let settings = &app.settings_cache;
+    let line = Line::from(vec![
+        Span::styled("Settings: ", Style::default().fg(MUTED_COLOR)),
+        Span::styled(
+            format!(
+                "{} workers, {} buffer, {} hashing, parallel processing {}",
+                settings.worker_threads,
+                format_bytes(settings.buffer_size as u64),
+                settings.hash_algorithm,
+                if settings.parallel_processing { "on" } else { "off" },
+            ),
+            Style::default().fg(Color::White),
+        ),
+    ]);
+
+    let paragraph = Paragraph::new(line).block(
+        Block::default()
+            .title(" ⏱ Performance ")
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(MUTED_COLOR))
+            .style(Style::default().bg(BACKGROUND_ALT)),
+    );
+    f.render_widget(paragraph, area);
+}
+
+fn draw_operations(f: &mut Frame, area: Rect, log: &PerformanceLog) {
+    let lines: Vec<Line> = if log.is_empty() {
+        let style = Style::default().fg(MUTED_COLOR).add_modifier(Modifier::ITALIC);
+        vec![
+            Line::from(Span::styled("Timings of scans and organize runs show up here", style)),
+            Line::from(Span::styled(
+                "Change the worker threads or buffer size in the settings and run again to compare",
+                style,
+            )),
+        ]
+    } else {
+        let rows = usize::from(area.height.saturating_sub(2));
+        let offset = log.selected.saturating_sub(rows.saturating_sub(1));
+        log.entries
+            .iter()
+            .enumerate()
+            .skip(offset)
+            .take(rows)
+            .map(|(idx, profile)| operation_line(profile, idx == log.selected))
+            .collect()
+    };
+
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .title(format!(" Recent operations ({}) ", log.entries.len()))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(MUTED_COLOR))
+            .style(Style::default().bg(BACKGROUND_ALT)),
+    );
+    f.render_widget(paragraph, area);
+}
+
+fn operation_line(profile: &OperationProfile, selected: bool) -> Line<'static> {
+    let style = if selected {
+        Style::default()
+            .fg(ACCENT_COLOR)
+            .add_modifier(Modifier::BOLD | Modifier::REVERSED)
+    } else {
+        Style::default().fg(Color::White)
+    };
+    let walk = profile
+        .stage(STAGE_WALK)
+        .map_or_else(|| "-".to_string(), |stage| format_elapsed(stage.elapsed));
+    let hash = profile
+        .stage(STAGE_HASH)
+        .and_then(StageTiming::bytes_per_sec)
+        .map_or_else(|| "-".to_string(), format_throughput);
+    let moves = profile
+        .stage(STAGE_ORGANIZE)
+        .and_then(StageTiming::items_per_sec)
+        .map_or_else(|| "-".to_string(), |rate| format!("{rate:.1} files/s"));
+
+    Line::from(vec![
+        Span::styled(
+            format!("{} ", profile.finished_at.format("%H:%M:%S")),
+            Style::default().fg(MUTED_COLOR),
+        ),
+        Span::styled(
+            format!(
+                "{:<9}{:>8}",
+                profile.operation.to_string(),
+                format_elapsed(profile.elapsed)
+            ),
+            style,
+        ),
+        Span::styled("  walk ", Style::default().fg(MUTED_COLOR)),
+        Span::styled(format!("{walk:>7}"), Style::default().fg(ACCENT_COLOR)),
+        Span::styled("  hash ", Style::default().fg(MUTED_COLOR)),
+        Span::styled(format!("{hash:>11}"), Style::default().fg(SUCCESS_COLOR)),
+        Span::styled("  move ", Style::default().fg(MUTED_COLOR)),
+        Span::styled(format!("{moves:>13}"), Style::default().fg(WARNING_COLOR)),
+        Span::styled(
+            format!(
+                "  {} workers, {} buffer, {}",
+                profile.worker_threads,
+                format_bytes(profile.buffer_size as u64),
+                profile.hash_algorithm
+            ),
+            Style::default().fg(MUTED_COLOR),
+        ),
+    ])
+}
+
+fn draw_stages(f: &mut Frame, area: Rect, log: &PerformanceLog) {
+    let mut lines = Vec::new();
+    if let Some(profile) = log.selected_entry() {
+        let previous = log.previous(log.selected);
+        for stage in &profile.stages {
+            let mut spans = vec![
+                Span::styled(format!("{:<17}", stage.name), Style::default().fg(Color::White)),
+                Span::styled(
+                    format!("{:>8}", format_elapsed(stage.elapsed)),
+                    Style::default().fg(ACCENT_COLOR),
+                ),
+                Span::styled(
+                    format!("  {:>9} items", format_count(stage.items)),
+                    Style::default().fg(MUTED_COLOR),
+                ),
+            ];
+            if let Some(rate) = stage.items_per_sec() {
+                spans.push(Span::styled(
+                    format!("  {rate:>8.1}/s"),
+                    Style::default().fg(MUTED_COLOR),
+                ));
+            }
+            if let Some(rate) = stage.bytes_per_sec() {
+                spans.push(Span::styled(
+                    format!("  {}", format_throughput(rate)),
+                    Style::default().fg(SUCCESS_COLOR),
+                ));
+            }
+            if let Some(change) =
+                previous.and_then(|previous| speed_change(stage, previous.stage(stage.name.as_str())?))
+            {
+                spans.push(change);
+            }
+            lines.push(Line::from(spans));
+        }
+        if let Some(previous) = previous {
+            let note = if profile.same_settings(previous) {
+                "Compared with the previous run, which used the same settings".to_string()
+            } else {
+                format!(
+                    "Compared with the previous run: {} workers, {} buffer, {}",
+                    previous.worker_threads,
+                    format_bytes(previous.buffer_size as u64),
+                    previous.hash_algorithm
+                )
+            };
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                note,
+                Style::default().fg(MUTED_COLOR).add_modifier(Modifier::ITALIC),
+            )));
+        }
+    }
+
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .title(" Stages ")
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(MUTED_COLOR))
+            .style(Style::default().bg(BACKGROUND_ALT)),
+    );
+    f.render_widget(paragraph, area);
+}
+
+/// How much faster `stage` handled its items than the same stage of an earlier run
+fn speed_change(stage: &StageTiming, before: &StageTiming) -> Option<Span<'static>> {
+    let (now, then) = match (stage.bytes_per_sec(), before.bytes_per_sec()) {
+        (Some(now), Some(then)) => (now, then),
+        _ => (stage.items_per_sec()?, before.items_per_sec()?),
+    };
+    let percent = (now / then - 1.0) * 100.0;
+    let color = if percent >= 0.0 { SUCCESS_COLOR } else { ERROR_COLOR };
+    Some(Span::styled(format!("  {percent:+.0}%"), Style::default().fg(color)))
+}
+
+fn draw_help(f: &mut Frame, area: Rect) {
+    let help = Paragraph::new(Line::from(vec![
+        Span::styled("↑↓", Style::default().fg(ACCENT_COLOR)),
+        Span::raw(" - Select run | "),
+        Span::styled("c", Style::default().fg(ERROR_COLOR)),
+        Span::raw(" - Clear | "),
+        Span::styled("Esc", Style::default().fg(ACCENT_COLOR)),
+        Span::raw(" - Back"),
+    ]))
+    .alignment(Alignment::Center)
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(MUTED_COLOR)),
+    );
+    f.render_widget(help, area);
+}
+
+/// Formats a stage time, with tenths of seconds since short runs are what gets compared
+fn format_elapsed(elapsed: Duration) -> String {
+    let secs = elapsed.as_secs_f64();
+    if secs < 1.0 {
+        format!("{}ms", elapsed.as_millis())
+    } else if secs < 60.0 {
+        format!("{secs:.1}s")
+    } else {
+        format!("{}m {}s", elapsed.as_secs() / 60, elapsed.as_secs() % 60)
+    }
+}
+
+fn format_throughput(bytes_per_sec: f64) -> String {
+    format!("{}/s", format_bytes(bytes_per_sec as u64))
+}
@@ -0,0 +1,56 @@
+use ratatui::{
+    Frame,
+    layout::Alignment,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+};
+use visualvault_app::App;
+
+use super::{ACCENT_COLOR, BACKGROUND_ALT, MUTED_COLOR, SUCCESS_COLOR, centered_rect};
+
+/// Draws the settings profile picker.
+pub fn draw_profile_picker(f: &mut Frame, app: &App) {
+    let Some(picker) = &app.profile_picker else {
+        return;
+    };
+
+    let area = centered_rect(50, 40, f.area());
+    f.render_widget(Clear, area);
+
+    let key_style = Style::default().fg(ACCENT_COLOR).add_modifier(Modifier::BOLD);
+    let hint_style = Style::default().fg(MUTED_COLOR).add_modifier(Modifier::ITALIC);
+    let mut lines = vec![Line::from("")];
+
+    for (i, name) in picker.profiles.iter().enumerate() {
+        let is_active = *name == picker.active;
+        let marker = if i == picker.selected { "▶ " } else { "  " };
+        let style = if i == picker.selected {
+            key_style
+        } else {
+            Style::default().fg(Color::White)
+        };
+        let mut spans = vec![Span::styled(format!("  {marker}{name}"), style)];
+        if is_active {
+            spans.push(Span::styled("  ● active", Style::default().fg(SUCCESS_COLOR)));
+        }
+        lines.push(Line::from(spans));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "Enter switches • n new from current • d deletes • Esc closes",
+        hint_style,
+    )));
+
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .title(" 👤 Settings Profiles ")
+            .title_alignment(Alignment::Center)
+            .title_style(key_style)
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(ACCENT_COLOR))
+            .style(Style::default().bg(BACKGROUND_ALT)),
+    );
+    f.render_widget(paragraph, area);
+}
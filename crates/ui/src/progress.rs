@@ -8,14 +8,19 @@ use ratatui::{
 
 use visualvault_app::App;
 use visualvault_models::AppState;
+use visualvault_utils::{Progress, Stage, StageStatus, format_bytes};
+
+/// Width of the stage names in front of their gauges
+const STAGE_NAME_WIDTH: u16 = 20;
 
 #[allow(clippy::significant_drop_tightening)]
 pub fn draw_progress_overlay(f: &mut Frame, app: &App) {
     // Get progress data
     let Ok(progress) = app.progress.try_read() else { return };
 
-    // Create centered overlay area
-    let area = centered_rect(60, 30, f.area());
+    // Size the overlay to the stages it lists
+    let stage_rows = u16::try_from(progress.stages.len()).unwrap_or(u16::MAX);
+    let area = centered_overlay(60, stage_rows.saturating_add(15), f.area());
 
     // Clear the area for the overlay
     f.render_widget(Clear, area);
@@ -25,11 +30,16 @@ pub fn draw_progress_overlay(f: &mut Frame, app: &App) {
         .direction(Direction::Vertical)
         .margin(2)
         .constraints([
-            Constraint::Length(3), // Title
-            Constraint::Length(3), // Progress bar
-            Constraint::Length(2), // Stats
-            Constraint::Length(2), // Message
-            Constraint::Length(2), // Time info
+            Constraint::Length(2),          // Title
+            Constraint::Length(1),          // Overall progress bar
+            Constraint::Length(1),          // Spacer
+            Constraint::Length(stage_rows), // One gauge per stage
+            Constraint::Length(1),          // Spacer
+            Constraint::Length(1),          // Stats
+            Constraint::Length(1),          // Current file
+            Constraint::Length(1),          // Message
+            Constraint::Length(1),          // Throughput and time info
+            Constraint::Length(1),          // Parallelism
         ])
         .split(area);
 
@@ -58,32 +68,24 @@ pub fn draw_progress_overlay(f: &mut Frame, app: &App) {
 
     f.render_widget(title, chunks[0]);
 
-    // Progress bar
-    let percentage = progress.percentage();
-    let label = if progress.total > 0 {
-        format!("{percentage:.0}%")
-    } else {
+    // Overall progress bar
+    let label = if progress.stages.is_empty() && progress.total == 0 {
         "Calculating...".to_string()
+    } else {
+        format!("Overall {:.0}%", progress.overall_ratio() * 100.0)
     };
-
-    #[allow(clippy::cast_precision_loss)]
-    #[allow(clippy::cast_possible_truncation)]
-    #[allow(clippy::cast_sign_loss)]
     let gauge = Gauge::default()
-        .block(Block::default().borders(Borders::NONE))
         .gauge_style(Style::default().fg(Color::Cyan).bg(Color::Rgb(40, 40, 40)))
-        .percent(percentage as u16)
+        .ratio(progress.overall_ratio())
         .label(label)
         .use_unicode(true);
 
     f.render_widget(gauge, chunks[1]);
 
+    draw_stages(f, &progress, chunks[3]);
+
     // Statistics
-    let stats_text = if progress.total > 0 {
-        format!("{} / {} items", progress.current, progress.total)
-    } else {
-        format!("{} items processed", progress.current)
-    };
+    let stats_text = stats_text(&progress);
 
     let stats = Paragraph::new(vec![Line::from(vec![Span::styled(
         stats_text,
@@ -91,7 +93,18 @@ pub fn draw_progress_overlay(f: &mut Frame, app: &App) {
     )])])
     .alignment(Alignment::Center);
 
-    f.render_widget(stats, chunks[2]);
+    f.render_widget(stats, chunks[5]);
+
+    // File handled last
+    if let Some(file) = &progress.current_file {
+        let current_file = Paragraph::new(vec![Line::from(vec![
+            Span::styled("📄 ", Style::default().fg(Color::Cyan)),
+            Span::styled(file.as_str(), Style::default().fg(Color::White)),
+        ])])
+        .alignment(Alignment::Center);
+
+        f.render_widget(current_file, chunks[6]);
+    }
 
     // Current message
     if !progress.message.is_empty() {
@@ -103,16 +116,11 @@ pub fn draw_progress_overlay(f: &mut Frame, app: &App) {
         )])])
         .alignment(Alignment::Center);
 
-        f.render_widget(message, chunks[3]);
+        f.render_widget(message, chunks[7]);
     }
 
-    // Time information
-    let elapsed = progress.elapsed();
-    let time_info = if let Some(eta) = progress.eta() {
-        format!("Elapsed: {} | ETA: {}", format_duration(elapsed), format_duration(eta))
-    } else {
-        format!("Elapsed: {}", format_duration(elapsed))
-    };
+    // Throughput and time information
+    let time_info = time_text(&progress);
 
     let time_paragraph = Paragraph::new(vec![Line::from(vec![Span::styled(
         time_info,
@@ -120,17 +128,110 @@ pub fn draw_progress_overlay(f: &mut Frame, app: &App) {
     )])])
     .alignment(Alignment::Center);
 
-    f.render_widget(time_paragraph, chunks[4]);
+    f.render_widget(time_paragraph, chunks[8]);
+
+    // Workers the operation picked for the drive
+    if let Some(parallelism) = &progress.parallelism {
+        let parallelism = Paragraph::new(Line::from(vec![
+            Span::styled("⚙ ", Style::default().fg(Color::Cyan)),
+            Span::styled(parallelism.as_str(), Style::default().fg(Color::Rgb(150, 150, 150))),
+        ]))
+        .alignment(Alignment::Center);
+
+        f.render_widget(parallelism, chunks[9]);
+    }
 }
 
-fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+/// Draws one row per stage with its status, name and own gauge
+fn draw_stages(f: &mut Frame, progress: &Progress, area: Rect) {
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(vec![Constraint::Length(1); progress.stages.len()])
+        .split(area);
+
+    for (stage, row) in progress.stages.iter().zip(rows.iter()) {
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Length(STAGE_NAME_WIDTH), Constraint::Min(10)])
+            .split(*row);
+
+        let (icon, color) = match stage.status {
+            StageStatus::Pending => ("·", Color::DarkGray),
+            StageStatus::Running => ("▶", Color::Cyan),
+            StageStatus::Done => ("✓", Color::Green),
+        };
+        let name = Paragraph::new(Line::from(vec![
+            Span::styled(format!("{icon} "), Style::default().fg(color)),
+            Span::styled(
+                stage.name,
+                Style::default().fg(if stage.status == StageStatus::Pending {
+                    Color::DarkGray
+                } else {
+                    Color::White
+                }),
+            ),
+        ]));
+        f.render_widget(name, columns[0]);
+
+        let gauge = Gauge::default()
+            .gauge_style(Style::default().fg(color).bg(Color::Rgb(40, 40, 40)))
+            .ratio(progress.stage_ratio(stage))
+            .label(stage_label(progress, stage))
+            .use_unicode(true);
+        f.render_widget(gauge, columns[1]);
+    }
+}
+
+/// Gauge label of a stage: its item count, or how many items it found while the total is unknown
+fn stage_label(progress: &Progress, stage: &Stage) -> String {
+    match stage.status {
+        StageStatus::Pending => "waiting".to_string(),
+        StageStatus::Done => format!("{} items", stage.processed),
+        StageStatus::Running if progress.total == 0 => format!("{} found", progress.current),
+        StageStatus::Running => format!("{} / {}", progress.current, progress.total),
+    }
+}
+
+/// Item count, plus the bytes handled when file sizes are known
+pub(crate) fn stats_text(progress: &Progress) -> String {
+    let mut stats_text = if progress.total > 0 {
+        format!("{} / {} items", progress.current, progress.total)
+    } else {
+        format!("{} items processed", progress.current)
+    };
+    if progress.total_bytes > 0 {
+        stats_text = format!(
+            "{stats_text} • {} / {}",
+            format_bytes(progress.bytes_processed),
+            format_bytes(progress.total_bytes)
+        );
+    } else if progress.bytes_processed > 0 {
+        stats_text = format!("{stats_text} • {}", format_bytes(progress.bytes_processed));
+    }
+    stats_text
+}
+
+/// Elapsed and remaining time with the current throughput
+pub(crate) fn time_text(progress: &Progress) -> String {
+    let elapsed = progress.elapsed();
+    let mut time_info = format!("Elapsed: {}", format_duration(elapsed));
+    if let Some(eta) = progress.eta() {
+        time_info = format!("{time_info} | Remaining: {}", format_duration(eta));
+    }
+    if let Some(rate) = progress.throughput() {
+        time_info = format!("{time_info} | {:.1} MB/s", rate / (1024.0 * 1024.0));
+    } else if let Some(rate) = progress.items_per_sec {
+        time_info = format!("{time_info} | {rate:.0} files/s");
+    }
+    time_info
+}
+
+/// Centers an overlay `percent_x` wide and `height` rows high, shrunk to fit `r`
+fn centered_overlay(percent_x: u16, height: u16, r: Rect) -> Rect {
+    let height = height.min(r.height);
     let popup_layout = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Percentage((100 - percent_y) / 2),
-            Constraint::Percentage(percent_y),
-            Constraint::Percentage((100 - percent_y) / 2),
-        ])
+        .constraints([Constraint::Fill(1), Constraint::Length(height), Constraint::Fill(1)])
         .split(r);
 
     Layout::default()
@@ -0,0 +1,88 @@
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph},
+};
+use visualvault_app::App;
+use visualvault_utils::format_bytes;
+
+use super::{ACCENT_COLOR, BACKGROUND_ALT, MUTED_COLOR, WARNING_COLOR};
+
+pub fn draw(f: &mut Frame, area: Rect, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Min(0),    // Suspect files
+            Constraint::Length(3), // Help
+        ])
+        .split(area);
+
+    draw_suspects(f, chunks[0], app);
+    draw_help(f, chunks[1], app);
+}
+
+fn draw_suspects(f: &mut Frame, area: Rect, app: &App) {
+    let source = app.settings_cache.source_folder.as_deref();
+
+    let lines: Vec<Line> = if app.suspect_files.is_empty() {
+        vec![Line::from(Span::styled(
+            "No suspect files left to quarantine",
+            Style::default().fg(MUTED_COLOR).add_modifier(Modifier::ITALIC),
+        ))]
+    } else {
+        app.suspect_files
+            .iter()
+            .skip(app.suspect_scroll)
+            .take(usize::from(area.height))
+            .map(|suspect| {
+                let shown = source
+                    .and_then(|source| suspect.path.strip_prefix(source).ok())
+                    .unwrap_or(&suspect.path);
+                Line::from(vec![
+                    Span::styled("⚠ ", Style::default().fg(WARNING_COLOR)),
+                    Span::styled(shown.display().to_string(), Style::default().fg(Color::White)),
+                    Span::styled(
+                        format!("  {}", format_bytes(suspect.size)),
+                        Style::default().fg(MUTED_COLOR),
+                    ),
+                    Span::styled(format!("  {}", suspect.reason), Style::default().fg(WARNING_COLOR)),
+                ])
+            })
+            .collect()
+    };
+
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .title(format!(" 🧪 Suspect Files ({}) ", app.suspect_files.len()))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(MUTED_COLOR))
+            .style(Style::default().bg(BACKGROUND_ALT)),
+    );
+    f.render_widget(paragraph, area);
+}
+
+fn draw_help(f: &mut Frame, area: Rect, app: &App) {
+    let folder = app.settings_cache.quarantine_path().map_or_else(
+        || "the quarantine folder".to_string(),
+        |folder| folder.display().to_string(),
+    );
+    let help = Paragraph::new(Line::from(vec![
+        Span::styled("m", Style::default().fg(WARNING_COLOR).add_modifier(Modifier::BOLD)),
+        Span::raw(format!(" - Move all to {folder} | ")),
+        Span::styled("↑↓", Style::default().fg(ACCENT_COLOR)),
+        Span::raw(" - Scroll | "),
+        Span::styled("Esc", Style::default().fg(ACCENT_COLOR)),
+        Span::raw(" - Back"),
+    ]))
+    .alignment(Alignment::Center)
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(MUTED_COLOR)),
+    );
+    f.render_widget(help, area);
+}
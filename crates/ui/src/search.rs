@@ -7,8 +7,8 @@ use ratatui::{
 };
 
 use visualvault_app::App;
-use visualvault_models::FileType;
 use visualvault_models::InputMode;
+use visualvault_models::{FileType, MediaFile, SearchScope};
 use visualvault_utils::format_bytes;
 
 pub fn draw(f: &mut Frame, area: Rect, app: &App) {
@@ -47,7 +47,7 @@ fn draw_search_bar(f: &mut Frame, area: Rect, app: &App) {
             } else {
                 Style::default().fg(Color::Gray)
             })
-            .title(" Search Files ")
+            .title(format!(" Search Files in {} (Tab to change) ", app.search_scope))
             .title_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
     );
 
@@ -62,6 +62,7 @@ fn draw_search_bar(f: &mut Frame, area: Rect, app: &App) {
     }
 }
 
+#[allow(clippy::too_many_lines)]
 fn draw_search_results(f: &mut Frame, area: Rect, app: &App) {
     if app.search_results.is_empty() && !app.search_input.is_empty() {
         // No results found
@@ -98,6 +99,7 @@ fn draw_search_results(f: &mut Frame, area: Rect, app: &App) {
             Line::from("• Press Enter to start typing"),
             Line::from("• Type to search file names"),
             Line::from("• Search is case-insensitive"),
+            Line::from("• Tab switches between source, destination and both"),
             Line::from(""),
             Line::from(vec![Span::styled(
                 "Tips:",
@@ -127,19 +129,37 @@ fn draw_search_results(f: &mut Frame, area: Rect, app: &App) {
         .height(1)
         .bottom_margin(1);
 
-    let rows: Vec<Row> = app
-        .search_results
+    // Borders, header and header margin take four lines
+    app.file_viewport.set_rows(area.height.saturating_sub(4) as usize);
+    let rows_area = Rect {
+        x: area.x + 1,
+        y: area.y + 3,
+        width: area.width.saturating_sub(2),
+        height: area.height.saturating_sub(4),
+    };
+    super::record_mouse_targets(app, |targets| targets.file_rows = Some(super::screen_area(rows_area)));
+    let visible = app
+        .file_viewport
+        .visible(app.selected_file_index, app.search_results.len());
+    let first_visible = visible.start;
+
+    let rows: Vec<Row> = app.search_results[visible]
         .iter()
-        .skip(app.scroll_offset)
-        .take(area.height.saturating_sub(4) as usize)
-        .map(|file| {
+        .enumerate()
+        .map(|(idx, file)| {
+            let style = if first_visible + idx == app.selected_file_index {
+                Style::default().bg(Color::Rgb(69, 71, 90)).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
             Row::new(vec![
                 Cell::from(&*file.name),
                 Cell::from(file.file_type.to_string()).style(Style::default().fg(get_type_color(&file.file_type))),
                 Cell::from(format_bytes(file.size)),
                 Cell::from(file.modified.format("%Y-%m-%d").to_string()),
-                Cell::from(file.path.parent().map(|p| p.display().to_string()).unwrap_or_default()),
+                Cell::from(location(app, file)),
             ])
+            .style(style)
         })
         .collect();
 
@@ -164,13 +184,33 @@ fn draw_search_results(f: &mut Frame, area: Rect, app: &App) {
     f.render_widget(table, area);
 }
 
+/// Folder of a result, marked as being in the library when searching both scopes
+fn location(app: &App, file: &MediaFile) -> String {
+    let folder = file.path.parent().map(|p| p.display().to_string()).unwrap_or_default();
+    let in_library = app
+        .settings_cache
+        .destination_folder
+        .as_ref()
+        .is_some_and(|destination| file.path.starts_with(destination));
+    if app.search_scope == SearchScope::Both && in_library {
+        format!("📚 {folder}")
+    } else {
+        folder
+    }
+}
+
 fn draw_search_status(f: &mut Frame, area: Rect, app: &App) {
-    let status_text = if app.input_mode == InputMode::Insert {
+    let keys = if app.input_mode == InputMode::Insert {
         "Press ESC to stop editing | Enter to search"
     } else if !app.search_results.is_empty() {
-        "Enter: View details | ↑↓: Navigate | /: New search | ESC: Back"
+        "↑↓: Navigate | g: Go to file | y/Y: Copy path/folder | /: New search | Tab: Scope | i: Reindex | ESC: Back"
     } else {
-        "Press Enter to start searching | ESC to go back"
+        "Press Enter to start searching | Tab: Scope | i: Index destination | ESC to go back"
+    };
+    let status_text = if app.search_scope.includes_destination() {
+        format!("{} | {keys}", index_status(app))
+    } else {
+        keys.to_string()
     };
 
     let status = Paragraph::new(status_text)
@@ -185,10 +225,27 @@ fn draw_search_status(f: &mut Frame, area: Rect, app: &App) {
     f.render_widget(status, area);
 }
 
+/// How current the destination index is
+fn index_status(app: &App) -> String {
+    if app.destination_index_task.is_some() {
+        return "Indexing destination...".to_string();
+    }
+    let destination = app.settings_cache.destination_folder.as_ref();
+    match &app.destination_index_info {
+        Some(info) if destination == Some(&info.root) => format!(
+            "Library: {} files, indexed {}",
+            info.files,
+            info.indexed_at.format("%Y-%m-%d %H:%M")
+        ),
+        _ => "Destination not indexed".to_string(),
+    }
+}
+
 const fn get_type_color(file_type: &FileType) -> Color {
     match file_type {
         FileType::Image => Color::Green,
         FileType::Video => Color::Blue,
+        FileType::Audio => Color::Magenta,
         FileType::Document => Color::Yellow,
         FileType::Other => Color::Gray,
     }
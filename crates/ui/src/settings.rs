@@ -1,18 +1,20 @@
+use chrono::{DateTime, Local, TimeZone};
 use ratatui::{
     Frame,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     symbols,
     text::{Line, Span},
-    widgets::{Block, BorderType, Borders, Gauge, List, ListItem, Paragraph, Tabs},
+    widgets::{Block, BorderType, Borders, Gauge, List, ListItem, ListState, Paragraph, Tabs},
 };
-use visualvault_config::Settings;
+use visualvault_config::{ConcurrencyMode, DiagnosticLevel, HashAlgorithm, RenameContext, Settings, TypeMapping};
 
 use std::path::Path;
 
 use visualvault_app::App;
 use visualvault_models::EditingField;
 use visualvault_models::InputMode;
+use visualvault_models::PreflightSeverity;
 use visualvault_utils::format_bytes;
 
 // Beautiful color palette (matching dashboard)
@@ -24,20 +26,40 @@ const MUTED_COLOR: Color = Color::Rgb(98, 114, 164); // Gray
 const BACKGROUND_ALT: Color = Color::Rgb(30, 30, 46); // Dark background
 const HIGHLIGHT_BG: Color = Color::Rgb(69, 71, 90); // Selection background
 
+/// Diagnostics shown at once below the settings tabs
+const MAX_DIAGNOSTIC_LINES: usize = 6;
+
 pub fn draw(f: &mut Frame, area: Rect, app: &App) {
     // Add a subtle background
     let background = Block::default().style(Style::default().bg(Color::Rgb(24, 24, 37)));
     f.render_widget(background, area);
 
-    let tabs = vec!["⚙️  General", "📁 Organization", "🚀 Performance"];
+    let tabs = vec![
+        "⚙️  General",
+        "📁 Organization",
+        "🚀 Performance",
+        "🏷️  File Types",
+        "📐 Rules",
+    ];
     let selected_tab = app.selected_tab;
 
+    // Problems found on load or save stay listed below the tabs until they are fixed
+    let diagnostics_height = if app.settings_diagnostics.is_empty() {
+        0
+    } else {
+        u16::try_from(app.settings_diagnostics.len().min(MAX_DIAGNOSTIC_LINES)).unwrap_or(0) + 2
+    };
     let chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Length(3), Constraint::Min(0)])
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(0),
+            Constraint::Length(diagnostics_height),
+        ])
         .split(area);
 
     // Draw enhanced tabs
+    super::record_tabs(app, chunks[0], &tabs);
     let tabs_widget = Tabs::new(tabs)
         .select(selected_tab)
         .block(
@@ -61,29 +83,162 @@ pub fn draw(f: &mut Frame, area: Rect, app: &App) {
         0 => draw_general_settings(f, chunks[1], app),
         1 => draw_organization_settings(f, chunks[1], app),
         2 => draw_performance_settings(f, chunks[1], app),
+        3 => draw_file_type_settings(f, chunks[1], app),
+        4 => draw_rules_settings(f, chunks[1], app),
+        5 => draw_routing_settings(f, chunks[1], app),
         _ => {}
     }
+
+    if !app.settings_diagnostics.is_empty() {
+        draw_diagnostics(f, chunks[2], app);
+    }
+}
+
+/// Lists the problems found in the settings, errors first
+fn draw_diagnostics(f: &mut Frame, area: Rect, app: &App) {
+    let errors = app
+        .settings_diagnostics
+        .iter()
+        .filter(|diagnostic| diagnostic.level == DiagnosticLevel::Error)
+        .count();
+    let lines: Vec<Line> = app
+        .settings_diagnostics
+        .iter()
+        .take(MAX_DIAGNOSTIC_LINES)
+        .map(|diagnostic| {
+            let (icon, color) = match diagnostic.level {
+                DiagnosticLevel::Error => ("✗", ERROR_COLOR),
+                DiagnosticLevel::Warning => ("⚠", WARNING_COLOR),
+            };
+            Line::from(vec![
+                Span::styled(format!(" {icon} {}: ", diagnostic.field), Style::default().fg(color)),
+                Span::styled(diagnostic.message.clone(), Style::default().fg(Color::White)),
+            ])
+        })
+        .collect();
+
+    let color = if errors > 0 { ERROR_COLOR } else { WARNING_COLOR };
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .title(format!(
+                " 🩺 Diagnostics: {errors} error(s), {} warning(s) ",
+                app.settings_diagnostics.len() - errors
+            ))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(color)),
+    );
+    f.render_widget(paragraph, area);
+}
+
+/// Warns when the source and destination are the same folder or nested inside each other
+pub fn folder_overlap_line(app: &App) -> Line<'static> {
+    app.folder_overlap.as_ref().map_or_else(
+        || Line::from(""),
+        |issue| {
+            let (icon, color) = match issue.severity {
+                PreflightSeverity::Blocking => ("✗", ERROR_COLOR),
+                PreflightSeverity::Warning => ("⚠", WARNING_COLOR),
+                PreflightSeverity::Info => ("ℹ", ACCENT_COLOR),
+            };
+            Line::from(Span::styled(
+                format!("  {icon} {}", issue.message),
+                Style::default().fg(color),
+            ))
+        },
+    )
+}
+
+/// Status of the folder being typed: matching folders after an ambiguous Tab, or whether
+/// the folder exists, is writable and how much space is free on its drive
+pub fn folder_check_line(app: &App, is_destination: bool) -> Line<'static> {
+    let muted = Style::default().fg(MUTED_COLOR);
+    if !app.path_candidates.is_empty() {
+        return Line::from(vec![
+            Span::styled("  ⇥ ", muted),
+            Span::styled(app.path_candidates.join("  "), muted),
+        ]);
+    }
+    if app.input_buffer.is_empty() {
+        return Line::from(Span::styled("  Tab completes folder names", muted));
+    }
+    let Some(check) = &app.folder_check else {
+        return Line::from(Span::styled("  ✗ Not found", Style::default().fg(ERROR_COLOR)));
+    };
+
+    let mut spans = if check.exists && !check.is_dir {
+        return Line::from(Span::styled("  ✗ Not a folder", Style::default().fg(ERROR_COLOR)));
+    } else if check.exists {
+        vec![Span::styled("  ✓ Exists", Style::default().fg(SUCCESS_COLOR))]
+    } else if is_destination {
+        vec![Span::styled(
+            format!(
+                "  + Will be created in {}",
+                truncate_path(check.existing.display().to_string(), 30)
+            ),
+            Style::default().fg(WARNING_COLOR),
+        )]
+    } else {
+        return Line::from(Span::styled("  ✗ Does not exist", Style::default().fg(ERROR_COLOR)));
+    };
+
+    spans.push(Span::styled(" • ", muted));
+    spans.push(if check.writable {
+        Span::styled("writable", Style::default().fg(SUCCESS_COLOR))
+    } else {
+        Span::styled("read-only", Style::default().fg(ERROR_COLOR))
+    });
+    if let Some(free) = check.free_space {
+        spans.push(Span::styled(" • ", muted));
+        spans.push(Span::styled(format!("{} free", format_bytes(free)), muted));
+    }
+    Line::from(spans)
+}
+
+/// How deep scans go below the source, with the number of folders that override it
+fn scan_depth_description(settings: &Settings) -> String {
+    let depth = match settings.source_folder.as_deref() {
+        Some(source) => settings.scan_depth(source),
+        None if settings.recurse_subfolders => settings.max_depth,
+        None => Some(0),
+    };
+    let description = match depth {
+        Some(0) => "Only the files directly in the source folder".to_string(),
+        Some(1) => "Scan 1 level of subfolders".to_string(),
+        Some(levels) => format!("Scan up to {levels} levels of subfolders"),
+        None => "Scan all subdirectories recursively".to_string(),
+    };
+    let overrides = match settings.folder_depths.len() {
+        _ if !settings.recurse_subfolders => String::new(),
+        0 => String::new(),
+        1 => ", 1 folder override".to_string(),
+        count => format!(", {count} folder overrides"),
+    };
+    format!("{description}{overrides} (Space to change)")
 }
 
 #[allow(clippy::too_many_lines)]
 #[allow(clippy::cognitive_complexity)]
 fn draw_general_settings(f: &mut Frame, area: Rect, app: &App) {
     let settings = &app.settings_cache;
+    let is_editing_source =
+        app.input_mode == InputMode::Insert && app.editing_field == Some(EditingField::SourceFolder);
+    let is_editing_dest =
+        app.input_mode == InputMode::Insert && app.editing_field == Some(EditingField::DestinationFolder);
 
+    // The folder being edited gets a line for its check below the input
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .margin(1)
         .constraints([
-            Constraint::Length(5),  // Source folder
-            Constraint::Length(5),  // Destination folder
-            Constraint::Length(11), // Options
-            Constraint::Min(0),     // Help text
+            Constraint::Length(if is_editing_source { 6 } else { 5 }), // Source folder
+            Constraint::Length(if is_editing_dest { 6 } else { 5 }),   // Destination folder
+            Constraint::Length(23),                                    // Options
+            Constraint::Min(0),                                        // Help text
         ])
         .split(area);
 
     // Source folder
-    let is_editing_source =
-        app.input_mode == InputMode::Insert && app.editing_field == Some(EditingField::SourceFolder);
 
     let source_text = if is_editing_source {
         app.input_buffer.clone()
@@ -127,6 +282,8 @@ fn draw_general_settings(f: &mut Frame, area: Rect, app: &App) {
                         .fg(WARNING_COLOR)
                         .add_modifier(Modifier::ITALIC | Modifier::SLOW_BLINK),
                 )
+            } else if app.selected_setting == 0 {
+                Span::styled("  Enter types • b browses", Style::default().fg(MUTED_COLOR))
             } else {
                 Span::raw("")
             },
@@ -144,13 +301,15 @@ fn draw_general_settings(f: &mut Frame, area: Rect, app: &App) {
                 Span::raw("")
             },
         ]),
+        if is_editing_source {
+            folder_check_line(app, false)
+        } else {
+            Line::from("")
+        },
     ]);
     f.render_widget(source, source_inner);
 
     // Destination folder
-    let is_editing_dest =
-        app.input_mode == InputMode::Insert && app.editing_field == Some(EditingField::DestinationFolder);
-
     let dest_text = if is_editing_dest {
         app.input_buffer.clone()
     } else if let Some(path) = &settings.destination_folder {
@@ -193,6 +352,8 @@ fn draw_general_settings(f: &mut Frame, area: Rect, app: &App) {
                         .fg(WARNING_COLOR)
                         .add_modifier(Modifier::ITALIC | Modifier::SLOW_BLINK),
                 )
+            } else if app.selected_setting == 1 {
+                Span::styled("  Enter types • b browses", Style::default().fg(MUTED_COLOR))
             } else {
                 Span::raw("")
             },
@@ -210,6 +371,11 @@ fn draw_general_settings(f: &mut Frame, area: Rect, app: &App) {
                 Span::raw("")
             },
         ]),
+        if is_editing_dest {
+            folder_check_line(app, true)
+        } else {
+            folder_overlap_line(app)
+        },
     ]);
     f.render_widget(destination, dest_inner);
 
@@ -218,21 +384,21 @@ fn draw_general_settings(f: &mut Frame, area: Rect, app: &App) {
         (
             settings.recurse_subfolders,
             "🔄 Recurse into subfolders",
-            "Scan all subdirectories recursively",
+            scan_depth_description(settings),
         ),
         (
             settings.verbose_output,
             "📝 Verbose output",
-            "Show detailed processing information",
+            "Show detailed processing information".to_string(),
         ),
         (
             settings.undo_enabled,
             "↩️  Enable undo history",
-            "Keep a history of changes for undo operations",
+            "Keep a history of changes for undo operations".to_string(),
         ),
     ];
 
-    let option_items: Vec<ListItem> = options
+    let mut option_items: Vec<ListItem> = options
         .iter()
         .enumerate()
         .map(|(idx, (enabled, name, desc))| {
@@ -267,7 +433,10 @@ fn draw_general_settings(f: &mut Frame, area: Rect, app: &App) {
                 .style(bg_style),
                 Line::from(vec![
                     Span::raw("      "),
-                    Span::styled(*desc, Style::default().fg(MUTED_COLOR).add_modifier(Modifier::ITALIC)),
+                    Span::styled(
+                        desc.clone(),
+                        Style::default().fg(MUTED_COLOR).add_modifier(Modifier::ITALIC),
+                    ),
                 ])
                 .style(bg_style),
                 Line::from("").style(bg_style), // Add spacing
@@ -275,6 +444,95 @@ fn draw_general_settings(f: &mut Frame, area: Rect, app: &App) {
         })
         .collect();
 
+    // The schedule cycles through presets instead of toggling
+    let schedule_focused = app.selected_setting == 5;
+    let schedule_bg = if schedule_focused {
+        Style::default().bg(HIGHLIGHT_BG)
+    } else {
+        Style::default()
+    };
+    let schedule_name_style = if schedule_focused {
+        Style::default().fg(ACCENT_COLOR).add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(Color::White)
+    };
+    let (schedule_text, schedule_color) = settings
+        .schedule
+        .map_or_else(|| ("off".to_string(), MUTED_COLOR), |s| (s.to_string(), SUCCESS_COLOR));
+    option_items.push(ListItem::new(vec![
+        Line::from(vec![
+            Span::raw(" "),
+            Span::styled("⏰", Style::default().fg(WARNING_COLOR)),
+            Span::raw("  "),
+            Span::styled("Scheduled organize: ", schedule_name_style),
+            Span::styled(
+                schedule_text,
+                Style::default().fg(schedule_color).add_modifier(Modifier::BOLD),
+            ),
+        ])
+        .style(schedule_bg),
+        Line::from(vec![
+            Span::raw("      "),
+            Span::styled(
+                "Scan and organize automatically while running (Space to change)",
+                Style::default().fg(MUTED_COLOR).add_modifier(Modifier::ITALIC),
+            ),
+        ])
+        .style(schedule_bg),
+        Line::from("").style(schedule_bg),
+    ]));
+
+    let notifications = &settings.notifications;
+    let desktop_focused = app.selected_setting == 6;
+    let desktop_bg = if desktop_focused {
+        Style::default().bg(HIGHLIGHT_BG)
+    } else {
+        Style::default()
+    };
+    let desktop_name_style = if desktop_focused {
+        Style::default().fg(ACCENT_COLOR).add_modifier(Modifier::BOLD)
+    } else if notifications.desktop {
+        Style::default().fg(Color::White)
+    } else {
+        Style::default().fg(Color::Rgb(180, 180, 180))
+    };
+    option_items.push(ListItem::new(vec![
+        Line::from(vec![
+            Span::raw(" "),
+            if notifications.desktop {
+                Span::styled("✅", Style::default().fg(SUCCESS_COLOR))
+            } else {
+                Span::styled("⬜", Style::default().fg(MUTED_COLOR))
+            },
+            Span::raw("  "),
+            Span::styled("🔔 Desktop notifications", desktop_name_style),
+        ])
+        .style(desktop_bg),
+        Line::from(vec![
+            Span::raw("      "),
+            Span::styled(
+                "Notify when a long scan or organize finishes while the terminal is in the background",
+                Style::default().fg(MUTED_COLOR).add_modifier(Modifier::ITALIC),
+            ),
+        ])
+        .style(desktop_bg),
+        Line::from("").style(desktop_bg),
+    ]));
+    option_items.push(cycle_item(
+        "⏱",
+        "Notify after: ",
+        format_threshold(notifications.desktop_after_secs),
+        "Shortest operation that triggers a desktop notification (Space to change)",
+        app.selected_setting == 7,
+    ));
+    option_items.push(cycle_item(
+        "♿",
+        "Accessibility: ",
+        settings.accessibility.to_string(),
+        "ASCII labels and high-contrast colors instead of emoji and RGB; auto follows NO_COLOR and TERM (Space to change)",
+        app.selected_setting == 8,
+    ));
+
     let options_list = List::new(option_items).block(
         Block::default()
             .title(" ⚙️  Options ")
@@ -298,7 +556,7 @@ fn draw_organization_settings(f: &mut Frame, area: Rect, app: &App) {
         .margin(1)
         .constraints([
             Constraint::Length(11), // Organization mode
-            Constraint::Length(13), // File type options
+            Constraint::Length(25), // File type options
             Constraint::Min(0),     // Preview
         ])
         .split(area);
@@ -393,7 +651,7 @@ fn draw_organization_settings(f: &mut Frame, area: Rect, app: &App) {
         ),
     ];
 
-    let type_items: Vec<ListItem> = type_options
+    let mut type_items: Vec<ListItem> = type_options
         .iter()
         .enumerate()
         .map(|(idx, (enabled, name, desc))| {
@@ -436,6 +694,118 @@ fn draw_organization_settings(f: &mut Frame, area: Rect, app: &App) {
         })
         .collect();
 
+    // Conflict and non-media policies cycle through their values instead of toggling
+    let policy_desc = if settings.conflict_policy.is_some() {
+        "When the destination file exists (Space to change)"
+    } else {
+        "When the destination file exists (follows Rename duplicates)"
+    };
+    type_items.push(cycle_item(
+        "⚔️",
+        "On conflict: ",
+        settings.effective_conflict_policy().to_string(),
+        policy_desc,
+        app.selected_setting == 7,
+    ));
+    type_items.push(cycle_item(
+        "📎",
+        "Non-media files: ",
+        settings.non_media_policy.to_string(),
+        "Ignore, move to Unsorted, or organize like media (Space to change)",
+        app.selected_setting == 8,
+    ));
+
+    let is_editing_template =
+        app.input_mode == InputMode::Insert && app.editing_field == Some(EditingField::RenameTemplate);
+    let template_value = if is_editing_template {
+        format!("{}│", app.input_buffer)
+    } else {
+        settings
+            .rename_template
+            .as_ref()
+            .map_or_else(|| "keep original names".to_string(), ToString::to_string)
+    };
+    type_items.push(cycle_item(
+        "✏️",
+        "Rename files: ",
+        template_value,
+        "Enter to edit, e.g. {date}_{time}_{original} or {camera}_{counter}; empty keeps names",
+        app.selected_setting == 9,
+    ));
+    type_items.push(cycle_item(
+        "📸",
+        "Screenshots: ",
+        if settings.separate_screenshots {
+            "own folder"
+        } else {
+            "with other images"
+        }
+        .to_string(),
+        "Screen-sized PNGs and Screenshot_* files go to Screenshots (Space to change)",
+        app.selected_setting == 10,
+    ));
+    type_items.push(cycle_item(
+        "🧾",
+        "Organized before: ",
+        settings.previously_organized.to_string(),
+        "Record organized files; skip or delete them when found in the source again (Space to change)",
+        app.selected_setting == 11,
+    ));
+    type_items.push(cycle_item(
+        "🕒",
+        "Time zone: ",
+        settings.timezone_policy.label(),
+        "Zone date folders are picked in: local, each photo's EXIF offset or a fixed offset (Space to change)",
+        app.selected_setting == 12,
+    ));
+    type_items.push(cycle_item(
+        "📜",
+        "Manifest: ",
+        settings.manifest.to_string(),
+        "Record placed files, original paths and hashes in the library: off, root or every folder (Space to change)",
+        app.selected_setting == 13,
+    ));
+    type_items.push(cycle_item(
+        "🔏",
+        "Checksums: ",
+        settings.checksum_sidecars.to_string(),
+        "Keep a SHA256SUMS or checksums.sfv file in every folder files are placed in (Space to change)",
+        app.selected_setting == 14,
+    ));
+    let skip_rules = &settings.skip_rules;
+    type_items.push(cycle_item(
+        "🪶",
+        "Skip smaller than: ",
+        match skip_rules.min_size_kb {
+            0 => "off".to_string(),
+            kb => format!("{kb} KB"),
+        },
+        "Leave thumbnails and cache files in the source when organizing (Space to change)",
+        app.selected_setting == 15,
+    ));
+    type_items.push(cycle_item(
+        "🔍",
+        "Skip images below: ",
+        if skip_rules.has_min_resolution() {
+            format!("{}×{}", skip_rules.min_width, skip_rules.min_height)
+        } else {
+            "off".to_string()
+        },
+        "Leave low-resolution images in the source, in either orientation (Space to change)",
+        app.selected_setting == 16,
+    ));
+    type_items.push(cycle_item(
+        "🌱",
+        "Skip newer than: ",
+        match skip_rules.min_age_days {
+            0 => "off".to_string(),
+            1 => "1 day".to_string(),
+            days => format!("{days} days"),
+        },
+        "Leave recently modified files for a later run (Space to change)",
+        app.selected_setting == 17,
+    ));
+
     let type_list = List::new(type_items).block(
         Block::default()
             .title(" 📁 File Type Options ")
@@ -444,12 +814,56 @@ fn draw_organization_settings(f: &mut Frame, area: Rect, app: &App) {
             .border_style(Style::default().fg(MUTED_COLOR))
             .style(Style::default().bg(BACKGROUND_ALT)),
     );
-    f.render_widget(type_list, chunks[1]);
+    // The options start at setting 3; keep the focused one in view
+    let mut type_state = ListState::default().with_selected(app.selected_setting.checked_sub(3));
+    f.render_stateful_widget(type_list, chunks[1], &mut type_state);
 
     // Enhanced preview
     draw_enhanced_organization_preview(f, chunks[2], app);
 }
 
+fn format_threshold(secs: u64) -> String {
+    if secs >= 60 && secs % 60 == 0 {
+        format!("{} min", secs / 60)
+    } else {
+        format!("{secs} s")
+    }
+}
+
+/// Builds a settings list item whose value cycles through a fixed set of choices
+fn cycle_item(icon: &str, label: &str, value: String, description: &str, focused: bool) -> ListItem<'static> {
+    let bg_style = if focused {
+        Style::default().bg(HIGHLIGHT_BG)
+    } else {
+        Style::default()
+    };
+    let name_style = if focused {
+        Style::default().fg(ACCENT_COLOR).add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(Color::White)
+    };
+
+    ListItem::new(vec![
+        Line::from(vec![
+            Span::raw(" "),
+            Span::styled(icon.to_string(), Style::default().fg(WARNING_COLOR)),
+            Span::raw("  "),
+            Span::styled(label.to_string(), name_style),
+            Span::styled(value, Style::default().fg(SUCCESS_COLOR).add_modifier(Modifier::BOLD)),
+        ])
+        .style(bg_style),
+        Line::from(vec![
+            Span::raw("      "),
+            Span::styled(
+                description.to_string(),
+                Style::default().fg(MUTED_COLOR).add_modifier(Modifier::ITALIC),
+            ),
+        ])
+        .style(bg_style),
+        Line::from("").style(bg_style),
+    ])
+}
+
 #[allow(clippy::too_many_lines)]
 fn draw_performance_settings(f: &mut Frame, area: Rect, app: &App) {
     let settings = &app.settings_cache;
@@ -460,7 +874,7 @@ fn draw_performance_settings(f: &mut Frame, area: Rect, app: &App) {
         .constraints([
             Constraint::Length(5),  // Thread count
             Constraint::Length(5),  // Buffer size
-            Constraint::Length(13), // Performance options
+            Constraint::Length(32), // Performance options
             Constraint::Min(0),     // Info
         ])
         .split(area);
@@ -520,6 +934,11 @@ fn draw_performance_settings(f: &mut Frame, area: Rect, app: &App) {
             Span::raw("")
         },
         Span::styled(format!(" / {max_threads} available"), Style::default().fg(MUTED_COLOR)),
+        if settings.concurrency == ConcurrencyMode::Auto {
+            Span::styled(" (auto concurrency picks per drive)", Style::default().fg(MUTED_COLOR))
+        } else {
+            Span::raw("")
+        },
     ])]);
     f.render_widget(thread_count, thread_chunks[0]);
 
@@ -621,9 +1040,14 @@ fn draw_performance_settings(f: &mut Frame, area: Rect, app: &App) {
             "💿 Optimize for SSD",
             "Use settings optimized for solid-state drives",
         ),
+        (
+            settings.follow_symlinks,
+            "🔗 Follow symlinks",
+            "Scan linked folders, visiting each folder only once",
+        ),
     ];
 
-    let perf_items: Vec<ListItem> = perf_options
+    let mut perf_items: Vec<ListItem> = perf_options
         .iter()
         .enumerate()
         .map(|(idx, (enabled, name, desc))| {
@@ -665,6 +1089,77 @@ fn draw_performance_settings(f: &mut Frame, area: Rect, app: &App) {
             ])
         })
         .collect();
+    perf_items.push(cycle_item(
+        "🧠",
+        "Memory budget: ",
+        if settings.memory_budget_mb == 0 {
+            "unlimited".to_string()
+        } else {
+            format_bytes(settings.memory_budget_mb * 1024 * 1024)
+        },
+        "Larger scans are paged from disk instead of memory (Space to change)",
+        app.selected_setting == 7,
+    ));
+    perf_items.push(cycle_item(
+        "#️⃣",
+        "Duplicate hash: ",
+        match settings.hash_algorithm {
+            HashAlgorithm::Sha256 => "SHA-256",
+            HashAlgorithm::Blake3 => "BLAKE3",
+            HashAlgorithm::Xxh3 => "xxHash3",
+        }
+        .to_string(),
+        "SHA-256 is the slowest, xxHash3 the fastest (Space to change)",
+        app.selected_setting == 8,
+    ));
+    perf_items.push(cycle_item(
+        "🧪",
+        "Check file headers: ",
+        if settings.check_file_headers { "on" } else { "off" }.to_string(),
+        "Set aside empty files and files without the header their extension promises (Space to change)",
+        app.selected_setting == 9,
+    ));
+    perf_items.push(cycle_item(
+        "🏷️",
+        "Extension mismatch: ",
+        settings.extension_mismatch.to_string(),
+        "Flag files whose content does not match the extension, fix it when organizing, or quarantine them",
+        app.selected_setting == 10,
+    ));
+    perf_items.push(cycle_item(
+        "📦",
+        "Inspect archives: ",
+        if settings.inspect_archives { "on" } else { "off" }.to_string(),
+        "List media inside zip and tar archives while scanning, press Z to extract them (Space to change)",
+        app.selected_setting == 11,
+    ));
+    perf_items.push(cycle_item(
+        "🎛️",
+        "Concurrency: ",
+        settings.concurrency.to_string(),
+        "Auto probes the drive at scan start and picks workers for SSDs, hard drives or network shares",
+        app.selected_setting == 12,
+    ));
+    perf_items.push(cycle_item(
+        "🐢",
+        "Max throughput: ",
+        match settings.throttle.max_mb_per_sec {
+            0 => "unlimited".to_string(),
+            mb => format!("{mb} MB/s"),
+        },
+        "Caps reads and copies so scans and organize runs leave a NAS or shared disk usable",
+        app.selected_setting == 13,
+    ));
+    perf_items.push(cycle_item(
+        "🐌",
+        "Max IOPS: ",
+        match settings.throttle.max_iops {
+            0 => "unlimited".to_string(),
+            iops => format!("{iops} files/s"),
+        },
+        "Caps how many files are opened, read or moved per second (Space to change)",
+        app.selected_setting == 14,
+    ));
 
     let perf_list = List::new(perf_items).block(
         Block::default()
@@ -674,12 +1169,438 @@ fn draw_performance_settings(f: &mut Frame, area: Rect, app: &App) {
             .border_style(Style::default().fg(MUTED_COLOR))
             .style(Style::default().bg(BACKGROUND_ALT)),
     );
-    f.render_widget(perf_list, chunks[2]);
+    let mut perf_state = ListState::default().with_selected(app.selected_setting.checked_sub(2));
+    f.render_stateful_widget(perf_list, chunks[2], &mut perf_state);
 
     // Enhanced performance info
     draw_enhanced_performance_info(f, chunks[3]);
 }
 
+/// Custom extension mappings, one row each, followed by a row for adding another
+#[allow(clippy::too_many_lines)]
+fn draw_file_type_settings(f: &mut Frame, area: Rect, app: &App) {
+    let mappings = &app.settings_cache.type_mappings;
+    let is_editing = app.input_mode == InputMode::Insert && app.editing_field == Some(EditingField::TypeMapping);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([Constraint::Min(5), Constraint::Length(9)])
+        .split(area);
+
+    let row_style = |focused: bool| {
+        if focused {
+            Style::default().bg(HIGHLIGHT_BG)
+        } else {
+            Style::default()
+        }
+    };
+    let edit_line = || {
+        Line::from(vec![
+            Span::raw("  ✏️  "),
+            Span::styled(
+                format!("{}│", app.input_buffer),
+                Style::default().fg(WARNING_COLOR).add_modifier(Modifier::BOLD),
+            ),
+        ])
+    };
+
+    let mut items: Vec<ListItem> = mappings
+        .iter()
+        .enumerate()
+        .map(|(idx, (extension, mapping))| {
+            let focused = app.selected_setting == idx;
+            if focused && is_editing {
+                return ListItem::new(edit_line()).style(row_style(true));
+            }
+            let type_color = match mapping {
+                TypeMapping::Ignore => MUTED_COLOR,
+                TypeMapping::Other | TypeMapping::Document => WARNING_COLOR,
+                _ => SUCCESS_COLOR,
+            };
+            let name_style = if focused {
+                Style::default().fg(ACCENT_COLOR).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            ListItem::new(Line::from(vec![
+                Span::raw("  "),
+                Span::styled(format!(".{extension:<10}"), name_style),
+                Span::styled(" ➜ ", Style::default().fg(MUTED_COLOR)),
+                Span::styled(
+                    mapping.to_string(),
+                    Style::default().fg(type_color).add_modifier(Modifier::BOLD),
+                ),
+            ]))
+            .style(row_style(focused))
+        })
+        .collect();
+
+    let add_focused = app.selected_setting == mappings.len();
+    items.push(if add_focused && is_editing {
+        ListItem::new(edit_line()).style(row_style(true))
+    } else {
+        ListItem::new(Line::from(Span::styled(
+            "  ➕ Add mapping",
+            if add_focused {
+                Style::default().fg(ACCENT_COLOR).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(MUTED_COLOR)
+            },
+        )))
+        .style(row_style(add_focused))
+    });
+
+    let list = List::new(items).block(
+        Block::default()
+            .title(format!(" 🏷️  Custom Type Mappings ({}) ", mappings.len()))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(MUTED_COLOR))
+            .style(Style::default().bg(BACKGROUND_ALT)),
+    );
+    f.render_widget(list, chunks[0]);
+
+    let key = |key: &'static str, desc: &'static str| {
+        Line::from(vec![
+            Span::raw("  "),
+            Span::styled(
+                format!("{key:<7}"),
+                Style::default().fg(WARNING_COLOR).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(desc, Style::default().fg(Color::White)),
+        ])
+    };
+    let help = Paragraph::new(vec![
+        Line::from(Span::styled(
+            "Mappings override the built-in extension list when scanning",
+            Style::default().fg(MUTED_COLOR).add_modifier(Modifier::ITALIC),
+        )),
+        Line::from(""),
+        key("Enter", "Add or edit as extension=type, e.g. insv=video or gpx=ignore"),
+        key("Space", "Cycle image, video, audio, document, other, ignore"),
+        key("x/Del", "Remove the selected mapping"),
+        key("S", "Save settings"),
+    ])
+    .block(
+        Block::default()
+            .title(" ℹ️  File Types ")
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(MUTED_COLOR)),
+    );
+    f.render_widget(help, chunks[1]);
+}
+
+/// Per-folder rules in the order they are tried, followed by a row for adding another
+#[allow(clippy::too_many_lines)]
+fn draw_rules_settings(f: &mut Frame, area: Rect, app: &App) {
+    let rules = &app.settings_cache.folder_rules;
+    let is_editing = app.input_mode == InputMode::Insert && app.editing_field == Some(EditingField::FolderRule);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([Constraint::Min(5), Constraint::Length(10)])
+        .split(area);
+
+    let row_style = |focused: bool| {
+        if focused {
+            Style::default().bg(HIGHLIGHT_BG)
+        } else {
+            Style::default()
+        }
+    };
+    let edit_line = || {
+        Line::from(vec![
+            Span::raw("  ✏️  "),
+            Span::styled(
+                format!("{}│", app.input_buffer),
+                Style::default().fg(WARNING_COLOR).add_modifier(Modifier::BOLD),
+            ),
+        ])
+    };
+
+    let mut items: Vec<ListItem> = rules
+        .iter()
+        .enumerate()
+        .map(|(idx, rule)| {
+            let focused = app.selected_setting == idx;
+            if focused && is_editing {
+                return ListItem::new(edit_line()).style(row_style(true));
+            }
+            let (mark, mark_color) = if rule.enabled {
+                ("✔", SUCCESS_COLOR)
+            } else {
+                ("✗", MUTED_COLOR)
+            };
+            let folder_style = if !rule.enabled {
+                Style::default().fg(MUTED_COLOR)
+            } else if focused {
+                Style::default().fg(ACCENT_COLOR).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("  {:>2}. ", idx + 1), Style::default().fg(MUTED_COLOR)),
+                Span::styled(
+                    format!("{mark} "),
+                    Style::default().fg(mark_color).add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(rule.folder.display().to_string(), folder_style),
+                Span::styled(" ➜ ", Style::default().fg(MUTED_COLOR)),
+                Span::styled(
+                    rule.target.clone(),
+                    Style::default()
+                        .fg(if rule.enabled { SUCCESS_COLOR } else { MUTED_COLOR })
+                        .add_modifier(Modifier::BOLD),
+                ),
+            ]))
+            .style(row_style(focused))
+        })
+        .collect();
+
+    let add_focused = app.selected_setting == rules.len();
+    items.push(if add_focused && is_editing {
+        ListItem::new(edit_line()).style(row_style(true))
+    } else {
+        ListItem::new(Line::from(Span::styled(
+            "  ➕ Add rule",
+            if add_focused {
+                Style::default().fg(ACCENT_COLOR).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(MUTED_COLOR)
+            },
+        )))
+        .style(row_style(add_focused))
+    });
+
+    let list = List::new(items).block(
+        Block::default()
+            .title(format!(" 📐 Folder Rules ({}) ", rules.len()))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(MUTED_COLOR))
+            .style(Style::default().bg(BACKGROUND_ALT)),
+    );
+    f.render_widget(list, chunks[0]);
+
+    let key = |key: &'static str, desc: &'static str| {
+        Line::from(vec![
+            Span::raw("  "),
+            Span::styled(
+                format!("{key:<7}"),
+                Style::default().fg(WARNING_COLOR).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(desc, Style::default().fg(Color::White)),
+        ])
+    };
+    let help = Paragraph::new(vec![
+        Line::from(Span::styled(
+            "The first enabled rule matching a file's source folder wins over the organization mode",
+            Style::default().fg(MUTED_COLOR).add_modifier(Modifier::ITALIC),
+        )),
+        Line::from(""),
+        key(
+            "Enter",
+            "Add or edit as folder=target, e.g. WhatsApp Images=WhatsApp/{year}",
+        ),
+        key("Space", "Enable or disable the selected rule"),
+        key("K/J", "Move the selected rule earlier or later"),
+        key("x/Del", "Remove the selected rule"),
+        key("S", "Save settings"),
+    ])
+    .block(
+        Block::default()
+            .title(" ℹ️  Rules ")
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(MUTED_COLOR)),
+    );
+    f.render_widget(help, chunks[1]);
+}
+
+/// Routing rules in priority order, a row for adding another and a field for trying them out
+#[allow(clippy::too_many_lines)]
+fn draw_routing_settings(f: &mut Frame, area: Rect, app: &App) {
+    let settings = &app.settings_cache;
+    let rules = &settings.routing_rules;
+    let is_editing = app.input_mode == InputMode::Insert && app.editing_field == Some(EditingField::RoutingRule);
+    let is_testing = app.input_mode == InputMode::Insert && app.editing_field == Some(EditingField::RoutingSample);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([Constraint::Min(5), Constraint::Length(4), Constraint::Length(11)])
+        .split(area);
+
+    let row_style = |focused: bool| {
+        if focused {
+            Style::default().bg(HIGHLIGHT_BG)
+        } else {
+            Style::default()
+        }
+    };
+    let edit_line = || {
+        Line::from(vec![
+            Span::raw("  ✏️  "),
+            Span::styled(
+                format!("{}│", app.input_buffer),
+                Style::default().fg(WARNING_COLOR).add_modifier(Modifier::BOLD),
+            ),
+        ])
+    };
+
+    let mut items: Vec<ListItem> = rules
+        .iter()
+        .enumerate()
+        .map(|(idx, rule)| {
+            let focused = app.selected_setting == idx;
+            if focused && is_editing {
+                return ListItem::new(edit_line()).style(row_style(true));
+            }
+            let valid = rule.regex().is_ok();
+            let (mark, mark_color) = match (rule.enabled, valid) {
+                (_, false) => ("⚠", ERROR_COLOR),
+                (true, true) => ("✔", SUCCESS_COLOR),
+                (false, true) => ("✗", MUTED_COLOR),
+            };
+            let pattern_style = if !rule.enabled {
+                Style::default().fg(MUTED_COLOR)
+            } else if focused {
+                Style::default().fg(ACCENT_COLOR).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("  {:>2}. ", idx + 1), Style::default().fg(MUTED_COLOR)),
+                Span::styled(
+                    format!("{mark} "),
+                    Style::default().fg(mark_color).add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(rule.pattern.clone(), pattern_style),
+                Span::styled(" ➜ ", Style::default().fg(MUTED_COLOR)),
+                Span::styled(
+                    rule.target.clone(),
+                    Style::default()
+                        .fg(if rule.enabled { SUCCESS_COLOR } else { MUTED_COLOR })
+                        .add_modifier(Modifier::BOLD),
+                ),
+            ]))
+            .style(row_style(focused))
+        })
+        .collect();
+
+    let add_focused = app.selected_setting == rules.len();
+    items.push(if add_focused && is_editing {
+        ListItem::new(edit_line()).style(row_style(true))
+    } else {
+        ListItem::new(Line::from(Span::styled(
+            "  ➕ Add rule",
+            if add_focused {
+                Style::default().fg(ACCENT_COLOR).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(MUTED_COLOR)
+            },
+        )))
+        .style(row_style(add_focused))
+    });
+
+    let list = List::new(items).block(
+        Block::default()
+            .title(format!(" 🔀 Routing Rules ({}) ", rules.len()))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(MUTED_COLOR))
+            .style(Style::default().bg(BACKGROUND_ALT)),
+    );
+    f.render_widget(list, chunks[0]);
+
+    // The sample is tried as it is typed, against the rules as edited
+    let sample = if is_testing {
+        app.input_buffer.trim()
+    } else {
+        app.routing_sample.as_str()
+    };
+    let sample_line = if is_testing {
+        edit_line()
+    } else if sample.is_empty() {
+        Line::from(Span::styled(
+            "  Press t to type a file name to try",
+            Style::default().fg(MUTED_COLOR).add_modifier(Modifier::ITALIC),
+        ))
+    } else {
+        Line::from(vec![
+            Span::raw("  "),
+            Span::styled(sample, Style::default().fg(Color::White)),
+        ])
+    };
+    let result_line = if sample.is_empty() {
+        Line::from("")
+    } else if let Some(rule) = settings.routing_rule(sample) {
+        let number = rules
+            .iter()
+            .position(|r| std::ptr::eq(r, rule))
+            .map_or(0, |idx| idx + 1);
+        Line::from(vec![
+            Span::raw("  ➜ "),
+            Span::styled(
+                get_preview_path(settings, sample, "image"),
+                Style::default().fg(SUCCESS_COLOR).add_modifier(Modifier::ITALIC),
+            ),
+            Span::styled(format!("  (rule {number})"), Style::default().fg(MUTED_COLOR)),
+        ])
+    } else {
+        Line::from(Span::styled(
+            "  ➜ No rule matches; the folder rules and organization mode decide",
+            Style::default().fg(WARNING_COLOR),
+        ))
+    };
+    let test = Paragraph::new(vec![sample_line, result_line]).block(
+        Block::default()
+            .title(" 🧪 Try a File Name ")
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(get_enhanced_border_style(false, is_testing)),
+    );
+    f.render_widget(test, chunks[1]);
+
+    let key = |key: &'static str, desc: &'static str| {
+        Line::from(vec![
+            Span::raw("  "),
+            Span::styled(
+                format!("{key:<7}"),
+                Style::default().fg(WARNING_COLOR).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(desc, Style::default().fg(Color::White)),
+        ])
+    };
+    let help = Paragraph::new(vec![
+        Line::from(Span::styled(
+            "The first enabled rule whose pattern matches a file name wins, ahead of the folder rules",
+            Style::default().fg(MUTED_COLOR).add_modifier(Modifier::ITALIC),
+        )),
+        Line::from(""),
+        key(
+            "Enter",
+            "Add or edit as pattern=target, e.g. ^IMG-\\d{8}-WA=WhatsApp/{year}",
+        ),
+        key("Space", "Enable or disable the selected rule"),
+        key("K/J", "Raise or lower the priority of the selected rule"),
+        key("t", "Try a file name against the rules"),
+        key("x/Del", "Remove the selected rule"),
+        key("S", "Save settings"),
+    ])
+    .block(
+        Block::default()
+            .title(" ℹ️  Routing ")
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(MUTED_COLOR)),
+    );
+    f.render_widget(help, chunks[2]);
+}
+
 fn draw_enhanced_organization_preview(f: &mut Frame, area: Rect, app: &App) {
     let settings = &app.settings_cache;
     let preview_examples = vec![
@@ -709,7 +1630,10 @@ fn draw_enhanced_organization_preview(f: &mut Frame, area: Rect, app: &App) {
     let preview = Paragraph::new(preview_lines)
         .block(
             Block::default()
-                .title(" 👁️  Organization Preview ")
+                .title(format!(
+                    " 👁️  Organization Preview (dates in {}) ",
+                    settings.timezone_policy.label()
+                ))
                 .borders(Borders::ALL)
                 .border_type(BorderType::Rounded)
                 .border_style(Style::default().fg(MUTED_COLOR))
@@ -825,12 +1749,21 @@ fn draw_enhanced_performance_info(f: &mut Frame, area: Rect) {
 }
 
 fn get_preview_path(settings: &Settings, filename: &str, file_type: &str) -> String {
+    let rule = settings.routing_rule(filename);
+    let renamed = preview_file_name(settings, filename);
+    let filename = renamed.as_str();
     let base = if let Some(dest) = &settings.destination_folder {
         dest.display().to_string()
     } else {
         "/destination".to_string()
     };
 
+    // Routing rules match the original name and win over the organization mode
+    if let Some(rule) = rule {
+        let folders = rule.render(&preview_modified().fixed_offset());
+        return format!("{base}/{}/{filename}", folders.join("/"));
+    }
+
     let path = match settings.organize_by.as_str() {
         "yearly" => format!("{base}/2024/{filename}"),
         "monthly" => format!("{base}/2024/03-March/{filename}"),
@@ -847,6 +1780,44 @@ fn get_preview_path(settings: &Settings, filename: &str, file_type: &str) -> Str
     }
 }
 
+/// When the example files of the previews were modified
+fn preview_modified() -> DateTime<Local> {
+    Local
+        .with_ymd_and_hms(2024, 3, 15, 14, 30, 22)
+        .single()
+        .unwrap_or_else(Local::now)
+}
+
+/// Applies the rename template and extension rule to an example file modified on 2024-03-15 14:30:22
+fn preview_file_name(settings: &Settings, filename: &str) -> String {
+    let path = Path::new(filename);
+    let stem = path.file_stem().map_or(filename, |s| s.to_str().unwrap_or(filename));
+    let extension = path.extension().and_then(|e| e.to_str()).unwrap_or_default();
+    let extension = if settings.lowercase_extensions {
+        extension.to_lowercase()
+    } else {
+        extension.to_string()
+    };
+
+    let stem = settings.rename_template.as_ref().map_or_else(
+        || stem.to_string(),
+        |template| {
+            template.render(&RenameContext {
+                modified: preview_modified(),
+                original: stem,
+                camera: None,
+                counter: 1,
+            })
+        },
+    );
+
+    if extension.is_empty() {
+        stem
+    } else {
+        format!("{stem}.{extension}")
+    }
+}
+
 fn capitalize_type(file_type: &str) -> String {
     match file_type {
         "image" => "Images",
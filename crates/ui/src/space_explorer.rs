@@ -0,0 +1,239 @@
+use std::path::Path;
+
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph},
+};
+use visualvault_app::App;
+use visualvault_models::SpaceFocus;
+use visualvault_utils::{format_bytes, format_count};
+
+use super::{ACCENT_COLOR, BACKGROUND_ALT, MUTED_COLOR, SUCCESS_COLOR, WARNING_COLOR};
+
+/// Width of the bar showing a folder's share of the explored folder
+const SHARE_BAR_WIDTH: usize = 10;
+
+pub fn draw(f: &mut Frame, area: Rect, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(3), // Folder being explored
+            Constraint::Min(0),    // Folders and files
+            Constraint::Length(3), // Help
+        ])
+        .split(area);
+
+    draw_summary(f, chunks[0], app);
+    let lists = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(chunks[1]);
+    draw_folders(f, lists[0], app);
+    draw_files(f, lists[1], app);
+    draw_help(f, chunks[2]);
+}
+
+fn draw_summary(f: &mut Frame, area: Rect, app: &App) {
+    let line = match &app.space_report {
+        Some(report) => Line::from(vec![
+            Span::styled(
+                shown_path(&report.root, app.settings_cache.destination_folder.as_deref()),
+                Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!(
+                    "  {} in {} files ({} media), {} folders",
+                    format_bytes(report.total.total_size),
+                    format_count(report.total.total_files),
+                    format_count(report.total.media_files),
+                    format_count(report.total.total_dirs)
+                ),
+                Style::default().fg(MUTED_COLOR),
+            ),
+        ]),
+        None => Line::from(Span::styled(
+            "Adding up the destination folder...",
+            Style::default().fg(MUTED_COLOR).add_modifier(Modifier::ITALIC),
+        )),
+    };
+    let title = if app.is_exploring_space() && app.space_report.is_some() {
+        " 💽 Space Explorer (updating...) "
+    } else {
+        " 💽 Space Explorer "
+    };
+
+    let paragraph = Paragraph::new(line).block(
+        Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(MUTED_COLOR))
+            .style(Style::default().bg(BACKGROUND_ALT)),
+    );
+    f.render_widget(paragraph, area);
+}
+
+fn draw_folders(f: &mut Frame, area: Rect, app: &App) {
+    let focused = app.space_focus == SpaceFocus::Folders;
+    let report = app.space_report.as_ref();
+    let lines: Vec<Line> = match report {
+        Some(report) if !report.folders.is_empty() => {
+            let rows = visible_rows(area, app.space_selected, focused);
+            report
+                .folders
+                .iter()
+                .enumerate()
+                .skip(rows.0)
+                .take(rows.1)
+                .map(|(idx, folder)| {
+                    let name = folder.path.strip_prefix(&report.root).unwrap_or(&folder.path);
+                    Line::from(vec![
+                        Span::styled(
+                            share_bar(folder.stats.total_size, report.total.total_size),
+                            Style::default().fg(WARNING_COLOR),
+                        ),
+                        Span::styled(
+                            format!(" {:>10} ", format_bytes(folder.stats.total_size)),
+                            Style::default().fg(ACCENT_COLOR),
+                        ),
+                        Span::styled(
+                            format!("📂 {}", name.display()),
+                            row_style(focused && idx == app.space_selected),
+                        ),
+                        Span::styled(
+                            format!("  {} files", format_count(folder.stats.total_files)),
+                            Style::default().fg(MUTED_COLOR),
+                        ),
+                    ])
+                })
+                .collect()
+        }
+        Some(_) => vec![empty_line("No subfolders here")],
+        None => Vec::new(),
+    };
+
+    let count = report.map_or(0, |report| report.folders.len());
+    f.render_widget(
+        Paragraph::new(lines).block(list_block(format!(" Largest folders ({count}) "), focused)),
+        area,
+    );
+}
+
+fn draw_files(f: &mut Frame, area: Rect, app: &App) {
+    let focused = app.space_focus == SpaceFocus::Files;
+    let report = app.space_report.as_ref();
+    let destination = app.settings_cache.destination_folder.as_deref();
+    let lines: Vec<Line> = match report {
+        Some(report) if !report.files.is_empty() => {
+            let rows = visible_rows(area, app.space_selected, focused);
+            report
+                .files
+                .iter()
+                .enumerate()
+                .skip(rows.0)
+                .take(rows.1)
+                .map(|(idx, (path, size))| {
+                    Line::from(vec![
+                        Span::styled(
+                            format!("{:>10} ", format_bytes(*size)),
+                            Style::default().fg(SUCCESS_COLOR),
+                        ),
+                        Span::styled(
+                            shown_path(path, destination),
+                            row_style(focused && idx == app.space_selected),
+                        ),
+                    ])
+                })
+                .collect()
+        }
+        Some(_) => vec![empty_line("No files here")],
+        None => Vec::new(),
+    };
+
+    let count = report.map_or(0, |report| report.files.len());
+    f.render_widget(
+        Paragraph::new(lines).block(list_block(format!(" Largest files ({count}) "), focused)),
+        area,
+    );
+}
+
+/// First row and number of rows to show so the selected row of a focused list stays visible
+fn visible_rows(area: Rect, selected: usize, focused: bool) -> (usize, usize) {
+    let rows = usize::from(area.height.saturating_sub(2));
+    let offset = if focused {
+        selected.saturating_sub(rows.saturating_sub(1))
+    } else {
+        0
+    };
+    (offset, rows)
+}
+
+/// A bar filled in proportion to `size`'s share of `total`
+fn share_bar(size: u64, total: u64) -> String {
+    let total = total.max(1);
+    let filled = usize::try_from(size.saturating_mul(SHARE_BAR_WIDTH as u64) / total)
+        .unwrap_or(SHARE_BAR_WIDTH)
+        .min(SHARE_BAR_WIDTH);
+    format!("{}{}", "█".repeat(filled), "░".repeat(SHARE_BAR_WIDTH - filled))
+}
+
+/// `path` relative to the destination folder, or in full outside it
+fn shown_path(path: &Path, destination: Option<&Path>) -> String {
+    match destination.and_then(|destination| path.strip_prefix(destination).ok()) {
+        Some(relative) if relative.as_os_str().is_empty() => "Destination folder".to_string(),
+        Some(relative) => relative.display().to_string(),
+        None => path.display().to_string(),
+    }
+}
+
+fn row_style(selected: bool) -> Style {
+    if selected {
+        Style::default()
+            .fg(ACCENT_COLOR)
+            .add_modifier(Modifier::BOLD | Modifier::REVERSED)
+    } else {
+        Style::default().fg(Color::White)
+    }
+}
+
+fn empty_line(text: &'static str) -> Line<'static> {
+    Line::from(Span::styled(
+        text,
+        Style::default().fg(MUTED_COLOR).add_modifier(Modifier::ITALIC),
+    ))
+}
+
+fn list_block(title: String, focused: bool) -> Block<'static> {
+    Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(if focused { ACCENT_COLOR } else { MUTED_COLOR }))
+        .style(Style::default().bg(BACKGROUND_ALT))
+}
+
+fn draw_help(f: &mut Frame, area: Rect) {
+    let help = Paragraph::new(Line::from(vec![
+        Span::styled("Enter", Style::default().fg(ACCENT_COLOR)),
+        Span::raw(" - Open folder / show file | "),
+        Span::styled("Backspace", Style::default().fg(ACCENT_COLOR)),
+        Span::raw(" - Back | "),
+        Span::styled("Tab", Style::default().fg(ACCENT_COLOR)),
+        Span::raw(" - Folders/files | "),
+        Span::styled("r", Style::default().fg(ACCENT_COLOR)),
+        Span::raw(" - Refresh | "),
+        Span::styled("Esc", Style::default().fg(ACCENT_COLOR)),
+        Span::raw(" - Close"),
+    ]))
+    .alignment(Alignment::Center)
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(MUTED_COLOR)),
+    );
+    f.render_widget(help, area);
+}
@@ -0,0 +1,150 @@
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+};
+use visualvault_app::App;
+use visualvault_models::{Toast, ToastSeverity, Toasts};
+
+use super::{ACCENT_COLOR, BACKGROUND_ALT, ERROR_COLOR, MUTED_COLOR, SUCCESS_COLOR, WARNING_COLOR};
+
+/// Widest a toast gets, borders included
+const TOAST_WIDTH: u16 = 64;
+
+/// Icon and color of toasts of `severity`
+pub const fn severity_style(severity: ToastSeverity) -> (&'static str, Color) {
+    match severity {
+        ToastSeverity::Info => ("ℹ", ACCENT_COLOR),
+        ToastSeverity::Success => ("✨", SUCCESS_COLOR),
+        ToastSeverity::Warning => ("⚠", WARNING_COLOR),
+        ToastSeverity::Error => ("🚨", ERROR_COLOR),
+    }
+}
+
+/// One line for `toast`, with how often it was raised in a row
+pub fn toast_line(toast: &Toast) -> Line<'static> {
+    let (icon, color) = severity_style(toast.severity);
+    let mut spans = vec![
+        Span::styled(format!("{icon} "), Style::default().fg(color)),
+        Span::styled(
+            toast.message.clone(),
+            Style::default().fg(color).add_modifier(Modifier::BOLD),
+        ),
+    ];
+    if toast.repeats > 1 {
+        spans.push(Span::styled(
+            format!(" (×{})", toast.repeats),
+            Style::default().fg(MUTED_COLOR),
+        ));
+    }
+    Line::from(spans)
+}
+
+/// Stacks the toasts on screen in the bottom right corner of `area`, newest at the bottom.
+///
+/// Toasts are only drawn; keys keep going to the view underneath.
+pub fn draw_toasts(f: &mut Frame, area: Rect, app: &App) {
+    let toasts: Vec<&Toast> = app.visible_toasts().collect();
+    let width = TOAST_WIDTH.min(area.width.saturating_sub(2));
+    let mut bottom = area.bottom();
+    for toast in toasts {
+        if bottom < area.top() + 3 || width < 10 {
+            break;
+        }
+        let rect = Rect::new(area.right().saturating_sub(width + 1), bottom - 3, width, 3);
+        bottom -= 3;
+
+        let (_, color) = severity_style(toast.severity);
+        f.render_widget(Clear, rect);
+        f.render_widget(
+            Paragraph::new(toast_line(toast)).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .border_style(Style::default().fg(color))
+                    .style(Style::default().bg(BACKGROUND_ALT)),
+            ),
+            rect,
+        );
+    }
+}
+
+/// The notification history view
+pub fn draw(f: &mut Frame, area: Rect, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Min(0),    // History
+            Constraint::Length(3), // Help
+        ])
+        .split(area);
+
+    draw_history(f, chunks[0], &app.toasts);
+    draw_help(f, chunks[1]);
+}
+
+fn draw_history(f: &mut Frame, area: Rect, toasts: &Toasts) {
+    let lines: Vec<Line> = if toasts.is_empty() {
+        vec![Line::from(Span::styled(
+            "Results of scans, organize runs and other actions show up here",
+            Style::default().fg(MUTED_COLOR).add_modifier(Modifier::ITALIC),
+        ))]
+    } else {
+        let rows = usize::from(area.height.saturating_sub(2));
+        let offset = toasts.selected.saturating_sub(rows.saturating_sub(1));
+        toasts
+            .entries
+            .iter()
+            .enumerate()
+            .skip(offset)
+            .take(rows)
+            .map(|(idx, toast)| {
+                let mut line = toast_line(toast);
+                line.spans.insert(
+                    0,
+                    Span::styled(
+                        format!("{} ", toast.timestamp.format("%H:%M:%S")),
+                        Style::default().fg(MUTED_COLOR),
+                    ),
+                );
+                if idx == toasts.selected {
+                    line = line.style(Style::default().add_modifier(Modifier::REVERSED));
+                }
+                line
+            })
+            .collect()
+    };
+
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .title(format!(" 🔔 Notifications ({}) ", toasts.len()))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(MUTED_COLOR))
+            .style(Style::default().bg(BACKGROUND_ALT)),
+    );
+    f.render_widget(paragraph, area);
+}
+
+fn draw_help(f: &mut Frame, area: Rect) {
+    let help = Paragraph::new(Line::from(vec![
+        Span::styled("d", Style::default().fg(ACCENT_COLOR)),
+        Span::raw(" - Hide toasts | "),
+        Span::styled("c", Style::default().fg(ERROR_COLOR)),
+        Span::raw(" - Clear history | "),
+        Span::styled("↑↓", Style::default().fg(ACCENT_COLOR)),
+        Span::raw(" - Scroll | "),
+        Span::styled("Esc", Style::default().fg(ACCENT_COLOR)),
+        Span::raw(" - Back"),
+    ]))
+    .alignment(Alignment::Center)
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(MUTED_COLOR)),
+    );
+    f.render_widget(help, area);
+}
@@ -0,0 +1,163 @@
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph},
+};
+use visualvault_app::App;
+use visualvault_models::{VerifyIssueKind, VerifyReport};
+use visualvault_utils::format_bytes;
+
+use super::{ACCENT_COLOR, BACKGROUND_ALT, ERROR_COLOR, MUTED_COLOR, SUCCESS_COLOR, WARNING_COLOR};
+
+pub fn draw(f: &mut Frame, area: Rect, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(4), // Summary
+            Constraint::Min(0),    // Issues
+            Constraint::Length(3), // Help
+        ])
+        .split(area);
+
+    draw_summary(f, chunks[0], app);
+    draw_issues(f, chunks[1], app);
+    draw_help(f, chunks[2]);
+}
+
+fn draw_summary(f: &mut Frame, area: Rect, app: &App) {
+    let lines = match &app.verify_report {
+        _ if app.verify_task.is_some() => vec![Line::from(Span::styled(
+            "⏳ Re-hashing library files...",
+            Style::default().fg(ACCENT_COLOR),
+        ))],
+        None => vec![Line::from(Span::styled(
+            "No verification has run yet",
+            Style::default().fg(MUTED_COLOR).add_modifier(Modifier::ITALIC),
+        ))],
+        Some(report) => summary_lines(report),
+    };
+
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .title(" 🛡 Library Verification ")
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(MUTED_COLOR))
+            .style(Style::default().bg(BACKGROUND_ALT)),
+    );
+    f.render_widget(paragraph, area);
+}
+
+fn summary_lines(report: &VerifyReport) -> Vec<Line<'_>> {
+    let count = |kind| report.count(kind).to_string();
+    vec![
+        Line::from(vec![
+            Span::styled(report.library.display().to_string(), Style::default().fg(Color::White)),
+            Span::styled(
+                format!("  {}", report.timestamp.format("%Y-%m-%d %H:%M:%S")),
+                Style::default().fg(MUTED_COLOR),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled(format!("{} checked", report.checked), Style::default().fg(ACCENT_COLOR)),
+            Span::raw(" | "),
+            Span::styled(
+                format!("{} newly indexed", report.added),
+                Style::default().fg(SUCCESS_COLOR),
+            ),
+            Span::raw(" | "),
+            Span::styled(
+                format!("{} missing", count(VerifyIssueKind::Missing)),
+                Style::default().fg(ERROR_COLOR),
+            ),
+            Span::raw(" | "),
+            Span::styled(
+                format!("{} modified", count(VerifyIssueKind::Modified)),
+                Style::default().fg(WARNING_COLOR),
+            ),
+            Span::raw(" | "),
+            Span::styled(
+                format!("{} corrupted", count(VerifyIssueKind::Corrupted)),
+                Style::default().fg(ERROR_COLOR),
+            ),
+        ]),
+    ]
+}
+
+fn draw_issues(f: &mut Frame, area: Rect, app: &App) {
+    let issues = app.verify_report.as_ref().map_or(&[][..], |report| &report.issues[..]);
+
+    let lines: Vec<Line> = if issues.is_empty() {
+        let message = if app.verify_report.is_some() {
+            "All indexed files are intact"
+        } else {
+            ""
+        };
+        vec![Line::from(Span::styled(
+            message,
+            Style::default().fg(MUTED_COLOR).add_modifier(Modifier::ITALIC),
+        ))]
+    } else {
+        issues
+            .iter()
+            .skip(app.verify_scroll)
+            .map(|issue| {
+                let (icon, color) = match issue.kind {
+                    VerifyIssueKind::Missing => ("❌", ERROR_COLOR),
+                    VerifyIssueKind::Modified => ("✏", WARNING_COLOR),
+                    VerifyIssueKind::Corrupted => ("⚠", ERROR_COLOR),
+                };
+                let detail = match (&issue.actual, &issue.error) {
+                    (_, Some(error)) => error.clone(),
+                    (Some(actual), None) if actual.size != issue.expected.size => {
+                        format!("{} → {}", format_bytes(issue.expected.size), format_bytes(actual.size))
+                    }
+                    _ => format_bytes(issue.expected.size),
+                };
+                Line::from(vec![
+                    Span::styled(
+                        format!("{icon} {:<10}", issue.kind.to_string()),
+                        Style::default().fg(color),
+                    ),
+                    Span::styled(issue.path.display().to_string(), Style::default().fg(Color::White)),
+                    Span::styled(format!("  {detail}"), Style::default().fg(MUTED_COLOR)),
+                ])
+            })
+            .collect()
+    };
+
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .title(format!(" Issues ({}) ", issues.len()))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(MUTED_COLOR))
+            .style(Style::default().bg(BACKGROUND_ALT)),
+    );
+    f.render_widget(paragraph, area);
+}
+
+fn draw_help(f: &mut Frame, area: Rect) {
+    let help = Paragraph::new(Line::from(vec![
+        Span::styled("e", Style::default().fg(ACCENT_COLOR).add_modifier(Modifier::BOLD)),
+        Span::raw(" - Export JSON | "),
+        Span::styled("a", Style::default().fg(WARNING_COLOR)),
+        Span::raw(" - Accept current files | "),
+        Span::styled("r", Style::default().fg(ACCENT_COLOR)),
+        Span::raw(" - Verify again | "),
+        Span::styled("↑↓", Style::default().fg(ACCENT_COLOR)),
+        Span::raw(" - Scroll | "),
+        Span::styled("Esc", Style::default().fg(ACCENT_COLOR)),
+        Span::raw(" - Back"),
+    ]))
+    .alignment(Alignment::Center)
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(MUTED_COLOR)),
+    );
+    f.render_widget(help, area);
+}
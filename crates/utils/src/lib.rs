@@ -1,12 +1,18 @@
 mod bytes;
+mod count;
 pub mod datetime;
 mod folder_stats;
+mod logs;
 pub mod media_types;
 mod path;
 mod progress;
 
 //
 pub use bytes::format_bytes;
+pub use count::format_count;
 pub use folder_stats::FolderStats;
-pub use path::create_cache_path;
-pub use progress::Progress;
+pub use logs::{LOG_FILE_NAME, LOG_RETENTION, MAX_LOG_BYTES, RotatingLogFile, log_dir, log_file_path, read_log_tail};
+pub use path::{create_cache_path, extended_length_path, sanitize_path_component};
+pub use progress::{
+    Progress, STAGE_HASH, STAGE_METADATA, STAGE_ORGANIZE, STAGE_TRANSFER, STAGE_WALK, Stage, StageStatus,
+};
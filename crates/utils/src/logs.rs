@@ -0,0 +1,191 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+/// Name of the log file currently written to; rotated files get `.1`, `.2` and so on appended
+pub const LOG_FILE_NAME: &str = "visualvault.log";
+
+/// Size at which the log file is rotated
+pub const MAX_LOG_BYTES: u64 = 10 * 1024 * 1024;
+
+/// Rotated log files kept next to the current one
+pub const LOG_RETENTION: usize = 5;
+
+/// Returns the folder holding the log files.
+///
+/// This is the platform state folder, `~/.local/state/visualvault/logs` on Linux, or the
+/// local data folder where the platform has none.
+#[must_use]
+pub fn log_dir() -> Option<PathBuf> {
+    dirs::state_dir()
+        .or_else(dirs::data_local_dir)
+        .map(|dir| dir.join("visualvault").join("logs"))
+}
+
+/// Returns the path of the log file currently written to
+#[must_use]
+pub fn log_file_path() -> Option<PathBuf> {
+    log_dir().map(|dir| dir.join(LOG_FILE_NAME))
+}
+
+/// Reads the last lines of the log file at `path`, at most `max_bytes` of them.
+///
+/// A line cut off at the start of the read is left out.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be opened or read.
+pub fn read_log_tail(path: &Path, max_bytes: u64) -> io::Result<Vec<String>> {
+    let mut file = File::open(path)?;
+    let len = file.metadata()?.len();
+    let start = len.saturating_sub(max_bytes);
+    // Read from the byte before the start to tell whether the first line is complete
+    file.seek(SeekFrom::Start(start.saturating_sub(1)))?;
+
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes)?;
+    let bytes = if start > 0 {
+        bytes
+            .iter()
+            .position(|&byte| byte == b'\n')
+            .map_or(&[][..], |newline| &bytes[newline + 1..])
+    } else {
+        &bytes[..]
+    };
+    Ok(String::from_utf8_lossy(bytes).lines().map(str::to_string).collect())
+}
+
+/// A log file that is rotated once it grows past a size limit.
+///
+/// On rotation the current file becomes `<name>.1`, older files move up by one and files
+/// beyond the retention count are removed.
+#[derive(Debug)]
+pub struct RotatingLogFile {
+    path: PathBuf,
+    max_bytes: u64,
+    retention: usize,
+    file: File,
+    written: u64,
+}
+
+impl RotatingLogFile {
+    /// Opens `path` for appending, creating it and its folder if needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the folder cannot be created or the file cannot be opened.
+    pub fn open(path: PathBuf, max_bytes: u64, retention: usize) -> io::Result<Self> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let written = file.metadata()?.len();
+        Ok(Self {
+            path,
+            max_bytes,
+            retention,
+            file,
+            written,
+        })
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+        let rotated = |index: usize| {
+            let mut name = self.path.clone().into_os_string();
+            name.push(format!(".{index}"));
+            PathBuf::from(name)
+        };
+
+        if self.retention == 0 {
+            fs::remove_file(&self.path)?;
+        } else {
+            let oldest = rotated(self.retention);
+            if oldest.exists() {
+                fs::remove_file(&oldest)?;
+            }
+            for index in (1..self.retention).rev() {
+                let from = rotated(index);
+                if from.exists() {
+                    fs::rename(&from, rotated(index + 1))?;
+                }
+            }
+            fs::rename(&self.path, rotated(1))?;
+        }
+
+        self.file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        self.written = 0;
+        Ok(())
+    }
+
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Write for RotatingLogFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.written > 0 && self.written + buf.len() as u64 > self.max_bytes {
+            self.rotate()?;
+        }
+        let written = self.file.write(buf)?;
+        self.written += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_rotates_past_size_limit_and_keeps_retention() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("logs").join(LOG_FILE_NAME);
+        let mut log = RotatingLogFile::open(path.clone(), 10, 2).unwrap();
+
+        for line in ["first\n", "second\n", "third\n", "fourth\n"] {
+            log.write_all(line.as_bytes()).unwrap();
+        }
+        log.flush().unwrap();
+
+        let read = |suffix: &str| fs::read_to_string(format!("{}{suffix}", path.display())).unwrap();
+        assert_eq!(read(""), "fourth\n");
+        assert_eq!(read(".1"), "third\n");
+        assert_eq!(read(".2"), "second\n");
+        assert!(!temp_dir.path().join("logs").join(format!("{LOG_FILE_NAME}.3")).exists());
+    }
+
+    #[test]
+    fn test_read_log_tail_skips_cut_off_line() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(LOG_FILE_NAME);
+        fs::write(&path, "first line\nsecond line\nthird\n").unwrap();
+
+        assert_eq!(
+            read_log_tail(&path, 1024).unwrap(),
+            ["first line", "second line", "third"]
+        );
+        assert_eq!(read_log_tail(&path, 18).unwrap(), ["second line", "third"]);
+        assert_eq!(read_log_tail(&path, 14).unwrap(), ["third"]);
+    }
+
+    #[test]
+    fn test_open_appends_to_existing_log() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(LOG_FILE_NAME);
+        fs::write(&path, "earlier session\n").unwrap();
+
+        let mut log = RotatingLogFile::open(path.clone(), MAX_LOG_BYTES, LOG_RETENTION).unwrap();
+        log.write_all(b"new session\n").unwrap();
+        assert_eq!(log.path(), path);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "earlier session\nnew session\n");
+    }
+}
@@ -4,7 +4,7 @@ use visualvault_models::FileType;
 #[allow(clippy::expect_used)]
 // Original media extensions for backward compatibility
 pub static MEDIA_EXTENSIONS: std::sync::LazyLock<Regex> = std::sync::LazyLock::new(|| {
-    Regex::new(r"(?i)\.(jpg|jpeg|png|gif|bmp|webp|svg|ico|tiff?|raw|cr2|nef|arw|dng|orf|rw2|pef|sr2|mp4|avi|mkv|mov|wmv|flv|webm|m4v|mpg|mpeg|3gp|3g2|mts|m2ts|vob|ogv|heic|heif)$").expect("Failed to compile MEDIA_EXTENSIONS regex")
+    Regex::new(r"(?i)\.(jpg|jpeg|png|gif|bmp|webp|svg|ico|tiff?|raw|cr2|nef|arw|dng|orf|rw2|pef|sr2|mp4|avi|mkv|mov|wmv|flv|webm|m4v|mpg|mpeg|3gp|3g2|mts|m2ts|vob|ogv|heic|heif|mp3|wav|flac|aac|ogg|oga|wma|m4a|opus|aiff?|ape)$").expect("Failed to compile MEDIA_EXTENSIONS regex")
 });
 
 #[must_use]
@@ -18,6 +18,11 @@ pub fn determine_file_type(extension: &str) -> FileType {
         "mp4" | "avi" | "mkv" | "mov" | "wmv" | "flv" | "webm" | "m4v" | "mpg" | "mpeg" | "3gp" | "3g2" | "mts"
         | "m2ts" | "vob" | "ogv" => FileType::Video,
 
+        // Audio
+        "mp3" | "wav" | "flac" | "aac" | "ogg" | "oga" | "wma" | "m4a" | "opus" | "aiff" | "aif" | "ape" => {
+            FileType::Audio
+        }
+
         // Documents
         "pdf" | "doc" | "docx" | "xls" | "xlsx" | "ppt" | "pptx" | "txt" | "odt" | "ods" | "odp" | "rtf" | "tex"
         | "md" | "csv" | "html" | "htm" | "xml" | "json" => FileType::Document,
@@ -62,6 +67,19 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_determine_file_type_audio() {
+        let audio_extensions = vec!["mp3", "wav", "flac", "aac", "ogg", "wma", "m4a", "opus", "aiff"];
+
+        for ext in audio_extensions {
+            assert_eq!(
+                determine_file_type(ext),
+                FileType::Audio,
+                "Extension '{ext}' should be identified as Audio"
+            );
+        }
+    }
+
     #[test]
     fn test_determine_file_type_documents() {
         // Test all document extensions
@@ -81,8 +99,7 @@ mod tests {
         // Test extensions that should be classified as Other
         let other_extensions = vec![
             "exe", "zip", "rar", "7z", "tar", "gz", "iso", "dmg", "pkg", "deb", "rpm", "msi", "app", "js", "py", "rs",
-            "go", "java", "cpp", "c", "css", "wma", // Audio files (not in our media types)
-            "",    // Empty string
+            "go", "java", "cpp", "c", "css", "", // Empty string
             "unknown", "xyz", "abc", "123",
         ];
 
@@ -160,8 +177,8 @@ mod tests {
             ("animation.gif", true),
             ("video.mp4", true),
             ("MOVIE.AVI", true),
-            ("song.mp3", false),
-            ("audio.wav", false),
+            ("song.mp3", true),
+            ("AUDIO.FLAC", true),
             ("document.pdf", false), // PDF not in MEDIA_EXTENSIONS
             ("file.txt", false),
             ("archive.zip", false),
@@ -181,7 +198,7 @@ mod tests {
     #[test]
     fn test_all_determine_file_type_extensions_consistency() {
         // Ensure all extensions in determine_file_type for Image and Video
-        // are covered by MEDIA_EXTENSIONS regex (except for audio which seems intentional)
+        // are covered by MEDIA_EXTENSIONS regex
 
         let image_extensions = vec![
             "jpg", "jpeg", "png", "gif", "bmp", "webp", "tiff", "heic", "raw", "heif",
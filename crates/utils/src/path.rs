@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use color_eyre::Result;
 
@@ -25,3 +25,184 @@ pub async fn create_cache_path(app_name: &str, filename: &str) -> Result<PathBuf
     tokio::fs::create_dir_all(cache_path).await?;
     Ok(cache_dir)
 }
+
+/// Paths at least this long get the extended-length prefix on Windows.
+///
+/// `MAX_PATH` is 260, but directories must leave room for an 8.3 file name, so
+/// `CreateDirectory` already fails at 248.
+const LONG_PATH_THRESHOLD: usize = 248;
+
+/// Longest file or folder name most file systems accept, in bytes
+const MAX_COMPONENT_LEN: usize = 255;
+
+/// Extensions up to this length are kept when a long name is shortened
+const MAX_KEPT_EXTENSION_LEN: usize = 16;
+
+/// Device names Windows reserves in every directory, with or without an extension
+const RESERVED_NAMES: &[&str] = &[
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8", "COM9", "LPT1", "LPT2",
+    "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+/// Makes a file or folder name built from a template safe to create on any platform.
+///
+/// Characters that Windows does not allow are replaced with underscores, surrounding
+/// whitespace and dots are trimmed, reserved device names such as `CON` or `nul.txt` get an
+/// underscore appended to the stem, and names longer than 255 bytes are shortened while
+/// keeping the extension. An empty string is returned if nothing usable remains.
+#[must_use]
+pub fn sanitize_path_component(value: &str) -> String {
+    let replaced: String = value
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect();
+    let trimmed = replaced.trim().trim_matches('.').trim();
+
+    let (stem, extension) = trimmed.split_once('.').unwrap_or((trimmed, ""));
+    let name = if is_reserved_name(stem) {
+        if extension.is_empty() {
+            format!("{stem}_")
+        } else {
+            format!("{stem}_.{extension}")
+        }
+    } else {
+        trimmed.to_string()
+    };
+
+    truncate_component(name)
+}
+
+fn is_reserved_name(stem: &str) -> bool {
+    let stem = stem.trim_end();
+    RESERVED_NAMES
+        .iter()
+        .any(|reserved| reserved.eq_ignore_ascii_case(stem))
+}
+
+fn truncate_component(name: String) -> String {
+    if name.len() <= MAX_COMPONENT_LEN {
+        return name;
+    }
+
+    let extension = name
+        .rfind('.')
+        .map(|idx| &name[idx..])
+        .filter(|ext| ext.len() <= MAX_KEPT_EXTENSION_LEN)
+        .unwrap_or("");
+    let mut end = MAX_COMPONENT_LEN - extension.len();
+    while !name.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}{extension}", name[..end].trim_end())
+}
+
+/// Returns a path Windows can open even when it is longer than `MAX_PATH`.
+///
+/// On Windows, long absolute paths get the `\\?\` prefix (`\\?\UNC\` for network shares),
+/// with separators and `.`/`..` components normalized as the prefix requires. Short,
+/// relative and already prefixed paths, and all paths on other platforms, are returned as is.
+#[must_use]
+pub fn extended_length_path(path: &Path) -> PathBuf {
+    if cfg!(windows) {
+        path.to_str()
+            .and_then(add_extended_prefix)
+            .map_or_else(|| path.to_path_buf(), PathBuf::from)
+    } else {
+        path.to_path_buf()
+    }
+}
+
+fn add_extended_prefix(path: &str) -> Option<String> {
+    if path.len() < LONG_PATH_THRESHOLD || path.starts_with(r"\\?\") {
+        return None;
+    }
+
+    let path = path.replace('/', r"\");
+    let (prefix, rest) = if let Some(share) = path.strip_prefix(r"\\") {
+        (r"\\?\UNC\", share)
+    } else if path.as_bytes().get(1) == Some(&b':') && path.as_bytes()[0].is_ascii_alphabetic() {
+        (r"\\?\", path.as_str())
+    } else {
+        return None;
+    };
+
+    let mut components: Vec<&str> = Vec::new();
+    for component in rest.split('\\') {
+        match component {
+            "" | "." => {}
+            ".." => {
+                // Never pop the drive or the server and share names
+                let root_len = if prefix.ends_with("UNC\\") { 2 } else { 1 };
+                if components.len() > root_len {
+                    components.pop();
+                }
+            }
+            component => components.push(component),
+        }
+    }
+
+    Some(format!("{prefix}{}", components.join(r"\")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_replaces_invalid_characters() {
+        assert_eq!(sanitize_path_component("AC/DC: Live?"), "AC_DC_ Live_");
+        assert_eq!(sanitize_path_component("  ..album.. "), "album");
+        assert_eq!(sanitize_path_component("back\\slash\tand tab"), "back_slash_and tab");
+        assert_eq!(sanitize_path_component(" . "), "");
+    }
+
+    #[test]
+    fn test_sanitize_renames_reserved_names() {
+        assert_eq!(sanitize_path_component("CON"), "CON_");
+        assert_eq!(sanitize_path_component("nul.txt"), "nul_.txt");
+        assert_eq!(sanitize_path_component("Com1.tar.gz"), "Com1_.tar.gz");
+        assert_eq!(sanitize_path_component("CONSOLE"), "CONSOLE");
+        assert_eq!(sanitize_path_component("LPT10"), "LPT10");
+    }
+
+    #[test]
+    fn test_sanitize_shortens_long_names_keeping_extension() {
+        let name = format!("{}.jpg", "ä".repeat(200));
+        let sanitized = sanitize_path_component(&name);
+        assert!(sanitized.len() <= MAX_COMPONENT_LEN);
+        assert!(sanitized.ends_with("ä.jpg"));
+
+        let folder = "x".repeat(300);
+        assert_eq!(sanitize_path_component(&folder).len(), MAX_COMPONENT_LEN);
+    }
+
+    #[test]
+    fn test_extended_prefix_for_long_paths() {
+        let long = format!(r"C:\Photos\{}\.\2024\..\2025", "a".repeat(250));
+        assert_eq!(
+            add_extended_prefix(&long),
+            Some(format!(r"\\?\C:\Photos\{}\2025", "a".repeat(250)))
+        );
+
+        let unc = format!("//server/share/{}/../b", "a".repeat(250));
+        assert_eq!(add_extended_prefix(&unc), Some(r"\\?\UNC\server\share\b".to_string()));
+    }
+
+    #[test]
+    fn test_extended_prefix_leaves_other_paths() {
+        assert_eq!(add_extended_prefix(r"C:\Photos\2024"), None);
+        assert_eq!(add_extended_prefix(&format!(r"\\?\C:\{}", "a".repeat(300))), None);
+        assert_eq!(add_extended_prefix(&format!(r"relative\{}", "a".repeat(300))), None);
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn test_extended_length_path_is_noop_off_windows() {
+        let path = PathBuf::from(format!("/photos/{}", "a".repeat(300)));
+        assert_eq!(extended_length_path(&path), path);
+    }
+}
@@ -1,4 +1,42 @@
-use std::time::Instant;
+use std::time::{Duration, Instant};
+
+/// Weight of the newest sample in the smoothed rates
+const RATE_SMOOTHING: f64 = 0.3;
+/// Minimum time between rate samples, so bursts of small files don't swing the rates
+const SAMPLE_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Stage that walks the source folders looking for media files
+pub const STAGE_WALK: &str = "Walking folders";
+/// Stage that classifies the files found and reads their metadata
+pub const STAGE_METADATA: &str = "Reading metadata";
+/// Stage that hashes files to find duplicates
+pub const STAGE_HASH: &str = "Hashing";
+/// Stage that moves or copies files into the library
+pub const STAGE_ORGANIZE: &str = "Organizing";
+/// Stage that moves organized files to a remote destination
+pub const STAGE_TRANSFER: &str = "Transferring";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StageStatus {
+    Pending,
+    Running,
+    Done,
+}
+
+/// One stage of a multi-stage operation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Stage {
+    pub name: &'static str,
+    pub status: StageStatus,
+    /// Items the stage handled, known once it is done
+    pub processed: usize,
+    /// Bytes the stage handled, known once it is done and 0 when it does not count them
+    pub bytes: u64,
+    /// When the stage started running
+    pub started_at: Option<Instant>,
+    /// How long the stage ran, known once it is done
+    pub elapsed: Duration,
+}
 
 #[derive(Debug, Clone)]
 pub struct Progress {
@@ -7,6 +45,22 @@ pub struct Progress {
     pub message: String,
     pub started_at: Instant,
     pub is_complete: bool,
+    /// Bytes of the files handled so far in the current phase
+    pub bytes_processed: u64,
+    /// Bytes of all files in the current phase, or 0 when unknown
+    pub total_bytes: u64,
+    /// Name of the file handled last
+    pub current_file: Option<String>,
+    /// Smoothed files per second
+    pub items_per_sec: Option<f64>,
+    /// Smoothed bytes per second
+    pub bytes_per_sec: Option<f64>,
+    /// Stages of the operation in order, empty for single-stage operations
+    pub stages: Vec<Stage>,
+    /// How many files are handled at once and why, when the operation chose that itself
+    pub parallelism: Option<String>,
+    /// Time, item count and byte count of the last rate sample
+    last_sample: Option<(Instant, usize, u64)>,
 }
 
 impl Default for Progress {
@@ -17,6 +71,14 @@ impl Default for Progress {
             message: String::new(),
             started_at: Instant::now(),
             is_complete: false,
+            bytes_processed: 0,
+            total_bytes: 0,
+            current_file: None,
+            items_per_sec: None,
+            bytes_per_sec: None,
+            stages: Vec::new(),
+            parallelism: None,
+            last_sample: None,
         }
     }
 }
@@ -28,11 +90,55 @@ impl Progress {
     }
 
     pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Starts a new phase of the operation, such as processing files after discovering them.
+    ///
+    /// Counters and rates start over while the elapsed time keeps running.
+    pub fn start_phase(&mut self, total: usize, total_bytes: u64, message: impl Into<String>) {
         self.current = 0;
-        self.total = 0;
-        self.message.clear();
-        self.started_at = Instant::now();
-        self.is_complete = false;
+        self.total = total;
+        self.total_bytes = total_bytes;
+        self.bytes_processed = 0;
+        self.current_file = None;
+        self.items_per_sec = None;
+        self.bytes_per_sec = None;
+        self.last_sample = Some((Instant::now(), 0, 0));
+        self.message = message.into();
+    }
+
+    /// Records that `current` files with `bytes_processed` bytes in total are done, the last one
+    /// being `file_name`, and updates the smoothed rates.
+    pub fn set_file_progress(&mut self, current: usize, bytes_processed: u64, file_name: impl Into<String>) {
+        self.current = current;
+        self.bytes_processed = bytes_processed;
+        self.current_file = Some(file_name.into());
+        self.update_rates(Instant::now());
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn update_rates(&mut self, now: Instant) {
+        let Some((last_time, last_current, last_bytes)) = self.last_sample else {
+            self.last_sample = Some((now, self.current, self.bytes_processed));
+            return;
+        };
+
+        let interval = now.saturating_duration_since(last_time);
+        if interval < SAMPLE_INTERVAL {
+            return;
+        }
+
+        let secs = interval.as_secs_f64();
+        let items = self.current.saturating_sub(last_current) as f64 / secs;
+        let bytes = self.bytes_processed.saturating_sub(last_bytes) as f64 / secs;
+        let smooth = |previous: Option<f64>, sample: f64| {
+            Some(previous.map_or(sample, |prev| RATE_SMOOTHING.mul_add(sample - prev, prev)))
+        };
+
+        self.items_per_sec = smooth(self.items_per_sec, items);
+        self.bytes_per_sec = smooth(self.bytes_per_sec, bytes);
+        self.last_sample = Some((now, self.current, self.bytes_processed));
     }
 
     #[allow(dead_code)]
@@ -67,7 +173,21 @@ impl Progress {
         self.started_at.elapsed()
     }
 
-    #[allow(clippy::missing_docs_in_private_items)]
+    /// Bytes per second, smoothed once enough samples were taken and averaged before that
+    #[allow(clippy::cast_precision_loss)]
+    #[must_use]
+    pub fn throughput(&self) -> Option<f64> {
+        if let Some(rate) = self.bytes_per_sec {
+            return Some(rate);
+        }
+        let elapsed = self.elapsed().as_secs_f64();
+        (self.bytes_processed > 0 && elapsed > 0.0).then(|| self.bytes_processed as f64 / elapsed)
+    }
+
+    /// Estimated time until the current phase finishes.
+    ///
+    /// Uses the remaining bytes when the total size is known, the smoothed file rate otherwise,
+    /// and the average rate since the start until rates have been sampled.
     #[allow(clippy::cast_precision_loss)]
     #[must_use]
     pub fn eta(&self) -> Option<std::time::Duration> {
@@ -80,6 +200,16 @@ impl Progress {
             return Some(std::time::Duration::from_secs(0));
         }
 
+        if let Some(rate) = self.bytes_per_sec.filter(|rate| *rate > 0.0 && self.total_bytes > 0) {
+            let remaining = self.total_bytes.saturating_sub(self.bytes_processed) as f64 / rate;
+            return Some(std::time::Duration::from_secs_f64(remaining));
+        }
+
+        if let Some(rate) = self.items_per_sec.filter(|rate| *rate > 0.0) {
+            let remaining = (self.total - self.current) as f64 / rate;
+            return Some(std::time::Duration::from_secs_f64(remaining));
+        }
+
         let elapsed = self.elapsed().as_secs_f64();
         let rate = self.current as f64 / elapsed;
 
@@ -92,9 +222,79 @@ impl Progress {
 
         Some(std::time::Duration::from_secs_f64(remaining))
     }
-}
 
-// ...existing code...
+    /// Adds the stages in `names` that are not known yet as pending, so views can show what
+    /// is still to come.
+    pub fn plan_stages(&mut self, names: &[&'static str]) {
+        for &name in names {
+            if !self.stages.iter().any(|stage| stage.name == name) {
+                self.stages.push(Stage {
+                    name,
+                    status: StageStatus::Pending,
+                    processed: 0,
+                    bytes: 0,
+                    started_at: None,
+                    elapsed: Duration::ZERO,
+                });
+            }
+        }
+    }
+
+    /// Finishes the running stage and starts `name` as a new phase, adding it if it was not
+    /// planned.
+    pub fn start_stage(&mut self, name: &'static str, total: usize, total_bytes: u64, message: impl Into<String>) {
+        self.finish_stages();
+        self.plan_stages(&[name]);
+        if let Some(stage) = self.stages.iter_mut().find(|stage| stage.name == name) {
+            stage.status = StageStatus::Running;
+            stage.processed = 0;
+            stage.bytes = 0;
+            stage.started_at = Some(Instant::now());
+        }
+        self.start_phase(total, total_bytes, message);
+    }
+
+    /// Marks the running stage as done, recording what it handled and how long it took
+    pub fn finish_stages(&mut self) {
+        let (current, bytes) = (self.current, self.bytes_processed);
+        for stage in &mut self.stages {
+            if stage.status == StageStatus::Running {
+                stage.status = StageStatus::Done;
+                stage.processed = current;
+                stage.bytes = bytes;
+                stage.elapsed = stage.started_at.map_or(Duration::ZERO, |started| started.elapsed());
+            }
+        }
+    }
+
+    #[must_use]
+    pub fn current_stage(&self) -> Option<&Stage> {
+        self.stages.iter().find(|stage| stage.status == StageStatus::Running)
+    }
+
+    /// Completed share of `stage` between 0 and 1; a running stage without a total reports 0
+    #[allow(clippy::cast_precision_loss)]
+    #[must_use]
+    pub fn stage_ratio(&self, stage: &Stage) -> f64 {
+        match stage.status {
+            StageStatus::Pending => 0.0,
+            StageStatus::Done => 1.0,
+            StageStatus::Running if self.total == 0 => 0.0,
+            StageStatus::Running => (self.current as f64 / self.total as f64).min(1.0),
+        }
+    }
+
+    /// Completed share of the whole operation between 0 and 1, counting every stage equally
+    #[allow(clippy::cast_precision_loss)]
+    #[must_use]
+    pub fn overall_ratio(&self) -> f64 {
+        if self.stages.is_empty() {
+            return (self.percentage() / 100.0).clamp(0.0, 1.0);
+        }
+        let done: f64 = self.stages.iter().map(|stage| self.stage_ratio(stage)).sum();
+        done / self.stages.len() as f64
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -323,6 +523,65 @@ mod tests {
         assert!(eta_ms < 50, "Fast progress should have low ETA: {eta_ms} ms");
     }
 
+    #[test]
+    fn test_start_phase_resets_counters_but_not_elapsed() {
+        let mut progress = Progress::new();
+        progress.started_at = Instant::now().checked_sub(Duration::from_secs(5)).unwrap();
+        progress.set_file_progress(40, 4000, "a.jpg");
+
+        progress.start_phase(10, 1000, "Organizing files...");
+
+        assert_eq!(progress.current, 0);
+        assert_eq!(progress.total, 10);
+        assert_eq!(progress.bytes_processed, 0);
+        assert_eq!(progress.total_bytes, 1000);
+        assert!(progress.current_file.is_none());
+        assert!(progress.items_per_sec.is_none());
+        assert!(progress.elapsed() >= Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_rates_are_smoothed() {
+        let mut progress = Progress::new();
+        let start = Instant::now();
+        progress.last_sample = Some((start, 0, 0));
+
+        progress.current = 10;
+        progress.bytes_processed = 10_000_000;
+        progress.update_rates(start + Duration::from_secs(1));
+        assert_eq!(progress.items_per_sec, Some(10.0));
+        assert_eq!(progress.bytes_per_sec, Some(10_000_000.0));
+
+        // A burst too soon after the last sample is not counted yet
+        progress.current = 100;
+        progress.update_rates(start + Duration::from_millis(1100));
+        assert_eq!(progress.items_per_sec, Some(10.0));
+
+        // 30 files in the next second move the rate only part of the way
+        progress.current = 40;
+        progress.bytes_processed = 40_000_000;
+        progress.update_rates(start + Duration::from_secs(2));
+        let items = progress.items_per_sec.unwrap();
+        assert!((items - 16.0).abs() < 1e-9, "got {items}");
+        assert_eq!(progress.throughput(), progress.bytes_per_sec);
+    }
+
+    #[test]
+    fn test_eta_prefers_remaining_bytes() {
+        let mut progress = Progress::new();
+        progress.start_phase(10, 100_000_000, "Organizing files...");
+        progress.current = 5;
+        progress.bytes_processed = 90_000_000;
+        progress.bytes_per_sec = Some(5_000_000.0);
+        progress.items_per_sec = Some(0.1);
+
+        // Half the files but only 10 MB left, at 5 MB/s
+        assert_eq!(progress.eta(), Some(Duration::from_secs(2)));
+
+        progress.total_bytes = 0;
+        assert_eq!(progress.eta(), Some(Duration::from_secs(50)));
+    }
+
     #[test]
     fn test_clone() {
         let mut progress = Progress::new();
@@ -406,4 +665,50 @@ mod tests {
         assert_eq!(counter.load(Ordering::SeqCst), 100);
         drop(final_progress);
     }
+
+    #[test]
+    fn test_stages_advance_in_order() {
+        let mut progress = Progress::new();
+        progress.plan_stages(&[STAGE_WALK, STAGE_METADATA]);
+        assert_eq!(progress.overall_ratio(), 0.0);
+        assert!(progress.current_stage().is_none());
+
+        progress.start_stage(STAGE_WALK, 0, 0, "Discovering files...");
+        progress.current = 40;
+        assert_eq!(progress.current_stage().unwrap().name, STAGE_WALK);
+        assert_eq!(progress.stage_ratio(&progress.stages[0]), 0.0);
+
+        progress.start_stage(STAGE_METADATA, 40, 0, "Processing files...");
+        assert_eq!(progress.stages[0].status, StageStatus::Done);
+        assert_eq!(progress.stages[0].processed, 40);
+        progress.current = 10;
+        assert_eq!(progress.stage_ratio(&progress.stages[1]), 0.25);
+        assert_eq!(progress.overall_ratio(), 0.625);
+
+        // Stages that were not planned are added at the end
+        progress.start_stage(STAGE_HASH, 4, 0, "Detecting duplicates...");
+        assert_eq!(progress.stages.len(), 3);
+        assert_eq!(progress.stages[1].processed, 10);
+        progress.current = 4;
+        progress.bytes_processed = 4096;
+        thread::sleep(Duration::from_millis(5));
+        progress.finish_stages();
+        assert!(progress.stages.iter().all(|stage| stage.status == StageStatus::Done));
+        assert_eq!(progress.stages[2].bytes, 4096);
+        assert!(progress.stages[2].elapsed >= Duration::from_millis(5));
+        assert_eq!(progress.overall_ratio(), 1.0);
+
+        progress.reset();
+        assert!(progress.stages.is_empty());
+    }
+
+    #[test]
+    fn test_overall_ratio_without_stages() {
+        let mut progress = Progress::new();
+        progress.set_total(4);
+        progress.set_current(1);
+        assert_eq!(progress.overall_ratio(), 0.25);
+        progress.set_current(8);
+        assert_eq!(progress.overall_ratio(), 1.0);
+    }
 }
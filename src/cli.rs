@@ -0,0 +1,194 @@
+//! Command line arguments for running `VisualVault` without the terminal UI.
+
+use std::ffi::OsString;
+use std::path::PathBuf;
+
+use clap::{Arg, ArgAction, ArgMatches, Command, value_parser};
+use visualvault_lib::TimezonePolicy;
+
+/// Operation run by a headless invocation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeadlessCommand {
+    Scan,
+    Organize,
+    Duplicates,
+}
+
+impl HeadlessCommand {
+    /// Name of the subcommand that runs this operation
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Scan => "scan",
+            Self::Organize => "organize",
+            Self::Duplicates => "duplicates",
+        }
+    }
+}
+
+/// Options of a headless invocation, taken from the command line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeadlessOptions {
+    pub command: HeadlessCommand,
+    /// Settings profile to use instead of the active one
+    pub profile: Option<String>,
+    /// Overrides the source folder of the settings
+    pub source: Option<PathBuf>,
+    /// Overrides the destination folder of the settings
+    pub destination: Option<PathBuf>,
+    /// Overrides the time zone policy of the settings for this run
+    pub timezone: Option<TimezonePolicy>,
+    /// Emits progress and the result as JSON lines instead of plain text
+    pub json: bool,
+    /// Leaves out progress, and in plain mode the summary as well
+    pub quiet: bool,
+}
+
+/// Log levels accepted by `--log-level` and the `VISUALVAULT_LOG_LEVEL` variable
+pub const LOG_LEVELS: [&str; 5] = ["error", "warn", "info", "debug", "trace"];
+
+/// Arguments of any invocation, headless or not
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cli {
+    /// The headless command to run, or `None` for the terminal UI
+    pub headless: Option<HeadlessOptions>,
+    /// Overrides the level `VisualVault` logs at
+    pub log_level: Option<String>,
+}
+
+/// Builds the command line definition.
+#[must_use]
+pub fn command() -> Command {
+    let source = Arg::new("source")
+        .long("source")
+        .short('s')
+        .value_name("DIR")
+        .value_parser(value_parser!(PathBuf))
+        .help("Folder to scan instead of the configured source folder");
+
+    Command::new("visualvault")
+        .version(env!("CARGO_PKG_VERSION"))
+        .about("A terminal-based media file organizer. Runs the interactive UI when no command is given.")
+        .arg(
+            Arg::new("profile")
+                .long("profile")
+                .short('p')
+                .value_name("NAME")
+                .global(true)
+                .help("Settings profile to use instead of the active one"),
+        )
+        .arg(
+            Arg::new("json")
+                .long("json")
+                .action(ArgAction::SetTrue)
+                .global(true)
+                .help("Print progress and the result as newline-delimited JSON events"),
+        )
+        .arg(
+            Arg::new("log-level")
+                .long("log-level")
+                .value_name("LEVEL")
+                .value_parser(LOG_LEVELS)
+                .global(true)
+                .help("Log at this level instead of debug; also read from VISUALVAULT_LOG_LEVEL"),
+        )
+        .arg(
+            Arg::new("quiet")
+                .long("quiet")
+                .short('q')
+                .action(ArgAction::SetTrue)
+                .global(true)
+                .help("Only report errors and, with --json, the final result"),
+        )
+        .subcommand(
+            Command::new("scan")
+                .about("Scan the source folder and summarize the media files found")
+                .arg(source.clone()),
+        )
+        .subcommand(
+            Command::new("organize")
+                .about("Scan the source folder and organize its files into the destination folder")
+                .arg(source.clone())
+                .arg(
+                    Arg::new("destination")
+                        .long("destination")
+                        .short('d')
+                        .value_name("DIR")
+                        .value_parser(value_parser!(PathBuf))
+                        .help("Folder to organize into instead of the configured destination folder"),
+                )
+                .arg(
+                    Arg::new("timezone")
+                        .long("timezone")
+                        .value_name("POLICY")
+                        .value_parser(|value: &str| value.parse::<TimezonePolicy>())
+                        .help("Pick date folders in local time, by each photo's EXIF offset (exif) or at an offset such as +09:00"),
+                ),
+        )
+        .subcommand(
+            Command::new("duplicates")
+                .about("Scan the source folder and list groups of identical files")
+                .arg(source),
+        )
+}
+
+/// Parses the command line.
+///
+/// Returns `None` when no command is given and the terminal UI should start.
+///
+/// # Errors
+///
+/// Returns a clap error for invalid arguments and for `--help` and `--version`, which
+/// callers should report with [`clap::Error::exit`].
+pub fn parse<I, T>(args: I) -> Result<Option<HeadlessOptions>, clap::Error>
+where
+    I: IntoIterator<Item = T>,
+    T: Into<OsString> + Clone,
+{
+    parse_args(args).map(|cli| cli.headless)
+}
+
+/// Parses the command line, including the options that apply to the terminal UI as well.
+///
+/// # Errors
+///
+/// Returns a clap error like [`parse`].
+pub fn parse_args<I, T>(args: I) -> Result<Cli, clap::Error>
+where
+    I: IntoIterator<Item = T>,
+    T: Into<OsString> + Clone,
+{
+    let matches = command().try_get_matches_from(args)?;
+    let log_level = matches.get_one::<String>("log-level").cloned();
+    let Some((name, sub)) = matches.subcommand() else {
+        return Ok(Cli {
+            headless: None,
+            log_level,
+        });
+    };
+    let log_level = sub.get_one::<String>("log-level").cloned().or(log_level);
+
+    let command = match name {
+        "scan" => HeadlessCommand::Scan,
+        "organize" => HeadlessCommand::Organize,
+        _ => HeadlessCommand::Duplicates,
+    };
+    let headless = HeadlessOptions {
+        command,
+        profile: sub.get_one::<String>("profile").cloned(),
+        source: sub.get_one::<PathBuf>("source").cloned(),
+        destination: optional_path(sub, "destination"),
+        timezone: sub.try_get_one::<TimezonePolicy>("timezone").ok().flatten().copied(),
+        json: sub.get_flag("json"),
+        quiet: sub.get_flag("quiet"),
+    };
+    Ok(Cli {
+        headless: Some(headless),
+        log_level,
+    })
+}
+
+/// Reads a path argument that only some subcommands define
+fn optional_path(matches: &ArgMatches, id: &str) -> Option<PathBuf> {
+    matches.try_get_one::<PathBuf>(id).ok().flatten().cloned()
+}
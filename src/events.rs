@@ -0,0 +1,202 @@
+//! Progress and result reporting for headless runs.
+//!
+//! With `--json`, every report is one JSON object per line on stdout, tagged by its `event`
+//! field, so wrappers can follow a run as it happens. Otherwise progress goes to stderr and
+//! the final summary to stdout as plain text.
+
+use std::io::Write;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::Serialize;
+use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
+use visualvault_lib::{FileType, MediaFile, Progress};
+
+use crate::headless::Summary;
+
+/// How often running operations report their progress
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+
+/// A report emitted while a headless command runs
+#[derive(Debug, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Event<'a> {
+    ScanStarted {
+        command: &'static str,
+        source: &'a Path,
+    },
+    ScanProgress {
+        current: usize,
+        total: usize,
+        message: &'a str,
+    },
+    FileFound {
+        path: &'a Path,
+        file_type: &'a FileType,
+        size: u64,
+    },
+    OrganizeStarted {
+        destination: &'a Path,
+        files: usize,
+    },
+    OrganizeProgress {
+        current: usize,
+        total: usize,
+        bytes_processed: u64,
+        total_bytes: u64,
+        file: Option<&'a str>,
+    },
+    UploadProgress {
+        current: usize,
+        total: usize,
+        bytes_processed: u64,
+        total_bytes: u64,
+        file: Option<&'a str>,
+    },
+    TransferProgress {
+        current: usize,
+        total: usize,
+        bytes_processed: u64,
+        total_bytes: u64,
+        file: Option<&'a str>,
+    },
+    Done {
+        exit_code: u8,
+        result: &'a Summary,
+    },
+    Error {
+        message: String,
+    },
+}
+
+impl Event<'_> {
+    /// Whether the event ends the run, so it is emitted even with `--quiet`
+    const fn is_final(&self) -> bool {
+        matches!(self, Self::Done { .. } | Self::Error { .. })
+    }
+}
+
+/// Operation whose progress is being reported
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    Scan,
+    Organize,
+    Upload,
+    Transfer,
+}
+
+/// Writes the events of a headless run in the format chosen on the command line
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Reporter {
+    json: bool,
+    quiet: bool,
+}
+
+impl Reporter {
+    #[must_use]
+    pub const fn new(json: bool, quiet: bool) -> Self {
+        Self { json, quiet }
+    }
+
+    /// Emits `event` as a JSON line, unless it is progress and the reporter is quiet.
+    ///
+    /// Plain output only prints the final summary and errors; see [`Reporter::progress`].
+    pub fn emit(&self, event: &Event<'_>) {
+        if !self.json {
+            match event {
+                Event::Done { result, .. } if !self.quiet => println!("{}", result.to_plain()),
+                Event::Error { message } => eprintln!("Error: {message}"),
+                _ => {}
+            }
+            return;
+        }
+        if self.quiet && !event.is_final() {
+            return;
+        }
+        if let Ok(line) = serde_json::to_string(event) {
+            let mut stdout = std::io::stdout().lock();
+            // A closed pipe only means nobody is listening anymore
+            let _ = writeln!(stdout, "{line}").and_then(|()| stdout.flush());
+        }
+    }
+
+    /// Emits one `file_found` event per file
+    pub fn files_found(&self, files: &[Arc<MediaFile>]) {
+        if !self.json || self.quiet {
+            return;
+        }
+        for file in files {
+            self.emit(&Event::FileFound {
+                path: &file.path,
+                file_type: &file.file_type,
+                size: file.size,
+            });
+        }
+    }
+
+    /// Reports a progress snapshot, as an event or as a line on stderr
+    pub fn progress(&self, phase: Phase, progress: &Progress) {
+        if self.quiet {
+            return;
+        }
+        if self.json {
+            let event = match phase {
+                Phase::Scan => Event::ScanProgress {
+                    current: progress.current,
+                    total: progress.total,
+                    message: &progress.message,
+                },
+                Phase::Organize => Event::OrganizeProgress {
+                    current: progress.current,
+                    total: progress.total,
+                    bytes_processed: progress.bytes_processed,
+                    total_bytes: progress.total_bytes,
+                    file: progress.current_file.as_deref(),
+                },
+                Phase::Upload => Event::UploadProgress {
+                    current: progress.current,
+                    total: progress.total,
+                    bytes_processed: progress.bytes_processed,
+                    total_bytes: progress.total_bytes,
+                    file: progress.current_file.as_deref(),
+                },
+                Phase::Transfer => Event::TransferProgress {
+                    current: progress.current,
+                    total: progress.total,
+                    bytes_processed: progress.bytes_processed,
+                    total_bytes: progress.total_bytes,
+                    file: progress.current_file.as_deref(),
+                },
+            };
+            self.emit(&event);
+        } else if progress.total > 0 {
+            eprintln!("{} {}/{}", progress.message, progress.current, progress.total);
+        }
+    }
+
+    /// Reports `progress` periodically until the returned task is aborted.
+    ///
+    /// Snapshots are only reported when something changed since the last one.
+    #[must_use]
+    pub fn watch(self, phase: Phase, progress: Arc<RwLock<Progress>>) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut last = None;
+            loop {
+                tokio::time::sleep(PROGRESS_INTERVAL).await;
+                let snapshot = progress.read().await.clone();
+                // Plain output only notes each new step, so logs of large runs stay short
+                let key = if self.json {
+                    (snapshot.current, snapshot.total, snapshot.message.clone())
+                } else {
+                    (0, 0, snapshot.message.clone())
+                };
+                if last.as_ref() != Some(&key) {
+                    self.progress(phase, &snapshot);
+                    last = Some(key);
+                }
+            }
+        })
+    }
+}
@@ -0,0 +1,629 @@
+//! Runs scans, organizing and duplicate detection without the terminal UI.
+//!
+//! Progress and results are reported through [`Reporter`], and the process exit code tells
+//! scripts whether the run succeeded.
+
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use color_eyre::eyre::{Result, eyre};
+use serde::Serialize;
+use tokio::sync::RwLock;
+use tracing::{error, warn};
+use visualvault_lib::internals::core::{InstanceLock, InstanceLockError, RcloneTransfer, S3Uploader};
+use visualvault_lib::{
+    ActivityKind, ActivityLog, CloudExport, CloudUploadReport, DuplicateStats, Engine, FileType, MediaFile,
+    Notifications, NotifiedOperation, Notifier, OperationReport, OrganizeResult, ProfileStore, Progress,
+    RemoteTransfer, STAGE_TRANSFER, Settings, format_bytes, project_space,
+};
+
+use crate::cli::{HeadlessCommand, HeadlessOptions};
+use crate::events::{Event, Phase, Reporter};
+
+/// Exit code of a run that failed before completing
+pub const EXIT_FAILURE: u8 = 1;
+
+/// Exit code of an organize run that finished but left files behind, because they failed
+/// or the conflict policy asks the user about existing destination files
+pub const EXIT_INCOMPLETE: u8 = 3;
+
+/// Counts of the files found by a scan
+#[derive(Debug, Clone, Serialize)]
+pub struct ScanSummary {
+    pub source: PathBuf,
+    pub files: usize,
+    pub images: usize,
+    pub videos: usize,
+    pub audio: usize,
+    pub documents: usize,
+    pub other: usize,
+    pub total_bytes: u64,
+}
+
+impl ScanSummary {
+    fn new(source: &Path, files: &[Arc<MediaFile>]) -> Self {
+        let count = |file_type: FileType| files.iter().filter(|file| file.file_type == file_type).count();
+        Self {
+            source: source.to_path_buf(),
+            files: files.len(),
+            images: count(FileType::Image),
+            videos: count(FileType::Video),
+            audio: count(FileType::Audio),
+            documents: count(FileType::Document),
+            other: count(FileType::Other),
+            total_bytes: files.iter().map(|file| file.size).sum(),
+        }
+    }
+}
+
+/// Groups of identical files found by a scan
+#[derive(Debug, Clone, Serialize)]
+pub struct DuplicateSummary {
+    pub groups: usize,
+    pub duplicates: usize,
+    pub wasted_bytes: u64,
+    /// Files whose hash was reused from the cache, and files that had to be hashed
+    pub hash_cache_hits: usize,
+    pub hash_cache_misses: usize,
+    /// Paths of each group, oldest file first
+    pub files: Vec<Vec<PathBuf>>,
+}
+
+impl DuplicateSummary {
+    fn new(stats: &DuplicateStats) -> Self {
+        Self {
+            groups: stats.total_groups,
+            duplicates: stats.total_duplicates,
+            wasted_bytes: stats.total_wasted_space,
+            hash_cache_hits: stats.hash_cache_hits,
+            hash_cache_misses: stats.hash_cache_misses,
+            files: stats
+                .groups
+                .iter()
+                .map(|group| {
+                    let mut files: Vec<_> = group.files.iter().collect();
+                    files.sort_by_key(|file| file.modified);
+                    files.into_iter().map(|file| file.path.clone()).collect()
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Outcome of organizing the scanned files
+#[derive(Debug, Clone, Serialize)]
+pub struct OrganizeSummary {
+    pub destination: PathBuf,
+    pub organized: usize,
+    pub total: usize,
+    pub skipped_duplicates: usize,
+    /// Files left in the source by the skip rules
+    pub skipped_too_small: usize,
+    pub skipped_low_resolution: usize,
+    pub skipped_too_new: usize,
+    pub conflicts_skipped: usize,
+    pub conflicts_overwritten: usize,
+    pub conflicts_renamed: usize,
+    /// Conflicts left in place because the conflict policy asks the user, which needs the UI
+    pub conflicts_deferred: usize,
+    /// Whether the placed files were moved back because more files failed than `rollback_threshold` allows
+    pub rolled_back: bool,
+    pub errors: Vec<String>,
+    /// Upload of the placed files to the cloud export bucket, when one is configured
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub upload: Option<CloudUploadReport>,
+    /// Move of the placed files to the rclone remote named as destination
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remote_transfer: Option<RemoteTransfer>,
+}
+
+impl OrganizeSummary {
+    fn new(result: &OrganizeResult) -> Self {
+        Self {
+            destination: result.destination.clone(),
+            organized: result.files_organized,
+            total: result.files_total,
+            skipped_duplicates: result.skipped_duplicates,
+            skipped_too_small: result.skipped_by_rules.too_small,
+            skipped_low_resolution: result.skipped_by_rules.low_resolution,
+            skipped_too_new: result.skipped_by_rules.too_new,
+            conflicts_skipped: result.conflicts.skipped,
+            conflicts_overwritten: result.conflicts.overwritten,
+            conflicts_renamed: result.conflicts.renamed,
+            conflicts_deferred: result.conflicts.deferred,
+            rolled_back: result.rolled_back,
+            errors: result.errors.clone(),
+            upload: None,
+            remote_transfer: None,
+        }
+    }
+
+    fn is_complete(&self) -> bool {
+        self.errors.is_empty()
+            && self.conflicts_deferred == 0
+            && self.upload.as_ref().is_none_or(|upload| upload.failed.is_empty())
+            && self.remote_transfer.as_ref().is_none_or(RemoteTransfer::is_complete)
+    }
+}
+
+/// Result of a headless run
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "command", rename_all = "lowercase")]
+pub enum Summary {
+    Scan {
+        scan: ScanSummary,
+        duration_ms: u128,
+    },
+    Organize {
+        scan: ScanSummary,
+        organize: Box<OrganizeSummary>,
+        duration_ms: u128,
+    },
+    Duplicates {
+        scan: ScanSummary,
+        duplicates: DuplicateSummary,
+        duration_ms: u128,
+    },
+}
+
+impl Summary {
+    /// Exit status reporting this result to the calling process
+    #[must_use]
+    pub fn exit_status(&self) -> u8 {
+        match self {
+            Self::Organize { organize, .. } if !organize.is_complete() => EXIT_INCOMPLETE,
+            _ => 0,
+        }
+    }
+
+    /// Formats the result as human-readable lines
+    #[must_use]
+    pub fn to_plain(&self) -> String {
+        let (scan, duration_ms) = match self {
+            Self::Scan { scan, duration_ms }
+            | Self::Organize { scan, duration_ms, .. }
+            | Self::Duplicates { scan, duration_ms, .. } => (scan, duration_ms),
+        };
+
+        let mut lines = vec![format!(
+            "Scanned {}: {} files ({} images, {} videos, {} audio, {} documents, {} other), {}",
+            scan.source.display(),
+            scan.files,
+            scan.images,
+            scan.videos,
+            scan.audio,
+            scan.documents,
+            scan.other,
+            format_bytes(scan.total_bytes)
+        )];
+
+        match self {
+            Self::Scan { .. } => {}
+            Self::Organize { organize, .. } => {
+                lines.push(format!(
+                    "Organized {} of {} files into {}",
+                    organize.organized,
+                    organize.total,
+                    organize.destination.display()
+                ));
+                if organize.skipped_duplicates > 0 {
+                    lines.push(format!("Skipped {} duplicates", organize.skipped_duplicates));
+                }
+                if organize.skipped_too_small + organize.skipped_low_resolution + organize.skipped_too_new > 0 {
+                    lines.push(format!(
+                        "Skip rules: {} too small, {} low resolution, {} too new",
+                        organize.skipped_too_small, organize.skipped_low_resolution, organize.skipped_too_new
+                    ));
+                }
+                if organize.conflicts_skipped + organize.conflicts_overwritten + organize.conflicts_renamed > 0 {
+                    lines.push(format!(
+                        "Conflicts: {} skipped, {} overwritten, {} renamed",
+                        organize.conflicts_skipped, organize.conflicts_overwritten, organize.conflicts_renamed
+                    ));
+                }
+                if organize.conflicts_deferred > 0 {
+                    lines.push(format!(
+                        "{} conflicts left in place by the \"ask\" conflict policy, choose another policy for unattended runs",
+                        organize.conflicts_deferred
+                    ));
+                }
+                for error in &organize.errors {
+                    lines.push(format!("Error: {error}"));
+                }
+                if let Some(upload) = &organize.upload {
+                    lines.push(format!(
+                        "Uploaded {} of {} files ({}) to bucket {}",
+                        upload.uploaded,
+                        upload.total(),
+                        format_bytes(upload.bytes),
+                        upload.bucket
+                    ));
+                    for failure in &upload.failed {
+                        lines.push(format!("Upload failed: {}: {}", failure.path.display(), failure.error));
+                    }
+                }
+                if let Some(transfer) = &organize.remote_transfer {
+                    lines.push(format!(
+                        "Moved {} files ({}) to {} with rclone",
+                        transfer.transferred,
+                        format_bytes(transfer.bytes),
+                        transfer.remote
+                    ));
+                    lines.extend(transfer.errors.iter().map(|error| format!("rclone: {error}")));
+                    lines.push(transfer.undo_boundary());
+                }
+            }
+            Self::Duplicates { duplicates, .. } => {
+                lines.push(format!(
+                    "Found {} duplicates in {} groups, {} wasted",
+                    duplicates.duplicates,
+                    duplicates.groups,
+                    format_bytes(duplicates.wasted_bytes)
+                ));
+                let hashed = duplicates.hash_cache_hits + duplicates.hash_cache_misses;
+                if hashed > 0 {
+                    lines.push(format!(
+                        "Hashed {hashed} files, {} reused from the cache",
+                        duplicates.hash_cache_hits
+                    ));
+                }
+                for (i, group) in duplicates.files.iter().enumerate() {
+                    lines.push(format!("Group {}:", i + 1));
+                    lines.extend(group.iter().map(|path| format!("  {}", path.display())));
+                }
+            }
+        }
+
+        lines.push(format!("Finished in {:.1}s", seconds(*duration_ms)));
+        lines.join("\n")
+    }
+
+    /// Builds the report sent to the notification targets.
+    ///
+    /// Listing duplicates does not delete anything, so it is reported as a scan.
+    #[must_use]
+    pub fn to_report(&self) -> OperationReport {
+        let report = match self {
+            Self::Scan { scan, duration_ms } | Self::Duplicates { scan, duration_ms, .. } => {
+                OperationReport::new(NotifiedOperation::Scan, millis(*duration_ms))
+                    .with_count("files_found", scan.files as u64)
+                    .with_count("total_bytes", scan.total_bytes)
+            }
+            Self::Organize {
+                scan,
+                organize,
+                duration_ms,
+            } => OperationReport::new(NotifiedOperation::Organize, millis(*duration_ms))
+                .with_count("files_found", scan.files as u64)
+                .with_count("files_organized", organize.organized as u64)
+                .with_count("files_total", organize.total as u64)
+                .with_count("skipped_duplicates", organize.skipped_duplicates as u64)
+                .with_count(
+                    "skipped_by_rules",
+                    (organize.skipped_too_small + organize.skipped_low_resolution + organize.skipped_too_new) as u64,
+                )
+                .with_count("conflicts_deferred", organize.conflicts_deferred as u64)
+                .with_count(
+                    "files_uploaded",
+                    organize.upload.as_ref().map_or(0, |upload| upload.uploaded as u64),
+                )
+                .with_errors(organize.errors.clone()),
+        };
+        match self {
+            Self::Duplicates { duplicates, .. } => report
+                .with_count("duplicate_groups", duplicates.groups as u64)
+                .with_count("duplicates", duplicates.duplicates as u64)
+                .with_count("wasted_bytes", duplicates.wasted_bytes),
+            _ => report,
+        }
+    }
+}
+
+fn millis(duration_ms: u128) -> Duration {
+    Duration::from_millis(u64::try_from(duration_ms).unwrap_or(u64::MAX))
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn seconds(millis: u128) -> f64 {
+    millis as f64 / 1000.0
+}
+
+/// Runs a headless command with the user's settings and reports its progress and result.
+///
+/// Errors are reported as well and turned into the exit code.
+pub async fn run(options: HeadlessOptions) -> ExitCode {
+    let reporter = Reporter::new(options.json, options.quiet);
+    let start = Instant::now();
+    let settings = match load_settings(options.profile.as_deref()) {
+        Ok(settings) => settings,
+        Err(e) => {
+            reporter.emit(&Event::Error { message: e.to_string() });
+            return ExitCode::from(EXIT_FAILURE);
+        }
+    };
+    for diagnostic in settings.validate() {
+        warn!("Settings: {}", diagnostic);
+    }
+    let notifications = settings.notifications.clone();
+
+    let outcome = run_with_settings(&options, settings, reporter).await;
+    if notifications.is_enabled() {
+        let report = match &outcome {
+            Ok(summary) => summary.to_report(),
+            Err(e) => OperationReport::failed(notified_operation(options.command), start.elapsed(), e.to_string()),
+        };
+        notify(&notifications, &report).await;
+    }
+
+    match outcome {
+        Ok(summary) => {
+            let exit_code = summary.exit_status();
+            reporter.emit(&Event::Done {
+                exit_code,
+                result: &summary,
+            });
+            ExitCode::from(exit_code)
+        }
+        Err(e) => {
+            error!("Headless {:?} failed: {}", options.command, e);
+            reporter.emit(&Event::Error { message: e.to_string() });
+            ExitCode::from(EXIT_FAILURE)
+        }
+    }
+}
+
+async fn run_with_settings(options: &HeadlessOptions, settings: Settings, reporter: Reporter) -> Result<Summary> {
+    let config_dir = dirs::config_dir().ok_or_else(|| eyre!("Could not find config directory"))?;
+    let engine = Engine::open(config_dir).await?;
+
+    let summary = execute(options, settings, &engine, reporter).await?;
+    if let Summary::Organize { organize, .. } = &summary {
+        record_activity(engine.config_dir(), organize);
+    }
+    Ok(summary)
+}
+
+/// Loads the settings of `profile`, or of the active profile when none is given
+fn load_settings(profile: Option<&str>) -> Result<Settings> {
+    let store = ProfileStore::open()?;
+    let Some(profile) = profile else {
+        return store.load(&store.active());
+    };
+    if !store.list()?.iter().any(|name| name.eq_ignore_ascii_case(profile)) {
+        return Err(eyre!("Profile '{}' does not exist", profile));
+    }
+    store.load(profile)
+}
+
+/// Runs `options.command` with `settings`, after applying the folder overrides of `options`.
+///
+/// Progress is sent to `reporter` while the command runs, and organizing records its undo
+/// history in the config folder of `engine`.
+///
+/// # Errors
+///
+/// Returns an error if a required folder is not configured, the source folder cannot be
+/// scanned, or organizing fails.
+pub async fn execute(
+    options: &HeadlessOptions,
+    mut settings: Settings,
+    engine: &Engine,
+    reporter: Reporter,
+) -> Result<Summary> {
+    if let Some(source) = &options.source {
+        settings.source_folder = Some(source.clone());
+    }
+    if let Some(destination) = &options.destination {
+        settings.destination_folder = Some(destination.clone());
+    }
+    if let Some(timezone) = options.timezone {
+        settings.timezone_policy = timezone;
+    }
+    let source = settings
+        .source_folder
+        .clone()
+        .ok_or_else(|| eyre!("Source folder not configured, pass --source or set it in the UI"))?;
+    if options.command == HeadlessCommand::Organize && settings.destination_folder.is_none() {
+        return Err(eyre!(
+            "Destination folder not configured, pass --destination or set it in the UI"
+        ));
+    }
+    let _lock = lock_instance(options.command, engine.config_dir())?;
+
+    let start = Instant::now();
+    let progress = Arc::new(RwLock::new(Progress::new()));
+    reporter.emit(&Event::ScanStarted {
+        command: options.command.name(),
+        source: &source,
+    });
+
+    let watcher = reporter.watch(Phase::Scan, Arc::clone(&progress));
+    let scanned = if options.command == HeadlessCommand::Scan {
+        engine
+            .scan(&settings, Arc::clone(&progress))
+            .await
+            .map(|files| (files, DuplicateStats::new()))
+    } else {
+        engine.find_duplicates(&settings, Arc::clone(&progress)).await
+    };
+    watcher.abort();
+    let (files, duplicates) = scanned?;
+    reporter.files_found(&files);
+    let scan = ScanSummary::new(&source, &files);
+
+    match options.command {
+        HeadlessCommand::Scan => {
+            return Ok(Summary::Scan {
+                scan,
+                duration_ms: start.elapsed().as_millis(),
+            });
+        }
+        HeadlessCommand::Duplicates => {
+            return Ok(Summary::Duplicates {
+                scan,
+                duplicates: DuplicateSummary::new(&duplicates),
+                duration_ms: start.elapsed().as_millis(),
+            });
+        }
+        HeadlessCommand::Organize => {}
+    }
+
+    if let Some(destination) = &settings.local_destination() {
+        let projection = project_space(&files, destination);
+        if let Some(free) = projection.free.filter(|_| !projection.fits()) {
+            return Err(eyre!(
+                "Not enough space on the destination drive: {} needed for {} files from other drives, {} free",
+                format_bytes(projection.required),
+                projection.copied_files,
+                format_bytes(free)
+            ));
+        }
+        if projection.is_tight() {
+            warn!(
+                "Organizing leaves only {} free on the destination drive",
+                format_bytes(projection.remaining().unwrap_or_default())
+            );
+        }
+        reporter.emit(&Event::OrganizeStarted {
+            destination,
+            files: files.len(),
+        });
+    }
+    let watcher = reporter.watch(Phase::Organize, Arc::clone(&progress));
+    let result = engine
+        .organize(files, duplicates, &settings, Arc::clone(&progress))
+        .await;
+    watcher.abort();
+    let result = result?;
+    reporter.progress(Phase::Organize, &*progress.read().await);
+
+    Ok(Summary::Organize {
+        scan,
+        organize: Box::new(finish_organize(&settings, &result, &progress, reporter).await),
+        duration_ms: start.elapsed().as_millis(),
+    })
+}
+
+/// Summarizes an organize run, after moving its files to the rclone remote named as
+/// destination, or uploading them when a cloud export is configured
+async fn finish_organize(
+    settings: &Settings,
+    result: &OrganizeResult,
+    progress: &Arc<RwLock<Progress>>,
+    reporter: Reporter,
+) -> OrganizeSummary {
+    let mut organize = OrganizeSummary::new(result);
+    if settings.dry_run {
+        return organize;
+    }
+    if let Some(transfer) = RcloneTransfer::from_settings(settings) {
+        progress.write().await.reset();
+        progress.write().await.start_stage(
+            STAGE_TRANSFER,
+            0,
+            0,
+            format!("Moving files to {} with rclone...", transfer.remote()),
+        );
+        let watcher = reporter.watch(Phase::Transfer, Arc::clone(progress));
+        organize.remote_transfer = Some(transfer.run(progress).await);
+        watcher.abort();
+        reporter.progress(Phase::Transfer, &*progress.read().await);
+    } else if settings.cloud_export.is_enabled() {
+        match upload_to_cloud(&settings.cloud_export, result, progress, reporter).await {
+            Ok(upload) => organize.upload = Some(upload),
+            Err(e) => organize.errors.push(format!("Cloud upload failed: {e}")),
+        }
+    }
+    organize
+}
+
+/// Uploads the files placed by `result` to the cloud export bucket, reporting the progress
+async fn upload_to_cloud(
+    cloud: &CloudExport,
+    result: &OrganizeResult,
+    progress: &Arc<RwLock<Progress>>,
+    reporter: Reporter,
+) -> Result<CloudUploadReport> {
+    let uploader = S3Uploader::new(cloud)?;
+    let (root, files) = (result.destination.clone(), result.placed_files());
+    progress.write().await.reset();
+    let watcher = reporter.watch(Phase::Upload, Arc::clone(progress));
+    let task_progress = Arc::clone(progress);
+    let upload = tokio::task::spawn_blocking(move || uploader.upload_files(&root, &files, &task_progress)).await;
+    watcher.abort();
+    reporter.progress(Phase::Upload, &*progress.read().await);
+    Ok(upload?)
+}
+
+/// Takes the instance lock for commands that change files; scans may run next to another instance.
+fn lock_instance(command: HeadlessCommand, config_dir: &Path) -> Result<Option<InstanceLock>> {
+    if command != HeadlessCommand::Organize {
+        return Ok(None);
+    }
+    match InstanceLock::acquire(&config_dir.join("visualvault")) {
+        Ok(lock) => Ok(Some(lock)),
+        Err(e @ InstanceLockError::Held { .. }) => Err(eyre!("{e}; not organizing while it runs")),
+        Err(e) => {
+            // Without a lock file there is nothing to protect against, so keep going
+            warn!("{}", e);
+            Ok(None)
+        }
+    }
+}
+
+const fn notified_operation(command: HeadlessCommand) -> NotifiedOperation {
+    match command {
+        HeadlessCommand::Organize => NotifiedOperation::Organize,
+        HeadlessCommand::Scan | HeadlessCommand::Duplicates => NotifiedOperation::Scan,
+    }
+}
+
+/// Sends `report` to the notification targets, waiting for it so it is not lost on exit
+async fn notify(notifications: &Notifications, report: &OperationReport) {
+    let result = match Notifier::new(notifications) {
+        Ok(notifier) => notifier.send(report).await,
+        Err(e) => Err(e),
+    };
+    if let Err(e) = result {
+        warn!("Failed to send {} notification: {}", report.operation, e);
+    }
+}
+
+/// Adds the organize run to the activity log shown in the UI
+fn record_activity(config_dir: &Path, organize: &OrganizeSummary) {
+    let message = format!(
+        "Command line organize: {} of {} files moved to {}",
+        organize.organized,
+        organize.total,
+        organize.destination.display()
+    );
+    let result = ActivityLog::load(config_dir).and_then(|mut log| {
+        log.record(ActivityKind::Organize, message)?;
+        if let Some(first) = organize.errors.first() {
+            log.record(
+                ActivityKind::Error,
+                format!(
+                    "Command line organize failed for {} files, first error: {first}",
+                    organize.errors.len()
+                ),
+            )?;
+        }
+        if let Some(upload) = &organize.upload {
+            log.record(
+                ActivityKind::Upload,
+                format!(
+                    "Command line upload: {} of {} files sent to bucket {}",
+                    upload.uploaded,
+                    upload.total(),
+                    upload.bucket
+                ),
+            )?;
+        }
+        Ok(())
+    });
+    if let Err(e) = result {
+        error!("Failed to write activity log: {}", e);
+    }
+}
@@ -1,4 +1,6 @@
-pub use visualvault_config as config;
-pub use visualvault_core as core;
-pub use visualvault_models as models;
-pub use visualvault_utils as utils;
+pub mod cli;
+pub mod events;
+pub mod headless;
+pub mod terminal;
+
+pub use visualvault_lib as engine;
@@ -1,22 +1,22 @@
 use color_eyre::eyre::Result;
-use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind},
-    execute,
-    terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
-};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind};
 use ratatui::{
     Terminal,
     backend::{Backend, CrosstermBackend},
 };
 use std::{
     io::{self, IsTerminal},
+    process::ExitCode,
     sync::Arc,
     time::{Duration, Instant},
 };
 use tokio::sync::RwLock;
 use tracing::{error, info};
 
+use visualvault::terminal::{self, JobControl};
+use visualvault::{cli, headless};
 use visualvault_app::App;
+use visualvault_lib::internals::utils::{LOG_RETENTION, MAX_LOG_BYTES, RotatingLogFile, log_file_path};
 use visualvault_ui::draw;
 
 #[cfg(windows)]
@@ -33,13 +33,25 @@ static GLOBAL: MiMalloc = MiMalloc;
 #[global_allocator]
 static GLOBAL: Jemalloc = Jemalloc;
 
+#[allow(clippy::unwrap_in_result)] // expanded by `tokio::main`
 #[tokio::main]
-async fn main() -> Result<()> {
+async fn main() -> Result<ExitCode> {
     // Install error hooks
     color_eyre::install()?;
 
+    // Subcommands run headless, without the terminal UI
+    let cli = match cli::parse_args(std::env::args_os()) {
+        Ok(cli) => cli,
+        Err(e) => e.exit(),
+    };
+    let headless = cli.headless;
+
     // Setup logging
-    setup_logging()?;
+    setup_logging(headless.is_none(), cli.log_level.as_deref())?;
+
+    if let Some(options) = headless {
+        return Ok(headless::run(options).await);
+    }
 
     // Run the application
     if let Err(e) = run().await {
@@ -47,32 +59,44 @@ async fn main() -> Result<()> {
         return Err(e);
     }
 
-    Ok(())
+    Ok(ExitCode::SUCCESS)
 }
 
-fn setup_logging() -> Result<()> {
+/// Logs to the platform log folder, rotating the file once it grows past `MAX_LOG_BYTES`.
+///
+/// `level` from the command line wins over the `VISUALVAULT_LOG_LEVEL` variable; both set
+/// how verbose `VisualVault` itself is, while other crates never log below info.
+fn setup_logging(announce: bool, level: Option<&str>) -> Result<()> {
     use std::env;
 
-    // create log file if not already exists to the project root
-    let log_dir = env::current_dir()?.join("logs");
-    std::fs::create_dir_all(&log_dir)?;
-    let log_path = log_dir.join("visualvault.log");
+    let log_path = log_file_path().ok_or_else(|| color_eyre::eyre::eyre!("Could not find a folder for logs"))?;
 
-    // Print where we're logging to
-    eprintln!("Logging to: {}", log_path.display());
+    // Print where we're logging to, keeping headless output clean for scripts
+    if announce {
+        eprintln!("Logging to: {}", log_path.display());
+    }
 
-    // Create or truncate log file
-    let log_file = std::fs::OpenOptions::new()
-        .create(true)
-        .write(true)
-        .truncate(true)
-        .open(&log_path)?;
+    let env_level = env::var("VISUALVAULT_LOG_LEVEL")
+        .ok()
+        .map(|level| level.trim().to_lowercase())
+        .filter(|level| cli::LOG_LEVELS.contains(&level.as_str()));
+    let level = level
+        .map(str::to_string)
+        .or(env_level)
+        .unwrap_or_else(|| "debug".to_string());
+    let others = if ["debug", "trace"].contains(&level.as_str()) {
+        "info"
+    } else {
+        level.as_str()
+    };
+
+    let log_file = RotatingLogFile::open(log_path.clone(), MAX_LOG_BYTES, LOG_RETENTION)?;
 
     // Configure tracing to write to file
     tracing_subscriber::fmt()
-        .with_writer(log_file)
+        .with_writer(std::sync::Mutex::new(log_file))
         .with_ansi(false)
-        .with_env_filter("visualvault=debug,info")
+        .with_env_filter(format!("visualvault={level},{others}"))
         .with_target(true)
         .with_line_number(true)
         .with_thread_ids(false)
@@ -93,10 +117,8 @@ async fn run() -> Result<()> {
         std::process::exit(1);
     }
 
-    enable_raw_mode()?;
-    let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
-    let backend = CrosstermBackend::new(stdout);
+    terminal::enter()?;
+    let backend = CrosstermBackend::new(io::stdout());
     let mut terminal = Terminal::new(backend)?;
 
     // Create app
@@ -106,9 +128,7 @@ async fn run() -> Result<()> {
     let res = run_app(&mut terminal, app).await;
 
     // Restore terminal
-    disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen, DisableMouseCapture)?;
-    terminal.show_cursor()?;
+    terminal::leave()?;
 
     if let Err(err) = res {
         error!("Runtime error: {:?}", err);
@@ -121,6 +141,7 @@ async fn run() -> Result<()> {
 async fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: Arc<RwLock<App>>) -> Result<()> {
     let tick_rate = Duration::from_millis(100);
     let mut last_tick = Instant::now();
+    let job_control = JobControl::listen()?;
 
     loop {
         // Draw UI
@@ -135,31 +156,49 @@ async fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: Arc<RwLock<App>>)
             .unwrap_or_else(|| Duration::from_secs(0));
 
         if event::poll(timeout)? {
-            if let Event::Key(key) = event::read()? {
-                if key.kind == KeyEventKind::Press {
+            let event = event::read()?;
+            match event {
+                Event::FocusGained => app.write().await.set_terminal_focus(true),
+                Event::FocusLost => app.write().await.set_terminal_focus(false),
+                Event::Mouse(mouse) => app.write().await.on_mouse(mouse).await?,
+                _ => {}
+            }
+            if let Event::Key(key) = event {
+                if key.kind == KeyEventKind::Press && terminal::is_suspend_key(&key) {
+                    job_control.request_suspend();
+                } else if key.kind == KeyEventKind::Press {
                     let mut app = app.write().await;
-
-                    match key.code {
-                        KeyCode::Char('c') if key.modifiers.contains(event::KeyModifiers::CONTROL) => {
-                            info!("User forced quit");
-                            return Ok(());
-                        }
-                        _ => {
-                            app.on_key(key).await?;
-                            if app.should_quit {
-                                info!("User requested quit");
-                                return Ok(());
-                            }
-                        }
+                    handle_key(&mut app, key).await?;
+                    if app.should_quit {
+                        info!("User requested quit");
+                        return Ok(());
                     }
                 }
             }
         }
 
+        job_control.handle(terminal)?;
+
         // Update app state on tick
         if last_tick.elapsed() >= tick_rate {
-            app.write().await.on_tick().await?;
+            let mut app = app.write().await;
+            app.on_tick().await?;
             last_tick = Instant::now();
+            // A quit waiting for the running operations happens once they stopped
+            if app.should_quit {
+                info!("Quitting after the running operations stopped");
+                return Ok(());
+            }
+        }
+    }
+}
+
+async fn handle_key(app: &mut App, key: KeyEvent) -> Result<()> {
+    match key.code {
+        KeyCode::Char('c') if key.modifiers.contains(event::KeyModifiers::CONTROL) => {
+            app.interrupt();
+            Ok(())
         }
+        _ => app.on_key(key).await,
     }
 }
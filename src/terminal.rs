@@ -0,0 +1,152 @@
+//! Setting up and restoring the terminal for the UI, and suspending it to the shell.
+//!
+//! Ctrl+Z arrives as a key press in raw mode, so the UI suspends itself: it restores the
+//! terminal, stops the process and sets the terminal up again once the shell continues it.
+//! A `SIGTSTP` sent from elsewhere does the same, and a `SIGCONT` after any other stop sets
+//! the terminal up again and redraws it.
+
+use std::io;
+#[cfg(unix)]
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, Ordering},
+};
+
+use crossterm::{
+    cursor::Show,
+    event::{
+        DisableFocusChange, DisableMouseCapture, EnableFocusChange, EnableMouseCapture, KeyCode, KeyEvent, KeyModifiers,
+    },
+    execute,
+    terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
+};
+use ratatui::{Terminal, backend::Backend};
+
+/// Puts the terminal in raw mode on the alternate screen, with mouse and focus reporting.
+///
+/// # Errors
+///
+/// Returns an error if the terminal cannot be set up.
+pub fn enter() -> io::Result<()> {
+    enable_raw_mode()?;
+    execute!(
+        io::stdout(),
+        EnterAlternateScreen,
+        EnableMouseCapture,
+        EnableFocusChange
+    )
+}
+
+/// Restores the terminal [`enter`] set up.
+///
+/// # Errors
+///
+/// Returns an error if the terminal cannot be restored.
+pub fn leave() -> io::Result<()> {
+    disable_raw_mode()?;
+    execute!(
+        io::stdout(),
+        LeaveAlternateScreen,
+        DisableMouseCapture,
+        DisableFocusChange,
+        Show
+    )
+}
+
+/// Returns whether `key` asks to suspend the UI to the shell.
+#[must_use]
+pub fn is_suspend_key(key: &KeyEvent) -> bool {
+    cfg!(unix) && key.code == KeyCode::Char('z') && key.modifiers.contains(KeyModifiers::CONTROL)
+}
+
+/// Suspends the UI on Ctrl+Z or `SIGTSTP`, and sets the terminal up again once continued.
+#[derive(Debug, Default)]
+pub struct JobControl {
+    #[cfg(unix)]
+    suspend: Arc<AtomicBool>,
+    #[cfg(unix)]
+    resumed: Arc<AtomicBool>,
+}
+
+#[cfg(unix)]
+impl JobControl {
+    /// Starts listening for `SIGTSTP` and `SIGCONT`.
+    ///
+    /// Handling `SIGTSTP` replaces the default of stopping right away, which would leave the
+    /// shell in raw mode on the alternate screen.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the signal handlers cannot be installed.
+    pub fn listen() -> io::Result<Self> {
+        use tokio::signal::unix::{SignalKind, signal};
+
+        let control = Self::default();
+        for (kind, flag) in [
+            (libc::SIGTSTP, Arc::clone(&control.suspend)),
+            (libc::SIGCONT, Arc::clone(&control.resumed)),
+        ] {
+            let mut signals = signal(SignalKind::from_raw(kind))?;
+            tokio::spawn(async move {
+                while signals.recv().await.is_some() {
+                    flag.store(true, Ordering::Release);
+                }
+            });
+        }
+        Ok(control)
+    }
+
+    /// Suspends the UI the next time [`JobControl::handle`] runs.
+    pub fn request_suspend(&self) {
+        self.suspend.store(true, Ordering::Release);
+    }
+
+    /// Suspends the UI when asked to, and sets the terminal up again after the process was
+    /// stopped and continued.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the terminal cannot be restored or set up again.
+    pub fn handle<B: Backend>(&self, terminal: &mut Terminal<B>) -> io::Result<()> {
+        if self.suspend.swap(false, Ordering::AcqRel) {
+            tracing::info!("Suspending to the shell");
+            leave()?;
+            // `SIGTSTP` is handled by `listen`, so the process stops itself with `SIGSTOP`,
+            // which cannot be handled, and carries on from here once the shell continues it
+            // SAFETY: raising a signal has no memory safety requirements
+            if unsafe { libc::raise(libc::SIGSTOP) } != 0 {
+                tracing::warn!("Could not suspend: {}", io::Error::last_os_error());
+            }
+            self.resumed.store(true, Ordering::Release);
+        }
+
+        if self.resumed.swap(false, Ordering::AcqRel) {
+            tracing::info!("Resumed, redrawing the terminal");
+            enter()?;
+            // The shell drew over the screen, so everything is drawn again
+            terminal.clear()?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(not(unix))]
+impl JobControl {
+    /// Suspending to the shell needs job control, which only Unix shells have.
+    ///
+    /// # Errors
+    ///
+    /// Never fails.
+    pub fn listen() -> io::Result<Self> {
+        Ok(Self::default())
+    }
+
+    pub const fn request_suspend(&self) {}
+
+    /// # Errors
+    ///
+    /// Never fails.
+    pub const fn handle<B: Backend>(&self, _terminal: &mut Terminal<B>) -> io::Result<()> {
+        Ok(())
+    }
+}
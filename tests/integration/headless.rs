@@ -0,0 +1,252 @@
+#![allow(clippy::unwrap_used)]
+#![allow(clippy::panic)]
+#![allow(clippy::panic_in_result_fn)]
+#![allow(clippy::unwrap_in_result)]
+
+use color_eyre::Result;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+use tokio::fs;
+
+use visualvault::cli::{self, HeadlessCommand, HeadlessOptions};
+use visualvault::events::{Event, Reporter};
+use visualvault::headless::{self, Summary};
+use visualvault_config::Settings;
+use visualvault_core::InstanceLock;
+use visualvault_lib::Engine;
+use visualvault_models::NotifiedOperation;
+
+async fn create_test_file(path: &Path, content: &[u8]) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).await?;
+    }
+    fs::write(path, content).await?;
+    Ok(())
+}
+
+async fn create_test_engine(config_dir: &Path) -> Result<Engine> {
+    Engine::with_cache(config_dir.to_path_buf(), ":memory:").await
+}
+
+fn options(command: HeadlessCommand, source: &Path) -> HeadlessOptions {
+    HeadlessOptions {
+        command,
+        profile: None,
+        source: Some(source.to_path_buf()),
+        destination: None,
+        timezone: None,
+        json: true,
+        quiet: true,
+    }
+}
+
+#[test]
+fn test_no_command_starts_ui() {
+    assert_eq!(cli::parse(["visualvault"]).unwrap(), None);
+}
+
+#[test]
+fn test_parse_organize() {
+    let options = cli::parse([
+        "visualvault",
+        "organize",
+        "--source",
+        "/in",
+        "-d",
+        "/out",
+        "--json",
+        "--profile",
+        "Phone import",
+        "--timezone",
+        "+09:00",
+    ])
+    .unwrap()
+    .unwrap();
+
+    assert_eq!(options.command, HeadlessCommand::Organize);
+    assert_eq!(options.source, Some(PathBuf::from("/in")));
+    assert_eq!(options.destination, Some(PathBuf::from("/out")));
+    assert_eq!(options.profile.as_deref(), Some("Phone import"));
+    assert_eq!(
+        options.timezone.map(|policy| policy.to_string()).as_deref(),
+        Some("+09:00")
+    );
+    assert!(options.json);
+}
+
+#[test]
+fn test_parse_global_options_before_command() {
+    let options = cli::parse(["visualvault", "--json", "-q", "duplicates"])
+        .unwrap()
+        .unwrap();
+    assert_eq!(options.command, HeadlessCommand::Duplicates);
+    assert!(options.json);
+    assert!(options.quiet);
+    assert_eq!(options.destination, None);
+}
+
+#[test]
+fn test_parse_log_level() {
+    let cli = cli::parse_args(["visualvault", "--log-level", "trace"]).unwrap();
+    assert_eq!(cli.headless, None);
+    assert_eq!(cli.log_level.as_deref(), Some("trace"));
+
+    let cli = cli::parse_args(["visualvault", "scan", "--log-level", "warn"]).unwrap();
+    assert_eq!(cli.log_level.as_deref(), Some("warn"));
+    assert!(cli::parse_args(["visualvault", "--log-level", "loud"]).is_err());
+}
+
+#[test]
+fn test_parse_rejects_invalid_arguments() {
+    assert!(cli::parse(["visualvault", "scan", "--destination", "/out"]).is_err());
+    assert!(cli::parse(["visualvault", "frobnicate"]).is_err());
+    assert!(cli::parse(["visualvault", "organize", "--timezone", "mars"]).is_err());
+    assert!(cli::parse(["visualvault", "scan", "--timezone", "exif"]).is_err());
+}
+
+#[tokio::test]
+async fn test_headless_scan_and_duplicates() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let source = temp_dir.path().join("source");
+    create_test_file(&source.join("a.jpg"), b"same photo").await?;
+    create_test_file(&source.join("nested/b.jpg"), b"same photo").await?;
+    create_test_file(&source.join("clip.mp4"), b"video").await?;
+
+    let settings = Settings::default();
+    let engine = create_test_engine(&temp_dir.path().join("config")).await?;
+
+    let summary = headless::execute(
+        &options(HeadlessCommand::Scan, &source),
+        settings.clone(),
+        &engine,
+        Reporter::new(true, true),
+    )
+    .await?;
+    let Summary::Scan { scan, .. } = &summary else {
+        panic!("expected a scan summary");
+    };
+    assert_eq!((scan.files, scan.images, scan.videos), (3, 2, 1));
+    assert_eq!(summary.exit_status(), 0);
+
+    let summary = headless::execute(
+        &options(HeadlessCommand::Duplicates, &source),
+        settings,
+        &engine,
+        Reporter::new(true, true),
+    )
+    .await?;
+    let Summary::Duplicates { duplicates, .. } = &summary else {
+        panic!("expected a duplicates summary");
+    };
+    assert_eq!(duplicates.groups, 1);
+    assert_eq!(duplicates.files[0].len(), 2);
+
+    let event = serde_json::to_value(Event::Done {
+        exit_code: summary.exit_status(),
+        result: &summary,
+    })?;
+    assert_eq!(event["event"], "done");
+    assert_eq!(event["exit_code"], 0);
+    assert_eq!(event["result"]["command"], "duplicates");
+    assert_eq!(event["result"]["duplicates"]["groups"], 1);
+
+    let report = summary.to_report();
+    assert_eq!(report.operation, NotifiedOperation::Scan);
+    assert_eq!(report.counts.get("duplicate_groups"), Some(&1));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_headless_organize() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let source = temp_dir.path().join("source");
+    let destination = temp_dir.path().join("library");
+    create_test_file(&source.join("a.jpg"), b"photo a").await?;
+    create_test_file(&source.join("b.jpg"), b"photo b").await?;
+
+    let mut options = options(HeadlessCommand::Organize, &source);
+    options.destination = Some(destination.clone());
+    let engine = create_test_engine(&temp_dir.path().join("config")).await?;
+
+    let summary = headless::execute(&options, Settings::default(), &engine, Reporter::new(true, true)).await?;
+    let Summary::Organize { organize, .. } = &summary else {
+        panic!("expected an organize summary");
+    };
+    assert_eq!(organize.organized, 2);
+    assert!(organize.errors.is_empty());
+    assert_eq!(summary.exit_status(), 0);
+    assert!(!source.join("a.jpg").exists());
+
+    let report = summary.to_report();
+    assert_eq!(report.operation, NotifiedOperation::Organize);
+    assert!(report.success);
+    assert_eq!(report.counts.get("files_organized"), Some(&2));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_headless_organize_requires_destination() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let engine = create_test_engine(temp_dir.path()).await?;
+
+    let result = headless::execute(
+        &options(HeadlessCommand::Organize, temp_dir.path()),
+        Settings::default(),
+        &engine,
+        Reporter::default(),
+    )
+    .await;
+    assert!(result.is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_headless_organize_refuses_while_another_instance_runs() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let source = temp_dir.path().join("source");
+    let config_dir = temp_dir.path().join("config");
+    create_test_file(&source.join("a.jpg"), b"photo a").await?;
+    let _other = InstanceLock::acquire(&config_dir.join("visualvault"))?;
+
+    let mut organize = options(HeadlessCommand::Organize, &source);
+    organize.destination = Some(temp_dir.path().join("library"));
+    let engine = create_test_engine(&config_dir).await?;
+
+    let error = headless::execute(&organize, Settings::default(), &engine, Reporter::default())
+        .await
+        .unwrap_err();
+    assert!(error.to_string().contains("Another VisualVault instance is running"));
+    assert!(source.join("a.jpg").exists());
+
+    // Scans do not change files, so they still run
+    headless::execute(
+        &options(HeadlessCommand::Scan, &source),
+        Settings::default(),
+        &engine,
+        Reporter::default(),
+    )
+    .await?;
+    Ok(())
+}
+
+#[test]
+fn test_progress_events_are_tagged() -> Result<()> {
+    let event = serde_json::to_value(Event::OrganizeProgress {
+        current: 3,
+        total: 10,
+        bytes_processed: 300,
+        total_bytes: 1000,
+        file: Some("IMG_0003.JPG"),
+    })?;
+    assert_eq!(event["event"], "organize_progress");
+    assert_eq!(event["current"], 3);
+    assert_eq!(event["file"], "IMG_0003.JPG");
+
+    let event = serde_json::to_value(Event::ScanStarted {
+        command: HeadlessCommand::Scan.name(),
+        source: Path::new("/photos"),
+    })?;
+    assert_eq!(event["event"], "scan_started");
+    assert_eq!(event["source"], "/photos");
+    Ok(())
+}
@@ -1,2 +1,3 @@
+mod headless;
 mod organizer;
 mod scanner;
@@ -2,6 +2,7 @@
 #![allow(clippy::unwrap_used)]
 #![allow(clippy::expect_used)]
 #![allow(clippy::panic_in_result_fn)]
+#![allow(clippy::unwrap_in_result)]
 
 use color_eyre::Result;
 use std::path::Path;
@@ -2,9 +2,8 @@
 #![allow(clippy::expect_used)]
 #![allow(clippy::float_cmp)] // For comparing floats in tests
 #![allow(clippy::panic)]
-#![allow(clippy::unwrap_used)]
-#![allow(clippy::expect_used)]
 #![allow(clippy::panic_in_result_fn)]
+#![allow(clippy::unwrap_in_result)]
 
 use color_eyre::Result;
 use std::path::Path;